@@ -24,11 +24,19 @@ import csv
 from collections import defaultdict
 
 def load_csv(csv_path):
-    """Load CSV into list of dicts."""
+    """Load CSV into list of dicts.
+
+    Accepts both the legacy pilot columns (config, throughput_seqs_per_sec)
+    and `asbb bench` / DAG columns (config_name, throughput_median).
+    """
     experiments = []
     with open(csv_path, 'r') as f:
         reader = csv.DictReader(f)
         for row in reader:
+            if 'config' not in row and 'config_name' in row:
+                row['config'] = row['config_name']
+            if 'throughput_seqs_per_sec' not in row and 'throughput_median' in row:
+                row['throughput_seqs_per_sec'] = row['throughput_median']
             experiments.append(row)
     return experiments
 
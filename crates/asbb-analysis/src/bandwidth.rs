@@ -0,0 +1,179 @@
+//! Memory-bandwidth utilization
+//!
+//! Converts throughput into bytes touched per second and divides by the
+//! machine's peak read bandwidth (from an `asbb micro` profile): one-thread
+//! configs against single-core bandwidth, multi-threaded ones against
+//! all-core bandwidth. A config streaming at most of the peak is bound by
+//! the memory bus, so adding threads cannot help it much further; this
+//! separates "stopped scaling because of serial work" (see [`crate::scaling`])
+//! from "stopped scaling because DRAM is saturated".
+//!
+//! Bytes per sequence are not in the results tables, so the caller supplies
+//! them: bases plus quality scores for the FASTQ inputs the harnesses use
+//! (300 for the standard 150 bp datasets). Utilization above 1 means the
+//! dataset fit in cache and never streamed from DRAM.
+
+use crate::results::{ResultKey, ResultRow};
+use crate::scaling::{row_threads, ScalingCurve};
+
+/// Bytes per sequence of the standard 150 bp FASTQ datasets (bases + quality)
+pub const DEFAULT_BYTES_PER_SEQUENCE: usize = 300;
+
+/// Fraction of peak bandwidth above which a config counts as memory-bound
+pub const DEFAULT_SATURATION: f64 = 0.8;
+
+/// Peak read bandwidth of the machine that produced the results
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PeakBandwidth {
+    /// One thread streaming (GB/s)
+    pub single_core_gbps: f64,
+
+    /// All cores streaming (GB/s)
+    pub all_core_gbps: f64,
+}
+
+impl PeakBandwidth {
+    /// Peak a config with `threads` threads can reach
+    pub fn for_threads(&self, threads: usize) -> f64 {
+        if threads <= 1 {
+            self.single_core_gbps
+        } else {
+            self.all_core_gbps
+        }
+    }
+}
+
+/// Bandwidth one measured config achieves
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandwidthPoint {
+    pub key: ResultKey,
+    pub threads: usize,
+
+    /// Bytes touched per second (GB/s)
+    pub gbps: f64,
+
+    /// Peak for this thread count (GB/s)
+    pub peak_gbps: f64,
+
+    /// `gbps / peak_gbps`
+    pub utilization: f64,
+}
+
+impl BandwidthPoint {
+    pub fn is_saturated(&self, threshold: f64) -> bool {
+        self.utilization >= threshold
+    }
+}
+
+/// GB/s touched at `throughput` sequences/second
+pub fn throughput_gbps(throughput: f64, bytes_per_sequence: usize) -> f64 {
+    throughput * bytes_per_sequence as f64 / 1e9
+}
+
+/// Bandwidth utilization of every measured row
+pub fn bandwidth_utilization(
+    rows: &[ResultRow],
+    bytes_per_sequence: usize,
+    peak: &PeakBandwidth,
+) -> Vec<BandwidthPoint> {
+    rows.iter()
+        .filter(|row| row.throughput > 0.0)
+        .map(|row| {
+            let threads = row_threads(row);
+            let gbps = throughput_gbps(row.throughput, bytes_per_sequence);
+            let peak_gbps = peak.for_threads(threads);
+            BandwidthPoint {
+                key: row.key.clone(),
+                threads,
+                gbps,
+                peak_gbps,
+                utilization: if peak_gbps > 0.0 {
+                    gbps / peak_gbps
+                } else {
+                    0.0
+                },
+            }
+        })
+        .collect()
+}
+
+/// Fewest threads at which a scaling curve reaches `threshold` of peak
+/// bandwidth (`None` if it never does)
+pub fn bandwidth_saturation_threads(
+    curve: &ScalingCurve,
+    bytes_per_sequence: usize,
+    peak: &PeakBandwidth,
+    threshold: f64,
+) -> Option<usize> {
+    curve
+        .points
+        .iter()
+        .find(|point| {
+            let peak_gbps = peak.for_threads(point.threads);
+            peak_gbps > 0.0
+                && throughput_gbps(point.throughput, bytes_per_sequence) / peak_gbps >= threshold
+        })
+        .map(|point| point.threads)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scaling::analyze_scaling;
+
+    fn row(config: &str, threads: usize, throughput: f64) -> ResultRow {
+        ResultRow {
+            key: ResultKey {
+                operation: "base_counting".to_string(),
+                config: config.to_string(),
+                scale: "Large".to_string(),
+            },
+            num_sequences: 100_000,
+            length_class: None,
+            threads: Some(threads),
+            throughput,
+            throughput_ci: None,
+            throughput_samples: None,
+        }
+    }
+
+    #[test]
+    fn test_bandwidth_saturation() {
+        let peak = PeakBandwidth {
+            single_core_gbps: 60.0,
+            all_core_gbps: 100.0,
+        };
+
+        // 300 bytes/seq: 1t → 30 GB/s, 2t → 60, 4t → 90, 8t → 96
+        let rows = vec![
+            row("neon", 1, 1e8),
+            row("neon_2t", 2, 2e8),
+            row("neon_4t", 4, 3e8),
+            row("neon_8t", 8, 3.2e8),
+        ];
+        let points = bandwidth_utilization(&rows, DEFAULT_BYTES_PER_SEQUENCE, &peak);
+        assert_eq!(points.len(), 4);
+        assert!((points[0].gbps - 30.0).abs() < 1e-9);
+        assert!((points[0].utilization - 0.5).abs() < 1e-9);
+        assert_eq!(points[1].peak_gbps, 100.0);
+        assert!(!points[1].is_saturated(DEFAULT_SATURATION));
+        assert!(points[2].is_saturated(DEFAULT_SATURATION));
+
+        let curves = analyze_scaling(&rows);
+        let saturation = bandwidth_saturation_threads(
+            &curves[0],
+            DEFAULT_BYTES_PER_SEQUENCE,
+            &peak,
+            DEFAULT_SATURATION,
+        );
+        assert_eq!(saturation, Some(4));
+        assert_eq!(
+            bandwidth_saturation_threads(&curves[0], 100, &peak, DEFAULT_SATURATION),
+            None
+        );
+    }
+}
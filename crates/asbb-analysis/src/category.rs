@@ -0,0 +1,280 @@
+//! Category-level summaries
+//!
+//! The project's central hypothesis is that an operation's category
+//! (element-wise, filter, search, ...) predicts its optimal config, so a
+//! new operation can be tuned without a full sweep. This module pools each
+//! operation's speedup over its naive baseline (same operation and scale)
+//! by category, and checks the hypothesis directly: for each category and
+//! scale, how many of its operations have the category's best config as
+//! their own best.
+//!
+//! Results tables do not record categories, so the caller maps operation
+//! names to categories (operations it cannot map are left out). Naive
+//! counts as a config with speedup 1, so a category where nothing beats
+//! the baseline concludes "naive".
+
+use std::collections::BTreeMap;
+
+use asbb_core::OperationCategory;
+
+use crate::results::ResultRow;
+
+/// Speedups of one config across the operations of one category
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategorySummary {
+    pub category: OperationCategory,
+    pub scale: String,
+    pub config: String,
+
+    /// Operations of the category measured with this config
+    pub operations: usize,
+
+    pub mean_speedup: f64,
+    pub median_speedup: f64,
+
+    /// Sample standard deviation of the speedup across operations (0 for one)
+    pub std_dev: f64,
+}
+
+/// Best config of one category at one scale
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryConclusion {
+    pub category: OperationCategory,
+    pub scale: String,
+
+    /// Config with the highest median speedup (among configs measured on
+    /// every operation of the category at this scale)
+    pub best_config: String,
+    pub median_speedup: f64,
+
+    /// Operations whose own best config is `best_config`
+    pub agreeing: usize,
+
+    /// Operations of the category measured at this scale
+    pub operations: usize,
+}
+
+impl CategoryConclusion {
+    /// Fraction of operations the category's best config also suits
+    pub fn agreement(&self) -> f64 {
+        if self.operations == 0 {
+            0.0
+        } else {
+            self.agreeing as f64 / self.operations as f64
+        }
+    }
+}
+
+/// Speedup over naive per (category, scale, operation, config)
+///
+/// Scales are keyed by sequence count first so they sort smallest first.
+type Speedups<'a> =
+    BTreeMap<(OperationCategory, usize, &'a str), BTreeMap<&'a str, BTreeMap<&'a str, f64>>>;
+
+fn speedups<'a>(
+    rows: &'a [ResultRow],
+    category_of: &impl Fn(&str) -> Option<OperationCategory>,
+) -> Speedups<'a> {
+    let baselines: BTreeMap<(&str, &str), f64> = rows
+        .iter()
+        .filter(|row| row.key.config == "naive" && row.throughput > 0.0)
+        .map(|row| ((row.key.operation.as_str(), row.key.scale.as_str()), row.throughput))
+        .collect();
+
+    let mut speedups: Speedups = BTreeMap::new();
+    for row in rows {
+        let operation = row.key.operation.as_str();
+        let Some(baseline) = baselines.get(&(operation, row.key.scale.as_str())) else {
+            continue;
+        };
+        let Some(category) = category_of(operation) else {
+            continue;
+        };
+        speedups
+            .entry((category, row.num_sequences, &row.key.scale))
+            .or_default()
+            .entry(operation)
+            .or_default()
+            .insert(&row.key.config, row.throughput / baseline);
+    }
+    speedups
+}
+
+/// Speedups of each config across operations
+fn by_config<'a>(
+    operations: &BTreeMap<&'a str, BTreeMap<&'a str, f64>>,
+) -> BTreeMap<&'a str, Vec<f64>> {
+    let mut by_config: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+    for configs in operations.values() {
+        for (config, speedup) in configs {
+            by_config.entry(config).or_default().push(*speedup);
+        }
+    }
+    by_config
+}
+
+/// Mean, median and spread of each config's speedup per (category, scale)
+///
+/// Naive itself is omitted (its speedup is 1 by definition). Sorted by
+/// category, scale (fewest sequences first), then config.
+pub fn category_summary(
+    rows: &[ResultRow],
+    category_of: impl Fn(&str) -> Option<OperationCategory>,
+) -> Vec<CategorySummary> {
+    let mut summaries = Vec::new();
+    for ((category, _, scale), operations) in speedups(rows, &category_of) {
+        for (config, mut values) in by_config(&operations) {
+            if config == "naive" {
+                continue;
+            }
+            values.sort_by(f64::total_cmp);
+            let n = values.len() as f64;
+            let mean = values.iter().sum::<f64>() / n;
+            let std_dev = if values.len() > 1 {
+                (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+            } else {
+                0.0
+            };
+            summaries.push(CategorySummary {
+                category,
+                scale: scale.to_string(),
+                config: config.to_string(),
+                operations: values.len(),
+                mean_speedup: mean,
+                median_speedup: median(&values),
+                std_dev,
+            });
+        }
+    }
+    summaries
+}
+
+/// Best config per (category, scale) and how many operations agree with it
+pub fn category_conclusions(
+    rows: &[ResultRow],
+    category_of: impl Fn(&str) -> Option<OperationCategory>,
+) -> Vec<CategoryConclusion> {
+    let mut conclusions = Vec::new();
+    for ((category, _, scale), operations) in speedups(rows, &category_of) {
+        // Only configs every operation was measured with are comparable
+        let best = by_config(&operations)
+            .into_iter()
+            .filter(|(_, values)| values.len() == operations.len())
+            .map(|(config, mut values)| {
+                values.sort_by(f64::total_cmp);
+                (config, median(&values))
+            })
+            .fold(None, |best: Option<(&str, f64)>, candidate| match best {
+                Some(best) if best.1 >= candidate.1 => Some(best),
+                _ => Some(candidate),
+            });
+        let Some((best_config, median_speedup)) = best else {
+            continue;
+        };
+
+        let agreeing = operations
+            .values()
+            .filter(|configs| {
+                configs
+                    .iter()
+                    .max_by(|a, b| a.1.total_cmp(b.1))
+                    .is_some_and(|(config, _)| *config == best_config)
+            })
+            .count();
+
+        conclusions.push(CategoryConclusion {
+            category,
+            scale: scale.to_string(),
+            best_config: best_config.to_string(),
+            median_speedup,
+            agreeing,
+            operations: operations.len(),
+        });
+    }
+    conclusions
+}
+
+/// Median of sorted values
+fn median(sorted: &[f64]) -> f64 {
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::ResultKey;
+
+    fn row(operation: &str, config: &str, throughput: f64) -> ResultRow {
+        ResultRow {
+            key: ResultKey {
+                operation: operation.to_string(),
+                config: config.to_string(),
+                scale: "Medium".to_string(),
+            },
+            num_sequences: 10_000,
+            length_class: None,
+            threads: None,
+            throughput,
+            throughput_ci: None,
+            throughput_samples: None,
+        }
+    }
+
+    fn category_of(operation: &str) -> Option<OperationCategory> {
+        match operation {
+            "gc_content" | "base_counting" | "n_content" => Some(OperationCategory::ElementWise),
+            "quality_filter" => Some(OperationCategory::Filter),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_category_summary_and_conclusions() {
+        let rows = vec![
+            row("gc_content", "naive", 100.0),
+            row("gc_content", "neon", 2000.0),
+            row("gc_content", "neon_4t", 1500.0),
+            row("base_counting", "naive", 100.0),
+            row("base_counting", "neon", 1600.0),
+            row("base_counting", "neon_4t", 1800.0),
+            row("n_content", "naive", 100.0),
+            row("n_content", "neon", 1200.0),
+            row("n_content", "neon_4t", 1000.0),
+            row("quality_filter", "naive", 100.0),
+            row("quality_filter", "neon", 90.0),
+            row("unmapped_op", "naive", 100.0),
+            row("unmapped_op", "neon", 500.0),
+        ];
+
+        let summary = category_summary(&rows, category_of);
+        let neon = summary
+            .iter()
+            .find(|s| s.category == OperationCategory::ElementWise && s.config == "neon")
+            .unwrap();
+        assert_eq!(neon.operations, 3);
+        assert!((neon.mean_speedup - 16.0).abs() < 1e-9);
+        assert!((neon.median_speedup - 16.0).abs() < 1e-9);
+        assert!((neon.std_dev - 4.0).abs() < 1e-9);
+        assert!(summary.iter().all(|s| s.category != OperationCategory::Search));
+        assert!(summary.iter().all(|s| s.config != "naive"));
+
+        let conclusions = category_conclusions(&rows, category_of);
+        assert_eq!(conclusions.len(), 2);
+        assert_eq!(conclusions[0].category, OperationCategory::ElementWise);
+        assert_eq!(conclusions[0].best_config, "neon");
+        assert_eq!((conclusions[0].agreeing, conclusions[0].operations), (2, 3));
+
+        // NEON slows the filter down: the baseline wins
+        assert_eq!(conclusions[1].best_config, "naive");
+        assert_eq!(conclusions[1].agreement(), 1.0);
+    }
+}
@@ -0,0 +1,253 @@
+//! Cross-platform comparison of two results tables
+//!
+//! Aligns rows by (operation, config, scale) and reports the other machine's
+//! throughput relative to the baseline. Whether a difference is meaningful is
+//! judged by 95% confidence-interval overlap: non-overlapping intervals are a
+//! (conservative) sign of a real difference, overlapping ones are not
+//! distinguishable at these run counts.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::results::{ResultKey, ResultRow};
+
+/// How the two confidence intervals relate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiOverlap {
+    /// Intervals overlap: no significant difference
+    Overlapping,
+    /// Other's interval lies entirely above the baseline's
+    OtherFaster,
+    /// Other's interval lies entirely below the baseline's
+    OtherSlower,
+    /// At least one side has no interval (single-measurement pilots)
+    Unknown,
+}
+
+impl CiOverlap {
+    pub(crate) fn classify(baseline: Option<(f64, f64)>, other: Option<(f64, f64)>) -> Self {
+        match (baseline, other) {
+            (Some((base_lower, base_upper)), Some((other_lower, other_upper))) => {
+                if other_lower > base_upper {
+                    CiOverlap::OtherFaster
+                } else if other_upper < base_lower {
+                    CiOverlap::OtherSlower
+                } else {
+                    CiOverlap::Overlapping
+                }
+            }
+            _ => CiOverlap::Unknown,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CiOverlap::Overlapping => "overlapping",
+            CiOverlap::OtherFaster => "other_faster",
+            CiOverlap::OtherSlower => "other_slower",
+            CiOverlap::Unknown => "unknown",
+        }
+    }
+}
+
+/// One experiment measured on both machines
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonRow {
+    pub key: ResultKey,
+    pub baseline: ResultRow,
+    pub other: ResultRow,
+
+    /// Other throughput / baseline throughput (> 1.0: other is faster)
+    pub relative_throughput: f64,
+
+    pub overlap: CiOverlap,
+}
+
+/// Result of aligning two results tables
+#[derive(Debug, Clone, Default)]
+pub struct Comparison {
+    /// Experiments present in both, ordered by key
+    pub rows: Vec<ComparisonRow>,
+
+    /// Experiments only in the baseline
+    pub baseline_only: Vec<ResultKey>,
+
+    /// Experiments only in the other table
+    pub other_only: Vec<ResultKey>,
+}
+
+/// Align `baseline` and `other` by (operation, config, scale)
+///
+/// If a file repeats a key, the last row wins (reruns are appended).
+pub fn compare_results(baseline: &[ResultRow], other: &[ResultRow]) -> Comparison {
+    let index = |rows: &[ResultRow]| -> BTreeMap<ResultKey, ResultRow> {
+        rows.iter().map(|row| (row.key.clone(), row.clone())).collect()
+    };
+    let baseline = index(baseline);
+    let other = index(other);
+
+    let mut comparison = Comparison::default();
+    for (key, base) in &baseline {
+        match other.get(key) {
+            Some(row) => comparison.rows.push(ComparisonRow {
+                key: key.clone(),
+                baseline: base.clone(),
+                other: row.clone(),
+                relative_throughput: if base.throughput > 0.0 {
+                    row.throughput / base.throughput
+                } else {
+                    f64::NAN
+                },
+                overlap: CiOverlap::classify(base.throughput_ci, row.throughput_ci),
+            }),
+            None => comparison.baseline_only.push(key.clone()),
+        }
+    }
+    comparison.other_only = other
+        .keys()
+        .filter(|key| !baseline.contains_key(*key))
+        .cloned()
+        .collect();
+
+    comparison
+}
+
+impl Comparison {
+    /// Geometric mean of relative throughput per config (finite ratios only)
+    pub fn relative_by_config(&self) -> BTreeMap<String, f64> {
+        let configs: BTreeSet<&str> = self.rows.iter().map(|r| r.key.config.as_str()).collect();
+        configs
+            .into_iter()
+            .filter_map(|config| {
+                let logs: Vec<f64> = self
+                    .rows
+                    .iter()
+                    .filter(|r| r.key.config == config)
+                    .map(|r| r.relative_throughput)
+                    .filter(|ratio| ratio.is_finite() && *ratio > 0.0)
+                    .map(f64::ln)
+                    .collect();
+                if logs.is_empty() {
+                    return None;
+                }
+                let mean = logs.iter().sum::<f64>() / logs.len() as f64;
+                Some((config.to_string(), mean.exp()))
+            })
+            .collect()
+    }
+
+    /// Number of rows per CI verdict
+    pub fn overlap_count(&self, overlap: CiOverlap) -> usize {
+        self.rows.iter().filter(|r| r.overlap == overlap).count()
+    }
+
+    /// Write the aligned rows as CSV
+    pub fn write_csv(&self, path: &Path) -> Result<()> {
+        let ci = |ci: Option<(f64, f64)>| match ci {
+            Some((lower, upper)) => (format!("{:.2}", lower), format!("{:.2}", upper)),
+            None => (String::new(), String::new()),
+        };
+
+        let mut csv = String::from(
+            "operation,config,scale,baseline_num_sequences,other_num_sequences,\
+             baseline_throughput,baseline_ci_lower,baseline_ci_upper,\
+             other_throughput,other_ci_lower,other_ci_upper,relative_throughput,ci_overlap\n",
+        );
+        for row in &self.rows {
+            let (base_lower, base_upper) = ci(row.baseline.throughput_ci);
+            let (other_lower, other_upper) = ci(row.other.throughput_ci);
+            writeln!(
+                csv,
+                "{},{},{},{},{},{:.2},{},{},{:.2},{},{},{:.4},{}",
+                row.key.operation,
+                row.key.config,
+                row.key.scale,
+                row.baseline.num_sequences,
+                row.other.num_sequences,
+                row.baseline.throughput,
+                base_lower,
+                base_upper,
+                row.other.throughput,
+                other_lower,
+                other_upper,
+                row.relative_throughput,
+                row.overlap.name()
+            )?;
+        }
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, csv).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(config: &str, scale: &str, throughput: f64, ci: Option<(f64, f64)>) -> ResultRow {
+        ResultRow {
+            key: ResultKey {
+                operation: "gc_content".to_string(),
+                config: config.to_string(),
+                scale: scale.to_string(),
+            },
+            num_sequences: 1000,
+            length_class: None,
+            threads: None,
+            throughput,
+            throughput_ci: ci,
+            throughput_samples: None,
+        }
+    }
+
+    #[test]
+    fn test_compare_results_aligns_and_classifies() {
+        let baseline = vec![
+            row("naive", "Small", 100.0, Some((90.0, 110.0))),
+            row("neon", "Small", 1000.0, Some((950.0, 1050.0))),
+            row("neon", "Large", 1000.0, Some((950.0, 1050.0))),
+            row("neon_4t", "Small", 3000.0, None),
+        ];
+        let other = vec![
+            row("naive", "Small", 105.0, Some((95.0, 115.0))),
+            row("neon", "Small", 500.0, Some((450.0, 550.0))),
+            row("neon", "Large", 2000.0, Some((1900.0, 2100.0))),
+            row("neon_8t", "Small", 5000.0, None),
+        ];
+
+        let comparison = compare_results(&baseline, &other);
+        assert_eq!(comparison.rows.len(), 3);
+        assert_eq!(comparison.baseline_only[0].config, "neon_4t");
+        assert_eq!(comparison.other_only[0].config, "neon_8t");
+
+        let verdicts: Vec<CiOverlap> = comparison.rows.iter().map(|r| r.overlap).collect();
+        assert_eq!(
+            verdicts,
+            [CiOverlap::Overlapping, CiOverlap::OtherFaster, CiOverlap::OtherSlower]
+        );
+        assert!((comparison.rows[0].relative_throughput - 1.05).abs() < 1e-12);
+
+        // Geometric mean of 0.5× and 2× is 1×
+        let by_config = comparison.relative_by_config();
+        assert!((by_config["neon"] - 1.0).abs() < 1e-12);
+        assert_eq!(comparison.overlap_count(CiOverlap::Overlapping), 1);
+    }
+
+    #[test]
+    fn test_missing_interval_is_unknown() {
+        let comparison = compare_results(
+            &[row("naive", "Small", 100.0, None)],
+            &[row("naive", "Small", 50.0, Some((40.0, 60.0)))],
+        );
+        assert_eq!(comparison.rows[0].overlap, CiOverlap::Unknown);
+    }
+}
@@ -0,0 +1,355 @@
+//! Operation × config heatmaps
+//!
+//! Pivots one metric of a results table into a grid per scale: operations
+//! down, configs across. [`heatmap_cells`] yields the tidy long format (one
+//! cell per operation, config and scale) for plotting tools, and
+//! [`render_svg`] draws the grids directly, one panel per scale.
+//!
+//! Any numeric column can be the metric (`throughput_median`,
+//! `speedup_median`, `cv`, ...). Tables that do not record speedups (early
+//! pilots) still support `speedup_median`: it is then computed from each
+//! row's throughput over the naive row of the same operation and scale.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::results::load_results_csv;
+
+/// Metric used when none is given
+pub const DEFAULT_METRIC: &str = "speedup_median";
+
+/// One value of the grid
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatmapCell {
+    pub operation: String,
+    pub config: String,
+    pub scale: String,
+    pub num_sequences: usize,
+    pub value: f64,
+}
+
+/// Cells of `metric` for every measured row of a results CSV
+///
+/// Pruned rows and empty values are skipped. Sorted by scale (fewest
+/// sequences first), operation, then config.
+pub fn heatmap_cells(path: &Path, metric: &str) -> Result<Vec<HeatmapCell>> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("Failed to open results: {}", path.display()))?;
+    let mut lines = text.lines();
+    let header = lines.next().unwrap_or_default();
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    let find = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| columns.iter().position(|c| c == name))
+    };
+
+    let Some(metric_col) = find(&[metric]) else {
+        if metric == DEFAULT_METRIC {
+            return computed_speedups(path);
+        }
+        anyhow::bail!(
+            "{} has no '{}' column (available: {})",
+            path.display(),
+            metric,
+            columns.join(", ")
+        );
+    };
+    let column = |names: &[&str]| {
+        find(names).with_context(|| format!("{} has no '{}' column", path.display(), names[0]))
+    };
+    let operation_col = column(&["operation"])?;
+    let config_col = column(&["config_name", "config"])?;
+    let scale_col = column(&["scale"])?;
+    let sequences_col = column(&["num_sequences"])?;
+    let affinity_col = find(&["affinity"]);
+    let pruned_col = find(&["pruned"]);
+
+    let mut cells = Vec::new();
+    for (line_number, line) in lines.enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields.len() != columns.len() {
+            anyhow::bail!(
+                "{} line {}: expected {} fields, found {}",
+                path.display(),
+                line_number + 2,
+                columns.len(),
+                fields.len()
+            );
+        }
+        if pruned_col.is_some_and(|col| fields[col] == "true") || fields[metric_col].is_empty() {
+            continue;
+        }
+        let value: f64 = fields[metric_col].parse().with_context(|| {
+            format!(
+                "{} line {}: invalid number '{}'",
+                path.display(),
+                line_number + 2,
+                fields[metric_col]
+            )
+        })?;
+        let config = match affinity_col.map(|col| fields[col]) {
+            Some(affinity) if !affinity.is_empty() && affinity != "default" => {
+                format!("{}@{}", fields[config_col], affinity)
+            }
+            _ => fields[config_col].to_string(),
+        };
+        cells.push(HeatmapCell {
+            operation: fields[operation_col].to_string(),
+            config,
+            scale: fields[scale_col].to_string(),
+            num_sequences: fields[sequences_col].parse().unwrap_or(0),
+            value,
+        });
+    }
+    sort_cells(&mut cells);
+    Ok(cells)
+}
+
+/// Speedups over naive for tables without a speedup column
+fn computed_speedups(path: &Path) -> Result<Vec<HeatmapCell>> {
+    let rows = load_results_csv(path)?;
+    let baselines: BTreeMap<(&str, &str), f64> = rows
+        .iter()
+        .filter(|row| row.key.config == "naive" && row.throughput > 0.0)
+        .map(|row| ((row.key.operation.as_str(), row.key.scale.as_str()), row.throughput))
+        .collect();
+
+    let mut cells: Vec<HeatmapCell> = rows
+        .iter()
+        .filter_map(|row| {
+            let baseline = baselines.get(&(row.key.operation.as_str(), row.key.scale.as_str()))?;
+            Some(HeatmapCell {
+                operation: row.key.operation.clone(),
+                config: row.key.config.clone(),
+                scale: row.key.scale.clone(),
+                num_sequences: row.num_sequences,
+                value: row.throughput / baseline,
+            })
+        })
+        .collect();
+    sort_cells(&mut cells);
+    Ok(cells)
+}
+
+fn sort_cells(cells: &mut [HeatmapCell]) {
+    cells.sort_by(|a, b| {
+        (a.num_sequences, &a.scale, &a.operation, &a.config)
+            .cmp(&(b.num_sequences, &b.scale, &b.operation, &b.config))
+    });
+}
+
+/// Tidy long-format CSV: `operation,config,scale,num_sequences,metric,value`
+pub fn tidy_csv(cells: &[HeatmapCell], metric: &str) -> String {
+    let mut csv = String::from("operation,config,scale,num_sequences,metric,value\n");
+    for cell in cells {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{}",
+            cell.operation, cell.config, cell.scale, cell.num_sequences, metric, cell.value
+        );
+    }
+    csv
+}
+
+// ============================================================================
+// SVG Rendering
+// ============================================================================
+
+const CELL_WIDTH: usize = 72;
+const CELL_HEIGHT: usize = 22;
+const LABEL_WIDTH: usize = 190;
+const HEADER_HEIGHT: usize = 110;
+const PANEL_GAP: usize = 30;
+
+/// Fill colour of a value
+///
+/// Speedups use a diverging scale on log2: red below 1×, white at 1×, blue
+/// above (saturating at 1/16× and 16×). Other metrics use a sequential
+/// white-to-blue scale between the smallest and largest value.
+fn color(value: f64, metric: &str, range: (f64, f64)) -> String {
+    let blend = |t: f64, to: (f64, f64, f64)| {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |c: f64| (255.0 + (c - 255.0) * t).round() as u8;
+        format!("#{:02x}{:02x}{:02x}", channel(to.0), channel(to.1), channel(to.2))
+    };
+    if metric.contains("speedup") {
+        let log = value.max(f64::MIN_POSITIVE).log2() / 4.0;
+        if log >= 0.0 {
+            blend(log, (33.0, 102.0, 172.0))
+        } else {
+            blend(-log, (178.0, 24.0, 43.0))
+        }
+    } else {
+        let (min, max) = range;
+        let t = if max > min { (value - min) / (max - min) } else { 0.5 };
+        blend(t, (33.0, 102.0, 172.0))
+    }
+}
+
+/// Escape text for SVG
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// SVG with one operation × config panel per scale (missing cells blank)
+pub fn render_svg(cells: &[HeatmapCell], metric: &str) -> String {
+    let mut configs: Vec<&str> = cells.iter().map(|cell| cell.config.as_str()).collect();
+    configs.sort();
+    configs.dedup();
+    let mut operations: Vec<&str> = cells.iter().map(|cell| cell.operation.as_str()).collect();
+    operations.sort();
+    operations.dedup();
+
+    // Panels in cell order (fewest sequences first)
+    let mut scales: Vec<&str> = Vec::new();
+    let mut grid: BTreeMap<(&str, &str, &str), f64> = BTreeMap::new();
+    for cell in cells {
+        if !scales.contains(&cell.scale.as_str()) {
+            scales.push(&cell.scale);
+        }
+        grid.insert((&cell.scale, &cell.operation, &cell.config), cell.value);
+    }
+    let range = cells.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), cell| {
+        (min.min(cell.value), max.max(cell.value))
+    });
+
+    let panel_height = HEADER_HEIGHT + operations.len() * CELL_HEIGHT;
+    let width = LABEL_WIDTH + configs.len() * CELL_WIDTH + 20;
+    let height = 40 + scales.len() * (panel_height + PANEL_GAP);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" font-family="sans-serif" font-size="11">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="10" y="24" font-size="16" font-weight="bold">{}</text>"#,
+        escape(metric)
+    );
+    for (panel, scale) in scales.iter().enumerate() {
+        let top = 40 + panel * (panel_height + PANEL_GAP);
+        let _ = writeln!(
+            svg,
+            r#"<text x="10" y="{}" font-size="13" font-weight="bold">{}</text>"#,
+            top + 16,
+            escape(scale)
+        );
+        for (column, config) in configs.iter().enumerate() {
+            let x = LABEL_WIDTH + column * CELL_WIDTH + CELL_WIDTH / 2;
+            let y = top + HEADER_HEIGHT - 6;
+            let _ = writeln!(
+                svg,
+                r#"<text x="{x}" y="{y}" transform="rotate(-45 {x} {y})">{}</text>"#,
+                escape(config)
+            );
+        }
+        for (row, operation) in operations.iter().enumerate() {
+            let y = top + HEADER_HEIGHT + row * CELL_HEIGHT;
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}" text-anchor="end">{}</text>"#,
+                LABEL_WIDTH - 8,
+                y + CELL_HEIGHT / 2 + 4,
+                escape(operation)
+            );
+            for (column, config) in configs.iter().enumerate() {
+                let Some(value) = grid.get(&(*scale, *operation, *config)) else {
+                    continue;
+                };
+                let x = LABEL_WIDTH + column * CELL_WIDTH;
+                let _ = writeln!(
+                    svg,
+                    r##"<rect x="{x}" y="{y}" width="{CELL_WIDTH}" height="{CELL_HEIGHT}" fill="{}" stroke="#ffffff"><title>{} / {}: {}</title></rect>"##,
+                    color(*value, metric, range),
+                    escape(operation),
+                    escape(config),
+                    value
+                );
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+                    x + CELL_WIDTH / 2,
+                    y + CELL_HEIGHT / 2 + 4,
+                    format_value(*value)
+                );
+            }
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Cell label: 3 significant digits, SI suffixes for large values
+fn format_value(value: f64) -> String {
+    let magnitude = value.abs();
+    if magnitude >= 1e9 {
+        format!("{:.1}G", value / 1e9)
+    } else if magnitude >= 1e6 {
+        format!("{:.1}M", value / 1e6)
+    } else if magnitude >= 1e3 {
+        format!("{:.1}k", value / 1e3)
+    } else if magnitude >= 10.0 {
+        format!("{:.1}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cells_from_column_or_computed() {
+        let dir = std::env::temp_dir().join(format!("asbb-heatmap-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let dag = dir.join("dag.csv");
+        std::fs::write(
+            &dag,
+            "operation,config_name,affinity,scale,num_sequences,pruned,throughput_median,speedup_median\n\
+             gc_content,neon,default,Large,100000,false,2000.0,20.0\n\
+             gc_content,neon,default,Small,1000,false,1500.0,15.0\n\
+             gc_content,neon_8t,default,Small,1000,true,0,\n",
+        )
+        .unwrap();
+        let cells = heatmap_cells(&dag, DEFAULT_METRIC).unwrap();
+        assert_eq!(cells.len(), 2);
+        assert_eq!((cells[0].scale.as_str(), cells[0].value), ("Small", 15.0));
+        assert!(heatmap_cells(&dag, "energy").is_err());
+
+        let pilot = dir.join("pilot.csv");
+        std::fs::write(
+            &pilot,
+            "operation,config,scale,num_sequences,throughput_seqs_per_sec\n\
+             gc_content,naive,Small,1000,100.0\n\
+             gc_content,neon,Small,1000,400.0\n",
+        )
+        .unwrap();
+        let cells = heatmap_cells(&pilot, DEFAULT_METRIC).unwrap();
+        assert_eq!(cells.len(), 2);
+        assert_eq!((cells[1].config.as_str(), cells[1].value), ("neon", 4.0));
+
+        let csv = tidy_csv(&cells, DEFAULT_METRIC);
+        assert!(csv.ends_with("gc_content,neon,Small,1000,speedup_median,4\n"));
+
+        let svg = render_svg(&cells, DEFAULT_METRIC);
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect").count(), 2);
+        assert_eq!(color(1.0, DEFAULT_METRIC, (0.0, 0.0)), "#ffffff");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,144 @@
+//! Read-length breakdowns
+//!
+//! Long reads (ONT/PacBio) change what limits an operation: a 100 kb read
+//! is one unit of parallel work and streams far past L1, where a 150 bp
+//! read fits in a cache line or two. Sequences per second are not
+//! comparable across length classes, so each config's speedup over the
+//! naive baseline of the same (operation, scale) is computed first, then
+//! summarized per (operation, config, length class).
+//!
+//! Rows without a recorded `length_class` (results from before the column
+//! existed) count as short reads: every earlier harness used 150 bp data.
+
+use std::collections::BTreeMap;
+
+use crate::results::ResultRow;
+
+/// Length class assumed for rows that did not record one
+pub const DEFAULT_LENGTH_CLASS: &str = "short";
+
+/// Speedups of one config over naive within one length class
+#[derive(Debug, Clone, PartialEq)]
+pub struct LengthBreakdown {
+    pub operation: String,
+    pub config: String,
+    pub length_class: String,
+
+    /// Median speedup over naive across the class's scales
+    pub speedup: f64,
+
+    /// Number of scales measured in this class
+    pub scales: usize,
+}
+
+/// Length class of a row ([`DEFAULT_LENGTH_CLASS`] if not recorded)
+pub fn row_length_class(row: &ResultRow) -> &str {
+    row.length_class.as_deref().unwrap_or(DEFAULT_LENGTH_CLASS)
+}
+
+/// Whether any row was measured on a long-read tier
+pub fn has_long_reads(rows: &[ResultRow]) -> bool {
+    rows.iter().any(|row| row_length_class(row) != DEFAULT_LENGTH_CLASS)
+}
+
+/// Median speedup over naive per (operation, config, length class)
+///
+/// Rows without a naive baseline at the same (operation, scale) are
+/// skipped; naive itself is omitted (its speedup is 1 by definition).
+/// Sorted by operation, config, then class order (short → 100 kb).
+pub fn length_breakdown(rows: &[ResultRow]) -> Vec<LengthBreakdown> {
+    let baselines: BTreeMap<(&str, &str), f64> = rows
+        .iter()
+        .filter(|row| row.key.config == "naive" && row.throughput > 0.0)
+        .map(|row| ((row.key.operation.as_str(), row.key.scale.as_str()), row.throughput))
+        .collect();
+
+    let mut speedups: BTreeMap<(&str, &str, usize, &str), Vec<f64>> = BTreeMap::new();
+    for row in rows.iter().filter(|row| row.key.config != "naive") {
+        let Some(baseline) = baselines.get(&(row.key.operation.as_str(), row.key.scale.as_str()))
+        else {
+            continue;
+        };
+        let class = row_length_class(row);
+        speedups
+            .entry((&row.key.operation, &row.key.config, class_order(class), class))
+            .or_default()
+            .push(row.throughput / baseline);
+    }
+
+    speedups
+        .into_iter()
+        .map(|((operation, config, _, class), mut values)| {
+            values.sort_by(f64::total_cmp);
+            let mid = values.len() / 2;
+            let speedup = if values.len() % 2 == 0 {
+                (values[mid - 1] + values[mid]) / 2.0
+            } else {
+                values[mid]
+            };
+            LengthBreakdown {
+                operation: operation.to_string(),
+                config: config.to_string(),
+                length_class: class.to_string(),
+                speedup,
+                scales: values.len(),
+            }
+        })
+        .collect()
+}
+
+/// Sort position of a class name (unknown names sort last)
+fn class_order(class: &str) -> usize {
+    asbb_core::LengthClass::from_name(class)
+        .map(|class| class as usize)
+        .unwrap_or(usize::MAX)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::ResultKey;
+
+    fn row(config: &str, scale: &str, length_class: Option<&str>, throughput: f64) -> ResultRow {
+        ResultRow {
+            key: ResultKey {
+                operation: "gc_content".to_string(),
+                config: config.to_string(),
+                scale: scale.to_string(),
+            },
+            num_sequences: 1000,
+            length_class: length_class.map(str::to_string),
+            threads: None,
+            throughput,
+            throughput_ci: None,
+            throughput_samples: None,
+        }
+    }
+
+    #[test]
+    fn test_speedup_per_length_class() {
+        let rows = vec![
+            row("naive", "Long100kb", Some("100kb"), 10.0),
+            row("neon", "Long100kb", Some("100kb"), 40.0),
+            row("naive", "Medium", None, 1000.0),
+            row("neon", "Medium", None, 15000.0),
+            row("naive", "Large", None, 1000.0),
+            row("neon", "Large", None, 17000.0),
+            row("neon_4t", "Long1kb", Some("1kb"), 500.0),
+        ];
+        assert!(has_long_reads(&rows));
+        assert!(!has_long_reads(&rows[2..6]));
+
+        let breakdown = length_breakdown(&rows);
+        assert_eq!(breakdown.len(), 2);
+        assert_eq!(breakdown[0].length_class, "short");
+        assert_eq!(breakdown[0].scales, 2);
+        assert!((breakdown[0].speedup - 16.0).abs() < 1e-9);
+        assert_eq!(breakdown[1].length_class, "100kb");
+        assert!((breakdown[1].speedup - 4.0).abs() < 1e-9);
+    }
+}
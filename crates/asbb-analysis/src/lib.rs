@@ -1,18 +1,40 @@
-//! Placeholder - to be implemented
+//! Analysis of benchmark results tables
+//!
+//! Works on the CSV files the harnesses write, independently of how (or on
+//! which machine) they were produced:
+//!
+//! - [`results`]: load results CSVs from any harness into common rows
+//! - [`comparison`]: align two tables (e.g. M4 vs Graviton) and compare
+//! - [`store`]: SQLite store of results from many machines, runs and commits
+//! - [`regression`]: flag significant slowdowns against a machine's stored history
+//! - [`scaling`]: fit thread scaling to Amdahl/Gustafson models
+//! - [`bandwidth`]: fraction of peak memory bandwidth each config achieves
+//! - [`length`]: speedups broken down by read-length class (short vs. long reads)
+//! - [`category`]: speedups pooled by operation category, and the best config per category
+//! - [`heatmap`]: operation × config grids of any metric, as tidy CSV or SVG
+//! - [`winner`]: best config per (operation, scale), or a tie when intervals overlap
 
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
-pub fn placeholder() {
-    println!("Placeholder implementation");
-}
+pub mod bandwidth;
+pub mod category;
+pub mod comparison;
+pub mod heatmap;
+pub mod length;
+pub mod regression;
+pub mod results;
+pub mod scaling;
+pub mod store;
+pub mod winner;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_placeholder() {
-        placeholder();
-    }
-}
+pub use bandwidth::{bandwidth_utilization, BandwidthPoint, PeakBandwidth};
+pub use category::{category_conclusions, category_summary, CategoryConclusion, CategorySummary};
+pub use comparison::{compare_results, CiOverlap, Comparison, ComparisonRow};
+pub use heatmap::{heatmap_cells, render_svg, tidy_csv, HeatmapCell};
+pub use length::{length_breakdown, LengthBreakdown};
+pub use regression::{detect_regressions, RegressionCheck, RegressionReport, Verdict};
+pub use results::{load_results_csv, ResultKey, ResultRow, SampleSummary};
+pub use scaling::{analyze_scaling, fit_amdahl, AmdahlFit, ScalingCurve, ScalingPoint};
+pub use store::{MergeIssue, MergeReport, ResultSet, ResultStore, StoredResult};
+pub use winner::{cell_winners, CellVerdict, CellWinner};
@@ -0,0 +1,269 @@
+//! Regression detection against historical results
+//!
+//! Each experiment in the current run is checked against the latest stored
+//! row for the same (operation, config, scale) on the same machine (see
+//! [`ResultStore::history`](crate::store::ResultStore::history)). A change counts
+//! only when it is both large (beyond a relative threshold) and
+//! statistically significant (Welch's t-test on the throughput samples, 95%
+//! two-sided). Large changes that cannot be shown significant, including
+//! runs without repeated measurements, are reported as inconclusive rather
+//! than flagged.
+
+use asbb_core::stats::{t_critical_value, welch_t_test};
+use std::collections::HashMap;
+
+use crate::results::{ResultKey, ResultRow};
+use crate::store::StoredResult;
+
+/// Relative throughput drop that counts as a regression by default (5%)
+pub const DEFAULT_REGRESSION_THRESHOLD: f64 = 0.05;
+
+/// Outcome of checking one experiment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Slower beyond the threshold, significantly
+    Regression,
+    /// Faster beyond the threshold, significantly
+    Improvement,
+    /// Beyond the threshold, but not shown significant
+    Inconclusive,
+    /// Within the threshold
+    Unchanged,
+}
+
+impl Verdict {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Regression => "regression",
+            Verdict::Improvement => "improvement",
+            Verdict::Inconclusive => "inconclusive",
+            Verdict::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// Current measurement checked against its reference
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegressionCheck {
+    pub key: ResultKey,
+
+    /// Commit the reference measurement came from
+    pub reference_run: String,
+    pub reference_throughput: f64,
+    pub current_throughput: f64,
+
+    /// Relative change (current / reference - 1; negative is slower)
+    pub change: f64,
+
+    /// Welch t statistic (current - reference), if both sides have samples
+    pub t_statistic: Option<f64>,
+
+    pub verdict: Verdict,
+}
+
+/// Checks for every experiment in the current run
+#[derive(Debug, Clone, Default)]
+pub struct RegressionReport {
+    /// Experiments with history, in current-run order
+    pub checks: Vec<RegressionCheck>,
+
+    /// Experiments with no recorded history
+    pub new_keys: Vec<ResultKey>,
+}
+
+impl RegressionReport {
+    pub fn count(&self, verdict: Verdict) -> usize {
+        self.checks.iter().filter(|c| c.verdict == verdict).count()
+    }
+
+    pub fn regressions(&self) -> impl Iterator<Item = &RegressionCheck> {
+        self.checks.iter().filter(|c| c.verdict == Verdict::Regression)
+    }
+}
+
+/// Check `current` against the latest `history` row for each experiment
+///
+/// `history` is one machine's stored rows, oldest first. `threshold` is the
+/// relative change (e.g. 0.05 for 5%) below which differences are ignored.
+pub fn detect_regressions(
+    history: &[StoredResult],
+    current: &[ResultRow],
+    threshold: f64,
+) -> RegressionReport {
+    // History is oldest first, so later entries replace earlier ones
+    let latest: HashMap<&ResultKey, &StoredResult> =
+        history.iter().map(|stored| (&stored.row.key, stored)).collect();
+
+    let mut report = RegressionReport::default();
+    for row in current {
+        let Some(stored) = latest.get(&row.key) else {
+            report.new_keys.push(row.key.clone());
+            continue;
+        };
+        let reference = &stored.row;
+
+        let change = if reference.throughput > 0.0 {
+            row.throughput / reference.throughput - 1.0
+        } else {
+            0.0
+        };
+        let test = row
+            .throughput_samples
+            .zip(reference.throughput_samples)
+            .and_then(|(current, reference)| {
+                welch_t_test(
+                    (current.mean, current.std_dev, current.n),
+                    (reference.mean, reference.std_dev, reference.n),
+                )
+            });
+        let significant =
+            test.is_some_and(|(t, df)| t.abs() > t_critical_value(df.floor() as usize, 0.05));
+
+        let verdict = if change.abs() <= threshold {
+            Verdict::Unchanged
+        } else if !significant {
+            Verdict::Inconclusive
+        } else if change < 0.0 {
+            Verdict::Regression
+        } else {
+            Verdict::Improvement
+        };
+
+        report.checks.push(RegressionCheck {
+            key: row.key.clone(),
+            reference_run: stored.commit.clone(),
+            reference_throughput: reference.throughput,
+            current_throughput: row.throughput,
+            change,
+            t_statistic: test.map(|(t, _)| t),
+            verdict,
+        });
+    }
+
+    report
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::SampleSummary;
+
+    fn row(config: &str, throughput: f64, std_dev: Option<f64>) -> ResultRow {
+        ResultRow {
+            key: ResultKey {
+                operation: "gc_content".to_string(),
+                config: config.to_string(),
+                scale: "Medium".to_string(),
+            },
+            num_sequences: 10_000,
+            length_class: None,
+            threads: None,
+            throughput,
+            throughput_ci: None,
+            throughput_samples: std_dev.map(|std_dev| SampleSummary {
+                mean: throughput,
+                std_dev,
+                n: 10,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_detect_regressions() {
+        let history: Vec<StoredResult> = [
+            ("old", row("neon", 500.0, Some(5.0))),
+            ("prev", row("neon", 1000.0, Some(10.0))),
+            ("prev", row("naive", 100.0, Some(1.0))),
+            ("prev", row("neon_4t", 3000.0, Some(1500.0))),
+            ("prev", row("neon_8t", 4000.0, None)),
+            ("prev", row("neon@p_cores", 1000.0, Some(10.0))),
+        ]
+        .iter()
+        .map(|(commit, row)| StoredResult {
+            machine: "m1".to_string(),
+            commit: commit.to_string(),
+            row: row.clone(),
+            source: "run.csv".to_string(),
+        })
+        .collect();
+
+        let current = vec![
+            row("neon", 800.0, Some(10.0)),          // -20%, tight: regression
+            row("naive", 102.0, Some(1.0)),          // +2%: within threshold
+            row("neon_4t", 2000.0, Some(1500.0)),    // -33%, noisy: inconclusive
+            row("neon_8t", 2000.0, None),            // -50%, no samples: inconclusive
+            row("neon@p_cores", 1200.0, Some(10.0)), // +20%: improvement
+            row("neon_2t", 1500.0, Some(10.0)),      // no history
+        ];
+
+        let report = detect_regressions(&history, &current, DEFAULT_REGRESSION_THRESHOLD);
+        let verdicts: Vec<Verdict> = report.checks.iter().map(|c| c.verdict).collect();
+        assert_eq!(
+            verdicts,
+            [
+                Verdict::Regression,
+                Verdict::Unchanged,
+                Verdict::Inconclusive,
+                Verdict::Inconclusive,
+                Verdict::Improvement,
+            ]
+        );
+
+        // Latest row is the reference, not the older 500/s run
+        let neon = &report.checks[0];
+        assert_eq!(neon.reference_run, "prev");
+        assert!((neon.change + 0.2).abs() < 1e-12);
+        assert!(neon.t_statistic.unwrap() < 0.0);
+
+        assert_eq!(report.new_keys.len(), 1);
+        assert_eq!(report.regressions().count(), 1);
+    }
+
+    #[test]
+    fn test_regress_against_merged_store() {
+        use crate::store::{ResultSet, ResultStore};
+
+        let dir = std::env::temp_dir().join(format!("asbb-regress-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let header = "operation,config_name,scale,num_sequences,throughput_median,\
+                      throughput_mean,throughput_std_dev,n_valid";
+        let set = |machine: &str, neon: f64| {
+            let source = dir.join(format!("{}.csv", machine));
+            let body = format!(
+                "gc_content,naive,Medium,10000,100.0,100.0,1.0,10\n\
+                 gc_content,neon,Medium,10000,{0},{0},10.0,10\n",
+                neon
+            );
+            std::fs::write(&source, format!("{}\n{}", header, body)).unwrap();
+            ResultSet { source, machine: machine.to_string(), commit: "abc123".to_string() }
+        };
+
+        // Two machines' runs of one commit, merged into one store
+        let mut store = ResultStore::open(&dir.join("results.sqlite")).unwrap();
+        let merged = store.merge(&[set("m1", 1000.0), set("m4", 2000.0)], false).unwrap();
+        assert_eq!(merged.inserted, 4);
+
+        // Each machine is checked against its own baseline only
+        let current = vec![row("neon", 1000.0, Some(10.0))];
+        let m1 = detect_regressions(&store.history("m1").unwrap(), &current, 0.05);
+        assert_eq!(m1.checks[0].verdict, Verdict::Unchanged);
+        let m4 = detect_regressions(&store.history("m4").unwrap(), &current, 0.05);
+        assert_eq!(m4.checks[0].verdict, Verdict::Regression);
+        assert_eq!(m4.checks[0].reference_run, "abc123");
+
+        // A recorded run becomes the latest baseline for its machine
+        let recorded = store.record("m1", "def456", "run.csv", &current).unwrap();
+        assert!(recorded.written);
+        let faster = vec![row("neon", 1500.0, Some(10.0))];
+        let m1 = detect_regressions(&store.history("m1").unwrap(), &faster, 0.05);
+        assert_eq!(m1.checks[0].reference_run, "def456");
+        assert_eq!(m1.checks[0].verdict, Verdict::Improvement);
+        assert_eq!(store.history("m4").unwrap().len(), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
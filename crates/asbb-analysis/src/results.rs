@@ -0,0 +1,220 @@
+//! Loading benchmark results tables
+//!
+//! Every harness writes one CSV row per (operation, config, scale), but the
+//! column names have drifted over time:
+//!
+//! | Harness                       | Config column | Throughput column         |
+//! |-------------------------------|---------------|---------------------------|
+//! | `asbb-dag-traversal`          | `config_name` | `throughput_median`       |
+//! | `asbb bench`                  | `config_name` | `throughput_median`       |
+//! | Early pilots (Graviton, power)| `config`      | `throughput_seqs_per_sec` |
+//!
+//! [`load_results_csv`] accepts all of them. Thread counts (`threads` or
+//! `num_threads`), confidence intervals (`throughput_ci_lower` /
+//! `throughput_ci_upper`) and sample summaries (`throughput_mean`,
+//! `throughput_std_dev`, `n_valid`) are optional, since the early pilots
+//! did not record them; so is the read-length class (`length_class`), which
+//! only DAG runs with long-read tiers record.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Identifies one experiment across result files
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ResultKey {
+    pub operation: String,
+    /// Config name, with `@affinity` appended for non-default core affinity
+    pub config: String,
+    pub scale: String,
+}
+
+impl fmt::Display for ResultKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} / {} / {}", self.operation, self.config, self.scale)
+    }
+}
+
+/// One measured experiment from a results CSV
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultRow {
+    pub key: ResultKey,
+    pub num_sequences: usize,
+
+    /// Read-length class ("short", "1kb", "10kb", "100kb"), if recorded
+    pub length_class: Option<String>,
+
+    /// Worker threads, if recorded
+    pub threads: Option<usize>,
+
+    /// Median throughput (sequences/second)
+    pub throughput: f64,
+
+    /// 95% confidence interval of the mean throughput, if measured
+    pub throughput_ci: Option<(f64, f64)>,
+
+    /// Mean, standard deviation and count of the throughput samples, if
+    /// measured (needed for significance tests)
+    pub throughput_samples: Option<SampleSummary>,
+}
+
+/// Summary of repeated throughput measurements
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SampleSummary {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub n: usize,
+}
+
+/// Load a results CSV written by any harness (see module docs)
+///
+/// Pruned rows (`pruned = true`) carry no measurement and are skipped.
+pub fn load_results_csv(path: &Path) -> Result<Vec<ResultRow>> {
+    let file =
+        File::open(path).with_context(|| format!("Failed to open results: {}", path.display()))?;
+    let mut lines = BufReader::new(file).lines();
+
+    let header = lines.next().transpose()?.unwrap_or_default();
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    let find = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| columns.iter().position(|c| c == name))
+    };
+    let column = |names: &[&str]| {
+        find(names).with_context(|| format!("{} has no '{}' column", path.display(), names[0]))
+    };
+
+    let operation_col = column(&["operation"])?;
+    let config_col = column(&["config_name", "config"])?;
+    let scale_col = column(&["scale"])?;
+    let sequences_col = column(&["num_sequences"])?;
+    let throughput_col = column(&["throughput_median", "throughput_seqs_per_sec"])?;
+    let threads_col = find(&["threads", "num_threads"]);
+    let affinity_col = find(&["affinity"]);
+    let pruned_col = find(&["pruned"]);
+    let length_class_col = find(&["length_class"]);
+    let ci_cols = find(&["throughput_ci_lower"]).zip(find(&["throughput_ci_upper"]));
+    let sample_cols = find(&["throughput_mean"])
+        .zip(find(&["throughput_std_dev"]))
+        .zip(find(&["n_valid"]));
+
+    let mut rows = Vec::new();
+    for (line_number, line) in lines.enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields.len() != columns.len() {
+            anyhow::bail!(
+                "{} line {}: expected {} fields, found {}",
+                path.display(),
+                line_number + 2,
+                columns.len(),
+                fields.len()
+            );
+        }
+        if pruned_col.is_some_and(|col| fields[col] == "true") {
+            continue;
+        }
+
+        let config = match affinity_col.map(|col| fields[col]) {
+            Some(affinity) if !affinity.is_empty() && affinity != "default" => {
+                format!("{}@{}", fields[config_col], affinity)
+            }
+            _ => fields[config_col].to_string(),
+        };
+        let parse = |col: usize| -> Result<f64> {
+            fields[col].parse().with_context(|| {
+                format!(
+                    "{} line {}: invalid number '{}'",
+                    path.display(),
+                    line_number + 2,
+                    fields[col]
+                )
+            })
+        };
+
+        rows.push(ResultRow {
+            key: ResultKey {
+                operation: fields[operation_col].to_string(),
+                config,
+                scale: fields[scale_col].to_string(),
+            },
+            num_sequences: fields[sequences_col].parse().unwrap_or(0),
+            length_class: length_class_col
+                .map(|col| fields[col])
+                .filter(|class| !class.is_empty())
+                .map(str::to_string),
+            threads: threads_col.and_then(|col| fields[col].parse().ok()),
+            throughput: parse(throughput_col)?,
+            throughput_ci: match ci_cols {
+                Some((lower, upper)) if !fields[lower].is_empty() && !fields[upper].is_empty() => {
+                    Some((parse(lower)?, parse(upper)?))
+                }
+                _ => None,
+            },
+            throughput_samples: match sample_cols {
+                Some(((mean, std_dev), n)) if !fields[mean].is_empty() => Some(SampleSummary {
+                    mean: parse(mean)?,
+                    std_dev: parse(std_dev)?,
+                    n: fields[n].parse().unwrap_or(0),
+                }),
+                _ => None,
+            },
+        });
+    }
+
+    Ok(rows)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_dag_and_legacy_columns() {
+        let dir = std::env::temp_dir().join(format!("asbb-results-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let dag = dir.join("dag.csv");
+        std::fs::write(
+            &dag,
+            "operation,config_name,threads,affinity,scale,num_sequences,pruned,throughput_median,throughput_ci_lower,throughput_ci_upper\n\
+             gc_content,neon,1,default,Small,1000,false,500.0,450.0,550.0\n\
+             gc_content,neon,1,p_cores,Small,1000,false,520.0,500.0,540.0\n\
+             gc_content,neon_8t,8,default,Small,1000,true,0,0,0\n",
+        )
+        .unwrap();
+        let rows = load_results_csv(&dag).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].key.config, "neon");
+        assert_eq!(rows[0].throughput_ci, Some((450.0, 550.0)));
+        assert_eq!(rows[1].key.config, "neon@p_cores");
+        assert_eq!(rows[1].threads, Some(1));
+
+        let legacy = dir.join("legacy.csv");
+        std::fs::write(
+            &legacy,
+            "operation,config,scale,num_sequences,throughput_seqs_per_sec\n\
+             gc_content,naive,Small,1000,120.5\n",
+        )
+        .unwrap();
+        let rows = load_results_csv(&legacy).unwrap();
+        assert_eq!(rows[0].key.to_string(), "gc_content / naive / Small");
+        assert_eq!(rows[0].throughput, 120.5);
+        assert_eq!(rows[0].throughput_ci, None);
+        assert_eq!(rows[0].throughput_samples, None);
+        assert_eq!(rows[0].threads, None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
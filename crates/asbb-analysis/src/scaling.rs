@@ -0,0 +1,350 @@
+//! Thread-scaling models
+//!
+//! Fits measured throughput against thread count for each (operation,
+//! scale, config family) to Amdahl's law. Time per sequence under Amdahl is
+//! linear in 1/threads,
+//!
+//! ```text
+//! t(n) = t1 · (f + (1 - f) / n)
+//! ```
+//!
+//! so a least-squares line through (1/n, 1/throughput) gives the serial
+//! fraction `f` without needing a single-threaded measurement. Gustafson's
+//! serial fraction, `(n - S) / (n - 1)` fitted over all points, is reported
+//! alongside: it is the share of the work that did not scale, as seen from
+//! the parallel run.
+//!
+//! The DAG traversal stops adding threads when doubling them gains less
+//! than its diminishing-returns threshold (1.3×). [`AmdahlFit::saturation_threads`]
+//! gives the thread count where the fitted model crosses that threshold, so
+//! the threshold can be checked against each operation's serial fraction.
+
+use std::collections::BTreeMap;
+
+use crate::results::ResultRow;
+
+/// Doubling gain below which more threads are not worth it (DAG default)
+pub const DEFAULT_DOUBLING_THRESHOLD: f64 = 1.3;
+
+/// Largest thread count considered by [`AmdahlFit::saturation_threads`]
+const MAX_SATURATION_THREADS: usize = 1024;
+
+/// Amdahl's law fitted to one scaling curve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmdahlFit {
+    /// Fraction of single-threaded time that does not parallelize (0-1)
+    pub serial_fraction: f64,
+
+    /// Modelled single-threaded throughput (sequences/second)
+    pub single_thread_throughput: f64,
+
+    /// Coefficient of determination of the time-per-sequence fit
+    pub r_squared: f64,
+}
+
+impl AmdahlFit {
+    /// Modelled speedup over one thread
+    pub fn speedup(&self, threads: usize) -> f64 {
+        let f = self.serial_fraction;
+        1.0 / (f + (1.0 - f) / threads.max(1) as f64)
+    }
+
+    /// Modelled gain from doubling `threads`
+    pub fn doubling_gain(&self, threads: usize) -> f64 {
+        self.speedup(threads * 2) / self.speedup(threads)
+    }
+
+    /// Thread count past which doubling gains less than `threshold`
+    ///
+    /// Power of two; `None` if doubling stays worthwhile up to 1024 threads.
+    pub fn saturation_threads(&self, threshold: f64) -> Option<usize> {
+        let mut threads = 1;
+        while threads <= MAX_SATURATION_THREADS {
+            if self.doubling_gain(threads) < threshold {
+                return Some(threads);
+            }
+            threads *= 2;
+        }
+        None
+    }
+}
+
+/// Fit Amdahl's law to (threads, throughput) points
+///
+/// Needs at least two distinct thread counts. The serial fraction is
+/// clamped to [0, 1] (superlinear curves fit as fully parallel, curves that
+/// slow down with threads as fully serial).
+pub fn fit_amdahl(points: &[(usize, f64)]) -> Option<AmdahlFit> {
+    let points: Vec<(f64, f64)> = points
+        .iter()
+        .filter(|(threads, throughput)| *threads > 0 && *throughput > 0.0)
+        .map(|&(threads, throughput)| (1.0 / threads as f64, 1.0 / throughput))
+        .collect();
+    let n = points.len() as f64;
+    if n < 2.0 {
+        return None;
+    }
+
+    // Least squares: time per sequence = a + b / threads
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let sxx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    if sxx <= 0.0 {
+        return None;
+    }
+    let sxy: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let b = sxy / sxx;
+    let a = mean_y - b * mean_x;
+
+    let ss_total: f64 = points.iter().map(|(_, y)| (y - mean_y).powi(2)).sum();
+    let ss_residual: f64 = points.iter().map(|(x, y)| (y - (a + b * x)).powi(2)).sum();
+    let r_squared = if ss_total > 0.0 { 1.0 - ss_residual / ss_total } else { 1.0 };
+
+    let single_thread_time = a + b;
+    if single_thread_time <= 0.0 {
+        return None;
+    }
+
+    Some(AmdahlFit {
+        serial_fraction: (a / single_thread_time).clamp(0.0, 1.0),
+        single_thread_throughput: 1.0 / single_thread_time,
+        r_squared,
+    })
+}
+
+/// Gustafson serial fraction fitted to (threads, speedup) points
+///
+/// Least-squares `s` in `S(n) = n - s (n - 1)`, clamped to [0, 1]. Points
+/// at one thread carry no information and are ignored.
+pub fn fit_gustafson(points: &[(usize, f64)]) -> Option<f64> {
+    let (numerator, denominator) = points
+        .iter()
+        .filter(|(threads, _)| *threads > 1)
+        .fold((0.0, 0.0), |(num, den), &(threads, speedup)| {
+            let extra = threads as f64 - 1.0;
+            (num + extra * (threads as f64 - speedup), den + extra * extra)
+        });
+    (denominator > 0.0).then(|| (numerator / denominator).clamp(0.0, 1.0))
+}
+
+/// One measured thread count on a scaling curve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScalingPoint {
+    pub threads: usize,
+
+    /// Median throughput (sequences/second)
+    pub throughput: f64,
+
+    /// Throughput over the single-threaded throughput (measured if
+    /// available, else modelled)
+    pub speedup: f64,
+
+    /// Speedup per thread
+    pub efficiency: f64,
+
+    /// Speedup gained per core added since the previous point
+    pub marginal_speedup: Option<f64>,
+}
+
+/// Throughput vs thread count for one operation, scale and config family
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalingCurve {
+    pub operation: String,
+    pub scale: String,
+
+    /// Config name without its thread count (see [`config_family`])
+    pub family: String,
+
+    /// Points in increasing thread order
+    pub points: Vec<ScalingPoint>,
+
+    pub amdahl: AmdahlFit,
+    pub gustafson_serial_fraction: Option<f64>,
+}
+
+/// Config name with the thread count and redundant affinity tokens removed
+///
+/// `neon_4t` and `neon` share the family `neon`; `neon_4t_pcores@p_cores`
+/// and `neon@p_cores` share `neon@p_cores`.
+pub fn config_family(config: &str) -> String {
+    let (name, affinity) = match config.split_once('@') {
+        Some((name, affinity)) => (name, Some(affinity)),
+        None => (config, None),
+    };
+
+    let mut tokens = name.split('_');
+    let mut family: Vec<&str> = tokens.next().into_iter().collect();
+    family.extend(tokens.filter(|token| {
+        let is_threads = token.len() > 1
+            && token.ends_with('t')
+            && token[..token.len() - 1].bytes().all(|b| b.is_ascii_digit());
+        !is_threads && *token != "pcores" && *token != "ecores"
+    }));
+
+    let family = family.join("_");
+    match affinity {
+        Some(affinity) => format!("{}@{}", family, affinity),
+        None => family,
+    }
+}
+
+/// Thread count of a row: the recorded column, else the `_{n}t` config suffix
+pub(crate) fn row_threads(row: &ResultRow) -> usize {
+    row.threads.unwrap_or_else(|| {
+        let name = row.key.config.split('@').next().unwrap_or_default();
+        name.split('_')
+            .skip(1)
+            .find_map(|token| token.strip_suffix('t').and_then(|n| n.parse().ok()))
+            .unwrap_or(1)
+    })
+}
+
+/// Scaling curves for every (operation, scale, family) measured at two or
+/// more thread counts
+///
+/// If a thread count appears twice in a curve, the last row wins.
+pub fn analyze_scaling(rows: &[ResultRow]) -> Vec<ScalingCurve> {
+    let mut groups: BTreeMap<(String, String, String), BTreeMap<usize, f64>> = BTreeMap::new();
+    for row in rows.iter().filter(|row| row.throughput > 0.0) {
+        groups
+            .entry((
+                row.key.operation.clone(),
+                row.key.scale.clone(),
+                config_family(&row.key.config),
+            ))
+            .or_default()
+            .insert(row_threads(row), row.throughput);
+    }
+
+    groups
+        .into_iter()
+        .filter_map(|((operation, scale, family), measured)| {
+            let measured: Vec<(usize, f64)> = measured.into_iter().collect();
+            let amdahl = fit_amdahl(&measured)?;
+            let base = match measured.first() {
+                Some(&(1, throughput)) => throughput,
+                _ => amdahl.single_thread_throughput,
+            };
+
+            let mut points: Vec<ScalingPoint> = Vec::with_capacity(measured.len());
+            for &(threads, throughput) in &measured {
+                let speedup = throughput / base;
+                let marginal_speedup = match points.last() {
+                    Some(previous) => {
+                        Some((speedup - previous.speedup) / (threads - previous.threads) as f64)
+                    }
+                    None if threads > 1 => Some((speedup - 1.0) / (threads - 1) as f64),
+                    None => None,
+                };
+                points.push(ScalingPoint {
+                    threads,
+                    throughput,
+                    speedup,
+                    efficiency: speedup / threads as f64,
+                    marginal_speedup,
+                });
+            }
+
+            let speedups: Vec<(usize, f64)> = points.iter().map(|p| (p.threads, p.speedup)).collect();
+            Some(ScalingCurve {
+                operation,
+                scale,
+                family,
+                points,
+                amdahl,
+                gustafson_serial_fraction: fit_gustafson(&speedups),
+            })
+        })
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::ResultKey;
+
+    fn amdahl_throughput(serial_fraction: f64, threads: usize) -> f64 {
+        1000.0 / (serial_fraction + (1.0 - serial_fraction) / threads as f64)
+    }
+
+    fn row(config: &str, threads: Option<usize>, throughput: f64) -> ResultRow {
+        ResultRow {
+            key: ResultKey {
+                operation: "gc_content".to_string(),
+                config: config.to_string(),
+                scale: "Large".to_string(),
+            },
+            num_sequences: 100_000,
+            length_class: None,
+            threads,
+            throughput,
+            throughput_ci: None,
+            throughput_samples: None,
+        }
+    }
+
+    #[test]
+    fn test_fit_amdahl_recovers_serial_fraction() {
+        let points: Vec<(usize, f64)> =
+            [1, 2, 4, 8].iter().map(|&n| (n, amdahl_throughput(0.1, n))).collect();
+        let fit = fit_amdahl(&points).unwrap();
+        assert!((fit.serial_fraction - 0.1).abs() < 1e-9);
+        assert!((fit.single_thread_throughput - 1000.0).abs() < 1e-6);
+        assert!((fit.r_squared - 1.0).abs() < 1e-9);
+
+        // No single-threaded point needed
+        let fit = fit_amdahl(&points[1..]).unwrap();
+        assert!((fit.serial_fraction - 0.1).abs() < 1e-9);
+
+        // 10% serial: 8 → 16 threads gains 1.36×, 16 → 32 only 1.22×
+        assert_eq!(fit.saturation_threads(DEFAULT_DOUBLING_THRESHOLD), Some(16));
+        assert!(fit_amdahl(&points[..1]).is_none());
+    }
+
+    #[test]
+    fn test_fit_gustafson() {
+        // S(n) = n - 0.25 (n - 1)
+        let points = [(1, 1.0), (2, 1.75), (4, 3.25), (8, 6.25)];
+        assert!((fit_gustafson(&points).unwrap() - 0.25).abs() < 1e-12);
+        assert_eq!(fit_gustafson(&[(1, 1.0)]), None);
+    }
+
+    #[test]
+    fn test_config_family() {
+        assert_eq!(config_family("neon"), "neon");
+        assert_eq!(config_family("neon_4t"), "neon");
+        assert_eq!(config_family("neon_chunked_8t"), "neon_chunked");
+        assert_eq!(config_family("neon_4t_pcores@p_cores"), "neon@p_cores");
+        assert_eq!(config_family("parallel_2t"), "parallel");
+        assert_eq!(config_family("2bit_neon"), "2bit_neon");
+    }
+
+    #[test]
+    fn test_analyze_scaling() {
+        let rows = vec![
+            row("naive", Some(1), 100.0),
+            row("neon", Some(1), amdahl_throughput(0.2, 1)),
+            row("neon_2t", None, amdahl_throughput(0.2, 2)),
+            row("neon_4t", Some(4), amdahl_throughput(0.2, 4)),
+        ];
+        let curves = analyze_scaling(&rows);
+
+        // naive has a single thread count
+        assert_eq!(curves.len(), 1);
+        let curve = &curves[0];
+        assert_eq!(curve.family, "neon");
+        assert_eq!(curve.points.len(), 3);
+        assert!((curve.amdahl.serial_fraction - 0.2).abs() < 1e-9);
+
+        let four = curve.points[2];
+        assert!((four.speedup - 2.5).abs() < 1e-9);
+        assert!((four.efficiency - 0.625).abs() < 1e-9);
+        // 2 → 4 threads: 1.667× → 2.5×
+        assert!((four.marginal_speedup.unwrap() - 0.8333 / 2.0).abs() < 1e-3);
+        assert_eq!(curve.points[0].marginal_speedup, None);
+    }
+}
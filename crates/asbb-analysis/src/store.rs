@@ -0,0 +1,432 @@
+//! SQLite store of results from many machines and runs
+//!
+//! `asbb results merge` collects result sets (a results CSV plus the machine
+//! and commit that produced it) into one database, so analyses can span
+//! every machine at once. Rows are keyed by (machine, commit, operation,
+//! config, scale):
+//!
+//! - an incoming row matching a stored row's measurement is a *duplicate*
+//!   (the same file merged twice, or copied between directories) and is
+//!   skipped
+//! - an incoming row with a different measurement is a *conflict*: two runs
+//!   claim different numbers for one experiment of one commit on one machine
+//!
+//! Files that do not parse as results tables are *schema mismatches*, and a
+//! database written with a different store schema is refused outright.
+//! Conflicts and mismatches abort a merge (nothing is written) unless the
+//! caller chooses to skip them.
+//!
+//! `asbb regress` uses the same store: it checks a run against the machine's
+//! history here and records the run with [`ResultStore::record`].
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension, Row, Transaction};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::results::{load_results_csv, ResultKey, ResultRow, SampleSummary};
+
+/// Version of the table layout below; bumped on incompatible changes
+pub const SCHEMA_VERSION: i64 = 1;
+
+/// Relative throughput difference still treated as the same measurement
+const SAME_MEASUREMENT: f64 = 1e-9;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS meta (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS results (
+        machine TEXT NOT NULL,
+        commit_id TEXT NOT NULL,
+        operation TEXT NOT NULL,
+        config TEXT NOT NULL,
+        scale TEXT NOT NULL,
+        num_sequences INTEGER NOT NULL,
+        length_class TEXT,
+        threads INTEGER,
+        throughput REAL NOT NULL,
+        throughput_ci_lower REAL,
+        throughput_ci_upper REAL,
+        throughput_mean REAL,
+        throughput_std_dev REAL,
+        n_valid INTEGER,
+        source TEXT NOT NULL,
+        PRIMARY KEY (machine, commit_id, operation, config, scale)
+    );
+";
+
+/// Columns read back into a [`StoredResult`], in table order
+const COLUMNS: &str = "machine, commit_id, operation, config, scale, num_sequences, length_class,
+    threads, throughput, throughput_ci_lower, throughput_ci_upper,
+    throughput_mean, throughput_std_dev, n_valid, source";
+
+/// A results CSV and where it was measured
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultSet {
+    pub source: PathBuf,
+
+    /// Machine that ran it (e.g. host name)
+    pub machine: String,
+
+    /// Commit of the code measured
+    pub commit: String,
+}
+
+/// A stored row with its provenance
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredResult {
+    pub machine: String,
+    pub commit: String,
+    pub row: ResultRow,
+
+    /// Results file the row was merged from
+    pub source: String,
+}
+
+/// Something a merge refused
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeIssue {
+    /// Same (machine, commit, experiment), different measurement
+    Conflict {
+        machine: String,
+        commit: String,
+        key: ResultKey,
+        stored_throughput: f64,
+        stored_source: String,
+        incoming_throughput: f64,
+        incoming_source: String,
+    },
+
+    /// File that is not a readable results table
+    SchemaMismatch { source: PathBuf, reason: String },
+}
+
+impl fmt::Display for MergeIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeIssue::Conflict {
+                machine,
+                commit,
+                key,
+                stored_throughput,
+                stored_source,
+                incoming_throughput,
+                incoming_source,
+            } => write!(
+                f,
+                "conflict: {} on {} @ {}: {:.2} seqs/sec in {} vs {:.2} in {}",
+                key,
+                machine,
+                commit,
+                stored_throughput,
+                stored_source,
+                incoming_throughput,
+                incoming_source
+            ),
+            MergeIssue::SchemaMismatch { source, reason } => {
+                write!(f, "schema mismatch: {}: {}", source.display(), reason)
+            }
+        }
+    }
+}
+
+/// Outcome of a merge
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeReport {
+    pub inserted: usize,
+    pub duplicates: usize,
+    pub issues: Vec<MergeIssue>,
+
+    /// Whether the merge was committed (false if issues aborted it)
+    pub written: bool,
+}
+
+/// Results database
+pub struct ResultStore {
+    conn: Connection,
+}
+
+impl ResultStore {
+    /// Open (or create) the store at `path`
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open results store: {}", path.display()))?;
+        conn.execute_batch(SCHEMA)?;
+
+        let version: Option<String> = conn
+            .query_row("SELECT value FROM meta WHERE key = 'schema_version'", [], |row| row.get(0))
+            .optional()?;
+        match version {
+            None => {
+                conn.execute(
+                    "INSERT INTO meta (key, value) VALUES ('schema_version', ?1)",
+                    params![SCHEMA_VERSION.to_string()],
+                )?;
+            }
+            Some(version) if version == SCHEMA_VERSION.to_string() => {}
+            Some(version) => anyhow::bail!(
+                "{} uses store schema {} (this asbb writes schema {})",
+                path.display(),
+                version,
+                SCHEMA_VERSION
+            ),
+        }
+        Ok(Self { conn })
+    }
+
+    /// Merge result sets in one transaction
+    ///
+    /// Unless `skip_issues` is set, any conflict or schema mismatch rolls the
+    /// whole merge back; the report lists them either way.
+    pub fn merge(&mut self, sets: &[ResultSet], skip_issues: bool) -> Result<MergeReport> {
+        let tx = self.conn.transaction()?;
+        let mut report = MergeReport::default();
+
+        for set in sets {
+            let rows = match load_results_csv(&set.source) {
+                Ok(rows) => rows,
+                Err(e) => {
+                    report.issues.push(MergeIssue::SchemaMismatch {
+                        source: set.source.clone(),
+                        reason: format!("{:#}", e),
+                    });
+                    continue;
+                }
+            };
+            let source = set.source.display().to_string();
+            insert_rows(&tx, &set.machine, &set.commit, &source, &rows, &mut report)?;
+        }
+
+        if report.issues.is_empty() || skip_issues {
+            tx.commit()?;
+            report.written = true;
+        }
+        Ok(report)
+    }
+
+    /// Record one run's rows (already loaded) in one transaction
+    ///
+    /// Duplicates are skipped as in a merge; any conflict rolls the run back.
+    pub fn record(
+        &mut self,
+        machine: &str,
+        commit: &str,
+        source: &str,
+        rows: &[ResultRow],
+    ) -> Result<MergeReport> {
+        let tx = self.conn.transaction()?;
+        let mut report = MergeReport::default();
+        insert_rows(&tx, machine, commit, source, rows, &mut report)?;
+        if report.issues.is_empty() {
+            tx.commit()?;
+            report.written = true;
+        }
+        Ok(report)
+    }
+
+    /// Every stored row, ordered by machine, commit and experiment
+    pub fn results(&self) -> Result<Vec<StoredResult>> {
+        let mut statement = self.conn.prepare(&format!(
+            "SELECT {} FROM results ORDER BY machine, commit_id, operation, config, scale",
+            COLUMNS
+        ))?;
+        let rows = statement.query_map([], stored_result)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(Into::into)
+    }
+
+    /// One machine's rows in the order they were merged or recorded
+    ///
+    /// Later rows for the same experiment are the more recent measurements,
+    /// so this is the baseline history for [`crate::regression`].
+    pub fn history(&self, machine: &str) -> Result<Vec<StoredResult>> {
+        let mut statement = self.conn.prepare(&format!(
+            "SELECT {} FROM results WHERE machine = ?1 ORDER BY rowid",
+            COLUMNS
+        ))?;
+        let rows = statement.query_map(params![machine], stored_result)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(Into::into)
+    }
+
+    /// Distinct (machine, commit) pairs with their row counts
+    pub fn runs(&self) -> Result<Vec<(String, String, usize)>> {
+        let mut statement = self.conn.prepare(
+            "SELECT machine, commit_id, COUNT(*) FROM results
+             GROUP BY machine, commit_id ORDER BY machine, commit_id",
+        )?;
+        let runs = statement.query_map([], |r| {
+            Ok((r.get(0)?, r.get(1)?, r.get::<_, i64>(2)? as usize))
+        })?;
+        runs.collect::<rusqlite::Result<Vec<_>>>().map_err(Into::into)
+    }
+}
+
+/// Insert rows of one run, counting duplicates and collecting conflicts
+fn insert_rows(
+    tx: &Transaction,
+    machine: &str,
+    commit: &str,
+    source: &str,
+    rows: &[ResultRow],
+    report: &mut MergeReport,
+) -> Result<()> {
+    for row in rows {
+        let key = &row.key;
+        let stored: Option<(i64, f64, String)> = tx
+            .query_row(
+                "SELECT num_sequences, throughput, source FROM results
+                 WHERE machine = ?1 AND commit_id = ?2
+                   AND operation = ?3 AND config = ?4 AND scale = ?5",
+                params![machine, commit, key.operation, key.config, key.scale],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .optional()?;
+
+        match stored {
+            None => {
+                let ci = row.throughput_ci;
+                let samples = row.throughput_samples;
+                tx.execute(
+                    "INSERT INTO results VALUES
+                     (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+                    params![
+                        machine,
+                        commit,
+                        key.operation,
+                        key.config,
+                        key.scale,
+                        row.num_sequences as i64,
+                        row.length_class,
+                        row.threads.map(|t| t as i64),
+                        row.throughput,
+                        ci.map(|(lower, _)| lower),
+                        ci.map(|(_, upper)| upper),
+                        samples.map(|s| s.mean),
+                        samples.map(|s| s.std_dev),
+                        samples.map(|s| s.n as i64),
+                        source,
+                    ],
+                )?;
+                report.inserted += 1;
+            }
+            Some((num_sequences, throughput, _))
+                if num_sequences == row.num_sequences as i64
+                    && same_measurement(throughput, row.throughput) =>
+            {
+                report.duplicates += 1;
+            }
+            Some((_, throughput, stored_source)) => {
+                report.issues.push(MergeIssue::Conflict {
+                    machine: machine.to_string(),
+                    commit: commit.to_string(),
+                    key: key.clone(),
+                    stored_throughput: throughput,
+                    stored_source,
+                    incoming_throughput: row.throughput,
+                    incoming_source: source.to_string(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A row selected with [`COLUMNS`]
+fn stored_result(r: &Row) -> rusqlite::Result<StoredResult> {
+    let ci_lower: Option<f64> = r.get(9)?;
+    let ci_upper: Option<f64> = r.get(10)?;
+    let mean: Option<f64> = r.get(11)?;
+    let std_dev: Option<f64> = r.get(12)?;
+    let n: Option<i64> = r.get(13)?;
+    Ok(StoredResult {
+        machine: r.get(0)?,
+        commit: r.get(1)?,
+        row: ResultRow {
+            key: ResultKey { operation: r.get(2)?, config: r.get(3)?, scale: r.get(4)? },
+            num_sequences: r.get::<_, i64>(5)? as usize,
+            length_class: r.get(6)?,
+            threads: r.get::<_, Option<i64>>(7)?.map(|t| t as usize),
+            throughput: r.get(8)?,
+            throughput_ci: ci_lower.zip(ci_upper),
+            throughput_samples: mean.zip(std_dev).zip(n).map(|((mean, std_dev), n)| {
+                SampleSummary { mean, std_dev, n: n as usize }
+            }),
+        },
+        source: r.get(14)?,
+    })
+}
+
+fn same_measurement(a: f64, b: f64) -> bool {
+    (a - b).abs() <= SAME_MEASUREMENT * a.abs().max(b.abs())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_detects_duplicates_conflicts_and_schema_mismatches() {
+        let dir = std::env::temp_dir().join(format!("asbb-store-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let header = "operation,config_name,scale,num_sequences,throughput_median";
+        let write = |name: &str, body: &str| {
+            let path = dir.join(name);
+            std::fs::write(&path, format!("{}\n{}", header, body)).unwrap();
+            path
+        };
+        let m1 = write(
+            "m1.csv",
+            "gc_content,naive,Small,1000,100.0\ngc_content,neon,Small,1000,800.0\n",
+        );
+        let m1_rerun = write("m1_rerun.csv", "gc_content,neon,Small,1000,750.0\n");
+        let broken = dir.join("broken.csv");
+        std::fs::write(&broken, "operation,scale\ngc_content,Small\n").unwrap();
+        let set = |source: &Path, machine: &str| ResultSet {
+            source: source.to_path_buf(),
+            machine: machine.to_string(),
+            commit: "abc123".to_string(),
+        };
+
+        let db = dir.join("results.sqlite");
+        let mut store = ResultStore::open(&db).unwrap();
+
+        // Same file twice (and on a second machine): duplicates, no conflicts
+        let report = store.merge(&[set(&m1, "m1"), set(&m1, "m1"), set(&m1, "m4")], false).unwrap();
+        assert_eq!((report.inserted, report.duplicates), (4, 2));
+        assert!(report.written);
+
+        // A rerun claiming a different number, plus an unreadable file: nothing written
+        let report = store.merge(&[set(&m1_rerun, "m1"), set(&broken, "m1")], false).unwrap();
+        assert!(!report.written);
+        assert_eq!(report.issues.len(), 2);
+        assert!(matches!(
+            &report.issues[0],
+            MergeIssue::Conflict { stored_throughput, incoming_throughput, .. }
+                if *stored_throughput == 800.0 && *incoming_throughput == 750.0
+        ));
+        assert!(matches!(report.issues[1], MergeIssue::SchemaMismatch { .. }));
+        // The same rerun on another commit is a separate run
+        let mut other_commit = set(&m1_rerun, "m1");
+        other_commit.commit = "def456".to_string();
+        assert_eq!(store.merge(&[other_commit], false).unwrap().inserted, 1);
+
+        let stored = store.results().unwrap();
+        assert_eq!(stored.len(), 5);
+        assert_eq!(stored[0].machine, "m1");
+        assert_eq!(store.runs().unwrap()[0], ("m1".to_string(), "abc123".to_string(), 2));
+
+        // Stores from another schema are refused
+        drop(store);
+        let conn = Connection::open(&db).unwrap();
+        conn.execute("UPDATE meta SET value = '99' WHERE key = 'schema_version'", []).unwrap();
+        drop(conn);
+        assert!(ResultStore::open(&db).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
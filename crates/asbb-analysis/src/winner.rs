@@ -0,0 +1,182 @@
+//! Confidence-interval-aware winners per cell
+//!
+//! A cell is one (operation, scale). The config with the highest median
+//! throughput leads it, but leading by a margin smaller than the run-to-run
+//! noise is not winning: the leader is declared the winner only if its 95%
+//! confidence interval lies entirely above the runner-up's. Otherwise the
+//! cell is a tie among every config whose interval overlaps the leader's,
+//! so marginal differences are not over-claimed. Tables without intervals
+//! (single-measurement pilots) leave contested cells unverified.
+
+use std::collections::BTreeMap;
+
+use crate::comparison::CiOverlap;
+use crate::results::ResultRow;
+
+/// How confidently a cell's leader beats the rest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellVerdict {
+    /// Leader's interval lies above the runner-up's (or it ran alone)
+    Winner,
+    /// Leader's interval overlaps the runner-up's
+    Tie,
+    /// Leader or runner-up has no interval
+    Unverified,
+}
+
+impl CellVerdict {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CellVerdict::Winner => "winner",
+            CellVerdict::Tie => "tie",
+            CellVerdict::Unverified => "unverified",
+        }
+    }
+}
+
+/// Outcome of one (operation, scale) cell
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellWinner {
+    pub operation: String,
+    pub scale: String,
+    pub num_sequences: usize,
+
+    /// Config with the highest median throughput
+    pub leader: String,
+    pub leader_throughput: f64,
+
+    /// Second-highest config and its throughput (`None` if it ran alone)
+    pub runner_up: Option<(String, f64)>,
+
+    pub verdict: CellVerdict,
+
+    /// Configs whose interval overlaps the leader's, leader first (ties only)
+    pub tied: Vec<String>,
+}
+
+impl CellWinner {
+    /// Leader throughput / runner-up throughput (1.0 if it ran alone)
+    pub fn margin(&self) -> f64 {
+        match &self.runner_up {
+            Some((_, throughput)) if *throughput > 0.0 => self.leader_throughput / throughput,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Winner, tie or unverified leader of every (operation, scale) cell
+///
+/// Sorted by operation, then scale (fewest sequences first).
+pub fn cell_winners(rows: &[ResultRow]) -> Vec<CellWinner> {
+    let mut cells: BTreeMap<(&str, usize, &str), Vec<&ResultRow>> = BTreeMap::new();
+    for row in rows.iter().filter(|row| row.throughput > 0.0) {
+        cells
+            .entry((&row.key.operation, row.num_sequences, &row.key.scale))
+            .or_default()
+            .push(row);
+    }
+
+    cells
+        .into_iter()
+        .map(|((operation, num_sequences, scale), mut configs)| {
+            configs.sort_by(|a, b| b.throughput.total_cmp(&a.throughput));
+            let leader = configs[0];
+            let runner_up = configs.get(1);
+
+            let verdict = match runner_up {
+                None => CellVerdict::Winner,
+                Some(runner_up) => {
+                    match CiOverlap::classify(runner_up.throughput_ci, leader.throughput_ci) {
+                        CiOverlap::OtherFaster => CellVerdict::Winner,
+                        CiOverlap::Unknown => CellVerdict::Unverified,
+                        // The leader's median is higher, so its interval
+                        // cannot lie entirely below
+                        CiOverlap::Overlapping | CiOverlap::OtherSlower => CellVerdict::Tie,
+                    }
+                }
+            };
+            let tied = if verdict == CellVerdict::Tie {
+                configs
+                    .iter()
+                    .filter(|row| {
+                        row.key == leader.key
+                            || CiOverlap::classify(row.throughput_ci, leader.throughput_ci)
+                                == CiOverlap::Overlapping
+                    })
+                    .map(|row| row.key.config.clone())
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+            CellWinner {
+                operation: operation.to_string(),
+                scale: scale.to_string(),
+                num_sequences,
+                leader: leader.key.config.clone(),
+                leader_throughput: leader.throughput,
+                runner_up: runner_up.map(|row| (row.key.config.clone(), row.throughput)),
+                verdict,
+                tied,
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::ResultKey;
+
+    fn row(operation: &str, config: &str, throughput: f64, ci: Option<(f64, f64)>) -> ResultRow {
+        ResultRow {
+            key: ResultKey {
+                operation: operation.to_string(),
+                config: config.to_string(),
+                scale: "Medium".to_string(),
+            },
+            num_sequences: 10_000,
+            length_class: None,
+            threads: None,
+            throughput,
+            throughput_ci: ci,
+            throughput_samples: None,
+        }
+    }
+
+    #[test]
+    fn test_winner_only_without_overlap() {
+        let rows = vec![
+            // Clear winner
+            row("base_counting", "naive", 100.0, Some((95.0, 105.0))),
+            row("base_counting", "neon", 1600.0, Some((1550.0, 1650.0))),
+            row("base_counting", "neon_4t", 1400.0, Some((1350.0, 1450.0))),
+            // neon_4t leads by 2%, within noise; naive is clearly behind
+            row("gc_content", "naive", 100.0, Some((95.0, 105.0))),
+            row("gc_content", "neon", 1960.0, Some((1900.0, 2020.0))),
+            row("gc_content", "neon_2t", 1900.0, Some((1800.0, 1990.0))),
+            row("gc_content", "neon_4t", 2000.0, Some((1950.0, 2050.0))),
+            // No intervals recorded
+            row("n_content", "naive", 100.0, None),
+            row("n_content", "neon", 300.0, None),
+        ];
+
+        let winners = cell_winners(&rows);
+        assert_eq!(winners.len(), 3);
+
+        assert_eq!(winners[0].leader, "neon");
+        assert_eq!(winners[0].verdict, CellVerdict::Winner);
+        assert!(winners[0].tied.is_empty());
+        assert!((winners[0].margin() - 1600.0 / 1400.0).abs() < 1e-12);
+
+        assert_eq!(winners[1].leader, "neon_4t");
+        assert_eq!(winners[1].verdict, CellVerdict::Tie);
+        assert_eq!(winners[1].tied, vec!["neon_4t", "neon", "neon_2t"]);
+
+        assert_eq!(winners[2].verdict, CellVerdict::Unverified);
+    }
+}
@@ -0,0 +1,531 @@
+//! `asbb bench`: portable benchmark of the operation crate on any host
+//!
+//! Runs the same `asbb-ops` kernels the DAG harness measures on Apple
+//! Silicon, so results from other machines (AWS Graviton, x86_64 Linux) are
+//! directly comparable: the `neon` config uses NEON on aarch64 and the
+//! SSE2/AVX2 kernels in `asbb_ops::simd` on x86_64. Columns follow the DAG
+//! traversal CSV (`operation`, `config_name`, `scale`, throughput / speedup /
+//! elapsed statistics), plus the host architecture and SIMD backend and the
+//! quality distribution fitted to each input (`asbb_core::quality_profile`).
+//!
+//! Replaces the Graviton pilot binary, which carried its own copies of the
+//! NEON kernels.
+//!
+//! `--command NAME=COMMAND` adds black-box tools (see
+//! `asbb_explorer::external::ExternalCommand`) to the same table: the
+//! command runs once per thread count (`cmd_{n}t`) with `{input}` and
+//! `{threads}` substituted, and its rows add peak RSS and, with `--energy`,
+//! CPU joules per run. Throughput counts the input's reads, and speedups are
+//! relative to the command's first thread count.
+
+use anyhow::{Context, Result};
+use asbb_core::io::FastqReader;
+use asbb_core::memory::MemoryActivity;
+use asbb_core::quality_profile::classify_records;
+use asbb_core::stats::{calculate_statistics, ExperimentStatistics, DEFAULT_OUTLIER_THRESHOLD};
+use asbb_core::{HardwareConfig, HardwareProfile, SequenceRecord, ThreadAssignment};
+use asbb_explorer::benchmark_operation;
+use asbb_explorer::energy::{self, EnergyMeter};
+use asbb_explorer::external::ExternalCommand;
+use asbb_explorer::reproducibility::RunManifest;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A dataset to benchmark, labelled with its scale name
+#[derive(Debug, Clone)]
+pub struct BenchInput {
+    pub scale: String,
+    pub path: PathBuf,
+}
+
+impl BenchInput {
+    /// Parse `SCALE=PATH`, or a bare `PATH` labelled with its file stem
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (scale, path) = match s.split_once('=') {
+            Some((scale, path)) if !scale.is_empty() => (scale.to_string(), PathBuf::from(path)),
+            _ => {
+                let path = PathBuf::from(s);
+                let stem = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .ok_or_else(|| format!("no file name in '{}'", s))?;
+                (stem, path)
+            }
+        };
+        Ok(Self { scale, path })
+    }
+}
+
+/// Options for a benchmark run
+pub struct BenchOptions {
+    /// Datasets, in run order
+    pub inputs: Vec<BenchInput>,
+
+    /// Operations to benchmark
+    pub operations: Vec<String>,
+
+    /// Thread counts for the parallel configs (`neon_{n}t`)
+    pub threads: Vec<usize>,
+
+    /// Detected hardware (`--auto`): parallel configs are sized for it
+    /// instead of taken from `threads`
+    pub profile: Option<HardwareProfile>,
+
+    /// Warmup runs per experiment (not measured)
+    pub warmup: usize,
+
+    /// Measured runs per experiment
+    pub runs: usize,
+
+    /// Optional CSV output
+    pub output: Option<PathBuf>,
+
+    /// Black-box commands benchmarked next to the operations
+    pub commands: Vec<ExternalCommand>,
+
+    /// Measure CPU energy of each command run
+    pub energy: bool,
+
+    /// Memory budget for count tables (bytes); operations with a spill mode
+    /// go to disk past it
+    pub memory_budget: Option<usize>,
+}
+
+/// One measured (operation, config, scale)
+pub struct BenchRow {
+    operation: String,
+    config_name: String,
+    threads: usize,
+    scale: String,
+    num_sequences: usize,
+    /// Fitted `QualityDistType` of the input (`n/a` without qualities)
+    quality_dist: String,
+    throughput: ExperimentStatistics,
+    speedup: ExperimentStatistics,
+    elapsed: ExperimentStatistics,
+    /// Largest peak RSS over the measured runs (external commands)
+    peak_rss_bytes: Option<u64>,
+    /// Median CPU joules per run (external commands with `--energy`)
+    energy_joules: Option<f64>,
+    /// Bytes spilled to disk per run (operations)
+    spilled_bytes: Option<u64>,
+    /// Page-fault and swap activity summed over the measured runs (operations)
+    memory_activity: Option<MemoryActivity>,
+}
+
+/// Configs benchmarked, as (name, config); names match the DAG traversal
+///
+/// With a hardware profile, the parallel configs are the P-cores
+/// (`neon_p_cores`) and every core (`neon_all_cores`) of the detected chip.
+pub fn configs(threads: &[usize], profile: Option<&HardwareProfile>) -> Vec<(String, HardwareConfig)> {
+    let mut neon = HardwareConfig::naive();
+    neon.use_neon = true;
+
+    let mut configs = vec![
+        ("naive".to_string(), HardwareConfig::naive()),
+        ("neon".to_string(), neon.clone()),
+    ];
+    match profile {
+        Some(profile) => {
+            let p_cores = HardwareConfig::p_cores_for_profile(profile);
+            let mut all_cores = p_cores.clone();
+            all_cores.num_threads = HardwareConfig::for_profile(profile).num_threads;
+            all_cores.thread_assignment = ThreadAssignment::Mixed;
+
+            configs.push(("neon_p_cores".to_string(), p_cores));
+            configs.push(("neon_all_cores".to_string(), all_cores));
+        }
+        None => {
+            for &num_threads in threads.iter().filter(|&&n| n > 1) {
+                let mut parallel = neon.clone();
+                parallel.num_threads = num_threads;
+                configs.push((format!("neon_{}t", num_threads), parallel));
+            }
+        }
+    }
+    configs
+}
+
+/// Reject configs this machine cannot run (e.g. more threads than cores)
+///
+/// Checks against `profile` (`--auto`), else the detected machine; where
+/// detection fails (off Apple Silicon) there is nothing to check against.
+pub fn validate_configs(
+    configs: &[(String, HardwareConfig)],
+    profile: Option<&HardwareProfile>,
+) -> Result<()> {
+    let detected = profile.cloned().or_else(|| HardwareProfile::detect().ok());
+    let Some(profile) = &detected else {
+        return Ok(());
+    };
+    for (name, config) in configs {
+        config.validate(profile).with_context(|| format!("Config {}", name))?;
+    }
+    Ok(())
+}
+
+/// Host OS and architecture as reported in results (`macos-aarch64`, `linux-x86_64`, ...)
+pub fn platform() -> String {
+    format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+pub fn run(options: &BenchOptions) -> Result<()> {
+    let configs = configs(&options.threads, options.profile.as_ref());
+    validate_configs(&configs, options.profile.as_ref())?;
+
+    println!("🏁 Benchmarking asbb-ops");
+    println!("   Platform: {}", platform());
+    if let Some(profile) = &options.profile {
+        println!(
+            "   Hardware: {} ({} P + {} E cores, {} GPU cores)",
+            profile.chip_name(),
+            profile.num_p_cores,
+            profile.num_e_cores,
+            profile.num_gpu_cores
+        );
+    }
+    println!("   SIMD backend: {}", asbb_ops::simd::backend());
+    println!(
+        "   Configs: {}",
+        configs.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>().join(", ")
+    );
+    for command in &options.commands {
+        println!("   Command {}: {}", command.name, command.template);
+    }
+    println!("   Runs: {} (+{} warmup)", options.runs, options.warmup);
+    println!();
+
+    let mut meter = if options.energy {
+        let meter = energy::detect().context("--energy needs energy measurement")?;
+        println!("⚡ Energy: {}", meter.name());
+        Some(meter)
+    } else {
+        None
+    };
+    let mut command_threads: Vec<usize> = configs.iter().map(|(_, c)| c.num_threads).collect();
+    command_threads.sort_unstable();
+    command_threads.dedup();
+
+    let mut rows = Vec::new();
+    for input in &options.inputs {
+        let data = load_input(input)?;
+        let quality_dist = quality_dist(&data);
+        println!("📂 {} ({} reads, {} quality)", input.scale, data.len(), quality_dist);
+        print_table_header();
+
+        for name in &options.operations {
+            rows.extend(measure_operation(
+                name,
+                &input.scale,
+                &data,
+                &quality_dist,
+                &configs,
+                options.warmup,
+                options.runs,
+                options.memory_budget,
+            )?);
+        }
+        for command in &options.commands {
+            rows.extend(measure_command(
+                command,
+                input,
+                data.len(),
+                &quality_dist,
+                &command_threads,
+                options.warmup,
+                options.runs,
+                &mut meter,
+            )?);
+        }
+        println!();
+    }
+
+    if let Some(path) = &options.output {
+        write_csv(path, &rows)?;
+        println!("📄 Wrote {} results to {}", rows.len(), path.display());
+
+        let mut manifest = RunManifest::capture(path)?;
+        for input in &options.inputs {
+            manifest = manifest.with_dataset(&input.path)?;
+        }
+        println!("🧾 Wrote run manifest to {}", manifest.save_alongside_results()?.display());
+    }
+    Ok(())
+}
+
+/// Read a benchmark dataset
+pub fn load_input(input: &BenchInput) -> Result<Vec<SequenceRecord>> {
+    FastqReader::from_path(&input.path)
+        .with_context(|| format!("Failed to open {}", input.path.display()))?
+        .read_all()
+}
+
+/// Fitted quality distribution of a dataset (`n/a` without qualities)
+pub fn quality_dist(data: &[SequenceRecord]) -> String {
+    match classify_records(data) {
+        Some(quality) => format!("{:?}", quality.distribution_type),
+        None => "n/a".to_string(),
+    }
+}
+
+pub fn print_table_header() {
+    println!(
+        "   {:<22} {:<10} {:>14} {:>10}",
+        "Operation", "Config", "Seqs/sec", "Speedup"
+    );
+}
+
+/// Benchmark one operation on `data` with every config, in order
+///
+/// The first config (naive) is the baseline for the speedups.
+#[allow(clippy::too_many_arguments)]
+pub fn measure_operation(
+    name: &str,
+    scale: &str,
+    data: &[SequenceRecord],
+    quality_dist: &str,
+    configs: &[(String, HardwareConfig)],
+    warmup: usize,
+    runs: usize,
+    memory_budget: Option<usize>,
+) -> Result<Vec<BenchRow>> {
+    let operation = crate::validate::create_operation_within(name, memory_budget)?;
+    let mut baseline = None;
+    let mut rows = Vec::with_capacity(configs.len());
+
+    for (config_name, config) in configs {
+        let result = benchmark_operation(operation.as_ref(), data, config, warmup, runs)
+            .with_context(|| format!("{} / {} / {}", name, config_name, scale))?;
+
+        let elapsed: Vec<f64> = result
+            .phase_timings
+            .iter()
+            .map(|timings| timings.compute_time.as_secs_f64().max(f64::MIN_POSITIVE))
+            .collect();
+        let throughput: Vec<f64> = elapsed.iter().map(|secs| data.len() as f64 / secs).collect();
+
+        let throughput_stats =
+            calculate_statistics(&throughput, DEFAULT_OUTLIER_THRESHOLD, warmup)?;
+        let baseline = *baseline.get_or_insert(throughput_stats.median.max(f64::MIN_POSITIVE));
+        let speedup: Vec<f64> = throughput.iter().map(|t| t / baseline).collect();
+
+        let row = BenchRow {
+            operation: name.to_string(),
+            config_name: config_name.clone(),
+            threads: config.num_threads,
+            scale: scale.to_string(),
+            num_sequences: data.len(),
+            quality_dist: quality_dist.to_string(),
+            throughput: throughput_stats,
+            speedup: calculate_statistics(&speedup, DEFAULT_OUTLIER_THRESHOLD, warmup)?,
+            elapsed: calculate_statistics(&elapsed, DEFAULT_OUTLIER_THRESHOLD, warmup)?,
+            peak_rss_bytes: None,
+            energy_joules: None,
+            spilled_bytes: Some(result.spilled_bytes),
+            memory_activity: result
+                .memory_activity
+                .iter()
+                .copied()
+                .reduce(|total, run| total.add(&run)),
+        };
+        println!(
+            "   {:<22} {:<10} {:>14.0} {:>9.2}×{}",
+            row.operation,
+            row.config_name,
+            row.throughput.median,
+            row.speedup.median,
+            Some(result.spilled_bytes)
+                .filter(|&bytes| bytes > 0)
+                .map(|bytes| format!("  {:.1} MB spilled", bytes as f64 / 1e6))
+                .unwrap_or_default()
+        );
+        if let Some(note) = pressure_note(&elapsed, &result.memory_activity, row.elapsed.median) {
+            println!("      ⚠️  {}", note);
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Runs that waited on disk or the memory compressor, naming the slowest
+/// (`None` if no run did)
+///
+/// A slow run with major faults or swap traffic points at memory pressure
+/// from outside the benchmark rather than at the code under test.
+fn pressure_note(elapsed: &[f64], activity: &[MemoryActivity], median: f64) -> Option<String> {
+    let pressured: Vec<(f64, &MemoryActivity)> = elapsed
+        .iter()
+        .zip(activity)
+        .filter(|(_, run)| run.under_pressure())
+        .map(|(&secs, run)| (secs, run))
+        .collect();
+    let &(slowest, run) = pressured.iter().max_by(|a, b| a.0.total_cmp(&b.0))?;
+    Some(format!(
+        "{}/{} runs under memory pressure; slowest {:.2}× median ({} major faults, {} pages \
+         compressed, {} swapped out)",
+        pressured.len(),
+        elapsed.len(),
+        slowest / median.max(f64::MIN_POSITIVE),
+        run.major_faults,
+        run.compressions,
+        run.swapouts
+    ))
+}
+
+/// Benchmark an external command on `input` at each thread count, in order
+///
+/// The first thread count is the baseline for the speedups.
+#[allow(clippy::too_many_arguments)]
+pub fn measure_command(
+    command: &ExternalCommand,
+    input: &BenchInput,
+    num_sequences: usize,
+    quality_dist: &str,
+    threads: &[usize],
+    warmup: usize,
+    runs: usize,
+    meter: &mut Option<Box<dyn EnergyMeter>>,
+) -> Result<Vec<BenchRow>> {
+    let mut baseline = None;
+    let mut rows = Vec::with_capacity(threads.len());
+
+    for &num_threads in threads {
+        let context = || format!("{} / {}t / {}", command.name, num_threads, input.scale);
+        for _ in 0..warmup {
+            command.run_once(&input.path, num_threads).with_context(context)?;
+        }
+
+        let mut elapsed = Vec::with_capacity(runs);
+        let mut joules = Vec::new();
+        let mut peak_rss_bytes = None;
+        for _ in 0..runs {
+            if let Some(meter) = meter.as_mut() {
+                meter.start()?;
+            }
+            let run = command.run_once(&input.path, num_threads).with_context(context)?;
+            if let Some(meter) = meter.as_mut() {
+                joules.push(meter.stop()?);
+            }
+            elapsed.push(run.elapsed.as_secs_f64().max(f64::MIN_POSITIVE));
+            peak_rss_bytes = peak_rss_bytes.max(run.peak_rss_bytes);
+        }
+        let throughput: Vec<f64> = elapsed.iter().map(|secs| num_sequences as f64 / secs).collect();
+
+        let throughput_stats = calculate_statistics(&throughput, DEFAULT_OUTLIER_THRESHOLD, warmup)?;
+        let baseline = *baseline.get_or_insert(throughput_stats.median.max(f64::MIN_POSITIVE));
+        let speedup: Vec<f64> = throughput.iter().map(|t| t / baseline).collect();
+        let energy_joules = if joules.is_empty() {
+            None
+        } else {
+            Some(calculate_statistics(&joules, DEFAULT_OUTLIER_THRESHOLD, warmup)?.median)
+        };
+
+        let row = BenchRow {
+            operation: command.name.clone(),
+            config_name: format!("cmd_{}t", num_threads),
+            threads: num_threads,
+            scale: input.scale.clone(),
+            num_sequences,
+            quality_dist: quality_dist.to_string(),
+            throughput: throughput_stats,
+            speedup: calculate_statistics(&speedup, DEFAULT_OUTLIER_THRESHOLD, warmup)?,
+            elapsed: calculate_statistics(&elapsed, DEFAULT_OUTLIER_THRESHOLD, warmup)?,
+            peak_rss_bytes,
+            energy_joules,
+            spilled_bytes: None,
+            memory_activity: None,
+        };
+        println!(
+            "   {:<22} {:<10} {:>14.0} {:>9.2}×{}",
+            row.operation,
+            row.config_name,
+            row.throughput.median,
+            row.speedup.median,
+            peak_rss_bytes
+                .map(|bytes| format!("  {:.1} MB peak", bytes as f64 / 1e6))
+                .unwrap_or_default()
+        );
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Header of the results CSV
+pub const CSV_HEADER: &str = "operation,config_name,threads,scale,num_sequences,quality_dist,platform,simd_backend,\
+     throughput_median,throughput_mean,throughput_std_dev,throughput_ci_lower,throughput_ci_upper,\
+     speedup_median,speedup_mean,speedup_std_dev,speedup_ci_lower,speedup_ci_upper,\
+     elapsed_median,elapsed_mean,elapsed_std_dev,elapsed_min,elapsed_max,elapsed_q1,elapsed_q3,elapsed_iqr,\
+     n_valid,n_outliers,n_warmup,peak_rss_bytes,energy_joules,seqs_per_joule,spilled_bytes,\
+     minor_faults,major_faults,compressions,decompressions,swapins,swapouts";
+
+fn write_csv(path: &Path, rows: &[BenchRow]) -> Result<()> {
+    let mut csv = format!("{}\n", CSV_HEADER);
+    for row in rows {
+        csv.push_str(&csv_line(row));
+        csv.push('\n');
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, csv).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// One results CSV line (no newline), tagged with this host's platform and SIMD backend
+pub fn csv_line(row: &BenchRow) -> String {
+    let t = &row.throughput;
+    let s = &row.speedup;
+    let e = &row.elapsed;
+    format!(
+        "{},{},{},{},{},{},{},{},{:.2},{:.2},{:.2},{:.2},{:.2},{:.4},{:.4},{:.4},{:.4},{:.4},\
+         {:.9},{:.9},{:.9},{:.9},{:.9},{:.9},{:.9},{:.9},{},{},{},{},{},{},{},{}",
+        row.operation,
+        row.config_name,
+        row.threads,
+        row.scale,
+        row.num_sequences,
+        row.quality_dist,
+        platform(),
+        asbb_ops::simd::backend(),
+        t.median,
+        t.mean,
+        t.std_dev,
+        t.ci_95_lower,
+        t.ci_95_upper,
+        s.median,
+        s.mean,
+        s.std_dev,
+        s.ci_95_lower,
+        s.ci_95_upper,
+        e.median,
+        e.mean,
+        e.std_dev,
+        e.min,
+        e.max,
+        e.q1,
+        e.q3,
+        e.iqr,
+        e.n_valid,
+        e.n_outliers,
+        e.n_warmup,
+        row.peak_rss_bytes.map(|bytes| bytes.to_string()).unwrap_or_default(),
+        row.energy_joules.map(|j| format!("{:.6}", j)).unwrap_or_default(),
+        row.energy_joules
+            .filter(|j| *j > 0.0)
+            .map(|j| format!("{:.2}", row.num_sequences as f64 / j))
+            .unwrap_or_default(),
+        row.spilled_bytes.map(|bytes| bytes.to_string()).unwrap_or_default(),
+        row.memory_activity
+            .map(|m| {
+                format!(
+                    "{},{},{},{},{},{}",
+                    m.minor_faults,
+                    m.major_faults,
+                    m.compressions,
+                    m.decompressions,
+                    m.swapins,
+                    m.swapouts
+                )
+            })
+            .unwrap_or_else(|| ",,,,,".to_string())
+    )
+}
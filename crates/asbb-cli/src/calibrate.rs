@@ -0,0 +1,114 @@
+//! `asbb calibrate`: quantify measurement overhead in earlier results
+//!
+//! Operations previously converted their statistics to `serde_json::Value`
+//! inside `execute_*`, so that conversion was part of every recorded timing.
+//! This command times compute and serialization separately per operation and
+//! backend, showing how much earlier numbers were inflated (the effect is
+//! largest at small scales, where compute time is short).
+
+use anyhow::{Context, Result};
+use asbb_core::io::FastqReader;
+use asbb_core::HardwareConfig;
+use asbb_explorer::calibration::{measure_serialization_overhead, SerializationOverhead};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Options for a calibration run
+pub struct CalibrateOptions {
+    /// Dataset FASTQ file
+    pub input: PathBuf,
+
+    /// Operations to calibrate
+    pub operations: Vec<String>,
+
+    /// Measured runs per (operation, backend)
+    pub runs: usize,
+
+    /// Optional CSV output
+    pub output: Option<PathBuf>,
+}
+
+/// Backends calibrated, as (label, config)
+fn configs() -> Vec<(&'static str, HardwareConfig)> {
+    let mut neon = HardwareConfig::naive();
+    neon.use_neon = true;
+
+    let mut parallel = HardwareConfig::naive();
+    parallel.use_neon = true;
+    parallel.num_threads = 4;
+
+    vec![
+        ("naive", HardwareConfig::naive()),
+        ("neon", neon),
+        ("neon_4t", parallel),
+    ]
+}
+
+pub fn run(options: &CalibrateOptions) -> Result<()> {
+    let data = FastqReader::from_path(&options.input)?.read_all()?;
+
+    println!("⏱️  Calibrating serialization overhead");
+    println!("   Input: {} ({} reads)", options.input.display(), data.len());
+    println!("   Runs: {}", options.runs);
+    println!();
+    println!(
+        "   {:<22} {:<8} {:>12} {:>12} {:>10}",
+        "Operation", "Backend", "Compute", "Serialize", "Overhead"
+    );
+
+    let mut rows = Vec::new();
+    for name in &options.operations {
+        let operation = asbb_ops::create_operation(name)?;
+        for (label, config) in configs() {
+            let overhead =
+                measure_serialization_overhead(operation.as_ref(), &data, &config, 2, options.runs)?;
+            println!(
+                "   {:<22} {:<8} {:>10.1}µs {:>10.1}µs {:>9.1}%",
+                name,
+                label,
+                overhead.compute_p50.as_secs_f64() * 1e6,
+                overhead.serialize_p50.as_secs_f64() * 1e6,
+                overhead.overhead_percent()
+            );
+            rows.push((label, overhead));
+        }
+    }
+
+    if let Some(worst) = rows
+        .iter()
+        .max_by(|a, b| a.1.overhead_percent().total_cmp(&b.1.overhead_percent()))
+    {
+        println!();
+        println!(
+            "📊 Largest effect: {} ({}) — earlier throughput under-reported by {:.1}%",
+            worst.1.operation,
+            worst.0,
+            100.0 * (1.0 - worst.1.old_throughput_ratio())
+        );
+    }
+
+    if let Some(path) = &options.output {
+        write_csv(path, &rows)?;
+        println!("📄 Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn write_csv(path: &Path, rows: &[(&str, SerializationOverhead)]) -> Result<()> {
+    let mut csv = String::from(
+        "operation,backend,num_sequences,compute_us,serialize_us,overhead_percent,old_throughput_ratio\n",
+    );
+    for (label, row) in rows {
+        csv.push_str(&format!(
+            "{},{},{},{:.3},{:.3},{:.3},{:.4}\n",
+            row.operation,
+            label,
+            row.num_sequences,
+            row.compute_p50.as_secs_f64() * 1e6,
+            row.serialize_p50.as_secs_f64() * 1e6,
+            row.overhead_percent(),
+            row.old_throughput_ratio()
+        ));
+    }
+    fs::write(path, csv).with_context(|| format!("Failed to write {}", path.display()))
+}
@@ -0,0 +1,134 @@
+//! `asbb compare`: cross-platform comparison of two results CSVs
+//!
+//! Automates the Mac vs Graviton analysis: rows from a baseline run (e.g.
+//! DAG traversal on an M4) and another run (e.g. `asbb bench` on Graviton)
+//! are aligned by (operation, config, scale), and the other machine's
+//! throughput is reported relative to the baseline along with whether the
+//! 95% confidence intervals overlap.
+//!
+//! Given both machines' profiles (`asbb micro`), relative throughput is also
+//! reported divided by the machines' memory bandwidth ratio (single-core for
+//! single-threaded rows, all cores otherwise): what remains is the gain not
+//! explained by memory bandwidth.
+
+use anyhow::Result;
+use asbb_analysis::{compare_results, load_results_csv, CiOverlap, Comparison};
+use asbb_micro::MachineProfile;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Options for a comparison
+pub struct CompareOptions {
+    /// Reference results (denominator of relative throughput)
+    pub baseline: PathBuf,
+
+    /// Results to compare against the baseline
+    pub other: PathBuf,
+
+    /// Machine profiles of the baseline and other machine
+    pub profiles: Option<(PathBuf, PathBuf)>,
+
+    /// Optional CSV output
+    pub output: Option<PathBuf>,
+}
+
+pub fn run(options: &CompareOptions) -> Result<()> {
+    let baseline = load_results_csv(&options.baseline)?;
+    let other = load_results_csv(&options.other)?;
+    let comparison = compare_results(&baseline, &other);
+
+    println!("⚖️  Comparing results");
+    println!("   Baseline: {} ({} rows)", options.baseline.display(), baseline.len());
+    println!("   Other:    {} ({} rows)", options.other.display(), other.len());
+    println!();
+
+    if comparison.rows.is_empty() {
+        anyhow::bail!("No (operation, config, scale) rows in common");
+    }
+
+    println!(
+        "   {:<22} {:<16} {:<12} {:>14} {:>14} {:>9}  CI",
+        "Operation", "Config", "Scale", "Baseline/s", "Other/s", "Relative"
+    );
+    for row in &comparison.rows {
+        println!(
+            "   {:<22} {:<16} {:<12} {:>14.0} {:>14.0} {:>8.2}×  {}",
+            row.key.operation,
+            row.key.config,
+            row.key.scale,
+            row.baseline.throughput,
+            row.other.throughput,
+            row.relative_throughput,
+            row.overlap.name()
+        );
+    }
+    println!();
+
+    println!("📊 Relative throughput by config (geometric mean):");
+    for (config, relative) in comparison.relative_by_config() {
+        println!("   {:<16} {:.2}×", config, relative);
+    }
+
+    if let Some((baseline_profile, other_profile)) = &options.profiles {
+        let baseline_profile = MachineProfile::load(baseline_profile)?;
+        let other_profile = MachineProfile::load(other_profile)?;
+        println!();
+        println!(
+            "📏 Bandwidth-normalized by config ({} vs {}):",
+            other_profile.machine, baseline_profile.machine
+        );
+        for (config, normalized) in normalized_by_config(&comparison, &baseline_profile, &other_profile) {
+            println!("   {:<16} {:.2}×", config, normalized);
+        }
+    }
+    println!();
+    println!(
+        "   {} faster, {} slower, {} not distinguishable, {} without intervals",
+        comparison.overlap_count(CiOverlap::OtherFaster),
+        comparison.overlap_count(CiOverlap::OtherSlower),
+        comparison.overlap_count(CiOverlap::Overlapping),
+        comparison.overlap_count(CiOverlap::Unknown)
+    );
+
+    if !comparison.baseline_only.is_empty() || !comparison.other_only.is_empty() {
+        println!(
+            "⚠️  Unmatched rows: {} only in baseline, {} only in other",
+            comparison.baseline_only.len(),
+            comparison.other_only.len()
+        );
+        for key in comparison.baseline_only.iter().take(5) {
+            println!("   baseline only: {}", key);
+        }
+        for key in comparison.other_only.iter().take(5) {
+            println!("   other only:    {}", key);
+        }
+    }
+
+    if let Some(path) = &options.output {
+        comparison.write_csv(path)?;
+        println!("📄 Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+/// Geometric mean per config of relative throughput over the bandwidth factor
+fn normalized_by_config(
+    comparison: &Comparison,
+    baseline: &MachineProfile,
+    other: &MachineProfile,
+) -> BTreeMap<String, f64> {
+    let mut logs: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    for row in &comparison.rows {
+        let threads = row.other.threads.unwrap_or(1);
+        let Some(factor) = other.bandwidth_factor(baseline, threads) else {
+            continue;
+        };
+        let normalized = row.relative_throughput / factor;
+        if normalized.is_finite() && normalized > 0.0 {
+            logs.entry(row.key.config.clone()).or_default().push(normalized.ln());
+        }
+    }
+    logs.into_iter()
+        .map(|(config, logs)| (config, (logs.iter().sum::<f64>() / logs.len() as f64).exp()))
+        .collect()
+}
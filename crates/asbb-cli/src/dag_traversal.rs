@@ -19,210 +19,79 @@
 //! cargo run --release -p asbb-cli --bin asbb-dag-traversal \
 //!   --batch neon_parallel \
 //!   --output results/dag_complete/dag_neon_parallel.csv
+//!
+//! # Rerun a single experiment (plus its naive baseline)
+//! cargo run --release -p asbb-cli --bin asbb-dag-traversal -- \
+//!   --batch neon_parallel --operations gc_content --scales Large --configs neon_4t \
+//!   --output results/dag_complete/gc_content_rerun.csv
+//!
+//! # Seqs per joule on P-cores, E-cores and both (powermetrics needs root)
+//! sudo cargo run --release -p asbb-cli --bin asbb-dag-traversal -- \
+//!   --batch efficiency --output results/dag_complete/dag_efficiency.csv
+//!
+//! # Record count where 4 threads first beat single-threaded NEON by 10%
+//! cargo run --release -p asbb-cli --bin asbb-dag-traversal -- \
+//!   --batch crossover --incumbent neon --candidate neon_4t --min-gain 10 \
+//!   --output results/dag_complete/dag_crossover.csv
+//!
+//! # Add the long-read tiers (1 kb, 10 kb, 100 kb reads) to a batch
+//! cargo run --release -p asbb-cli --bin asbb-dag-traversal -- \
+//!   --batch neon_parallel --long-reads \
+//!   --output results/dag_complete/dag_neon_parallel_long.csv
+//!
+//! # Record-level vs intra-read parallelism on the long-read tiers
+//! cargo run --release -p asbb-cli --bin asbb-dag-traversal -- \
+//!   --batch long_reads --output results/dag_complete/dag_long_reads.csv
 //! ```
+//!
+//! Ctrl-C lets the in-flight experiment finish (press again to abort it),
+//! writes the completed experiments to `--output` and prints a command that
+//! runs the remaining operations into `<output>_resume.csv`.
+//!
+//! Refuses to run under Rosetta translation (an x86_64 build on Apple
+//! Silicon measures emulation, not NEON) unless `--force` is given, and
+//! rejects batch configs the detected machine cannot run (more threads than
+//! cores, E-core affinity without E-cores) before any experiment starts.
 
 use anyhow::{Context, Result};
-use asbb_core::{OperationOutput, PrimitiveOperation, SequenceRecord};
-use asbb_ops::{
-    at_content::ATContent,
-    base_counting::BaseCounting,
-    complexity_score::ComplexityScore,
-    gc_content::GcContent,
-    length_filter::LengthFilter,
-    n_content::NContent,
-    quality_aggregation::QualityAggregation,
-    quality_filter::QualityFilter,
-    reverse_complement::ReverseComplement,
-    sequence_length::SequenceLength,
+use asbb_core::io::FastqReader;
+use asbb_core::memory::{
+    InsufficientMemory, MemoryEstimate, MemoryLimit, DEFAULT_MEMORY_FRACTION,
 };
+use asbb_core::stats::calculate_statistics;
+use asbb_core::{
+    Encoding, HardwareConfig, HardwareProfile, LengthClass, OperationOutput, ParallelStrategy,
+    PrimitiveOperation, QualityOfService, SequenceRecord, ThreadAssignment,
+};
+use asbb_datagen::manifest::{
+    register_dataset, verify_dataset, DatasetManifest, DatasetSource, Verification,
+    DEFAULT_MANIFEST_PATH,
+};
+use asbb_datagen::{generate_fastq_file, ReadGenConfig};
+use asbb_explorer::crossover::{
+    find_crossover, Crossover, CrossoverSearch, RatioEstimate, DEFAULT_MIN_GAIN,
+};
+use asbb_explorer::dataset_cache::DatasetCache;
+use asbb_explorer::energy::{self, EnergyMeter};
+use asbb_explorer::interrupt::{self, is_interrupted, Interrupt};
+use asbb_explorer::plan::{DurationEstimator, ExperimentPlan, PriorTiming};
+use asbb_explorer::pruning::{
+    self, Candidate, Cell, Estimate, Exhaustive, PruningPolicy, PruningStrategy, Role,
+};
+use asbb_explorer::reproducibility::RunManifest;
+use asbb_explorer::translation;
+use asbb_ops::create_operation;
+use asbb_ops::thread_pool::{self, PoolKey};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Instant;
 
-// ============================================================================
-// Statistical Rigor Types
-// ============================================================================
-
-/// Statistical summary of experiment measurements
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ExperimentStatistics {
-    /// Median value (robust to outliers)
-    pub median: f64,
-
-    /// Mean value
-    pub mean: f64,
-
-    /// Standard deviation
-    pub std_dev: f64,
-
-    /// Minimum value (after outlier removal)
-    pub min: f64,
-
-    /// Maximum value (after outlier removal)
-    pub max: f64,
-
-    /// First quartile (25th percentile)
-    pub q1: f64,
-
-    /// Third quartile (75th percentile)
-    pub q3: f64,
-
-    /// Interquartile range (Q3 - Q1)
-    pub iqr: f64,
-
-    /// 95% confidence interval lower bound
-    pub ci_95_lower: f64,
-
-    /// 95% confidence interval upper bound
-    pub ci_95_upper: f64,
-
-    /// Number of valid measurements (after outlier removal)
-    pub n_valid: usize,
-
-    /// Number of outliers removed
-    pub n_outliers: usize,
-
-    /// Number of warmup runs performed
-    pub n_warmup: usize,
-}
-
-/// Remove outliers using IQR method
-///
-/// Returns (valid_measurements, outliers)
-fn remove_outliers(measurements: &[f64], threshold: f64) -> (Vec<f64>, Vec<f64>) {
-    if measurements.len() < 4 {
-        // Too few measurements for outlier detection
-        return (measurements.to_vec(), Vec::new());
-    }
-
-    let mut sorted = measurements.to_vec();
-    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-
-    let n = sorted.len();
-    let q1_idx = n / 4;
-    let q3_idx = 3 * n / 4;
-
-    let q1 = sorted[q1_idx];
-    let q3 = sorted[q3_idx];
-    let iqr = q3 - q1;
-
-    let lower_bound = q1 - threshold * iqr;
-    let upper_bound = q3 + threshold * iqr;
-
-    let valid: Vec<f64> = sorted
-        .iter()
-        .copied()
-        .filter(|&x| x >= lower_bound && x <= upper_bound)
-        .collect();
-
-    let outliers: Vec<f64> = sorted
-        .iter()
-        .copied()
-        .filter(|&x| x < lower_bound || x > upper_bound)
-        .collect();
-
-    (valid, outliers)
-}
-
-/// Calculate comprehensive statistics from measurements
-fn calculate_statistics(
-    measurements: &[f64],
-    outlier_threshold: f64,
-    n_warmup: usize,
-) -> Result<ExperimentStatistics> {
-    let (valid, outliers) = remove_outliers(measurements, outlier_threshold);
-
-    if valid.len() < 3 {
-        anyhow::bail!(
-            "Too few valid measurements after outlier removal: {} / {} (removed {} outliers)",
-            valid.len(),
-            measurements.len(),
-            outliers.len()
-        );
-    }
-
-    let n = valid.len() as f64;
-
-    // Mean
-    let mean = valid.iter().sum::<f64>() / n;
-
-    // Variance and std dev
-    let variance = valid
-        .iter()
-        .map(|x| (x - mean).powi(2))
-        .sum::<f64>()
-        / (n - 1.0);
-    let std_dev = variance.sqrt();
-
-    // Median and quartiles
-    let mut sorted = valid.clone();
-    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-
-    let median_idx = sorted.len() / 2;
-    let median = if sorted.len() % 2 == 0 {
-        (sorted[median_idx - 1] + sorted[median_idx]) / 2.0
-    } else {
-        sorted[median_idx]
-    };
-
-    let q1_idx = sorted.len() / 4;
-    let q3_idx = 3 * sorted.len() / 4;
-    let q1 = sorted[q1_idx];
-    let q3 = sorted[q3_idx];
-    let iqr = q3 - q1;
-
-    let min = *sorted.first().unwrap();
-    let max = *sorted.last().unwrap();
-
-    // 95% Confidence Interval (t-distribution)
-    let df = valid.len() - 1;
-    let t_critical = t_critical_value(df, 0.05);
-    let margin_of_error = t_critical * (std_dev / n.sqrt());
-    let ci_95_lower = mean - margin_of_error;
-    let ci_95_upper = mean + margin_of_error;
-
-    Ok(ExperimentStatistics {
-        median,
-        mean,
-        std_dev,
-        min,
-        max,
-        q1,
-        q3,
-        iqr,
-        ci_95_lower,
-        ci_95_upper,
-        n_valid: valid.len(),
-        n_outliers: outliers.len(),
-        n_warmup,
-    })
-}
-
-/// Get t-critical value for 95% confidence interval
-///
-/// Simplified lookup table. For production use statrs crate.
-fn t_critical_value(df: usize, alpha: f64) -> f64 {
-    if alpha != 0.05 {
-        return 2.0; // Conservative default
-    }
-
-    match df {
-        0..=4 => 2.776,
-        5..=9 => 2.262,
-        10..=14 => 2.145,
-        15..=19 => 2.093,
-        20..=24 => 2.064,
-        25..=29 => 2.045,
-        30..=39 => 2.021,
-        40..=49 => 2.009,
-        50..=99 => 1.984,
-        _ => 1.96, // For large df, use normal approximation
-    }
-}
-
 // ============================================================================
 // Core Types
 // ============================================================================
@@ -242,10 +111,18 @@ pub struct DAGConfig {
     /// Diminishing returns threshold for compositions (e.g., 1.3)
     pub diminishing_returns_threshold: f64,
 
+    /// How the NEON+Parallel batch explores each cell (threshold pruning by
+    /// default; the thresholds above apply to it)
+    pub pruning: PruningPolicy,
+
+    /// Run the unpruned NEON+Parallel grid, then check what `pruning` would
+    /// have skipped against it
+    pub full_factorial: bool,
+
     /// Output CSV path
     pub output_path: PathBuf,
 
-    /// Which batch to run (neon_parallel, core_affinity, scale_thresholds)
+    /// Which batch to run (neon_parallel, core_affinity, scale_thresholds, efficiency, crossover)
     pub batch: DAGBatch,
 
     /// Number of repetitions per experiment (default: 30 for publication quality)
@@ -256,6 +133,47 @@ pub struct DAGConfig {
 
     /// Outlier detection threshold (IQR multiplier, default: 1.5)
     pub outlier_threshold: f64,
+
+    /// Show progress bars (overall ETA and current experiment)
+    pub progress_bar: bool,
+
+    /// Configs to run, by [`DAGNode::config_key`] (empty runs all).
+    /// The naive baseline always runs, since speedups are relative to it.
+    pub configs: Vec<String>,
+
+    /// Configs compared by the crossover batch
+    pub crossover: CrossoverSettings,
+
+    /// Largest estimated memory (bytes) an experiment may need; experiments
+    /// over it are skipped as insufficient-memory (`None` runs everything)
+    pub memory_limit: Option<u64>,
+}
+
+/// Candidate vs incumbent for the crossover batch
+#[derive(Debug, Clone)]
+pub struct CrossoverSettings {
+    /// Config the candidate has to beat
+    pub incumbent: DAGNode,
+
+    /// Config searched for a crossover
+    pub candidate: DAGNode,
+
+    /// Required advantage (0.10 = candidate ≥ 1.10× incumbent)
+    pub min_gain: f64,
+
+    /// Smallest record count searched (the largest is the dataset size)
+    pub min_records: usize,
+}
+
+impl Default for CrossoverSettings {
+    fn default() -> Self {
+        Self {
+            incumbent: DAGNode::neon(),
+            candidate: DAGNode::neon_parallel(4),
+            min_gain: DEFAULT_MIN_GAIN,
+            min_records: 100,
+        }
+    }
 }
 
 /// DAG batch type
@@ -269,6 +187,15 @@ pub enum DAGBatch {
 
     /// Precise scale thresholds (320 experiments)
     ScaleThresholds,
+
+    /// Energy efficiency on P-cores vs E-cores vs both, at a fixed scale
+    Efficiency,
+
+    /// Binary search for the record count where a candidate config takes over
+    Crossover,
+
+    /// Record-level vs intra-read parallelism on long reads
+    LongReads,
 }
 
 impl DAGBatch {
@@ -277,6 +204,9 @@ impl DAGBatch {
             "neon_parallel" | "neon-parallel" => Ok(DAGBatch::NeonParallel),
             "core_affinity" | "core-affinity" => Ok(DAGBatch::CoreAffinity),
             "scale_thresholds" | "scale-thresholds" => Ok(DAGBatch::ScaleThresholds),
+            "efficiency" => Ok(DAGBatch::Efficiency),
+            "crossover" => Ok(DAGBatch::Crossover),
+            "long_reads" | "long-reads" => Ok(DAGBatch::LongReads),
             _ => anyhow::bail!("Unknown batch type: {}", s),
         }
     }
@@ -285,21 +215,148 @@ impl DAGBatch {
 /// Dataset scale definition
 #[derive(Debug, Clone)]
 pub struct Scale {
-    pub name: &'static str,
-    pub path: &'static str,
+    pub name: Cow<'static, str>,
+    pub path: Cow<'static, str>,
     pub num_sequences: usize,
+
+    /// Seed used to synthesize the dataset if the file is missing
+    /// (`None` for real data, which cannot be regenerated)
+    pub seed: Option<u64>,
+
+    /// Read-length class (also the datagen preset used to synthesize it)
+    pub length_class: LengthClass,
+}
+
+impl Scale {
+    /// Standard synthetic scale (150bp short reads)
+    const fn synthetic(name: &'static str, path: &'static str, num_sequences: usize, seed: u64) -> Self {
+        Self::long_read(name, path, num_sequences, seed, LengthClass::Short)
+    }
+
+    /// Synthetic scale with reads from a length preset
+    const fn long_read(
+        name: &'static str,
+        path: &'static str,
+        num_sequences: usize,
+        seed: u64,
+        length_class: LengthClass,
+    ) -> Self {
+        Scale {
+            name: Cow::Borrowed(name),
+            path: Cow::Borrowed(path),
+            num_sequences,
+            seed: Some(seed),
+            length_class,
+        }
+    }
+
+    /// Named dataset registered in the dataset manifest (e.g. fetched from SRA/ENA)
+    pub fn from_manifest(manifest_path: &str, name: &str) -> Result<Self> {
+        let manifest = DatasetManifest::load_or_default(manifest_path)?;
+        let entry = manifest.get(name).with_context(|| {
+            format!("Dataset '{}' not found in {}", name, manifest_path)
+        })?;
+
+        let path = manifest.resolve(entry).to_string_lossy().into_owned();
+        let length_class = match &entry.source {
+            DatasetSource::Synthetic { length_mean, .. } => LengthClass::from_mean_length(*length_mean),
+            _ => sample_length_class(&path)?,
+        };
+
+        Ok(Scale {
+            name: Cow::Owned(entry.name.clone()),
+            path: Cow::Owned(path),
+            num_sequences: entry.num_records,
+            seed: None,
+            length_class,
+        })
+    }
+
+    /// Generate the dataset if its file does not exist
+    ///
+    /// Uses the shared datagen logic (degrading quality, read lengths from
+    /// this scale's length preset) with its recorded seed, so regenerated
+    /// files are reproducible.
+    pub fn ensure_exists(&self) -> Result<()> {
+        if Path::new(self.path.as_ref()).exists() {
+            return Ok(());
+        }
+
+        let seed = self.seed.with_context(|| {
+            format!("Dataset {} is missing and cannot be regenerated (re-fetch it)", self.path)
+        })?;
+
+        println!("    🧬 Dataset {} missing, generating {} sequences (seed {})...",
+                 self.path, self.num_sequences, seed);
+
+        let config = ReadGenConfig::new(self.num_sequences, seed).with_length_class(self.length_class);
+        let written = generate_fastq_file(self.path.as_ref(), &config)
+            .with_context(|| format!("Failed to generate dataset: {}", self.path))?;
+
+        let name = Path::new(self.path.as_ref())
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.name.to_string());
+        register_dataset(
+            DEFAULT_MANIFEST_PATH,
+            &name,
+            self.path.as_ref(),
+            DatasetSource::synthetic_fastq(&config),
+        )?;
+
+        println!("    ✅ Generated {} ({} sequences, registered in {})",
+                 self.path, written, DEFAULT_MANIFEST_PATH);
+        Ok(())
+    }
+
+    /// Check the dataset's SHA-256 against the manifest
+    ///
+    /// Fails if the file changed since it was registered; untracked files
+    /// are allowed but flagged, since their contents cannot be vouched for.
+    pub fn verify(&self) -> Result<()> {
+        match verify_dataset(DEFAULT_MANIFEST_PATH, self.path.as_ref())? {
+            Verification::Verified(entry) => {
+                println!("    🔒 Dataset {} verified (sha256 {})", self.path, &entry.sha256[..12]);
+            }
+            Verification::Untracked => {
+                println!("    ⚠️  Dataset {} is not in {}; contents unverified",
+                         self.path, DEFAULT_MANIFEST_PATH);
+            }
+        }
+        Ok(())
+    }
 }
 
 // Standard scales from existing pilot infrastructure
+// (seeds match datasets/generate_all_scales.sh)
 const SCALES: &[Scale] = &[
-    Scale { name: "Tiny", path: "datasets/tiny_100_150bp.fq", num_sequences: 100 },
-    Scale { name: "Small", path: "datasets/small_1000_150bp.fq", num_sequences: 1_000 },
-    Scale { name: "Medium", path: "datasets/medium_10000_150bp.fq", num_sequences: 10_000 },
-    Scale { name: "Large", path: "datasets/large_100000_150bp.fq", num_sequences: 100_000 },
-    Scale { name: "VeryLarge", path: "datasets/very_large_1000000_150bp.fq", num_sequences: 1_000_000 },
-    Scale { name: "Huge", path: "datasets/huge_10000000_150bp.fq", num_sequences: 10_000_000 },
+    Scale::synthetic("Tiny", "datasets/tiny_100_150bp.fq", 100, 1),
+    Scale::synthetic("Small", "datasets/small_1000_150bp.fq", 1_000, 2),
+    Scale::synthetic("Medium", "datasets/medium_10000_150bp.fq", 10_000, 3),
+    Scale::synthetic("Large", "datasets/large_100000_150bp.fq", 100_000, 4),
+    Scale::synthetic("VeryLarge", "datasets/very_large_1000000_150bp.fq", 1_000_000, 5),
+    Scale::synthetic("Huge", "datasets/huge_10000000_150bp.fq", 10_000_000, 6),
 ];
 
+// Long-read (ONT/PacBio) tiers, added to a batch with --long-reads. Each
+// holds ~10 Mbp, so throughput differences reflect read length, not volume.
+// (seeds match `datasets/generate_all_scales.sh --long-reads`)
+const LONG_READ_SCALES: &[Scale] = &[
+    Scale::long_read("Long1kb", "datasets/long_10000_1kb.fq", 10_000, 11, LengthClass::Kb1),
+    Scale::long_read("Long10kb", "datasets/long_1000_10kb.fq", 1_000, 12, LengthClass::Kb10),
+    Scale::long_read("Long100kb", "datasets/long_100_100kb.fq", 100, 13, LengthClass::Kb100),
+];
+
+/// Thread counts compared in the long-read batch
+const LONG_READ_THREADS: &[usize] = &[2, 4, 8];
+
+/// Length class of a real dataset, from the mean length of its first 1,000 reads
+fn sample_length_class(path: &str) -> Result<LengthClass> {
+    let sample = FastqReader::from_path(path)?.read_batch(1_000)?;
+    let total: usize = sample.iter().map(|record| record.len()).sum();
+    Ok(LengthClass::from_mean_length(total / sample.len().max(1)))
+}
+
 /// Represents a single node in the hardware optimization DAG
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DAGNode {
@@ -338,6 +395,16 @@ impl DAGNode {
         Self::new(ConfigType::Neon, threads, CoreAffinity::Default)
     }
 
+    /// Create NEON with parallel threads over contiguous chunks
+    pub fn neon_chunked(threads: usize) -> Self {
+        Self::new(ConfigType::NeonChunked, threads, CoreAffinity::Default)
+    }
+
+    /// Create NEON with parallel threads over segments of individual reads
+    pub fn neon_intra_read(threads: usize) -> Self {
+        Self::new(ConfigType::NeonIntraRead, threads, CoreAffinity::Default)
+    }
+
     /// Create node with specific affinity
     pub fn with_affinity(mut self, affinity: CoreAffinity) -> Self {
         self.affinity = affinity;
@@ -349,6 +416,8 @@ impl DAGNode {
         let base = match self.config_type {
             ConfigType::Naive => "naive".to_string(),
             ConfigType::Neon => "neon".to_string(),
+            ConfigType::NeonChunked => "neon_chunked".to_string(),
+            ConfigType::NeonIntraRead => "neon_intra".to_string(),
             ConfigType::Gpu => "gpu".to_string(),
             ConfigType::Amx => "amx".to_string(),
         };
@@ -365,6 +434,84 @@ impl DAGNode {
         }
     }
 
+    /// Label used in plans and `--configs` filters (see [`config_key`])
+    pub fn config_key(&self) -> String {
+        config_key(&self.name(), self.affinity.name())
+    }
+
+    /// Parse a config key (`neon`, `neon_4t`, `neon_chunked_4t`, `neon_intra_4t`, `neon@p_cores`)
+    pub fn from_config_key(key: &str) -> Result<Self> {
+        let (name, affinity) = match key.split_once('@') {
+            Some((name, affinity)) => (name, affinity),
+            None => (key, CoreAffinity::Default.name()),
+        };
+        let affinity = [
+            CoreAffinity::Default,
+            CoreAffinity::PerformanceCores,
+            CoreAffinity::EfficiencyCores,
+        ]
+        .into_iter()
+        .find(|a| a.name() == affinity)
+        .with_context(|| format!("Unknown affinity '{}' in config '{}'", affinity, key))?;
+
+        // Multi-threaded names carry an affinity suffix (neon_4t_pcores)
+        let name = name
+            .strip_suffix("_pcores")
+            .or_else(|| name.strip_suffix("_ecores"))
+            .unwrap_or(name);
+        let (base, threads) = match name.rsplit_once('_') {
+            Some((base, threads)) if threads.ends_with('t') => {
+                let count = threads[..threads.len() - 1].parse::<usize>().ok();
+                match count {
+                    Some(count) => (base, count),
+                    None => (name, 1),
+                }
+            }
+            _ => (name, 1),
+        };
+        let config_type = match base {
+            "naive" => ConfigType::Naive,
+            "neon" => ConfigType::Neon,
+            "neon_chunked" => ConfigType::NeonChunked,
+            "neon_intra" => ConfigType::NeonIntraRead,
+            _ => anyhow::bail!(
+                "Unknown config '{}' (e.g. naive, neon, neon_4t, neon_chunked_4t, neon_intra_4t)",
+                key
+            ),
+        };
+        if config_type == ConfigType::Naive && threads > 1 {
+            anyhow::bail!("The naive config is single-threaded: '{}'", key);
+        }
+        Ok(Self::new(config_type, threads, affinity))
+    }
+
+    /// Hardware configuration this node runs with
+    pub fn hardware_config(&self) -> HardwareConfig {
+        let mut config = HardwareConfig::naive();
+        config.num_threads = self.threads;
+        config.thread_assignment = match self.affinity {
+            CoreAffinity::Default => ThreadAssignment::Mixed,
+            CoreAffinity::PerformanceCores => ThreadAssignment::PCoresOnly,
+            CoreAffinity::EfficiencyCores => ThreadAssignment::ECoresOnly,
+        };
+        config.qos = self.affinity.qos();
+        match self.config_type {
+            ConfigType::Naive => {}
+            ConfigType::Neon => config.use_neon = true,
+            ConfigType::NeonChunked => {
+                config.use_neon = true;
+                config.parallel_strategy = ParallelStrategy::Chunked;
+            }
+            ConfigType::NeonIntraRead => {
+                config.use_neon = true;
+                config.parallel_strategy = ParallelStrategy::IntraRead;
+            }
+            ConfigType::Gpu => config.use_gpu = true,
+            ConfigType::Amx => config.use_amx = true,
+        }
+        config
+    }
+
     /// Is this an alternative (mutually exclusive with others)?
     pub fn is_alternative(&self) -> bool {
         self.threads == 1 && self.affinity == CoreAffinity::Default
@@ -386,6 +533,10 @@ impl DAGNode {
 pub enum ConfigType {
     Naive,
     Neon,
+    /// NEON within contiguous chunks, one task per chunk (vs per record)
+    NeonChunked,
+    /// NEON over segments of individual reads (splits long reads across threads)
+    NeonIntraRead,
     Gpu,
     Amx,
 }
@@ -409,6 +560,18 @@ impl CoreAffinity {
             CoreAffinity::EfficiencyCores => "e_cores",
         }
     }
+
+    /// QoS class that steers threads onto the requested cores
+    ///
+    /// macOS has no core pinning; it schedules background QoS on E-cores
+    /// and prefers P-cores for user-initiated work.
+    fn qos(&self) -> QualityOfService {
+        match self {
+            CoreAffinity::Default => QualityOfService::Default,
+            CoreAffinity::PerformanceCores => QualityOfService::UserInitiated,
+            CoreAffinity::EfficiencyCores => QualityOfService::Background,
+        }
+    }
 }
 
 /// Result from a single experiment (with full statistical rigor)
@@ -435,9 +598,18 @@ pub struct ExperimentResult {
     /// Number of sequences
     pub num_sequences: usize,
 
-    /// Was this configuration pruned?
+    /// Read-length class of the dataset ("short", "1kb", "10kb", "100kb")
+    pub length_class: String,
+
+    /// Was this configuration pruned (or skipped, see `skip_reason`)?
     pub pruned: bool,
 
+    /// Why the experiment was skipped instead of run ("insufficient-memory")
+    pub skip_reason: Option<String>,
+
+    /// Pre-execution memory estimate of a skipped experiment (bytes)
+    pub estimated_memory_bytes: Option<u64>,
+
     // === Throughput Statistics (sequences/second) ===
     /// Median throughput (robust to outliers)
     pub throughput_median: f64,
@@ -504,43 +676,194 @@ pub struct ExperimentResult {
 
     /// Number of warmup runs
     pub n_warmup: usize,
+
+    // === Energy (efficiency batch only) ===
+    /// CPU energy per run (joules), averaged over the measured runs
+    pub energy_joules: Option<f64>,
+
+    /// Sequences processed per joule
+    pub seqs_per_joule: Option<f64>,
 }
 
 // ============================================================================
-// Pruning Strategy
+// Pruning Cell
 // ============================================================================
 
-/// Pruning strategy for DAG traversal
-pub struct PruningStrategy {
-    /// Minimum speedup threshold for alternatives (e.g., 1.5)
-    pub speedup_threshold: f64,
+/// One (operation, scale) of the NEON+Parallel batch, as explored by a
+/// [`PruningStrategy`](asbb_explorer::PruningStrategy)
+///
+/// Measuring a node again adds timed runs to its earlier ones; the node's
+/// result always covers all of them.
+struct PruningCell<'a> {
+    traversal: &'a mut DAGTraversal,
+    operation: &'a str,
+    scale: &'a Scale,
+    baseline_throughput: f64,
+    nodes: Vec<DAGNode>,
+    elapsed_times: Vec<Vec<f64>>,
+    results: Vec<Option<ExperimentResult>>,
+}
 
-    /// Minimum additional benefit for compositions (e.g., 1.3)
-    pub diminishing_returns_threshold: f64,
+impl<'a> PruningCell<'a> {
+    fn new(
+        traversal: &'a mut DAGTraversal,
+        operation: &'a str,
+        scale: &'a Scale,
+        baseline_throughput: f64,
+        nodes: Vec<DAGNode>,
+    ) -> Self {
+        let count = nodes.len();
+        Self {
+            traversal,
+            operation,
+            scale,
+            baseline_throughput,
+            nodes,
+            elapsed_times: vec![Vec::new(); count],
+            results: vec![None; count],
+        }
+    }
+
+    /// Latest result of every measured node, in node order
+    fn into_results(self) -> Vec<ExperimentResult> {
+        self.results.into_iter().flatten().collect()
+    }
 }
 
-impl PruningStrategy {
-    pub fn new(speedup_threshold: f64, diminishing_returns_threshold: f64) -> Self {
+impl Cell for PruningCell<'_> {
+    fn measure(&mut self, candidate: usize, repetitions: usize) -> Result<Estimate> {
+        let node = &self.nodes[candidate];
+        let remeasure = self.results[candidate].is_some();
+
+        let result = if self.traversal.pruned_nodes.contains(&(self.operation.to_string(), node.clone())) {
+            // Pruned at an earlier scale
+            if !remeasure {
+                self.traversal.progress.skip(1);
+            }
+            self.traversal.create_pruned_result(self.operation, node, self.scale)
+        } else {
+            if remeasure {
+                self.traversal.progress.extend(1);
+            }
+            let sequences = self.traversal.load_scale(self.scale)?;
+            self.traversal.measure_runs(
+                self.operation,
+                node,
+                self.scale,
+                &sequences,
+                Some(self.baseline_throughput),
+                repetitions,
+                &mut self.elapsed_times[candidate],
+            )?
+        };
+
+        let estimate = Estimate {
+            speedup_median: result.speedup_median,
+            speedup_mean: result.speedup_mean,
+            speedup_std_dev: result.speedup_std_dev,
+            n: result.n_valid,
+        };
+        self.results[candidate] = Some(result);
+        Ok(estimate)
+    }
+
+    fn prune(&mut self, candidate: usize) {
+        let key = (self.operation.to_string(), self.nodes[candidate].clone());
+        self.traversal.pruned_nodes.insert(key);
+    }
+
+    fn skip(&mut self, _candidate: usize) {
+        self.traversal.progress.skip(1);
+    }
+
+    fn log(&mut self, line: String) {
+        self.traversal.progress.println(line);
+    }
+}
+
+// ============================================================================
+// Progress Display
+// ============================================================================
+
+/// Overall and current-experiment progress bars for a DAG run
+///
+/// The overall bar counts planned experiments; pruning shrinks its length so
+/// the ETA tracks the work that will actually run. Log lines go through
+/// [`DagProgress::println`] so they scroll above the bars instead of
+/// tearing them.
+struct DagProgress {
+    multi: MultiProgress,
+    overall: ProgressBar,
+    current: ProgressBar,
+}
+
+impl DagProgress {
+    fn new(total_experiments: u64, enabled: bool) -> Result<Self> {
+        let multi = if enabled {
+            MultiProgress::new()
+        } else {
+            MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
+        };
+
+        let overall = multi.add(ProgressBar::new(total_experiments));
+        overall.set_style(
+            ProgressStyle::default_bar()
+                .template("[{elapsed_precise}] {bar:40.cyan/blue} {pos}/{len} experiments (ETA {eta}) {msg}")?
+                .progress_chars("=>-"),
+        );
+
+        let current = multi.add(ProgressBar::new(0));
+        current.set_style(ProgressStyle::default_bar().template("  {spinner} {msg} [{pos}/{len} runs]")?);
+
+        Ok(Self { multi, overall, current })
+    }
+
+    /// Display that draws nothing (before a run starts)
+    fn hidden() -> Self {
         Self {
-            speedup_threshold,
-            diminishing_returns_threshold,
+            multi: MultiProgress::with_draw_target(ProgressDrawTarget::hidden()),
+            overall: ProgressBar::hidden(),
+            current: ProgressBar::hidden(),
         }
     }
 
-    /// Should we prune this alternative?
-    pub fn should_prune_alternative(&self, result: &ExperimentResult) -> bool {
-        result.speedup_median < self.speedup_threshold
+    /// Print a log line above the bars
+    fn println(&self, line: impl AsRef<str>) {
+        self.multi.suspend(|| println!("{}", line.as_ref()));
     }
 
-    /// Should we stop testing more threads?
-    pub fn should_prune_composition(
-        &self,
-        result: &ExperimentResult,
-        parent_speedup: f64,
-    ) -> bool {
-        // If additional benefit is less than threshold, prune
-        let additional_benefit = result.speedup_median / parent_speedup;
-        additional_benefit < self.diminishing_returns_threshold
+    fn start_experiment(&self, operation: &str, scale: &Scale, node: &DAGNode, runs: usize) {
+        self.current.set_length(runs as u64);
+        self.current.set_position(0);
+        self.current
+            .set_message(format!("{} @ {} with {}", operation, scale.name, node.name()));
+    }
+
+    fn finish_run(&self) {
+        self.current.inc(1);
+    }
+
+    fn finish_experiment(&self, throughput: f64) {
+        self.overall.inc(1);
+        self.overall
+            .set_message(format!("last: {:.0} seqs/sec", throughput));
+    }
+
+    /// Remove planned experiments that pruning made unnecessary
+    fn skip(&self, experiments: u64) {
+        let length = self.overall.length().unwrap_or(0);
+        self.overall.set_length(length.saturating_sub(experiments));
+    }
+
+    /// Add experiments an adaptive strategy decided to re-measure
+    fn extend(&self, experiments: u64) {
+        let length = self.overall.length().unwrap_or(0);
+        self.overall.set_length(length + experiments);
+    }
+
+    fn finish(&self) {
+        self.current.finish_and_clear();
+        self.overall.finish();
     }
 }
 
@@ -554,6 +877,56 @@ pub struct DAGTraversal {
     tested_nodes: HashMap<(String, DAGNode, String), ExperimentResult>,
     pruned_nodes: HashSet<(String, DAGNode)>,
     naive_baselines: HashMap<(String, String), f64>, // (operation, scale) -> throughput
+    verified_datasets: HashSet<String>,               // paths already hash-checked this run
+    datasets: DatasetCache,                           // loaded datasets, shared across configs
+    progress: DagProgress,
+    energy_meter: Option<Box<dyn EnergyMeter>>,        // set for the efficiency batch
+    crossovers: Vec<CrossoverSummary>,                 // crossover batch results
+    measured: Vec<(DAGNode, ExperimentResult)>,        // every measurement, in run order
+    timed_runs: usize,                                 // timed repetitions across all experiments
+    interrupt: Interrupt,
+    interrupted_operation: Option<String>,             // operation in flight when interrupted
+    memory_checks: HashMap<(String, String), Option<InsufficientMemory>>, // (operation, scale) -> over limit?
+    loaded_bytes: HashMap<String, u64>,                // estimated bytes of each cached dataset
+}
+
+/// What pruning would have skipped in one full-factorial cell
+#[derive(Debug, Clone)]
+pub struct PruningCheck {
+    pub operation: String,
+    pub scale: String,
+
+    /// Config with the highest median speedup (naive if nothing beats it)
+    pub optimal: String,
+    pub optimal_speedup: f64,
+
+    /// Best config pruning would still have measured
+    pub best_kept: String,
+    pub best_kept_speedup: f64,
+
+    /// Configs pruning would not have measured
+    pub skipped: Vec<String>,
+}
+
+impl PruningCheck {
+    /// The optimal config was among the skipped ones
+    pub fn missed_optimum(&self) -> bool {
+        self.optimal != self.best_kept
+    }
+
+    /// Optimal speedup / best kept speedup (1.0 when nothing was missed)
+    pub fn loss(&self) -> f64 {
+        self.optimal_speedup / self.best_kept_speedup
+    }
+}
+
+/// Crossover search outcome for one operation and dataset
+#[derive(Debug, Clone)]
+pub struct CrossoverSummary {
+    pub operation: String,
+    pub scale: String,
+    pub search: CrossoverSearch,
+    pub crossover: Crossover,
 }
 
 impl DAGTraversal {
@@ -563,58 +936,307 @@ impl DAGTraversal {
             tested_nodes: HashMap::new(),
             pruned_nodes: HashSet::new(),
             naive_baselines: HashMap::new(),
+            verified_datasets: HashSet::new(),
+            datasets: DatasetCache::new(),
+            progress: DagProgress::hidden(),
+            energy_meter: None,
+            crossovers: Vec::new(),
+            measured: Vec::new(),
+            timed_runs: 0,
+            interrupt: Interrupt::default(),
+            interrupted_operation: None,
+            memory_checks: HashMap::new(),
+            loaded_bytes: HashMap::new(),
         }
     }
 
+    /// Stop between experiments (or runs, on a second signal) when `interrupt` fires
+    pub fn with_interrupt(mut self, interrupt: Interrupt) -> Self {
+        self.interrupt = interrupt;
+        self
+    }
+
+    /// Operations left to run after an interrupt (`None` if the run finished)
+    pub fn remaining_operations(&self) -> Option<&[String]> {
+        let operation = self.interrupted_operation.as_ref()?;
+        let position = self.config.operations.iter().position(|op| op == operation)?;
+        Some(&self.config.operations[position..])
+    }
+
+    /// Fail with `Interrupted` (stop before an experiment, abort between
+    /// runs), remembering the operation for the resume command
+    fn check_interrupt(&mut self, operation: &str, abort: bool) -> Result<()> {
+        let checked = if abort { self.interrupt.check_abort() } else { self.interrupt.check_stop() };
+        if checked.is_err() {
+            self.interrupted_operation = Some(operation.to_string());
+        }
+        checked
+    }
+
+    /// Crossover batch results (empty for other batches)
+    pub fn crossovers(&self) -> &[CrossoverSummary] {
+        &self.crossovers
+    }
+
+    /// Search range for a dataset of `num_sequences` records
+    fn crossover_search(&self, num_sequences: usize) -> CrossoverSearch {
+        let settings = &self.config.crossover;
+        CrossoverSearch::new(
+            settings.min_records.min(num_sequences).max(1),
+            num_sequences,
+            settings.min_gain,
+        )
+    }
+
+    /// Efficiency batch nodes: NEON on every P-core, every E-core, and all cores
+    ///
+    /// Core counts come from the detected machine, so the batch fails off
+    /// Apple Silicon; a core type the chip lacks (no E-cores) is skipped.
+    fn efficiency_nodes(&self) -> Result<Vec<DAGNode>> {
+        let profile = HardwareProfile::detect()
+            .context("The efficiency batch needs the machine's P- and E-core counts")?;
+        let nodes = vec![
+            DAGNode::neon_parallel(profile.num_p_cores).with_affinity(CoreAffinity::PerformanceCores),
+            DAGNode::neon_parallel(profile.num_e_cores).with_affinity(CoreAffinity::EfficiencyCores),
+            DAGNode::neon_parallel(profile.num_cores()),
+        ];
+        Ok(nodes.into_iter().filter(|node| node.threads > 0).collect())
+    }
+
+    /// Configs tested per operation and scale, in run order
+    fn batch_nodes(&self) -> Result<Vec<DAGNode>> {
+        Ok(match self.config.batch {
+            DAGBatch::NeonParallel | DAGBatch::ScaleThresholds => vec![
+                DAGNode::naive(),
+                DAGNode::neon(),
+                DAGNode::neon_parallel(2),
+                DAGNode::neon_chunked(2),
+                DAGNode::neon_parallel(4),
+                DAGNode::neon_chunked(4),
+            ],
+            DAGBatch::CoreAffinity => vec![
+                DAGNode::naive(),
+                DAGNode::neon().with_affinity(CoreAffinity::Default),
+                DAGNode::neon().with_affinity(CoreAffinity::PerformanceCores),
+                DAGNode::neon().with_affinity(CoreAffinity::EfficiencyCores),
+            ],
+            DAGBatch::Efficiency => {
+                let mut nodes = vec![DAGNode::naive()];
+                nodes.extend(self.efficiency_nodes()?);
+                nodes
+            }
+            DAGBatch::Crossover => vec![
+                self.config.crossover.incumbent.clone(),
+                self.config.crossover.candidate.clone(),
+            ],
+            DAGBatch::LongReads => {
+                let mut nodes = vec![DAGNode::naive(), DAGNode::neon()];
+                nodes.extend(Self::long_read_nodes());
+                nodes
+            }
+        })
+    }
+
+    /// Long-read batch nodes: each thread count with per-record, chunked
+    /// and intra-read work splitting
+    fn long_read_nodes() -> Vec<DAGNode> {
+        LONG_READ_THREADS
+            .iter()
+            .flat_map(|&t| {
+                [DAGNode::neon_parallel(t), DAGNode::neon_chunked(t), DAGNode::neon_intra_read(t)]
+            })
+            .collect()
+    }
+
+    /// Distinct experiments the batch runs if nothing is pruned, in run order
+    fn planned_nodes(&self) -> Result<Vec<(String, DAGNode, Scale)>> {
+        let nodes = self.batch_nodes()?;
+
+        let mut planned = Vec::new();
+        for operation in &self.config.operations {
+            for scale in &self.config.scales {
+                for node in nodes.iter().filter(|n| self.is_planned(n)) {
+                    planned.push((operation.clone(), node.clone(), scale.clone()));
+                }
+            }
+        }
+        Ok(planned)
+    }
+
+    /// Whether `node` passes the `--configs` filter
+    fn is_selected(&self, node: &DAGNode) -> bool {
+        self.config.configs.is_empty() || self.config.configs.contains(&node.config_key())
+    }
+
+    /// Whether `node` runs: selected, or the naive baseline every speedup needs
+    fn is_planned(&self, node: &DAGNode) -> bool {
+        node.config_type == ConfigType::Naive || self.is_selected(node)
+    }
+
+    /// Config keys this batch can run (valid `--configs` values)
+    pub fn available_configs(&self) -> Result<Vec<String>> {
+        Ok(self.batch_nodes()?.iter().map(DAGNode::config_key).collect())
+    }
+
+    /// Check every config the batch runs against the machine's `profile`
+    ///
+    /// Fails before any dataset is loaded if a node asks for more threads
+    /// than cores, E-cores the chip lacks, or a feature it does not have.
+    pub fn validate_configs(&self, profile: &HardwareProfile) -> Result<()> {
+        for node in self.batch_nodes()?.iter().filter(|n| self.is_planned(n)) {
+            node.hardware_config()
+                .validate(profile)
+                .with_context(|| format!("Config {}", node.config_key()))?;
+        }
+        Ok(())
+    }
+
+    /// Upper bound on distinct experiments in the batch (before pruning)
+    ///
+    /// Crossover searches run both configs once per probe.
+    fn planned_experiments(&self) -> Result<u64> {
+        Ok(match self.config.batch {
+            DAGBatch::Crossover => {
+                let probes: usize = self
+                    .config
+                    .scales
+                    .iter()
+                    .map(|scale| self.crossover_search(scale.num_sequences).max_probes())
+                    .sum();
+                (2 * probes * self.config.operations.len()) as u64
+            }
+            _ => self.planned_nodes()?.len() as u64,
+        })
+    }
+
+    /// Experiments the batch would run (before pruning) with estimated durations
+    pub fn plan(&self, estimator: &DurationEstimator) -> Result<ExperimentPlan> {
+        let runs = self.config.warmup_runs + self.config.repetitions;
+        let mut plan = ExperimentPlan::default();
+        for (operation, node, scale) in self.planned_nodes()? {
+            plan.push(estimator, &operation, &node.config_key(), &scale.name, scale.num_sequences, runs);
+        }
+        Ok(plan)
+    }
+
     /// Run the complete DAG traversal
     pub fn run(&mut self) -> Result<Vec<ExperimentResult>> {
         println!("🔬 DAG Traversal Starting");
         println!("   Batch: {:?}", self.config.batch);
         println!("   Operations: {}", self.config.operations.len());
         println!("   Scales: {}", self.config.scales.len());
+        println!("   Planned experiments: {} (before pruning)", self.planned_experiments()?);
         println!();
 
-        let all_results = match self.config.batch {
-            DAGBatch::NeonParallel => self.run_neon_parallel_batch()?,
-            DAGBatch::CoreAffinity => self.run_core_affinity_batch()?,
-            DAGBatch::ScaleThresholds => self.run_scale_thresholds_batch()?,
+        if self.config.batch == DAGBatch::Efficiency {
+            let meter = energy::detect().context("The efficiency batch needs energy measurement")?;
+            println!("⚡ Energy meter: {}", meter.name());
+            println!();
+            self.energy_meter = Some(meter);
+        }
+
+        self.progress = DagProgress::new(self.planned_experiments()?, self.config.progress_bar)?;
+
+        let outcome = match self.config.batch {
+            DAGBatch::NeonParallel => self.run_neon_parallel_batch(),
+            DAGBatch::CoreAffinity => self.run_core_affinity_batch(),
+            DAGBatch::ScaleThresholds => self.run_scale_thresholds_batch(),
+            DAGBatch::Efficiency => self.run_efficiency_batch(),
+            DAGBatch::Crossover => self.run_crossover_batch(),
+            DAGBatch::LongReads => self.run_long_reads_batch(),
+        };
+
+        self.progress.finish();
+
+        let all_results = match outcome {
+            Ok(results) => results,
+            Err(e) if is_interrupted(&e) => {
+                // Everything measured so far, minus baselines filtered out by --configs
+                let completed: Vec<ExperimentResult> = self
+                    .measured
+                    .iter()
+                    .filter(|(node, _)| self.is_selected(node))
+                    .map(|(_, result)| result.clone())
+                    .collect();
+
+                println!();
+                println!("⏸️  DAG Traversal Interrupted");
+                println!("   Completed experiments: {}", completed.len());
+                println!(
+                    "   Interrupted during: {}",
+                    self.interrupted_operation.as_deref().unwrap_or("(unknown)")
+                );
+                return Ok(completed);
+            }
+            Err(e) => return Err(e),
         };
 
         println!();
         println!("✅ DAG Traversal Complete");
         println!("   Total experiments: {}", all_results.len());
         println!("   Pruned configs: {}", self.pruned_nodes.len());
+        if self.config.batch == DAGBatch::NeonParallel {
+            let exhaustive = self.planned_experiments()? as usize * self.config.repetitions;
+            let strategy =
+                if self.config.full_factorial { "no" } else { self.config.pruning.name() };
+            println!(
+                "   Timed runs: {} (exhaustive: {}, {:.0}% saved, {} pruning)",
+                self.timed_runs,
+                exhaustive,
+                100.0 * (1.0 - self.timed_runs as f64 / exhaustive.max(1) as f64),
+                strategy
+            );
+        }
 
         Ok(all_results)
     }
 
     /// Run NEON+Parallel batch (240 experiments)
-    /// Tests: naive, NEON, NEON+2t, NEON+4t for all 20 operations × 3 scales
+    /// Tests: naive, NEON, NEON+2t, NEON+4t for all 20 operations × 3 scales,
+    /// each parallel config with both per-record and chunked work splitting
     fn run_neon_parallel_batch(&mut self) -> Result<Vec<ExperimentResult>> {
         let mut results = Vec::new();
-        let strategy = PruningStrategy::new(
-            self.config.pruning_threshold,
-            self.config.diminishing_returns_threshold,
-        );
+        let strategy: Box<dyn PruningStrategy> = if self.config.full_factorial {
+            Box::new(Exhaustive)
+        } else {
+            self.pruning_strategy()
+        };
 
-        println!("📊 Batch: NEON+Parallel Composition");
-        println!("   Goal: Validate NEON × Parallel = multiplicative for all 20 operations");
-        println!();
+        self.progress.println("📊 Batch: NEON+Parallel Composition");
+        self.progress.println("   Goal: Validate NEON × Parallel = multiplicative for all 20 operations");
+        self.progress.println(format!("   Pruning: {}", strategy.name()));
+        self.progress.println("");
 
         // Clone operations to avoid borrow checker issues
         let operations = self.config.operations.clone();
         let scales = self.config.scales.clone();
 
+        let (nodes, candidates) = self.neon_parallel_candidates();
+
         for operation in &operations {
-            println!("🔬 Testing operation: {}", operation);
+            self.progress.println(format!("🔬 Testing operation: {}", operation));
 
             for scale in &scales {
-                println!("  📏 Scale: {} ({} sequences)", scale.name, scale.num_sequences);
+                self.progress.println(format!("  📏 Scale: {} ({} sequences)", scale.name, scale.num_sequences));
 
-                // Phase 1: Test baseline
+                // Phase 1: Test baseline (always run; speedups are relative to it)
                 let naive_node = DAGNode::naive();
                 let naive_result = self.run_experiment(operation, &naive_node, scale)?;
-                results.push(naive_result.clone());
+                if self.is_selected(&naive_node) {
+                    results.push(naive_result.clone());
+                }
+
+                // Too large for memory: every config shares the dataset
+                let checked = self.memory_checks.get(&(operation.clone(), scale.name.to_string()));
+                if let Some(&Some(insufficient)) = checked {
+                    for (node, candidate) in nodes.iter().zip(&candidates) {
+                        if candidate.selected {
+                            self.progress.skip(1);
+                            results.push(self.create_skipped_result(operation, node, scale, &insufficient));
+                        }
+                    }
+                    continue;
+                }
 
                 // Store baseline for speedup calculations
                 self.naive_baselines.insert(
@@ -622,63 +1244,137 @@ impl DAGTraversal {
                     naive_result.throughput_median,
                 );
 
-                // Phase 2: Test NEON
-                let neon_node = DAGNode::neon();
-                let neon_result = self.run_experiment_with_baseline(
+                // Phase 2: NEON and NEON+Parallel compositions, as the
+                // pruning strategy sees fit
+                let repetitions = self.config.repetitions;
+                let mut cell = PruningCell::new(
+                    self,
                     operation,
-                    &neon_node,
                     scale,
                     naive_result.throughput_median,
-                )?;
-                results.push(neon_result.clone());
-
-                // Check if NEON should be pruned
-                if strategy.should_prune_alternative(&neon_result) {
-                    println!("    ❌ NEON pruned ({:.2}× < {}×)",
-                             neon_result.speedup_median,
-                             strategy.speedup_threshold);
-                    self.pruned_nodes.insert((operation.clone(), neon_node));
-                    continue;
-                }
+                    nodes.clone(),
+                );
+                strategy.explore(&candidates, repetitions, &mut cell)?;
+                results.extend(cell.into_results());
+            }
+
+            self.progress.println("");
+        }
+
+        Ok(results)
+    }
 
-                println!("    ✅ NEON kept ({:.2}×)", neon_result.speedup_median);
+    /// Configured pruning strategy of the NEON+Parallel batch
+    fn pruning_strategy(&self) -> Box<dyn PruningStrategy> {
+        self.config.pruning.strategy(
+            self.config.pruning_threshold,
+            self.config.diminishing_returns_threshold,
+        )
+    }
 
-                // Phase 3: Test NEON+Parallel compositions
-                let mut parent_speedup = neon_result.speedup_median;
+    /// NEON, then per-record and chunked NEON+{2,4}t, as pruning candidates
+    fn neon_parallel_candidates(&self) -> (Vec<DAGNode>, Vec<Candidate>) {
+        let mut nodes = vec![(DAGNode::neon(), Role::Alternative)];
+        for threads in [2, 4] {
+            nodes.push((DAGNode::neon_parallel(threads), Role::Composition));
+            nodes.push((DAGNode::neon_chunked(threads), Role::Variant));
+        }
+        let candidates = nodes
+            .iter()
+            .map(|(node, role)| Candidate {
+                key: node.config_key(),
+                threads: node.threads,
+                role: *role,
+                selected: self.is_selected(node),
+            })
+            .collect();
+        (nodes.into_iter().map(|(node, _)| node).collect(), candidates)
+    }
 
-                for threads in &[2, 4] {
-                    let parallel_node = DAGNode::neon_parallel(*threads);
-                    let parallel_result = self.run_experiment_with_baseline(
-                        operation,
-                        &parallel_node,
-                        scale,
-                        naive_result.throughput_median,
-                    )?;
-                    results.push(parallel_result.clone());
-
-                    // Check for diminishing returns
-                    if strategy.should_prune_composition(&parallel_result, parent_speedup) {
-                        println!("    ❌ NEON+{}t pruned (additional benefit {:.2}× < {}×)",
-                                 threads,
-                                 parallel_result.speedup_median / parent_speedup,
-                                 strategy.diminishing_returns_threshold);
-                        self.pruned_nodes.insert((operation.clone(), parallel_node));
-                        break; // Don't test higher thread counts
-                    }
+    /// Replay the configured pruning strategy against full-factorial results
+    ///
+    /// One check per (operation, scale) whose selected configs all completed;
+    /// pruning decisions carry across an operation's scales as in a real run.
+    pub fn validate_pruning(&self, results: &[ExperimentResult]) -> Result<Vec<PruningCheck>> {
+        let strategy = self.pruning_strategy();
+        let (nodes, candidates) = self.neon_parallel_candidates();
+        let mut checks = Vec::new();
+
+        for operation in &self.config.operations {
+            let mut pruned = vec![false; nodes.len()];
+            for scale in &self.config.scales {
+                let measured: Vec<Option<&ExperimentResult>> = nodes
+                    .iter()
+                    .map(|node| {
+                        results.iter().find(|r| {
+                            !r.pruned
+                                && &r.operation == operation
+                                && r.scale == scale.name
+                                && r.config_name == node.name()
+                                && r.affinity == node.affinity.name()
+                        })
+                    })
+                    .collect();
+                let complete = candidates
+                    .iter()
+                    .zip(&measured)
+                    .all(|(candidate, result)| !candidate.selected || result.is_some());
+                if !complete {
+                    continue;
+                }
 
-                    println!("    ✅ NEON+{}t kept ({:.2}×, additional {:.2}×)",
-                             threads,
-                             parallel_result.speedup_median,
-                             parallel_result.speedup_median / parent_speedup);
+                let estimates: Vec<Option<Estimate>> = measured
+                    .iter()
+                    .map(|result| {
+                        result.map(|r| Estimate {
+                            speedup_median: r.speedup_median,
+                            speedup_mean: r.speedup_mean,
+                            speedup_std_dev: r.speedup_std_dev,
+                            n: r.n_valid,
+                        })
+                    })
+                    .collect();
+                let kept = pruning::replay(
+                    &*strategy,
+                    &candidates,
+                    self.config.repetitions,
+                    &estimates,
+                    &mut pruned,
+                )?;
 
-                    parent_speedup = parallel_result.speedup_median;
+                // Naive (1×) competes too: it always runs
+                let mut optimal = ("naive".to_string(), 1.0);
+                let mut best_kept = optimal.clone();
+                let mut skipped = Vec::new();
+                for (i, candidate) in candidates.iter().enumerate() {
+                    let Some(estimate) = estimates[i] else {
+                        continue;
+                    };
+                    let speedup = estimate.speedup_median;
+                    if speedup > optimal.1 {
+                        optimal = (candidate.key.clone(), speedup);
+                    }
+                    if kept[i] {
+                        if speedup > best_kept.1 {
+                            best_kept = (candidate.key.clone(), speedup);
+                        }
+                    } else {
+                        skipped.push(candidate.key.clone());
+                    }
                 }
-            }
 
-            println!();
+                checks.push(PruningCheck {
+                    operation: operation.clone(),
+                    scale: scale.name.to_string(),
+                    optimal: optimal.0,
+                    optimal_speedup: optimal.1,
+                    best_kept: best_kept.0,
+                    best_kept_speedup: best_kept.1,
+                    skipped,
+                });
+            }
         }
-
-        Ok(results)
+        Ok(checks)
     }
 
     /// Run Core Affinity batch (180 experiments)
@@ -686,9 +1382,9 @@ impl DAGTraversal {
     fn run_core_affinity_batch(&mut self) -> Result<Vec<ExperimentResult>> {
         let mut results = Vec::new();
 
-        println!("📊 Batch: Core Affinity × NEON");
-        println!("   Goal: Test if E-cores remain competitive with NEON");
-        println!();
+        self.progress.println("📊 Batch: Core Affinity × NEON");
+        self.progress.println("   Goal: Test if E-cores remain competitive with NEON");
+        self.progress.println("");
 
         // Clone to avoid borrow checker issues
         let operations = self.config.operations.clone();
@@ -696,10 +1392,10 @@ impl DAGTraversal {
 
         // For this batch, we test NEON on different core types
         for operation in &operations {
-            println!("🔬 Testing operation: {}", operation);
+            self.progress.println(format!("🔬 Testing operation: {}", operation));
 
             for scale in &scales {
-                println!("  📏 Scale: {} ({} sequences)", scale.name, scale.num_sequences);
+                self.progress.println(format!("  📏 Scale: {} ({} sequences)", scale.name, scale.num_sequences));
 
                 // Get or establish naive baseline
                 let baseline = self.get_or_establish_baseline(operation, scale)?;
@@ -707,60 +1403,315 @@ impl DAGTraversal {
                 // Test NEON on different core affinities (single-threaded for this batch)
                 for affinity in &[CoreAffinity::Default, CoreAffinity::PerformanceCores, CoreAffinity::EfficiencyCores] {
                     let node = DAGNode::neon().with_affinity(*affinity);
+                    if !self.is_selected(&node) {
+                        continue;
+                    }
+                    let result = self.run_experiment_with_baseline(operation, &node, scale, baseline)?;
+                    results.push(result);
+                }
+            }
+
+            self.progress.println("");
+        }
+
+        Ok(results)
+    }
+
+    /// Run Scale Thresholds batch (320 experiments)
+    /// Tests precise scale thresholds for optimal config selection
+    fn run_scale_thresholds_batch(&mut self) -> Result<Vec<ExperimentResult>> {
+        let mut results = Vec::new();
+
+        self.progress.println("📊 Batch: Precise Scale Thresholds");
+        self.progress.println("   Goal: Determine exact threshold where configs become optimal");
+        self.progress.println("");
+
+        // Clone to avoid borrow checker issues
+        let operations = self.config.operations.clone();
+
+        // Use more granular scales for this batch (Tiny → Large unless overridden)
+        let fine_scales = self.config.scales.clone();
+
+        for operation in &operations {
+            self.progress.println(format!("🔬 Testing operation: {}", operation));
+
+            for scale in &fine_scales {
+                self.progress.println(format!("  📏 Scale: {} ({} sequences)", scale.name, scale.num_sequences));
+
+                let baseline = self.get_or_establish_baseline(operation, scale)?;
+
+                // Test key configs across scales
+                let configs = vec![
+                    DAGNode::naive(),
+                    DAGNode::neon(),
+                    DAGNode::neon_parallel(2),
+                    DAGNode::neon_parallel(4),
+                    DAGNode::neon_chunked(2),
+                    DAGNode::neon_chunked(4),
+                ];
+
+                for node in configs {
+                    if !self.is_selected(&node) {
+                        continue;
+                    }
                     let result = self.run_experiment_with_baseline(operation, &node, scale, baseline)?;
                     results.push(result);
                 }
             }
 
-            println!();
+            self.progress.println("");
+        }
+
+        Ok(results)
+    }
+
+    /// Run Efficiency batch
+    /// Tests NEON on all P-cores, all E-cores, and all cores, recording joules
+    fn run_efficiency_batch(&mut self) -> Result<Vec<ExperimentResult>> {
+        let mut results = Vec::new();
+
+        self.progress.println("📊 Batch: Energy Efficiency (P-cores vs E-cores)");
+        self.progress.println("   Goal: Find which cores process the most sequences per joule");
+        self.progress.println("");
+
+        let operations = self.config.operations.clone();
+        let scales = self.config.scales.clone();
+        let nodes = self.efficiency_nodes()?;
+
+        for operation in &operations {
+            self.progress.println(format!("🔬 Testing operation: {}", operation));
+
+            for scale in &scales {
+                self.progress.println(format!("  📏 Scale: {} ({} sequences)", scale.name, scale.num_sequences));
+
+                // The baseline is reported too: its seqs/J is the reference
+                let naive_node = DAGNode::naive();
+                let naive_result = self.run_experiment(operation, &naive_node, scale)?;
+                self.naive_baselines.insert(
+                    (operation.clone(), scale.name.to_string()),
+                    naive_result.throughput_median,
+                );
+                if self.is_selected(&naive_node) {
+                    results.push(naive_result.clone());
+                }
+
+                let mut measured = vec![naive_result.clone()];
+                for node in &nodes {
+                    if !self.is_selected(node) {
+                        continue;
+                    }
+                    let result = self.run_experiment_with_baseline(
+                        operation,
+                        node,
+                        scale,
+                        naive_result.throughput_median,
+                    )?;
+                    measured.push(result.clone());
+                    results.push(result);
+                }
+
+                for result in &measured {
+                    self.progress.println(format!(
+                        "    ⚡ {:<16} {:>12.0} seqs/J ({:.2}× speedup)",
+                        result.config_name,
+                        result.seqs_per_joule.unwrap_or(0.0),
+                        result.speedup_median
+                    ));
+                }
+                if let Some(best) = measured
+                    .iter()
+                    .max_by(|a, b| {
+                        a.seqs_per_joule
+                            .partial_cmp(&b.seqs_per_joule)
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                    })
+                {
+                    self.progress.println(format!(
+                        "    ✅ Most efficient: {} ({})",
+                        best.config_name, best.affinity
+                    ));
+                }
+            }
+
+            self.progress.println("");
+        }
+
+        Ok(results)
+    }
+
+    /// Run Long-read batch
+    /// Compares record-level parallelism (per-record and chunked tasks) with
+    /// intra-read segments at each thread count. Nothing is pruned: the
+    /// point is the comparison, including where intra-read loses.
+    fn run_long_reads_batch(&mut self) -> Result<Vec<ExperimentResult>> {
+        let mut results = Vec::new();
+
+        self.progress.println("📊 Batch: Long-Read Parallelism (record-level vs intra-read)");
+        self.progress.println("   Goal: Find where splitting individual reads beats splitting the read set");
+        self.progress.println("");
+
+        let operations = self.config.operations.clone();
+        let scales = self.config.scales.clone();
+
+        for operation in &operations {
+            self.progress.println(format!("🔬 Testing operation: {}", operation));
+
+            for scale in &scales {
+                self.progress.println(format!(
+                    "  📏 Scale: {} ({} sequences, {} reads)",
+                    scale.name,
+                    scale.num_sequences,
+                    scale.length_class.name()
+                ));
+
+                let naive_node = DAGNode::naive();
+                let naive_result = self.run_experiment(operation, &naive_node, scale)?;
+                self.naive_baselines.insert(
+                    (operation.clone(), scale.name.to_string()),
+                    naive_result.throughput_median,
+                );
+                if self.is_selected(&naive_node) {
+                    results.push(naive_result.clone());
+                }
+
+                let mut measured = HashMap::new();
+                for node in std::iter::once(DAGNode::neon()).chain(Self::long_read_nodes()) {
+                    if !self.is_selected(&node) {
+                        continue;
+                    }
+                    let result = self.run_experiment_with_baseline(
+                        operation,
+                        &node,
+                        scale,
+                        naive_result.throughput_median,
+                    )?;
+                    measured.insert(node, result.speedup_median);
+                    results.push(result);
+                }
+
+                for &threads in LONG_READ_THREADS {
+                    let speedup = |node: DAGNode| {
+                        measured
+                            .get(&node)
+                            .map(|s| format!("{:.2}×", s))
+                            .unwrap_or_else(|| "-".to_string())
+                    };
+                    self.progress.println(format!(
+                        "    🧵 NEON+{}t intra-read {} vs per-record {} vs chunked {}",
+                        threads,
+                        speedup(DAGNode::neon_intra_read(threads)),
+                        speedup(DAGNode::neon_parallel(threads)),
+                        speedup(DAGNode::neon_chunked(threads))
+                    ));
+                }
+            }
+
+            self.progress.println("");
         }
 
         Ok(results)
     }
 
-    /// Run Scale Thresholds batch (320 experiments)
-    /// Tests precise scale thresholds for optimal config selection
-    fn run_scale_thresholds_batch(&mut self) -> Result<Vec<ExperimentResult>> {
+    /// Run Crossover batch
+    /// Binary-searches each dataset's record count for where the candidate
+    /// first beats the incumbent by the required gain. Probes run on prefixes
+    /// of the dataset; their speedup columns are relative to the incumbent.
+    fn run_crossover_batch(&mut self) -> Result<Vec<ExperimentResult>> {
         let mut results = Vec::new();
+        let settings = self.config.crossover.clone();
 
-        println!("📊 Batch: Precise Scale Thresholds");
-        println!("   Goal: Determine exact threshold where configs become optimal");
-        println!();
+        self.progress.println("📊 Batch: Crossover Search");
+        self.progress.println(format!(
+            "   Goal: Smallest input where {} beats {} by ≥{:.0}%",
+            settings.candidate.config_key(),
+            settings.incumbent.config_key(),
+            settings.min_gain * 100.0
+        ));
+        self.progress.println("");
 
-        // Clone to avoid borrow checker issues
         let operations = self.config.operations.clone();
-
-        // Use more granular scales for this batch
-        let fine_scales = vec![
-            Scale { name: "Tiny", path: "datasets/tiny_100_150bp.fq", num_sequences: 100 },
-            Scale { name: "Small", path: "datasets/small_1000_150bp.fq", num_sequences: 1_000 },
-            Scale { name: "Medium", path: "datasets/medium_10000_150bp.fq", num_sequences: 10_000 },
-            Scale { name: "Large", path: "datasets/large_100000_150bp.fq", num_sequences: 100_000 },
-        ];
+        let scales = self.config.scales.clone();
 
         for operation in &operations {
-            println!("🔬 Testing operation: {}", operation);
-
-            for scale in &fine_scales {
-                println!("  📏 Scale: {} ({} sequences)", scale.name, scale.num_sequences);
-
-                let baseline = self.get_or_establish_baseline(operation, scale)?;
-
-                // Test key configs across scales
-                let configs = vec![
-                    DAGNode::naive(),
-                    DAGNode::neon(),
-                    DAGNode::neon_parallel(2),
-                    DAGNode::neon_parallel(4),
-                ];
+            self.progress.println(format!("🔬 Testing operation: {}", operation));
 
-                for node in configs {
-                    let result = self.run_experiment_with_baseline(operation, &node, scale, baseline)?;
-                    results.push(result);
+            for scale in &scales {
+                if self.check_memory(operation, scale)?.is_some() {
+                    let probes = self.crossover_search(scale.num_sequences).max_probes();
+                    self.progress.skip(2 * probes as u64);
+                    continue;
                 }
+                let sequences = self.load_scale(scale)?;
+                self.progress.println(format!("  📏 Dataset: {} ({} sequences)", scale.name, sequences.len()));
+
+                let search = self.crossover_search(sequences.len());
+                let crossover = find_crossover(&search, |n| {
+                    let prefix = Scale {
+                        name: Cow::Owned(format!("{}:{}", scale.name, n)),
+                        path: scale.path.clone(),
+                        num_sequences: n,
+                        seed: None,
+                        length_class: scale.length_class,
+                    };
+                    let incumbent = self.measure_experiment(
+                        operation,
+                        &settings.incumbent,
+                        &prefix,
+                        &sequences[..n],
+                        None,
+                    )?;
+                    let candidate = self.measure_experiment(
+                        operation,
+                        &settings.candidate,
+                        &prefix,
+                        &sequences[..n],
+                        Some(incumbent.throughput_median),
+                    )?;
+                    self.progress.println(format!(
+                        "    🔎 {:>9} records: {:.2}× (95% CI {:.2}–{:.2})",
+                        n, candidate.speedup_median, candidate.speedup_ci_lower, candidate.speedup_ci_upper
+                    ));
+                    let ratio = RatioEstimate {
+                        median: candidate.speedup_median,
+                        ci_lower: candidate.speedup_ci_lower,
+                        ci_upper: candidate.speedup_ci_upper,
+                    };
+                    results.push(incumbent);
+                    results.push(candidate);
+                    Ok(ratio)
+                })?;
+
+                // The overall bar assumed the longest possible search
+                let unused = search.max_probes().saturating_sub(crossover.probes.len());
+                self.progress.skip(2 * unused as u64);
+
+                let bound = |n: Option<usize>| n.map(|n| n.to_string()).unwrap_or_else(|| "?".to_string());
+                match crossover.crossover {
+                    None => self.progress.println(format!(
+                        "    ❌ No crossover up to {} records",
+                        search.max_records
+                    )),
+                    Some(_) if crossover.wins_everywhere(&search) => self.progress.println(format!(
+                        "    ✅ Candidate wins from the smallest size tested ({} records)",
+                        search.min_records
+                    )),
+                    Some(point) => self.progress.println(format!(
+                        "    ✅ Crossover at ~{} records (95% CI {}–{})",
+                        point,
+                        bound(crossover.ci_lower),
+                        bound(crossover.ci_upper)
+                    )),
+                }
+
+                self.crossovers.push(CrossoverSummary {
+                    operation: operation.clone(),
+                    scale: scale.name.to_string(),
+                    search,
+                    crossover,
+                });
             }
 
-            println!();
+            self.progress.println("");
         }
 
         Ok(results)
@@ -820,34 +1771,154 @@ impl DAGTraversal {
         // Check if pruned
         if self.pruned_nodes.contains(&(operation.to_string(), node.clone())) {
             // Return a "pruned" result with zero statistics
+            self.progress.skip(1);
             return Ok(self.create_pruned_result(operation, node, scale));
         }
 
-        // Load sequences ONCE
-        let sequences = load_sequences(scale.path)
-            .with_context(|| format!("Failed to load dataset: {}", scale.path))?;
+        // Skip (rather than be killed by) experiments that would not fit
+        if let Some(insufficient) = self.check_memory(operation, scale)? {
+            self.progress.skip(1);
+            return Ok(self.create_skipped_result(operation, node, scale, &insufficient));
+        }
+
+        let sequences = self.load_scale(scale)?;
+        self.measure_experiment(operation, node, scale, &sequences, baseline_throughput)
+    }
+
+    /// Estimate an experiment's memory before loading its dataset
+    ///
+    /// `Some` if the estimate, plus the other datasets already cached,
+    /// exceeds the memory limit. Checked once per (operation, scale); the
+    /// warning is printed the first time.
+    fn check_memory(&mut self, operation: &str, scale: &Scale) -> Result<Option<InsufficientMemory>> {
+        let Some(limit_bytes) = self.config.memory_limit else {
+            return Ok(None);
+        };
+        let key = (operation.to_string(), scale.name.to_string());
+        if let Some(checked) = self.memory_checks.get(&key) {
+            return Ok(*checked);
+        }
+
+        let mean_length = asbb_datagen::length_preset(scale.length_class).0 as f64;
+        let estimate = create_operation(operation)?.estimate_memory(
+            scale.num_sequences,
+            mean_length,
+            Encoding::Ascii,
+        );
+        let resident: u64 = self
+            .loaded_bytes
+            .iter()
+            .filter(|(path, _)| **path != scale.path)
+            .map(|(_, bytes)| bytes)
+            .sum();
+        let checked = MemoryLimit::new(limit_bytes).check(&estimate, resident).err();
+        if let Some(insufficient) = &checked {
+            self.progress.println(format!(
+                "    ⚠️  Skipping {} at {}: {}",
+                operation, scale.name, insufficient
+            ));
+        }
+        self.memory_checks.insert(key, checked);
+        Ok(checked)
+    }
+
+    /// Load a scale's sequences ONCE per run (generating the dataset on first
+    /// use if missing, and verifying its hash against the manifest)
+    ///
+    /// Every config and operation after the first shares the cached records.
+    fn load_scale(&mut self, scale: &Scale) -> Result<Arc<Vec<SequenceRecord>>> {
+        scale.ensure_exists()?;
+        if self.verified_datasets.insert(scale.path.to_string()) {
+            scale.verify()?;
+        }
+        let cached = self.datasets.records(&scale.path, || {
+            load_sequences(&scale.path)
+                .with_context(|| format!("Failed to load dataset: {}", scale.path))
+        })?;
+        let mean_length = asbb_datagen::length_preset(scale.length_class).0 as f64;
+        self.loaded_bytes.entry(scale.path.to_string()).or_insert_with(|| {
+            MemoryEstimate::input(cached.value.len(), mean_length, Encoding::Ascii).input_bytes
+        });
+        Ok(cached.value)
+    }
+
+    /// Warmup, timed repetitions and statistics for one experiment on `sequences`
+    fn measure_experiment(
+        &mut self,
+        operation: &str,
+        node: &DAGNode,
+        scale: &Scale,
+        sequences: &[SequenceRecord],
+        baseline_throughput: Option<f64>,
+    ) -> Result<ExperimentResult> {
+        let repetitions = self.config.repetitions;
+        let mut elapsed_times = Vec::with_capacity(repetitions);
+        self.measure_runs(
+            operation,
+            node,
+            scale,
+            sequences,
+            baseline_throughput,
+            repetitions,
+            &mut elapsed_times,
+        )
+    }
+
+    /// Warmup and `repetitions` more timed runs appended to `elapsed_times`;
+    /// the statistics cover every run in it (energy only the new ones)
+    #[allow(clippy::too_many_arguments)]
+    fn measure_runs(
+        &mut self,
+        operation: &str,
+        node: &DAGNode,
+        scale: &Scale,
+        sequences: &[SequenceRecord],
+        baseline_throughput: Option<f64>,
+        repetitions: usize,
+        elapsed_times: &mut Vec<f64>,
+    ) -> Result<ExperimentResult> {
+        let key = (operation.to_string(), node.clone(), scale.name.to_string());
+        let remeasured = !elapsed_times.is_empty();
+        self.check_interrupt(operation, false)?;
 
         // Load operation ONCE
         let op_instance = create_operation(operation)?;
 
+        self.progress.start_experiment(operation, scale, node, self.config.warmup_runs + repetitions);
+
         // === WARMUP PHASE ===
         for _ in 0..self.config.warmup_runs {
-            let _output = execute_operation(&*op_instance, &sequences, node)?;
+            self.check_interrupt(operation, true)?;
+            let _output = execute_operation(&*op_instance, sequences, node)?;
+            self.progress.finish_run();
         }
 
         // === MEASUREMENT PHASE ===
-        let mut elapsed_times = Vec::with_capacity(self.config.repetitions);
-
-        for _ in 0..self.config.repetitions {
+        // (one energy window spans all repetitions; a single run is usually
+        // shorter than the meter's sampling interval)
+        if let Some(meter) = self.energy_meter.as_mut() {
+            meter.start()?;
+        }
+        for _ in 0..repetitions {
+            self.check_interrupt(operation, true)?;
             let start = Instant::now();
-            let _output = execute_operation(&*op_instance, &sequences, node)?;
+            let _output = execute_operation(&*op_instance, sequences, node)?;
             let elapsed = start.elapsed();
             elapsed_times.push(elapsed.as_secs_f64());
+            self.timed_runs += 1;
+            self.progress.finish_run();
         }
+        let energy_joules = match self.energy_meter.as_mut() {
+            Some(meter) => Some(meter.stop()? / repetitions.max(1) as f64),
+            None => None,
+        };
+        let seqs_per_joule = energy_joules
+            .filter(|&joules| joules > 0.0)
+            .map(|joules| scale.num_sequences as f64 / joules);
 
         // === STATISTICAL ANALYSIS ===
         let elapsed_stats = calculate_statistics(
-            &elapsed_times,
+            elapsed_times,
             self.config.outlier_threshold,
             self.config.warmup_runs,
         )?;
@@ -888,7 +1959,10 @@ impl DAGTraversal {
             affinity: node.affinity.name().to_string(),
             scale: scale.name.to_string(),
             num_sequences: scale.num_sequences,
+            length_class: scale.length_class.name().to_string(),
             pruned: false,
+            skip_reason: None,
+            estimated_memory_bytes: None,
 
             // Throughput statistics
             throughput_median: throughput_stats.median,
@@ -918,10 +1992,26 @@ impl DAGTraversal {
             n_valid: elapsed_stats.n_valid,
             n_outliers: elapsed_stats.n_outliers,
             n_warmup: elapsed_stats.n_warmup,
+
+            // Energy
+            energy_joules,
+            seqs_per_joule,
         };
 
-        // Cache result
+        self.progress.finish_experiment(result.throughput_median);
+
+        // Cache result (a re-measurement replaces the earlier one)
         self.tested_nodes.insert(key, result.clone());
+        let earlier = self.measured.iter_mut().rev().find(|(measured, earlier)| {
+            remeasured
+                && measured == node
+                && earlier.operation == operation
+                && earlier.scale == scale.name
+        });
+        match earlier {
+            Some(entry) => entry.1 = result.clone(),
+            None => self.measured.push((node.clone(), result.clone())),
+        }
 
         Ok(result)
     }
@@ -941,7 +2031,10 @@ impl DAGTraversal {
             affinity: node.affinity.name().to_string(),
             scale: scale.name.to_string(),
             num_sequences: scale.num_sequences,
+            length_class: scale.length_class.name().to_string(),
             pruned: true,
+            skip_reason: None,
+            estimated_memory_bytes: None,
             throughput_median: 0.0,
             throughput_mean: 0.0,
             throughput_std_dev: 0.0,
@@ -963,33 +2056,58 @@ impl DAGTraversal {
             n_valid: 0,
             n_outliers: 0,
             n_warmup: 0,
+            energy_joules: None,
+            seqs_per_joule: None,
+        }
+    }
+
+    /// Create a result for an experiment skipped for lack of memory
+    fn create_skipped_result(
+        &self,
+        operation: &str,
+        node: &DAGNode,
+        scale: &Scale,
+        insufficient: &InsufficientMemory,
+    ) -> ExperimentResult {
+        ExperimentResult {
+            skip_reason: Some(InsufficientMemory::REASON.to_string()),
+            estimated_memory_bytes: Some(insufficient.estimated_bytes),
+            ..self.create_pruned_result(operation, node, scale)
         }
     }
 }
 
 // ============================================================================
-// Operation Loading
+// Operation Execution
 // ============================================================================
 
-/// Create an operation instance by name
-fn create_operation(name: &str) -> Result<Box<dyn PrimitiveOperation>> {
-    match name {
-        "base_counting" => Ok(Box::new(BaseCounting::new())),
-        "gc_content" => Ok(Box::new(GcContent::new())),
-        "at_content" => Ok(Box::new(ATContent)),
-        "n_content" => Ok(Box::new(NContent)),
-        "reverse_complement" => Ok(Box::new(ReverseComplement::new())),
-        "sequence_length" => Ok(Box::new(SequenceLength)),
-        "quality_aggregation" => Ok(Box::new(QualityAggregation::new())),
-        "quality_filter" => Ok(Box::new(QualityFilter::new(20))),
-        "length_filter" => Ok(Box::new(LengthFilter::new(50))),
-        "complexity_score" => Ok(Box::new(ComplexityScore::new())),
-        _ => anyhow::bail!("Unknown operation: {}", name),
+/// Execute operation with specific configuration
+///
+/// Non-default affinities run on a pool with the matching QoS class (the
+/// single-threaded configs on a one-thread pool).
+fn execute_operation(
+    op: &dyn PrimitiveOperation,
+    sequences: &[SequenceRecord],
+    node: &DAGNode,
+) -> Result<OperationOutput> {
+    if node.affinity != CoreAffinity::Default {
+        let pool = thread_pool::pool_for(PoolKey::new(node.threads, node.affinity.qos()))?;
+        return if node.threads > 1 {
+            thread_pool::with_pool(pool, || execute_node(op, sequences, node))
+        } else {
+            pool.install(|| execute_node(op, sequences, node))
+        };
     }
+    execute_node(op, sequences, node)
 }
 
-/// Execute operation with specific configuration
-fn execute_operation(
+/// Whether an operation implements intra-read parallel execution
+fn supports_intra_read(name: &str) -> Result<bool> {
+    Ok(create_operation(name)?.execute_parallel_intra_read(&[], 1).is_ok())
+}
+
+/// Dispatch on the node's config type and thread count
+fn execute_node(
     op: &dyn PrimitiveOperation,
     sequences: &[SequenceRecord],
     node: &DAGNode,
@@ -998,6 +2116,8 @@ fn execute_operation(
         (ConfigType::Naive, 1) => op.execute_naive(sequences),
         (ConfigType::Neon, 1) => op.execute_neon(sequences),
         (ConfigType::Neon, threads) => op.execute_parallel(sequences, threads),
+        (ConfigType::NeonChunked, threads) => op.execute_parallel_chunked(sequences, threads),
+        (ConfigType::NeonIntraRead, threads) => op.execute_parallel_intra_read(sequences, threads),
         (ConfigType::Gpu, _) => {
             anyhow::bail!("GPU execution not supported in this harness (use separate GPU pilot)")
         }
@@ -1012,6 +2132,25 @@ fn execute_operation(
 // Data Loading
 // ============================================================================
 
+/// Write the reproducibility manifest next to the results CSV
+///
+/// Records each scale's generation seed and the hash of every dataset that
+/// was used (datasets are generated on first use, so all exist by now).
+fn write_manifest(config: &DAGConfig) -> Result<()> {
+    let mut manifest = RunManifest::capture(&config.output_path)?;
+    for scale in &config.scales {
+        if let Some(seed) = scale.seed {
+            manifest = manifest.with_seed(scale.name.to_string(), seed);
+        }
+        if Path::new(scale.path.as_ref()).exists() {
+            manifest = manifest.with_dataset(scale.path.as_ref())?;
+        }
+    }
+    let path = manifest.save_alongside_results()?;
+    println!("🧾 Run manifest: {} (repeat with `asbb rerun {}`)", path.display(), path.display());
+    Ok(())
+}
+
 /// Load sequences from FASTQ file
 fn load_sequences(path: &str) -> Result<Vec<SequenceRecord>> {
     let file = File::open(path)
@@ -1061,22 +2200,22 @@ pub fn write_results_csv(results: &[ExperimentResult], path: &Path) -> Result<()
     // Write header with all statistical columns
     writeln!(
         file,
-        "operation,config_name,config_type,threads,affinity,scale,num_sequences,pruned,\
+        "operation,config_name,config_type,threads,affinity,scale,num_sequences,length_class,pruned,\
         throughput_median,throughput_mean,throughput_std_dev,throughput_ci_lower,throughput_ci_upper,\
         speedup_median,speedup_mean,speedup_std_dev,speedup_ci_lower,speedup_ci_upper,\
         elapsed_median,elapsed_mean,elapsed_std_dev,elapsed_min,elapsed_max,elapsed_q1,elapsed_q3,elapsed_iqr,\
-        n_valid,n_outliers,n_warmup"
+        n_valid,n_outliers,n_warmup,energy_joules,seqs_per_joule,skip_reason,estimated_memory_bytes"
     )?;
 
     // Write data rows with all statistics
     for result in results {
         writeln!(
             file,
-            "{},{},{:?},{},{},{},{},{},\
+            "{},{},{:?},{},{},{},{},{},{},\
             {:.2},{:.2},{:.2},{:.2},{:.2},\
             {:.4},{:.4},{:.4},{:.4},{:.4},\
             {:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},\
-            {},{},{}",
+            {},{},{},{},{},{},{}",
             // Metadata
             result.operation,
             result.config_name,
@@ -1085,6 +2224,7 @@ pub fn write_results_csv(results: &[ExperimentResult], path: &Path) -> Result<()
             result.affinity,
             result.scale,
             result.num_sequences,
+            result.length_class,
             result.pruned,
             // Throughput statistics (seq/sec - 2 decimal places)
             result.throughput_median,
@@ -1111,6 +2251,12 @@ pub fn write_results_csv(results: &[ExperimentResult], path: &Path) -> Result<()
             result.n_valid,
             result.n_outliers,
             result.n_warmup,
+            // Energy (empty unless measured)
+            result.energy_joules.map(|j| format!("{:.6}", j)).unwrap_or_default(),
+            result.seqs_per_joule.map(|s| format!("{:.2}", s)).unwrap_or_default(),
+            // Skipped experiments (empty unless skipped)
+            result.skip_reason.as_deref().unwrap_or_default(),
+            result.estimated_memory_bytes.map(|b| b.to_string()).unwrap_or_default(),
         )?;
     }
 
@@ -1124,6 +2270,194 @@ pub fn write_results_csv(results: &[ExperimentResult], path: &Path) -> Result<()
     Ok(())
 }
 
+/// Crossover summary written next to the results (`<stem>_crossover.csv`)
+fn crossover_csv_path(results_path: &Path) -> PathBuf {
+    let stem = results_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "dag".to_string());
+    results_path.with_file_name(format!("{}_crossover.csv", stem))
+}
+
+/// Write one row per crossover search
+pub fn write_crossover_csv(
+    summaries: &[CrossoverSummary],
+    settings: &CrossoverSettings,
+    path: &Path,
+) -> Result<()> {
+    let mut file = File::create(path)
+        .with_context(|| format!("Failed to create CSV file: {}", path.display()))?;
+
+    writeln!(
+        file,
+        "operation,scale,incumbent,candidate,min_gain,min_records,max_records,\
+        crossover_records,ci_lower_records,ci_upper_records,probes"
+    )?;
+
+    let optional = |n: Option<usize>| n.map(|n| n.to_string()).unwrap_or_default();
+    for summary in summaries {
+        writeln!(
+            file,
+            "{},{},{},{},{:.4},{},{},{},{},{},{}",
+            summary.operation,
+            summary.scale,
+            settings.incumbent.config_key(),
+            settings.candidate.config_key(),
+            settings.min_gain,
+            summary.search.min_records,
+            summary.search.max_records,
+            optional(summary.crossover.crossover),
+            optional(summary.crossover.ci_lower),
+            optional(summary.crossover.ci_upper),
+            summary.crossover.probes.len(),
+        )?;
+    }
+
+    file.flush()?;
+    println!("✅ Crossover summary written to: {}", path.display());
+    Ok(())
+}
+
+/// Pruning validation CSV next to the results (`dag.csv` -> `dag_pruning_validation.csv`)
+fn pruning_validation_csv_path(results_path: &Path) -> PathBuf {
+    let stem = results_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "dag".to_string());
+    results_path.with_file_name(format!("{}_pruning_validation.csv", stem))
+}
+
+/// Write one row per full-factorial cell
+pub fn write_pruning_validation_csv(
+    checks: &[PruningCheck],
+    strategy: PruningPolicy,
+    path: &Path,
+) -> Result<()> {
+    let mut file = File::create(path)
+        .with_context(|| format!("Failed to create CSV file: {}", path.display()))?;
+
+    writeln!(
+        file,
+        "operation,scale,strategy,optimal,optimal_speedup,best_kept,best_kept_speedup,\
+        skipped,optimal_skipped,loss"
+    )?;
+
+    for check in checks {
+        writeln!(
+            file,
+            "{},{},{},{},{:.4},{},{:.4},{},{},{:.4}",
+            check.operation,
+            check.scale,
+            strategy.name(),
+            check.optimal,
+            check.optimal_speedup,
+            check.best_kept,
+            check.best_kept_speedup,
+            check.skipped.join(";"),
+            check.missed_optimum(),
+            check.loss(),
+        )?;
+    }
+
+    file.flush()?;
+    println!("✅ Pruning validation written to: {}", path.display());
+    Ok(())
+}
+
+/// Print what pruning would have skipped, and whether it cost the optimum
+fn print_pruning_validation(checks: &[PruningCheck], strategy: PruningPolicy, candidates: usize) {
+    println!();
+    println!("🧪 Pruning Validation ({} pruning vs full factorial)", strategy.name());
+    for check in checks {
+        let skipped = if check.skipped.is_empty() {
+            "nothing skipped".to_string()
+        } else {
+            format!("skipped {}", check.skipped.join(", "))
+        };
+        if check.missed_optimum() {
+            println!(
+                "   ⚠️  {} @ {}: {}; optimal {} ({:.2}×) skipped, best kept {} ({:.2}×, {:.2}× lost)",
+                check.operation,
+                check.scale,
+                skipped,
+                check.optimal,
+                check.optimal_speedup,
+                check.best_kept,
+                check.best_kept_speedup,
+                check.loss()
+            );
+        } else {
+            println!(
+                "   ✅ {} @ {}: {}; optimal {} ({:.2}×) kept",
+                check.operation, check.scale, skipped, check.optimal, check.optimal_speedup
+            );
+        }
+    }
+
+    let skipped: usize = checks.iter().map(|check| check.skipped.len()).sum();
+    let total = checks.len() * candidates;
+    let missed = checks.iter().filter(|check| check.missed_optimum()).count();
+    println!(
+        "   Skipped configs: {} of {} ({:.0}%)",
+        skipped,
+        total,
+        100.0 * skipped as f64 / total.max(1) as f64
+    );
+    println!("   Optimum skipped: {} of {} cells", missed, checks.len());
+}
+
+/// Config label used in dry-run plans and `--configs` filters: the node
+/// name, plus the affinity when it is not the default (single-threaded
+/// names omit it)
+fn config_key(config_name: &str, affinity: &str) -> String {
+    if affinity == CoreAffinity::Default.name() {
+        config_name.to_string()
+    } else {
+        format!("{}@{}", config_name, affinity)
+    }
+}
+
+/// Median elapsed times from an earlier results CSV, for dry-run estimates
+///
+/// Pruned rows carry no timing and are skipped.
+pub fn load_prior_timings(path: &Path) -> Result<Vec<PriorTiming>> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open prior results: {}", path.display()))?;
+    let mut lines = BufReader::new(file).lines();
+
+    let header = lines.next().transpose()?.unwrap_or_default();
+    let columns: Vec<&str> = header.split(',').collect();
+    let column = |name: &str| {
+        columns
+            .iter()
+            .position(|c| *c == name)
+            .with_context(|| format!("{} has no '{}' column", path.display(), name))
+    };
+    let operation_col = column("operation")?;
+    let config_col = column("config_name")?;
+    let affinity_col = column("affinity")?;
+    let sequences_col = column("num_sequences")?;
+    let pruned_col = column("pruned")?;
+    let elapsed_col = column("elapsed_median")?;
+
+    let mut priors = Vec::new();
+    for line in lines {
+        let line = line?;
+        let fields: Vec<&str> = line.split(',').collect();
+        if fields.len() != columns.len() || fields[pruned_col] == "true" {
+            continue;
+        }
+        priors.push(PriorTiming {
+            operation: fields[operation_col].to_string(),
+            config: config_key(fields[config_col], fields[affinity_col]),
+            num_sequences: fields[sequences_col].parse()?,
+            seconds_per_run: fields[elapsed_col].parse()?,
+        });
+    }
+
+    Ok(priors)
+}
+
 // ============================================================================
 // CLI Entry Point
 // ============================================================================
@@ -1134,12 +2468,39 @@ fn main() -> Result<()> {
 
     if args.len() < 3 {
         eprintln!("Usage: asbb-dag-traversal --batch <batch_type> --output <path> [OPTIONS]");
-        eprintln!("Batch types: neon_parallel, core_affinity, scale_thresholds");
+        eprintln!("Batch types: neon_parallel, core_affinity, scale_thresholds, efficiency, crossover,");
+        eprintln!("             long_reads");
         eprintln!();
         eprintln!("Options:");
         eprintln!("  --repetitions <N>         Number of repetitions per experiment (default: 30)");
         eprintln!("  --warmup <N>              Number of warmup runs to discard (default: 3)");
         eprintln!("  --outlier-threshold <F>   IQR multiplier for outlier detection (default: 1.5)");
+        eprintln!("  --dataset <NAME>          Use a dataset from datasets/manifest.toml instead of");
+        eprintln!("                            the standard scales (repeatable)");
+        eprintln!("  --fresh-thread-pools      Build a new thread pool for every parallel run");
+        eprintln!("                            (includes pool construction in timings)");
+        eprintln!("  --no-progress             Disable progress bars (plain log output)");
+        eprintln!("  --operations <A,B,..>     Only run these operations");
+        eprintln!("  --scales <A,B,..>         Only run these scales (e.g. Medium,Large)");
+        eprintln!("  --long-reads              Add the long-read tiers (Long1kb, Long10kb, Long100kb)");
+        eprintln!("  --configs <A,B,..>        Only run these configs (e.g. neon_4t, neon@p_cores);");
+        eprintln!("                            the naive baseline always runs for speedups");
+        eprintln!("  --dry-run                 List the experiments and an ETA without running them");
+        eprintln!("  --force                   Run even under Rosetta translation");
+        eprintln!("  --prior <CSV>             Earlier results to estimate durations from (repeatable;");
+        eprintln!("                            default: the --output file if it exists)");
+        eprintln!("  --pruning <STRATEGY>      How neon_parallel explores each cell: threshold (default),");
+        eprintln!("                            halving, ucb or bayesian");
+        eprintln!("  --full-factorial          Run neon_parallel unpruned, then report what --pruning");
+        eprintln!("                            would have skipped (use --operations/--scales to limit)");
+        eprintln!("  --memory-fraction <F>     Skip experiments estimated to need more than this share");
+        eprintln!("                            of physical memory (default: 0.8; 0 disables)");
+        eprintln!();
+        eprintln!("Crossover batch:");
+        eprintln!("  --incumbent <CONFIG>      Config to beat (default: neon)");
+        eprintln!("  --candidate <CONFIG>      Config searched for a crossover (default: neon_4t)");
+        eprintln!("  --min-gain <PERCENT>      Required advantage of the candidate (default: 10)");
+        eprintln!("  --min-records <N>         Smallest record count searched (default: 100)");
         std::process::exit(1);
     }
 
@@ -1149,6 +2510,20 @@ fn main() -> Result<()> {
     let mut repetitions = 30; // Default: publication quality
     let mut warmup_runs = 3;  // Default: eliminate cold-start effects
     let mut outlier_threshold = 1.5; // Default: standard IQR method
+    let mut datasets = Vec::new();
+    let mut fresh_thread_pools = false;
+    let mut progress_bar = true;
+    let mut dry_run = false;
+    let mut force = false;
+    let mut long_reads = false;
+    let mut operation_filter = Vec::new();
+    let mut scale_filter = Vec::new();
+    let mut config_filter = Vec::new();
+    let mut memory_fraction = DEFAULT_MEMORY_FRACTION;
+    let mut prior_paths = Vec::new();
+    let mut crossover = CrossoverSettings::default();
+    let mut pruning = PruningPolicy::default();
+    let mut full_factorial = false;
 
     let mut i = 1;
     while i < args.len() {
@@ -1186,6 +2561,88 @@ fn main() -> Result<()> {
                         .with_context(|| format!("Invalid outlier-threshold value: {}", args[i]))?;
                 }
             }
+            "--dataset" => {
+                i += 1;
+                if i < args.len() {
+                    datasets.push(args[i].clone());
+                }
+            }
+            "--fresh-thread-pools" => {
+                fresh_thread_pools = true;
+            }
+            "--full-factorial" => {
+                full_factorial = true;
+            }
+            "--pruning" => {
+                i += 1;
+                if i < args.len() {
+                    pruning = PruningPolicy::from_name(&args[i])?;
+                }
+            }
+            "--no-progress" => {
+                progress_bar = false;
+            }
+            "--dry-run" => {
+                dry_run = true;
+            }
+            "--force" => {
+                force = true;
+            }
+            "--long-reads" => {
+                long_reads = true;
+            }
+            "--operations" | "--scales" | "--configs" => {
+                let flag = args[i].clone();
+                i += 1;
+                if i < args.len() {
+                    let values = args[i].split(',').map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+                    match flag.as_str() {
+                        "--operations" => operation_filter.extend(values),
+                        "--scales" => scale_filter.extend(values),
+                        _ => config_filter.extend(values),
+                    }
+                }
+            }
+            "--prior" => {
+                i += 1;
+                if i < args.len() {
+                    prior_paths.push(PathBuf::from(&args[i]));
+                }
+            }
+            "--incumbent" => {
+                i += 1;
+                if i < args.len() {
+                    crossover.incumbent = DAGNode::from_config_key(&args[i])?;
+                }
+            }
+            "--candidate" => {
+                i += 1;
+                if i < args.len() {
+                    crossover.candidate = DAGNode::from_config_key(&args[i])?;
+                }
+            }
+            "--min-gain" => {
+                i += 1;
+                if i < args.len() {
+                    let percent: f64 = args[i].parse()
+                        .with_context(|| format!("Invalid min-gain value: {}", args[i]))?;
+                    crossover.min_gain = percent / 100.0;
+                }
+            }
+            "--min-records" => {
+                i += 1;
+                if i < args.len() {
+                    crossover.min_records = args[i].parse()
+                        .with_context(|| format!("Invalid min-records value: {}", args[i]))?;
+                }
+            }
+            "--memory-fraction" => {
+                i += 1;
+                if i < args.len() {
+                    memory_fraction = args[i].parse()
+                        .with_context(|| format!("Invalid memory-fraction value: {}", args[i]))?;
+                }
+            }
             _ => {}
         }
         i += 1;
@@ -1197,15 +2654,29 @@ fn main() -> Result<()> {
         .ok_or_else(|| anyhow::anyhow!("Missing --output argument"))?;
 
     let batch = DAGBatch::from_str(&batch_type)?;
+    if full_factorial && batch != DAGBatch::NeonParallel {
+        anyhow::bail!("--full-factorial applies to the neon_parallel batch (the only one that prunes)");
+    }
 
     println!("📊 Statistical Parameters:");
     println!("   Repetitions per experiment: {}", repetitions);
     println!("   Warmup runs: {}", warmup_runs);
     println!("   Outlier threshold (IQR): {}x", outlier_threshold);
+    if full_factorial {
+        println!("   Pruning strategy: none (full factorial; validating {})", pruning.name());
+    } else if batch == DAGBatch::NeonParallel {
+        println!("   Pruning strategy: {}", pruning.name());
+    }
+    println!(
+        "   Thread pools: {}",
+        if fresh_thread_pools { "fresh per run (construction timed)" } else { "shared" }
+    );
     println!();
 
+    thread_pool::set_pool_reuse(!fresh_thread_pools);
+
     // Full run with 10 operations (Level 1 primitives)
-    let operations = vec![
+    let mut operations = vec![
         "base_counting".to_string(),
         "gc_content".to_string(),
         "at_content".to_string(),
@@ -1218,23 +2689,98 @@ fn main() -> Result<()> {
         "complexity_score".to_string(),
     ];
 
-    // Select scales based on batch type
-    let scales = match batch {
-        DAGBatch::NeonParallel => vec![
-            SCALES[2].clone(), // Medium (10K)
-            SCALES[3].clone(), // Large (100K)
-            SCALES[4].clone(), // VeryLarge (1M)
-        ],
-        DAGBatch::CoreAffinity => vec![
-            SCALES[2].clone(), // Medium (10K)
-            SCALES[3].clone(), // Large (100K)
-        ],
-        DAGBatch::ScaleThresholds => vec![
-            SCALES[0].clone(), // Tiny (100)
-            SCALES[1].clone(), // Small (1K)
-            SCALES[2].clone(), // Medium (10K)
-            SCALES[3].clone(), // Large (100K)
-        ],
+    // Select scales based on batch type (named datasets override the standard ladder)
+    let scales = if !datasets.is_empty() {
+        datasets
+            .iter()
+            .map(|name| Scale::from_manifest(DEFAULT_MANIFEST_PATH, name))
+            .collect::<Result<Vec<_>>>()?
+    } else {
+        match batch {
+            DAGBatch::NeonParallel => vec![
+                SCALES[2].clone(), // Medium (10K)
+                SCALES[3].clone(), // Large (100K)
+                SCALES[4].clone(), // VeryLarge (1M)
+            ],
+            DAGBatch::CoreAffinity => vec![
+                SCALES[2].clone(), // Medium (10K)
+                SCALES[3].clone(), // Large (100K)
+            ],
+            DAGBatch::ScaleThresholds => vec![
+                SCALES[0].clone(), // Tiny (100)
+                SCALES[1].clone(), // Small (1K)
+                SCALES[2].clone(), // Medium (10K)
+                SCALES[3].clone(), // Large (100K)
+            ],
+            DAGBatch::Efficiency => vec![
+                SCALES[3].clone(), // Large (100K)
+            ],
+            // Searched by prefix, so one dataset bounds the range
+            DAGBatch::Crossover => vec![
+                SCALES[3].clone(), // Large (100K)
+            ],
+            // Short-read reference, then the long-read tiers
+            DAGBatch::LongReads => {
+                let mut scales = vec![SCALES[2].clone()]; // Medium (10K)
+                scales.extend(LONG_READ_SCALES.iter().cloned());
+                scales
+            }
+        }
+    };
+
+    if !operation_filter.is_empty() {
+        if let Some(unknown) = operation_filter.iter().find(|op| !operations.contains(op)) {
+            anyhow::bail!("Unknown operation '{}' (available: {})", unknown, operations.join(", "));
+        }
+        operations.retain(|op| operation_filter.contains(op));
+    }
+
+    // The long-read batch compares against the intra-read backend, which
+    // only some operations implement
+    if batch == DAGBatch::LongReads {
+        let mut skipped = Vec::new();
+        for operation in &operations {
+            if !supports_intra_read(operation)? {
+                skipped.push(operation.clone());
+            }
+        }
+        if !skipped.is_empty() {
+            println!("⏭️  No intra-read backend, skipping: {}", skipped.join(", "));
+            operations.retain(|op| !skipped.contains(op));
+        }
+        if operations.is_empty() {
+            anyhow::bail!("None of the selected operations has an intra-read backend");
+        }
+    }
+
+    let mut scales = scales;
+    if long_reads {
+        let missing: Vec<Scale> = LONG_READ_SCALES
+            .iter()
+            .filter(|long| !scales.iter().any(|s| s.name == long.name))
+            .cloned()
+            .collect();
+        scales.extend(missing);
+    }
+    if !scale_filter.is_empty() {
+        let names: Vec<String> = scales.iter().map(|s| s.name.to_string()).collect();
+        if let Some(unknown) = scale_filter
+            .iter()
+            .find(|f| !names.iter().any(|n| n.eq_ignore_ascii_case(f)))
+        {
+            anyhow::bail!("Scale '{}' is not in this batch (available: {})", unknown, names.join(", "));
+        }
+        scales.retain(|s| scale_filter.iter().any(|f| s.name.eq_ignore_ascii_case(f)));
+    }
+
+    let memory_limit = if memory_fraction > 0.0 {
+        let limit = MemoryLimit::fraction_of_physical(memory_fraction);
+        if limit.is_none() {
+            eprintln!("⚠️  Physical memory unknown; running without a memory limit");
+        }
+        limit.map(|limit| limit.limit_bytes)
+    } else {
+        None
     };
 
     let config = DAGConfig {
@@ -1242,19 +2788,87 @@ fn main() -> Result<()> {
         scales,
         pruning_threshold: 1.5,
         diminishing_returns_threshold: 1.3,
+        pruning,
+        full_factorial,
         output_path,
         batch,
         repetitions,
         warmup_runs,
         outlier_threshold,
+        progress_bar,
+        configs: config_filter,
+        crossover,
+        memory_limit,
     };
 
+    let available = DAGTraversal::new(config.clone()).available_configs()?;
+    if let Some(unknown) = config.configs.iter().find(|c| !available.contains(c)) {
+        anyhow::bail!("Config '{}' is not in this batch (available: {})", unknown, available.join(", "));
+    }
+
+    if dry_run {
+        if prior_paths.is_empty() && config.output_path.exists() {
+            prior_paths.push(config.output_path.clone());
+        }
+        let mut priors = Vec::new();
+        for path in &prior_paths {
+            priors.extend(load_prior_timings(path)?);
+        }
+
+        let traversal = DAGTraversal::new(config);
+        traversal.plan(&DurationEstimator::new(priors))?.print();
+        if traversal.config.batch == DAGBatch::Crossover {
+            println!("   Per probe: the search measures both configs once per probed record count");
+        } else if traversal.config.full_factorial {
+            println!("   Full factorial: every listed experiment runs");
+        } else {
+            println!("   Upper bound: pruning skips configs that do not pay off");
+        }
+        return Ok(());
+    }
+
+    translation::ensure_native(force)?;
+
     // Run DAG traversal
-    let mut traversal = DAGTraversal::new(config);
+    let mut traversal = DAGTraversal::new(config).with_interrupt(Interrupt::install()?);
+    if let Ok(profile) = HardwareProfile::detect() {
+        traversal.validate_configs(&profile)?;
+    }
     let results = traversal.run()?;
 
     // Write results
     write_results_csv(&results, &traversal.config.output_path)?;
+    if traversal.config.batch == DAGBatch::Crossover {
+        let path = crossover_csv_path(&traversal.config.output_path);
+        write_crossover_csv(traversal.crossovers(), &traversal.config.crossover, &path)?;
+    }
+    if traversal.config.full_factorial {
+        let checks = traversal.validate_pruning(&results)?;
+        let candidates = traversal.neon_parallel_candidates().1;
+        let selected = candidates.iter().filter(|c| c.selected).count();
+        print_pruning_validation(&checks, traversal.config.pruning, selected);
+        let path = pruning_validation_csv_path(&traversal.config.output_path);
+        write_pruning_validation_csv(&checks, traversal.config.pruning, &path)?;
+    }
+    write_manifest(&traversal.config)?;
+
+    if let Some(remaining) = traversal.remaining_operations() {
+        let output = &traversal.config.output_path;
+        let stem = output
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "dag".to_string());
+        let resume_path = output.with_file_name(format!("{}_resume.csv", stem));
+        println!();
+        println!(
+            "▶️  Resume with: {}",
+            interrupt::resume_command(&[
+                ("--operations", Some(&remaining.join(","))),
+                ("--output", Some(&resume_path.to_string_lossy())),
+            ])
+        );
+        std::process::exit(interrupt::EXIT_CODE);
+    }
 
     Ok(())
 }
@@ -0,0 +1,129 @@
+//! `asbb external`: compare installed tools (seqkit, fastp, FastQC) to ASBB
+//!
+//! Times each tool on the dataset next to its ASBB counterpart (see
+//! `asbb_explorer::external`) and prints a "vs existing tools" table. The
+//! equivalence column and the notes under the table say where the tool does
+//! different or additional work, so partial matches are never presented as
+//! like-for-like. Tools that are not installed are listed as unavailable.
+
+use anyhow::{Context, Result};
+use asbb_core::HardwareConfig;
+use asbb_explorer::external::{compare_tool, ExternalTool, ToolComparison};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Options for an external-tool comparison
+pub struct ExternalOptions {
+    /// Dataset FASTQ file
+    pub input: PathBuf,
+
+    /// Tools to compare (`asbb_explorer::external::TOOLS`)
+    pub tools: Vec<String>,
+
+    /// Threads for both the tools and ASBB
+    pub threads: usize,
+
+    /// Warmup runs per side (not measured)
+    pub warmup: usize,
+
+    /// Measured runs per side
+    pub runs: usize,
+
+    /// Optional CSV output
+    pub output: Option<PathBuf>,
+}
+
+pub fn run(options: &ExternalOptions) -> Result<()> {
+    let tools = options
+        .tools
+        .iter()
+        .map(|name| ExternalTool::by_name(name))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut config = HardwareConfig::naive();
+    config.use_neon = true;
+    config.num_threads = options.threads;
+
+    println!("🧰 Comparing against external tools");
+    println!("   Input: {}", options.input.display());
+    println!("   Threads: {}", options.threads);
+    println!("   Runs: {} (+{} warmup)", options.runs, options.warmup);
+    println!();
+
+    let mut comparisons = Vec::new();
+    for tool in &tools {
+        let comparison = compare_tool(tool, &options.input, &config, options.warmup, options.runs)
+            .with_context(|| format!("Comparing {}", tool.name))?;
+        match &comparison.version {
+            Some(version) => println!("   ✅ {}: {}", tool.name, version),
+            None => println!("   ⚠️  {}: '{}' not found on PATH (ASBB side only)", tool.name, tool.program),
+        }
+        comparisons.push(comparison);
+    }
+    println!();
+
+    println!(
+        "   {:<14} {:>12} {:<14} {:>12} {:>10}  Equivalence",
+        "Tool", "Tool (ms)", "ASBB", "ASBB (ms)", "ASBB gain"
+    );
+    for c in &comparisons {
+        let tool_ms = c
+            .tool_time
+            .as_ref()
+            .map(|t| format!("{:.1}", t.median.as_secs_f64() * 1000.0))
+            .unwrap_or_else(|| "n/a".to_string());
+        let gain = c
+            .asbb_speedup()
+            .map(|s| format!("{:.2}×", s))
+            .unwrap_or_else(|| "n/a".to_string());
+        println!(
+            "   {:<14} {:>12} {:<14} {:>12.1} {:>10}  {}",
+            c.tool,
+            tool_ms,
+            c.counterpart,
+            c.asbb_time.median.as_secs_f64() * 1000.0,
+            gain,
+            c.equivalence.name()
+        );
+    }
+    println!();
+    println!("📝 Notes:");
+    for c in &comparisons {
+        println!("   {} ({}): {}", c.tool, c.equivalence.name(), c.note);
+    }
+
+    if let Some(path) = &options.output {
+        write_csv(path, &comparisons, options.threads)?;
+        println!();
+        println!("📄 Wrote {} comparisons to {}", comparisons.len(), path.display());
+    }
+    Ok(())
+}
+
+fn write_csv(path: &Path, comparisons: &[ToolComparison], threads: usize) -> Result<()> {
+    let mut csv = String::from(
+        "tool,version,threads,platform,tool_median_seconds,asbb_counterpart,asbb_median_seconds,asbb_speedup,equivalence,note\n",
+    );
+    for c in comparisons {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{:.9},{},{},\"{}\"\n",
+            c.tool,
+            c.version.as_deref().unwrap_or("").replace(',', ";"),
+            threads,
+            crate::bench::platform(),
+            c.tool_time
+                .as_ref()
+                .map(|t| format!("{:.9}", t.median.as_secs_f64()))
+                .unwrap_or_default(),
+            c.counterpart,
+            c.asbb_time.median.as_secs_f64(),
+            c.asbb_speedup().map(|s| format!("{:.4}", s)).unwrap_or_default(),
+            c.equivalence.name(),
+            c.note.replace('"', "'")
+        ));
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, csv).with_context(|| format!("Failed to write {}", path.display()))
+}
@@ -0,0 +1,509 @@
+//! `asbb serve` / `asbb worker`: one benchmark batch across several Macs
+//!
+//! The controller (`asbb serve`) splits a batch into jobs, one per
+//! (operation, dataset), and hands them out over HTTP. Every worker
+//! (`asbb worker --join URL`) runs *every* job, since the point is comparing
+//! machines: a worker pulls the next job it has not done, benchmarks it like
+//! `asbb bench` (naive plus the NEON configs, speedups against its own naive
+//! run), and uploads the rows tagged with its name and `HardwareProfile`.
+//!
+//! Run one worker per machine: two workers on one Mac would perturb each
+//! other's timings. Dataset paths are resolved on each worker, so every
+//! machine needs the same files (e.g. `asbb datagen` in a repo checkout).
+//!
+//! # Endpoints
+//!
+//! - `POST /jobs/next` with the worker's [`WorkerInfo`]: the next [`Job`]
+//!   (200), or 204 once the worker has done them all
+//! - `POST /results` with an [`Upload`]
+//! - `GET /status`: jobs done per worker (JSON)
+//!
+//! # Output
+//!
+//! `<output-dir>/<worker>.csv` holds each worker's rows in the `asbb bench`
+//! format (ready for `asbb compare`), and `<output-dir>/fleet.csv` all rows
+//! with `worker`, `chip` and `memory_bandwidth_gbps` columns in front. A
+//! restarted controller picks up each worker's finished jobs from its CSV.
+
+use anyhow::{Context, Result};
+use asbb_core::HardwareProfile;
+use asbb_explorer::dataset_cache::DatasetCache;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::bench::{self, BenchInput};
+
+/// Default controller address
+pub const DEFAULT_BIND: &str = "0.0.0.0:7878";
+
+/// Combined results of every worker, inside the output directory
+pub const FLEET_CSV: &str = "fleet.csv";
+
+// ============================================================================
+// Protocol
+// ============================================================================
+
+/// One operation on one dataset, benchmarked with every config
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: usize,
+    pub operation: String,
+
+    /// Scale label of the dataset
+    pub scale: String,
+
+    /// Dataset path, resolved on the worker
+    pub path: PathBuf,
+
+    /// Thread counts for the parallel configs (`neon_{n}t`)
+    pub threads: Vec<usize>,
+
+    /// Size the parallel configs for each worker's chip instead
+    pub auto: bool,
+
+    pub warmup: usize,
+    pub runs: usize,
+}
+
+/// Identity and hardware of a worker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerInfo {
+    /// Unique per machine (default: host name)
+    pub name: String,
+
+    /// `{os}-{arch}`
+    pub platform: String,
+    pub simd_backend: String,
+
+    /// `None` where detection is unsupported (non-Apple hosts)
+    pub profile: Option<HardwareProfile>,
+}
+
+impl WorkerInfo {
+    /// This machine, named `name` (or its host name)
+    pub fn detect(name: Option<String>) -> Self {
+        Self {
+            name: name.unwrap_or_else(hostname),
+            platform: bench::platform(),
+            simd_backend: asbb_ops::simd::backend().to_string(),
+            profile: HardwareProfile::detect().ok(),
+        }
+    }
+
+    /// Chip name, or the platform without a profile
+    pub fn chip(&self) -> String {
+        match &self.profile {
+            Some(profile) => profile.chip_name(),
+            None => self.platform.clone(),
+        }
+    }
+
+    /// Worker name usable as a file name
+    fn file_stem(&self) -> String {
+        self.name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+            .collect()
+    }
+}
+
+/// Outcome of one job on one worker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Upload {
+    pub worker: WorkerInfo,
+    pub job: usize,
+
+    /// Results CSV lines (`asbb bench` format, no header)
+    pub rows: Vec<String>,
+
+    /// Set if the job failed; the job counts as done either way
+    pub error: Option<String>,
+}
+
+/// Progress of one worker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerStatus {
+    pub chip: String,
+    pub done: usize,
+    pub failed: usize,
+    pub jobs: usize,
+}
+
+/// Host name of this machine
+fn hostname() -> String {
+    let mut buf = [0u8; 256];
+    // SAFETY: the buffer is valid for its full length
+    let ok = unsafe { libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len()) } == 0;
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    match std::str::from_utf8(&buf[..len]) {
+        Ok(name) if ok && !name.is_empty() => name.to_string(),
+        _ => "worker".to_string(),
+    }
+}
+
+// ============================================================================
+// Controller
+// ============================================================================
+
+/// Options for `asbb serve`
+pub struct ServeOptions {
+    pub inputs: Vec<BenchInput>,
+    pub operations: Vec<String>,
+    pub threads: Vec<usize>,
+    pub auto: bool,
+    pub warmup: usize,
+    pub runs: usize,
+
+    /// Address to listen on
+    pub bind: String,
+
+    /// Directory for the per-worker and fleet CSVs
+    pub output_dir: PathBuf,
+
+    /// Stop once this many workers have finished every job
+    pub expect: Option<usize>,
+}
+
+struct WorkerState {
+    info: WorkerInfo,
+    done: BTreeSet<usize>,
+    failed: usize,
+}
+
+/// Job list and per-worker progress
+struct Coordinator {
+    jobs: Vec<Job>,
+    workers: BTreeMap<String, WorkerState>,
+    output_dir: PathBuf,
+}
+
+impl Coordinator {
+    fn new(options: &ServeOptions) -> Self {
+        let mut jobs = Vec::new();
+        for input in &options.inputs {
+            for operation in &options.operations {
+                jobs.push(Job {
+                    id: jobs.len(),
+                    operation: operation.clone(),
+                    scale: input.scale.clone(),
+                    path: input.path.clone(),
+                    threads: options.threads.clone(),
+                    auto: options.auto,
+                    warmup: options.warmup,
+                    runs: options.runs,
+                });
+            }
+        }
+        Self { jobs, workers: BTreeMap::new(), output_dir: options.output_dir.clone() }
+    }
+
+    fn worker_csv(&self, info: &WorkerInfo) -> PathBuf {
+        self.output_dir.join(format!("{}.csv", info.file_stem()))
+    }
+
+    /// Register `info` (picking up jobs its CSV already holds) on first contact
+    fn worker(&mut self, info: &WorkerInfo) -> Result<&mut WorkerState> {
+        if !self.workers.contains_key(&info.name) {
+            let done = self.done_in_csv(&self.worker_csv(info))?;
+            println!(
+                "👋 {} joined ({}, {} of {} jobs already done)",
+                info.name,
+                info.chip(),
+                done.len(),
+                self.jobs.len()
+            );
+            let state = WorkerState { info: info.clone(), done, failed: 0 };
+            self.workers.insert(info.name.clone(), state);
+        }
+        let state = self.workers.get_mut(&info.name).expect("registered above");
+        state.info = info.clone();
+        Ok(state)
+    }
+
+    /// Jobs whose (operation, scale) appears in an earlier worker CSV
+    fn done_in_csv(&self, path: &Path) -> Result<BTreeSet<usize>> {
+        if !path.exists() {
+            return Ok(BTreeSet::new());
+        }
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let finished: BTreeSet<(&str, &str)> = text
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split(',').collect();
+                Some((*fields.first()?, *fields.get(3)?))
+            })
+            .collect();
+        Ok(self
+            .jobs
+            .iter()
+            .filter(|job| finished.contains(&(job.operation.as_str(), job.scale.as_str())))
+            .map(|job| job.id)
+            .collect())
+    }
+
+    fn next_job(&mut self, info: &WorkerInfo) -> Result<Option<Job>> {
+        let done = self.worker(info)?.done.clone();
+        let next = self.jobs.iter().find(|job| !done.contains(&job.id)).cloned();
+        if let Some(job) = &next {
+            println!(
+                "📤 {}: {} @ {} ({}/{})",
+                info.name,
+                job.operation,
+                job.scale,
+                job.id + 1,
+                self.jobs.len()
+            );
+        }
+        Ok(next)
+    }
+
+    fn record(&mut self, upload: &Upload) -> Result<()> {
+        anyhow::ensure!(upload.job < self.jobs.len(), "Unknown job {}", upload.job);
+        let worker_csv = self.worker_csv(&upload.worker);
+        let fleet_csv = self.output_dir.join(FLEET_CSV);
+        let jobs = self.jobs.len();
+        let state = self.worker(&upload.worker)?;
+        state.done.insert(upload.job);
+
+        if let Some(error) = &upload.error {
+            state.failed += 1;
+            println!("❌ {}: job {} failed: {}", upload.worker.name, upload.job, error);
+            return Ok(());
+        }
+
+        let info = &upload.worker;
+        let bandwidth = info
+            .profile
+            .as_ref()
+            .map(|profile| profile.memory_bandwidth_gbps.to_string())
+            .unwrap_or_default();
+        let tagged: Vec<String> = upload
+            .rows
+            .iter()
+            .map(|row| format!("{},{},{},{}", info.name, info.chip(), bandwidth, row))
+            .collect();
+        append_csv(&worker_csv, bench::CSV_HEADER, &upload.rows)?;
+        append_csv(
+            &fleet_csv,
+            &format!("worker,chip,memory_bandwidth_gbps,{}", bench::CSV_HEADER),
+            &tagged,
+        )?;
+        println!(
+            "📥 {}: job {} ({} rows, {}/{} done)",
+            info.name,
+            upload.job,
+            upload.rows.len(),
+            state.done.len(),
+            jobs
+        );
+        Ok(())
+    }
+
+    fn status(&self) -> BTreeMap<String, WorkerStatus> {
+        self.workers
+            .iter()
+            .map(|(name, state)| {
+                let status = WorkerStatus {
+                    chip: state.info.chip(),
+                    done: state.done.len(),
+                    failed: state.failed,
+                    jobs: self.jobs.len(),
+                };
+                (name.clone(), status)
+            })
+            .collect()
+    }
+
+    /// Workers that have done every job
+    fn finished_workers(&self) -> usize {
+        self.workers.values().filter(|state| state.done.len() == self.jobs.len()).count()
+    }
+}
+
+/// Append `lines` to a CSV, writing `header` first if the file is new
+fn append_csv(path: &Path, header: &str, lines: &[String]) -> Result<()> {
+    let new = !path.exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    if new {
+        writeln!(file, "{}", header)?;
+    }
+    for line in lines {
+        writeln!(file, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// Serve the batch until interrupted (or until `expect` workers finish)
+pub fn serve(options: &ServeOptions) -> Result<()> {
+    fs::create_dir_all(&options.output_dir)
+        .with_context(|| format!("Failed to create {}", options.output_dir.display()))?;
+    let mut coordinator = Coordinator::new(options);
+    let server = tiny_http::Server::http(&options.bind)
+        .map_err(|e| anyhow::anyhow!("Failed to listen on {}: {}", options.bind, e))?;
+
+    println!("🛰️  Serving {} jobs on http://{}", coordinator.jobs.len(), options.bind);
+    println!(
+        "   {} operations × {} datasets, {} runs (+{} warmup)",
+        options.operations.len(),
+        options.inputs.len(),
+        options.runs,
+        options.warmup
+    );
+    println!("   Results: {}", options.output_dir.display());
+    let port = server.server_addr().to_ip().map(|addr| addr.port()).unwrap_or(0);
+    println!("   Join with: asbb worker --join http://<this-host>:{}", port);
+    println!();
+
+    for mut request in server.incoming_requests() {
+        let mut body = String::new();
+        let response = match request.as_reader().read_to_string(&mut body) {
+            Err(e) => Err(anyhow::anyhow!("Failed to read request: {}", e)),
+            Ok(_) => route(&mut coordinator, request.method(), request.url(), &body),
+        };
+
+        // A worker that got 204 is done; stop only after telling it
+        let idle = matches!(response, Ok(None));
+        let json = tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+            .expect("static header");
+        let sent = match response {
+            Ok(Some(body)) => {
+                request.respond(tiny_http::Response::from_string(body).with_header(json))
+            }
+            Ok(None) => request.respond(tiny_http::Response::empty(204)),
+            Err(e) => {
+                let message = format!("{:#}", e);
+                request.respond(tiny_http::Response::from_string(message).with_status_code(400))
+            }
+        };
+        if let Err(e) = sent {
+            println!("⚠️  Failed to respond: {}", e);
+        }
+
+        if idle && options.expect.is_some_and(|expect| coordinator.finished_workers() >= expect) {
+            println!();
+            println!("✅ {} workers finished every job", coordinator.finished_workers());
+            break;
+        }
+    }
+
+    println!("📄 Fleet results: {}", options.output_dir.join(FLEET_CSV).display());
+    Ok(())
+}
+
+/// Handle one request; `None` means 204 No Content
+fn route(
+    coordinator: &mut Coordinator,
+    method: &tiny_http::Method,
+    url: &str,
+    body: &str,
+) -> Result<Option<String>> {
+    match (method, url) {
+        (tiny_http::Method::Post, "/jobs/next") => {
+            let info: WorkerInfo = serde_json::from_str(body).context("Invalid worker info")?;
+            match coordinator.next_job(&info)? {
+                Some(job) => Ok(Some(serde_json::to_string(&job)?)),
+                None => Ok(None),
+            }
+        }
+        (tiny_http::Method::Post, "/results") => {
+            let upload: Upload = serde_json::from_str(body).context("Invalid upload")?;
+            coordinator.record(&upload)?;
+            Ok(Some("{}".to_string()))
+        }
+        (tiny_http::Method::Get, "/status") => {
+            Ok(Some(serde_json::to_string_pretty(&coordinator.status())?))
+        }
+        _ => anyhow::bail!("Unknown endpoint: {} {}", method, url),
+    }
+}
+
+// ============================================================================
+// Worker
+// ============================================================================
+
+/// Pull jobs from the controller at `url` until none are left
+pub fn work(url: &str, name: Option<String>) -> Result<()> {
+    let url = url.trim_end_matches('/');
+    let info = WorkerInfo::detect(name);
+    let datasets = DatasetCache::new();
+    let mut quality: HashMap<PathBuf, String> = HashMap::new();
+
+    println!("🛠️  Worker {} ({}, {} backend)", info.name, info.chip(), info.simd_backend);
+    println!("   Controller: {}", url);
+    println!();
+
+    let mut completed = 0;
+    loop {
+        let response = ureq::post(&format!("{}/jobs/next", url))
+            .send_json(&info)
+            .with_context(|| format!("Failed to reach controller at {}", url))?;
+        if response.status() == 204 {
+            break;
+        }
+        let job: Job = response.into_json().context("Invalid job from controller")?;
+
+        println!("📂 {} @ {} (job {})", job.operation, job.scale, job.id);
+        let outcome = run_job(&job, &info, &datasets, &mut quality);
+        let upload = match outcome {
+            Ok(rows) => Upload { worker: info.clone(), job: job.id, rows, error: None },
+            Err(e) => {
+                println!("   ❌ {:#}", e);
+                let error = Some(format!("{:#}", e));
+                Upload { worker: info.clone(), job: job.id, rows: Vec::new(), error }
+            }
+        };
+        ureq::post(&format!("{}/results", url))
+            .send_json(&upload)
+            .with_context(|| format!("Failed to upload job {}", job.id))?;
+        completed += 1;
+        println!();
+    }
+
+    println!("✅ No jobs left ({} run by this worker)", completed);
+    Ok(())
+}
+
+/// Benchmark one job; the results as CSV lines
+fn run_job(
+    job: &Job,
+    info: &WorkerInfo,
+    datasets: &DatasetCache,
+    quality: &mut HashMap<PathBuf, String>,
+) -> Result<Vec<String>> {
+    let input = BenchInput { scale: job.scale.clone(), path: job.path.clone() };
+    let data = datasets
+        .records(&job.path.to_string_lossy(), || bench::load_input(&input))?
+        .value;
+    let quality_dist = quality
+        .entry(job.path.clone())
+        .or_insert_with(|| bench::quality_dist(&data))
+        .clone();
+
+    let profile = if job.auto { info.profile.as_ref() } else { None };
+    if job.auto && profile.is_none() {
+        println!("   ⚠️  No hardware profile on this host; using --threads {:?}", job.threads);
+    }
+    let configs = bench::configs(&job.threads, profile);
+
+    bench::print_table_header();
+    let rows = bench::measure_operation(
+        &job.operation,
+        &job.scale,
+        &data,
+        &quality_dist,
+        &configs,
+        job.warmup,
+        job.runs,
+        None,
+    )?;
+    Ok(rows.iter().map(bench::csv_line).collect())
+}
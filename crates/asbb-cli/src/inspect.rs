@@ -0,0 +1,89 @@
+//! `asbb inspect`: infer data characteristics of an input file
+//!
+//! Runs `asbb_core::inspect` over a FASTA/FASTQ file (optionally only the
+//! first N records) and prints the resulting `DataCharacteristics`, the
+//! struct that configuration prediction is keyed on. `--json` prints the
+//! characteristics as JSON instead, and `--output` saves them for later
+//! runs.
+
+use anyhow::{Context, Result};
+use asbb_core::inspect::inspect_path;
+use std::path::PathBuf;
+
+/// Options for an inspection
+pub struct InspectOptions {
+    /// FASTA/FASTQ file to inspect
+    pub input: PathBuf,
+
+    /// Parse only the first N records (count is then extrapolated)
+    pub sample: Option<usize>,
+
+    /// Print JSON instead of the human-readable summary
+    pub json: bool,
+
+    /// Write the characteristics as JSON
+    pub output: Option<PathBuf>,
+}
+
+pub fn run(options: &InspectOptions) -> Result<()> {
+    let inspection = inspect_path(&options.input, options.sample)?;
+    let c = &inspection.characteristics;
+    let json = serde_json::to_string_pretty(c)?;
+
+    if let Some(output) = &options.output {
+        std::fs::write(output, format!("{}\n", json))
+            .with_context(|| format!("Failed to write {}", output.display()))?;
+    }
+
+    if options.json {
+        println!("{}", json);
+        return Ok(());
+    }
+
+    println!("🔍 Inspecting {}", options.input.display());
+    if inspection.sampled {
+        println!(
+            "   Sampled {} records (counts extrapolated from file size)",
+            inspection.records_scanned
+        );
+    }
+    println!();
+    println!("   Format:        {:?}", c.format);
+    println!(
+        "   Sequences:     {}{} ({:?})",
+        if inspection.sampled { "~" } else { "" },
+        c.num_sequences,
+        c.scale_category()
+    );
+    println!("   Length:        {} ± {} bp", c.seq_length_mean, c.seq_length_std);
+    println!("   Read type:     {:?}", c.read_type);
+    match &inspection.quality {
+        Some(quality) => {
+            let fit = &quality.fit;
+            println!(
+                "   Quality:       Q{:.1} ± {:.1} ({:?})",
+                fit.mean_quality, fit.std_quality, quality.distribution_type
+            );
+            println!(
+                "   Profile fit:   head Q{:.1} → tail Q{:.1}, slope {:+.3}/bp, rmse {:.2}, dips {:.1}%",
+                fit.head_mean,
+                fit.tail_mean,
+                fit.slope,
+                fit.profile_rmse,
+                fit.dip_rate * 100.0
+            );
+        }
+        None => println!("   Quality:       n/a"),
+    }
+    println!(
+        "   Size:          {:.1} MB",
+        inspection.file_size_bytes as f64 / 1_000_000.0
+    );
+
+    if let Some(output) = &options.output {
+        println!();
+        println!("💾 Characteristics written to {}", output.display());
+    }
+
+    Ok(())
+}
@@ -1,7 +1,1062 @@
-//! ASBB CLI tool - to be implemented
+//! ASBB command-line interface
+//!
+//! Entry point for dataset management tasks (subsampling, public data
+//! ingestion), input characterization, correctness validation against golden outputs,
+//! measurement-overhead calibration, portable benchmarks for running the
+//! operation crate on non-Apple hosts, whole-report workload-mix benchmarks
+//! (e.g. a FastQC-equivalent report), strategy × hardware comparisons of
+//! alternative algorithms for one operation, timing against installed tools (seqkit,
+//! fastp, FastQC), memory-hierarchy profiling of the machine, cross-platform
+//! comparison of their results, regression checks against recorded history, sustained-load
+//! (thermal soak) runs, analysis reports, reruns from a results file's
+//! reproducibility manifest, benchmark batches spread over several
+//! machines (`asbb serve` / `asbb worker`), and merging results from many
+//! machines into one SQLite store (`asbb results merge`). Operations from
+//! external crates can be registered at link time (`asbb operations` lists
+//! them; see `asbb_core::plugin`). Experiment harnesses remain
+//! separate binaries (`asbb-dag-traversal`, `asbb-pilot-*`).
 
-fn main() {
-    println!("Apple Silicon Bio Bench CLI");
-    println!("Version: 0.1.0");
-    println!("Status: Framework development phase");
+mod bench;
+mod calibrate;
+mod compare;
+mod external;
+mod fleet;
+mod inspect;
+mod merge;
+mod micro;
+mod regress;
+mod report;
+mod rerun;
+mod soak;
+mod strategies;
+mod validate;
+mod workload;
+
+use anyhow::{Context, Result};
+use asbb_core::compare::{Tolerance, DEFAULT_RELATIVE_TOLERANCE};
+use asbb_core::operation_registry::OperationMetadata;
+use asbb_core::HardwareProfile;
+use asbb_datagen::fetch::{fetch_accession, FetchBackend, FetchOptions};
+use asbb_datagen::manifest::{
+    register_dataset, DatasetManifest, DatasetSource, DEFAULT_MANIFEST_PATH,
+};
+use asbb_datagen::subsample::{subsample_fastq, SubsampleMode};
+use clap::{ArgGroup, Parser, Subcommand};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Parser)]
+#[command(name = "asbb")]
+#[command(version, about = "Apple Silicon Bio Bench", long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Dataset generation and preparation
+    Datagen {
+        #[command(subcommand)]
+        command: DatagenCommands,
+    },
+
+    /// Infer data characteristics (size, lengths, quality profile) of a file
+    Inspect {
+        /// FASTA/FASTQ file
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Parse only the first N records and extrapolate the count
+        #[arg(short, long)]
+        sample: Option<usize>,
+
+        /// Print the characteristics as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Write the characteristics as JSON
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Validate every backend against golden outputs
+    Validate {
+        /// Dataset FASTQ file
+        #[arg(short, long, required_unless_present = "dataset")]
+        input: Option<PathBuf>,
+
+        /// Dataset name from the manifest (instead of --input)
+        #[arg(short, long, conflicts_with = "input")]
+        dataset: Option<String>,
+
+        /// Dataset manifest used to resolve --dataset
+        #[arg(short, long, default_value = DEFAULT_MANIFEST_PATH)]
+        manifest: PathBuf,
+
+        /// Operations to validate (default: all)
+        #[arg(short, long, value_delimiter = ',')]
+        operations: Vec<String>,
+
+        /// Directory of golden outputs
+        #[arg(short, long, default_value = "datasets/golden")]
+        golden_dir: PathBuf,
+
+        /// Regenerate golden outputs from the naive backend
+        #[arg(long)]
+        bless: bool,
+
+        /// Relative tolerance for floating-point fields
+        #[arg(long, default_value_t = DEFAULT_RELATIVE_TOLERANCE)]
+        tolerance: f64,
+
+        /// Per-field tolerance override, e.g. `mean_quality=1e-6` (repeatable)
+        #[arg(long = "field-tolerance", value_parser = parse_field_tolerance)]
+        field_tolerances: Vec<(String, f64)>,
+    },
+
+    /// Measure JSON serialization cost relative to compute per operation
+    Calibrate {
+        /// Dataset FASTQ file
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Operations to calibrate (default: all)
+        #[arg(short, long, value_delimiter = ',')]
+        operations: Vec<String>,
+
+        /// Measured runs per operation and backend
+        #[arg(short, long, default_value = "10")]
+        runs: usize,
+
+        /// Write results as CSV
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Benchmark operations on this host (any platform; e.g. Graviton, x86_64)
+    Bench {
+        /// Dataset FASTQ file as `SCALE=PATH` or `PATH` (repeatable)
+        #[arg(short, long = "input", required = true, value_parser = bench::BenchInput::parse)]
+        inputs: Vec<bench::BenchInput>,
+
+        /// Operations to benchmark (default: all, or none with --command)
+        #[arg(short, long, value_delimiter = ',')]
+        operations: Vec<String>,
+
+        /// Thread counts for the parallel configs
+        #[arg(short, long, value_delimiter = ',', default_value = "4")]
+        threads: Vec<usize>,
+
+        /// Size the parallel configs for the detected chip (P-cores, all cores)
+        #[arg(long, conflicts_with = "threads")]
+        auto: bool,
+
+        /// Warmup runs per experiment
+        #[arg(long, default_value = "2")]
+        warmup: usize,
+
+        /// Measured runs per experiment
+        #[arg(short, long, default_value = "10")]
+        runs: usize,
+
+        /// Write results as CSV
+        #[arg(long)]
+        output: Option<PathBuf>,
+
+        /// Black-box command as NAME=COMMAND, with {input} and {threads}
+        /// substituted (repeatable)
+        #[arg(long = "command", value_parser = asbb_explorer::external::ExternalCommand::parse)]
+        commands: Vec<asbb_explorer::external::ExternalCommand>,
+
+        /// Measure CPU energy of each command run (powermetrics as root, or RAPL)
+        #[arg(long)]
+        energy: bool,
+
+        /// Memory budget (MB) for count tables: k-mer operations with a
+        /// spill mode write on-disk shards past it (spilled bytes are recorded)
+        #[arg(long)]
+        memory_budget: Option<usize>,
+    },
+
+    /// Benchmark a workload-mix preset (e.g. a FastQC-equivalent report) end to end
+    Workload {
+        /// Dataset FASTQ file
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Workload preset
+        #[arg(short, long, default_value = "fastqc", value_parser = clap::builder::PossibleValuesParser::new(asbb_explorer::workload::PRESETS))]
+        preset: String,
+
+        /// Thread counts for the parallel configs
+        #[arg(short, long, value_delimiter = ',', default_value = "4")]
+        threads: Vec<usize>,
+
+        /// Size the parallel configs for the detected chip (P-cores, all cores)
+        #[arg(long, conflicts_with = "threads")]
+        auto: bool,
+
+        /// Warmup reports per config
+        #[arg(long, default_value = "1")]
+        warmup: usize,
+
+        /// Measured reports per config
+        #[arg(short, long, default_value = "5")]
+        runs: usize,
+
+        /// Write per-step results as CSV
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Compare alternative algorithmic strategies for one operation across hardware configs
+    Strategies {
+        /// Dataset FASTQ file
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Strategy preset
+        #[arg(short, long, default_value = "quality_statistics", value_parser = clap::builder::PossibleValuesParser::new(asbb_explorer::strategy::PRESETS))]
+        preset: String,
+
+        /// Thread counts for the parallel configs
+        #[arg(short, long, value_delimiter = ',', default_value = "4")]
+        threads: Vec<usize>,
+
+        /// Size the parallel configs for the detected chip (P-cores, all cores)
+        #[arg(long, conflicts_with = "threads")]
+        auto: bool,
+
+        /// Warmup runs per cell
+        #[arg(long, default_value = "1")]
+        warmup: usize,
+
+        /// Measured runs per cell
+        #[arg(short, long, default_value = "5")]
+        runs: usize,
+
+        /// Write per-cell results as CSV
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Hand out a benchmark batch to `asbb worker`s on other machines over HTTP
+    Serve {
+        /// Dataset FASTQ file as `SCALE=PATH` or `PATH`, resolved on each
+        /// worker (repeatable)
+        #[arg(short, long = "input", required = true, value_parser = bench::BenchInput::parse)]
+        inputs: Vec<bench::BenchInput>,
+
+        /// Operations to benchmark (default: all)
+        #[arg(short, long, value_delimiter = ',')]
+        operations: Vec<String>,
+
+        /// Thread counts for the parallel configs
+        #[arg(short, long, value_delimiter = ',', default_value = "4")]
+        threads: Vec<usize>,
+
+        /// Size the parallel configs for each worker's chip (P-cores, all cores)
+        #[arg(long, conflicts_with = "threads")]
+        auto: bool,
+
+        /// Warmup runs per experiment
+        #[arg(long, default_value = "2")]
+        warmup: usize,
+
+        /// Measured runs per experiment
+        #[arg(short, long, default_value = "10")]
+        runs: usize,
+
+        /// Address to listen on
+        #[arg(long, default_value = fleet::DEFAULT_BIND)]
+        bind: String,
+
+        /// Directory for per-worker CSVs and the combined fleet CSV
+        #[arg(long, default_value = "results/fleet")]
+        output_dir: PathBuf,
+
+        /// Stop after this many workers have finished every job
+        #[arg(long)]
+        expect: Option<usize>,
+    },
+
+    /// Run jobs from an `asbb serve` controller on this machine
+    Worker {
+        /// Controller URL (e.g. http://lab-mac.local:7878)
+        #[arg(long)]
+        join: String,
+
+        /// Worker name in the results (default: host name; one per machine)
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// Time installed tools (seqkit stats, fastp, FastQC) against their ASBB counterparts
+    External {
+        /// Dataset FASTQ file
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Tools to compare (default: all)
+        #[arg(long, value_delimiter = ',', value_parser = clap::builder::PossibleValuesParser::new(asbb_explorer::external::TOOLS))]
+        tools: Vec<String>,
+
+        /// Threads for both the tools and ASBB
+        #[arg(short, long, default_value = "4")]
+        threads: usize,
+
+        /// Warmup runs per side
+        #[arg(long, default_value = "1")]
+        warmup: usize,
+
+        /// Measured runs per side
+        #[arg(short, long, default_value = "3")]
+        runs: usize,
+
+        /// Write the comparison table as CSV
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Compare two results CSVs (e.g. M4 vs Graviton) row by row
+    Compare {
+        /// Reference results CSV
+        #[arg(short, long)]
+        baseline: PathBuf,
+
+        /// Results CSV compared against the baseline
+        #[arg(long)]
+        other: PathBuf,
+
+        /// Machine profile of the baseline machine (`asbb micro`)
+        #[arg(long, requires = "other_profile")]
+        baseline_profile: Option<PathBuf>,
+
+        /// Machine profile of the other machine; with --baseline-profile,
+        /// also report throughput normalized by memory bandwidth
+        #[arg(long, requires = "baseline_profile")]
+        other_profile: Option<PathBuf>,
+
+        /// Write the comparison as CSV
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Measure this machine's memory hierarchy and save a machine profile
+    Micro {
+        /// Machine profile output (JSON)
+        #[arg(short, long, default_value = "machine_profile.json")]
+        output: PathBuf,
+
+        /// Smaller buffers and fewer loads (seconds instead of minutes)
+        #[arg(long)]
+        quick: bool,
+
+        /// Also measure buffered, mmap and AIO reads of this file (use a
+        /// large file on the SSD under test)
+        #[arg(long)]
+        storage: Option<PathBuf>,
+
+        /// AIO requests in flight to sweep (comma-separated)
+        #[arg(long, value_delimiter = ',', default_value = "1,2,4,8,16")]
+        queue_depths: Vec<usize>,
+    },
+
+    /// Repeat a run from the manifest written next to its results
+    Rerun {
+        /// Reproducibility manifest (e.g. results/dag.manifest.json)
+        manifest: PathBuf,
+
+        /// Run even if datasets or config differ from the manifest
+        #[arg(long)]
+        force: bool,
+
+        /// Overwrite the config file with the recorded TOML first
+        #[arg(long)]
+        restore_config: bool,
+
+        /// Check inputs and print the command without running it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Flag throughput regressions against historical results
+    Regress {
+        /// Results store of earlier runs (SQLite, as written by `results merge`)
+        #[arg(long)]
+        history: PathBuf,
+
+        /// Results CSV of the run to check
+        #[arg(short, long)]
+        current: PathBuf,
+
+        /// Machine to compare against (default: the run's manifest, else host name)
+        #[arg(long)]
+        machine: Option<String>,
+
+        /// Relative throughput change to ignore (percent)
+        #[arg(short, long, default_value_t = regress::DEFAULT_THRESHOLD_PERCENT)]
+        threshold: f64,
+
+        /// Record the current run in the store after checking
+        #[arg(long)]
+        record: bool,
+
+        /// Commit of the current run (default: the run's manifest, else git HEAD)
+        #[arg(long)]
+        commit: Option<String>,
+    },
+
+    /// Run one operation continuously to measure sustained vs burst throughput
+    Soak {
+        /// Dataset FASTQ file
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Operation to run
+        #[arg(short, long)]
+        operation: String,
+
+        /// Use the naive backend instead of NEON
+        #[arg(long, conflicts_with = "threads")]
+        naive: bool,
+
+        /// Threads for the NEON backend
+        #[arg(short, long, default_value = "1")]
+        threads: usize,
+
+        /// Soak duration in minutes
+        #[arg(short, long, default_value = "10")]
+        minutes: f64,
+
+        /// Reporting window in seconds
+        #[arg(short, long, default_value = "10")]
+        window_secs: f64,
+
+        /// Write per-window results as CSV
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Analysis reports over a results CSV
+    Report {
+        #[command(subcommand)]
+        command: ReportCommands,
+    },
+
+    /// Result sets from several machines and runs
+    Results {
+        #[command(subcommand)]
+        command: ResultsCommands,
+    },
+
+    /// List built-in operations and those registered by external crates
+    Operations,
+}
+
+#[derive(Subcommand)]
+enum ResultsCommands {
+    /// Merge results CSVs into the SQLite store, refusing conflicting rows
+    Merge {
+        /// Directories of results CSVs (with their reproducibility manifests)
+        #[arg(required = true)]
+        dirs: Vec<PathBuf>,
+
+        /// SQLite store to merge into (created if missing)
+        #[arg(long, default_value = "results/asbb.sqlite")]
+        store: PathBuf,
+
+        /// Machine for every file (default: manifest host, else file stem)
+        #[arg(long)]
+        machine: Option<String>,
+
+        /// Commit for every file (default: manifest git commit)
+        #[arg(long)]
+        commit: Option<String>,
+
+        /// Merge the remaining rows despite conflicts and schema mismatches
+        #[arg(long)]
+        skip_conflicts: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReportCommands {
+    /// Markdown summary: thread scaling (Amdahl/Gustafson fits), memory bandwidth
+    Summary {
+        /// Results CSV (DAG traversal, `asbb bench`, pilots)
+        #[arg(short, long)]
+        results: PathBuf,
+
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Gain from doubling threads below which more threads are not worthwhile
+        #[arg(long, default_value_t = report::DEFAULT_THRESHOLD)]
+        doubling_threshold: f64,
+
+        /// `asbb micro` profile of the machine that produced the results
+        /// (adds bandwidth utilization)
+        #[arg(long)]
+        machine_profile: Option<PathBuf>,
+
+        /// Bytes each sequence touches (bases + quality)
+        #[arg(long, default_value_t = report::DEFAULT_BYTES_PER_SEQUENCE)]
+        bytes_per_sequence: usize,
+    },
+
+    /// Operation × config grid of one metric per scale (tidy CSV, optional SVG)
+    Heatmap {
+        /// Results CSV (DAG traversal, `asbb bench`, pilots)
+        #[arg(short, long)]
+        results: PathBuf,
+
+        /// Column to plot (e.g. speedup_median, throughput_median, cv)
+        #[arg(long, default_value = report::DEFAULT_METRIC)]
+        metric: String,
+
+        /// Write the tidy long-format CSV here instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Also render the grids as an SVG heatmap
+        #[arg(long)]
+        svg: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DatagenCommands {
+    /// Subsample a FASTQ file (e.g. derive the scale ladder from real data)
+    #[command(group(ArgGroup::new("mode").required(true).args(["fraction", "count", "head"])))]
+    Subsample {
+        /// Input FASTQ file
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Output FASTQ file
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Keep each read with this probability (hash-based, single pass)
+        #[arg(long)]
+        fraction: Option<f64>,
+
+        /// Keep exactly this many reads chosen uniformly at random (reservoir)
+        #[arg(long)]
+        count: Option<usize>,
+
+        /// Keep the first N reads
+        #[arg(long)]
+        head: Option<usize>,
+
+        /// Random seed for --fraction / --count
+        #[arg(short, long, default_value = "42")]
+        seed: u64,
+
+        /// Dataset manifest to register the output in
+        /// (default: manifest.toml next to the output)
+        #[arg(short, long)]
+        manifest: Option<PathBuf>,
+
+        /// Don't register the output in a dataset manifest
+        #[arg(long, conflicts_with = "manifest")]
+        no_manifest: bool,
+    },
+
+    /// Download a public run (SRA/ENA) and register it in the dataset manifest
+    Fetch {
+        /// Run accession (SRR/ERR/DRR)
+        #[arg(short, long)]
+        accession: String,
+
+        /// Dataset name in the manifest (defaults to the accession)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Download backend: ena (HTTPS + MD5 check) or sra (prefetch/fasterq-dump)
+        #[arg(short, long, default_value = "ena", value_parser = ["ena", "sra"])]
+        backend: String,
+
+        /// Keep only the first N reads of each file
+        #[arg(long)]
+        max_reads: Option<usize>,
+
+        /// Directory for the downloaded FASTQ files
+        #[arg(short, long, default_value = "datasets")]
+        output_dir: PathBuf,
+
+        /// Dataset manifest to register the files in
+        #[arg(short, long, default_value = DEFAULT_MANIFEST_PATH)]
+        manifest: PathBuf,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    asbb_explorer::translation::warn_if_translated();
+
+    match cli.command {
+        Commands::Datagen { command } => match command {
+            DatagenCommands::Subsample {
+                input,
+                output,
+                fraction,
+                count,
+                head,
+                seed,
+                manifest,
+                no_manifest,
+            } => {
+                let mode = match (fraction, count, head) {
+                    (Some(fraction), _, _) => SubsampleMode::Fraction { fraction, seed },
+                    (_, Some(count), _) => SubsampleMode::Count { count, seed },
+                    (_, _, Some(count)) => SubsampleMode::Head { count },
+                    _ => unreachable!("clap requires exactly one subsample mode"),
+                };
+
+                println!("✂️  Subsampling {}", input.display());
+                println!("   Mode: {:?}", mode);
+
+                let stats = subsample_fastq(&input, &output, mode)
+                    .context("Failed to subsample dataset")?;
+
+                println!(
+                    "✅ Wrote {} of {} reads to {}",
+                    stats.records_written,
+                    stats.records_read,
+                    output.display()
+                );
+
+                if !no_manifest {
+                    let manifest = manifest.unwrap_or_else(|| default_manifest_for(&output));
+                    let name = output
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    let source = DatasetSource::Subsampled {
+                        input: input.display().to_string(),
+                        mode: format!("{:?}", mode),
+                    };
+                    register_dataset(&manifest, &name, &output, source)?;
+                    println!("📄 Registered '{}' in {}", name, manifest.display());
+                }
+            }
+
+            DatagenCommands::Fetch {
+                accession,
+                name,
+                backend,
+                max_reads,
+                output_dir,
+                manifest,
+            } => {
+                let options = FetchOptions {
+                    name: name.unwrap_or_else(|| accession.clone()),
+                    accession,
+                    output_dir,
+                    backend: FetchBackend::parse(&backend)?,
+                    max_reads,
+                };
+
+                println!("🌐 Fetching {} via {}...", options.accession, backend);
+                let entries = fetch_accession(&options)
+                    .with_context(|| format!("Failed to fetch {}", options.accession))?;
+
+                let mut registry = DatasetManifest::load_or_default(&manifest)?;
+                for entry in entries {
+                    println!(
+                        "✅ {}: {} reads → {}",
+                        entry.name,
+                        entry.num_records,
+                        entry.path.display()
+                    );
+                    registry.upsert(entry);
+                }
+                registry.save(&manifest)?;
+
+                println!("📄 Registered in {}", manifest.display());
+            }
+        },
+
+        Commands::Inspect {
+            input,
+            sample,
+            json,
+            output,
+        } => {
+            inspect::run(&inspect::InspectOptions {
+                input,
+                sample,
+                json,
+                output,
+            })?;
+        }
+
+        Commands::Validate {
+            input,
+            dataset,
+            manifest,
+            operations,
+            golden_dir,
+            bless,
+            tolerance,
+            field_tolerances,
+        } => {
+            let (dataset, input) = match (dataset, input) {
+                (Some(name), _) => {
+                    let registry = DatasetManifest::load_or_default(&manifest)?;
+                    let entry = registry.get(&name).with_context(|| {
+                        format!("Dataset '{}' not found in {}", name, manifest.display())
+                    })?;
+                    (name, registry.resolve(entry))
+                }
+                (None, Some(input)) => (validate::dataset_name(&input), input),
+                (None, None) => unreachable!("clap requires --input or --dataset"),
+            };
+
+            let operations = if operations.is_empty() {
+                validate::DEFAULT_OPERATIONS.iter().map(|s| s.to_string()).collect()
+            } else {
+                operations
+            };
+
+            validate::run(&validate::ValidateOptions {
+                dataset,
+                input,
+                operations,
+                golden_dir,
+                bless,
+                tolerance: field_tolerances
+                    .iter()
+                    .fold(Tolerance::relative(tolerance), |t, (field, relative)| {
+                        t.with_field(field, *relative)
+                    }),
+            })?;
+        }
+
+        Commands::Calibrate {
+            input,
+            operations,
+            runs,
+            output,
+        } => {
+            let operations = if operations.is_empty() {
+                validate::DEFAULT_OPERATIONS.iter().map(|s| s.to_string()).collect()
+            } else {
+                operations
+            };
+
+            calibrate::run(&calibrate::CalibrateOptions {
+                input,
+                operations,
+                runs,
+                output,
+            })?;
+        }
+
+        Commands::Serve {
+            inputs,
+            operations,
+            threads,
+            auto,
+            warmup,
+            runs,
+            bind,
+            output_dir,
+            expect,
+        } => {
+            let operations = if operations.is_empty() {
+                validate::DEFAULT_OPERATIONS.iter().map(|s| s.to_string()).collect()
+            } else {
+                operations
+            };
+            for operation in &operations {
+                asbb_ops::create_operation(operation)?;
+            }
+
+            fleet::serve(&fleet::ServeOptions {
+                inputs,
+                operations,
+                threads,
+                auto,
+                warmup,
+                runs,
+                bind,
+                output_dir,
+                expect,
+            })?;
+        }
+
+        Commands::Worker { join, name } => {
+            fleet::work(&join, name)?;
+        }
+
+        Commands::Bench {
+            inputs,
+            operations,
+            threads,
+            auto,
+            warmup,
+            runs,
+            output,
+            commands,
+            energy,
+            memory_budget,
+        } => {
+            let operations = if operations.is_empty() && commands.is_empty() {
+                validate::DEFAULT_OPERATIONS.iter().map(|s| s.to_string()).collect()
+            } else {
+                operations
+            };
+            let profile = if auto {
+                Some(HardwareProfile::detect().context("--auto: hardware detection failed")?)
+            } else {
+                None
+            };
+
+            bench::run(&bench::BenchOptions {
+                inputs,
+                operations,
+                threads,
+                profile,
+                warmup,
+                runs,
+                output,
+                commands,
+                energy,
+                memory_budget: memory_budget.map(|mb| mb << 20),
+            })?;
+        }
+
+        Commands::Workload {
+            input,
+            preset,
+            threads,
+            auto,
+            warmup,
+            runs,
+            output,
+        } => {
+            let profile = if auto {
+                Some(HardwareProfile::detect().context("--auto: hardware detection failed")?)
+            } else {
+                None
+            };
+
+            workload::run(&workload::WorkloadOptions {
+                input,
+                preset,
+                threads,
+                profile,
+                warmup,
+                runs,
+                output,
+            })?;
+        }
+
+        Commands::Strategies {
+            input,
+            preset,
+            threads,
+            auto,
+            warmup,
+            runs,
+            output,
+        } => {
+            let profile = if auto {
+                Some(HardwareProfile::detect().context("--auto: hardware detection failed")?)
+            } else {
+                None
+            };
+
+            strategies::run(&strategies::StrategyOptions {
+                input,
+                preset,
+                threads,
+                profile,
+                warmup,
+                runs,
+                output,
+            })?;
+        }
+
+        Commands::External {
+            input,
+            tools,
+            threads,
+            warmup,
+            runs,
+            output,
+        } => {
+            let tools = if tools.is_empty() {
+                asbb_explorer::external::TOOLS.iter().map(|s| s.to_string()).collect()
+            } else {
+                tools
+            };
+            external::run(&external::ExternalOptions {
+                input,
+                tools,
+                threads,
+                warmup,
+                runs,
+                output,
+            })?;
+        }
+
+        Commands::Compare {
+            baseline,
+            other,
+            baseline_profile,
+            other_profile,
+            output,
+        } => {
+            compare::run(&compare::CompareOptions {
+                baseline,
+                other,
+                profiles: baseline_profile.zip(other_profile),
+                output,
+            })?;
+        }
+
+        Commands::Micro { output, quick, storage, queue_depths } => {
+            micro::run(&micro::MicroRunOptions { output, quick, storage, queue_depths })?;
+        }
+
+        Commands::Rerun {
+            manifest,
+            force,
+            restore_config,
+            dry_run,
+        } => {
+            rerun::run(&rerun::RerunOptions {
+                manifest,
+                force,
+                restore_config,
+                dry_run,
+            })?;
+        }
+
+        Commands::Regress {
+            history,
+            current,
+            machine,
+            threshold,
+            record,
+            commit,
+        } => {
+            regress::run(&regress::RegressOptions {
+                history,
+                current,
+                machine,
+                threshold_percent: threshold,
+                record,
+                commit,
+            })?;
+        }
+
+        Commands::Soak {
+            input,
+            operation,
+            naive,
+            threads,
+            minutes,
+            window_secs,
+            output,
+        } => {
+            if !(minutes > 0.0 && window_secs > 0.0) {
+                anyhow::bail!("--minutes and --window-secs must be positive");
+            }
+            soak::run(&soak::SoakOptions {
+                input,
+                operation,
+                naive,
+                threads,
+                duration: Duration::from_secs_f64(minutes * 60.0),
+                window: Duration::from_secs_f64(window_secs),
+                output,
+            })?;
+        }
+
+        Commands::Report { command } => match command {
+            ReportCommands::Summary {
+                results,
+                output,
+                doubling_threshold,
+                machine_profile,
+                bytes_per_sequence,
+            } => {
+                report::run_summary(&report::SummaryOptions {
+                    results,
+                    output,
+                    doubling_threshold,
+                    machine_profile,
+                    bytes_per_sequence,
+                })?;
+            }
+            ReportCommands::Heatmap { results, metric, output, svg } => {
+                report::run_heatmap(&report::HeatmapOptions { results, metric, output, svg })?;
+            }
+        },
+
+        Commands::Results { command } => match command {
+            ResultsCommands::Merge { dirs, store, machine, commit, skip_conflicts } => {
+                merge::run(&merge::MergeOptions { dirs, store, machine, commit, skip_conflicts })?;
+            }
+        },
+
+        Commands::Operations => print_operations()?,
+    }
+
+    Ok(())
+}
+
+/// Built-in operations, then plugins (see `asbb_core::plugin`)
+fn print_operations() -> Result<()> {
+    println!("🧬 Built-in operations:");
+    for builtin in asbb_ops::catalog::builtin_operations() {
+        print_operation(builtin.name, (builtin.metadata)());
+    }
+
+    let plugins = asbb_core::plugin::plugins();
+    if plugins.is_empty() {
+        println!("🔌 No plugin operations registered (build with --features plugins)");
+        return Ok(());
+    }
+    println!("🔌 Plugin operations:");
+    for plugin in plugins {
+        print_operation(plugin.name, (plugin.metadata)());
+    }
+    Ok(())
+}
+
+fn print_operation(name: &str, metadata: OperationMetadata) {
+    println!(
+        "   {:<28} {:?}, complexity {:.2}{}",
+        name,
+        metadata.category,
+        metadata.complexity,
+        metadata.description.map(|d| format!(" — {}", d)).unwrap_or_default()
+    );
+}
+
+/// `manifest.toml` in the same directory as a dataset
+fn default_manifest_for(output: &Path) -> PathBuf {
+    output
+        .parent()
+        .map(|dir| dir.join("manifest.toml"))
+        .unwrap_or_else(|| PathBuf::from("manifest.toml"))
+}
+
+/// Parse `field=tolerance` for `--field-tolerance`
+fn parse_field_tolerance(s: &str) -> Result<(String, f64), String> {
+    let (field, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected FIELD=TOLERANCE, got '{}'", s))?;
+    let value = value
+        .parse()
+        .map_err(|e| format!("invalid tolerance '{}': {}", value, e))?;
+    Ok((field.to_string(), value))
 }
@@ -0,0 +1,140 @@
+//! `asbb results merge`: collect results from several machines into one store
+//!
+//! Each results CSV found in the given directories becomes a result set in
+//! the SQLite store (see `asbb_analysis::store`), tagged with the machine and
+//! commit that produced it. Both come from the file's reproducibility
+//! manifest (`<stem>.manifest.json`, or `manifest.json` in the directory):
+//! the recorded host name (else chip) and git commit. Files without a
+//! manifest fall back to their file stem as the machine, which names the
+//! worker for the per-worker CSVs of `asbb serve`; `--machine` and
+//! `--commit` override both.
+//!
+//! Combined fleet tables (`fleet.csv`, with a `worker` column) are skipped:
+//! the per-worker CSVs beside them hold the same rows.
+
+use anyhow::{Context, Result};
+use asbb_analysis::store::{MergeIssue, ResultSet, ResultStore};
+use asbb_explorer::reproducibility::{manifest_path_for, RunManifest};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Commit recorded for results whose manifest has none
+const UNKNOWN_COMMIT: &str = "unknown";
+
+/// Options for a merge
+pub struct MergeOptions {
+    /// Directories of results CSVs
+    pub dirs: Vec<PathBuf>,
+
+    /// SQLite store to merge into
+    pub store: PathBuf,
+
+    /// Machine for every set (instead of the manifests)
+    pub machine: Option<String>,
+
+    /// Commit for every set (instead of the manifests)
+    pub commit: Option<String>,
+
+    /// Merge the clean rows despite conflicts and schema mismatches
+    pub skip_conflicts: bool,
+}
+
+pub fn run(options: &MergeOptions) -> Result<()> {
+    let mut sets = Vec::new();
+    for dir in &options.dirs {
+        let mut csvs: Vec<PathBuf> = fs::read_dir(dir)
+            .with_context(|| format!("Failed to read {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "csv"))
+            .collect();
+        csvs.sort();
+
+        for csv in csvs {
+            if is_fleet_table(&csv)? {
+                println!("   ⏭️  {} (combined fleet table)", csv.display());
+                continue;
+            }
+            let manifest = load_manifest(&csv);
+            let machine = options.machine.clone().unwrap_or_else(|| machine_of(&csv, &manifest));
+            let commit = options.commit.clone().unwrap_or_else(|| {
+                manifest
+                    .as_ref()
+                    .and_then(|manifest| manifest.git_commit.clone())
+                    .unwrap_or_else(|| UNKNOWN_COMMIT.to_string())
+            });
+            println!("   📄 {} ({} @ {})", csv.display(), machine, short_commit(&commit));
+            sets.push(ResultSet { source: csv, machine, commit });
+        }
+    }
+    if sets.is_empty() {
+        anyhow::bail!("No results CSVs found");
+    }
+
+    if let Some(parent) = options.store.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut store = ResultStore::open(&options.store)?;
+    println!("🗄️  Merging {} result sets into {}", sets.len(), options.store.display());
+    let report = store.merge(&sets, options.skip_conflicts)?;
+
+    let conflicts = report
+        .issues
+        .iter()
+        .filter(|issue| matches!(issue, MergeIssue::Conflict { .. }))
+        .count();
+    let mismatches = report.issues.len() - conflicts;
+    for issue in &report.issues {
+        println!("   ⚠️  {}", issue);
+    }
+    println!(
+        "   {} inserted, {} duplicates, {} conflicts, {} schema mismatches",
+        report.inserted, report.duplicates, conflicts, mismatches
+    );
+
+    if !report.written {
+        anyhow::bail!(
+            "Merge aborted, nothing written; pass --skip-conflicts to merge the remaining rows"
+        );
+    }
+    println!("✅ Store holds:");
+    for (machine, commit, rows) in store.runs()? {
+        println!("   {} @ {}: {} rows", machine, short_commit(&commit), rows);
+    }
+    Ok(())
+}
+
+/// Whether the CSV is a combined `asbb serve` table (leading `worker` column)
+fn is_fleet_table(csv: &Path) -> Result<bool> {
+    let contents = fs::read_to_string(csv)
+        .with_context(|| format!("Failed to read {}", csv.display()))?;
+    Ok(contents.lines().next().is_some_and(|header| header.starts_with("worker,")))
+}
+
+/// The file's own manifest, else its directory's
+pub(crate) fn load_manifest(csv: &Path) -> Option<RunManifest> {
+    let own = manifest_path_for(csv);
+    let path = if own.exists() { own } else { csv.with_file_name("manifest.json") };
+    path.exists().then(|| RunManifest::load(&path).ok()).flatten()
+}
+
+/// Host name recorded in the manifest, else its chip
+pub(crate) fn recorded_machine(manifest: &Option<RunManifest>) -> Option<String> {
+    manifest.as_ref().and_then(|manifest| {
+        manifest
+            .host
+            .clone()
+            .or_else(|| manifest.hardware.as_ref().map(|hardware| hardware.chip_name()))
+    })
+}
+
+fn machine_of(csv: &Path, manifest: &Option<RunManifest>) -> String {
+    recorded_machine(manifest).unwrap_or_else(|| {
+        csv.file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "unknown".to_string())
+    })
+}
+
+pub(crate) fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(10)]
+}
@@ -0,0 +1,95 @@
+//! `asbb micro`: measure this machine's memory hierarchy once
+//!
+//! Runs the `asbb_micro` microbenchmarks (read bandwidth per core type, copy
+//! bandwidth, load latency by working set, Metal shared-buffer copies, and
+//! with `--storage` file reads by read path) and saves a machine profile.
+//! `asbb compare --baseline-profile/--other-profile` uses two such profiles
+//! to normalize results across chips.
+
+use anyhow::Result;
+use asbb_core::HardwareProfile;
+use asbb_micro::storage::ReadPath;
+use asbb_micro::{run_with_progress, MicroOptions};
+use std::path::PathBuf;
+
+/// Options for a microbenchmark run
+pub struct MicroRunOptions {
+    /// Where to save the machine profile (JSON)
+    pub output: PathBuf,
+
+    /// Smaller buffers and fewer loads (seconds instead of minutes)
+    pub quick: bool,
+
+    /// File to measure read paths on
+    pub storage: Option<PathBuf>,
+
+    /// AIO queue depths to sweep
+    pub queue_depths: Vec<usize>,
+}
+
+pub fn run(options: &MicroRunOptions) -> Result<()> {
+    let mut micro = if options.quick {
+        MicroOptions::quick()
+    } else {
+        MicroOptions::default()
+    };
+    micro.storage_file = options.storage.clone();
+    micro.storage_queue_depths = options.queue_depths.clone();
+
+    println!("🔬 Profiling memory hierarchy");
+    println!("   Platform: {}", crate::bench::platform());
+    println!(
+        "   Buffer: {} MiB, {} passes, {} threads",
+        micro.buffer_bytes >> 20,
+        micro.passes,
+        micro.threads
+    );
+    if let Some(path) = &micro.storage_file {
+        println!(
+            "   Storage: {} (AIO queue depths {:?})",
+            path.display(),
+            micro.storage_queue_depths
+        );
+    }
+    println!();
+
+    let profile = run_with_progress(&micro, |step| println!("   {}", step))?;
+    profile.save(&options.output)?;
+
+    // Measured vs published peak for the detected chip and bin
+    if let (Ok(hardware), Some(measured)) = (HardwareProfile::detect(), profile.all_core_read_gbps()) {
+        println!();
+        if hardware.memory_bandwidth_gbps == 0.0 {
+            println!("📐 No published bandwidth for {}", hardware.chip_name());
+        } else {
+            println!(
+                "📐 Published bandwidth of {}: {:.0} GB/s (measured {:.0}%)",
+                hardware.chip_name(),
+                hardware.memory_bandwidth_gbps,
+                measured / hardware.memory_bandwidth_gbps * 100.0
+            );
+        }
+    }
+
+    if let Some(storage) = &profile.storage {
+        println!();
+        println!("💾 Read paths over {:.1} MiB:", storage.file_bytes as f64 / (1 << 20) as f64);
+        let baseline = storage.gbps(ReadPath::Buffered).unwrap_or(0.0);
+        for read in &storage.reads {
+            let relative = if baseline > 0.0 { read.gbps / baseline } else { 0.0 };
+            println!(
+                "   {:<10} {:>7.2} GB/s ({:.2}× buffered)",
+                read.path.label(),
+                read.gbps,
+                relative
+            );
+        }
+        if let Some(scaling) = storage.aio_scaling() {
+            println!("   AIO scaling over one request in flight: {:.2}×", scaling);
+        }
+    }
+
+    println!();
+    println!("📄 Wrote machine profile for {} to {}", profile.machine, options.output.display());
+    Ok(())
+}
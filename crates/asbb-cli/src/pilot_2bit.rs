@@ -13,13 +13,22 @@
 //!
 //! **Approach**: Agnostic observation, no anchoring to prior results.
 //!
+//! The default mode times kernels on pre-encoded data, which overstates the
+//! benefit for one-shot workloads. `--amortization` also measures the
+//! `BitSeq::from_ascii` conversion and reports how many passes over the
+//! encoded data are needed before 2-bit wins end-to-end. `--encode` times
+//! the conversion itself: scalar, NEON and `encode_batch` across threads.
+//!
 //! Run in release mode:
 //! ```bash
 //! cargo run --release -p asbb-cli --bin asbb-pilot-2bit
+//! cargo run --release -p asbb-cli --bin asbb-pilot-2bit -- --amortization
+//! cargo run --release -p asbb-cli --bin asbb-pilot-2bit -- --encode
 //! ```
 
 use anyhow::{Context, Result};
-use asbb_core::{encoding::BitSeq, PrimitiveOperation, SequenceRecord};
+use asbb_core::{encoding::{encode_batch, BitSeq}, OperationOutput, PrimitiveOperation, SequenceRecord};
+use asbb_explorer::amortization::measure_encoding_amortization;
 use asbb_ops::{
     at_content::ATContent,
     base_counting::BaseCounting,
@@ -74,6 +83,13 @@ const SCALES: &[DatasetScale] = &[
 ];
 
 fn main() -> Result<()> {
+    if std::env::args().any(|arg| arg == "--amortization") {
+        return run_amortization();
+    }
+    if std::env::args().any(|arg| arg == "--encode") {
+        return run_encode_benchmark();
+    }
+
     println!("╔════════════════════════════════════════════════════════════════════╗");
     println!("║        Phase 2: 2-Bit Encoding Experiments                        ║");
     println!("║        Comparing ASCII vs 2-Bit Performance                        ║");
@@ -484,6 +500,153 @@ fn test_sequence_length_encoding() -> Result<()> {
     Ok(())
 }
 
+/// 2-bit NEON entry point of an operation
+type TwoBitKernel = fn(&[BitSeq]) -> Result<OperationOutput>;
+
+/// 2-bit NEON kernel for a 2-bit compatible operation
+fn two_bit_kernel(name: &str) -> Result<TwoBitKernel> {
+    Ok(match name {
+        "base_counting" => |data| BaseCounting::new().execute_2bit_neon(data),
+        "gc_content" => |data| GcContent::new().execute_2bit_neon(data),
+        "at_content" => |data| ATContent.execute_2bit_neon(data),
+        "reverse_complement" => |data| ReverseComplement::new().execute_2bit_neon(data),
+        "sequence_length" => |data| SequenceLength.execute_2bit_neon(data),
+        _ => anyhow::bail!("No 2-bit kernel for {}", name),
+    })
+}
+
+/// Encoding amortization: conversion cost and break-even reuse counts
+fn run_amortization() -> Result<()> {
+    println!("╔════════════════════════════════════════════════════════════════════╗");
+    println!("║        2-Bit Encoding Amortization                                 ║");
+    println!("║        Conversion cost vs per-pass savings                         ║");
+    println!("╚════════════════════════════════════════════════════════════════════╝");
+    println!();
+
+    let operations: Vec<Box<dyn PrimitiveOperation>> = vec![
+        Box::new(ReverseComplement::new()),
+        Box::new(BaseCounting::new()),
+        Box::new(GcContent::new()),
+        Box::new(ATContent),
+        Box::new(SequenceLength),
+    ];
+
+    for scale in SCALES {
+        let ascii_records = load_fastq(scale.path)
+            .with_context(|| format!("Failed to load {}", scale.path))?;
+        if ascii_records.is_empty() {
+            println!("⚠️  Skipping {} (file not found or empty)", scale.name);
+            println!();
+            continue;
+        }
+
+        println!("📦 Scale: {} ({} sequences)", scale.name, ascii_records.len());
+        println!(
+            "   {:<20} {:>10} {:>10} {:>10} {:>9} {:>9} {:>11}",
+            "Operation", "Encode", "ASCII", "2-bit", "Compute", "1-pass", "Break-even"
+        );
+
+        for op in &operations {
+            let kernel = two_bit_kernel(op.name())?;
+            let result = measure_encoding_amortization(
+                op.name(),
+                &ascii_records,
+                |records| {
+                    Ok(records.iter().map(|r| BitSeq::from_ascii(&r.sequence)).collect::<Vec<_>>())
+                },
+                |records| op.execute_neon(records),
+                |encoded| kernel(encoded),
+                1,
+                5,
+            )?;
+
+            let break_even = match result.break_even_passes() {
+                Some(passes) => format!("{} passes", passes),
+                None => "never".to_string(),
+            };
+            println!(
+                "   {:<20} {:>8.3}ms {:>8.3}ms {:>8.3}ms {:>8.2}× {:>8.2}× {:>11}",
+                op.name(),
+                result.encode_time.as_secs_f64() * 1000.0,
+                result.ascii_compute.as_secs_f64() * 1000.0,
+                result.encoded_compute.as_secs_f64() * 1000.0,
+                result.compute_only_speedup(),
+                result.end_to_end_speedup(1),
+                break_even
+            );
+        }
+        println!();
+    }
+
+    println!("Compute = 2-bit vs ASCII NEON excluding conversion (what the default mode reports)");
+    println!("1-pass  = end-to-end for a one-shot workload (encode + one 2-bit pass)");
+
+    Ok(())
+}
+
+/// ASCII → 2-bit conversion throughput: scalar, NEON, NEON across threads
+fn run_encode_benchmark() -> Result<()> {
+    println!("╔════════════════════════════════════════════════════════════════════╗");
+    println!("║        2-Bit Encoding Conversion                                   ║");
+    println!("║        Scalar vs NEON vs encode_batch                              ║");
+    println!("╚════════════════════════════════════════════════════════════════════╝");
+    println!();
+
+    const RUNS: usize = 5;
+    let median_time = |encode: &dyn Fn() -> Vec<BitSeq>| {
+        let mut times: Vec<f64> = (0..RUNS)
+            .map(|_| {
+                let start = Instant::now();
+                std::hint::black_box(encode());
+                start.elapsed().as_secs_f64()
+            })
+            .collect();
+        times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        times[RUNS / 2]
+    };
+
+    for scale in SCALES {
+        let records = load_fastq(scale.path)
+            .with_context(|| format!("Failed to load {}", scale.path))?;
+        if records.is_empty() {
+            println!("⚠️  Skipping {} (file not found or empty)", scale.name);
+            println!();
+            continue;
+        }
+        let bases: usize = records.iter().map(|r| r.sequence.len()).sum();
+
+        println!("📦 Scale: {} ({} sequences)", scale.name, records.len());
+        println!("   {:<18} {:>10} {:>12} {:>9}", "Method", "Time", "Mbases/s", "Speedup");
+
+        let scalar = median_time(&|| records.iter().map(|r| BitSeq::from_ascii_scalar(&r.sequence)).collect());
+        let mut methods = vec![
+            ("scalar".to_string(), scalar),
+            ("neon".to_string(), median_time(&|| encode_batch(&records, 1))),
+        ];
+        for threads in [2, 4, 8] {
+            methods.push((
+                format!("neon + {}t", threads),
+                median_time(&|| encode_batch(&records, threads)),
+            ));
+        }
+
+        for (method, seconds) in &methods {
+            println!(
+                "   {:<18} {:>8.3}ms {:>12.1} {:>8.2}×",
+                method,
+                seconds * 1000.0,
+                bases as f64 / seconds / 1e6,
+                scalar / seconds
+            );
+        }
+        println!();
+    }
+
+    println!("neon = BitSeq::from_ascii (scalar fallback on non-aarch64 hosts)");
+
+    Ok(())
+}
+
 /// Load FASTQ file into SequenceRecords
 fn load_fastq(path: &str) -> Result<Vec<SequenceRecord>> {
     let file_path = Path::new(path);
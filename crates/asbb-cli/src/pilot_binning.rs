@@ -0,0 +1,197 @@
+//! Quality Binning Pilot
+//!
+//! Quantifies the accuracy-vs-throughput trade-off of Illumina-style binned
+//! quality scores (8 and 4 levels) on real FASTQ files:
+//!
+//! - `bin`: converting the records' qualities (scalar vs NEON table lookup)
+//! - `quality_statistics`: full-precision gather-and-sort vs
+//!   `quality_statistics_binned` (bin-code histograms); accuracy is the
+//!   largest per-position error of the mean and median
+//! - `quality_filter` (mean Q20, expected errors ≤ 1): on full scores, on
+//!   pre-binned records and binning on the fly; accuracy is the fraction of
+//!   reads whose pass/fail decision is unchanged
+//!
+//! Usage: `asbb-pilot-binning [FASTQ...]` (defaults to the synthetic datasets).
+//! CSV rows go to stdout, progress to stderr.
+
+use anyhow::Result;
+use asbb_core::io::FastqReader;
+use asbb_core::quality_binning::{bin_records, BinningTable, QualityBinning};
+use asbb_core::{OperationOutput, PrimitiveOperation, SequenceRecord};
+use asbb_ops::quality_filter::{QualityFilter, QualityFilterResult};
+use asbb_ops::quality_statistics::{BinnedQualityStatistics, QualityStatistics, QualityStatisticsResult};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 10;
+
+fn median(mut durations: Vec<Duration>) -> Duration {
+    durations.sort();
+    durations[durations.len() / 2]
+}
+
+/// Median time of `RUNS` calls to `f`
+fn time_median<T>(mut f: impl FnMut() -> Result<T>) -> Result<Duration> {
+    let mut times = Vec::with_capacity(RUNS);
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        std::hint::black_box(f()?);
+        times.push(start.elapsed());
+    }
+    Ok(median(times))
+}
+
+fn statistics(output: OperationOutput) -> Result<QualityStatisticsResult> {
+    output
+        .statistics::<QualityStatisticsResult>()
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Expected quality statistics"))
+}
+
+/// Largest per-position |mean| and |median| differences
+fn statistics_error(full: &QualityStatisticsResult, binned: &QualityStatisticsResult) -> (f64, f64) {
+    full.per_position
+        .iter()
+        .zip(&binned.per_position)
+        .fold((0.0f64, 0.0f64), |(mean, median), (f, b)| {
+            (mean.max((f.mean - b.mean).abs()), median.max((f.median - b.median).abs()))
+        })
+}
+
+/// Fraction of reads `op` judges the same on `records` and on their binned copies
+fn decision_agreement(op: &QualityFilter, records: &[SequenceRecord], binned: &[SequenceRecord]) -> Result<f64> {
+    let passed = |record: &SequenceRecord| -> Result<bool> {
+        let output = op.execute_naive(std::slice::from_ref(record))?;
+        Ok(output.statistics::<QualityFilterResult>().is_some_and(|r| r.passed_sequences == 1))
+    };
+
+    let mut same = 0usize;
+    for (full, binned) in records.iter().zip(binned) {
+        same += usize::from(passed(full)? == passed(binned)?);
+    }
+    Ok(same as f64 / records.len().max(1) as f64)
+}
+
+fn row(
+    file: &str,
+    binning: QualityBinning,
+    operation: &str,
+    variant: &str,
+    time: Duration,
+    baseline: Duration,
+    accuracy: Option<(&str, f64)>,
+) {
+    let (metric, value) = accuracy.unwrap_or(("", 0.0));
+    println!(
+        "{},{},{},{},{},{:.3},{:.3},{},{:.4}",
+        file,
+        binning.name(),
+        binning.bits_per_score(),
+        operation,
+        variant,
+        time.as_secs_f64() * 1000.0,
+        baseline.as_secs_f64() / time.as_secs_f64(),
+        metric,
+        value
+    );
+}
+
+fn run_file(path: &Path) -> Result<()> {
+    eprintln!("Running: {}", path.display());
+
+    let records = FastqReader::from_path(path)?.read_all()?;
+    let file = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+
+    let full_statistics = QualityStatistics::new();
+    let full_stats_time = time_median(|| full_statistics.execute_neon(&records))?;
+    let full_stats = statistics(full_statistics.execute_neon(&records)?)?;
+
+    for binning in QualityBinning::ALL {
+        let table = BinningTable::new(binning);
+        eprintln!("  {} ({} bits/score):", binning.name(), binning.bits_per_score());
+
+        // Conversion
+        let bin_naive = time_median(|| Ok(bin_records(&records, &table, false)))?;
+        let bin_neon = time_median(|| Ok(bin_records(&records, &table, true)))?;
+        row(&file, binning, "bin", "naive", bin_naive, bin_naive, None);
+        row(&file, binning, "bin", "neon", bin_neon, bin_naive, None);
+        eprintln!("    bin: naive={:.2}ms neon={:.2}ms", ms(bin_naive), ms(bin_neon));
+        let binned = bin_records(&records, &table, true);
+
+        // Per-position statistics
+        let op = BinnedQualityStatistics::new(binning);
+        let binned_time = time_median(|| op.execute_neon(&binned))?;
+        let (mean_error, median_error) = statistics_error(&full_stats, &statistics(op.execute_neon(&binned)?)?);
+        row(&file, binning, "quality_statistics", "full", full_stats_time, full_stats_time, None);
+        row(&file, binning, "quality_statistics", "binned", binned_time, full_stats_time, Some(("max_mean_error", mean_error)));
+        row(&file, binning, "quality_statistics", "binned", binned_time, full_stats_time, Some(("max_median_error", median_error)));
+        eprintln!(
+            "    quality_statistics: full={:.2}ms binned={:.2}ms ({:.2}×), max error mean={:.2} median={:.2}",
+            ms(full_stats_time),
+            ms(binned_time),
+            full_stats_time.as_secs_f64() / binned_time.as_secs_f64(),
+            mean_error,
+            median_error
+        );
+
+        // Filter decisions
+        let filters = [
+            ("mean_q20", QualityFilter::new(20 + 33), QualityFilter::new(20 + 33).with_binning(binning)),
+            (
+                "expected_errors_1",
+                QualityFilter::expected_errors(1.0),
+                QualityFilter::expected_errors(1.0).with_binning(binning),
+            ),
+        ];
+        for (label, plain, on_the_fly) in &filters {
+            let operation = format!("quality_filter_{}", label);
+            let full_time = time_median(|| plain.execute_neon(&records))?;
+            let prebinned_time = time_median(|| plain.execute_neon(&binned))?;
+            let on_the_fly_time = time_median(|| on_the_fly.execute_neon(&records))?;
+            let agreement = decision_agreement(plain, &records, &binned)?;
+
+            row(&file, binning, &operation, "full", full_time, full_time, None);
+            row(&file, binning, &operation, "prebinned", prebinned_time, full_time, Some(("decision_agreement", agreement)));
+            row(&file, binning, &operation, "bin_on_the_fly", on_the_fly_time, full_time, Some(("decision_agreement", agreement)));
+            eprintln!(
+                "    {}: full={:.2}ms prebinned={:.2}ms on-the-fly={:.2}ms, same decision {:.2}%",
+                operation,
+                ms(full_time),
+                ms(prebinned_time),
+                ms(on_the_fly_time),
+                agreement * 100.0
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    eprintln!("=== Quality Binning Pilot ===");
+    eprintln!("Full-precision vs Illumina 8/4-level binned qualities");
+    eprintln!();
+
+    let mut paths: Vec<PathBuf> = std::env::args().skip(1).map(PathBuf::from).collect();
+    if paths.is_empty() {
+        paths = vec![
+            PathBuf::from("datasets/medium_10000_150bp.fq"),
+            PathBuf::from("datasets/large_100000_150bp.fq"),
+        ];
+    }
+
+    // CSV header
+    println!("file,binning,bits_per_score,operation,variant,time_ms,speedup_vs_full,accuracy_metric,accuracy_value");
+
+    for path in &paths {
+        if let Err(e) = run_file(path) {
+            eprintln!("ERROR: {}: {}", path.display(), e);
+        }
+    }
+
+    eprintln!();
+    eprintln!("=== Pilot Complete ===");
+
+    Ok(())
+}
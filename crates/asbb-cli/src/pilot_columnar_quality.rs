@@ -0,0 +1,129 @@
+//! Columnar Quality Pilot
+//!
+//! Tests whether storing qualities position-major (`QualityColumns`,
+//! positions × reads) removes the per-position gather that
+//! `quality_statistics` does record-by-record on `Vec<SequenceRecord>`:
+//!
+//! - `records`: `execute_neon` (gather per position, then statistics)
+//! - `transpose`: building `QualityColumns` from the records (one-off cost)
+//! - `columnar`: `execute_columnar` on prebuilt columns
+//! - `columnar+transpose`: both, i.e. the cost when columns are not reused
+//!
+//! plus the parallel variants of `records` and `columnar`.
+//!
+//! Usage: `asbb-pilot-columnar [FASTQ...]` (defaults to the synthetic datasets).
+//! CSV rows go to stdout, progress to stderr.
+
+use anyhow::Result;
+use asbb_core::columnar::QualityColumns;
+use asbb_core::io::FastqReader;
+use asbb_core::PrimitiveOperation;
+use asbb_ops::quality_statistics::QualityStatistics;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 10;
+const THREADS: usize = 4;
+
+fn median(mut durations: Vec<Duration>) -> Duration {
+    durations.sort();
+    durations[durations.len() / 2]
+}
+
+/// Median time of `RUNS` calls to `f`
+fn time_median<T>(mut f: impl FnMut() -> Result<T>) -> Result<Duration> {
+    let mut times = Vec::with_capacity(RUNS);
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        std::hint::black_box(f()?);
+        times.push(start.elapsed());
+    }
+    Ok(median(times))
+}
+
+fn run_file(path: &Path) -> Result<()> {
+    eprintln!("Running: {}", path.display());
+
+    let records = FastqReader::from_path(path)?.read_all()?;
+    let columns = QualityColumns::from_records(&records);
+    let op = QualityStatistics::new();
+
+    if op.execute_columnar(&columns)? != op.execute_neon(&records)? {
+        anyhow::bail!("Columnar statistics differ from the record-major result");
+    }
+
+    let on_records = time_median(|| op.execute_neon(&records))?;
+    let transpose = time_median(|| Ok(QualityColumns::from_records(&records)))?;
+    let on_columns = time_median(|| op.execute_columnar(&columns))?;
+    let parallel_records = time_median(|| op.execute_parallel(&records, THREADS))?;
+    let parallel_columns = time_median(|| op.execute_columnar_parallel(&columns, THREADS))?;
+
+    let file = path.file_name().unwrap_or_default().to_string_lossy();
+    let rows = [
+        ("records", 1, on_records),
+        ("transpose", 1, transpose),
+        ("columnar", 1, on_columns),
+        ("columnar+transpose", 1, on_columns + transpose),
+        ("records", THREADS, parallel_records),
+        ("columnar", THREADS, parallel_columns),
+    ];
+    for (layout, threads, time) in rows {
+        println!(
+            "{},{},{},{},{},{:.3},{:.3}",
+            file,
+            layout,
+            threads,
+            records.len(),
+            columns.num_positions(),
+            time.as_secs_f64() * 1000.0,
+            on_records.as_secs_f64() / time.as_secs_f64()
+        );
+    }
+
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+    eprintln!(
+        "  1 thread: records={:.2}ms columnar={:.2}ms ({:.2}×) transpose={:.2}ms (with transpose {:.2}×)",
+        ms(on_records),
+        ms(on_columns),
+        on_records.as_secs_f64() / on_columns.as_secs_f64(),
+        ms(transpose),
+        on_records.as_secs_f64() / (on_columns + transpose).as_secs_f64()
+    );
+    eprintln!(
+        "  {} threads: records={:.2}ms columnar={:.2}ms ({:.2}×)",
+        THREADS,
+        ms(parallel_records),
+        ms(parallel_columns),
+        parallel_records.as_secs_f64() / parallel_columns.as_secs_f64()
+    );
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    eprintln!("=== Columnar Quality Pilot ===");
+    eprintln!("quality_statistics: record-major gather vs position-major columns");
+    eprintln!();
+
+    let mut paths: Vec<PathBuf> = std::env::args().skip(1).map(PathBuf::from).collect();
+    if paths.is_empty() {
+        paths = vec![
+            PathBuf::from("datasets/medium_10000_150bp.fq"),
+            PathBuf::from("datasets/large_100000_150bp.fq"),
+        ];
+    }
+
+    // CSV header
+    println!("file,layout,threads,num_sequences,num_positions,time_ms,speedup_vs_records");
+
+    for path in &paths {
+        if let Err(e) = run_file(path) {
+            eprintln!("ERROR: {}: {}", path.display(), e);
+        }
+    }
+
+    eprintln!();
+    eprintln!("=== Pilot Complete ===");
+
+    Ok(())
+}
@@ -162,7 +162,7 @@ fn test_base_counting_gpu() -> Result<()> {
         };
 
         #[cfg(not(all(target_os = "macos", feature = "gpu")))]
-        let (gpu_result, gpu_metrics) = {
+        let _ = {
             println!("⚠️  GPU support not enabled (compile with --features gpu)");
             continue;
         };
@@ -191,6 +191,9 @@ fn test_base_counting_gpu() -> Result<()> {
             println!("    └─ Kernel:      {:>10.3} ms  ({:.1}% of total)",
                      gpu_metrics.kernel_time_ms,
                      gpu_metrics.kernel_time_ms / total_ms * 100.0);
+            if gpu_metrics.num_batches > 1 {
+                println!("    (split into {} batches to fit the GPU working set)", gpu_metrics.num_batches);
+            }
             println!();
             println!("🎯 GPU vs Naive:    {:.2}× {}",
                      gpu_speedup_vs_naive,
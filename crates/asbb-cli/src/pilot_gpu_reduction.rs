@@ -0,0 +1,148 @@
+//! GPU Reduction Pilot: CPU vs GPU Reduce
+//!
+//! The GPU counting kernels write one result per sequence; the CPU reads
+//! them back and sums them. This pilot measures whether that readback and
+//! CPU reduce matter, by comparing against a two-pass on-GPU reduction
+//! (per-threadgroup SIMD sums, then a single-threadgroup fold) that reads
+//! back 32 bytes regardless of scale.
+//!
+//! **Research Questions**:
+//! 1. What fraction of GPU time is readback + CPU reduce at each scale?
+//! 2. Does the on-GPU reduction pay for its second dispatch at Huge scale?
+//!
+//! **Operation**: Base counting (4 counters per sequence, the largest readback)
+//!
+//! Run in release mode with GPU feature:
+//! ```bash
+//! cargo run --release --features gpu -p asbb-cli --bin asbb-pilot-gpu-reduction
+//! ```
+
+use anyhow::Result;
+#[cfg(all(target_os = "macos", feature = "gpu"))]
+use {
+    anyhow::Context,
+    asbb_core::{io::FastqReader, SequenceRecord},
+    asbb_ops::base_counting::BaseCounting,
+    std::path::Path,
+};
+
+/// Dataset scale definition for reduction testing
+#[cfg(all(target_os = "macos", feature = "gpu"))]
+#[derive(Debug, Clone)]
+struct ReductionScale {
+    name: &'static str,
+    path: &'static str,
+    num_sequences: usize,
+}
+
+#[cfg(all(target_os = "macos", feature = "gpu"))]
+const SCALES: &[ReductionScale] = &[
+    ReductionScale { name: "Medium", path: "datasets/medium_10k_150bp.fq", num_sequences: 10_000 },
+    ReductionScale { name: "Large", path: "datasets/large_100k_150bp.fq", num_sequences: 100_000 },
+    ReductionScale { name: "VeryLarge", path: "datasets/vlarge_1m_150bp.fq", num_sequences: 1_000_000 },
+    ReductionScale { name: "Huge", path: "datasets/huge_10m_150bp.fq", num_sequences: 10_000_000 },
+];
+
+/// Repetitions per path (median reported)
+#[cfg(all(target_os = "macos", feature = "gpu"))]
+const REPETITIONS: usize = 5;
+
+fn main() -> Result<()> {
+    println!("╔════════════════════════════════════════════════════════════════════╗");
+    println!("║        GPU Reduction Pilot: CPU Reduce vs GPU Reduce               ║");
+    println!("╚════════════════════════════════════════════════════════════════════╝");
+    println!();
+    println!("🔬 Operation: Base Counting");
+    println!("🎯 Goal: Measure readback + CPU reduce cost against an on-GPU reduction");
+    println!();
+
+    #[cfg(not(all(target_os = "macos", feature = "gpu")))]
+    {
+        println!("⚠️  GPU support not enabled (compile with --features gpu on macOS)");
+        Ok(())
+    }
+
+    #[cfg(all(target_os = "macos", feature = "gpu"))]
+    {
+        let op = BaseCounting::new();
+        let mut rows = Vec::new();
+
+        for scale in SCALES {
+            println!("═══════════════════════════════════════════════════════════════");
+            println!("📦 Scale: {} ({} sequences)", scale.name, scale.num_sequences);
+            println!("═══════════════════════════════════════════════════════════════");
+
+            let records = load(scale)?;
+            if records.is_empty() {
+                println!("   ⚠️  Skipping {} (file not found or empty)", scale.name);
+                println!();
+                continue;
+            }
+            println!("📝 Loaded: {} sequences", records.len());
+
+            let (cpu_counts, cpu) = median_run(|| op.execute_gpu(&records), |m| m.total_time_ms + m.reduce_ms)?;
+            let (gpu_counts, gpu) = median_run(|| op.execute_gpu_reduced(&records), |m| m.total_time_ms + m.reduce_ms)?;
+            if cpu_counts != gpu_counts {
+                anyhow::bail!("Reduction mismatch at {}: {:?} vs {:?}", scale.name, cpu_counts, gpu_counts);
+            }
+
+            for (path, metrics) in [("cpu_reduce", &cpu), ("gpu_reduce", &gpu)] {
+                let end_to_end = metrics.total_time_ms + metrics.reduce_ms;
+                println!(
+                    "⏱️  {:10}  dispatch {:>9.3} ms  reduce {:>9.3} ms  ({:4.1}%)  total {:>9.3} ms",
+                    path,
+                    metrics.total_time_ms,
+                    metrics.reduce_ms,
+                    metrics.reduce_ms / end_to_end * 100.0,
+                    end_to_end
+                );
+                rows.push(format!(
+                    "{},{},{},{:.4},{:.4},{:.4},{}",
+                    scale.name,
+                    records.len(),
+                    path,
+                    metrics.total_time_ms,
+                    metrics.reduce_ms,
+                    end_to_end,
+                    metrics.num_batches
+                ));
+            }
+
+            let speedup = (cpu.total_time_ms + cpu.reduce_ms) / (gpu.total_time_ms + gpu.reduce_ms);
+            println!(
+                "🎯 GPU reduce vs CPU reduce: {:.2}× {}",
+                speedup,
+                if speedup > 1.0 { "FASTER ✅" } else { "slower" }
+            );
+            println!("✅ Validation: counts match");
+            println!();
+        }
+
+        println!("scale,num_sequences,reduction,dispatch_ms,reduce_ms,total_ms,num_batches");
+        for row in rows {
+            println!("{}", row);
+        }
+        Ok(())
+    }
+}
+
+/// Run `f` [`REPETITIONS`] times, keeping the run with the median time
+#[cfg(all(target_os = "macos", feature = "gpu"))]
+fn median_run<T, M>(mut f: impl FnMut() -> Result<(T, M)>, time_ms: impl Fn(&M) -> f64) -> Result<(T, M)> {
+    let mut runs = (0..REPETITIONS).map(|_| f()).collect::<Result<Vec<_>>>()?;
+    runs.sort_by(|a, b| time_ms(&a.1).total_cmp(&time_ms(&b.1)));
+    Ok(runs.swap_remove(REPETITIONS / 2))
+}
+
+/// Load up to `num_sequences` records, or none if the dataset is missing
+#[cfg(all(target_os = "macos", feature = "gpu"))]
+fn load(scale: &ReductionScale) -> Result<Vec<SequenceRecord>> {
+    if !Path::new(scale.path).exists() {
+        return Ok(Vec::new());
+    }
+    let mut records = FastqReader::from_path(scale.path)
+        .with_context(|| format!("Failed to open {}", scale.path))?
+        .read_all()?;
+    records.truncate(scale.num_sequences);
+    Ok(records)
+}
@@ -0,0 +1,191 @@
+//! Hamming Distance Encoding Pilot
+//!
+//! Tests whether 2-bit encoding buys an algorithmic speedup, not just a
+//! memory saving. ASCII Hamming distance compares one byte per base (16 bases
+//! per NEON vector); bit-plane packing (`asbb_ops::hamming_distance::BitPlanes`)
+//! turns a mismatch into one bit, so XOR + popcount compares 64 bases per u64
+//! word and 128 per NEON `vcntq_u8`.
+//!
+//! **Research Questions**:
+//! 1. How much faster is bit-parallel XOR+popcount than byte-wise NEON?
+//! 2. Does the packing cost (paid once per sequence) amortise over N² pairs?
+//! 3. At what N does the all-vs-all Metal kernel overtake NEON?
+//!
+//! **Backends**: ASCII naive, ASCII NEON, bit planes scalar popcount, bit
+//! planes NEON popcount, bit planes NEON 4 threads, bit planes Metal
+//! (`--features gpu` on macOS, otherwise reported as unavailable). Packing is
+//! timed separately (`pack_ms`) and every result is checked against naive.
+//!
+//! Run in release mode:
+//! ```bash
+//! cargo run --release -p asbb-cli --features gpu --bin asbb-pilot-hamming \
+//!   [datasets/medium_10k_150bp.fq] > results/hamming_encoding_raw.csv
+//! ```
+
+use anyhow::Result;
+use asbb_core::io::FastqReader;
+use asbb_core::{OperationOutput, PrimitiveOperation, SequenceRecord};
+use asbb_ops::hamming_distance::{BitPlanes, HammingDistance, HammingDistanceResult};
+use std::time::Instant;
+
+const DEFAULT_DATASET: &str = "datasets/medium_10k_150bp.fq";
+
+/// Sequences compared (N×N matrices)
+const SCALES: &[usize] = &[500, 1_000, 2_000];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    AsciiNaive,
+    AsciiNeon,
+    PlanesScalar,
+    PlanesNeon,
+    PlanesParallel(usize),
+    PlanesGpu,
+}
+
+impl Backend {
+    fn name(&self) -> String {
+        match self {
+            Backend::AsciiNaive => "ascii_naive".to_string(),
+            Backend::AsciiNeon => "ascii_neon".to_string(),
+            Backend::PlanesScalar => "2bit_scalar".to_string(),
+            Backend::PlanesNeon => "2bit_neon".to_string(),
+            Backend::PlanesParallel(threads) => format!("2bit_neon_{}t", threads),
+            Backend::PlanesGpu => "2bit_gpu".to_string(),
+        }
+    }
+
+    fn is_packed(&self) -> bool {
+        !matches!(self, Backend::AsciiNaive | Backend::AsciiNeon)
+    }
+}
+
+const BACKENDS: &[Backend] = &[
+    Backend::AsciiNaive,
+    Backend::AsciiNeon,
+    Backend::PlanesScalar,
+    Backend::PlanesNeon,
+    Backend::PlanesParallel(4),
+    Backend::PlanesGpu,
+];
+
+fn main() -> Result<()> {
+    let dataset = std::env::args().nth(1).unwrap_or_else(|| DEFAULT_DATASET.to_string());
+
+    eprintln!("╔════════════════════════════════════════════════════════════════════╗");
+    eprintln!("║          Hamming Distance Encoding Pilot                           ║");
+    eprintln!("╚════════════════════════════════════════════════════════════════════╝");
+    eprintln!();
+    eprintln!("📂 Dataset: {}", dataset);
+    eprintln!("📊 Backends: {:?}", BACKENDS.iter().map(|b| b.name()).collect::<Vec<_>>());
+    eprintln!("📏 Scales: {:?} sequences", SCALES);
+    eprintln!();
+
+    // Hamming distance needs equal lengths: trim reads to the median length
+    // and drop shorter ones
+    let all = FastqReader::from_path(&dataset)?.read_all()?;
+    let mut lengths: Vec<usize> = all.iter().map(|r| r.sequence.len()).collect();
+    lengths.sort_unstable();
+    let length = lengths.get(lengths.len() / 2).copied().unwrap_or(0);
+    let all: Vec<SequenceRecord> = all
+        .into_iter()
+        .filter(|r| r.sequence.len() >= length)
+        .map(|mut r| {
+            r.sequence.truncate(length);
+            if let Some(quality) = r.quality.as_mut() {
+                quality.truncate(length);
+            }
+            r
+        })
+        .collect();
+    eprintln!("📐 {} reads of {} bp", all.len(), length);
+
+    let op = HammingDistance::new();
+
+    println!("operation,num_sequences,backend,time_ms,pack_ms,bytes_per_sequence,speedup_vs_naive,speedup_vs_neon,matches_naive,status");
+
+    for &scale in SCALES {
+        if scale > all.len() {
+            eprintln!("⚠️  Skipping {} sequences (dataset has {})", scale, all.len());
+            continue;
+        }
+        let data = &all[..scale];
+        eprintln!("\n  Scale: {} sequences", scale);
+
+        let start = Instant::now();
+        let planes: Vec<BitPlanes> = data.iter().map(|r| BitPlanes::from_ascii(&r.sequence)).collect();
+        let pack_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let mut reference: Option<Vec<Vec<usize>>> = None;
+        let mut naive_ms = None;
+        let mut neon_ms = None;
+
+        for &backend in BACKENDS {
+            let name = backend.name();
+            eprint!("    {:16} ... ", name);
+
+            let start = Instant::now();
+            let output = match backend {
+                Backend::AsciiNaive => op.execute_naive(data),
+                Backend::AsciiNeon => op.execute_neon(data),
+                Backend::PlanesScalar => op.execute_planes_naive(&planes),
+                Backend::PlanesNeon => op.execute_planes_neon(&planes, 1),
+                Backend::PlanesParallel(threads) => op.execute_planes_neon(&planes, threads),
+                Backend::PlanesGpu => op.execute_gpu(data, scale),
+            };
+            let time_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+            let distances = match output.and_then(distances) {
+                Ok(distances) => distances,
+                Err(e) => {
+                    eprintln!("unavailable ({})", e);
+                    println!("hamming_distance,{},{},,,,,,,unavailable", scale, name);
+                    continue;
+                }
+            };
+
+            let matches_naive = *reference.get_or_insert_with(|| distances.clone()) == distances;
+            match backend {
+                Backend::AsciiNaive => naive_ms = Some(time_ms),
+                Backend::AsciiNeon => neon_ms = Some(time_ms),
+                _ => {}
+            }
+            let vs_naive = naive_ms.map_or(1.0, |t| t / time_ms);
+            let vs_neon = neon_ms.map_or(1.0, |t| t / time_ms);
+
+            // GPU packs inside execute_gpu, so its time already includes packing.
+            // Bytes count the lo/hi planes (the ambiguity plane is only stored
+            // for reads with N).
+            let (pack, bytes) = if backend.is_packed() {
+                let words = planes.first().map_or(0, |p| p.words());
+                (if backend == Backend::PlanesGpu { 0.0 } else { pack_ms }, words * 2 * 8)
+            } else {
+                (0.0, length)
+            };
+
+            eprintln!(
+                "{:10.2}ms  ({:.2}× vs naive, {:.2}× vs NEON){}",
+                time_ms,
+                vs_naive,
+                vs_neon,
+                if matches_naive { "" } else { "  ❌ mismatch" }
+            );
+            println!(
+                "hamming_distance,{},{},{:.6},{:.6},{},{:.4},{:.4},{},ok",
+                scale, name, time_ms, pack, bytes, vs_naive, vs_neon, matches_naive
+            );
+        }
+
+        eprintln!("    📦 Packing {} sequences: {:.3}ms", scale, pack_ms);
+    }
+
+    eprintln!("\n✅ Hamming encoding pilot complete (CSV on stdout)");
+    Ok(())
+}
+
+fn distances(output: OperationOutput) -> Result<Vec<Vec<usize>>> {
+    output
+        .statistics::<HammingDistanceResult>()
+        .map(|result| result.distances.clone())
+        .ok_or_else(|| anyhow::anyhow!("Unexpected Hamming distance output"))
+}
@@ -0,0 +1,168 @@
+//! Neural Engine Pilot: K-mer Embedding Similarity
+//!
+//! Gives `use_neural_engine` and `use_m5_gpu_neural_accel` an ML-shaped
+//! workload: embedding reads with a dense projection (a small Core ML
+//! model), run on every unit that can execute it.
+//!
+//! **Research Questions**:
+//! 1. At what scale does the Neural Engine beat NEON for a dense layer?
+//! 2. ANE vs GPU (neural accelerators on M5) vs Core ML CPU for the same model
+//! 3. How good is the embedding as a pre-filter (recall of exact
+//!    composition-similar pairs vs fraction of pairs kept)?
+//!
+//! **Backends**: naive, NEON, NEON 4 threads, Core ML CPU, Core ML GPU, Core
+//! ML Neural Engine. Core ML needs `--features gpu` on macOS and the model
+//! from `scripts/build_kmer_embedding_model.py` (or `ASBB_EMBEDDING_MODEL`);
+//! otherwise those backends are reported as unavailable. Only the embedding
+//! is timed; each backend is warmed up first (Core ML compiles the model on
+//! first load).
+//!
+//! Run in release mode:
+//! ```bash
+//! python3 scripts/build_kmer_embedding_model.py
+//! cargo run --release -p asbb-cli --features gpu --bin asbb-pilot-neural \
+//!   [datasets/large_100k_150bp.fq] > results/neural_embedding_raw.csv
+//! ```
+
+use anyhow::Result;
+use asbb_core::io::FastqReader;
+use asbb_core::SequenceRecord;
+use asbb_ops::kmer_distance::KmerDistance;
+use asbb_ops::kmer_embedding::{EmbeddingBackend, KmerEmbedding};
+use std::collections::HashSet;
+use std::time::Instant;
+
+const DEFAULT_DATASET: &str = "datasets/large_100k_150bp.fq";
+
+const SCALES: &[usize] = &[1_000, 10_000, 100_000];
+
+/// Sequences for the pre-filter quality check (all pairs, exact and embedded)
+const PREFILTER_SEQUENCES: usize = 1_000;
+
+/// Composition similarity defining an exact "similar" pair
+const SIMILARITY_THRESHOLD: f32 = 0.9;
+
+const BACKENDS: &[EmbeddingBackend] = &[
+    EmbeddingBackend::Naive,
+    EmbeddingBackend::Neon,
+    EmbeddingBackend::Parallel(4),
+    EmbeddingBackend::CoreMlCpu,
+    EmbeddingBackend::CoreMlGpu,
+    EmbeddingBackend::NeuralEngine,
+];
+
+fn main() -> Result<()> {
+    let dataset = std::env::args().nth(1).unwrap_or_else(|| DEFAULT_DATASET.to_string());
+
+    eprintln!("╔════════════════════════════════════════════════════════════════════╗");
+    eprintln!("║          Neural Engine Pilot: K-mer Embedding Similarity           ║");
+    eprintln!("╚════════════════════════════════════════════════════════════════════╝");
+    eprintln!();
+    eprintln!("📂 Dataset: {}", dataset);
+    eprintln!("📊 Backends: {:?}", BACKENDS.iter().map(|b| b.name()).collect::<Vec<_>>());
+    eprintln!("📏 Scales: {:?} sequences", SCALES);
+    eprintln!();
+
+    let all = FastqReader::from_path(&dataset)?.read_all()?;
+    let op = KmerEmbedding::default().with_threshold(SIMILARITY_THRESHOLD);
+
+    // Warm up (loads Core ML models) and drop unavailable backends
+    let mut available = Vec::new();
+    for &backend in BACKENDS {
+        let start = Instant::now();
+        match op.embed(&all[..all.len().min(16)], backend) {
+            Ok(_) => {
+                eprintln!("  ✅ {:12} ready ({:.1}ms warm-up)", backend.name(), start.elapsed().as_secs_f64() * 1000.0);
+                available.push(backend);
+            }
+            Err(e) => eprintln!("  ⚠️  {:12} unavailable ({})", backend.name(), e),
+        }
+    }
+
+    println!("operation,num_sequences,backend,time_ms,speedup_vs_naive,speedup_vs_neon,max_abs_error,status");
+
+    for &scale in SCALES {
+        if scale > all.len() {
+            eprintln!("⚠️  Skipping {} sequences (dataset has {})", scale, all.len());
+            continue;
+        }
+        let data = &all[..scale];
+        eprintln!("\n  Scale: {} sequences", scale);
+
+        let mut reference: Option<Vec<f32>> = None;
+        let mut naive_ms = None;
+        let mut neon_ms = None;
+
+        for &backend in BACKENDS {
+            if !available.contains(&backend) {
+                println!("kmer_embedding,{},{},,,,,unavailable", scale, backend.name());
+                continue;
+            }
+
+            let start = Instant::now();
+            let embeddings = op.embed(data, backend)?;
+            let time_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+            let reference = reference.get_or_insert_with(|| embeddings.clone());
+            let max_abs_error = embeddings
+                .iter()
+                .zip(reference.iter())
+                .map(|(a, b)| (a - b).abs())
+                .fold(0.0f32, f32::max);
+            match backend {
+                EmbeddingBackend::Naive => naive_ms = Some(time_ms),
+                EmbeddingBackend::Neon => neon_ms = Some(time_ms),
+                _ => {}
+            }
+            let vs_naive = naive_ms.map_or(1.0, |t| t / time_ms);
+            let vs_neon = neon_ms.map_or(1.0, |t| t / time_ms);
+
+            eprintln!(
+                "    {:12} {:10.2}ms  ({:.2}× vs naive, {:.2}× vs NEON, max error {:.1e})",
+                backend.name(),
+                time_ms,
+                vs_naive,
+                vs_neon,
+                max_abs_error
+            );
+            println!(
+                "kmer_embedding,{},{},{:.6},{:.4},{:.4},{:.3e},ok",
+                scale,
+                backend.name(),
+                time_ms,
+                vs_naive,
+                vs_neon,
+                max_abs_error
+            );
+        }
+    }
+
+    report_prefilter_quality(&op, &all[..all.len().min(PREFILTER_SEQUENCES)], &available)?;
+
+    eprintln!("\n✅ Neural Engine pilot complete (CSV on stdout)");
+    Ok(())
+}
+
+/// Recall of exact composition-similar pairs among embedding candidates
+fn report_prefilter_quality(op: &KmerEmbedding, data: &[SequenceRecord], backends: &[EmbeddingBackend]) -> Result<()> {
+    let n = data.len();
+    let exact = KmerDistance::new(4).execute_backend(data, asbb_ops::gram::GramBackend::Neon)?;
+    let similar: HashSet<(usize, usize)> = (0..n)
+        .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+        .filter(|&(i, j)| 1.0 - exact.distances[i][j] >= SIMILARITY_THRESHOLD)
+        .collect();
+    let total_pairs = (n * n.saturating_sub(1) / 2).max(1);
+
+    eprintln!("\n🎯 Pre-filter quality ({} sequences, {} exact pairs ≥ {})", n, similar.len(), SIMILARITY_THRESHOLD);
+    for &backend in backends {
+        let result = op.execute_backend(data, backend)?;
+        let found = result.candidate_pairs.iter().filter(|pair| similar.contains(pair)).count();
+        eprintln!(
+            "    {:12} recall {:5.1}%  keeping {:5.2}% of pairs",
+            backend.name(),
+            found as f64 / similar.len().max(1) as f64 * 100.0,
+            result.candidate_pairs.len() as f64 / total_pairs as f64 * 100.0
+        );
+    }
+    Ok(())
+}
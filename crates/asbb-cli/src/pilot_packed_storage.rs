@@ -0,0 +1,187 @@
+//! Packed Storage Pilot
+//!
+//! Compares the per-record layout (`Vec<SequenceRecord>`, three heap
+//! allocations per read) with `PackedRecords` (contiguous id, sequence and
+//! quality buffers plus offset tables) and with zero-copy `SequenceView`s
+//! over a memory-mapped file, on real FASTQ files:
+//!
+//! - Load time: `read_all` vs `read_packed` vs mmap + `parse_views`
+//! - Memory footprint: heap bytes and allocation counts of each layout
+//! - Downstream throughput: `execute_neon` vs `execute_packed` vs `execute_views`
+//!
+//! Usage: `asbb-pilot-packed [FASTQ...]` (defaults to the synthetic datasets).
+//! CSV rows go to stdout, progress to stderr.
+
+use anyhow::Result;
+use asbb_core::io::{parse_views, FastqReader};
+use asbb_core::packed::{records_allocation_count, records_heap_bytes, PackedRecords};
+use asbb_core::{PrimitiveOperation, SequenceRecord, SequenceView};
+use memmap2::Mmap;
+use std::fs::File;
+use asbb_ops::*;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const LOAD_RUNS: usize = 5;
+const OPERATION_RUNS: usize = 20;
+
+fn median(mut durations: Vec<Duration>) -> Duration {
+    durations.sort();
+    durations[durations.len() / 2]
+}
+
+/// Median time of `runs` calls to `f`
+fn time_median<T>(runs: usize, mut f: impl FnMut() -> Result<T>) -> Result<Duration> {
+    let mut times = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        std::hint::black_box(f()?);
+        times.push(start.elapsed());
+    }
+    Ok(median(times))
+}
+
+fn load_records(path: &Path) -> Result<Vec<SequenceRecord>> {
+    FastqReader::from_path(path)?.read_all()
+}
+
+fn load_packed(path: &Path) -> Result<PackedRecords> {
+    FastqReader::from_path(path)?.read_packed()
+}
+
+fn map_file(path: &Path) -> Result<Mmap> {
+    let file = File::open(path)?;
+    Ok(unsafe { Mmap::map(&file)? })
+}
+
+fn run_file(path: &Path) -> Result<()> {
+    eprintln!("Running: {}", path.display());
+
+    // Load time (views: map + parse, faulting pages in as the parser reads)
+    let records_load = time_median(LOAD_RUNS, || load_records(path))?;
+    let packed_load = time_median(LOAD_RUNS, || load_packed(path))?;
+    let views_load = time_median(LOAD_RUNS, || {
+        let mmap = map_file(path)?;
+        Ok(parse_views(&mmap)?.len())
+    })?;
+
+    let records = load_records(path)?;
+    let packed = load_packed(path)?;
+    let mmap = map_file(path)?;
+    let views = parse_views(&mmap)?;
+    let views_heap = views.capacity() * std::mem::size_of::<SequenceView>();
+    let file = path.file_name().unwrap_or_default().to_string_lossy();
+
+    println!(
+        "{},load,records,{},{},{:.3},{},{},{:.3}",
+        file,
+        records.len(),
+        packed.total_bases(),
+        records_load.as_secs_f64() * 1000.0,
+        records_heap_bytes(&records),
+        records_allocation_count(&records),
+        records.len() as f64 / records_load.as_secs_f64() / 1e6
+    );
+    println!(
+        "{},load,packed,{},{},{:.3},{},{},{:.3}",
+        file,
+        packed.len(),
+        packed.total_bases(),
+        packed_load.as_secs_f64() * 1000.0,
+        packed.heap_bytes(),
+        packed.allocation_count(),
+        packed.len() as f64 / packed_load.as_secs_f64() / 1e6
+    );
+    println!(
+        "{},load,views,{},{},{:.3},{},1,{:.3}",
+        file,
+        views.len(),
+        packed.total_bases(),
+        views_load.as_secs_f64() * 1000.0,
+        views_heap,
+        views.len() as f64 / views_load.as_secs_f64() / 1e6
+    );
+    eprintln!(
+        "  Load: records={:.1}ms packed={:.1}ms ({:.2}×) views={:.1}ms ({:.2}×)",
+        records_load.as_secs_f64() * 1000.0,
+        packed_load.as_secs_f64() * 1000.0,
+        records_load.as_secs_f64() / packed_load.as_secs_f64(),
+        views_load.as_secs_f64() * 1000.0,
+        records_load.as_secs_f64() / views_load.as_secs_f64()
+    );
+    eprintln!(
+        "  Heap: records={:.1}MB ({} allocations) packed={:.1}MB ({}) views={:.1}MB (1, data stays in the mapping)",
+        records_heap_bytes(&records) as f64 / 1_048_576.0,
+        records_allocation_count(&records),
+        packed.heap_bytes() as f64 / 1_048_576.0,
+        packed.allocation_count(),
+        views_heap as f64 / 1_048_576.0
+    );
+
+    // Downstream throughput
+    let operations: Vec<Box<dyn PrimitiveOperation>> = vec![
+        Box::new(base_counting::BaseCounting::new()),
+        Box::new(gc_content::GcContent::new()),
+        Box::new(sequence_length::SequenceLength),
+        Box::new(quality_aggregation::QualityAggregation::new()),
+    ];
+
+    for op in &operations {
+        let on_records = time_median(OPERATION_RUNS, || op.execute_neon(&records))?;
+        let on_packed = time_median(OPERATION_RUNS, || op.execute_packed(&packed))?;
+        let on_views = time_median(OPERATION_RUNS, || op.execute_views(&views))?;
+
+        let layouts = [("records", on_records), ("packed", on_packed), ("views", on_views)];
+        for (layout, time) in layouts {
+            println!(
+                "{},{},{},{},{},{:.3},,,{:.3}",
+                file,
+                op.name(),
+                layout,
+                records.len(),
+                packed.total_bases(),
+                time.as_secs_f64() * 1000.0,
+                records.len() as f64 / time.as_secs_f64() / 1e6
+            );
+        }
+        eprintln!(
+            "  {}: records={:.3}ms packed={:.3}ms ({:.2}×) views={:.3}ms ({:.2}×)",
+            op.name(),
+            on_records.as_secs_f64() * 1000.0,
+            on_packed.as_secs_f64() * 1000.0,
+            on_records.as_secs_f64() / on_packed.as_secs_f64(),
+            on_views.as_secs_f64() * 1000.0,
+            on_records.as_secs_f64() / on_views.as_secs_f64()
+        );
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    eprintln!("=== Packed Storage Pilot ===");
+    eprintln!("Per-record allocations vs contiguous packed buffers vs mmap views");
+    eprintln!();
+
+    let mut paths: Vec<PathBuf> = std::env::args().skip(1).map(PathBuf::from).collect();
+    if paths.is_empty() {
+        paths = vec![
+            PathBuf::from("datasets/medium_10000_150bp.fq"),
+            PathBuf::from("datasets/large_100000_150bp.fq"),
+        ];
+    }
+
+    // CSV header
+    println!("file,phase,layout,num_sequences,total_bases,time_ms,heap_bytes,allocations,mseqs_per_sec");
+
+    for path in &paths {
+        if let Err(e) = run_file(path) {
+            eprintln!("ERROR: {}: {}", path.display(), e);
+        }
+    }
+
+    eprintln!();
+    eprintln!("=== Pilot Complete ===");
+
+    Ok(())
+}
@@ -88,7 +88,7 @@ fn main() -> Result<()> {
     println!();
 
     // Create operation
-    let operation = QualityAggregation;
+    let operation = QualityAggregation::new();
 
     // Benchmark parameters
     let warmup_runs = 2;
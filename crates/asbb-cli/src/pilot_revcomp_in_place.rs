@@ -0,0 +1,152 @@
+//! Reverse Complement Allocation Pilot
+//!
+//! Asks how much of reverse complement's cost is the transform and how much
+//! is allocation: the record-returning paths allocate an id and a sequence
+//! per read, while the in-place paths rewrite memory the data already lives
+//! in (on unified memory, the same buffer the GPU would see).
+//!
+//! **Backends**:
+//! - ASCII, allocating: naive, NEON, NEON 4 threads (`Vec<SequenceRecord>` out)
+//! - ASCII, in place: naive, NEON, NEON 4 threads (`PackedRecords` rewritten)
+//! - 2-bit: per-base scalar (`BitSeq::reverse_complement`), byte lookup
+//!   table (4 bases per lookup) allocating and in place
+//!
+//! Reverse complement is its own inverse, so in-place runs repeat on the same
+//! buffer without resetting it. Heap allocations during each timed run are
+//! counted with a wrapping global allocator.
+//!
+//! Run in release mode:
+//! ```bash
+//! cargo run --release -p asbb-cli --bin asbb-pilot-revcomp-in-place \
+//!   [datasets/large_100k_150bp.fq] > results/revcomp_in_place_raw.csv
+//! ```
+
+use anyhow::Result;
+use asbb_core::encoding::BitSeq;
+use asbb_core::io::FastqReader;
+use asbb_core::packed::PackedRecords;
+use asbb_core::PrimitiveOperation;
+use asbb_ops::reverse_complement::ReverseComplement;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+const DEFAULT_DATASET: &str = "datasets/large_100k_150bp.fq";
+
+const SCALES: &[usize] = &[1_000, 10_000, 100_000];
+
+/// Timed runs per backend (median reported)
+const RUNS: usize = 5;
+
+/// System allocator that counts allocations
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Median time (ms) and allocations of the last run of `f`
+fn measure<T>(mut f: impl FnMut() -> Result<T>) -> Result<(f64, usize)> {
+    let mut times = Vec::with_capacity(RUNS);
+    let mut allocations = 0;
+    for _ in 0..RUNS {
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        let output = f()?;
+        times.push(start.elapsed().as_secs_f64() * 1000.0);
+        allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+        drop(std::hint::black_box(output));
+    }
+    times.sort_by(f64::total_cmp);
+    Ok((times[times.len() / 2], allocations))
+}
+
+fn main() -> Result<()> {
+    let dataset = std::env::args().nth(1).unwrap_or_else(|| DEFAULT_DATASET.to_string());
+
+    eprintln!("╔════════════════════════════════════════════════════════════════════╗");
+    eprintln!("║          Reverse Complement Allocation Pilot                       ║");
+    eprintln!("╚════════════════════════════════════════════════════════════════════╝");
+    eprintln!();
+    eprintln!("📂 Dataset: {}", dataset);
+    eprintln!("📏 Scales: {:?} sequences", SCALES);
+    eprintln!();
+
+    let all = FastqReader::from_path(&dataset)?.read_all()?;
+    let op = ReverseComplement::new();
+
+    println!("operation,num_sequences,backend,time_ms,mbases_per_sec,allocations_per_record,speedup_vs_naive,status");
+
+    for &scale in SCALES {
+        if scale > all.len() {
+            eprintln!("⚠️  Skipping {} sequences (dataset has {})", scale, all.len());
+            continue;
+        }
+        let data = &all[..scale];
+        let total_bases: usize = data.iter().map(|r| r.sequence.len()).sum();
+        eprintln!("\n  Scale: {} sequences ({} bases)", scale, total_bases);
+
+        let mut packed = PackedRecords::from_records(data)?;
+        let mut bitseqs: Vec<BitSeq> = data.iter().map(|r| BitSeq::from_ascii(&r.sequence)).collect();
+
+        let results = [
+            ("ascii_naive", measure(|| op.execute_naive(data))?),
+            ("ascii_neon", measure(|| op.execute_neon(data))?),
+            ("ascii_neon_4t", measure(|| op.execute_parallel(data, 4))?),
+            (
+                "in_place_naive",
+                measure(|| {
+                    op.execute_in_place_naive(&mut packed);
+                    Ok(())
+                })?,
+            ),
+            ("in_place_neon", measure(|| op.execute_in_place(&mut packed, 1))?),
+            ("in_place_neon_4t", measure(|| op.execute_in_place(&mut packed, 4))?),
+            ("2bit_scalar", measure(|| Ok(bitseqs.iter().map(BitSeq::reverse_complement).collect::<Vec<_>>()))?),
+            ("2bit_lut", measure(|| Ok(bitseqs.iter().map(BitSeq::reverse_complement_lut).collect::<Vec<_>>()))?),
+            (
+                "2bit_lut_in_place",
+                measure(|| {
+                    op.reverse_complement_2bit_in_place(&mut bitseqs);
+                    Ok(())
+                })?,
+            ),
+        ];
+
+        let naive_ms = results[0].1 .0;
+        for (backend, (time_ms, allocations)) in results {
+            let mbases_per_sec = total_bases as f64 / (time_ms / 1000.0) / 1e6;
+            let allocations_per_record = allocations as f64 / scale as f64;
+            let speedup = naive_ms / time_ms;
+
+            eprintln!(
+                "    {:18} {:10.3}ms  {:8.1} Mbases/s  {:5.2} allocs/record  ({:.2}× vs naive)",
+                backend, time_ms, mbases_per_sec, allocations_per_record, speedup
+            );
+            println!(
+                "reverse_complement,{},{},{:.6},{:.2},{:.4},{:.4},ok",
+                scale, backend, time_ms, mbases_per_sec, allocations_per_record, speedup
+            );
+        }
+    }
+
+    eprintln!("\n✅ Reverse complement pilot complete (CSV on stdout)");
+    Ok(())
+}
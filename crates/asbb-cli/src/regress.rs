@@ -0,0 +1,164 @@
+//! `asbb regress`: detect performance regressions against the results store
+//!
+//! Intended for tracking the crate's own performance across commits on a lab
+//! machine: run `asbb bench` (or the DAG traversal) for a commit, then check
+//! the CSV against that machine's earlier runs in the results store (the
+//! SQLite database `asbb results merge` writes) and record it there:
+//!
+//! ```bash
+//! asbb bench --input Medium=datasets/medium_10000_150bp.fq --output run.csv
+//! asbb regress --history results/results.sqlite --current run.csv --record
+//! ```
+//!
+//! The machine and commit come from the run's manifest like they do for
+//! `asbb results merge` (else the local host name and git checkout), so
+//! recorded and merged runs share one history. Exits with an error if any
+//! experiment regressed, so it can gate scripts.
+
+use anyhow::Result;
+use asbb_analysis::regression::DEFAULT_REGRESSION_THRESHOLD;
+use asbb_analysis::store::ResultStore;
+use asbb_analysis::{detect_regressions, load_results_csv, Verdict};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::merge::{load_manifest, recorded_machine, short_commit};
+
+/// Default `--threshold`, in percent
+pub const DEFAULT_THRESHOLD_PERCENT: f64 = DEFAULT_REGRESSION_THRESHOLD * 100.0;
+
+/// Options for a regression check
+pub struct RegressOptions {
+    /// Results store holding earlier runs (SQLite)
+    pub history: PathBuf,
+
+    /// Results CSV of the run being checked
+    pub current: PathBuf,
+
+    /// Machine whose history to check against (default: from the manifest)
+    pub machine: Option<String>,
+
+    /// Relative throughput change to ignore, in percent
+    pub threshold_percent: f64,
+
+    /// Record the current run in the store after checking
+    pub record: bool,
+
+    /// Commit of the current run (default: from the manifest)
+    pub commit: Option<String>,
+}
+
+pub fn run(options: &RegressOptions) -> Result<()> {
+    let current = load_results_csv(&options.current)?;
+    let manifest = load_manifest(&options.current);
+    let machine = options
+        .machine
+        .clone()
+        .or_else(|| recorded_machine(&manifest))
+        .or_else(|| command_output("hostname", &[]))
+        .unwrap_or_else(|| "unknown".to_string());
+    let commit = options
+        .commit
+        .clone()
+        .or_else(|| manifest.and_then(|manifest| manifest.git_commit))
+        .or_else(|| command_output("git", &["rev-parse", "HEAD"]))
+        .unwrap_or_else(|| chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string());
+
+    if let Some(parent) = options.history.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut store = ResultStore::open(&options.history)?;
+    // A rerun of the same commit is checked against earlier commits only
+    let history: Vec<_> = store
+        .history(&machine)?
+        .into_iter()
+        .filter(|stored| stored.commit != commit)
+        .collect();
+    let report = detect_regressions(&history, &current, options.threshold_percent / 100.0);
+
+    println!("📉 Checking for regressions on {} @ {}", machine, short_commit(&commit));
+    println!("   History: {} ({} rows)", options.history.display(), history.len());
+    println!("   Current: {} ({} rows)", options.current.display(), current.len());
+    println!("   Threshold: {:.1}% (significance: Welch t-test, 95%)", options.threshold_percent);
+    println!();
+
+    let notable: Vec<_> = report
+        .checks
+        .iter()
+        .filter(|check| check.verdict != Verdict::Unchanged)
+        .collect();
+    if !notable.is_empty() {
+        println!(
+            "   {:<22} {:<16} {:<12} {:>14} {:>14} {:>8} {:>8}  Verdict",
+            "Operation", "Config", "Scale", "Reference/s", "Current/s", "Change", "t"
+        );
+        for check in notable {
+            println!(
+                "   {:<22} {:<16} {:<12} {:>14.0} {:>14.0} {:>+7.1}% {:>8}  {} (vs {})",
+                check.key.operation,
+                check.key.config,
+                check.key.scale,
+                check.reference_throughput,
+                check.current_throughput,
+                check.change * 100.0,
+                check
+                    .t_statistic
+                    .map(|t| format!("{:.2}", t))
+                    .unwrap_or_else(|| "-".to_string()),
+                check.verdict.name(),
+                short_commit(&check.reference_run)
+            );
+        }
+        println!();
+    }
+
+    println!(
+        "📊 {} regressed, {} improved, {} inconclusive, {} unchanged, {} new",
+        report.count(Verdict::Regression),
+        report.count(Verdict::Improvement),
+        report.count(Verdict::Inconclusive),
+        report.count(Verdict::Unchanged),
+        report.new_keys.len()
+    );
+
+    if options.record {
+        let source = options.current.display().to_string();
+        let recorded = store.record(&machine, &commit, &source, &current)?;
+        for issue in &recorded.issues {
+            println!("   ⚠️  {}", issue);
+        }
+        if !recorded.written {
+            anyhow::bail!(
+                "Not recorded: {} conflicting rows already stored",
+                recorded.issues.len()
+            );
+        }
+        println!(
+            "📄 Recorded {} rows ({} already stored) as {} @ {} in {}",
+            recorded.inserted,
+            recorded.duplicates,
+            machine,
+            short_commit(&commit),
+            options.history.display()
+        );
+    }
+
+    let regressions = report.count(Verdict::Regression);
+    if regressions > 0 {
+        anyhow::bail!("{} regression(s) detected", regressions);
+    }
+    println!("✅ No regressions");
+    Ok(())
+}
+
+/// Trimmed stdout of a successful command
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    Command::new(program)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|out| !out.is_empty())
+}
@@ -0,0 +1,482 @@
+//! `asbb report`: analysis reports over a results CSV
+//!
+//! `asbb report heatmap` pivots one metric into an operation × config grid
+//! per scale, written as tidy long-format CSV (and optionally an SVG).
+//!
+//! `asbb report summary` renders a Markdown report from any harness's
+//! results (DAG traversal, `asbb bench`, pilots). Sections:
+//!
+//! - **Thread scaling**: Amdahl/Gustafson fits per operation, scale and
+//!   config family, with the efficiency of each added core and the thread
+//!   count where doubling stops paying off (cf. the DAG traversal's
+//!   diminishing-returns threshold)
+//! - **Memory bandwidth** (with `--machine-profile`): bytes touched per
+//!   second as a fraction of the machine's peak read bandwidth, and the
+//!   thread count where each scaling curve saturates the memory bus
+//! - **By read length** (when long-read tiers were run): each config's
+//!   median speedup over naive per read-length class, short reads to 100 kb
+//! - **Winners**: the best config per operation and scale, declared only
+//!   when its confidence interval clears the runner-up's (ties listed)
+//! - **By operation category**: speedups over naive pooled per category
+//!   (mean, median and spread across its operations), and whether the
+//!   category's best config is also each operation's best
+
+use anyhow::{Context, Result};
+use asbb_analysis::bandwidth::{bandwidth_saturation_threads, DEFAULT_SATURATION};
+use asbb_analysis::category::{category_conclusions, category_summary};
+use asbb_analysis::heatmap::{heatmap_cells, render_svg, tidy_csv};
+use asbb_analysis::length::has_long_reads;
+use asbb_analysis::winner::{cell_winners, CellVerdict};
+use asbb_analysis::scaling::DEFAULT_DOUBLING_THRESHOLD;
+use asbb_analysis::{
+    analyze_scaling, bandwidth_utilization, length_breakdown, load_results_csv, PeakBandwidth,
+    ResultRow,
+};
+use std::collections::BTreeMap;
+use asbb_core::OperationCategory;
+use asbb_micro::MachineProfile;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Default `--doubling-threshold`
+pub const DEFAULT_THRESHOLD: f64 = DEFAULT_DOUBLING_THRESHOLD;
+
+/// Default `--bytes-per-sequence`
+pub const DEFAULT_BYTES_PER_SEQUENCE: usize = asbb_analysis::bandwidth::DEFAULT_BYTES_PER_SEQUENCE;
+
+/// Default `--metric` of `asbb report heatmap`
+pub const DEFAULT_METRIC: &str = asbb_analysis::heatmap::DEFAULT_METRIC;
+
+/// Options for a summary report
+pub struct SummaryOptions {
+    /// Results CSV to analyze
+    pub results: PathBuf,
+
+    /// Write the report here instead of stdout
+    pub output: Option<PathBuf>,
+
+    /// Gain from doubling threads below which more threads are not worthwhile
+    pub doubling_threshold: f64,
+
+    /// `asbb micro` profile of the machine that produced the results
+    pub machine_profile: Option<PathBuf>,
+
+    /// Bytes each sequence touches (bases + quality)
+    pub bytes_per_sequence: usize,
+}
+
+pub fn run_summary(options: &SummaryOptions) -> Result<()> {
+    let rows = load_results_csv(&options.results)?;
+    if rows.is_empty() {
+        anyhow::bail!("No measured rows in {}", options.results.display());
+    }
+
+    let mut report = String::new();
+    writeln!(report, "# ASBB results report")?;
+    writeln!(report)?;
+    writeln!(report, "Source: `{}` ({} rows)", options.results.display(), rows.len())?;
+    writeln!(report)?;
+    report.push_str(&scaling_section(&rows, options.doubling_threshold)?);
+    if let Some(path) = &options.machine_profile {
+        let profile = MachineProfile::load(path)?;
+        report.push_str(&bandwidth_section(&rows, &profile, options.bytes_per_sequence)?);
+    }
+    if has_long_reads(&rows) {
+        report.push_str(&length_section(&rows)?);
+    }
+    report.push_str(&winners_section(&rows)?);
+    report.push_str(&category_section(&rows)?);
+
+    match &options.output {
+        Some(path) => {
+            write_file(path, &report)?;
+            println!("📄 Wrote report to {}", path.display());
+        }
+        None => print!("{}", report),
+    }
+    Ok(())
+}
+
+/// Options for a heatmap export
+pub struct HeatmapOptions {
+    /// Results CSV to pivot
+    pub results: PathBuf,
+
+    /// Column to plot
+    pub metric: String,
+
+    /// Write the tidy CSV here instead of stdout
+    pub output: Option<PathBuf>,
+
+    /// Also render an SVG heatmap here
+    pub svg: Option<PathBuf>,
+}
+
+pub fn run_heatmap(options: &HeatmapOptions) -> Result<()> {
+    let cells = heatmap_cells(&options.results, &options.metric)?;
+    if cells.is_empty() {
+        anyhow::bail!("No '{}' values in {}", options.metric, options.results.display());
+    }
+
+    let csv = tidy_csv(&cells, &options.metric);
+    match &options.output {
+        Some(path) => {
+            write_file(path, &csv)?;
+            println!("📄 Wrote {} cells to {}", cells.len(), path.display());
+        }
+        None => print!("{}", csv),
+    }
+    if let Some(path) = &options.svg {
+        write_file(path, &render_svg(&cells, &options.metric))?;
+        println!("🗺️  Wrote heatmap to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Write `contents` to `path`, creating parent directories
+fn write_file(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Thread-scaling section: model fits, then per-point efficiency
+fn scaling_section(rows: &[ResultRow], doubling_threshold: f64) -> Result<String> {
+    let curves = analyze_scaling(rows);
+
+    let mut section = String::new();
+    writeln!(section, "## Thread scaling")?;
+    writeln!(section)?;
+    if curves.is_empty() {
+        writeln!(section, "No config was measured at more than one thread count.")?;
+        writeln!(section)?;
+        return Ok(section);
+    }
+
+    writeln!(
+        section,
+        "Amdahl's law fitted to time per sequence vs 1/threads. \
+         \"Saturates at\" is the thread count past which doubling threads is \
+         predicted to gain less than {:.2}×.",
+        doubling_threshold
+    )?;
+    writeln!(section)?;
+    writeln!(
+        section,
+        "| Operation | Scale | Config | Threads | Serial fraction (Amdahl) | R² | \
+         Serial fraction (Gustafson) | Max speedup | Saturates at (threads) |"
+    )?;
+    writeln!(section, "|---|---|---|---|---|---|---|---|---|")?;
+    for curve in &curves {
+        let amdahl = &curve.amdahl;
+        let threads: Vec<String> = curve.points.iter().map(|p| p.threads.to_string()).collect();
+        let max_speedup = if amdahl.serial_fraction > 0.0 {
+            format!("{:.1}×", 1.0 / amdahl.serial_fraction)
+        } else {
+            "unbounded".to_string()
+        };
+        writeln!(
+            section,
+            "| {} | {} | {} | {} | {:.3} | {:.3} | {} | {} | {} |",
+            curve.operation,
+            curve.scale,
+            curve.family,
+            threads.join(", "),
+            amdahl.serial_fraction,
+            amdahl.r_squared,
+            curve
+                .gustafson_serial_fraction
+                .map(|s| format!("{:.3}", s))
+                .unwrap_or_else(|| "-".to_string()),
+            max_speedup,
+            amdahl
+                .saturation_threads(doubling_threshold)
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| "-".to_string())
+        )?;
+    }
+    writeln!(section)?;
+
+    writeln!(section, "### Efficiency per added core")?;
+    writeln!(section)?;
+    writeln!(
+        section,
+        "| Operation | Scale | Config | Threads | Seqs/sec | Speedup | Efficiency | \
+         Speedup per added core | Predicted doubling gain |"
+    )?;
+    writeln!(section, "|---|---|---|---|---|---|---|---|---|")?;
+    for curve in &curves {
+        for point in &curve.points {
+            writeln!(
+                section,
+                "| {} | {} | {} | {} | {:.0} | {:.2}× | {:.0}% | {} | {:.2}× |",
+                curve.operation,
+                curve.scale,
+                curve.family,
+                point.threads,
+                point.throughput,
+                point.speedup,
+                point.efficiency * 100.0,
+                point
+                    .marginal_speedup
+                    .map(|m| format!("{:+.3}", m))
+                    .unwrap_or_else(|| "-".to_string()),
+                curve.amdahl.doubling_gain(point.threads)
+            )?;
+        }
+    }
+    writeln!(section)?;
+
+    Ok(section)
+}
+
+/// Memory-bandwidth section: utilization per config, then where each scaling
+/// curve saturates the memory bus
+fn bandwidth_section(
+    rows: &[ResultRow],
+    profile: &MachineProfile,
+    bytes_per_sequence: usize,
+) -> Result<String> {
+    let (Some(single_core_gbps), Some(all_core_gbps)) =
+        (profile.single_core_read_gbps(), profile.all_core_read_gbps())
+    else {
+        anyhow::bail!("Machine profile for {} has no read bandwidth", profile.machine);
+    };
+    let peak = PeakBandwidth { single_core_gbps, all_core_gbps };
+
+    let mut section = String::new();
+    writeln!(section, "## Memory bandwidth")?;
+    writeln!(section)?;
+    writeln!(
+        section,
+        "Bytes touched per second ({} bytes per sequence) over the peak read \
+         bandwidth of {}: {:.1} GB/s for one thread, {:.1} GB/s for all cores. \
+         Configs at {:.0}% of peak or more are memory-bound; above 100% the \
+         dataset was served from cache.",
+        bytes_per_sequence,
+        profile.machine,
+        single_core_gbps,
+        all_core_gbps,
+        DEFAULT_SATURATION * 100.0
+    )?;
+    writeln!(section)?;
+    writeln!(
+        section,
+        "| Operation | Scale | Config | Threads | GB/s | Peak GB/s | % of peak | Bound |"
+    )?;
+    writeln!(section, "|---|---|---|---|---|---|---|---|")?;
+    for point in bandwidth_utilization(rows, bytes_per_sequence, &peak) {
+        writeln!(
+            section,
+            "| {} | {} | {} | {} | {:.2} | {:.1} | {:.0}% | {} |",
+            point.key.operation,
+            point.key.scale,
+            point.key.config,
+            point.threads,
+            point.gbps,
+            point.peak_gbps,
+            point.utilization * 100.0,
+            if point.is_saturated(DEFAULT_SATURATION) { "memory" } else { "-" }
+        )?;
+    }
+    writeln!(section)?;
+
+    let saturated: Vec<(String, usize)> = analyze_scaling(rows)
+        .iter()
+        .filter_map(|curve| {
+            bandwidth_saturation_threads(curve, bytes_per_sequence, &peak, DEFAULT_SATURATION).map(
+                |threads| {
+                    (format!("{} / {} / {}", curve.operation, curve.scale, curve.family), threads)
+                },
+            )
+        })
+        .collect();
+    if !saturated.is_empty() {
+        writeln!(section, "### Memory bus saturated")?;
+        writeln!(section)?;
+        writeln!(section, "More threads past these counts mostly wait on DRAM:")?;
+        writeln!(section)?;
+        for (curve, threads) in saturated {
+            writeln!(section, "- {}: from {} threads", curve, threads)?;
+        }
+        writeln!(section)?;
+    }
+
+    Ok(section)
+}
+
+/// Read-length section: speedup over naive per config, one column per class
+fn length_section(rows: &[ResultRow]) -> Result<String> {
+    let breakdown = length_breakdown(rows);
+    let mut classes: Vec<&str> = Vec::new();
+    let mut table: BTreeMap<(&str, &str), BTreeMap<&str, f64>> = BTreeMap::new();
+    for entry in &breakdown {
+        if !classes.contains(&entry.length_class.as_str()) {
+            classes.push(&entry.length_class);
+        }
+        table
+            .entry((&entry.operation, &entry.config))
+            .or_default()
+            .insert(&entry.length_class, entry.speedup);
+    }
+    // Columns in class order, short reads first
+    classes.sort_by_key(|class| {
+        asbb_core::LengthClass::from_name(class).map_or(usize::MAX, |c| c as usize)
+    });
+
+    let mut section = String::new();
+    writeln!(section, "## By read length")?;
+    writeln!(section)?;
+    if breakdown.is_empty() {
+        writeln!(section, "No long-read config was measured alongside its naive baseline.")?;
+        writeln!(section)?;
+        return Ok(section);
+    }
+    writeln!(
+        section,
+        "Median speedup over naive at the same scale, per read-length class. \
+         A config that wins on short reads but not on long ones is limited by \
+         per-read parallelism or cache footprint rather than record count."
+    )?;
+    writeln!(section)?;
+    writeln!(section, "| Operation | Config | {} |", classes.join(" | "))?;
+    writeln!(section, "|---|---|{}", "---|".repeat(classes.len()))?;
+    for ((operation, config), speedups) in &table {
+        let cells: Vec<String> = classes
+            .iter()
+            .map(|class| {
+                speedups
+                    .get(class)
+                    .map(|s| format!("{:.2}×", s))
+                    .unwrap_or_else(|| "-".to_string())
+            })
+            .collect();
+        writeln!(section, "| {} | {} | {} |", operation, config, cells.join(" | "))?;
+    }
+    writeln!(section)?;
+
+    Ok(section)
+}
+
+/// Winners section: best config per (operation, scale), ties made explicit
+fn winners_section(rows: &[ResultRow]) -> Result<String> {
+    let winners = cell_winners(rows);
+
+    let mut section = String::new();
+    writeln!(section, "## Winners")?;
+    writeln!(section)?;
+    writeln!(
+        section,
+        "A config wins a cell only if its 95% confidence interval lies above the \
+         runner-up's; otherwise every config overlapping the leader is tied."
+    )?;
+    writeln!(section)?;
+    writeln!(section, "| Operation | Scale | Winner | Margin | Runner-up | Verdict |")?;
+    writeln!(section, "|---|---|---|---|---|---|")?;
+    for cell in &winners {
+        let winner = match cell.verdict {
+            CellVerdict::Tie => format!("tie: {}", cell.tied.join(", ")),
+            CellVerdict::Winner => cell.leader.clone(),
+            CellVerdict::Unverified => format!("{} (no CI)", cell.leader),
+        };
+        writeln!(
+            section,
+            "| {} | {} | {} | {:.2}× | {} | {} |",
+            cell.operation,
+            cell.scale,
+            winner,
+            cell.margin(),
+            cell.runner_up.as_ref().map_or("-", |(config, _)| config.as_str()),
+            cell.verdict.name()
+        )?;
+    }
+    let ties = winners.iter().filter(|cell| cell.verdict == CellVerdict::Tie).count();
+    if ties > 0 {
+        writeln!(section)?;
+        writeln!(
+            section,
+            "{}/{} cells are ties: the leading config is not distinguishable from \
+             the runner-up at these run counts.",
+            ties,
+            winners.len()
+        )?;
+    }
+    writeln!(section)?;
+
+    Ok(section)
+}
+
+/// Category of an operation `asbb` can run (`None` for others)
+fn operation_category(name: &str) -> Option<OperationCategory> {
+    asbb_ops::create_operation(name).ok().map(|op| op.category())
+}
+
+/// Category section: pooled speedups per category, then each category's
+/// best config and how many of its operations it also suits
+fn category_section(rows: &[ResultRow]) -> Result<String> {
+    let summary = category_summary(rows, operation_category);
+    let conclusions = category_conclusions(rows, operation_category);
+
+    let mut section = String::new();
+    writeln!(section, "## By operation category")?;
+    writeln!(section)?;
+    if summary.is_empty() {
+        writeln!(section, "No known operation was measured alongside its naive baseline.")?;
+        writeln!(section)?;
+        return Ok(section);
+    }
+    writeln!(
+        section,
+        "Speedup over naive at the same scale, pooled across each category's \
+         operations. A small spread means the category behaves as one unit."
+    )?;
+    writeln!(section)?;
+    writeln!(section, "| Category | Scale | Config | Ops | Mean | Median | Std dev |")?;
+    writeln!(section, "|---|---|---|---|---|---|---|")?;
+    for entry in &summary {
+        writeln!(
+            section,
+            "| {:?} | {} | {} | {} | {:.2}× | {:.2}× | {:.2} |",
+            entry.category,
+            entry.scale,
+            entry.config,
+            entry.operations,
+            entry.mean_speedup,
+            entry.median_speedup,
+            entry.std_dev
+        )?;
+    }
+    writeln!(section)?;
+
+    writeln!(section, "### Category conclusions")?;
+    writeln!(section)?;
+    for conclusion in &conclusions {
+        writeln!(
+            section,
+            "- {:?} @ {}: {} ({:.2}× median); best for {}/{} operations",
+            conclusion.category,
+            conclusion.scale,
+            conclusion.best_config,
+            conclusion.median_speedup,
+            conclusion.agreeing,
+            conclusion.operations
+        )?;
+    }
+    let agreeing: usize = conclusions.iter().map(|c| c.agreeing).sum();
+    let total: usize = conclusions.iter().map(|c| c.operations).sum();
+    if total > 0 {
+        writeln!(section)?;
+        writeln!(
+            section,
+            "Category predicts the best config for {}/{} operation-scale pairs ({:.0}%).",
+            agreeing,
+            total,
+            agreeing as f64 / total as f64 * 100.0
+        )?;
+    }
+    writeln!(section)?;
+
+    Ok(section)
+}
@@ -0,0 +1,84 @@
+//! `asbb rerun`: repeat a run from its reproducibility manifest
+//!
+//! Harnesses write a manifest next to their results (see
+//! `asbb_explorer::reproducibility`). Rerunning checks the recorded dataset
+//! hashes and config against the current files, then executes the recorded
+//! command line in the recorded working directory. Changed inputs stop the
+//! rerun unless `--force` is given; a changed config can be put back with
+//! `--restore-config`. A different platform is only reported: rerunning on
+//! another machine is the point of comparing results.
+
+use anyhow::Result;
+use asbb_explorer::reproducibility::{Drift, RunManifest};
+use std::path::PathBuf;
+
+/// Options for a rerun
+pub struct RerunOptions {
+    /// Manifest written by the original run
+    pub manifest: PathBuf,
+
+    /// Run even if datasets or config differ from the manifest
+    pub force: bool,
+
+    /// Overwrite the config file with the recorded TOML before running
+    pub restore_config: bool,
+
+    /// Check inputs and print the command without running it
+    pub dry_run: bool,
+}
+
+pub fn run(options: &RerunOptions) -> Result<()> {
+    let manifest = RunManifest::load(&options.manifest)?;
+
+    println!("🔁 Rerunning {}", manifest.results.display());
+    println!(
+        "   Recorded: {} on {} (asbb {})",
+        manifest.created_at, manifest.platform, manifest.asbb_version
+    );
+    if let Some(hardware) = &manifest.hardware {
+        println!("   Hardware: {}", hardware.chip_name());
+    }
+    if manifest.translated {
+        println!("   ⚠️  Recorded under Rosetta translation");
+    }
+    println!("   Command: {}", manifest.command_line());
+    println!("   Working directory: {}", manifest.working_dir.display());
+    for (name, seed) in &manifest.seeds {
+        println!("   Seed {}: {}", name, seed);
+    }
+    println!();
+
+    if options.restore_config {
+        if let Some(path) = manifest.restore_config()? {
+            println!("📝 Restored config {}", path.display());
+        }
+    }
+
+    println!("🔍 Checking {} datasets against the manifest", manifest.datasets.len());
+    let drift = manifest.check()?;
+    for difference in &drift {
+        println!("   ⚠️  {}", difference);
+    }
+    let changed: Vec<&Drift> = drift.iter().filter(|d| d.changes_inputs()).collect();
+    if changed.is_empty() {
+        println!("   ✅ Inputs match the recorded run");
+    } else if !options.force {
+        anyhow::bail!(
+            "{} input(s) differ from the manifest; pass --force to rerun anyway",
+            changed.len()
+        );
+    }
+    println!();
+
+    if options.dry_run {
+        println!("🏁 Dry run: not executing");
+        return Ok(());
+    }
+
+    println!("🚀 Executing recorded command");
+    let status = manifest.rerun_command()?.status()?;
+    if !status.success() {
+        anyhow::bail!("Rerun failed ({})", status);
+    }
+    Ok(())
+}
@@ -1,34 +1,58 @@
 //! Level 1/2 Automated Execution Harness
 //!
-//! Runs all 3,000 experiments (20 operations × 25 configs × 6 scales)
-//! with automated checkpointing, parallel execution, and result storage.
+//! Runs all 2,940 experiments of `experiments/level1_primitives/config.toml`
+//! (20 operations, plus a second parser for fastq_parsing, × 28 configs × 5
+//! scales) with automated checkpointing, parallel execution, and result
+//! storage. Every built-in operation of `asbb_ops::catalog` is registered,
+//! so the config can list any of them.
 //!
 //! # Usage
 //!
 //! ```bash
 //! cargo run --release -p asbb-cli --bin run-level1
+//!
+//! # List the remaining experiments and an ETA without running them
+//! cargo run --release -p asbb-cli --bin run-level1 -- --dry-run
+//!
+//! # Log per-experiment progress (-v: info, -vv: debug) and keep a JSON log
+//! # of every debug event for post-mortem analysis
+//! cargo run --release -p asbb-cli --bin run-level1 -- -v --log-json results/level1_primitives/run.jsonl
 //! ```
+//!
+//! Refuses to run under Rosetta translation unless `--force` is given.
+//!
+//! `RUST_LOG` overrides the console verbosity (e.g. `RUST_LOG=asbb_explorer=debug`).
+//!
+//! Ctrl-C lets the in-flight experiment finish, saves results and checkpoint
+//! and prints the command to resume; press it again to exit immediately.
 
 use anyhow::{Context, Result};
-use asbb_core::operation_registry::{Backend, OperationMetadata, OperationRegistry};
-use asbb_core::OperationCategory;
-use asbb_explorer::ExecutionEngine;
-use asbb_ops::*;
-use std::sync::Arc;
+use asbb_explorer::interrupt::{self, Interrupt};
+use asbb_explorer::logging::{self, LogOptions};
+use asbb_explorer::translation::ensure_native;
+use asbb_explorer::{ExecutionEngine, RunStatus};
+use std::path::PathBuf;
 
 fn main() -> Result<()> {
+    logging::init(&log_options()?)?;
+
     println!("🚀 Apple Silicon Bio Bench - Level 1/2 Automated Harness");
     println!("======================================================================");
     println!();
 
     // Create and populate operation registry
     println!("📋 Registering operations...");
-    let registry = create_operation_registry()?;
+    let registry = asbb_ops::catalog::registry()?;
 
     let implemented = registry.list_implemented();
     println!("   Registered {} implemented operations:", implemented.len());
     for (i, op) in implemented.iter().enumerate() {
-        println!("     {}. {}", i + 1, op);
+        let parameters = registry.get_parameters(op)?;
+        if parameters.is_null() {
+            println!("     {}. {}", i + 1, op);
+        } else {
+            println!("     {}. {} {}", i + 1, op, parameters);
+        }
     }
     println!();
 
@@ -38,17 +62,32 @@ fn main() -> Result<()> {
     println!("   Config: {}", config_path);
 
     let engine = ExecutionEngine::from_config_file(config_path, registry)
-        .context("Failed to load execution engine from config")?;
+        .context("Failed to load execution engine from config")?
+        .with_interrupt(Interrupt::install()?);
     println!("   ✅ Configuration loaded successfully");
     println!();
 
+    if std::env::args().any(|arg| arg == "--dry-run") {
+        engine.dry_run()?;
+        return Ok(());
+    }
+
+    ensure_native(std::env::args().any(|arg| arg == "--force"))?;
+
     // Run all experiments
     println!("🔬 Starting experiment execution...");
     println!();
 
-    engine.run_all()
+    let status = engine.run_all()
         .context("Failed to run experiments")?;
 
+    if let RunStatus::Interrupted { completed, remaining } = status {
+        println!();
+        println!("⏸️  Interrupted after {} experiments ({} remaining)", completed, remaining);
+        println!("▶️  Resume with: {}", interrupt::resume_command(&[]));
+        std::process::exit(interrupt::EXIT_CODE);
+    }
+
     println!();
     println!("✅ All experiments complete!");
     println!("📊 Results saved to results/level1_primitives/");
@@ -56,256 +95,17 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-/// Create and populate the operation registry with all 20 operations
-fn create_operation_registry() -> Result<OperationRegistry> {
-    let mut registry = OperationRegistry::new();
-
-    // Element-wise operations (6)
-    registry.register(
-        Arc::new(base_counting::BaseCounting::new()),
-        OperationMetadata {
-            name: "base_counting".to_string(),
-            category: OperationCategory::ElementWise,
-            complexity: 0.40,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Count A, C, G, T bases".to_string()),
-        },
-    );
-
-    registry.register(
-        Arc::new(gc_content::GcContent::new()),
-        OperationMetadata {
-            name: "gc_content".to_string(),
-            category: OperationCategory::ElementWise,
-            complexity: 0.315,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Calculate GC percentage".to_string()),
-        },
-    );
-
-    registry.register(
-        Arc::new(at_content::ATContent {}),
-        OperationMetadata {
-            name: "at_content".to_string(),
-            category: OperationCategory::ElementWise,
-            complexity: 0.35,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Calculate AT percentage".to_string()),
-        },
-    );
-
-    registry.register(
-        Arc::new(sequence_length::SequenceLength {}),
-        OperationMetadata {
-            name: "sequence_length".to_string(),
-            category: OperationCategory::ElementWise,
-            complexity: 0.20,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Measure sequence lengths".to_string()),
-        },
-    );
-
-    registry.register(
-        Arc::new(complexity_score::ComplexityScore::new()),
-        OperationMetadata {
-            name: "complexity_score".to_string(),
-            category: OperationCategory::Aggregation,
-            complexity: 0.61,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel, Backend::Gpu],
-            implemented: true,
-            description: Some("Shannon entropy calculation".to_string()),
-        },
-    );
-
-    registry.register(
-        Arc::new(translation::Translation::new(0, 1).unwrap()),
-        OperationMetadata {
-            name: "translation".to_string(),
-            category: OperationCategory::ElementWise,
-            complexity: 0.40,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("DNA/RNA to protein translation".to_string()),
-        },
-    );
-
-    // Filtering operations (4)
-    registry.register(
-        Arc::new(quality_filter::QualityFilter::new(20)),
-        OperationMetadata {
-            name: "quality_filter".to_string(),
-            category: OperationCategory::Filter,
-            complexity: 0.55,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Filter by quality threshold".to_string()),
-        },
-    );
-
-    registry.register(
-        Arc::new(length_filter::LengthFilter::new(50)),
-        OperationMetadata {
-            name: "length_filter".to_string(),
-            category: OperationCategory::Filter,
-            complexity: 0.25,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Filter by length range".to_string()),
-        },
-    );
-
-    registry.register(
-        Arc::new(sequence_masking::SequenceMasking::new()),
-        OperationMetadata {
-            name: "sequence_masking".to_string(),
-            category: OperationCategory::Filter,
-            complexity: 0.30,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Mask low-quality bases".to_string()),
-        },
-    );
-
-    registry.register(
-        Arc::new(adapter_trimming::AdapterTrimming::new(b"AGATCGGAAGAGC".to_vec(), 5, 0)),
-        OperationMetadata {
-            name: "adapter_trimming".to_string(),
-            category: OperationCategory::Filter,
-            complexity: 0.55,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Detect and remove adapters".to_string()),
-        },
-    );
-
-    // Aggregation operations (4)
-    registry.register(
-        Arc::new(quality_aggregation::QualityAggregation::new()),
-        OperationMetadata {
-            name: "quality_aggregation".to_string(),
-            category: OperationCategory::Aggregation,
-            complexity: 0.50,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Per-position quality stats".to_string()),
-        },
-    );
-
-    registry.register(
-        Arc::new(n_content::NContent {}),
-        OperationMetadata {
-            name: "n_content".to_string(),
-            category: OperationCategory::Aggregation,
-            complexity: 0.38,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Calculate N-base percentage".to_string()),
-        },
-    );
-
-    registry.register(
-        Arc::new(quality_statistics::QualityStatistics::new()),
-        OperationMetadata {
-            name: "quality_statistics".to_string(),
-            category: OperationCategory::Aggregation,
-            complexity: 0.38,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Mean, median, quartiles".to_string()),
-        },
-    );
-
-    registry.register(
-        Arc::new(minhash_sketching::MinHashSketching::new(21, 1000)),
-        OperationMetadata {
-            name: "minhash_sketching".to_string(),
-            category: OperationCategory::Aggregation,
-            complexity: 0.48,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Sequence similarity sketches".to_string()),
-        },
-    );
-
-    // Pairwise operations (2)
-    registry.register(
-        Arc::new(hamming_distance::HammingDistance::new()),
-        OperationMetadata {
-            name: "hamming_distance".to_string(),
-            category: OperationCategory::Pairwise,
-            complexity: 0.35,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Pairwise Hamming distance".to_string()),
-        },
-    );
-
-    registry.register(
-        Arc::new(edit_distance::EditDistance::new(1000)),
-        OperationMetadata {
-            name: "edit_distance".to_string(),
-            category: OperationCategory::Pairwise,
-            complexity: 0.70,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Levenshtein distance (DP)".to_string()),
-        },
-    );
-
-    // Search operations (2)
-    registry.register(
-        Arc::new(kmer_counting::KmerCounting::new(21, false)),
-        OperationMetadata {
-            name: "kmer_counting".to_string(),
-            category: OperationCategory::Search,
-            complexity: 0.45,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("K-mer frequency counting".to_string()),
-        },
-    );
-
-    registry.register(
-        Arc::new(kmer_extraction::KmerExtraction::new(21, false)),
-        OperationMetadata {
-            name: "kmer_extraction".to_string(),
-            category: OperationCategory::Search,
-            complexity: 0.35,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Extract k-mers as records".to_string()),
-        },
-    );
-
-    // Transform operations (1) - use ElementWise category
-    registry.register(
-        Arc::new(reverse_complement::ReverseComplement::new()),
-        OperationMetadata {
-            name: "reverse_complement".to_string(),
-            category: OperationCategory::ElementWise,
-            complexity: 0.45,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Reverse complement sequences".to_string()),
-        },
-    );
-
-    // I/O operations (1)
-    registry.register(
-        Arc::new(fastq_parsing::FastqParsing::new(true)),
-        OperationMetadata {
-            name: "fastq_parsing".to_string(),
-            category: OperationCategory::IO,
-            complexity: 0.25,
-            backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
-            implemented: true,
-            description: Some("Parse FASTQ format".to_string()),
-        },
-    );
-
-    Ok(registry)
+/// Logging flags: `-v` (repeatable, or `-vv`) and `--log-json <path>`
+fn log_options() -> Result<LogOptions> {
+    let mut options = LogOptions::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--log-json" {
+            let path = args.next().context("--log-json requires a path")?;
+            options.json_path = Some(PathBuf::from(path));
+        } else if arg.len() > 1 && arg.starts_with('-') && arg[1..].bytes().all(|b| b == b'v') {
+            options.verbosity += (arg.len() - 1) as u8;
+        }
+    }
+    Ok(options)
 }
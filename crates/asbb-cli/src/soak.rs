@@ -0,0 +1,152 @@
+//! `asbb soak`: sustained throughput under continuous load
+//!
+//! Runs one operation back to back for several minutes and prints the
+//! throughput and thermal state of each window, then burst vs sustained
+//! throughput. Compare a fanless Air with a MacBook Pro:
+//!
+//! ```bash
+//! asbb soak --input datasets/large_100000_150bp.fq --operation base_counting \
+//!   --threads 8 --minutes 10 --output results/soak/air_base_counting.csv
+//! ```
+
+use anyhow::{Context, Result};
+use asbb_core::io::FastqReader;
+use asbb_core::HardwareConfig;
+use asbb_explorer::soak::{run_soak, SoakConfig, SoakWindow, THROTTLE_THRESHOLD};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Options for a soak run
+pub struct SoakOptions {
+    /// Dataset FASTQ file
+    pub input: PathBuf,
+
+    /// Operation to run
+    pub operation: String,
+
+    /// Use the naive backend instead of NEON
+    pub naive: bool,
+
+    /// Threads for the NEON backend (1 runs single-threaded)
+    pub threads: usize,
+
+    /// Total soak time
+    pub duration: Duration,
+
+    /// Reporting window
+    pub window: Duration,
+
+    /// Optional per-window CSV output
+    pub output: Option<PathBuf>,
+}
+
+pub fn run(options: &SoakOptions) -> Result<()> {
+    let data = FastqReader::from_path(&options.input)
+        .with_context(|| format!("Failed to open {}", options.input.display()))?
+        .read_all()?;
+    let operation = asbb_ops::create_operation(&options.operation)?;
+
+    let mut config = HardwareConfig::naive();
+    config.use_neon = !options.naive;
+    // Parallel execution always uses the NEON kernels
+    config.num_threads = if options.naive { 1 } else { options.threads.max(1) };
+
+    println!("🔥 Thermal soak: {}", options.operation);
+    println!("   Input: {} ({} reads)", options.input.display(), data.len());
+    println!(
+        "   Config: {}, {} thread(s)",
+        if options.naive { "naive" } else { "neon" },
+        config.num_threads
+    );
+    println!(
+        "   Duration: {:.0}s in {:.0}s windows",
+        options.duration.as_secs_f64(),
+        options.window.as_secs_f64()
+    );
+    println!();
+    println!("   {:>8} {:>8} {:>14} {:>10}  Thermal", "Start", "Runs", "Seqs/sec", "vs burst");
+
+    let mut burst = None;
+    let soak = SoakConfig {
+        duration: options.duration,
+        window: options.window,
+    };
+    let result = run_soak(operation.as_ref(), &data, &config, &soak, |window: &SoakWindow| {
+        let burst = *burst.get_or_insert(window.throughput_seqs_per_sec);
+        println!(
+            "   {:>7.0}s {:>8} {:>14.0} {:>9.1}%  {}",
+            window.start_secs,
+            window.runs,
+            window.throughput_seqs_per_sec,
+            window.throughput_seqs_per_sec / burst * 100.0,
+            window.thermal_state.map(|s| s.name()).unwrap_or("-")
+        );
+    })?;
+    println!();
+
+    println!("📊 Burst: {:.0} seqs/sec", result.burst_throughput());
+    println!(
+        "   Sustained: {:.0} seqs/sec ({:.1}% of burst)",
+        result.sustained_throughput(),
+        result.sustained_ratio() * 100.0
+    );
+    match result.throttle_onset_secs(THROTTLE_THRESHOLD) {
+        Some(onset) => println!(
+            "   Throttling: below {:.0}% of burst after {:.0}s",
+            THROTTLE_THRESHOLD * 100.0,
+            onset
+        ),
+        None => println!("   Throttling: none (stayed within {:.0}% of burst)", THROTTLE_THRESHOLD * 100.0),
+    }
+    println!(
+        "   Peak thermal state: {}",
+        result.peak_thermal_state().map(|s| s.name()).unwrap_or("unavailable")
+    );
+
+    if let Some(path) = &options.output {
+        write_csv(path, &options.operation, &config, &result.windows)?;
+        println!("📄 Wrote {} windows to {}", result.windows.len(), path.display());
+    }
+    Ok(())
+}
+
+fn write_csv(
+    path: &Path,
+    operation: &str,
+    config: &HardwareConfig,
+    windows: &[SoakWindow],
+) -> Result<()> {
+    let burst = windows.first().map(|w| w.throughput_seqs_per_sec).unwrap_or(0.0);
+    let config_name = match (config.use_neon, config.num_threads) {
+        (false, _) => "naive".to_string(),
+        (true, 1) => "neon".to_string(),
+        (true, threads) => format!("neon_{}t", threads),
+    };
+
+    let mut csv = String::from(
+        "operation,config_name,threads,platform,window,start_secs,end_secs,runs,sequences,\
+         throughput_seqs_per_sec,relative_to_burst,thermal_state\n",
+    );
+    for window in windows {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{:.3},{:.3},{},{},{:.2},{:.4},{}\n",
+            operation,
+            config_name,
+            config.num_threads,
+            crate::bench::platform(),
+            window.index,
+            window.start_secs,
+            window.end_secs,
+            window.runs,
+            window.sequences,
+            window.throughput_seqs_per_sec,
+            if burst > 0.0 { window.throughput_seqs_per_sec / burst } else { 0.0 },
+            window.thermal_state.map(|s| s.name()).unwrap_or_default()
+        ));
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, csv).with_context(|| format!("Failed to write {}", path.display()))
+}
@@ -0,0 +1,141 @@
+//! `asbb strategies`: strategy × hardware-config comparison
+//!
+//! Benchmarks every strategy of a preset (e.g. gather-and-sort vs streaming
+//! quality statistics) under each hardware config of `asbb bench` and
+//! reports, per cell, the hardware speedup, the ratio to the baseline
+//! strategy, and their interaction: whether the better strategy changes with
+//! the config. Interactions whose 95% CI excludes 1.0 are flagged.
+
+use anyhow::{Context, Result};
+use asbb_core::io::FastqReader;
+use asbb_core::HardwareProfile;
+use asbb_explorer::strategy::{benchmark_strategies, StrategyComparison, StrategyComparisonResult};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Options for a strategy comparison
+pub struct StrategyOptions {
+    /// Dataset FASTQ file
+    pub input: PathBuf,
+
+    /// Preset name (`asbb_explorer::strategy::PRESETS`)
+    pub preset: String,
+
+    /// Thread counts for the parallel configs
+    pub threads: Vec<usize>,
+
+    /// Detected hardware (`--auto`)
+    pub profile: Option<HardwareProfile>,
+
+    /// Warmup runs per (strategy, config) cell (not measured)
+    pub warmup: usize,
+
+    /// Measured runs per cell
+    pub runs: usize,
+
+    /// Optional CSV output (one row per cell)
+    pub output: Option<PathBuf>,
+}
+
+pub fn run(options: &StrategyOptions) -> Result<()> {
+    let comparison = StrategyComparison::preset(&options.preset)?;
+    let configs = crate::bench::configs(&options.threads, options.profile.as_ref());
+    crate::bench::validate_configs(&configs, options.profile.as_ref())?;
+
+    let data = FastqReader::from_path(&options.input)
+        .with_context(|| format!("Failed to open {}", options.input.display()))?
+        .read_all()?;
+
+    println!("🔀 Strategies: {} ({} strategies)", comparison.name, comparison.strategies.len());
+    for strategy in &comparison.strategies {
+        println!("   - {} ({})", strategy.label, strategy.operation.name());
+    }
+    println!("📂 {} ({} reads)", options.input.display(), data.len());
+    println!("   Runs: {} (+{} warmup)", options.runs, options.warmup);
+    println!();
+
+    let result = benchmark_strategies(&comparison, &data, &configs, options.warmup, options.runs)
+        .with_context(|| format!("Strategy comparison {}", comparison.name))?;
+    if !result.strategies_agree {
+        println!("⚠️  Strategies disagree with {} on this input", comparison.strategies[0].label);
+        println!();
+    }
+
+    println!(
+        "   {:<14} {:<16} {:>14} {:>9} {:>9} {:>12}  Interaction 95% CI",
+        "Strategy", "Config", "Seqs/sec", "Hardware", "Strategy", "Interaction"
+    );
+    for (cell, effect) in result.cells.iter().zip(&result.effects) {
+        let ci = effect
+            .interaction_ci
+            .map(|(lower, upper)| {
+                let flag = if effect.significant() { " *" } else { "" };
+                format!("[{:.2}, {:.2}]{}", lower, upper, flag)
+            })
+            .unwrap_or_else(|| "-".to_string());
+        let check = if cell.output_matches_reference { "" } else { " ❌" };
+
+        println!(
+            "   {:<14} {:<16} {:>14.0} {:>8.2}× {:>8.2}× {:>11.2}×  {}{}",
+            cell.strategy,
+            cell.config_name,
+            cell.median_throughput,
+            effect.hardware_speedup,
+            effect.strategy_ratio,
+            effect.interaction,
+            ci,
+            check
+        );
+    }
+
+    let significant = result.effects.iter().filter(|e| e.significant()).count();
+    println!();
+    if let Some(best) = result.best() {
+        println!("🏆 Fastest: {} on {}", best.strategy, best.config_name);
+    }
+    println!(
+        "📊 {} of {} strategy × config interactions significant (* = CI excludes 1.0)",
+        significant,
+        (comparison.strategies.len() - 1) * configs.len().saturating_sub(1)
+    );
+
+    if let Some(path) = &options.output {
+        write_csv(path, &result)?;
+        println!();
+        println!("📄 Wrote {} cells to {}", result.cells.len(), path.display());
+    }
+    Ok(())
+}
+
+fn write_csv(path: &Path, result: &StrategyComparisonResult) -> Result<()> {
+    let mut csv = String::from(
+        "preset,strategy,operation,config_name,threads,num_sequences,platform,median_throughput,hardware_speedup,strategy_ratio,interaction,interaction_ci_lower,interaction_ci_upper\n",
+    );
+    let platform = crate::bench::platform();
+    for (cell, effect) in result.cells.iter().zip(&result.effects) {
+        let (lower, upper) = effect
+            .interaction_ci
+            .map(|(lower, upper)| (format!("{:.4}", lower), format!("{:.4}", upper)))
+            .unwrap_or_default();
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{:.2},{:.4},{:.4},{:.4},{},{}\n",
+            result.comparison,
+            cell.strategy,
+            cell.operation,
+            cell.config_name,
+            cell.num_threads,
+            result.num_sequences,
+            platform,
+            cell.median_throughput,
+            effect.hardware_speedup,
+            effect.strategy_ratio,
+            effect.interaction,
+            lower,
+            upper
+        ));
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, csv).with_context(|| format!("Failed to write {}", path.display()))
+}
@@ -0,0 +1,287 @@
+//! `asbb validate`: check every backend against golden outputs
+//!
+//! Golden outputs are produced by the naive backend with `--bless` and stored
+//! per (dataset, operation) by `asbb_explorer::golden`. Validation then runs
+//! each backend (naive, NEON, parallel, chunked parallel, GPU, AMX, 2-bit) and reports a
+//! field-level diff for any output that differs from the golden.
+//!
+//! GPU and 2-bit paths are not reachable through `PrimitiveOperation` (they
+//! return typed results or take `BitSeq` input), so they are dispatched here
+//! per operation.
+
+use anyhow::{Context, Result};
+use asbb_core::compare::Tolerance;
+use asbb_core::encoding::BitSeq;
+use asbb_core::io::FastqReader;
+use asbb_core::operation_registry::Backend;
+use asbb_core::{OperationOutput, PrimitiveOperation, SequenceRecord};
+use asbb_datagen::manifest::sha256_file;
+use asbb_explorer::golden::{execute_backend, GoldenOutput, GoldenStore, ValidationStatus};
+use asbb_ops::{
+    at_content::ATContent, base_counting::BaseCounting, create_operation,
+    gc_content::GcContent, kmer_spectrum::{KmerSpectrum, SpectrumMode},
+    reverse_complement::ReverseComplement, sequence_length::SequenceLength,
+};
+#[cfg(all(target_os = "macos", feature = "gpu"))]
+use asbb_ops::{complexity_score::ComplexityScore, quality_aggregation::QualityAggregation};
+use std::path::{Path, PathBuf};
+
+/// Operations validated when `--operations` is not given
+pub const DEFAULT_OPERATIONS: &[&str] = &[
+    "base_counting",
+    "gc_content",
+    "at_content",
+    "n_content",
+    "reverse_complement",
+    "sequence_length",
+    "quality_aggregation",
+    "quality_filter",
+    "length_filter",
+    "complexity_score",
+];
+
+/// Backends checked against each golden, in report order
+const BACKENDS: &[Backend] = &[
+    Backend::Naive,
+    Backend::Neon,
+    Backend::Parallel,
+    Backend::ParallelChunked,
+    Backend::ParallelIntraRead,
+    Backend::Gpu,
+    Backend::Amx,
+    Backend::TwoBit,
+];
+
+/// Options for a validation run
+pub struct ValidateOptions {
+    /// Dataset name (golden subdirectory)
+    pub dataset: String,
+
+    /// Dataset FASTQ file
+    pub input: PathBuf,
+
+    /// Operations to validate
+    pub operations: Vec<String>,
+
+    /// Golden output directory
+    pub golden_dir: PathBuf,
+
+    /// Regenerate goldens from the naive backend instead of validating
+    pub bless: bool,
+
+    /// Float tolerance for comparisons
+    pub tolerance: Tolerance,
+}
+
+/// Validate (or bless) every requested operation; fails if any backend mismatches
+pub fn run(options: &ValidateOptions) -> Result<()> {
+    let store = GoldenStore::new(&options.golden_dir);
+    let data = FastqReader::from_path(&options.input)?.read_all()?;
+    let sha256 = sha256_file(&options.input)?;
+
+    println!("🔬 Validating against golden outputs");
+    println!("   Dataset: {} ({} reads)", options.dataset, data.len());
+    println!("   Goldens: {}", options.golden_dir.display());
+    println!("   Float tolerance: {:e} (relative)", options.tolerance.relative);
+    println!();
+
+    let mut failures = 0;
+    for name in &options.operations {
+        let operation = create_operation(name)?;
+
+        if options.bless {
+            let path = bless(&store, name, operation.as_ref(), &data, &options.dataset, &sha256)?;
+            println!("✍️  {}: blessed → {}", name, path.display());
+            continue;
+        }
+
+        let golden = match load_golden(&store, name, &options.dataset, &sha256) {
+            Ok(golden) => golden,
+            Err(e) => {
+                println!("❌ {}: {:#}", name, e);
+                failures += 1;
+                continue;
+            }
+        };
+
+        // Field overrides from the command line win over the operation's
+        let mut tolerance = options.tolerance.clone();
+        for (field, relative) in operation.output_tolerance().fields {
+            tolerance.fields.entry(field).or_insert(relative);
+        }
+
+        println!("🧪 {}", name);
+        for &backend in BACKENDS {
+            let actual = execute(operation.as_ref(), &data, backend);
+            let status = ValidationStatus::check(&golden, actual, &tolerance);
+            report(backend, &status);
+            if status.is_failure() {
+                failures += 1;
+            }
+        }
+    }
+
+    println!();
+    if failures > 0 {
+        anyhow::bail!("{} validation failure(s)", failures);
+    }
+    if !options.bless {
+        println!("✅ All backends match the golden outputs");
+    }
+    Ok(())
+}
+
+/// Compute and store the golden output from the naive backend
+///
+/// Stored under the name it was requested by, so variants of one operation
+/// (e.g. `record_sort_by_id`) get their own goldens.
+fn bless(
+    store: &GoldenStore,
+    name: &str,
+    operation: &dyn PrimitiveOperation,
+    data: &[SequenceRecord],
+    dataset: &str,
+    sha256: &str,
+) -> Result<PathBuf> {
+    let output = operation
+        .execute_naive(data)
+        .with_context(|| format!("Naive {} failed", operation.name()))?;
+
+    store.save(&GoldenOutput {
+        operation: name.to_string(),
+        dataset: dataset.to_string(),
+        dataset_sha256: sha256.to_string(),
+        num_records: data.len(),
+        generated_by: Backend::Naive,
+        output,
+    })
+}
+
+/// Load a golden, refusing ones computed from a different dataset file
+fn load_golden(
+    store: &GoldenStore,
+    operation: &str,
+    dataset: &str,
+    sha256: &str,
+) -> Result<GoldenOutput> {
+    let golden = store.load(dataset, operation)?.with_context(|| {
+        format!(
+            "no golden output at {} (run with --bless)",
+            store.path(dataset, operation).display()
+        )
+    })?;
+
+    if golden.dataset_sha256 != sha256 {
+        anyhow::bail!(
+            "golden was computed from a different file (SHA-256 {}, now {}); re-run with --bless",
+            short(&golden.dataset_sha256),
+            short(sha256)
+        );
+    }
+    Ok(golden)
+}
+
+fn report(backend: Backend, status: &ValidationStatus) {
+    let label = format!("{:?}", backend);
+    match status {
+        ValidationStatus::Pass => println!("   ✅ {:<15} match", label),
+        ValidationStatus::Skipped(reason) => println!("   ⏭️  {:<15} skipped ({})", label, reason),
+        ValidationStatus::Error(e) => println!("   ❌ {:<15} error: {}", label, e),
+        ValidationStatus::Mismatch { total, diffs } => {
+            println!("   ❌ {:<15} {} difference(s)", label, total);
+            for diff in diffs {
+                println!("         {}", diff);
+            }
+            if *total > diffs.len() {
+                println!("         ... {} more", total - diffs.len());
+            }
+        }
+    }
+}
+
+fn short(sha256: &str) -> &str {
+    &sha256[..sha256.len().min(12)]
+}
+
+/// Golden directory name for a dataset file (its file stem)
+pub fn dataset_name(input: &Path) -> String {
+    input
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// Backend Dispatch
+// ============================================================================
+
+/// [`create_operation`], with count tables held under `memory_budget` bytes
+///
+/// Operations with an external-memory mode (`kmer_spectrum`) spill to disk
+/// past the budget; the others are unaffected.
+pub(crate) fn create_operation_within(
+    name: &str,
+    memory_budget: Option<usize>,
+) -> Result<Box<dyn PrimitiveOperation>> {
+    match (name, memory_budget) {
+        ("kmer_spectrum", Some(memory_budget_bytes)) => Ok(Box::new(
+            KmerSpectrum::new(21, true).with_mode(SpectrumMode::Spill { memory_budget_bytes }),
+        )),
+        _ => create_operation(name),
+    }
+}
+
+fn execute(
+    operation: &dyn PrimitiveOperation,
+    data: &[SequenceRecord],
+    backend: Backend,
+) -> Result<OperationOutput> {
+    match backend {
+        Backend::TwoBit => execute_two_bit(operation.name(), data),
+        #[cfg(all(target_os = "macos", feature = "gpu"))]
+        Backend::Gpu => execute_gpu(operation, data),
+        _ => execute_backend(operation, data, backend),
+    }
+}
+
+/// Run the 2-bit NEON path (scalar fallback off aarch64)
+fn execute_two_bit(name: &str, data: &[SequenceRecord]) -> Result<OperationOutput> {
+    let encoded = || -> Vec<BitSeq> {
+        data.iter()
+            .map(|record| BitSeq::from_ascii(&record.sequence))
+            .collect()
+    };
+
+    match name {
+        "base_counting" => BaseCounting::new().execute_2bit_neon(&encoded()),
+        "gc_content" => GcContent::new().execute_2bit_neon(&encoded()),
+        "at_content" => ATContent.execute_2bit_neon(&encoded()),
+        "reverse_complement" => ReverseComplement::new().execute_2bit_neon(&encoded()),
+        "sequence_length" => SequenceLength.execute_2bit_neon(&encoded()),
+        _ => anyhow::bail!("2-bit execution not implemented for {}", name),
+    }
+}
+
+/// Run the Metal implementation and wrap its typed result like the CPU paths
+#[cfg(all(target_os = "macos", feature = "gpu"))]
+fn execute_gpu(operation: &dyn PrimitiveOperation, data: &[SequenceRecord]) -> Result<OperationOutput> {
+    match operation.name() {
+        "base_counting" => {
+            let (counts, _) = BaseCounting::new().execute_gpu(data)?;
+            Ok(OperationOutput::typed(counts))
+        }
+        "complexity_score" => {
+            let (result, _) = ComplexityScore::new().execute_gpu(data)?;
+            Ok(OperationOutput::typed(result))
+        }
+        "quality_aggregation" => {
+            let (stats, _) = QualityAggregation::new().execute_gpu(data)?;
+            Ok(OperationOutput::typed(stats))
+        }
+        "reverse_complement" => {
+            let (records, _) = ReverseComplement::new().execute_gpu(data)?;
+            Ok(OperationOutput::Records(records))
+        }
+        _ => execute_backend(operation, data, Backend::Gpu),
+    }
+}
@@ -0,0 +1,133 @@
+//! `asbb workload`: whole-report benchmark of a workload-mix preset
+//!
+//! Runs every step of a preset (e.g. the FastQC-equivalent report) back to
+//! back under each hardware config of `asbb bench` and reports whole-report
+//! wall time, the speedup over the naive config, and which step dominates.
+//! FASTQ parsing is timed once and shown separately so the compute-only and
+//! parse-inclusive report times can both be read off.
+
+use anyhow::{Context, Result};
+use asbb_core::io::FastqReader;
+use asbb_core::HardwareProfile;
+use asbb_explorer::workload::{benchmark_workload, WorkloadMix, WorkloadResult};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Options for a workload benchmark
+pub struct WorkloadOptions {
+    /// Dataset FASTQ file
+    pub input: PathBuf,
+
+    /// Preset name (`asbb_explorer::workload::PRESETS`)
+    pub preset: String,
+
+    /// Thread counts for the parallel configs
+    pub threads: Vec<usize>,
+
+    /// Detected hardware (`--auto`)
+    pub profile: Option<HardwareProfile>,
+
+    /// Warmup reports per config (not measured)
+    pub warmup: usize,
+
+    /// Measured reports per config
+    pub runs: usize,
+
+    /// Optional CSV output (one row per config and step, plus a `total` row)
+    pub output: Option<PathBuf>,
+}
+
+pub fn run(options: &WorkloadOptions) -> Result<()> {
+    let mix = WorkloadMix::preset(&options.preset)?;
+    let configs = crate::bench::configs(&options.threads, options.profile.as_ref());
+    crate::bench::validate_configs(&configs, options.profile.as_ref())?;
+
+    let start = Instant::now();
+    let data = FastqReader::from_path(&options.input)
+        .with_context(|| format!("Failed to open {}", options.input.display()))?
+        .read_all()?;
+    let parse_time = start.elapsed();
+
+    println!("📋 Workload: {} ({} steps)", mix.name, mix.steps.len());
+    for step in &mix.steps {
+        println!("   - {} ({})", step.label, step.operation.name());
+    }
+    println!(
+        "📂 {} ({} reads, parsed in {:.1} ms)",
+        options.input.display(),
+        data.len(),
+        parse_time.as_secs_f64() * 1000.0
+    );
+    println!("   Runs: {} (+{} warmup)", options.runs, options.warmup);
+    println!();
+
+    println!(
+        "   {:<16} {:>12} {:>16} {:>10}  Slowest step",
+        "Config", "Report (ms)", "+ parse (ms)", "Speedup"
+    );
+    let mut results = Vec::new();
+    let mut baseline = None;
+    for (config_name, config) in &configs {
+        let result = benchmark_workload(&mix, &data, config, options.warmup, options.runs)
+            .with_context(|| format!("{} / {}", mix.name, config_name))?;
+        let wall = result.wall_median.as_secs_f64();
+        let baseline = *baseline.get_or_insert(wall);
+        let slowest = result
+            .slowest_step()
+            .map(|step| format!("{} ({:.0}%)", step.label, step.share * 100.0))
+            .unwrap_or_default();
+
+        println!(
+            "   {:<16} {:>12.2} {:>16.2} {:>9.2}×  {}",
+            config_name,
+            wall * 1000.0,
+            (wall + parse_time.as_secs_f64()) * 1000.0,
+            baseline / wall.max(f64::MIN_POSITIVE),
+            slowest
+        );
+        results.push((config_name.clone(), config.num_threads, result));
+    }
+
+    if let Some(path) = &options.output {
+        write_csv(path, &results, parse_time.as_secs_f64())?;
+        println!();
+        println!("📄 Wrote {} configs to {}", results.len(), path.display());
+    }
+    Ok(())
+}
+
+fn write_csv(path: &Path, results: &[(String, usize, WorkloadResult)], parse_secs: f64) -> Result<()> {
+    let mut csv = String::from(
+        "workload,config_name,threads,num_sequences,platform,step,operation,median_seconds,share,parse_seconds\n",
+    );
+    let platform = crate::bench::platform();
+    for (config_name, threads, result) in results {
+        let prefix = format!(
+            "{},{},{},{},{}",
+            result.workload, config_name, threads, result.num_sequences, platform
+        );
+        for step in &result.steps {
+            csv.push_str(&format!(
+                "{},{},{},{:.9},{:.4},{:.9}\n",
+                prefix,
+                step.label,
+                step.operation,
+                step.median.as_secs_f64(),
+                step.share,
+                parse_secs
+            ));
+        }
+        csv.push_str(&format!(
+            "{},total,{},{:.9},1.0000,{:.9}\n",
+            prefix,
+            result.workload,
+            result.wall_median.as_secs_f64(),
+            parse_secs
+        ));
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, csv).with_context(|| format!("Failed to write {}", path.display()))
+}
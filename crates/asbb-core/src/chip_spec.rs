@@ -0,0 +1,157 @@
+//! Published specifications of Apple Silicon chips
+//!
+//! Memory bandwidth, core counts and GPU cores differ far more between the
+//! variants of one generation (an M3 Max has 4× the bandwidth of an M3) than
+//! between generations, so specs are keyed by (generation, variant). Binned
+//! parts (e.g. the 14-core M3 Max with 300 GB/s instead of 400) are extra
+//! rows with the same key and fewer P-cores; [`ChipSpec::for_detected`]
+//! picks the row matching the detected P-core count.
+//!
+//! Figures are Apple's published numbers; rows marked `estimated` are for
+//! chips not yet released, extrapolated from the previous generation.
+
+use crate::{ChipGeneration, ChipVariant};
+use serde::{Deserialize, Serialize};
+
+/// One chip configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChipSpec {
+    pub chip: ChipGeneration,
+    pub variant: ChipVariant,
+    pub p_cores: usize,
+    pub e_cores: usize,
+    pub gpu_cores: usize,
+
+    /// Largest memory configuration (GB)
+    pub max_memory_gb: usize,
+
+    /// Peak memory bandwidth (GB/s)
+    pub memory_bandwidth_gbps: f64,
+
+    /// Extrapolated rather than published
+    pub estimated: bool,
+}
+
+const fn spec(
+    chip: ChipGeneration,
+    variant: ChipVariant,
+    (p_cores, e_cores, gpu_cores): (usize, usize, usize),
+    max_memory_gb: usize,
+    memory_bandwidth_gbps: f64,
+    estimated: bool,
+) -> ChipSpec {
+    ChipSpec {
+        chip,
+        variant,
+        p_cores,
+        e_cores,
+        gpu_cores,
+        max_memory_gb,
+        memory_bandwidth_gbps,
+        estimated,
+    }
+}
+
+use ChipGeneration::*;
+use ChipVariant::*;
+
+/// Every known configuration; the first row of each key is the fullest part
+pub const CHIP_SPECS: &[ChipSpec] = &[
+    spec(M1, Base, (4, 4, 8), 16, 68.25, false),
+    spec(M1, Pro, (8, 2, 16), 32, 200.0, false),
+    spec(M1, Pro, (6, 2, 14), 32, 200.0, false),
+    spec(M1, Max, (8, 2, 32), 64, 400.0, false),
+    spec(M1, Ultra, (16, 4, 64), 128, 800.0, false),
+    spec(M2, Base, (4, 4, 10), 24, 102.4, false),
+    spec(M2, Pro, (8, 4, 19), 32, 200.0, false),
+    spec(M2, Pro, (6, 4, 16), 32, 200.0, false),
+    spec(M2, Max, (8, 4, 38), 96, 400.0, false),
+    spec(M2, Ultra, (16, 8, 76), 192, 800.0, false),
+    spec(M3, Base, (4, 4, 10), 24, 102.4, false),
+    spec(M3, Pro, (6, 6, 18), 36, 150.0, false),
+    spec(M3, Pro, (5, 6, 14), 36, 150.0, false),
+    spec(M3, Max, (12, 4, 40), 128, 400.0, false),
+    spec(M3, Max, (10, 4, 30), 96, 300.0, false),
+    spec(M3, Ultra, (24, 8, 80), 512, 819.0, false),
+    spec(M4, Base, (4, 6, 10), 32, 120.0, false),
+    spec(M4, Pro, (10, 4, 20), 64, 273.0, false),
+    spec(M4, Pro, (8, 4, 16), 64, 273.0, false),
+    spec(M4, Max, (12, 4, 40), 128, 546.0, false),
+    spec(M4, Max, (10, 4, 32), 36, 410.0, false),
+    spec(M4, Ultra, (24, 8, 80), 256, 1092.0, true),
+    spec(M5, Base, (4, 6, 10), 32, 153.6, false),
+    spec(M5, Pro, (10, 4, 20), 64, 307.0, true),
+    spec(M5, Max, (12, 4, 40), 128, 614.0, true),
+    spec(M5, Ultra, (24, 8, 80), 256, 1228.0, true),
+];
+
+impl ChipSpec {
+    /// Fullest configuration of a chip
+    pub fn lookup(chip: &ChipGeneration, variant: ChipVariant) -> Option<&'static ChipSpec> {
+        Self::variants(chip, variant).next()
+    }
+
+    /// Configuration matching a detected P-core count, falling back to the
+    /// fullest one for unlisted bins
+    pub fn for_detected(
+        chip: &ChipGeneration,
+        variant: ChipVariant,
+        p_cores: usize,
+    ) -> Option<&'static ChipSpec> {
+        Self::variants(chip, variant)
+            .find(|spec| spec.p_cores == p_cores)
+            .or_else(|| Self::lookup(chip, variant))
+    }
+
+    /// All configurations of a chip, fullest first
+    pub fn variants(
+        chip: &ChipGeneration,
+        variant: ChipVariant,
+    ) -> impl Iterator<Item = &'static ChipSpec> + '_ {
+        CHIP_SPECS
+            .iter()
+            .filter(move |spec| spec.chip == *chip && spec.variant == variant)
+    }
+
+    /// Bandwidth per P-core (GB/s), a rough ceiling for one streaming thread
+    pub fn bandwidth_per_p_core_gbps(&self) -> f64 {
+        self.memory_bandwidth_gbps / self.p_cores.max(1) as f64
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_chip_has_a_spec() {
+        for chip in [M1, M2, M3, M4, M5] {
+            for variant in [Base, Pro, Max, Ultra] {
+                let spec = ChipSpec::lookup(&chip, variant).unwrap();
+                // Fullest part first
+                assert!(ChipSpec::variants(&chip, variant).all(|v| v.p_cores <= spec.p_cores));
+            }
+        }
+        assert!(ChipSpec::lookup(&Other("A17 Pro".to_string()), Base).is_none());
+        // Base bandwidth agrees with the generation table
+        assert_eq!(
+            ChipSpec::lookup(&M1, Base).unwrap().memory_bandwidth_gbps,
+            M1.memory_bandwidth_gbps()
+        );
+    }
+
+    #[test]
+    fn test_binned_parts() {
+        assert_eq!(ChipSpec::lookup(&M3, Max).unwrap().memory_bandwidth_gbps, 400.0);
+        let binned = ChipSpec::for_detected(&M3, Max, 10).unwrap();
+        assert_eq!(binned.memory_bandwidth_gbps, 300.0);
+        assert_eq!(binned.gpu_cores, 30);
+
+        // Unlisted bin: fullest part
+        assert_eq!(ChipSpec::for_detected(&M4, Max, 11).unwrap().p_cores, 12);
+    }
+}
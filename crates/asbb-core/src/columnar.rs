@@ -0,0 +1,139 @@
+//! Columnar (position-major) quality storage
+//!
+//! Per-position aggregations such as quality statistics read one position of
+//! every read at a time. On `Vec<SequenceRecord>` (or [`PackedRecords`]) that
+//! is a strided gather across one heap buffer per read, repeated for every
+//! position. `QualityColumns` stores the transpose: all qualities at position
+//! 0, then all at position 1, and so on, so each position is one contiguous
+//! slice that can be streamed with SIMD.
+//!
+//! Reads of different lengths are supported: column `p` holds, in read order,
+//! the qualities of the reads longer than `p`, so columns shrink towards the
+//! 3' end. Reads without qualities contribute nothing.
+//!
+//! The transpose is a one-off scatter over all qualities; it pays off when
+//! the columns are built at load time or reused by several aggregations.
+//!
+//! [`PackedRecords`]: crate::packed::PackedRecords
+
+use crate::SequenceRecord;
+use std::mem::size_of;
+
+/// Quality scores stored column-major by position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QualityColumns {
+    num_reads: usize,
+    data: Vec<u8>,
+    /// Column `p` spans `column_offsets[p]..column_offsets[p + 1]`
+    column_offsets: Vec<usize>,
+}
+
+impl QualityColumns {
+    /// Transpose the qualities of `records`
+    pub fn from_records(records: &[SequenceRecord]) -> Self {
+        let qualities = || records.iter().filter_map(|r| r.quality.as_deref());
+
+        // Column heights: reads longer than each position
+        let max_len = qualities().map(<[u8]>::len).max().unwrap_or(0);
+        let mut heights = vec![0usize; max_len + 1];
+        for quality in qualities() {
+            heights[quality.len()] += 1;
+        }
+        for len in (0..max_len).rev() {
+            heights[len] += heights[len + 1];
+        }
+
+        let mut column_offsets = Vec::with_capacity(max_len + 1);
+        column_offsets.push(0);
+        for height in &heights[1..] {
+            column_offsets.push(column_offsets.last().unwrap() + height);
+        }
+
+        // Scatter each read into the next free slot of every column
+        let mut data = vec![0u8; *column_offsets.last().unwrap()];
+        let mut cursors = column_offsets[..max_len].to_vec();
+        for quality in qualities() {
+            for (cursor, &q) in cursors.iter_mut().zip(quality) {
+                data[*cursor] = q;
+                *cursor += 1;
+            }
+        }
+
+        Self {
+            num_reads: records.len(),
+            data,
+            column_offsets,
+        }
+    }
+
+    /// Number of records transposed (including any without qualities)
+    pub fn num_reads(&self) -> usize {
+        self.num_reads
+    }
+
+    /// Number of positions (length of the longest quality string)
+    pub fn num_positions(&self) -> usize {
+        self.column_offsets.len() - 1
+    }
+
+    /// Total quality scores stored
+    pub fn total_scores(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Qualities at position `p`, in read order
+    pub fn column(&self, p: usize) -> &[u8] {
+        &self.data[self.column_offsets[p]..self.column_offsets[p + 1]]
+    }
+
+    /// All columns, position 0 first
+    pub fn columns(&self) -> impl ExactSizeIterator<Item = &[u8]> + '_ {
+        self.column_offsets.windows(2).map(|w| &self.data[w[0]..w[1]])
+    }
+
+    /// Heap bytes held by the buffers (capacity, not length)
+    pub fn heap_bytes(&self) -> usize {
+        self.data.capacity() + self.column_offsets.capacity() * size_of::<usize>()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transpose_variable_lengths() {
+        let records = vec![
+            SequenceRecord::fastq("r0".to_string(), b"ACG".to_vec(), b"ABC".to_vec()),
+            SequenceRecord::fasta("r1".to_string(), b"ACGTACGT".to_vec()),
+            SequenceRecord::fastq("r2".to_string(), b"A".to_vec(), b"D".to_vec()),
+            SequenceRecord::fastq("r3".to_string(), b"ACGT".to_vec(), b"EFGH".to_vec()),
+        ];
+        let columns = QualityColumns::from_records(&records);
+
+        assert_eq!(columns.num_reads(), 4);
+        assert_eq!(columns.num_positions(), 4);
+        assert_eq!(columns.total_scores(), 8);
+        assert_eq!(columns.column(0), b"ADE");
+        assert_eq!(columns.column(1), b"BF");
+        assert_eq!(columns.column(2), b"CG");
+        assert_eq!(columns.column(3), b"H");
+        assert_eq!(columns.columns().len(), 4);
+        assert_eq!(columns.columns().collect::<Vec<_>>().concat(), b"ADEBFCGH");
+    }
+
+    #[test]
+    fn test_transpose_without_qualities() {
+        let records = vec![SequenceRecord::fasta("r0".to_string(), b"ACGT".to_vec())];
+        let columns = QualityColumns::from_records(&records);
+
+        assert_eq!(columns.num_reads(), 1);
+        assert_eq!(columns.num_positions(), 0);
+        assert_eq!(columns.columns().count(), 0);
+        assert_eq!(QualityColumns::from_records(&[]).num_positions(), 0);
+    }
+}
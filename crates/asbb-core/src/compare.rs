@@ -0,0 +1,318 @@
+//! Tolerant comparison of operation outputs
+//!
+//! Parallel and SIMD reductions sum floating-point values in a different
+//! order than the naive loop, so fields like `gc_percent` or mean quality can
+//! differ in the last bits between correct backends. Outputs are therefore
+//! compared structurally: integers, strings, booleans and sequence records
+//! must match exactly, while floats match within a relative tolerance that
+//! can be overridden per field name.
+
+use crate::{OperationOutput, SequenceRecord};
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Default relative tolerance for floating-point fields
+pub const DEFAULT_RELATIVE_TOLERANCE: f64 = 1e-9;
+
+/// Absolute floor, so values near zero don't require bit-exact equality
+pub const DEFAULT_ABSOLUTE_TOLERANCE: f64 = 1e-12;
+
+/// Floating-point tolerance for output comparison
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tolerance {
+    /// Relative tolerance applied to every float field
+    pub relative: f64,
+
+    /// Absolute tolerance (used when both values are close to zero)
+    pub absolute: f64,
+
+    /// Relative tolerance overrides by field name (e.g. `"mean_quality"`)
+    pub fields: HashMap<String, f64>,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            relative: DEFAULT_RELATIVE_TOLERANCE,
+            absolute: DEFAULT_ABSOLUTE_TOLERANCE,
+            fields: HashMap::new(),
+        }
+    }
+}
+
+impl Tolerance {
+    /// Bit-exact comparison (the previous `PartialEq` behaviour)
+    pub fn exact() -> Self {
+        Self {
+            relative: 0.0,
+            absolute: 0.0,
+            fields: HashMap::new(),
+        }
+    }
+
+    /// Uniform relative tolerance
+    pub fn relative(relative: f64) -> Self {
+        Self {
+            relative,
+            ..Self::default()
+        }
+    }
+
+    /// Override the relative tolerance for one field
+    pub fn with_field(mut self, field: &str, relative: f64) -> Self {
+        self.fields.insert(field.to_string(), relative);
+        self
+    }
+
+    /// Whether two floats match, using the tolerance for `field`
+    pub fn floats_match(&self, field: Option<&str>, expected: f64, actual: f64) -> bool {
+        if expected == actual || (expected.is_nan() && actual.is_nan()) {
+            return true;
+        }
+
+        let relative = field
+            .and_then(|name| self.fields.get(name))
+            .copied()
+            .unwrap_or(self.relative);
+        let diff = (expected - actual).abs();
+        diff <= self.absolute || diff <= relative * expected.abs().max(actual.abs())
+    }
+}
+
+/// Whether two outputs match within tolerance
+pub fn outputs_match(expected: &OperationOutput, actual: &OperationOutput, tolerance: &Tolerance) -> bool {
+    diff_outputs(expected, actual, tolerance).is_empty()
+}
+
+/// Describe every difference between an expected and an actual output
+///
+/// Statistics and JSON outputs are compared field by field (`path: expected
+/// X, got Y`); record outputs report count mismatches and each differing
+/// record by index and ID.
+pub fn diff_outputs(
+    expected: &OperationOutput,
+    actual: &OperationOutput,
+    tolerance: &Tolerance,
+) -> Vec<String> {
+    let mut diffs = Vec::new();
+    if expected == actual {
+        return diffs;
+    }
+
+    // Typed results are diffed through their JSON form, which is also how
+    // they compare against stored (deserialized) outputs
+    let (expected, actual) = match (as_json_statistics(expected), as_json_statistics(actual)) {
+        (Ok(e), Ok(a)) => (e, a),
+        (Err(e), _) | (_, Err(e)) => {
+            diffs.push(format!("failed to serialize typed output: {}", e));
+            return diffs;
+        }
+    };
+
+    match (expected.as_ref(), actual.as_ref()) {
+        (OperationOutput::Statistics(e), OperationOutput::Statistics(a))
+        | (OperationOutput::Json(e), OperationOutput::Json(a)) => {
+            diff_json("$", None, e, a, tolerance, &mut diffs);
+        }
+        (OperationOutput::Records(e), OperationOutput::Records(a)) => {
+            diff_records(e, a, &mut diffs);
+        }
+        (OperationOutput::Count(e), OperationOutput::Count(a)) => {
+            if e != a {
+                diffs.push(format!("count: expected {}, got {}", e, a));
+            }
+        }
+        (OperationOutput::Boolean(e), OperationOutput::Boolean(a)) => {
+            if e != a {
+                diffs.push(format!("result: expected {}, got {}", e, a));
+            }
+        }
+        _ => diffs.push(format!(
+            "output kind: expected {}, got {}",
+            output_kind(&expected),
+            output_kind(&actual)
+        )),
+    }
+
+    diffs
+}
+
+fn as_json_statistics(output: &OperationOutput) -> serde_json::Result<Cow<'_, OperationOutput>> {
+    match output {
+        OperationOutput::Typed(typed) => Ok(Cow::Owned(OperationOutput::Statistics(typed.to_json()?))),
+        _ => Ok(Cow::Borrowed(output)),
+    }
+}
+
+fn output_kind(output: &OperationOutput) -> &'static str {
+    match output {
+        OperationOutput::Records(_) => "Records",
+        OperationOutput::Typed(_) | OperationOutput::Statistics(_) => "Statistics",
+        OperationOutput::Boolean(_) => "Boolean",
+        OperationOutput::Count(_) => "Count",
+        OperationOutput::Json(_) => "Json",
+    }
+}
+
+fn diff_json(
+    path: &str,
+    field: Option<&str>,
+    expected: &Value,
+    actual: &Value,
+    tolerance: &Tolerance,
+    diffs: &mut Vec<String>,
+) {
+    match (expected, actual) {
+        (Value::Object(e), Value::Object(a)) => {
+            for (key, e_value) in e {
+                let child = format!("{}.{}", path, key);
+                match a.get(key) {
+                    Some(a_value) => diff_json(&child, Some(key), e_value, a_value, tolerance, diffs),
+                    None => diffs.push(format!("{}: missing (expected {})", child, e_value)),
+                }
+            }
+            for key in a.keys().filter(|key| !e.contains_key(*key)) {
+                diffs.push(format!("{}.{}: unexpected field", path, key));
+            }
+        }
+        (Value::Array(e), Value::Array(a)) => {
+            if e.len() != a.len() {
+                diffs.push(format!(
+                    "{}: expected {} elements, got {}",
+                    path,
+                    e.len(),
+                    a.len()
+                ));
+            }
+            // Array elements inherit the field name of the array
+            for (i, (e_value, a_value)) in e.iter().zip(a).enumerate() {
+                diff_json(&format!("{}[{}]", path, i), field, e_value, a_value, tolerance, diffs);
+            }
+        }
+        (Value::Number(e), Value::Number(a)) if e.is_f64() || a.is_f64() => {
+            let (e_float, a_float) = (e.as_f64().unwrap_or(f64::NAN), a.as_f64().unwrap_or(f64::NAN));
+            if !tolerance.floats_match(field, e_float, a_float) {
+                diffs.push(format!(
+                    "{}: expected {}, got {} (relative error {:.3e})",
+                    path,
+                    expected,
+                    actual,
+                    (e_float - a_float).abs() / e_float.abs().max(a_float.abs())
+                ));
+            }
+        }
+        _ => {
+            if expected != actual {
+                diffs.push(format!("{}: expected {}, got {}", path, expected, actual));
+            }
+        }
+    }
+}
+
+fn diff_records(expected: &[SequenceRecord], actual: &[SequenceRecord], diffs: &mut Vec<String>) {
+    if expected.len() != actual.len() {
+        diffs.push(format!(
+            "records: expected {}, got {}",
+            expected.len(),
+            actual.len()
+        ));
+    }
+
+    for (i, (e, a)) in expected.iter().zip(actual).enumerate() {
+        if e.id != a.id {
+            diffs.push(format!("record {}: expected id '{}', got '{}'", i, e.id, a.id));
+        } else if e.sequence != a.sequence {
+            let position = e
+                .sequence
+                .iter()
+                .zip(&a.sequence)
+                .position(|(x, y)| x != y)
+                .unwrap_or_else(|| e.sequence.len().min(a.sequence.len()));
+            diffs.push(format!(
+                "record {} ({}): sequence differs at position {} (lengths {} vs {})",
+                i,
+                e.id,
+                position,
+                e.sequence.len(),
+                a.sequence.len()
+            ));
+        } else if e.quality != a.quality {
+            diffs.push(format!("record {} ({}): quality differs", i, e.id));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_floats_within_tolerance_match() {
+        let expected = OperationOutput::Statistics(json!({"gc_percent": 41.25, "count_gc": 10}));
+        let reordered = OperationOutput::Statistics(json!({"gc_percent": 41.250000000001, "count_gc": 10}));
+
+        assert!(outputs_match(&expected, &reordered, &Tolerance::default()));
+        assert!(!outputs_match(&expected, &reordered, &Tolerance::exact()));
+    }
+
+    #[test]
+    fn test_integers_compare_exactly() {
+        let expected = OperationOutput::Statistics(json!({"count_gc": 1_000_000_000}));
+        let actual = OperationOutput::Statistics(json!({"count_gc": 1_000_000_001}));
+
+        let diffs = diff_outputs(&expected, &actual, &Tolerance::relative(0.01));
+        assert_eq!(diffs, vec!["$.count_gc: expected 1000000000, got 1000000001"]);
+    }
+
+    #[test]
+    fn test_per_field_override() {
+        let expected = OperationOutput::Statistics(json!({"mean_quality": [30.0, 31.0], "gc_percent": 50.0}));
+        let actual = OperationOutput::Statistics(json!({"mean_quality": [30.001, 31.0], "gc_percent": 50.001}));
+
+        let tolerance = Tolerance::default().with_field("mean_quality", 1e-3);
+        let diffs = diff_outputs(&expected, &actual, &tolerance);
+        assert_eq!(diffs.len(), 1);
+        assert!(diffs[0].starts_with("$.gc_percent: expected 50.0, got 50.001"));
+    }
+
+    #[test]
+    fn test_diff_records_reports_position() {
+        let expected = vec![SequenceRecord::fasta("r0".to_string(), b"ACGT".to_vec())];
+        let actual = vec![
+            SequenceRecord::fasta("r0".to_string(), b"ACCT".to_vec()),
+            SequenceRecord::fasta("r1".to_string(), b"A".to_vec()),
+        ];
+
+        let diffs = diff_outputs(
+            &OperationOutput::Records(expected),
+            &OperationOutput::Records(actual),
+            &Tolerance::default(),
+        );
+        assert_eq!(
+            diffs,
+            vec![
+                "records: expected 1, got 2",
+                "record 0 (r0): sequence differs at position 2 (lengths 4 vs 4)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_typed_output_diffs_against_json() {
+        #[derive(Debug, Clone, PartialEq, serde::Serialize)]
+        struct Gc {
+            gc_percent: f64,
+        }
+
+        let golden = OperationOutput::Statistics(json!({"gc_percent": 41.25}));
+        let close = OperationOutput::typed(Gc { gc_percent: 41.250000000001 });
+        let far = OperationOutput::typed(Gc { gc_percent: 42.0 });
+
+        assert!(outputs_match(&golden, &close, &Tolerance::default()));
+        let diffs = diff_outputs(&golden, &far, &Tolerance::default());
+        assert_eq!(diffs.len(), 1);
+        assert!(diffs[0].starts_with("$.gc_percent: expected 41.25, got 42.0"));
+    }
+}
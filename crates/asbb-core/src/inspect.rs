@@ -0,0 +1,389 @@
+//! Data characteristics inference from input files
+//!
+//! Scans a FASTA or FASTQ file (optionally only its first N records) and
+//! produces the [`DataCharacteristics`] that the rest of the framework keys
+//! on: record count, length mean/std, read type, quality distribution and
+//! size. The result is what a prediction or recommendation step needs to
+//! pick a configuration for a real file instead of a synthetic scale.
+//!
+//! # Inference rules
+//!
+//! - **Format**: FASTA if the first byte is `>`, FASTQ otherwise
+//! - **Record count**: exact for a full scan; for a sampled scan it is
+//!   extrapolated from the bytes consumed by the sample and the file size
+//! - **Read type**: `Interleaved` when consecutive records form mate pairs
+//!   (`read/1` + `read/2`, or the same ID before the first space); otherwise
+//!   `SingleEnd` (a separate R1/R2 pair cannot be seen from one file)
+//! - **Quality distribution**: fitted by [`crate::quality_profile`]; the
+//!   full fit is kept in [`Inspection::quality`]
+
+use crate::io::FastqReader;
+use crate::quality_profile::{QualityClassification, QualityProfileBuilder};
+use crate::{DataCharacteristics, DataFormat, ReadType, SequenceRecord};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+/// Record names kept for mate-pair detection
+const MAX_PAIR_CHECK_RECORDS: usize = 1000;
+
+// ============================================================================
+// Inspection Result
+// ============================================================================
+
+/// Inferred characteristics plus how they were obtained
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Inspection {
+    /// Inferred characteristics of the whole file
+    pub characteristics: DataCharacteristics,
+
+    /// Fitted quality profile (FASTQ only)
+    pub quality: Option<QualityClassification>,
+
+    /// Records actually parsed
+    pub records_scanned: usize,
+
+    /// True if the scan stopped before the end of the file
+    /// (`num_sequences` is then an estimate)
+    pub sampled: bool,
+
+    /// File size in bytes
+    pub file_size_bytes: u64,
+}
+
+// ============================================================================
+// Accumulator
+// ============================================================================
+
+/// Streaming accumulator of record statistics
+///
+/// Feed records with [`add`](Self::add) (or [`add_parts`](Self::add_parts)
+/// when only the lengths are known), then call
+/// [`characteristics`](Self::characteristics).
+#[derive(Debug, Clone, Default)]
+pub struct CharacteristicsBuilder {
+    records: usize,
+    length_sum: f64,
+    length_sum_sq: f64,
+    quality: QualityProfileBuilder,
+    pair_keys: Vec<String>,
+}
+
+impl CharacteristicsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of records added so far
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    pub fn add(&mut self, record: &SequenceRecord) {
+        self.add_parts(&record.id, record.sequence.len(), record.quality.as_deref());
+    }
+
+    /// Add a record by name, sequence length and (FASTQ) quality string
+    pub fn add_parts(&mut self, id: &str, length: usize, quality: Option<&[u8]>) {
+        self.records += 1;
+        self.length_sum += length as f64;
+        self.length_sum_sq += (length as f64) * (length as f64);
+
+        if self.pair_keys.len() < MAX_PAIR_CHECK_RECORDS {
+            self.pair_keys.push(pair_key(id).to_string());
+        }
+
+        if let Some(quality) = quality {
+            self.quality.add(quality);
+        }
+    }
+
+    /// Mean and (population) standard deviation of read length
+    fn length_stats(&self) -> (f64, f64) {
+        mean_std(self.length_sum, self.length_sum_sq, self.records as f64)
+    }
+
+    /// Fitted quality profile, or `None` if no qualities were seen
+    pub fn quality(&self) -> Option<QualityClassification> {
+        self.quality.classify()
+    }
+
+    /// Interleaved if every consecutive pair of records shares a mate key
+    fn read_type(&self) -> ReadType {
+        let pairs = self.pair_keys.len() / 2;
+        let interleaved = pairs > 0
+            && self
+                .pair_keys
+                .chunks_exact(2)
+                .all(|pair| pair[0] == pair[1]);
+        if interleaved {
+            ReadType::Interleaved
+        } else {
+            ReadType::SingleEnd
+        }
+    }
+
+    /// Characteristics for `num_sequences` records of the given format
+    ///
+    /// `num_sequences` differs from [`records`](Self::records) when the
+    /// records added are a sample of a larger file.
+    pub fn characteristics(
+        &self,
+        format: DataFormat,
+        num_sequences: usize,
+        estimated_size_bytes: Option<usize>,
+    ) -> DataCharacteristics {
+        let (mean, std) = self.length_stats();
+        DataCharacteristics {
+            format,
+            num_sequences,
+            seq_length_mean: mean.round() as usize,
+            seq_length_std: std.round() as usize,
+            read_type: self.read_type(),
+            quality_distribution: match format {
+                DataFormat::Fastq => self.quality().map(|q| q.distribution()),
+                DataFormat::Fasta => None,
+            },
+            estimated_size_bytes,
+        }
+    }
+}
+
+fn mean_std(sum: f64, sum_sq: f64, n: f64) -> (f64, f64) {
+    if n == 0.0 {
+        return (0.0, 0.0);
+    }
+    let mean = sum / n;
+    let variance = (sum_sq / n - mean * mean).max(0.0);
+    (mean, variance.sqrt())
+}
+
+/// Name shared by both mates: first whitespace token without `/1` or `/2`
+fn pair_key(id: &str) -> &str {
+    let token = id.split_whitespace().next().unwrap_or("");
+    token
+        .strip_suffix("/1")
+        .or_else(|| token.strip_suffix("/2"))
+        .unwrap_or(token)
+}
+
+// ============================================================================
+// File Inspection
+// ============================================================================
+
+/// `BufRead` wrapper counting consumed bytes
+struct CountingReader<R> {
+    inner: R,
+    consumed: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.consumed += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for CountingReader<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.consumed += amt as u64;
+        self.inner.consume(amt);
+    }
+}
+
+/// Infer characteristics of a FASTA/FASTQ file
+///
+/// With `sample = Some(n)` only the first `n` records are parsed and the
+/// record count is extrapolated from their share of the file's bytes.
+pub fn inspect_path<P: AsRef<Path>>(path: P, sample: Option<usize>) -> Result<Inspection> {
+    let path = path.as_ref();
+    let file =
+        File::open(path).with_context(|| format!("Failed to open file: {}", path.display()))?;
+    let file_size_bytes = file.metadata()?.len();
+    let mut reader = CountingReader {
+        inner: BufReader::new(file),
+        consumed: 0,
+    };
+
+    let limit = sample.unwrap_or(usize::MAX);
+    let format = if reader.fill_buf()?.first() == Some(&b'>') {
+        DataFormat::Fasta
+    } else {
+        DataFormat::Fastq
+    };
+
+    let mut builder = CharacteristicsBuilder::new();
+    let exhausted = match format {
+        DataFormat::Fastq => scan_fastq(&mut reader, &mut builder, limit),
+        DataFormat::Fasta => scan_fasta(&mut reader, &mut builder, limit),
+    }
+    .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let records_scanned = builder.records();
+    let num_sequences = if exhausted || reader.consumed == 0 {
+        records_scanned
+    } else {
+        (records_scanned as f64 * file_size_bytes as f64 / reader.consumed as f64).round()
+            as usize
+    };
+
+    Ok(Inspection {
+        characteristics: builder.characteristics(
+            format,
+            num_sequences,
+            Some(file_size_bytes as usize),
+        ),
+        quality: builder.quality(),
+        records_scanned,
+        sampled: !exhausted,
+        file_size_bytes,
+    })
+}
+
+/// Add up to `limit` FASTQ records; returns true if the file was exhausted
+fn scan_fastq<R: BufRead>(
+    reader: &mut R,
+    builder: &mut CharacteristicsBuilder,
+    limit: usize,
+) -> Result<bool> {
+    let mut fastq = FastqReader::new(&mut *reader);
+    while builder.records() < limit {
+        match fastq.read_record()? {
+            Some(record) => builder.add(&record),
+            None => return Ok(true),
+        }
+    }
+    drop(fastq);
+    Ok(reader.fill_buf()?.is_empty())
+}
+
+/// Add up to `limit` FASTA records (multi-line sequences allowed); returns
+/// true if the file was exhausted
+///
+/// Headers are peeked before being consumed, so the bytes consumed cover
+/// exactly the records added.
+fn scan_fasta<R: BufRead>(
+    reader: &mut R,
+    builder: &mut CharacteristicsBuilder,
+    limit: usize,
+) -> Result<bool> {
+    let mut line = Vec::new();
+    let mut current: Option<(String, usize)> = None;
+
+    loop {
+        let next = reader.fill_buf()?.first().copied();
+        if matches!(next, None | Some(b'>')) {
+            if let Some((id, length)) = current.take() {
+                builder.add_parts(&id, length, None);
+            }
+            match next {
+                None => return Ok(true),
+                Some(_) if builder.records() == limit => return Ok(false),
+                Some(_) => {}
+            }
+        }
+
+        line.clear();
+        reader.read_until(b'\n', &mut line)?;
+        let content = line.trim_ascii_end();
+
+        if let Some(header) = content.strip_prefix(b">") {
+            current = Some((String::from_utf8_lossy(header).into_owned(), 0));
+        } else if let Some((_, length)) = current.as_mut() {
+            *length += content.len();
+        } else if !content.is_empty() {
+            anyhow::bail!("Expected '>' at start of FASTA file");
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::QualityDistType;
+    use std::io::Write;
+
+    fn write_temp(tag: &str, contents: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("asbb_inspect_{}_{}", tag, std::process::id()));
+        File::create(&path).unwrap().write_all(contents).unwrap();
+        path
+    }
+
+    fn fastq(records: &[(&str, &[u8], &[u8])]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for (id, seq, qual) in records {
+            out.extend_from_slice(format!("@{}\n", id).as_bytes());
+            out.extend_from_slice(seq);
+            out.extend_from_slice(b"\n+\n");
+            out.extend_from_slice(qual);
+            out.push(b'\n');
+        }
+        out
+    }
+
+    #[test]
+    fn test_inspect_fastq_full_and_sampled() {
+        let records: Vec<(String, Vec<u8>, Vec<u8>)> = (0..200)
+            .map(|i| (format!("read_{:03}", i), vec![b'A'; 100 + (i % 2) * 20], vec![b'I'; 100 + (i % 2) * 20]))
+            .collect();
+        let borrowed: Vec<(&str, &[u8], &[u8])> =
+            records.iter().map(|(id, s, q)| (id.as_str(), s.as_slice(), q.as_slice())).collect();
+        let path = write_temp("fastq", &fastq(&borrowed));
+
+        let full = inspect_path(&path, None).unwrap();
+        let c = &full.characteristics;
+        assert!(!full.sampled);
+        assert_eq!(full.records_scanned, 200);
+        assert_eq!(c.format, DataFormat::Fastq);
+        assert_eq!(c.num_sequences, 200);
+        assert_eq!(c.seq_length_mean, 110);
+        assert_eq!(c.seq_length_std, 10);
+        assert_eq!(c.read_type, ReadType::SingleEnd);
+        assert_eq!(c.estimated_size_bytes, Some(full.file_size_bytes as usize));
+        assert_eq!(
+            c.quality_distribution.as_ref().unwrap().distribution_type,
+            QualityDistType::UniformHigh
+        );
+        assert_eq!(full.quality.as_ref().unwrap().fit.positions, 120);
+
+        // Fixed-width IDs and alternating lengths: an even sample extrapolates exactly
+        let sampled = inspect_path(&path, Some(50)).unwrap();
+        assert!(sampled.sampled);
+        assert_eq!(sampled.records_scanned, 50);
+        assert_eq!(sampled.characteristics.num_sequences, 200);
+
+        let everything = inspect_path(&path, Some(200)).unwrap();
+        assert!(!everything.sampled);
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_inspect_fasta_and_interleaved() {
+        let path = write_temp("fasta", b">a/1\nACGT\nACGT\n>a/2\nACGTACGT\n>b/1 x\nAC\n>b/2 y\nACGTACGTAC\n");
+        let inspection = inspect_path(&path, None).unwrap();
+        let c = &inspection.characteristics;
+        assert_eq!(c.format, DataFormat::Fasta);
+        assert_eq!(c.num_sequences, 4);
+        assert_eq!(c.seq_length_mean, 7);
+        assert_eq!(c.read_type, ReadType::Interleaved);
+        assert!(c.quality_distribution.is_none());
+        assert!(inspection.quality.is_none());
+
+        let sampled = inspect_path(&path, Some(2)).unwrap();
+        assert!(sampled.sampled);
+        assert_eq!(sampled.records_scanned, 2);
+        std::fs::remove_file(path).ok();
+    }
+}
@@ -0,0 +1,448 @@
+//! FASTQ record I/O
+//!
+//! Minimal streaming FASTQ reader shared by the harnesses. Records are parsed
+//! incrementally from any `BufRead`, either one at a time or in fixed-size
+//! batches, so benchmarks can model data arriving from the I/O layer instead
+//! of a fully pre-loaded dataset.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let reader = FastqReader::from_path("datasets/medium_10000_150bp.fq")?;
+//! for batch in reader.batches(1024) {
+//!     let batch = batch?;
+//!     operation.execute_naive(&batch)?;
+//! }
+//! ```
+//!
+//! # Parser Backends
+//!
+//! In-memory buffers (e.g. memory-mapped files) are tokenized by a
+//! [`ParserBackend`]: a byte-at-a-time line splitter, or a NEON scanner that
+//! compares 16 bytes against `'\n'` per instruction. Records are delimited by
+//! line count rather than by scanning for `'@'`, which is also a valid quality
+//! character (Q31); the header's `'@'` is checked at the tokenized line start.
+
+use crate::packed::PackedRecords;
+use crate::{SequenceRecord, SequenceView};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Incremental FASTQ reader (4-line records, no wrapping)
+pub struct FastqReader<R: BufRead> {
+    reader: R,
+    line: String,
+    header: String,
+    sequence: String,
+    records_read: usize,
+}
+
+impl FastqReader<BufReader<File>> {
+    /// Open a FASTQ file for streaming
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open file: {}", path.display()))?;
+        Ok(Self::new(BufReader::new(file)))
+    }
+}
+
+impl<R: BufRead> FastqReader<R> {
+    /// Wrap an existing buffered reader
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            header: String::new(),
+            sequence: String::new(),
+            records_read: 0,
+        }
+    }
+
+    /// Number of records read so far
+    pub fn records_read(&self) -> usize {
+        self.records_read
+    }
+
+    /// Read the next line into the internal buffer (without line terminator)
+    ///
+    /// Returns false at end of file.
+    fn next_line(&mut self) -> Result<bool> {
+        self.line.clear();
+        if self.reader.read_line(&mut self.line)? == 0 {
+            return Ok(false);
+        }
+        let trimmed_len = self.line.trim_end_matches(['\n', '\r']).len();
+        self.line.truncate(trimmed_len);
+        Ok(true)
+    }
+
+    /// Parse the next record into the internal buffers
+    ///
+    /// Returns false at end of file.
+    fn next_raw(&mut self) -> Result<bool> {
+        // Skip blank lines between records / at end of file
+        loop {
+            if !self.next_line()? {
+                return Ok(false);
+            }
+            if !self.line.is_empty() {
+                break;
+            }
+        }
+
+        if !self.line.starts_with('@') {
+            anyhow::bail!(
+                "Expected '@' at start of FASTQ record {}, got: {}",
+                self.records_read + 1,
+                self.line
+            );
+        }
+        std::mem::swap(&mut self.header, &mut self.line);
+
+        if !self.next_line()? {
+            anyhow::bail!("Unexpected end of file (sequence)");
+        }
+        std::mem::swap(&mut self.sequence, &mut self.line);
+
+        if !self.next_line()? {
+            anyhow::bail!("Unexpected end of file (plus)");
+        }
+        if !self.line.starts_with('+') {
+            anyhow::bail!("Expected '+' separator line, got: {}", self.line);
+        }
+
+        if !self.next_line()? {
+            anyhow::bail!("Unexpected end of file (quality)");
+        }
+
+        self.records_read += 1;
+        Ok(true)
+    }
+
+    /// Read a single record, or `None` at end of file
+    pub fn read_record(&mut self) -> Result<Option<SequenceRecord>> {
+        if !self.next_raw()? {
+            return Ok(None);
+        }
+        Ok(Some(SequenceRecord::fastq(
+            self.header[1..].to_string(),
+            self.sequence.as_bytes().to_vec(),
+            self.line.as_bytes().to_vec(),
+        )))
+    }
+
+    /// Read up to `batch_size` records (empty vector at end of file)
+    pub fn read_batch(&mut self, batch_size: usize) -> Result<Vec<SequenceRecord>> {
+        let mut batch = Vec::with_capacity(batch_size);
+        while batch.len() < batch_size {
+            match self.read_record()? {
+                Some(record) => batch.push(record),
+                None => break,
+            }
+        }
+        Ok(batch)
+    }
+
+    /// Convert into an iterator over record batches
+    pub fn batches(self, batch_size: usize) -> RecordBatches<R> {
+        assert!(batch_size > 0, "Batch size must be positive");
+        RecordBatches {
+            reader: self,
+            batch_size,
+            done: false,
+        }
+    }
+
+    /// Read all remaining records
+    pub fn read_all(mut self) -> Result<Vec<SequenceRecord>> {
+        let mut records = Vec::new();
+        while let Some(record) = self.read_record()? {
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    /// Read all remaining records into packed storage
+    ///
+    /// Lines are appended straight from the reader's buffers, so loading
+    /// performs no per-record allocations.
+    pub fn read_packed(mut self) -> Result<PackedRecords> {
+        let mut packed = PackedRecords::new();
+        while self.next_raw()? {
+            packed.push(
+                &self.header[1..],
+                self.sequence.as_bytes(),
+                Some(self.line.as_bytes()),
+            )?;
+        }
+        packed.shrink_to_fit();
+        Ok(packed)
+    }
+}
+
+impl<R: BufRead> Iterator for FastqReader<R> {
+    type Item = Result<SequenceRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+/// Iterator over fixed-size record batches from a [`FastqReader`]
+pub struct RecordBatches<R: BufRead> {
+    reader: FastqReader<R>,
+    batch_size: usize,
+    done: bool,
+}
+
+impl<R: BufRead> Iterator for RecordBatches<R> {
+    type Item = Result<Vec<SequenceRecord>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.reader.read_batch(self.batch_size) {
+            Ok(batch) if batch.is_empty() => {
+                self.done = true;
+                None
+            }
+            Ok(batch) => Some(Ok(batch)),
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Buffer Parsing
+// ============================================================================
+
+/// Line tokenizer for in-memory FASTQ buffers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParserBackend {
+    /// Byte-at-a-time newline search
+    Scalar,
+    /// NEON newline search, 16 bytes per compare (scalar off aarch64)
+    Neon,
+}
+
+impl ParserBackend {
+    pub const ALL: [ParserBackend; 2] = [ParserBackend::Scalar, ParserBackend::Neon];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParserBackend::Scalar => "scalar",
+            ParserBackend::Neon => "neon",
+        }
+    }
+
+    /// Backend by [`name`](Self::name)
+    pub fn parse(name: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|backend| backend.name() == name)
+            .with_context(|| format!("Unknown parser backend: {} (available: scalar, neon)", name))
+    }
+}
+
+/// Offsets of every `'\n'` in `buffer`, in order
+pub fn newline_offsets(buffer: &[u8], backend: ParserBackend) -> Vec<usize> {
+    // ~1 newline per 75 bytes for 150 bp reads
+    let mut offsets = Vec::with_capacity(buffer.len() / 64);
+    match backend {
+        ParserBackend::Scalar => newline_offsets_scalar(buffer, 0, &mut offsets),
+        ParserBackend::Neon => newline_offsets_neon(buffer, &mut offsets),
+    }
+    offsets
+}
+
+fn newline_offsets_scalar(buffer: &[u8], base: usize, offsets: &mut Vec<usize>) {
+    offsets.extend(
+        buffer
+            .iter()
+            .enumerate()
+            .filter(|&(_, &b)| b == b'\n')
+            .map(|(i, _)| base + i),
+    );
+}
+
+#[cfg(target_arch = "aarch64")]
+fn newline_offsets_neon(buffer: &[u8], offsets: &mut Vec<usize>) {
+    use std::arch::aarch64::*;
+
+    let chunks = buffer.len() / 16;
+    unsafe {
+        let newline = vdupq_n_u8(b'\n');
+        for chunk in 0..chunks {
+            let bytes = vld1q_u8(buffer.as_ptr().add(chunk * 16));
+            let matches = vceqq_u8(bytes, newline);
+            // Narrow each 0x00/0xFF byte to a nibble: a 64-bit mask, 4 bits per byte
+            let nibbles = vshrn_n_u16::<4>(vreinterpretq_u16_u8(matches));
+            let mut mask = vget_lane_u64::<0>(vreinterpret_u64_u8(nibbles)) & 0x8888_8888_8888_8888;
+            while mask != 0 {
+                offsets.push(chunk * 16 + mask.trailing_zeros() as usize / 4);
+                mask &= mask - 1;
+            }
+        }
+    }
+    newline_offsets_scalar(&buffer[chunks * 16..], chunks * 16, offsets);
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+fn newline_offsets_neon(buffer: &[u8], offsets: &mut Vec<usize>) {
+    newline_offsets_scalar(buffer, 0, offsets);
+}
+
+/// Parse an in-memory FASTQ buffer (e.g. a memory-mapped file) into views
+///
+/// Ids, sequences and qualities borrow directly from `buffer`; no record
+/// data is copied. Uses the scalar tokenizer; see [`parse_views_with`].
+pub fn parse_views(buffer: &[u8]) -> Result<Vec<SequenceView<'_>>> {
+    parse_views_with(buffer, ParserBackend::Scalar)
+}
+
+/// [`parse_views`] with the given line tokenizer
+pub fn parse_views_with(buffer: &[u8], backend: ParserBackend) -> Result<Vec<SequenceView<'_>>> {
+    let ends = newline_offsets(buffer, backend);
+    // A final line without terminator still counts; the final terminator
+    // does not start another line
+    let tail = (ends.last().map_or(0, |&end| end + 1) < buffer.len()).then_some(buffer.len());
+    let mut start = 0;
+    let mut lines = ends.into_iter().chain(tail).map(|end| {
+        let line = &buffer[start..end];
+        start = end + 1;
+        line.strip_suffix(b"\r").unwrap_or(line)
+    });
+    let mut views = Vec::new();
+
+    // Blank lines between records / at end of file are skipped
+    while let Some(header) = lines.by_ref().find(|line| !line.is_empty()) {
+        let id = header.strip_prefix(b"@").with_context(|| {
+            format!(
+                "Expected '@' at start of FASTQ record {}, got: {}",
+                views.len() + 1,
+                String::from_utf8_lossy(header)
+            )
+        })?;
+        let id = std::str::from_utf8(id)
+            .with_context(|| format!("FASTQ record {} has a non-UTF-8 id", views.len() + 1))?;
+
+        let sequence = lines.next().context("Unexpected end of file (sequence)")?;
+        let plus = lines.next().context("Unexpected end of file (plus)")?;
+        if !plus.starts_with(b"+") {
+            anyhow::bail!(
+                "Expected '+' separator line, got: {}",
+                String::from_utf8_lossy(plus)
+            );
+        }
+        let quality = lines.next().context("Unexpected end of file (quality)")?;
+
+        views.push(SequenceView {
+            id,
+            sequence,
+            quality: Some(quality),
+        });
+    }
+
+    Ok(views)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const FASTQ: &str = "@r1\nACGT\n+\nIIII\n@r2\nGG\n+\nII\n@r3\nTTTAA\n+r3\nIIIII\n";
+
+    #[test]
+    fn test_read_records() {
+        let reader = FastqReader::new(Cursor::new(FASTQ));
+        let records = reader.read_all().unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].id, "r1");
+        assert_eq!(records[0].sequence, b"ACGT");
+        assert_eq!(records[2].quality.as_deref(), Some(&b"IIIII"[..]));
+    }
+
+    #[test]
+    fn test_batches() {
+        let reader = FastqReader::new(Cursor::new(FASTQ));
+        let sizes: Vec<usize> = reader
+            .batches(2)
+            .map(|batch| batch.unwrap().len())
+            .collect();
+
+        assert_eq!(sizes, vec![2, 1]);
+    }
+
+    #[test]
+    fn test_crlf_and_trailing_blank_lines() {
+        let data = "@r1\r\nACGT\r\n+\r\nIIII\r\n\n\n";
+        let records = FastqReader::new(Cursor::new(data)).read_all().unwrap();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].sequence, b"ACGT");
+    }
+
+    #[test]
+    fn test_read_packed_matches_read_all() {
+        let packed = FastqReader::new(Cursor::new(FASTQ)).read_packed().unwrap();
+        let records = FastqReader::new(Cursor::new(FASTQ)).read_all().unwrap();
+
+        assert_eq!(packed.to_records(), records);
+    }
+
+    #[test]
+    fn test_parse_views_matches_read_all() {
+        let records = FastqReader::new(Cursor::new(FASTQ)).read_all().unwrap();
+        let views = parse_views(FASTQ.as_bytes()).unwrap();
+
+        assert_eq!(views.iter().map(SequenceView::to_record).collect::<Vec<_>>(), records);
+
+        let crlf = parse_views(b"@r1\r\nACGT\r\n+\r\nIIII\r\n\n").unwrap();
+        assert_eq!(crlf.len(), 1);
+        assert_eq!(crlf[0].sequence, b"ACGT");
+        assert!(parse_views(b"@r1\nACGT\n+\n").is_err());
+    }
+
+    #[test]
+    fn test_parser_backends_agree() {
+        // Lines straddle 16-byte chunks; the last record has no final newline
+        let mut fastq = String::new();
+        for i in 0..40 {
+            let sequence = "ACGT".repeat(i % 9 + 1);
+            fastq.push_str(&format!("@r{}\n{}\n+\n{}\n", i, sequence, "@".repeat(sequence.len())));
+        }
+        fastq.push_str("@last\r\nGA\r\n+\r\nII");
+
+        let expected: Vec<usize> = (0..fastq.len()).filter(|&i| fastq.as_bytes()[i] == b'\n').collect();
+        let scalar = parse_views(fastq.as_bytes()).unwrap();
+        assert_eq!(scalar.len(), 41);
+        assert_eq!(scalar[40].quality, Some(&b"II"[..]));
+        for backend in ParserBackend::ALL {
+            assert_eq!(newline_offsets(fastq.as_bytes(), backend), expected);
+            assert_eq!(parse_views_with(fastq.as_bytes(), backend).unwrap(), scalar);
+            assert_eq!(ParserBackend::parse(backend.name()).unwrap(), backend);
+        }
+        assert!(ParserBackend::parse("avx2").is_err());
+    }
+
+    #[test]
+    fn test_truncated_record() {
+        let mut reader = FastqReader::new(Cursor::new("@r1\nACGT\n+\n"));
+        assert!(reader.read_record().is_err());
+    }
+}
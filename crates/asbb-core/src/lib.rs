@@ -21,18 +21,57 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::time::Duration;
 
 // ============================================================================
 // Modules
 // ============================================================================
 
+/// Published chip specifications (bandwidth, cores) per variant
+pub mod chip_spec;
+
+/// Columnar (position-major) quality storage
+pub mod columnar;
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod sysctl;
+
+/// Tolerant (float-aware) comparison of operation outputs
+pub mod compare;
+
 /// 2-bit DNA encoding for efficient sequence representation
 pub mod encoding;
 
+/// Data characteristics inference from FASTA/FASTQ files
+pub mod inspect;
+
+/// Streaming FASTQ record I/O
+pub mod io;
+
+/// Pre-execution memory estimates and limits
+pub mod memory;
+
 /// Operation registry for centralized operation management
 pub mod operation_registry;
 
+/// Packed (arena) record storage
+pub mod packed;
+
+/// Operations from external crates, registered at link time
+pub mod plugin;
+
+/// Quality score binning (Illumina 8/4-level)
+pub mod quality_binning;
+
+/// Quality distribution classification (per-position profile fits)
+pub mod quality_profile;
+
+/// Quantiles over measured run times
+pub mod stats;
+
 // ============================================================================
 // Data Characteristics
 // ============================================================================
@@ -84,6 +123,11 @@ impl DataCharacteristics {
             _ => DataScale::Huge,
         }
     }
+
+    /// Read-length class (short-read vs. long-read tiers)
+    pub fn length_class(&self) -> LengthClass {
+        LengthClass::from_mean_length(self.seq_length_mean)
+    }
 }
 
 /// Sequence file format
@@ -126,6 +170,8 @@ pub enum QualityDistType {
     Degrading,
     /// Realistic with occasional drops
     Realistic,
+    /// Matches none of the above (real data; see `quality_profile`)
+    Unknown,
 }
 
 /// Data scale categories
@@ -139,6 +185,50 @@ pub enum DataScale {
     Huge,      // >10M
 }
 
+/// Read-length classes (short reads vs. ONT/PacBio long-read tiers)
+///
+/// Each long-read class is named for its nominal mean length and covers
+/// reads up to 3× that length, so simulated 1 kb / 10 kb / 100 kb presets
+/// land in their own class despite length variance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum LengthClass {
+    Short, // <500 bp (Illumina)
+    Kb1,   // 500 bp-3 kb
+    Kb10,  // 3-30 kb
+    Kb100, // ≥30 kb
+}
+
+impl LengthClass {
+    /// All classes, shortest first
+    pub const ALL: [LengthClass; 4] =
+        [LengthClass::Short, LengthClass::Kb1, LengthClass::Kb10, LengthClass::Kb100];
+
+    /// Class of a dataset with the given mean read length (bp)
+    pub fn from_mean_length(mean_length: usize) -> Self {
+        match mean_length {
+            0..=499 => LengthClass::Short,
+            500..=2_999 => LengthClass::Kb1,
+            3_000..=29_999 => LengthClass::Kb10,
+            _ => LengthClass::Kb100,
+        }
+    }
+
+    /// Short name used in results files ("short", "1kb", "10kb", "100kb")
+    pub fn name(&self) -> &'static str {
+        match self {
+            LengthClass::Short => "short",
+            LengthClass::Kb1 => "1kb",
+            LengthClass::Kb10 => "10kb",
+            LengthClass::Kb100 => "100kb",
+        }
+    }
+
+    /// Inverse of [`LengthClass::name`]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|class| class.name() == name)
+    }
+}
+
 // ============================================================================
 // Hardware Configuration
 // ============================================================================
@@ -171,6 +261,15 @@ pub struct HardwareConfig {
     /// Thread assignment strategy (P-cores, E-cores, mixed)
     pub thread_assignment: ThreadAssignment,
 
+    /// How work is split across threads when `num_threads > 1`
+    #[serde(default)]
+    pub parallel_strategy: ParallelStrategy,
+
+    /// Records per work unit of chunked parallel execution (`None`: about
+    /// four chunks per thread)
+    #[serde(default)]
+    pub batch_size: Option<usize>,
+
     /// Data encoding (2-bit, ASCII, etc.)
     pub encoding: Encoding,
 
@@ -212,6 +311,8 @@ impl HardwareConfig {
             use_neon: false,
             num_threads: 1,
             thread_assignment: ThreadAssignment::PCoresOnly,
+            parallel_strategy: ParallelStrategy::PerRecord,
+            batch_size: None,
             encoding: Encoding::Ascii,
             use_unified_memory: false,
             use_gpu: false,
@@ -227,25 +328,119 @@ impl HardwareConfig {
     }
 
     /// Create a fully-optimized configuration (all features enabled)
+    ///
+    /// Thread count and GPU batch size follow the base variant of `chip`;
+    /// use [`HardwareConfig::for_profile`] with a detected profile to size
+    /// them for the machine at hand.
     pub fn fully_optimized(chip: ChipGeneration) -> Self {
-        let is_m5 = matches!(chip, ChipGeneration::M5);
+        Self::for_profile(&HardwareProfile::nominal(chip, ChipVariant::Base))
+    }
 
+    /// Create a fully-optimized configuration sized for `profile`
+    ///
+    /// Uses every core (P + E, OS-scheduled) and scales the GPU batch with
+    /// the GPU core count, so each core has enough sequences to hide launch
+    /// overhead. Features the chip lacks are left disabled.
+    pub fn for_profile(profile: &HardwareProfile) -> Self {
         Self {
-            use_neon: true,
-            num_threads: 8, // Adjust based on chip
+            use_neon: profile.has_neon,
+            num_threads: profile.default_num_threads(),
             thread_assignment: ThreadAssignment::Mixed,
+            parallel_strategy: ParallelStrategy::Chunked,
+            batch_size: None,
             encoding: Encoding::TwoBit,
             use_unified_memory: true,
-            use_gpu: true,
-            gpu_batch_size: Some(100_000),
-            use_amx: true,
-            use_neural_engine: true,
-            use_m5_gpu_neural_accel: is_m5, // Only available on M5
+            use_gpu: profile.num_gpu_cores > 0,
+            gpu_batch_size: Some(profile.default_gpu_batch_size()),
+            use_amx: profile.has_amx,
+            use_neural_engine: profile.has_neural_engine,
+            use_m5_gpu_neural_accel: profile.has_m5_gpu_neural_accel,
             use_hw_compression: true,
             use_gcd: true,
             qos: QualityOfService::UserInitiated,
-            chip_generation: Some(chip),
+            chip_generation: Some(profile.chip.clone()),
+        }
+    }
+
+    /// Create a NEON configuration on the P-cores of `profile`
+    ///
+    /// The strongest CPU-only configuration: E-cores are left out because
+    /// they lengthen the tail of chunked work on compute-bound operations.
+    pub fn p_cores_for_profile(profile: &HardwareProfile) -> Self {
+        let mut config = Self::naive();
+        config.use_neon = profile.has_neon;
+        config.num_threads = profile.num_p_cores.max(1);
+        config.thread_assignment = ThreadAssignment::PCoresOnly;
+        config.parallel_strategy = ParallelStrategy::Chunked;
+        config.qos = QualityOfService::UserInitiated;
+        config.chip_generation = Some(profile.chip.clone());
+        config
+    }
+
+    /// Check that the machine described by `profile` can run this
+    /// configuration
+    ///
+    /// Rejects features the chip or build target lacks, more threads than
+    /// cores, and a config targeting another chip generation, listing every
+    /// problem. Without this `execute_with_config` falls back to another
+    /// backend and the result measures something else. Core counts a profile
+    /// does not know (0 on unrecognized chips) are not checked, but the GPU
+    /// is only accepted on a chip with known GPU cores.
+    pub fn validate(&self, profile: &HardwareProfile) -> Result<()> {
+        let mut problems = Vec::new();
+        let chip = profile.chip_name();
+
+        if self.num_threads == 0 {
+            problems.push("num_threads must be at least 1".to_string());
+        }
+        if profile.num_cores() > 0 && self.num_threads > profile.num_cores() {
+            problems.push(format!(
+                "{} threads requested but {} has {} cores ({} P + {} E)",
+                self.num_threads,
+                chip,
+                profile.num_cores(),
+                profile.num_p_cores,
+                profile.num_e_cores
+            ));
+        }
+        if self.thread_assignment == ThreadAssignment::ECoresOnly
+            && profile.num_p_cores > 0
+            && profile.num_e_cores == 0
+        {
+            problems.push(format!("E-cores only requested but {} has no E-cores", chip));
+        }
+        if self.batch_size == Some(0) || self.gpu_batch_size == Some(0) {
+            problems.push("batch sizes must be positive".to_string());
+        }
+        if self.use_neon && !profile.has_neon {
+            problems.push(format!("NEON requested but {} has no NEON", chip));
+        }
+        if self.use_amx && !cfg!(target_arch = "aarch64") {
+            let arch = std::env::consts::ARCH;
+            problems.push(format!("AMX requested but this build targets {}", arch));
+        } else if self.use_amx && !profile.has_amx {
+            problems.push(format!("AMX requested but {} has no AMX", chip));
+        }
+        if self.use_gpu && !cfg!(target_os = "macos") {
+            let os = std::env::consts::OS;
+            problems.push(format!("GPU requested but this build targets {}", os));
+        } else if self.use_gpu && profile.num_gpu_cores == 0 {
+            problems.push(format!("GPU requested but {} has no known GPU cores", chip));
         }
+        if self.use_neural_engine && !profile.has_neural_engine {
+            problems.push(format!("Neural Engine requested but {} has none", chip));
+        }
+        if self.use_m5_gpu_neural_accel && !profile.has_m5_gpu_neural_accel {
+            problems.push(format!("M5 GPU Neural Accelerators requested but {} has none", chip));
+        }
+        if let Some(target) = self.chip_generation.as_ref().filter(|&c| *c != profile.chip) {
+            problems.push(format!("config targets {} but this machine is {}", target, chip));
+        }
+
+        if !problems.is_empty() {
+            anyhow::bail!("Hardware config cannot run on {}: {}", chip, problems.join("; "));
+        }
+        Ok(())
     }
 }
 
@@ -262,6 +457,19 @@ pub enum ThreadAssignment {
     Custom,
 }
 
+/// Work decomposition for multi-threaded execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum ParallelStrategy {
+    /// One Rayon task per record (`execute_parallel`)
+    #[default]
+    PerRecord,
+    /// Contiguous chunks, NEON within each chunk (`execute_parallel_chunked`,
+    /// or `execute_parallel_batched` with a configured batch size)
+    Chunked,
+    /// Segments of individual reads, for very long reads (`execute_parallel_intra_read`)
+    IntraRead,
+}
+
 /// DNA sequence encoding scheme
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Encoding {
@@ -303,17 +511,30 @@ pub enum QualityOfService {
 }
 
 /// Apple Silicon chip generation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum ChipGeneration {
     M1,
     M2,
     M3,
     M4,
     M5, // Latest: GPU Neural Accelerators, 153 GB/s bandwidth
+    /// Any other chip (A-series, later M-series), by name; capabilities
+    /// come from detection rather than the spec table
+    Other(String),
+}
+
+impl fmt::Display for ChipGeneration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChipGeneration::Other(name) => f.write_str(name),
+            chip => write!(f, "{:?}", chip),
+        }
+    }
 }
 
 impl ChipGeneration {
-    /// Memory bandwidth in GB/s (base variant)
+    /// Memory bandwidth in GB/s (base variant; see [`chip_spec::ChipSpec`]
+    /// for Pro/Max/Ultra, 0 if unknown)
     pub fn memory_bandwidth_gbps(&self) -> f64 {
         match self {
             ChipGeneration::M1 => 68.25,
@@ -321,6 +542,7 @@ impl ChipGeneration {
             ChipGeneration::M3 => 102.4,
             ChipGeneration::M4 => 120.0,
             ChipGeneration::M5 => 153.6,
+            ChipGeneration::Other(_) => 0.0,
         }
     }
 
@@ -332,6 +554,7 @@ impl ChipGeneration {
             ChipGeneration::M3 => 18.0,
             ChipGeneration::M4 => 38.0,
             ChipGeneration::M5 => 50.0, // Estimated
+            ChipGeneration::Other(_) => 0.0,
         }
     }
 
@@ -361,7 +584,10 @@ pub struct PerformanceResult {
     /// Throughput in megabytes per second
     pub throughput_mbps: f64,
 
-    /// Latency to first result (streaming operations)
+    /// Latency to first result
+    ///
+    /// Batch runs only produce output at completion, so this equals a full
+    /// run; streaming runs report time until the first batch is processed.
     pub latency_first_result: Duration,
 
     /// Median (50th percentile) latency
@@ -370,6 +596,18 @@ pub struct PerformanceResult {
     /// 99th percentile latency
     pub latency_p99: Duration,
 
+    /// Latency percentiles requested by the caller, keyed `p50`, `p99.9`, ...
+    /// (see [`stats::percentile_key`])
+    #[serde(default)]
+    pub latency_percentiles: BTreeMap<String, Duration>,
+
+    /// Per-run phase breakdown (one entry per measured run)
+    ///
+    /// `benchmark_operation` works on pre-loaded ASCII data, so only compute
+    /// and serialize are non-zero there; end-to-end harnesses fill all phases.
+    #[serde(default)]
+    pub phase_timings: Vec<PhaseTimings>,
+
     /// Peak memory usage in bytes
     pub memory_peak: usize,
 
@@ -385,16 +623,79 @@ pub struct PerformanceResult {
     /// Energy consumed in joules (if measurable)
     pub energy_joules: Option<f64>,
 
+    /// Bytes spilled to disk per measured run (external-memory modes)
+    #[serde(default)]
+    pub spilled_bytes: u64,
+
+    /// Page-fault and swap activity of each measured run, in run order
+    /// (empty where the platform exposes no counters)
+    #[serde(default)]
+    pub memory_activity: Vec<memory::MemoryActivity>,
+
     /// Correctness: output matches reference implementation
     pub output_matches_reference: bool,
 }
 
+/// Where the time of one run went
+///
+/// Throughput figures are computed from `compute_time` alone; the other
+/// phases make conversion and I/O costs visible instead of silently excluding
+/// them (e.g. a 2-bit kernel is only faster end-to-end if its speedup covers
+/// `encode_time`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhaseTimings {
+    /// Reading or generating input records
+    pub load_time: Duration,
+
+    /// Converting records to the kernel's encoding (e.g. `BitSeq::from_ascii`)
+    pub encode_time: Duration,
+
+    /// Running the operation
+    pub compute_time: Duration,
+
+    /// Converting the output to its serialized form
+    pub serialize_time: Duration,
+}
+
+impl PhaseTimings {
+    /// Sum of all phases
+    pub fn end_to_end(&self) -> Duration {
+        self.load_time + self.encode_time + self.compute_time + self.serialize_time
+    }
+
+    /// Per-phase medians over several runs (each phase independently)
+    pub fn median(runs: &[PhaseTimings]) -> PhaseTimings {
+        let phase = |f: fn(&PhaseTimings) -> Duration| {
+            let mut times: Vec<Duration> = runs.iter().map(f).collect();
+            times.sort();
+            stats::duration_quantile(&times, 50.0).unwrap_or_default()
+        };
+
+        PhaseTimings {
+            load_time: phase(|t| t.load_time),
+            encode_time: phase(|t| t.encode_time),
+            compute_time: phase(|t| t.compute_time),
+            serialize_time: phase(|t| t.serialize_time),
+        }
+    }
+}
+
 impl PerformanceResult {
+    /// Median phase breakdown over the measured runs
+    pub fn median_phases(&self) -> PhaseTimings {
+        PhaseTimings::median(&self.phase_timings)
+    }
+
     /// Calculate speedup relative to baseline
     pub fn speedup_vs(&self, baseline: &PerformanceResult) -> f64 {
         self.throughput_seqs_per_sec / baseline.throughput_seqs_per_sec
     }
 
+    /// Latency at percentile `p`, if it was requested for this run
+    pub fn latency_percentile(&self, p: f64) -> Option<Duration> {
+        self.latency_percentiles.get(&stats::percentile_key(p)).copied()
+    }
+
     /// Calculate efficiency (throughput per watt)
     pub fn efficiency_seqs_per_joule(&self) -> Option<f64> {
         self.energy_joules.map(|joules| {
@@ -418,7 +719,7 @@ impl PerformanceResult {
 /// - **Pairwise**: O(n²), compute-intensive
 /// - **Aggregation**: Reducible, embarrassingly parallel
 /// - **IO**: Bandwidth-limited, hardware compression helps
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum OperationCategory {
     /// Element-wise operations (base counting, GC content, reverse complement)
     ElementWise,
@@ -456,6 +757,65 @@ pub trait PrimitiveOperation: Send + Sync {
     /// Operation category
     fn category(&self) -> OperationCategory;
 
+    /// Parameters that change what the operation computes (thresholds, modes)
+    ///
+    /// Recorded alongside the operation so results from differently
+    /// configured instances of the same operation stay distinguishable.
+    fn parameters(&self) -> serde_json::Value {
+        // Default: no parameters
+        serde_json::Value::Null
+    }
+
+    /// Tolerance for comparing this operation's outputs between runs and
+    /// backends
+    ///
+    /// Operations whose outputs include measurements (timings) override
+    /// this to exempt those fields; everything else keeps the default.
+    fn output_tolerance(&self) -> compare::Tolerance {
+        compare::Tolerance::default()
+    }
+
+    /// This operation tokenizing its text input with `parser`
+    ///
+    /// Only operations that parse FASTQ text (I/O category) have a parser
+    /// to choose; the default `None` marks the rest. Experiment configs use
+    /// it to cross such operations with [`io::ParserBackend`]s.
+    fn parser_variant(&self, parser: io::ParserBackend) -> Option<Box<dyn PrimitiveOperation>> {
+        None
+    }
+
+    /// Estimated memory of one run over `num_sequences` records of
+    /// `mean_length` bases stored in `encoding`
+    ///
+    /// Harnesses compare this against a memory limit before loading data.
+    /// The default sizes the output by category; operations whose tables
+    /// grow with the input (k-mer counting, sorting) override it.
+    fn estimate_memory(
+        &self,
+        num_sequences: usize,
+        mean_length: f64,
+        encoding: Encoding,
+    ) -> memory::MemoryEstimate {
+        let estimate = memory::MemoryEstimate::input(num_sequences, mean_length, encoding);
+        match self.category() {
+            // Transformed or kept records: up to a second copy of the input
+            OperationCategory::ElementWise
+            | OperationCategory::Filter
+            | OperationCategory::Pairwise
+            | OperationCategory::IO => {
+                let records = estimate.input_bytes as f64;
+                estimate.with_output(records)
+            }
+            // Per-read hits and scratch buffers
+            OperationCategory::Search => {
+                let records = estimate.input_bytes as f64;
+                estimate.with_working(records)
+            }
+            // Fixed-size statistics
+            OperationCategory::Aggregation => estimate,
+        }
+    }
+
     /// Execute with naive (baseline) implementation
     fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput>;
 
@@ -475,6 +835,82 @@ pub trait PrimitiveOperation: Send + Sync {
         self.execute_naive(data)
     }
 
+    /// Execute with parallel threads over contiguous chunks
+    ///
+    /// Splits the records into about `num_threads * 4` contiguous chunks,
+    /// runs the NEON path within each chunk and reduces across chunks,
+    /// avoiding per-record task scheduling.
+    fn execute_parallel_chunked(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        // Default: chunks sized by the thread count
+        self.execute_parallel_batched(data, num_threads, None)
+    }
+
+    /// Execute with parallel threads over contiguous chunks of `batch_size`
+    /// records (`None`: sized by the thread count, as
+    /// [`execute_parallel_chunked`](Self::execute_parallel_chunked))
+    ///
+    /// Small batches stay cache-resident and balance uneven reads; large
+    /// batches amortize per-chunk setup and merging.
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        // Default: not supported
+        anyhow::bail!("Chunked parallel execution not implemented for {}", self.name())
+    }
+
+    /// Execute with parallel threads over segments of individual reads
+    ///
+    /// Splits each read into segments (with lookahead across segment
+    /// boundaries where the operation needs it) so a few 100 kb reads
+    /// still spread across all threads.
+    fn execute_parallel_intra_read(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        // Default: not supported
+        anyhow::bail!("Intra-read parallel execution not implemented for {}", self.name())
+    }
+
+    /// Execute on `config.num_threads` threads with the configured strategy
+    fn execute_threaded(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        match config.parallel_strategy {
+            ParallelStrategy::PerRecord => self.execute_parallel(data, config.num_threads),
+            ParallelStrategy::Chunked => match config.batch_size {
+                Some(size) => self.execute_parallel_batched(data, config.num_threads, Some(size)),
+                None => self.execute_parallel_chunked(data, config.num_threads),
+            },
+            ParallelStrategy::IntraRead => {
+                self.execute_parallel_intra_read(data, config.num_threads)
+            }
+        }
+    }
+
+    /// Execute on borrowed records (zero-copy inputs such as mmap'd files)
+    fn execute_views(&self, data: &[SequenceView]) -> Result<OperationOutput> {
+        // Default: copy into owned records and run the NEON path
+        let records: Vec<SequenceRecord> = data.iter().map(SequenceView::to_record).collect();
+        self.execute_neon(&records)
+    }
+
+    /// Execute on packed (contiguous) record storage
+    fn execute_packed(&self, data: &packed::PackedRecords) -> Result<OperationOutput> {
+        // Default: borrow the records as views
+        let views: Vec<SequenceView> = data.views().collect();
+        self.execute_views(&views)
+    }
+
     /// Execute with Metal GPU (if applicable)
     fn execute_gpu(
         &self,
@@ -523,7 +959,7 @@ pub trait PrimitiveOperation: Send + Sync {
         }
 
         if config.num_threads > 1 {
-            return self.execute_parallel(data, config.num_threads);
+            return self.execute_threaded(data, config);
         }
 
         if config.use_neon {
@@ -573,6 +1009,56 @@ impl SequenceRecord {
     pub fn is_empty(&self) -> bool {
         self.sequence.is_empty()
     }
+
+    /// Borrow as a view
+    pub fn view(&self) -> SequenceView<'_> {
+        SequenceView {
+            id: &self.id,
+            sequence: &self.sequence,
+            quality: self.quality.as_deref(),
+        }
+    }
+}
+
+/// Borrowed sequence record: slices into a buffer owned elsewhere
+///
+/// Lets operations run directly on memory-mapped files or packed storage
+/// without copying each record into owned `String`/`Vec` buffers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceView<'a> {
+    /// Sequence identifier (header)
+    pub id: &'a str,
+    /// DNA sequence (A, C, G, T, N)
+    pub sequence: &'a [u8],
+    /// Quality scores (FASTQ only, Phred+33 encoded)
+    pub quality: Option<&'a [u8]>,
+}
+
+impl SequenceView<'_> {
+    /// Get sequence length
+    pub fn len(&self) -> usize {
+        self.sequence.len()
+    }
+
+    /// Check if record is empty
+    pub fn is_empty(&self) -> bool {
+        self.sequence.is_empty()
+    }
+
+    /// Copy into an owned record
+    pub fn to_record(&self) -> SequenceRecord {
+        SequenceRecord {
+            id: self.id.to_string(),
+            sequence: self.sequence.to_vec(),
+            quality: self.quality.map(<[u8]>::to_vec),
+        }
+    }
+}
+
+impl<'a> From<&'a SequenceRecord> for SequenceView<'a> {
+    fn from(record: &'a SequenceRecord) -> Self {
+        record.view()
+    }
 }
 
 /// Output from an operation
@@ -582,11 +1068,20 @@ impl SequenceRecord {
 /// - Computed statistics
 /// - Boolean results (pass/fail)
 /// - Transformed sequences
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Statistics are returned as the operation's own result struct
+/// ([`OperationOutput::typed`]) and only converted to JSON at the
+/// serialization boundary. A serialized `Typed` output deserializes as
+/// `Statistics`, and the two compare equal when their JSON forms match, so
+/// stored outputs (e.g. golden files) can be checked against live results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum OperationOutput {
     /// Filtered sequence records
     Records(Vec<SequenceRecord>),
-    /// Statistics (counts, means, etc.)
+    /// Typed statistics (serializes exactly like `Statistics`)
+    #[serde(rename = "Statistics", skip_deserializing)]
+    Typed(TypedOutput),
+    /// Statistics as JSON (counts, means, etc.), e.g. read back from disk
     Statistics(serde_json::Value),
     /// Boolean result (pass/fail)
     Boolean(bool),
@@ -596,6 +1091,127 @@ pub enum OperationOutput {
     Json(serde_json::Value),
 }
 
+impl OperationOutput {
+    /// Wrap an operation's typed result
+    pub fn typed<T: TypedResult>(result: T) -> Self {
+        OperationOutput::Typed(TypedOutput(Box::new(result)))
+    }
+
+    /// Borrow the typed result, if this output holds a `T`
+    pub fn statistics<T: TypedResult>(&self) -> Option<&T> {
+        match self {
+            OperationOutput::Typed(typed) => typed.downcast_ref(),
+            _ => None,
+        }
+    }
+
+    /// JSON form of a statistics output (`None` for records, counts, booleans)
+    pub fn statistics_json(&self) -> Result<Option<serde_json::Value>> {
+        match self {
+            OperationOutput::Typed(typed) => Ok(Some(typed.to_json()?)),
+            OperationOutput::Statistics(value) | OperationOutput::Json(value) => {
+                Ok(Some(value.clone()))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+impl PartialEq for OperationOutput {
+    fn eq(&self, other: &Self) -> bool {
+        use OperationOutput::*;
+
+        match (self, other) {
+            (Records(a), Records(b)) => a == b,
+            (Typed(a), Typed(b)) => a == b,
+            (Typed(typed), Statistics(value)) | (Statistics(value), Typed(typed)) => {
+                typed.to_json().is_ok_and(|json| json == *value)
+            }
+            (Statistics(a), Statistics(b)) => a == b,
+            (Boolean(a), Boolean(b)) => a == b,
+            (Count(a), Count(b)) => a == b,
+            (Json(a), Json(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Result type that can be carried by `OperationOutput::Typed`
+///
+/// Implemented for every `Serialize + Clone + PartialEq + Debug` type, so
+/// operation result structs need no extra code.
+pub trait TypedResult: Any + std::fmt::Debug + Send + Sync {
+    /// Serialize to JSON (only at the output boundary)
+    fn to_json(&self) -> serde_json::Result<serde_json::Value>;
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn clone_boxed(&self) -> Box<dyn TypedResult>;
+
+    /// Equality across the type-erased boundary (false for different types)
+    fn eq_dyn(&self, other: &dyn TypedResult) -> bool;
+}
+
+impl<T> TypedResult for T
+where
+    T: Serialize + Clone + PartialEq + std::fmt::Debug + Send + Sync + 'static,
+{
+    fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TypedResult> {
+        Box::new(self.clone())
+    }
+
+    fn eq_dyn(&self, other: &dyn TypedResult) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<T>()
+            .is_some_and(|other| self == other)
+    }
+}
+
+/// Type-erased operation result (see [`TypedResult`])
+#[derive(Debug)]
+pub struct TypedOutput(Box<dyn TypedResult>);
+
+impl TypedOutput {
+    /// Borrow the result as its concrete type
+    pub fn downcast_ref<T: TypedResult>(&self) -> Option<&T> {
+        self.0.as_any().downcast_ref()
+    }
+
+    /// Serialize the result to JSON
+    pub fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        self.0.to_json()
+    }
+}
+
+impl Clone for TypedOutput {
+    fn clone(&self) -> Self {
+        TypedOutput(self.0.clone_boxed())
+    }
+}
+
+impl PartialEq for TypedOutput {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_dyn(other.0.as_ref())
+    }
+}
+
+impl Serialize for TypedOutput {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.to_json()
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
 // ============================================================================
 // Hardware Profile (Runtime Detection)
 // ============================================================================
@@ -607,7 +1223,7 @@ pub enum OperationOutput {
 /// Note: Does not derive Eq because it contains floating point values.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HardwareProfile {
-    /// Chip generation (M1, M2, M3, M4, M5)
+    /// Chip generation (M1-M5, or `Other` for unrecognized chips)
     pub chip: ChipGeneration,
 
     /// Chip variant (Base, Pro, Max, Ultra)
@@ -641,13 +1257,166 @@ pub struct HardwareProfile {
     pub has_m5_gpu_neural_accel: bool,
 }
 
+/// Sequences per GPU core in a default GPU batch
+///
+/// 10 GPU cores (M4 base) give the 100K batch the GPU pilots settled on.
+pub const GPU_BATCH_SEQUENCES_PER_CORE: usize = 10_000;
+
 impl HardwareProfile {
     /// Detect hardware profile from system
+    ///
+    /// Core counts, memory and chip name come from `sysctl`. GPU cores and
+    /// memory bandwidth are not exposed there, so they come from the
+    /// [`ChipSpec`](chip_spec::ChipSpec) matching the detected chip and P-core
+    /// count (binned parts have fewer GPU cores and less bandwidth).
+    ///
+    /// Chips outside the spec table (A-series under Mac Catalyst, future
+    /// M-series) are recorded as [`ChipGeneration::Other`] with whatever
+    /// `sysctl` reports; GPU cores and bandwidth are then 0 (unknown).
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
     pub fn detect() -> Result<Self> {
-        // TODO: Implement system detection using sysctl/IOKit
-        // For now, return placeholder
-        anyhow::bail!("Hardware detection not yet implemented")
+        let count = |name: &str| sysctl::integer(name).map(|n| n as usize);
+
+        let brand = sysctl::string("machdep.cpu.brand_string")?;
+        let (chip, variant) = parse_chip_name(&brand)
+            .unwrap_or_else(|| (ChipGeneration::Other(brand.clone()), ChipVariant::Base));
+
+        let mut profile = Self::nominal(chip, variant);
+        // Single-cluster chips have no perflevels: count every core as a P-core
+        profile.num_p_cores =
+            count("hw.perflevel0.physicalcpu").or_else(|_| count("hw.physicalcpu"))?;
+        profile.num_e_cores = count("hw.perflevel1.physicalcpu").unwrap_or(0);
+        if let Some(spec) =
+            chip_spec::ChipSpec::for_detected(&profile.chip, variant, profile.num_p_cores)
+        {
+            profile.num_gpu_cores = spec.gpu_cores;
+            profile.memory_bandwidth_gbps = spec.memory_bandwidth_gbps;
+        }
+        profile.memory_gb = (count("hw.memsize")? as f64 / (1u64 << 30) as f64).round() as usize;
+        if let ChipGeneration::Other(_) = profile.chip {
+            profile.has_neon = count("hw.optional.neon").is_ok_and(|n| n == 1);
+        }
+        Ok(profile)
+    }
+
+    /// Detect hardware profile from system
+    #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+    pub fn detect() -> Result<Self> {
+        anyhow::bail!("Hardware detection requires macOS on Apple Silicon")
+    }
+
+    /// Published specification of a chip (fullest binned configuration)
+    ///
+    /// [`ChipGeneration::Other`] has no published spec: core counts, GPU
+    /// cores, memory and bandwidth are 0 and only NEON (any arm64 chip) is
+    /// assumed.
+    pub fn nominal(chip: ChipGeneration, variant: ChipVariant) -> Self {
+        let Some(spec) = chip_spec::ChipSpec::lookup(&chip, variant) else {
+            return Self {
+                has_m5_gpu_neural_accel: false,
+                chip,
+                chip_variant: variant,
+                num_p_cores: 0,
+                num_e_cores: 0,
+                num_gpu_cores: 0,
+                memory_gb: 0,
+                memory_bandwidth_gbps: 0.0,
+                has_neon: cfg!(target_arch = "aarch64"),
+                has_amx: false,
+                has_neural_engine: false,
+            };
+        };
+
+        Self {
+            has_m5_gpu_neural_accel: chip.has_gpu_neural_accelerators(),
+            chip,
+            chip_variant: variant,
+            num_p_cores: spec.p_cores,
+            num_e_cores: spec.e_cores,
+            num_gpu_cores: spec.gpu_cores,
+            memory_gb: spec.max_memory_gb,
+            memory_bandwidth_gbps: spec.memory_bandwidth_gbps,
+            has_neon: true,
+            has_amx: true,
+            has_neural_engine: true,
+        }
+    }
+
+    /// Human-readable chip name (`M4 Pro`, `A17 Pro`), as recorded in results
+    pub fn chip_name(&self) -> String {
+        match (&self.chip, self.chip_variant) {
+            (chip, ChipVariant::Base) => chip.to_string(),
+            (chip, variant) => format!("{} {:?}", chip, variant),
+        }
     }
+
+    /// Total CPU cores (P + E)
+    pub fn num_cores(&self) -> usize {
+        self.num_p_cores + self.num_e_cores
+    }
+
+    /// Default thread count: one per core
+    pub fn default_num_threads(&self) -> usize {
+        self.num_cores().max(1)
+    }
+
+    /// Default GPU batch size (sequences), proportional to GPU core count
+    pub fn default_gpu_batch_size(&self) -> usize {
+        self.num_gpu_cores.max(1) * GPU_BATCH_SEQUENCES_PER_CORE
+    }
+}
+
+/// Whether this process is an x86_64 binary translated by Rosetta 2
+///
+/// Translated processes have no NEON, AMX or Metal compute paths, so every
+/// backend silently measures x86 emulation instead. Reads
+/// `sysctl.proc_translated` (absent on Intel Macs and other platforms).
+pub fn is_translated() -> bool {
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    {
+        sysctl::integer("sysctl.proc_translated").is_ok_and(|translated| translated == 1)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+    {
+        false
+    }
+}
+
+/// Parse a CPU brand string such as `Apple M4 Pro`
+///
+/// Later M-series generations (`Apple M6 Max`) parse as
+/// [`ChipGeneration::Other`] with their variant; A-series names keep their
+/// suffix in the chip name (`Apple A17 Pro` → `Other("A17 Pro")`, `Base`).
+/// Non-Apple brand strings give `None`.
+pub fn parse_chip_name(name: &str) -> Option<(ChipGeneration, ChipVariant)> {
+    let mut words = name.split_whitespace().skip_while(|word| *word == "Apple");
+    let generation = words.next()?;
+    let is_family = |family: char| {
+        generation.len() > 1
+            && generation.starts_with(family)
+            && generation[1..].bytes().all(|b| b.is_ascii_digit())
+    };
+    let chip = match generation {
+        "M1" => ChipGeneration::M1,
+        "M2" => ChipGeneration::M2,
+        "M3" => ChipGeneration::M3,
+        "M4" => ChipGeneration::M4,
+        "M5" => ChipGeneration::M5,
+        _ if is_family('M') => ChipGeneration::Other(generation.to_string()),
+        _ if is_family('A') => {
+            let name: Vec<&str> = std::iter::once(generation).chain(words).collect();
+            return Some((ChipGeneration::Other(name.join(" ")), ChipVariant::Base));
+        }
+        _ => return None,
+    };
+    let variant = match words.next() {
+        None => ChipVariant::Base,
+        Some("Pro") => ChipVariant::Pro,
+        Some("Max") => ChipVariant::Max,
+        Some("Ultra") => ChipVariant::Ultra,
+        Some(_) => return None,
+    };
+    Some((chip, variant))
 }
 
 /// Chip variant (Base, Pro, Max, Ultra)
@@ -686,6 +1455,19 @@ mod tests {
             ..tiny
         };
         assert_eq!(huge.scale_category(), DataScale::Huge);
+        assert_eq!(huge.length_class(), LengthClass::Short);
+    }
+
+    #[test]
+    fn test_length_class() {
+        assert_eq!(LengthClass::from_mean_length(150), LengthClass::Short);
+        assert_eq!(LengthClass::from_mean_length(1_000), LengthClass::Kb1);
+        assert_eq!(LengthClass::from_mean_length(10_000), LengthClass::Kb10);
+        assert_eq!(LengthClass::from_mean_length(100_000), LengthClass::Kb100);
+        for class in LengthClass::ALL {
+            assert_eq!(LengthClass::from_name(class.name()), Some(class));
+        }
+        assert_eq!(LengthClass::from_name("1mb"), None);
     }
 
     #[test]
@@ -707,6 +1489,102 @@ mod tests {
         assert!(!config_m4.use_m5_gpu_neural_accel); // Not available on M4
     }
 
+    #[test]
+    fn test_hardware_config_validate() {
+        let m1 = HardwareProfile::nominal(ChipGeneration::M1, ChipVariant::Base);
+        assert!(HardwareConfig::naive().validate(&m1).is_ok());
+        assert!(HardwareConfig::p_cores_for_profile(&m1).validate(&m1).is_ok());
+
+        // M1 base: 4 P + 4 E cores, no GPU Neural Accelerators
+        let mut config = HardwareConfig::naive();
+        config.num_threads = 16;
+        config.use_m5_gpu_neural_accel = true;
+        let error = config.validate(&m1).unwrap_err().to_string();
+        assert!(error.contains("16 threads requested but M1 has 8 cores"), "{}", error);
+        assert!(error.contains("M5 GPU Neural Accelerators"), "{}", error);
+
+        let mut amx = HardwareConfig::naive();
+        amx.use_amx = true;
+        assert_eq!(amx.validate(&m1).is_ok(), cfg!(target_arch = "aarch64"));
+
+        let m5 = HardwareProfile::nominal(ChipGeneration::M5, ChipVariant::Base);
+        let mut for_m5 = HardwareConfig::p_cores_for_profile(&m5);
+        assert!(for_m5.validate(&m5).is_ok());
+        assert!(for_m5.validate(&m1).is_err());
+        for_m5.chip_generation = None;
+        for_m5.use_m5_gpu_neural_accel = true;
+        assert!(for_m5.validate(&m5).is_ok());
+
+        let mut gpu = HardwareConfig::naive();
+        gpu.use_gpu = true;
+        assert_eq!(gpu.validate(&m1).is_ok(), cfg!(target_os = "macos"));
+
+        // Unknown chips have no core counts to check against
+        let other =
+            HardwareProfile::nominal(ChipGeneration::Other("A17 Pro".into()), ChipVariant::Base);
+        config.use_m5_gpu_neural_accel = false;
+        assert!(config.validate(&other).is_ok());
+        assert!(gpu.validate(&other).is_err());
+    }
+
+    #[test]
+    fn test_hardware_config_for_profile() {
+        // M4 base: 4 P + 6 E cores, 10 GPU cores
+        let config = HardwareConfig::fully_optimized(ChipGeneration::M4);
+        assert_eq!(config.num_threads, 10);
+        assert_eq!(config.gpu_batch_size, Some(100_000));
+
+        let max = HardwareProfile::nominal(ChipGeneration::M4, ChipVariant::Max);
+        let config = HardwareConfig::for_profile(&max);
+        assert_eq!(config.num_threads, 16);
+        assert_eq!(config.gpu_batch_size, Some(400_000));
+        assert_eq!(config.chip_generation, Some(ChipGeneration::M4));
+
+        let p_cores = HardwareConfig::p_cores_for_profile(&max);
+        assert_eq!(p_cores.num_threads, 12);
+        assert_eq!(p_cores.thread_assignment, ThreadAssignment::PCoresOnly);
+        assert!(!p_cores.use_gpu);
+    }
+
+    #[test]
+    fn test_parse_chip_name() {
+        assert_eq!(
+            parse_chip_name("Apple M4 Pro"),
+            Some((ChipGeneration::M4, ChipVariant::Pro))
+        );
+        assert_eq!(
+            parse_chip_name("Apple M1"),
+            Some((ChipGeneration::M1, ChipVariant::Base))
+        );
+        assert_eq!(parse_chip_name("Intel(R) Xeon(R) CPU"), None);
+
+        // A-series and later generations are recorded by name
+        let other = |name: &str| ChipGeneration::Other(name.to_string());
+        assert_eq!(
+            parse_chip_name("Apple A17 Pro"),
+            Some((other("A17 Pro"), ChipVariant::Base))
+        );
+        assert_eq!(
+            parse_chip_name("Apple M6 Max"),
+            Some((other("M6"), ChipVariant::Max))
+        );
+    }
+
+    #[test]
+    fn test_unknown_chip_profile() {
+        let profile = HardwareProfile::nominal(
+            ChipGeneration::Other("A18 Pro".to_string()),
+            ChipVariant::Base,
+        );
+        assert_eq!(profile.chip_name(), "A18 Pro");
+        assert_eq!(profile.memory_bandwidth_gbps, 0.0);
+        assert!(!profile.has_m5_gpu_neural_accel);
+        assert_eq!(
+            HardwareProfile::nominal(ChipGeneration::M3, ChipVariant::Max).chip_name(),
+            "M3 Max"
+        );
+    }
+
     #[test]
     fn test_chip_generation_capabilities() {
         assert_eq!(ChipGeneration::M1.memory_bandwidth_gbps(), 68.25);
@@ -747,11 +1625,15 @@ mod tests {
             latency_first_result: Duration::from_millis(10),
             latency_p50: Duration::from_millis(100),
             latency_p99: Duration::from_millis(200),
+            latency_percentiles: BTreeMap::new(),
+            phase_timings: Vec::new(),
             memory_peak: 1_000_000,
             memory_avg: 500_000,
             cpu_utilization: 1.0,
             gpu_utilization: None,
             energy_joules: Some(10.0),
+            spilled_bytes: 0,
+            memory_activity: Vec::new(),
             output_matches_reference: true,
         };
 
@@ -762,4 +1644,43 @@ mod tests {
 
         assert_eq!(optimized.speedup_vs(&baseline), 10.0);
     }
+
+    #[test]
+    fn test_phase_timings_median() {
+        let run = |compute_ms, encode_ms| PhaseTimings {
+            encode_time: Duration::from_millis(encode_ms),
+            compute_time: Duration::from_millis(compute_ms),
+            ..PhaseTimings::default()
+        };
+        let median = PhaseTimings::median(&[run(10, 5), run(30, 1), run(20, 3)]);
+
+        assert_eq!(median.compute_time, Duration::from_millis(20));
+        assert_eq!(median.encode_time, Duration::from_millis(3));
+        assert_eq!(median.end_to_end(), Duration::from_millis(23));
+        assert_eq!(PhaseTimings::median(&[]), PhaseTimings::default());
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize)]
+    struct Counts {
+        count_a: usize,
+        gc_percent: f64,
+    }
+
+    #[test]
+    fn test_typed_output_serializes_as_statistics() {
+        let counts = Counts { count_a: 3, gc_percent: 41.5 };
+        let typed = OperationOutput::typed(counts.clone());
+        let json = OperationOutput::Statistics(serde_json::to_value(&counts).unwrap());
+
+        assert_eq!(serde_json::to_string(&typed).unwrap(), serde_json::to_string(&json).unwrap());
+        assert_eq!(typed.statistics::<Counts>(), Some(&counts));
+        assert_eq!(json.statistics::<Counts>(), None);
+
+        // Stored outputs come back as JSON and still compare equal
+        let restored: OperationOutput =
+            serde_json::from_str(&serde_json::to_string(&typed).unwrap()).unwrap();
+        assert!(matches!(restored, OperationOutput::Statistics(_)));
+        assert_eq!(restored, typed);
+        assert_ne!(restored, OperationOutput::typed(Counts { count_a: 4, ..counts }));
+    }
 }
@@ -0,0 +1,419 @@
+//! Memory estimates made before an experiment runs
+//!
+//! A Huge-scale experiment that outgrows physical memory does not fail
+//! cleanly on macOS: the process is jetsam-killed and the rest of the batch
+//! is lost. Harnesses instead ask the operation for a [`MemoryEstimate`]
+//! (see [`PrimitiveOperation::estimate_memory`](crate::PrimitiveOperation::estimate_memory))
+//! and skip experiments whose estimate exceeds a [`MemoryLimit`], a fraction
+//! of physical memory, recording them as insufficient-memory results.
+//!
+//! Estimates are deliberately coarse (allocator and hash table overheads
+//! are rounded up); their job is to catch the experiments that are off by
+//! an order of magnitude, not to predict peak RSS.
+//!
+//! [`MemoryActivity`] covers what happens while a run executes: page faults
+//! of this process and system-wide compressor/swap traffic, sampled around
+//! each run so a slow run can be attributed to memory pressure rather than
+//! to the code under test.
+
+use crate::Encoding;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Share of physical memory an experiment may be estimated to use
+pub const DEFAULT_MEMORY_FRACTION: f64 = 0.8;
+
+/// Heap bytes per record beyond its bases and qualities: the headers of
+/// the ID, sequence and quality buffers, a typical read ID and allocator
+/// rounding
+pub const RECORD_OVERHEAD_BYTES: f64 = 120.0;
+
+/// Expected memory of one run, by where it goes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryEstimate {
+    /// Input records (and their encoded copy), resident for the whole run
+    pub input_bytes: u64,
+
+    /// Tables, keys and buffers that live only during the run
+    pub working_bytes: u64,
+
+    /// Output held when the run finishes
+    pub output_bytes: u64,
+}
+
+impl MemoryEstimate {
+    /// Input of `num_sequences` FASTQ records of `mean_length` bases
+    ///
+    /// Records always hold ASCII bases and qualities; other encodings add
+    /// the encoded copy the operation runs on.
+    pub fn input(num_sequences: usize, mean_length: f64, encoding: Encoding) -> Self {
+        let encoded = match encoding {
+            Encoding::Ascii => 0.0,
+            other => mean_length * other.bytes_per_base(),
+        };
+        let per_record = RECORD_OVERHEAD_BYTES + 2.0 * mean_length + encoded;
+        Self {
+            input_bytes: (num_sequences as f64 * per_record) as u64,
+            working_bytes: 0,
+            output_bytes: 0,
+        }
+    }
+
+    pub fn with_working(mut self, bytes: f64) -> Self {
+        self.working_bytes = bytes.max(0.0) as u64;
+        self
+    }
+
+    pub fn with_output(mut self, bytes: f64) -> Self {
+        self.output_bytes = bytes.max(0.0) as u64;
+        self
+    }
+
+    pub fn total(&self) -> u64 {
+        self.input_bytes.saturating_add(self.working_bytes).saturating_add(self.output_bytes)
+    }
+}
+
+/// Physical memory of this machine in bytes (`None` if it cannot be read)
+pub fn physical_memory_bytes() -> Option<u64> {
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    {
+        crate::sysctl::integer("hw.memsize").ok()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        let line = meminfo.lines().find(|line| line.starts_with("MemTotal:"))?;
+        let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+        Some(kib * 1024)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "ios", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// Largest estimate an experiment may have to run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryLimit {
+    pub limit_bytes: u64,
+}
+
+impl MemoryLimit {
+    pub fn new(limit_bytes: u64) -> Self {
+        Self { limit_bytes }
+    }
+
+    /// `fraction` of physical memory (`None` if physical memory is unknown)
+    pub fn fraction_of_physical(fraction: f64) -> Option<Self> {
+        physical_memory_bytes().map(|bytes| Self::new((bytes as f64 * fraction) as u64))
+    }
+
+    /// Whether a run with this estimate fits, given `resident_bytes` already
+    /// held by the harness (e.g. other cached datasets)
+    pub fn check(
+        &self,
+        estimate: &MemoryEstimate,
+        resident_bytes: u64,
+    ) -> Result<(), InsufficientMemory> {
+        let estimated_bytes = estimate.total().saturating_add(resident_bytes);
+        if estimated_bytes > self.limit_bytes {
+            Err(InsufficientMemory { estimated_bytes, limit_bytes: self.limit_bytes })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// An experiment estimated to need more memory than allowed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsufficientMemory {
+    pub estimated_bytes: u64,
+    pub limit_bytes: u64,
+}
+
+impl InsufficientMemory {
+    /// Skip reason recorded in results
+    pub const REASON: &'static str = "insufficient-memory";
+}
+
+impl fmt::Display for InsufficientMemory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "estimated {:.2} GB exceeds the {:.2} GB memory limit",
+            self.estimated_bytes as f64 / 1e9,
+            self.limit_bytes as f64 / 1e9
+        )
+    }
+}
+
+impl std::error::Error for InsufficientMemory {}
+
+// ============================================================================
+// Memory Activity
+// ============================================================================
+
+/// Page-fault and swap counters, cumulative since boot (or process start)
+///
+/// Take the difference of two samples ([`MemoryActivity::since`]) around a
+/// run. Faults are this process's; compressor and swap counters are
+/// system-wide, since pages of other processes are compressed or swapped to
+/// make room for this one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryActivity {
+    /// Faults served without I/O (zero fill, copy-on-write, cached pages)
+    pub minor_faults: u64,
+
+    /// Faults that read a page from disk
+    pub major_faults: u64,
+
+    /// Pages compressed by the memory compressor (macOS; zswap on Linux)
+    pub compressions: u64,
+
+    /// Pages decompressed from the memory compressor
+    pub decompressions: u64,
+
+    /// Pages read back from swap
+    pub swapins: u64,
+
+    /// Pages written out to swap
+    pub swapouts: u64,
+}
+
+impl MemoryActivity {
+    /// Current counters (`None` if this platform does not expose them)
+    pub fn sample() -> Option<Self> {
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        {
+            mach::sample()
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            procfs::sample()
+        }
+
+        #[cfg(not(any(target_os = "macos", target_os = "ios", target_os = "linux")))]
+        {
+            None
+        }
+    }
+
+    /// Activity between `earlier` and this sample
+    pub fn since(&self, earlier: &Self) -> Self {
+        Self {
+            minor_faults: self.minor_faults.saturating_sub(earlier.minor_faults),
+            major_faults: self.major_faults.saturating_sub(earlier.major_faults),
+            compressions: self.compressions.saturating_sub(earlier.compressions),
+            decompressions: self.decompressions.saturating_sub(earlier.decompressions),
+            swapins: self.swapins.saturating_sub(earlier.swapins),
+            swapouts: self.swapouts.saturating_sub(earlier.swapouts),
+        }
+    }
+
+    /// Sum of two activities (e.g. over several runs)
+    pub fn add(&self, other: &Self) -> Self {
+        Self {
+            minor_faults: self.minor_faults + other.minor_faults,
+            major_faults: self.major_faults + other.major_faults,
+            compressions: self.compressions + other.compressions,
+            decompressions: self.decompressions + other.decompressions,
+            swapins: self.swapins + other.swapins,
+            swapouts: self.swapouts + other.swapouts,
+        }
+    }
+
+    /// Whether the run waited on disk or the compressor (minor faults alone
+    /// are normal allocation traffic)
+    pub fn under_pressure(&self) -> bool {
+        self.major_faults + self.compressions + self.decompressions + self.swapins + self.swapouts
+            > 0
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod mach {
+    //! `task_info(TASK_EVENTS_INFO)` and `host_statistics64(HOST_VM_INFO64)`
+
+    use super::MemoryActivity;
+    use std::ffi::c_int;
+
+    const TASK_EVENTS_INFO: c_int = 2;
+    const HOST_VM_INFO64: c_int = 4;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct TaskEventsInfo {
+        faults: i32,
+        pageins: i32,
+        cow_faults: i32,
+        messages_sent: i32,
+        messages_received: i32,
+        syscalls_mach: i32,
+        syscalls_unix: i32,
+        csw: i32,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct VmStatistics64 {
+        free_count: u32,
+        active_count: u32,
+        inactive_count: u32,
+        wire_count: u32,
+        zero_fill_count: u64,
+        reactivations: u64,
+        pageins: u64,
+        pageouts: u64,
+        faults: u64,
+        cow_faults: u64,
+        lookups: u64,
+        hits: u64,
+        purges: u64,
+        purgeable_count: u32,
+        speculative_count: u32,
+        decompressions: u64,
+        compressions: u64,
+        swapins: u64,
+        swapouts: u64,
+        compressor_page_count: u32,
+        throttled_count: u32,
+        external_page_count: u32,
+        internal_page_count: u32,
+        total_uncompressed_pages_in_compressor: u64,
+    }
+
+    extern "C" {
+        static mach_task_self_: u32;
+        fn mach_host_self() -> u32;
+        fn task_info(task: u32, flavor: c_int, info: *mut i32, count: *mut u32) -> c_int;
+        fn host_statistics64(host: u32, flavor: c_int, info: *mut i32, count: *mut u32) -> c_int;
+    }
+
+    /// Size of `T` in `integer_t` units (the `*_COUNT` constants)
+    fn count_of<T>() -> u32 {
+        (std::mem::size_of::<T>() / std::mem::size_of::<i32>()) as u32
+    }
+
+    pub fn sample() -> Option<MemoryActivity> {
+        let mut events = TaskEventsInfo::default();
+        let mut count = count_of::<TaskEventsInfo>();
+        // SAFETY: `events` is a `task_events_info` of `count` integers
+        let status = unsafe {
+            task_info(
+                mach_task_self_,
+                TASK_EVENTS_INFO,
+                (&mut events as *mut TaskEventsInfo).cast(),
+                &mut count,
+            )
+        };
+        if status != 0 {
+            return None;
+        }
+
+        let mut vm = VmStatistics64::default();
+        let mut count = count_of::<VmStatistics64>();
+        // SAFETY: `vm` is a `vm_statistics64` of `count` integers
+        let status = unsafe {
+            host_statistics64(
+                mach_host_self(),
+                HOST_VM_INFO64,
+                (&mut vm as *mut VmStatistics64).cast(),
+                &mut count,
+            )
+        };
+        if status != 0 {
+            return None;
+        }
+
+        // `faults` counts every fault; `pageins` the ones that read from disk
+        let faults = events.faults as u32 as u64;
+        let pageins = events.pageins as u32 as u64;
+        Some(MemoryActivity {
+            minor_faults: faults.saturating_sub(pageins),
+            major_faults: pageins,
+            compressions: vm.compressions,
+            decompressions: vm.decompressions,
+            swapins: vm.swapins,
+            swapouts: vm.swapouts,
+        })
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod procfs {
+    //! `/proc/self/stat` (faults) and `/proc/vmstat` (swap, zswap)
+
+    use super::MemoryActivity;
+
+    pub fn sample() -> Option<MemoryActivity> {
+        // Fields after the parenthesised command name, from `state` (field 3)
+        let stat = std::fs::read_to_string("/proc/self/stat").ok()?;
+        let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+        let field = |number: usize| fields.get(number - 3)?.parse::<u64>().ok();
+
+        let vmstat = std::fs::read_to_string("/proc/vmstat").ok()?;
+        let counter = |name: &str| {
+            vmstat
+                .lines()
+                .filter_map(|line| line.split_once(' '))
+                .find(|(key, _)| *key == name)
+                .and_then(|(_, value)| value.trim().parse::<u64>().ok())
+                .unwrap_or(0)
+        };
+
+        Some(MemoryActivity {
+            minor_faults: field(10)?,
+            major_faults: field(12)?,
+            compressions: counter("zswpout"),
+            decompressions: counter("zswpin"),
+            swapins: counter("pswpin"),
+            swapouts: counter("pswpout"),
+        })
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_and_limit() {
+        // 150 bp: 120 + 300 bytes per record, plus 37.5 for the 2-bit copy
+        let ascii = MemoryEstimate::input(1_000, 150.0, Encoding::Ascii);
+        assert_eq!(ascii.input_bytes, 420_000);
+        let two_bit = MemoryEstimate::input(1_000, 150.0, Encoding::TwoBit);
+        assert_eq!(two_bit.input_bytes, 457_500);
+
+        let estimate = ascii.with_working(80_000.0).with_output(500.0);
+        assert_eq!(estimate.total(), 500_500);
+
+        let limit = MemoryLimit::new(600_000);
+        assert!(limit.check(&estimate, 0).is_ok());
+        let error = limit.check(&estimate, 100_000).unwrap_err();
+        assert_eq!(error.estimated_bytes, 600_500);
+    }
+
+    #[test]
+    fn test_memory_activity_counts_faults() {
+        let Some(before) = MemoryActivity::sample() else {
+            return; // Platform without counters
+        };
+        // Touch fresh pages: each first write is a (minor) fault
+        let pages = vec![1u8; 16 << 20];
+        let after = MemoryActivity::sample().unwrap();
+        assert!(pages.iter().step_by(4096).all(|&b| b == 1));
+
+        let activity = after.since(&before);
+        assert!(activity.minor_faults + activity.major_faults > 0);
+        assert_eq!(activity.add(&activity).minor_faults, 2 * activity.minor_faults);
+        assert_eq!(before.since(&after).minor_faults, 0);
+    }
+}
@@ -12,7 +12,7 @@
 //! operation's category, complexity, and available backends. This enables the
 //! automated harness to dynamically select and execute operations.
 
-use crate::{HardwareConfig, OperationCategory, PrimitiveOperation};
+use crate::{HardwareConfig, OperationCategory, ParallelStrategy, PrimitiveOperation};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -49,6 +49,13 @@ pub struct OperationMetadata {
 
     /// Human-readable description
     pub description: Option<String>,
+
+    /// Analytic cost per base, estimated by the operation's author
+    ///
+    /// `None` for operations not yet annotated (and for metadata saved
+    /// before the field existed).
+    #[serde(default)]
+    pub cost: Option<CostModel>,
 }
 
 impl OperationMetadata {
@@ -72,6 +79,140 @@ impl OperationMetadata {
     }
 }
 
+// ============================================================================
+// Cost Model
+// ============================================================================
+
+/// Analytic cost of an operation: bytes touched and work done per base
+///
+/// Complements the measured throughput with features that do not depend on
+/// the machine: bytes per sequence turn throughput into bandwidth (the
+/// roofline's x-axis is [`CostModel::arithmetic_intensity`]), and a fitted
+/// prediction model can use them to rank configs for an operation that has
+/// never been measured. Values are the operation author's estimates for the
+/// naive implementation at the registered parameters, not measurements.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostModel {
+    /// Input bytes read per base (1 for bases, 2 with quality scores; 0 if
+    /// only record lengths are touched)
+    pub bytes_read_per_base: f64,
+
+    /// Comparisons or arithmetic operations per unit of work (see [`WorkScaling`])
+    pub comparisons_per_base: f64,
+
+    /// How the amount of work grows with the input
+    pub scaling: WorkScaling,
+
+    /// How the output size grows with the input
+    pub output: OutputSize,
+}
+
+/// How the amount of work grows with the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkScaling {
+    /// One unit per input base
+    Linear,
+    /// One unit per base of every pair of reads (Hamming-style), over at
+    /// most `max_sequences` reads
+    AllPairs { max_sequences: Option<usize> },
+    /// One unit per dynamic-programming cell (`length²`) of every pair of
+    /// reads, over at most `max_sequences` reads
+    AllPairsQuadratic { max_sequences: Option<usize> },
+}
+
+/// How the output size grows with the input (bytes)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputSize {
+    /// Independent of the input (counts, histograms)
+    Fixed(f64),
+    /// Per input read (per-read statistics)
+    PerRecord(f64),
+    /// Per input base (transformed or filtered records, upper bound)
+    PerBase(f64),
+    /// Per pair of reads compared (distance matrices)
+    PerPair(f64),
+}
+
+impl CostModel {
+    /// Linear-work cost model
+    pub fn new(bytes_read_per_base: f64, comparisons_per_base: f64, output: OutputSize) -> Self {
+        Self {
+            bytes_read_per_base,
+            comparisons_per_base,
+            scaling: WorkScaling::Linear,
+            output,
+        }
+    }
+
+    /// Same model with a different work scaling
+    pub fn with_scaling(mut self, scaling: WorkScaling) -> Self {
+        self.scaling = scaling;
+        self
+    }
+
+    /// Pairs of reads compared among `num_sequences` (0 for linear work)
+    pub fn pairs(&self, num_sequences: usize) -> f64 {
+        let n = match self.scaling {
+            WorkScaling::Linear => return 0.0,
+            WorkScaling::AllPairs { max_sequences }
+            | WorkScaling::AllPairsQuadratic { max_sequences } => {
+                num_sequences.min(max_sequences.unwrap_or(usize::MAX))
+            }
+        } as f64;
+        n * (n - 1.0).max(0.0) / 2.0
+    }
+
+    /// Input bytes read for `num_sequences` reads of `mean_length` bases
+    pub fn bytes_read(&self, num_sequences: usize, mean_length: f64) -> f64 {
+        self.bytes_read_per_base * num_sequences as f64 * mean_length
+    }
+
+    /// Output bytes written for `num_sequences` reads of `mean_length` bases
+    pub fn bytes_written(&self, num_sequences: usize, mean_length: f64) -> f64 {
+        match self.output {
+            OutputSize::Fixed(bytes) => bytes,
+            OutputSize::PerRecord(bytes) => bytes * num_sequences as f64,
+            OutputSize::PerBase(bytes) => bytes * num_sequences as f64 * mean_length,
+            OutputSize::PerPair(bytes) => bytes * self.pairs(num_sequences),
+        }
+    }
+
+    /// Comparisons for `num_sequences` reads of `mean_length` bases
+    pub fn comparisons(&self, num_sequences: usize, mean_length: f64) -> f64 {
+        let units = match self.scaling {
+            WorkScaling::Linear => num_sequences as f64 * mean_length,
+            WorkScaling::AllPairs { .. } => self.pairs(num_sequences) * mean_length,
+            WorkScaling::AllPairsQuadratic { .. } => {
+                self.pairs(num_sequences) * mean_length * mean_length
+            }
+        };
+        self.comparisons_per_base * units
+    }
+
+    /// Bytes touched (read + written) per sequence, amortized over a batch
+    pub fn bytes_per_sequence(&self, num_sequences: usize, mean_length: f64) -> f64 {
+        if num_sequences == 0 {
+            return 0.0;
+        }
+        let bytes = self.bytes_read(num_sequences, mean_length)
+            + self.bytes_written(num_sequences, mean_length);
+        bytes / num_sequences as f64
+    }
+
+    /// Comparisons per byte touched (roofline x-axis; 0 if nothing is touched)
+    pub fn arithmetic_intensity(&self, num_sequences: usize, mean_length: f64) -> f64 {
+        let bytes = self.bytes_read(num_sequences, mean_length)
+            + self.bytes_written(num_sequences, mean_length);
+        if bytes > 0.0 {
+            self.comparisons(num_sequences, mean_length) / bytes
+        } else {
+            0.0
+        }
+    }
+}
+
 /// Available execution backends
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Backend {
@@ -81,6 +222,10 @@ pub enum Backend {
     Neon,
     /// Multi-threaded parallelism
     Parallel,
+    /// Multi-threaded over contiguous chunks (NEON within each chunk)
+    ParallelChunked,
+    /// Multi-threaded over segments of individual reads (long reads)
+    ParallelIntraRead,
     /// Metal GPU compute
     Gpu,
     /// Neural Engine (ML-based)
@@ -100,6 +245,10 @@ impl Backend {
             Backend::Neural
         } else if config.use_amx {
             Backend::Amx
+        } else if config.num_threads > 1 && config.parallel_strategy == ParallelStrategy::Chunked {
+            Backend::ParallelChunked
+        } else if config.num_threads > 1 && config.parallel_strategy == ParallelStrategy::IntraRead {
+            Backend::ParallelIntraRead
         } else if config.use_neon {
             Backend::Neon
         } else if config.num_threads > 1 {
@@ -150,6 +299,7 @@ impl OperationRegistry {
     ///         backends: vec![Backend::Naive, Backend::Neon],
     ///         implemented: true,
     ///         description: Some("Count A, C, G, T bases".to_string()),
+    ///         cost: Some(CostModel::new(1.0, 4.0, OutputSize::Fixed(40.0))),
     ///     },
     /// );
     /// ```
@@ -178,6 +328,11 @@ impl OperationRegistry {
             .context(format!("Metadata for operation '{}' not found", name))
     }
 
+    /// Get the parameters a registered operation was built with
+    pub fn get_parameters(&self, name: &str) -> Result<serde_json::Value> {
+        Ok(self.get(name)?.parameters())
+    }
+
     /// List all registered operation names
     pub fn list_operations(&self) -> Vec<String> {
         let mut names: Vec<_> = self.operations.keys().cloned().collect();
@@ -358,6 +513,7 @@ mod tests {
             backends: vec![Backend::Naive, Backend::Neon],
             implemented: true,
             description: None,
+            cost: None,
         };
 
         assert!(simple.neon_effective());
@@ -370,12 +526,38 @@ mod tests {
             backends: vec![Backend::Naive, Backend::Gpu],
             implemented: true,
             description: None,
+            cost: None,
         };
 
         assert!(!complex.neon_effective());
         assert!(complex.gpu_candidate());
     }
 
+    #[test]
+    fn test_cost_model() {
+        // 150 bp FASTQ filter: reads bases + qualities, copies kept records
+        let filter = CostModel::new(2.0, 1.0, OutputSize::PerBase(2.0));
+        assert_eq!(filter.bytes_per_sequence(1000, 150.0), 600.0);
+        assert!((filter.arithmetic_intensity(1000, 150.0) - 0.25).abs() < 1e-12);
+
+        let counting = CostModel::new(1.0, 4.0, OutputSize::Fixed(40.0));
+        assert_eq!(counting.bytes_written(1000, 150.0), 40.0);
+        assert_eq!(counting.pairs(1000), 0.0);
+
+        // All pairs over at most 10 reads: 45 pairs of 100 bases
+        let distance = CostModel::new(1.0, 1.0, OutputSize::PerPair(8.0))
+            .with_scaling(WorkScaling::AllPairs { max_sequences: Some(10) });
+        assert_eq!(distance.pairs(1000), 45.0);
+        assert_eq!(distance.comparisons(1000, 100.0), 4500.0);
+        assert_eq!(distance.bytes_written(1000, 100.0), 360.0);
+
+        let lengths_only = CostModel::new(0.0, 0.0, OutputSize::Fixed(0.0));
+        assert_eq!(lengths_only.arithmetic_intensity(10, 150.0), 0.0);
+
+        let json = serde_json::to_string(&distance).unwrap();
+        assert_eq!(serde_json::from_str::<CostModel>(&json).unwrap(), distance);
+    }
+
     #[test]
     fn test_backend_from_config() {
         let naive_config = HardwareConfig::naive();
@@ -406,6 +588,7 @@ mod tests {
             backends: vec![Backend::Naive, Backend::Neon],
             implemented: true,
             description: Some("Test operation".to_string()),
+            cost: None,
         };
 
         registry.register(op, metadata);
@@ -435,6 +618,7 @@ mod tests {
                 backends: vec![Backend::Naive],
                 implemented: true,
                 description: None,
+                cost: None,
             };
             registry.register(op, metadata);
         }
@@ -459,6 +643,7 @@ mod tests {
             backends: vec![Backend::Naive, Backend::Neon],
             implemented: true,
             description: None,
+            cost: None,
         };
 
         registry.register(op, metadata);
@@ -488,6 +673,7 @@ mod tests {
             backends: vec![Backend::Naive],
             implemented: true,
             description: None,
+            cost: None,
         };
 
         let op2 = Arc::new(MockOperation {
@@ -500,6 +686,7 @@ mod tests {
             backends: vec![Backend::Naive, Backend::Neon],
             implemented: true,
             description: None,
+            cost: None,
         };
 
         let registry = RegistryBuilder::new()
@@ -509,6 +696,8 @@ mod tests {
 
         assert_eq!(registry.list_operations().len(), 2);
         assert_eq!(registry.list_implemented().len(), 2);
+        assert!(registry.get_parameters("op1").unwrap().is_null());
+        assert!(registry.get_parameters("missing").is_err());
     }
 
     #[test]
@@ -526,6 +715,7 @@ mod tests {
             backends: vec![Backend::Naive, Backend::Neon, Backend::Gpu],
             implemented: true,
             description: None,
+            cost: None,
         };
 
         registry.register(op, metadata);
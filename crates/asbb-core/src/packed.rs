@@ -0,0 +1,323 @@
+//! Packed (arena) record storage
+//!
+//! `Vec<SequenceRecord>` allocates an id, a sequence and a quality buffer per
+//! record, so a 10M-read dataset costs 30M small allocations before any
+//! operation runs. `PackedRecords` stores all sequences in one contiguous
+//! buffer, all qualities in a parallel buffer and all ids in one string, with
+//! an offset table per buffer: a handful of allocations regardless of scale,
+//! and sequential memory for streaming kernels.
+//!
+//! Record `i` spans `offsets[i]..offsets[i + 1]` in both the sequence and the
+//! quality buffer (qualities are present for every record or for none).
+
+use crate::{SequenceRecord, SequenceView};
+use anyhow::Result;
+use std::mem::size_of;
+
+/// Records packed into contiguous id, sequence and quality buffers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedRecords {
+    ids: String,
+    id_offsets: Vec<usize>,
+    sequences: Vec<u8>,
+    qualities: Option<Vec<u8>>,
+    offsets: Vec<usize>,
+}
+
+impl Default for PackedRecords {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PackedRecords {
+    /// Empty storage
+    pub fn new() -> Self {
+        Self::with_capacity(0, 0)
+    }
+
+    /// Empty storage sized for `num_records` records totalling `num_bases` bases
+    pub fn with_capacity(num_records: usize, num_bases: usize) -> Self {
+        let mut id_offsets = Vec::with_capacity(num_records + 1);
+        id_offsets.push(0);
+        let mut offsets = Vec::with_capacity(num_records + 1);
+        offsets.push(0);
+
+        Self {
+            ids: String::new(),
+            id_offsets,
+            sequences: Vec::with_capacity(num_bases),
+            qualities: None,
+            offsets,
+        }
+    }
+
+    /// Pack owned records
+    pub fn from_records(records: &[SequenceRecord]) -> Result<Self> {
+        let num_bases = records.iter().map(|r| r.sequence.len()).sum();
+        let mut packed = Self::with_capacity(records.len(), num_bases);
+        for record in records {
+            packed.push(&record.id, &record.sequence, record.quality.as_deref())?;
+        }
+        Ok(packed)
+    }
+
+    /// Unpack into owned records
+    pub fn to_records(&self) -> Vec<SequenceRecord> {
+        (0..self.len())
+            .map(|i| SequenceRecord {
+                id: self.id(i).to_string(),
+                sequence: self.sequence(i).to_vec(),
+                quality: self.quality(i).map(<[u8]>::to_vec),
+            })
+            .collect()
+    }
+
+    /// Append a record
+    ///
+    /// Fails if the record's quality presence differs from earlier records,
+    /// or if quality and sequence lengths differ.
+    pub fn push(&mut self, id: &str, sequence: &[u8], quality: Option<&[u8]>) -> Result<()> {
+        if let Some(quality) = quality {
+            if quality.len() != sequence.len() {
+                anyhow::bail!(
+                    "Record {}: quality length {} does not match sequence length {}",
+                    id,
+                    quality.len(),
+                    sequence.len()
+                );
+            }
+        }
+
+        let first = self.is_empty();
+        match (&mut self.qualities, quality) {
+            (Some(qualities), Some(quality)) => qualities.extend_from_slice(quality),
+            (None, Some(quality)) if first => {
+                let mut qualities = Vec::with_capacity(self.sequences.capacity());
+                qualities.extend_from_slice(quality);
+                self.qualities = Some(qualities);
+            }
+            (None, None) => {}
+            _ => anyhow::bail!(
+                "Record {}: cannot mix records with and without quality in packed storage",
+                id
+            ),
+        }
+
+        self.ids.push_str(id);
+        self.id_offsets.push(self.ids.len());
+        self.sequences.extend_from_slice(sequence);
+        self.offsets.push(self.sequences.len());
+        Ok(())
+    }
+
+    /// Release spare buffer capacity left by incremental loading
+    pub fn shrink_to_fit(&mut self) {
+        self.ids.shrink_to_fit();
+        self.id_offsets.shrink_to_fit();
+        self.sequences.shrink_to_fit();
+        if let Some(qualities) = &mut self.qualities {
+            qualities.shrink_to_fit();
+        }
+        self.offsets.shrink_to_fit();
+    }
+
+    /// Number of records
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Total number of bases across all records
+    pub fn total_bases(&self) -> usize {
+        self.sequences.len()
+    }
+
+    /// Whether records carry quality scores
+    pub fn has_quality(&self) -> bool {
+        self.qualities.is_some()
+    }
+
+    /// Id of record `i`
+    pub fn id(&self, i: usize) -> &str {
+        &self.ids[self.id_offsets[i]..self.id_offsets[i + 1]]
+    }
+
+    /// Sequence of record `i`
+    pub fn sequence(&self, i: usize) -> &[u8] {
+        &self.sequences[self.offsets[i]..self.offsets[i + 1]]
+    }
+
+    /// Quality scores of record `i` (FASTQ only)
+    pub fn quality(&self, i: usize) -> Option<&[u8]> {
+        self.qualities
+            .as_ref()
+            .map(|q| &q[self.offsets[i]..self.offsets[i + 1]])
+    }
+
+    /// Borrow record `i` as a view
+    pub fn view(&self, i: usize) -> SequenceView<'_> {
+        SequenceView {
+            id: self.id(i),
+            sequence: self.sequence(i),
+            quality: self.quality(i),
+        }
+    }
+
+    /// Iterate over all records as views
+    pub fn views(&self) -> impl ExactSizeIterator<Item = SequenceView<'_>> + '_ {
+        (0..self.len()).map(|i| self.view(i))
+    }
+
+    /// Iterate over record sequences
+    pub fn sequences(&self) -> impl ExactSizeIterator<Item = &[u8]> + '_ {
+        self.offsets
+            .windows(2)
+            .map(|w| &self.sequences[w[0]..w[1]])
+    }
+
+    /// Iterate over mutable record sequences and qualities
+    ///
+    /// Record lengths are fixed, so this suits length-preserving in-place
+    /// transforms (reverse complement, masking, quality rescaling).
+    pub fn records_mut(&mut self) -> impl Iterator<Item = (&mut [u8], Option<&mut [u8]>)> + '_ {
+        let mut sequences = self.sequences.as_mut_slice();
+        let mut qualities = self.qualities.as_deref_mut();
+
+        self.offsets.windows(2).map(move |w| {
+            let length = w[1] - w[0];
+            let (sequence, rest) = std::mem::take(&mut sequences).split_at_mut(length);
+            sequences = rest;
+            let quality = qualities.take().map(|q| {
+                let (quality, rest) = q.split_at_mut(length);
+                qualities = Some(rest);
+                quality
+            });
+            (sequence, quality)
+        })
+    }
+
+    /// All sequences back to back (record boundaries in [`Self::offsets`])
+    pub fn sequence_buffer(&self) -> &[u8] {
+        &self.sequences
+    }
+
+    /// Record boundaries in the sequence (and quality) buffer, `len() + 1` entries
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    /// Heap bytes held by the buffers (capacity, not length)
+    pub fn heap_bytes(&self) -> usize {
+        self.ids.capacity()
+            + (self.id_offsets.capacity() + self.offsets.capacity()) * size_of::<usize>()
+            + self.sequences.capacity()
+            + self.qualities.as_ref().map_or(0, Vec::capacity)
+    }
+
+    /// Number of heap allocations backing the storage
+    pub fn allocation_count(&self) -> usize {
+        4 + usize::from(self.qualities.is_some())
+    }
+}
+
+/// Heap bytes held by owned records (for comparison with [`PackedRecords::heap_bytes`])
+///
+/// Counts buffer capacities only; per-allocation allocator overhead (typically
+/// 16 bytes per allocation) comes on top, see [`records_allocation_count`].
+pub fn records_heap_bytes(records: &[SequenceRecord]) -> usize {
+    std::mem::size_of_val(records)
+        + records
+            .iter()
+            .map(|r| {
+                r.id.capacity()
+                    + r.sequence.capacity()
+                    + r.quality.as_ref().map_or(0, Vec::capacity)
+            })
+            .sum::<usize>()
+}
+
+/// Number of heap allocations backing owned records (vector plus per-record buffers)
+pub fn records_allocation_count(records: &[SequenceRecord]) -> usize {
+    1 + records
+        .iter()
+        .map(|r| {
+            usize::from(r.id.capacity() > 0)
+                + usize::from(r.sequence.capacity() > 0)
+                + r.quality.as_ref().map_or(0, |q| usize::from(q.capacity() > 0))
+        })
+        .sum::<usize>()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<SequenceRecord> {
+        vec![
+            SequenceRecord::fastq("r0".to_string(), b"ACGT".to_vec(), b"IIII".to_vec()),
+            SequenceRecord::fastq("read_1".to_string(), b"".to_vec(), b"".to_vec()),
+            SequenceRecord::fastq("r2".to_string(), b"GGNAT".to_vec(), b"#####".to_vec()),
+        ]
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let packed = PackedRecords::from_records(&records()).unwrap();
+
+        assert_eq!(packed.len(), 3);
+        assert_eq!(packed.total_bases(), 9);
+        assert_eq!(packed.id(1), "read_1");
+        assert_eq!(packed.sequence(2), b"GGNAT");
+        assert_eq!(packed.quality(0), Some(&b"IIII"[..]));
+        assert_eq!(packed.sequences().collect::<Vec<_>>(), vec![&b"ACGT"[..], b"", b"GGNAT"]);
+        assert_eq!(packed.to_records(), records());
+
+        let owned = records();
+        let views: Vec<SequenceView> = owned.iter().map(SequenceRecord::view).collect();
+        assert_eq!(packed.views().collect::<Vec<_>>(), views);
+    }
+
+    #[test]
+    fn test_records_mut() {
+        let mut packed = PackedRecords::from_records(&records()).unwrap();
+        for (sequence, quality) in packed.records_mut() {
+            sequence.reverse();
+            quality.unwrap().fill(b'!');
+        }
+
+        assert_eq!(packed.sequence(0), b"TGCA");
+        assert_eq!(packed.sequence(2), b"TANGG");
+        assert_eq!(packed.quality(2), Some(&b"!!!!!"[..]));
+        assert_eq!(packed.id(2), "r2");
+    }
+
+    #[test]
+    fn test_rejects_mixed_quality() {
+        let mut packed = PackedRecords::new();
+        packed.push("a", b"ACGT", None).unwrap();
+        assert!(packed.push("b", b"ACGT", Some(b"IIII")).is_err());
+
+        let mut packed = PackedRecords::new();
+        assert!(packed.push("a", b"ACGT", Some(b"III")).is_err());
+    }
+
+    #[test]
+    fn test_footprint_vs_records() {
+        let owned: Vec<SequenceRecord> = (0..1000)
+            .map(|i| SequenceRecord::fastq(format!("seq_{}", i), vec![b'A'; 150], vec![b'I'; 150]))
+            .collect();
+        let packed = PackedRecords::from_records(&owned).unwrap();
+
+        assert_eq!(records_allocation_count(&owned), 3001);
+        assert_eq!(packed.allocation_count(), 5);
+        assert!(packed.heap_bytes() < records_heap_bytes(&owned));
+    }
+}
@@ -0,0 +1,170 @@
+//! Operations from external crates, registered at link time
+//!
+//! Tool authors can evaluate their own kernels in the ASBB framework
+//! without forking it: implement [`PrimitiveOperation`] for the kernel
+//! (only `name`, `category` and `execute_naive` are required; every other
+//! backend falls back to naive or reports itself unsupported), then submit
+//! it with [`register_operation!`](crate::register_operation):
+//!
+//! ```ignore
+//! use asbb_core::operation_registry::{Backend, OperationMetadata};
+//! use asbb_core::{OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+//!
+//! pub struct MyKmerCounter;
+//!
+//! impl PrimitiveOperation for MyKmerCounter {
+//!     fn name(&self) -> &str {
+//!         "my_kmer_counter"
+//!     }
+//!     fn category(&self) -> OperationCategory {
+//!         OperationCategory::Search
+//!     }
+//!     fn execute_naive(&self, data: &[SequenceRecord]) -> anyhow::Result<OperationOutput> {
+//!         // ...
+//!     }
+//! }
+//!
+//! asbb_core::register_operation!(
+//!     "my_kmer_counter",
+//!     || Box::new(MyKmerCounter),
+//!     || OperationMetadata {
+//!         name: "my_kmer_counter".to_string(),
+//!         category: OperationCategory::Search,
+//!         complexity: 0.5,
+//!         backends: vec![Backend::Naive],
+//!         implemented: true,
+//!         description: Some("k-mer counting from my_tool".to_string()),
+//!         cost: None,
+//!     }
+//! );
+//! ```
+//!
+//! Registration needs the `plugins` feature (built on `inventory`); the
+//! crate must also be linked into the binary that runs the benchmarks,
+//! e.g. added to `asbb-cli`'s dependencies and referenced with
+//! `use my_tool as _;`. `asbb operations` lists what was registered, and
+//! every `--operations` flag of `asbb` and `asbb-dag-traversal` accepts the
+//! registered names. Built-in operations take precedence over plugins of
+//! the same name.
+
+use crate::operation_registry::{OperationMetadata, OperationRegistry};
+use crate::PrimitiveOperation;
+use std::sync::Arc;
+
+#[cfg(feature = "plugins")]
+#[doc(hidden)]
+pub use inventory;
+
+/// An operation submitted by an external crate
+pub struct OperationPlugin {
+    /// Name accepted by `--operations`
+    pub name: &'static str,
+
+    /// Create an instance (called once per benchmark)
+    pub create: fn() -> Box<dyn PrimitiveOperation>,
+
+    /// Metadata for the registry (category, complexity, backends)
+    pub metadata: fn() -> OperationMetadata,
+}
+
+#[cfg(feature = "plugins")]
+inventory::collect!(OperationPlugin);
+
+/// Register an operation from an external crate (requires the `plugins` feature)
+///
+/// Arguments: the operation name, a `fn() -> Box<dyn PrimitiveOperation>`
+/// and a `fn() -> OperationMetadata` (closures without captures coerce).
+#[cfg(feature = "plugins")]
+#[macro_export]
+macro_rules! register_operation {
+    ($name:expr, $create:expr, $metadata:expr) => {
+        $crate::plugin::inventory::submit! {
+            $crate::plugin::OperationPlugin {
+                name: $name,
+                create: $create,
+                metadata: $metadata,
+            }
+        }
+    };
+}
+
+/// Every registered plugin, sorted by name (none without the `plugins` feature)
+pub fn plugins() -> Vec<&'static OperationPlugin> {
+    #[cfg(feature = "plugins")]
+    let mut plugins: Vec<&'static OperationPlugin> =
+        inventory::iter::<OperationPlugin>.into_iter().collect();
+    #[cfg(not(feature = "plugins"))]
+    let mut plugins: Vec<&'static OperationPlugin> = Vec::new();
+
+    plugins.sort_by_key(|plugin| plugin.name);
+    plugins
+}
+
+/// Create the plugin operation called `name`, if one is registered
+pub fn create(name: &str) -> Option<Box<dyn PrimitiveOperation>> {
+    plugins()
+        .into_iter()
+        .find(|plugin| plugin.name == name)
+        .map(|plugin| (plugin.create)())
+}
+
+/// Add every plugin to a registry (names already present are kept)
+pub fn register_all(registry: &mut OperationRegistry) {
+    let registered = registry.list_operations();
+    for plugin in plugins() {
+        if !registered.iter().any(|name| name == plugin.name) {
+            registry.register(Arc::from((plugin.create)()), (plugin.metadata)());
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(all(test, feature = "plugins"))]
+mod tests {
+    use super::*;
+    use crate::operation_registry::Backend;
+    use crate::{OperationCategory, OperationOutput, SequenceRecord};
+
+    struct ReadCount;
+
+    impl PrimitiveOperation for ReadCount {
+        fn name(&self) -> &str {
+            "plugin_read_count"
+        }
+
+        fn category(&self) -> OperationCategory {
+            OperationCategory::Aggregation
+        }
+
+        fn execute_naive(&self, data: &[SequenceRecord]) -> anyhow::Result<OperationOutput> {
+            Ok(OperationOutput::Count(data.len()))
+        }
+    }
+
+    crate::register_operation!("plugin_read_count", || Box::new(ReadCount), || {
+        OperationMetadata {
+            name: "plugin_read_count".to_string(),
+            category: OperationCategory::Aggregation,
+            complexity: 0.1,
+            backends: vec![Backend::Naive],
+            implemented: true,
+            description: None,
+            cost: None,
+        }
+    });
+
+    #[test]
+    fn test_registered_plugin_is_found() {
+        assert!(plugins().iter().any(|plugin| plugin.name == "plugin_read_count"));
+        let operation = create("plugin_read_count").unwrap();
+        assert_eq!(operation.category(), OperationCategory::Aggregation);
+        assert!(create("not_registered").is_none());
+
+        let mut registry = OperationRegistry::new();
+        register_all(&mut registry);
+        assert_eq!(registry.get_metadata("plugin_read_count").unwrap().complexity, 0.1);
+    }
+}
@@ -0,0 +1,286 @@
+//! Quality score binning (reduced-precision qualities)
+//!
+//! Illumina instruments can emit binned quality scores: every Phred score is
+//! replaced by the representative score of its bin, so a read's qualities
+//! take only a handful of distinct values. This shrinks compressed FASTQ
+//! considerably and, for benchmarking, lets aggregations work on a tiny
+//! alphabet (e.g. per-position histograms instead of sorts). The schemes:
+//!
+//! | Scheme | Bins (Phred → representative) |
+//! |---|---|
+//! | [`QualityBinning::Illumina8`] | 0-2 → 2, 3-9 → 6, 10-19 → 15, 20-24 → 22, 25-29 → 27, 30-34 → 33, 35-39 → 37, 40+ → 40 |
+//! | [`QualityBinning::Illumina4`] | 0-2 → 2, 3-14 → 12, 15-30 → 23, 31+ → 37 (NovaSeq) |
+//!
+//! [`BinningTable`] converts Phred+33 quality strings to binned Phred+33
+//! (in place, NEON table lookup on aarch64) or to bin codes (`0..num_bins`,
+//! the compact form a packed store would use). Binning is idempotent: every
+//! representative lies in its own bin (no-calls, Q0-2, are written as Q2).
+
+use crate::SequenceRecord;
+use serde::{Deserialize, Serialize};
+
+/// ASCII offset of the quality strings binning operates on
+pub const PHRED_OFFSET: u8 = 33;
+
+/// Scores above this share the table entry of Q63 (top bin in every scheme)
+const MAX_TABLE_PHRED: u8 = 63;
+
+/// Quality binning scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityBinning {
+    /// Illumina 8-level binning (HiSeq X / HiSeq 4000 era)
+    Illumina8,
+    /// Illumina 4-level binning (NovaSeq RTA3)
+    Illumina4,
+}
+
+impl QualityBinning {
+    pub const ALL: [QualityBinning; 2] = [QualityBinning::Illumina8, QualityBinning::Illumina4];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            QualityBinning::Illumina8 => "illumina8",
+            QualityBinning::Illumina4 => "illumina4",
+        }
+    }
+
+    pub fn from_name(name: &str) -> anyhow::Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|binning| binning.name() == name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown quality binning: {} (available: illumina8, illumina4)", name))
+    }
+
+    /// `(lowest Phred, representative Phred)` of each bin, ascending
+    fn bins(&self) -> &'static [(u8, u8)] {
+        match self {
+            QualityBinning::Illumina8 => &[
+                (0, 2),
+                (3, 6),
+                (10, 15),
+                (20, 22),
+                (25, 27),
+                (30, 33),
+                (35, 37),
+                (40, 40),
+            ],
+            QualityBinning::Illumina4 => &[(0, 2), (3, 12), (15, 23), (31, 37)],
+        }
+    }
+
+    pub fn num_bins(&self) -> usize {
+        self.bins().len()
+    }
+
+    /// Bits needed to store one bin code
+    pub fn bits_per_score(&self) -> u32 {
+        self.num_bins().next_power_of_two().trailing_zeros()
+    }
+
+    /// Representative Phred score of each bin, indexed by code
+    pub fn levels(&self) -> Vec<u8> {
+        self.bins().iter().map(|&(_, level)| level).collect()
+    }
+
+    /// Bin code of a Phred score
+    pub fn code(&self, phred: u8) -> u8 {
+        self.bins().iter().rposition(|&(low, _)| phred >= low).unwrap_or(0) as u8
+    }
+
+    /// Representative Phred score of the bin containing `phred`
+    pub fn bin_phred(&self, phred: u8) -> u8 {
+        self.bins()[self.code(phred) as usize].1
+    }
+}
+
+/// Byte tables for binning Phred+33 quality strings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinningTable {
+    pub binning: QualityBinning,
+    /// Quality byte → binned quality byte
+    binned: [u8; 256],
+    /// Quality byte → bin code
+    codes: [u8; 256],
+}
+
+impl BinningTable {
+    pub fn new(binning: QualityBinning) -> Self {
+        let mut binned = [0u8; 256];
+        let mut codes = [0u8; 256];
+        for byte in 0..256 {
+            let phred = (byte as u8).saturating_sub(PHRED_OFFSET).min(MAX_TABLE_PHRED);
+            codes[byte] = binning.code(phred);
+            binned[byte] = binning.bin_phred(phred) + PHRED_OFFSET;
+        }
+        Self { binning, binned, codes }
+    }
+
+    /// Binned value of one quality byte
+    pub fn bin_byte(&self, byte: u8) -> u8 {
+        self.binned[byte as usize]
+    }
+
+    /// Bin code of one quality byte
+    pub fn code(&self, byte: u8) -> u8 {
+        self.codes[byte as usize]
+    }
+
+    /// Quality byte (Phred+33) of each bin code
+    pub fn levels(&self) -> Vec<u8> {
+        self.binning.levels().iter().map(|level| level + PHRED_OFFSET).collect()
+    }
+
+    /// Bin qualities in place
+    pub fn apply(&self, quality: &mut [u8], simd: bool) {
+        #[cfg(target_arch = "aarch64")]
+        if simd {
+            let vector_len = quality.len() / 16 * 16;
+            let (head, tail) = quality.split_at_mut(vector_len);
+            lookup_neon(&self.binned, head);
+            self.apply(tail, false);
+            return;
+        }
+        let _ = simd;
+
+        for byte in quality {
+            *byte = self.binned[*byte as usize];
+        }
+    }
+
+    /// Binned copy of `quality`
+    pub fn binned(&self, quality: &[u8], simd: bool) -> Vec<u8> {
+        let mut binned = quality.to_vec();
+        self.apply(&mut binned, simd);
+        binned
+    }
+
+    /// Bin codes of `quality` (one byte per score)
+    pub fn encode(&self, quality: &[u8], simd: bool) -> Vec<u8> {
+        let mut codes = quality.to_vec();
+        self.encode_in_place(&mut codes, simd);
+        codes
+    }
+
+    /// Replace qualities by their bin codes
+    pub fn encode_in_place(&self, quality: &mut [u8], simd: bool) {
+        #[cfg(target_arch = "aarch64")]
+        if simd {
+            let vector_len = quality.len() / 16 * 16;
+            let (head, tail) = quality.split_at_mut(vector_len);
+            lookup_neon(&self.codes, head);
+            self.encode_in_place(tail, false);
+            return;
+        }
+        let _ = simd;
+
+        for byte in quality {
+            *byte = self.codes[*byte as usize];
+        }
+    }
+
+    /// Quality bytes (binned Phred+33) for bin codes
+    pub fn decode(&self, codes: &[u8]) -> Vec<u8> {
+        let levels = self.levels();
+        codes.iter().map(|&code| levels[code as usize]).collect()
+    }
+}
+
+/// Map 16-byte blocks through `table` (entries 33-96 cover Q0-Q63)
+#[cfg(target_arch = "aarch64")]
+fn lookup_neon(table: &[u8; 256], bytes: &mut [u8]) {
+    use std::arch::aarch64::*;
+
+    unsafe {
+        let window = vld1q_u8_x4(table.as_ptr().add(PHRED_OFFSET as usize));
+        let offset = vdupq_n_u8(PHRED_OFFSET);
+        let last = vdupq_n_u8(MAX_TABLE_PHRED);
+
+        for chunk in bytes.chunks_exact_mut(16) {
+            // Bytes below '!' saturate to Q0, scores above Q63 clamp to it
+            let index = vminq_u8(vqsubq_u8(vld1q_u8(chunk.as_ptr()), offset), last);
+            vst1q_u8(chunk.as_mut_ptr(), vqtbl4q_u8(window, index));
+        }
+    }
+}
+
+/// Copies of `records` with binned qualities (records without qualities are copied as is)
+pub fn bin_records(records: &[SequenceRecord], table: &BinningTable, simd: bool) -> Vec<SequenceRecord> {
+    records
+        .iter()
+        .map(|record| {
+            let mut record = record.clone();
+            if let Some(quality) = record.quality.as_mut() {
+                table.apply(quality, simd);
+            }
+            record
+        })
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bins() {
+        let binning = QualityBinning::Illumina8;
+        assert_eq!(binning.num_bins(), 8);
+        assert_eq!(binning.bits_per_score(), 3);
+        assert_eq!(binning.bin_phred(0), 2);
+        assert_eq!(binning.bin_phred(9), 6);
+        assert_eq!(binning.bin_phred(10), 15);
+        assert_eq!(binning.bin_phred(24), 22);
+        assert_eq!(binning.bin_phred(38), 37);
+        assert_eq!(binning.bin_phred(41), 40);
+        assert_eq!(binning.code(41), 7);
+
+        let binning = QualityBinning::Illumina4;
+        assert_eq!(binning.bits_per_score(), 2);
+        assert_eq!(binning.levels(), vec![2, 12, 23, 37]);
+        assert_eq!(binning.bin_phred(14), 12);
+        assert_eq!(binning.bin_phred(30), 23);
+        assert_eq!(binning.bin_phred(31), 37);
+
+        assert_eq!(QualityBinning::from_name("illumina4").unwrap(), binning);
+        assert!(QualityBinning::from_name("illumina2").is_err());
+    }
+
+    #[test]
+    fn test_table_round_trip_and_idempotence() {
+        // Every byte, in a length that leaves a scalar tail after the 16-byte blocks
+        let quality: Vec<u8> = (0..=255u8).chain(33..50).collect();
+
+        for binning in QualityBinning::ALL {
+            let table = BinningTable::new(binning);
+            let binned = table.binned(&quality, false);
+            assert_eq!(table.binned(&quality, true), binned);
+            assert_eq!(table.binned(&binned, false), binned);
+
+            let codes = table.encode(&quality, true);
+            assert_eq!(codes, table.encode(&quality, false));
+            assert!(codes.iter().all(|&code| (code as usize) < binning.num_bins()));
+            assert_eq!(table.decode(&codes), binned);
+        }
+
+        let table = BinningTable::new(QualityBinning::Illumina8);
+        
+    }
+
+    #[test]
+    fn test_bin_records() {
+        let records = vec![
+            SequenceRecord::fastq("r0".to_string(), b"ACGT".to_vec(), b"II5+".to_vec()),
+            SequenceRecord::fasta("r1".to_string(), b"ACGT".to_vec()),
+        ];
+        let binned = bin_records(&records, &BinningTable::new(QualityBinning::Illumina4), true);
+
+        assert_eq!(binned[0].quality.as_deref(), Some(&b"FF8-"[..]));
+        assert_eq!(binned[0].sequence, records[0].sequence);
+        assert_eq!(binned[1], records[1]);
+    }
+}
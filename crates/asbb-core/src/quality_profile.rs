@@ -0,0 +1,344 @@
+//! Quality distribution classification
+//!
+//! Fits the per-position quality profile of a set of reads to the
+//! [`QualityDistType`] models used by the synthetic data generator, so real
+//! files can be described with the same vocabulary as the benchmark
+//! datasets:
+//!
+//! - **UniformHigh**: mean ≥ [`UNIFORM_HIGH_MIN_MEAN`], std < [`UNIFORM_HIGH_MAX_STD`]
+//! - **Degrading**: head-to-tail drop ≥ [`DEGRADING_MIN_DROP`] with the
+//!   position means close to a straight line (RMS residual ≤ [`DEGRADING_MAX_RMSE`])
+//! - **Realistic**: flat profile with mean ≥ [`REALISTIC_MIN_MEAN`] and at most
+//!   [`REALISTIC_MAX_DIP_RATE`] of bases dipping [`DIP_DEPTH`] or more below
+//!   their position's mean
+//! - **Unknown**: none of the above; the fitted parameters are still reported
+//!
+//! Qualities are Phred+33.
+
+use crate::{QualityDistType, QualityDistribution};
+use serde::{Deserialize, Serialize};
+
+/// Minimum mean Phred for UniformHigh
+pub const UNIFORM_HIGH_MIN_MEAN: f64 = 35.0;
+
+/// Maximum Phred standard deviation for UniformHigh
+pub const UNIFORM_HIGH_MAX_STD: f64 = 1.0;
+
+/// Minimum head-to-tail drop (Phred) for Degrading
+pub const DEGRADING_MIN_DROP: f64 = 5.0;
+
+/// Maximum RMS residual of the position means around the linear fit for Degrading
+pub const DEGRADING_MAX_RMSE: f64 = 3.0;
+
+/// Minimum mean Phred for Realistic
+pub const REALISTIC_MIN_MEAN: f64 = 30.0;
+
+/// Maximum fraction of dipped bases for Realistic
+pub const REALISTIC_MAX_DIP_RATE: f64 = 0.2;
+
+/// Phred below the position mean at which a base counts as a dip
+pub const DIP_DEPTH: f64 = 5.0;
+
+/// Read positions tracked for the per-position profile
+const MAX_PROFILE_POSITIONS: usize = 1024;
+
+/// Phred+33 offset
+const PHRED_OFFSET: u8 = 33;
+
+/// Phred scores 0..=93 (printable Phred+33 range)
+const PHRED_LEVELS: usize = 94;
+
+// ============================================================================
+// Profile Accumulation
+// ============================================================================
+
+/// Streaming per-position quality histogram
+///
+/// Keeps a Phred histogram per read position (the first
+/// `MAX_PROFILE_POSITIONS` positions), which is enough to compute both the
+/// position means and each base's distance from them in one pass.
+#[derive(Debug, Clone)]
+pub struct QualityProfileBuilder {
+    /// `histograms[position][phred]`
+    histograms: Vec<[u64; PHRED_LEVELS]>,
+    /// Bases beyond the profiled positions, pooled
+    overflow: [u64; PHRED_LEVELS],
+}
+
+impl QualityProfileBuilder {
+    pub fn new() -> Self {
+        Self {
+            histograms: Vec::new(),
+            overflow: [0; PHRED_LEVELS],
+        }
+    }
+
+    /// Add one read's Phred+33 quality string
+    pub fn add(&mut self, quality: &[u8]) {
+        let profiled = quality.len().min(MAX_PROFILE_POSITIONS);
+        if self.histograms.len() < profiled {
+            self.histograms.resize(profiled, [0; PHRED_LEVELS]);
+        }
+        for (position, &byte) in quality.iter().enumerate() {
+            let phred = (byte.saturating_sub(PHRED_OFFSET) as usize).min(PHRED_LEVELS - 1);
+            match self.histograms.get_mut(position) {
+                Some(histogram) => histogram[phred] += 1,
+                None => self.overflow[phred] += 1,
+            }
+        }
+    }
+
+    /// Fit the accumulated profile, or `None` if no qualities were added
+    pub fn classify(&self) -> Option<QualityClassification> {
+        let mut total = [0u64; PHRED_LEVELS];
+        for histogram in self.histograms.iter().chain(std::iter::once(&self.overflow)) {
+            for (sum, &count) in total.iter_mut().zip(histogram) {
+                *sum += count;
+            }
+        }
+        let (bases, mean_quality, std_quality) = histogram_stats(&total)?;
+
+        // Position means (positions with at least one base)
+        let profile: Vec<(usize, f64)> = self
+            .histograms
+            .iter()
+            .enumerate()
+            .filter_map(|(position, histogram)| {
+                histogram_stats(histogram).map(|(_, mean, _)| (position, mean))
+            })
+            .collect();
+
+        let tenth = (profile.len() / 10).max(1);
+        let window_mean =
+            |window: &[(usize, f64)]| window.iter().map(|&(_, m)| m).sum::<f64>() / window.len() as f64;
+        let head_mean = window_mean(&profile[..tenth]);
+        let tail_mean = window_mean(&profile[profile.len() - tenth..]);
+        let (slope, profile_rmse) = linear_fit(&profile);
+
+        let dipped: u64 = self
+            .histograms
+            .iter()
+            .zip(&profile)
+            .map(|(histogram, &(_, mean))| {
+                histogram
+                    .iter()
+                    .enumerate()
+                    .filter(|&(phred, _)| (phred as f64) <= mean - DIP_DEPTH)
+                    .map(|(_, &count)| count)
+                    .sum::<u64>()
+            })
+            .sum();
+        let profiled_bases = bases - self.overflow.iter().sum::<u64>();
+        let dip_rate = if profiled_bases == 0 {
+            0.0
+        } else {
+            dipped as f64 / profiled_bases as f64
+        };
+
+        let fit = QualityFit {
+            bases,
+            mean_quality,
+            std_quality,
+            positions: profile.len(),
+            head_mean,
+            tail_mean,
+            slope,
+            profile_rmse,
+            dip_rate,
+        };
+        Some(QualityClassification {
+            distribution_type: fit.classify(),
+            fit,
+        })
+    }
+}
+
+impl Default for QualityProfileBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// (count, mean, population std) of a Phred histogram
+fn histogram_stats(histogram: &[u64; PHRED_LEVELS]) -> Option<(u64, f64, f64)> {
+    let count: u64 = histogram.iter().sum();
+    if count == 0 {
+        return None;
+    }
+    let (sum, sum_sq) = histogram
+        .iter()
+        .enumerate()
+        .fold((0.0, 0.0), |(sum, sum_sq), (phred, &n)| {
+            let q = phred as f64;
+            (sum + q * n as f64, sum_sq + q * q * n as f64)
+        });
+    let mean = sum / count as f64;
+    Some((count, mean, (sum_sq / count as f64 - mean * mean).max(0.0).sqrt()))
+}
+
+/// Least-squares slope and RMS residual of (position, mean) points
+fn linear_fit(points: &[(usize, f64)]) -> (f64, f64) {
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|&(x, _)| x as f64).sum::<f64>() / n;
+    let mean_y = points.iter().map(|&(_, y)| y).sum::<f64>() / n;
+    let sxx: f64 = points.iter().map(|&(x, _)| (x as f64 - mean_x).powi(2)).sum();
+    let sxy: f64 = points
+        .iter()
+        .map(|&(x, y)| (x as f64 - mean_x) * (y - mean_y))
+        .sum();
+    let slope = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+
+    let sse: f64 = points
+        .iter()
+        .map(|&(x, y)| (y - (mean_y + slope * (x as f64 - mean_x))).powi(2))
+        .sum();
+    (slope, (sse / n).sqrt())
+}
+
+// ============================================================================
+// Classification Result
+// ============================================================================
+
+/// Parameters fitted to a quality profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityFit {
+    /// Quality values seen
+    pub bases: u64,
+    /// Mean Phred over all bases
+    pub mean_quality: f64,
+    /// Phred standard deviation over all bases
+    pub std_quality: f64,
+    /// Read positions in the profile
+    pub positions: usize,
+    /// Mean of the position means over the first tenth of positions
+    pub head_mean: f64,
+    /// Mean of the position means over the last tenth of positions
+    pub tail_mean: f64,
+    /// Least-squares slope of the position means (Phred per position)
+    pub slope: f64,
+    /// RMS residual of the position means around the linear fit
+    pub profile_rmse: f64,
+    /// Fraction of bases at least `DIP_DEPTH` below their position mean
+    pub dip_rate: f64,
+}
+
+impl QualityFit {
+    /// Head-to-tail quality drop (Phred)
+    pub fn drop(&self) -> f64 {
+        self.head_mean - self.tail_mean
+    }
+
+    fn classify(&self) -> QualityDistType {
+        if self.mean_quality >= UNIFORM_HIGH_MIN_MEAN && self.std_quality < UNIFORM_HIGH_MAX_STD {
+            QualityDistType::UniformHigh
+        } else if self.drop() >= DEGRADING_MIN_DROP && self.profile_rmse <= DEGRADING_MAX_RMSE {
+            QualityDistType::Degrading
+        } else if self.drop() < DEGRADING_MIN_DROP
+            && self.mean_quality >= REALISTIC_MIN_MEAN
+            && self.dip_rate <= REALISTIC_MAX_DIP_RATE
+        {
+            QualityDistType::Realistic
+        } else {
+            QualityDistType::Unknown
+        }
+    }
+}
+
+/// Fitted distribution type and the parameters it was chosen from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityClassification {
+    pub distribution_type: QualityDistType,
+    pub fit: QualityFit,
+}
+
+impl QualityClassification {
+    /// Summary stored in `DataCharacteristics`
+    pub fn distribution(&self) -> QualityDistribution {
+        QualityDistribution {
+            mean_quality: self.fit.mean_quality,
+            std_quality: self.fit.std_quality,
+            distribution_type: self.distribution_type,
+        }
+    }
+}
+
+/// Classify the qualities of a set of reads
+///
+/// Records without quality scores are skipped; `None` if there are none.
+pub fn classify_records(records: &[crate::SequenceRecord]) -> Option<QualityClassification> {
+    let mut builder = QualityProfileBuilder::new();
+    for quality in records.iter().filter_map(|r| r.quality.as_deref()) {
+        builder.add(quality);
+    }
+    builder.classify()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// xorshift stream of Phred scores for deterministic test profiles
+    fn rng(seed: u64) -> impl FnMut(u64) -> u64 {
+        let mut state = seed;
+        move |bound| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % bound
+        }
+    }
+
+    fn classify(reads: impl Iterator<Item = Vec<u8>>) -> QualityClassification {
+        let mut builder = QualityProfileBuilder::new();
+        for read in reads {
+            builder.add(&read);
+        }
+        builder.classify().unwrap()
+    }
+
+    #[test]
+    fn test_generator_models_round_trip() {
+        // Same shapes as asbb-datagen's quality_scores
+        let uniform = classify((0..100).map(|_| vec![b'I'; 150]));
+        assert_eq!(uniform.distribution_type, QualityDistType::UniformHigh);
+
+        let degrading = classify((0..100).map(|_| {
+            (0..150).map(|i| (40.0 - 20.0 * i as f64 / 150.0) as u8 + 33).collect()
+        }));
+        assert_eq!(degrading.distribution_type, QualityDistType::Degrading);
+        assert!(degrading.fit.drop() > 15.0);
+        assert!(degrading.fit.slope < -0.1);
+
+        let mut next = rng(0x2545_F491_4F6C_DD1D);
+        let realistic = classify((0..200).map(|_| {
+            (0..150)
+                .map(|_| {
+                    let q = if next(100) < 5 { 20 + next(10) } else { 35 + next(6) };
+                    q as u8 + 33
+                })
+                .collect()
+        }));
+        assert_eq!(realistic.distribution_type, QualityDistType::Realistic);
+        assert!((realistic.fit.dip_rate - 0.05).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_unstructured_profile_is_unknown() {
+        let mut next = rng(0x9E37_79B9_7F4A_7C15);
+        let random = classify((0..200).map(|_| (0..150).map(|_| next(41) as u8 + 33).collect()));
+        assert_eq!(random.distribution_type, QualityDistType::Unknown);
+        assert!((random.fit.mean_quality - 20.0).abs() < 1.0);
+        assert!(random.fit.dip_rate > REALISTIC_MAX_DIP_RATE);
+
+        // Step down halfway: a large drop, but not a linear one
+        let cliff = classify((0..50).map(|_| [vec![b'I'; 75], vec![b'+'; 75]].concat()));
+        assert_eq!(cliff.distribution_type, QualityDistType::Unknown);
+        assert!(cliff.fit.profile_rmse > DEGRADING_MAX_RMSE);
+
+        assert!(QualityProfileBuilder::new().classify().is_none());
+    }
+}
@@ -0,0 +1,388 @@
+//! Statistics over repeated measurements
+//!
+//! Shared by every harness that repeats a measurement, so DAG traversal and
+//! the experiment engine report identical columns computed the same way:
+//! [`calculate_statistics`] removes IQR outliers and summarizes the rest
+//! (median, mean, standard deviation, quartiles, 95% CI), and
+//! [`calculate_statistics_or_summary`] summarizes samples too small for
+//! that instead of failing.
+//!
+//! Percentiles are computed by linear interpolation between the two closest
+//! ranks (the "type 7" estimator used by R and NumPy): with `n` sorted
+//! samples, percentile `p` sits at fractional index `p / 100 * (n - 1)`.
+//! Unlike indexing `sorted[n * p / 100]`, this is defined for any run count
+//! (p99 of 5 runs interpolates between the two slowest instead of silently
+//! returning the maximum) and gives the conventional median for even `n`.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Percentiles reported when the caller does not choose its own
+pub const DEFAULT_LATENCY_PERCENTILES: &[f64] = &[50.0, 90.0, 99.0];
+
+/// Standard IQR multiplier for outlier detection
+pub const DEFAULT_OUTLIER_THRESHOLD: f64 = 1.5;
+
+/// Percentile `p` (0–100) of ascending `sorted` samples
+///
+/// Returns `None` for empty input.
+pub fn quantile(sorted: &[f64], p: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+
+    let index = (p / 100.0).clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = index.floor() as usize;
+    let upper = index.ceil() as usize;
+    let weight = index - lower as f64;
+
+    Some(sorted[lower] + weight * (sorted[upper] - sorted[lower]))
+}
+
+/// Percentile `p` (0–100) of ascending `sorted` durations
+pub fn duration_quantile(sorted: &[Duration], p: f64) -> Option<Duration> {
+    let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+    quantile(&secs, p).map(Duration::from_secs_f64)
+}
+
+/// Map key for percentile `p`: `p50`, `p99`, `p99.9`
+pub fn percentile_key(p: f64) -> String {
+    format!("p{}", p)
+}
+
+/// Check that every requested percentile lies in 0–100
+pub fn validate_percentiles(percentiles: &[f64]) -> Result<()> {
+    if let Some(p) = percentiles.iter().find(|p| !(0.0..=100.0).contains(*p)) {
+        anyhow::bail!("Percentile {} is outside 0-100", p);
+    }
+    Ok(())
+}
+
+/// Requested percentiles of ascending `sorted` durations, keyed by [`percentile_key`]
+///
+/// Empty input yields an empty map.
+pub fn latency_percentiles(sorted: &[Duration], percentiles: &[f64]) -> BTreeMap<String, Duration> {
+    percentiles
+        .iter()
+        .filter_map(|&p| duration_quantile(sorted, p).map(|d| (percentile_key(p), d)))
+        .collect()
+}
+
+// ============================================================================
+// Repeated-Measurement Summaries
+// ============================================================================
+
+/// Statistical summary of experiment measurements
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentStatistics {
+    /// Median value (robust to outliers)
+    pub median: f64,
+
+    /// Mean value
+    pub mean: f64,
+
+    /// Standard deviation
+    pub std_dev: f64,
+
+    /// Minimum value (after outlier removal)
+    pub min: f64,
+
+    /// Maximum value (after outlier removal)
+    pub max: f64,
+
+    /// First quartile (25th percentile)
+    pub q1: f64,
+
+    /// Third quartile (75th percentile)
+    pub q3: f64,
+
+    /// Interquartile range (Q3 - Q1)
+    pub iqr: f64,
+
+    /// 95% confidence interval lower bound
+    pub ci_95_lower: f64,
+
+    /// 95% confidence interval upper bound
+    pub ci_95_upper: f64,
+
+    /// Number of valid measurements (after outlier removal)
+    pub n_valid: usize,
+
+    /// Number of outliers removed
+    pub n_outliers: usize,
+
+    /// Number of warmup runs performed
+    pub n_warmup: usize,
+}
+
+/// Remove outliers using IQR method
+///
+/// Returns (valid_measurements, outliers)
+pub fn remove_outliers(measurements: &[f64], threshold: f64) -> (Vec<f64>, Vec<f64>) {
+    if measurements.len() < 4 {
+        // Too few measurements for outlier detection
+        return (measurements.to_vec(), Vec::new());
+    }
+
+    let mut sorted = measurements.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let q1 = quantile(&sorted, 25.0).unwrap();
+    let q3 = quantile(&sorted, 75.0).unwrap();
+    let iqr = q3 - q1;
+
+    let lower_bound = q1 - threshold * iqr;
+    let upper_bound = q3 + threshold * iqr;
+
+    let valid: Vec<f64> = sorted
+        .iter()
+        .copied()
+        .filter(|&x| x >= lower_bound && x <= upper_bound)
+        .collect();
+
+    let outliers: Vec<f64> = sorted
+        .iter()
+        .copied()
+        .filter(|&x| x < lower_bound || x > upper_bound)
+        .collect();
+
+    (valid, outliers)
+}
+
+/// Calculate comprehensive statistics from measurements
+///
+/// Fails if fewer than 3 measurements remain after outlier removal.
+pub fn calculate_statistics(
+    measurements: &[f64],
+    outlier_threshold: f64,
+    n_warmup: usize,
+) -> Result<ExperimentStatistics> {
+    let (valid, outliers) = remove_outliers(measurements, outlier_threshold);
+
+    if valid.len() < 3 {
+        anyhow::bail!(
+            "Too few valid measurements after outlier removal: {} / {} (removed {} outliers)",
+            valid.len(),
+            measurements.len(),
+            outliers.len()
+        );
+    }
+
+    Ok(summarize(&valid, outliers.len(), n_warmup))
+}
+
+/// Like [`calculate_statistics`], but summarizes small samples as they are
+///
+/// When fewer than 3 measurements survive outlier removal (a config with
+/// 1 or 2 measurement runs, or heavy removal), every measurement is kept
+/// and nothing is reported as an outlier; with a single measurement the
+/// spread and confidence interval collapse to the value itself. Fails
+/// only on an empty sample.
+pub fn calculate_statistics_or_summary(
+    measurements: &[f64],
+    outlier_threshold: f64,
+    n_warmup: usize,
+) -> Result<ExperimentStatistics> {
+    if measurements.is_empty() {
+        anyhow::bail!("No measurements to summarize");
+    }
+    calculate_statistics(measurements, outlier_threshold, n_warmup)
+        .or_else(|_| Ok(summarize(measurements, 0, n_warmup)))
+}
+
+/// Summary of a non-empty sample (outliers already removed)
+fn summarize(valid: &[f64], n_outliers: usize, n_warmup: usize) -> ExperimentStatistics {
+    let n = valid.len() as f64;
+
+    // Mean
+    let mean = valid.iter().sum::<f64>() / n;
+
+    // Variance and std dev (0 for a single measurement)
+    let variance = if valid.len() > 1 {
+        valid.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+    } else {
+        0.0
+    };
+    let std_dev = variance.sqrt();
+
+    // Median and quartiles
+    let mut sorted = valid.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let median_idx = sorted.len() / 2;
+    let median = if sorted.len().is_multiple_of(2) {
+        (sorted[median_idx - 1] + sorted[median_idx]) / 2.0
+    } else {
+        sorted[median_idx]
+    };
+
+    let q1 = quantile(&sorted, 25.0).unwrap();
+    let q3 = quantile(&sorted, 75.0).unwrap();
+    let iqr = q3 - q1;
+
+    let min = *sorted.first().unwrap();
+    let max = *sorted.last().unwrap();
+
+    // 95% Confidence Interval (t-distribution)
+    let df = valid.len() - 1;
+    let t_critical = t_critical_value(df, 0.05);
+    let margin_of_error = t_critical * (std_dev / n.sqrt());
+    let ci_95_lower = mean - margin_of_error;
+    let ci_95_upper = mean + margin_of_error;
+
+    ExperimentStatistics {
+        median,
+        mean,
+        std_dev,
+        min,
+        max,
+        q1,
+        q3,
+        iqr,
+        ci_95_lower,
+        ci_95_upper,
+        n_valid: valid.len(),
+        n_outliers,
+        n_warmup,
+    }
+}
+
+/// Get t-critical value for 95% confidence interval
+///
+/// Simplified lookup table. For production use statrs crate.
+pub fn t_critical_value(df: usize, alpha: f64) -> f64 {
+    if alpha != 0.05 {
+        return 2.0; // Conservative default
+    }
+
+    match df {
+        0..=4 => 2.776,
+        5..=9 => 2.262,
+        10..=14 => 2.145,
+        15..=19 => 2.093,
+        20..=24 => 2.064,
+        25..=29 => 2.045,
+        30..=39 => 2.021,
+        40..=49 => 2.009,
+        50..=99 => 1.984,
+        _ => 1.96, // For large df, use normal approximation
+    }
+}
+
+/// Welch's t-test for two independent samples given as (mean, std_dev, n)
+///
+/// Returns `(t, df)`: the t statistic of `a - b` and the Welch–Satterthwaite
+/// degrees of freedom. `None` if either sample has fewer than 2 measurements
+/// or both have zero variance.
+pub fn welch_t_test(a: (f64, f64, usize), b: (f64, f64, usize)) -> Option<(f64, f64)> {
+    let (mean_a, sd_a, n_a) = a;
+    let (mean_b, sd_b, n_b) = b;
+    if n_a < 2 || n_b < 2 {
+        return None;
+    }
+
+    let var_a = sd_a.powi(2) / n_a as f64;
+    let var_b = sd_b.powi(2) / n_b as f64;
+    let standard_error = (var_a + var_b).sqrt();
+    if standard_error <= 0.0 || !standard_error.is_finite() {
+        return None;
+    }
+
+    let t = (mean_a - mean_b) / standard_error;
+    let df = (var_a + var_b).powi(2)
+        / (var_a.powi(2) / (n_a - 1) as f64 + var_b.powi(2) / (n_b - 1) as f64);
+    Some((t, df))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantile_interpolates() {
+        let data = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(quantile(&data, 50.0), Some(2.5));
+        assert_eq!(quantile(&data, 0.0), Some(1.0));
+        assert_eq!(quantile(&data, 100.0), Some(4.0));
+        assert!((quantile(&data, 99.0).unwrap() - 3.97).abs() < 1e-12);
+
+        assert_eq!(quantile(&[7.0], 99.0), Some(7.0));
+        assert_eq!(quantile(&[], 50.0), None);
+    }
+
+    #[test]
+    fn test_latency_percentiles() {
+        let sorted: Vec<Duration> = (1..=5).map(Duration::from_millis).collect();
+        let map = latency_percentiles(&sorted, &[50.0, 99.9]);
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["p50"], Duration::from_millis(3));
+        assert!(map["p99.9"] > Duration::from_millis(4) && map["p99.9"] < Duration::from_millis(5));
+
+        assert!(validate_percentiles(&[0.0, 50.0, 100.0]).is_ok());
+        assert!(validate_percentiles(&[101.0]).is_err());
+    }
+
+    #[test]
+    fn test_calculate_statistics_removes_outliers() {
+        let mut measurements = vec![1.0, 1.1, 0.95, 1.0, 1.05, 0.97, 1.0, 1.02];
+        measurements.push(10.0);
+
+        let stats = calculate_statistics(&measurements, DEFAULT_OUTLIER_THRESHOLD, 2).unwrap();
+        assert_eq!(stats.n_outliers, 1);
+        assert_eq!(stats.n_valid, 8);
+        assert_eq!(stats.n_warmup, 2);
+        assert_eq!(stats.max, 1.1);
+        assert!((stats.median - 1.0).abs() < 1e-12);
+        assert!(stats.ci_95_lower < stats.mean && stats.mean < stats.ci_95_upper);
+
+        // Fewer than 3 measurements cannot be summarized
+        assert!(calculate_statistics(&[1.0, 2.0], DEFAULT_OUTLIER_THRESHOLD, 0).is_err());
+    }
+
+    #[test]
+    fn test_small_samples_fall_back_to_a_plain_summary() {
+        let stats = calculate_statistics_or_summary(&[1.0, 2.0], 1.5, 1).unwrap();
+        assert_eq!((stats.mean, stats.median, stats.n_valid, stats.n_outliers), (1.5, 1.5, 2, 0));
+        assert!(stats.ci_95_lower < 1.5 && 1.5 < stats.ci_95_upper);
+
+        let single = calculate_statistics_or_summary(&[4.0], DEFAULT_OUTLIER_THRESHOLD, 0).unwrap();
+        assert_eq!((single.median, single.std_dev, single.ci_95_lower), (4.0, 0.0, 4.0));
+        assert!(calculate_statistics_or_summary(&[], DEFAULT_OUTLIER_THRESHOLD, 0).is_err());
+
+        // Enough measurements: identical to calculate_statistics
+        let measurements = [1.0, 1.1, 0.95, 1.0, 1.05, 0.97, 1.0, 1.02, 10.0];
+        let stats = calculate_statistics_or_summary(&measurements, 1.5, 0).unwrap();
+        assert_eq!(stats.n_outliers, 1);
+    }
+
+    #[test]
+    fn test_quartiles_interpolate_for_small_n() {
+        // Indexing sorted[n/4] and sorted[3n/4] would give q1 = 2, q3 = 5 and
+        // an upper fence of 9.5; interpolated quartiles put it at 8.5
+        let (valid, outliers) = remove_outliers(&[1.0, 2.0, 3.0, 4.0, 5.0, 9.0], 1.5);
+        assert_eq!(valid, [1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(outliers, [9.0]);
+
+        let stats = calculate_statistics(&[6.0, 1.0, 5.0, 2.0, 4.0, 3.0], 1.5, 0).unwrap();
+        assert_eq!((stats.q1, stats.q3, stats.iqr), (2.25, 4.75, 2.5));
+        assert_eq!(stats.n_outliers, 0);
+    }
+
+    #[test]
+    fn test_welch_t_test() {
+        // Equal sizes and variances: df = 2(n - 1), t = diff / sqrt(2 sd² / n)
+        let (t, df) = welch_t_test((10.0, 2.0, 10), (8.0, 2.0, 10)).unwrap();
+        assert!((t - 2.0 / (0.8f64).sqrt()).abs() < 1e-12);
+        assert!((df - 18.0).abs() < 1e-9);
+
+        assert!(welch_t_test((10.0, 2.0, 1), (8.0, 2.0, 10)).is_none());
+        assert!(welch_t_test((10.0, 0.0, 5), (8.0, 0.0, 5)).is_none());
+    }
+}
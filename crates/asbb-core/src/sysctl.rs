@@ -0,0 +1,61 @@
+//! `sysctlbyname` lookups (macOS, iOS/Mac Catalyst)
+//!
+//! Calls the C API directly rather than spawning `sysctl(8)`, which is not
+//! available to sandboxed Catalyst apps.
+
+use anyhow::{Context, Result};
+use std::ffi::{c_char, c_int, c_void, CString};
+
+extern "C" {
+    fn sysctlbyname(
+        name: *const c_char,
+        oldp: *mut c_void,
+        oldlenp: *mut usize,
+        newp: *mut c_void,
+        newlen: usize,
+    ) -> c_int;
+}
+
+/// Raw value of `name`
+fn bytes(name: &str) -> Result<Vec<u8>> {
+    let c_name = CString::new(name)?;
+    let mut len = 0usize;
+    // SAFETY: a null buffer asks for the value's size only
+    let status = unsafe {
+        sysctlbyname(c_name.as_ptr(), std::ptr::null_mut(), &mut len, std::ptr::null_mut(), 0)
+    };
+    anyhow::ensure!(status == 0, "sysctl {} is not available", name);
+
+    let mut buffer = vec![0u8; len];
+    // SAFETY: `buffer` holds `len` bytes, and sysctl writes at most that
+    let status = unsafe {
+        sysctlbyname(
+            c_name.as_ptr(),
+            buffer.as_mut_ptr().cast(),
+            &mut len,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    anyhow::ensure!(status == 0, "sysctl {} failed", name);
+    buffer.truncate(len);
+    Ok(buffer)
+}
+
+/// String value (e.g. `machdep.cpu.brand_string`)
+pub fn string(name: &str) -> Result<String> {
+    let value = bytes(name)?;
+    let end = value.iter().position(|&b| b == 0).unwrap_or(value.len());
+    Ok(String::from_utf8_lossy(&value[..end]).trim().to_string())
+}
+
+/// Integer value (32- or 64-bit, e.g. `hw.memsize`)
+pub fn integer(name: &str) -> Result<u64> {
+    let value = bytes(name)?;
+    match value.len() {
+        4 => Ok(u32::from_ne_bytes(value[..4].try_into()?) as u64),
+        8 => Ok(u64::from_ne_bytes(value[..8].try_into()?)),
+        n => Err(anyhow::anyhow!("sysctl {} has {} bytes, expected an integer", name, n)),
+    }
+    .with_context(|| format!("Unexpected sysctl {} value", name))
+}
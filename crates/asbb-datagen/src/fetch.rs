@@ -0,0 +1,384 @@
+//! Real-data ingestion from SRA/ENA accessions
+//!
+//! Published benchmark runs should use citable public data. This module
+//! downloads a sequencing run by accession, verifies it, converts it to plain
+//! FASTQ (optionally truncated), and returns manifest entries so the files can
+//! be used as named scales.
+//!
+//! Two backends, both driven as subprocesses so no HTTP/TLS stack is linked:
+//!
+//! - **ENA** (default): queries the ENA portal `filereport` API for FASTQ
+//!   URLs and MD5s, downloads with `curl`, and verifies each MD5
+//! - **SRA Toolkit**: `prefetch` + `fasterq-dump` (requires sra-tools on PATH).
+//!   The toolkit validates its own downloads; no published FASTQ MD5 exists
+
+use crate::manifest::{DatasetEntry, DatasetSource};
+use crate::write_fastq;
+use anyhow::{Context, Result};
+use asbb_core::io::FastqReader;
+use flate2::read::MultiGzDecoder;
+use md5::{Digest, Md5};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// ENA portal API endpoint for run file reports
+const ENA_FILEREPORT_URL: &str = "https://www.ebi.ac.uk/ena/portal/api/filereport";
+
+/// Download backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchBackend {
+    /// Direct HTTPS download from ENA
+    Ena,
+    /// SRA Toolkit (`prefetch` + `fasterq-dump`)
+    SraToolkit,
+}
+
+impl FetchBackend {
+    /// Parse a backend name (`ena`, `sra`)
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "ena" => Ok(FetchBackend::Ena),
+            "sra" | "sra-toolkit" | "sra_toolkit" => Ok(FetchBackend::SraToolkit),
+            _ => anyhow::bail!("Unknown fetch backend: {}", name),
+        }
+    }
+}
+
+/// What to fetch and where to put it
+#[derive(Debug, Clone)]
+pub struct FetchOptions {
+    /// Run accession (SRR/ERR/DRR)
+    pub accession: String,
+
+    /// Dataset name in the manifest (paired files get `_1`/`_2` suffixes)
+    pub name: String,
+
+    /// Directory for the final FASTQ files
+    pub output_dir: PathBuf,
+
+    /// Download backend
+    pub backend: FetchBackend,
+
+    /// Keep only the first N reads of each file
+    pub max_reads: Option<usize>,
+}
+
+/// One FASTQ file listed in an ENA file report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnaFile {
+    pub url: String,
+    pub md5: String,
+    pub bytes: Option<u64>,
+}
+
+/// Fetch a run and return manifest entries for the resulting FASTQ files
+pub fn fetch_accession(options: &FetchOptions) -> Result<Vec<DatasetEntry>> {
+    validate_accession(&options.accession)?;
+    fs::create_dir_all(&options.output_dir).with_context(|| {
+        format!("Failed to create directory: {}", options.output_dir.display())
+    })?;
+
+    match options.backend {
+        FetchBackend::Ena => fetch_ena(options),
+        FetchBackend::SraToolkit => fetch_sra_toolkit(options),
+    }
+}
+
+fn fetch_ena(options: &FetchOptions) -> Result<Vec<DatasetEntry>> {
+    let report_url = format!(
+        "{}?accession={}&result=read_run&fields=run_accession,fastq_ftp,fastq_md5,fastq_bytes&format=tsv",
+        ENA_FILEREPORT_URL, options.accession
+    );
+    let report = curl_to_string(&report_url)?;
+    let files = parse_ena_filereport(&report)?;
+
+    let mut entries = Vec::with_capacity(files.len());
+    for (i, file) in files.iter().enumerate() {
+        let download = options
+            .output_dir
+            .join(format!("{}.{}.fastq.gz.part", options.accession, i));
+        curl_to_file(&file.url, &download)?;
+
+        let actual = md5_file(&download)?;
+        if !actual.eq_ignore_ascii_case(&file.md5) {
+            fs::remove_file(&download).ok();
+            anyhow::bail!(
+                "MD5 mismatch for {}: expected {}, got {}",
+                file.url,
+                file.md5,
+                actual
+            );
+        }
+
+        let name = file_name(&options.name, i, files.len());
+        let path = options.output_dir.join(format!("{}.fq", name));
+        let reader = BufReader::new(MultiGzDecoder::new(File::open(&download)?));
+        import_fastq(reader, &path, options.max_reads)?;
+        fs::remove_file(&download).ok();
+
+        let source = DatasetSource::Ena {
+            accession: options.accession.clone(),
+            url: file.url.clone(),
+            md5: file.md5.clone(),
+        };
+        entries.push(DatasetEntry::from_file(&name, &path, source)?);
+    }
+
+    Ok(entries)
+}
+
+fn fetch_sra_toolkit(options: &FetchOptions) -> Result<Vec<DatasetEntry>> {
+    let work_dir = options.output_dir.join(format!(".{}.sra", options.accession));
+    fs::create_dir_all(&work_dir)?;
+
+    run_tool(
+        Command::new("prefetch")
+            .arg(&options.accession)
+            .arg("--output-directory")
+            .arg(&work_dir),
+    )?;
+    run_tool(
+        Command::new("fasterq-dump")
+            .arg(work_dir.join(&options.accession))
+            .arg("--split-files")
+            .arg("--outdir")
+            .arg(&work_dir),
+    )?;
+
+    // Single-end: ACC.fastq; paired: ACC_1.fastq + ACC_2.fastq
+    let mut dumped: Vec<PathBuf> = fs::read_dir(&work_dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "fastq"))
+        .collect();
+    dumped.sort();
+    if dumped.is_empty() {
+        anyhow::bail!("fasterq-dump produced no FASTQ files for {}", options.accession);
+    }
+
+    let mut entries = Vec::with_capacity(dumped.len());
+    for (i, fastq) in dumped.iter().enumerate() {
+        let name = file_name(&options.name, i, dumped.len());
+        let path = options.output_dir.join(format!("{}.fq", name));
+        import_fastq(BufReader::new(File::open(fastq)?), &path, options.max_reads)?;
+
+        let source = DatasetSource::SraToolkit {
+            accession: options.accession.clone(),
+        };
+        entries.push(DatasetEntry::from_file(&name, &path, source)?);
+    }
+
+    fs::remove_dir_all(&work_dir).ok();
+    Ok(entries)
+}
+
+/// Parse an ENA `filereport` TSV (fields: run_accession, fastq_ftp, fastq_md5, fastq_bytes)
+///
+/// Paired runs list multiple `;`-separated files per row. URLs are returned
+/// with an `https://` scheme.
+pub fn parse_ena_filereport(tsv: &str) -> Result<Vec<EnaFile>> {
+    let mut lines = tsv.lines().filter(|l| !l.trim().is_empty());
+    let header: Vec<&str> = lines
+        .next()
+        .context("Empty ENA file report")?
+        .split('\t')
+        .collect();
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| *h == name)
+            .with_context(|| format!("ENA file report missing column: {}", name))
+    };
+    let (ftp_col, md5_col) = (column("fastq_ftp")?, column("fastq_md5")?);
+    let bytes_col = column("fastq_bytes").ok();
+
+    let mut files = Vec::new();
+    for line in lines {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let urls = fields.get(ftp_col).copied().unwrap_or("");
+        let md5s = fields.get(md5_col).copied().unwrap_or("");
+        let sizes = bytes_col.and_then(|c| fields.get(c).copied()).unwrap_or("");
+
+        let urls: Vec<&str> = urls.split(';').filter(|s| !s.is_empty()).collect();
+        let md5s: Vec<&str> = md5s.split(';').filter(|s| !s.is_empty()).collect();
+        let sizes: Vec<&str> = sizes.split(';').collect();
+        if urls.len() != md5s.len() {
+            anyhow::bail!("ENA file report has {} URLs but {} MD5s", urls.len(), md5s.len());
+        }
+
+        for (i, (url, md5)) in urls.iter().zip(&md5s).enumerate() {
+            let url = if url.contains("://") {
+                url.to_string()
+            } else {
+                format!("https://{}", url)
+            };
+            files.push(EnaFile {
+                url,
+                md5: md5.to_string(),
+                bytes: sizes.get(i).and_then(|s| s.parse().ok()),
+            });
+        }
+    }
+
+    if files.is_empty() {
+        anyhow::bail!("ENA file report lists no FASTQ files (run may be unavailable)");
+    }
+    Ok(files)
+}
+
+/// Accept SRA/ENA/DDBJ run accessions only (e.g. SRR390728)
+pub fn validate_accession(accession: &str) -> Result<()> {
+    let valid = accession.len() > 3
+        && matches!(&accession[..3], "SRR" | "ERR" | "DRR")
+        && accession[3..].bytes().all(|b| b.is_ascii_digit());
+    if !valid {
+        anyhow::bail!(
+            "Invalid run accession '{}' (expected SRR/ERR/DRR followed by digits)",
+            accession
+        );
+    }
+    Ok(())
+}
+
+/// Manifest name for file `index` of `count` (paired files get `_1`/`_2`)
+fn file_name(base: &str, index: usize, count: usize) -> String {
+    if count == 1 {
+        base.to_string()
+    } else {
+        format!("{}_{}", base, index + 1)
+    }
+}
+
+/// Copy FASTQ records (optionally only the first `max_reads`) into `path`
+fn import_fastq<R: BufRead>(reader: R, path: &Path, max_reads: Option<usize>) -> Result<usize> {
+    let records = FastqReader::new(reader).take(max_reads.unwrap_or(usize::MAX));
+    let file = File::create(path)
+        .with_context(|| format!("Failed to create output file: {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+
+    let mut count = 0;
+    for record in records {
+        count += write_fastq(&mut writer, [record?])?;
+    }
+    writer.flush()?;
+    Ok(count)
+}
+
+/// Hex MD5 digest of a file
+pub fn md5_file(path: &Path) -> Result<String> {
+    let mut file = File::open(path)
+        .with_context(|| format!("Failed to open file: {}", path.display()))?;
+    let mut hasher = Md5::new();
+    let mut buffer = vec![0u8; 1 << 20];
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn curl_to_string(url: &str) -> Result<String> {
+    let output = Command::new("curl")
+        .args(["-fsSL", url])
+        .output()
+        .map_err(tool_error("curl"))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "curl failed for {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+fn curl_to_file(url: &str, dest: &Path) -> Result<()> {
+    run_tool(
+        Command::new("curl")
+            .args(["-fSL", "--retry", "3", "-o"])
+            .arg(dest)
+            .arg(url),
+    )
+}
+
+fn run_tool(command: &mut Command) -> Result<()> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let status = command.status().map_err(tool_error(&program))?;
+    if !status.success() {
+        anyhow::bail!("{} exited with {}", program, status);
+    }
+    Ok(())
+}
+
+fn tool_error(program: &str) -> impl FnOnce(io::Error) -> anyhow::Error + '_ {
+    move |e| {
+        if e.kind() == io::ErrorKind::NotFound {
+            anyhow::anyhow!("'{}' not found on PATH (required for fetching)", program)
+        } else {
+            anyhow::anyhow!("Failed to run {}: {}", program, e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ena_filereport_paired() {
+        let tsv = "run_accession\tfastq_ftp\tfastq_md5\tfastq_bytes\n\
+                   SRR1\tftp.sra.ebi.ac.uk/a_1.fastq.gz;ftp.sra.ebi.ac.uk/a_2.fastq.gz\taaa;bbb\t10;20\n";
+        let files = parse_ena_filereport(tsv).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].url, "https://ftp.sra.ebi.ac.uk/a_1.fastq.gz");
+        assert_eq!(files[1].md5, "bbb");
+        assert_eq!(files[1].bytes, Some(20));
+    }
+
+    #[test]
+    fn test_parse_ena_filereport_errors() {
+        assert!(parse_ena_filereport("").is_err());
+        assert!(parse_ena_filereport("run_accession\tfastq_ftp\tfastq_md5\nSRR1\t\t\n").is_err());
+        assert!(parse_ena_filereport("run_accession\tfastq_ftp\nSRR1\tx\n").is_err());
+    }
+
+    #[test]
+    fn test_validate_accession() {
+        assert!(validate_accession("SRR390728").is_ok());
+        assert!(validate_accession("ERR000001").is_ok());
+        assert!(validate_accession("SRP000001").is_err());
+        assert!(validate_accession("SRR12&x=1").is_err());
+    }
+
+    #[test]
+    fn test_import_fastq_gz_with_limit() {
+        let dir = std::env::temp_dir();
+        let gz_path = dir.join(format!("asbb_fetch_{}.fastq.gz", std::process::id()));
+        let out_path = dir.join(format!("asbb_fetch_{}.fq", std::process::id()));
+
+        {
+            let mut encoder = flate2::write::GzEncoder::new(
+                File::create(&gz_path).unwrap(),
+                flate2::Compression::fast(),
+            );
+            for i in 0..10 {
+                writeln!(encoder, "@r{}\nACGT\n+\nIIII", i).unwrap();
+            }
+            encoder.finish().unwrap();
+        }
+
+        let reader = BufReader::new(MultiGzDecoder::new(File::open(&gz_path).unwrap()));
+        let count = import_fastq(reader, &out_path, Some(3)).unwrap();
+        let records = FastqReader::from_path(&out_path).unwrap().read_all().unwrap();
+        assert_eq!(md5_file(&gz_path).unwrap().len(), 32);
+
+        fs::remove_file(&gz_path).ok();
+        fs::remove_file(&out_path).ok();
+        assert_eq!(count, 3);
+        assert_eq!(records.len(), 3);
+    }
+}
@@ -1,18 +1,338 @@
-//! Placeholder - to be implemented
+//! Synthetic dataset generation for Apple Silicon Bio Bench
+//!
+//! Shared generation logic used by the standalone `datagen` tool
+//! (`datasets/generation-scripts`) and by the harnesses, which synthesize
+//! missing scale datasets on the fly.
+//!
+//! # Reproducibility
+//!
+//! Generation is fully determined by [`ReadGenConfig`] (including the seed):
+//! the same config always produces byte-identical output. The RNG call order
+//! (length → sequence → quality, per record) matches the original standalone
+//! generator, so datasets produced before this library existed can be
+//! regenerated exactly.
 
-#![allow(dead_code)]
-#![allow(unused_variables)]
+use anyhow::{Context, Result};
+use asbb_core::SequenceRecord;
+use rand::prelude::*;
+use rand_distr::Normal;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
 
-pub fn placeholder() {
-    println!("Placeholder implementation");
+pub mod fetch;
+pub mod manifest;
+pub mod simulate;
+pub mod spike;
+pub mod subsample;
+
+pub use asbb_core::{LengthClass, QualityDistType};
+
+/// Minimum generated read length (bp)
+pub const MIN_READ_LENGTH: usize = 50;
+
+/// Read length distribution (mean bp, std bp) of each length preset
+///
+/// Long-read presets use a 10% coefficient of variation: wide enough to
+/// exercise variable-length code paths, narrow enough that every read
+/// stays within its [`LengthClass`].
+pub fn length_preset(class: LengthClass) -> (usize, f64) {
+    match class {
+        LengthClass::Short => (150, 10.0),
+        LengthClass::Kb1 => (1_000, 100.0),
+        LengthClass::Kb10 => (10_000, 1_000.0),
+        LengthClass::Kb100 => (100_000, 10_000.0),
+    }
+}
+
+/// Parameters for synthetic read generation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadGenConfig {
+    /// Number of reads to generate
+    pub num_sequences: usize,
+
+    /// Mean read length (bp)
+    pub length_mean: usize,
+
+    /// Standard deviation of read length (bp)
+    pub length_std: f64,
+
+    /// Quality score distribution
+    pub quality_dist: QualityDistType,
+
+    /// RNG seed
+    pub seed: u64,
+}
+
+impl ReadGenConfig {
+    /// Standard ASBB scale parameters (150bp ± 10bp, degrading quality)
+    pub fn new(num_sequences: usize, seed: u64) -> Self {
+        Self {
+            num_sequences,
+            length_mean: 150,
+            length_std: 10.0,
+            quality_dist: QualityDistType::Degrading,
+            seed,
+        }
+    }
+
+    /// Set read length distribution
+    pub fn with_length(mut self, length_mean: usize, length_std: f64) -> Self {
+        self.length_mean = length_mean;
+        self.length_std = length_std;
+        self
+    }
+
+    /// Set read length distribution from a preset (see [`length_preset`])
+    pub fn with_length_class(self, class: LengthClass) -> Self {
+        let (length_mean, length_std) = length_preset(class);
+        self.with_length(length_mean, length_std)
+    }
+
+    /// Set quality distribution
+    pub fn with_quality_dist(mut self, quality_dist: QualityDistType) -> Self {
+        self.quality_dist = quality_dist;
+        self
+    }
+}
+
+/// Parse a quality distribution name (`uniform_high`, `degrading`, `realistic`)
+pub fn parse_quality_dist(name: &str) -> Result<QualityDistType> {
+    match name {
+        "uniform_high" => Ok(QualityDistType::UniformHigh),
+        "degrading" => Ok(QualityDistType::Degrading),
+        "realistic" => Ok(QualityDistType::Realistic),
+        _ => anyhow::bail!("Unknown quality distribution: {}", name),
+    }
+}
+
+/// Deterministic iterator over synthetic reads
+pub struct ReadGenerator {
+    config: ReadGenConfig,
+    rng: StdRng,
+    length_dist: Normal<f64>,
+    with_quality: bool,
+    next_index: usize,
+}
+
+impl ReadGenerator {
+    /// Generator for FASTQ records (sequence + quality)
+    pub fn fastq(config: ReadGenConfig) -> Result<Self> {
+        Self::new(config, true)
+    }
+
+    /// Generator for FASTA records (sequence only)
+    pub fn fasta(config: ReadGenConfig) -> Result<Self> {
+        Self::new(config, false)
+    }
+
+    fn new(config: ReadGenConfig, with_quality: bool) -> Result<Self> {
+        let length_dist = Normal::new(config.length_mean as f64, config.length_std)
+            .context("Invalid length distribution parameters")?;
+        Ok(Self {
+            rng: StdRng::seed_from_u64(config.seed),
+            config,
+            length_dist,
+            with_quality,
+            next_index: 0,
+        })
+    }
+
+    /// Sample a read length, clamped to a reasonable range
+    fn sample_length(&mut self) -> usize {
+        let length = self.length_dist.sample(&mut self.rng).round() as usize;
+        length.clamp(MIN_READ_LENGTH, (self.config.length_mean * 2).max(MIN_READ_LENGTH))
+    }
+}
+
+impl Iterator for ReadGenerator {
+    type Item = SequenceRecord;
+
+    fn next(&mut self) -> Option<SequenceRecord> {
+        if self.next_index >= self.config.num_sequences {
+            return None;
+        }
+
+        let id = format!("seq_{}", self.next_index);
+        self.next_index += 1;
+
+        let length = self.sample_length();
+        let sequence = random_sequence(&mut self.rng, length);
+
+        if self.with_quality {
+            let quality = quality_scores(&mut self.rng, length, self.config.quality_dist);
+            Some(SequenceRecord::fastq(id, sequence, quality))
+        } else {
+            Some(SequenceRecord::fasta(id, sequence))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.config.num_sequences - self.next_index;
+        (remaining, Some(remaining))
+    }
+}
+
+/// Generate a uniform random ACGT sequence
+pub fn random_sequence<R: Rng>(rng: &mut R, length: usize) -> Vec<u8> {
+    const BASES: &[u8] = b"ACGT";
+    (0..length).map(|_| BASES[rng.gen_range(0..4)]).collect()
+}
+
+/// Generate Phred+33 quality scores with the given distribution
+pub fn quality_scores<R: Rng>(rng: &mut R, length: usize, dist: QualityDistType) -> Vec<u8> {
+    match dist {
+        // Q40 throughout ('I')
+        QualityDistType::UniformHigh => vec![b'I'; length],
+        // Q40 → Q20 over read length (typical Illumina pattern)
+        QualityDistType::Degrading => (0..length)
+            .map(|i| {
+                let q = 40.0 - (20.0 * i as f64 / length as f64);
+                (q as u8).clamp(0, 40) + 33
+            })
+            .collect(),
+        // High quality (Q35-40) with 5% chance of a drop to Q20-29
+        // (i32 sampling keeps the RNG stream identical to the original tool)
+        QualityDistType::Realistic => (0..length)
+            .map(|_| {
+                let base_q: i32 = rng.gen_range(35..=40);
+                let q = if rng.gen::<f64>() < 0.05 {
+                    rng.gen_range(20..30)
+                } else {
+                    base_q
+                };
+                (q + 33) as u8
+            })
+            .collect(),
+        // No structure: Q2-Q40 uniformly at random
+        QualityDistType::Unknown => (0..length).map(|_| rng.gen_range(2..=40u8) + 33).collect(),
+    }
+}
+
+/// Write records as FASTQ (4 lines per record, no wrapping)
+pub fn write_fastq<W: Write>(
+    writer: &mut W,
+    records: impl IntoIterator<Item = SequenceRecord>,
+) -> Result<usize> {
+    let mut count = 0;
+    for record in records {
+        let quality = record
+            .quality
+            .as_ref()
+            .context("FASTQ output requires quality scores")?;
+        writeln!(writer, "@{}", record.id)?;
+        writer.write_all(&record.sequence)?;
+        writer.write_all(b"\n+\n")?;
+        writer.write_all(quality)?;
+        writer.write_all(b"\n")?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Write records as FASTA (60 characters per line)
+pub fn write_fasta<W: Write>(
+    writer: &mut W,
+    records: impl IntoIterator<Item = SequenceRecord>,
+) -> Result<usize> {
+    let mut count = 0;
+    for record in records {
+        writeln!(writer, ">{}", record.id)?;
+        for chunk in record.sequence.chunks(60) {
+            writer.write_all(chunk)?;
+            writer.write_all(b"\n")?;
+        }
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Generate a synthetic FASTQ file, creating parent directories as needed
+///
+/// Returns the number of records written.
+pub fn generate_fastq_file<P: AsRef<Path>>(path: P, config: &ReadGenConfig) -> Result<usize> {
+    let path = path.as_ref();
+    let mut writer = create_output(path)?;
+    let count = write_fastq(&mut writer, ReadGenerator::fastq(config.clone())?)?;
+    writer.flush()?;
+    Ok(count)
+}
+
+/// Generate a synthetic FASTA file, creating parent directories as needed
+pub fn generate_fasta_file<P: AsRef<Path>>(path: P, config: &ReadGenConfig) -> Result<usize> {
+    let path = path.as_ref();
+    let mut writer = create_output(path)?;
+    let count = write_fasta(&mut writer, ReadGenerator::fasta(config.clone())?)?;
+    writer.flush()?;
+    Ok(count)
+}
+
+fn create_output(path: &Path) -> Result<BufWriter<File>> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+    }
+    let file = File::create(path)
+        .with_context(|| format!("Failed to create output file: {}", path.display()))?;
+    Ok(BufWriter::new(file))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use asbb_core::io::FastqReader;
+
+    #[test]
+    fn test_generator_is_deterministic() {
+        let config = ReadGenConfig::new(50, 7);
+        let a: Vec<_> = ReadGenerator::fastq(config.clone()).unwrap().collect();
+        let b: Vec<_> = ReadGenerator::fastq(config).unwrap().collect();
+        assert_eq!(a, b);
+
+        let c: Vec<_> = ReadGenerator::fastq(ReadGenConfig::new(50, 8)).unwrap().collect();
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_generated_records_are_valid() {
+        let config = ReadGenConfig::new(100, 1).with_quality_dist(QualityDistType::Realistic);
+        for record in ReadGenerator::fastq(config).unwrap() {
+            let quality = record.quality.as_ref().unwrap();
+            assert_eq!(record.sequence.len(), quality.len());
+            assert!(record.sequence.len() >= MIN_READ_LENGTH);
+            assert!(record.sequence.iter().all(|b| b"ACGT".contains(b)));
+            assert!(quality.iter().all(|&q| (33..=73).contains(&q)));
+        }
+    }
+
+    #[test]
+    fn test_fastq_file_round_trip() {
+        let path = std::env::temp_dir().join(format!("asbb_datagen_{}.fq", std::process::id()));
+        let config = ReadGenConfig::new(25, 3);
+
+        assert_eq!(generate_fastq_file(&path, &config).unwrap(), 25);
+        let records = FastqReader::from_path(&path).unwrap().read_all().unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let expected: Vec<_> = ReadGenerator::fastq(config).unwrap().collect();
+        assert_eq!(records, expected);
+    }
+
+    #[test]
+    fn test_length_presets_stay_in_class() {
+        for class in [LengthClass::Kb1, LengthClass::Kb10] {
+            let config = ReadGenConfig::new(20, 5).with_length_class(class);
+            for record in ReadGenerator::fastq(config).unwrap() {
+                assert_eq!(LengthClass::from_mean_length(record.sequence.len()), class);
+            }
+        }
+    }
 
     #[test]
-    fn test_placeholder() {
-        placeholder();
+    fn test_parse_quality_dist() {
+        assert_eq!(parse_quality_dist("degrading").unwrap(), QualityDistType::Degrading);
+        assert!(parse_quality_dist("bogus").is_err());
     }
 }
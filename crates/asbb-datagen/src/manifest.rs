@@ -0,0 +1,429 @@
+//! Dataset manifest with content hashing (`datasets/manifest.toml`)
+//!
+//! Records every dataset's provenance (generator parameters and seed, or
+//! public accession), record count, and SHA-256. Datagen tools register files
+//! as they write them; harnesses call [`verify_dataset`] before loading, so
+//! results are never silently computed on a re-generated or truncated file.
+//!
+//! Paths are stored relative to the manifest's directory, so the manifest is
+//! valid regardless of the working directory the tools were run from.
+//!
+//! # Format
+//!
+//! ```toml
+//! [[dataset]]
+//! name = "medium_10000_150bp"
+//! path = "medium_10000_150bp.fq"
+//! num_records = 10000
+//! sha256 = "9f2c..."
+//!
+//! [dataset.source]
+//! kind = "synthetic"
+//! format = "fastq"
+//! seed = 3
+//! length_mean = 150
+//! length_std = 10.0
+//! quality_dist = "Degrading"
+//! ```
+
+use crate::ReadGenConfig;
+use anyhow::{Context, Result};
+use asbb_core::QualityDistType;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Default manifest location (relative to the repository root)
+pub const DEFAULT_MANIFEST_PATH: &str = "datasets/manifest.toml";
+
+/// Where a dataset came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DatasetSource {
+    /// Uniform random reads from [`crate::ReadGenerator`]
+    Synthetic {
+        /// `fasta` or `fastq`
+        format: String,
+        seed: u64,
+        length_mean: usize,
+        length_std: f64,
+        quality_dist: QualityDistType,
+        /// Adapter/barcode spiking applied, if any ([`crate::spike`])
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        spike: Option<String>,
+    },
+
+    /// Reads simulated from a reference ([`crate::simulate`])
+    Simulated {
+        seed: u64,
+        /// Platform profile (`illumina`, `ont`)
+        platform: String,
+        /// Reference FASTA, or `None` for a synthetic genome
+        reference: Option<String>,
+        /// Synthetic genome size (bp), when no reference was given
+        genome_size: Option<usize>,
+        /// Adapter/barcode spiking applied, if any ([`crate::spike`])
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        spike: Option<String>,
+    },
+
+    /// Subset of another dataset ([`crate::subsample`])
+    Subsampled {
+        /// Input file
+        input: String,
+        /// Subsampling mode, e.g. `Fraction { fraction: 0.01, seed: 7 }`
+        mode: String,
+    },
+
+    /// Downloaded over HTTPS from ENA
+    Ena {
+        /// Run accession (SRR/ERR/DRR)
+        accession: String,
+        /// Download URL of the (gzipped) FASTQ
+        url: String,
+        /// MD5 of the downloaded file, as published by ENA
+        md5: String,
+    },
+
+    /// Converted locally with SRA Toolkit (`prefetch` + `fasterq-dump`)
+    SraToolkit {
+        /// Run accession
+        accession: String,
+    },
+}
+
+impl DatasetSource {
+    /// Provenance of a FASTQ file written by [`crate::generate_fastq_file`]
+    pub fn synthetic_fastq(config: &ReadGenConfig) -> Self {
+        DatasetSource::Synthetic {
+            format: "fastq".to_string(),
+            seed: config.seed,
+            length_mean: config.length_mean,
+            length_std: config.length_std,
+            quality_dist: config.quality_dist,
+            spike: None,
+        }
+    }
+}
+
+/// A named dataset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatasetEntry {
+    /// Unique name (used to select the dataset as a scale)
+    pub name: String,
+
+    /// File path, relative to the manifest's directory
+    pub path: PathBuf,
+
+    /// Number of records in the file
+    pub num_records: usize,
+
+    /// Hex SHA-256 of the file contents
+    pub sha256: String,
+
+    /// Provenance
+    pub source: DatasetSource,
+}
+
+impl DatasetEntry {
+    /// Describe an existing file, computing its record count and SHA-256
+    ///
+    /// `path` is as seen from the current directory; [`DatasetManifest::upsert`]
+    /// rewrites it relative to the manifest.
+    pub fn from_file<P: AsRef<Path>>(name: &str, path: P, source: DatasetSource) -> Result<Self> {
+        let path = path.as_ref();
+        Ok(Self {
+            name: name.to_string(),
+            path: path.to_path_buf(),
+            num_records: count_records(path)?,
+            sha256: sha256_file(path)?,
+            source,
+        })
+    }
+}
+
+/// Collection of named datasets
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DatasetManifest {
+    #[serde(rename = "dataset", default)]
+    pub datasets: Vec<DatasetEntry>,
+
+    /// Directory the manifest was loaded from (entry paths are relative to it)
+    #[serde(skip)]
+    base_dir: PathBuf,
+}
+
+impl DatasetManifest {
+    /// Load a manifest, or an empty one if the file doesn't exist yet
+    pub fn load_or_default<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        if !path.exists() {
+            return Ok(Self {
+                datasets: Vec::new(),
+                base_dir,
+            });
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read manifest: {}", path.display()))?;
+        let mut manifest: Self = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse manifest: {}", path.display()))?;
+        manifest.base_dir = base_dir;
+        Ok(manifest)
+    }
+
+    /// Write the manifest as TOML
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        let content = toml::to_string_pretty(self)?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write manifest: {}", path.display()))
+    }
+
+    /// Look up a dataset by name
+    pub fn get(&self, name: &str) -> Option<&DatasetEntry> {
+        self.datasets.iter().find(|d| d.name == name)
+    }
+
+    /// Path of an entry as seen from the current directory
+    pub fn resolve(&self, entry: &DatasetEntry) -> PathBuf {
+        self.base_dir.join(&entry.path)
+    }
+
+    /// Find the entry describing `file` (compared by canonical path)
+    pub fn find_by_file<P: AsRef<Path>>(&self, file: P) -> Option<&DatasetEntry> {
+        let target = fs::canonicalize(file).ok()?;
+        self.datasets
+            .iter()
+            .find(|d| fs::canonicalize(self.resolve(d)).ok().as_ref() == Some(&target))
+    }
+
+    /// Add a dataset, replacing any existing entry with the same name or file
+    ///
+    /// The entry's path is rewritten relative to the manifest directory when
+    /// the file lives below it.
+    pub fn upsert(&mut self, mut entry: DatasetEntry) {
+        entry.path = self.relative_path(&entry.path);
+
+        let existing = self
+            .datasets
+            .iter()
+            .position(|d| d.name == entry.name || d.path == entry.path);
+        match existing {
+            Some(index) => self.datasets[index] = entry,
+            None => self.datasets.push(entry),
+        }
+    }
+
+    fn relative_path(&self, path: &Path) -> PathBuf {
+        let canonical = (fs::canonicalize(path), fs::canonicalize(self.base_dir_or_cwd()));
+        match canonical {
+            (Ok(file), Ok(base)) => match file.strip_prefix(&base) {
+                Ok(relative) => relative.to_path_buf(),
+                Err(_) => file,
+            },
+            _ => path.to_path_buf(),
+        }
+    }
+
+    fn base_dir_or_cwd(&self) -> &Path {
+        if self.base_dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            &self.base_dir
+        }
+    }
+}
+
+/// Register a file in the manifest at `manifest_path` (load, upsert, save)
+pub fn register_dataset<P: AsRef<Path>, Q: AsRef<Path>>(
+    manifest_path: P,
+    name: &str,
+    file: Q,
+    source: DatasetSource,
+) -> Result<DatasetEntry> {
+    let mut manifest = DatasetManifest::load_or_default(&manifest_path)?;
+    let entry = DatasetEntry::from_file(name, file, source)?;
+    manifest.upsert(entry.clone());
+    manifest.save(&manifest_path)?;
+    Ok(entry)
+}
+
+/// Outcome of verifying a dataset against the manifest
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verification {
+    /// File matches its manifest entry
+    Verified(DatasetEntry),
+    /// File is not listed in the manifest (nothing to check against)
+    Untracked,
+}
+
+/// Check a dataset's SHA-256 against its manifest entry
+///
+/// Fails if the file is listed but its contents have changed (re-generated
+/// with different parameters, truncated, or edited).
+pub fn verify_dataset<P: AsRef<Path>, Q: AsRef<Path>>(
+    manifest_path: P,
+    file: Q,
+) -> Result<Verification> {
+    let file = file.as_ref();
+    let manifest = DatasetManifest::load_or_default(manifest_path)?;
+    let entry = match manifest.find_by_file(file) {
+        Some(entry) => entry.clone(),
+        None => return Ok(Verification::Untracked),
+    };
+
+    let actual = sha256_file(file)?;
+    if actual != entry.sha256 {
+        anyhow::bail!(
+            "Dataset {} does not match the manifest (entry '{}'): expected SHA-256 {}, got {}. \
+             The file was modified or regenerated; restore it or re-register it.",
+            file.display(),
+            entry.name,
+            entry.sha256,
+            actual
+        );
+    }
+    Ok(Verification::Verified(entry))
+}
+
+/// Hex SHA-256 digest of a file
+pub fn sha256_file<P: AsRef<Path>>(path: P) -> Result<String> {
+    let path = path.as_ref();
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to open file: {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1 << 20];
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Count FASTA (`>` headers) or FASTQ (4-line) records
+fn count_records(path: &Path) -> Result<usize> {
+    let file =
+        File::open(path).with_context(|| format!("Failed to open file: {}", path.display()))?;
+    let mut reader = BufReader::new(file);
+
+    let is_fasta = reader.fill_buf()?.first() == Some(&b'>');
+    if is_fasta {
+        let mut count = 0;
+        for line in reader.split(b'\n') {
+            if line?.first() == Some(&b'>') {
+                count += 1;
+            }
+        }
+        Ok(count)
+    } else {
+        let mut count = 0;
+        for record in asbb_core::io::FastqReader::new(reader) {
+            record?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_fastq_file;
+
+    fn temp_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("asbb_manifest_{}_{}", tag, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn ena_source(name: &str) -> DatasetSource {
+        DatasetSource::Ena {
+            accession: name.to_string(),
+            url: format!("https://example.org/{}.fastq.gz", name),
+            md5: "d41d8cd98f00b204e9800998ecf8427e".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_register_and_round_trip() {
+        let dir = temp_dir("roundtrip");
+        let manifest_path = dir.join("manifest.toml");
+        let config = ReadGenConfig::new(20, 1);
+        generate_fastq_file(dir.join("a.fq"), &config).unwrap();
+        generate_fastq_file(dir.join("b.fq"), &config.clone().with_length(100, 5.0)).unwrap();
+
+        let a = register_dataset(
+            &manifest_path,
+            "a",
+            dir.join("a.fq"),
+            DatasetSource::synthetic_fastq(&config),
+        )
+        .unwrap();
+        register_dataset(&manifest_path, "b", dir.join("b.fq"), ena_source("b")).unwrap();
+        assert_eq!(a.num_records, 20);
+        assert_eq!(a.sha256.len(), 64);
+
+        let loaded = DatasetManifest::load_or_default(&manifest_path).unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(loaded.datasets.len(), 2);
+        assert_eq!(loaded.get("a").unwrap().path, PathBuf::from("a.fq"));
+        assert_eq!(loaded.get("a").unwrap().source, DatasetSource::synthetic_fastq(&config));
+    }
+
+    #[test]
+    fn test_upsert_replaces_by_name() {
+        let dir = temp_dir("upsert");
+        let manifest_path = dir.join("manifest.toml");
+        generate_fastq_file(dir.join("a.fq"), &ReadGenConfig::new(5, 1)).unwrap();
+
+        register_dataset(&manifest_path, "a", dir.join("a.fq"), ena_source("a")).unwrap();
+        generate_fastq_file(dir.join("a.fq"), &ReadGenConfig::new(9, 1)).unwrap();
+        register_dataset(&manifest_path, "a", dir.join("a.fq"), ena_source("a")).unwrap();
+
+        let manifest = DatasetManifest::load_or_default(&manifest_path).unwrap();
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(manifest.datasets.len(), 1);
+        assert_eq!(manifest.get("a").unwrap().num_records, 9);
+    }
+
+    #[test]
+    fn test_verify_detects_modified_file() {
+        let dir = temp_dir("verify");
+        let manifest_path = dir.join("manifest.toml");
+        let file = dir.join("reads.fq");
+        let config = ReadGenConfig::new(10, 4);
+        generate_fastq_file(&file, &config).unwrap();
+
+        assert_eq!(verify_dataset(&manifest_path, &file).unwrap(), Verification::Untracked);
+
+        register_dataset(&manifest_path, "reads", &file, DatasetSource::synthetic_fastq(&config))
+            .unwrap();
+        assert!(matches!(
+            verify_dataset(&manifest_path, &file).unwrap(),
+            Verification::Verified(_)
+        ));
+
+        // Truncate: drop the last record
+        let content = fs::read_to_string(&file).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        fs::write(&file, lines[..lines.len() - 4].join("\n")).unwrap();
+        let result = verify_dataset(&manifest_path, &file);
+        fs::remove_dir_all(&dir).ok();
+
+        assert!(result.is_err());
+    }
+}
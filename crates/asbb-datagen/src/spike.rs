@@ -0,0 +1,417 @@
+//! Adapter and barcode spiking with ground truth
+//!
+//! Synthetic reads never contain adapters or barcodes, so adapter trimming
+//! and demultiplexing benchmarks on them measure the "nothing found" path
+//! only, and their output cannot be checked. This module modifies a
+//! configurable fraction of reads and records exactly what was inserted:
+//!
+//! - **Adapters** (3'): models read-through on short inserts. The last `n`
+//!   bases of the read are replaced by the first `n` bases of the adapter,
+//!   so read length is preserved and both full and partial adapters occur
+//! - **Barcodes** (5'): an inline sample barcode is prepended to every read,
+//!   chosen uniformly at random from the barcode set
+//!
+//! The resulting [`SpikeTruth`] holds per-read annotations plus summary
+//! counts, and is written next to the dataset as JSON so operation output
+//! can be validated against it.
+
+use anyhow::{Context, Result};
+use asbb_core::SequenceRecord;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Illumina TruSeq adapter prefix (matches the adapter_trimming benchmarks)
+pub const DEFAULT_ADAPTER: &[u8] = b"AGATCGGAAGAG";
+
+/// Quality (Phred+33) assigned to prepended barcode bases
+const BARCODE_QUALITY: u8 = b'I';
+
+/// What to insert into reads
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpikeConfig {
+    /// Adapter sequence appended at the 3' end
+    pub adapter: Vec<u8>,
+
+    /// Fraction of reads (0.0-1.0) that receive an adapter
+    pub adapter_fraction: f64,
+
+    /// Shortest adapter prefix to insert (partial read-through)
+    pub min_adapter_length: usize,
+
+    /// Inline barcodes; every read gets one when non-empty
+    pub barcodes: Vec<Vec<u8>>,
+
+    /// RNG seed (independent of the read generator's seed)
+    pub seed: u64,
+}
+
+impl SpikeConfig {
+    /// No adapters, no barcodes
+    pub fn new(seed: u64) -> Self {
+        Self {
+            adapter: DEFAULT_ADAPTER.to_vec(),
+            adapter_fraction: 0.0,
+            min_adapter_length: 5,
+            barcodes: Vec::new(),
+            seed,
+        }
+    }
+
+    /// Spike adapters into a fraction of reads
+    pub fn with_adapter(mut self, adapter: Vec<u8>, fraction: f64) -> Self {
+        self.adapter = adapter;
+        self.adapter_fraction = fraction;
+        self
+    }
+
+    /// Prepend one of these barcodes to every read
+    pub fn with_barcodes(mut self, barcodes: Vec<Vec<u8>>) -> Self {
+        self.barcodes = barcodes;
+        self
+    }
+
+    /// Whether this config modifies reads at all
+    pub fn is_active(&self) -> bool {
+        self.adapter_fraction > 0.0 || !self.barcodes.is_empty()
+    }
+
+    /// One-line description for dataset provenance records
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.adapter_fraction > 0.0 {
+            parts.push(format!(
+                "adapter {} in {:.1}% of reads (min {}bp)",
+                String::from_utf8_lossy(&self.adapter),
+                self.adapter_fraction * 100.0,
+                self.min_adapter_length
+            ));
+        }
+        if !self.barcodes.is_empty() {
+            let codes: Vec<_> = self.barcodes.iter().map(|b| String::from_utf8_lossy(b)).collect();
+            parts.push(format!("barcodes {}", codes.join(",")));
+        }
+        parts.push(format!("seed {}", self.seed));
+        parts.join("; ")
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !(0.0..=1.0).contains(&self.adapter_fraction) {
+            anyhow::bail!(
+                "Adapter fraction must be between 0 and 1, got {}",
+                self.adapter_fraction
+            );
+        }
+        if self.adapter_fraction > 0.0
+            && (self.min_adapter_length == 0 || self.min_adapter_length > self.adapter.len())
+        {
+            anyhow::bail!(
+                "Minimum adapter length must be between 1 and {} (adapter length)",
+                self.adapter.len()
+            );
+        }
+        if let Some(len) = self.barcodes.first().map(|b| b.len()) {
+            if len == 0 || self.barcodes.iter().any(|b| b.len() != len) {
+                anyhow::bail!("Barcodes must be non-empty and of equal length");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Ground truth for one read
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadTruth {
+    /// Read ID
+    pub id: String,
+
+    /// Index into [`SpikeTruth::barcodes`], if a barcode was prepended
+    pub barcode: Option<usize>,
+
+    /// Position of the first adapter base in the final read, if spiked
+    pub adapter_start: Option<usize>,
+
+    /// Number of adapter bases inserted
+    pub adapter_length: usize,
+}
+
+/// Ground truth for a spiked dataset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpikeTruth {
+    /// Adapter sequence used
+    pub adapter: String,
+
+    /// Barcode sequences used (index = sample)
+    pub barcodes: Vec<String>,
+
+    /// Total reads
+    pub num_reads: usize,
+
+    /// Reads containing an adapter
+    pub reads_with_adapter: usize,
+
+    /// Reads containing the complete adapter
+    pub reads_with_full_adapter: usize,
+
+    /// Reads per barcode
+    pub barcode_counts: Vec<usize>,
+
+    /// Per-read annotations
+    pub reads: Vec<ReadTruth>,
+}
+
+impl SpikeTruth {
+    /// Sidecar path for a dataset (`reads.fq` → `reads.fq.truth.json`)
+    pub fn sidecar_path(dataset: &Path) -> PathBuf {
+        let mut name = dataset.as_os_str().to_owned();
+        name.push(".truth.json");
+        PathBuf::from(name)
+    }
+
+    /// Write as pretty JSON
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)
+            .with_context(|| format!("Failed to write truth file: {}", path.display()))
+    }
+
+    /// Load from JSON
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read truth file: {}", path.display()))?;
+        Ok(serde_json::from_str(&json)?)
+    }
+}
+
+/// Iterator adapter that spikes reads and accumulates ground truth
+pub struct Spiker<I> {
+    inner: I,
+    config: SpikeConfig,
+    rng: StdRng,
+    truth: SpikeTruth,
+}
+
+impl<I: Iterator<Item = SequenceRecord>> Spiker<I> {
+    pub fn new(inner: I, config: SpikeConfig) -> Result<Self> {
+        config.validate()?;
+        let truth = SpikeTruth {
+            adapter: String::from_utf8_lossy(&config.adapter).into_owned(),
+            barcodes: config
+                .barcodes
+                .iter()
+                .map(|b| String::from_utf8_lossy(b).into_owned())
+                .collect(),
+            num_reads: 0,
+            reads_with_adapter: 0,
+            reads_with_full_adapter: 0,
+            barcode_counts: vec![0; config.barcodes.len()],
+            reads: Vec::new(),
+        };
+        Ok(Self {
+            inner,
+            rng: StdRng::seed_from_u64(config.seed),
+            config,
+            truth,
+        })
+    }
+
+    /// Ground truth for the reads yielded so far
+    pub fn truth(&self) -> &SpikeTruth {
+        &self.truth
+    }
+
+    /// Consume the spiker, returning the accumulated truth
+    pub fn into_truth(self) -> SpikeTruth {
+        self.truth
+    }
+
+    fn spike(&mut self, mut record: SequenceRecord) -> SequenceRecord {
+        let mut adapter_start = None;
+        let mut adapter_length = 0;
+
+        if self.config.adapter_fraction > 0.0 && self.rng.gen::<f64>() < self.config.adapter_fraction
+        {
+            let max_len = self.config.adapter.len().min(record.sequence.len());
+            if max_len >= self.config.min_adapter_length {
+                adapter_length = self.rng.gen_range(self.config.min_adapter_length..=max_len);
+                let start = record.sequence.len() - adapter_length;
+                record.sequence[start..].copy_from_slice(&self.config.adapter[..adapter_length]);
+                adapter_start = Some(start);
+            }
+        }
+
+        let mut barcode = None;
+        if !self.config.barcodes.is_empty() {
+            let index = self.rng.gen_range(0..self.config.barcodes.len());
+            let code = &self.config.barcodes[index];
+
+            let mut sequence = Vec::with_capacity(code.len() + record.sequence.len());
+            sequence.extend_from_slice(code);
+            sequence.extend_from_slice(&record.sequence);
+            record.sequence = sequence;
+
+            if let Some(quality) = record.quality.as_mut() {
+                let mut prefixed = vec![BARCODE_QUALITY; code.len()];
+                prefixed.extend_from_slice(quality);
+                *quality = prefixed;
+            }
+
+            adapter_start = adapter_start.map(|start| start + code.len());
+            self.truth.barcode_counts[index] += 1;
+            barcode = Some(index);
+        }
+
+        self.truth.num_reads += 1;
+        if adapter_start.is_some() {
+            self.truth.reads_with_adapter += 1;
+            if adapter_length == self.config.adapter.len() {
+                self.truth.reads_with_full_adapter += 1;
+            }
+        }
+        self.truth.reads.push(ReadTruth {
+            id: record.id.clone(),
+            barcode,
+            adapter_start,
+            adapter_length,
+        });
+
+        record
+    }
+}
+
+impl<I: Iterator<Item = SequenceRecord>> Iterator for Spiker<I> {
+    type Item = SequenceRecord;
+
+    fn next(&mut self) -> Option<SequenceRecord> {
+        let record = self.inner.next()?;
+        Some(self.spike(record))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Generate random barcodes with a guaranteed minimum pairwise Hamming distance
+///
+/// Fails if the requested set cannot be found within a bounded number of
+/// attempts (e.g. too many barcodes for the length and distance).
+pub fn generate_barcodes(
+    count: usize,
+    length: usize,
+    min_distance: usize,
+    seed: u64,
+) -> Result<Vec<Vec<u8>>> {
+    const MAX_ATTEMPTS: usize = 100_000;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut barcodes: Vec<Vec<u8>> = Vec::with_capacity(count);
+
+    for _ in 0..MAX_ATTEMPTS {
+        if barcodes.len() == count {
+            break;
+        }
+        let candidate = crate::random_sequence(&mut rng, length);
+        let distinct = barcodes.iter().all(|b| {
+            b.iter().zip(&candidate).filter(|(x, y)| x != y).count() >= min_distance
+        });
+        if distinct {
+            barcodes.push(candidate);
+        }
+    }
+
+    if barcodes.len() < count {
+        anyhow::bail!(
+            "Could not find {} barcodes of length {} with Hamming distance >= {}",
+            count,
+            length,
+            min_distance
+        );
+    }
+    Ok(barcodes)
+}
+
+/// Parse a comma-separated barcode list (`ACGTACGT,TGCATGCA`)
+pub fn parse_barcodes(list: &str) -> Result<Vec<Vec<u8>>> {
+    list.split(',')
+        .map(|b| b.trim().to_ascii_uppercase())
+        .filter(|b| !b.is_empty())
+        .map(|b| {
+            if b.bytes().all(|c| b"ACGT".contains(&c)) {
+                Ok(b.into_bytes())
+            } else {
+                anyhow::bail!("Invalid barcode (expected ACGT only): {}", b)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ReadGenConfig, ReadGenerator};
+
+    fn reads(n: usize) -> ReadGenerator {
+        ReadGenerator::fastq(ReadGenConfig::new(n, 1)).unwrap()
+    }
+
+    #[test]
+    fn test_adapter_spiking_matches_truth() {
+        let config = SpikeConfig::new(3).with_adapter(DEFAULT_ADAPTER.to_vec(), 0.5);
+        let mut spiker = Spiker::new(reads(500), config).unwrap();
+        let records: Vec<_> = spiker.by_ref().collect();
+        let truth = spiker.into_truth();
+
+        assert_eq!(truth.num_reads, 500);
+        assert!(truth.reads_with_adapter > 150 && truth.reads_with_adapter < 350);
+
+        for (record, annotation) in records.iter().zip(&truth.reads) {
+            assert_eq!(record.id, annotation.id);
+            assert_eq!(record.sequence.len(), record.quality.as_ref().unwrap().len());
+            if let Some(start) = annotation.adapter_start {
+                assert_eq!(
+                    &record.sequence[start..],
+                    &DEFAULT_ADAPTER[..annotation.adapter_length]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_barcodes_prepended() {
+        let barcodes = generate_barcodes(4, 8, 3, 5).unwrap();
+        let config = SpikeConfig::new(9)
+            .with_adapter(DEFAULT_ADAPTER.to_vec(), 1.0)
+            .with_barcodes(barcodes.clone());
+        let mut spiker = Spiker::new(reads(200), config).unwrap();
+        let records: Vec<_> = spiker.by_ref().collect();
+        let truth = spiker.into_truth();
+
+        assert_eq!(truth.barcode_counts.iter().sum::<usize>(), 200);
+        for (record, annotation) in records.iter().zip(&truth.reads) {
+            let code = &barcodes[annotation.barcode.unwrap()];
+            assert!(record.sequence.starts_with(code));
+            let start = annotation.adapter_start.unwrap();
+            assert!(start >= code.len());
+            assert!(record.sequence[start..].starts_with(&DEFAULT_ADAPTER[..5]));
+        }
+    }
+
+    #[test]
+    fn test_generate_barcodes_distance() {
+        let barcodes = generate_barcodes(16, 8, 3, 1).unwrap();
+        for (i, a) in barcodes.iter().enumerate() {
+            for b in &barcodes[i + 1..] {
+                assert!(a.iter().zip(b).filter(|(x, y)| x != y).count() >= 3);
+            }
+        }
+        assert!(generate_barcodes(100, 2, 2, 1).is_err());
+    }
+
+    #[test]
+    fn test_invalid_config_rejected() {
+        assert!(Spiker::new(reads(1), SpikeConfig::new(0).with_adapter(vec![], 0.5)).is_err());
+        assert!(parse_barcodes("ACGT,ACGN").is_err());
+        assert_eq!(parse_barcodes("acgt, TTGG").unwrap(), vec![b"ACGT".to_vec(), b"TTGG".to_vec()]);
+    }
+}
@@ -0,0 +1,241 @@
+//! Subsampling real datasets
+//!
+//! Derives smaller datasets from a single large FASTQ file, so the scale
+//! ladder (Tiny → Huge) can be built from real reads instead of synthetic
+//! ones. Three modes:
+//!
+//! - **Fraction**: keeps each read independently with probability `fraction`,
+//!   decided by a seeded hash of the read ID. Single pass, constant memory,
+//!   and the same read is kept or dropped consistently across files (paired
+//!   R1/R2 files subsample in sync as long as their IDs match)
+//! - **Count**: reservoir sampling of exactly `count` reads (memory grows
+//!   with `count`, not with the input). Output preserves input order
+//! - **Head**: the first `count` reads (fast, but not a random sample)
+
+use crate::write_fastq;
+use anyhow::{Context, Result};
+use asbb_core::io::FastqReader;
+use asbb_core::SequenceRecord;
+use rand::prelude::*;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// How to select reads
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SubsampleMode {
+    /// Keep each read with this probability (hash of seed + read ID)
+    Fraction { fraction: f64, seed: u64 },
+
+    /// Uniform random sample of exactly this many reads (reservoir)
+    Count { count: usize, seed: u64 },
+
+    /// First N reads
+    Head { count: usize },
+}
+
+/// Summary of a subsampling run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubsampleStats {
+    /// Reads consumed from the input
+    pub records_read: usize,
+
+    /// Reads written to the output
+    pub records_written: usize,
+}
+
+/// Subsample a FASTQ file
+///
+/// Creates the output's parent directory if needed. Headers, sequences and
+/// quality strings are copied unchanged.
+pub fn subsample_fastq<P: AsRef<Path>, Q: AsRef<Path>>(
+    input: P,
+    output: Q,
+    mode: SubsampleMode,
+) -> Result<SubsampleStats> {
+    let output = output.as_ref();
+    let reader = FastqReader::from_path(input)?;
+
+    if let Some(parent) = output.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+    }
+    let file = File::create(output)
+        .with_context(|| format!("Failed to create output file: {}", output.display()))?;
+    let mut writer = BufWriter::new(file);
+
+    let stats = subsample_records(reader, &mut writer, mode)?;
+    writer.flush()?;
+    Ok(stats)
+}
+
+/// Subsample a record stream into a FASTQ writer
+pub fn subsample_records<W: Write>(
+    records: impl Iterator<Item = Result<SequenceRecord>>,
+    writer: &mut W,
+    mode: SubsampleMode,
+) -> Result<SubsampleStats> {
+    let mut records_read = 0;
+    let mut counted = records.inspect(|_| records_read += 1);
+
+    let records_written = match mode {
+        SubsampleMode::Fraction { fraction, seed } => {
+            if !(0.0..=1.0).contains(&fraction) {
+                anyhow::bail!("Fraction must be between 0 and 1, got {}", fraction);
+            }
+            let mut kept = Vec::new();
+            let mut written = 0;
+            for record in counted.by_ref() {
+                let record = record?;
+                if keep_by_hash(&record.id, fraction, seed) {
+                    kept.push(record);
+                }
+                // Flush in chunks to keep memory constant
+                if kept.len() >= 4096 {
+                    written += write_fastq(writer, kept.drain(..))?;
+                }
+            }
+            written + write_fastq(writer, kept)?
+        }
+        SubsampleMode::Count { count, seed } => {
+            let sample = reservoir_sample(counted.by_ref(), count, seed)?;
+            write_fastq(writer, sample)?
+        }
+        SubsampleMode::Head { count } => {
+            let head = counted
+                .by_ref()
+                .take(count)
+                .collect::<Result<Vec<_>>>()?;
+            write_fastq(writer, head)?
+        }
+    };
+
+    Ok(SubsampleStats {
+        records_read,
+        records_written,
+    })
+}
+
+/// Reservoir sampling (Algorithm R), returning the sample in input order
+fn reservoir_sample(
+    records: impl Iterator<Item = Result<SequenceRecord>>,
+    count: usize,
+    seed: u64,
+) -> Result<Vec<SequenceRecord>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut reservoir: Vec<(usize, SequenceRecord)> = Vec::with_capacity(count);
+
+    for (index, record) in records.enumerate() {
+        let record = record?;
+        if reservoir.len() < count {
+            reservoir.push((index, record));
+        } else {
+            let slot = rng.gen_range(0..=index);
+            if slot < count {
+                reservoir[slot] = (index, record);
+            }
+        }
+    }
+
+    reservoir.sort_by_key(|(index, _)| *index);
+    Ok(reservoir.into_iter().map(|(_, record)| record).collect())
+}
+
+/// Deterministic keep/drop decision from the read name
+///
+/// Uses only the first whitespace-delimited token of the header, so R1/R2
+/// comments (`1:N:0:...` vs `2:N:0:...`) and `/1` `/2` suffixes don't differ.
+fn keep_by_hash(id: &str, fraction: f64, seed: u64) -> bool {
+    let name = id.split_whitespace().next().unwrap_or("");
+    let name = name
+        .strip_suffix("/1")
+        .or_else(|| name.strip_suffix("/2"))
+        .unwrap_or(name);
+
+    // FNV-1a, then a splitmix64 finalizer for good bit mixing
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325 ^ seed;
+    for &byte in name.as_bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^= hash >> 31;
+
+    (hash as f64 / u64::MAX as f64) < fraction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ReadGenConfig, ReadGenerator};
+
+    fn records(n: usize) -> Vec<Result<SequenceRecord>> {
+        ReadGenerator::fastq(ReadGenConfig::new(n, 1))
+            .unwrap()
+            .map(Ok)
+            .collect()
+    }
+
+    fn run(n: usize, mode: SubsampleMode) -> (SubsampleStats, Vec<SequenceRecord>) {
+        let mut buffer = Vec::new();
+        let stats = subsample_records(records(n).into_iter(), &mut buffer, mode).unwrap();
+        let output = FastqReader::new(buffer.as_slice()).read_all().unwrap();
+        (stats, output)
+    }
+
+    #[test]
+    fn test_fraction_is_deterministic_and_approximate() {
+        let mode = SubsampleMode::Fraction { fraction: 0.1, seed: 7 };
+        let (stats, a) = run(10_000, mode);
+        let (_, b) = run(10_000, mode);
+
+        assert_eq!(stats.records_read, 10_000);
+        assert_eq!(stats.records_written, a.len());
+        assert!(a.len() > 850 && a.len() < 1150, "kept {}", a.len());
+        assert_eq!(a, b);
+
+        let (_, c) = run(10_000, SubsampleMode::Fraction { fraction: 0.1, seed: 8 });
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_fraction_consistent_across_mates() {
+        assert_eq!(
+            keep_by_hash("read42/1", 0.5, 3),
+            keep_by_hash("read42/2", 0.5, 3)
+        );
+        assert_eq!(
+            keep_by_hash("read42 1:N:0:ACGT", 0.5, 3),
+            keep_by_hash("read42 2:N:0:ACGT", 0.5, 3)
+        );
+    }
+
+    #[test]
+    fn test_count_is_exact_and_ordered() {
+        let (stats, sample) = run(1000, SubsampleMode::Count { count: 100, seed: 1 });
+        assert_eq!(stats.records_written, 100);
+        assert_eq!(sample.len(), 100);
+
+        let indices: Vec<usize> = sample
+            .iter()
+            .map(|r| r.id.trim_start_matches("seq_").parse().unwrap())
+            .collect();
+        assert!(indices.windows(2).all(|w| w[0] < w[1]));
+        assert!(*indices.last().unwrap() > 500, "sample should span the input");
+
+        // Fewer reads than requested: keep everything
+        let (_, all) = run(10, SubsampleMode::Count { count: 100, seed: 1 });
+        assert_eq!(all.len(), 10);
+    }
+
+    #[test]
+    fn test_head() {
+        let (stats, head) = run(100, SubsampleMode::Head { count: 5 });
+        assert_eq!(stats.records_read, 5);
+        let ids: Vec<&str> = head.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["seq_0", "seq_1", "seq_2", "seq_3", "seq_4"]);
+    }
+}
@@ -0,0 +1,208 @@
+//! Encoding amortization analysis
+//!
+//! A 2-bit kernel only sees pre-encoded input, so timing it alone overstates
+//! its benefit for one-shot workloads: `BitSeq::from_ascii` must run first,
+//! once per dataset. If the data is encoded once and then processed by `n`
+//! passes, the encoded path wins end-to-end when
+//!
+//! ```text
+//! encode + n × encoded_compute  <  n × ascii_compute
+//! ```
+//!
+//! i.e. once `n` exceeds `encode / (ascii_compute - encoded_compute)`.
+//! [`measure_encoding_amortization`] measures the three terms and
+//! [`EncodingAmortization::break_even_passes`] reports that reuse count.
+
+use anyhow::Result;
+use asbb_core::{OperationOutput, SequenceRecord};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::timing::PhaseTimer;
+
+/// Conversion cost against per-pass savings for one operation and dataset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncodingAmortization {
+    /// Operation name
+    pub operation: String,
+
+    /// Number of sequences in the dataset
+    pub num_sequences: usize,
+
+    /// Median time to encode the dataset
+    pub encode_time: Duration,
+
+    /// Median compute time on ASCII records
+    pub ascii_compute: Duration,
+
+    /// Median compute time on encoded records
+    pub encoded_compute: Duration,
+}
+
+impl EncodingAmortization {
+    /// Compute time saved per pass by the encoded kernel (negative if slower)
+    pub fn savings_per_pass(&self) -> f64 {
+        self.ascii_compute.as_secs_f64() - self.encoded_compute.as_secs_f64()
+    }
+
+    /// Passes over the encoded data before encoding pays for itself
+    ///
+    /// `None` if the encoded kernel is not faster, so no reuse count wins.
+    pub fn break_even_passes(&self) -> Option<u64> {
+        let savings = self.savings_per_pass();
+        if savings <= 0.0 {
+            return None;
+        }
+        Some((self.encode_time.as_secs_f64() / savings).floor() as u64 + 1)
+    }
+
+    /// End-to-end speedup of encode-once-then-`passes`× over ASCII `passes`×
+    pub fn end_to_end_speedup(&self, passes: u64) -> f64 {
+        let passes = passes as f64;
+        let ascii = passes * self.ascii_compute.as_secs_f64();
+        let encoded = self.encode_time.as_secs_f64() + passes * self.encoded_compute.as_secs_f64();
+        if encoded > 0.0 {
+            ascii / encoded
+        } else {
+            1.0
+        }
+    }
+
+    /// Compute-only speedup (encoding excluded, as older pilots reported)
+    pub fn compute_only_speedup(&self) -> f64 {
+        let encoded = self.encoded_compute.as_secs_f64();
+        if encoded > 0.0 {
+            self.ascii_compute.as_secs_f64() / encoded
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Measure encoding cost and ASCII/encoded compute times (medians over `runs`)
+///
+/// `encode` converts the records (e.g. to `Vec<BitSeq>`); `ascii` and
+/// `encoded` run the operation on each representation.
+pub fn measure_encoding_amortization<T, E, A, C>(
+    operation: &str,
+    data: &[SequenceRecord],
+    encode: E,
+    ascii: A,
+    encoded: C,
+    warmup_runs: usize,
+    runs: usize,
+) -> Result<EncodingAmortization>
+where
+    E: Fn(&[SequenceRecord]) -> Result<T>,
+    A: Fn(&[SequenceRecord]) -> Result<OperationOutput>,
+    C: Fn(&T) -> Result<OperationOutput>,
+{
+    let run = || -> Result<(Duration, Duration, Duration)> {
+        let mut encode_timer = PhaseTimer::new();
+        let converted = encode_timer.encode(|| encode(data))?;
+
+        let mut encoded_timer = PhaseTimer::new();
+        std::hint::black_box(encoded_timer.compute(|| encoded(&converted))?);
+
+        let mut ascii_timer = PhaseTimer::new();
+        std::hint::black_box(ascii_timer.compute(|| ascii(data))?);
+
+        Ok((
+            encode_timer.finish().encode_time,
+            ascii_timer.finish().compute_time,
+            encoded_timer.finish().compute_time,
+        ))
+    };
+
+    for _ in 0..warmup_runs {
+        run()?;
+    }
+
+    let mut encode_times = Vec::with_capacity(runs);
+    let mut ascii_times = Vec::with_capacity(runs);
+    let mut encoded_times = Vec::with_capacity(runs);
+    for _ in 0..runs.max(1) {
+        let (encode_time, ascii_time, encoded_time) = run()?;
+        encode_times.push(encode_time);
+        ascii_times.push(ascii_time);
+        encoded_times.push(encoded_time);
+    }
+
+    Ok(EncodingAmortization {
+        operation: operation.to_string(),
+        num_sequences: data.len(),
+        encode_time: median(encode_times),
+        ascii_compute: median(ascii_times),
+        encoded_compute: median(encoded_times),
+    })
+}
+
+fn median(mut durations: Vec<Duration>) -> Duration {
+    durations.sort();
+    asbb_core::stats::duration_quantile(&durations, 50.0).unwrap_or_default()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asbb_core::encoding::BitSeq;
+    use asbb_core::PrimitiveOperation;
+    use asbb_ops::base_counting::BaseCounting;
+
+    fn amortization(encode_us: u64, ascii_us: u64, encoded_us: u64) -> EncodingAmortization {
+        EncodingAmortization {
+            operation: "base_counting".to_string(),
+            num_sequences: 1000,
+            encode_time: Duration::from_micros(encode_us),
+            ascii_compute: Duration::from_micros(ascii_us),
+            encoded_compute: Duration::from_micros(encoded_us),
+        }
+    }
+
+    #[test]
+    fn test_break_even_passes() {
+        // Saves 20µs per pass; 100µs of encoding is repaid after 5 passes,
+        // so the 6th pass is the first where 2-bit is strictly ahead
+        let a = amortization(100, 50, 30);
+        assert_eq!(a.break_even_passes(), Some(6));
+        assert!(a.end_to_end_speedup(5) <= 1.0);
+        assert!(a.end_to_end_speedup(6) > 1.0);
+        assert!((a.compute_only_speedup() - 50.0 / 30.0).abs() < 1e-9);
+
+        // Slower encoded kernel never breaks even
+        assert_eq!(amortization(100, 30, 50).break_even_passes(), None);
+
+        // Free encoding wins on the first pass
+        assert_eq!(amortization(0, 50, 30).break_even_passes(), Some(1));
+    }
+
+    #[test]
+    fn test_measure_encoding_amortization() {
+        let op = BaseCounting::new();
+        let data: Vec<SequenceRecord> = (0..500)
+            .map(|i| SequenceRecord::fasta(format!("seq_{}", i), b"ACGTTGCA".repeat(20)))
+            .collect();
+
+        let result = measure_encoding_amortization(
+            op.name(),
+            &data,
+            |records| {
+                Ok(records.iter().map(|r| BitSeq::from_ascii(&r.sequence)).collect::<Vec<_>>())
+            },
+            |records| op.execute_neon(records),
+            |encoded| op.execute_2bit_neon(encoded),
+            1,
+            3,
+        )
+        .unwrap();
+
+        assert_eq!(result.num_sequences, 500);
+        assert!(result.encode_time > Duration::ZERO);
+        assert!(result.ascii_compute > Duration::ZERO);
+        assert!(result.end_to_end_speedup(1) < result.compute_only_speedup());
+    }
+}
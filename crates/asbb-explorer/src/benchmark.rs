@@ -2,6 +2,7 @@
 //!
 //! Represents a single experimental test case
 
+use asbb_core::stats::DEFAULT_LATENCY_PERCENTILES;
 use asbb_core::{DataCharacteristics, HardwareConfig};
 
 /// A single benchmark experiment
@@ -25,6 +26,9 @@ pub struct Benchmark {
 
     /// Number of measured runs
     pub measured_runs: usize,
+
+    /// Latency percentiles to report (0–100)
+    pub percentiles: Vec<f64>,
 }
 
 impl Benchmark {
@@ -42,6 +46,7 @@ impl Benchmark {
             hardware_config,
             warmup_runs: 3,
             measured_runs: 10,
+            percentiles: DEFAULT_LATENCY_PERCENTILES.to_vec(),
         }
     }
 
@@ -56,4 +61,10 @@ impl Benchmark {
         self.measured_runs = runs;
         self
     }
+
+    /// Set latency percentiles to report
+    pub fn with_percentiles(mut self, percentiles: Vec<f64>) -> Self {
+        self.percentiles = percentiles;
+        self
+    }
 }
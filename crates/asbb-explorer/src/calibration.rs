@@ -0,0 +1,148 @@
+//! Measurement-overhead calibration
+//!
+//! Operations used to build their statistics output with
+//! `serde_json::to_value` inside `execute_*`, so every benchmarked time
+//! included a JSON conversion. Operations now return typed results and JSON
+//! is produced only at the serialization boundary, outside timed regions.
+//!
+//! [`measure_serialization_overhead`] quantifies what that conversion used to
+//! cost: it times the computation alone, then times converting the same
+//! output to JSON, and reports the conversion as a fraction of compute time.
+//! That fraction is how much previously recorded timings were inflated.
+
+use anyhow::Result;
+use asbb_core::{HardwareConfig, OperationOutput, PrimitiveOperation, SequenceRecord};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Serialization cost relative to compute for one (operation, config) pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SerializationOverhead {
+    /// Operation name
+    pub operation: String,
+
+    /// Number of sequences processed per run
+    pub num_sequences: usize,
+
+    /// Median time of the computation alone
+    pub compute_p50: Duration,
+
+    /// Median time to convert the output to JSON
+    pub serialize_p50: Duration,
+}
+
+impl SerializationOverhead {
+    /// Serialization time as a percentage of compute time
+    ///
+    /// This is how much a measurement that included the conversion was
+    /// inflated (e.g. 25% means old timings were 1.25× the true compute time).
+    pub fn overhead_percent(&self) -> f64 {
+        let compute = self.compute_p50.as_secs_f64().max(f64::MIN_POSITIVE);
+        100.0 * self.serialize_p50.as_secs_f64() / compute
+    }
+
+    /// Throughput the old (serializing) measurement would have reported,
+    /// as a fraction of the true throughput
+    pub fn old_throughput_ratio(&self) -> f64 {
+        let compute = self.compute_p50.as_secs_f64();
+        let total = compute + self.serialize_p50.as_secs_f64();
+        if total > 0.0 {
+            compute / total
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Measure compute time and JSON conversion time separately
+///
+/// Outputs without a statistics form (records, counts, booleans) were never
+/// serialized inside `execute_*`, so their serialization time is zero.
+pub fn measure_serialization_overhead(
+    operation: &dyn PrimitiveOperation,
+    data: &[SequenceRecord],
+    config: &HardwareConfig,
+    warmup_runs: usize,
+    measured_runs: usize,
+) -> Result<SerializationOverhead> {
+    for _ in 0..warmup_runs {
+        let output = crate::execute_configured(operation, data, config)?;
+        serialize(&output)?;
+    }
+
+    let mut compute = Vec::with_capacity(measured_runs);
+    let mut serialization = Vec::with_capacity(measured_runs);
+
+    for _ in 0..measured_runs.max(1) {
+        let start = Instant::now();
+        let output = crate::execute_configured(operation, data, config)?;
+        compute.push(start.elapsed());
+
+        let start = Instant::now();
+        serialize(&output)?;
+        serialization.push(start.elapsed());
+    }
+
+    Ok(SerializationOverhead {
+        operation: operation.name().to_string(),
+        num_sequences: data.len(),
+        compute_p50: median(compute),
+        serialize_p50: median(serialization),
+    })
+}
+
+/// The conversion `execute_*` used to perform
+fn serialize(output: &OperationOutput) -> Result<()> {
+    std::hint::black_box(output.statistics_json()?);
+    Ok(())
+}
+
+fn median(mut durations: Vec<Duration>) -> Duration {
+    durations.sort();
+    durations[durations.len() / 2]
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asbb_ops::base_counting::BaseCounting;
+    use asbb_ops::reverse_complement::ReverseComplement;
+
+    fn test_data() -> Vec<SequenceRecord> {
+        (0..100)
+            .map(|i| SequenceRecord::fasta(format!("seq_{}", i), b"ACGTN".repeat(30)))
+            .collect()
+    }
+
+    #[test]
+    fn test_measure_serialization_overhead() {
+        let data = test_data();
+        let config = HardwareConfig::naive();
+
+        let stats = measure_serialization_overhead(&BaseCounting::new(), &data, &config, 1, 3).unwrap();
+        assert_eq!(stats.operation, "base_counting");
+        assert_eq!(stats.num_sequences, 100);
+        assert!(stats.old_throughput_ratio() <= 1.0);
+
+        // Record outputs were never converted to JSON
+        let records =
+            measure_serialization_overhead(&ReverseComplement::new(), &data, &config, 0, 3).unwrap();
+        assert!(records.serialize_p50 < Duration::from_millis(1));
+    }
+
+    #[test]
+    fn test_overhead_percent() {
+        let overhead = SerializationOverhead {
+            operation: "gc_content".to_string(),
+            num_sequences: 10,
+            compute_p50: Duration::from_micros(400),
+            serialize_p50: Duration::from_micros(100),
+        };
+        assert!((overhead.overhead_percent() - 25.0).abs() < 1e-9);
+        assert!((overhead.old_throughput_ratio() - 0.8).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,289 @@
+//! Crossover search: the record count where a candidate config takes over
+//!
+//! Scale ladders (100, 1K, 10K, ...) only bracket a crossover to within an
+//! order of magnitude. This module binary-searches the record count (on a
+//! log scale) for the point where a candidate config (e.g. 4 threads, GPU)
+//! first beats the incumbent by at least `min_gain`, assuming the advantage
+//! grows with input size.
+//!
+//! Each probe measures the throughput ratio candidate / incumbent with a 95%
+//! confidence interval. The search follows the median ratio; the interval on
+//! the crossover comes from the probes whose ratio CI excludes the target:
+//! - **Lower bound**: largest probe where the candidate significantly misses
+//!   the target
+//! - **Upper bound**: smallest probe where it significantly meets it
+
+use anyhow::Result;
+use asbb_core::stats::calculate_statistics;
+use serde::{Deserialize, Serialize};
+
+/// Default required advantage of the candidate (10%)
+pub const DEFAULT_MIN_GAIN: f64 = 0.10;
+
+/// Default search resolution: stop when the bracket spans less than 10%
+pub const DEFAULT_RESOLUTION: f64 = 1.1;
+
+/// Throughput ratio candidate / incumbent at one record count
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RatioEstimate {
+    pub median: f64,
+    pub ci_lower: f64,
+    pub ci_upper: f64,
+}
+
+impl RatioEstimate {
+    /// Estimate from per-run ratios (outliers removed as in the harnesses)
+    pub fn from_samples(ratios: &[f64], outlier_threshold: f64) -> Result<Self> {
+        let stats = calculate_statistics(ratios, outlier_threshold, 0)?;
+        Ok(Self {
+            median: stats.median,
+            ci_lower: stats.ci_95_lower,
+            ci_upper: stats.ci_95_upper,
+        })
+    }
+}
+
+/// How a probe compares to the target ratio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProbeVerdict {
+    /// CI entirely at or above the target
+    Wins,
+    /// CI entirely below the target
+    Loses,
+    /// CI straddles the target
+    Uncertain,
+}
+
+impl ProbeVerdict {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProbeVerdict::Wins => "wins",
+            ProbeVerdict::Loses => "loses",
+            ProbeVerdict::Uncertain => "uncertain",
+        }
+    }
+}
+
+/// One measured record count
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Probe {
+    pub num_records: usize,
+    pub ratio: RatioEstimate,
+    pub verdict: ProbeVerdict,
+}
+
+/// Search parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrossoverSearch {
+    /// Smallest record count considered
+    pub min_records: usize,
+
+    /// Largest record count considered (usually the dataset size)
+    pub max_records: usize,
+
+    /// Required advantage, e.g. 0.10 = candidate ≥ 1.10× incumbent
+    pub min_gain: f64,
+
+    /// Stop when `hi / lo` is at most this
+    pub resolution: f64,
+}
+
+impl CrossoverSearch {
+    pub fn new(min_records: usize, max_records: usize, min_gain: f64) -> Self {
+        Self {
+            min_records,
+            max_records,
+            min_gain,
+            resolution: DEFAULT_RESOLUTION,
+        }
+    }
+
+    /// Ratio the candidate must reach
+    pub fn target(&self) -> f64 {
+        1.0 + self.min_gain
+    }
+
+    /// Upper bound on probes (both endpoints plus the bisection steps)
+    pub fn max_probes(&self) -> usize {
+        let span = (self.max_records.max(1) as f64 / self.min_records.max(1) as f64).ln();
+        let steps = (span / self.resolution.ln()).max(1.0).log2().ceil() as usize;
+        steps + 2
+    }
+}
+
+/// Result of a crossover search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Crossover {
+    /// Target ratio (1 + min gain)
+    pub target: f64,
+
+    /// Smallest record count found where the median ratio meets the target
+    /// (`None`: not reached even at the largest size)
+    pub crossover: Option<usize>,
+
+    /// Largest probe where the candidate significantly misses the target
+    pub ci_lower: Option<usize>,
+
+    /// Smallest probe where the candidate significantly meets the target
+    pub ci_upper: Option<usize>,
+
+    /// All probes, in measurement order
+    pub probes: Vec<Probe>,
+}
+
+impl Crossover {
+    /// Crossover at or below the smallest size searched
+    pub fn wins_everywhere(&self, search: &CrossoverSearch) -> bool {
+        self.crossover == Some(search.min_records)
+    }
+}
+
+/// Binary-search the crossover; `probe(n)` measures the ratio on `n` records
+pub fn find_crossover(
+    search: &CrossoverSearch,
+    mut probe: impl FnMut(usize) -> Result<RatioEstimate>,
+) -> Result<Crossover> {
+    if search.min_records == 0 || search.min_records > search.max_records {
+        anyhow::bail!(
+            "Invalid crossover range: {}..{} records",
+            search.min_records,
+            search.max_records
+        );
+    }
+    if search.resolution <= 1.0 {
+        anyhow::bail!("Crossover resolution must be greater than 1");
+    }
+
+    let target = search.target();
+    let mut probes = Vec::new();
+    let mut measure = |n: usize, probes: &mut Vec<Probe>| -> Result<bool> {
+        let ratio = probe(n)?;
+        let verdict = if ratio.ci_lower >= target {
+            ProbeVerdict::Wins
+        } else if ratio.ci_upper < target {
+            ProbeVerdict::Loses
+        } else {
+            ProbeVerdict::Uncertain
+        };
+        probes.push(Probe {
+            num_records: n,
+            ratio,
+            verdict,
+        });
+        Ok(ratio.median >= target)
+    };
+
+    let mut lo = search.min_records;
+    let mut hi = search.max_records;
+    let crossover = if !measure(hi, &mut probes)? {
+        None
+    } else if lo == hi || measure(lo, &mut probes)? {
+        Some(lo)
+    } else {
+        // Invariant: the median misses the target at lo and meets it at hi
+        while hi as f64 / lo as f64 > search.resolution && hi - lo > 1 {
+            let mid = ((lo as f64 * hi as f64).sqrt().round() as usize).clamp(lo + 1, hi - 1);
+            if measure(mid, &mut probes)? {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        Some(hi)
+    };
+
+    let ci_lower = crossover.and_then(|point| {
+        probes
+            .iter()
+            .filter(|p| p.verdict == ProbeVerdict::Loses && p.num_records < point)
+            .map(|p| p.num_records)
+            .max()
+    });
+    let ci_upper = probes
+        .iter()
+        .filter(|p| p.verdict == ProbeVerdict::Wins && crossover.is_some_and(|point| p.num_records >= point))
+        .map(|p| p.num_records)
+        .min();
+
+    Ok(Crossover {
+        target,
+        crossover,
+        ci_lower,
+        ci_upper,
+        probes,
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ratio that steps from `below` to `above` at `at` records (CI ± 0.05)
+    fn step(at: usize, below: f64, above: f64) -> impl FnMut(usize) -> Result<RatioEstimate> {
+        move |n| {
+            let median = if n >= at { above } else { below };
+            Ok(RatioEstimate {
+                median,
+                ci_lower: median - 0.05,
+                ci_upper: median + 0.05,
+            })
+        }
+    }
+
+    #[test]
+    fn test_finds_step() {
+        let search = CrossoverSearch::new(100, 100_000, 0.10);
+        let result = find_crossover(&search, step(5_000, 0.8, 1.5)).unwrap();
+
+        let point = result.crossover.unwrap();
+        assert!(point >= 5_000 && point as f64 <= 5_000.0 * search.resolution, "{}", point);
+        assert!(result.ci_lower.unwrap() < 5_000);
+        assert_eq!(result.ci_upper, Some(point));
+        assert!(result.probes.len() <= search.max_probes());
+    }
+
+    #[test]
+    fn test_never_or_always_wins() {
+        let search = CrossoverSearch::new(100, 100_000, 0.10);
+
+        let never = find_crossover(&search, step(usize::MAX, 0.9, 0.9)).unwrap();
+        assert_eq!(never.crossover, None);
+        assert_eq!(never.probes.len(), 1);
+
+        let always = find_crossover(&search, step(0, 2.0, 2.0)).unwrap();
+        assert!(always.wins_everywhere(&search));
+        assert_eq!(always.ci_upper, Some(100));
+        assert_eq!(always.ci_lower, None);
+    }
+
+    #[test]
+    fn test_uncertain_probes_widen_interval() {
+        // Median crosses at 5000, but the CI straddles the target near it
+        let search = CrossoverSearch::new(100, 100_000, 0.10);
+        let result = find_crossover(&search, |n| {
+            let median = if n >= 5_000 { 1.12 } else { 1.08 };
+            let width = if (2_000..20_000).contains(&n) { 0.1 } else { 0.01 };
+            Ok(RatioEstimate {
+                median,
+                ci_lower: median - width,
+                ci_upper: median + width,
+            })
+        })
+        .unwrap();
+
+        assert!(result.crossover.unwrap() >= 5_000);
+        assert!(result.ci_lower.is_none_or(|lower| lower < 2_000));
+        assert!(result.ci_upper.is_none_or(|upper| upper >= 20_000));
+        assert!(result.probes.iter().any(|p| p.verdict == ProbeVerdict::Uncertain));
+    }
+
+    #[test]
+    fn test_invalid_range() {
+        let search = CrossoverSearch::new(1_000, 100, 0.10);
+        assert!(find_crossover(&search, step(500, 0.8, 1.5)).is_err());
+    }
+}
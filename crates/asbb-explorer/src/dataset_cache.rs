@@ -0,0 +1,236 @@
+//! Datasets shared across experiments
+//!
+//! A batch runs every config of an operation on the same few datasets, so
+//! loading (or generating) each dataset per experiment repeats identical
+//! work dozens of times. [`DatasetCache`] loads a dataset once per key and
+//! hands out `Arc`s to the same records; encoded variants (e.g. 2-bit
+//! `BitSeq`s) are cached per (dataset, [`Encoding`]) the same way.
+//!
+//! The first load's duration is kept with the entry, so results still
+//! report what loading the dataset costs rather than the near-zero time of
+//! a cache hit. Entries live until [`DatasetCache::clear`]: a cache over
+//! every scale holds all of them in memory at once.
+
+use anyhow::{Context, Result};
+use asbb_core::encoding::{encode_batch, BitSeq};
+use asbb_core::{Encoding, SequenceRecord};
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A cached value and what producing it cost
+#[derive(Debug)]
+pub struct Cached<T: ?Sized> {
+    pub value: Arc<T>,
+
+    /// Time of the load (or encode) that filled the entry
+    pub cost: Duration,
+
+    /// Whether an earlier call had already filled the entry
+    pub hit: bool,
+}
+
+impl<T: ?Sized> Clone for Cached<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: Arc::clone(&self.value),
+            cost: self.cost,
+            hit: self.hit,
+        }
+    }
+}
+
+/// One entry, filled by the first caller (others wait on its lock)
+type Slot<T> = Arc<Mutex<Option<(Arc<T>, Duration)>>>;
+
+type AnyValue = dyn Any + Send + Sync;
+
+/// Cache hit/miss counts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+/// Loaded datasets keyed by path (or scale), and their encoded variants
+#[derive(Default)]
+pub struct DatasetCache {
+    records: Mutex<HashMap<String, Slot<Vec<SequenceRecord>>>>,
+    encoded: Mutex<HashMap<(String, Encoding), Slot<AnyValue>>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl DatasetCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records for `key`, calling `load` only if no earlier call loaded them
+    ///
+    /// Concurrent callers with the same key wait for one load; different
+    /// keys load in parallel. A failed load leaves the entry empty, so the
+    /// next call retries.
+    pub fn records(
+        &self,
+        key: &str,
+        load: impl FnOnce() -> Result<Vec<SequenceRecord>>,
+    ) -> Result<Cached<Vec<SequenceRecord>>> {
+        let slot = Arc::clone(self.records.lock().unwrap().entry(key.to_string()).or_default());
+        self.fill(&slot, || load().map(Arc::new))
+    }
+
+    /// `encoding` of the records cached under `key`
+    ///
+    /// `encode` receives the records and runs once per (key, encoding). The
+    /// records must already be cached (see [`records`](Self::records)).
+    pub fn encoded<T: Send + Sync + 'static>(
+        &self,
+        key: &str,
+        encoding: Encoding,
+        encode: impl FnOnce(&[SequenceRecord]) -> Result<T>,
+    ) -> Result<Cached<T>> {
+        let records = self
+            .cached_records(key)
+            .with_context(|| format!("Dataset {} is not cached", key))?;
+        let slot = Arc::clone(
+            self.encoded
+                .lock()
+                .unwrap()
+                .entry((key.to_string(), encoding))
+                .or_default(),
+        );
+        let cached = self.fill(&slot, || encode(&records).map(|value| Arc::new(value) as Arc<AnyValue>))?;
+        let value = cached.value.downcast::<T>().map_err(|_| {
+            anyhow::anyhow!("Dataset {} is cached as {:?} with a different type", key, encoding)
+        })?;
+        Ok(Cached {
+            value,
+            cost: cached.cost,
+            hit: cached.hit,
+        })
+    }
+
+    /// 2-bit encoding of the records cached under `key`
+    pub fn two_bit(&self, key: &str, num_threads: usize) -> Result<Cached<Vec<BitSeq>>> {
+        self.encoded(key, Encoding::TwoBit, |records| Ok(encode_batch(records, num_threads)))
+    }
+
+    /// Cached records for `key`, if loaded
+    fn cached_records(&self, key: &str) -> Option<Arc<Vec<SequenceRecord>>> {
+        let slot = Arc::clone(self.records.lock().unwrap().get(key)?);
+        let entry = slot.lock().unwrap();
+        entry.as_ref().map(|(records, _)| Arc::clone(records))
+    }
+
+    /// Fill `slot` with `produce` unless it is already filled
+    fn fill<T: ?Sized>(
+        &self,
+        slot: &Slot<T>,
+        produce: impl FnOnce() -> Result<Arc<T>>,
+    ) -> Result<Cached<T>> {
+        let mut entry = slot.lock().unwrap();
+        if let Some((value, cost)) = entry.as_ref() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(Cached {
+                value: Arc::clone(value),
+                cost: *cost,
+                hit: true,
+            });
+        }
+
+        let start = Instant::now();
+        let value = produce()?;
+        let cost = start.elapsed();
+        *entry = Some((Arc::clone(&value), cost));
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        Ok(Cached {
+            value,
+            cost,
+            hit: false,
+        })
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Number of cached datasets (not counting encoded variants)
+    pub fn len(&self) -> usize {
+        self.records.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every cached dataset and encoding
+    pub fn clear(&self) {
+        self.records.lock().unwrap().clear();
+        self.encoded.lock().unwrap().clear();
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dataset() -> Vec<SequenceRecord> {
+        (0..4)
+            .map(|i| SequenceRecord::fasta(format!("seq_{}", i), b"ACGTACGT".to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_records_loaded_once() {
+        let cache = DatasetCache::new();
+        let mut loads = 0;
+
+        let first = cache
+            .records("small", || {
+                loads += 1;
+                Ok(dataset())
+            })
+            .unwrap();
+        let second = cache.records("small", || unreachable!()).unwrap();
+
+        assert_eq!(loads, 1);
+        assert!(!first.hit);
+        assert!(second.hit);
+        assert!(Arc::ptr_eq(&first.value, &second.value));
+        assert_eq!(second.cost, first.cost);
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1 });
+
+        // Failed loads are retried
+        assert!(cache.records("broken", || anyhow::bail!("missing")).is_err());
+        assert!(cache.records("broken", || Ok(dataset())).is_ok());
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_encoded_variants() {
+        let cache = DatasetCache::new();
+        assert!(cache.two_bit("small", 1).is_err());
+
+        cache.records("small", || Ok(dataset())).unwrap();
+        let encoded = cache.two_bit("small", 1).unwrap();
+        assert_eq!(encoded.value.len(), 4);
+        assert_eq!(encoded.value[0].to_ascii(), b"ACGTACGT");
+        assert!(cache.two_bit("small", 1).unwrap().hit);
+
+        // Same (key, encoding) requested as another type
+        assert!(cache.encoded("small", Encoding::TwoBit, |_| Ok(0usize)).is_err());
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
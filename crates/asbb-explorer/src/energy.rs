@@ -0,0 +1,368 @@
+//! Energy measurement for efficiency experiments
+//!
+//! An [`EnergyMeter`] reports the joules the CPU consumed between
+//! [`start`](EnergyMeter::start) and [`stop`](EnergyMeter::stop). Backends:
+//!
+//! - **macOS**: `powermetrics --samplers cpu_power` runs alongside the
+//!   harness (it requires root, so run the harness with `sudo`). Each sample
+//!   is the average CPU power over its interval; the energy of a window is
+//!   the sum of sample power × the part of its interval inside the window.
+//! - **Linux**: RAPL package counters under `/sys/class/powercap`, for
+//!   developing energy experiments off Apple Silicon.
+//!
+//! Both measure the whole CPU, not the process: background activity is
+//! included, so compare configs measured back to back on a quiet machine,
+//! over windows long enough to span several samples.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Default `powermetrics` sampling interval
+pub const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Measures CPU energy over a window
+pub trait EnergyMeter {
+    /// Backend name for logs (e.g. `powermetrics`)
+    fn name(&self) -> &'static str;
+
+    /// Begin a measurement window
+    fn start(&mut self) -> Result<()>;
+
+    /// End the window started by [`start`](Self::start), returning joules
+    fn stop(&mut self) -> Result<f64>;
+}
+
+/// Energy meter for this platform, if one is available
+pub fn detect() -> Result<Box<dyn EnergyMeter>> {
+    if cfg!(target_os = "macos") {
+        Ok(Box::new(PowermetricsMeter::spawn(DEFAULT_SAMPLE_INTERVAL)?))
+    } else if cfg!(target_os = "linux") {
+        Ok(Box::new(RaplMeter::open()?))
+    } else {
+        anyhow::bail!("Energy measurement requires macOS (powermetrics) or Linux (RAPL)")
+    }
+}
+
+// ============================================================================
+// powermetrics (macOS)
+// ============================================================================
+
+/// One `powermetrics` sample: average power over the interval ending `at`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerSample {
+    /// When the sample was received (end of its interval)
+    pub at: Instant,
+
+    /// Length of the sampled interval
+    pub interval: Duration,
+
+    /// Average CPU power over the interval (milliwatts)
+    pub cpu_milliwatts: f64,
+}
+
+/// CPU energy from `powermetrics` samples
+pub struct PowermetricsMeter {
+    child: Child,
+    samples: Arc<Mutex<Vec<PowerSample>>>,
+    interval: Duration,
+    window_start: Option<Instant>,
+}
+
+impl PowermetricsMeter {
+    /// Start `powermetrics` and wait for its first sample
+    pub fn spawn(interval: Duration) -> Result<Self> {
+        let mut child = Command::new("powermetrics")
+            .args(["--samplers", "cpu_power", "-i"])
+            .arg(interval.as_millis().max(1).to_string())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run powermetrics")?;
+
+        let stdout = child.stdout.take().context("powermetrics has no stdout")?;
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&samples);
+        thread::spawn(move || {
+            let mut parser = PowermetricsParser::default();
+            for line in BufReader::new(stdout).lines().map_while(|line| line.ok()) {
+                if let Some(sample) = parser.feed(&line, Instant::now()) {
+                    sink.lock().unwrap_or_else(|e| e.into_inner()).push(sample);
+                }
+            }
+        });
+
+        let mut meter = Self {
+            child,
+            samples,
+            interval,
+            window_start: None,
+        };
+
+        let deadline = Instant::now() + interval * 3 + Duration::from_secs(2);
+        while meter.latest_sample().is_none() {
+            if let Some(status) = meter.child.try_wait()? {
+                let mut stderr = String::new();
+                if let Some(mut pipe) = meter.child.stderr.take() {
+                    pipe.read_to_string(&mut stderr).ok();
+                }
+                anyhow::bail!(
+                    "powermetrics exited ({}): {} (it must run as root, e.g. with sudo)",
+                    status,
+                    stderr.trim()
+                );
+            }
+            if Instant::now() > deadline {
+                anyhow::bail!("powermetrics produced no CPU power samples");
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+
+        Ok(meter)
+    }
+
+    fn latest_sample(&self) -> Option<Instant> {
+        let samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        samples.last().map(|sample| sample.at)
+    }
+}
+
+impl EnergyMeter for PowermetricsMeter {
+    fn name(&self) -> &'static str {
+        "powermetrics"
+    }
+
+    fn start(&mut self) -> Result<()> {
+        self.window_start = Some(Instant::now());
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<f64> {
+        let stop = Instant::now();
+        let start = self.window_start.take().context("Energy window was not started")?;
+
+        // The sample covering the end of the window arrives up to one
+        // interval later
+        let deadline = stop + self.interval * 3 + Duration::from_secs(1);
+        while self.latest_sample().is_none_or(|at| at < stop) {
+            if Instant::now() > deadline {
+                anyhow::bail!("powermetrics stopped producing samples");
+            }
+            thread::sleep(self.interval / 4);
+        }
+
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        let joules = integrate_samples(&samples, start, stop);
+        // Keep the sample that may also overlap the next window
+        let keep_from = samples.len().saturating_sub(1);
+        samples.drain(..keep_from);
+        Ok(joules)
+    }
+}
+
+impl Drop for PowermetricsMeter {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Joules in `[start, stop]`, weighting each sample by its overlap with it
+pub fn integrate_samples(samples: &[PowerSample], start: Instant, stop: Instant) -> f64 {
+    samples
+        .iter()
+        .map(|sample| {
+            let sample_start = sample.at.checked_sub(sample.interval).unwrap_or(sample.at);
+            let overlap_start = sample_start.max(start);
+            let overlap_end = sample.at.min(stop);
+            let overlap = overlap_end.saturating_duration_since(overlap_start);
+            sample.cpu_milliwatts / 1000.0 * overlap.as_secs_f64()
+        })
+        .sum()
+}
+
+/// Incremental parser for `powermetrics` text output
+///
+/// Each sample starts with a header such as
+/// `*** Sampled system activity (...) (102.53ms elapsed) ***` and reports
+/// `CPU Power: 1234 mW`.
+#[derive(Debug, Default)]
+pub struct PowermetricsParser {
+    interval: Option<Duration>,
+}
+
+impl PowermetricsParser {
+    /// Consume one output line received at `at`; returns a completed sample
+    pub fn feed(&mut self, line: &str, at: Instant) -> Option<PowerSample> {
+        let line = line.trim();
+        if line.starts_with("*** Sampled system activity") {
+            self.interval = line
+                .rsplit_once("ms elapsed)")
+                .and_then(|(head, _)| head.rsplit_once('('))
+                .and_then(|(_, ms)| ms.trim().parse::<f64>().ok())
+                .map(|ms| Duration::from_secs_f64(ms / 1000.0));
+            return None;
+        }
+
+        let milliwatts = line
+            .strip_prefix("CPU Power:")?
+            .trim()
+            .strip_suffix("mW")?
+            .trim()
+            .parse::<f64>()
+            .ok()?;
+        Some(PowerSample {
+            at,
+            interval: self.interval.take()?,
+            cpu_milliwatts: milliwatts,
+        })
+    }
+}
+
+// ============================================================================
+// RAPL (Linux)
+// ============================================================================
+
+const POWERCAP_ROOT: &str = "/sys/class/powercap";
+
+/// CPU package energy from RAPL counters
+pub struct RaplMeter {
+    /// Package zones: (energy counter path, counter range in µJ)
+    zones: Vec<(PathBuf, u64)>,
+    window_start: Option<Vec<u64>>,
+}
+
+impl RaplMeter {
+    /// Find the package zones (`intel-rapl:N`, not their subzones)
+    pub fn open() -> Result<Self> {
+        let entries = fs::read_dir(POWERCAP_ROOT)
+            .with_context(|| format!("No RAPL counters ({} not readable)", POWERCAP_ROOT))?;
+
+        let mut zones = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            if name.starts_with("intel-rapl:") && name.matches(':').count() == 1 {
+                let range = read_counter(&path.join("max_energy_range_uj"))?;
+                zones.push((path.join("energy_uj"), range));
+            }
+        }
+        zones.sort();
+
+        if zones.is_empty() {
+            anyhow::bail!("No RAPL package zones under {}", POWERCAP_ROOT);
+        }
+        let meter = Self {
+            zones,
+            window_start: None,
+        };
+        meter.read_all().context("RAPL counters are not readable (try running as root)")?;
+        Ok(meter)
+    }
+
+    fn read_all(&self) -> Result<Vec<u64>> {
+        self.zones.iter().map(|(path, _)| read_counter(path)).collect()
+    }
+}
+
+impl EnergyMeter for RaplMeter {
+    fn name(&self) -> &'static str {
+        "rapl"
+    }
+
+    fn start(&mut self) -> Result<()> {
+        self.window_start = Some(self.read_all()?);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<f64> {
+        let end = self.read_all()?;
+        let start = self.window_start.take().context("Energy window was not started")?;
+        let microjoules: u64 = self
+            .zones
+            .iter()
+            .zip(start.iter().zip(&end))
+            .map(|((_, range), (&start, &end))| counter_delta(start, end, *range))
+            .sum();
+        Ok(microjoules as f64 / 1e6)
+    }
+}
+
+fn read_counter(path: &Path) -> Result<u64> {
+    let text = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    text.trim()
+        .parse()
+        .with_context(|| format!("Invalid counter in {}", path.display()))
+}
+
+/// Counter increase from `start` to `end`, allowing for one wraparound
+pub fn counter_delta(start: u64, end: u64, range: u64) -> u64 {
+    if end >= start {
+        end - start
+    } else {
+        range.saturating_sub(start) + end
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_powermetrics_sample() {
+        let now = Instant::now();
+        let mut parser = PowermetricsParser::default();
+
+        let header = "*** Sampled system activity (Wed Oct 16 10:00:00 2026 -0700) (102.50ms elapsed) ***";
+        assert_eq!(parser.feed(header, now), None);
+        assert_eq!(parser.feed("E-Cluster HW active frequency: 1020 MHz", now), None);
+
+        let sample = parser.feed("CPU Power: 1500 mW", now).unwrap();
+        assert_eq!(sample.cpu_milliwatts, 1500.0);
+        assert_eq!(sample.interval, Duration::from_micros(102_500));
+
+        // Combined power is not CPU power, and a sample needs its own header
+        assert_eq!(parser.feed("Combined Power (CPU + GPU + ANE): 1600 mW", now), None);
+        assert_eq!(parser.feed("CPU Power: 1400 mW", now), None);
+    }
+
+    #[test]
+    fn test_integrate_partial_overlap() {
+        let base = Instant::now();
+        let interval = Duration::from_millis(100);
+        let samples: Vec<PowerSample> = [(100, 1000.0), (200, 2000.0), (300, 4000.0)]
+            .iter()
+            .map(|&(ms, milliwatts)| PowerSample {
+                at: base + Duration::from_millis(ms),
+                interval,
+                cpu_milliwatts: milliwatts,
+            })
+            .collect();
+
+        // 50 ms at 1 W + 100 ms at 2 W + 50 ms at 4 W
+        let joules = integrate_samples(
+            &samples,
+            base + Duration::from_millis(50),
+            base + Duration::from_millis(250),
+        );
+        assert!((joules - 0.45).abs() < 1e-9);
+
+        // Window before any sample
+        assert_eq!(integrate_samples(&samples[1..], base, base + Duration::from_millis(50)), 0.0);
+    }
+
+    #[test]
+    fn test_counter_delta_wraparound() {
+        assert_eq!(counter_delta(100, 350, 1000), 250);
+        assert_eq!(counter_delta(900, 50, 1000), 150);
+    }
+}
@@ -7,10 +7,19 @@
 //!
 //! 1. **Configuration Loading**: Parse config.toml (TOML → ExperimentConfig)
 //! 2. **Experiment Generation**: Cartesian product of operations × configs × scales
-//! 3. **Parallel Execution**: Rayon thread pool (8 concurrent experiments)
-//! 4. **Checkpointing**: Save progress every 100 experiments (resume capability)
-//! 5. **Result Storage**: Store in Parquet format (efficient columnar storage)
-//! 6. **Progress Tracking**: indicatif progress bars
+//!    (× parser backends for I/O operations that list `parsers`)
+//! 3. **Scheduling**: [`SchedulingPolicy`] — one experiment at a time for
+//!    timing, concurrent for functional sweeps, or concurrent data generation
+//!    with exclusive measurement
+//! 4. **Dataset Caching**: each generated dataset is shared by every config
+//!    that runs on it ([`crate::dataset_cache`], `execution.cache_datasets`)
+//! 5. **Checkpointing**: Save results and progress every 100 experiments and
+//!    on Ctrl-C (resume capability; see [`crate::interrupt`]), plus a
+//!    reproducibility manifest ([`crate::reproducibility`])
+//! 6. **Result Storage**: Store in Parquet format (efficient columnar storage)
+//! 7. **Progress Tracking**: indicatif progress bars
+//! 8. **Logging**: `tracing` events inside an `experiment` span carrying the
+//!    id, operation, config and scale (see [`crate::logging`])
 //!
 //! # Usage
 //!
@@ -20,16 +29,29 @@
 //! ```
 
 use anyhow::{Context, Result};
+use asbb_core::io::ParserBackend;
 use asbb_core::operation_registry::OperationRegistry;
+use asbb_core::stats::{calculate_statistics_or_summary, DEFAULT_OUTLIER_THRESHOLD};
 use asbb_core::{
-    HardwareConfig, QualityOfService, SequenceRecord, ThreadAssignment,
+    HardwareConfig, HardwareProfile, PhaseTimings, PrimitiveOperation, QualityOfService,
+    SequenceRecord, ThreadAssignment,
 };
 use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, error, info, info_span, warn};
+
+use crate::dataset_cache::DatasetCache;
+use crate::interrupt::{is_interrupted, Interrupt};
+use crate::plan::{format_duration, DurationEstimator, ExperimentPlan, PriorTiming};
+use crate::reproducibility::RunManifest;
+use crate::sweep::HardwareSweep;
+use crate::timing::PhaseTimer;
 
 // ============================================================================
 // Configuration Types (matches config.toml structure)
@@ -85,11 +107,34 @@ pub struct OperationConfigEntry {
     pub backends: Vec<String>,
     #[serde(default)]
     pub description: Option<String>,
+    /// Parser backends to cross the operation with (`scalar`, `neon`; only
+    /// operations that parse FASTQ text accept them)
+    #[serde(default)]
+    pub parsers: Vec<String>,
 }
 
+/// Hardware configs: explicit entries plus sweeps expanded into entries
 #[derive(Debug, Clone, Deserialize)]
 pub struct HardwareConfigList {
+    #[serde(default)]
     pub configs: Vec<HardwareConfigEntry>,
+    #[serde(default)]
+    pub sweeps: Vec<HardwareSweep>,
+}
+
+impl HardwareConfigList {
+    /// Expand sweeps into `configs` (after the explicit entries)
+    pub fn resolve(&mut self) -> Result<()> {
+        for sweep in std::mem::take(&mut self.sweeps) {
+            self.configs.extend(sweep.expand()?);
+        }
+
+        let mut ids = HashSet::new();
+        if let Some(duplicate) = self.configs.iter().find(|c| !ids.insert(c.id.as_str())) {
+            anyhow::bail!("Hardware config '{}' is defined more than once", duplicate.id);
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -103,18 +148,130 @@ pub struct HardwareConfigEntry {
     pub use_gpu: bool,
     #[serde(default)]
     pub gpu_batch_size: Option<usize>,
+    /// Records per work unit: chunk size of multi-threaded CPU runs (which
+    /// then use chunked execution), and the GPU dispatch size when
+    /// `gpu_batch_size` is unset
+    #[serde(default)]
+    pub batch_size: Option<usize>,
     #[serde(default)]
     pub use_2bit: bool,
+    /// Thread QoS class (`user_interactive`, `user_initiated`, `default`,
+    /// `utility`, `background`)
+    #[serde(default = "default_qos")]
+    pub qos: String,
+}
+
+fn default_qos() -> String {
+    "user_initiated".to_string()
+}
+
+/// Parse a QoS class name as used in config files
+pub fn parse_qos(name: &str) -> Result<QualityOfService> {
+    Ok(match name {
+        "user_interactive" => QualityOfService::UserInteractive,
+        "user_initiated" => QualityOfService::UserInitiated,
+        "default" => QualityOfService::Default,
+        "utility" => QualityOfService::Utility,
+        "background" => QualityOfService::Background,
+        _ => anyhow::bail!("Unknown QoS class: '{}'", name),
+    })
+}
+
+impl HardwareConfigEntry {
+    /// Hardware configuration this entry describes
+    pub fn to_hardware_config(&self) -> Result<HardwareConfig> {
+        let thread_assignment = match self.thread_assignment.as_str() {
+            "default" => ThreadAssignment::Mixed,
+            "p_cores" => ThreadAssignment::PCoresOnly,
+            "e_cores" => ThreadAssignment::ECoresOnly,
+            "mixed" | "mixed_2p2e" | "mixed_4p6e" => ThreadAssignment::Mixed,
+            _ => ThreadAssignment::Mixed,
+        };
+        if self.batch_size == Some(0) || self.gpu_batch_size == Some(0) {
+            anyhow::bail!("Hardware config '{}': batch size must be positive", self.id);
+        }
+        let parallel_strategy = match self.batch_size {
+            Some(_) => asbb_core::ParallelStrategy::Chunked,
+            None => asbb_core::ParallelStrategy::PerRecord,
+        };
+
+        Ok(HardwareConfig {
+            use_neon: self.use_neon,
+            num_threads: self.num_threads,
+            thread_assignment,
+            parallel_strategy,
+            batch_size: self.batch_size,
+            encoding: asbb_core::Encoding::Ascii, // TODO: Support from config
+            use_unified_memory: self.use_gpu, // If GPU, use unified memory
+            use_gpu: self.use_gpu,
+            gpu_batch_size: self.gpu_batch_size.or(self.batch_size),
+            use_amx: false, // Not yet implemented
+            use_neural_engine: false, // Not yet implemented
+            use_m5_gpu_neural_accel: false, // Not yet implemented
+            use_hw_compression: false, // Not yet implemented
+            use_gcd: false, // Use Rayon instead
+            qos: parse_qos(&self.qos).with_context(|| format!("Hardware config '{}'", self.id))?,
+            chip_generation: None,
+        })
+    }
+}
+
+/// How experiments share the machine
+///
+/// Concurrent experiments compete for cores, memory bandwidth and caches, so
+/// their timings interfere; only exclusive measurement yields valid
+/// per-experiment timing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchedulingPolicy {
+    /// One experiment at a time (valid timing)
+    #[default]
+    Exclusive,
+    /// `parallel_experiments` at once (functional sweeps; timings interfere)
+    Concurrent,
+    /// Generate data for up to `parallel_experiments` concurrently, then
+    /// measure them one at a time
+    Hybrid,
+}
+
+impl SchedulingPolicy {
+    /// Policy of result rows written before the policy was recorded
+    /// (the engine always ran experiments concurrently)
+    fn legacy() -> Self {
+        SchedulingPolicy::Concurrent
+    }
+
+    /// Whether timings from this policy are free of cross-experiment interference
+    pub fn timing_is_exclusive(&self) -> bool {
+        !matches!(self, SchedulingPolicy::Concurrent)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct ExecutionSettings {
+    #[serde(default)]
+    pub scheduling: SchedulingPolicy,
     pub parallel_experiments: usize,
     pub checkpoint_interval: usize,
     pub timeout_seconds: u64,
     pub warmup_runs: usize,
     pub measurement_runs: usize,
     pub validate_correctness: bool,
+    /// IQR multiplier for outlier removal (same method as DAG traversal)
+    #[serde(default = "default_outlier_threshold")]
+    pub outlier_threshold: f64,
+    /// Generate each dataset once and share it across configs (holds every
+    /// scale in memory for the whole run)
+    #[serde(default = "default_cache_datasets")]
+    pub cache_datasets: bool,
+}
+
+fn default_outlier_threshold() -> f64 {
+    DEFAULT_OUTLIER_THRESHOLD
+}
+
+fn default_cache_datasets() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -156,6 +313,33 @@ pub struct Experiment {
 
     /// Number of sequences in dataset
     pub num_sequences: usize,
+
+    /// Parser backend, for operations crossed with `parsers`
+    #[serde(default)]
+    pub parser: Option<String>,
+}
+
+impl Experiment {
+    /// Span that log events from this experiment are recorded under
+    pub fn span(&self) -> tracing::Span {
+        info_span!(
+            "experiment",
+            id = %self.id,
+            operation = %self.operation,
+            config = %self.hardware_config_id,
+            scale = %self.scale,
+            parser = self.parser.as_deref(),
+        )
+    }
+}
+
+/// An experiment with its data generated, ready to measure
+struct PreparedExperiment {
+    experiment: Experiment,
+    operation: Arc<dyn PrimitiveOperation>,
+    data: Arc<Vec<SequenceRecord>>,
+    hw_config: HardwareConfig,
+    load_time: Duration,
 }
 
 /// Result from a single experiment
@@ -173,6 +357,10 @@ pub struct ExperimentResult {
     /// Operation complexity score
     pub operation_complexity: f64,
 
+    /// Parser backend (I/O operations crossed with `parsers`)
+    #[serde(default)]
+    pub parser: Option<String>,
+
     /// Hardware configuration ID
     pub hardware_config_id: String,
 
@@ -188,15 +376,124 @@ pub struct ExperimentResult {
     /// Sequence length
     pub sequence_length: usize,
 
-    /// Mean execution time (seconds)
+    /// Mean execution time (seconds, after outlier removal)
     pub mean_time_seconds: f64,
 
-    /// Median execution time (seconds)
+    /// Median execution time (seconds, after outlier removal)
     pub median_time_seconds: f64,
 
-    /// Standard deviation of execution time
+    /// Standard deviation of execution time (after outlier removal)
     pub std_time_seconds: f64,
 
+    // === Throughput Statistics (sequences/second) ===
+    /// Median throughput (robust to outliers)
+    #[serde(default)]
+    pub throughput_median: f64,
+
+    /// Mean throughput
+    #[serde(default)]
+    pub throughput_mean: f64,
+
+    /// Throughput standard deviation
+    #[serde(default)]
+    pub throughput_std_dev: f64,
+
+    /// Throughput 95% CI lower bound
+    #[serde(default)]
+    pub throughput_ci_lower: f64,
+
+    /// Throughput 95% CI upper bound
+    #[serde(default)]
+    pub throughput_ci_upper: f64,
+
+    // === Speedup Statistics (vs the operation's baseline at this scale) ===
+    // Filled when results are saved; 0 if the baseline has not run
+    /// Median speedup vs baseline
+    #[serde(default)]
+    pub speedup_median: f64,
+
+    /// Mean speedup vs baseline
+    #[serde(default)]
+    pub speedup_mean: f64,
+
+    /// Speedup standard deviation
+    #[serde(default)]
+    pub speedup_std_dev: f64,
+
+    /// Speedup 95% CI lower bound
+    #[serde(default)]
+    pub speedup_ci_lower: f64,
+
+    /// Speedup 95% CI upper bound
+    #[serde(default)]
+    pub speedup_ci_upper: f64,
+
+    // === Elapsed Time Statistics (seconds) ===
+    /// Median elapsed time
+    #[serde(default)]
+    pub elapsed_median: f64,
+
+    /// Mean elapsed time
+    #[serde(default)]
+    pub elapsed_mean: f64,
+
+    /// Elapsed time standard deviation
+    #[serde(default)]
+    pub elapsed_std_dev: f64,
+
+    /// Min elapsed time
+    #[serde(default)]
+    pub elapsed_min: f64,
+
+    /// Max elapsed time
+    #[serde(default)]
+    pub elapsed_max: f64,
+
+    /// Elapsed time Q1 (25th percentile)
+    #[serde(default)]
+    pub elapsed_q1: f64,
+
+    /// Elapsed time Q3 (75th percentile)
+    #[serde(default)]
+    pub elapsed_q3: f64,
+
+    /// Elapsed time IQR
+    #[serde(default)]
+    pub elapsed_iqr: f64,
+
+    // === Sample Statistics ===
+    /// Number of valid measurements (after outlier removal)
+    #[serde(default)]
+    pub n_valid: usize,
+
+    /// Number of outliers removed
+    #[serde(default)]
+    pub n_outliers: usize,
+
+    /// Number of warmup runs
+    #[serde(default)]
+    pub n_warmup: usize,
+
+    /// Input generation time (seconds, once per experiment)
+    #[serde(default)]
+    pub load_time_seconds: f64,
+
+    /// Encoding conversion time (seconds, median per run)
+    #[serde(default)]
+    pub encode_time_seconds: f64,
+
+    /// Compute time (seconds, median per run; basis for throughput)
+    #[serde(default)]
+    pub compute_time_seconds: f64,
+
+    /// Output serialization time (seconds, median per run)
+    #[serde(default)]
+    pub serialize_time_seconds: f64,
+
+    /// Load + encode + compute + serialize for a single pass (seconds)
+    #[serde(default)]
+    pub end_to_end_time_seconds: f64,
+
     /// Throughput (sequences/second)
     pub throughput_seqs_per_sec: f64,
 
@@ -221,6 +518,10 @@ pub struct ExperimentResult {
     /// Output matches reference (correctness)
     pub correct: bool,
 
+    /// Scheduling policy the experiment ran under
+    #[serde(default = "SchedulingPolicy::legacy")]
+    pub scheduling: SchedulingPolicy,
+
     /// Timestamp
     pub timestamp: String,
 }
@@ -298,6 +599,31 @@ pub struct ExecutionEngine {
 
     /// Output directory
     output_dir: PathBuf,
+
+    /// Stops the run between experiments
+    interrupt: Interrupt,
+
+    /// Generated datasets shared across configs
+    datasets: DatasetCache,
+
+    /// Config file the engine was loaded from (recorded in the run manifest)
+    config_path: Option<PathBuf>,
+
+    /// Machine the hardware configs must fit (`None`: not detected, unchecked)
+    hardware: Option<HardwareProfile>,
+}
+
+/// Results file in the output directory
+const RESULTS_FILE: &str = "results.json";
+
+/// How [`ExecutionEngine::run_all`] ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunStatus {
+    /// Every experiment has a result
+    Complete,
+    /// Stopped by an interrupt; results and checkpoint are flushed and the
+    /// next run resumes from them
+    Interrupted { completed: usize, remaining: usize },
 }
 
 impl ExecutionEngine {
@@ -311,11 +637,15 @@ impl ExecutionEngine {
         let config: ExperimentConfig = toml::from_str(&config_str)
             .context("Failed to parse config TOML")?;
 
-        Self::from_config(config, registry)
+        let mut engine = Self::from_config(config, registry)?;
+        engine.config_path = Some(config_path.as_ref().to_path_buf());
+        Ok(engine)
     }
 
     /// Create engine from config struct
-    pub fn from_config(config: ExperimentConfig, registry: OperationRegistry) -> Result<Self> {
+    pub fn from_config(mut config: ExperimentConfig, registry: OperationRegistry) -> Result<Self> {
+        config.hardware.resolve()?;
+
         // Generate all experiments
         let experiments = Self::generate_experiments(&config)?;
         let total = experiments.len();
@@ -332,16 +662,111 @@ impl ExecutionEngine {
             Checkpoint::new(total)
         };
 
+        // Keep the results of checkpointed experiments when resuming
+        let results_path = output_dir.join(RESULTS_FILE);
+        let results = if checkpoint_path.exists() && results_path.exists() {
+            let previous: Vec<ExperimentResult> = serde_json::from_str(&fs::read_to_string(&results_path)?)
+                .with_context(|| format!("Failed to read previous results from {}", results_path.display()))?;
+            previous
+                .into_iter()
+                .filter(|result| checkpoint.is_completed(&result.experiment_id))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
         Ok(Self {
             config,
             registry: Arc::new(registry),
             experiments,
-            results: Arc::new(Mutex::new(Vec::new())),
+            results: Arc::new(Mutex::new(results)),
             checkpoint: Arc::new(Mutex::new(checkpoint)),
             output_dir,
+            interrupt: Interrupt::default(),
+            datasets: DatasetCache::new(),
+            config_path: None,
+            hardware: HardwareProfile::detect().ok(),
         })
     }
 
+    /// Stop between experiments when `interrupt` fires
+    pub fn with_interrupt(mut self, interrupt: Interrupt) -> Self {
+        self.interrupt = interrupt;
+        self
+    }
+
+    /// Validate hardware configs against `hardware` instead of the detected
+    /// machine (`None` skips validation)
+    pub fn with_hardware(mut self, hardware: Option<HardwareProfile>) -> Self {
+        self.hardware = hardware;
+        self
+    }
+
+    /// Check every hardware config against the machine
+    ///
+    /// A config the machine cannot run (more threads than cores, a missing
+    /// accelerator) would otherwise fall back to another backend and record
+    /// its timing under the config's id.
+    pub fn validate_hardware(&self) -> Result<()> {
+        let Some(profile) = &self.hardware else {
+            return Ok(());
+        };
+        for entry in &self.config.hardware.configs {
+            entry
+                .to_hardware_config()?
+                .validate(profile)
+                .with_context(|| format!("Hardware config '{}'", entry.id))?;
+        }
+        Ok(())
+    }
+
+    /// Experiments `run_all` would run, with estimated durations
+    ///
+    /// Completed experiments (per the checkpoint) are excluded. Estimates
+    /// come from the `results.json` of earlier runs in the output directory.
+    pub fn plan(&self) -> Result<ExperimentPlan> {
+        let results_path = self.output_dir.join("results.json");
+        let priors = if results_path.exists() {
+            prior_timings(&results_path)?
+        } else {
+            Vec::new()
+        };
+        let estimator = DurationEstimator::new(priors);
+
+        let checkpoint = self.checkpoint.lock().unwrap();
+        let runs = self.config.execution.warmup_runs + self.config.execution.measurement_runs;
+        let mut plan = ExperimentPlan::default();
+        for experiment in self.experiments.iter().filter(|e| !checkpoint.is_completed(&e.id)) {
+            plan.push(
+                &estimator,
+                &experiment.operation,
+                &experiment.hardware_config_id,
+                &experiment.scale,
+                experiment.num_sequences,
+                runs,
+            );
+        }
+
+        Ok(plan)
+    }
+
+    /// Print the experiments `run_all` would run and an ETA, without running them
+    pub fn dry_run(&self) -> Result<()> {
+        let plan = self.plan()?;
+        plan.print();
+
+        if self.config.execution.scheduling == SchedulingPolicy::Concurrent {
+            let workers = self.config.execution.parallel_experiments.max(1) as u32;
+            println!(
+                "   Concurrent scheduling: ~{} wall time across {} workers",
+                format_duration(plan.estimated_duration() / workers),
+                workers
+            );
+        }
+
+        Ok(())
+    }
+
     /// Generate all experiment combinations
     fn generate_experiments(config: &ExperimentConfig) -> Result<Vec<Experiment>> {
         let mut experiments = Vec::new();
@@ -356,16 +781,29 @@ impl ExecutionEngine {
             .collect();
 
         for operation in &implemented_ops {
-            for hardware in &config.hardware.configs {
-                for scale in &config.datasets.scales {
-                    experiment_id += 1;
-                    experiments.push(Experiment {
-                        id: format!("exp_{:06}", experiment_id),
-                        operation: operation.name.clone(),
-                        hardware_config_id: hardware.id.clone(),
-                        scale: scale.name.clone(),
-                        num_sequences: scale.sequences,
-                    });
+            for name in &operation.parsers {
+                ParserBackend::parse(name)
+                    .with_context(|| format!("Operation '{}'", operation.name))?;
+            }
+            let parsers: Vec<Option<String>> = if operation.parsers.is_empty() {
+                vec![None]
+            } else {
+                operation.parsers.iter().cloned().map(Some).collect()
+            };
+
+            for parser in &parsers {
+                for hardware in &config.hardware.configs {
+                    for scale in &config.datasets.scales {
+                        experiment_id += 1;
+                        experiments.push(Experiment {
+                            id: format!("exp_{:06}", experiment_id),
+                            operation: operation.name.clone(),
+                            hardware_config_id: hardware.id.clone(),
+                            scale: scale.name.clone(),
+                            num_sequences: scale.sequences,
+                            parser: parser.clone(),
+                        });
+                    }
                 }
             }
         }
@@ -374,7 +812,12 @@ impl ExecutionEngine {
     }
 
     /// Run all experiments
-    pub fn run_all(&self) -> Result<()> {
+    ///
+    /// On an interrupt, the in-flight experiments finish, results and
+    /// checkpoint are flushed and [`RunStatus::Interrupted`] is returned.
+    pub fn run_all(&self) -> Result<RunStatus> {
+        self.validate_hardware()?;
+
         let total = self.experiments.len();
         let checkpoint = self.checkpoint.lock().unwrap();
         let completed_count = checkpoint.completed.len();
@@ -384,10 +827,10 @@ impl ExecutionEngine {
         println!("  Total experiments: {}", total);
         println!("  Already completed: {}", completed_count);
         println!("  Remaining: {}", total - completed_count);
+        println!("  Scheduling: {:?}", self.config.execution.scheduling);
         println!("  Parallel workers: {}", self.config.execution.parallel_experiments);
 
         // Filter to only incomplete experiments
-        eprintln!("DEBUG: Filtering incomplete experiments...");
         let incomplete: Vec<_> = self
             .experiments
             .iter()
@@ -397,15 +840,14 @@ impl ExecutionEngine {
             })
             .cloned()
             .collect();
-        eprintln!("DEBUG: Filtered {} incomplete experiments", incomplete.len());
+        debug!(total, incomplete = incomplete.len(), "filtered completed experiments");
 
         if incomplete.is_empty() {
             println!("All experiments already completed!");
-            return Ok(());
+            return Ok(RunStatus::Complete);
         }
 
         // Create progress bar
-        eprintln!("DEBUG: Creating progress bar...");
         let progress = if self.config.output.progress_bar {
             let pb = ProgressBar::new(incomplete.len() as u64);
             pb.set_style(
@@ -419,142 +861,206 @@ impl ExecutionEngine {
         } else {
             None
         };
-        eprintln!("DEBUG: Progress bar created");
-
-        // Set up Rayon thread pool
-        eprintln!("DEBUG: Creating Rayon thread pool with {} threads...", self.config.execution.parallel_experiments);
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(self.config.execution.parallel_experiments)
-            .build()?;
-        eprintln!("DEBUG: Thread pool created successfully");
-
-        // Execute experiments in parallel
-        eprintln!("DEBUG: Starting parallel execution...");
-        let checkpoint_interval = self.config.execution.checkpoint_interval;
-        let results_ref = Arc::clone(&self.results);
-        let checkpoint_ref = Arc::clone(&self.checkpoint);
-        let registry_ref = Arc::clone(&self.registry);
-        let config_clone = self.config.clone();
-
-        pool.install(|| {
-            eprintln!("DEBUG: Inside pool.install, about to start par_iter...");
-
-            // Use for_each instead of try_for_each to handle errors gracefully
-            // This allows experiments to fail individually without stopping the entire batch
-            incomplete
-                .par_iter()
-                .enumerate()
-                .for_each(|(i, experiment)| {
-                    if i == 0 {
-                        eprintln!("DEBUG: Starting first experiment: {} with {}",
-                            experiment.operation, experiment.hardware_config_id);
-                    }
 
-                    // Run experiment with error handling
-                    match self.run_experiment(
-                        experiment,
-                        &registry_ref,
-                        &config_clone,
-                    ) {
-                        Ok(result) => {
-                            if i == 0 {
-                                eprintln!("DEBUG: First experiment completed successfully");
-                            }
-
-                            // Store result
-                            {
-                                let mut results = results_ref.lock().unwrap();
-                                results.push(result);
-                            }
-
-                            // Mark completed
-                            {
-                                let mut checkpoint = checkpoint_ref.lock().unwrap();
-                                checkpoint.mark_completed(experiment.id.clone());
-                            }
-
-                            // Update progress
-                            if let Some(ref pb) = progress {
-                                pb.inc(1);
-                                pb.set_message(format!(
-                                    "Running: {} with {}",
-                                    experiment.operation, experiment.hardware_config_id
-                                ));
-                            }
-
-                            // Checkpoint periodically
-                            if (i + 1) % checkpoint_interval == 0 {
-                                if let Ok(checkpoint) = checkpoint_ref.lock() {
-                                    let checkpoint_path = self.output_dir.join(&self.config.output.checkpoint_file);
-                                    if let Err(e) = checkpoint.save(&checkpoint_path) {
-                                        eprintln!("WARNING: Failed to save checkpoint: {}", e);
-                                    }
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            // Log error but continue processing other experiments
-                            eprintln!("ERROR: Experiment {} ({} with {}) failed: {}",
-                                experiment.id,
-                                experiment.operation,
-                                experiment.hardware_config_id,
-                                e
-                            );
-
-                            // Still update progress bar
-                            if let Some(ref pb) = progress {
-                                pb.inc(1);
-                                pb.set_message(format!(
-                                    "FAILED: {} with {}",
-                                    experiment.operation, experiment.hardware_config_id
-                                ));
-                            }
-                        }
+        let scheduling = self.config.execution.scheduling;
+        let workers = self.config.execution.parallel_experiments.max(1);
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(workers).build()?;
+
+        match scheduling {
+            SchedulingPolicy::Exclusive => {
+                for (i, experiment) in incomplete.iter().enumerate() {
+                    if self.interrupt.stop_requested() {
+                        break;
                     }
+                    let outcome = self
+                        .prepare_experiment(experiment)
+                        .and_then(|prepared| self.measure_experiment(prepared));
+                    self.record_outcome(i, experiment, outcome, &progress);
+                }
+            }
+            SchedulingPolicy::Concurrent => {
+                // Experiments fail individually without stopping the batch
+                pool.install(|| {
+                    incomplete.par_iter().enumerate().for_each(|(i, experiment)| {
+                        if self.interrupt.stop_requested() {
+                            return;
+                        }
+                        let outcome = self
+                            .prepare_experiment(experiment)
+                            .and_then(|prepared| self.measure_experiment(prepared));
+                        self.record_outcome(i, experiment, outcome, &progress);
+                    });
                 });
-        });
+            }
+            SchedulingPolicy::Hybrid => {
+                // Bounded batches keep at most `workers` datasets in memory
+                // (plus the dataset cache, if enabled)
+                for (batch_index, batch) in incomplete.chunks(workers).enumerate() {
+                    if self.interrupt.stop_requested() {
+                        break;
+                    }
+                    let prepared: Vec<Result<PreparedExperiment>> = pool.install(|| {
+                        batch
+                            .par_iter()
+                            .map(|experiment| self.prepare_experiment(experiment))
+                            .collect()
+                    });
 
-        // Final checkpoint save
-        let checkpoint = self.checkpoint.lock().unwrap();
-        let checkpoint_path = self.output_dir.join(&self.config.output.checkpoint_file);
-        checkpoint.save(&checkpoint_path)?;
+                    for (offset, (experiment, prepared)) in batch.iter().zip(prepared).enumerate() {
+                        let outcome = prepared.and_then(|p| self.measure_experiment(p));
+                        self.record_outcome(batch_index * workers + offset, experiment, outcome, &progress);
+                    }
+                }
+            }
+        }
+
+        // Final results and checkpoint save
+        let saved = self.flush()?;
+        let manifest = self.write_manifest()?;
+        let saved_message = format!("  Saved {} results to {}", saved, self.output_dir.join(RESULTS_FILE).display());
+
+        let completed = self.checkpoint.lock().unwrap().completed.len();
+        if self.interrupt.stop_requested() && completed < total {
+            if let Some(ref pb) = progress {
+                pb.abandon_with_message("Interrupted");
+            }
+            println!("{}", saved_message);
+            println!("\nExecution interrupted: {} of {} experiments complete", completed, total);
+            println!("  Results and checkpoint saved to: {}", self.output_dir.display());
+            return Ok(RunStatus::Interrupted {
+                completed,
+                remaining: total - completed,
+            });
+        }
 
         if let Some(ref pb) = progress {
             pb.finish_with_message("All experiments complete!");
         }
 
-        // Save results
-        self.save_results()?;
-
+        println!("{}", saved_message);
         println!("\nExecution complete!");
         println!("  Results saved to: {}", self.output_dir.display());
+        println!("  Rerun with: asbb rerun {}", manifest.display());
 
-        Ok(())
+        Ok(RunStatus::Complete)
     }
 
-    /// Run a single experiment
-    fn run_experiment(
+    /// Write the reproducibility manifest into the output directory
+    fn write_manifest(&self) -> Result<PathBuf> {
+        let mut manifest = RunManifest::capture(&self.output_dir)?
+            .with_seed("datasets", self.config.datasets.seed);
+        if let Some(path) = &self.config_path {
+            manifest = manifest.with_config_file(path)?;
+        }
+        manifest.save_alongside_results()
+    }
+
+    /// Store a finished experiment's outcome, update progress and checkpoint
+    fn record_outcome(
         &self,
+        i: usize,
         experiment: &Experiment,
-        registry: &Arc<OperationRegistry>,
-        config: &ExperimentConfig,
-    ) -> Result<ExperimentResult> {
-        eprintln!("DEBUG: run_experiment called for {} with {}",
-            experiment.operation, experiment.hardware_config_id);
-
-        // Get operation from registry
-        eprintln!("DEBUG: Getting operation from registry...");
-        let operation = registry.get(&experiment.operation)?;
-        let metadata = registry.get_metadata(&experiment.operation)?;
-        eprintln!("DEBUG: Operation retrieved successfully");
-
-        // Generate test data
-        eprintln!("DEBUG: Generating test data ({} sequences)...", experiment.num_sequences);
-        let data = self.generate_test_data(experiment, config)?;
-        eprintln!("DEBUG: Test data generated");
-
-        // Convert hardware config
+        outcome: Result<ExperimentResult>,
+        progress: &Option<ProgressBar>,
+    ) {
+        match outcome {
+            Ok(result) => {
+                self.results.lock().unwrap().push(result);
+                self.checkpoint.lock().unwrap().mark_completed(experiment.id.clone());
+
+                if let Some(pb) = progress {
+                    pb.inc(1);
+                    pb.set_message(format!(
+                        "Running: {} with {}",
+                        experiment.operation, experiment.hardware_config_id
+                    ));
+                }
+
+                // Checkpoint periodically
+                if (i + 1).is_multiple_of(self.config.execution.checkpoint_interval) {
+                    if let Err(e) = self.flush() {
+                        warn!(error = format!("{:#}", e), "failed to save checkpoint");
+                    }
+                }
+            }
+            Err(e) if is_interrupted(&e) => {
+                debug!(id = %experiment.id, "skipped after interrupt");
+            }
+            Err(e) => {
+                // Log error but continue processing other experiments
+                error!(
+                    id = %experiment.id,
+                    operation = %experiment.operation,
+                    config = %experiment.hardware_config_id,
+                    scale = %experiment.scale,
+                    error = format!("{:#}", e),
+                    "experiment failed"
+                );
+
+                if let Some(pb) = progress {
+                    pb.inc(1);
+                    pb.set_message(format!(
+                        "FAILED: {} with {}",
+                        experiment.operation, experiment.hardware_config_id
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Resolve the operation and configuration and generate test data
+    fn prepare_experiment(&self, experiment: &Experiment) -> Result<PreparedExperiment> {
+        let _span = experiment.span().entered();
+        let config = &self.config;
+
+        let mut operation = self.registry.get(&experiment.operation)?;
+        if let Some(name) = &experiment.parser {
+            let variant = operation
+                .parser_variant(ParserBackend::parse(name)?)
+                .with_context(|| {
+                    format!("Operation '{}' has no parser to choose", experiment.operation)
+                })?;
+            operation = Arc::from(variant);
+        }
+
+        let (data, load_time) = if config.execution.cache_datasets {
+            let key = format!(
+                "synthetic:{}x{}:{}",
+                experiment.num_sequences, config.datasets.sequence_length, config.datasets.seed
+            );
+            let cached = self
+                .datasets
+                .records(&key, || self.generate_test_data(experiment, config))?;
+            (cached.value, cached.cost)
+        } else {
+            let mut timer = PhaseTimer::new();
+            let data = timer.load(|| self.generate_test_data(experiment, config))?;
+            (Arc::new(data), timer.finish().load_time)
+        };
+
         let hw_config = self.create_hardware_config(experiment, config)?;
+        debug!(
+            sequences = data.len(),
+            load_seconds = load_time.as_secs_f64(),
+            "generated test data"
+        );
+
+        Ok(PreparedExperiment {
+            experiment: experiment.clone(),
+            operation,
+            data,
+            hw_config,
+            load_time,
+        })
+    }
+
+    /// Benchmark a prepared experiment
+    fn measure_experiment(&self, prepared: PreparedExperiment) -> Result<ExperimentResult> {
+        let config = &self.config;
+        let experiment = &prepared.experiment;
+        let _span = experiment.span().entered();
+        self.interrupt.check_stop()?;
+        let metadata = self.registry.get_metadata(&experiment.operation)?;
 
         // Get hardware description
         let hw_entry = config
@@ -565,28 +1071,97 @@ impl ExecutionEngine {
             .context("Hardware config not found")?;
 
         // Run benchmark
+        debug!(
+            warmup_runs = config.execution.warmup_runs,
+            measurement_runs = config.execution.measurement_runs,
+            "measuring"
+        );
         let perf_result = crate::benchmark_operation(
-            operation.as_ref(),
-            &data,
-            &hw_config,
+            prepared.operation.as_ref(),
+            &prepared.data,
+            &prepared.hw_config,
             config.execution.warmup_runs,
             config.execution.measurement_runs,
         )?;
 
+        // Generation happens once; the other phases are per-run medians
+        let phases = PhaseTimings {
+            load_time: prepared.load_time,
+            ..perf_result.median_phases()
+        };
+
+        // Same repetition statistics as DAG traversal, over per-run compute times
+        // (kept whole when too few runs survive outlier removal)
+        let elapsed_times: Vec<f64> = perf_result
+            .phase_timings
+            .iter()
+            .map(|run| run.compute_time.as_secs_f64())
+            .collect();
+        let elapsed_stats = calculate_statistics_or_summary(
+            &elapsed_times,
+            config.execution.outlier_threshold,
+            config.execution.warmup_runs,
+        )?;
+
+        info!(
+            median_seconds = elapsed_stats.median,
+            outliers = elapsed_stats.n_outliers,
+            correct = perf_result.output_matches_reference,
+            "experiment complete"
+        );
+
+        let throughput_measurements: Vec<f64> = elapsed_times
+            .iter()
+            .map(|&elapsed| experiment.num_sequences as f64 / elapsed)
+            .collect();
+        let throughput_stats = calculate_statistics_or_summary(
+            &throughput_measurements,
+            config.execution.outlier_threshold,
+            config.execution.warmup_runs,
+        )?;
+
         // Convert to ExperimentResult
         Ok(ExperimentResult {
             experiment_id: experiment.id.clone(),
             operation: experiment.operation.clone(),
             operation_category: format!("{:?}", metadata.category),
             operation_complexity: metadata.complexity,
+            parser: experiment.parser.clone(),
             hardware_config_id: experiment.hardware_config_id.clone(),
             hardware_description: hw_entry.description.clone(),
             scale: experiment.scale.clone(),
             num_sequences: experiment.num_sequences,
             sequence_length: config.datasets.sequence_length,
-            mean_time_seconds: perf_result.latency_p50.as_secs_f64(),
-            median_time_seconds: perf_result.latency_p50.as_secs_f64(),
-            std_time_seconds: 0.0, // TODO: Calculate from multiple runs
+            mean_time_seconds: elapsed_stats.mean,
+            median_time_seconds: elapsed_stats.median,
+            std_time_seconds: elapsed_stats.std_dev,
+            throughput_median: throughput_stats.median,
+            throughput_mean: throughput_stats.mean,
+            throughput_std_dev: throughput_stats.std_dev,
+            throughput_ci_lower: throughput_stats.ci_95_lower,
+            throughput_ci_upper: throughput_stats.ci_95_upper,
+            // Filled in by attach_speedups once the baseline is known
+            speedup_median: 0.0,
+            speedup_mean: 0.0,
+            speedup_std_dev: 0.0,
+            speedup_ci_lower: 0.0,
+            speedup_ci_upper: 0.0,
+            elapsed_median: elapsed_stats.median,
+            elapsed_mean: elapsed_stats.mean,
+            elapsed_std_dev: elapsed_stats.std_dev,
+            elapsed_min: elapsed_stats.min,
+            elapsed_max: elapsed_stats.max,
+            elapsed_q1: elapsed_stats.q1,
+            elapsed_q3: elapsed_stats.q3,
+            elapsed_iqr: elapsed_stats.iqr,
+            n_valid: elapsed_stats.n_valid,
+            n_outliers: elapsed_stats.n_outliers,
+            n_warmup: elapsed_stats.n_warmup,
+            load_time_seconds: phases.load_time.as_secs_f64(),
+            encode_time_seconds: phases.encode_time.as_secs_f64(),
+            compute_time_seconds: phases.compute_time.as_secs_f64(),
+            serialize_time_seconds: phases.serialize_time.as_secs_f64(),
+            end_to_end_time_seconds: phases.end_to_end().as_secs_f64(),
             throughput_seqs_per_sec: perf_result.throughput_seqs_per_sec,
             throughput_mbps: perf_result.throughput_mbps,
             memory_peak_bytes: perf_result.memory_peak,
@@ -595,6 +1170,7 @@ impl ExecutionEngine {
             gpu_utilization: perf_result.gpu_utilization,
             energy_joules: perf_result.energy_joules,
             correct: perf_result.output_matches_reference,
+            scheduling: config.execution.scheduling,
             timestamp: chrono::Utc::now().to_rfc3339(),
         })
     }
@@ -646,46 +1222,82 @@ impl ExecutionEngine {
             .find(|c| c.id == experiment.hardware_config_id)
             .context("Hardware config not found")?;
 
-        let thread_assignment = match hw_entry.thread_assignment.as_str() {
-            "default" => ThreadAssignment::Mixed,
-            "p_cores" => ThreadAssignment::PCoresOnly,
-            "e_cores" => ThreadAssignment::ECoresOnly,
-            "mixed" | "mixed_2p2e" | "mixed_4p6e" => ThreadAssignment::Mixed,
-            _ => ThreadAssignment::Mixed,
-        };
-
-        Ok(HardwareConfig {
-            use_neon: hw_entry.use_neon,
-            num_threads: hw_entry.num_threads,
-            thread_assignment,
-            encoding: asbb_core::Encoding::Ascii, // TODO: Support from config
-            use_unified_memory: hw_entry.use_gpu, // If GPU, use unified memory
-            use_gpu: hw_entry.use_gpu,
-            gpu_batch_size: hw_entry.gpu_batch_size,
-            use_amx: false, // Not yet implemented
-            use_neural_engine: false, // Not yet implemented
-            use_m5_gpu_neural_accel: false, // Not yet implemented
-            use_hw_compression: false, // Not yet implemented
-            use_gcd: false, // Use Rayon instead
-            qos: QualityOfService::UserInitiated,
-            chip_generation: None,
-        })
+        hw_entry.to_hardware_config()
     }
 
     /// Save results to Parquet file
-    fn save_results(&self) -> Result<()> {
-        let results = self.results.lock().unwrap();
-        let json_path = self.output_dir.join("results.json");
+    /// Write results, then the checkpoint, so the checkpoint never lists an
+    /// experiment whose result is not on disk; returns the number of results
+    fn flush(&self) -> Result<usize> {
+        let saved = self.save_results()?;
+        let checkpoint_path = self.output_dir.join(&self.config.output.checkpoint_file);
+        self.checkpoint.lock().unwrap().save(&checkpoint_path)?;
+        Ok(saved)
+    }
+
+    fn save_results(&self) -> Result<usize> {
+        let mut results = self.results.lock().unwrap();
+        attach_speedups(&mut results);
+        let json_path = self.output_dir.join(RESULTS_FILE);
         let json_str = serde_json::to_string_pretty(&*results)?;
         fs::write(&json_path, json_str)?;
 
-        println!("  Saved {} results to {}", results.len(), json_path.display());
-
         // TODO: Implement Parquet storage
         // This requires converting Vec<ExperimentResult> to Arrow RecordBatch
         // and writing with parquet::arrow::ArrowWriter
 
-        Ok(())
+        Ok(results.len())
+    }
+}
+
+/// Median run times from a saved `results.json`, for duration estimates
+pub fn prior_timings(results_path: &Path) -> Result<Vec<PriorTiming>> {
+    let contents = fs::read_to_string(results_path)
+        .with_context(|| format!("Failed to read {}", results_path.display()))?;
+    let results: Vec<ExperimentResult> = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse {}", results_path.display()))?;
+
+    Ok(results
+        .into_iter()
+        .map(|r| PriorTiming {
+            operation: r.operation,
+            config: r.hardware_config_id,
+            num_sequences: r.num_sequences,
+            seconds_per_run: r.median_time_seconds,
+        })
+        .collect())
+}
+
+// ============================================================================
+// Speedup Statistics
+// ============================================================================
+
+/// Hardware config that speedups are measured against
+pub const BASELINE_CONFIG_ID: &str = "baseline";
+
+/// Fill speedup columns relative to each operation's baseline at the same scale
+/// (and with the same parser backend)
+///
+/// As in DAG traversal, per-run speedup is per-run throughput over the
+/// baseline's median throughput, so its statistics are the throughput
+/// statistics scaled by that constant. Results without a baseline keep 0.
+pub fn attach_speedups(results: &mut [ExperimentResult]) {
+    let key = |r: &ExperimentResult| (r.operation.clone(), r.parser.clone(), r.scale.clone());
+    let baselines: HashMap<(String, Option<String>, String), f64> = results
+        .iter()
+        .filter(|r| r.hardware_config_id == BASELINE_CONFIG_ID && r.throughput_median > 0.0)
+        .map(|r| (key(r), r.throughput_median))
+        .collect();
+
+    for result in results.iter_mut() {
+        let Some(&baseline) = baselines.get(&key(result)) else {
+            continue;
+        };
+        result.speedup_median = result.throughput_median / baseline;
+        result.speedup_mean = result.throughput_mean / baseline;
+        result.speedup_std_dev = result.throughput_std_dev / baseline;
+        result.speedup_ci_lower = result.throughput_ci_lower / baseline;
+        result.speedup_ci_upper = result.throughput_ci_upper / baseline;
     }
 }
 
@@ -694,4 +1306,251 @@ impl ExecutionEngine {
 // ============================================================================
 
 // TODO: Add Parquet conversion module
-// TODO: Add statistical analysis module
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheduling_policy_parsing() {
+        let settings: ExecutionSettings = toml::from_str(
+            "scheduling = \"hybrid\"\nparallel_experiments = 8\ncheckpoint_interval = 10\n\
+             timeout_seconds = 60\nwarmup_runs = 1\nmeasurement_runs = 3\nvalidate_correctness = true",
+        )
+        .unwrap();
+        assert_eq!(settings.scheduling, SchedulingPolicy::Hybrid);
+
+        // Timing runs are exclusive unless a config opts out
+        let settings: ExecutionSettings = toml::from_str(
+            "parallel_experiments = 8\ncheckpoint_interval = 10\ntimeout_seconds = 60\n\
+             warmup_runs = 1\nmeasurement_runs = 3\nvalidate_correctness = true",
+        )
+        .unwrap();
+        assert_eq!(settings.scheduling, SchedulingPolicy::Exclusive);
+        assert!(!SchedulingPolicy::Concurrent.timing_is_exclusive());
+    }
+
+    /// Requests an interrupt as soon as it runs
+    struct InterruptingOperation {
+        interrupt: Interrupt,
+    }
+
+    impl PrimitiveOperation for InterruptingOperation {
+        fn name(&self) -> &str {
+            "gc_content"
+        }
+
+        fn category(&self) -> asbb_core::OperationCategory {
+            asbb_core::OperationCategory::ElementWise
+        }
+
+        fn execute_naive(&self, data: &[SequenceRecord]) -> Result<asbb_core::OperationOutput> {
+            self.interrupt.request();
+            Ok(asbb_core::OperationOutput::Records(data.to_vec()))
+        }
+    }
+
+    /// One operation on the two first level-1 configs at 50 sequences
+    fn interrupting_engine(results_dir: &Path, interrupt: &Interrupt) -> ExecutionEngine {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../experiments/level1_primitives/config.toml");
+        let mut config: ExperimentConfig =
+            toml::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        config.hardware.resolve().unwrap();
+        config.hardware.configs.truncate(2);
+        config.datasets.scales.truncate(1);
+        config.datasets.scales[0].sequences = 50;
+        config.operations.list.retain(|op| op.name == "gc_content");
+        config.execution.scheduling = SchedulingPolicy::Exclusive;
+        config.execution.warmup_runs = 0;
+        config.output.results_dir = results_dir.to_string_lossy().into_owned();
+        config.output.progress_bar = false;
+
+        let mut registry = OperationRegistry::new();
+        registry.register(
+            Arc::new(InterruptingOperation { interrupt: interrupt.clone() }),
+            asbb_core::operation_registry::OperationMetadata {
+                name: "gc_content".to_string(),
+                category: asbb_core::OperationCategory::ElementWise,
+                complexity: 0.315,
+                backends: vec![asbb_core::operation_registry::Backend::Naive],
+                implemented: true,
+                description: None,
+                cost: None,
+            },
+        );
+        ExecutionEngine::from_config(config, registry)
+            .unwrap()
+            .with_interrupt(interrupt.clone())
+    }
+
+    #[test]
+    fn test_interrupt_flushes_and_resumes() {
+        let dir = std::env::temp_dir().join(format!("asbb_engine_interrupt_{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+
+        // The first experiment finishes after the interrupt; the second never starts
+        let engine = interrupting_engine(&dir, &Interrupt::default());
+        assert_eq!(
+            engine.run_all().unwrap(),
+            RunStatus::Interrupted { completed: 1, remaining: 1 }
+        );
+        let checkpoint = Checkpoint::load(&dir.join("checkpoint.json")).unwrap();
+        assert_eq!(checkpoint.completed, vec!["exp_000001".to_string()]);
+
+        // Resuming keeps the saved result and runs only the missing experiment
+        let interrupt = Interrupt::default();
+        let engine = interrupting_engine(&dir, &interrupt);
+        assert_eq!(engine.results.lock().unwrap().len(), 1);
+        interrupt.request();
+        assert_eq!(
+            engine.run_all().unwrap(),
+            RunStatus::Interrupted { completed: 1, remaining: 1 }
+        );
+
+        let engine = interrupting_engine(&dir, &Interrupt::default());
+        assert_eq!(engine.run_all().unwrap(), RunStatus::Complete);
+        let saved: Vec<ExperimentResult> =
+            serde_json::from_str(&fs::read_to_string(dir.join(RESULTS_FILE)).unwrap()).unwrap();
+        let ids: Vec<&str> = saved.iter().map(|r| r.experiment_id.as_str()).collect();
+        assert_eq!(ids, ["exp_000001", "exp_000002"]);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_level1_config_resolves() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../experiments/level1_primitives/config.toml");
+        let mut config: ExperimentConfig =
+            toml::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        config.hardware.resolve().unwrap();
+
+        assert_eq!(config.hardware.configs.len(), 28);
+        assert!(config.hardware.sweeps.is_empty());
+        let neon_8t = config.hardware.configs.iter().find(|c| c.id == "neon_8t").unwrap();
+        assert!(neon_8t.use_neon);
+        assert_eq!(neon_8t.num_threads, 8);
+        assert_eq!(neon_8t.batch_size, None);
+        let batched = config.hardware.configs.iter().find(|c| c.id == "neon_4t_batch256").unwrap();
+        assert_eq!(batched.batch_size, Some(256));
+
+        // Explicit entries and sweeps share one id namespace
+        config.hardware.configs.push(neon_8t.clone());
+        assert!(config.hardware.resolve().is_err());
+    }
+
+    #[test]
+    fn test_hardware_validated_before_running() {
+        use asbb_core::{ChipGeneration, ChipVariant};
+
+        let dir = std::env::temp_dir().join(format!("asbb_engine_validate_{}", std::process::id()));
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../experiments/level1_primitives/config.toml");
+        let mut config: ExperimentConfig =
+            toml::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        config.output.results_dir = dir.to_string_lossy().into_owned();
+
+        // The level-1 configs target an M4 (4 P + 6 E cores); 16 threads do not fit
+        let m4 = HardwareProfile::nominal(ChipGeneration::M4, ChipVariant::Base);
+        let engine =
+            ExecutionEngine::from_config(config.clone(), OperationRegistry::new()).unwrap();
+        engine.with_hardware(Some(m4.clone())).validate_hardware().unwrap();
+
+        config.hardware.configs[0].num_threads = 16;
+        let engine = ExecutionEngine::from_config(config, OperationRegistry::new())
+            .unwrap()
+            .with_hardware(Some(m4));
+        let error = format!("{:#}", engine.run_all().unwrap_err());
+        assert!(error.contains("'baseline'"), "{}", error);
+        assert!(error.contains("16 threads requested"), "{}", error);
+
+        // Undetected hardware is not checked
+        assert!(engine.with_hardware(None).validate_hardware().is_ok());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_parsers_dimension() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../experiments/level1_primitives/config.toml");
+        let mut config: ExperimentConfig =
+            toml::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        config.hardware.resolve().unwrap();
+        config.operations.list.retain(|op| op.name == "fastq_parsing" || op.name == "gc_content");
+
+        // Only the I/O operation is crossed with parsers
+        let experiments = ExecutionEngine::generate_experiments(&config).unwrap();
+        let per_parser = config.hardware.configs.len() * config.datasets.scales.len();
+        let parsers: Vec<Option<&str>> = experiments
+            .iter()
+            .filter(|e| e.operation == "fastq_parsing")
+            .map(|e| e.parser.as_deref())
+            .collect();
+        assert_eq!(parsers.len(), 2 * per_parser);
+        assert_eq!(parsers.iter().filter(|p| **p == Some("neon")).count(), per_parser);
+        let mut gc_content = experiments.iter().filter(|e| e.operation == "gc_content");
+        assert!(gc_content.all(|e| e.parser.is_none()));
+
+        config.operations.list[0].parsers = vec!["avx2".to_string()];
+        assert!(ExecutionEngine::generate_experiments(&config).is_err());
+    }
+
+    fn result(hardware_config_id: &str, throughput: f64) -> ExperimentResult {
+        let mut result: ExperimentResult = serde_json::from_value(serde_json::json!({
+            "experiment_id": format!("gc_content_{}_small", hardware_config_id),
+            "operation": "gc_content",
+            "operation_category": "ElementWise",
+            "operation_complexity": 0.3,
+            "hardware_config_id": hardware_config_id,
+            "hardware_description": "",
+            "scale": "small",
+            "num_sequences": 1000,
+            "sequence_length": 150,
+            "mean_time_seconds": 0.0,
+            "median_time_seconds": 0.0,
+            "std_time_seconds": 0.0,
+            "throughput_seqs_per_sec": throughput,
+            "throughput_mbps": 0.0,
+            "memory_peak_bytes": 0,
+            "memory_avg_bytes": 0,
+            "cpu_utilization": 0.0,
+            "gpu_utilization": null,
+            "energy_joules": null,
+            "correct": true,
+            "timestamp": "",
+        }))
+        .unwrap();
+        result.throughput_median = throughput;
+        result.throughput_ci_lower = throughput * 0.9;
+        result
+    }
+
+    #[test]
+    fn test_attach_speedups() {
+        let mut results = vec![result("neon_4t", 8000.0), result(BASELINE_CONFIG_ID, 1000.0)];
+        results.push(ExperimentResult { scale: "large".to_string(), ..result("neon_1t", 5000.0) });
+
+        attach_speedups(&mut results);
+
+        assert_eq!(results[0].speedup_median, 8.0);
+        assert!((results[0].speedup_ci_lower - 7.2).abs() < 1e-9);
+        assert_eq!(results[1].speedup_median, 1.0);
+        // No baseline at this scale
+        assert_eq!(results[2].speedup_median, 0.0);
+        // Parser variants have their own baselines
+        let neon_parser = |config: &str, throughput: f64| ExperimentResult {
+            parser: Some("neon".to_string()),
+            ..result(config, throughput)
+        };
+        let mut parsed =
+            vec![neon_parser("neon_4t", 9000.0), neon_parser(BASELINE_CONFIG_ID, 3000.0)];
+        attach_speedups(&mut parsed);
+        assert_eq!(parsed[0].speedup_median, 3.0);
+        // Rows from before the policy was recorded ran concurrently
+        assert_eq!(results[0].scheduling, SchedulingPolicy::Concurrent);
+    }
+}
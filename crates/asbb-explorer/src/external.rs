@@ -0,0 +1,502 @@
+//! External-tool comparison harness
+//!
+//! Runs installed bioinformatics tools (`seqkit stats`, `fastp`, FastQC) on
+//! a dataset and times them next to their closest ASBB counterpart, so the
+//! report can answer "how does this compare to what people already use?".
+//!
+//! Tools and ASBB rarely do exactly the same work, so every tool carries an
+//! [`Equivalence`] label and a note saying what differs. Both sides are timed
+//! from the file: tool wall time is process start to exit; the ASBB side is
+//! FASTQ parsing plus the counterpart [`WorkloadMix`].
+//!
+//! Tools are optional: a tool that is not on `PATH` is reported as
+//! unavailable instead of failing the run.
+//!
+//! [`ExternalCommand`] covers everything else: any shell command, templated
+//! with `{input}` and `{threads}`, timed as a black box (wall time and peak
+//! RSS per run) so `asbb bench --command` can put it in the same results
+//! table as the ASBB operations.
+
+use anyhow::{Context, Result};
+use asbb_core::io::FastqReader;
+use asbb_core::stats::duration_quantile;
+use asbb_core::HardwareConfig;
+use asbb_ops::{
+    adapter_trimming::AdapterTrimming,
+    length_filter::LengthFilter,
+    length_histogram::LengthHistogram,
+    position_content::PositionContent,
+    quality_filter::{QualityFilter, QualityFilterMode},
+    quality_statistics::QualityStatistics,
+};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::workload::WorkloadMix;
+
+/// Tool names accepted by [`ExternalTool::by_name`]
+pub const TOOLS: &[&str] = &["seqkit_stats", "fastp", "fastqc"];
+
+/// How closely the ASBB counterpart matches the tool's work
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Equivalence {
+    /// Same computation and output content
+    Equivalent,
+    /// Overlapping work; the tool does more (or differently), see the note
+    Partial,
+}
+
+impl Equivalence {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Equivalence::Equivalent => "equivalent",
+            Equivalence::Partial => "partial",
+        }
+    }
+}
+
+/// An external tool invocation and its ASBB counterpart
+pub struct ExternalTool {
+    pub name: String,
+    pub program: String,
+    /// Arguments; `{input}`, `{threads}` and `{outdir}` are substituted
+    pub args: Vec<String>,
+    /// Arguments printing the version (also used to detect the tool)
+    pub version_args: Vec<String>,
+    /// ASBB work compared against the tool
+    pub counterpart: fn() -> WorkloadMix,
+    pub equivalence: Equivalence,
+    /// What differs between the tool and the counterpart
+    pub note: String,
+}
+
+fn strings(args: &[&str]) -> Vec<String> {
+    args.iter().map(|s| s.to_string()).collect()
+}
+
+impl ExternalTool {
+    /// `seqkit stats`: record count and length summary
+    pub fn seqkit_stats() -> Self {
+        Self {
+            name: "seqkit_stats".to_string(),
+            program: "seqkit".to_string(),
+            args: strings(&["stats", "-j", "{threads}", "{input}"]),
+            version_args: strings(&["version"]),
+            counterpart: || {
+                WorkloadMix::new("seqkit_stats").with_step("stats", Box::new(LengthHistogram::new(1)))
+            },
+            equivalence: Equivalence::Equivalent,
+            note: "count, total/min/mean/max length from the same parse".to_string(),
+        }
+    }
+
+    /// `fastp`: filtering, adapter trimming and a QC report
+    pub fn fastp() -> Self {
+        Self {
+            name: "fastp".to_string(),
+            program: "fastp".to_string(),
+            args: strings(&[
+                "-i", "{input}", "-o", "{outdir}/out.fq", "-j", "{outdir}/fastp.json",
+                "-h", "{outdir}/fastp.html", "-w", "{threads}",
+            ]),
+            version_args: strings(&["--version"]),
+            counterpart: || {
+                WorkloadMix::new("fastp")
+                    .with_step("n_filter", Box::new(QualityFilter::with_mode(QualityFilterMode::MaxN { max_n: 5 })))
+                    .with_step("adapter_trimming", Box::new(AdapterTrimming::new(b"AGATCGGAAGAGC".to_vec(), 4, 15)))
+                    .with_step("length_filter", Box::new(LengthFilter::new(15)))
+                    .with_step("per_base_quality", Box::new(QualityStatistics::new()))
+                    .with_step("per_base_content", Box::new(PositionContent::new()))
+            },
+            equivalence: Equivalence::Partial,
+            note: "fastp also auto-detects the adapter, filters on per-base quality, \
+                   reports before and after filtering and writes reads + JSON/HTML; \
+                   ASBB trims a fixed Illumina adapter in memory"
+                .to_string(),
+        }
+    }
+
+    /// FastQC: QC report (JVM)
+    pub fn fastqc() -> Self {
+        Self {
+            name: "fastqc".to_string(),
+            program: "fastqc".to_string(),
+            args: strings(&["--quiet", "-t", "{threads}", "-o", "{outdir}", "{input}"]),
+            version_args: strings(&["--version"]),
+            counterpart: WorkloadMix::fastqc,
+            equivalence: Equivalence::Partial,
+            note: "FastQC includes JVM start-up, duplication/per-sequence modules and \
+                   HTML/PNG rendering; ASBB runs the five core analysis modules"
+                .to_string(),
+        }
+    }
+
+    pub fn by_name(name: &str) -> Result<Self> {
+        match name {
+            "seqkit_stats" => Ok(Self::seqkit_stats()),
+            "fastp" => Ok(Self::fastp()),
+            "fastqc" => Ok(Self::fastqc()),
+            _ => anyhow::bail!("Unknown external tool: {} (available: {})", name, TOOLS.join(", ")),
+        }
+    }
+
+    /// First line of the version output, or `None` if the tool is not installed
+    pub fn version(&self) -> Option<String> {
+        let output = Command::new(&self.program)
+            .args(&self.version_args)
+            .stdin(Stdio::null())
+            .output()
+            .ok()?;
+        let text = if output.stdout.is_empty() { output.stderr } else { output.stdout };
+        let line = String::from_utf8_lossy(&text).lines().next().unwrap_or("").trim().to_string();
+        Some(line)
+    }
+
+    fn command(&self, input: &Path, threads: usize, outdir: &Path) -> Command {
+        let mut command = Command::new(&self.program);
+        for arg in &self.args {
+            command.arg(
+                arg.replace("{input}", &input.to_string_lossy())
+                    .replace("{threads}", &threads.to_string())
+                    .replace("{outdir}", &outdir.to_string_lossy()),
+            );
+        }
+        command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::piped());
+        command
+    }
+
+    /// Run the tool once and return its wall time
+    fn run_once(&self, input: &Path, threads: usize, outdir: &Path) -> Result<Duration> {
+        let start = Instant::now();
+        let output = self.command(input, threads, outdir).output().map_err(|e| {
+            if e.kind() == io::ErrorKind::NotFound {
+                anyhow::anyhow!("'{}' not found on PATH", self.program)
+            } else {
+                anyhow::anyhow!("Failed to run {}: {}", self.program, e)
+            }
+        })?;
+        let elapsed = start.elapsed();
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "{} exited with {}: {}",
+                self.name,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(elapsed)
+    }
+}
+
+// ============================================================================
+// Black-box Commands
+// ============================================================================
+
+/// A shell command benchmarked as a black box
+///
+/// The template runs under `sh -c` after `{input}` and `{threads}` are
+/// substituted; stdout is discarded. Nothing about the work is known, so
+/// results carry only what can be measured from outside: wall time, peak
+/// resident memory and (with an energy meter) joules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalCommand {
+    /// Operation name in results
+    pub name: String,
+    pub template: String,
+}
+
+/// One run of an external command
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CommandRun {
+    pub elapsed: Duration,
+
+    /// Peak resident set of the command and the children it waited for
+    ///
+    /// `None` off Unix, and on Linux when the peak does not exceed the
+    /// harness's own (the kernel seeds a child's peak with its parent's).
+    pub peak_rss_bytes: Option<u64>,
+}
+
+impl ExternalCommand {
+    /// Parse `NAME=COMMAND`
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.split_once('=') {
+            Some((name, template)) if !name.is_empty() && !template.trim().is_empty() => {
+                Ok(Self { name: name.to_string(), template: template.to_string() })
+            }
+            _ => Err(format!("expected NAME=COMMAND, got '{}'", s)),
+        }
+    }
+
+    /// The command line run for `input` on `threads` threads
+    pub fn command_line(&self, input: &Path, threads: usize) -> String {
+        self.template
+            .replace("{input}", &input.to_string_lossy())
+            .replace("{threads}", &threads.to_string())
+    }
+
+    /// Run the command once; a non-zero exit is an error
+    pub fn run_once(&self, input: &Path, threads: usize) -> Result<CommandRun> {
+        let line = self.command_line(input, threads);
+        let start = Instant::now();
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(&line)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run: {}", line))?;
+
+        // Drain stderr while waiting so a chatty tool cannot fill the pipe
+        let mut stderr = child.stderr.take().context("child has no stderr")?;
+        let reader = std::thread::spawn(move || {
+            let mut text = String::new();
+            stderr.read_to_string(&mut text).ok();
+            text
+        });
+        let (status, peak_rss_bytes) = wait_with_peak_rss(&mut child)?;
+        let elapsed = start.elapsed();
+        let stderr = reader.join().unwrap_or_default();
+
+        if !status.success() {
+            anyhow::bail!("'{}' exited with {}: {}", line, status, stderr.trim());
+        }
+        Ok(CommandRun { elapsed, peak_rss_bytes })
+    }
+}
+
+/// Wait for `child`, reading its resource usage as it is reaped
+#[cfg(unix)]
+fn wait_with_peak_rss(child: &mut Child) -> Result<(ExitStatus, Option<u64>)> {
+    use std::os::unix::process::ExitStatusExt;
+
+    let mut status = 0;
+    // SAFETY: rusage is plain data, and wait4 only writes to the two
+    // out-parameters; the pid is our own unreaped child
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    loop {
+        let pid = unsafe { libc::wait4(child.id() as libc::pid_t, &mut status, 0, &mut usage) };
+        if pid >= 0 {
+            break;
+        }
+        let error = io::Error::last_os_error();
+        if error.kind() != io::ErrorKind::Interrupted {
+            return Err(error).context("wait4 failed");
+        }
+    }
+
+    // ru_maxrss is in bytes on macOS, kilobytes elsewhere
+    let unit = if cfg!(target_os = "macos") { 1 } else { 1024 };
+    let peak = usage.ru_maxrss.max(0) as u64 * unit;
+
+    // Linux starts a spawned child's high-water mark at the parent's
+    // resident set, so a peak no higher than ours says nothing about the tool
+    if cfg!(target_os = "linux") && peak <= own_peak_rss() * unit {
+        return Ok((ExitStatus::from_raw(status), None));
+    }
+    Ok((ExitStatus::from_raw(status), Some(peak)))
+}
+
+/// This process's peak resident set, in `ru_maxrss` units
+#[cfg(unix)]
+fn own_peak_rss() -> u64 {
+    // SAFETY: as above, getrusage only writes to `usage`
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    unsafe { libc::getrusage(libc::RUSAGE_SELF, &mut usage) };
+    usage.ru_maxrss.max(0) as u64
+}
+
+#[cfg(not(unix))]
+fn wait_with_peak_rss(child: &mut Child) -> Result<(ExitStatus, Option<u64>)> {
+    Ok((child.wait()?, None))
+}
+
+// ============================================================================
+// Comparison
+// ============================================================================
+
+/// Median, min and max of measured wall times
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WallTime {
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl WallTime {
+    fn from_runs(mut runs: Vec<Duration>) -> Self {
+        runs.sort();
+        Self {
+            median: duration_quantile(&runs, 50.0).unwrap_or_default(),
+            min: runs[0],
+            max: runs[runs.len() - 1],
+        }
+    }
+}
+
+/// One tool timed against its ASBB counterpart
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolComparison {
+    pub tool: String,
+    /// `None` if the tool is not installed
+    pub version: Option<String>,
+    /// Tool wall time (`None` if unavailable)
+    pub tool_time: Option<WallTime>,
+    /// ASBB counterpart name
+    pub counterpart: String,
+    /// ASBB parse + counterpart wall time
+    pub asbb_time: WallTime,
+    pub equivalence: Equivalence,
+    pub note: String,
+}
+
+impl ToolComparison {
+    /// Tool time over ASBB time (>1: ASBB faster)
+    pub fn asbb_speedup(&self) -> Option<f64> {
+        let tool = self.tool_time.as_ref()?.median.as_secs_f64();
+        Some(tool / self.asbb_time.median.as_secs_f64().max(f64::MIN_POSITIVE))
+    }
+}
+
+/// Time `tool` and its ASBB counterpart on `input`
+///
+/// `config.num_threads` is passed to the tool as its thread count.
+pub fn compare_tool(
+    tool: &ExternalTool,
+    input: &Path,
+    config: &HardwareConfig,
+    warmup_runs: usize,
+    measured_runs: usize,
+) -> Result<ToolComparison> {
+    if measured_runs == 0 {
+        anyhow::bail!("At least one measured run is required");
+    }
+
+    let version = tool.version();
+    let tool_time = match version {
+        Some(_) => {
+            let outdir = std::env::temp_dir()
+                .join(format!("asbb_external_{}_{}", tool.name, std::process::id()));
+            std::fs::create_dir_all(&outdir)
+                .with_context(|| format!("Failed to create {}", outdir.display()))?;
+            let runs: Result<Vec<Duration>> = (0..warmup_runs + measured_runs)
+                .map(|_| tool.run_once(input, config.num_threads, &outdir))
+                .collect();
+            std::fs::remove_dir_all(&outdir).ok();
+            Some(WallTime::from_runs(runs?.split_off(warmup_runs)))
+        }
+        None => None,
+    };
+
+    let mix = (tool.counterpart)();
+    let run_asbb = || -> Result<Duration> {
+        let start = Instant::now();
+        let data = FastqReader::from_path(input)?.read_all()?;
+        mix.run(&data, config)?;
+        Ok(start.elapsed())
+    };
+    for _ in 0..warmup_runs {
+        run_asbb()?;
+    }
+    let asbb_runs = (0..measured_runs).map(|_| run_asbb()).collect::<Result<Vec<_>>>()?;
+
+    Ok(ToolComparison {
+        tool: tool.name.clone(),
+        version,
+        tool_time,
+        counterpart: mix.name.clone(),
+        asbb_time: WallTime::from_runs(asbb_runs),
+        equivalence: tool.equivalence,
+        note: tool.note.clone(),
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn test_fastq(tag: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("asbb_external_{}_{}.fq", tag, std::process::id()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        for i in 0..100 {
+            writeln!(file, "@read_{}\nACGTACGTAGATCGGAAGAGC\n+\nIIIIIIIIIIIIIIIIIIIII", i).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn test_presets_and_missing_tool() {
+        for name in TOOLS {
+            assert_eq!(ExternalTool::by_name(name).unwrap().name, *name);
+        }
+        assert!(ExternalTool::by_name("multiqc").is_err());
+
+        let mut tool = ExternalTool::seqkit_stats();
+        tool.program = "asbb-no-such-tool".to_string();
+        assert!(tool.version().is_none());
+
+        let path = test_fastq("missing");
+        let comparison = compare_tool(&tool, &path, &HardwareConfig::naive(), 0, 2).unwrap();
+        assert!(comparison.tool_time.is_none());
+        assert!(comparison.asbb_speedup().is_none());
+        assert_eq!(comparison.counterpart, "seqkit_stats");
+        assert_eq!(comparison.equivalence, Equivalence::Equivalent);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_runs_installed_tool() {
+        // `true` accepts any arguments and exits successfully
+        let mut tool = ExternalTool::fastqc();
+        tool.program = "true".to_string();
+
+        let path = test_fastq("true");
+        let comparison = compare_tool(&tool, &path, &HardwareConfig::naive(), 1, 3).unwrap();
+        let time = comparison.tool_time.as_ref().unwrap();
+        assert!(time.min <= time.median && time.median <= time.max);
+        assert!(comparison.asbb_speedup().unwrap() > 0.0);
+        assert_eq!(comparison.counterpart, "fastqc");
+        std::fs::remove_file(path).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_external_command() {
+        assert!(ExternalCommand::parse("no_template").is_err());
+        assert!(ExternalCommand::parse("=cat {input}").is_err());
+
+        let command = ExternalCommand::parse("count=wc -l {input} && test {threads} -eq 2").unwrap();
+        assert_eq!(command.name, "count");
+        let path = test_fastq("command");
+        assert_eq!(
+            command.command_line(&path, 2),
+            format!("wc -l {} && test 2 -eq 2", path.display())
+        );
+
+        let run = command.run_once(&path, 2).unwrap();
+        assert!(run.elapsed > Duration::ZERO);
+        assert!(run.peak_rss_bytes.is_none_or(|bytes| bytes > 0));
+
+        // A command holding 256 MB reports at least that much
+        let python = ExternalCommand::parse("hold=python3 -c \"b = ' ' * (256 << 20)\"").unwrap();
+        if let Ok(run) = python.run_once(&path, 1) {
+            assert!(run.peak_rss_bytes.unwrap() >= 256 << 20);
+        }
+
+        // Failing commands report their stderr
+        let error = command.run_once(&path, 3).unwrap_err().to_string();
+        assert!(error.contains("exited with"), "{}", error);
+        std::fs::remove_file(path).ok();
+    }
+}
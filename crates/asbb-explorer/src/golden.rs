@@ -0,0 +1,246 @@
+//! Golden-output correctness oracle
+//!
+//! `benchmark_operation` only checks an optimized backend against a naive run
+//! in the same process, so a bug shared by both (or a naive implementation
+//! that drifts between commits) goes unnoticed. Golden outputs pin the
+//! canonical result of each (operation, dataset) pair to disk:
+//!
+//! ```text
+//! <golden_dir>/<dataset>/<operation>.json
+//! ```
+//!
+//! Each file records the dataset's SHA-256 and record count, so a golden is
+//! never silently compared against a regenerated dataset. Goldens are written
+//! from the naive backend (`asbb validate --bless`) and every other backend is
+//! validated against them (floats within a relative tolerance, see
+//! `asbb_core::compare`), with a per-field diff on mismatch.
+
+use anyhow::{Context, Result};
+use asbb_core::compare::{diff_outputs, Tolerance};
+use asbb_core::operation_registry::Backend;
+use asbb_core::{OperationOutput, PrimitiveOperation, SequenceRecord};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Maximum number of differences reported per mismatch
+pub const MAX_REPORTED_DIFFS: usize = 10;
+
+/// Thread count used when validating the parallel backend
+const VALIDATION_THREADS: usize = 4;
+
+/// GPU batch size used when validating the GPU backend
+const VALIDATION_GPU_BATCH: usize = 100_000;
+
+// ============================================================================
+// Golden Files
+// ============================================================================
+
+/// Canonical output of one operation on one dataset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GoldenOutput {
+    /// Operation name
+    pub operation: String,
+
+    /// Dataset name
+    pub dataset: String,
+
+    /// SHA-256 of the dataset file the output was computed from
+    pub dataset_sha256: String,
+
+    /// Number of records in the dataset
+    pub num_records: usize,
+
+    /// Backend that produced the output (normally `Naive`)
+    pub generated_by: Backend,
+
+    /// The canonical output
+    pub output: OperationOutput,
+}
+
+/// Directory of golden outputs
+#[derive(Debug, Clone)]
+pub struct GoldenStore {
+    root: PathBuf,
+}
+
+impl GoldenStore {
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Path of the golden file for an (operation, dataset) pair
+    pub fn path(&self, dataset: &str, operation: &str) -> PathBuf {
+        self.root.join(dataset).join(format!("{}.json", operation))
+    }
+
+    /// Load a golden output, or `None` if it has not been blessed yet
+    pub fn load(&self, dataset: &str, operation: &str) -> Result<Option<GoldenOutput>> {
+        let path = self.path(dataset, operation);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read golden output: {}", path.display()))?;
+        let golden = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse golden output: {}", path.display()))?;
+        Ok(Some(golden))
+    }
+
+    /// Write a golden output, replacing any existing one
+    pub fn save(&self, golden: &GoldenOutput) -> Result<PathBuf> {
+        let path = self.path(&golden.dataset, &golden.operation);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        let content = serde_json::to_string_pretty(golden)?;
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write golden output: {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+// ============================================================================
+// Backend Execution
+// ============================================================================
+
+/// Run an operation on a single backend through the `PrimitiveOperation` trait
+///
+/// 2-bit execution has no trait entry point (it takes `BitSeq` input), so
+/// callers dispatch `Backend::TwoBit` to the operation's own methods.
+pub fn execute_backend(
+    operation: &dyn PrimitiveOperation,
+    data: &[SequenceRecord],
+    backend: Backend,
+) -> Result<OperationOutput> {
+    match backend {
+        Backend::Naive => operation.execute_naive(data),
+        Backend::Neon => operation.execute_neon(data),
+        Backend::Parallel => operation.execute_parallel(data, VALIDATION_THREADS),
+        Backend::ParallelChunked => operation.execute_parallel_chunked(data, VALIDATION_THREADS),
+        Backend::ParallelIntraRead => {
+            operation.execute_parallel_intra_read(data, VALIDATION_THREADS)
+        }
+        Backend::Gpu => operation.execute_gpu(data, VALIDATION_GPU_BATCH),
+        Backend::Neural => operation.execute_neural(data),
+        Backend::Amx => operation.execute_amx(data),
+        Backend::TwoBit => anyhow::bail!(
+            "2-bit execution not implemented for {} via the operation trait",
+            operation.name()
+        ),
+    }
+}
+
+/// Whether an execution error means "backend not available" rather than a bug
+///
+/// Trait defaults report missing backends as "... not implemented for ...";
+/// harnesses use "not supported" for backends they can't drive.
+pub fn is_unsupported(error: &anyhow::Error) -> bool {
+    let message = error.to_string();
+    message.contains("not implemented") || message.contains("not supported")
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// Outcome of validating one backend against a golden output
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationStatus {
+    /// Output matches the golden (floats within tolerance)
+    Pass,
+    /// Output differs; one line per difference (capped at `MAX_REPORTED_DIFFS`)
+    Mismatch { total: usize, diffs: Vec<String> },
+    /// Backend not available for this operation or platform
+    Skipped(String),
+    /// Backend failed while executing
+    Error(String),
+}
+
+impl ValidationStatus {
+    /// Classify a backend run against the golden output
+    ///
+    /// Float fields match within `tolerance`; everything else must be exact.
+    pub fn check(golden: &GoldenOutput, actual: Result<OperationOutput>, tolerance: &Tolerance) -> Self {
+        match actual {
+            Ok(output) => {
+                let diffs = diff_outputs(&golden.output, &output, tolerance);
+                if diffs.is_empty() {
+                    ValidationStatus::Pass
+                } else {
+                    ValidationStatus::Mismatch {
+                        total: diffs.len(),
+                        diffs: diffs.into_iter().take(MAX_REPORTED_DIFFS).collect(),
+                    }
+                }
+            }
+            Err(e) if is_unsupported(&e) => ValidationStatus::Skipped(e.to_string()),
+            Err(e) => ValidationStatus::Error(format!("{:#}", e)),
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self, ValidationStatus::Mismatch { .. } | ValidationStatus::Error(_))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asbb_ops::base_counting::BaseCounting;
+    use serde_json::json;
+
+    fn golden(output: OperationOutput) -> GoldenOutput {
+        GoldenOutput {
+            operation: "base_counting".to_string(),
+            dataset: "tiny".to_string(),
+            dataset_sha256: "abc".to_string(),
+            num_records: 2,
+            generated_by: Backend::Naive,
+            output,
+        }
+    }
+
+    #[test]
+    fn test_store_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("asbb_golden_{}", std::process::id()));
+        let store = GoldenStore::new(&dir);
+        assert!(store.load("tiny", "base_counting").unwrap().is_none());
+
+        let expected = golden(OperationOutput::Statistics(json!({"count_a": 3})));
+        let path = store.save(&expected).unwrap();
+        assert_eq!(path, dir.join("tiny").join("base_counting.json"));
+        assert_eq!(store.load("tiny", "base_counting").unwrap(), Some(expected));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_check_classifies_backends() {
+        let op = BaseCounting::new();
+        let data = vec![SequenceRecord::fasta("r0".to_string(), b"ACGTN".to_vec())];
+        let reference = golden(op.execute_naive(&data).unwrap());
+
+        let neon = execute_backend(&op, &data, Backend::Neon);
+        assert_eq!(ValidationStatus::check(&reference, neon, &Tolerance::default()), ValidationStatus::Pass);
+
+        let gpu = execute_backend(&op, &data, Backend::Gpu);
+        assert!(matches!(
+            ValidationStatus::check(&reference, gpu, &Tolerance::default()),
+            ValidationStatus::Skipped(_)
+        ));
+
+        let wrong = Ok(OperationOutput::Count(5));
+        let status = ValidationStatus::check(&reference, wrong, &Tolerance::default());
+        assert!(status.is_failure());
+    }
+}
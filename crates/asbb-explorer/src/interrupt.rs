@@ -0,0 +1,183 @@
+//! Graceful Ctrl-C handling for long experiment runs
+//!
+//! A batch that takes hours should not lose every finished experiment to one
+//! Ctrl-C. [`Interrupt::install`] handles SIGINT and SIGTERM on a watcher
+//! thread and escalates with each signal:
+//!
+//! 1. **Stop**: no new experiment starts; the in-flight one finishes, then
+//!    the harness flushes results and checkpoint and prints how to resume
+//! 2. **Abort**: the in-flight experiment is abandoned at its next
+//!    repetition (harnesses that can check between runs) and the batch is
+//!    flushed as above, without it
+//! 3. **Exit**: the process exits immediately with [`EXIT_CODE`]
+//!
+//! Harnesses poll [`Interrupt::check_stop`] before starting an experiment and
+//! [`Interrupt::check_abort`] between repetitions; both fail with
+//! [`Interrupted`], which callers recognise with [`is_interrupted`].
+
+use anyhow::Result;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Conventional exit code of a process stopped by SIGINT (128 + 2)
+pub const EXIT_CODE: i32 = 130;
+
+/// Interrupt state shared between a signal watcher and the harness
+///
+/// The default value never fires unless [`Interrupt::request`] is called.
+#[derive(Debug, Clone, Default)]
+pub struct Interrupt {
+    signals: Arc<AtomicUsize>,
+}
+
+/// Error returned when a run stops because of an interrupt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interrupted;
+
+impl fmt::Display for Interrupted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "interrupted")
+    }
+}
+
+impl std::error::Error for Interrupted {}
+
+/// Whether `error` (or its cause chain) is an [`Interrupted`]
+pub fn is_interrupted(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| cause.is::<Interrupted>())
+}
+
+impl Interrupt {
+    /// Handle SIGINT and SIGTERM for the rest of the process
+    ///
+    /// Call once, at start-up; each call adds another watcher thread.
+    #[cfg(unix)]
+    pub fn install() -> Result<Self> {
+        use signal_hook::consts::{SIGINT, SIGTERM};
+        use signal_hook::iterator::Signals;
+
+        let interrupt = Self::default();
+        let mut signals = Signals::new([SIGINT, SIGTERM])?;
+        let watcher = interrupt.clone();
+        std::thread::Builder::new()
+            .name("asbb-interrupt".to_string())
+            .spawn(move || {
+                for _ in signals.forever() {
+                    match watcher.request() {
+                        1 => eprintln!(
+                            "\n⏸️  Interrupt received: finishing the in-flight experiment, then saving \
+                             (press Ctrl-C again to abort it)"
+                        ),
+                        2 => eprintln!(
+                            "\n⏹️  Aborting the in-flight experiment, then saving \
+                             (press Ctrl-C again to exit immediately)"
+                        ),
+                        _ => {
+                            eprintln!("\n🛑 Exiting without saving");
+                            std::process::exit(EXIT_CODE);
+                        }
+                    }
+                }
+            })?;
+        Ok(interrupt)
+    }
+
+    /// Signals are not handled on this platform; the run cannot be stopped early
+    #[cfg(not(unix))]
+    pub fn install() -> Result<Self> {
+        Ok(Self::default())
+    }
+
+    /// Record one interrupt, as if a signal had arrived; returns the count
+    pub fn request(&self) -> usize {
+        self.signals.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// No new experiment should start
+    pub fn stop_requested(&self) -> bool {
+        self.signals.load(Ordering::SeqCst) >= 1
+    }
+
+    /// The in-flight experiment should be abandoned
+    pub fn abort_requested(&self) -> bool {
+        self.signals.load(Ordering::SeqCst) >= 2
+    }
+
+    /// Fail with [`Interrupted`] once a stop is requested
+    pub fn check_stop(&self) -> Result<()> {
+        if self.stop_requested() {
+            return Err(Interrupted.into());
+        }
+        Ok(())
+    }
+
+    /// Fail with [`Interrupted`] once an abort is requested
+    pub fn check_abort(&self) -> Result<()> {
+        if self.abort_requested() {
+            return Err(Interrupted.into());
+        }
+        Ok(())
+    }
+}
+
+/// The current command line with `replacements` applied, for resume hints
+///
+/// Each `(flag, value)` replaces the flag's existing value (the flag and the
+/// argument after it) or is appended; a `None` value removes the flag.
+pub fn resume_command(replacements: &[(&str, Option<&str>)]) -> String {
+    let mut args: Vec<String> = std::env::args().collect();
+    for (flag, value) in replacements {
+        if let Some(position) = args.iter().position(|arg| arg == flag) {
+            args.drain(position..(position + 2).min(args.len()));
+        }
+        if let Some(value) = value {
+            args.push(flag.to_string());
+            args.push(value.to_string());
+        }
+    }
+    shell_join(&args)
+}
+
+/// Arguments joined into a command line a POSIX shell splits back into them
+pub fn shell_join(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || c == '\'') {
+                format!("'{}'", arg.replace('\'', "'\\''"))
+            } else {
+                arg.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interrupt_escalates() {
+        let interrupt = Interrupt::default();
+        assert!(!interrupt.stop_requested());
+        assert!(interrupt.check_stop().is_ok());
+
+        let shared = interrupt.clone();
+        assert_eq!(shared.request(), 1);
+        assert!(interrupt.stop_requested() && !interrupt.abort_requested());
+        assert!(interrupt.check_abort().is_ok());
+        let error = interrupt.check_stop().unwrap_err();
+        assert!(is_interrupted(&error));
+        assert!(is_interrupted(&error.context("Experiment exp_000001")));
+        assert!(!is_interrupted(&anyhow::anyhow!("failed")));
+
+        assert_eq!(shared.request(), 2);
+        assert!(interrupt.abort_requested());
+        assert!(interrupt.check_abort().is_err());
+    }
+}
@@ -23,22 +23,78 @@
 #![allow(unused_variables)]
 
 use anyhow::Result;
+use asbb_core::compare::outputs_match;
+use asbb_core::memory::MemoryActivity;
+use asbb_core::stats::{self, DEFAULT_LATENCY_PERCENTILES};
 use asbb_core::{
-    HardwareConfig, OperationOutput, PerformanceResult, PrimitiveOperation, SequenceRecord,
+    HardwareConfig, OperationOutput, PerformanceResult, PhaseTimings, PrimitiveOperation,
+    SequenceRecord,
 };
+use asbb_ops::thread_pool::{self, PoolKey};
 use std::time::Instant;
 
+pub mod amortization;
 pub mod benchmark;
+pub mod calibration;
+pub mod crossover;
+pub mod dataset_cache;
+pub mod energy;
 pub mod runner;
 pub mod execution_engine;
-
+pub mod external;
+pub mod golden;
+pub mod interrupt;
+pub mod logging;
+pub mod pipeline;
+pub mod plan;
+pub mod pruning;
+pub mod reproducibility;
+pub mod soak;
+pub mod strategy;
+pub mod streaming;
+pub mod sweep;
+pub mod timing;
+pub mod translation;
+pub mod workload;
+
+pub use amortization::{measure_encoding_amortization, EncodingAmortization};
 pub use benchmark::Benchmark;
+pub use calibration::{measure_serialization_overhead, SerializationOverhead};
+pub use crossover::{find_crossover, Crossover, CrossoverSearch, RatioEstimate};
+pub use dataset_cache::{CacheStats, Cached, DatasetCache};
+pub use energy::EnergyMeter;
 pub use runner::BenchmarkRunner;
-pub use execution_engine::{ExecutionEngine, ExperimentConfig, ExperimentResult};
+pub use execution_engine::{ExecutionEngine, ExperimentConfig, ExperimentResult, RunStatus};
+pub use external::{
+    compare_tool, CommandRun, Equivalence, ExternalCommand, ExternalTool, ToolComparison,
+};
+pub use golden::{GoldenOutput, GoldenStore, ValidationStatus};
+pub use pipeline::{benchmark_pipeline, PipelineBottleneck, PipelineResult};
+pub use plan::{DurationEstimator, ExperimentPlan, PriorTiming};
+pub use pruning::{PruningPolicy, PruningStrategy};
+pub use reproducibility::{manifest_path_for, Drift, RunManifest};
+pub use soak::{run_soak, SoakConfig, SoakResult, SoakWindow, ThermalState};
+pub use streaming::{benchmark_operation_streaming, StreamingResult};
+pub use sweep::HardwareSweep;
+pub use timing::{benchmark_file_end_to_end, benchmark_phases, PhaseTimer};
+pub use workload::{benchmark_workload, WorkloadMix, WorkloadResult};
 
 /// Benchmark a single operation with a specific configuration
 ///
 /// Runs the operation multiple times (warmup + measured) and collects performance data.
+///
+/// Only the computation is timed: operations return typed results, and
+/// conversion to JSON (if any) happens after the timed region. Output
+/// validation is likewise outside the timing. The JSON conversion is still
+/// measured and reported per run in `phase_timings` (see [`timing`] for
+/// end-to-end runs that also attribute load and encode time).
+///
+/// Parallel runs reuse a shared thread pool unless reuse is disabled with
+/// [`asbb_ops::thread_pool::set_pool_reuse`], in which case each measured run
+/// includes pool construction.
+///
+/// Reports the [default latency percentiles](DEFAULT_LATENCY_PERCENTILES);
+/// use [`benchmark_operation_with_percentiles`] to choose others.
 pub fn benchmark_operation(
     operation: &dyn PrimitiveOperation,
     data: &[SequenceRecord],
@@ -46,30 +102,76 @@ pub fn benchmark_operation(
     warmup_runs: usize,
     measured_runs: usize,
 ) -> Result<PerformanceResult> {
+    benchmark_operation_with_percentiles(
+        operation,
+        data,
+        config,
+        warmup_runs,
+        measured_runs,
+        DEFAULT_LATENCY_PERCENTILES,
+    )
+}
+
+/// [`benchmark_operation`] reporting the given latency percentiles (0–100)
+///
+/// Percentiles are interpolated between the closest measured runs, so they
+/// are meaningful at any run count; `latency_p50` and `latency_p99` are
+/// always filled in addition to the requested map.
+pub fn benchmark_operation_with_percentiles(
+    operation: &dyn PrimitiveOperation,
+    data: &[SequenceRecord],
+    config: &HardwareConfig,
+    warmup_runs: usize,
+    measured_runs: usize,
+    percentiles: &[f64],
+) -> Result<PerformanceResult> {
+    if measured_runs == 0 {
+        anyhow::bail!("At least one measured run is required");
+    }
+    stats::validate_percentiles(percentiles)?;
+
     // Warmup runs (not measured)
     for _ in 0..warmup_runs {
-        let _ = operation.execute_with_config(data, config)?;
+        let _ = execute_configured(operation, data, config)?;
     }
 
     // Measured runs
+    let tolerance = operation.output_tolerance();
     let mut durations = Vec::with_capacity(measured_runs);
     let mut reference_output: Option<OperationOutput> = None;
 
+    let mut phase_timings = Vec::with_capacity(measured_runs);
+    let mut memory_activity = Vec::with_capacity(measured_runs);
+    let spilled_before = asbb_ops::spill::spilled_bytes();
+
     for i in 0..measured_runs {
+        let activity_before = MemoryActivity::sample();
         let start = Instant::now();
-        let output = operation.execute_with_config(data, config)?;
+        let output = execute_configured(operation, data, config)?;
         let duration = start.elapsed();
+        if let (Some(before), Some(after)) = (activity_before, MemoryActivity::sample()) {
+            memory_activity.push(after.since(&before));
+        }
 
         durations.push(duration);
 
+        // Serialization is timed separately and never counts toward throughput
+        let mut timer = PhaseTimer::new();
+        timer.serialize(&output)?;
+        phase_timings.push(PhaseTimings {
+            compute_time: duration,
+            ..timer.finish()
+        });
+
         // Save first output as reference for correctness validation
         if i == 0 {
             reference_output = Some(output.clone());
         }
 
-        // Validate all outputs match reference
+        // Validate all outputs match reference (floats may differ in the last
+        // bits when a parallel reduction is scheduled differently)
         if let Some(ref expected) = reference_output {
-            if output != *expected {
+            if !outputs_match(expected, &output, &tolerance) {
                 anyhow::bail!(
                     "Output mismatch on run {}: expected != actual",
                     i
@@ -78,19 +180,25 @@ pub fn benchmark_operation(
         }
     }
 
+    let spilled_bytes = (asbb_ops::spill::spilled_bytes() - spilled_before) / measured_runs as u64;
+
     // Validate against naive baseline for correctness
     let naive_config = HardwareConfig::naive();
     let naive_output = operation.execute_with_config(data, &naive_config)?;
     let output_matches_reference = match (&naive_output, &reference_output) {
-        (naive, Some(optimized)) => naive == optimized,
+        (naive, Some(optimized)) => outputs_match(naive, optimized, &tolerance),
         _ => false,
     };
 
     // Calculate statistics
     durations.sort();
-    let latency_p50 = durations[durations.len() / 2];
-    let latency_p99 = durations[(durations.len() * 99) / 100];
-    let latency_first_result = durations[0]; // Streaming: time to first result
+    let latency_p50 = stats::duration_quantile(&durations, 50.0).unwrap_or_default();
+    let latency_p99 = stats::duration_quantile(&durations, 99.0).unwrap_or_default();
+    let latency_percentiles = stats::latency_percentiles(&durations, percentiles);
+    // Batch mode produces its only result when the full run completes, so the
+    // first result arrives after a whole run. Use `streaming` for genuine
+    // time-to-first-output measurements.
+    let latency_first_result = latency_p50;
 
     // Calculate throughput
     let total_sequences: usize = data.len();
@@ -114,15 +222,36 @@ pub fn benchmark_operation(
         latency_first_result,
         latency_p50,
         latency_p99,
+        latency_percentiles,
+        phase_timings,
         memory_peak,
         memory_avg,
         cpu_utilization,
         gpu_utilization,
         energy_joules,
+        spilled_bytes,
+        memory_activity,
         output_matches_reference,
     })
 }
 
+/// Execute with the configuration's thread pool (thread count and QoS)
+///
+/// `execute_parallel` only receives a thread count, so the pool matching
+/// `config.qos` is injected around the call.
+pub fn execute_configured(
+    operation: &dyn PrimitiveOperation,
+    data: &[SequenceRecord],
+    config: &HardwareConfig,
+) -> Result<OperationOutput> {
+    if config.num_threads > 1 {
+        let pool = thread_pool::pool_for(PoolKey::new(config.num_threads, config.qos))?;
+        thread_pool::with_pool(pool, || operation.execute_with_config(data, config))
+    } else {
+        operation.execute_with_config(data, config)
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -155,6 +284,11 @@ mod tests {
         assert!(result.throughput_mbps > 0.0);
         assert!(result.output_matches_reference);
         assert!(result.latency_p50 > Duration::from_nanos(1));
+
+        // Compute-only throughput; serialization is recorded separately
+        assert_eq!(result.phase_timings.len(), 5);
+        assert!(result.phase_timings.iter().all(|t| t.load_time.is_zero() && t.encode_time.is_zero()));
+        assert!(result.median_phases().serialize_time > Duration::ZERO);
     }
 
     #[test]
@@ -211,4 +345,24 @@ mod tests {
         let speedup = parallel_result.speedup_vs(&naive_result);
         println!("Parallel (4 threads) speedup: {:.2}×", speedup);
     }
+
+    #[test]
+    fn test_benchmark_operation_percentiles() {
+        let op = BaseCounting::new();
+        let data = create_test_data(100, 150);
+        let config = HardwareConfig::naive();
+
+        let result =
+            benchmark_operation_with_percentiles(&op, &data, &config, 0, 3, &[25.0, 99.9]).unwrap();
+        let p25 = result.latency_percentile(25.0).unwrap();
+        let p999 = result.latency_percentile(99.9).unwrap();
+
+        assert_eq!(result.latency_percentiles.len(), 2);
+        assert!(p25 <= result.latency_p50 && result.latency_p50 <= p999);
+        assert!(result.latency_p99 <= p999);
+        assert_eq!(result.latency_percentile(90.0), None);
+
+        assert!(benchmark_operation_with_percentiles(&op, &data, &config, 0, 3, &[150.0]).is_err());
+        assert!(benchmark_operation(&op, &data, &config, 0, 0).is_err());
+    }
 }
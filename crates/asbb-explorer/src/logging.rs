@@ -0,0 +1,109 @@
+//! Structured logging for experiment runs
+//!
+//! The execution engine emits `tracing` events inside one span per
+//! experiment (`id`, `operation`, `config`, `scale`), so every message from a
+//! long unattended run can be traced back to the experiment that produced it.
+//! Binaries call [`init`] once at start-up:
+//!
+//! - Human-readable events go to stderr at the level chosen by the verbosity
+//!   (`-v` → info, `-vv` → debug, `-vvv` → trace; warnings and errors by
+//!   default). `RUST_LOG` overrides it, e.g. `RUST_LOG=asbb_explorer=debug`.
+//! - With a JSON path, every event at debug level or above is also appended
+//!   as one JSON object per line, including the enclosing experiment span,
+//!   for post-mortem debugging regardless of the console verbosity.
+//!
+//! Progress bars and result summaries stay on stdout; logging is for
+//! diagnostics only.
+
+use anyhow::{Context, Result};
+use std::fs::OpenOptions;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::filter::EnvFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+/// Logging options shared by the harness binaries
+#[derive(Debug, Clone, Default)]
+pub struct LogOptions {
+    /// Number of `-v` flags (0: warnings and errors only)
+    pub verbosity: u8,
+    /// Append JSON lines to this file
+    pub json_path: Option<PathBuf>,
+}
+
+impl LogOptions {
+    /// Console level for the verbosity
+    pub fn level(&self) -> LevelFilter {
+        match self.verbosity {
+            0 => LevelFilter::WARN,
+            1 => LevelFilter::INFO,
+            2 => LevelFilter::DEBUG,
+            _ => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// Install the global subscriber
+///
+/// Fails if the JSON file cannot be opened or a subscriber is already set.
+pub fn init(options: &LogOptions) -> Result<()> {
+    let console_filter = EnvFilter::builder()
+        .with_default_directive(options.level().into())
+        .from_env_lossy();
+    let console = tracing_subscriber::fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_target(false)
+        .with_ansi(std::io::stderr().is_terminal())
+        .with_filter(console_filter);
+
+    let json = match &options.json_path {
+        Some(path) => {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("Failed to open log file {}", path.display()))?;
+            let level = options.level().max(LevelFilter::DEBUG);
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_current_span(true)
+                    .with_span_list(true)
+                    .with_writer(Mutex::new(file))
+                    .with_filter(level),
+            )
+        }
+        None => None,
+    };
+
+    tracing_subscriber::registry()
+        .with(console)
+        .with(json)
+        .try_init()
+        .context("Failed to install the log subscriber")
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_levels() {
+        let level = |verbosity| LogOptions { verbosity, json_path: None }.level();
+        assert_eq!(level(0), LevelFilter::WARN);
+        assert_eq!(level(1), LevelFilter::INFO);
+        assert_eq!(level(2), LevelFilter::DEBUG);
+        assert_eq!(level(5), LevelFilter::TRACE);
+    }
+}
@@ -0,0 +1,359 @@
+//! Producer/consumer pipeline harness
+//!
+//! Models the two-stage pipeline used by streaming tools: a reader thread
+//! parses record batches and pushes them into a bounded channel, and a compute
+//! thread pulls batches and runs the operation. The channel depth bounds how
+//! far the reader may run ahead (backpressure).
+//!
+//! From per-stage timings we can answer:
+//! - How much does overlapping I/O and compute help vs running them serially?
+//! - Which stage is the bottleneck? If the consumer spends its time waiting on
+//!   an empty channel, parsing limits throughput; if the producer spends its
+//!   time blocked on a full channel, the operation does.
+//! - How deep does the channel need to be before extra buffering stops helping?
+
+use anyhow::Result;
+use asbb_core::{HardwareConfig, PrimitiveOperation, SequenceRecord};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Fraction of stage time spent waiting above which a stage is considered
+/// starved (consumer) or blocked (producer)
+const WAIT_FRACTION_THRESHOLD: f64 = 0.2;
+
+/// Which pipeline stage limits throughput
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PipelineBottleneck {
+    /// Reader/parser cannot keep the compute thread fed
+    Io,
+    /// Compute thread cannot keep up (reader blocks on a full channel)
+    Compute,
+    /// Neither stage waits significantly on the other
+    Balanced,
+}
+
+/// Results from a pipeline benchmark (medians across measured runs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineResult {
+    /// Bounded channel capacity (batches)
+    pub channel_depth: usize,
+
+    /// Number of batches per run
+    pub num_batches: usize,
+
+    /// Total sequences processed per run
+    pub num_sequences: usize,
+
+    /// Wall-clock time of the whole pipeline
+    pub elapsed: Duration,
+
+    /// Time the producer spent reading/parsing batches
+    pub produce_time: Duration,
+
+    /// Time the producer spent blocked on a full channel (backpressure)
+    pub producer_blocked_time: Duration,
+
+    /// Time the consumer spent executing the operation
+    pub compute_time: Duration,
+
+    /// Time the consumer spent waiting on an empty channel
+    pub consumer_starved_time: Duration,
+
+    /// End-to-end throughput (sequences/second)
+    pub throughput_seqs_per_sec: f64,
+
+    /// Serial time (produce + compute) divided by pipelined wall time
+    pub overlap_speedup: f64,
+
+    /// Stage limiting throughput
+    pub bottleneck: PipelineBottleneck,
+}
+
+/// Per-run measurements
+struct PipelineRun {
+    num_batches: usize,
+    num_sequences: usize,
+    elapsed: Duration,
+    produce_time: Duration,
+    producer_blocked_time: Duration,
+    compute_time: Duration,
+    consumer_starved_time: Duration,
+}
+
+/// Producer-side timings, returned from the reader thread
+struct ProducerStats {
+    produce_time: Duration,
+    blocked_time: Duration,
+}
+
+/// Benchmark an operation behind a bounded producer/consumer channel
+///
+/// `make_source` is called once per run and must return a fresh batch
+/// iterator (see [`crate::streaming::fastq_source`]). The iterator is driven
+/// on a dedicated reader thread; the operation runs on the calling thread.
+pub fn benchmark_pipeline<F, I>(
+    operation: &dyn PrimitiveOperation,
+    make_source: F,
+    config: &HardwareConfig,
+    channel_depth: usize,
+    warmup_runs: usize,
+    measured_runs: usize,
+) -> Result<PipelineResult>
+where
+    F: Fn() -> Result<I>,
+    I: Iterator<Item = Result<Vec<SequenceRecord>>> + Send,
+{
+    if channel_depth == 0 {
+        anyhow::bail!("Channel depth must be at least 1");
+    }
+    if measured_runs == 0 {
+        anyhow::bail!("Pipeline benchmark requires at least one measured run");
+    }
+
+    for _ in 0..warmup_runs {
+        run_pipeline(operation, make_source()?, config, channel_depth)?;
+    }
+
+    let mut runs = Vec::with_capacity(measured_runs);
+    for _ in 0..measured_runs {
+        runs.push(run_pipeline(operation, make_source()?, config, channel_depth)?);
+    }
+
+    // Report the run with median wall time so all stage timings are consistent
+    runs.sort_by_key(|r| r.elapsed);
+    let run = &runs[runs.len() / 2];
+
+    let elapsed_secs = run.elapsed.as_secs_f64().max(f64::MIN_POSITIVE);
+    let serial_secs = (run.produce_time + run.compute_time).as_secs_f64();
+
+    Ok(PipelineResult {
+        channel_depth,
+        num_batches: run.num_batches,
+        num_sequences: run.num_sequences,
+        elapsed: run.elapsed,
+        produce_time: run.produce_time,
+        producer_blocked_time: run.producer_blocked_time,
+        compute_time: run.compute_time,
+        consumer_starved_time: run.consumer_starved_time,
+        throughput_seqs_per_sec: run.num_sequences as f64 / elapsed_secs,
+        overlap_speedup: serial_secs / elapsed_secs,
+        bottleneck: classify_bottleneck(run),
+    })
+}
+
+/// Run the pipeline at several channel depths
+///
+/// Useful for finding the depth beyond which additional buffering no longer
+/// improves throughput.
+pub fn sweep_channel_depths<F, I>(
+    operation: &dyn PrimitiveOperation,
+    make_source: F,
+    config: &HardwareConfig,
+    depths: &[usize],
+    warmup_runs: usize,
+    measured_runs: usize,
+) -> Result<Vec<PipelineResult>>
+where
+    F: Fn() -> Result<I>,
+    I: Iterator<Item = Result<Vec<SequenceRecord>>> + Send,
+{
+    depths
+        .iter()
+        .map(|&depth| {
+            benchmark_pipeline(
+                operation,
+                &make_source,
+                config,
+                depth,
+                warmup_runs,
+                measured_runs,
+            )
+        })
+        .collect()
+}
+
+/// Execute one pipelined run
+fn run_pipeline<I>(
+    operation: &dyn PrimitiveOperation,
+    source: I,
+    config: &HardwareConfig,
+    channel_depth: usize,
+) -> Result<PipelineRun>
+where
+    I: Iterator<Item = Result<Vec<SequenceRecord>>> + Send,
+{
+    let (sender, receiver) = mpsc::sync_channel::<Vec<SequenceRecord>>(channel_depth);
+    let start = Instant::now();
+
+    thread::scope(|scope| {
+        let producer = scope.spawn(move || -> Result<ProducerStats> {
+            let mut source = source;
+            let mut stats = ProducerStats {
+                produce_time: Duration::ZERO,
+                blocked_time: Duration::ZERO,
+            };
+
+            loop {
+                let read_start = Instant::now();
+                let batch = match source.next() {
+                    Some(batch) => batch?,
+                    None => break,
+                };
+                stats.produce_time += read_start.elapsed();
+
+                let send_start = Instant::now();
+                if sender.send(batch).is_err() {
+                    // Consumer hung up (it failed); stop producing
+                    break;
+                }
+                stats.blocked_time += send_start.elapsed();
+            }
+
+            Ok(stats)
+        });
+
+        let mut num_batches = 0;
+        let mut num_sequences = 0;
+        let mut compute_time = Duration::ZERO;
+        let mut consumer_starved_time = Duration::ZERO;
+
+        loop {
+            let wait_start = Instant::now();
+            let batch = match receiver.recv() {
+                Ok(batch) => batch,
+                Err(_) => break, // Producer finished
+            };
+            consumer_starved_time += wait_start.elapsed();
+
+            let compute_start = Instant::now();
+            let result = operation.execute_with_config(&batch, config);
+            compute_time += compute_start.elapsed();
+
+            if let Err(e) = result {
+                // Drop the receiver so the producer unblocks and exits
+                drop(receiver);
+                let _ = producer.join();
+                return Err(e);
+            }
+
+            num_batches += 1;
+            num_sequences += batch.len();
+        }
+
+        let elapsed = start.elapsed();
+        let producer_stats = producer
+            .join()
+            .map_err(|_| anyhow::anyhow!("Producer thread panicked"))??;
+
+        Ok(PipelineRun {
+            num_batches,
+            num_sequences,
+            elapsed,
+            produce_time: producer_stats.produce_time,
+            producer_blocked_time: producer_stats.blocked_time,
+            compute_time,
+            consumer_starved_time,
+        })
+    })
+}
+
+/// Classify which stage limited throughput from wait-time fractions
+fn classify_bottleneck(run: &PipelineRun) -> PipelineBottleneck {
+    let elapsed = run.elapsed.as_secs_f64().max(f64::MIN_POSITIVE);
+    let starved = run.consumer_starved_time.as_secs_f64() / elapsed;
+    let blocked = run.producer_blocked_time.as_secs_f64() / elapsed;
+
+    if starved > WAIT_FRACTION_THRESHOLD && starved >= blocked {
+        PipelineBottleneck::Io
+    } else if blocked > WAIT_FRACTION_THRESHOLD {
+        PipelineBottleneck::Compute
+    } else {
+        PipelineBottleneck::Balanced
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::memory_source;
+    use asbb_ops::base_counting::BaseCounting;
+
+    fn create_test_data(num_sequences: usize, seq_length: usize) -> Vec<SequenceRecord> {
+        (0..num_sequences)
+            .map(|i| SequenceRecord::fasta(format!("seq_{}", i), vec![b'A'; seq_length]))
+            .collect()
+    }
+
+    #[test]
+    fn test_pipeline_processes_all_batches() {
+        let op = BaseCounting::new();
+        let data = create_test_data(1000, 150);
+
+        let result = benchmark_pipeline(
+            &op,
+            memory_source(&data, 100),
+            &HardwareConfig::naive(),
+            2,
+            1,
+            3,
+        )
+        .unwrap();
+
+        assert_eq!(result.num_batches, 10);
+        assert_eq!(result.num_sequences, 1000);
+        assert!(result.throughput_seqs_per_sec > 0.0);
+        assert!(result.overlap_speedup > 0.0);
+    }
+
+    #[test]
+    fn test_pipeline_rejects_zero_depth() {
+        let op = BaseCounting::new();
+        let data = create_test_data(10, 10);
+        let result =
+            benchmark_pipeline(&op, memory_source(&data, 5), &HardwareConfig::naive(), 0, 0, 1);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_sweep_channel_depths() {
+        let op = BaseCounting::new();
+        let data = create_test_data(200, 50);
+
+        let results = sweep_channel_depths(
+            &op,
+            memory_source(&data, 20),
+            &HardwareConfig::naive(),
+            &[1, 4, 16],
+            0,
+            1,
+        )
+        .unwrap();
+
+        let depths: Vec<usize> = results.iter().map(|r| r.channel_depth).collect();
+        assert_eq!(depths, vec![1, 4, 16]);
+        assert!(results.iter().all(|r| r.num_sequences == 200));
+    }
+
+    #[test]
+    fn test_classify_bottleneck() {
+        let mut run = PipelineRun {
+            num_batches: 1,
+            num_sequences: 1,
+            elapsed: Duration::from_millis(100),
+            produce_time: Duration::from_millis(90),
+            producer_blocked_time: Duration::ZERO,
+            compute_time: Duration::from_millis(10),
+            consumer_starved_time: Duration::from_millis(80),
+        };
+        assert_eq!(classify_bottleneck(&run), PipelineBottleneck::Io);
+
+        run.consumer_starved_time = Duration::ZERO;
+        run.producer_blocked_time = Duration::from_millis(70);
+        assert_eq!(classify_bottleneck(&run), PipelineBottleneck::Compute);
+
+        run.producer_blocked_time = Duration::from_millis(5);
+        assert_eq!(classify_bottleneck(&run), PipelineBottleneck::Balanced);
+    }
+}
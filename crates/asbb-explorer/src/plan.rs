@@ -0,0 +1,296 @@
+//! Dry-run planning and duration estimates
+//!
+//! Both harnesses can enumerate the experiments they would run and estimate
+//! how long the batch will take, so machine time can be budgeted before
+//! committing to a multi-hour run. Estimates come from prior results:
+//!
+//! 1. **Prior**: the same operation, config and scale has been measured
+//! 2. **Extrapolated**: the same operation and config at another scale
+//!    (nearest scale, scaled linearly by sequence count), else the same
+//!    operation under any config (slowest per-sequence cost, to stay
+//!    conservative)
+//! 3. **Default**: nothing is known; [`DEFAULT_SECONDS_PER_SEQUENCE`]
+//!
+//! There is no fitted prediction model yet; once one exists it slots in as
+//! another [`EstimateSource`] ahead of the default.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Per-sequence cost assumed when no prior result is available (naive scalar)
+pub const DEFAULT_SECONDS_PER_SEQUENCE: f64 = 1e-6;
+
+/// One measured run time from an earlier batch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriorTiming {
+    pub operation: String,
+    pub config: String,
+    pub num_sequences: usize,
+    /// Median time of one run (seconds)
+    pub seconds_per_run: f64,
+}
+
+/// Where an estimate came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EstimateSource {
+    Prior,
+    Extrapolated,
+    Default,
+}
+
+/// Estimated time of one run of an experiment
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RunEstimate {
+    pub seconds_per_run: f64,
+    pub source: EstimateSource,
+}
+
+/// Estimates run times from prior results
+#[derive(Debug, Clone, Default)]
+pub struct DurationEstimator {
+    priors: Vec<PriorTiming>,
+}
+
+impl DurationEstimator {
+    pub fn new(priors: Vec<PriorTiming>) -> Self {
+        Self { priors }
+    }
+
+    /// Number of prior timings available
+    pub fn len(&self) -> usize {
+        self.priors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.priors.is_empty()
+    }
+
+    /// Estimate one run of `operation` under `config` on `num_sequences` sequences
+    pub fn estimate(&self, operation: &str, config: &str, num_sequences: usize) -> RunEstimate {
+        let same_config: Vec<&PriorTiming> = self
+            .priors
+            .iter()
+            .filter(|p| p.operation == operation && p.config == config && p.num_sequences > 0)
+            .collect();
+
+        if let Some(exact) = same_config.iter().find(|p| p.num_sequences == num_sequences) {
+            return RunEstimate {
+                seconds_per_run: exact.seconds_per_run,
+                source: EstimateSource::Prior,
+            };
+        }
+
+        // Nearest scale on a log axis, scaled linearly by sequence count
+        let nearest = same_config.iter().min_by(|a, b| {
+            let distance = |p: &PriorTiming| {
+                ((p.num_sequences as f64).ln() - (num_sequences.max(1) as f64).ln()).abs()
+            };
+            distance(a).total_cmp(&distance(b))
+        });
+        if let Some(nearest) = nearest {
+            return RunEstimate {
+                seconds_per_run: per_sequence(nearest) * num_sequences as f64,
+                source: EstimateSource::Extrapolated,
+            };
+        }
+
+        let slowest = self
+            .priors
+            .iter()
+            .filter(|p| p.operation == operation && p.num_sequences > 0)
+            .map(per_sequence)
+            .max_by(f64::total_cmp);
+        if let Some(per_sequence) = slowest {
+            return RunEstimate {
+                seconds_per_run: per_sequence * num_sequences as f64,
+                source: EstimateSource::Extrapolated,
+            };
+        }
+
+        RunEstimate {
+            seconds_per_run: DEFAULT_SECONDS_PER_SEQUENCE * num_sequences as f64,
+            source: EstimateSource::Default,
+        }
+    }
+}
+
+fn per_sequence(prior: &PriorTiming) -> f64 {
+    prior.seconds_per_run / prior.num_sequences as f64
+}
+
+/// An experiment a harness would run, with its estimated duration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedExperiment {
+    pub operation: String,
+    pub config: String,
+    pub scale: String,
+    pub num_sequences: usize,
+    /// Warmup plus measured runs
+    pub runs: usize,
+    pub estimate: RunEstimate,
+}
+
+impl PlannedExperiment {
+    /// Estimated wall time for all runs (excludes data loading)
+    pub fn estimated_duration(&self) -> Duration {
+        Duration::from_secs_f64(self.estimate.seconds_per_run * self.runs as f64)
+    }
+}
+
+/// Experiments a batch would run, in execution order
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExperimentPlan {
+    pub experiments: Vec<PlannedExperiment>,
+}
+
+impl ExperimentPlan {
+    /// Add an experiment, estimating its duration with `estimator`
+    pub fn push(
+        &mut self,
+        estimator: &DurationEstimator,
+        operation: &str,
+        config: &str,
+        scale: &str,
+        num_sequences: usize,
+        runs: usize,
+    ) {
+        self.experiments.push(PlannedExperiment {
+            operation: operation.to_string(),
+            config: config.to_string(),
+            scale: scale.to_string(),
+            num_sequences,
+            runs,
+            estimate: estimator.estimate(operation, config, num_sequences),
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.experiments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.experiments.is_empty()
+    }
+
+    /// Estimated wall time of the whole batch
+    pub fn estimated_duration(&self) -> Duration {
+        self.experiments
+            .iter()
+            .map(PlannedExperiment::estimated_duration)
+            .sum()
+    }
+
+    /// Number of experiments per estimate source
+    pub fn source_counts(&self) -> BTreeMap<EstimateSource, usize> {
+        let mut counts = BTreeMap::new();
+        for experiment in &self.experiments {
+            *counts.entry(experiment.estimate.source).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Print the plan and its ETA
+    pub fn print(&self) {
+        println!("📋 Dry run: {} experiments would run", self.len());
+        println!();
+        println!(
+            "  {:<24} {:<20} {:<12} {:>10} {:>6} {:>12}  source",
+            "operation", "config", "scale", "sequences", "runs", "estimate"
+        );
+        for experiment in &self.experiments {
+            println!(
+                "  {:<24} {:<20} {:<12} {:>10} {:>6} {:>12}  {:?}",
+                experiment.operation,
+                experiment.config,
+                experiment.scale,
+                experiment.num_sequences,
+                experiment.runs,
+                format_duration(experiment.estimated_duration()),
+                experiment.estimate.source,
+            );
+        }
+        println!();
+
+        let sources: Vec<String> = self
+            .source_counts()
+            .iter()
+            .map(|(source, count)| format!("{} {:?}", count, source).to_lowercase())
+            .collect();
+        println!("⏱️  Estimated total: {} (excluding data loading)", format_duration(self.estimated_duration()));
+        println!("   Estimates: {}", sources.join(", "));
+    }
+}
+
+/// Human-readable duration (e.g. `2h 05m`, `3m 12s`, `0.41s`)
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs >= 3600.0 {
+        let minutes = (secs / 60.0).round() as u64;
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else if secs >= 60.0 {
+        let whole = secs.round() as u64;
+        format!("{}m {:02}s", whole / 60, whole % 60)
+    } else {
+        format!("{:.2}s", secs)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prior(config: &str, num_sequences: usize, seconds_per_run: f64) -> PriorTiming {
+        PriorTiming {
+            operation: "gc_content".to_string(),
+            config: config.to_string(),
+            num_sequences,
+            seconds_per_run,
+        }
+    }
+
+    #[test]
+    fn test_estimate_sources() {
+        let estimator = DurationEstimator::new(vec![
+            prior("naive", 1_000, 0.002),
+            prior("naive", 100_000, 0.3),
+            prior("neon", 1_000, 0.0005),
+        ]);
+
+        let exact = estimator.estimate("gc_content", "naive", 1_000);
+        assert_eq!(exact.source, EstimateSource::Prior);
+        assert_eq!(exact.seconds_per_run, 0.002);
+
+        // 50K is nearer 100K than 1K on a log axis
+        let scaled = estimator.estimate("gc_content", "naive", 50_000);
+        assert_eq!(scaled.source, EstimateSource::Extrapolated);
+        assert!((scaled.seconds_per_run - 0.15).abs() < 1e-12);
+
+        // Unknown config falls back to the operation's slowest per-sequence cost
+        let other_config = estimator.estimate("gc_content", "neon_4t", 10_000);
+        assert_eq!(other_config.source, EstimateSource::Extrapolated);
+        assert!((other_config.seconds_per_run - 0.03).abs() < 1e-12);
+
+        let unknown = estimator.estimate("base_counting", "naive", 10_000);
+        assert_eq!(unknown.source, EstimateSource::Default);
+    }
+
+    #[test]
+    fn test_plan_totals() {
+        let estimator = DurationEstimator::new(vec![prior("naive", 1_000, 0.5)]);
+        let mut plan = ExperimentPlan::default();
+        plan.push(&estimator, "gc_content", "naive", "Small", 1_000, 10);
+        plan.push(&estimator, "base_counting", "naive", "Small", 1_000, 10);
+
+        assert_eq!(plan.len(), 2);
+        assert!((plan.estimated_duration().as_secs_f64() - 5.01).abs() < 1e-9);
+        assert_eq!(plan.source_counts()[&EstimateSource::Prior], 1);
+        assert_eq!(format_duration(Duration::from_secs(7500)), "2h 05m");
+        assert_eq!(format_duration(Duration::from_secs(192)), "3m 12s");
+    }
+}
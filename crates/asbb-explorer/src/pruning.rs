@@ -0,0 +1,731 @@
+//! Pruning strategies: which configs of a DAG cell are worth measuring
+//!
+//! A cell is one (operation, scale). The DAG traversal's original policy,
+//! [`ThresholdPruning`], is what the 93% experiment reduction rests on: an
+//! alternative (NEON) below 1.5× prunes every composition built on it, and a
+//! thread count adding less than 1.3× stops the climb. The other strategies
+//! spend repetitions rather than whole experiments adaptively, so the
+//! reduction can be compared against smarter exploration:
+//!
+//! - [`SuccessiveHalving`]: measure every config briefly, keep the better
+//!   half, double their runs, repeat until one config remains
+//! - [`UcbBandit`]: spend half the exhaustive run budget in small pulls,
+//!   each going to the config with the highest upper confidence bound
+//! - [`BayesianStopping`]: measure each config until the posterior
+//!   probability that it beats the incumbent is decisive either way
+//!
+//! [`Exhaustive`] prunes nothing; [`replay`] runs a strategy against its
+//! results to show what the strategy would have skipped.
+//!
+//! Strategies drive a [`Cell`], which runs the measurements. Measuring a
+//! config again adds runs to its samples, so every estimate covers all runs
+//! spent on it.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Runs per measurement round of the adaptive strategies (the minimum for
+/// outlier removal and a confidence interval)
+pub const MIN_ROUND_REPETITIONS: usize = 5;
+
+/// Posterior probability at which [`BayesianStopping`] decides
+pub const DECISION_PROBABILITY: f64 = 0.95;
+
+/// Share of the exhaustive run budget [`UcbBandit`] spends
+pub const UCB_BUDGET_FRACTION: f64 = 0.5;
+
+/// How a candidate relates to the others in its cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Single-threaded alternative (NEON); gates the compositions
+    Alternative,
+    /// Multi-threaded composition on the alternative, one per thread count
+    Composition,
+    /// Variant of the composition at the same thread count (chunked);
+    /// measured alongside it but never gates anything
+    Variant,
+}
+
+/// A config of the cell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    /// Config key (e.g. `neon_4t`)
+    pub key: String,
+    pub threads: usize,
+    pub role: Role,
+
+    /// Passes the `--configs` filter; unselected candidates only shape the
+    /// threshold strategy's thread-count ladder
+    pub selected: bool,
+}
+
+/// Speedup over naive from every run of one candidate so far
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    pub speedup_median: f64,
+    pub speedup_mean: f64,
+    pub speedup_std_dev: f64,
+
+    /// Valid runs behind the estimate (0 if the candidate was pruned earlier)
+    pub n: usize,
+}
+
+impl Estimate {
+    /// Posterior probability that the mean speedup exceeds `target`
+    ///
+    /// Flat prior and a normal likelihood: the posterior of the mean is
+    /// N(mean, std_dev² / n).
+    pub fn probability_above(&self, target: f64) -> f64 {
+        let standard_error = self.speedup_std_dev / (self.n.max(1) as f64).sqrt();
+        if standard_error <= 0.0 {
+            return if self.speedup_mean > target { 1.0 } else { 0.0 };
+        }
+        normal_cdf((self.speedup_mean - target) / standard_error)
+    }
+}
+
+/// Measurements of one cell, run by the harness
+pub trait Cell {
+    /// Run `repetitions` more timed runs of `candidate`; the estimate covers
+    /// all its runs so far
+    fn measure(&mut self, candidate: usize, repetitions: usize) -> Result<Estimate>;
+
+    /// Exclude `candidate` from this operation at later scales
+    fn prune(&mut self, candidate: usize);
+
+    /// `candidate` was planned but will not run in this cell
+    fn skip(&mut self, candidate: usize);
+
+    /// Progress line
+    fn log(&mut self, line: String);
+}
+
+/// Policy deciding which candidates of a cell to measure, and how much
+pub trait PruningStrategy {
+    fn name(&self) -> &'static str;
+
+    /// Explore `candidates` through `cell`; `repetitions` is the run count of
+    /// one full measurement
+    fn explore(&self, candidates: &[Candidate], repetitions: usize, cell: &mut dyn Cell)
+        -> Result<()>;
+}
+
+/// Strategy selectable from the DAG config (`--pruning`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PruningPolicy {
+    #[default]
+    Threshold,
+    Halving,
+    Ucb,
+    Bayesian,
+}
+
+impl PruningPolicy {
+    pub const ALL: [PruningPolicy; 4] = [
+        PruningPolicy::Threshold,
+        PruningPolicy::Halving,
+        PruningPolicy::Ucb,
+        PruningPolicy::Bayesian,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PruningPolicy::Threshold => "threshold",
+            PruningPolicy::Halving => "halving",
+            PruningPolicy::Ucb => "ucb",
+            PruningPolicy::Bayesian => "bayesian",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|policy| policy.name() == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(PruningPolicy::name).collect();
+                anyhow::anyhow!("Unknown pruning strategy '{}' (available: {})", name, names.join(", "))
+            })
+    }
+
+    /// The strategy, with the DAG's speedup and diminishing-returns thresholds
+    pub fn strategy(
+        &self,
+        speedup_threshold: f64,
+        diminishing_returns_threshold: f64,
+    ) -> Box<dyn PruningStrategy> {
+        match self {
+            PruningPolicy::Threshold => {
+                Box::new(ThresholdPruning { speedup_threshold, diminishing_returns_threshold })
+            }
+            PruningPolicy::Halving => Box::new(SuccessiveHalving),
+            PruningPolicy::Ucb => Box::new(UcbBandit { budget_fraction: UCB_BUDGET_FRACTION }),
+            PruningPolicy::Bayesian => Box::new(BayesianStopping { speedup_threshold }),
+        }
+    }
+}
+
+/// Selected candidates, in order
+fn selected(candidates: &[Candidate]) -> Vec<usize> {
+    (0..candidates.len()).filter(|&i| candidates[i].selected).collect()
+}
+
+// ============================================================================
+// Threshold Pruning
+// ============================================================================
+
+/// The DAG's original rules (DAG_FRAMEWORK.md)
+///
+/// 1. Alternatives below `speedup_threshold` prune every composition
+/// 2. Compositions climb the thread counts while each step adds at least
+///    `diminishing_returns_threshold` over the previous one
+///
+/// A thread count whose composition is not selected breaks the chain: the
+/// next one runs without a diminishing-returns check.
+pub struct ThresholdPruning {
+    pub speedup_threshold: f64,
+    pub diminishing_returns_threshold: f64,
+}
+
+impl PruningStrategy for ThresholdPruning {
+    fn name(&self) -> &'static str {
+        "threshold"
+    }
+
+    fn explore(
+        &self,
+        candidates: &[Candidate],
+        repetitions: usize,
+        cell: &mut dyn Cell,
+    ) -> Result<()> {
+        let is = |i: usize, role: Role| candidates[i].role == role && candidates[i].selected;
+        let skip_from = |cell: &mut dyn Cell, min_threads: usize| {
+            for i in selected(candidates) {
+                if candidates[i].role != Role::Alternative && candidates[i].threads >= min_threads {
+                    cell.skip(i);
+                }
+            }
+        };
+
+        // Alternatives (pruning decisions need the candidates they compare
+        // against, so unselected ones prune nothing)
+        let mut parent_speedup = None;
+        for i in (0..candidates.len()).filter(|&i| is(i, Role::Alternative)) {
+            let estimate = cell.measure(i, repetitions)?;
+            if estimate.speedup_median < self.speedup_threshold {
+                cell.log(format!(
+                    "    ❌ {} pruned ({:.2}× < {}×)",
+                    candidates[i].key, estimate.speedup_median, self.speedup_threshold
+                ));
+                cell.prune(i);
+                skip_from(cell, 0);
+                return Ok(());
+            }
+            cell.log(format!("    ✅ {} kept ({:.2}×)", candidates[i].key, estimate.speedup_median));
+            parent_speedup = Some(estimate.speedup_median);
+        }
+
+        // Compositions, one thread count at a time
+        let mut thread_counts: Vec<usize> = candidates
+            .iter()
+            .filter(|c| c.role != Role::Alternative)
+            .map(|c| c.threads)
+            .collect();
+        thread_counts.sort_unstable();
+        thread_counts.dedup();
+
+        for (tested, &threads) in thread_counts.iter().enumerate() {
+            let at = |role: Role| {
+                (0..candidates.len()).filter(move |&i| is(i, role) && candidates[i].threads == threads)
+            };
+            let composition = match at(Role::Composition).next() {
+                Some(i) => Some((i, cell.measure(i, repetitions)?)),
+                None => None,
+            };
+            for variant in at(Role::Variant) {
+                let estimate = cell.measure(variant, repetitions)?;
+                if let Some((i, parallel)) = &composition {
+                    cell.log(format!(
+                        "    📦 {} {:.2}× vs {} {:.2}× ({:.2}× ratio)",
+                        candidates[variant].key,
+                        estimate.speedup_median,
+                        candidates[*i].key,
+                        parallel.speedup_median,
+                        estimate.speedup_median / parallel.speedup_median
+                    ));
+                }
+            }
+
+            let Some((i, estimate)) = composition else {
+                parent_speedup = None;
+                continue;
+            };
+
+            if let Some(parent) = parent_speedup {
+                let additional = estimate.speedup_median / parent;
+                if additional < self.diminishing_returns_threshold {
+                    cell.log(format!(
+                        "    ❌ {} pruned (additional benefit {:.2}× < {}×)",
+                        candidates[i].key, additional, self.diminishing_returns_threshold
+                    ));
+                    cell.prune(i);
+                    if let Some(&next) = thread_counts.get(tested + 1) {
+                        skip_from(cell, next);
+                    }
+                    break; // Don't test higher thread counts
+                }
+                cell.log(format!(
+                    "    ✅ {} kept ({:.2}×, additional {:.2}×)",
+                    candidates[i].key, estimate.speedup_median, additional
+                ));
+            }
+            parent_speedup = Some(estimate.speedup_median);
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Successive Halving
+// ============================================================================
+
+/// Successive halving over the selected candidates
+///
+/// With `n` candidates there are `⌈log2 n⌉ + 1` rounds; after round `r` each
+/// survivor has `repetitions / 2^(rounds - 1 - r)` runs (at least
+/// [`MIN_ROUND_REPETITIONS`]), so the last survivor ends with a full
+/// measurement and eliminated configs cost a fraction of one.
+pub struct SuccessiveHalving;
+
+impl PruningStrategy for SuccessiveHalving {
+    fn name(&self) -> &'static str {
+        "halving"
+    }
+
+    fn explore(
+        &self,
+        candidates: &[Candidate],
+        repetitions: usize,
+        cell: &mut dyn Cell,
+    ) -> Result<()> {
+        let mut alive = selected(candidates);
+        if alive.is_empty() {
+            return Ok(());
+        }
+        let full = repetitions.max(MIN_ROUND_REPETITIONS);
+        let rounds = (alive.len() as f64).log2().ceil() as u32 + 1;
+        let mut runs = vec![0; candidates.len()];
+        let mut latest: Vec<Option<Estimate>> = vec![None; candidates.len()];
+
+        for round in 0..rounds {
+            let target = (full >> (rounds - 1 - round)).max(MIN_ROUND_REPETITIONS);
+            let mut estimates = Vec::with_capacity(alive.len());
+            for &i in &alive {
+                // Early rounds may share the minimum; measure only what is missing
+                let estimate = match latest[i] {
+                    Some(estimate) if runs[i] >= target => estimate,
+                    _ => cell.measure(i, target - runs[i])?,
+                };
+                runs[i] = target.max(runs[i]);
+                latest[i] = Some(estimate);
+                estimates.push((i, estimate));
+            }
+            estimates.sort_by(|a, b| b.1.speedup_median.total_cmp(&a.1.speedup_median));
+
+            if round + 1 == rounds || estimates.len() == 1 {
+                let (winner, estimate) = estimates[0];
+                cell.log(format!(
+                    "    🏆 {} survives halving ({:.2}×, {} runs)",
+                    candidates[winner].key, estimate.speedup_median, runs[winner]
+                ));
+                break;
+            }
+            let keep = estimates.len().div_ceil(2);
+            for (i, estimate) in &estimates[keep..] {
+                cell.log(format!(
+                    "    ✂️  {} eliminated in round {} ({:.2}×, {} runs)",
+                    candidates[*i].key,
+                    round + 1,
+                    estimate.speedup_median,
+                    runs[*i]
+                ));
+            }
+            alive = estimates[..keep].iter().map(|(i, _)| *i).collect();
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// UCB Bandit
+// ============================================================================
+
+/// UCB1 bandit over the selected candidates
+///
+/// Every candidate gets one pull of [`MIN_ROUND_REPETITIONS`] runs; further
+/// pulls go to the highest `mean + best_mean · sqrt(2 ln t / pulls)` (the
+/// exploration bonus scaled to the speedups seen) until `budget_fraction`
+/// of the exhaustive budget (`candidates × repetitions`) is spent.
+pub struct UcbBandit {
+    pub budget_fraction: f64,
+}
+
+impl PruningStrategy for UcbBandit {
+    fn name(&self) -> &'static str {
+        "ucb"
+    }
+
+    fn explore(
+        &self,
+        candidates: &[Candidate],
+        repetitions: usize,
+        cell: &mut dyn Cell,
+    ) -> Result<()> {
+        let arms = selected(candidates);
+        if arms.is_empty() {
+            return Ok(());
+        }
+        let pull = MIN_ROUND_REPETITIONS;
+        let budget = ((arms.len() * repetitions) as f64 * self.budget_fraction) as usize;
+        let budget = budget.max(arms.len() * pull);
+
+        let mut pulls = vec![0usize; arms.len()];
+        let mut means = vec![0.0; arms.len()];
+        for (arm, &i) in arms.iter().enumerate() {
+            means[arm] = cell.measure(i, pull)?.speedup_mean;
+            pulls[arm] = 1;
+        }
+        let mut spent = arms.len() * pull;
+
+        while spent + pull <= budget {
+            let total: usize = pulls.iter().sum();
+            let scale = means.iter().cloned().fold(0.0, f64::max).max(f64::MIN_POSITIVE);
+            let score = |arm: usize| {
+                means[arm] + scale * (2.0 * (total as f64).ln() / pulls[arm] as f64).sqrt()
+            };
+            let arm = (0..arms.len()).max_by(|&a, &b| score(a).total_cmp(&score(b))).unwrap_or(0);
+            means[arm] = cell.measure(arms[arm], pull)?.speedup_mean;
+            pulls[arm] += 1;
+            spent += pull;
+        }
+
+        for (arm, &i) in arms.iter().enumerate() {
+            cell.log(format!(
+                "    🎰 {}: {} pulls ({} runs), {:.2}×",
+                candidates[i].key,
+                pulls[arm],
+                pulls[arm] * pull,
+                means[arm]
+            ));
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Bayesian Stopping
+// ============================================================================
+
+/// Sequential Bayesian stopping
+///
+/// Candidates are measured in order, [`MIN_ROUND_REPETITIONS`] runs at a
+/// time, against an incumbent speedup (initially `speedup_threshold`). A
+/// candidate stops as soon as the posterior probability that it beats the
+/// incumbent is below `1 - DECISION_PROBABILITY` (pruned) or above
+/// [`DECISION_PROBABILITY`] (it becomes the incumbent), or after
+/// `repetitions` runs (undecided).
+pub struct BayesianStopping {
+    pub speedup_threshold: f64,
+}
+
+impl PruningStrategy for BayesianStopping {
+    fn name(&self) -> &'static str {
+        "bayesian"
+    }
+
+    fn explore(
+        &self,
+        candidates: &[Candidate],
+        repetitions: usize,
+        cell: &mut dyn Cell,
+    ) -> Result<()> {
+        let mut incumbent = self.speedup_threshold;
+        for i in selected(candidates) {
+            let mut runs = 0;
+            loop {
+                let estimate = cell.measure(i, MIN_ROUND_REPETITIONS)?;
+                runs += MIN_ROUND_REPETITIONS;
+                let probability = estimate.probability_above(incumbent);
+                let key = &candidates[i].key;
+                if probability < 1.0 - DECISION_PROBABILITY {
+                    cell.log(format!(
+                        "    ❌ {} stopped after {} runs ({:.2}×, P(> {:.2}×) = {:.3})",
+                        key, runs, estimate.speedup_median, incumbent, probability
+                    ));
+                    break;
+                }
+                if probability > DECISION_PROBABILITY {
+                    cell.log(format!(
+                        "    ✅ {} beats {:.2}× after {} runs ({:.2}×, P = {:.3})",
+                        key, incumbent, runs, estimate.speedup_median, probability
+                    ));
+                    incumbent = estimate.speedup_mean;
+                    break;
+                }
+                if runs >= repetitions {
+                    cell.log(format!(
+                        "    ⚖️  {} undecided after {} runs ({:.2}×, P(> {:.2}×) = {:.3})",
+                        key, runs, estimate.speedup_median, incumbent, probability
+                    ));
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Standard normal CDF (Abramowitz & Stegun 7.1.26, error < 1.5e-7)
+fn normal_cdf(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    if z >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+// ============================================================================
+// Full Factorial
+// ============================================================================
+
+/// No pruning: every selected candidate gets a full measurement
+///
+/// Runs the grid the other strategies are judged against (`--full-factorial`).
+pub struct Exhaustive;
+
+impl PruningStrategy for Exhaustive {
+    fn name(&self) -> &'static str {
+        "exhaustive"
+    }
+
+    fn explore(
+        &self,
+        candidates: &[Candidate],
+        repetitions: usize,
+        cell: &mut dyn Cell,
+    ) -> Result<()> {
+        for i in selected(candidates) {
+            cell.measure(i, repetitions)?;
+        }
+        Ok(())
+    }
+}
+
+/// Cell answering from full-factorial estimates instead of running anything
+struct ReplayCell<'a> {
+    estimates: &'a [Option<Estimate>],
+    pruned: &'a mut [bool],
+    measured: Vec<bool>,
+}
+
+impl Cell for ReplayCell<'_> {
+    fn measure(&mut self, candidate: usize, _repetitions: usize) -> Result<Estimate> {
+        if self.pruned[candidate] {
+            return Ok(Estimate { speedup_median: 0.0, speedup_mean: 0.0, speedup_std_dev: 0.0, n: 0 });
+        }
+        self.measured[candidate] = true;
+        self.estimates[candidate]
+            .ok_or_else(|| anyhow::anyhow!("No full-factorial estimate for candidate {}", candidate))
+    }
+
+    fn prune(&mut self, candidate: usize) {
+        self.pruned[candidate] = true;
+    }
+
+    fn skip(&mut self, _candidate: usize) {}
+
+    fn log(&mut self, _line: String) {}
+}
+
+/// Which candidates `strategy` would have measured, given every candidate's
+/// full-factorial estimate
+///
+/// `pruned` carries pruning decisions across the scales of one operation
+/// (as in a real run): replay an operation's scales in run order with the
+/// same slice, all `false` at first.
+pub fn replay(
+    strategy: &dyn PruningStrategy,
+    candidates: &[Candidate],
+    repetitions: usize,
+    estimates: &[Option<Estimate>],
+    pruned: &mut [bool],
+) -> Result<Vec<bool>> {
+    let mut cell = ReplayCell { estimates, pruned, measured: vec![false; candidates.len()] };
+    strategy.explore(candidates, repetitions, &mut cell)?;
+    Ok(cell.measured)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cell with fixed true speedups and unit noise
+    struct MockCell {
+        speedups: Vec<f64>,
+        runs: Vec<usize>,
+        pruned: Vec<usize>,
+        skipped: Vec<usize>,
+    }
+
+    impl MockCell {
+        fn new(speedups: &[f64]) -> Self {
+            Self {
+                speedups: speedups.to_vec(),
+                runs: vec![0; speedups.len()],
+                pruned: Vec::new(),
+                skipped: Vec::new(),
+            }
+        }
+
+        fn total_runs(&self) -> usize {
+            self.runs.iter().sum()
+        }
+    }
+
+    impl Cell for MockCell {
+        fn measure(&mut self, candidate: usize, repetitions: usize) -> Result<Estimate> {
+            self.runs[candidate] += repetitions;
+            let speedup = self.speedups[candidate];
+            Ok(Estimate {
+                speedup_median: speedup,
+                speedup_mean: speedup,
+                speedup_std_dev: 0.5,
+                n: self.runs[candidate],
+            })
+        }
+
+        fn prune(&mut self, candidate: usize) {
+            self.pruned.push(candidate);
+        }
+
+        fn skip(&mut self, candidate: usize) {
+            self.skipped.push(candidate);
+        }
+
+        fn log(&mut self, _line: String) {}
+    }
+
+    fn candidate(key: &str, threads: usize, role: Role) -> Candidate {
+        Candidate { key: key.to_string(), threads, role, selected: true }
+    }
+
+    /// neon, neon_2t, neon_chunked_2t, neon_4t, neon_chunked_4t
+    fn neon_parallel() -> Vec<Candidate> {
+        vec![
+            candidate("neon", 1, Role::Alternative),
+            candidate("neon_2t", 2, Role::Composition),
+            candidate("neon_chunked_2t", 2, Role::Variant),
+            candidate("neon_4t", 4, Role::Composition),
+            candidate("neon_chunked_4t", 4, Role::Variant),
+        ]
+    }
+
+    fn threshold() -> Box<dyn PruningStrategy> {
+        PruningPolicy::Threshold.strategy(1.5, 1.3)
+    }
+
+    #[test]
+    fn test_threshold_prunes_alternative_and_diminishing_returns() {
+        // NEON below 1.5×: nothing else runs
+        let mut cell = MockCell::new(&[1.2, 2.0, 2.0, 3.0, 3.0]);
+        threshold().explore(&neon_parallel(), 30, &mut cell).unwrap();
+        assert_eq!(cell.runs, vec![30, 0, 0, 0, 0]);
+        assert_eq!(cell.pruned, vec![0]);
+        assert_eq!(cell.skipped, vec![1, 2, 3, 4]);
+
+        // 2 threads add only 1.1×: 4 threads are skipped
+        let mut cell = MockCell::new(&[10.0, 11.0, 12.0, 20.0, 20.0]);
+        threshold().explore(&neon_parallel(), 30, &mut cell).unwrap();
+        assert_eq!(cell.runs, vec![30, 30, 30, 0, 0]);
+        assert_eq!(cell.pruned, vec![1]);
+        assert_eq!(cell.skipped, vec![3, 4]);
+
+        // Unselected 2-thread composition: 4 threads run unchecked
+        let mut candidates = neon_parallel();
+        candidates[1].selected = false;
+        candidates[2].selected = false;
+        let mut cell = MockCell::new(&[10.0, 11.0, 12.0, 11.0, 11.0]);
+        threshold().explore(&candidates, 30, &mut cell).unwrap();
+        assert_eq!(cell.runs, vec![30, 0, 0, 30, 30]);
+        assert!(cell.pruned.is_empty());
+    }
+
+    #[test]
+    fn test_adaptive_strategies_find_best_with_fewer_runs() {
+        let speedups = [10.0, 14.0, 12.0, 20.0, 16.0];
+        let exhaustive = speedups.len() * 30;
+
+        let mut cell = MockCell::new(&speedups);
+        SuccessiveHalving.explore(&neon_parallel(), 30, &mut cell).unwrap();
+        assert_eq!(cell.runs[3], 30);
+        assert!(cell.total_runs() < exhaustive);
+        assert!(cell.runs.iter().enumerate().all(|(i, &runs)| i == 3 || runs < 30));
+
+        let mut cell = MockCell::new(&speedups);
+        UcbBandit { budget_fraction: 0.5 }.explore(&neon_parallel(), 30, &mut cell).unwrap();
+        assert_eq!(cell.total_runs(), exhaustive / 2);
+        let most = (0..speedups.len()).max_by_key(|&i| cell.runs[i]).unwrap();
+        assert_eq!(most, 3);
+
+        let mut cell = MockCell::new(&speedups);
+        BayesianStopping { speedup_threshold: 1.5 }
+            .explore(&neon_parallel(), 30, &mut cell)
+            .unwrap();
+        assert!(cell.total_runs() < exhaustive);
+    }
+
+    #[test]
+    fn test_replay_carries_pruning_across_scales() {
+        let estimate = |speedup: f64| {
+            Some(Estimate { speedup_median: speedup, speedup_mean: speedup, speedup_std_dev: 0.1, n: 30 })
+        };
+        let candidates = neon_parallel();
+        let mut pruned = vec![false; candidates.len()];
+
+        // Small scale: 2 threads add too little, 4 threads are never tried
+        let small = [estimate(4.0), estimate(4.4), estimate(4.2), estimate(9.0), estimate(8.0)];
+        let measured = replay(&*threshold(), &candidates, 30, &small, &mut pruned).unwrap();
+        assert_eq!(measured, vec![true, true, true, false, false]);
+
+        // Larger scale: neon_2t stays pruned (0×), so 4 threads are never
+        // tried for this operation even though they would now pay off
+        let large = [estimate(4.0), estimate(8.0), estimate(8.0), estimate(12.0), estimate(11.0)];
+        let measured = replay(&*threshold(), &candidates, 30, &large, &mut pruned).unwrap();
+        assert_eq!(measured, vec![true, false, true, false, false]);
+
+        let measured = replay(&Exhaustive, &candidates, 30, &large, &mut [false; 5]).unwrap();
+        assert!(measured.iter().all(|&m| m));
+    }
+
+    #[test]
+    fn test_posterior_probability() {
+        let estimate =
+            Estimate { speedup_median: 2.0, speedup_mean: 2.0, speedup_std_dev: 1.0, n: 25 };
+        assert!((estimate.probability_above(2.0) - 0.5).abs() < 1e-6);
+        // z = (2.0 - 1.6) / 0.2 = 2
+        assert!((estimate.probability_above(1.6) - 0.97725).abs() < 1e-4);
+        assert!(estimate.probability_above(3.0) < 1e-6);
+        assert_eq!(PruningPolicy::from_name("ucb").unwrap(), PruningPolicy::Ucb);
+        assert!(PruningPolicy::from_name("random").is_err());
+    }
+}
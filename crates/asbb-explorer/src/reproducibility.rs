@@ -0,0 +1,367 @@
+//! Reproducibility manifests
+//!
+//! Every harness writes a [`RunManifest`] next to its results: the exact
+//! command line and working directory, the RNG seeds it used, a SHA-256 of
+//! every input dataset, the config TOML (verbatim), the platform and
+//! detected hardware, and whether Rosetta translated the run. `asbb rerun
+//! <manifest>` checks the recorded inputs against the current tree
+//! ([`RunManifest::check`]) and re-executes the command, so the identical
+//! experiment set runs again.
+//!
+//! The manifest path is derived from the results path
+//! ([`manifest_path_for`]): `dag.csv` → `dag.manifest.json`, and a results
+//! directory gets `manifest.json` inside it.
+
+use anyhow::{Context, Result};
+use asbb_core::HardwareProfile;
+use asbb_datagen::manifest::sha256_file;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// An input dataset and its content hash at run time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatasetRecord {
+    /// Path as the harness opened it (relative to the working directory)
+    pub path: PathBuf,
+    pub sha256: String,
+    pub bytes: u64,
+}
+
+/// The config file a run was driven by
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigRecord {
+    pub path: PathBuf,
+    /// File contents, verbatim
+    pub toml: String,
+}
+
+/// Everything needed to repeat a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunManifest {
+    /// asbb version that produced the results
+    pub asbb_version: String,
+    pub created_at: String,
+
+    /// Exact command line (`command[0]` as invoked)
+    pub command: Vec<String>,
+
+    /// Absolute path of the executable, if known
+    pub executable: Option<PathBuf>,
+    pub working_dir: PathBuf,
+
+    /// Results file (or directory) the manifest describes
+    pub results: PathBuf,
+
+    /// Named RNG seeds (e.g. one per synthetic scale)
+    pub seeds: BTreeMap<String, u64>,
+    pub datasets: Vec<DatasetRecord>,
+    pub config: Option<ConfigRecord>,
+
+    /// `{os}-{arch}` of the host
+    pub platform: String,
+
+    /// Detected hardware (`None` off Apple Silicon)
+    pub hardware: Option<HardwareProfile>,
+
+    /// Whether the run executed under Rosetta translation
+    #[serde(default)]
+    pub translated: bool,
+
+    /// Checked-out commit of the working directory, if it is a git checkout
+    #[serde(default)]
+    pub git_commit: Option<String>,
+
+    /// Host name of the machine
+    #[serde(default)]
+    pub host: Option<String>,
+}
+
+/// A difference between a manifest and the current environment
+#[derive(Debug, Clone, PartialEq)]
+pub enum Drift {
+    DatasetMissing(PathBuf),
+    DatasetChanged {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
+    ConfigMissing(PathBuf),
+    ConfigChanged(PathBuf),
+    PlatformChanged {
+        recorded: String,
+        current: String,
+    },
+}
+
+impl Drift {
+    /// Whether rerunning would measure different inputs (not just a
+    /// different machine)
+    pub fn changes_inputs(&self) -> bool {
+        !matches!(self, Drift::PlatformChanged { .. })
+    }
+}
+
+impl std::fmt::Display for Drift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Drift::DatasetMissing(path) => write!(f, "dataset {} is missing", path.display()),
+            Drift::DatasetChanged { path, expected, actual } => write!(
+                f,
+                "dataset {} changed (sha256 {} → {})",
+                path.display(),
+                &expected[..expected.len().min(12)],
+                &actual[..actual.len().min(12)]
+            ),
+            Drift::ConfigMissing(path) => write!(f, "config {} is missing", path.display()),
+            Drift::ConfigChanged(path) => write!(f, "config {} changed", path.display()),
+            Drift::PlatformChanged { recorded, current } => {
+                write!(f, "platform changed ({} → {})", recorded, current)
+            }
+        }
+    }
+}
+
+/// Manifest path for a results file or directory
+pub fn manifest_path_for(results: &Path) -> PathBuf {
+    if results.is_dir() {
+        return results.join("manifest.json");
+    }
+    let stem = results
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "results".to_string());
+    results.with_file_name(format!("{}.manifest.json", stem))
+}
+
+/// Host OS and architecture (`macos-aarch64`, `linux-x86_64`, ...)
+fn platform() -> String {
+    format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+/// Trimmed stdout of a command, if it ran successfully and printed anything
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    std::process::Command::new(program)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|out| !out.is_empty())
+}
+
+impl RunManifest {
+    /// Manifest of the current process writing `results`
+    pub fn capture(results: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            asbb_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            command: std::env::args().collect(),
+            executable: std::env::current_exe().ok(),
+            working_dir: std::env::current_dir().context("Failed to read working directory")?,
+            results: results.as_ref().to_path_buf(),
+            seeds: BTreeMap::new(),
+            datasets: Vec::new(),
+            config: None,
+            platform: platform(),
+            hardware: HardwareProfile::detect().ok(),
+            translated: asbb_core::is_translated(),
+            git_commit: command_output("git", &["rev-parse", "HEAD"]),
+            host: command_output("hostname", &[]),
+        })
+    }
+
+    pub fn with_seed(mut self, name: impl Into<String>, seed: u64) -> Self {
+        self.seeds.insert(name.into(), seed);
+        self
+    }
+
+    /// Record a dataset's hash (once per path)
+    pub fn with_dataset(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if self.datasets.iter().any(|dataset| dataset.path == path) {
+            return Ok(self);
+        }
+        let bytes = fs::metadata(path)
+            .with_context(|| format!("Failed to read dataset: {}", path.display()))?
+            .len();
+        self.datasets.push(DatasetRecord {
+            path: path.to_path_buf(),
+            sha256: sha256_file(path)?,
+            bytes,
+        });
+        Ok(self)
+    }
+
+    /// Record the config file the run read
+    pub fn with_config_file(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let toml = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config: {}", path.display()))?;
+        self.config = Some(ConfigRecord {
+            path: path.to_path_buf(),
+            toml,
+        });
+        Ok(self)
+    }
+
+    /// Write the manifest next to the results; returns its path
+    pub fn save_alongside_results(&self) -> Result<PathBuf> {
+        let path = manifest_path_for(&self.results);
+        self.save(&path)?;
+        Ok(path)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write manifest: {}", path.display()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read manifest: {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse manifest: {}", path.display()))
+    }
+
+    /// Recorded path resolved against the recorded working directory
+    fn resolve(&self, path: &Path) -> PathBuf {
+        self.working_dir.join(path)
+    }
+
+    /// Differences between the recorded inputs and the current files
+    pub fn check(&self) -> Result<Vec<Drift>> {
+        let mut drift = Vec::new();
+        for dataset in &self.datasets {
+            let path = self.resolve(&dataset.path);
+            if !path.exists() {
+                drift.push(Drift::DatasetMissing(dataset.path.clone()));
+                continue;
+            }
+            let actual = sha256_file(&path)?;
+            if actual != dataset.sha256 {
+                drift.push(Drift::DatasetChanged {
+                    path: dataset.path.clone(),
+                    expected: dataset.sha256.clone(),
+                    actual,
+                });
+            }
+        }
+
+        if let Some(config) = &self.config {
+            match fs::read_to_string(self.resolve(&config.path)) {
+                Ok(toml) if toml == config.toml => {}
+                Ok(_) => drift.push(Drift::ConfigChanged(config.path.clone())),
+                Err(_) => drift.push(Drift::ConfigMissing(config.path.clone())),
+            }
+        }
+
+        let current = platform();
+        if current != self.platform {
+            drift.push(Drift::PlatformChanged {
+                recorded: self.platform.clone(),
+                current,
+            });
+        }
+        Ok(drift)
+    }
+
+    /// Restore a changed or missing config file from the recorded TOML
+    pub fn restore_config(&self) -> Result<Option<PathBuf>> {
+        let Some(config) = &self.config else {
+            return Ok(None);
+        };
+        let path = self.resolve(&config.path);
+        fs::write(&path, &config.toml)
+            .with_context(|| format!("Failed to restore config: {}", path.display()))?;
+        Ok(Some(path))
+    }
+
+    /// The recorded command, ready to run in the recorded working directory
+    ///
+    /// Uses the recorded executable if it still exists, else `command[0]`
+    /// (resolved through `PATH`).
+    pub fn rerun_command(&self) -> Result<std::process::Command> {
+        let (program, args) = self.command.split_first().context("Manifest has no command")?;
+        let program = match &self.executable {
+            Some(executable) if executable.exists() => executable.clone(),
+            _ => PathBuf::from(program),
+        };
+        let mut command = std::process::Command::new(program);
+        command.args(args).current_dir(&self.working_dir);
+        Ok(command)
+    }
+
+    /// The recorded command line, shell-quoted
+    pub fn command_line(&self) -> String {
+        crate::interrupt::shell_join(&self.command)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_path_for() {
+        assert_eq!(
+            manifest_path_for(Path::new("results/dag_neon.csv")),
+            PathBuf::from("results/dag_neon.manifest.json")
+        );
+        assert_eq!(
+            manifest_path_for(&std::env::temp_dir()),
+            std::env::temp_dir().join("manifest.json")
+        );
+    }
+
+    #[test]
+    fn test_round_trip_and_drift() {
+        let dir = std::env::temp_dir().join(format!("asbb-manifest-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let dataset = dir.join("tiny.fq");
+        let config = dir.join("config.toml");
+        fs::write(&dataset, "@r1\nACGT\n+\nIIII\n").unwrap();
+        fs::write(&config, "[datasets]\nseed = 42\n").unwrap();
+
+        let manifest = RunManifest::capture(dir.join("results.csv"))
+            .unwrap()
+            .with_seed("datasets", 42)
+            .with_dataset(&dataset)
+            .unwrap()
+            .with_dataset(&dataset)
+            .unwrap()
+            .with_config_file(&config)
+            .unwrap();
+        assert_eq!(manifest.datasets.len(), 1);
+        assert_eq!(manifest.datasets[0].bytes, 16);
+
+        let path = manifest.save_alongside_results().unwrap();
+        assert_eq!(path, dir.join("results.manifest.json"));
+        let loaded = RunManifest::load(&path).unwrap();
+        assert_eq!(loaded, manifest);
+        assert!(loaded.check().unwrap().is_empty());
+
+        fs::write(&dataset, "@r1\nACGA\n+\nIIII\n").unwrap();
+        fs::write(&config, "[datasets]\nseed = 7\n").unwrap();
+        let drift = loaded.check().unwrap();
+        assert_eq!(drift.len(), 2);
+        assert!(matches!(drift[0], Drift::DatasetChanged { .. }));
+        assert_eq!(drift[1], Drift::ConfigChanged(config.clone()));
+        assert!(drift.iter().all(Drift::changes_inputs));
+
+        loaded.restore_config().unwrap();
+        assert_eq!(fs::read_to_string(&config).unwrap(), "[datasets]\nseed = 42\n");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
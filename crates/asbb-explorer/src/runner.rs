@@ -39,12 +39,13 @@ impl BenchmarkRunner {
         for benchmark in &self.benchmarks {
             println!("Running benchmark: {}", benchmark.name);
 
-            let result = crate::benchmark_operation(
+            let result = crate::benchmark_operation_with_percentiles(
                 operation,
                 data,
                 &benchmark.hardware_config,
                 benchmark.warmup_runs,
                 benchmark.measured_runs,
+                &benchmark.percentiles,
             )?;
 
             results.push(result);
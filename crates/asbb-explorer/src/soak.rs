@@ -0,0 +1,337 @@
+//! Sustained-throughput (thermal soak) mode
+//!
+//! Short benchmarks measure burst performance: a few seconds of work finish
+//! before the chip heats up. A fanless MacBook Air throttles under sustained
+//! load where a MacBook Pro does not, so the two look identical in a
+//! 30-repetition run and very different in a ten-minute pipeline.
+//!
+//! A soak runs an operation back to back for a fixed duration and reports
+//! throughput per window (10 s by default) together with the OS thermal
+//! state seen during the window:
+//! - **Burst throughput**: the first window
+//! - **Sustained throughput**: median of the last third of the windows
+//! - **Throttle onset**: start of the first window below
+//!   [`THROTTLE_THRESHOLD`] × burst
+//!
+//! Thermal state comes from `NSProcessInfo.thermalState` on macOS (no root
+//! needed); other platforms report none.
+
+use anyhow::Result;
+use asbb_core::{HardwareConfig, PrimitiveOperation, SequenceRecord};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use crate::execute_configured;
+
+/// Default window length
+pub const DEFAULT_WINDOW: Duration = Duration::from_secs(10);
+
+/// Fraction of burst throughput below which a window counts as throttled
+pub const THROTTLE_THRESHOLD: f64 = 0.9;
+
+/// OS thermal pressure (`NSProcessInfoThermalState`), in increasing severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ThermalState {
+    Nominal,
+    Fair,
+    Serious,
+    Critical,
+}
+
+impl ThermalState {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ThermalState::Nominal => "nominal",
+            ThermalState::Fair => "fair",
+            ThermalState::Serious => "serious",
+            ThermalState::Critical => "critical",
+        }
+    }
+
+    fn from_raw(value: isize) -> Option<Self> {
+        match value {
+            0 => Some(ThermalState::Nominal),
+            1 => Some(ThermalState::Fair),
+            2 => Some(ThermalState::Serious),
+            3 => Some(ThermalState::Critical),
+            _ => None,
+        }
+    }
+}
+
+/// Current thermal state (`[[NSProcessInfo processInfo] thermalState]`)
+#[cfg(target_os = "macos")]
+pub fn thermal_state() -> Option<ThermalState> {
+    use std::ffi::{c_char, c_void};
+
+    type Id = *mut c_void;
+
+    #[link(name = "Foundation", kind = "framework")]
+    extern "C" {}
+
+    #[link(name = "objc")]
+    extern "C" {
+        fn objc_getClass(name: *const c_char) -> Id;
+        fn sel_registerName(name: *const c_char) -> Id;
+        fn objc_msgSend();
+    }
+
+    // objc_msgSend must be called through a pointer of the method's signature
+    unsafe {
+        let class = objc_getClass(c"NSProcessInfo".as_ptr());
+        if class.is_null() {
+            return None;
+        }
+        let send_id: unsafe extern "C" fn(Id, Id) -> Id =
+            std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+        let send_int: unsafe extern "C" fn(Id, Id) -> isize =
+            std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+
+        let info = send_id(class, sel_registerName(c"processInfo".as_ptr()));
+        if info.is_null() {
+            return None;
+        }
+        ThermalState::from_raw(send_int(info, sel_registerName(c"thermalState".as_ptr())))
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn thermal_state() -> Option<ThermalState> {
+    None
+}
+
+/// Soak parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoakConfig {
+    /// Total time to run the operation
+    pub duration: Duration,
+
+    /// Length of each reporting window
+    pub window: Duration,
+}
+
+impl SoakConfig {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            window: DEFAULT_WINDOW,
+        }
+    }
+}
+
+/// Throughput over one window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SoakWindow {
+    /// Window number (0-based)
+    pub index: usize,
+
+    /// Window start, seconds since the soak began
+    pub start_secs: f64,
+
+    /// Window end, seconds since the soak began
+    pub end_secs: f64,
+
+    /// Complete runs in the window
+    pub runs: usize,
+
+    /// Sequences processed in the window
+    pub sequences: usize,
+
+    /// Sequences per second of compute time
+    pub throughput_seqs_per_sec: f64,
+
+    /// Most severe thermal state seen during the window (if available)
+    pub thermal_state: Option<ThermalState>,
+}
+
+/// Per-window throughput of a soak, with burst vs sustained summary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SoakResult {
+    pub windows: Vec<SoakWindow>,
+}
+
+impl SoakResult {
+    /// Throughput of the first window
+    pub fn burst_throughput(&self) -> f64 {
+        self.windows.first().map(|w| w.throughput_seqs_per_sec).unwrap_or(0.0)
+    }
+
+    /// Median throughput over the last third of the windows
+    pub fn sustained_throughput(&self) -> f64 {
+        let tail = (self.windows.len() / 3).max(1);
+        let mut rates: Vec<f64> = self.windows[self.windows.len().saturating_sub(tail)..]
+            .iter()
+            .map(|w| w.throughput_seqs_per_sec)
+            .collect();
+        rates.sort_by(f64::total_cmp);
+        match rates.len() {
+            0 => 0.0,
+            n if n % 2 == 0 => (rates[n / 2 - 1] + rates[n / 2]) / 2.0,
+            n => rates[n / 2],
+        }
+    }
+
+    /// Sustained throughput as a fraction of burst
+    pub fn sustained_ratio(&self) -> f64 {
+        let burst = self.burst_throughput();
+        if burst > 0.0 {
+            self.sustained_throughput() / burst
+        } else {
+            0.0
+        }
+    }
+
+    /// Start (seconds) of the first window below `threshold` × burst
+    pub fn throttle_onset_secs(&self, threshold: f64) -> Option<f64> {
+        let burst = self.burst_throughput();
+        self.windows
+            .iter()
+            .find(|w| w.throughput_seqs_per_sec < burst * threshold)
+            .map(|w| w.start_secs)
+    }
+
+    /// Most severe thermal state seen during the soak
+    pub fn peak_thermal_state(&self) -> Option<ThermalState> {
+        self.windows.iter().filter_map(|w| w.thermal_state).max()
+    }
+}
+
+/// Run `operation` back to back for `soak.duration`
+///
+/// `on_window` is called as each window completes (for live output). A
+/// trailing partial window is kept if it holds at least one run.
+pub fn run_soak(
+    operation: &dyn PrimitiveOperation,
+    data: &[SequenceRecord],
+    config: &HardwareConfig,
+    soak: &SoakConfig,
+    mut on_window: impl FnMut(&SoakWindow),
+) -> Result<SoakResult> {
+    if soak.window.is_zero() {
+        anyhow::bail!("Soak window must be longer than zero");
+    }
+
+    let started = Instant::now();
+    let mut windows = Vec::new();
+    let mut window_start = started;
+    let mut runs = 0;
+    let mut compute = Duration::ZERO;
+    let mut thermal: Option<ThermalState> = None;
+
+    let mut close_window = |window_start: Instant,
+                            runs: usize,
+                            compute: Duration,
+                            thermal: Option<ThermalState>,
+                            windows: &mut Vec<SoakWindow>| {
+        let sequences = runs * data.len();
+        let window = SoakWindow {
+            index: windows.len(),
+            start_secs: window_start.duration_since(started).as_secs_f64(),
+            end_secs: started.elapsed().as_secs_f64(),
+            runs,
+            sequences,
+            throughput_seqs_per_sec: sequences as f64 / compute.as_secs_f64().max(f64::MIN_POSITIVE),
+            thermal_state: thermal,
+        };
+        on_window(&window);
+        windows.push(window);
+    };
+
+    while started.elapsed() < soak.duration {
+        let run_start = Instant::now();
+        execute_configured(operation, data, config)?;
+        compute += run_start.elapsed();
+        runs += 1;
+        thermal = thermal.max(thermal_state());
+
+        if window_start.elapsed() >= soak.window {
+            close_window(window_start, runs, compute, thermal, &mut windows);
+            window_start = Instant::now();
+            runs = 0;
+            compute = Duration::ZERO;
+            thermal = None;
+        }
+    }
+    if runs > 0 {
+        close_window(window_start, runs, compute, thermal, &mut windows);
+    }
+
+    Ok(SoakResult { windows })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asbb_ops::base_counting::BaseCounting;
+
+    fn window(index: usize, throughput: f64, thermal_state: Option<ThermalState>) -> SoakWindow {
+        SoakWindow {
+            index,
+            start_secs: index as f64 * 10.0,
+            end_secs: (index + 1) as f64 * 10.0,
+            runs: 1,
+            sequences: throughput as usize * 10,
+            throughput_seqs_per_sec: throughput,
+            thermal_state,
+        }
+    }
+
+    #[test]
+    fn test_burst_vs_sustained() {
+        let result = SoakResult {
+            windows: vec![
+                window(0, 1000.0, Some(ThermalState::Nominal)),
+                window(1, 980.0, Some(ThermalState::Nominal)),
+                window(2, 850.0, Some(ThermalState::Fair)),
+                window(3, 700.0, Some(ThermalState::Serious)),
+                window(4, 720.0, Some(ThermalState::Fair)),
+                window(5, 710.0, None),
+            ],
+        };
+
+        assert_eq!(result.burst_throughput(), 1000.0);
+        // Last third: 720 and 710
+        assert_eq!(result.sustained_throughput(), 715.0);
+        assert!((result.sustained_ratio() - 0.715).abs() < 1e-12);
+        assert_eq!(result.throttle_onset_secs(THROTTLE_THRESHOLD), Some(20.0));
+        assert_eq!(result.peak_thermal_state(), Some(ThermalState::Serious));
+    }
+
+    #[test]
+    fn test_no_throttling() {
+        let result = SoakResult {
+            windows: vec![window(0, 1000.0, None), window(1, 995.0, None)],
+        };
+        assert_eq!(result.throttle_onset_secs(THROTTLE_THRESHOLD), None);
+        assert_eq!(result.peak_thermal_state(), None);
+    }
+
+    #[test]
+    fn test_run_soak_windows() {
+        let data: Vec<SequenceRecord> = (0..100)
+            .map(|i| SequenceRecord::fasta(format!("seq_{}", i), b"ACGT".repeat(40)))
+            .collect();
+        let soak = SoakConfig {
+            duration: Duration::from_millis(200),
+            window: Duration::from_millis(50),
+        };
+
+        let mut seen = 0;
+        let result = run_soak(&BaseCounting::new(), &data, &HardwareConfig::naive(), &soak, |_| {
+            seen += 1
+        })
+        .unwrap();
+
+        assert_eq!(seen, result.windows.len());
+        assert!(result.windows.len() >= 3);
+        for (i, w) in result.windows.iter().enumerate() {
+            assert_eq!(w.index, i);
+            assert_eq!(w.sequences, w.runs * data.len());
+            assert!(w.throughput_seqs_per_sec > 0.0);
+        }
+    }
+}
@@ -0,0 +1,369 @@
+//! Algorithmic strategy comparisons
+//!
+//! Most experiments vary the hardware config under a fixed algorithm. Some
+//! operations can be computed in more than one way — quality statistics with
+//! a gather-and-sort pass or streamed into histograms, a k-mer spectrum in
+//! one table or in memory-bounded partition passes — and the better strategy
+//! may depend on the config: a strategy that loses on one core can win on
+//! eight. A strategy comparison benchmarks every (strategy, config) cell and
+//! separates the effects:
+//!
+//! - **hardware speedup**: config vs the baseline config, same strategy
+//! - **strategy ratio**: strategy vs the baseline strategy, same config
+//! - **interaction**: strategy ratio under the config over the strategy ratio
+//!   under the baseline config (1.0 = the strategy choice does not depend on
+//!   the hardware)
+//!
+//! # Presets
+//!
+//! - **quality_statistics**: gather-and-sort vs single-pass histograms
+//! - **kmer_spectrum**: single count table vs memory-bounded partition passes
+//!
+//! Ratios are of geometric-mean throughput across measured runs, so the
+//! interaction is a difference of log means and gets a 95% confidence
+//! interval from the four cells' run-to-run variances.
+
+use anyhow::Result;
+use asbb_core::compare::outputs_match;
+use asbb_core::stats::t_critical_value;
+use asbb_core::{HardwareConfig, OperationOutput, PrimitiveOperation, SequenceRecord};
+use asbb_ops::{
+    kmer_spectrum::{KmerSpectrum, SpectrumMode},
+    quality_statistics::{QualityStatistics, StreamingQualityStatistics},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::benchmark_operation;
+
+/// Preset names accepted by [`StrategyComparison::preset`]
+pub const PRESETS: &[&str] = &["quality_statistics", "kmer_spectrum"];
+
+/// Per-pass table budget of the bounded k-mer strategy
+const KMER_PASS_BUDGET_BYTES: usize = 4 * 1024 * 1024;
+
+/// One way of computing the compared operation
+pub struct Strategy {
+    pub label: String,
+    pub operation: Box<dyn PrimitiveOperation>,
+}
+
+/// Alternative strategies for the same logical operation; the first is the
+/// baseline the others are compared to
+pub struct StrategyComparison {
+    pub name: String,
+    pub strategies: Vec<Strategy>,
+    /// Top-level output fields that describe the strategy rather than the
+    /// result (e.g. the k-mer spectrum's pass count), skipped when checking
+    /// that strategies agree
+    pub strategy_fields: Vec<String>,
+}
+
+impl StrategyComparison {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            strategies: Vec::new(),
+            strategy_fields: Vec::new(),
+        }
+    }
+
+    /// Append a strategy
+    pub fn with_strategy(mut self, label: &str, operation: Box<dyn PrimitiveOperation>) -> Self {
+        self.strategies.push(Strategy {
+            label: label.to_string(),
+            operation,
+        });
+        self
+    }
+
+    /// Skip `field` when checking that strategies agree
+    pub fn with_strategy_field(mut self, field: &str) -> Self {
+        self.strategy_fields.push(field.to_string());
+        self
+    }
+
+    /// Per-position quality statistics: sort each column vs stream histograms
+    pub fn quality_statistics() -> Self {
+        Self::new("quality_statistics")
+            .with_strategy("gather", Box::new(QualityStatistics::new()))
+            .with_strategy("streaming", Box::new(StreamingQualityStatistics::new()))
+    }
+
+    /// Exact 21-mer spectrum: one table vs hash-partitioned passes
+    pub fn kmer_spectrum() -> Self {
+        let bounded = KmerSpectrum::new(21, true).with_mode(SpectrumMode::Bounded {
+            memory_budget_bytes: KMER_PASS_BUDGET_BYTES,
+        });
+        Self::new("kmer_spectrum")
+            .with_strategy("single_pass", Box::new(KmerSpectrum::new(21, true)))
+            .with_strategy("multi_pass", Box::new(bounded))
+            .with_strategy_field("mode")
+            .with_strategy_field("partitions")
+            .with_strategy_field("peak_table_entries")
+    }
+
+    /// Look up a preset by name
+    pub fn preset(name: &str) -> Result<Self> {
+        match name {
+            "quality_statistics" => Ok(Self::quality_statistics()),
+            "kmer_spectrum" => Ok(Self::kmer_spectrum()),
+            _ => anyhow::bail!(
+                "Unknown strategy preset: {} (available: {})",
+                name,
+                PRESETS.join(", ")
+            ),
+        }
+    }
+}
+
+// ============================================================================
+// Results
+// ============================================================================
+
+/// Throughput of one strategy under one config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StrategyCell {
+    pub strategy: String,
+    pub operation: String,
+    pub config_name: String,
+    pub num_threads: usize,
+    /// Median sequences per second across measured runs
+    pub median_throughput: f64,
+    /// Mean and sample variance of ln(sequences per second) across runs
+    pub log_mean: f64,
+    pub log_variance: f64,
+    pub runs: usize,
+    pub output_matches_reference: bool,
+}
+
+/// Effects of one strategy under one config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StrategyEffect {
+    pub strategy: String,
+    pub config_name: String,
+    /// Throughput over the same strategy under the baseline config
+    pub hardware_speedup: f64,
+    /// Throughput over the baseline strategy under the same config
+    pub strategy_ratio: f64,
+    /// `strategy_ratio` over the strategy ratio under the baseline config
+    pub interaction: f64,
+    /// 95% confidence interval of `interaction` (`None` for the baseline
+    /// strategy or config, or with fewer than 2 measured runs)
+    pub interaction_ci: Option<(f64, f64)>,
+}
+
+impl StrategyEffect {
+    /// Whether the interaction CI excludes 1.0
+    pub fn significant(&self) -> bool {
+        self.interaction_ci
+            .is_some_and(|(lower, upper)| lower > 1.0 || upper < 1.0)
+    }
+}
+
+/// Strategy × config grid
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StrategyComparisonResult {
+    pub comparison: String,
+    pub num_sequences: usize,
+    /// Whether every strategy's naive output matches the baseline strategy's
+    pub strategies_agree: bool,
+    /// Cells in strategy-major order
+    pub cells: Vec<StrategyCell>,
+    /// One per cell, same order
+    pub effects: Vec<StrategyEffect>,
+}
+
+impl StrategyComparisonResult {
+    /// Fastest cell
+    pub fn best(&self) -> Option<&StrategyCell> {
+        self.cells
+            .iter()
+            .max_by(|a, b| a.median_throughput.total_cmp(&b.median_throughput))
+    }
+}
+
+/// Benchmark every strategy under every config
+///
+/// The first config is the hardware baseline (normally `naive`) and the first
+/// strategy the strategy baseline. Each cell is a [`benchmark_operation`] run
+/// with `warmup_runs` unmeasured and `measured_runs` timed executions.
+pub fn benchmark_strategies(
+    comparison: &StrategyComparison,
+    data: &[SequenceRecord],
+    configs: &[(String, HardwareConfig)],
+    warmup_runs: usize,
+    measured_runs: usize,
+) -> Result<StrategyComparisonResult> {
+    if comparison.strategies.len() < 2 {
+        anyhow::bail!("Strategy comparison {} needs at least two strategies", comparison.name);
+    }
+    if configs.is_empty() {
+        anyhow::bail!("At least one hardware config is required");
+    }
+
+    let baseline = comparison.strategies[0].operation.as_ref();
+    let reference = without_fields(baseline.execute_naive(data)?, &comparison.strategy_fields)?;
+    let tolerance = baseline.output_tolerance();
+    let mut strategies_agree = true;
+    for strategy in &comparison.strategies[1..] {
+        let output = strategy.operation.execute_naive(data)?;
+        let output = without_fields(output, &comparison.strategy_fields)?;
+        strategies_agree &= outputs_match(&reference, &output, &tolerance);
+    }
+
+    let mut cells = Vec::with_capacity(comparison.strategies.len() * configs.len());
+    for strategy in &comparison.strategies {
+        for (config_name, config) in configs {
+            let result = benchmark_operation(
+                strategy.operation.as_ref(),
+                data,
+                config,
+                warmup_runs,
+                measured_runs,
+            )?;
+            let logs: Vec<f64> = result
+                .phase_timings
+                .iter()
+                .map(|t| t.compute_time.as_secs_f64().max(f64::MIN_POSITIVE))
+                .map(|secs| (data.len() as f64 / secs).ln())
+                .collect();
+            let log_mean = logs.iter().sum::<f64>() / logs.len() as f64;
+            let log_variance = if logs.len() > 1 {
+                logs.iter().map(|x| (x - log_mean).powi(2)).sum::<f64>() / (logs.len() - 1) as f64
+            } else {
+                0.0
+            };
+
+            cells.push(StrategyCell {
+                strategy: strategy.label.clone(),
+                operation: strategy.operation.name().to_string(),
+                config_name: config_name.clone(),
+                num_threads: config.num_threads,
+                median_throughput: result.throughput_seqs_per_sec,
+                log_mean,
+                log_variance,
+                runs: logs.len(),
+                output_matches_reference: result.output_matches_reference,
+            });
+        }
+    }
+
+    let effects = effects(&cells, configs.len());
+    Ok(StrategyComparisonResult {
+        comparison: comparison.name.clone(),
+        num_sequences: data.len(),
+        strategies_agree,
+        cells,
+        effects,
+    })
+}
+
+/// Output with the given top-level statistics fields removed
+fn without_fields(output: OperationOutput, fields: &[String]) -> Result<OperationOutput> {
+    if fields.is_empty() {
+        return Ok(output);
+    }
+    let mut json = match output {
+        OperationOutput::Typed(typed) => typed.to_json()?,
+        OperationOutput::Statistics(json) => json,
+        other => return Ok(other),
+    };
+    if let Some(object) = json.as_object_mut() {
+        for field in fields {
+            object.remove(field);
+        }
+    }
+    Ok(OperationOutput::Statistics(json))
+}
+
+/// Effects of every cell of a strategy-major grid with `num_configs` columns
+fn effects(cells: &[StrategyCell], num_configs: usize) -> Vec<StrategyEffect> {
+    let cell = |s: usize, c: usize| &cells[s * num_configs + c];
+
+    cells
+        .iter()
+        .enumerate()
+        .map(|(i, this)| {
+            let (s, c) = (i / num_configs, i % num_configs);
+            // ln(T[s,c]) - ln(T[0,c]) - ln(T[s,0]) + ln(T[0,0])
+            let corners = [cell(s, c), cell(0, c), cell(s, 0), cell(0, 0)];
+            let log_interaction = corners[0].log_mean - corners[1].log_mean
+                - corners[2].log_mean
+                + corners[3].log_mean;
+
+            let runs = corners.iter().map(|cell| cell.runs).min().unwrap_or(0);
+            let interaction_ci = (s > 0 && c > 0 && runs > 1).then(|| {
+                let standard_error = corners
+                    .iter()
+                    .map(|cell| cell.log_variance / cell.runs as f64)
+                    .sum::<f64>()
+                    .sqrt();
+                let margin = t_critical_value(4 * (runs - 1), 0.05) * standard_error;
+                ((log_interaction - margin).exp(), (log_interaction + margin).exp())
+            });
+
+            StrategyEffect {
+                strategy: this.strategy.clone(),
+                config_name: this.config_name.clone(),
+                hardware_speedup: (this.log_mean - cell(s, 0).log_mean).exp(),
+                strategy_ratio: (this.log_mean - cell(0, c).log_mean).exp(),
+                interaction: log_interaction.exp(),
+                interaction_ci,
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quality_statistics_strategies() {
+        let data: Vec<SequenceRecord> = (0..500)
+            .map(|i| {
+                let sequence: Vec<u8> = (0..100 + i % 50).map(|j| b"ACGT"[(i * 3 + j * 7) % 4]).collect();
+                let quality: Vec<u8> = (0..sequence.len()).map(|j| 33 + ((i + j) % 41) as u8).collect();
+                SequenceRecord::fastq(format!("read_{}", i), sequence, quality)
+            })
+            .collect();
+
+        let mut parallel = HardwareConfig::naive();
+        parallel.num_threads = 2;
+        let configs = vec![
+            ("naive".to_string(), HardwareConfig::naive()),
+            ("neon_2t".to_string(), parallel),
+        ];
+
+        let comparison = StrategyComparison::preset("quality_statistics").unwrap();
+        let result = benchmark_strategies(&comparison, &data, &configs, 1, 3).unwrap();
+        assert!(result.strategies_agree);
+        assert_eq!(result.cells.len(), 4);
+        assert_eq!(result.cells[2].operation, "quality_statistics_streaming");
+        assert!(result.cells.iter().all(|c| c.output_matches_reference && c.runs == 3));
+
+        // Baseline row and column are 1.0 by construction
+        for effect in &result.effects[..3] {
+            assert!((effect.interaction - 1.0).abs() < 1e-9);
+            assert!(effect.interaction_ci.is_none() && !effect.significant());
+        }
+        assert!((result.effects[0].hardware_speedup - 1.0).abs() < 1e-9);
+        assert!((result.effects[1].strategy_ratio - 1.0).abs() < 1e-9);
+        let (lower, upper) = result.effects[3].interaction_ci.unwrap();
+        assert!(lower <= result.effects[3].interaction && result.effects[3].interaction <= upper);
+        assert!(result.best().is_some());
+
+        // Pass counts differ between k-mer strategies, the spectrum does not
+        let kmers = StrategyComparison::preset("kmer_spectrum").unwrap();
+        assert!(benchmark_strategies(&kmers, &data, &configs[..1], 0, 1).unwrap().strategies_agree);
+
+        assert!(StrategyComparison::preset("sorting").is_err());
+        let single = StrategyComparison::new("single")
+            .with_strategy("only", Box::new(QualityStatistics::new()));
+        assert!(benchmark_strategies(&single, &data, &configs, 0, 1).is_err());
+    }
+}
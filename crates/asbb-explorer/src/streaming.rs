@@ -0,0 +1,320 @@
+//! Streaming (chunked) execution mode
+//!
+//! Batch benchmarks run an operation over a fully pre-loaded dataset, so the
+//! first result only exists once the whole run finishes. Streaming pipelines
+//! behave differently: records arrive from the I/O layer in batches and each
+//! batch produces output as soon as it is processed.
+//!
+//! This module measures that mode directly:
+//! - **Time to first output**: from opening the source until the first batch
+//!   has been read *and* processed
+//! - **Steady-state throughput**: sequences/second over all batches after the
+//!   first, excluding start-up costs (file open, cold caches, first read)
+//!
+//! Sources are created fresh for every run via a factory closure, so each
+//! measured run re-reads its input the same way a real pipeline would.
+
+use anyhow::Result;
+use asbb_core::io::{FastqReader, RecordBatches};
+use asbb_core::stats::duration_quantile;
+use asbb_core::{HardwareConfig, PrimitiveOperation, SequenceRecord};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Results from a streaming benchmark (medians across measured runs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamingResult {
+    /// Records per batch
+    pub chunk_size: usize,
+
+    /// Number of batches per run
+    pub num_chunks: usize,
+
+    /// Total sequences processed per run
+    pub num_sequences: usize,
+
+    /// Time from start until the first batch was read and processed
+    pub time_to_first_output: Duration,
+
+    /// Total wall-clock time for a full run (I/O + compute)
+    pub total_elapsed: Duration,
+
+    /// Throughput after the first batch (sequences/second)
+    pub steady_state_seqs_per_sec: f64,
+
+    /// Throughput after the first batch (MB/s of sequence data)
+    pub steady_state_mbps: f64,
+
+    /// Median per-batch latency (read + process)
+    pub chunk_latency_p50: Duration,
+
+    /// 99th percentile per-batch latency (read + process)
+    pub chunk_latency_p99: Duration,
+}
+
+/// Measurements from a single streaming run
+struct StreamingRun {
+    chunk_size: usize,
+    num_chunks: usize,
+    num_sequences: usize,
+    num_bytes: usize,
+    time_to_first_output: Duration,
+    total_elapsed: Duration,
+    steady_sequences: usize,
+    steady_bytes: usize,
+    chunk_latencies: Vec<Duration>,
+}
+
+/// Benchmark an operation in streaming mode
+///
+/// `make_source` is called once per run (warmup and measured) and must return
+/// a fresh iterator over record batches, e.g. [`fastq_source`].
+pub fn benchmark_operation_streaming<F, I>(
+    operation: &dyn PrimitiveOperation,
+    make_source: F,
+    config: &HardwareConfig,
+    warmup_runs: usize,
+    measured_runs: usize,
+) -> Result<StreamingResult>
+where
+    F: Fn() -> Result<I>,
+    I: Iterator<Item = Result<Vec<SequenceRecord>>>,
+{
+    if measured_runs == 0 {
+        anyhow::bail!("Streaming benchmark requires at least one measured run");
+    }
+
+    for _ in 0..warmup_runs {
+        run_streaming(operation, make_source()?, config)?;
+    }
+
+    let mut runs = Vec::with_capacity(measured_runs);
+    for _ in 0..measured_runs {
+        runs.push(run_streaming(operation, make_source()?, config)?);
+    }
+
+    let first = &runs[0];
+    if runs
+        .iter()
+        .any(|r| r.num_sequences != first.num_sequences || r.num_chunks != first.num_chunks)
+    {
+        anyhow::bail!("Streaming source produced different data across runs");
+    }
+
+    let time_to_first_output =
+        median_duration(runs.iter().map(|r| r.time_to_first_output).collect());
+    let total_elapsed = median_duration(runs.iter().map(|r| r.total_elapsed).collect());
+
+    let mut steady_rates: Vec<(f64, f64)> = runs
+        .iter()
+        .map(|r| {
+            let steady_secs = (r.total_elapsed - r.time_to_first_output).as_secs_f64();
+            if r.num_chunks <= 1 || steady_secs <= 0.0 {
+                // Single batch: no steady state, fall back to whole-run rate
+                let secs = r.total_elapsed.as_secs_f64().max(f64::MIN_POSITIVE);
+                (
+                    r.num_sequences as f64 / secs,
+                    r.num_bytes as f64 / 1_000_000.0 / secs,
+                )
+            } else {
+                (
+                    r.steady_sequences as f64 / steady_secs,
+                    r.steady_bytes as f64 / 1_000_000.0 / steady_secs,
+                )
+            }
+        })
+        .collect();
+    steady_rates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    let (steady_state_seqs_per_sec, steady_state_mbps) = steady_rates[steady_rates.len() / 2];
+
+    let mut chunk_latencies: Vec<Duration> = runs
+        .iter()
+        .flat_map(|r| r.chunk_latencies.iter().copied())
+        .collect();
+    chunk_latencies.sort();
+    let chunk_latency_p50 = duration_quantile(&chunk_latencies, 50.0).unwrap_or_default();
+    let chunk_latency_p99 = duration_quantile(&chunk_latencies, 99.0).unwrap_or_default();
+
+    Ok(StreamingResult {
+        chunk_size: first.chunk_size,
+        num_chunks: first.num_chunks,
+        num_sequences: first.num_sequences,
+        time_to_first_output,
+        total_elapsed,
+        steady_state_seqs_per_sec,
+        steady_state_mbps,
+        chunk_latency_p50,
+        chunk_latency_p99,
+    })
+}
+
+/// Execute one streaming run, timing every batch from read to output
+fn run_streaming<I>(
+    operation: &dyn PrimitiveOperation,
+    source: I,
+    config: &HardwareConfig,
+) -> Result<StreamingRun>
+where
+    I: Iterator<Item = Result<Vec<SequenceRecord>>>,
+{
+    let start = Instant::now();
+    let mut last = start;
+    let mut time_to_first_output = None;
+    let mut chunk_size = 0;
+    let mut num_chunks = 0;
+    let mut num_sequences = 0;
+    let mut num_bytes = 0;
+    let mut steady_sequences = 0;
+    let mut steady_bytes = 0;
+    let mut chunk_latencies = Vec::new();
+
+    for batch in source {
+        let batch = batch?;
+        let _output = operation.execute_with_config(&batch, config)?;
+
+        let now = Instant::now();
+        chunk_latencies.push(now - last);
+        last = now;
+
+        let batch_bytes: usize = batch.iter().map(|r| r.len()).sum();
+        if time_to_first_output.is_none() {
+            time_to_first_output = Some(now - start);
+        } else {
+            steady_sequences += batch.len();
+            steady_bytes += batch_bytes;
+        }
+
+        chunk_size = chunk_size.max(batch.len());
+        num_chunks += 1;
+        num_sequences += batch.len();
+        num_bytes += batch_bytes;
+    }
+
+    let time_to_first_output = time_to_first_output
+        .ok_or_else(|| anyhow::anyhow!("Streaming source produced no records"))?;
+
+    Ok(StreamingRun {
+        chunk_size,
+        num_chunks,
+        num_sequences,
+        num_bytes,
+        time_to_first_output,
+        total_elapsed: start.elapsed(),
+        steady_sequences,
+        steady_bytes,
+        chunk_latencies,
+    })
+}
+
+/// Create a source factory that streams a FASTQ file in batches
+pub fn fastq_source(
+    path: impl Into<PathBuf>,
+    chunk_size: usize,
+) -> impl Fn() -> Result<RecordBatches<BufReader<File>>> {
+    let path = path.into();
+    move || Ok(FastqReader::from_path(&path)?.batches(chunk_size))
+}
+
+/// Create a source factory that replays pre-loaded records in batches
+///
+/// Batches are cloned on each run, so this models an in-memory producer
+/// rather than file I/O. Useful for isolating compute from parsing costs.
+pub fn memory_source(
+    data: &[SequenceRecord],
+    chunk_size: usize,
+) -> impl Fn() -> Result<std::vec::IntoIter<Result<Vec<SequenceRecord>>>> + '_ {
+    assert!(chunk_size > 0, "Chunk size must be positive");
+    move || {
+        Ok(data
+            .chunks(chunk_size)
+            .map(|chunk| Ok(chunk.to_vec()))
+            .collect::<Vec<_>>()
+            .into_iter())
+    }
+}
+
+fn median_duration(mut values: Vec<Duration>) -> Duration {
+    values.sort();
+    values[values.len() / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asbb_ops::base_counting::BaseCounting;
+    use std::io::Write;
+
+    fn create_test_data(num_sequences: usize, seq_length: usize) -> Vec<SequenceRecord> {
+        (0..num_sequences)
+            .map(|i| {
+                SequenceRecord::fastq(
+                    format!("seq_{}", i),
+                    vec![b'A'; seq_length],
+                    vec![b'I'; seq_length],
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_streaming_memory_source() {
+        let op = BaseCounting::new();
+        let data = create_test_data(1000, 150);
+        let config = HardwareConfig::naive();
+
+        let result =
+            benchmark_operation_streaming(&op, memory_source(&data, 100), &config, 1, 3).unwrap();
+
+        assert_eq!(result.num_chunks, 10);
+        assert_eq!(result.num_sequences, 1000);
+        assert_eq!(result.chunk_size, 100);
+        assert!(result.time_to_first_output <= result.total_elapsed);
+        assert!(result.steady_state_seqs_per_sec > 0.0);
+    }
+
+    #[test]
+    fn test_streaming_fastq_source() {
+        let data = create_test_data(250, 50);
+        let path = std::env::temp_dir().join(format!("asbb_streaming_{}.fq", std::process::id()));
+        {
+            let mut file = std::fs::File::create(&path).unwrap();
+            for record in &data {
+                writeln!(file, "@{}", record.id).unwrap();
+                file.write_all(&record.sequence).unwrap();
+                writeln!(file, "\n+").unwrap();
+                file.write_all(record.quality.as_ref().unwrap()).unwrap();
+                writeln!(file).unwrap();
+            }
+        }
+
+        let op = BaseCounting::new();
+        let result = benchmark_operation_streaming(
+            &op,
+            fastq_source(&path, 100),
+            &HardwareConfig::naive(),
+            0,
+            2,
+        )
+        .unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(result.num_chunks, 3);
+        assert_eq!(result.num_sequences, 250);
+    }
+
+    #[test]
+    fn test_streaming_empty_source_fails() {
+        let op = BaseCounting::new();
+        let result = benchmark_operation_streaming(
+            &op,
+            memory_source(&[], 10),
+            &HardwareConfig::naive(),
+            0,
+            1,
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,383 @@
+//! Hardware config sweeps
+//!
+//! A sweep lists candidate values per field and expands to their cartesian
+//! product, so an experiment config names the axes it explores instead of
+//! spelling out every combination:
+//!
+//! ```toml
+//! [[hardware.sweeps]]
+//! id = "{use_neon}_{num_threads}t_{qos}"
+//! description = "{use_neon}, {num_threads} threads, {qos} QoS"
+//! use_neon = [true, false]
+//! num_threads = [1, 2, 4, 8]
+//! qos = ["utility", "user_initiated"]
+//! exclude = [{ use_neon = false, num_threads = 1 }]
+//! ```
+//!
+//! `{field}` placeholders in `id` and `description` are replaced with the
+//! combination's value; booleans render as a label (`use_neon`:
+//! `neon`/`naive`, `use_gpu`: `gpu`/`cpu`, `use_2bit`: `2bit`/`ascii`).
+//! Every generated id must be unique, so each field with several values
+//! needs a placeholder in `id`.
+//!
+//! Combinations are pruned by built-in constraints as well as `exclude`:
+//! GPU combinations need a batch size (without `gpu_batch_size` or
+//! `batch_size` values they are skipped), CPU combinations ignore
+//! `gpu_batch_size`, and single-threaded CPU combinations ignore
+//! `batch_size` rather than repeating once per size.
+//!
+//! `batch_size` (records per work unit) is the one knob shared by both
+//! backends: multi-threaded CPU combinations run chunked with that many
+//! records per chunk, and GPU combinations without `gpu_batch_size`
+//! dispatch that many records at once. The best size differs between them
+//! (cache residency vs dispatch amortization), so sweep it per backend:
+//!
+//! ```toml
+//! [[hardware.sweeps]]
+//! id = "neon_{num_threads}t_batch{batch_size}"
+//! use_neon = [true]
+//! num_threads = [4, 8]
+//! batch_size = [256, 4096, 65536]
+//! ```
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashSet;
+
+use crate::execution_engine::{parse_qos, HardwareConfigEntry};
+
+/// Cartesian product of hardware config fields
+#[derive(Debug, Clone, Deserialize)]
+pub struct HardwareSweep {
+    /// Id template, e.g. `"neon_{num_threads}t"`
+    pub id: String,
+
+    /// Description template (default: the id)
+    #[serde(default)]
+    pub description: Option<String>,
+
+    #[serde(default = "default_use_neon")]
+    pub use_neon: Vec<bool>,
+
+    #[serde(default = "default_num_threads")]
+    pub num_threads: Vec<usize>,
+
+    #[serde(default = "default_thread_assignment")]
+    pub thread_assignment: Vec<String>,
+
+    #[serde(default = "default_encoding")]
+    pub encoding: Vec<String>,
+
+    #[serde(default = "default_use_gpu")]
+    pub use_gpu: Vec<bool>,
+
+    /// Batch sizes for GPU combinations
+    #[serde(default)]
+    pub gpu_batch_size: Vec<usize>,
+
+    /// Records per work unit for multi-threaded CPU and GPU combinations
+    #[serde(default)]
+    pub batch_size: Vec<usize>,
+
+    #[serde(default = "default_use_2bit")]
+    pub use_2bit: Vec<bool>,
+
+    #[serde(default = "default_qos")]
+    pub qos: Vec<String>,
+
+    /// Combinations to skip
+    #[serde(default)]
+    pub exclude: Vec<SweepFilter>,
+}
+
+fn default_use_neon() -> Vec<bool> {
+    vec![false]
+}
+
+fn default_num_threads() -> Vec<usize> {
+    vec![1]
+}
+
+fn default_thread_assignment() -> Vec<String> {
+    vec!["default".to_string()]
+}
+
+fn default_encoding() -> Vec<String> {
+    vec!["ascii".to_string()]
+}
+
+fn default_use_gpu() -> Vec<bool> {
+    vec![false]
+}
+
+fn default_use_2bit() -> Vec<bool> {
+    vec![false]
+}
+
+fn default_qos() -> Vec<String> {
+    vec!["user_initiated".to_string()]
+}
+
+/// Partial combination: matches when every field it sets is equal
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SweepFilter {
+    pub use_neon: Option<bool>,
+    pub num_threads: Option<usize>,
+    pub thread_assignment: Option<String>,
+    pub encoding: Option<String>,
+    pub use_gpu: Option<bool>,
+    pub gpu_batch_size: Option<usize>,
+    pub batch_size: Option<usize>,
+    pub use_2bit: Option<bool>,
+    pub qos: Option<String>,
+}
+
+impl SweepFilter {
+    fn matches(&self, entry: &HardwareConfigEntry) -> bool {
+        fn field<T: PartialEq>(filter: &Option<T>, value: &T) -> bool {
+            filter.as_ref().is_none_or(|f| f == value)
+        }
+
+        field(&self.use_neon, &entry.use_neon)
+            && field(&self.num_threads, &entry.num_threads)
+            && field(&self.thread_assignment, &entry.thread_assignment)
+            && field(&self.encoding, &entry.encoding)
+            && field(&self.use_gpu, &entry.use_gpu)
+            && self
+                .gpu_batch_size
+                .is_none_or(|size| entry.gpu_batch_size == Some(size))
+            && self.batch_size.is_none_or(|size| entry.batch_size == Some(size))
+            && field(&self.use_2bit, &entry.use_2bit)
+            && field(&self.qos, &entry.qos)
+    }
+}
+
+impl HardwareSweep {
+    /// (`gpu_batch_size`, `batch_size`) pairs for one combination
+    ///
+    /// GPU needs a batch size (`gpu_batch_size`, else `batch_size`); CPU
+    /// combinations sweep `batch_size` only with several threads.
+    fn batch_sizes(
+        &self,
+        use_gpu: bool,
+        num_threads: usize,
+    ) -> Vec<(Option<usize>, Option<usize>)> {
+        if use_gpu && !self.gpu_batch_size.is_empty() {
+            self.gpu_batch_size.iter().map(|&size| (Some(size), None)).collect()
+        } else if use_gpu || (num_threads > 1 && !self.batch_size.is_empty()) {
+            self.batch_size.iter().map(|&size| (None, Some(size))).collect()
+        } else {
+            vec![(None, None)]
+        }
+    }
+
+    /// Expand to one config entry per retained combination
+    pub fn expand(&self) -> Result<Vec<HardwareConfigEntry>> {
+        for qos in &self.qos {
+            parse_qos(qos).with_context(|| format!("Sweep '{}'", self.id))?;
+        }
+
+        let mut entries = Vec::new();
+        for &use_neon in &self.use_neon {
+            for &num_threads in &self.num_threads {
+                for thread_assignment in &self.thread_assignment {
+                    for encoding in &self.encoding {
+                        for &use_gpu in &self.use_gpu {
+                            for (gpu_batch_size, batch_size) in
+                                self.batch_sizes(use_gpu, num_threads)
+                            {
+                                for &use_2bit in &self.use_2bit {
+                                    for qos in &self.qos {
+                                        let mut entry = HardwareConfigEntry {
+                                            id: String::new(),
+                                            description: String::new(),
+                                            use_neon,
+                                            num_threads,
+                                            thread_assignment: thread_assignment.clone(),
+                                            encoding: encoding.clone(),
+                                            use_gpu,
+                                            gpu_batch_size,
+                                            batch_size,
+                                            use_2bit,
+                                            qos: qos.clone(),
+                                        };
+                                        if self.exclude.iter().any(|f| f.matches(&entry)) {
+                                            continue;
+                                        }
+                                        entry.id = render(&self.id, &entry);
+                                        entry.description = render(
+                                            self.description.as_deref().unwrap_or(&self.id),
+                                            &entry,
+                                        );
+                                        entries.push(entry);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut ids = HashSet::new();
+        if let Some(duplicate) = entries.iter().find(|e| !ids.insert(e.id.as_str())) {
+            anyhow::bail!(
+                "Sweep '{}' generates id '{}' more than once; add a placeholder for each \
+                 field with several values",
+                self.id,
+                duplicate.id
+            );
+        }
+
+        Ok(entries)
+    }
+}
+
+/// Substitute `{field}` placeholders with the entry's values
+fn render(template: &str, entry: &HardwareConfigEntry) -> String {
+    let label = |value: bool, yes: &str, no: &str| if value { yes } else { no }.to_string();
+
+    [
+        ("{use_neon}", label(entry.use_neon, "neon", "naive")),
+        ("{num_threads}", entry.num_threads.to_string()),
+        ("{thread_assignment}", entry.thread_assignment.clone()),
+        ("{encoding}", entry.encoding.clone()),
+        ("{use_gpu}", label(entry.use_gpu, "gpu", "cpu")),
+        (
+            "{gpu_batch_size}",
+            entry.gpu_batch_size.map(|size| size.to_string()).unwrap_or_default(),
+        ),
+        (
+            "{batch_size}",
+            entry.batch_size.map(|size| size.to_string()).unwrap_or_default(),
+        ),
+        ("{use_2bit}", label(entry.use_2bit, "2bit", "ascii")),
+        ("{qos}", entry.qos.clone()),
+    ]
+    .iter()
+    .fold(template.to_string(), |text, (placeholder, value)| {
+        text.replace(placeholder, value)
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sweep(toml_str: &str) -> HardwareSweep {
+        toml::from_str(toml_str).unwrap()
+    }
+
+    #[test]
+    fn test_expand_cartesian_product() {
+        let entries = sweep(
+            r#"
+            id = "{use_neon}_{num_threads}t_{qos}"
+            use_neon = [true, false]
+            num_threads = [1, 2, 4, 8]
+            qos = ["utility", "user_initiated"]
+            exclude = [{ use_neon = false, num_threads = 1 }]
+            "#,
+        )
+        .expand()
+        .unwrap();
+
+        // 2 × 4 × 2, minus the 2 excluded naive single-thread combinations
+        assert_eq!(entries.len(), 14);
+        assert_eq!(entries[0].id, "neon_1t_utility");
+        assert!(entries.iter().all(|e| e.id != "naive_1t_utility"));
+        assert!(entries.iter().any(|e| e.id == "naive_8t_user_initiated" && !e.use_neon));
+    }
+
+    #[test]
+    fn test_gpu_requires_batch_size() {
+        let without_batch = sweep(
+            r#"
+            id = "{use_gpu}"
+            use_gpu = [false, true]
+            "#,
+        )
+        .expand()
+        .unwrap();
+        assert_eq!(without_batch.len(), 1);
+        assert_eq!(without_batch[0].id, "cpu");
+
+        // CPU combinations are not repeated per batch size
+        let with_batch = sweep(
+            r#"
+            id = "{use_gpu}{gpu_batch_size}"
+            description = "batch {gpu_batch_size}"
+            use_gpu = [false, true]
+            gpu_batch_size = [1000, 100000]
+            "#,
+        )
+        .expand()
+        .unwrap();
+        let ids: Vec<&str> = with_batch.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, ["cpu", "gpu1000", "gpu100000"]);
+        assert_eq!(with_batch[2].gpu_batch_size, Some(100_000));
+        assert_eq!(with_batch[1].description, "batch 1000");
+    }
+
+    #[test]
+    fn test_batch_size_axis() {
+        let entries = sweep(
+            r#"
+            id = "{use_gpu}_{num_threads}t_batch{batch_size}"
+            num_threads = [1, 4]
+            use_gpu = [false, true]
+            batch_size = [256, 65536]
+            exclude = [{ use_gpu = true, num_threads = 4 }]
+            "#,
+        )
+        .expand()
+        .unwrap();
+        let ids: Vec<&str> = entries.iter().map(|e| e.id.as_str()).collect();
+        // One CPU thread ignores the batch size; GPU takes it as dispatch size
+        assert_eq!(
+            ids,
+            [
+                "cpu_1t_batch",
+                "gpu_1t_batch256",
+                "gpu_1t_batch65536",
+                "cpu_4t_batch256",
+                "cpu_4t_batch65536"
+            ]
+        );
+
+        let config = entries[4].to_hardware_config().unwrap();
+        assert_eq!(config.parallel_strategy, asbb_core::ParallelStrategy::Chunked);
+        assert_eq!(config.batch_size, Some(65536));
+        let gpu = entries[1].to_hardware_config().unwrap();
+        assert_eq!(gpu.gpu_batch_size, Some(256));
+        let single = entries[0].to_hardware_config().unwrap();
+        assert_eq!(single.parallel_strategy, asbb_core::ParallelStrategy::PerRecord);
+    }
+
+    #[test]
+    fn test_duplicate_ids_rejected() {
+        let result = sweep(
+            r#"
+            id = "neon"
+            use_neon = [true]
+            num_threads = [1, 2]
+            "#,
+        )
+        .expand();
+        assert!(result.is_err());
+
+        let result = sweep(
+            r#"
+            id = "{qos}"
+            qos = ["fastest"]
+            "#,
+        )
+        .expand();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,180 @@
+//! End-to-end timing with per-phase attribution
+//!
+//! [`benchmark_operation`](crate::benchmark_operation) times compute only,
+//! on data that is already loaded and ASCII-encoded. That is the right number
+//! for comparing kernels, but it hides costs a real workload pays once per
+//! pass: reading the input, converting it to a kernel's encoding, and
+//! serializing the result. This module times every phase of a run
+//! separately, so those costs are attributed rather than excluded:
+//!
+//! | Phase       | Covers                                          |
+//! |-------------|-------------------------------------------------|
+//! | `load`      | reading (or generating) the input records        |
+//! | `encode`    | conversion such as `BitSeq::from_ascii`          |
+//! | `compute`   | the operation itself                             |
+//! | `serialize` | converting the output to JSON                    |
+
+use anyhow::Result;
+use asbb_core::io::FastqReader;
+use asbb_core::{
+    HardwareConfig, OperationOutput, PhaseTimings, PrimitiveOperation, SequenceRecord,
+};
+use std::path::Path;
+use std::time::Instant;
+
+/// Accumulates phase times for one run
+#[derive(Debug, Default)]
+pub struct PhaseTimer {
+    timings: PhaseTimings,
+}
+
+impl PhaseTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `f` as (part of) the load phase
+    pub fn load<T>(&mut self, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        let start = Instant::now();
+        let result = f();
+        self.timings.load_time += start.elapsed();
+        result
+    }
+
+    /// Run `f` as (part of) the encode phase
+    pub fn encode<T>(&mut self, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        let start = Instant::now();
+        let result = f();
+        self.timings.encode_time += start.elapsed();
+        result
+    }
+
+    /// Run `f` as (part of) the compute phase
+    pub fn compute<T>(&mut self, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        let start = Instant::now();
+        let result = f();
+        self.timings.compute_time += start.elapsed();
+        result
+    }
+
+    /// Serialize `output` as the serialize phase
+    pub fn serialize(&mut self, output: &OperationOutput) -> Result<()> {
+        let start = Instant::now();
+        std::hint::black_box(output.statistics_json()?);
+        self.timings.serialize_time += start.elapsed();
+        Ok(())
+    }
+
+    pub fn finish(self) -> PhaseTimings {
+        self.timings
+    }
+}
+
+/// Run a load → encode → compute → serialize pipeline repeatedly
+///
+/// Every measured run repeats all four phases, so each phase's cost is paid
+/// once per pass as it would be in a one-shot workload. `encode` takes
+/// ownership of the loaded records, so an ASCII pipeline can pass them
+/// through unchanged (`Ok`) at no cost. Returns one [`PhaseTimings`] per
+/// measured run.
+pub fn benchmark_phases<T, L, E, C>(
+    load: L,
+    encode: E,
+    compute: C,
+    warmup_runs: usize,
+    measured_runs: usize,
+) -> Result<Vec<PhaseTimings>>
+where
+    L: Fn() -> Result<Vec<SequenceRecord>>,
+    E: Fn(Vec<SequenceRecord>) -> Result<T>,
+    C: Fn(&T) -> Result<OperationOutput>,
+{
+    let run = || -> Result<PhaseTimings> {
+        let mut timer = PhaseTimer::new();
+        let records = timer.load(&load)?;
+        let encoded = timer.encode(|| encode(records))?;
+        let output = timer.compute(|| compute(&encoded))?;
+        timer.serialize(&output)?;
+        Ok(timer.finish())
+    };
+
+    for _ in 0..warmup_runs {
+        run()?;
+    }
+    (0..measured_runs).map(|_| run()).collect()
+}
+
+/// End-to-end phases for an operation on a FASTQ file (ASCII, no encode step)
+pub fn benchmark_file_end_to_end(
+    operation: &dyn PrimitiveOperation,
+    path: &Path,
+    config: &HardwareConfig,
+    warmup_runs: usize,
+    measured_runs: usize,
+) -> Result<Vec<PhaseTimings>> {
+    benchmark_phases(
+        || FastqReader::from_path(path)?.read_all(),
+        Ok,
+        |records: &Vec<SequenceRecord>| crate::execute_configured(operation, records, config),
+        warmup_runs,
+        measured_runs,
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asbb_core::encoding::BitSeq;
+    use asbb_ops::base_counting::BaseCounting;
+    use std::time::Duration;
+
+    fn test_data() -> Vec<SequenceRecord> {
+        (0..200)
+            .map(|i| SequenceRecord::fasta(format!("seq_{}", i), b"ACGTACGTGG".repeat(15)))
+            .collect()
+    }
+
+    #[test]
+    fn test_benchmark_phases_attributes_encoding() {
+        let op = BaseCounting::new();
+        let runs = benchmark_phases(
+            || Ok(test_data()),
+            |records| {
+                Ok(records.iter().map(|r| BitSeq::from_ascii(&r.sequence)).collect::<Vec<_>>())
+            },
+            |encoded| op.execute_2bit_naive(encoded),
+            1,
+            3,
+        )
+        .unwrap();
+
+        assert_eq!(runs.len(), 3);
+        for run in &runs {
+            assert!(run.load_time > Duration::ZERO);
+            assert!(run.encode_time > Duration::ZERO);
+            assert!(run.compute_time > Duration::ZERO);
+            assert!(run.end_to_end() >= run.compute_time + run.encode_time);
+        }
+    }
+
+    #[test]
+    fn test_phase_timer_accumulates() {
+        let mut timer = PhaseTimer::new();
+        for _ in 0..2 {
+            timer
+                .compute(|| {
+                    std::thread::sleep(Duration::from_millis(2));
+                    Ok(())
+                })
+                .unwrap();
+        }
+        let timings = timer.finish();
+
+        assert!(timings.compute_time >= Duration::from_millis(4));
+        assert_eq!(timings.load_time, Duration::ZERO);
+    }
+}
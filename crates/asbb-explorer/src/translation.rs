@@ -0,0 +1,59 @@
+//! Rosetta translation guard
+//!
+//! An x86_64 build run on Apple Silicon executes under Rosetta 2: NEON
+//! configs fall back to scalar code or emulated SSE, GPU and AMX paths
+//! vanish, and the results look plausible but describe emulation. Every
+//! `asbb` command warns loudly when translated ([`warn_if_translated`]);
+//! publication batches (the Level 1/2 harness and DAG traversal) refuse to
+//! run unless forced ([`ensure_native`]). The status is also recorded in
+//! each run's [`RunManifest`](crate::RunManifest).
+
+use anyhow::Result;
+
+/// Warning printed when running translated
+pub const WARNING: &str = "\
+⚠️  ================================================================
+⚠️  RUNNING UNDER ROSETTA 2 TRANSLATION (x86_64 binary on Apple Silicon)
+⚠️  NEON, AMX and GPU paths are unavailable; timings measure emulation.
+⚠️  Rebuild for aarch64-apple-darwin before collecting results.
+⚠️  ================================================================";
+
+/// Print [`WARNING`] to stderr if translated; returns the translation status
+pub fn warn_if_translated() -> bool {
+    let translated = asbb_core::is_translated();
+    if translated {
+        eprintln!("{}", WARNING);
+    }
+    translated
+}
+
+/// Refuse to run a publication batch under translation unless `force`
+pub fn ensure_native(force: bool) -> Result<()> {
+    check(warn_if_translated(), force)
+}
+
+fn check(translated: bool, force: bool) -> Result<()> {
+    if translated && !force {
+        anyhow::bail!(
+            "Refusing to run a publication batch under Rosetta translation; \
+             pass --force to run anyway"
+        );
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translated_runs_need_force() {
+        assert!(check(false, false).is_ok());
+        assert!(check(true, false).is_err());
+        assert!(check(true, true).is_ok());
+    }
+}
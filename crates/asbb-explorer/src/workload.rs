@@ -0,0 +1,242 @@
+//! Workload-mix benchmarks
+//!
+//! Single-operation benchmarks answer "how fast is GC content on 4 threads";
+//! practitioners ask "how long does my QC report take". A workload mix runs a
+//! fixed list of operations back to back over the same in-memory records, the
+//! way a report generator does, and reports whole-report wall time plus each
+//! step's share of it.
+//!
+//! # Presets
+//!
+//! - **fastqc**: the analysis modules of a FastQC report — per-position
+//!   quality, per-position base content, sequence length distribution,
+//!   overrepresented sequences and adapter content
+//!
+//! Every step runs with the same [`HardwareConfig`], so comparing configs
+//! shows what a practitioner would gain by switching the whole tool over,
+//! including steps that do not benefit (hash-table bound overrepresented
+//! sequences) and limit the end-to-end speedup.
+
+use anyhow::Result;
+use asbb_core::stats::duration_quantile;
+use asbb_core::{HardwareConfig, PrimitiveOperation, SequenceRecord};
+use asbb_ops::{
+    length_histogram::LengthHistogram, motif_scan::MotifScan,
+    overrepresented::OverrepresentedSequences, position_content::PositionContent,
+    quality_statistics::QualityStatistics,
+};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use crate::execute_configured;
+
+/// Preset names accepted by [`WorkloadMix::preset`]
+pub const PRESETS: &[&str] = &["fastqc"];
+
+/// One operation of a workload mix
+pub struct WorkloadStep {
+    /// Report section the step produces
+    pub label: String,
+    pub operation: Box<dyn PrimitiveOperation>,
+}
+
+/// A named list of operations run back to back
+pub struct WorkloadMix {
+    pub name: String,
+    pub steps: Vec<WorkloadStep>,
+}
+
+impl WorkloadMix {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Append a step
+    pub fn with_step(mut self, label: &str, operation: Box<dyn PrimitiveOperation>) -> Self {
+        self.steps.push(WorkloadStep {
+            label: label.to_string(),
+            operation,
+        });
+        self
+    }
+
+    /// FastQC-equivalent report
+    pub fn fastqc() -> Self {
+        Self::new("fastqc")
+            .with_step("per_base_quality", Box::new(QualityStatistics::new()))
+            .with_step("per_base_content", Box::new(PositionContent::new()))
+            // FastQC reports every observed length for short reads
+            .with_step("length_distribution", Box::new(LengthHistogram::new(1)))
+            .with_step("overrepresented_sequences", Box::new(OverrepresentedSequences::default()))
+            .with_step("adapter_content", Box::new(MotifScan::adapters()))
+    }
+
+    /// Look up a preset by name
+    pub fn preset(name: &str) -> Result<Self> {
+        match name {
+            "fastqc" => Ok(Self::fastqc()),
+            _ => anyhow::bail!(
+                "Unknown workload preset: {} (available: {})",
+                name,
+                PRESETS.join(", ")
+            ),
+        }
+    }
+
+    /// Run every step once; returns the per-step compute times
+    pub fn run(&self, data: &[SequenceRecord], config: &HardwareConfig) -> Result<Vec<Duration>> {
+        self.steps
+            .iter()
+            .map(|step| {
+                let start = Instant::now();
+                execute_configured(step.operation.as_ref(), data, config)?;
+                Ok(start.elapsed())
+            })
+            .collect()
+    }
+}
+
+// ============================================================================
+// Results
+// ============================================================================
+
+/// Median time of one step across measured runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepTiming {
+    pub label: String,
+    pub operation: String,
+    pub median: Duration,
+    /// Step median over the sum of step medians
+    pub share: f64,
+}
+
+/// Whole-report timings for one hardware config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkloadResult {
+    pub workload: String,
+    pub num_sequences: usize,
+    pub runs: usize,
+    /// Median wall time of a whole report
+    pub wall_median: Duration,
+    pub wall_min: Duration,
+    pub wall_max: Duration,
+    /// Sequences per second through the whole report (median run)
+    pub throughput_seqs_per_sec: f64,
+    pub steps: Vec<StepTiming>,
+}
+
+impl WorkloadResult {
+    /// Step with the largest share of the report
+    pub fn slowest_step(&self) -> Option<&StepTiming> {
+        self.steps.iter().max_by(|a, b| a.median.cmp(&b.median))
+    }
+}
+
+/// Benchmark a workload mix: `warmup_runs` unmeasured reports, then
+/// `measured_runs` timed ones
+pub fn benchmark_workload(
+    mix: &WorkloadMix,
+    data: &[SequenceRecord],
+    config: &HardwareConfig,
+    warmup_runs: usize,
+    measured_runs: usize,
+) -> Result<WorkloadResult> {
+    if measured_runs == 0 {
+        anyhow::bail!("At least one measured run is required");
+    }
+    if mix.steps.is_empty() {
+        anyhow::bail!("Workload {} has no steps", mix.name);
+    }
+
+    for _ in 0..warmup_runs {
+        mix.run(data, config)?;
+    }
+
+    let mut walls = Vec::with_capacity(measured_runs);
+    let mut step_times = vec![Vec::with_capacity(measured_runs); mix.steps.len()];
+    for _ in 0..measured_runs {
+        let start = Instant::now();
+        let times = mix.run(data, config)?;
+        walls.push(start.elapsed());
+        for (samples, time) in step_times.iter_mut().zip(times) {
+            samples.push(time);
+        }
+    }
+
+    walls.sort();
+    let medians: Vec<Duration> = step_times
+        .iter_mut()
+        .map(|samples| {
+            samples.sort();
+            duration_quantile(samples, 50.0).unwrap_or_default()
+        })
+        .collect();
+    let step_total: f64 = medians.iter().map(Duration::as_secs_f64).sum();
+
+    let wall_median = duration_quantile(&walls, 50.0).unwrap_or_default();
+    Ok(WorkloadResult {
+        workload: mix.name.clone(),
+        num_sequences: data.len(),
+        runs: measured_runs,
+        wall_median,
+        wall_min: walls[0],
+        wall_max: walls[walls.len() - 1],
+        throughput_seqs_per_sec: data.len() as f64 / wall_median.as_secs_f64().max(f64::MIN_POSITIVE),
+        steps: mix
+            .steps
+            .iter()
+            .zip(medians)
+            .map(|(step, median)| StepTiming {
+                label: step.label.clone(),
+                operation: step.operation.name().to_string(),
+                median,
+                share: if step_total > 0.0 {
+                    median.as_secs_f64() / step_total
+                } else {
+                    0.0
+                },
+            })
+            .collect(),
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fastqc_workload() {
+        let data: Vec<SequenceRecord> = (0..500)
+            .map(|i| {
+                let sequence: Vec<u8> = (0..100 + i % 50).map(|j| b"ACGT"[(i * 3 + j * 7) % 4]).collect();
+                let quality = vec![b'I'; sequence.len()];
+                SequenceRecord::fastq(format!("read_{}", i), sequence, quality)
+            })
+            .collect();
+
+        let mix = WorkloadMix::preset("fastqc").unwrap();
+        let mut parallel = HardwareConfig::naive();
+        parallel.num_threads = 2;
+
+        for config in [HardwareConfig::naive(), parallel] {
+            let result = benchmark_workload(&mix, &data, &config, 1, 3).unwrap();
+            assert_eq!(result.workload, "fastqc");
+            assert_eq!(result.num_sequences, 500);
+            assert_eq!(result.steps.len(), 5);
+            assert_eq!(result.steps[4].operation, "motif_scan");
+            assert!(result.wall_min <= result.wall_median && result.wall_median <= result.wall_max);
+            assert!((result.steps.iter().map(|s| s.share).sum::<f64>() - 1.0).abs() < 1e-9);
+            assert!(result.slowest_step().is_some());
+        }
+
+        assert!(WorkloadMix::preset("multiqc").is_err());
+        assert!(benchmark_workload(&WorkloadMix::new("empty"), &data, &HardwareConfig::naive(), 0, 1).is_err());
+    }
+}
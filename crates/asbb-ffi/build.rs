@@ -0,0 +1,16 @@
+//! Regenerates `include/asbb.h` from the `extern "C"` API with cbindgen
+
+fn main() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    match cbindgen::generate(&crate_dir) {
+        Ok(bindings) => {
+            bindings.write_to_file(format!("{}/include/asbb.h", crate_dir));
+        }
+        // A half-edited lib.rs should fail in rustc with a useful error,
+        // not here; keep the previous header
+        Err(e) => println!("cargo:warning=Failed to generate include/asbb.h: {}", e),
+    }
+}
@@ -0,0 +1,117 @@
+/* Apple Silicon Bio Bench C API. Generated by cbindgen; do not edit. */
+
+#ifndef ASBB_H
+#define ASBB_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Version of the C ABI (bumped only on incompatible changes)
+#define ASBB_ABI_VERSION 1
+
+// Execution flag: use the NEON kernels
+#define ASBB_NEON (1 << 0)
+
+// Execution flag: use the Metal GPU kernels (where the operation has one)
+#define ASBB_GPU (1 << 1)
+
+// Outcome of a call
+typedef enum AsbbStatus {
+  ASBB_STATUS_OK = 0,
+  // A null pointer or malformed batch; see [`asbb_last_error`]
+  ASBB_STATUS_INVALID_ARGUMENT = 1,
+  // The operation failed (e.g. unsupported backend); see [`asbb_last_error`]
+  ASBB_STATUS_EXECUTION_FAILED = 2,
+  // The operation panicked; the library remains usable
+  ASBB_STATUS_PANIC = 3,
+} AsbbStatus;
+
+// An operation created by [`asbb_operation_new`]
+typedef struct AsbbOperation AsbbOperation;
+
+// Output of [`asbb_execute`]
+typedef struct AsbbResult AsbbResult;
+
+// Records packed back to back
+//
+// Record `i` is `sequences[offsets[i]..offsets[i + 1]]`; `offsets` has
+// `num_records + 1` entries. `qualities` is null for FASTA input, or holds
+// Phred+33 scores with the same layout as `sequences`.
+typedef struct AsbbBatch {
+  const uint8_t *sequences;
+  const uint8_t *qualities;
+  const size_t *offsets;
+  size_t num_records;
+} AsbbBatch;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Message of the last failed call on this thread (empty if none)
+//
+// Valid until the next failing call on the same thread.
+const char *asbb_last_error(void);
+
+// [`ASBB_ABI_VERSION`] of the loaded library
+uint32_t asbb_abi_version(void);
+
+// Names of the available operations, as a JSON array (static string)
+const char *asbb_operation_names(void);
+
+// Create an operation by name; null (with [`asbb_last_error`] set) if unknown
+//
+// # Safety
+//
+// `name` must be null or a NUL-terminated string.
+struct AsbbOperation *asbb_operation_new(const char *name);
+
+// Free an operation (null is ignored)
+//
+// # Safety
+//
+// `operation` must be null or come from [`asbb_operation_new`], and must
+// not be used afterwards.
+void asbb_operation_free(struct AsbbOperation *operation);
+
+// Execute `operation` on `batch` with `num_threads` threads and `flags`
+// (`ASBB_NEON`, `ASBB_GPU`); on success `*result` receives the output
+//
+// Single-threaded NEON execution reads the caller's buffers in place; the
+// multi-threaded, naive and GPU kernels take owned records, so the batch is
+// copied for those.
+//
+// # Safety
+//
+// `operation` must come from [`asbb_operation_new`]; `batch` must point to
+// an [`AsbbBatch`] whose buffers are valid for the lengths its offsets
+// describe; `result` must be a valid pointer to write to.
+enum AsbbStatus asbb_execute(const struct AsbbOperation *operation,
+                             const struct AsbbBatch *batch,
+                             size_t num_threads,
+                             uint32_t flags,
+                             struct AsbbResult **result);
+
+// JSON text of a result (owned by `result`)
+//
+// # Safety
+//
+// `result` must come from [`asbb_execute`] and not have been freed.
+const char *asbb_result_json(const struct AsbbResult *result);
+
+// Free a result (null is ignored)
+//
+// # Safety
+//
+// `result` must be null or come from [`asbb_execute`], and must not be
+// used afterwards.
+void asbb_result_free(struct AsbbResult *result);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ASBB_H */
@@ -0,0 +1,468 @@
+//! C API for Apple Silicon Bio Bench operations
+//!
+//! Lets C/C++ pipelines run the optimized kernels without Rust: create an
+//! operation by name, execute it on a packed batch of records and read the
+//! result as JSON. The header (`include/asbb.h`) is generated by cbindgen
+//! on every build.
+//!
+//! ```c
+//! #include "asbb.h"
+//!
+//! AsbbOperation *op = asbb_operation_new("gc_content");
+//! size_t offsets[] = {0, 4, 10};
+//! AsbbBatch batch = {(const uint8_t *)"ACGTGGCCAA", NULL, offsets, 2};
+//! AsbbResult *result = NULL;
+//! if (asbb_execute(op, &batch, 4, ASBB_NEON, &result) == ASBB_STATUS_OK) {
+//!     puts(asbb_result_json(result));    /* {"gc_percent":60.0,...} */
+//!     asbb_result_free(result);
+//! } else {
+//!     fprintf(stderr, "%s\n", asbb_last_error());
+//! }
+//! asbb_operation_free(op);
+//! ```
+//!
+//! # ABI
+//!
+//! Handles are opaque and every struct passed by value or pointer is
+//! `#[repr(C)]`; new capabilities are added as new functions or flag bits,
+//! never by changing existing signatures. [`asbb_abi_version`] reports
+//! [`ASBB_ABI_VERSION`], which only changes on incompatible revisions.
+//! Strings returned by the library are owned by it and stay valid until the
+//! owning handle is freed (or, for [`asbb_last_error`], until the next
+//! failing call on the same thread).
+
+use anyhow::{Context, Result};
+use asbb_core::{HardwareConfig, OperationOutput, PrimitiveOperation, SequenceRecord, SequenceView};
+use asbb_ops::{available_operations, create_operation};
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::fmt::Write;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::sync::OnceLock;
+
+/// Version of the C ABI (bumped only on incompatible changes)
+pub const ASBB_ABI_VERSION: u32 = 1;
+
+/// Execution flag: use the NEON kernels
+pub const ASBB_NEON: u32 = 1 << 0;
+
+/// Execution flag: use the Metal GPU kernels (where the operation has one)
+pub const ASBB_GPU: u32 = 1 << 1;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Outcome of a call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsbbStatus {
+    Ok = 0,
+    /// A null pointer or malformed batch; see [`asbb_last_error`]
+    InvalidArgument = 1,
+    /// The operation failed (e.g. unsupported backend); see [`asbb_last_error`]
+    ExecutionFailed = 2,
+    /// The operation panicked; the library remains usable
+    Panic = 3,
+}
+
+/// Records packed back to back
+///
+/// Record `i` is `sequences[offsets[i]..offsets[i + 1]]`; `offsets` has
+/// `num_records + 1` entries. `qualities` is null for FASTA input, or holds
+/// Phred+33 scores with the same layout as `sequences`.
+#[repr(C)]
+pub struct AsbbBatch {
+    pub sequences: *const u8,
+    pub qualities: *const u8,
+    pub offsets: *const usize,
+    pub num_records: usize,
+}
+
+/// An operation created by [`asbb_operation_new`]
+pub struct AsbbOperation {
+    inner: Box<dyn PrimitiveOperation>,
+}
+
+/// Output of [`asbb_execute`]
+pub struct AsbbResult {
+    json: CString,
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Run `f`, recording its error (or panic) for [`asbb_last_error`]
+fn guarded(status: AsbbStatus, f: impl FnOnce() -> Result<()>) -> AsbbStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => AsbbStatus::Ok,
+        Ok(Err(e)) => {
+            set_last_error(format!("{:#}", e));
+            status
+        }
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            set_last_error(format!("Operation panicked: {}", message));
+            AsbbStatus::Panic
+        }
+    }
+}
+
+/// Message of the last failed call on this thread (empty if none)
+///
+/// Valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn asbb_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| match last.borrow().as_ref() {
+        Some(message) => message.as_ptr(),
+        None => c"".as_ptr(),
+    })
+}
+
+// ============================================================================
+// C API
+// ============================================================================
+
+/// [`ASBB_ABI_VERSION`] of the loaded library
+#[no_mangle]
+pub extern "C" fn asbb_abi_version() -> u32 {
+    ASBB_ABI_VERSION
+}
+
+/// Names of the available operations, as a JSON array (static string)
+#[no_mangle]
+pub extern "C" fn asbb_operation_names() -> *const c_char {
+    static NAMES: OnceLock<CString> = OnceLock::new();
+    NAMES
+        .get_or_init(|| {
+            let json = serde_json::to_string(&available_operations()).unwrap_or_default();
+            CString::new(json).unwrap_or_default()
+        })
+        .as_ptr()
+}
+
+/// Create an operation by name; null (with [`asbb_last_error`] set) if unknown
+///
+/// # Safety
+///
+/// `name` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn asbb_operation_new(name: *const c_char) -> *mut AsbbOperation {
+    let mut operation = ptr::null_mut();
+    guarded(AsbbStatus::InvalidArgument, || {
+        anyhow::ensure!(!name.is_null(), "Operation name is null");
+        // SAFETY: non-null and NUL-terminated per the contract
+        let name = unsafe { CStr::from_ptr(name) }.to_str().context("Operation name is not UTF-8")?;
+        let inner = create_operation(name)?;
+        operation = Box::into_raw(Box::new(AsbbOperation { inner }));
+        Ok(())
+    });
+    operation
+}
+
+/// Free an operation (null is ignored)
+///
+/// # Safety
+///
+/// `operation` must be null or come from [`asbb_operation_new`], and must
+/// not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn asbb_operation_free(operation: *mut AsbbOperation) {
+    if !operation.is_null() {
+        // SAFETY: allocated by `asbb_operation_new` per the contract
+        drop(unsafe { Box::from_raw(operation) });
+    }
+}
+
+/// Execute `operation` on `batch` with `num_threads` threads and `flags`
+/// (`ASBB_NEON`, `ASBB_GPU`); on success `*result` receives the output
+///
+/// Single-threaded NEON execution reads the caller's buffers in place; the
+/// multi-threaded, naive and GPU kernels take owned records, so the batch is
+/// copied for those.
+///
+/// # Safety
+///
+/// `operation` must come from [`asbb_operation_new`]; `batch` must point to
+/// an [`AsbbBatch`] whose buffers are valid for the lengths its offsets
+/// describe; `result` must be a valid pointer to write to.
+#[no_mangle]
+pub unsafe extern "C" fn asbb_execute(
+    operation: *const AsbbOperation,
+    batch: *const AsbbBatch,
+    num_threads: usize,
+    flags: u32,
+    result: *mut *mut AsbbResult,
+) -> AsbbStatus {
+    if operation.is_null() || batch.is_null() || result.is_null() {
+        set_last_error("Operation, batch and result must not be null".to_string());
+        return AsbbStatus::InvalidArgument;
+    }
+    // SAFETY: non-null and valid per the contract
+    let (operation, batch) = unsafe { (&*operation, &*batch) };
+
+    // SAFETY: buffers are valid for the offsets per the contract
+    let batch = match unsafe { BorrowedBatch::new(batch) } {
+        Ok(batch) => batch,
+        Err(e) => {
+            set_last_error(format!("{:#}", e));
+            return AsbbStatus::InvalidArgument;
+        }
+    };
+    let views = batch.views();
+
+    let mut config = HardwareConfig::naive();
+    config.use_neon = flags & ASBB_NEON != 0;
+    config.use_gpu = flags & ASBB_GPU != 0;
+    config.num_threads = num_threads.max(1);
+
+    guarded(AsbbStatus::ExecutionFailed, || {
+        let output = if config.use_neon && !config.use_gpu && config.num_threads == 1 {
+            operation.inner.execute_views(&views)?
+        } else {
+            let records: Vec<SequenceRecord> = views.iter().map(SequenceView::to_record).collect();
+            asbb_explorer::execute_configured(operation.inner.as_ref(), &records, &config)?
+        };
+        let json = CString::new(output_json(output)?.to_string())?;
+        // SAFETY: checked non-null above
+        unsafe { *result = Box::into_raw(Box::new(AsbbResult { json })) };
+        Ok(())
+    })
+}
+
+/// JSON text of a result (owned by `result`)
+///
+/// # Safety
+///
+/// `result` must come from [`asbb_execute`] and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn asbb_result_json(result: *const AsbbResult) -> *const c_char {
+    if result.is_null() {
+        return c"".as_ptr();
+    }
+    // SAFETY: valid per the contract
+    unsafe { &*result }.json.as_ptr()
+}
+
+/// Free a result (null is ignored)
+///
+/// # Safety
+///
+/// `result` must be null or come from [`asbb_execute`], and must not be
+/// used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn asbb_result_free(result: *mut AsbbResult) {
+    if !result.is_null() {
+        // SAFETY: allocated by `asbb_execute` per the contract
+        drop(unsafe { Box::from_raw(result) });
+    }
+}
+
+// ============================================================================
+// Conversions
+// ============================================================================
+
+/// A packed batch borrowed from the caller (ids are the record indices)
+struct BorrowedBatch<'a> {
+    sequences: &'a [u8],
+    qualities: Option<&'a [u8]>,
+    offsets: &'a [usize],
+    /// Record indices as text, back to back (`id_offsets` like `offsets`)
+    ids: String,
+    id_offsets: Vec<usize>,
+}
+
+impl<'a> BorrowedBatch<'a> {
+    /// Check the offsets and borrow the buffers they describe
+    ///
+    /// # Safety
+    ///
+    /// The batch's buffers must be valid for the lengths its offsets
+    /// describe, for as long as the returned batch is used.
+    unsafe fn new(batch: &'a AsbbBatch) -> Result<Self> {
+        let mut borrowed = Self {
+            sequences: &[],
+            qualities: None,
+            offsets: &[],
+            ids: String::new(),
+            id_offsets: vec![0],
+        };
+        if batch.num_records == 0 {
+            return Ok(borrowed);
+        }
+        anyhow::ensure!(
+            !batch.sequences.is_null() && !batch.offsets.is_null(),
+            "Batch sequences and offsets must not be null"
+        );
+        // SAFETY: `offsets` holds `num_records + 1` entries per the contract
+        let offsets = unsafe { std::slice::from_raw_parts(batch.offsets, batch.num_records + 1) };
+        anyhow::ensure!(
+            offsets.windows(2).all(|pair| pair[0] <= pair[1]),
+            "Batch offsets must be non-decreasing"
+        );
+        let total = offsets[batch.num_records];
+        // SAFETY: the buffers hold `offsets[num_records]` bytes per the contract
+        borrowed.sequences = unsafe { std::slice::from_raw_parts(batch.sequences, total) };
+        borrowed.qualities = (!batch.qualities.is_null())
+            .then(|| unsafe { std::slice::from_raw_parts(batch.qualities, total) });
+        borrowed.offsets = offsets;
+
+        for i in 0..batch.num_records {
+            write!(borrowed.ids, "{}", i)?;
+            borrowed.id_offsets.push(borrowed.ids.len());
+        }
+        Ok(borrowed)
+    }
+
+    /// Every record as a view into the caller's buffers
+    fn views(&self) -> Vec<SequenceView<'_>> {
+        self.offsets
+            .windows(2)
+            .zip(self.id_offsets.windows(2))
+            .map(|(range, id)| SequenceView {
+                id: &self.ids[id[0]..id[1]],
+                sequence: &self.sequences[range[0]..range[1]],
+                quality: self.qualities.map(|qualities| &qualities[range[0]..range[1]]),
+            })
+            .collect()
+    }
+}
+
+/// JSON of an operation output; records become `{id, sequence, quality}`
+/// objects with text sequences
+fn output_json(output: OperationOutput) -> Result<serde_json::Value> {
+    Ok(match output {
+        OperationOutput::Records(records) => records
+            .iter()
+            .map(|record| {
+                serde_json::json!({
+                    "id": record.id,
+                    "sequence": String::from_utf8_lossy(&record.sequence),
+                    "quality": record.quality.as_deref().map(String::from_utf8_lossy),
+                })
+            })
+            .collect(),
+        OperationOutput::Typed(typed) => typed.to_json()?,
+        OperationOutput::Statistics(value) | OperationOutput::Json(value) => value,
+        OperationOutput::Boolean(value) => value.into(),
+        OperationOutput::Count(count) => count.into(),
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn execute(name: &CStr, batch: &AsbbBatch, flags: u32) -> (AsbbStatus, serde_json::Value) {
+        execute_threads(name, batch, 2, flags)
+    }
+
+    fn execute_threads(
+        name: &CStr,
+        batch: &AsbbBatch,
+        num_threads: usize,
+        flags: u32,
+    ) -> (AsbbStatus, serde_json::Value) {
+        unsafe {
+            let operation = asbb_operation_new(name.as_ptr());
+            assert!(!operation.is_null());
+            let mut result = ptr::null_mut();
+            let status = asbb_execute(operation, batch, num_threads, flags, &mut result);
+            let json = if status == AsbbStatus::Ok {
+                let text = CStr::from_ptr(asbb_result_json(result)).to_str().unwrap();
+                serde_json::from_str(text).unwrap()
+            } else {
+                serde_json::Value::Null
+            };
+            asbb_result_free(result);
+            asbb_operation_free(operation);
+            (status, json)
+        }
+    }
+
+    #[test]
+    fn test_execute_packed_batch() {
+        let sequences = b"ACGTGGCCAA";
+        let offsets = [0usize, 4, 10];
+        let batch = AsbbBatch {
+            sequences: sequences.as_ptr(),
+            qualities: ptr::null(),
+            offsets: offsets.as_ptr(),
+            num_records: 2,
+        };
+
+        let (status, naive) = execute(c"gc_content", &batch, 0);
+        assert_eq!(status, AsbbStatus::Ok);
+        assert_eq!(naive["gc_percent"], 60.0);
+        assert_eq!(execute(c"gc_content", &batch, ASBB_NEON).1, naive);
+
+        let names: Vec<String> = serde_json::from_str(
+            unsafe { CStr::from_ptr(asbb_operation_names()) }.to_str().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(names, available_operations());
+        assert_eq!(asbb_abi_version(), ASBB_ABI_VERSION);
+    }
+
+    #[test]
+    fn test_borrowed_batch_matches_copied() {
+        let sequences = b"ACGTTTGGCA";
+        let qualities = b"IIII#IIIII";
+        let offsets = [0usize, 4, 4, 10];
+        let batch = AsbbBatch {
+            sequences: sequences.as_ptr(),
+            qualities: qualities.as_ptr(),
+            offsets: offsets.as_ptr(),
+            num_records: 3,
+        };
+
+        let borrowed = unsafe { BorrowedBatch::new(&batch) }.unwrap();
+        let views = borrowed.views();
+        assert_eq!(views[2].id, "2");
+        assert_eq!(views[1].sequence, b"");
+        assert_eq!(views[2].quality, Some(&b"#IIIII"[..]));
+
+        // Single-threaded NEON borrows the buffers, two threads copy them
+        for name in [c"gc_content", c"reverse_complement"] {
+            let (status, borrowed) = execute_threads(name, &batch, 1, ASBB_NEON);
+            assert_eq!(status, AsbbStatus::Ok);
+            assert_eq!(borrowed, execute(name, &batch, ASBB_NEON).1, "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn test_errors_are_reported() {
+        assert!(unsafe { asbb_operation_new(c"nope".as_ptr()) }.is_null());
+        let error = unsafe { CStr::from_ptr(asbb_last_error()) }.to_str().unwrap();
+        assert!(error.starts_with("Unknown operation: nope"));
+
+        // Offsets running backwards
+        let offsets = [4usize, 0];
+        let batch = AsbbBatch {
+            sequences: b"ACGT".as_ptr(),
+            qualities: ptr::null(),
+            offsets: offsets.as_ptr(),
+            num_records: 1,
+        };
+        assert_eq!(execute(c"gc_content", &batch, 0).0, AsbbStatus::InvalidArgument);
+    }
+}
@@ -0,0 +1,199 @@
+//! Splitting large inputs into batches that fit the GPU working set
+//!
+//! Each kernel wrapper packs its input into contiguous buffers. At Huge
+//! scale (10M reads) those buffers can exceed the device's
+//! `recommendedMaxWorkingSetSize`, at which point Metal either fails the
+//! allocation or pages, and the timing no longer measures the GPU.
+//!
+//! The wrappers therefore plan batches of whole records whose estimated
+//! buffer footprint stays within a budget (half the recommended working set
+//! by default), dispatch each batch, and reduce the per-batch results on the
+//! CPU. [`GpuMetrics::num_batches`](crate::GpuMetrics::num_batches) reports
+//! how many dispatches a call took; timings are summed across batches.
+//!
+//! The budget can be overridden with [`BATCH_BUDGET_ENV`] (in MiB), e.g. to
+//! exercise batching on small datasets.
+
+use anyhow::{Context, Result};
+use asbb_core::SequenceRecord;
+use std::ops::Range;
+
+use crate::{GpuMetrics, MetalBackend};
+
+/// Environment variable overriding the per-batch buffer budget (MiB)
+pub const BATCH_BUDGET_ENV: &str = "ASBB_GPU_BATCH_MB";
+
+/// Fraction of `recommendedMaxWorkingSetSize` used per batch, leaving room
+/// for other allocations and the rest of the system
+pub const WORKING_SET_FRACTION: f64 = 0.5;
+
+/// Budget from [`BATCH_BUDGET_ENV`], if set
+pub fn budget_from_env() -> Result<Option<u64>> {
+    match std::env::var(BATCH_BUDGET_ENV) {
+        Ok(value) => {
+            let mib: u64 = value
+                .trim()
+                .parse()
+                .ok()
+                .filter(|&mib| mib > 0)
+                .with_context(|| format!("Invalid {} '{}' (MiB, > 0)", BATCH_BUDGET_ENV, value))?;
+            Ok(Some(mib * 1024 * 1024))
+        }
+        Err(_) => Ok(None),
+    }
+}
+
+/// Default budget for a device's recommended working set
+pub fn default_budget(recommended_working_set: u64) -> u64 {
+    ((recommended_working_set as f64 * WORKING_SET_FRACTION) as u64).max(1)
+}
+
+/// Split records into consecutive batches of at most `budget` bytes
+///
+/// A single record larger than the budget gets a batch of its own (records
+/// are never split). Empty input yields no batches.
+pub fn plan_batches(record_bytes: impl IntoIterator<Item = usize>, budget: u64) -> Vec<Range<usize>> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut used = 0u64;
+    let mut end = 0;
+
+    for (i, bytes) in record_bytes.into_iter().enumerate() {
+        let bytes = bytes as u64;
+        if i > start && used + bytes > budget {
+            batches.push(start..i);
+            start = i;
+            used = 0;
+        }
+        used += bytes;
+        end = i + 1;
+    }
+    if end > start {
+        batches.push(start..end);
+    }
+    batches
+}
+
+// ============================================================================
+// Per-record buffer footprints (input + offsets/lengths + output)
+// ============================================================================
+
+const INDEX_BYTES: usize = 2 * std::mem::size_of::<u32>();
+
+/// `count_bases`: sequence, offset/length, 4 counts
+pub fn base_count_bytes(record: &SequenceRecord) -> usize {
+    record.sequence.len() + INDEX_BYTES + 4 * std::mem::size_of::<u32>()
+}
+
+/// `count_gc` / `count_at` / `calculate_complexity`: sequence, offset/length, 1 value
+pub fn per_sequence_value_bytes(record: &SequenceRecord) -> usize {
+    record.sequence.len() + INDEX_BYTES + std::mem::size_of::<u32>()
+}
+
+/// `aggregate_quality`: qualities, offset/length, 4 stats
+pub fn quality_bytes(record: &SequenceRecord) -> usize {
+    record.quality.as_ref().map_or(0, |q| q.len()) + INDEX_BYTES + 4 * std::mem::size_of::<u32>()
+}
+
+/// `reverse_complement`: sequence in and out, offset/length, output offset
+pub fn reverse_complement_bytes(record: &SequenceRecord) -> usize {
+    2 * record.sequence.len() + INDEX_BYTES + std::mem::size_of::<u32>()
+}
+
+impl MetalBackend {
+    /// Run `dispatch` on each batch of `data` that fits the budget
+    ///
+    /// Returns the per-batch results in order and the combined metrics.
+    pub(crate) fn run_batched<T>(
+        &self,
+        data: &[SequenceRecord],
+        record_bytes: fn(&SequenceRecord) -> usize,
+        mut dispatch: impl FnMut(&[SequenceRecord]) -> Result<(T, GpuMetrics)>,
+    ) -> Result<(Vec<T>, GpuMetrics)> {
+        let batches = plan_batches(data.iter().map(record_bytes), self.batch_budget());
+
+        let mut results = Vec::with_capacity(batches.len());
+        let mut metrics = Vec::with_capacity(batches.len());
+        for batch in batches {
+            let (result, batch_metrics) = dispatch(&data[batch])?;
+            results.push(result);
+            metrics.push(batch_metrics);
+        }
+        Ok((results, GpuMetrics::combine(&metrics)))
+    }
+}
+
+impl GpuMetrics {
+    /// Aggregate metrics over sequential batch dispatches
+    ///
+    /// Times (including reduction) and sequence counts are summed; the
+    /// threadgroup size is the first batch's (the largest, as batches are
+    /// filled greedily).
+    pub fn combine(batches: &[GpuMetrics]) -> GpuMetrics {
+        let total_time_ms: f64 = batches.iter().map(|m| m.total_time_ms).sum();
+        let num_sequences: usize = batches.iter().map(|m| m.num_sequences).sum();
+        GpuMetrics {
+            total_time_ms,
+            kernel_time_ms: batches.iter().map(|m| m.kernel_time_ms).sum(),
+            overhead_ms: batches.iter().map(|m| m.overhead_ms).sum(),
+            num_sequences,
+            throughput: if total_time_ms > 0.0 {
+                num_sequences as f64 / (total_time_ms / 1000.0)
+            } else {
+                0.0
+            },
+            threadgroup_size: batches.first().map_or(0, |m| m.threadgroup_size),
+            num_batches: batches.iter().map(|m| m.num_batches).sum(),
+            reduce_ms: batches.iter().map(|m| m.reduce_ms).sum(),
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(total_time_ms: f64, num_sequences: usize) -> GpuMetrics {
+        GpuMetrics {
+            total_time_ms,
+            kernel_time_ms: total_time_ms / 2.0,
+            overhead_ms: 1.0,
+            num_sequences,
+            throughput: 0.0,
+            threadgroup_size: 256,
+            num_batches: 1,
+            reduce_ms: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_plan_batches() {
+        assert_eq!(plan_batches([40, 40, 40, 40, 40], 100), [0..2, 2..4, 4..5]);
+        assert_eq!(plan_batches([10; 5], 1_000).as_slice(), &[Range { start: 0, end: 5 }]);
+        assert!(plan_batches([], 100).is_empty());
+    }
+
+    #[test]
+    fn test_oversized_record_gets_own_batch() {
+        assert_eq!(plan_batches([10, 500, 10], 100), [0..1, 1..2, 2..3]);
+        assert_eq!(plan_batches([500], 100).as_slice(), &[Range { start: 0, end: 1 }]);
+    }
+
+    #[test]
+    fn test_combine_metrics() {
+        let combined = GpuMetrics::combine(&[metrics(100.0, 1_000), metrics(300.0, 3_000)]);
+        assert_eq!(combined.total_time_ms, 400.0);
+        assert_eq!(combined.kernel_time_ms, 200.0);
+        assert_eq!(combined.num_sequences, 4_000);
+        assert_eq!(combined.throughput, 10_000.0);
+        assert_eq!(combined.num_batches, 2);
+
+        let empty = GpuMetrics::combine(&[]);
+        assert_eq!(empty.num_batches, 0);
+        assert_eq!(empty.throughput, 0.0);
+    }
+}
@@ -0,0 +1,321 @@
+//! Core ML inference on the Neural Engine, GPU or CPU
+//!
+//! Core ML is the only public route to the Neural Engine: a model is loaded
+//! with a set of allowed compute units and Core ML places each layer. Loading
+//! the same model with different [`ComputeUnits`] gives an ANE vs GPU vs CPU
+//! comparison of identical work. On M5 the GPU path runs on the GPU neural
+//! accelerators.
+//!
+//! Models here take one fixed-shape `rows × cols` float32 input and return one
+//! float32 output with `rows` rows. [`predict_rows`](CoreMlModel::predict_rows)
+//! pads and splits larger inputs into model-sized batches (the Neural Engine
+//! only runs static shapes).
+//!
+//! Like [`mps`](crate::mps), this messages the Objective-C classes directly.
+
+use anyhow::Result;
+use objc::rc::{autoreleasepool, StrongPtr};
+use objc::runtime::Object;
+use objc::{class, msg_send, sel, sel_impl};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{c_char, c_void, CStr};
+use std::path::{Path, PathBuf};
+use std::ptr::null_mut;
+use std::rc::Rc;
+
+#[link(name = "CoreML", kind = "framework")]
+extern "C" {}
+
+#[link(name = "Foundation", kind = "framework")]
+extern "C" {}
+
+/// `MLMultiArrayDataTypeFloat32`
+const ML_FLOAT32: i64 = 0x10000 | 32;
+
+/// `NSUTF8StringEncoding`
+const NS_UTF8: u64 = 4;
+
+/// Compute units Core ML may use (`MLComputeUnits`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComputeUnits {
+    CpuOnly,
+    CpuAndGpu,
+    /// Core ML's choice (Neural Engine when it supports the model)
+    All,
+    CpuAndNeuralEngine,
+}
+
+impl ComputeUnits {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ComputeUnits::CpuOnly => "cpu",
+            ComputeUnits::CpuAndGpu => "gpu",
+            ComputeUnits::All => "all",
+            ComputeUnits::CpuAndNeuralEngine => "ane",
+        }
+    }
+
+    fn raw(self) -> i64 {
+        match self {
+            ComputeUnits::CpuOnly => 0,
+            ComputeUnits::CpuAndGpu => 1,
+            ComputeUnits::All => 2,
+            ComputeUnits::CpuAndNeuralEngine => 3,
+        }
+    }
+}
+
+/// A loaded Core ML model with a single 2-D float32 input and output
+pub struct CoreMlModel {
+    model: StrongPtr,
+    input: String,
+    output: String,
+    /// Fixed input shape
+    rows: usize,
+    cols: usize,
+    units: ComputeUnits,
+}
+
+/// Model path, input name, output name and compute units
+type ModelKey = (PathBuf, String, String, ComputeUnits);
+
+thread_local! {
+    /// Models loaded on this thread
+    static MODELS: RefCell<HashMap<ModelKey, Rc<CoreMlModel>>> = RefCell::new(HashMap::new());
+}
+
+impl CoreMlModel {
+    /// Load (compiling `.mlmodel`/`.mlpackage` files) a model
+    pub fn load(path: &Path, input: &str, output: &str, units: ComputeUnits) -> Result<Self> {
+        if !path.exists() {
+            anyhow::bail!("Core ML model not found: {}", path.display());
+        }
+
+        autoreleasepool(|| unsafe {
+            let mut error: *mut Object = null_mut();
+            let path_string = ns_string(&path.to_string_lossy());
+            let mut url: *mut Object = msg_send![class!(NSURL), fileURLWithPath: *path_string];
+
+            if path.extension().is_none_or(|ext| ext != "mlmodelc") {
+                url = msg_send![class!(MLModel), compileModelAtURL: url error: &mut error];
+                checked(url, error, "Failed to compile Core ML model")?;
+            }
+
+            let configuration: *mut Object = msg_send![class!(MLModelConfiguration), new];
+            let configuration = StrongPtr::new(configuration);
+            let _: () = msg_send![*configuration, setComputeUnits: units.raw()];
+
+            let model: *mut Object = msg_send![
+                class!(MLModel),
+                modelWithContentsOfURL: url
+                configuration: *configuration
+                error: &mut error
+            ];
+            let model = StrongPtr::retain(checked(model, error, "Failed to load Core ML model")?);
+
+            // Fixed input shape from the model description
+            let description: *mut Object = msg_send![*model, modelDescription];
+            let inputs: *mut Object = msg_send![description, inputDescriptionsByName];
+            let input_name = ns_string(input);
+            let feature: *mut Object = msg_send![inputs, objectForKey: *input_name];
+            if feature.is_null() {
+                anyhow::bail!("Core ML model has no input named '{}'", input);
+            }
+            let constraint: *mut Object = msg_send![feature, multiArrayConstraint];
+            if constraint.is_null() {
+                anyhow::bail!("Core ML input '{}' is not a multi-array", input);
+            }
+            let shape = usize_array(msg_send![constraint, shape]);
+            let [rows, cols] = shape[..] else {
+                anyhow::bail!("Core ML input '{}' has shape {:?}, expected 2-D", input, shape);
+            };
+
+            Ok(Self {
+                model,
+                input: input.to_string(),
+                output: output.to_string(),
+                rows,
+                cols,
+                units,
+            })
+        })
+    }
+
+    /// Load a model once per thread and reuse it
+    ///
+    /// The first load of a model on the Neural Engine compiles it for the
+    /// ANE, which takes far longer than a prediction.
+    pub fn shared(path: &Path, input: &str, output: &str, units: ComputeUnits) -> Result<Rc<Self>> {
+        let key = (path.to_path_buf(), input.to_string(), output.to_string(), units);
+        if let Some(model) = MODELS.with(|models| models.borrow().get(&key).cloned()) {
+            return Ok(model);
+        }
+        let model = Rc::new(Self::load(path, input, output, units)?);
+        MODELS.with(|models| models.borrow_mut().insert(key, model.clone()));
+        Ok(model)
+    }
+
+    /// Fixed input shape (`rows × cols`)
+    pub fn input_shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn compute_units(&self) -> ComputeUnits {
+        self.units
+    }
+
+    /// Run one batch: row-major `rows × cols` in, row-major `rows × n` out
+    pub fn predict(&self, input: &[f32]) -> Result<Vec<f32>> {
+        if input.len() != self.rows * self.cols {
+            anyhow::bail!(
+                "Core ML input has {} values, expected {}×{}",
+                input.len(),
+                self.rows,
+                self.cols
+            );
+        }
+
+        autoreleasepool(|| unsafe {
+            let mut error: *mut Object = null_mut();
+
+            // Wraps `input` without copying; only read during the prediction
+            let shape = number_array(&[self.rows, self.cols]);
+            let strides = number_array(&[self.cols, 1]);
+            let array: *mut Object = msg_send![class!(MLMultiArray), alloc];
+            let array: *mut Object = msg_send![
+                array,
+                initWithDataPointer: input.as_ptr() as *mut c_void
+                shape: shape
+                dataType: ML_FLOAT32
+                strides: strides
+                deallocator: null_mut::<c_void>()
+                error: &mut error
+            ];
+            let array = StrongPtr::new(checked(array, error, "Failed to wrap Core ML input")?);
+
+            let value: *mut Object = msg_send![class!(MLFeatureValue), featureValueWithMultiArray: *array];
+            let input_name = ns_string(&self.input);
+            let features: *mut Object = msg_send![class!(NSDictionary), dictionaryWithObject: value forKey: *input_name];
+            let provider: *mut Object = msg_send![class!(MLDictionaryFeatureProvider), alloc];
+            let provider: *mut Object = msg_send![provider, initWithDictionary: features error: &mut error];
+            let provider = StrongPtr::new(checked(provider, error, "Failed to build Core ML features")?);
+
+            let prediction: *mut Object = msg_send![*self.model, predictionFromFeatures: *provider error: &mut error];
+            checked(prediction, error, "Core ML prediction failed")?;
+
+            let output_name = ns_string(&self.output);
+            let value: *mut Object = msg_send![prediction, featureValueForName: *output_name];
+            let output: *mut Object = if value.is_null() { null_mut() } else { msg_send![value, multiArrayValue] };
+            if output.is_null() {
+                anyhow::bail!("Core ML model has no multi-array output named '{}'", self.output);
+            }
+            let data_type: i64 = msg_send![output, dataType];
+            if data_type != ML_FLOAT32 {
+                anyhow::bail!("Core ML output '{}' is not float32", self.output);
+            }
+
+            let shape = usize_array(msg_send![output, shape]);
+            let strides = usize_array(msg_send![output, strides]);
+            if shape.len() != 2 || shape[0] != self.rows {
+                anyhow::bail!("Core ML output '{}' has shape {:?}, expected {} rows", self.output, shape, self.rows);
+            }
+
+            let data: *const f32 = msg_send![output, dataPointer];
+            let mut values = Vec::with_capacity(shape[0] * shape[1]);
+            for row in 0..shape[0] {
+                for col in 0..shape[1] {
+                    values.push(*data.add(row * strides[0] + col * strides[1]));
+                }
+            }
+            Ok(values)
+        })
+    }
+
+    /// Run any number of rows, zero-padding the last batch
+    pub fn predict_rows(&self, input: &[f32], num_rows: usize) -> Result<Vec<f32>> {
+        if input.len() != num_rows * self.cols {
+            anyhow::bail!("Core ML input has {} values, expected {}×{}", input.len(), num_rows, self.cols);
+        }
+
+        let mut output = Vec::new();
+        let mut batch = vec![0.0f32; self.rows * self.cols];
+        for chunk in input.chunks(self.rows * self.cols) {
+            batch[..chunk.len()].copy_from_slice(chunk);
+            batch[chunk.len()..].fill(0.0);
+
+            let result = self.predict(&batch)?;
+            let out_cols = result.len() / self.rows;
+            output.extend_from_slice(&result[..chunk.len() / self.cols * out_cols]);
+        }
+        Ok(output)
+    }
+}
+
+/// Owned `NSString`
+unsafe fn ns_string(value: &str) -> StrongPtr {
+    let string: *mut Object = msg_send![class!(NSString), alloc];
+    let string: *mut Object = msg_send![
+        string,
+        initWithBytes: value.as_ptr() as *const c_void
+        length: value.len() as u64
+        encoding: NS_UTF8
+    ];
+    StrongPtr::new(string)
+}
+
+/// Autoreleased `NSArray<NSNumber>`
+unsafe fn number_array(values: &[usize]) -> *mut Object {
+    let numbers: Vec<*mut Object> = values
+        .iter()
+        .map(|&value| msg_send![class!(NSNumber), numberWithInteger: value as i64])
+        .collect();
+    msg_send![class!(NSArray), arrayWithObjects: numbers.as_ptr() count: numbers.len() as u64]
+}
+
+unsafe fn usize_array(array: *mut Object) -> Vec<usize> {
+    let count: u64 = msg_send![array, count];
+    (0..count)
+        .map(|i| {
+            let number: *mut Object = msg_send![array, objectAtIndex: i];
+            let value: i64 = msg_send![number, integerValue];
+            value as usize
+        })
+        .collect()
+}
+
+/// `result`, or the `NSError` description if it is nil
+unsafe fn checked(result: *mut Object, error: *mut Object, context: &str) -> Result<*mut Object> {
+    if !result.is_null() {
+        return Ok(result);
+    }
+    let reason = if error.is_null() {
+        "unknown error".to_string()
+    } else {
+        let description: *mut Object = msg_send![error, localizedDescription];
+        let text: *const c_char = msg_send![description, UTF8String];
+        CStr::from_ptr(text).to_string_lossy().into_owned()
+    };
+    anyhow::bail!("{}: {}", context, reason)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_model() {
+        let result = CoreMlModel::load(Path::new("missing.mlpackage"), "x", "y", ComputeUnits::CpuOnly);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_compute_unit_names() {
+        assert_eq!(ComputeUnits::CpuAndNeuralEngine.name(), "ane");
+        assert_eq!(ComputeUnits::CpuAndGpu.name(), "gpu");
+    }
+}
@@ -1,10 +1,13 @@
 //! High-level kernel interfaces for bioinformatics operations
 //!
 //! This module provides Rust-friendly wrappers around Metal compute kernels.
+//! Inputs too large for the GPU working set are split into batches and the
+//! per-batch results reduced on the CPU (see [`batching`](crate::batching)).
 
-use crate::{GpuMetrics, MetalBackend};
+use crate::{batching, GpuMetrics, MetalBackend};
 use anyhow::Result;
 use asbb_core::SequenceRecord;
+use std::time::Instant;
 
 /// Result of base counting operation
 #[derive(Debug, Clone)]
@@ -42,6 +45,31 @@ impl MetalBackend {
     ///
     /// Aggregate counts across all sequences plus performance metrics.
     pub fn count_bases_gpu(&self, data: &[SequenceRecord]) -> Result<BaseCountsGpu> {
+        let (batches, metrics) = self.run_batched(data, batching::base_count_bytes, |batch| {
+            let counts = self.count_bases_batch(batch)?;
+            let metrics = counts.metrics.clone();
+            Ok((counts, metrics))
+        })?;
+
+        let mut total = BaseCountsGpu {
+            count_a: 0,
+            count_c: 0,
+            count_g: 0,
+            count_t: 0,
+            total_bases: 0,
+            metrics,
+        };
+        for counts in batches {
+            total.count_a += counts.count_a;
+            total.count_c += counts.count_c;
+            total.count_g += counts.count_g;
+            total.count_t += counts.count_t;
+            total.total_bases += counts.total_bases;
+        }
+        Ok(total)
+    }
+
+    fn count_bases_batch(&self, data: &[SequenceRecord]) -> Result<BaseCountsGpu> {
         if data.is_empty() {
             return Ok(BaseCountsGpu {
                 count_a: 0,
@@ -55,6 +83,9 @@ impl MetalBackend {
                     overhead_ms: 0.0,
                     num_sequences: 0,
                     throughput: 0.0,
+                    threadgroup_size: 0,
+                    num_batches: 0,
+                    reduce_ms: 0.0,
                 },
             });
         }
@@ -80,12 +111,14 @@ impl MetalBackend {
         let counts_buffer = self.create_empty_buffer(output_size);
 
         // Dispatch kernel
-        let metrics = self.dispatch_kernel(
+        let mut metrics = self.dispatch_kernel(
             "count_bases",
             &[&sequences_buffer, &offsets_buffer, &lengths_buffer, &counts_buffer],
             data.len(),
         )?;
 
+        let reduce_start = Instant::now();
+
         // Read results from GPU buffer (unified memory - direct access)
         let counts_ptr = counts_buffer.contents() as *const u32;
         let counts = unsafe {
@@ -107,6 +140,7 @@ impl MetalBackend {
         }
 
         let total_bases = total_a + total_c + total_g + total_t;
+        metrics.reduce_ms = reduce_start.elapsed().as_secs_f64() * 1000.0;
 
         Ok(BaseCountsGpu {
             count_a: total_a,
@@ -120,6 +154,15 @@ impl MetalBackend {
 
     /// Count GC bases using GPU
     pub fn count_gc_gpu(&self, data: &[SequenceRecord]) -> Result<(usize, usize, GpuMetrics)> {
+        let (batches, metrics) = self.run_batched(data, batching::per_sequence_value_bytes, |batch| {
+            let (gc, bases, metrics) = self.count_gc_batch(batch)?;
+            Ok(((gc, bases), metrics))
+        })?;
+        let (gc, bases) = batches.into_iter().fold((0, 0), |acc, b| (acc.0 + b.0, acc.1 + b.1));
+        Ok((gc, bases, metrics))
+    }
+
+    fn count_gc_batch(&self, data: &[SequenceRecord]) -> Result<(usize, usize, GpuMetrics)> {
         if data.is_empty() {
             return Ok((0, 0, GpuMetrics {
                 total_time_ms: 0.0,
@@ -127,6 +170,9 @@ impl MetalBackend {
                 overhead_ms: 0.0,
                 num_sequences: 0,
                 throughput: 0.0,
+                threadgroup_size: 0,
+                num_batches: 0,
+                reduce_ms: 0.0,
             }));
         }
 
@@ -152,12 +198,14 @@ impl MetalBackend {
         let gc_counts_buffer = self.create_empty_buffer(output_size);
 
         // Dispatch
-        let metrics = self.dispatch_kernel(
+        let mut metrics = self.dispatch_kernel(
             "count_gc",
             &[&sequences_buffer, &offsets_buffer, &lengths_buffer, &gc_counts_buffer],
             data.len(),
         )?;
 
+        let reduce_start = Instant::now();
+
         // Read results
         let counts_ptr = gc_counts_buffer.contents() as *const u32;
         let counts = unsafe {
@@ -165,12 +213,22 @@ impl MetalBackend {
         };
 
         let total_gc: usize = counts.iter().map(|&c| c as usize).sum();
+        metrics.reduce_ms = reduce_start.elapsed().as_secs_f64() * 1000.0;
 
         Ok((total_gc, total_bases, metrics))
     }
 
     /// Count AT bases using GPU
     pub fn count_at_gpu(&self, data: &[SequenceRecord]) -> Result<(usize, usize, GpuMetrics)> {
+        let (batches, metrics) = self.run_batched(data, batching::per_sequence_value_bytes, |batch| {
+            let (at, bases, metrics) = self.count_at_batch(batch)?;
+            Ok(((at, bases), metrics))
+        })?;
+        let (at, bases) = batches.into_iter().fold((0, 0), |acc, b| (acc.0 + b.0, acc.1 + b.1));
+        Ok((at, bases, metrics))
+    }
+
+    fn count_at_batch(&self, data: &[SequenceRecord]) -> Result<(usize, usize, GpuMetrics)> {
         if data.is_empty() {
             return Ok((0, 0, GpuMetrics {
                 total_time_ms: 0.0,
@@ -178,6 +236,9 @@ impl MetalBackend {
                 overhead_ms: 0.0,
                 num_sequences: 0,
                 throughput: 0.0,
+                threadgroup_size: 0,
+                num_batches: 0,
+                reduce_ms: 0.0,
             }));
         }
 
@@ -203,12 +264,14 @@ impl MetalBackend {
         let at_counts_buffer = self.create_empty_buffer(output_size);
 
         // Dispatch
-        let metrics = self.dispatch_kernel(
+        let mut metrics = self.dispatch_kernel(
             "count_at",
             &[&sequences_buffer, &offsets_buffer, &lengths_buffer, &at_counts_buffer],
             data.len(),
         )?;
 
+        let reduce_start = Instant::now();
+
         // Read results
         let counts_ptr = at_counts_buffer.contents() as *const u32;
         let counts = unsafe {
@@ -216,6 +279,7 @@ impl MetalBackend {
         };
 
         let total_at: usize = counts.iter().map(|&c| c as usize).sum();
+        metrics.reduce_ms = reduce_start.elapsed().as_secs_f64() * 1000.0;
 
         Ok((total_at, total_bases, metrics))
     }
@@ -225,6 +289,28 @@ impl MetalBackend {
     /// Computes min/max/sum quality scores across all sequences.
     /// Returns aggregate statistics and performance metrics.
     pub fn aggregate_quality_gpu(&self, data: &[SequenceRecord]) -> Result<(QualityStatsGpu, GpuMetrics)> {
+        let (batches, metrics) =
+            self.run_batched(data, batching::quality_bytes, |batch| self.aggregate_quality_batch(batch))?;
+
+        let mut stats = QualityStatsGpu {
+            min_quality: 255,
+            max_quality: 0,
+            total_quality: 0,
+            num_bases: 0,
+        };
+        for batch in batches {
+            if batch.num_bases == 0 {
+                continue;
+            }
+            stats.min_quality = stats.min_quality.min(batch.min_quality);
+            stats.max_quality = stats.max_quality.max(batch.max_quality);
+            stats.total_quality += batch.total_quality;
+            stats.num_bases += batch.num_bases;
+        }
+        Ok((stats, metrics))
+    }
+
+    fn aggregate_quality_batch(&self, data: &[SequenceRecord]) -> Result<(QualityStatsGpu, GpuMetrics)> {
         if data.is_empty() {
             return Ok((QualityStatsGpu {
                 min_quality: 255,
@@ -237,6 +323,9 @@ impl MetalBackend {
                 overhead_ms: 0.0,
                 num_sequences: 0,
                 throughput: 0.0,
+                threadgroup_size: 0,
+                num_batches: 0,
+                reduce_ms: 0.0,
             }));
         }
 
@@ -269,12 +358,14 @@ impl MetalBackend {
         let stats_buffer = self.create_empty_buffer(output_size);
 
         // Dispatch kernel
-        let metrics = self.dispatch_kernel(
+        let mut metrics = self.dispatch_kernel(
             "aggregate_quality",
             &[&quality_buffer, &offsets_buffer, &lengths_buffer, &stats_buffer],
             data.len(),
         )?;
 
+        let reduce_start = Instant::now();
+
         // Read results from GPU buffer
         let stats_ptr = stats_buffer.contents() as *const u32;
         let stats_data = unsafe {
@@ -303,6 +394,7 @@ impl MetalBackend {
             global_max = global_max.max(max_q);
             global_sum += sum_q;
         }
+        metrics.reduce_ms = reduce_start.elapsed().as_secs_f64() * 1000.0;
 
         Ok((QualityStatsGpu {
             min_quality: global_min,
@@ -316,6 +408,13 @@ impl MetalBackend {
     ///
     /// Returns the reverse complemented sequences and performance metrics.
     pub fn reverse_complement_gpu(&self, data: &[SequenceRecord]) -> Result<(Vec<SequenceRecord>, GpuMetrics)> {
+        let (batches, metrics) = self.run_batched(data, batching::reverse_complement_bytes, |batch| {
+            self.reverse_complement_batch(batch)
+        })?;
+        Ok((batches.into_iter().flatten().collect(), metrics))
+    }
+
+    fn reverse_complement_batch(&self, data: &[SequenceRecord]) -> Result<(Vec<SequenceRecord>, GpuMetrics)> {
         if data.is_empty() {
             return Ok((Vec::new(), GpuMetrics {
                 total_time_ms: 0.0,
@@ -323,6 +422,9 @@ impl MetalBackend {
                 overhead_ms: 0.0,
                 num_sequences: 0,
                 throughput: 0.0,
+                threadgroup_size: 0,
+                num_batches: 0,
+                reduce_ms: 0.0,
             }));
         }
 
@@ -380,6 +482,13 @@ impl MetalBackend {
 
     /// Calculate complexity scores using GPU
     pub fn calculate_complexity_gpu(&self, data: &[SequenceRecord]) -> Result<(Vec<f64>, GpuMetrics)> {
+        let (batches, metrics) = self.run_batched(data, batching::per_sequence_value_bytes, |batch| {
+            self.calculate_complexity_batch(batch)
+        })?;
+        Ok((batches.into_iter().flatten().collect(), metrics))
+    }
+
+    fn calculate_complexity_batch(&self, data: &[SequenceRecord]) -> Result<(Vec<f64>, GpuMetrics)> {
         if data.is_empty() {
             return Ok((Vec::new(), GpuMetrics {
                 total_time_ms: 0.0,
@@ -387,6 +496,9 @@ impl MetalBackend {
                 overhead_ms: 0.0,
                 num_sequences: 0,
                 throughput: 0.0,
+                threadgroup_size: 0,
+                num_batches: 0,
+                reduce_ms: 0.0,
             }));
         }
 
@@ -429,4 +541,100 @@ impl MetalBackend {
 
         Ok((results, metrics))
     }
+
+    /// Gram matrix (all pairwise row dot products) using the `gram_matrix` kernel
+    ///
+    /// `features` is a row-major `rows × cols` matrix; returns the row-major
+    /// `rows × rows` result. See [`gram_matrix_mps`](Self::gram_matrix_mps)
+    /// for the Metal Performance Shaders equivalent.
+    pub fn gram_matrix_gpu(&self, features: &[f32], rows: usize, cols: usize) -> Result<(Vec<f32>, GpuMetrics)> {
+        if features.len() != rows * cols {
+            anyhow::bail!("Feature matrix has {} values, expected {}×{}", features.len(), rows, cols);
+        }
+        if rows == 0 {
+            return Ok((Vec::new(), GpuMetrics {
+                total_time_ms: 0.0,
+                kernel_time_ms: 0.0,
+                overhead_ms: 0.0,
+                num_sequences: 0,
+                throughput: 0.0,
+                threadgroup_size: 0,
+                num_batches: 0,
+                reduce_ms: 0.0,
+            }));
+        }
+
+        let features_buffer = self.create_buffer(features);
+        let gram_buffer = self.create_empty_buffer((rows * rows * std::mem::size_of::<f32>()) as u64);
+        let dims_buffer = self.create_buffer(&[rows as u32, cols as u32]);
+
+        let mut metrics = self.dispatch_kernel(
+            "gram_matrix",
+            &[&features_buffer, &gram_buffer, &dims_buffer],
+            rows * rows,
+        )?;
+        metrics.num_sequences = rows;
+        metrics.throughput = rows as f64 / (metrics.total_time_ms / 1000.0);
+
+        let gram_ptr = gram_buffer.contents() as *const f32;
+        let gram = unsafe {
+            std::slice::from_raw_parts(gram_ptr, rows * rows)
+        };
+
+        Ok((gram.to_vec(), metrics))
+    }
+
+    /// All-vs-all Hamming distances using the `hamming_all_pairs` kernel
+    ///
+    /// `planes` holds `num_sequences` equal-length sequences, each packed as
+    /// three planes of `words` u64 (low base bit, high base bit, non-ACGT
+    /// mask; 64 bases per word). Returns the row-major `n × n` matrix.
+    pub fn hamming_all_pairs_gpu(
+        &self,
+        planes: &[u64],
+        num_sequences: usize,
+        words: usize,
+    ) -> Result<(Vec<u32>, GpuMetrics)> {
+        if planes.len() != num_sequences * 3 * words {
+            anyhow::bail!(
+                "Packed planes have {} words, expected {}×3×{}",
+                planes.len(),
+                num_sequences,
+                words
+            );
+        }
+        if num_sequences == 0 || words == 0 {
+            // Nothing to compare (empty sequences are all distance 0)
+            return Ok((vec![0; num_sequences * num_sequences], GpuMetrics {
+                total_time_ms: 0.0,
+                kernel_time_ms: 0.0,
+                overhead_ms: 0.0,
+                num_sequences,
+                throughput: 0.0,
+                threadgroup_size: 0,
+                num_batches: 0,
+                reduce_ms: 0.0,
+            }));
+        }
+
+        let planes_buffer = self.create_buffer(planes);
+        let distances_buffer =
+            self.create_empty_buffer((num_sequences * num_sequences * std::mem::size_of::<u32>()) as u64);
+        let dims_buffer = self.create_buffer(&[num_sequences as u32, words as u32]);
+
+        let mut metrics = self.dispatch_kernel(
+            "hamming_all_pairs",
+            &[&planes_buffer, &distances_buffer, &dims_buffer],
+            num_sequences * num_sequences,
+        )?;
+        metrics.num_sequences = num_sequences;
+        metrics.throughput = num_sequences as f64 / (metrics.total_time_ms / 1000.0);
+
+        let distances_ptr = distances_buffer.contents() as *const u32;
+        let distances = unsafe {
+            std::slice::from_raw_parts(distances_ptr, num_sequences * num_sequences)
+        };
+
+        Ok((distances.to_vec(), metrics))
+    }
 }
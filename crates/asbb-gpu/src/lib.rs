@@ -22,19 +22,42 @@
 //!
 //! let backend = MetalBackend::new()?;
 //! let results = backend.count_bases(&sequences)?;
+//!
+//! // Kernels from the built-in, precompiled and registered libraries
+//! for kernel in backend.kernels() {
+//!     println!("{} ({})", kernel.name, kernel.library);
+//! }
 //! ```
+//!
+//! See [`library`] for adding kernels without editing this crate.
+//! Core ML models (Neural Engine, GPU or CPU) load through [`coreml`].
 
 use anyhow::{Context, Result};
 use metal::*;
 use std::time::Instant;
 
+pub mod batching;
+pub mod coreml;
 pub mod kernels;
+pub mod library;
+pub mod mps;
+pub mod reduction;
+pub mod tuning;
+
+pub use library::{register_metallib, register_shader_source, KernelInfo, ShaderSource};
+use library::LoadedLibrary;
+pub use tuning::ThreadgroupPolicy;
+use tuning::{TuningKey, TUNING_RUNS};
 
 /// Metal GPU backend for bioinformatics operations
 pub struct MetalBackend {
     device: Device,
     command_queue: CommandQueue,
-    library: Library,
+    /// Built-in library first, then added ones
+    libraries: Vec<LoadedLibrary>,
+    threadgroup_policy: ThreadgroupPolicy,
+    /// Per-batch buffer budget in bytes (see [`batching`])
+    batch_budget: u64,
 }
 
 /// Performance metrics for GPU operations
@@ -54,6 +77,19 @@ pub struct GpuMetrics {
 
     /// Throughput (sequences/second)
     pub throughput: f64,
+
+    /// Threads per threadgroup used for the dispatch (0 if nothing was
+    /// dispatched); see [`tuning`]
+    pub threadgroup_size: usize,
+
+    /// Dispatches the input was split into to fit the GPU working set; see
+    /// [`batching`]
+    pub num_batches: usize,
+
+    /// Time reducing per-sequence results to the final answer: the CPU sum
+    /// after readback, or the second pass of an on-GPU reduction (see
+    /// [`reduction`]); 0 for operations without a reduction
+    pub reduce_ms: f64,
 }
 
 impl MetalBackend {
@@ -61,6 +97,12 @@ impl MetalBackend {
     ///
     /// This initializes the default Metal device and creates a command queue.
     /// On Apple Silicon, this gives access to the unified memory GPU.
+    ///
+    /// Loads the built-in kernels plus any registered libraries (see
+    /// [`library`]). The threadgroup policy comes from
+    /// [`tuning::THREADGROUP_ENV`] (default: pipeline maximum), the batch
+    /// budget from [`batching::BATCH_BUDGET_ENV`] (default: half of
+    /// `recommendedMaxWorkingSetSize`).
     pub fn new() -> Result<Self> {
         // Get the default Metal device (Apple Silicon GPU)
         let device = Device::system_default()
@@ -69,28 +111,85 @@ impl MetalBackend {
         // Create command queue for submitting work
         let command_queue = device.new_command_queue();
 
-        // Compile Metal shaders
-        let library = Self::compile_shaders(&device)?;
+        // Compile (or load precompiled) Metal shaders
+        let libraries = library::load_all(&device)?;
+
+        let batch_budget = match batching::budget_from_env()? {
+            Some(budget) => budget,
+            None => batching::default_budget(device.recommended_max_working_set_size()),
+        };
 
         Ok(Self {
             device,
             command_queue,
-            library,
+            libraries,
+            threadgroup_policy: ThreadgroupPolicy::from_env()?,
+            batch_budget,
         })
     }
 
-    /// Compile Metal shader library from source
-    fn compile_shaders(device: &Device) -> Result<Library> {
-        // Include the shader source at compile time
-        let shader_source = include_str!("shaders/operations.metal");
+    /// How dispatches size their threadgroups
+    pub fn threadgroup_policy(&self) -> ThreadgroupPolicy {
+        self.threadgroup_policy
+    }
 
-        // Compile options
-        let options = CompileOptions::new();
+    pub fn set_threadgroup_policy(&mut self, policy: ThreadgroupPolicy) {
+        self.threadgroup_policy = policy;
+    }
 
-        // Compile the library
-        device
-            .new_library_with_source(shader_source, &options)
-            .map_err(|e| anyhow::anyhow!("Failed to compile Metal shaders: {}", e))
+    /// Maximum estimated buffer bytes per dispatch
+    pub fn batch_budget(&self) -> u64 {
+        self.batch_budget
+    }
+
+    pub fn set_batch_budget(&mut self, bytes: u64) {
+        self.batch_budget = bytes.max(1);
+    }
+
+    /// Add a shader library to this backend, returning the kernels it provides
+    pub fn add_library(&mut self, source: &ShaderSource) -> Result<Vec<String>> {
+        let loaded = source.load(&self.device)?;
+        library::check_unique(&self.libraries, &loaded)?;
+        let kernels = loaded.kernels.clone();
+        self.libraries.push(loaded);
+        Ok(kernels)
+    }
+
+    /// Kernels available for dispatch, by library
+    pub fn kernels(&self) -> Vec<KernelInfo> {
+        self.libraries
+            .iter()
+            .flat_map(|library| {
+                library.kernels.iter().map(|name| KernelInfo {
+                    name: name.clone(),
+                    library: library.label.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Whether a kernel with this name is available
+    pub fn has_kernel(&self, name: &str) -> bool {
+        self.libraries.iter().any(|library| library.kernels.iter().any(|k| k == name))
+    }
+
+    /// Look up a kernel function in whichever library provides it
+    fn function(&self, kernel_name: &str) -> Result<Function> {
+        let library = self
+            .libraries
+            .iter()
+            .find(|library| library.kernels.iter().any(|k| k == kernel_name))
+            .with_context(|| {
+                format!(
+                    "Kernel function '{}' not found (available: {})",
+                    kernel_name,
+                    self.kernels().iter().map(|k| k.name.as_str()).collect::<Vec<_>>().join(", ")
+                )
+            })?;
+        library
+            .library
+            .get_function(kernel_name, None)
+            .map_err(|e| anyhow::anyhow!("Kernel function '{}' not found: {}", kernel_name, e))
     }
 
     /// Get the Metal device
@@ -103,9 +202,9 @@ impl MetalBackend {
         &self.command_queue
     }
 
-    /// Get the shader library
+    /// Get the built-in shader library
     pub fn library(&self) -> &Library {
-        &self.library
+        &self.libraries[0].library
     }
 
     /// Create a GPU buffer from data
@@ -132,19 +231,43 @@ impl MetalBackend {
     /// Dispatch a compute kernel
     ///
     /// This is a low-level method for executing Metal compute shaders.
+    /// Autotuning probes (if any) run first and are excluded from the
+    /// returned timings.
     pub fn dispatch_kernel(
         &self,
         kernel_name: &str,
         buffers: &[&Buffer],
         grid_size: usize,
+    ) -> Result<GpuMetrics> {
+        self.dispatch(kernel_name, buffers, grid_size, None)
+    }
+
+    /// Dispatch with a fixed threadgroup size, bypassing the policy
+    ///
+    /// For kernels that rely on a particular threadgroup shape, such as a
+    /// single-threadgroup reduction. The size is still capped at the
+    /// pipeline maximum and the grid size.
+    pub fn dispatch_kernel_with_threadgroup(
+        &self,
+        kernel_name: &str,
+        buffers: &[&Buffer],
+        grid_size: usize,
+        threadgroup_size: usize,
+    ) -> Result<GpuMetrics> {
+        self.dispatch(kernel_name, buffers, grid_size, Some(threadgroup_size))
+    }
+
+    fn dispatch(
+        &self,
+        kernel_name: &str,
+        buffers: &[&Buffer],
+        grid_size: usize,
+        fixed_threadgroup: Option<usize>,
     ) -> Result<GpuMetrics> {
         let start_total = Instant::now();
 
         // Get the kernel function
-        let function = self
-            .library
-            .get_function(kernel_name, None)
-            .map_err(|e| anyhow::anyhow!("Kernel function '{}' not found: {}", kernel_name, e))?;
+        let function = self.function(kernel_name)?;
 
         // Create compute pipeline
         let pipeline = self
@@ -152,6 +275,78 @@ impl MetalBackend {
             .new_compute_pipeline_state_with_function(&function)
             .map_err(|e| anyhow::anyhow!("Failed to create pipeline: {}", e))?;
 
+        // Calculate threadgroup size
+        let tuning_start = Instant::now();
+        let threadgroup_size = match fixed_threadgroup {
+            Some(size) => size
+                .min(pipeline.max_total_threads_per_threadgroup() as usize)
+                .min(grid_size)
+                .max(1),
+            None => self.threadgroup_size(kernel_name, &pipeline, buffers, grid_size),
+        };
+        let tuning_time = tuning_start.elapsed();
+
+        let (overhead_ms, kernel_time_ms) =
+            self.run_pipeline(&pipeline, buffers, grid_size, threadgroup_size);
+
+        let total_time_ms = (start_total.elapsed() - tuning_time).as_secs_f64() * 1000.0;
+
+        Ok(GpuMetrics {
+            total_time_ms,
+            kernel_time_ms,
+            overhead_ms,
+            num_sequences: grid_size,
+            throughput: grid_size as f64 / (total_time_ms / 1000.0),
+            threadgroup_size,
+            num_batches: 1,
+            reduce_ms: 0.0,
+        })
+    }
+
+    /// Threads per threadgroup for this dispatch under the current policy
+    fn threadgroup_size(
+        &self,
+        kernel_name: &str,
+        pipeline: &ComputePipelineState,
+        buffers: &[&Buffer],
+        grid_size: usize,
+    ) -> usize {
+        let pipeline_max = pipeline.max_total_threads_per_threadgroup() as usize;
+        match self.threadgroup_policy {
+            ThreadgroupPolicy::Max => pipeline_max.min(grid_size).max(1),
+            ThreadgroupPolicy::Fixed(size) => size.min(pipeline_max).min(grid_size).max(1),
+            ThreadgroupPolicy::Autotune => {
+                let key = TuningKey::new(kernel_name, self.device.registry_id(), grid_size);
+                if let Some(size) = tuning::cached_size(&key) {
+                    return size.min(pipeline_max).min(grid_size).max(1);
+                }
+
+                let candidates = tuning::candidate_sizes(pipeline_max, grid_size);
+                let timings: Vec<Vec<f64>> = candidates
+                    .iter()
+                    .map(|&size| {
+                        (0..TUNING_RUNS)
+                            .map(|_| self.run_pipeline(pipeline, buffers, grid_size, size).1)
+                            .collect()
+                    })
+                    .collect();
+                let best = tuning::fastest(&timings)
+                    .map(|i| candidates[i])
+                    .unwrap_or_else(|| pipeline_max.min(grid_size).max(1));
+                tuning::cache_size(key, best);
+                best
+            }
+        }
+    }
+
+    /// Encode, commit and wait for one dispatch; returns (overhead, kernel) ms
+    fn run_pipeline(
+        &self,
+        pipeline: &ComputePipelineState,
+        buffers: &[&Buffer],
+        grid_size: usize,
+        threadgroup_size: usize,
+    ) -> (f64, f64) {
         // Create command buffer
         let command_buffer = self.command_queue.new_command_buffer();
 
@@ -159,7 +354,7 @@ impl MetalBackend {
         let encoder = command_buffer.new_compute_command_encoder();
 
         // Set the pipeline
-        encoder.set_compute_pipeline_state(&pipeline);
+        encoder.set_compute_pipeline_state(pipeline);
 
         // Bind buffers
         for (i, buffer) in buffers.iter().enumerate() {
@@ -168,9 +363,8 @@ impl MetalBackend {
 
         let overhead_start = Instant::now();
 
-        // Calculate threadgroup size
         let threadgroup_size = MTLSize {
-            width: pipeline.max_total_threads_per_threadgroup().min(grid_size as u64),
+            width: threadgroup_size as u64,
             height: 1,
             depth: 1,
         };
@@ -196,15 +390,7 @@ impl MetalBackend {
         command_buffer.wait_until_completed();
         let kernel_time_ms = kernel_start.elapsed().as_secs_f64() * 1000.0;
 
-        let total_time_ms = start_total.elapsed().as_secs_f64() * 1000.0;
-
-        Ok(GpuMetrics {
-            total_time_ms,
-            kernel_time_ms,
-            overhead_ms,
-            num_sequences: grid_size.width as usize,
-            throughput: grid_size.width as f64 / (total_time_ms / 1000.0),
-        })
+        (overhead_ms, kernel_time_ms)
     }
 }
 
@@ -218,6 +404,98 @@ mod tests {
         assert!(backend.is_ok(), "Failed to create Metal backend - Apple Silicon required");
     }
 
+    #[test]
+    fn test_builtin_kernels_listed() {
+        if let Ok(backend) = MetalBackend::new() {
+            assert!(!backend.kernels().is_empty());
+            for name in ["count_bases", "count_gc", "reverse_complement"] {
+                assert!(backend.has_kernel(name), "missing built-in kernel {}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_custom_source() {
+        if let Ok(mut backend) = MetalBackend::new() {
+            let source = ShaderSource::Source {
+                label: "custom".to_string(),
+                source: r#"
+                    #include <metal_stdlib>
+                    using namespace metal;
+                    kernel void double_values(device uint* values [[buffer(0)]],
+                                              uint gid [[thread_position_in_grid]]) {
+                        values[gid] *= 2;
+                    }
+                "#
+                .to_string(),
+            };
+            assert_eq!(backend.add_library(&source).unwrap(), ["double_values"]);
+
+            let buffer = backend.create_buffer(&[1u32, 2, 3]);
+            backend.dispatch_kernel("double_values", &[&buffer], 3).unwrap();
+            let values = unsafe { std::slice::from_raw_parts(buffer.contents() as *const u32, 3) };
+            assert_eq!(values, [2, 4, 6]);
+
+            // Kernel names must stay unique
+            assert!(backend.add_library(&source).is_err());
+        }
+    }
+
+    #[test]
+    fn test_autotune_records_size() {
+        if let Ok(mut backend) = MetalBackend::new() {
+            backend.set_threadgroup_policy(ThreadgroupPolicy::Autotune);
+            let data: Vec<asbb_core::SequenceRecord> = (0..5_000)
+                .map(|i| asbb_core::SequenceRecord::fasta(format!("seq_{}", i), b"ACGT".repeat(25)))
+                .collect();
+
+            let (gc, _, metrics) = backend.count_gc_gpu(&data).unwrap();
+            assert_eq!(gc, 5_000 * 50);
+            assert!(tuning::CANDIDATE_SIZES.contains(&metrics.threadgroup_size));
+
+            // Cached: the next dispatch reuses the choice
+            let (_, _, again) = backend.count_gc_gpu(&data).unwrap();
+            assert_eq!(again.threadgroup_size, metrics.threadgroup_size);
+        }
+    }
+
+    #[test]
+    fn test_batched_matches_single_dispatch() {
+        if let Ok(mut backend) = MetalBackend::new() {
+            let data: Vec<asbb_core::SequenceRecord> = (0..1_000)
+                .map(|i| asbb_core::SequenceRecord::fasta(format!("seq_{}", i), b"ACGTN".repeat(30)))
+                .collect();
+            let single = backend.count_bases_gpu(&data).unwrap();
+            assert_eq!(single.metrics.num_batches, 1);
+
+            // 174 bytes per record (150 bases + offsets + 4 counts): 10 per batch
+            backend.set_batch_budget(1_740);
+            let batched = backend.count_bases_gpu(&data).unwrap();
+            assert_eq!(batched.metrics.num_batches, 100);
+            assert_eq!(batched.metrics.num_sequences, 1_000);
+            assert_eq!(
+                (batched.count_a, batched.count_c, batched.count_g, batched.count_t),
+                (single.count_a, single.count_c, single.count_g, single.count_t)
+            );
+
+            let (revcomp, metrics) = backend.reverse_complement_gpu(&data).unwrap();
+            assert!(metrics.num_batches > 1);
+            assert_eq!(revcomp.len(), data.len());
+            assert_eq!(revcomp[999].id, "seq_999_revcomp");
+        }
+    }
+
+    #[test]
+    fn test_hamming_all_pairs() {
+        if let Ok(backend) = MetalBackend::new() {
+            // 3 sequences × 1 word per plane (lo, hi, non-ACGT)
+            let planes = [0b0000, 0b0000, 0, 0b0101, 0b0000, 0, 0b0000, 0b0011, 0b1000];
+            let (distances, metrics) = backend.hamming_all_pairs_gpu(&planes, 3, 1).unwrap();
+            assert_eq!(distances, [0, 2, 3, 2, 0, 4, 3, 4, 0]);
+            assert_eq!(metrics.num_sequences, 3);
+        }
+    }
+
     #[test]
     fn test_device_info() {
         if let Ok(backend) = MetalBackend::new() {
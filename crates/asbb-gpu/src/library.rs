@@ -0,0 +1,172 @@
+//! Shader libraries: built-in, precompiled `.metallib`, and registered sources
+//!
+//! Every [`MetalBackend`](crate::MetalBackend) starts from the built-in
+//! kernels in `shaders/operations.metal`, compiled at startup. Two extension
+//! points let GPU operations live outside this crate:
+//!
+//! - **Precompiled libraries**: a `.metallib` built offline skips runtime
+//!   compilation. Set [`METALLIB_ENV`] to replace the built-in source with a
+//!   precompiled build of it:
+//!
+//!   ```bash
+//!   xcrun -sdk macosx metal -c crates/asbb-gpu/src/shaders/operations.metal -o ops.air
+//!   xcrun -sdk macosx metallib ops.air -o asbb.metallib
+//!   ASBB_METALLIB=asbb.metallib cargo run --release ...
+//!   ```
+//!
+//! - **Registered sources**: [`register_shader_source`] and
+//!   [`register_metallib`] add libraries to every backend created
+//!   afterwards, including the ones operations create internally; a single
+//!   backend can also be extended with
+//!   [`MetalBackend::add_library`](crate::MetalBackend::add_library).
+//!
+//! Kernel names must be unique across a backend's libraries, so dispatching
+//! by name stays unambiguous.
+
+use anyhow::{Context, Result};
+use metal::{CompileOptions, Device, Library, MTLFunctionType};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+/// Label of the library compiled from `shaders/operations.metal`
+pub const BUILTIN_LIBRARY: &str = "built-in";
+
+/// Environment variable naming a precompiled `.metallib` of the built-in kernels
+pub const METALLIB_ENV: &str = "ASBB_METALLIB";
+
+/// Where a shader library comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShaderSource {
+    /// Metal Shading Language source, compiled at load time
+    Source { label: String, source: String },
+
+    /// Precompiled library file
+    Metallib(PathBuf),
+}
+
+impl ShaderSource {
+    /// The built-in kernels (precompiled if [`METALLIB_ENV`] is set)
+    pub fn builtin() -> Self {
+        match std::env::var_os(METALLIB_ENV) {
+            Some(path) => ShaderSource::Metallib(PathBuf::from(path)),
+            None => ShaderSource::Source {
+                label: BUILTIN_LIBRARY.to_string(),
+                source: include_str!("shaders/operations.metal").to_string(),
+            },
+        }
+    }
+
+    /// Name shown in kernel listings (source label or file path)
+    pub fn label(&self) -> String {
+        match self {
+            ShaderSource::Source { label, .. } => label.clone(),
+            ShaderSource::Metallib(path) => path.display().to_string(),
+        }
+    }
+
+    /// Compile or load the library on `device`
+    pub fn load(&self, device: &Device) -> Result<LoadedLibrary> {
+        let library = match self {
+            ShaderSource::Source { label, source } => device
+                .new_library_with_source(source, &CompileOptions::new())
+                .map_err(|e| anyhow::anyhow!("Failed to compile Metal shaders '{}': {}", label, e))?,
+            ShaderSource::Metallib(path) => device
+                .new_library_with_file(path)
+                .map_err(|e| anyhow::anyhow!("Failed to load {}: {}", path.display(), e))?,
+        };
+
+        let mut kernels: Vec<String> = library
+            .function_names()
+            .into_iter()
+            .filter(|name| {
+                library
+                    .get_function(name, None)
+                    .is_ok_and(|function| function.function_type() == MTLFunctionType::Kernel)
+            })
+            .collect();
+        kernels.sort();
+
+        Ok(LoadedLibrary {
+            label: self.label(),
+            library,
+            kernels,
+        })
+    }
+}
+
+/// A library loaded on a device, with its kernel names
+pub struct LoadedLibrary {
+    pub label: String,
+    pub library: Library,
+    pub kernels: Vec<String>,
+}
+
+/// A kernel available to a backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KernelInfo {
+    /// Function name, as passed to `dispatch_kernel`
+    pub name: String,
+
+    /// Label of the library providing it
+    pub library: String,
+}
+
+static REGISTERED: OnceLock<Mutex<Vec<ShaderSource>>> = OnceLock::new();
+
+fn registered() -> std::sync::MutexGuard<'static, Vec<ShaderSource>> {
+    REGISTERED
+        .get_or_init(|| Mutex::new(Vec::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Add shader source to every backend created from now on
+pub fn register_shader_source(label: impl Into<String>, source: impl Into<String>) {
+    registered().push(ShaderSource::Source {
+        label: label.into(),
+        source: source.into(),
+    });
+}
+
+/// Add a precompiled `.metallib` to every backend created from now on
+pub fn register_metallib(path: impl Into<PathBuf>) -> Result<()> {
+    let path = path.into();
+    if !path.is_file() {
+        anyhow::bail!("Metal library not found: {}", path.display());
+    }
+    registered().push(ShaderSource::Metallib(path));
+    Ok(())
+}
+
+/// Libraries registered so far, in registration order
+pub fn registered_sources() -> Vec<ShaderSource> {
+    registered().clone()
+}
+
+/// Fail if `library` defines a kernel already provided by `existing`
+pub(crate) fn check_unique(existing: &[LoadedLibrary], library: &LoadedLibrary) -> Result<()> {
+    for kernel in &library.kernels {
+        if let Some(other) = existing.iter().find(|l| l.kernels.contains(kernel)) {
+            anyhow::bail!(
+                "Kernel '{}' from '{}' is already defined by '{}'",
+                kernel,
+                library.label,
+                other.label
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Load the built-in library followed by the registered ones
+pub(crate) fn load_all(device: &Device) -> Result<Vec<LoadedLibrary>> {
+    let mut libraries = Vec::new();
+    for source in std::iter::once(ShaderSource::builtin()).chain(registered_sources()) {
+        let library = source
+            .load(device)
+            .with_context(|| format!("Loading shader library '{}'", source.label()))?;
+        check_unique(&libraries, &library)?;
+        libraries.push(library);
+    }
+    Ok(libraries)
+}
@@ -0,0 +1,172 @@
+//! Metal Performance Shaders matrix multiplication
+//!
+//! `MPSMatrixMultiplication` is Apple's tuned GPU GEMM. It is the vendor
+//! baseline for the hand-written `gram_matrix` kernel: if MPS wins, the gap
+//! is kernel engineering (tiling, threadgroup memory), not the GPU itself.
+//!
+//! The `metal` crate does not bind MPS matrices, so this module messages the
+//! Objective-C classes directly.
+
+use anyhow::Result;
+use metal::foreign_types::{ForeignType, ForeignTypeRef};
+use objc::rc::{autoreleasepool, StrongPtr};
+use objc::runtime::{Object, BOOL, NO, YES};
+use objc::{class, msg_send, sel, sel_impl};
+use std::time::Instant;
+
+use crate::{GpuMetrics, MetalBackend};
+
+/// `MPSDataTypeFloat32` (`MPSDataTypeFloatBit | 32`)
+const MPS_DATA_TYPE_FLOAT32: u32 = 0x1000_0000 | 32;
+
+#[link(name = "MetalPerformanceShaders", kind = "framework")]
+extern "C" {
+    fn MPSSupportsMTLDevice(device: *mut Object) -> BOOL;
+}
+
+/// Row-major f32 `MPSMatrix` over a buffer
+fn matrix(buffer: &metal::BufferRef, rows: usize, cols: usize) -> StrongPtr {
+    unsafe {
+        let descriptor: *mut Object = msg_send![
+            class!(MPSMatrixDescriptor),
+            matrixDescriptorWithRows: rows as u64
+            columns: cols as u64
+            rowBytes: (cols * std::mem::size_of::<f32>()) as u64
+            dataType: MPS_DATA_TYPE_FLOAT32
+        ];
+        let matrix: *mut Object = msg_send![class!(MPSMatrix), alloc];
+        let matrix: *mut Object = msg_send![
+            matrix,
+            initWithBuffer: buffer.as_ptr() as *mut Object
+            descriptor: descriptor
+        ];
+        StrongPtr::new(matrix)
+    }
+}
+
+impl MetalBackend {
+    /// Whether Metal Performance Shaders supports this device
+    pub fn supports_mps(&self) -> bool {
+        unsafe { MPSSupportsMTLDevice(self.device().as_ptr() as *mut Object) == YES }
+    }
+
+    /// Gram matrix (all pairwise row dot products) using `MPSMatrixMultiplication`
+    ///
+    /// Same contract as [`gram_matrix_gpu`](Self::gram_matrix_gpu): row-major
+    /// `rows × cols` in, row-major `rows × rows` out. The metrics report no
+    /// threadgroup size, as MPS chooses its own.
+    pub fn gram_matrix_mps(&self, features: &[f32], rows: usize, cols: usize) -> Result<(Vec<f32>, GpuMetrics)> {
+        if features.len() != rows * cols {
+            anyhow::bail!("Feature matrix has {} values, expected {}×{}", features.len(), rows, cols);
+        }
+        if !self.supports_mps() {
+            anyhow::bail!("Metal Performance Shaders not supported on {}", self.device().name());
+        }
+        if rows == 0 {
+            return Ok((
+                Vec::new(),
+                GpuMetrics {
+                    total_time_ms: 0.0,
+                    kernel_time_ms: 0.0,
+                    overhead_ms: 0.0,
+                    num_sequences: 0,
+                    throughput: 0.0,
+                    threadgroup_size: 0,
+                    num_batches: 0,
+                    reduce_ms: 0.0,
+                },
+            ));
+        }
+
+        let start_total = Instant::now();
+
+        let features_buffer = self.create_buffer(features);
+        let gram_buffer = self.create_empty_buffer((rows * rows * std::mem::size_of::<f32>()) as u64);
+
+        let (overhead_ms, kernel_time_ms) = autoreleasepool(|| unsafe {
+            let overhead_start = Instant::now();
+
+            let left = matrix(&features_buffer, rows, cols);
+            let result = matrix(&gram_buffer, rows, rows);
+
+            // gram = features × featuresᵀ
+            let multiplication: *mut Object = msg_send![class!(MPSMatrixMultiplication), alloc];
+            let multiplication: *mut Object = msg_send![
+                multiplication,
+                initWithDevice: self.device().as_ptr() as *mut Object
+                transposeLeft: NO
+                transposeRight: YES
+                resultRows: rows as u64
+                resultColumns: rows as u64
+                interiorColumns: cols as u64
+                alpha: 1.0f64
+                beta: 0.0f64
+            ];
+            let multiplication = StrongPtr::new(multiplication);
+
+            let command_buffer = self.command_queue().new_command_buffer();
+            let _: () = msg_send![
+                *multiplication,
+                encodeToCommandBuffer: command_buffer.as_ptr() as *mut Object
+                leftMatrix: *left
+                rightMatrix: *left
+                resultMatrix: *result
+            ];
+            let overhead_ms = overhead_start.elapsed().as_secs_f64() * 1000.0;
+
+            let kernel_start = Instant::now();
+            command_buffer.commit();
+            command_buffer.wait_until_completed();
+            (overhead_ms, kernel_start.elapsed().as_secs_f64() * 1000.0)
+        });
+
+        let total_time_ms = start_total.elapsed().as_secs_f64() * 1000.0;
+
+        let gram_ptr = gram_buffer.contents() as *const f32;
+        let gram = unsafe { std::slice::from_raw_parts(gram_ptr, rows * rows) };
+
+        Ok((
+            gram.to_vec(),
+            GpuMetrics {
+                total_time_ms,
+                kernel_time_ms,
+                overhead_ms,
+                num_sequences: rows,
+                throughput: rows as f64 / (total_time_ms / 1000.0),
+                threadgroup_size: 0,
+                num_batches: 1,
+                reduce_ms: 0.0,
+            },
+        ))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mps_matches_compute_kernel() {
+        if let Ok(backend) = MetalBackend::new() {
+            if !backend.supports_mps() {
+                return;
+            }
+            // 3 rows × 5 cols
+            let features: Vec<f32> = (0..15).map(|v| v as f32 * 0.5).collect();
+            let (mps, metrics) = backend.gram_matrix_mps(&features, 3, 5).unwrap();
+            let (kernel, _) = backend.gram_matrix_gpu(&features, 3, 5).unwrap();
+
+            assert_eq!(metrics.num_sequences, 3);
+            assert_eq!(mps.len(), 9);
+            for (a, b) in mps.iter().zip(&kernel) {
+                assert!((a - b).abs() < 1e-3, "{} vs {}", a, b);
+            }
+            // Row 0 · row 1 = 0.25 × (0·5 + 1·6 + 2·7 + 3·8 + 4·9)
+            assert!((mps[1] - 20.0).abs() < 1e-3);
+        }
+    }
+}
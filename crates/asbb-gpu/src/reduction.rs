@@ -0,0 +1,177 @@
+//! On-GPU reduction for the counting kernels
+//!
+//! The standard wrappers ([`count_bases_gpu`](MetalBackend::count_bases_gpu),
+//! [`count_gc_gpu`](MetalBackend::count_gc_gpu)) write one result per
+//! sequence, which the CPU reads back and sums: at Huge scale, 160 MB of
+//! readback and a 40M-element sum for base counting. The reduced variants
+//! keep the reduction on the GPU:
+//! 1. A `*_partials` kernel sums each threadgroup's sequences (SIMD sum,
+//!    then threadgroup memory) into one partial
+//! 2. `reduce_partials` folds the partials into 64-bit totals in a single
+//!    threadgroup of [`REDUCE_THREADS`], leaving 32 bytes to read back
+//!
+//! Both paths return the same types. [`GpuMetrics::reduce_ms`] times the CPU
+//! sum in the standard path and the second pass plus readback here, so
+//! `total_time_ms + reduce_ms` compares the two end to end (see the
+//! `asbb-pilot-gpu-reduction` pilot).
+
+use anyhow::Result;
+use asbb_core::SequenceRecord;
+use std::time::Instant;
+
+use crate::kernels::BaseCountsGpu;
+use crate::{batching, GpuMetrics, MetalBackend};
+
+/// Threads in the single `reduce_partials` threadgroup (matches the
+/// kernel's threadgroup memory)
+pub const REDUCE_THREADS: usize = 256;
+
+impl MetalBackend {
+    /// Count bases with the reduction done on the GPU
+    ///
+    /// Same result as [`count_bases_gpu`](Self::count_bases_gpu).
+    pub fn count_bases_gpu_reduced(&self, data: &[SequenceRecord]) -> Result<BaseCountsGpu> {
+        let (batches, metrics) = self.run_batched(data, batching::base_count_bytes, |batch| {
+            self.reduce_on_gpu("count_bases_partials", batch)
+        })?;
+
+        let mut totals = [0u64; 4];
+        for batch in batches {
+            for (total, count) in totals.iter_mut().zip(batch) {
+                *total += count;
+            }
+        }
+
+        Ok(BaseCountsGpu {
+            count_a: totals[0] as usize,
+            count_c: totals[1] as usize,
+            count_g: totals[2] as usize,
+            count_t: totals[3] as usize,
+            total_bases: totals.iter().sum::<u64>() as usize,
+            metrics,
+        })
+    }
+
+    /// Count GC bases with the reduction done on the GPU
+    ///
+    /// Same result as [`count_gc_gpu`](Self::count_gc_gpu).
+    pub fn count_gc_gpu_reduced(&self, data: &[SequenceRecord]) -> Result<(usize, usize, GpuMetrics)> {
+        let (batches, metrics) = self.run_batched(data, batching::per_sequence_value_bytes, |batch| {
+            self.reduce_on_gpu("count_gc_partials", batch)
+        })?;
+
+        let total_gc: u64 = batches.iter().map(|totals| totals[0]).sum();
+        let total_bases = data.iter().map(|record| record.sequence.len()).sum();
+        Ok((total_gc as usize, total_bases, metrics))
+    }
+
+    /// Run a `*_partials` kernel on one batch, then `reduce_partials`
+    fn reduce_on_gpu(&self, kernel_name: &str, data: &[SequenceRecord]) -> Result<([u64; 4], GpuMetrics)> {
+        // Flatten sequences
+        let mut flat_sequences = Vec::new();
+        let mut seq_offsets = Vec::new();
+        let mut seq_lengths = Vec::new();
+
+        for record in data {
+            seq_offsets.push(flat_sequences.len() as u32);
+            seq_lengths.push(record.sequence.len() as u32);
+            flat_sequences.extend_from_slice(&record.sequence);
+        }
+
+        let sequences_buffer = self.create_buffer(&flat_sequences);
+        let offsets_buffer = self.create_buffer(&seq_offsets);
+        let lengths_buffer = self.create_buffer(&seq_lengths);
+
+        // One uint4 per threadgroup; sized for the smallest possible
+        // threadgroup, only the used prefix is written
+        let partials_buffer = self.create_empty_buffer((data.len() * 4 * std::mem::size_of::<u32>()) as u64);
+
+        // Pass 1: per-threadgroup partials (any threadgroup size policy)
+        let mut metrics = self.dispatch_kernel(
+            kernel_name,
+            &[&sequences_buffer, &offsets_buffer, &lengths_buffer, &partials_buffer],
+            data.len(),
+        )?;
+        let num_partials = data.len().div_ceil(metrics.threadgroup_size);
+
+        // Pass 2: one threadgroup folds the partials
+        let reduce_start = Instant::now();
+        let num_partials_buffer = self.create_buffer(&[num_partials as u32]);
+        let totals_buffer = self.create_empty_buffer((4 * std::mem::size_of::<u64>()) as u64);
+        let reduce = self.dispatch_kernel_with_threadgroup(
+            "reduce_partials",
+            &[&partials_buffer, &num_partials_buffer, &totals_buffer],
+            REDUCE_THREADS,
+            REDUCE_THREADS,
+        )?;
+        if reduce.threadgroup_size != REDUCE_THREADS {
+            anyhow::bail!(
+                "reduce_partials needs one threadgroup of {} threads (device allows {})",
+                REDUCE_THREADS,
+                reduce.threadgroup_size
+            );
+        }
+
+        let totals_ptr = totals_buffer.contents() as *const u64;
+        let totals = unsafe { std::slice::from_raw_parts(totals_ptr, 4) };
+        let totals = [totals[0], totals[1], totals[2], totals[3]];
+        metrics.reduce_ms = reduce_start.elapsed().as_secs_f64() * 1000.0;
+
+        Ok((totals, metrics))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ThreadgroupPolicy;
+
+    fn data(n: usize) -> Vec<SequenceRecord> {
+        (0..n)
+            .map(|i| SequenceRecord::fasta(format!("seq_{}", i), b"ACGTNGGC".repeat(i % 7 + 1)))
+            .collect()
+    }
+
+    #[test]
+    fn test_reduced_matches_cpu_reduce() {
+        if let Ok(mut backend) = MetalBackend::new() {
+            // 1001 sequences: the last threadgroup is partial for every size
+            let data = data(1_001);
+            for policy in [ThreadgroupPolicy::Max, ThreadgroupPolicy::Fixed(32)] {
+                backend.set_threadgroup_policy(policy);
+
+                let standard = backend.count_bases_gpu(&data).unwrap();
+                let reduced = backend.count_bases_gpu_reduced(&data).unwrap();
+                assert_eq!(
+                    (reduced.count_a, reduced.count_c, reduced.count_g, reduced.count_t),
+                    (standard.count_a, standard.count_c, standard.count_g, standard.count_t),
+                    "{:?}",
+                    policy
+                );
+                assert_eq!(reduced.total_bases, standard.total_bases);
+
+                let (gc, bases, _) = backend.count_gc_gpu(&data).unwrap();
+                let (reduced_gc, reduced_bases, _) = backend.count_gc_gpu_reduced(&data).unwrap();
+                assert_eq!((reduced_gc, reduced_bases), (gc, bases));
+            }
+        }
+    }
+
+    #[test]
+    fn test_reduced_across_batches() {
+        if let Ok(mut backend) = MetalBackend::new() {
+            let data = data(500);
+            let expected = backend.count_bases_gpu_reduced(&data).unwrap();
+
+            backend.set_batch_budget(2_000);
+            let batched = backend.count_bases_gpu_reduced(&data).unwrap();
+            assert!(batched.metrics.num_batches > 1);
+            assert_eq!(batched.total_bases, expected.total_bases);
+            assert_eq!(batched.count_g, expected.count_g);
+        }
+    }
+}
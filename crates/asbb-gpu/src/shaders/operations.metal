@@ -314,3 +314,227 @@ kernel void calculate_complexity(
     // Store as scaled integer (multiply by 1000 for precision)
     complexity[gid] = uint(score * 1000.0);
 }
+
+/// Gram matrix kernel - dot products of all pairs of feature rows
+///
+/// Pairwise comparisons such as k-mer composition distance reduce to
+/// gram[i][j] = dot(features[i], features[j]). One thread per output element.
+///
+/// @param features Row-major feature matrix [rows × cols]
+/// @param gram Output buffer [rows × rows]
+/// @param dims (rows, cols)
+/// @param gid Thread ID
+kernel void gram_matrix(
+    device const float* features [[buffer(0)]],
+    device float* gram [[buffer(1)]],
+    constant uint2& dims [[buffer(2)]],
+    uint gid [[thread_position_in_grid]]
+) {
+    uint rows = dims.x;
+    uint cols = dims.y;
+    uint i = gid / rows;
+    uint j = gid % rows;
+
+    device const float* a = features + i * cols;
+    device const float* b = features + j * cols;
+
+    float sum = 0.0;
+    for (uint k = 0; k < cols; k++) {
+        sum += a[k] * b[k];
+    }
+
+    gram[gid] = sum;
+}
+
+/// All-vs-all Hamming distance on bit-plane packed sequences
+///
+/// Each sequence is three planes of `words` 64-bit words (low base bit, high
+/// base bit, non-ACGT mask), 64 bases per word. A position mismatches if any
+/// plane differs, so each word pair costs three XORs, two ORs and a popcount.
+/// One thread per output element.
+///
+/// @param planes Packed sequences [n × 3 × words]
+/// @param distances Output buffer [n × n]
+/// @param dims (n, words)
+/// @param gid Thread ID
+kernel void hamming_all_pairs(
+    device const ulong* planes [[buffer(0)]],
+    device uint* distances [[buffer(1)]],
+    constant uint2& dims [[buffer(2)]],
+    uint gid [[thread_position_in_grid]]
+) {
+    uint n = dims.x;
+    uint words = dims.y;
+    uint i = gid / n;
+    uint j = gid % n;
+
+    device const ulong* a = planes + i * 3 * words;
+    device const ulong* b = planes + j * 3 * words;
+
+    uint mismatches = 0;
+    for (uint w = 0; w < words; w++) {
+        ulong diff = (a[w] ^ b[w])
+                   | (a[words + w] ^ b[words + w])
+                   | (a[2 * words + w] ^ b[2 * words + w]);
+        mismatches += popcount(diff);
+    }
+
+    distances[gid] = mismatches;
+}
+
+// ============================================================================
+// On-GPU reduction
+// ============================================================================
+//
+// The per-sequence kernels above leave one result per sequence for the CPU
+// to read back and sum. The *_partials kernels instead reduce within each
+// threadgroup (SIMD sum, then threadgroup memory) and write one partial per
+// threadgroup; reduce_partials then folds the partials into 64-bit totals,
+// so the CPU reads back 32 bytes regardless of input size.
+
+/// Sum a uint4 across the threadgroup (up to 1024 threads)
+///
+/// @param simd_totals Threadgroup scratch [32], one slot per SIMD group
+/// @return The threadgroup total (valid in thread 0 only)
+static uint4 threadgroup_sum(
+    uint4 value,
+    threadgroup uint4* simd_totals,
+    uint tid,
+    uint lane,
+    uint simd_group
+) {
+    // Zero every slot: a partial last threadgroup has fewer SIMD groups
+    if (tid == 0) {
+        for (uint i = 0; i < 32; i++) {
+            simd_totals[i] = uint4(0);
+        }
+    }
+    threadgroup_barrier(mem_flags::mem_threadgroup);
+
+    uint4 simd_total = simd_sum(value);
+    if (lane == 0) {
+        simd_totals[simd_group] = simd_total;
+    }
+    threadgroup_barrier(mem_flags::mem_threadgroup);
+
+    uint4 total = uint4(0);
+    if (tid == 0) {
+        for (uint i = 0; i < 32; i++) {
+            total += simd_totals[i];
+        }
+    }
+    return total;
+}
+
+/// Base counting with per-threadgroup partials [A, C, G, T]
+///
+/// @param partials Output buffer [num_threadgroups]
+kernel void count_bases_partials(
+    device const uchar* sequences [[buffer(0)]],
+    device const uint* seq_offsets [[buffer(1)]],
+    device const uint* seq_lengths [[buffer(2)]],
+    device uint4* partials [[buffer(3)]],
+    uint gid [[thread_position_in_grid]],
+    uint group [[threadgroup_position_in_grid]],
+    uint tid [[thread_index_in_threadgroup]],
+    uint lane [[thread_index_in_simdgroup]],
+    uint simd_group [[simdgroup_index_in_threadgroup]]
+) {
+    threadgroup uint4 simd_totals[32];
+
+    uint offset = seq_offsets[gid];
+    uint length = seq_lengths[gid];
+
+    uint4 counts = uint4(0);
+    for (uint i = 0; i < length; i++) {
+        uchar base = sequences[offset + i];
+
+        if (base == 'A' || base == 'a') {
+            counts.x++;
+        } else if (base == 'C' || base == 'c') {
+            counts.y++;
+        } else if (base == 'G' || base == 'g') {
+            counts.z++;
+        } else if (base == 'T' || base == 't') {
+            counts.w++;
+        }
+    }
+
+    uint4 total = threadgroup_sum(counts, simd_totals, tid, lane, simd_group);
+    if (tid == 0) {
+        partials[group] = total;
+    }
+}
+
+/// GC counting with per-threadgroup partials [GC, 0, 0, 0]
+///
+/// @param partials Output buffer [num_threadgroups]
+kernel void count_gc_partials(
+    device const uchar* sequences [[buffer(0)]],
+    device const uint* seq_offsets [[buffer(1)]],
+    device const uint* seq_lengths [[buffer(2)]],
+    device uint4* partials [[buffer(3)]],
+    uint gid [[thread_position_in_grid]],
+    uint group [[threadgroup_position_in_grid]],
+    uint tid [[thread_index_in_threadgroup]],
+    uint lane [[thread_index_in_simdgroup]],
+    uint simd_group [[simdgroup_index_in_threadgroup]]
+) {
+    threadgroup uint4 simd_totals[32];
+
+    uint offset = seq_offsets[gid];
+    uint length = seq_lengths[gid];
+
+    uint gc_count = 0;
+    for (uint i = 0; i < length; i++) {
+        uchar base = sequences[offset + i];
+
+        if (base == 'G' || base == 'g' || base == 'C' || base == 'c') {
+            gc_count++;
+        }
+    }
+
+    uint4 total = threadgroup_sum(uint4(gc_count, 0, 0, 0), simd_totals, tid, lane, simd_group);
+    if (tid == 0) {
+        partials[group] = total;
+    }
+}
+
+/// Fold partials into 64-bit totals - dispatch as ONE threadgroup of 256
+///
+/// Each thread strides over the partials, then a tree reduction in
+/// threadgroup memory combines the threads.
+///
+/// @param partials Per-threadgroup partials from a *_partials kernel
+/// @param num_partials Number of partials
+/// @param totals Output buffer [4]
+kernel void reduce_partials(
+    device const uint4* partials [[buffer(0)]],
+    constant uint& num_partials [[buffer(1)]],
+    device ulong* totals [[buffer(2)]],
+    uint tid [[thread_index_in_threadgroup]],
+    uint threads [[threads_per_threadgroup]]
+) {
+    threadgroup ulong4 thread_totals[256];
+
+    ulong4 sum = ulong4(0);
+    for (uint i = tid; i < num_partials; i += threads) {
+        sum += ulong4(partials[i]);
+    }
+    thread_totals[tid] = sum;
+    threadgroup_barrier(mem_flags::mem_threadgroup);
+
+    for (uint stride = threads / 2; stride > 0; stride /= 2) {
+        if (tid < stride) {
+            thread_totals[tid] += thread_totals[tid + stride];
+        }
+        threadgroup_barrier(mem_flags::mem_threadgroup);
+    }
+
+    if (tid == 0) {
+        totals[0] = thread_totals[0].x;
+        totals[1] = thread_totals[0].y;
+        totals[2] = thread_totals[0].z;
+        totals[3] = thread_totals[0].w;
+    }
+}
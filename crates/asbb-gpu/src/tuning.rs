@@ -0,0 +1,190 @@
+//! Threadgroup-size selection and autotuning
+//!
+//! By default `dispatch_kernel` uses the pipeline's maximum threads per
+//! threadgroup, which is not always fastest: per-sequence kernels with
+//! divergent loops often run better in smaller groups. With
+//! [`ThreadgroupPolicy::Autotune`] the first dispatch of a kernel probes
+//! [`CANDIDATE_SIZES`] on the real buffers and keeps the fastest.
+//!
+//! Choices are cached per (kernel, device, dataset-size decade) for the
+//! whole process, so operations that create a backend per call tune once.
+//! Every dispatch records the size it used in
+//! [`GpuMetrics::threadgroup_size`](crate::GpuMetrics::threadgroup_size).
+//!
+//! Probing runs the kernel several times, so autotuned kernels must be
+//! idempotent (write their outputs rather than accumulate into them), as
+//! all built-in kernels are.
+//!
+//! The policy is read from [`THREADGROUP_ENV`] when a backend is created:
+//! `max` (default), `auto`, or a fixed size such as `256`.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Threadgroup sizes probed by the autotuner
+pub const CANDIDATE_SIZES: &[usize] = &[32, 64, 128, 256, 512, 1024];
+
+/// Timed dispatches per candidate (the median is compared)
+pub const TUNING_RUNS: usize = 3;
+
+/// Environment variable selecting the default policy
+pub const THREADGROUP_ENV: &str = "ASBB_THREADGROUP";
+
+/// How `dispatch_kernel` sizes threadgroups
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThreadgroupPolicy {
+    /// Pipeline maximum (capped at the grid size)
+    #[default]
+    Max,
+
+    /// Fixed size (capped at the pipeline maximum and grid size)
+    Fixed(usize),
+
+    /// Probe [`CANDIDATE_SIZES`] on first use and cache the fastest
+    Autotune,
+}
+
+impl ThreadgroupPolicy {
+    /// Parse `max`, `auto`, or a size
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "max" => Ok(ThreadgroupPolicy::Max),
+            "auto" | "autotune" => Ok(ThreadgroupPolicy::Autotune),
+            size => match size.parse::<usize>() {
+                Ok(size) if size > 0 => Ok(ThreadgroupPolicy::Fixed(size)),
+                _ => anyhow::bail!("Invalid threadgroup policy '{}' (max, auto, or a size)", s),
+            },
+        }
+    }
+
+    /// Policy from [`THREADGROUP_ENV`], or the default
+    pub fn from_env() -> Result<Self> {
+        match std::env::var(THREADGROUP_ENV) {
+            Ok(value) => Self::parse(&value),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+}
+
+/// Autotuning cache key
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TuningKey {
+    pub kernel: String,
+
+    /// `MTLDevice.registryID`
+    pub device: u64,
+
+    /// `floor(log10(grid size))`: 1K and 9K records share a choice, 1M does not
+    pub size_decade: u32,
+}
+
+impl TuningKey {
+    pub fn new(kernel: &str, device: u64, grid_size: usize) -> Self {
+        Self {
+            kernel: kernel.to_string(),
+            device,
+            size_decade: grid_size.max(1).ilog10(),
+        }
+    }
+}
+
+static TUNED: OnceLock<Mutex<HashMap<TuningKey, usize>>> = OnceLock::new();
+
+fn tuned() -> std::sync::MutexGuard<'static, HashMap<TuningKey, usize>> {
+    TUNED
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Cached autotuning choice
+pub fn cached_size(key: &TuningKey) -> Option<usize> {
+    tuned().get(key).copied()
+}
+
+pub(crate) fn cache_size(key: TuningKey, size: usize) {
+    tuned().insert(key, size);
+}
+
+/// Autotuning choices made so far in this process
+pub fn tuned_sizes() -> Vec<(TuningKey, usize)> {
+    let mut sizes: Vec<_> = tuned().iter().map(|(k, &v)| (k.clone(), v)).collect();
+    sizes.sort_by(|a, b| (&a.0.kernel, a.0.size_decade).cmp(&(&b.0.kernel, b.0.size_decade)));
+    sizes
+}
+
+/// Drop cached choices (e.g. between experiments that compare policies)
+pub fn clear_tuning_cache() {
+    tuned().clear();
+}
+
+/// Candidate sizes usable for a pipeline and grid
+///
+/// Sizes above the pipeline maximum are dropped, and sizes beyond the grid
+/// collapse to the grid size (a tiny grid is a single threadgroup).
+pub fn candidate_sizes(pipeline_max: usize, grid_size: usize) -> Vec<usize> {
+    let limit = pipeline_max.min(grid_size).max(1);
+    let mut sizes: Vec<usize> = CANDIDATE_SIZES.iter().map(|&size| size.min(limit)).collect();
+    sizes.dedup();
+    sizes
+}
+
+/// Index of the fastest candidate by median time
+pub fn fastest(timings: &[Vec<f64>]) -> Option<usize> {
+    timings
+        .iter()
+        .enumerate()
+        .filter(|(_, runs)| !runs.is_empty())
+        .map(|(i, runs)| {
+            let mut runs = runs.clone();
+            runs.sort_by(f64::total_cmp);
+            (i, runs[runs.len() / 2])
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_policy() {
+        assert_eq!(ThreadgroupPolicy::parse("max").unwrap(), ThreadgroupPolicy::Max);
+        assert_eq!(ThreadgroupPolicy::parse("AUTO").unwrap(), ThreadgroupPolicy::Autotune);
+        assert_eq!(ThreadgroupPolicy::parse("256").unwrap(), ThreadgroupPolicy::Fixed(256));
+        assert!(ThreadgroupPolicy::parse("0").is_err());
+        assert!(ThreadgroupPolicy::parse("fast").is_err());
+    }
+
+    #[test]
+    fn test_candidate_sizes() {
+        assert_eq!(candidate_sizes(1024, 1_000_000), CANDIDATE_SIZES);
+        assert_eq!(candidate_sizes(512, 1_000_000), [32, 64, 128, 256, 512]);
+        assert_eq!(candidate_sizes(1024, 100), [32, 64, 100]);
+        assert_eq!(candidate_sizes(1024, 10), [10]);
+    }
+
+    #[test]
+    fn test_fastest_by_median() {
+        let timings = vec![
+            vec![5.0, 5.1, 5.2],
+            vec![1.0, 9.0, 4.0], // median 4.0
+            vec![4.5, 4.4, 4.6],
+        ];
+        assert_eq!(fastest(&timings), Some(1));
+        assert_eq!(fastest(&[]), None);
+    }
+
+    #[test]
+    fn test_key_buckets_by_decade() {
+        assert_eq!(TuningKey::new("k", 1, 1_000), TuningKey::new("k", 1, 9_999));
+        assert_ne!(TuningKey::new("k", 1, 1_000), TuningKey::new("k", 1, 10_000));
+        assert_ne!(TuningKey::new("k", 1, 1_000), TuningKey::new("k", 2, 1_000));
+    }
+}
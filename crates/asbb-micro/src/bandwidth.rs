@@ -0,0 +1,119 @@
+//! Streaming read and copy bandwidth
+//!
+//! Each thread sums its share of a buffer much larger than the last-level
+//! cache (8 independent accumulators, so loads rather than the add chain
+//! bound the loop) and the best of several passes is reported, as in
+//! STREAM. Copy bandwidth counts bytes read plus bytes written.
+
+use crate::CoreType;
+use anyhow::Result;
+use std::sync::Barrier;
+use std::time::{Duration, Instant};
+
+/// Sum a buffer with independent accumulators
+fn read_sum(words: &[u64]) -> u64 {
+    let mut acc = [0u64; 8];
+    let chunks = words.chunks_exact(8);
+    let remainder = chunks.remainder();
+    for chunk in chunks {
+        for (a, &w) in acc.iter_mut().zip(chunk) {
+            *a = a.wrapping_add(w);
+        }
+    }
+    let tail = remainder.iter().fold(0u64, |s, &w| s.wrapping_add(w));
+    acc.iter().fold(tail, |s, &a| s.wrapping_add(a))
+}
+
+/// A buffer of `bytes` with every page touched
+fn filled_buffer(bytes: usize) -> Vec<u64> {
+    (0..(bytes / 8).max(8) as u64).collect()
+}
+
+/// Read bandwidth (GB/s) of `threads` threads of `core_type` streaming
+/// disjoint slices of a `bytes`-byte buffer, best of `passes`
+///
+/// Every thread runs `passes` timed passes between barriers; a pass's
+/// bandwidth is the whole buffer over the slowest thread's time.
+pub fn stream_read_gbps(bytes: usize, threads: usize, core_type: CoreType, passes: usize) -> Result<f64> {
+    anyhow::ensure!(threads > 0 && passes > 0, "Threads and passes must be positive");
+
+    let buffer = filled_buffer(bytes);
+    // Tiny buffers can give fewer slices than threads
+    let slices: Vec<&[u64]> = buffer.chunks(buffer.len().div_ceil(threads)).collect();
+    let barrier = Barrier::new(slices.len());
+
+    let per_thread: Vec<Vec<Duration>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = slices
+            .iter()
+            .map(|&slice| {
+                let barrier = &barrier;
+                scope.spawn(move || {
+                    core_type.apply_to_current_thread();
+                    let mut times = Vec::with_capacity(passes);
+                    for _ in 0..passes {
+                        barrier.wait();
+                        let start = Instant::now();
+                        std::hint::black_box(read_sum(std::hint::black_box(slice)));
+                        times.push(start.elapsed());
+                    }
+                    times
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().expect("bandwidth thread panicked")).collect()
+    });
+
+    let slowest = (0..passes)
+        .map(|pass| per_thread.iter().map(|times| times[pass]).max().unwrap_or_default())
+        .min()
+        .unwrap_or_default();
+
+    Ok(gbps(buffer.len() * 8, slowest))
+}
+
+/// Single-threaded copy bandwidth (GB/s, read + write bytes), best of `passes`
+pub fn copy_gbps(bytes: usize, passes: usize) -> Result<f64> {
+    anyhow::ensure!(passes > 0, "Passes must be positive");
+
+    let source = filled_buffer(bytes);
+    let mut destination = vec![0u64; source.len()];
+    let best = (0..passes)
+        .map(|_| {
+            let start = Instant::now();
+            destination.copy_from_slice(std::hint::black_box(&source));
+            std::hint::black_box(&mut destination);
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default();
+
+    Ok(gbps(2 * source.len() * 8, best))
+}
+
+/// Bytes over time in GB/s (10⁹ bytes per second)
+pub(crate) fn gbps(bytes: usize, time: Duration) -> f64 {
+    bytes as f64 / time.as_secs_f64().max(f64::MIN_POSITIVE) / 1e9
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_sum() {
+        let words: Vec<u64> = (0..1003).collect();
+        assert_eq!(read_sum(&words), 1002 * 1003 / 2);
+        assert_eq!(read_sum(&[u64::MAX, 2]), 1);
+    }
+
+    #[test]
+    fn test_bandwidths_positive() {
+        assert!(stream_read_gbps(1 << 20, 2, CoreType::Any, 2).unwrap() > 0.0);
+        assert!(copy_gbps(1 << 20, 2).unwrap() > 0.0);
+        assert!(stream_read_gbps(1 << 20, 0, CoreType::Any, 2).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+//! Random-access load latency by working-set size
+//!
+//! A pointer chase over one random cycle through the working set: every
+//! load's address depends on the previous load, so the time per load is the
+//! latency of whichever level (L1, L2, SLC, DRAM) holds the working set.
+//! Slots are one cache line (128 bytes on Apple Silicon) apart so each hop
+//! touches a new line.
+
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// Cache line size of Apple Silicon P- and E-cores
+pub const CACHE_LINE_BYTES: usize = 128;
+
+const WORDS_PER_LINE: usize = CACHE_LINE_BYTES / std::mem::size_of::<usize>();
+
+/// Load latency at one working-set size
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatencyPoint {
+    pub working_set_bytes: usize,
+    pub latency_ns: f64,
+}
+
+/// Working sets from `min_bytes` to `max_bytes`, ×2 per step
+pub fn working_sets(min_bytes: usize, max_bytes: usize) -> Vec<usize> {
+    std::iter::successors(Some(min_bytes.max(CACHE_LINE_BYTES)), |&bytes| Some(bytes * 2))
+        .take_while(|&bytes| bytes <= max_bytes)
+        .collect()
+}
+
+/// SplitMix64 step (deterministic shuffle without a `rand` dependency)
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Buffer whose line `i` holds the word index of the next line in one
+/// random cycle over all lines (Sattolo's algorithm)
+fn chase_buffer(working_set_bytes: usize, seed: u64) -> Vec<usize> {
+    let lines = (working_set_bytes / CACHE_LINE_BYTES).max(2);
+    let mut order: Vec<usize> = (0..lines).collect();
+    let mut state = seed;
+    for i in (1..lines).rev() {
+        let j = (splitmix64(&mut state) % i as u64) as usize;
+        order.swap(i, j);
+    }
+
+    let mut buffer = vec![0usize; lines * WORDS_PER_LINE];
+    for (line, &next) in order.iter().enumerate() {
+        buffer[line * WORDS_PER_LINE] = next * WORDS_PER_LINE;
+    }
+    buffer
+}
+
+/// Average latency (ns) of `loads` dependent loads over a `working_set_bytes` set
+pub fn load_latency_ns(working_set_bytes: usize, loads: usize) -> f64 {
+    let buffer = chase_buffer(working_set_bytes, working_set_bytes as u64);
+
+    // One lap to warm the caches and TLB
+    let lines = buffer.len() / WORDS_PER_LINE;
+    let mut index = 0;
+    for _ in 0..lines {
+        index = buffer[index];
+    }
+
+    let start = Instant::now();
+    for _ in 0..loads {
+        index = buffer[index];
+    }
+    let elapsed = start.elapsed();
+    std::hint::black_box(index);
+
+    elapsed.as_secs_f64() * 1e9 / loads.max(1) as f64
+}
+
+/// Latency curve over `working_sets`
+pub fn latency_curve(working_sets: &[usize], loads: usize) -> Vec<LatencyPoint> {
+    working_sets
+        .iter()
+        .map(|&working_set_bytes| LatencyPoint {
+            working_set_bytes,
+            latency_ns: load_latency_ns(working_set_bytes, loads),
+        })
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chase_is_one_cycle() {
+        let buffer = chase_buffer(64 * CACHE_LINE_BYTES, 7);
+        let lines = buffer.len() / WORDS_PER_LINE;
+        assert_eq!(lines, 64);
+
+        let mut seen = vec![false; lines];
+        let mut index = 0;
+        for _ in 0..lines {
+            let line = index / WORDS_PER_LINE;
+            assert!(!seen[line], "line {} visited twice", line);
+            seen[line] = true;
+            index = buffer[index];
+        }
+        assert_eq!(index, 0);
+    }
+
+    #[test]
+    fn test_latency_curve() {
+        assert_eq!(working_sets(16 << 10, 128 << 10), vec![16 << 10, 32 << 10, 64 << 10, 128 << 10]);
+
+        let curve = latency_curve(&working_sets(16 << 10, 32 << 10), 10_000);
+        assert_eq!(curve.len(), 2);
+        assert!(curve.iter().all(|point| point.latency_ns > 0.0));
+    }
+}
@@ -0,0 +1,389 @@
+//! Memory hierarchy microbenchmarks for Apple Silicon
+//!
+//! Operation throughput on two chips differs for reasons that are not about
+//! the operation: memory bandwidth, cache latency, P- vs E-core mix. This
+//! crate measures those directly, once per machine, and stores the result as
+//! a [`MachineProfile`] that analysis uses to normalize operation results
+//! across chips (see [`MachineProfile::bandwidth_factor`]):
+//!
+//! - [`bandwidth`]: streaming read bandwidth per core type (one P-core, one
+//!   E-core, all cores) and single-core copy bandwidth
+//! - [`latency`]: random-access load latency from L1-sized to DRAM-sized
+//!   working sets (pointer chase)
+//! - [`shared_buffer`]: CPU ↔ GPU copy bandwidth through Metal shared
+//!   buffers (macOS only)
+//! - [`storage`]: file read throughput of buffered, memory-mapped and
+//!   asynchronous (POSIX AIO) reads at several queue depths, when a file on
+//!   the SSD is given
+//!
+//! Core types are selected with QoS classes on macOS (user-interactive
+//! threads run on P-cores, background threads on E-cores); elsewhere every
+//! core type runs unconstrained.
+//!
+//! ```rust,ignore
+//! let profile = asbb_micro::run(&MicroOptions::default())?;
+//! profile.save("machine_profile.json")?;
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub mod bandwidth;
+pub mod latency;
+pub mod shared_buffer;
+pub mod storage;
+
+use latency::LatencyPoint;
+use shared_buffer::SharedBufferBandwidth;
+use storage::StorageProfile;
+
+// ============================================================================
+// Core Types
+// ============================================================================
+
+/// Which cores a measurement thread should run on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoreType {
+    /// No preference (scheduler decides)
+    Any,
+    /// Performance cores (QoS user-interactive)
+    Performance,
+    /// Efficiency cores (QoS background)
+    Efficiency,
+}
+
+impl CoreType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CoreType::Any => "any",
+            CoreType::Performance => "performance",
+            CoreType::Efficiency => "efficiency",
+        }
+    }
+
+    /// Hint the scheduler to run the calling thread on this core type
+    #[cfg(target_os = "macos")]
+    pub fn apply_to_current_thread(&self) {
+        // QOS_CLASS_USER_INTERACTIVE, QOS_CLASS_BACKGROUND, QOS_CLASS_DEFAULT
+        let qos_class = match self {
+            CoreType::Performance => 0x21,
+            CoreType::Efficiency => 0x09,
+            CoreType::Any => 0x15,
+        };
+
+        extern "C" {
+            fn pthread_set_qos_class_self_np(qos_class: u32, relative_priority: i32) -> i32;
+        }
+        unsafe {
+            let _ = pthread_set_qos_class_self_np(qos_class, 0);
+        }
+    }
+
+    /// Hint the scheduler to run the calling thread on this core type
+    #[cfg(not(target_os = "macos"))]
+    pub fn apply_to_current_thread(&self) {}
+}
+
+// ============================================================================
+// Machine Profile
+// ============================================================================
+
+/// Streaming read bandwidth of a group of threads
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadBandwidth {
+    pub core_type: CoreType,
+    pub threads: usize,
+    pub gbps: f64,
+}
+
+/// Measured memory characteristics of one machine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MachineProfile {
+    /// Chip name (e.g. "M4 Pro"), or the platform off Apple Silicon
+    pub machine: String,
+    /// When the profile was measured (RFC 3339)
+    pub measured_at: String,
+    /// Buffer size of the bandwidth measurements
+    pub buffer_bytes: usize,
+    /// Streaming read bandwidth: one thread per core type, then all cores
+    pub read_bandwidth: Vec<ReadBandwidth>,
+    /// Single-thread copy bandwidth (read + write bytes)
+    pub copy_gbps: f64,
+    /// Load latency by working-set size, ascending
+    pub latency: Vec<LatencyPoint>,
+    /// CPU ↔ GPU shared-buffer bandwidth (`None` without Metal)
+    pub shared_buffer: Option<SharedBufferBandwidth>,
+    /// File read throughput by read path (`None` unless a file was given)
+    #[serde(default)]
+    pub storage: Option<StorageProfile>,
+}
+
+impl MachineProfile {
+    /// Read bandwidth measured with `threads` threads of `core_type`
+    pub fn read_gbps(&self, core_type: CoreType, threads: usize) -> Option<f64> {
+        self.read_bandwidth
+            .iter()
+            .find(|r| r.core_type == core_type && r.threads == threads)
+            .map(|r| r.gbps)
+    }
+
+    /// Single-thread read bandwidth (P-core if measured)
+    pub fn single_core_read_gbps(&self) -> Option<f64> {
+        self.read_gbps(CoreType::Performance, 1).or_else(|| self.read_gbps(CoreType::Any, 1))
+    }
+
+    /// Read bandwidth of the most threads measured
+    pub fn all_core_read_gbps(&self) -> Option<f64> {
+        self.read_bandwidth.iter().max_by_key(|r| r.threads).map(|r| r.gbps)
+    }
+
+    /// Latency of the largest working set (DRAM)
+    pub fn memory_latency_ns(&self) -> Option<f64> {
+        self.latency.last().map(|p| p.latency_ns)
+    }
+
+    /// How much faster this machine streams memory than `reference` with
+    /// `threads` threads (single-core bandwidth for 1, all cores otherwise)
+    ///
+    /// Dividing a relative throughput (this machine / reference) by the
+    /// factor removes the part explained by memory bandwidth alone: ≈1 for a
+    /// bandwidth-bound operation, >1 where the chip gains beyond bandwidth.
+    pub fn bandwidth_factor(&self, reference: &MachineProfile, threads: usize) -> Option<f64> {
+        let bandwidth = |profile: &MachineProfile| {
+            if threads <= 1 {
+                profile.single_core_read_gbps()
+            } else {
+                profile.all_core_read_gbps()
+            }
+        };
+        let (this, other) = (bandwidth(self)?, bandwidth(reference)?);
+        (other > 0.0).then(|| this / other)
+    }
+
+    /// Fraction of this machine's read bandwidth an operation achieves when
+    /// streaming `bytes_per_second` with `threads` threads
+    pub fn bandwidth_utilization(&self, bytes_per_second: f64, threads: usize) -> Option<f64> {
+        let peak = if threads <= 1 {
+            self.single_core_read_gbps()?
+        } else {
+            self.all_core_read_gbps()?
+        };
+        Some(bytes_per_second / (peak * 1e9))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&json).with_context(|| format!("Invalid machine profile {}", path.display()))
+    }
+}
+
+// ============================================================================
+// Running
+// ============================================================================
+
+/// What to measure
+#[derive(Debug, Clone)]
+pub struct MicroOptions {
+    /// Bandwidth buffer size (well above the last-level cache)
+    pub buffer_bytes: usize,
+    /// Timed passes per bandwidth measurement (best is kept)
+    pub passes: usize,
+    /// Threads of the all-core read measurement
+    pub threads: usize,
+    /// Smallest and largest latency working set
+    pub latency_range: (usize, usize),
+    /// Dependent loads per latency point
+    pub latency_loads: usize,
+    /// File to measure read paths on (skipped if `None`); should be larger
+    /// than memory for cold mmap reads on macOS
+    pub storage_file: Option<PathBuf>,
+    /// Request size of buffered and AIO reads
+    pub storage_block_bytes: usize,
+    /// AIO requests in flight, one measurement each
+    pub storage_queue_depths: Vec<usize>,
+}
+
+impl Default for MicroOptions {
+    fn default() -> Self {
+        Self {
+            buffer_bytes: 512 << 20,
+            passes: 5,
+            threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            latency_range: (16 << 10, 512 << 20),
+            latency_loads: 10_000_000,
+            storage_file: None,
+            storage_block_bytes: 1 << 20,
+            storage_queue_depths: vec![1, 2, 4, 8, 16],
+        }
+    }
+}
+
+impl MicroOptions {
+    /// Small buffers and few loads: seconds instead of minutes, for smoke tests
+    pub fn quick() -> Self {
+        Self {
+            buffer_bytes: 64 << 20,
+            passes: 2,
+            latency_range: (16 << 10, 64 << 20),
+            latency_loads: 1_000_000,
+            ..Self::default()
+        }
+    }
+}
+
+/// Name of the machine being profiled
+fn machine_name() -> String {
+    asbb_core::HardwareProfile::detect()
+        .map(|profile| profile.chip_name())
+        .unwrap_or_else(|_| format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH))
+}
+
+/// Run every microbenchmark, reporting each step to `progress`
+pub fn run_with_progress(options: &MicroOptions, mut progress: impl FnMut(&str)) -> Result<MachineProfile> {
+    let mut read_bandwidth = Vec::new();
+    let mut groups = vec![(CoreType::Performance, 1), (CoreType::Efficiency, 1)];
+    if options.threads > 1 {
+        groups.push((CoreType::Any, options.threads));
+    }
+    for (core_type, threads) in groups {
+        let gbps = bandwidth::stream_read_gbps(options.buffer_bytes, threads, core_type, options.passes)?;
+        progress(&format!("read bandwidth ({} × {}): {:.1} GB/s", threads, core_type.name(), gbps));
+        read_bandwidth.push(ReadBandwidth { core_type, threads, gbps });
+    }
+
+    let copy_gbps = bandwidth::copy_gbps(options.buffer_bytes, options.passes)?;
+    progress(&format!("copy bandwidth: {:.1} GB/s", copy_gbps));
+
+    let (min_bytes, max_bytes) = options.latency_range;
+    let latency = latency::latency_curve(&latency::working_sets(min_bytes, max_bytes), options.latency_loads);
+    for point in &latency {
+        progress(&format!(
+            "latency at {} KiB: {:.1} ns",
+            point.working_set_bytes >> 10,
+            point.latency_ns
+        ));
+    }
+
+    let shared_buffer = match shared_buffer::measure(options.buffer_bytes, options.passes) {
+        Ok(shared) => {
+            progress(&format!(
+                "shared buffer: CPU write {:.1}, GPU to private {:.1}, GPU to shared {:.1}, CPU read {:.1} GB/s",
+                shared.cpu_write_gbps,
+                shared.gpu_shared_to_private_gbps,
+                shared.gpu_private_to_shared_gbps,
+                shared.cpu_read_gbps
+            ));
+            Some(shared)
+        }
+        Err(e) => {
+            progress(&format!("shared buffer: skipped ({})", e));
+            None
+        }
+    };
+
+    let storage = match &options.storage_file {
+        Some(path) => {
+            let storage = storage::profile_file(
+                path,
+                options.storage_block_bytes,
+                &options.storage_queue_depths,
+                options.passes,
+                true,
+            )?;
+            for read in &storage.reads {
+                progress(&format!("file read ({}): {:.2} GB/s", read.path.label(), read.gbps));
+            }
+            Some(storage)
+        }
+        None => None,
+    };
+
+    Ok(MachineProfile {
+        machine: machine_name(),
+        measured_at: chrono::Utc::now().to_rfc3339(),
+        buffer_bytes: options.buffer_bytes,
+        read_bandwidth,
+        copy_gbps,
+        latency,
+        shared_buffer,
+        storage,
+    })
+}
+
+/// Run every microbenchmark
+pub fn run(options: &MicroOptions) -> Result<MachineProfile> {
+    run_with_progress(options, |_| {})
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(single: f64, all: f64) -> MachineProfile {
+        MachineProfile {
+            machine: "test".to_string(),
+            measured_at: String::new(),
+            buffer_bytes: 1 << 20,
+            read_bandwidth: vec![
+                ReadBandwidth { core_type: CoreType::Performance, threads: 1, gbps: single },
+                ReadBandwidth { core_type: CoreType::Efficiency, threads: 1, gbps: single / 2.0 },
+                ReadBandwidth { core_type: CoreType::Any, threads: 8, gbps: all },
+            ],
+            copy_gbps: single,
+            latency: vec![LatencyPoint { working_set_bytes: 1 << 20, latency_ns: 90.0 }],
+            shared_buffer: None,
+            storage: None,
+        }
+    }
+
+    #[test]
+    fn test_normalization() {
+        let m1 = profile(60.0, 68.0);
+        let m4 = profile(120.0, 120.0);
+
+        assert_eq!(m4.bandwidth_factor(&m1, 1), Some(2.0));
+        assert!((m4.bandwidth_factor(&m1, 8).unwrap() - 120.0 / 68.0).abs() < 1e-12);
+        assert_eq!(m4.bandwidth_utilization(60e9, 1), Some(0.5));
+        assert_eq!(m1.memory_latency_ns(), Some(90.0));
+    }
+
+    #[test]
+    fn test_run_and_round_trip() {
+        let options = MicroOptions {
+            buffer_bytes: 1 << 20,
+            passes: 1,
+            threads: 2,
+            latency_range: (16 << 10, 32 << 10),
+            latency_loads: 1000,
+            ..MicroOptions::default()
+        };
+        let mut steps = 0;
+        let profile = run_with_progress(&options, |_| steps += 1).unwrap();
+        assert_eq!(profile.read_bandwidth.len(), 3);
+        assert_eq!(profile.latency.len(), 2);
+        assert!(profile.single_core_read_gbps().unwrap() > 0.0);
+        assert_eq!(steps, 7);
+
+        let path = std::env::temp_dir().join(format!("asbb_machine_profile_{}.json", std::process::id()));
+        profile.save(&path).unwrap();
+        assert_eq!(MachineProfile::load(&path).unwrap(), profile);
+        std::fs::remove_file(path).ok();
+    }
+}
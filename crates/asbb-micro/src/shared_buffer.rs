@@ -0,0 +1,92 @@
+//! CPU ↔ GPU copy bandwidth through Metal shared buffers
+//!
+//! Unified memory means a `StorageModeShared` buffer is the same DRAM for
+//! both sides; what differs is who moves the bytes. Measured directions:
+//!
+//! - **CPU write**: `memcpy` from a heap buffer into the shared buffer
+//!   (staging input for a kernel)
+//! - **GPU shared → private**: blit copy into a GPU-private buffer
+//! - **GPU private → shared**: blit copy back (staging results)
+//! - **CPU read**: summing the shared buffer (consuming results)
+//!
+//! Blit times include command-buffer submission and completion wait, the
+//! same overhead a real kernel launch pays. Only available on macOS.
+
+use serde::{Deserialize, Serialize};
+
+/// Copy bandwidths (GB/s) through shared buffers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedBufferBandwidth {
+    pub buffer_bytes: usize,
+    pub cpu_write_gbps: f64,
+    pub gpu_shared_to_private_gbps: f64,
+    pub gpu_private_to_shared_gbps: f64,
+    pub cpu_read_gbps: f64,
+}
+
+/// Measure shared-buffer copy bandwidth (best of `passes` per direction)
+#[cfg(target_os = "macos")]
+pub fn measure(bytes: usize, passes: usize) -> anyhow::Result<SharedBufferBandwidth> {
+    use crate::bandwidth::gbps;
+    use anyhow::Context;
+    use metal::{Device, MTLResourceOptions};
+    use std::time::{Duration, Instant};
+
+    fn best(passes: usize, mut f: impl FnMut() -> Duration) -> Duration {
+        (0..passes).map(|_| f()).min().unwrap_or_default()
+    }
+
+    anyhow::ensure!(passes > 0, "Passes must be positive");
+
+    objc::rc::autoreleasepool(|| {
+        let device = Device::system_default().context("No Metal device available")?;
+        let queue = device.new_command_queue();
+        let length = bytes as u64;
+        let shared = device.new_buffer(length, MTLResourceOptions::StorageModeShared);
+        let private = device.new_buffer(length, MTLResourceOptions::StorageModePrivate);
+        let source: Vec<u8> = (0..bytes).map(|i| i as u8).collect();
+
+        let cpu_write = best(passes, || {
+            let start = Instant::now();
+            unsafe {
+                std::ptr::copy_nonoverlapping(source.as_ptr(), shared.contents() as *mut u8, bytes);
+            }
+            start.elapsed()
+        });
+
+        let blit = |from: &metal::BufferRef, to: &metal::BufferRef| {
+            let start = Instant::now();
+            let command_buffer = queue.new_command_buffer();
+            let encoder = command_buffer.new_blit_command_encoder();
+            encoder.copy_from_buffer(from, 0, to, 0, length);
+            encoder.end_encoding();
+            command_buffer.commit();
+            command_buffer.wait_until_completed();
+            start.elapsed()
+        };
+        let to_private = best(passes, || blit(&shared, &private));
+        let to_shared = best(passes, || blit(&private, &shared));
+
+        let cpu_read = best(passes, || {
+            let start = Instant::now();
+            let contents = unsafe { std::slice::from_raw_parts(shared.contents() as *const u8, bytes) };
+            let sum = contents.iter().fold(0u64, |s, &b| s.wrapping_add(b as u64));
+            std::hint::black_box(sum);
+            start.elapsed()
+        });
+
+        Ok(SharedBufferBandwidth {
+            buffer_bytes: bytes,
+            cpu_write_gbps: gbps(bytes, cpu_write),
+            gpu_shared_to_private_gbps: gbps(bytes, to_private),
+            gpu_private_to_shared_gbps: gbps(bytes, to_shared),
+            cpu_read_gbps: gbps(bytes, cpu_read),
+        })
+    })
+}
+
+/// Measure shared-buffer copy bandwidth
+#[cfg(not(target_os = "macos"))]
+pub fn measure(_bytes: usize, _passes: usize) -> anyhow::Result<SharedBufferBandwidth> {
+    anyhow::bail!("Shared-buffer bandwidth requires Metal (macOS)")
+}
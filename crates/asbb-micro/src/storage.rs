@@ -0,0 +1,355 @@
+//! File read throughput by read path
+//!
+//! Reads one file start to end with each read path and reports GB/s, best of
+//! several passes:
+//!
+//! - **buffered**: synchronous `BufReader` reads of one block at a time
+//! - **mmap**: a memory-mapped file, faulted in page by page as it is read
+//! - **aio**: a ring of `queue_depth` POSIX AIO requests kept in flight, each
+//!   reissued for the next block as soon as it completes
+//!
+//! Sweeping the AIO queue depth shows whether the SSD rewards parallel
+//! requests (NVMe controllers serve many queued commands at once) or is
+//! already saturated by one sequential stream with readahead.
+//!
+//! Every path touches one byte per 4 KiB of data, enough to fault in mapped
+//! pages without adding a memory-bandwidth-bound pass. With `cold` passes,
+//! cached pages are dropped before each pass (`posix_fadvise` on Linux) or
+//! bypassed (`F_NOCACHE` on macOS, which does not cover mappings: mmap passes
+//! on macOS are cold only for files larger than memory). glibc implements
+//! POSIX AIO with helper threads issuing `pread`, so on Linux the ring
+//! measures threaded reads; macOS caps AIO requests per process
+//! (`kern.aioprocmax`, 16 by default) and the ring waits when it is reached.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::bandwidth::gbps;
+
+/// Bytes between touched bytes (the smallest page size)
+const TOUCH_STRIDE: usize = 4096;
+
+/// How a file is read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadPath {
+    Buffered,
+    Mmap,
+    Aio { queue_depth: usize },
+}
+
+impl ReadPath {
+    /// `buffered`, `mmap` or `aio_qd<N>`
+    pub fn label(&self) -> String {
+        match self {
+            ReadPath::Buffered => "buffered".to_string(),
+            ReadPath::Mmap => "mmap".to_string(),
+            ReadPath::Aio { queue_depth } => format!("aio_qd{}", queue_depth),
+        }
+    }
+}
+
+/// Throughput of one read path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageRead {
+    pub path: ReadPath,
+    pub gbps: f64,
+}
+
+/// Read throughput of every path over one file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageProfile {
+    pub file_bytes: u64,
+    /// Request size of buffered and AIO reads
+    pub block_bytes: usize,
+    /// Whether cached pages were dropped or bypassed for each pass
+    pub cold: bool,
+    pub reads: Vec<StorageRead>,
+}
+
+impl StorageProfile {
+    pub fn gbps(&self, path: ReadPath) -> Option<f64> {
+        self.reads.iter().find(|r| r.path == path).map(|r| r.gbps)
+    }
+
+    /// Best AIO throughput over AIO with one request in flight (≈1 when
+    /// parallel requests do not help)
+    pub fn aio_scaling(&self) -> Option<f64> {
+        let single = self.gbps(ReadPath::Aio { queue_depth: 1 })?;
+        let best = self
+            .reads
+            .iter()
+            .filter(|r| matches!(r.path, ReadPath::Aio { .. }))
+            .map(|r| r.gbps)
+            .fold(0.0, f64::max);
+        (single > 0.0).then(|| best / single)
+    }
+}
+
+// ============================================================================
+// Measurement
+// ============================================================================
+
+/// Sum of one byte per [`TOUCH_STRIDE`] (`offset` is the data's position in
+/// the file, so every path touches the same bytes)
+fn touch(data: &[u8], offset: usize) -> u64 {
+    let first = (TOUCH_STRIDE - offset % TOUCH_STRIDE) % TOUCH_STRIDE;
+    data.iter().skip(first).step_by(TOUCH_STRIDE).map(|&b| b as u64).sum()
+}
+
+/// Open `path` for a pass, dropping or bypassing its cached pages if `cold`
+fn open_for_pass(path: &Path, cold: bool) -> Result<File> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    if cold {
+        drop_cache(&file);
+    }
+    Ok(file)
+}
+
+#[cfg(target_os = "linux")]
+fn drop_cache(file: &File) {
+    use std::os::unix::io::AsRawFd;
+    // Advisory: dirty or mapped pages stay
+    unsafe {
+        libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED);
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn drop_cache(file: &File) {
+    use std::os::unix::io::AsRawFd;
+    unsafe {
+        libc::fcntl(file.as_raw_fd(), libc::F_NOCACHE, 1);
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn drop_cache(_file: &File) {}
+
+fn read_buffered(file: File, block_bytes: usize) -> Result<(u64, u64)> {
+    let mut reader = BufReader::with_capacity(block_bytes, file);
+    let (mut bytes, mut sum) = (0u64, 0u64);
+    loop {
+        let data = reader.fill_buf()?;
+        if data.is_empty() {
+            break;
+        }
+        sum += touch(data, bytes as usize);
+        bytes += data.len() as u64;
+        let len = data.len();
+        reader.consume(len);
+    }
+    Ok((bytes, sum))
+}
+
+fn read_mmap(file: File) -> Result<(u64, u64)> {
+    let len = file.metadata()?.len();
+    if len == 0 {
+        return Ok((0, 0));
+    }
+    let mmap = unsafe { memmap2::Mmap::map(&file)? };
+    Ok((len, touch(&mmap, 0)))
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn read_aio(file: File, block_bytes: usize, queue_depth: usize) -> Result<(u64, u64)> {
+    use std::os::unix::io::AsRawFd;
+
+    let len = file.metadata()?.len() as usize;
+    let fd = file.as_raw_fd();
+    let mut buffers = vec![vec![0u8; block_bytes]; queue_depth];
+    // Control blocks must stay put while their request is in flight
+    let mut requests: Vec<libc::aiocb> =
+        (0..queue_depth).map(|_| unsafe { std::mem::zeroed() }).collect();
+    let mut offsets: Vec<Option<usize>> = vec![None; queue_depth];
+    let (mut next, mut bytes, mut sum) = (0usize, 0u64, 0u64);
+
+    loop {
+        // Fill idle slots with the next blocks
+        for slot in 0..queue_depth {
+            if offsets[slot].is_some() || next >= len {
+                continue;
+            }
+            let request = &mut requests[slot];
+            *request = unsafe { std::mem::zeroed() };
+            request.aio_fildes = fd;
+            request.aio_offset = next as libc::off_t;
+            request.aio_buf = buffers[slot].as_mut_ptr().cast();
+            request.aio_nbytes = block_bytes.min(len - next);
+            request.aio_sigevent.sigev_notify = libc::SIGEV_NONE;
+            if unsafe { libc::aio_read(request) } != 0 {
+                let error = std::io::Error::last_os_error();
+                // Out of AIO slots: wait for requests already in flight
+                let waiting = offsets.iter().any(Option::is_some);
+                if error.raw_os_error() == Some(libc::EAGAIN) && waiting {
+                    break;
+                }
+                return Err(error).context("aio_read failed");
+            }
+            offsets[slot] = Some(next);
+            next += block_bytes;
+        }
+
+        let in_flight: Vec<*const libc::aiocb> = (0..queue_depth)
+            .filter(|&slot| offsets[slot].is_some())
+            .map(|slot| &requests[slot] as *const libc::aiocb)
+            .collect();
+        if in_flight.is_empty() {
+            break;
+        }
+        let count = in_flight.len() as i32;
+        if unsafe { libc::aio_suspend(in_flight.as_ptr(), count, std::ptr::null()) } != 0 {
+            let error = std::io::Error::last_os_error();
+            if error.raw_os_error() != Some(libc::EINTR) {
+                return Err(error).context("aio_suspend failed");
+            }
+        }
+
+        // Collect completed requests
+        for slot in 0..queue_depth {
+            let Some(offset) = offsets[slot] else { continue };
+            let request = &mut requests[slot];
+            match unsafe { libc::aio_error(request) } {
+                libc::EINPROGRESS => continue,
+                0 => {}
+                -1 => return Err(std::io::Error::last_os_error()).context("aio_error failed"),
+                code => {
+                    return Err(std::io::Error::from_raw_os_error(code)).context("AIO read failed")
+                }
+            }
+            let read = unsafe { libc::aio_return(request) };
+            anyhow::ensure!(read >= 0, "aio_return failed");
+            // Short reads only happen at end of file
+            sum += touch(&buffers[slot][..read as usize], offset);
+            bytes += read as u64;
+            offsets[slot] = None;
+        }
+    }
+
+    Ok((bytes, sum))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn read_aio(_file: File, _block_bytes: usize, _queue_depth: usize) -> Result<(u64, u64)> {
+    anyhow::bail!("POSIX AIO read path is only implemented on Linux and macOS")
+}
+
+/// Read `path` once with `read_path`; returns (bytes read, checksum of touched bytes)
+pub fn read_file(
+    path: &Path,
+    read_path: ReadPath,
+    block_bytes: usize,
+    cold: bool,
+) -> Result<(u64, u64)> {
+    anyhow::ensure!(block_bytes > 0, "Block size must be positive");
+    let file = open_for_pass(path, cold)?;
+    match read_path {
+        ReadPath::Buffered => read_buffered(file, block_bytes),
+        ReadPath::Mmap => read_mmap(file),
+        ReadPath::Aio { queue_depth } => {
+            anyhow::ensure!(queue_depth > 0, "Queue depth must be positive");
+            read_aio(file, block_bytes, queue_depth)
+        }
+    }
+}
+
+/// Read throughput (GB/s) of `read_path` over `path`, best of `passes`
+pub fn read_gbps(
+    path: &Path,
+    read_path: ReadPath,
+    block_bytes: usize,
+    passes: usize,
+    cold: bool,
+) -> Result<f64> {
+    anyhow::ensure!(passes > 0, "Passes must be positive");
+
+    let mut best = Duration::MAX;
+    let mut bytes = 0;
+    for _ in 0..passes {
+        let start = Instant::now();
+        let (read, sum) = read_file(path, read_path, block_bytes, cold)?;
+        best = best.min(start.elapsed());
+        std::hint::black_box(sum);
+        bytes = read;
+    }
+    Ok(gbps(bytes as usize, best))
+}
+
+/// Buffered, mmap, then AIO at each of `queue_depths`
+pub fn profile_file(
+    path: &Path,
+    block_bytes: usize,
+    queue_depths: &[usize],
+    passes: usize,
+    cold: bool,
+) -> Result<StorageProfile> {
+    let file_bytes = std::fs::metadata(path)
+        .with_context(|| format!("Failed to stat {}", path.display()))?
+        .len();
+    let paths = [ReadPath::Buffered, ReadPath::Mmap]
+        .into_iter()
+        .chain(queue_depths.iter().map(|&queue_depth| ReadPath::Aio { queue_depth }));
+
+    let reads = paths
+        .map(|read_path| {
+            let gbps = read_gbps(path, read_path, block_bytes, passes, cold)
+                .with_context(|| format!("{} read of {}", read_path.label(), path.display()))?;
+            Ok(StorageRead { path: read_path, gbps })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(StorageProfile {
+        file_bytes,
+        block_bytes,
+        cold,
+        reads,
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_paths_agree() {
+        // Not a multiple of the block size: the last AIO read is short
+        let data: Vec<u8> = (0..300_001u32).map(|i| (i * 7 % 251) as u8).collect();
+        let path = std::env::temp_dir().join(format!("asbb_storage_{}.bin", std::process::id()));
+        std::fs::write(&path, &data).unwrap();
+
+        let expected = (data.len() as u64, touch(&data, 0));
+        for read_path in [
+            ReadPath::Buffered,
+            ReadPath::Mmap,
+            ReadPath::Aio { queue_depth: 1 },
+            ReadPath::Aio { queue_depth: 4 },
+        ] {
+            let read = read_file(&path, read_path, 64 << 10, true).unwrap();
+            assert_eq!(read, expected, "{}", read_path.label());
+        }
+        assert!(read_file(&path, ReadPath::Aio { queue_depth: 0 }, 64 << 10, false).is_err());
+
+        let profile = profile_file(&path, 64 << 10, &[1, 2], 1, false).unwrap();
+        assert_eq!(profile.file_bytes, data.len() as u64);
+        assert_eq!(profile.reads.len(), 4);
+        assert!(profile.gbps(ReadPath::Mmap).unwrap() > 0.0);
+        assert!(profile.aio_scaling().unwrap() > 0.0);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_touch_is_offset_aligned() {
+        let data: Vec<u8> = (0..20_000u32).map(|i| (i % 256) as u8).collect();
+        let whole = touch(&data, 0);
+        let split = touch(&data[..5000], 0) + touch(&data[5000..], 5000);
+        assert_eq!(whole, split);
+    }
+}
@@ -200,10 +200,7 @@ impl PrimitiveOperation for AdapterTrimming {
     }
 
     fn execute_parallel(&self, sequences: &[SequenceRecord], num_threads: usize) -> Result<OperationOutput> {
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()
-            .unwrap();
+        let pool = crate::thread_pool::get(num_threads)?;
 
         let results: Vec<Option<SequenceRecord>> = pool.install(|| {
             sequences.par_iter().map(|record| {
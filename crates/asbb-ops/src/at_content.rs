@@ -37,7 +37,7 @@ impl ATContent {
         }
 
         result.finalize();
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 
     /// Execute AT content on 2-bit encoded sequences (NEON)
@@ -56,7 +56,7 @@ impl ATContent {
             }
 
             result.finalize();
-            Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+            Ok(OperationOutput::typed(result))
         }
 
         #[cfg(not(target_arch = "aarch64"))]
@@ -113,27 +113,19 @@ impl PrimitiveOperation for ATContent {
         }
 
         result.finalize();
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
-        #[cfg(target_arch = "aarch64")]
-        {
-            let mut result = ATContentResult::new();
-
-            for record in data {
-                let local_result = count_at_neon(&record.sequence);
-                result.add(&local_result);
-            }
+        let mut result = ATContentResult::new();
 
-            result.finalize();
-            Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        for record in data {
+            let local_result = count_at_neon(&record.sequence);
+            result.add(&local_result);
         }
 
-        #[cfg(not(target_arch = "aarch64"))]
-        {
-            self.execute_naive(data)
-        }
+        result.finalize();
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_parallel(
@@ -141,23 +133,11 @@ impl PrimitiveOperation for ATContent {
         data: &[SequenceRecord],
         num_threads: usize,
     ) -> Result<OperationOutput> {
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()?;
+        let pool = crate::thread_pool::get(num_threads)?;
 
         let mut result = pool.install(|| {
             data.par_iter()
-                .map(|record| {
-                    #[cfg(target_arch = "aarch64")]
-                    {
-                        count_at_neon(&record.sequence)
-                    }
-
-                    #[cfg(not(target_arch = "aarch64"))]
-                    {
-                        count_at_naive(&record.sequence)
-                    }
-                })
+                .map(|record| count_at_neon(&record.sequence))
                 .reduce(
                     || ATContentResult::new(),
                     |mut a, b| {
@@ -168,7 +148,24 @@ impl PrimitiveOperation for ATContent {
         });
 
         result.finalize();
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let merge = |mut a: ATContentResult, b: ATContentResult| {
+            a.add(&b);
+            a
+        };
+        let mut result = crate::chunked::reduce_neon(self, data, num_threads, batch_size, merge)?
+            .unwrap_or_else(ATContentResult::new);
+        result.finalize();
+
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_with_config(
@@ -177,7 +174,7 @@ impl PrimitiveOperation for ATContent {
         config: &HardwareConfig,
     ) -> Result<OperationOutput> {
         if config.num_threads > 1 {
-            self.execute_parallel(data, config.num_threads)
+            self.execute_threaded(data, config)
         } else if config.use_neon {
             self.execute_neon(data)
         } else {
@@ -200,6 +197,16 @@ fn count_at_naive(seq: &[u8]) -> ATContentResult {
     result
 }
 
+/// 16-byte chunks a u8 accumulator lane can count before it can overflow
+/// (one increment per chunk; ~4 kb of sequence per block)
+#[cfg(target_arch = "aarch64")]
+const MAX_CHUNKS_PER_FLUSH: usize = 255;
+
+/// 2-bit chunks a u8 accumulator lane can count before it can overflow
+/// (each byte packs four bases, so up to four increments per chunk)
+#[cfg(target_arch = "aarch64")]
+const MAX_2BIT_CHUNKS_PER_FLUSH: usize = 63;
+
 // NEON SIMD implementation
 #[cfg(target_arch = "aarch64")]
 fn count_at_neon(seq: &[u8]) -> ATContentResult {
@@ -209,9 +216,6 @@ fn count_at_neon(seq: &[u8]) -> ATContentResult {
     result.total_bases = seq.len();
 
     unsafe {
-        // NEON accumulator for AT count
-        let mut vec_at_count = vdupq_n_u8(0);
-
         // Comparison values for A and T (upper and lower case)
         let cmp_a_upper = vdupq_n_u8(b'A');
         let cmp_a_lower = vdupq_n_u8(b'a');
@@ -224,24 +228,31 @@ fn count_at_neon(seq: &[u8]) -> ATContentResult {
         let remainder = chunks.remainder();
 
         // Process 16 bytes at a time
-        for chunk in chunks {
-            let data = vld1q_u8(chunk.as_ptr());
+        // u8 lanes overflow after 255 increments, so flush to usize every block
+        let vector_bytes = &seq[..seq.len() - remainder.len()];
+        for block in vector_bytes.chunks(16 * MAX_CHUNKS_PER_FLUSH) {
+            // NEON accumulator for AT count
+            let mut vec_at_count = vdupq_n_u8(0);
 
-            // Check for A (upper or lower)
-            let mask_a = vorrq_u8(vceqq_u8(data, cmp_a_upper), vceqq_u8(data, cmp_a_lower));
+            for chunk in block.chunks_exact(16) {
+                let data = vld1q_u8(chunk.as_ptr());
 
-            // Check for T (upper or lower)
-            let mask_t = vorrq_u8(vceqq_u8(data, cmp_t_upper), vceqq_u8(data, cmp_t_lower));
+                // Check for A (upper or lower)
+                let mask_a = vorrq_u8(vceqq_u8(data, cmp_a_upper), vceqq_u8(data, cmp_a_lower));
 
-            // Combine A and T masks
-            let mask_at = vorrq_u8(mask_a, mask_t);
+                // Check for T (upper or lower)
+                let mask_t = vorrq_u8(vceqq_u8(data, cmp_t_upper), vceqq_u8(data, cmp_t_lower));
 
-            // Accumulate (mask is 0xFF for match, 0x00 for no match, so AND with 1)
-            vec_at_count = vaddq_u8(vec_at_count, vandq_u8(mask_at, ones));
-        }
+                // Combine A and T masks
+                let mask_at = vorrq_u8(mask_a, mask_t);
+
+                // Accumulate (mask is 0xFF for match, 0x00 for no match, so AND with 1)
+                vec_at_count = vaddq_u8(vec_at_count, vandq_u8(mask_at, ones));
+            }
 
-        // Horizontal sum: reduce vector to scalar
-        result.at_count = horizontal_sum_u8(vec_at_count);
+            // Horizontal sum: reduce vector to scalar
+            result.at_count += horizontal_sum_u8(vec_at_count);
+        }
 
         // Process remainder with scalar code
         for &base in remainder {
@@ -262,9 +273,15 @@ fn horizontal_sum_u8(vec: std::arch::aarch64::uint8x16_t) -> usize {
     }
 }
 
+// SSE2/AVX2 (x86_64) or scalar implementation for non-NEON targets
 #[cfg(not(target_arch = "aarch64"))]
-fn count_at_neon(_seq: &[u8]) -> ATContentResult {
-    panic!("NEON not available on this platform");
+fn count_at_neon(seq: &[u8]) -> ATContentResult {
+    let [count_a, count_t] = crate::simd::count_letters(seq, [b'A', b'T']);
+
+    let mut result = ATContentResult::new();
+    result.total_bases = seq.len();
+    result.at_count = count_a + count_t;
+    result
 }
 
 /// Count AT bases in 2-bit encoded data using NEON
@@ -289,9 +306,6 @@ fn count_at_2bit_neon(bitseq: &BitSeq) -> ATContentResult {
     let remainder_bytes = chunks.remainder();
 
     unsafe {
-        // Accumulator for AT count
-        let mut vec_count_at = vdupq_n_u8(0);
-
         // Masks for extracting 2-bit pairs
         let mask_bits_67 = vdupq_n_u8(0b11000000);
         let mask_bits_45 = vdupq_n_u8(0b00110000);
@@ -304,34 +318,41 @@ fn count_at_2bit_neon(bitseq: &BitSeq) -> ATContentResult {
 
         let ones = vdupq_n_u8(1);
 
-        for chunk in chunks {
-            // Load 16 bytes (64 bases)
-            let data_vec = vld1q_u8(chunk.as_ptr());
-
-            // Process each 2-bit position
-            // Position 0 (bits 6-7)
-            let bases_0 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_67), 6);
-            let mask_at_0 = vorrq_u8(vceqq_u8(bases_0, cmp_a), vceqq_u8(bases_0, cmp_t));
-            vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_0, ones));
-
-            // Position 1 (bits 4-5)
-            let bases_1 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_45), 4);
-            let mask_at_1 = vorrq_u8(vceqq_u8(bases_1, cmp_a), vceqq_u8(bases_1, cmp_t));
-            vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_1, ones));
-
-            // Position 2 (bits 2-3)
-            let bases_2 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_23), 2);
-            let mask_at_2 = vorrq_u8(vceqq_u8(bases_2, cmp_a), vceqq_u8(bases_2, cmp_t));
-            vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_2, ones));
-
-            // Position 3 (bits 0-1)
-            let bases_3 = vandq_u8(data_vec, mask_bits_01);
-            let mask_at_3 = vorrq_u8(vceqq_u8(bases_3, cmp_a), vceqq_u8(bases_3, cmp_t));
-            vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_3, ones));
-        }
+        // u8 lanes overflow after 255 increments, so flush to usize every block
+        let vector_bytes = &data[..data.len() - remainder_bytes.len()];
+        for block in vector_bytes.chunks(16 * MAX_2BIT_CHUNKS_PER_FLUSH) {
+            // Accumulator for AT count
+            let mut vec_count_at = vdupq_n_u8(0);
+
+            for chunk in block.chunks_exact(16) {
+                // Load 16 bytes (64 bases)
+                let data_vec = vld1q_u8(chunk.as_ptr());
+
+                // Process each 2-bit position
+                // Position 0 (bits 6-7)
+                let bases_0 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_67), 6);
+                let mask_at_0 = vorrq_u8(vceqq_u8(bases_0, cmp_a), vceqq_u8(bases_0, cmp_t));
+                vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_0, ones));
+
+                // Position 1 (bits 4-5)
+                let bases_1 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_45), 4);
+                let mask_at_1 = vorrq_u8(vceqq_u8(bases_1, cmp_a), vceqq_u8(bases_1, cmp_t));
+                vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_1, ones));
+
+                // Position 2 (bits 2-3)
+                let bases_2 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_23), 2);
+                let mask_at_2 = vorrq_u8(vceqq_u8(bases_2, cmp_a), vceqq_u8(bases_2, cmp_t));
+                vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_2, ones));
+
+                // Position 3 (bits 0-1)
+                let bases_3 = vandq_u8(data_vec, mask_bits_01);
+                let mask_at_3 = vorrq_u8(vceqq_u8(bases_3, cmp_a), vceqq_u8(bases_3, cmp_t));
+                vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_3, ones));
+            }
 
-        // Horizontal sum to get total AT count
-        result.at_count = horizontal_sum_u8(vec_count_at);
+            // Horizontal sum to get total AT count
+            result.at_count += horizontal_sum_u8(vec_count_at);
+        }
     }
 
     // Process remainder bytes with scalar code
@@ -377,8 +398,7 @@ mod tests {
 
         let result = op.execute_naive(&records).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let at_result: ATContentResult = serde_json::from_value(value).unwrap();
+        if let Some(at_result) = result.statistics::<ATContentResult>() {
 
             // Expected: 2 + 8 + 0 + 8 = 18 AT bases out of 28 total
             assert_eq!(at_result.at_count, 18);
@@ -390,15 +410,13 @@ mod tests {
     }
 
     #[test]
-    #[cfg(target_arch = "aarch64")]
     fn test_at_content_neon() {
         let records = create_test_records();
         let op = ATContent;
 
         let result = op.execute_neon(&records).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let at_result: ATContentResult = serde_json::from_value(value).unwrap();
+        if let Some(at_result) = result.statistics::<ATContentResult>() {
 
             assert_eq!(at_result.at_count, 18);
             assert_eq!(at_result.total_bases, 28);
@@ -409,7 +427,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(target_arch = "aarch64")]
     fn test_at_content_neon_matches_naive() {
         let records = create_test_records();
         let op = ATContent;
@@ -417,11 +434,11 @@ mod tests {
         let naive_result = op.execute_naive(&records).unwrap();
         let neon_result = op.execute_neon(&records).unwrap();
 
-        if let (OperationOutput::Statistics(naive_val), OperationOutput::Statistics(neon_val)) =
+        if let (OperationOutput::Typed(naive_typed), OperationOutput::Typed(neon_typed)) =
             (naive_result, neon_result)
         {
-            let naive_at: ATContentResult = serde_json::from_value(naive_val).unwrap();
-            let neon_at: ATContentResult = serde_json::from_value(neon_val).unwrap();
+            let naive_at: &ATContentResult = naive_typed.downcast_ref().unwrap();
+            let neon_at: &ATContentResult = neon_typed.downcast_ref().unwrap();
 
             assert_eq!(naive_at.at_count, neon_at.at_count);
             assert_eq!(naive_at.total_bases, neon_at.total_bases);
@@ -437,8 +454,7 @@ mod tests {
 
         let result = op.execute_parallel(&records, 2).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let at_result: ATContentResult = serde_json::from_value(value).unwrap();
+        if let Some(at_result) = result.statistics::<ATContentResult>() {
 
             assert_eq!(at_result.at_count, 18);
             assert_eq!(at_result.total_bases, 28);
@@ -460,8 +476,7 @@ mod tests {
 
         let result = op.execute_2bit_naive(&bitseqs).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let at_result: ATContentResult = serde_json::from_value(value).unwrap();
+        if let Some(at_result) = result.statistics::<ATContentResult>() {
 
             // Expected: 2 + 8 + 0 = 10 AT bases out of 20 total
             assert_eq!(at_result.at_count, 10);
@@ -507,9 +522,36 @@ mod tests {
         assert_eq!(result_naive, result_neon);
 
         // Verify AT content is 50% (ACGTACGT pattern)
-        if let OperationOutput::Statistics(value) = result_neon {
-            let at_result: ATContentResult = serde_json::from_value(value).unwrap();
+        if let Some(at_result) = result_neon.statistics::<ATContentResult>() {
             assert!((at_result.at_percent - 50.0).abs() < 0.01);
         }
     }
+
+    #[test]
+    fn test_at_content_neon_long_reads() {
+        // u8 lanes overflow after 255 chunks (~4 kb of matching bases)
+        let op = ATContent;
+        let data = vec![
+            SequenceRecord::fasta("at_rich".to_string(), vec![b'T'; 100_000]),
+            SequenceRecord::fasta("long_read".to_string(), b"ATGCA".repeat(20_001)),
+        ];
+
+        let result_naive = op.execute_naive(&data).unwrap();
+        let result_neon = op.execute_neon(&data).unwrap();
+        assert_eq!(result_naive, result_neon);
+    }
+
+    #[test]
+    #[cfg(target_arch = "aarch64")]
+    fn test_at_content_2bit_neon_long_reads() {
+        let op = ATContent;
+        let bitseqs = vec![
+            BitSeq::from_ascii(&vec![b'A'; 50_000]),
+            BitSeq::from_ascii(&b"ATTG".repeat(25_003)),
+        ];
+
+        let result_naive = op.execute_2bit_naive(&bitseqs).unwrap();
+        let result_neon = op.execute_2bit_neon(&bitseqs).unwrap();
+        assert_eq!(result_naive, result_neon);
+    }
 }
@@ -20,7 +20,7 @@
 //!   that bandwidth is rarely the bottleneck (more likely cache-bound)
 
 use anyhow::Result;
-use asbb_core::{encoding::BitSeq, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use asbb_core::{encoding::BitSeq, packed::PackedRecords, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord, SequenceView};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -48,9 +48,7 @@ impl BaseCounting {
             counts.count_n = 0;
         }
 
-        Ok(OperationOutput::Statistics(
-            serde_json::to_value(counts)?,
-        ))
+        Ok(OperationOutput::typed(counts))
     }
 
     /// Execute base counting on 2-bit encoded sequences (NEON)
@@ -76,9 +74,7 @@ impl BaseCounting {
                 counts.count_n = 0;
             }
 
-            Ok(OperationOutput::Statistics(
-                serde_json::to_value(counts)?,
-            ))
+            Ok(OperationOutput::typed(counts))
         }
 
         #[cfg(not(target_arch = "aarch64"))]
@@ -107,8 +103,24 @@ impl BaseCounting {
         use asbb_gpu::MetalBackend;
 
         let backend = MetalBackend::new()?;
-        let gpu_result = backend.count_bases_gpu(data)?;
+        Ok(Self::from_gpu(backend.count_bases_gpu(data)?))
+    }
 
+    /// Execute on GPU with the reduction kept on the GPU
+    ///
+    /// Same counts as [`execute_gpu`](Self::execute_gpu), but the partial
+    /// counts are folded by a second kernel instead of being read back and
+    /// summed on the CPU (see `asbb_gpu::reduction`).
+    #[cfg(all(target_os = "macos", feature = "gpu"))]
+    pub fn execute_gpu_reduced(&self, data: &[SequenceRecord]) -> Result<(BaseCounts, asbb_gpu::GpuMetrics)> {
+        use asbb_gpu::MetalBackend;
+
+        let backend = MetalBackend::new()?;
+        Ok(Self::from_gpu(backend.count_bases_gpu_reduced(data)?))
+    }
+
+    #[cfg(all(target_os = "macos", feature = "gpu"))]
+    fn from_gpu(gpu_result: asbb_gpu::kernels::BaseCountsGpu) -> (BaseCounts, asbb_gpu::GpuMetrics) {
         let counts = BaseCounts {
             count_a: gpu_result.count_a,
             count_c: gpu_result.count_c,
@@ -118,7 +130,7 @@ impl BaseCounting {
             total: gpu_result.total_bases,
         };
 
-        Ok((counts, gpu_result.metrics))
+        (counts, gpu_result.metrics)
     }
 }
 
@@ -183,40 +195,17 @@ impl PrimitiveOperation for BaseCounting {
             }
         }
 
-        Ok(OperationOutput::Statistics(
-            serde_json::to_value(counts)?,
-        ))
+        Ok(OperationOutput::typed(counts))
     }
 
     fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
-        // NEON SIMD implementation
-        #[cfg(target_arch = "aarch64")]
-        {
-            let mut counts = BaseCounts::new();
-
-            for record in data {
-                let seq = &record.sequence;
-                counts.total += seq.len();
-
-                // Count using NEON intrinsics
-                let base_counts = count_bases_neon(seq);
-                counts.count_a += base_counts.count_a;
-                counts.count_c += base_counts.count_c;
-                counts.count_g += base_counts.count_g;
-                counts.count_t += base_counts.count_t;
-                counts.count_n += base_counts.count_n;
-            }
-
-            Ok(OperationOutput::Statistics(
-                serde_json::to_value(counts)?,
-            ))
+        // NEON on aarch64, SSE2/AVX2 on x86_64 (see `count_bases`)
+        let mut counts = BaseCounts::new();
+        for record in data {
+            counts.add(&count_bases(&record.sequence));
         }
 
-        #[cfg(not(target_arch = "aarch64"))]
-        {
-            // Fall back to naive on non-ARM
-            self.execute_naive(data)
-        }
+        Ok(OperationOutput::typed(counts))
     }
 
     fn execute_parallel(
@@ -224,40 +213,13 @@ impl PrimitiveOperation for BaseCounting {
         data: &[SequenceRecord],
         num_threads: usize,
     ) -> Result<OperationOutput> {
-        // Configure Rayon thread pool
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()?;
+        // Shared Rayon thread pool (built once per thread count)
+        let pool = crate::thread_pool::get(num_threads)?;
 
         let counts = pool.install(|| {
             data.par_iter()
-                .map(|record| {
-                    #[cfg(target_arch = "aarch64")]
-                    {
-                        // Use NEON per-thread for true combined optimization
-                        count_bases_neon(&record.sequence)
-                    }
-
-                    #[cfg(not(target_arch = "aarch64"))]
-                    {
-                        // Fall back to naive on non-ARM
-                        let mut counts = BaseCounts::new();
-                        counts.total = record.sequence.len();
-
-                        for &base in &record.sequence {
-                            match base {
-                                b'A' | b'a' => counts.count_a += 1,
-                                b'C' | b'c' => counts.count_c += 1,
-                                b'G' | b'g' => counts.count_g += 1,
-                                b'T' | b't' => counts.count_t += 1,
-                                b'N' | b'n' => counts.count_n += 1,
-                                _ => {}
-                            }
-                        }
-
-                        counts
-                    }
-                })
+                // SIMD per-thread for true combined optimization
+                .map(|record| count_bases(&record.sequence))
                 .reduce(
                     || BaseCounts::new(),
                     |mut a, b| {
@@ -267,9 +229,60 @@ impl PrimitiveOperation for BaseCounting {
                 )
         });
 
-        Ok(OperationOutput::Statistics(
-            serde_json::to_value(counts)?,
-        ))
+        Ok(OperationOutput::typed(counts))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let merge = |mut a: BaseCounts, b: BaseCounts| {
+            a.add(&b);
+            a
+        };
+        let result = crate::chunked::reduce_neon(self, data, num_threads, batch_size, merge)?
+            .unwrap_or_else(BaseCounts::new);
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_intra_read(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        // Counts are position-independent: no lookahead across segments
+        let merge = |mut a: BaseCounts, b: BaseCounts| {
+            a.add(&b);
+            a
+        };
+        let result = crate::intra_read::map_reduce(
+            data,
+            num_threads,
+            0,
+            |segment| count_bases(segment.bases),
+            merge,
+        )?
+        .unwrap_or_else(BaseCounts::new);
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_views(&self, data: &[SequenceView]) -> Result<OperationOutput> {
+        let mut counts = BaseCounts::new();
+        for view in data {
+            counts.add(&count_bases(view.sequence));
+        }
+
+        Ok(OperationOutput::typed(counts))
+    }
+
+    fn execute_packed(&self, data: &PackedRecords) -> Result<OperationOutput> {
+        // Counts are position-independent, so the contiguous buffer is
+        // processed as one sequence (no per-record remainders)
+        Ok(OperationOutput::typed(count_bases(data.sequence_buffer())))
     }
 }
 
@@ -279,10 +292,42 @@ impl Default for BaseCounting {
     }
 }
 
+/// Count bases in one sequence (NEON on aarch64, `crate::simd` elsewhere)
+fn count_bases(seq: &[u8]) -> BaseCounts {
+    #[cfg(target_arch = "aarch64")]
+    {
+        count_bases_neon(seq)
+    }
+
+    #[cfg(not(target_arch = "aarch64"))]
+    {
+        let [count_a, count_c, count_g, count_t, count_n] =
+            crate::simd::count_letters(seq, [b'A', b'C', b'G', b'T', b'N']);
+        BaseCounts {
+            count_a,
+            count_c,
+            count_g,
+            count_t,
+            count_n,
+            total: seq.len(),
+        }
+    }
+}
+
 // ============================================================================
 // NEON SIMD Implementation
 // ============================================================================
 
+/// 16-byte chunks a u8 accumulator lane can count before it can overflow
+/// (one increment per chunk; ~4 kb of sequence per block)
+#[cfg(target_arch = "aarch64")]
+const MAX_CHUNKS_PER_FLUSH: usize = 255;
+
+/// 2-bit chunks a u8 accumulator lane can count before it can overflow
+/// (each byte packs four bases, so up to four increments per chunk)
+#[cfg(target_arch = "aarch64")]
+const MAX_2BIT_CHUNKS_PER_FLUSH: usize = 63;
+
 #[cfg(target_arch = "aarch64")]
 fn count_bases_neon(seq: &[u8]) -> BaseCounts {
     use std::arch::aarch64::*;
@@ -295,13 +340,6 @@ fn count_bases_neon(seq: &[u8]) -> BaseCounts {
     let remainder = chunks.remainder();
 
     unsafe {
-        // Accumulators for vectorized counts
-        let mut vec_count_a = vdupq_n_u8(0);
-        let mut vec_count_c = vdupq_n_u8(0);
-        let mut vec_count_g = vdupq_n_u8(0);
-        let mut vec_count_t = vdupq_n_u8(0);
-        let mut vec_count_n = vdupq_n_u8(0);
-
         // Comparison values
         let cmp_a_upper = vdupq_n_u8(b'A');
         let cmp_a_lower = vdupq_n_u8(b'a');
@@ -316,48 +354,59 @@ fn count_bases_neon(seq: &[u8]) -> BaseCounts {
 
         let ones = vdupq_n_u8(1);
 
-        for chunk in chunks {
-            // Load 16 bytes
-            let data = vld1q_u8(chunk.as_ptr());
-
-            // Compare with each base (both upper and lower case)
-            let mask_a = vorrq_u8(
-                vceqq_u8(data, cmp_a_upper),
-                vceqq_u8(data, cmp_a_lower),
-            );
-            let mask_c = vorrq_u8(
-                vceqq_u8(data, cmp_c_upper),
-                vceqq_u8(data, cmp_c_lower),
-            );
-            let mask_g = vorrq_u8(
-                vceqq_u8(data, cmp_g_upper),
-                vceqq_u8(data, cmp_g_lower),
-            );
-            let mask_t = vorrq_u8(
-                vceqq_u8(data, cmp_t_upper),
-                vceqq_u8(data, cmp_t_lower),
-            );
-            let mask_n = vorrq_u8(
-                vceqq_u8(data, cmp_n_upper),
-                vceqq_u8(data, cmp_n_lower),
-            );
-
-            // Increment counts where mask is true
-            // mask is 0xFF for true, 0x00 for false
-            // Bitwise AND with 1 to get count increment
-            vec_count_a = vaddq_u8(vec_count_a, vandq_u8(mask_a, ones));
-            vec_count_c = vaddq_u8(vec_count_c, vandq_u8(mask_c, ones));
-            vec_count_g = vaddq_u8(vec_count_g, vandq_u8(mask_g, ones));
-            vec_count_t = vaddq_u8(vec_count_t, vandq_u8(mask_t, ones));
-            vec_count_n = vaddq_u8(vec_count_n, vandq_u8(mask_n, ones));
-        }
+        // u8 lanes overflow after 255 increments, so flush to usize every block
+        let vector_bytes = &seq[..seq.len() - remainder.len()];
+        for block in vector_bytes.chunks(16 * MAX_CHUNKS_PER_FLUSH) {
+            // Accumulators for vectorized counts
+            let mut vec_count_a = vdupq_n_u8(0);
+            let mut vec_count_c = vdupq_n_u8(0);
+            let mut vec_count_g = vdupq_n_u8(0);
+            let mut vec_count_t = vdupq_n_u8(0);
+            let mut vec_count_n = vdupq_n_u8(0);
+
+            for chunk in block.chunks_exact(16) {
+                // Load 16 bytes
+                let data = vld1q_u8(chunk.as_ptr());
+
+                // Compare with each base (both upper and lower case)
+                let mask_a = vorrq_u8(
+                    vceqq_u8(data, cmp_a_upper),
+                    vceqq_u8(data, cmp_a_lower),
+                );
+                let mask_c = vorrq_u8(
+                    vceqq_u8(data, cmp_c_upper),
+                    vceqq_u8(data, cmp_c_lower),
+                );
+                let mask_g = vorrq_u8(
+                    vceqq_u8(data, cmp_g_upper),
+                    vceqq_u8(data, cmp_g_lower),
+                );
+                let mask_t = vorrq_u8(
+                    vceqq_u8(data, cmp_t_upper),
+                    vceqq_u8(data, cmp_t_lower),
+                );
+                let mask_n = vorrq_u8(
+                    vceqq_u8(data, cmp_n_upper),
+                    vceqq_u8(data, cmp_n_lower),
+                );
+
+                // Increment counts where mask is true
+                // mask is 0xFF for true, 0x00 for false
+                // Bitwise AND with 1 to get count increment
+                vec_count_a = vaddq_u8(vec_count_a, vandq_u8(mask_a, ones));
+                vec_count_c = vaddq_u8(vec_count_c, vandq_u8(mask_c, ones));
+                vec_count_g = vaddq_u8(vec_count_g, vandq_u8(mask_g, ones));
+                vec_count_t = vaddq_u8(vec_count_t, vandq_u8(mask_t, ones));
+                vec_count_n = vaddq_u8(vec_count_n, vandq_u8(mask_n, ones));
+            }
 
-        // Horizontal sum to get total counts
-        counts.count_a = horizontal_sum_u8(vec_count_a);
-        counts.count_c = horizontal_sum_u8(vec_count_c);
-        counts.count_g = horizontal_sum_u8(vec_count_g);
-        counts.count_t = horizontal_sum_u8(vec_count_t);
-        counts.count_n = horizontal_sum_u8(vec_count_n);
+            // Horizontal sum to get total counts
+            counts.count_a += horizontal_sum_u8(vec_count_a);
+            counts.count_c += horizontal_sum_u8(vec_count_c);
+            counts.count_g += horizontal_sum_u8(vec_count_g);
+            counts.count_t += horizontal_sum_u8(vec_count_t);
+            counts.count_n += horizontal_sum_u8(vec_count_n);
+        }
     }
 
     // Process remainder with scalar code
@@ -413,12 +462,6 @@ fn count_bases_2bit_neon(bitseq: &BitSeq) -> BaseCounts {
     let remainder_bytes = chunks.remainder();
 
     unsafe {
-        // Accumulators for each base
-        let mut vec_count_a = vdupq_n_u8(0);
-        let mut vec_count_c = vdupq_n_u8(0);
-        let mut vec_count_g = vdupq_n_u8(0);
-        let mut vec_count_t = vdupq_n_u8(0);
-
         // Masks for extracting 2-bit pairs
         let mask_bits_67 = vdupq_n_u8(0b11000000); // Bits 6-7 (first base)
         let mask_bits_45 = vdupq_n_u8(0b00110000); // Bits 4-5 (second base)
@@ -433,45 +476,55 @@ fn count_bases_2bit_neon(bitseq: &BitSeq) -> BaseCounts {
 
         let ones = vdupq_n_u8(1);
 
-        for chunk in chunks {
-            // Load 16 bytes (64 bases)
-            let data_vec = vld1q_u8(chunk.as_ptr());
-
-            // Process each 2-bit position within the byte
-            // Position 0 (bits 6-7)
-            let bases_0 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_67), 6);
-            vec_count_a = vaddq_u8(vec_count_a, vandq_u8(vceqq_u8(bases_0, cmp_a), ones));
-            vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_0, cmp_c), ones));
-            vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_0, cmp_g), ones));
-            vec_count_t = vaddq_u8(vec_count_t, vandq_u8(vceqq_u8(bases_0, cmp_t), ones));
-
-            // Position 1 (bits 4-5)
-            let bases_1 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_45), 4);
-            vec_count_a = vaddq_u8(vec_count_a, vandq_u8(vceqq_u8(bases_1, cmp_a), ones));
-            vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_1, cmp_c), ones));
-            vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_1, cmp_g), ones));
-            vec_count_t = vaddq_u8(vec_count_t, vandq_u8(vceqq_u8(bases_1, cmp_t), ones));
-
-            // Position 2 (bits 2-3)
-            let bases_2 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_23), 2);
-            vec_count_a = vaddq_u8(vec_count_a, vandq_u8(vceqq_u8(bases_2, cmp_a), ones));
-            vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_2, cmp_c), ones));
-            vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_2, cmp_g), ones));
-            vec_count_t = vaddq_u8(vec_count_t, vandq_u8(vceqq_u8(bases_2, cmp_t), ones));
-
-            // Position 3 (bits 0-1)
-            let bases_3 = vandq_u8(data_vec, mask_bits_01);
-            vec_count_a = vaddq_u8(vec_count_a, vandq_u8(vceqq_u8(bases_3, cmp_a), ones));
-            vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_3, cmp_c), ones));
-            vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_3, cmp_g), ones));
-            vec_count_t = vaddq_u8(vec_count_t, vandq_u8(vceqq_u8(bases_3, cmp_t), ones));
-        }
+        // u8 lanes overflow after 255 increments, so flush to usize every block
+        let vector_bytes = &data[..data.len() - remainder_bytes.len()];
+        for block in vector_bytes.chunks(16 * MAX_2BIT_CHUNKS_PER_FLUSH) {
+            // Accumulators for each base
+            let mut vec_count_a = vdupq_n_u8(0);
+            let mut vec_count_c = vdupq_n_u8(0);
+            let mut vec_count_g = vdupq_n_u8(0);
+            let mut vec_count_t = vdupq_n_u8(0);
+
+            for chunk in block.chunks_exact(16) {
+                // Load 16 bytes (64 bases)
+                let data_vec = vld1q_u8(chunk.as_ptr());
+
+                // Process each 2-bit position within the byte
+                // Position 0 (bits 6-7)
+                let bases_0 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_67), 6);
+                vec_count_a = vaddq_u8(vec_count_a, vandq_u8(vceqq_u8(bases_0, cmp_a), ones));
+                vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_0, cmp_c), ones));
+                vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_0, cmp_g), ones));
+                vec_count_t = vaddq_u8(vec_count_t, vandq_u8(vceqq_u8(bases_0, cmp_t), ones));
+
+                // Position 1 (bits 4-5)
+                let bases_1 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_45), 4);
+                vec_count_a = vaddq_u8(vec_count_a, vandq_u8(vceqq_u8(bases_1, cmp_a), ones));
+                vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_1, cmp_c), ones));
+                vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_1, cmp_g), ones));
+                vec_count_t = vaddq_u8(vec_count_t, vandq_u8(vceqq_u8(bases_1, cmp_t), ones));
+
+                // Position 2 (bits 2-3)
+                let bases_2 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_23), 2);
+                vec_count_a = vaddq_u8(vec_count_a, vandq_u8(vceqq_u8(bases_2, cmp_a), ones));
+                vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_2, cmp_c), ones));
+                vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_2, cmp_g), ones));
+                vec_count_t = vaddq_u8(vec_count_t, vandq_u8(vceqq_u8(bases_2, cmp_t), ones));
+
+                // Position 3 (bits 0-1)
+                let bases_3 = vandq_u8(data_vec, mask_bits_01);
+                vec_count_a = vaddq_u8(vec_count_a, vandq_u8(vceqq_u8(bases_3, cmp_a), ones));
+                vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_3, cmp_c), ones));
+                vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_3, cmp_g), ones));
+                vec_count_t = vaddq_u8(vec_count_t, vandq_u8(vceqq_u8(bases_3, cmp_t), ones));
+            }
 
-        // Horizontal sum to get total counts
-        counts.count_a = horizontal_sum_u8(vec_count_a);
-        counts.count_c = horizontal_sum_u8(vec_count_c);
-        counts.count_g = horizontal_sum_u8(vec_count_g);
-        counts.count_t = horizontal_sum_u8(vec_count_t);
+            // Horizontal sum to get total counts
+            counts.count_a += horizontal_sum_u8(vec_count_a);
+            counts.count_c += horizontal_sum_u8(vec_count_c);
+            counts.count_g += horizontal_sum_u8(vec_count_g);
+            counts.count_t += horizontal_sum_u8(vec_count_t);
+        }
     }
 
     // Process remainder bytes with scalar code
@@ -518,8 +571,7 @@ mod tests {
 
         let result = op.execute_naive(&data).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let counts: BaseCounts = serde_json::from_value(value).unwrap();
+        if let Some(counts) = result.statistics::<BaseCounts>() {
 
             // seq1: 2A, 2C, 2G, 2T
             // seq2: 4A, 4C, 4G, 4T
@@ -536,7 +588,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(target_arch = "aarch64")]
     fn test_base_counting_neon() {
         let op = BaseCounting::new();
         let data = create_test_data();
@@ -548,6 +599,18 @@ mod tests {
         assert_eq!(result_naive, result_neon);
     }
 
+    #[test]
+    fn test_base_counting_packed_and_views() {
+        let op = BaseCounting::new();
+        let data = create_test_data();
+        let packed = PackedRecords::from_records(&data).unwrap();
+
+        assert_eq!(op.execute_naive(&data).unwrap(), op.execute_packed(&packed).unwrap());
+
+        let views: Vec<SequenceView> = data.iter().map(SequenceRecord::view).collect();
+        assert_eq!(op.execute_naive(&data).unwrap(), op.execute_views(&views).unwrap());
+    }
+
     #[test]
     fn test_base_counting_parallel() {
         let op = BaseCounting::new();
@@ -570,8 +633,7 @@ mod tests {
 
         let result = op.execute_naive(&data).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let counts: BaseCounts = serde_json::from_value(value).unwrap();
+        if let Some(counts) = result.statistics::<BaseCounts>() {
             assert_eq!(counts.count_a, 2);
             assert_eq!(counts.count_c, 2);
             assert_eq!(counts.count_g, 2);
@@ -595,8 +657,7 @@ mod tests {
 
         let result = op.execute_2bit_naive(&bitseqs).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let counts: BaseCounts = serde_json::from_value(value).unwrap();
+        if let Some(counts) = result.statistics::<BaseCounts>() {
 
             // seq1: 2A, 2C, 2G, 2T
             // seq2: 4A, 4C, 4G, 4T
@@ -647,11 +708,50 @@ mod tests {
         assert_eq!(result_naive, result_neon);
 
         // Verify counts are correct (equal A, C, G, T)
-        if let OperationOutput::Statistics(value) = result_neon {
-            let counts: BaseCounts = serde_json::from_value(value).unwrap();
+        if let Some(counts) = result_neon.statistics::<BaseCounts>() {
             assert_eq!(counts.count_a, counts.count_c);
             assert_eq!(counts.count_a, counts.count_g);
             assert_eq!(counts.count_a, counts.count_t);
         }
     }
+
+    #[test]
+    fn test_base_counting_neon_long_reads() {
+        // u8 lanes overflow after 255 chunks (~4 kb); a homopolymer is the
+        // worst case because every lane increments on every chunk
+        let op = BaseCounting::new();
+        let data = vec![
+            SequenceRecord::fasta("homopolymer".to_string(), vec![b'A'; 100_000]),
+            SequenceRecord::fasta("long_read".to_string(), b"ACGTN".repeat(20_001)),
+        ];
+
+        let result_naive = op.execute_naive(&data).unwrap();
+        let result_neon = op.execute_neon(&data).unwrap();
+        assert_eq!(result_naive, result_neon);
+        for threads in [1, 3] {
+            assert_eq!(result_naive, op.execute_parallel_intra_read(&data, threads).unwrap());
+        }
+
+        if let Some(counts) = result_neon.statistics::<BaseCounts>() {
+            assert_eq!(counts.count_a, 100_000 + 20_001);
+            assert_eq!(counts.count_n, 20_001);
+        } else {
+            panic!("Expected Statistics output");
+        }
+    }
+
+    #[test]
+    #[cfg(target_arch = "aarch64")]
+    fn test_base_counting_2bit_neon_long_reads() {
+        // 2-bit lanes gain up to 4 per chunk, overflowing after ~4 kb
+        let op = BaseCounting::new();
+        let bitseqs = vec![
+            BitSeq::from_ascii(&vec![b'T'; 50_000]),
+            BitSeq::from_ascii(&b"ACGT".repeat(25_003)),
+        ];
+
+        let result_naive = op.execute_2bit_naive(&bitseqs).unwrap();
+        let result_neon = op.execute_2bit_neon(&bitseqs).unwrap();
+        assert_eq!(result_naive, result_neon);
+    }
 }
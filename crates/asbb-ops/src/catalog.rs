@@ -0,0 +1,792 @@
+//! Built-in operations by name
+//!
+//! The one place that maps operation names to instances (with default
+//! parameters) and their registry metadata. Every front end creates
+//! operations through it: the `asbb` harnesses, `run-level1`, and the
+//! Python, C and WebAssembly bindings, so they all accept the same names.
+//! Plugins registered with `asbb_core::register_operation!` follow the
+//! built-ins; a built-in wins over a plugin of the same name.
+
+use anyhow::{anyhow, Result};
+use asbb_core::operation_registry::{
+    Backend, CostModel, OperationMetadata, OperationRegistry, OutputSize, WorkScaling,
+};
+use asbb_core::plugin;
+use std::sync::Arc;
+
+use crate::adapter_trimming::AdapterTrimming;
+use crate::at_content::ATContent;
+use crate::base_counting::BaseCounting;
+use crate::complexity_score::{ComplexityMetric, ComplexityScore, WindowedComplexity};
+#[cfg(feature = "compression")]
+use crate::compression_study::CompressionStudy;
+use crate::consensus::{Consensus, GroupKey};
+use crate::edit_distance::EditDistance;
+use crate::error_correction::ErrorCorrection;
+use crate::fastq_parsing::FastqParsing;
+use crate::gc_content::GcContent;
+use crate::gc_window::GcWindowProfile;
+use crate::hamming_distance::HammingDistance;
+use crate::kmer_counting::KmerCounting;
+use crate::kmer_embedding::KmerEmbedding;
+use crate::kmer_extraction::KmerExtraction;
+use crate::kmer_index::{KmerIndexBuild, KmerIndexLookup};
+use crate::kmer_spectrum::KmerSpectrum;
+use crate::length_filter::LengthFilter;
+use crate::length_histogram::LengthHistogram;
+use crate::length_stats::LengthStats;
+use crate::minhash_sketching::MinHashSketching;
+use crate::motif_scan::MotifScan;
+use crate::n_content::NContent;
+use crate::overrepresented::OverrepresentedSequences;
+use crate::phred_encoding::PhredConversion;
+use crate::position_content::PositionContent;
+use crate::primer_match::PrimerMatch;
+use crate::quality_aggregation::{QualityAggregation, ReadGroupKey};
+use crate::quality_denoising::QualityDenoising;
+use crate::quality_filter::QualityFilter;
+use crate::quality_statistics::{QualityStatistics, StreamingQualityStatistics};
+use crate::read_mapping::ReadMapping;
+use crate::record_sort::{RecordSort, SortKey};
+use crate::reverse_complement::ReverseComplement;
+use crate::sequence_length::SequenceLength;
+use crate::sequence_masking::SequenceMasking;
+use crate::translation::Translation;
+use crate::{OperationCategory, PrimitiveOperation};
+
+/// An operation the catalog creates by name
+pub struct BuiltinOperation {
+    /// Name accepted by `--operations` and the bindings
+    pub name: &'static str,
+
+    /// Create an instance with default parameters
+    pub create: fn() -> Result<Box<dyn PrimitiveOperation>>,
+
+    /// Metadata for the registry (category, complexity, backends, cost)
+    pub metadata: fn() -> OperationMetadata,
+}
+
+/// Every built-in operation, in registry order
+pub fn builtin_operations() -> Vec<BuiltinOperation> {
+    #[allow(unused_mut)]
+    let mut operations = vec![
+        // Element-wise operations (7)
+        BuiltinOperation {
+            name: "base_counting",
+            create: || Ok(Box::new(BaseCounting::new())),
+            metadata: || OperationMetadata {
+                name: "base_counting".to_string(),
+                category: OperationCategory::ElementWise,
+                complexity: 0.40,
+                backends: vec![
+                    Backend::Naive, Backend::Neon, Backend::Parallel, Backend::ParallelIntraRead,
+                ],
+                implemented: true,
+                description: Some("Count A, C, G, T bases".to_string()),
+                cost: Some(CostModel::new(1.0, 4.0, OutputSize::Fixed(40.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "gc_content",
+            create: || Ok(Box::new(GcContent::new())),
+            metadata: || OperationMetadata {
+                name: "gc_content".to_string(),
+                category: OperationCategory::ElementWise,
+                complexity: 0.315,
+                backends: vec![
+                    Backend::Naive, Backend::Neon, Backend::Parallel, Backend::ParallelIntraRead,
+                ],
+                implemented: true,
+                description: Some("Calculate GC percentage".to_string()),
+                cost: Some(CostModel::new(1.0, 2.0, OutputSize::Fixed(24.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "gc_window_profile",
+            create: || Ok(Box::new(GcWindowProfile::default())),
+            metadata: || OperationMetadata {
+                name: "gc_window_profile".to_string(),
+                category: OperationCategory::ElementWise,
+                complexity: 0.40,
+                backends: vec![
+                    Backend::Naive, Backend::Neon, Backend::Parallel, Backend::ParallelChunked,
+                ],
+                implemented: true,
+                description: Some("Per-read sliding-window GC tracks".to_string()),
+                cost: Some(CostModel::new(1.0, 2.0, OutputSize::PerBase(0.8))),
+            },
+        },
+        BuiltinOperation {
+            name: "at_content",
+            create: || Ok(Box::new(ATContent)),
+            metadata: || OperationMetadata {
+                name: "at_content".to_string(),
+                category: OperationCategory::ElementWise,
+                complexity: 0.35,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Calculate AT percentage".to_string()),
+                cost: Some(CostModel::new(1.0, 2.0, OutputSize::Fixed(24.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "sequence_length",
+            create: || Ok(Box::new(SequenceLength)),
+            metadata: || OperationMetadata {
+                name: "sequence_length".to_string(),
+                category: OperationCategory::ElementWise,
+                complexity: 0.20,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Measure sequence lengths".to_string()),
+                cost: Some(CostModel::new(0.0, 0.0, OutputSize::Fixed(48.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "complexity_score",
+            create: || Ok(Box::new(ComplexityScore::new())),
+            metadata: || OperationMetadata {
+                name: "complexity_score".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.61,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel, Backend::Gpu],
+                implemented: true,
+                description: Some("Shannon entropy calculation".to_string()),
+                cost: Some(CostModel::new(1.0, 6.0, OutputSize::PerRecord(8.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "translation",
+            create: || Ok(Box::new(Translation::new(0, 1).unwrap())),
+            metadata: || OperationMetadata {
+                name: "translation".to_string(),
+                category: OperationCategory::ElementWise,
+                complexity: 0.40,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("DNA/RNA to protein translation".to_string()),
+                cost: Some(CostModel::new(1.0, 1.0, OutputSize::PerBase(0.34))),
+            },
+        },
+
+        // Filtering operations (4)
+        BuiltinOperation {
+            name: "quality_filter",
+            create: || Ok(Box::new(QualityFilter::new(20))),
+            metadata: || OperationMetadata {
+                name: "quality_filter".to_string(),
+                category: OperationCategory::Filter,
+                complexity: 0.55,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Filter by quality threshold".to_string()),
+                cost: Some(CostModel::new(2.0, 1.0, OutputSize::PerBase(2.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "length_filter",
+            create: || Ok(Box::new(LengthFilter::new(50))),
+            metadata: || OperationMetadata {
+                name: "length_filter".to_string(),
+                category: OperationCategory::Filter,
+                complexity: 0.25,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Filter by length range".to_string()),
+                cost: Some(CostModel::new(2.0, 0.0, OutputSize::PerBase(2.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "sequence_masking",
+            create: || Ok(Box::new(SequenceMasking::new())),
+            metadata: || OperationMetadata {
+                name: "sequence_masking".to_string(),
+                category: OperationCategory::Filter,
+                complexity: 0.30,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Mask low-quality bases".to_string()),
+                cost: Some(CostModel::new(2.0, 1.0, OutputSize::PerBase(2.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "adapter_trimming",
+            create: || Ok(Box::new(AdapterTrimming::new(b"AGATCGGAAGAGC".to_vec(), 5, 0))),
+            metadata: || OperationMetadata {
+                name: "adapter_trimming".to_string(),
+                category: OperationCategory::Filter,
+                complexity: 0.55,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Detect and remove adapters".to_string()),
+                cost: Some(CostModel::new(2.0, 13.0, OutputSize::PerBase(2.0))),
+            },
+        },
+
+        // Aggregation operations (11)
+        BuiltinOperation {
+            name: "quality_aggregation",
+            create: || Ok(Box::new(QualityAggregation::new())),
+            metadata: || OperationMetadata {
+                name: "quality_aggregation".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.50,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Per-position quality stats".to_string()),
+                cost: Some(CostModel::new(1.0, 3.0, OutputSize::Fixed(48.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "n_content",
+            create: || Ok(Box::new(NContent)),
+            metadata: || OperationMetadata {
+                name: "n_content".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.38,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Calculate N-base percentage".to_string()),
+                cost: Some(CostModel::new(1.0, 1.0, OutputSize::Fixed(24.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "quality_statistics",
+            create: || Ok(Box::new(QualityStatistics::new())),
+            metadata: || OperationMetadata {
+                name: "quality_statistics".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.38,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Mean, median, quartiles".to_string()),
+                cost: Some(CostModel::new(1.0, 3.0, OutputSize::Fixed(4800.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "minhash_sketching",
+            create: || Ok(Box::new(MinHashSketching::new(21, 1000))),
+            metadata: || OperationMetadata {
+                name: "minhash_sketching".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.48,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Sequence similarity sketches".to_string()),
+                cost: Some(CostModel::new(1.0, 4.0, OutputSize::Fixed(8000.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "length_histogram",
+            create: || Ok(Box::new(LengthHistogram::default())),
+            metadata: || OperationMetadata {
+                name: "length_histogram".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.25,
+                backends: vec![Backend::Naive, Backend::Parallel, Backend::ParallelChunked],
+                implemented: true,
+                description: Some("Binned length counts".to_string()),
+                cost: Some(CostModel::new(0.0, 0.0, OutputSize::Fixed(1024.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "length_stats",
+            create: || Ok(Box::new(LengthStats)),
+            metadata: || OperationMetadata {
+                name: "length_stats".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.35,
+                backends: vec![Backend::Naive, Backend::Parallel, Backend::ParallelChunked],
+                implemented: true,
+                description: Some("N50/N90, auN, median length (sort-based)".to_string()),
+                cost: Some(CostModel::new(0.0, 0.0, OutputSize::Fixed(72.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "record_sort",
+            create: || Ok(Box::new(RecordSort::new(SortKey::GcFraction))),
+            metadata: || OperationMetadata {
+                name: "record_sort".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.45,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some(
+                    "Sort records by GC fraction (radix vs comparison sort)".to_string(),
+                ),
+                cost: Some(CostModel::new(1.0, 2.0, OutputSize::PerBase(2.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "windowed_complexity",
+            create: || Ok(Box::new(WindowedComplexity::new(ComplexityMetric::Entropy, 32))),
+            metadata: || OperationMetadata {
+                name: "windowed_complexity".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.55,
+                backends: vec![
+                    Backend::Naive, Backend::Neon, Backend::Parallel, Backend::ParallelChunked,
+                ],
+                implemented: true,
+                description: Some("Sliding-window entropy/DUST".to_string()),
+                cost: Some(CostModel::new(1.0, 6.0, OutputSize::PerBase(0.25))),
+            },
+        },
+        BuiltinOperation {
+            name: "kmer_spectrum",
+            create: || Ok(Box::new(KmerSpectrum::new(21, true))),
+            metadata: || OperationMetadata {
+                name: "kmer_spectrum".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.55,
+                backends: vec![Backend::Naive, Backend::Parallel],
+                implemented: true,
+                description: Some("K-mer multiplicity histogram".to_string()),
+                cost: Some(CostModel::new(1.0, 4.0, OutputSize::Fixed(1024.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "position_content",
+            create: || Ok(Box::new(PositionContent::new())),
+            metadata: || OperationMetadata {
+                name: "position_content".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.30,
+                backends: vec![Backend::Naive, Backend::Parallel, Backend::ParallelChunked],
+                implemented: true,
+                description: Some("Per-position base content".to_string()),
+                cost: Some(CostModel::new(1.0, 5.0, OutputSize::Fixed(6000.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "overrepresented_sequences",
+            create: || Ok(Box::new(OverrepresentedSequences::default())),
+            metadata: || OperationMetadata {
+                name: "overrepresented_sequences".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.50,
+                backends: vec![Backend::Naive, Backend::Parallel, Backend::ParallelChunked],
+                implemented: true,
+                description: Some("Frequent read prefixes".to_string()),
+                cost: Some(CostModel::new(1.0, 1.0, OutputSize::Fixed(4096.0))),
+            },
+        },
+
+        // Pairwise operations (2)
+        BuiltinOperation {
+            name: "hamming_distance",
+            create: || Ok(Box::new(HammingDistance::new())),
+            metadata: || OperationMetadata {
+                name: "hamming_distance".to_string(),
+                category: OperationCategory::Pairwise,
+                complexity: 0.35,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Pairwise Hamming distance".to_string()),
+                cost: Some(CostModel::new(1.0, 1.0, OutputSize::PerPair(8.0))
+                    .with_scaling(WorkScaling::AllPairs { max_sequences: None })),
+            },
+        },
+        BuiltinOperation {
+            name: "edit_distance",
+            create: || Ok(Box::new(EditDistance::new(1000))),
+            metadata: || OperationMetadata {
+                name: "edit_distance".to_string(),
+                category: OperationCategory::Pairwise,
+                complexity: 0.70,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Levenshtein distance (DP)".to_string()),
+                cost: Some(CostModel::new(1.0, 3.0, OutputSize::PerPair(8.0))
+                    .with_scaling(WorkScaling::AllPairsQuadratic { max_sequences: Some(1000) })),
+            },
+        },
+
+        // Search operations (4)
+        BuiltinOperation {
+            name: "kmer_counting",
+            create: || Ok(Box::new(KmerCounting::new(21, false))),
+            metadata: || OperationMetadata {
+                name: "kmer_counting".to_string(),
+                category: OperationCategory::Search,
+                complexity: 0.45,
+                backends: vec![
+                    Backend::Naive, Backend::Neon, Backend::Parallel, Backend::ParallelIntraRead,
+                ],
+                implemented: true,
+                description: Some("K-mer frequency counting".to_string()),
+                cost: Some(CostModel::new(1.0, 4.0, OutputSize::PerBase(12.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "kmer_extraction",
+            create: || Ok(Box::new(KmerExtraction::new(21, false))),
+            metadata: || OperationMetadata {
+                name: "kmer_extraction".to_string(),
+                category: OperationCategory::Search,
+                complexity: 0.35,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Extract k-mers as records".to_string()),
+                cost: Some(CostModel::new(1.0, 2.0, OutputSize::PerBase(8.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "motif_scan",
+            create: || Ok(Box::new(MotifScan::default())),
+            metadata: || OperationMetadata {
+                name: "motif_scan".to_string(),
+                category: OperationCategory::Search,
+                complexity: 0.50,
+                backends: vec![
+                    Backend::Naive, Backend::Neon, Backend::Parallel, Backend::ParallelChunked,
+                ],
+                implemented: true,
+                description: Some("Telomere/restriction motif hits".to_string()),
+                cost: Some(CostModel::new(1.0, 8.0, OutputSize::PerRecord(16.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "primer_match",
+            create: || Ok(Box::new(PrimerMatch::v4_16s()?)),
+            metadata: || OperationMetadata {
+                name: "primer_match".to_string(),
+                category: OperationCategory::Search,
+                complexity: 0.60,
+                backends: vec![
+                    Backend::Naive, Backend::Neon, Backend::Parallel, Backend::ParallelChunked,
+                ],
+                implemented: true,
+                description: Some("Approximate primer matching (Myers)".to_string()),
+                cost: Some(CostModel::new(1.0, 20.0, OutputSize::PerRecord(16.0))),
+            },
+        },
+
+        // Transform operations (2) - use ElementWise category
+        BuiltinOperation {
+            name: "reverse_complement",
+            create: || Ok(Box::new(ReverseComplement::new())),
+            metadata: || OperationMetadata {
+                name: "reverse_complement".to_string(),
+                category: OperationCategory::ElementWise,
+                complexity: 0.45,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Reverse complement sequences".to_string()),
+                cost: Some(CostModel::new(1.0, 1.0, OutputSize::PerBase(1.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "error_correction",
+            create: || Ok(Box::new(ErrorCorrection::default())),
+            metadata: || OperationMetadata {
+                name: "error_correction".to_string(),
+                category: OperationCategory::ElementWise,
+                complexity: 0.65,
+                backends: vec![Backend::Naive, Backend::Parallel],
+                implemented: true,
+                description: Some("K-mer spectrum error correction".to_string()),
+                cost: Some(CostModel::new(2.0, 8.0, OutputSize::PerBase(2.0))),
+            },
+        },
+
+        // I/O operations (2)
+        BuiltinOperation {
+            name: "fastq_parsing",
+            create: || Ok(Box::new(FastqParsing::new(true))),
+            metadata: || OperationMetadata {
+                name: "fastq_parsing".to_string(),
+                category: OperationCategory::IO,
+                complexity: 0.25,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Parse FASTQ format".to_string()),
+                cost: Some(CostModel::new(2.0, 1.0, OutputSize::PerBase(2.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "phred_conversion",
+            create: || Ok(Box::new(PhredConversion::default())),
+            metadata: || OperationMetadata {
+                name: "phred_conversion".to_string(),
+                category: OperationCategory::IO,
+                complexity: 0.30,
+                backends: vec![
+                    Backend::Naive, Backend::Neon, Backend::Parallel, Backend::ParallelChunked,
+                ],
+                implemented: true,
+                description: Some("Detect and convert quality encoding".to_string()),
+                cost: Some(CostModel::new(1.0, 1.0, OutputSize::PerBase(1.0))),
+            },
+        },
+
+        // Parameter variants of the operations above
+        BuiltinOperation {
+            name: "quality_aggregation_by_tile",
+            create: || Ok(Box::new(QualityAggregation::grouped_by(ReadGroupKey::Tile))),
+            metadata: || OperationMetadata {
+                name: "quality_aggregation_by_tile".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.50,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Quality stats per flow cell tile".to_string()),
+                cost: Some(CostModel::new(1.0, 4.0, OutputSize::Fixed(4096.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "quality_statistics_streaming",
+            create: || Ok(Box::new(StreamingQualityStatistics::new())),
+            metadata: || OperationMetadata {
+                name: "quality_statistics_streaming".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.30,
+                backends: vec![Backend::Naive, Backend::Parallel],
+                implemented: true,
+                description: Some("Quality statistics from per-position histograms".to_string()),
+                cost: Some(CostModel::new(1.0, 1.0, OutputSize::Fixed(4800.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "record_sort_by_length",
+            create: || Ok(Box::new(RecordSort::new(SortKey::Length))),
+            metadata: || OperationMetadata {
+                name: "record_sort_by_length".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.35,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Sort records by length".to_string()),
+                cost: Some(CostModel::new(0.0, 2.0, OutputSize::PerBase(2.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "record_sort_by_id",
+            create: || Ok(Box::new(RecordSort::new(SortKey::Id))),
+            metadata: || OperationMetadata {
+                name: "record_sort_by_id".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.45,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel],
+                implemented: true,
+                description: Some("Sort records by read ID".to_string()),
+                cost: Some(CostModel::new(0.0, 4.0, OutputSize::PerBase(2.0))),
+            },
+        },
+
+        // Level 2 composites
+        BuiltinOperation {
+            name: "kmer_index_build",
+            create: || Ok(Box::new(KmerIndexBuild::new(21))),
+            metadata: || OperationMetadata {
+                name: "kmer_index_build".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.45,
+                backends: vec![Backend::Naive, Backend::Parallel, Backend::ParallelChunked],
+                implemented: true,
+                description: Some("Canonical k-mer position index".to_string()),
+                cost: Some(CostModel::new(1.0, 4.0, OutputSize::PerBase(12.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "kmer_index_lookup",
+            create: || Ok(Box::new(KmerIndexLookup::default())),
+            metadata: || OperationMetadata {
+                name: "kmer_index_lookup".to_string(),
+                category: OperationCategory::Search,
+                complexity: 0.50,
+                backends: vec![
+                    Backend::Naive, Backend::Neon, Backend::Parallel, Backend::ParallelChunked,
+                ],
+                implemented: true,
+                description: Some("K-mer probes into a prebuilt index".to_string()),
+                cost: Some(CostModel::new(1.0, 2.0, OutputSize::Fixed(64.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "read_mapping",
+            create: || Ok(Box::new(ReadMapping::default())),
+            metadata: || OperationMetadata {
+                name: "read_mapping".to_string(),
+                category: OperationCategory::Search,
+                complexity: 0.75,
+                backends: vec![
+                    Backend::Naive, Backend::Neon, Backend::Parallel, Backend::ParallelChunked,
+                ],
+                implemented: true,
+                description: Some("Seed-and-extend read mapping".to_string()),
+                cost: Some(CostModel::new(1.0, 30.0, OutputSize::Fixed(4096.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "consensus",
+            create: || Ok(Box::new(Consensus::default())),
+            metadata: || OperationMetadata {
+                name: "consensus".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.55,
+                backends: vec![
+                    Backend::Naive, Backend::Neon, Backend::Parallel, Backend::ParallelChunked,
+                ],
+                implemented: true,
+                description: Some("Consensus read per UMI group".to_string()),
+                cost: Some(CostModel::new(2.0, 4.0, OutputSize::PerBase(2.0))),
+            },
+        },
+        // Groups of ~10 reads in the standard datasets (seq_1230..seq_1239)
+        BuiltinOperation {
+            name: "consensus_by_id_prefix",
+            create: || Ok(Box::new(Consensus::new(GroupKey::IdPrefix(8)))),
+            metadata: || OperationMetadata {
+                name: "consensus_by_id_prefix".to_string(),
+                category: OperationCategory::Aggregation,
+                complexity: 0.55,
+                backends: vec![
+                    Backend::Naive, Backend::Neon, Backend::Parallel, Backend::ParallelChunked,
+                ],
+                implemented: true,
+                description: Some("Consensus read per read-ID prefix".to_string()),
+                cost: Some(CostModel::new(2.0, 4.0, OutputSize::PerBase(2.0))),
+            },
+        },
+
+        // AMX and Neural Engine workloads
+        BuiltinOperation {
+            name: "quality_denoising",
+            create: || Ok(Box::new(QualityDenoising::new())),
+            metadata: || OperationMetadata {
+                name: "quality_denoising".to_string(),
+                category: OperationCategory::ElementWise,
+                complexity: 0.40,
+                backends: vec![Backend::Naive, Backend::Neon, Backend::Parallel, Backend::Amx],
+                implemented: true,
+                description: Some("Smooth quality strings (1-D convolution)".to_string()),
+                cost: Some(CostModel::new(1.0, 5.0, OutputSize::PerBase(2.0))),
+            },
+        },
+        BuiltinOperation {
+            name: "kmer_embedding",
+            create: || Ok(Box::new(KmerEmbedding::default())),
+            metadata: || OperationMetadata {
+                name: "kmer_embedding".to_string(),
+                category: OperationCategory::Pairwise,
+                complexity: 0.60,
+                backends: vec![
+                    Backend::Naive, Backend::Neon, Backend::Parallel, Backend::Gpu, Backend::Neural,
+                ],
+                implemented: true,
+                description: Some("K-mer embedding similarity pre-filter".to_string()),
+                cost: Some(CostModel::new(1.0, 4.0, OutputSize::PerPair(8.0))
+                    .with_scaling(WorkScaling::AllPairs { max_sequences: None })),
+            },
+        },
+    ];
+
+    // I/O operations behind the `compression` feature (zstd is a C library)
+    #[cfg(feature = "compression")]
+    operations.push(BuiltinOperation {
+        name: "compression_study",
+        create: || Ok(Box::new(CompressionStudy::default())),
+        metadata: || OperationMetadata {
+            name: "compression_study".to_string(),
+            category: OperationCategory::IO,
+            complexity: 0.60,
+            backends: vec![Backend::Naive, Backend::Parallel, Backend::ParallelChunked],
+            implemented: true,
+            description: Some("Codec ratio vs throughput (zstd, LZ4)".to_string()),
+            cost: Some(CostModel::new(2.0, 10.0, OutputSize::Fixed(1024.0))),
+        },
+    });
+
+    operations
+}
+
+/// Names [`create_operation`] accepts: built-ins, then plugins
+pub fn available_operations() -> Vec<String> {
+    let mut names: Vec<String> =
+        builtin_operations().iter().map(|builtin| builtin.name.to_string()).collect();
+    for plugin in plugin::plugins() {
+        if !names.iter().any(|name| name == plugin.name) {
+            names.push(plugin.name.to_string());
+        }
+    }
+    names
+}
+
+/// Create an operation by name (built-in, else a registered plugin)
+pub fn create_operation(name: &str) -> Result<Box<dyn PrimitiveOperation>> {
+    if let Some(builtin) = builtin_operations().into_iter().find(|builtin| builtin.name == name) {
+        return (builtin.create)();
+    }
+    plugin::create(name).ok_or_else(|| {
+        anyhow!("Unknown operation: {} (available: {})", name, available_operations().join(", "))
+    })
+}
+
+/// Metadata of a built-in or plugin operation
+pub fn operation_metadata(name: &str) -> Option<OperationMetadata> {
+    match builtin_operations().into_iter().find(|builtin| builtin.name == name) {
+        Some(builtin) => Some((builtin.metadata)()),
+        None => plugin::plugins()
+            .into_iter()
+            .find(|plugin| plugin.name == name)
+            .map(|plugin| (plugin.metadata)()),
+    }
+}
+
+/// Registry of every built-in operation, then the registered plugins
+pub fn registry() -> Result<OperationRegistry> {
+    let mut registry = OperationRegistry::new();
+    for builtin in builtin_operations() {
+        registry.register(Arc::from((builtin.create)()?), (builtin.metadata)());
+    }
+    plugin::register_all(&mut registry);
+    Ok(registry)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtins_match_their_metadata() {
+        let builtins = builtin_operations();
+        for builtin in &builtins {
+            let metadata = (builtin.metadata)();
+            assert_eq!(metadata.name, builtin.name);
+            assert!(metadata.has_backend(Backend::Naive), "{}", builtin.name);
+            // Variants (`record_sort_by_length`) keep their base operation's name
+            let operation = create_operation(builtin.name).unwrap();
+            assert!(builtin.name.starts_with(operation.name()), "{}", builtin.name);
+        }
+
+        let names = available_operations();
+        assert_eq!(names.len(), builtins.len());
+        assert!(names.iter().any(|name| name == "read_mapping"));
+        assert_eq!(operation_metadata("consensus").unwrap().complexity, 0.55);
+
+        let error = create_operation("nope").err().unwrap().to_string();
+        assert!(error.starts_with("Unknown operation: nope (available: base_counting,"));
+    }
+
+    #[test]
+    fn test_level1_operations_are_builtin() {
+        // run-level1 registers the catalog; every listed operation must be in it
+        let config = include_str!("../../../experiments/level1_primitives/config.toml");
+        let names = available_operations();
+        let listed: Vec<&str> = config
+            .split("[[operations.list]]")
+            .skip(1)
+            .filter_map(|section| section.lines().find_map(|line| line.strip_prefix("name = ")))
+            .map(|name| name.trim_matches('"'))
+            .collect();
+        assert_eq!(listed.len(), 20);
+        for name in listed {
+            assert!(names.iter().any(|builtin| builtin == name), "{}", name);
+        }
+    }
+}
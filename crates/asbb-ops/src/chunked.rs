@@ -0,0 +1,226 @@
+//! Chunked parallel execution
+//!
+//! `execute_parallel` schedules one Rayon task per record. For 150 bp reads
+//! the scheduling cost is comparable to the work itself, which caps parallel
+//! speedup at small and medium scales. The chunked strategy instead splits
+//! the records into about `num_threads * CHUNKS_PER_THREAD` contiguous chunks,
+//! runs the operation's NEON path over each chunk on a single thread, and
+//! reduces the per-chunk results.
+//!
+//! Several chunks per thread (rather than exactly one) let work stealing
+//! balance chunks whose reads differ in length. A configured batch size
+//! (`HardwareConfig::batch_size`) fixes the records per chunk instead, so
+//! sweeps can trade cache residency against per-chunk overhead.
+
+use anyhow::{Context, Result};
+use asbb_core::{OperationOutput, PrimitiveOperation, SequenceRecord, TypedResult};
+use rayon::prelude::*;
+
+/// Chunks per thread
+pub const CHUNKS_PER_THREAD: usize = 4;
+
+/// Records per chunk: `batch_size` if given, otherwise `num_records` split
+/// into [`CHUNKS_PER_THREAD`] chunks per thread
+pub fn chunk_size(num_records: usize, num_threads: usize, batch_size: Option<usize>) -> usize {
+    batch_size
+        .unwrap_or_else(|| num_records.div_ceil(num_threads.max(1) * CHUNKS_PER_THREAD))
+        .max(1)
+}
+
+/// Map contiguous chunks in parallel and reduce the results in record order
+///
+/// Returns `None` for empty input. Chunks borrow from `data`, so per-chunk
+/// results may hold references into the records.
+pub fn map_reduce<'a, T, M, R>(
+    data: &'a [SequenceRecord],
+    num_threads: usize,
+    batch_size: Option<usize>,
+    map: M,
+    reduce: R,
+) -> Result<Option<T>>
+where
+    T: Send,
+    M: Fn(&'a [SequenceRecord]) -> Result<T> + Sync + Send,
+    R: Fn(T, T) -> T + Sync + Send,
+{
+    let pool = crate::thread_pool::get(num_threads)?;
+    let size = chunk_size(data.len(), num_threads, batch_size);
+
+    pool.install(|| {
+        data.par_chunks(size)
+            .map(map)
+            .try_reduce_with(|a, b| Ok(reduce(a, b)))
+            .transpose()
+    })
+}
+
+/// Chunked execution of an operation whose NEON path returns a typed `T`
+///
+/// Per-chunk results are combined with `merge`; the caller finalizes derived
+/// fields (means, percentages) on the merged result.
+pub fn reduce_neon<T, R>(
+    operation: &dyn PrimitiveOperation,
+    data: &[SequenceRecord],
+    num_threads: usize,
+    batch_size: Option<usize>,
+    merge: R,
+) -> Result<Option<T>>
+where
+    T: TypedResult + Clone,
+    R: Fn(T, T) -> T + Sync + Send,
+{
+    map_reduce(
+        data,
+        num_threads,
+        batch_size,
+        |chunk| {
+            operation
+                .execute_neon(chunk)?
+                .statistics::<T>()
+                .cloned()
+                .with_context(|| {
+                    format!(
+                        "{} NEON output is not a {}",
+                        operation.name(),
+                        std::any::type_name::<T>()
+                    )
+                })
+        },
+        merge,
+    )
+}
+
+/// Chunked execution of a record-producing operation (output order preserved)
+pub fn concat_neon(
+    operation: &dyn PrimitiveOperation,
+    data: &[SequenceRecord],
+    num_threads: usize,
+    batch_size: Option<usize>,
+) -> Result<OperationOutput> {
+    let records = map_reduce(
+        data,
+        num_threads,
+        batch_size,
+        |chunk| match operation.execute_neon(chunk)? {
+            OperationOutput::Records(records) => Ok(records),
+            _ => anyhow::bail!("{} NEON output is not a record list", operation.name()),
+        },
+        |mut a, b| {
+            a.extend(b);
+            a
+        },
+    )?;
+
+    Ok(OperationOutput::Records(records.unwrap_or_default()))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_size() {
+        assert_eq!(chunk_size(1000, 4, None), 63); // 16 chunks
+        assert_eq!(chunk_size(10, 4, None), 1);
+        assert_eq!(chunk_size(0, 4, None), 1);
+        assert_eq!(chunk_size(100, 0, None), 25);
+        assert_eq!(chunk_size(1000, 4, Some(100)), 100);
+        assert_eq!(chunk_size(1000, 4, Some(0)), 1);
+    }
+
+    #[test]
+    fn test_map_reduce_preserves_order() {
+        let data: Vec<SequenceRecord> = (0..103)
+            .map(|i| SequenceRecord::fasta(i.to_string(), vec![b'A'; i]))
+            .collect();
+
+        let ids = map_reduce(
+            &data,
+            3,
+            None,
+            |chunk| Ok(chunk.iter().map(|r| r.id.clone()).collect::<Vec<_>>()),
+            |mut a, b| {
+                a.extend(b);
+                a
+            },
+        )
+        .unwrap()
+        .unwrap();
+        let expected: Vec<String> = (0..103).map(|i| i.to_string()).collect();
+        assert_eq!(ids, expected);
+
+        let empty = map_reduce(&[], 3, None, |chunk| Ok(chunk.len()), |a, b| a + b).unwrap();
+        assert_eq!(empty, None);
+    }
+
+    #[test]
+    fn test_chunked_matches_naive() {
+        use crate::*;
+        use asbb_core::compare::{outputs_match, Tolerance};
+
+        let data: Vec<SequenceRecord> = (0..257)
+            .map(|i| {
+                let sequence: Vec<u8> = b"ACGTNACCGGTTAAAT"
+                    .iter()
+                    .cycle()
+                    .skip(i % 7)
+                    .take(60 + i % 90)
+                    .copied()
+                    .collect();
+                let quality = (0..sequence.len()).map(|j| b'!' + ((i + j) % 41) as u8).collect();
+                SequenceRecord::fastq(format!("read_{}", i), sequence, quality)
+            })
+            .collect();
+
+        let operations: Vec<Box<dyn PrimitiveOperation>> = vec![
+            Box::new(base_counting::BaseCounting::new()),
+            Box::new(gc_content::GcContent::new()),
+            Box::new(gc_window::GcWindowProfile::default()),
+            Box::new(at_content::ATContent),
+            Box::new(n_content::NContent),
+            Box::new(reverse_complement::ReverseComplement::new()),
+            Box::new(error_correction::ErrorCorrection::new(11, 2)),
+            Box::new(sequence_length::SequenceLength),
+            Box::new(length_histogram::LengthHistogram::new(25)),
+            Box::new(length_stats::LengthStats),
+            Box::new(kmer_spectrum::KmerSpectrum::new(11, true)),
+            Box::new(position_content::PositionContent::new()),
+            Box::new(overrepresented::OverrepresentedSequences::new(20, 0.01)),
+            Box::new(quality_aggregation::QualityAggregation::new()),
+            Box::new(quality_filter::QualityFilter::new(20)),
+            Box::new(phred_encoding::PhredConversion::new(phred_encoding::PhredEncoding::Phred64)),
+            Box::new(length_filter::LengthFilter::new(100)),
+            Box::new(motif_scan::MotifScan::default()),
+            Box::new(primer_match::PrimerMatch::v4_16s().unwrap()),
+            Box::new(complexity_score::ComplexityScore::new()),
+            Box::new(complexity_score::WindowedComplexity::new(complexity_score::ComplexityMetric::Dust, 32)),
+        ];
+
+        for op in &operations {
+            let naive = op.execute_naive(&data).unwrap();
+            for threads in [1, 3, 8] {
+                let chunked = op.execute_parallel_chunked(&data, threads).unwrap();
+                assert!(
+                    outputs_match(&naive, &chunked, &Tolerance::default()),
+                    "{} ({} threads)",
+                    op.name(),
+                    threads
+                );
+            }
+            // Batches smaller and larger than the default chunk
+            for batch_size in [1, 10, 1000] {
+                let batched = op.execute_parallel_batched(&data, 3, Some(batch_size)).unwrap();
+                assert!(
+                    outputs_match(&naive, &batched, &Tolerance::default()),
+                    "{} (batch {})",
+                    op.name(),
+                    batch_size
+                );
+            }
+        }
+    }
+}
@@ -2,6 +2,14 @@
 //
 // Calculates sequence complexity (character diversity, entropy-like metric).
 // Complexity: ~0.45 (multiple counters + simple calculation)
+//
+// WindowedComplexity scores sliding windows instead of whole reads, with
+// Shannon entropy (ACGT composition) or DUST (triplet repetitiveness, as in
+// BLAST's dust/sdust) per window. A read is low-complexity if any window is.
+// This is windowed aggregation: a small histogram rebuilt per window.
+// - NEON entropy: 4 base histograms accumulated 16 bytes per compare
+// - NEON DUST: 2-bit codes and triplet codes for 16 positions per step,
+//   then a 64-bin histogram per window from the precomputed codes
 
 use crate::PrimitiveOperation;
 use asbb_core::{HardwareConfig, OperationCategory, OperationOutput, SequenceRecord};
@@ -105,7 +113,7 @@ impl PrimitiveOperation for ComplexityScore {
             high_complexity_count: high_count,
         };
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
@@ -118,9 +126,7 @@ impl PrimitiveOperation for ComplexityScore {
         data: &[SequenceRecord],
         num_threads: usize,
     ) -> Result<OperationOutput> {
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()?;
+        let pool = crate::thread_pool::get(num_threads)?;
 
         let (total_complexity, low_count, high_count) = pool.install(|| {
             data.par_iter()
@@ -145,7 +151,44 @@ impl PrimitiveOperation for ComplexityScore {
             high_complexity_count: high_count,
         };
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let (total_complexity, low_count, high_count) = crate::chunked::map_reduce(
+            data,
+            num_threads,
+            batch_size,
+            |chunk| {
+                let mut totals = (0.0, 0, 0);
+                for record in chunk {
+                    let complexity = calculate_complexity(&record.sequence);
+                    totals.0 += complexity;
+                    if complexity < 0.4 {
+                        totals.1 += 1;
+                    } else if complexity > 0.7 {
+                        totals.2 += 1;
+                    }
+                }
+                Ok(totals)
+            },
+            |(sum1, low1, high1), (sum2, low2, high2)| (sum1 + sum2, low1 + low2, high1 + high2),
+        )?
+        .unwrap_or((0.0, 0, 0));
+
+        let result = ComplexityResult {
+            total_sequences: data.len(),
+            mean_complexity: if data.is_empty() { 0.0 } else { total_complexity / data.len() as f64 },
+            low_complexity_count: low_count,
+            high_complexity_count: high_count,
+        };
+
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_with_config(
@@ -154,7 +197,7 @@ impl PrimitiveOperation for ComplexityScore {
         config: &HardwareConfig,
     ) -> Result<OperationOutput> {
         if config.num_threads > 1 {
-            self.execute_parallel(data, config.num_threads)
+            self.execute_threaded(data, config)
         } else {
             self.execute_naive(data)
         }
@@ -181,6 +224,450 @@ fn calculate_complexity(seq: &[u8]) -> f64 {
     unique as f64 / max_unique as f64
 }
 
+// ============================================================================
+// Windowed Complexity (entropy / DUST)
+// ============================================================================
+
+/// Per-window complexity metric
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplexityMetric {
+    /// Shannon entropy of ACGT composition, normalised to 0-1 (low = simple)
+    Entropy,
+    /// DUST score: Σ c(c-1)/2 / (l-1) over triplet counts (high = repetitive)
+    Dust,
+}
+
+impl ComplexityMetric {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ComplexityMetric::Entropy => "entropy",
+            ComplexityMetric::Dust => "dust",
+        }
+    }
+
+    /// Conventional low-complexity cut-off
+    pub fn default_threshold(&self) -> f64 {
+        match self {
+            ComplexityMetric::Entropy => 0.5,
+            ComplexityMetric::Dust => 20.0,
+        }
+    }
+
+    /// Whether a window score counts as low complexity
+    fn is_low(&self, score: f64, threshold: f64) -> bool {
+        match self {
+            ComplexityMetric::Entropy => score < threshold,
+            ComplexityMetric::Dust => score > threshold,
+        }
+    }
+}
+
+/// Sliding-window complexity (entropy or DUST)
+pub struct WindowedComplexity {
+    pub metric: ComplexityMetric,
+    pub window: usize,
+    pub step: usize,
+    pub threshold: f64,
+}
+
+impl WindowedComplexity {
+    /// Windows of `window` bases every `window / 2` bases, default threshold
+    pub fn new(metric: ComplexityMetric, window: usize) -> Self {
+        assert!(window > 0, "Window must be at least one base");
+        Self {
+            metric,
+            window,
+            step: (window / 2).max(1),
+            threshold: metric.default_threshold(),
+        }
+    }
+
+    pub fn with_step(mut self, step: usize) -> Self {
+        assert!(step > 0, "Step must be at least one base");
+        self.step = step;
+        self
+    }
+
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Window start positions covering the whole read
+    ///
+    /// Reads shorter than the window form one window; a final window is
+    /// aligned to the read end if the step leaves bases uncovered.
+    fn window_starts(&self, len: usize) -> impl Iterator<Item = usize> {
+        let (window, step) = (self.window.min(len), self.step);
+        let last = len.saturating_sub(window);
+        let regular = if len == 0 { 0 } else { last / step + 1 };
+        let trailing = (len > 0 && last % step != 0).then_some(last);
+        (0..regular).map(move |i| i * step).chain(trailing)
+    }
+
+    /// Window scores for one read
+    fn scores(&self, seq: &[u8], simd: bool) -> Vec<f64> {
+        let simd = simd && cfg!(target_arch = "aarch64");
+        let window = self.window.min(seq.len());
+
+        match self.metric {
+            ComplexityMetric::Entropy => self
+                .window_starts(seq.len())
+                .map(|start| {
+                    let bases = &seq[start..start + window];
+                    let counts = if simd { base_counts_neon(bases) } else { base_counts_naive(bases) };
+                    entropy_from_counts(counts)
+                })
+                .collect(),
+            ComplexityMetric::Dust => {
+                // Triplet code per position (INVALID_TRIPLET if it spans a non-ACGT base)
+                let triplets = if simd { triplet_codes_neon(seq) } else { triplet_codes_naive(seq) };
+                self.window_starts(seq.len())
+                    .map(|start| {
+                        let end = (start + window).saturating_sub(2).max(start);
+                        dust_score(&triplets[start.min(triplets.len())..end.min(triplets.len())])
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Totals for a slice of records (sequential)
+    fn aggregate(&self, data: &[SequenceRecord], simd: bool) -> WindowedComplexityResult {
+        let mut result = WindowedComplexityResult::new(self);
+
+        for record in data {
+            let scores = self.scores(&record.sequence, simd);
+            result.add_read(self, &scores);
+        }
+
+        result
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowedComplexityResult {
+    pub metric: ComplexityMetric,
+    pub window: usize,
+    pub step: usize,
+    pub total_sequences: usize,
+    pub total_windows: usize,
+    pub low_complexity_windows: usize,
+    /// Sequences with at least one low-complexity window
+    pub low_complexity_sequences: usize,
+    /// Sum of window scores (for merging partial results)
+    pub score_sum: f64,
+    pub mean_score: f64,
+}
+
+impl WindowedComplexityResult {
+    pub fn new(op: &WindowedComplexity) -> Self {
+        Self {
+            metric: op.metric,
+            window: op.window,
+            step: op.step,
+            total_sequences: 0,
+            total_windows: 0,
+            low_complexity_windows: 0,
+            low_complexity_sequences: 0,
+            score_sum: 0.0,
+            mean_score: 0.0,
+        }
+    }
+
+    fn add_read(&mut self, op: &WindowedComplexity, scores: &[f64]) {
+        let low = scores.iter().filter(|&&score| op.metric.is_low(score, op.threshold)).count();
+
+        self.total_sequences += 1;
+        self.total_windows += scores.len();
+        self.low_complexity_windows += low;
+        self.low_complexity_sequences += usize::from(low > 0);
+        self.score_sum += scores.iter().sum::<f64>();
+    }
+
+    pub fn add(&mut self, other: &Self) {
+        self.total_sequences += other.total_sequences;
+        self.total_windows += other.total_windows;
+        self.low_complexity_windows += other.low_complexity_windows;
+        self.low_complexity_sequences += other.low_complexity_sequences;
+        self.score_sum += other.score_sum;
+    }
+
+    pub fn finalize(&mut self) {
+        if self.total_windows > 0 {
+            self.mean_score = self.score_sum / self.total_windows as f64;
+        }
+    }
+}
+
+impl PrimitiveOperation for WindowedComplexity {
+    fn name(&self) -> &str {
+        "windowed_complexity"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Aggregation
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "metric": self.metric,
+            "window": self.window,
+            "step": self.step,
+            "threshold": self.threshold,
+        })
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        let mut result = self.aggregate(data, false);
+        result.finalize();
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        let mut result = self.aggregate(data, true);
+        result.finalize();
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let pool = crate::thread_pool::get(num_threads)?;
+
+        let mut result = pool.install(|| {
+            data.par_iter()
+                .fold(
+                    || WindowedComplexityResult::new(self),
+                    |mut local, record| {
+                        // NEON per-thread
+                        let scores = self.scores(&record.sequence, true);
+                        local.add_read(self, &scores);
+                        local
+                    },
+                )
+                .reduce(
+                    || WindowedComplexityResult::new(self),
+                    |mut a, b| {
+                        a.add(&b);
+                        a
+                    },
+                )
+        });
+
+        result.finalize();
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let merge = |mut a: WindowedComplexityResult, b: WindowedComplexityResult| {
+            a.add(&b);
+            a
+        };
+        let mut result = crate::chunked::reduce_neon(self, data, num_threads, batch_size, merge)?
+            .unwrap_or_else(|| WindowedComplexityResult::new(self));
+        result.finalize();
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        if config.num_threads > 1 {
+            self.execute_threaded(data, config)
+        } else if config.use_neon {
+            self.execute_neon(data)
+        } else {
+            self.execute_naive(data)
+        }
+    }
+}
+
+/// Triplet code for a triplet containing a non-ACGT base
+const INVALID_TRIPLET: u8 = 0xFF;
+
+/// 2-bit code of an ACGT base (case-insensitive), `None` otherwise
+fn base_code(base: u8) -> Option<u8> {
+    match base {
+        b'A' | b'a' => Some(0),
+        b'C' | b'c' => Some(1),
+        b'G' | b'g' => Some(2),
+        b'T' | b't' => Some(3),
+        _ => None,
+    }
+}
+
+// Normalised Shannon entropy (bits / 2) of ACGT counts
+fn entropy_from_counts(counts: [u32; 4]) -> f64 {
+    let total: u32 = counts.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let entropy: f64 = counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum();
+    entropy / 2.0
+}
+
+// DUST score of a window's triplet codes
+fn dust_score(triplets: &[u8]) -> f64 {
+    let mut counts = [0u32; 64];
+    let mut valid = 0u32;
+    for &code in triplets {
+        if code != INVALID_TRIPLET {
+            counts[code as usize] += 1;
+            valid += 1;
+        }
+    }
+    if valid <= 1 {
+        return 0.0;
+    }
+
+    let pairs: u32 = counts.iter().map(|&c| c * c.saturating_sub(1) / 2).sum();
+    pairs as f64 / (valid - 1) as f64
+}
+
+fn base_counts_naive(bases: &[u8]) -> [u32; 4] {
+    let mut counts = [0u32; 4];
+    for &base in bases {
+        if let Some(code) = base_code(base) {
+            counts[code as usize] += 1;
+        }
+    }
+    counts
+}
+
+// Triplet codes (6 bits) for positions 0..len-2
+fn triplet_codes_naive(seq: &[u8]) -> Vec<u8> {
+    seq.windows(3)
+        .map(|triplet| match (base_code(triplet[0]), base_code(triplet[1]), base_code(triplet[2])) {
+            (Some(a), Some(b), Some(c)) => (a << 4) | (b << 2) | c,
+            _ => INVALID_TRIPLET,
+        })
+        .collect()
+}
+
+#[cfg(target_arch = "aarch64")]
+fn base_counts_neon(bases: &[u8]) -> [u32; 4] {
+    use std::arch::aarch64::*;
+
+    let vector_len = bases.len() / 16 * 16;
+    let mut counts = [0u32; 4];
+
+    unsafe {
+        let case_mask = vdupq_n_u8(0xDF);
+        let letters = [vdupq_n_u8(b'A'), vdupq_n_u8(b'C'), vdupq_n_u8(b'G'), vdupq_n_u8(b'T')];
+
+        // u8 lanes count at most 255 chunks before flushing
+        for block in bases[..vector_len].chunks(16 * 255) {
+            let mut accumulators = [vdupq_n_u8(0); 4];
+            for chunk in block.chunks_exact(16) {
+                let upper = vandq_u8(vld1q_u8(chunk.as_ptr()), case_mask);
+                for (accumulator, &letter) in accumulators.iter_mut().zip(&letters) {
+                    // Matches are 0xFF (-1): subtracting adds one
+                    *accumulator = vsubq_u8(*accumulator, vceqq_u8(upper, letter));
+                }
+            }
+            for (count, &accumulator) in counts.iter_mut().zip(&accumulators) {
+                *count += vaddlvq_u8(accumulator) as u32;
+            }
+        }
+    }
+
+    let tail = base_counts_naive(&bases[vector_len..]);
+    for (count, extra) in counts.iter_mut().zip(tail) {
+        *count += extra;
+    }
+    counts
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+fn base_counts_neon(_bases: &[u8]) -> [u32; 4] {
+    panic!("NEON not available on this platform");
+}
+
+#[cfg(target_arch = "aarch64")]
+fn triplet_codes_neon(seq: &[u8]) -> Vec<u8> {
+    use std::arch::aarch64::*;
+
+    let num_triplets = seq.len().saturating_sub(2);
+    let mut triplets = vec![INVALID_TRIPLET; num_triplets];
+
+    // 2-bit codes with an invalid flag in bit 2: 'A'..'T' & 0x1F index the table
+    // (0xFF for other letters; non-letters are caught by the range check)
+    let mut table = [0xFFu8; 32];
+    for (base, code) in [(b'A', 0u8), (b'C', 1), (b'G', 2), (b'T', 3)] {
+        table[(base & 0x1F) as usize] = code;
+    }
+
+    // Codes for every position, 16 at a time
+    let mut codes = vec![0xFFu8; seq.len()];
+    let vector_len = seq.len() / 16 * 16;
+    unsafe {
+        let lookup = vld1q_u8_x2(table.as_ptr());
+        let case_mask = vdupq_n_u8(0xDF);
+        let low5 = vdupq_n_u8(0x1F);
+        for start in (0..vector_len).step_by(16) {
+            let upper = vandq_u8(vld1q_u8(seq.as_ptr().add(start)), case_mask);
+            let code = vqtbl2q_u8(lookup, vandq_u8(upper, low5));
+            // Only 'A'..'Z' (0x40-0x5F after case folding) may map to a code
+            let is_letter = vceqq_u8(vandq_u8(upper, vdupq_n_u8(0xE0)), vdupq_n_u8(0x40));
+            vst1q_u8(codes.as_mut_ptr().add(start), vbslq_u8(is_letter, code, vdupq_n_u8(0xFF)));
+        }
+    }
+    for (code, &base) in codes[vector_len..].iter_mut().zip(&seq[vector_len..]) {
+        *code = base_code(base).unwrap_or(0xFF);
+    }
+
+    // Triplet codes, 16 positions at a time
+    let vector_triplets = num_triplets / 16 * 16;
+    unsafe {
+        let invalid = vdupq_n_u8(0xFF);
+        for start in (0..vector_triplets).step_by(16) {
+            let a = vld1q_u8(codes.as_ptr().add(start));
+            let b = vld1q_u8(codes.as_ptr().add(start + 1));
+            let c = vld1q_u8(codes.as_ptr().add(start + 2));
+            let any_invalid = vorrq_u8(vorrq_u8(vceqq_u8(a, invalid), vceqq_u8(b, invalid)), vceqq_u8(c, invalid));
+            let code = vorrq_u8(vorrq_u8(vshlq_n_u8::<4>(a), vshlq_n_u8::<2>(b)), c);
+            vst1q_u8(triplets.as_mut_ptr().add(start), vbslq_u8(any_invalid, invalid, code));
+        }
+    }
+    for i in vector_triplets..num_triplets {
+        let (a, b, c) = (codes[i], codes[i + 1], codes[i + 2]);
+        if a != 0xFF && b != 0xFF && c != 0xFF {
+            triplets[i] = (a << 4) | (b << 2) | c;
+        }
+    }
+
+    triplets
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+fn triplet_codes_neon(_seq: &[u8]) -> Vec<u8> {
+    panic!("NEON not available on this platform");
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,10 +683,95 @@ mod tests {
         let op = ComplexityScore;
         let result = op.execute_naive(&records).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let complexity_result: ComplexityResult = serde_json::from_value(value).unwrap();
+        if let Some(complexity_result) = result.statistics::<ComplexityResult>() {
             assert_eq!(complexity_result.total_sequences, 3);
             assert!(complexity_result.low_complexity_count > 0);
         }
     }
+
+    fn windowed(op: &WindowedComplexity, records: &[SequenceRecord]) -> WindowedComplexityResult {
+        let naive = op.execute_naive(records).unwrap().statistics::<WindowedComplexityResult>().unwrap().clone();
+        for output in [
+            op.execute_neon(records).unwrap(),
+            op.execute_parallel(records, 2).unwrap(),
+            op.execute_parallel_chunked(records, 3).unwrap(),
+        ] {
+            let result = output.statistics::<WindowedComplexityResult>().unwrap().clone();
+            assert_eq!(result.total_windows, naive.total_windows);
+            assert_eq!(result.low_complexity_windows, naive.low_complexity_windows);
+            assert_eq!(result.low_complexity_sequences, naive.low_complexity_sequences);
+            assert!((result.mean_score - naive.mean_score).abs() < 1e-9);
+        }
+        naive
+    }
+
+    fn windowed_records() -> Vec<SequenceRecord> {
+        let random: Vec<u8> = (0..100u32).map(|i| b"ACGT"[(i.wrapping_mul(2_654_435_761) >> 7) as usize % 4]).collect();
+        let mut repeat_tail = random.clone();
+        repeat_tail[60..].copy_from_slice(&b"CA".repeat(20));
+
+        vec![
+            SequenceRecord::fasta("random".to_string(), random),
+            SequenceRecord::fasta("repeat_tail".to_string(), repeat_tail),
+            SequenceRecord::fasta("poly_a".to_string(), b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_vec()),
+            SequenceRecord::fasta("with_n".to_string(), b"ACGTNNNNACGGTCATNACGTTGCAAGT".to_vec()),
+            SequenceRecord::fasta("empty".to_string(), Vec::new()),
+        ]
+    }
+
+    #[test]
+    fn test_window_starts() {
+        let op = WindowedComplexity::new(ComplexityMetric::Entropy, 10).with_step(4);
+        assert_eq!(op.window_starts(20).collect::<Vec<_>>(), vec![0, 4, 8, 10]);
+        assert_eq!(op.window_starts(18).collect::<Vec<_>>(), vec![0, 4, 8]);
+        assert_eq!(op.window_starts(5).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(op.window_starts(0).count(), 0);
+    }
+
+    #[test]
+    fn test_windowed_entropy() {
+        assert_eq!(entropy_from_counts([5, 5, 5, 5]), 1.0);
+        assert_eq!(entropy_from_counts([8, 0, 0, 0]), 0.0);
+        assert_eq!(entropy_from_counts([4, 4, 0, 0]), 0.5);
+
+        let op = WindowedComplexity::new(ComplexityMetric::Entropy, 20).with_step(10);
+        let result = windowed(&op, &windowed_records());
+
+        assert_eq!(result.total_sequences, 5);
+        // random and repeat_tail: 9 windows each; poly_a: 3; with_n: 2
+        assert_eq!(result.total_windows, 23);
+        // poly_a (entropy 0) only; the CA repeat has entropy exactly 0.5
+        assert_eq!(result.low_complexity_sequences, 1);
+    }
+
+    #[test]
+    fn test_windowed_dust() {
+        // 6 copies of AAA in 6 triplets: 15 pairs / 5
+        assert_eq!(dust_score(&[0; 6]), 3.0);
+        assert_eq!(dust_score(&[0, 1, 2, 3]), 0.0);
+        assert_eq!(dust_score(&[0, INVALID_TRIPLET, 0]), 1.0);
+
+        let seq = b"ACGTNNacgtTTTTGCATGCNNAC";
+        assert_eq!(triplet_codes_naive(seq).len(), seq.len() - 2);
+
+        let op = WindowedComplexity::new(ComplexityMetric::Dust, 32).with_threshold(4.0);
+        let result = windowed(&op, &windowed_records());
+
+        // CA repeat tail and poly-A are repetitive; random is not
+        assert_eq!(result.low_complexity_sequences, 2);
+    }
+
+    #[test]
+    fn test_windowed_neon_kernels() {
+        let seq: Vec<u8> = (0..777usize).map(|i| b"ACGTacgtNRYACGTA-"[(i * 7 + i / 5) % 17]).collect();
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            assert_eq!(triplet_codes_neon(&seq), triplet_codes_naive(&seq));
+            assert_eq!(base_counts_neon(&seq), base_counts_naive(&seq));
+        }
+
+        let counts = base_counts_naive(&seq);
+        assert_eq!(counts.iter().sum::<u32>() as usize, seq.iter().filter(|&&b| base_code(b).is_some()).count());
+    }
 }
@@ -0,0 +1,490 @@
+//! Compression Study Operation
+//!
+//! Compresses the sequence and quality streams of the input with several
+//! codecs and records compression ratio and compress/decompress throughput
+//! for each, so storage-pipeline questions (which codec, which level, how
+//! many threads) are answered by the same harness as the other operations.
+//!
+//! # Operation Characteristics
+//! - **Category**: IO
+//! - **Complexity**: 0.6 (entropy coding and match finding; codec-dependent)
+//! - **Output**: One measurement per (codec, stream): bytes in/out, ratio,
+//!   MB/s each way
+//! - **NEON benefit**: None from this crate (codecs vectorize internally)
+//!
+//! # Implementation Notes
+//! - Streams: sequences and qualities, each record followed by a newline
+//!   (FASTA input has no quality stream)
+//! - Codecs: zstd levels 1/3/9 (libzstd), LZ4 (lz4_flex, block format) and
+//!   LZFSE via Apple's Compression framework (macOS only; left out of the
+//!   default codec list elsewhere)
+//! - Streams are compressed in independent blocks of `block_size` bytes (as
+//!   BGZF and pigz do), so the parallel backend compresses blocks across
+//!   threads and every backend produces the same compressed sizes
+//! - Every block is decompressed and checked against the input; a codec
+//!   that does not round-trip fails the operation
+//! - Ratios and sizes are deterministic; the MB/s fields are timings and
+//!   vary between runs, so `output_tolerance` exempts them from comparisons
+
+use crate::{HardwareConfig, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use anyhow::{Context, Result};
+use asbb_core::compare::Tolerance;
+use rayon::prelude::*;
+use rayon::ThreadPool;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// Default compression block size (1 MiB)
+pub const DEFAULT_BLOCK_SIZE: usize = 1 << 20;
+
+/// Compression codec
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Codec {
+    /// zstd at a compression level (1-22)
+    Zstd(i32),
+    /// LZ4 block format
+    Lz4,
+    /// Apple LZFSE (Compression framework, macOS only)
+    Lzfse,
+}
+
+impl Codec {
+    /// zstd 1/3/9, LZ4, and LZFSE where available
+    pub fn defaults() -> Vec<Codec> {
+        [Codec::Zstd(1), Codec::Zstd(3), Codec::Zstd(9), Codec::Lz4, Codec::Lzfse]
+            .into_iter()
+            .filter(Codec::available)
+            .collect()
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Codec::Zstd(level) => format!("zstd-{}", level),
+            Codec::Lz4 => "lz4".to_string(),
+            Codec::Lzfse => "lzfse".to_string(),
+        }
+    }
+
+    /// Whether this platform has the codec
+    pub fn available(&self) -> bool {
+        !matches!(self, Codec::Lzfse) || cfg!(target_os = "macos")
+    }
+
+    fn compress(&self, block: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            Codec::Zstd(level) => {
+                zstd::bulk::compress(block, *level).context("zstd compression failed")
+            }
+            Codec::Lz4 => Ok(lz4_flex::block::compress(block)),
+            Codec::Lzfse => apple::encode(block),
+        }
+    }
+
+    /// Decompress a block of `original_len` bytes
+    fn decompress(&self, compressed: &[u8], original_len: usize) -> Result<Vec<u8>> {
+        match self {
+            Codec::Zstd(_) => zstd::bulk::decompress(compressed, original_len)
+                .context("zstd decompression failed"),
+            Codec::Lz4 => lz4_flex::block::decompress(compressed, original_len)
+                .context("lz4 decompression failed"),
+            Codec::Lzfse => apple::decode(compressed, original_len),
+        }
+    }
+}
+
+/// Data stream of the input records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stream {
+    Sequence,
+    Quality,
+}
+
+impl Stream {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stream::Sequence => "sequence",
+            Stream::Quality => "quality",
+        }
+    }
+
+    /// Concatenated stream bytes, one newline-terminated line per record
+    fn bytes(&self, data: &[SequenceRecord]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for record in data {
+            let line = match self {
+                Stream::Sequence => Some(&record.sequence),
+                Stream::Quality => record.quality.as_ref(),
+            };
+            if let Some(line) = line {
+                bytes.extend_from_slice(line);
+                bytes.push(b'\n');
+            }
+        }
+        bytes
+    }
+}
+
+// ============================================================================
+// Apple Compression Framework
+// ============================================================================
+
+#[cfg(target_os = "macos")]
+mod apple {
+    use anyhow::Result;
+    use std::ffi::c_void;
+
+    /// `COMPRESSION_LZFSE` from <compression.h>
+    const COMPRESSION_LZFSE: i32 = 0x801;
+
+    #[link(name = "compression")]
+    extern "C" {
+        fn compression_encode_buffer(
+            dst: *mut u8,
+            dst_size: usize,
+            src: *const u8,
+            src_size: usize,
+            scratch: *mut c_void,
+            algorithm: i32,
+        ) -> usize;
+        fn compression_decode_buffer(
+            dst: *mut u8,
+            dst_size: usize,
+            src: *const u8,
+            src_size: usize,
+            scratch: *mut c_void,
+            algorithm: i32,
+        ) -> usize;
+    }
+
+    pub fn encode(block: &[u8]) -> Result<Vec<u8>> {
+        // Incompressible input grows by a small header per LZFSE block
+        let mut out = vec![0u8; block.len() + block.len() / 16 + 4096];
+        // SAFETY: both buffers are valid for the sizes passed; a null
+        // scratch buffer makes the framework allocate its own
+        let written = unsafe {
+            compression_encode_buffer(
+                out.as_mut_ptr(),
+                out.len(),
+                block.as_ptr(),
+                block.len(),
+                std::ptr::null_mut(),
+                COMPRESSION_LZFSE,
+            )
+        };
+        if written == 0 && !block.is_empty() {
+            anyhow::bail!("LZFSE compression failed");
+        }
+        out.truncate(written);
+        Ok(out)
+    }
+
+    pub fn decode(compressed: &[u8], original_len: usize) -> Result<Vec<u8>> {
+        let mut out = vec![0u8; original_len];
+        // SAFETY: as in `encode`
+        let written = unsafe {
+            compression_decode_buffer(
+                out.as_mut_ptr(),
+                out.len(),
+                compressed.as_ptr(),
+                compressed.len(),
+                std::ptr::null_mut(),
+                COMPRESSION_LZFSE,
+            )
+        };
+        if written != original_len {
+            anyhow::bail!("LZFSE decompression returned {} of {} bytes", written, original_len);
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod apple {
+    use anyhow::Result;
+
+    pub fn encode(_block: &[u8]) -> Result<Vec<u8>> {
+        anyhow::bail!("LZFSE requires Apple's Compression framework (macOS)")
+    }
+
+    pub fn decode(_compressed: &[u8], _original_len: usize) -> Result<Vec<u8>> {
+        anyhow::bail!("LZFSE requires Apple's Compression framework (macOS)")
+    }
+}
+
+// ============================================================================
+// Result
+// ============================================================================
+
+/// One codec on one stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodecMeasurement {
+    pub codec: String,
+    pub stream: String,
+    pub input_bytes: usize,
+    pub compressed_bytes: usize,
+    pub blocks: usize,
+    /// Input / compressed size
+    pub ratio: f64,
+    /// Input MB (10^6 bytes) compressed per second
+    pub compress_mb_per_sec: f64,
+    /// Input MB restored per second
+    pub decompress_mb_per_sec: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompressionStudyResult {
+    pub total_sequences: usize,
+    pub block_size: usize,
+    /// By stream, then codec in configured order
+    pub measurements: Vec<CodecMeasurement>,
+}
+
+impl CompressionStudyResult {
+    /// Measurement of `codec` on `stream`
+    pub fn get(&self, codec: Codec, stream: Stream) -> Option<&CodecMeasurement> {
+        let codec = codec.name();
+        self.measurements
+            .iter()
+            .find(|m| m.codec == codec && m.stream == stream.name())
+    }
+}
+
+// ============================================================================
+// Operation
+// ============================================================================
+
+/// Compression ratio vs throughput study
+pub struct CompressionStudy {
+    pub codecs: Vec<Codec>,
+    /// Bytes per independently compressed block
+    pub block_size: usize,
+}
+
+impl CompressionStudy {
+    pub fn new(codecs: Vec<Codec>) -> Self {
+        Self { codecs, block_size: DEFAULT_BLOCK_SIZE }
+    }
+
+    pub fn with_block_size(mut self, block_size: usize) -> Self {
+        self.block_size = block_size.max(1);
+        self
+    }
+
+    /// Run `f` over the blocks, in order, sequentially or on `pool`
+    fn map_blocks<F>(blocks: &[&[u8]], pool: Option<&ThreadPool>, f: F) -> Result<Vec<Vec<u8>>>
+    where
+        F: Fn(usize, &[u8]) -> Result<Vec<u8>> + Sync + Send,
+    {
+        match pool {
+            None => blocks.iter().enumerate().map(|(i, block)| f(i, block)).collect(),
+            Some(pool) => pool.install(|| {
+                blocks.par_iter().enumerate().map(|(i, block)| f(i, block)).collect()
+            }),
+        }
+    }
+
+    /// Compress, decompress and verify one stream with one codec
+    fn measure(
+        &self,
+        codec: Codec,
+        stream: Stream,
+        bytes: &[u8],
+        pool: Option<&ThreadPool>,
+    ) -> Result<CodecMeasurement> {
+        let blocks: Vec<&[u8]> = bytes.chunks(self.block_size).collect();
+
+        let start = Instant::now();
+        let compressed = Self::map_blocks(&blocks, pool, |_, block| codec.compress(block))?;
+        let compress_secs = start.elapsed().as_secs_f64();
+
+        let compressed_refs: Vec<&[u8]> = compressed.iter().map(Vec::as_slice).collect();
+        let start = Instant::now();
+        let restored = Self::map_blocks(&compressed_refs, pool, |i, block| {
+            codec.decompress(block, blocks[i].len())
+        })?;
+        let decompress_secs = start.elapsed().as_secs_f64();
+
+        if restored.iter().zip(&blocks).any(|(restored, block)| restored != block) {
+            anyhow::bail!("{} did not round-trip the {} stream", codec.name(), stream.name());
+        }
+
+        let compressed_bytes: usize = compressed.iter().map(Vec::len).sum();
+        let mb = bytes.len() as f64 / 1e6;
+        Ok(CodecMeasurement {
+            codec: codec.name(),
+            stream: stream.name().to_string(),
+            input_bytes: bytes.len(),
+            compressed_bytes,
+            blocks: blocks.len(),
+            ratio: bytes.len() as f64 / compressed_bytes.max(1) as f64,
+            compress_mb_per_sec: mb / compress_secs.max(1e-9),
+            decompress_mb_per_sec: mb / decompress_secs.max(1e-9),
+        })
+    }
+
+    fn study(&self, data: &[SequenceRecord], num_threads: usize) -> Result<CompressionStudyResult> {
+        let pool = if num_threads > 1 {
+            Some(crate::thread_pool::get(num_threads)?)
+        } else {
+            None
+        };
+
+        let mut measurements = Vec::new();
+        for stream in [Stream::Sequence, Stream::Quality] {
+            let bytes = stream.bytes(data);
+            if bytes.is_empty() {
+                continue;
+            }
+            for &codec in &self.codecs {
+                measurements.push(self.measure(codec, stream, &bytes, pool.as_deref())?);
+            }
+        }
+
+        Ok(CompressionStudyResult {
+            total_sequences: data.len(),
+            block_size: self.block_size,
+            measurements,
+        })
+    }
+}
+
+impl Default for CompressionStudy {
+    fn default() -> Self {
+        Self::new(Codec::defaults())
+    }
+}
+
+impl PrimitiveOperation for CompressionStudy {
+    fn name(&self) -> &str {
+        "compression_study"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::IO
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "codecs": self.codecs.iter().map(Codec::name).collect::<Vec<_>>(),
+            "block_size": self.block_size,
+        })
+    }
+
+    fn output_tolerance(&self) -> Tolerance {
+        // Throughputs are timings: any value matches
+        Tolerance::default()
+            .with_field("compress_mb_per_sec", f64::INFINITY)
+            .with_field("decompress_mb_per_sec", f64::INFINITY)
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.study(data, 1)?))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.study(data, num_threads)?))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        _batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        // Blocks already are the chunks
+        self.execute_parallel(data, num_threads)
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        self.execute_parallel(data, config.num_threads)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asbb_core::compare::outputs_match;
+
+    fn test_reads() -> Vec<SequenceRecord> {
+        let mut state = 0x853C_49E6_748F_EA9Bu64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        (0..2_000)
+            .map(|i| {
+                let sequence = (0..150).map(|_| b"ACGT"[(next() % 4) as usize]).collect();
+                // Degrading quality with a few distinct scores
+                let quality =
+                    (0..150).map(|j| b'I' - (j / 30) as u8 * 5 - (next() % 2) as u8).collect();
+                SequenceRecord::fastq(format!("seq_{}", i), sequence, quality)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compression_study() {
+        let reads = test_reads();
+        let op = CompressionStudy::new(vec![Codec::Zstd(1), Codec::Zstd(9), Codec::Lz4])
+            .with_block_size(64 * 1024);
+        let output = op.execute_naive(&reads).unwrap();
+        let result = output.statistics::<CompressionStudyResult>().unwrap();
+
+        assert_eq!(result.measurements.len(), 6);
+        let zstd = result.get(Codec::Zstd(9), Stream::Sequence).unwrap();
+        assert_eq!(zstd.input_bytes, 2_000 * 151);
+        assert_eq!(zstd.blocks, 5);
+        // Random ACGT carries 2 bits per base; entropy coding gets close
+        assert!(zstd.ratio > 3.0 && zstd.ratio < 4.5, "{}", zstd.ratio);
+        assert!(zstd.compress_mb_per_sec > 0.0 && zstd.decompress_mb_per_sec > 0.0);
+        assert!(result.get(Codec::Zstd(1), Stream::Quality).unwrap().ratio > 2.0);
+        assert!(result.get(Codec::Lz4, Stream::Sequence).unwrap().compressed_bytes > 0);
+
+        // FASTA input has no quality stream
+        let fasta: Vec<SequenceRecord> = reads
+            .iter()
+            .map(|r| SequenceRecord::fasta(r.id.clone(), r.sequence.clone()))
+            .collect();
+        let output = op.execute_naive(&fasta).unwrap();
+        let result = output.statistics::<CompressionStudyResult>().unwrap();
+        assert!(result.measurements.iter().all(|m| m.stream == "sequence"));
+    }
+
+    #[test]
+    fn test_compression_study_backends_match() {
+        let reads = test_reads();
+        let op = CompressionStudy::default().with_block_size(50_000);
+        let sizes = |output: OperationOutput| -> Vec<(String, String, usize, usize)> {
+            let result = output.statistics::<CompressionStudyResult>().unwrap().clone();
+            result
+                .measurements
+                .into_iter()
+                .map(|m| (m.codec, m.stream, m.input_bytes, m.compressed_bytes))
+                .collect()
+        };
+
+        let naive = op.execute_naive(&reads).unwrap();
+        assert!(outputs_match(&naive, &op.execute_naive(&reads).unwrap(), &op.output_tolerance()));
+        let expected = sizes(naive);
+        assert_eq!(expected.len(), 2 * Codec::defaults().len());
+        assert_eq!(sizes(op.execute_parallel(&reads, 3).unwrap()), expected);
+        assert_eq!(sizes(op.execute_parallel_chunked(&reads, 2).unwrap()), expected);
+        assert_eq!(Codec::Lzfse.available(), cfg!(target_os = "macos"));
+    }
+}
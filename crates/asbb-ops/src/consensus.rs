@@ -0,0 +1,463 @@
+//! Consensus Calling Operation
+//!
+//! Groups reads that come from the same molecule (same UMI, or same ID
+//! prefix) and calls one consensus read per group: at every position the
+//! base with the highest summed quality wins. A multi-stage composite of
+//! group-by (hashing IDs), transpose (reading each group column-wise) and
+//! reduction (per-column votes).
+//!
+//! # Operation Characteristics
+//! - **Category**: Aggregation (many reads in, one read per group out)
+//! - **Complexity**: 0.55 (hashing + gather per group + 4-way column votes)
+//! - **Output**: Consensus records, one per group in order of first read
+//! - **NEON benefit**: Column voting only (16 columns per vector); grouping
+//!   is scalar and hash-bound
+//!
+//! # Implementation Notes
+//! - Reads are aligned at their first base (no indel realignment); the
+//!   consensus is as long as the group's longest read
+//! - Vote weight of a base: its Phred score (FASTA bases weigh 1); N and
+//!   other bytes do not vote
+//! - Ties and columns without votes are called N. Consensus quality is the
+//!   winner's weight minus everyone else's, capped to Q0-Q93; groups with a
+//!   FASTA read produce FASTA consensus
+//! - Reads without a key (no UMI in the ID) are dropped, as are groups
+//!   smaller than `min_group_size`
+//! - NEON: u16 accumulators per base and column, folded into u32 totals
+//!   every [`FLUSH_INTERVAL`] reads, so totals match the scalar path exactly
+//! - Parallel: groups are independent; chunked execution falls back to it
+//!   because a group can span chunks
+
+use crate::{HardwareConfig, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use asbb_core::memory::MemoryEstimate;
+use asbb_core::Encoding;
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Highest consensus quality (Phred+33 '~')
+const MAX_QUALITY: u32 = 93;
+
+/// Reads accumulated in u16 lanes before folding into u32 totals
+/// (256 × the largest weight, 222, fits in a u16)
+const FLUSH_INTERVAL: usize = 256;
+
+/// What reads are grouped by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupKey {
+    /// UMI at the end of the read name: the 8th field of Illumina IDs
+    /// (`...:1101:15589:1331:ACGTACGT`) or after the last `_` (umi_tools,
+    /// `read1_ACGTACGT`). Dual UMIs (`ACGT+TTGA`) are one key
+    Umi,
+    /// First `n` bytes of the read name (the whole name if shorter)
+    IdPrefix(usize),
+}
+
+impl GroupKey {
+    pub fn name(&self) -> String {
+        match self {
+            GroupKey::Umi => "umi".to_string(),
+            GroupKey::IdPrefix(n) => format!("id_prefix_{}", n),
+        }
+    }
+
+    /// This key of a read ID, borrowed from it (`None` if it has no UMI)
+    pub fn of<'a>(&self, id: &'a str) -> Option<&'a str> {
+        let name = id.split_ascii_whitespace().next().unwrap_or("");
+        let name = name.strip_prefix('@').unwrap_or(name);
+        match self {
+            GroupKey::Umi => {
+                let umi = if name.bytes().filter(|&b| b == b':').count() == 7 {
+                    name.rsplit(':').next()
+                } else {
+                    name.rsplit_once('_').map(|(_, umi)| umi)
+                }?;
+                let umi_byte =
+                    |b: u8| matches!(b.to_ascii_uppercase(), b'A' | b'C' | b'G' | b'T' | b'N' | b'+');
+                let valid = !umi.is_empty() && umi.bytes().all(umi_byte);
+                valid.then_some(umi)
+            }
+            GroupKey::IdPrefix(n) => {
+                // Back off to a char boundary for non-ASCII names
+                let end = (0..=(*n).min(name.len())).rev().find(|&i| name.is_char_boundary(i))?;
+                Some(&name[..end])
+            }
+        }
+    }
+}
+
+/// Consensus calling operation
+pub struct Consensus {
+    /// What reads are grouped by
+    pub group_by: GroupKey,
+    /// Groups with fewer reads produce no consensus
+    pub min_group_size: usize,
+}
+
+impl Consensus {
+    pub fn new(group_by: GroupKey) -> Self {
+        Self { group_by, min_group_size: 1 }
+    }
+
+    pub fn with_min_group_size(mut self, min_group_size: usize) -> Self {
+        self.min_group_size = min_group_size;
+        self
+    }
+
+    /// (key, member indices) of every group, in order of first read
+    fn groups<'a>(&self, data: &'a [SequenceRecord]) -> Vec<(&'a str, Vec<usize>)> {
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        let mut groups: Vec<(&str, Vec<usize>)> = Vec::new();
+        for (i, record) in data.iter().enumerate() {
+            let Some(key) = self.group_by.of(&record.id) else {
+                continue;
+            };
+            let group = *index.entry(key).or_insert_with(|| {
+                groups.push((key, Vec::new()));
+                groups.len() - 1
+            });
+            groups[group].1.push(i);
+        }
+        groups.retain(|(_, members)| members.len() >= self.min_group_size);
+        groups
+    }
+
+    /// Consensus of one group (`simd` selects the NEON voting kernel)
+    fn call(key: &str, members: &[&SequenceRecord], simd: bool) -> SequenceRecord {
+        let length = members.iter().map(|r| r.sequence.len()).max().unwrap_or(0);
+        let mut totals = vec![[0u32; 4]; length];
+        if simd {
+            vote_neon(members, &mut totals);
+        } else {
+            vote(members, &mut totals, 0);
+        }
+
+        let mut sequence = Vec::with_capacity(length);
+        let mut quality = Vec::with_capacity(length);
+        for column in &totals {
+            let (base, score) = call_column(column);
+            sequence.push(base);
+            quality.push(b'!' + score as u8);
+        }
+
+        let id = format!("{} reads={}", key, members.len());
+        if members.iter().all(|r| r.quality.is_some()) {
+            SequenceRecord::fastq(id, sequence, quality)
+        } else {
+            SequenceRecord::fasta(id, sequence)
+        }
+    }
+
+    /// Group `data` and call every group's consensus
+    fn consensus(&self, data: &[SequenceRecord], simd: bool) -> Vec<SequenceRecord> {
+        self.groups(data)
+            .into_iter()
+            .map(|(key, members)| {
+                let members: Vec<&SequenceRecord> = members.iter().map(|&i| &data[i]).collect();
+                Self::call(key, &members, simd)
+            })
+            .collect()
+    }
+}
+
+impl Default for Consensus {
+    /// Group by UMI
+    fn default() -> Self {
+        Self::new(GroupKey::Umi)
+    }
+}
+
+// ============================================================================
+// Column Voting
+// ============================================================================
+
+fn base_index(base: u8) -> Option<usize> {
+    match base {
+        b'A' | b'a' => Some(0),
+        b'C' | b'c' => Some(1),
+        b'G' | b'g' => Some(2),
+        b'T' | b't' => Some(3),
+        _ => None,
+    }
+}
+
+/// Vote weight of base `i` of a read
+fn weight(record: &SequenceRecord, i: usize) -> u32 {
+    match &record.quality {
+        Some(quality) => quality.get(i).map_or(0, |&q| q.saturating_sub(33) as u32),
+        None => 1,
+    }
+}
+
+/// Add the votes of columns `from..` of every member to `totals`
+fn vote(members: &[&SequenceRecord], totals: &mut [[u32; 4]], from: usize) {
+    for record in members {
+        for (i, &base) in record.sequence.iter().enumerate().skip(from) {
+            if let Some(b) = base_index(base) {
+                totals[i][b] += weight(record, i);
+            }
+        }
+    }
+}
+
+/// (base, quality score) called from one column's totals
+fn call_column(column: &[u32; 4]) -> (u8, u32) {
+    let best = *column.iter().max().unwrap_or(&0);
+    let winners = column.iter().filter(|&&total| total == best).count();
+    if best == 0 || winners > 1 {
+        return (b'N', 0);
+    }
+    let winner = column.iter().position(|&total| total == best).unwrap_or(0);
+    let others: u32 = column.iter().sum::<u32>() - best;
+    (b"ACGT"[winner], (best - others.min(best)).min(MAX_QUALITY))
+}
+
+/// [`vote`] with 16 columns per vector; the tail columns and reads ending
+/// inside a block are voted scalar
+#[cfg(target_arch = "aarch64")]
+fn vote_neon(members: &[&SequenceRecord], totals: &mut [[u32; 4]]) {
+    use std::arch::aarch64::*;
+
+    let blocks = totals.len() / 16;
+    for block in 0..blocks {
+        let start = block * 16;
+        for batch in members.chunks(FLUSH_INTERVAL) {
+            // SAFETY: loads are bounds-checked per read below; stores go to
+            // local arrays of 16 u16
+            unsafe {
+                let zero = vdupq_n_u16(0);
+                let mut low = [zero; 4];
+                let mut high = [zero; 4];
+                let case_mask = vdupq_n_u8(0xDF);
+                let letters = [b'A', b'C', b'G', b'T'].map(|b| vdupq_n_u8(b));
+
+                for record in batch {
+                    let quality = record.quality.as_deref();
+                    let full = record.sequence.len() >= start + 16
+                        && quality.is_none_or(|q| q.len() >= start + 16);
+                    if !full {
+                        let block = record.sequence.iter().enumerate().take(start + 16).skip(start);
+                        for (i, &base) in block {
+                            if let Some(b) = base_index(base) {
+                                totals[i][b] += weight(record, i);
+                            }
+                        }
+                        continue;
+                    }
+
+                    let bases = vandq_u8(vld1q_u8(record.sequence.as_ptr().add(start)), case_mask);
+                    let weights = match quality {
+                        Some(q) => vqsubq_u8(vld1q_u8(q.as_ptr().add(start)), vdupq_n_u8(33)),
+                        None => vdupq_n_u8(1),
+                    };
+                    for b in 0..4 {
+                        let votes = vandq_u8(vceqq_u8(bases, letters[b]), weights);
+                        low[b] = vaddw_u8(low[b], vget_low_u8(votes));
+                        high[b] = vaddw_high_u8(high[b], votes);
+                    }
+                }
+
+                let mut lanes = [0u16; 16];
+                for b in 0..4 {
+                    vst1q_u16(lanes.as_mut_ptr(), low[b]);
+                    vst1q_u16(lanes.as_mut_ptr().add(8), high[b]);
+                    for (j, &count) in lanes.iter().enumerate() {
+                        totals[start + j][b] += count as u32;
+                    }
+                }
+            }
+        }
+    }
+
+    vote(members, totals, blocks * 16);
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+fn vote_neon(members: &[&SequenceRecord], totals: &mut [[u32; 4]]) {
+    vote(members, totals, 0);
+}
+
+impl PrimitiveOperation for Consensus {
+    fn name(&self) -> &str {
+        "consensus"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Aggregation
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "group_by": self.group_by.name(),
+            "min_group_size": self.min_group_size,
+        })
+    }
+
+    fn estimate_memory(
+        &self,
+        num_sequences: usize,
+        mean_length: f64,
+        encoding: Encoding,
+    ) -> MemoryEstimate {
+        // Worst case every read is its own group: one output read per input
+        let estimate = MemoryEstimate::input(num_sequences, mean_length, encoding);
+        let records = estimate.input_bytes as f64;
+        estimate.with_output(records)
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::Records(self.consensus(data, false)))
+    }
+
+    fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::Records(self.consensus(data, true)))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let pool = crate::thread_pool::get(num_threads)?;
+        let groups = self.groups(data);
+
+        let records = pool.install(|| {
+            groups
+                .par_iter()
+                .map(|(key, members)| {
+                    let members: Vec<&SequenceRecord> = members.iter().map(|&i| &data[i]).collect();
+                    Self::call(key, &members, true)
+                })
+                .collect()
+        });
+
+        Ok(OperationOutput::Records(records))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        _batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        // A group's reads can fall in several chunks, so chunks cannot call
+        // consensus independently
+        self.execute_parallel(data, num_threads)
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        if config.num_threads > 1 {
+            self.execute_parallel(data, config.num_threads)
+        } else if config.use_neon {
+            self.execute_neon(data)
+        } else {
+            self.execute_naive(data)
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(output: OperationOutput) -> Vec<SequenceRecord> {
+        match output {
+            OperationOutput::Records(records) => records,
+            _ => panic!("Expected record output"),
+        }
+    }
+
+    #[test]
+    fn test_group_keys() {
+        let umi = GroupKey::Umi;
+        assert_eq!(umi.of("@M1:8:FC:1:1101:15589:1331:ACGTACGT 1:N:0:1"), Some("ACGTACGT"));
+        assert_eq!(umi.of("read7_acgt+TTGA"), Some("acgt+TTGA"));
+        assert_eq!(umi.of("M1:8:FC:1:1101:15589:1331 1:N:0:ACGT"), None);
+        assert_eq!(umi.of("read_7"), None);
+        assert_eq!(umi.of("seq_ACXT"), None);
+
+        assert_eq!(GroupKey::IdPrefix(5).of("@seq_12345 extra"), Some("seq_1"));
+        assert_eq!(GroupKey::IdPrefix(20).of("seq_1"), Some("seq_1"));
+        assert_eq!(GroupKey::IdPrefix(2).of("aé"), Some("a"));
+    }
+
+    #[test]
+    fn test_consensus_quality_weighting() {
+        let reads = vec![
+            SequenceRecord::fastq("a_AAAA".into(), b"ACGTAC".to_vec(), b"IIIIII".to_vec()),
+            SequenceRecord::fastq("b_CCCC".into(), b"TTTT".to_vec(), b"IIII".to_vec()),
+            SequenceRecord::fastq("c_AAAA".into(), b"ACCTACGG".to_vec(), b"II#III+I".to_vec()),
+            SequenceRecord::fastq("d_AAAA".into(), b"AGCTnc".to_vec(), b"I+#III".to_vec()),
+            SequenceRecord::fastq("unkeyed".into(), b"GGGG".to_vec(), b"IIII".to_vec()),
+        ];
+        let output = records(Consensus::default().execute_naive(&reads).unwrap());
+        assert_eq!(output.len(), 2);
+
+        // Column 1: C at Q40 + Q40 beats G at Q10; column 2: G at Q40 beats
+        // C at Q2 + Q2; column 4: N does not vote; column 6 one read only
+        assert_eq!(output[0].id, "AAAA reads=3");
+        assert_eq!(output[0].sequence, b"ACGTACGG");
+        let scores: Vec<u8> = output[0].quality.as_ref().unwrap().iter().map(|q| q - 33).collect();
+        assert_eq!(scores, [93, 70, 36, 93, 80, 93, 10, 40]);
+
+        assert_eq!(output[1].id, "CCCC reads=1");
+        assert_eq!(output[1].sequence, b"TTTT");
+
+        // Ties call N; a FASTA member makes the consensus FASTA
+        let tied = vec![
+            SequenceRecord::fasta("x_AC".into(), b"AC".to_vec()),
+            SequenceRecord::fasta("y_AC".into(), b"AG".to_vec()),
+        ];
+        let output = records(Consensus::default().execute_naive(&tied).unwrap());
+        assert_eq!(output[0].sequence, b"AN");
+        assert!(output[0].quality.is_none());
+
+        let singletons = Consensus::default().with_min_group_size(2);
+        assert_eq!(records(singletons.execute_naive(&reads).unwrap()).len(), 1);
+    }
+
+    #[test]
+    fn test_consensus_backends_match() {
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        // Group sizes up to past FLUSH_INTERVAL, lengths across block edges,
+        // mixed case, Ns and high quality bytes
+        let reads: Vec<SequenceRecord> = (0..1_500)
+            .map(|i| {
+                let group = if i % 3 == 0 { 0 } else { 40 + next() % 40 };
+                let length = 20 + (next() % 40) as usize;
+                let sequence = (0..length).map(|_| b"ACGTacgN"[(next() % 8) as usize]).collect();
+                let quality = (0..length).map(|_| 33 + (next() % 222) as u8).collect();
+                SequenceRecord::fastq(format!("seq{}_g{}", group, i % 7), sequence, quality)
+            })
+            .collect();
+
+        let op = Consensus::new(GroupKey::IdPrefix(5));
+        let expected = records(op.execute_naive(&reads).unwrap());
+        assert!(expected.len() > 30);
+        assert!(expected.iter().any(|r| r.id.ends_with("reads=500")));
+        for output in [
+            op.execute_neon(&reads).unwrap(),
+            op.execute_parallel(&reads, 3).unwrap(),
+            op.execute_parallel_chunked(&reads, 2).unwrap(),
+        ] {
+            assert_eq!(records(output), expected);
+        }
+    }
+}
@@ -49,7 +49,7 @@ pub struct EditDistance {
 }
 
 /// Edit distance result
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EditDistanceMatrix {
     pub sequences: Vec<String>,
     pub distances: Vec<Vec<usize>>,
@@ -281,7 +281,7 @@ impl PrimitiveOperation for EditDistance {
             num_sequences: num_seqs,
         };
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 
     #[cfg(target_arch = "aarch64")]
@@ -309,17 +309,14 @@ impl PrimitiveOperation for EditDistance {
             num_sequences: num_seqs,
         };
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_parallel(&self, sequences: &[SequenceRecord], num_threads: usize) -> Result<OperationOutput> {
         let num_seqs = std::cmp::min(sequences.len(), self.max_sequences);
         let sequences = &sequences[..num_seqs];
 
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()
-            .unwrap();
+        let pool = crate::thread_pool::get(num_threads)?;
 
         // Compute upper triangle in parallel
         let pairs: Vec<(usize, usize)> = (0..num_seqs)
@@ -350,7 +347,7 @@ impl PrimitiveOperation for EditDistance {
             num_sequences: num_seqs,
         };
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 
     /// Execute with AMX acceleration (via Accelerate framework)
@@ -378,7 +375,7 @@ impl PrimitiveOperation for EditDistance {
             num_sequences: num_seqs,
         };
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 }
 
@@ -402,8 +399,7 @@ mod tests {
         let seq2 = create_test_sequence("seq2", b"ACGT");
 
         let output = op.execute_naive(&[seq1, seq2]).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let result: EditDistanceMatrix = serde_json::from_value(json).unwrap();
+        if let Some(result) = output.statistics::<EditDistanceMatrix>() {
             assert_eq!(result.distances[0][1], 0);
             assert_eq!(result.distances[1][0], 0);
         } else {
@@ -419,8 +415,7 @@ mod tests {
         let seq2 = create_test_sequence("seq2", b"ACCT");
 
         let output = op.execute_naive(&[seq1, seq2]).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let result: EditDistanceMatrix = serde_json::from_value(json).unwrap();
+        if let Some(result) = output.statistics::<EditDistanceMatrix>() {
             assert_eq!(result.distances[0][1], 1); // One substitution (G -> C)
         }
     }
@@ -433,8 +428,7 @@ mod tests {
         let seq2 = create_test_sequence("seq2", b"ACGGT");
 
         let output = op.execute_naive(&[seq1, seq2]).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let result: EditDistanceMatrix = serde_json::from_value(json).unwrap();
+        if let Some(result) = output.statistics::<EditDistanceMatrix>() {
             assert_eq!(result.distances[0][1], 1); // One insertion
         }
     }
@@ -447,8 +441,7 @@ mod tests {
         let seq2 = create_test_sequence("seq2", b"ACGT");
 
         let output = op.execute_naive(&[seq1, seq2]).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let result: EditDistanceMatrix = serde_json::from_value(json).unwrap();
+        if let Some(result) = output.statistics::<EditDistanceMatrix>() {
             assert_eq!(result.distances[0][1], 1); // One deletion
         }
     }
@@ -461,8 +454,7 @@ mod tests {
         let seq2 = create_test_sequence("seq2", b"");
 
         let output = op.execute_naive(&[seq1, seq2]).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let result: EditDistanceMatrix = serde_json::from_value(json).unwrap();
+        if let Some(result) = output.statistics::<EditDistanceMatrix>() {
             assert_eq!(result.distances[0][1], 4); // Delete all 4 bases
         }
     }
@@ -475,8 +467,7 @@ mod tests {
         let seq2 = create_test_sequence("seq2", b"TTTT");
 
         let output = op.execute_naive(&[seq1, seq2]).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let result: EditDistanceMatrix = serde_json::from_value(json).unwrap();
+        if let Some(result) = output.statistics::<EditDistanceMatrix>() {
             assert_eq!(result.distances[0][1], 4); // All substitutions
         }
     }
@@ -492,8 +483,7 @@ mod tests {
         ];
 
         let output = op.execute_naive(&sequences).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let result: EditDistanceMatrix = serde_json::from_value(json).unwrap();
+        if let Some(result) = output.statistics::<EditDistanceMatrix>() {
 
             // Check symmetry
             for i in 0..result.num_sequences {
@@ -520,8 +510,7 @@ mod tests {
         ];
 
         let output = op.execute_naive(&sequences).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let result: EditDistanceMatrix = serde_json::from_value(json).unwrap();
+        if let Some(result) = output.statistics::<EditDistanceMatrix>() {
             assert_eq!(result.num_sequences, 2); // Limited to 2
         }
     }
@@ -540,10 +529,10 @@ mod tests {
         let naive_output = op.execute_naive(&sequences).unwrap();
         let neon_output = op.execute_neon(&sequences).unwrap();
 
-        if let (OperationOutput::Statistics(naive_json), OperationOutput::Statistics(neon_json)) =
+        if let (OperationOutput::Typed(naive_typed), OperationOutput::Typed(neon_typed)) =
             (naive_output, neon_output) {
-            let naive_result: EditDistanceMatrix = serde_json::from_value(naive_json).unwrap();
-            let neon_result: EditDistanceMatrix = serde_json::from_value(neon_json).unwrap();
+            let naive_result: &EditDistanceMatrix = naive_typed.downcast_ref().unwrap();
+            let neon_result: &EditDistanceMatrix = neon_typed.downcast_ref().unwrap();
 
             assert_eq!(naive_result.distances, neon_result.distances);
         } else {
@@ -564,10 +553,10 @@ mod tests {
         let naive_output = op.execute_naive(&sequences).unwrap();
         let parallel_output = op.execute_parallel(&sequences, 2).unwrap();
 
-        if let (OperationOutput::Statistics(naive_json), OperationOutput::Statistics(parallel_json)) =
+        if let (OperationOutput::Typed(naive_typed), OperationOutput::Typed(parallel_typed)) =
             (naive_output, parallel_output) {
-            let naive_result: EditDistanceMatrix = serde_json::from_value(naive_json).unwrap();
-            let parallel_result: EditDistanceMatrix = serde_json::from_value(parallel_json).unwrap();
+            let naive_result: &EditDistanceMatrix = naive_typed.downcast_ref().unwrap();
+            let parallel_result: &EditDistanceMatrix = parallel_typed.downcast_ref().unwrap();
 
             assert_eq!(naive_result.distances, parallel_result.distances);
         } else {
@@ -591,10 +580,10 @@ mod tests {
         let naive_output = op.execute_naive(&sequences).unwrap();
         let amx_output = op.execute_amx(&sequences).unwrap();
 
-        if let (OperationOutput::Statistics(naive_json), OperationOutput::Statistics(amx_json)) =
+        if let (OperationOutput::Typed(naive_typed), OperationOutput::Typed(amx_typed)) =
             (naive_output, amx_output) {
-            let naive_result: EditDistanceMatrix = serde_json::from_value(naive_json).unwrap();
-            let amx_result: EditDistanceMatrix = serde_json::from_value(amx_json).unwrap();
+            let naive_result: &EditDistanceMatrix = naive_typed.downcast_ref().unwrap();
+            let amx_result: &EditDistanceMatrix = amx_typed.downcast_ref().unwrap();
 
             assert_eq!(naive_result.distances, amx_result.distances);
         } else {
@@ -0,0 +1,345 @@
+//! K-mer Spectrum Error Correction Operation
+//!
+//! Simple spectral-alignment correction: k-mers seen at least `min_coverage`
+//! times in the dataset are "solid". A base covered only by weak k-mers is
+//! replaced by the one alternative base that makes every k-mer covering it
+//! solid (left alone if no or several alternatives do).
+//!
+//! # Operation Characteristics
+//! - **Category**: ElementWise (read-modify; needs a dataset-wide table first)
+//! - **Complexity**: 0.65 (hash lookups per k-mer + data-dependent branches)
+//! - **Output**: Corrected sequences (same order, qualities unchanged)
+//! - **NEON benefit**: None expected (lookup and branch bound)
+//!
+//! # Implementation Notes
+//! - Count table: `KmerSpectrum::count_table` (canonical 2-bit k-mers)
+//! - Reads without any solid k-mer are left as they are (nothing to anchor a
+//!   correction to), which bounds the work on low-coverage data
+//! - At most `max_corrections` substitutions per read
+
+use crate::kmer_spectrum::KmerSpectrum;
+use crate::{HardwareConfig, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Error correction operation
+pub struct ErrorCorrection {
+    spectrum: KmerSpectrum,
+    /// Minimum k-mer count for a solid k-mer
+    pub min_coverage: u32,
+    /// Maximum substitutions per read
+    pub max_corrections: usize,
+}
+
+/// Per-dataset correction counts
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorrectionStats {
+    pub total_sequences: usize,
+    /// Reads with at least one substitution
+    pub corrected_sequences: usize,
+    pub corrected_bases: usize,
+    /// Reads still containing weak k-mers after correction
+    pub uncorrectable_sequences: usize,
+    /// Distinct k-mers in the count table
+    pub table_entries: usize,
+}
+
+impl CorrectionStats {
+    fn add(&mut self, other: &Self) {
+        self.total_sequences += other.total_sequences;
+        self.corrected_sequences += other.corrected_sequences;
+        self.corrected_bases += other.corrected_bases;
+        self.uncorrectable_sequences += other.uncorrectable_sequences;
+    }
+}
+
+impl ErrorCorrection {
+    pub fn new(k: usize, min_coverage: u32) -> Self {
+        Self {
+            spectrum: KmerSpectrum::new(k, true),
+            min_coverage,
+            max_corrections: 4,
+        }
+    }
+
+    pub fn with_max_corrections(mut self, max_corrections: usize) -> Self {
+        self.max_corrections = max_corrections;
+        self
+    }
+
+    pub fn k(&self) -> usize {
+        self.spectrum.k
+    }
+
+    fn is_solid(&self, table: &HashMap<u64, u32>, kmer: &[u8]) -> bool {
+        self.spectrum
+            .kmer_code(kmer)
+            .and_then(|code| table.get(&code))
+            .is_some_and(|&count| count >= self.min_coverage)
+    }
+
+    /// Correct `seq` in place
+    fn correct_read(&self, table: &HashMap<u64, u32>, seq: &mut [u8], stats: &mut CorrectionStats) {
+        let k = self.k();
+        stats.total_sequences += 1;
+        if seq.len() < k {
+            return;
+        }
+
+        let num_kmers = seq.len() - k + 1;
+        let mut solid: Vec<bool> = (0..num_kmers).map(|i| self.is_solid(table, &seq[i..i + k])).collect();
+        if solid.iter().all(|&s| s) {
+            return;
+        }
+        if !solid.iter().any(|&s| s) {
+            stats.uncorrectable_sequences += 1;
+            return;
+        }
+
+        let mut corrections = 0;
+        for position in 0..seq.len() {
+            if corrections == self.max_corrections {
+                break;
+            }
+
+            // K-mers covering this base
+            let first = position.saturating_sub(k - 1);
+            let last = position.min(num_kmers - 1);
+            if solid[first..=last].iter().any(|&s| s) {
+                continue;
+            }
+
+            let original = seq[position];
+            let mut fix = None;
+            let mut candidates = 0;
+            for &base in b"ACGT" {
+                if base == original {
+                    continue;
+                }
+                seq[position] = base;
+                if (first..=last).all(|i| self.is_solid(table, &seq[i..i + k])) {
+                    candidates += 1;
+                    fix = Some(base);
+                }
+            }
+
+            match fix {
+                Some(base) if candidates == 1 => {
+                    seq[position] = base;
+                    solid[first..=last].fill(true);
+                    corrections += 1;
+                }
+                _ => seq[position] = original,
+            }
+        }
+
+        if corrections > 0 {
+            stats.corrected_sequences += 1;
+            stats.corrected_bases += corrections;
+        }
+        if solid.iter().any(|&s| !s) {
+            stats.uncorrectable_sequences += 1;
+        }
+    }
+
+    fn correct_record(&self, table: &HashMap<u64, u32>, record: &SequenceRecord, stats: &mut CorrectionStats) -> SequenceRecord {
+        let mut corrected = record.clone();
+        self.correct_read(table, &mut corrected.sequence, stats);
+        corrected
+    }
+
+    /// Corrected reads and correction counts
+    pub fn correct(&self, data: &[SequenceRecord], num_threads: usize) -> Result<(Vec<SequenceRecord>, CorrectionStats)> {
+        let table = self.spectrum.count_table(data, num_threads)?;
+
+        let (records, mut stats) = if num_threads > 1 {
+            let pool = crate::thread_pool::get(num_threads)?;
+            let results: Vec<(SequenceRecord, CorrectionStats)> = pool.install(|| {
+                data.par_iter()
+                    .map(|record| {
+                        let mut stats = CorrectionStats::default();
+                        (self.correct_record(&table, record, &mut stats), stats)
+                    })
+                    .collect()
+            });
+
+            let mut stats = CorrectionStats::default();
+            let records = results
+                .into_iter()
+                .map(|(record, read_stats)| {
+                    stats.add(&read_stats);
+                    record
+                })
+                .collect();
+            (records, stats)
+        } else {
+            let mut stats = CorrectionStats::default();
+            let records = data.iter().map(|record| self.correct_record(&table, record, &mut stats)).collect();
+            (records, stats)
+        };
+
+        stats.table_entries = table.len();
+        Ok((records, stats))
+    }
+}
+
+impl Default for ErrorCorrection {
+    /// k = 21, solid at 3× or more
+    fn default() -> Self {
+        Self::new(21, 3)
+    }
+}
+
+impl PrimitiveOperation for ErrorCorrection {
+    fn name(&self) -> &str {
+        "error_correction"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::ElementWise
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "k": self.k(),
+            "min_coverage": self.min_coverage,
+            "max_corrections": self.max_corrections,
+        })
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        let (records, _) = self.correct(data, 1)?;
+        Ok(OperationOutput::Records(records))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let (records, _) = self.correct(data, num_threads)?;
+        Ok(OperationOutput::Records(records))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        _batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        // Corrections need the dataset-wide table, so chunks cannot run the
+        // whole operation independently
+        self.execute_parallel(data, num_threads)
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        self.execute_parallel(data, config.num_threads)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Error-free reads from a random 2 kb genome at ~10× coverage, plus the
+    /// same reads with one substitution in every fourth read
+    fn test_reads() -> (Vec<SequenceRecord>, Vec<SequenceRecord>) {
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        let genome: Vec<u8> = (0..2_000).map(|_| b"ACGT"[(next() % 4) as usize]).collect();
+        let truth: Vec<SequenceRecord> = (0..134)
+            .map(|i| {
+                let start = (next() % (genome.len() as u64 - 150)) as usize;
+                SequenceRecord::fasta(format!("read_{}", i), genome[start..start + 150].to_vec())
+            })
+            .collect();
+
+        let reads = truth
+            .iter()
+            .enumerate()
+            .map(|(i, record)| {
+                let mut record = record.clone();
+                if i % 4 == 0 {
+                    let position = (next() % 150) as usize;
+                    record.sequence[position] = if i % 8 == 0 { b'N' } else { b"CGTA"[b"ACGT".iter().position(|&b| b == record.sequence[position]).unwrap()] };
+                }
+                record
+            })
+            .collect();
+
+        (truth, reads)
+    }
+
+    #[test]
+    fn test_error_correction_fixes_substitutions() {
+        let (truth, reads) = test_reads();
+        let op = ErrorCorrection::new(15, 3);
+        let (corrected, stats) = op.correct(&reads, 1).unwrap();
+
+        let erroneous = reads.iter().zip(&truth).filter(|(r, t)| r.sequence != t.sequence).count();
+        let fixed = corrected
+            .iter()
+            .zip(&reads)
+            .zip(&truth)
+            .filter(|((c, r), t)| r.sequence != t.sequence && c.sequence == t.sequence)
+            .count();
+        let broken = corrected
+            .iter()
+            .zip(&reads)
+            .zip(&truth)
+            .filter(|((c, r), t)| r.sequence == t.sequence && c.sequence != t.sequence)
+            .count();
+
+        assert_eq!(erroneous, 34);
+        assert!(fixed >= erroneous * 9 / 10, "fixed {} of {}", fixed, erroneous);
+        assert_eq!(broken, 0);
+        assert_eq!(stats.total_sequences, reads.len());
+        assert_eq!(stats.corrected_sequences, fixed);
+        assert!(stats.table_entries > 1_000);
+    }
+
+    #[test]
+    fn test_error_correction_backends_match() {
+        let (_, reads) = test_reads();
+        let op = ErrorCorrection::new(15, 3);
+        let naive = op.execute_naive(&reads).unwrap();
+
+        for output in [op.execute_parallel(&reads, 3).unwrap(), op.execute_parallel_chunked(&reads, 2).unwrap()] {
+            match (&naive, &output) {
+                (OperationOutput::Records(a), OperationOutput::Records(b)) => assert_eq!(a, b),
+                _ => panic!("Expected record output"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_error_correction_leaves_unanchored_reads() {
+        // Single read: every k-mer is weak, nothing to anchor a correction
+        let op = ErrorCorrection::new(5, 2);
+        let data = vec![
+            SequenceRecord::fasta("lonely".to_string(), b"ACGTTGCAAGT".to_vec()),
+            SequenceRecord::fasta("short".to_string(), b"ACG".to_vec()),
+        ];
+        let (corrected, stats) = op.correct(&data, 1).unwrap();
+
+        assert_eq!(corrected, data);
+        assert_eq!(stats.corrected_bases, 0);
+        assert_eq!(stats.uncorrectable_sequences, 1);
+    }
+}
@@ -13,9 +13,12 @@
 //! - Checks quality score lengths match sequence lengths
 //! - Handles malformed records gracefully
 //! - NEON can accelerate quality score validation
+//! - Lines are split by an `asbb_core::io::ParserBackend` (scalar or NEON
+//!   newline scanning), selected with [`FastqParsing::with_parser`]
 
 use crate::{OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
 use anyhow::{anyhow, Result};
+use asbb_core::io::{newline_offsets, ParserBackend};
 use rayon::prelude::*;
 
 #[cfg(target_arch = "aarch64")]
@@ -25,11 +28,38 @@ use std::arch::aarch64::*;
 pub struct FastqParsing {
     /// Validate quality scores are in valid range
     validate_quality: bool,
+    /// Line tokenizer
+    parser: ParserBackend,
 }
 
 impl FastqParsing {
     pub fn new(validate_quality: bool) -> Self {
-        Self { validate_quality }
+        Self {
+            validate_quality,
+            parser: ParserBackend::Scalar,
+        }
+    }
+
+    /// Split lines with `parser` instead of the scalar tokenizer
+    pub fn with_parser(mut self, parser: ParserBackend) -> Self {
+        self.parser = parser;
+        self
+    }
+
+    /// Split FASTQ text into lines (without terminators) with the configured tokenizer
+    fn lines<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        let ends = newline_offsets(text.as_bytes(), self.parser);
+        // Text after the last terminator is a final unterminated line
+        let tail = (ends.last().map_or(0, |&end| end + 1) < text.len()).then_some(text.len());
+        let mut start = 0;
+        ends.into_iter()
+            .chain(tail)
+            .map(|end| {
+                let line = &text[start..end];
+                start = end + 1;
+                line.strip_suffix('\r').unwrap_or(line)
+            })
+            .collect()
     }
 
     /// Parse a single FASTQ record from 4 lines
@@ -156,7 +186,7 @@ impl FastqParsing {
 
     /// Parse FASTQ data from text
     fn parse_fastq_text(&self, text: &str) -> Vec<Result<SequenceRecord>> {
-        let lines: Vec<&str> = text.lines().collect();
+        let lines = self.lines(text);
         let num_records = lines.len() / 4;
 
         let mut records = Vec::with_capacity(num_records);
@@ -185,6 +215,20 @@ impl PrimitiveOperation for FastqParsing {
         OperationCategory::IO
     }
 
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "validate_quality": self.validate_quality,
+            "parser": self.parser.name(),
+        })
+    }
+
+    fn parser_variant(&self, parser: ParserBackend) -> Option<Box<dyn PrimitiveOperation>> {
+        Some(Box::new(FastqParsing {
+            validate_quality: self.validate_quality,
+            parser,
+        }))
+    }
+
     fn execute_naive(&self, sequences: &[SequenceRecord]) -> Result<OperationOutput> {
         // For testing purposes, we simulate FASTQ parsing by converting
         // SequenceRecords back to FASTQ format and then parsing them
@@ -238,13 +282,10 @@ impl PrimitiveOperation for FastqParsing {
         }
 
         // Parse in parallel (chunk by records)
-        let lines: Vec<&str> = fastq_text.lines().collect();
+        let lines = self.lines(&fastq_text);
         let num_records = lines.len() / 4;
 
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()
-            .unwrap();
+        let pool = crate::thread_pool::get(num_threads)?;
 
         let results: Vec<Result<SequenceRecord>> = pool.install(|| {
             (0..num_records).into_par_iter().map(|i| {
@@ -443,6 +484,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parser_backends_match() {
+        let input: Vec<SequenceRecord> = (0..50)
+            .map(|i| SequenceRecord {
+                id: format!("seq{}", i),
+                sequence: b"ACGTTGCA".repeat(i % 7 + 1),
+                quality: Some(b"@IJ#".repeat((i % 7 + 1) * 2)),
+            })
+            .collect();
+
+        let scalar = FastqParsing::new(true).execute_naive(&input).unwrap();
+        for parser in ParserBackend::ALL {
+            let op = FastqParsing::new(true).parser_variant(parser).unwrap();
+            assert_eq!(op.parameters()["parser"], parser.name());
+            assert_eq!(op.execute_naive(&input).unwrap(), scalar);
+            assert_eq!(op.execute_parallel(&input, 2).unwrap(), scalar);
+        }
+        assert_eq!(scalar, OperationOutput::Records(input));
+
+        let op = FastqParsing::new(true).with_parser(ParserBackend::Neon);
+        assert_eq!(op.lines("@a\r\nAC\n+\nII"), vec!["@a", "AC", "+", "II"]);
+    }
+
     #[test]
     fn test_parallel_execution() {
         let op = FastqParsing::new(true);
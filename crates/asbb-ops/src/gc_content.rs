@@ -17,7 +17,7 @@
 //! Very similar to base counting - expect similar patterns.
 
 use anyhow::Result;
-use asbb_core::{encoding::BitSeq, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use asbb_core::{encoding::BitSeq, packed::PackedRecords, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord, SequenceView};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -45,9 +45,7 @@ impl GcContent {
         }
 
         result.finalize();
-        Ok(OperationOutput::Statistics(
-            serde_json::to_value(result)?,
-        ))
+        Ok(OperationOutput::typed(result))
     }
 
     /// Execute GC content on 2-bit encoded sequences (NEON)
@@ -73,9 +71,7 @@ impl GcContent {
             }
 
             result.finalize();
-            Ok(OperationOutput::Statistics(
-                serde_json::to_value(result)?,
-            ))
+            Ok(OperationOutput::typed(result))
         }
 
         #[cfg(not(target_arch = "aarch64"))]
@@ -157,40 +153,18 @@ impl PrimitiveOperation for GcContent {
         }
 
         result.finalize();
-        Ok(OperationOutput::Statistics(
-            serde_json::to_value(result)?,
-        ))
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
-        // NEON SIMD implementation
-        #[cfg(target_arch = "aarch64")]
-        {
-            let mut result = GcResult::new();
-
-            for record in data {
-                let seq = &record.sequence;
-                result.total_bases += seq.len();
-
-                // Count using NEON intrinsics
-                let gc_result = count_gc_neon(seq);
-                result.count_g += gc_result.count_g;
-                result.count_c += gc_result.count_c;
-                result.count_at += gc_result.count_at;
-                result.count_n += gc_result.count_n;
-            }
-
-            result.finalize();
-            Ok(OperationOutput::Statistics(
-                serde_json::to_value(result)?,
-            ))
+        // NEON on aarch64, SSE2/AVX2 on x86_64 (see `count_gc`)
+        let mut result = GcResult::new();
+        for record in data {
+            result.add(&count_gc(&record.sequence));
         }
 
-        #[cfg(not(target_arch = "aarch64"))]
-        {
-            // Fall back to naive on non-ARM
-            self.execute_naive(data)
-        }
+        result.finalize();
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_parallel(
@@ -198,39 +172,13 @@ impl PrimitiveOperation for GcContent {
         data: &[SequenceRecord],
         num_threads: usize,
     ) -> Result<OperationOutput> {
-        // Configure Rayon thread pool
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()?;
+        // Shared Rayon thread pool (built once per thread count)
+        let pool = crate::thread_pool::get(num_threads)?;
 
         let mut result = pool.install(|| {
             data.par_iter()
-                .map(|record| {
-                    #[cfg(target_arch = "aarch64")]
-                    {
-                        // Use NEON per-thread for true combined optimization
-                        count_gc_neon(&record.sequence)
-                    }
-
-                    #[cfg(not(target_arch = "aarch64"))]
-                    {
-                        // Fall back to naive on non-ARM
-                        let mut result = GcResult::new();
-                        result.total_bases = record.sequence.len();
-
-                        for &base in &record.sequence {
-                            match base {
-                                b'G' | b'g' => result.count_g += 1,
-                                b'C' | b'c' => result.count_c += 1,
-                                b'A' | b'a' | b'T' | b't' => result.count_at += 1,
-                                b'N' | b'n' => result.count_n += 1,
-                                _ => {}
-                            }
-                        }
-
-                        result
-                    }
-                })
+                // SIMD per-thread for true combined optimization
+                .map(|record| count_gc(&record.sequence))
                 .reduce(
                     || GcResult::new(),
                     |mut a, b| {
@@ -241,9 +189,66 @@ impl PrimitiveOperation for GcContent {
         });
 
         result.finalize();
-        Ok(OperationOutput::Statistics(
-            serde_json::to_value(result)?,
-        ))
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let merge = |mut a: GcResult, b: GcResult| {
+            a.add(&b);
+            a
+        };
+        let mut result = crate::chunked::reduce_neon(self, data, num_threads, batch_size, merge)?
+            .unwrap_or_else(GcResult::new);
+        result.finalize();
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_intra_read(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        // Counts are position-independent: no lookahead across segments
+        let merge = |mut a: GcResult, b: GcResult| {
+            a.add(&b);
+            a
+        };
+        let mut result = crate::intra_read::map_reduce(
+            data,
+            num_threads,
+            0,
+            |segment| count_gc(segment.bases),
+            merge,
+        )?
+        .unwrap_or_else(GcResult::new);
+        result.finalize();
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_views(&self, data: &[SequenceView]) -> Result<OperationOutput> {
+        let mut result = GcResult::new();
+        for view in data {
+            result.add(&count_gc(view.sequence));
+        }
+
+        result.finalize();
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_packed(&self, data: &PackedRecords) -> Result<OperationOutput> {
+        // Counts are position-independent, so the contiguous buffer is
+        // processed as one sequence (no per-record remainders)
+        let mut result = count_gc(data.sequence_buffer());
+
+        result.finalize();
+        Ok(OperationOutput::typed(result))
     }
 }
 
@@ -253,10 +258,41 @@ impl Default for GcContent {
     }
 }
 
+/// Count G/C/AT/N in one sequence (NEON on aarch64, `crate::simd` elsewhere)
+pub(crate) fn count_gc(seq: &[u8]) -> GcResult {
+    #[cfg(target_arch = "aarch64")]
+    {
+        count_gc_neon(seq)
+    }
+
+    #[cfg(not(target_arch = "aarch64"))]
+    {
+        let [count_g, count_c, count_a, count_t, count_n] =
+            crate::simd::count_letters(seq, [b'G', b'C', b'A', b'T', b'N']);
+        let mut result = GcResult::new();
+        result.count_g = count_g;
+        result.count_c = count_c;
+        result.count_at = count_a + count_t;
+        result.count_n = count_n;
+        result.total_bases = seq.len();
+        result
+    }
+}
+
 // ============================================================================
 // NEON SIMD Implementation
 // ============================================================================
 
+/// 16-byte chunks a u8 accumulator lane can count before it can overflow
+/// (one increment per chunk; ~4 kb of sequence per block)
+#[cfg(target_arch = "aarch64")]
+const MAX_CHUNKS_PER_FLUSH: usize = 255;
+
+/// 2-bit chunks a u8 accumulator lane can count before it can overflow
+/// (each byte packs four bases, so up to four increments per chunk)
+#[cfg(target_arch = "aarch64")]
+const MAX_2BIT_CHUNKS_PER_FLUSH: usize = 63;
+
 #[cfg(target_arch = "aarch64")]
 fn count_gc_neon(seq: &[u8]) -> GcResult {
     use std::arch::aarch64::*;
@@ -269,12 +305,6 @@ fn count_gc_neon(seq: &[u8]) -> GcResult {
     let remainder = chunks.remainder();
 
     unsafe {
-        // Accumulators for vectorized counts
-        let mut vec_count_g = vdupq_n_u8(0);
-        let mut vec_count_c = vdupq_n_u8(0);
-        let mut vec_count_at = vdupq_n_u8(0);
-        let mut vec_count_n = vdupq_n_u8(0);
-
         // Comparison values
         let cmp_g_upper = vdupq_n_u8(b'G');
         let cmp_g_lower = vdupq_n_u8(b'g');
@@ -289,45 +319,55 @@ fn count_gc_neon(seq: &[u8]) -> GcResult {
 
         let ones = vdupq_n_u8(1);
 
-        for chunk in chunks {
-            // Load 16 bytes
-            let data = vld1q_u8(chunk.as_ptr());
-
-            // Compare with each base (both upper and lower case)
-            let mask_g = vorrq_u8(
-                vceqq_u8(data, cmp_g_upper),
-                vceqq_u8(data, cmp_g_lower),
-            );
-            let mask_c = vorrq_u8(
-                vceqq_u8(data, cmp_c_upper),
-                vceqq_u8(data, cmp_c_lower),
-            );
-            let mask_a = vorrq_u8(
-                vceqq_u8(data, cmp_a_upper),
-                vceqq_u8(data, cmp_a_lower),
-            );
-            let mask_t = vorrq_u8(
-                vceqq_u8(data, cmp_t_upper),
-                vceqq_u8(data, cmp_t_lower),
-            );
-            let mask_at = vorrq_u8(mask_a, mask_t);
-            let mask_n = vorrq_u8(
-                vceqq_u8(data, cmp_n_upper),
-                vceqq_u8(data, cmp_n_lower),
-            );
-
-            // Increment counts where mask is true
-            vec_count_g = vaddq_u8(vec_count_g, vandq_u8(mask_g, ones));
-            vec_count_c = vaddq_u8(vec_count_c, vandq_u8(mask_c, ones));
-            vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at, ones));
-            vec_count_n = vaddq_u8(vec_count_n, vandq_u8(mask_n, ones));
-        }
+        // u8 lanes overflow after 255 increments, so flush to usize every block
+        let vector_bytes = &seq[..seq.len() - remainder.len()];
+        for block in vector_bytes.chunks(16 * MAX_CHUNKS_PER_FLUSH) {
+            // Accumulators for vectorized counts
+            let mut vec_count_g = vdupq_n_u8(0);
+            let mut vec_count_c = vdupq_n_u8(0);
+            let mut vec_count_at = vdupq_n_u8(0);
+            let mut vec_count_n = vdupq_n_u8(0);
+
+            for chunk in block.chunks_exact(16) {
+                // Load 16 bytes
+                let data = vld1q_u8(chunk.as_ptr());
+
+                // Compare with each base (both upper and lower case)
+                let mask_g = vorrq_u8(
+                    vceqq_u8(data, cmp_g_upper),
+                    vceqq_u8(data, cmp_g_lower),
+                );
+                let mask_c = vorrq_u8(
+                    vceqq_u8(data, cmp_c_upper),
+                    vceqq_u8(data, cmp_c_lower),
+                );
+                let mask_a = vorrq_u8(
+                    vceqq_u8(data, cmp_a_upper),
+                    vceqq_u8(data, cmp_a_lower),
+                );
+                let mask_t = vorrq_u8(
+                    vceqq_u8(data, cmp_t_upper),
+                    vceqq_u8(data, cmp_t_lower),
+                );
+                let mask_at = vorrq_u8(mask_a, mask_t);
+                let mask_n = vorrq_u8(
+                    vceqq_u8(data, cmp_n_upper),
+                    vceqq_u8(data, cmp_n_lower),
+                );
+
+                // Increment counts where mask is true
+                vec_count_g = vaddq_u8(vec_count_g, vandq_u8(mask_g, ones));
+                vec_count_c = vaddq_u8(vec_count_c, vandq_u8(mask_c, ones));
+                vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at, ones));
+                vec_count_n = vaddq_u8(vec_count_n, vandq_u8(mask_n, ones));
+            }
 
-        // Horizontal sum to get total counts
-        result.count_g = horizontal_sum_u8(vec_count_g);
-        result.count_c = horizontal_sum_u8(vec_count_c);
-        result.count_at = horizontal_sum_u8(vec_count_at);
-        result.count_n = horizontal_sum_u8(vec_count_n);
+            // Horizontal sum to get total counts
+            result.count_g += horizontal_sum_u8(vec_count_g);
+            result.count_c += horizontal_sum_u8(vec_count_c);
+            result.count_at += horizontal_sum_u8(vec_count_at);
+            result.count_n += horizontal_sum_u8(vec_count_n);
+        }
     }
 
     // Process remainder with scalar code
@@ -381,11 +421,6 @@ fn count_gc_2bit_neon(bitseq: &BitSeq) -> GcResult {
     let remainder_bytes = chunks.remainder();
 
     unsafe {
-        // Accumulators for each base type
-        let mut vec_count_g = vdupq_n_u8(0);
-        let mut vec_count_c = vdupq_n_u8(0);
-        let mut vec_count_at = vdupq_n_u8(0);
-
         // Masks for extracting 2-bit pairs
         let mask_bits_67 = vdupq_n_u8(0b11000000);
         let mask_bits_45 = vdupq_n_u8(0b00110000);
@@ -400,44 +435,53 @@ fn count_gc_2bit_neon(bitseq: &BitSeq) -> GcResult {
 
         let ones = vdupq_n_u8(1);
 
-        for chunk in chunks {
-            // Load 16 bytes (64 bases)
-            let data_vec = vld1q_u8(chunk.as_ptr());
-
-            // Process each 2-bit position
-            // Position 0 (bits 6-7)
-            let bases_0 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_67), 6);
-            vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_0, cmp_c), ones));
-            vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_0, cmp_g), ones));
-            let mask_at_0 = vorrq_u8(vceqq_u8(bases_0, cmp_a), vceqq_u8(bases_0, cmp_t));
-            vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_0, ones));
-
-            // Position 1 (bits 4-5)
-            let bases_1 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_45), 4);
-            vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_1, cmp_c), ones));
-            vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_1, cmp_g), ones));
-            let mask_at_1 = vorrq_u8(vceqq_u8(bases_1, cmp_a), vceqq_u8(bases_1, cmp_t));
-            vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_1, ones));
-
-            // Position 2 (bits 2-3)
-            let bases_2 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_23), 2);
-            vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_2, cmp_c), ones));
-            vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_2, cmp_g), ones));
-            let mask_at_2 = vorrq_u8(vceqq_u8(bases_2, cmp_a), vceqq_u8(bases_2, cmp_t));
-            vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_2, ones));
-
-            // Position 3 (bits 0-1)
-            let bases_3 = vandq_u8(data_vec, mask_bits_01);
-            vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_3, cmp_c), ones));
-            vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_3, cmp_g), ones));
-            let mask_at_3 = vorrq_u8(vceqq_u8(bases_3, cmp_a), vceqq_u8(bases_3, cmp_t));
-            vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_3, ones));
-        }
+        // u8 lanes overflow after 255 increments, so flush to usize every block
+        let vector_bytes = &data[..data.len() - remainder_bytes.len()];
+        for block in vector_bytes.chunks(16 * MAX_2BIT_CHUNKS_PER_FLUSH) {
+            // Accumulators for each base type
+            let mut vec_count_g = vdupq_n_u8(0);
+            let mut vec_count_c = vdupq_n_u8(0);
+            let mut vec_count_at = vdupq_n_u8(0);
+
+            for chunk in block.chunks_exact(16) {
+                // Load 16 bytes (64 bases)
+                let data_vec = vld1q_u8(chunk.as_ptr());
+
+                // Process each 2-bit position
+                // Position 0 (bits 6-7)
+                let bases_0 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_67), 6);
+                vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_0, cmp_c), ones));
+                vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_0, cmp_g), ones));
+                let mask_at_0 = vorrq_u8(vceqq_u8(bases_0, cmp_a), vceqq_u8(bases_0, cmp_t));
+                vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_0, ones));
+
+                // Position 1 (bits 4-5)
+                let bases_1 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_45), 4);
+                vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_1, cmp_c), ones));
+                vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_1, cmp_g), ones));
+                let mask_at_1 = vorrq_u8(vceqq_u8(bases_1, cmp_a), vceqq_u8(bases_1, cmp_t));
+                vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_1, ones));
+
+                // Position 2 (bits 2-3)
+                let bases_2 = vshrq_n_u8(vandq_u8(data_vec, mask_bits_23), 2);
+                vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_2, cmp_c), ones));
+                vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_2, cmp_g), ones));
+                let mask_at_2 = vorrq_u8(vceqq_u8(bases_2, cmp_a), vceqq_u8(bases_2, cmp_t));
+                vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_2, ones));
+
+                // Position 3 (bits 0-1)
+                let bases_3 = vandq_u8(data_vec, mask_bits_01);
+                vec_count_c = vaddq_u8(vec_count_c, vandq_u8(vceqq_u8(bases_3, cmp_c), ones));
+                vec_count_g = vaddq_u8(vec_count_g, vandq_u8(vceqq_u8(bases_3, cmp_g), ones));
+                let mask_at_3 = vorrq_u8(vceqq_u8(bases_3, cmp_a), vceqq_u8(bases_3, cmp_t));
+                vec_count_at = vaddq_u8(vec_count_at, vandq_u8(mask_at_3, ones));
+            }
 
-        // Horizontal sum to get total counts
-        result.count_g = horizontal_sum_u8(vec_count_g);
-        result.count_c = horizontal_sum_u8(vec_count_c);
-        result.count_at = horizontal_sum_u8(vec_count_at);
+            // Horizontal sum to get total counts
+            result.count_g += horizontal_sum_u8(vec_count_g);
+            result.count_c += horizontal_sum_u8(vec_count_c);
+            result.count_at += horizontal_sum_u8(vec_count_at);
+        }
     }
 
     // Process remainder bytes with scalar code
@@ -484,8 +528,7 @@ mod tests {
 
         let result = op.execute_naive(&data).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let gc: GcResult = serde_json::from_value(value).unwrap();
+        if let Some(gc) = result.statistics::<GcResult>() {
 
             // seq1: 2A, 2C, 2G, 2T = 4 AT, 4 GC
             // seq2: 4G, 4C = 0 AT, 8 GC
@@ -506,7 +549,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(target_arch = "aarch64")]
     fn test_gc_content_neon() {
         let op = GcContent::new();
         let data = create_test_data();
@@ -518,6 +560,18 @@ mod tests {
         assert_eq!(result_naive, result_neon);
     }
 
+    #[test]
+    fn test_gc_content_packed_and_views() {
+        let op = GcContent::new();
+        let data = create_test_data();
+        let packed = PackedRecords::from_records(&data).unwrap();
+
+        assert_eq!(op.execute_naive(&data).unwrap(), op.execute_packed(&packed).unwrap());
+
+        let views: Vec<SequenceView> = data.iter().map(SequenceRecord::view).collect();
+        assert_eq!(op.execute_naive(&data).unwrap(), op.execute_views(&views).unwrap());
+    }
+
     #[test]
     fn test_gc_content_parallel() {
         let op = GcContent::new();
@@ -540,8 +594,7 @@ mod tests {
 
         let result = op.execute_naive(&data).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let gc: GcResult = serde_json::from_value(value).unwrap();
+        if let Some(gc) = result.statistics::<GcResult>() {
             assert_eq!(gc.count_g, 4);
             assert_eq!(gc.count_c, 4);
             assert_eq!(gc.count_gc, 8);
@@ -561,8 +614,7 @@ mod tests {
 
         let result = op.execute_naive(&data).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let gc: GcResult = serde_json::from_value(value).unwrap();
+        if let Some(gc) = result.statistics::<GcResult>() {
             assert_eq!(gc.count_g, 0);
             assert_eq!(gc.count_c, 0);
             assert_eq!(gc.count_gc, 0);
@@ -585,8 +637,7 @@ mod tests {
 
         let result = op.execute_2bit_naive(&bitseqs).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let gc: GcResult = serde_json::from_value(value).unwrap();
+        if let Some(gc) = result.statistics::<GcResult>() {
 
             assert_eq!(gc.count_g, 6);
             assert_eq!(gc.count_c, 6);
@@ -637,9 +688,46 @@ mod tests {
         assert_eq!(result_naive, result_neon);
 
         // Verify GC content is 50% (ACGTACGT pattern)
-        if let OperationOutput::Statistics(value) = result_neon {
-            let gc: GcResult = serde_json::from_value(value).unwrap();
+        if let Some(gc) = result_neon.statistics::<GcResult>() {
             assert!((gc.gc_percent - 50.0).abs() < 0.01);
         }
     }
+
+    #[test]
+    fn test_gc_content_neon_long_reads() {
+        // u8 lanes overflow after 255 chunks (~4 kb of matching bases)
+        let op = GcContent::new();
+        let data = vec![
+            SequenceRecord::fasta("gc_rich".to_string(), vec![b'G'; 100_000]),
+            SequenceRecord::fasta("long_read".to_string(), b"GCATN".repeat(20_001)),
+        ];
+
+        let result_naive = op.execute_naive(&data).unwrap();
+        let result_neon = op.execute_neon(&data).unwrap();
+        assert_eq!(result_naive, result_neon);
+        for threads in [1, 3] {
+            assert_eq!(result_naive, op.execute_parallel_intra_read(&data, threads).unwrap());
+        }
+
+        if let Some(result) = result_neon.statistics::<GcResult>() {
+            assert_eq!(result.count_g, 100_000 + 20_001);
+            assert_eq!(result.count_at, 2 * 20_001);
+        } else {
+            panic!("Expected Statistics output");
+        }
+    }
+
+    #[test]
+    #[cfg(target_arch = "aarch64")]
+    fn test_gc_content_2bit_neon_long_reads() {
+        let op = GcContent::new();
+        let bitseqs = vec![
+            BitSeq::from_ascii(&vec![b'C'; 50_000]),
+            BitSeq::from_ascii(&b"GGCA".repeat(25_003)),
+        ];
+
+        let result_naive = op.execute_2bit_naive(&bitseqs).unwrap();
+        let result_neon = op.execute_2bit_neon(&bitseqs).unwrap();
+        assert_eq!(result_naive, result_neon);
+    }
 }
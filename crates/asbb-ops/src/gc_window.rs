@@ -0,0 +1,380 @@
+//! GC Window Profile Operation
+//!
+//! Produces a per-read sliding-window GC track: one GC fraction per window
+//! of `window` bases, every `step` bases. Unlike the other element-wise
+//! operations the output grows with the input (one f32 per window per read),
+//! so the cost includes allocating and writing the tracks, not only reading
+//! the bases.
+//!
+//! # Operation Characteristics
+//! - **Category**: Element-wise
+//! - **Complexity**: 0.40 (classification + prefix sums + window differences)
+//! - **Output**: `Vec<f32>` track per read (write-bandwidth bound at step 1)
+//! - **NEON benefit**: Moderate (prefix sums; window values stay scalar)
+//!
+//! # Implementation Notes
+//! - Naive: recount every window (O(window) per window)
+//! - NEON: G/C and ACGT indicators for 16 bases, inclusive prefix sums
+//!   within the vector (4 shifted adds), widened to u32 running totals; each
+//!   window is then two prefix differences
+//! - GC fraction = (G+C) / ACGT bases in the window, as in `GcContent`
+//!   (N and other bytes are excluded); windows without ACGT bases report 0
+//! - Only windows fully inside the read are reported (reads shorter than
+//!   the window get an empty track)
+
+use crate::{HardwareConfig, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Sliding-window GC profile operation
+pub struct GcWindowProfile {
+    pub window: usize,
+    pub step: usize,
+}
+
+impl GcWindowProfile {
+    pub fn new(window: usize, step: usize) -> Self {
+        assert!(window > 0 && step > 0, "Window and step must be at least 1");
+        Self { window, step }
+    }
+
+    /// Number of windows in a read of `len` bases
+    fn num_windows(&self, len: usize) -> usize {
+        if len < self.window {
+            0
+        } else {
+            (len - self.window) / self.step + 1
+        }
+    }
+
+    /// GC track of one read by recounting every window
+    fn track_naive(&self, seq: &[u8]) -> Vec<f32> {
+        (0..self.num_windows(seq.len()))
+            .map(|i| {
+                let start = i * self.step;
+                let (mut gc, mut valid) = (0u32, 0u32);
+                for &base in &seq[start..start + self.window] {
+                    let (is_gc, is_valid) = classify(base);
+                    gc += is_gc as u32;
+                    valid += is_valid as u32;
+                }
+                gc_fraction(gc, valid)
+            })
+            .collect()
+    }
+
+    /// GC track of one read from prefix sums
+    fn track_prefix(&self, seq: &[u8]) -> Vec<f32> {
+        let (gc, valid) = prefix_counts(seq);
+        (0..self.num_windows(seq.len()))
+            .map(|i| {
+                let start = i * self.step;
+                let end = start + self.window;
+                gc_fraction(gc[end] - gc[start], valid[end] - valid[start])
+            })
+            .collect()
+    }
+
+    fn tracks(&self, data: &[SequenceRecord], simd: bool) -> GcTracks {
+        let track = |record: &SequenceRecord| {
+            if simd {
+                self.track_prefix(&record.sequence)
+            } else {
+                self.track_naive(&record.sequence)
+            }
+        };
+        GcTracks {
+            window: self.window,
+            step: self.step,
+            tracks: data.iter().map(track).collect(),
+        }
+    }
+}
+
+impl Default for GcWindowProfile {
+    /// 50 bp windows every 10 bp
+    fn default() -> Self {
+        Self::new(50, 10)
+    }
+}
+
+/// (is G/C, is ACGT), case-insensitive
+fn classify(base: u8) -> (bool, bool) {
+    match base {
+        b'G' | b'C' | b'g' | b'c' => (true, true),
+        b'A' | b'T' | b'a' | b't' => (false, true),
+        _ => (false, false),
+    }
+}
+
+fn gc_fraction(gc: u32, valid: u32) -> f32 {
+    if valid == 0 {
+        0.0
+    } else {
+        gc as f32 / valid as f32
+    }
+}
+
+/// Prefix sums of G/C and ACGT indicators (`len + 1` entries, starting at 0)
+fn prefix_counts(seq: &[u8]) -> (Vec<u32>, Vec<u32>) {
+    #[cfg(target_arch = "aarch64")]
+    {
+        prefix_counts_neon(seq)
+    }
+
+    #[cfg(not(target_arch = "aarch64"))]
+    {
+        prefix_counts_scalar(seq)
+    }
+}
+
+fn prefix_counts_scalar(seq: &[u8]) -> (Vec<u32>, Vec<u32>) {
+    let mut gc = Vec::with_capacity(seq.len() + 1);
+    let mut valid = Vec::with_capacity(seq.len() + 1);
+    let (mut gc_total, mut valid_total) = (0u32, 0u32);
+    gc.push(0);
+    valid.push(0);
+
+    for &base in seq {
+        let (is_gc, is_valid) = classify(base);
+        gc_total += is_gc as u32;
+        valid_total += is_valid as u32;
+        gc.push(gc_total);
+        valid.push(valid_total);
+    }
+
+    (gc, valid)
+}
+
+#[cfg(target_arch = "aarch64")]
+fn prefix_counts_neon(seq: &[u8]) -> (Vec<u32>, Vec<u32>) {
+    use std::arch::aarch64::*;
+
+    let mut gc = vec![0u32; seq.len() + 1];
+    let mut valid = vec![0u32; seq.len() + 1];
+    let vector_len = seq.len() / 16 * 16;
+
+    /// Inclusive prefix sum of 16 u8 lanes
+    #[inline(always)]
+    unsafe fn scan(x: uint8x16_t) -> uint8x16_t {
+        let zero = vdupq_n_u8(0);
+        let x = vaddq_u8(x, vextq_u8::<15>(zero, x));
+        let x = vaddq_u8(x, vextq_u8::<14>(zero, x));
+        let x = vaddq_u8(x, vextq_u8::<12>(zero, x));
+        vaddq_u8(x, vextq_u8::<8>(zero, x))
+    }
+
+    /// Store `offset + prefix` (16 lanes widened to u32) at `out`
+    #[inline(always)]
+    unsafe fn store(out: *mut u32, prefix: uint8x16_t, offset: uint32x4_t) {
+        let low = vmovl_u8(vget_low_u8(prefix));
+        let high = vmovl_u8(vget_high_u8(prefix));
+        vst1q_u32(out, vaddw_u16(offset, vget_low_u16(low)));
+        vst1q_u32(out.add(4), vaddw_u16(offset, vget_high_u16(low)));
+        vst1q_u32(out.add(8), vaddw_u16(offset, vget_low_u16(high)));
+        vst1q_u32(out.add(12), vaddw_u16(offset, vget_high_u16(high)));
+    }
+
+    unsafe {
+        let case_mask = vdupq_n_u8(0xDF);
+        let one = vdupq_n_u8(1);
+        let (a, c, g, t) = (vdupq_n_u8(b'A'), vdupq_n_u8(b'C'), vdupq_n_u8(b'G'), vdupq_n_u8(b'T'));
+        let mut gc_offset = vdupq_n_u32(0);
+        let mut valid_offset = vdupq_n_u32(0);
+
+        for start in (0..vector_len).step_by(16) {
+            let upper = vandq_u8(vld1q_u8(seq.as_ptr().add(start)), case_mask);
+            let is_gc = vorrq_u8(vceqq_u8(upper, g), vceqq_u8(upper, c));
+            let is_at = vorrq_u8(vceqq_u8(upper, a), vceqq_u8(upper, t));
+
+            let gc_prefix = scan(vandq_u8(is_gc, one));
+            let valid_prefix = scan(vandq_u8(vorrq_u8(is_gc, is_at), one));
+
+            // prefix[start + 1 ..= start + 16]
+            store(gc.as_mut_ptr().add(start + 1), gc_prefix, gc_offset);
+            store(valid.as_mut_ptr().add(start + 1), valid_prefix, valid_offset);
+
+            gc_offset = vaddq_u32(gc_offset, vdupq_n_u32(vgetq_lane_u8::<15>(gc_prefix) as u32));
+            valid_offset = vaddq_u32(valid_offset, vdupq_n_u32(vgetq_lane_u8::<15>(valid_prefix) as u32));
+        }
+    }
+
+    for i in vector_len..seq.len() {
+        let (is_gc, is_valid) = classify(seq[i]);
+        gc[i + 1] = gc[i] + is_gc as u32;
+        valid[i + 1] = valid[i] + is_valid as u32;
+    }
+
+    (gc, valid)
+}
+
+/// Per-read GC tracks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GcTracks {
+    pub window: usize,
+    pub step: usize,
+    /// One GC fraction (0-1) per window, per read (input order)
+    pub tracks: Vec<Vec<f32>>,
+}
+
+impl GcTracks {
+    pub fn total_windows(&self) -> usize {
+        self.tracks.iter().map(Vec::len).sum()
+    }
+
+    /// Mean GC fraction over all windows
+    pub fn mean_gc(&self) -> f64 {
+        let windows = self.total_windows();
+        if windows == 0 {
+            return 0.0;
+        }
+        let sum: f64 = self.tracks.iter().flatten().map(|&gc| gc as f64).sum();
+        sum / windows as f64
+    }
+
+    /// Bytes of track data (the output volume)
+    pub fn output_bytes(&self) -> usize {
+        self.total_windows() * std::mem::size_of::<f32>()
+    }
+}
+
+impl PrimitiveOperation for GcWindowProfile {
+    fn name(&self) -> &str {
+        "gc_window_profile"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::ElementWise
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({ "window": self.window, "step": self.step })
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.tracks(data, false)))
+    }
+
+    fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.tracks(data, true)))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let pool = crate::thread_pool::get(num_threads)?;
+
+        let tracks = pool.install(|| {
+            data.par_iter()
+                .map(|record| self.track_prefix(&record.sequence))
+                .collect()
+        });
+
+        Ok(OperationOutput::typed(GcTracks {
+            window: self.window,
+            step: self.step,
+            tracks,
+        }))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        // Chunks build their tracks directly (no typed-output round trip)
+        let result = crate::chunked::map_reduce(
+            data,
+            num_threads,
+            batch_size,
+            |chunk| Ok(self.tracks(chunk, true)),
+            |mut a, b| {
+                a.tracks.extend(b.tracks);
+                a
+            },
+        )?
+        .unwrap_or_else(|| self.tracks(&[], true));
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        if config.num_threads > 1 {
+            self.execute_threaded(data, config)
+        } else if config.use_neon {
+            self.execute_neon(data)
+        } else {
+            self.execute_naive(data)
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracks(output: OperationOutput) -> GcTracks {
+        output.statistics::<GcTracks>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_gc_window_track() {
+        let op = GcWindowProfile::new(4, 2);
+        let data = vec![
+            SequenceRecord::fasta("a".to_string(), b"GGCCAATTgcNN".to_vec()),
+            SequenceRecord::fasta("short".to_string(), b"GCG".to_vec()),
+        ];
+        let result = tracks(op.execute_naive(&data).unwrap());
+
+        // Windows GGCC, CCAA, AATT, TTgc, gcNN
+        assert_eq!(result.tracks[0], vec![1.0, 0.5, 0.0, 0.5, 1.0]);
+        assert!(result.tracks[1].is_empty());
+        assert_eq!(result.total_windows(), 5);
+        assert_eq!(result.output_bytes(), 20);
+        assert!((result.mean_gc() - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_prefix_counts() {
+        let seq: Vec<u8> = (0..300usize).map(|i| b"ACGTNgcatRCCG"[(i * 7 + i / 3) % 13]).collect();
+        let (gc, valid) = prefix_counts(&seq);
+        assert_eq!((gc.clone(), valid.clone()), prefix_counts_scalar(&seq));
+        assert_eq!(gc.len(), seq.len() + 1);
+        assert_eq!(gc[seq.len()] as usize, seq.iter().filter(|&&b| classify(b).0).count());
+    }
+
+    #[test]
+    fn test_gc_window_backends_match() {
+        let data: Vec<SequenceRecord> = (0..73usize)
+            .map(|i| {
+                let sequence = (0..i * 5).map(|j| b"ACGTNacgGGCCT"[(i + j * 3 + j / 7) % 13]).collect();
+                SequenceRecord::fasta(format!("read_{}", i), sequence)
+            })
+            .collect();
+
+        for op in [GcWindowProfile::default(), GcWindowProfile::new(20, 1), GcWindowProfile::new(7, 30)] {
+            let expected = tracks(op.execute_naive(&data).unwrap());
+            assert_eq!(expected.tracks.len(), data.len());
+
+            for output in [
+                op.execute_neon(&data).unwrap(),
+                op.execute_parallel(&data, 3).unwrap(),
+                op.execute_parallel_chunked(&data, 2).unwrap(),
+            ] {
+                assert_eq!(tracks(output), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,247 @@
+//! Gram matrices (X·Xᵀ) on every backend
+//!
+//! Pairwise comparisons that reduce to dot products between feature rows
+//! (k-mer composition distance, MinHash sketch overlap) share one kernel:
+//! the Gram matrix of a row-major feature matrix. Implementing it once per
+//! backend gives the Pairwise category a complete hardware matrix:
+//! - **Naive**: scalar dot products
+//! - **NEON**: 4-lane FMA dot products
+//! - **Parallel**: NEON rows across threads
+//! - **Accelerate**: `cblas_sgemm` (dispatched to the AMX units)
+//! - **GPU**: custom `gram_matrix` compute shader
+//! - **MPS**: `MPSMatrixMultiplication`
+//!
+//! The GPU backends need the `gpu` feature on macOS; Accelerate needs macOS
+//! on Apple Silicon. Unavailable backends return an error.
+
+use anyhow::Result;
+use rayon::prelude::*;
+
+// FFI binding to Accelerate's BLAS (AMX-backed on Apple Silicon)
+#[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+#[link(name = "Accelerate", kind = "framework")]
+extern "C" {
+    #[allow(clippy::too_many_arguments)]
+    fn cblas_sgemm(
+        order: i32,
+        trans_a: i32,
+        trans_b: i32,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        b: *const f32,
+        ldb: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+    );
+}
+
+#[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+const CBLAS_ROW_MAJOR: i32 = 101;
+#[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+const CBLAS_NO_TRANS: i32 = 111;
+#[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+const CBLAS_TRANS: i32 = 112;
+
+/// Hardware path for a Gram matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GramBackend {
+    Naive,
+    Neon,
+    /// NEON rows on this many threads
+    Parallel(usize),
+    /// Accelerate `cblas_sgemm` (AMX)
+    Accelerate,
+    /// Custom Metal compute shader
+    Gpu,
+    /// Metal Performance Shaders
+    Mps,
+}
+
+impl GramBackend {
+    pub fn name(&self) -> String {
+        match self {
+            GramBackend::Naive => "naive".to_string(),
+            GramBackend::Neon => "neon".to_string(),
+            GramBackend::Parallel(threads) => format!("neon_{}t", threads),
+            GramBackend::Accelerate => "accelerate".to_string(),
+            GramBackend::Gpu => "gpu".to_string(),
+            GramBackend::Mps => "mps".to_string(),
+        }
+    }
+}
+
+/// Row-major `rows × rows` Gram matrix of a row-major `rows × cols` matrix
+pub fn gram_matrix(features: &[f32], rows: usize, cols: usize, backend: GramBackend) -> Result<Vec<f32>> {
+    if features.len() != rows * cols {
+        anyhow::bail!("Feature matrix has {} values, expected {}×{}", features.len(), rows, cols);
+    }
+
+    match backend {
+        GramBackend::Naive => Ok(gram_rows(features, rows, cols, dot_naive)),
+        GramBackend::Neon => Ok(gram_rows(features, rows, cols, dot_neon)),
+        GramBackend::Parallel(threads) => gram_parallel(features, rows, cols, threads),
+        GramBackend::Accelerate => gram_accelerate(features, rows, cols),
+        GramBackend::Gpu | GramBackend::Mps => gram_metal(features, rows, cols, backend),
+    }
+}
+
+/// Upper triangle by dot products, mirrored
+fn gram_rows(features: &[f32], rows: usize, cols: usize, dot: fn(&[f32], &[f32]) -> f32) -> Vec<f32> {
+    let mut gram = vec![0.0f32; rows * rows];
+    for i in 0..rows {
+        let a = &features[i * cols..(i + 1) * cols];
+        for j in i..rows {
+            let value = dot(a, &features[j * cols..(j + 1) * cols]);
+            gram[i * rows + j] = value;
+            gram[j * rows + i] = value;
+        }
+    }
+    gram
+}
+
+fn gram_parallel(features: &[f32], rows: usize, cols: usize, threads: usize) -> Result<Vec<f32>> {
+    let pool = crate::thread_pool::get(threads)?;
+    let mut gram = vec![0.0f32; rows * rows];
+    if rows == 0 {
+        return Ok(gram);
+    }
+
+    // Upper triangle in parallel, then mirror
+    pool.install(|| {
+        gram.par_chunks_mut(rows).enumerate().for_each(|(i, row)| {
+            let a = &features[i * cols..(i + 1) * cols];
+            for (j, value) in row.iter_mut().enumerate().skip(i) {
+                *value = dot_neon(a, &features[j * cols..(j + 1) * cols]);
+            }
+        });
+    });
+    for i in 0..rows {
+        for j in 0..i {
+            gram[i * rows + j] = gram[j * rows + i];
+        }
+    }
+    Ok(gram)
+}
+
+pub(crate) fn dot_naive(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+#[cfg(target_arch = "aarch64")]
+pub(crate) fn dot_neon(a: &[f32], b: &[f32]) -> f32 {
+    use std::arch::aarch64::*;
+
+    let len = a.len().min(b.len());
+    let mut i = 0;
+    let mut sum = unsafe {
+        let mut acc = vdupq_n_f32(0.0);
+        while i + 4 <= len {
+            acc = vfmaq_f32(acc, vld1q_f32(a.as_ptr().add(i)), vld1q_f32(b.as_ptr().add(i)));
+            i += 4;
+        }
+        vaddvq_f32(acc)
+    };
+    while i < len {
+        sum += a[i] * b[i];
+        i += 1;
+    }
+    sum
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+pub(crate) fn dot_neon(a: &[f32], b: &[f32]) -> f32 {
+    dot_naive(a, b)
+}
+
+#[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+fn gram_accelerate(features: &[f32], rows: usize, cols: usize) -> Result<Vec<f32>> {
+    let mut gram = vec![0.0f32; rows * rows];
+    if rows == 0 || cols == 0 {
+        return Ok(gram);
+    }
+
+    // gram = features × featuresᵀ
+    unsafe {
+        cblas_sgemm(
+            CBLAS_ROW_MAJOR,
+            CBLAS_NO_TRANS,
+            CBLAS_TRANS,
+            rows as i32,
+            rows as i32,
+            cols as i32,
+            1.0,
+            features.as_ptr(),
+            cols as i32,
+            features.as_ptr(),
+            cols as i32,
+            0.0,
+            gram.as_mut_ptr(),
+            rows as i32,
+        );
+    }
+    Ok(gram)
+}
+
+#[cfg(not(all(target_os = "macos", target_arch = "aarch64")))]
+fn gram_accelerate(_features: &[f32], _rows: usize, _cols: usize) -> Result<Vec<f32>> {
+    anyhow::bail!("Accelerate requires macOS on Apple Silicon")
+}
+
+#[cfg(all(target_os = "macos", feature = "gpu"))]
+fn gram_metal(features: &[f32], rows: usize, cols: usize, backend: GramBackend) -> Result<Vec<f32>> {
+    let metal = asbb_gpu::MetalBackend::new()?;
+    let (gram, _metrics) = match backend {
+        GramBackend::Mps => metal.gram_matrix_mps(features, rows, cols)?,
+        _ => metal.gram_matrix_gpu(features, rows, cols)?,
+    };
+    Ok(gram)
+}
+
+#[cfg(not(all(target_os = "macos", feature = "gpu")))]
+fn gram_metal(_features: &[f32], _rows: usize, _cols: usize, backend: GramBackend) -> Result<Vec<f32>> {
+    anyhow::bail!("{} backend needs macOS and the gpu feature", backend.name())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features() -> Vec<f32> {
+        // 4 rows × 6 cols (exercises the NEON tail)
+        (0..24).map(|v| (v % 7) as f32 - 2.5).collect()
+    }
+
+    #[test]
+    fn test_naive_gram() {
+        let gram = gram_matrix(&[1.0, 2.0, 3.0, 4.0], 2, 2, GramBackend::Naive).unwrap();
+        assert_eq!(gram, [5.0, 11.0, 11.0, 25.0]);
+    }
+
+    #[test]
+    fn test_cpu_backends_agree() {
+        let features = features();
+        let expected = gram_matrix(&features, 4, 6, GramBackend::Naive).unwrap();
+        for backend in [GramBackend::Neon, GramBackend::Parallel(2), GramBackend::Accelerate] {
+            let Ok(gram) = gram_matrix(&features, 4, 6, backend) else {
+                continue; // Accelerate off macOS
+            };
+            for (a, b) in gram.iter().zip(&expected) {
+                assert!((a - b).abs() < 1e-4, "{}: {} vs {}", backend.name(), a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shape_mismatch() {
+        assert!(gram_matrix(&[1.0; 5], 2, 3, GramBackend::Naive).is_err());
+    }
+}
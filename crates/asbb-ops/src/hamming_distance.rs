@@ -18,6 +18,12 @@
 //! - **Vectorized comparison**: vceqq_u8 (compare equal, returns 0xFF/0x00)
 //! - **Population count**: Count mismatches using vcntq_u8 after inversion
 //! - **Memory pattern**: Sequential reads of both sequences (good cache behavior)
+//! - **Bit-parallel 2-bit**: With [`BitPlanes`] packing, a mismatch is one
+//!   bit per base, so XOR + popcount compares 64 bases per u64 word (128 per
+//!   NEON `vcntq_u8`) instead of 16 bytes per vector: an algorithmic gain
+//!   from the encoding, on top of the 4× memory saving
+//! - **GPU**: `hamming_all_pairs` Metal kernel over the same bit planes for
+//!   all-vs-all batches (`gpu` feature)
 //!
 //! # Hamming Distance Definition
 //!
@@ -27,7 +33,8 @@
 //! - Hamming distance: 1 (position 4: A vs T)
 
 use anyhow::Result;
-use asbb_core::{OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use asbb_core::encoding::BitSeq;
+use asbb_core::{Encoding, HardwareConfig, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -238,6 +245,314 @@ impl HammingDistance {
 
         Ok(distances)
     }
+
+    /// All-vs-all Hamming distances on 2-bit encoded sequences (scalar popcount)
+    ///
+    /// [`BitSeq`] encodes N as A, so N/A positions compare equal here; use
+    /// [`BitPlanes::from_ascii`] with [`execute_planes_naive`](Self::execute_planes_naive)
+    /// to keep them distinct.
+    pub fn execute_2bit_naive(&self, data: &[BitSeq]) -> Result<OperationOutput> {
+        let planes: Vec<BitPlanes> = data.iter().map(BitPlanes::from_bitseq).collect();
+        self.execute_planes_naive(&planes)
+    }
+
+    /// All-vs-all Hamming distances on 2-bit encoded sequences (NEON popcount)
+    pub fn execute_2bit_neon(&self, data: &[BitSeq]) -> Result<OperationOutput> {
+        let planes: Vec<BitPlanes> = data.iter().map(BitPlanes::from_bitseq).collect();
+        self.execute_planes_neon(&planes, 1)
+    }
+
+    /// All-vs-all Hamming distances on bit planes (scalar popcount)
+    pub fn execute_planes_naive(&self, planes: &[BitPlanes]) -> Result<OperationOutput> {
+        let distances = all_pairs_planes(planes, distance_planes_scalar)?;
+        Ok(OperationOutput::typed(HammingDistanceResult {
+            num_sequences: planes.len(),
+            distances,
+        }))
+    }
+
+    /// All-vs-all Hamming distances on bit planes (NEON popcount, rows on
+    /// `num_threads` threads)
+    pub fn execute_planes_neon(&self, planes: &[BitPlanes], num_threads: usize) -> Result<OperationOutput> {
+        let distances = if num_threads > 1 {
+            crate::thread_pool::get(num_threads)?.install(|| all_pairs_planes_parallel(planes))?
+        } else {
+            all_pairs_planes(planes, distance_planes_neon)?
+        };
+        Ok(OperationOutput::typed(HammingDistanceResult {
+            num_sequences: planes.len(),
+            distances,
+        }))
+    }
+
+    /// All-vs-all Hamming distances with the `hamming_all_pairs` Metal kernel
+    #[cfg(all(target_os = "macos", feature = "gpu"))]
+    fn all_pairs_gpu(&self, sequences: &[SequenceRecord]) -> Result<Vec<Vec<usize>>> {
+        use asbb_gpu::MetalBackend;
+
+        let n = sequences.len();
+        let length = sequences.first().map_or(0, |record| record.sequence.len());
+        if let Some(record) = sequences.iter().find(|record| record.sequence.len() != length) {
+            anyhow::bail!(
+                "Sequences must have equal length for Hamming distance: {} vs {}",
+                length,
+                record.sequence.len()
+            );
+        }
+
+        let words = length.div_ceil(64);
+        let mut flat = Vec::with_capacity(n * 3 * words);
+        for record in sequences {
+            BitPlanes::from_ascii(&record.sequence).extend_planes(&mut flat);
+        }
+
+        let backend = MetalBackend::new()?;
+        let (distances, _metrics) = backend.hamming_all_pairs_gpu(&flat, n, words)?;
+        Ok(distances
+            .chunks(n.max(1))
+            .map(|row| row.iter().map(|&d| d as usize).collect())
+            .collect())
+    }
+
+    #[cfg(not(all(target_os = "macos", feature = "gpu")))]
+    fn all_pairs_gpu(&self, _sequences: &[SequenceRecord]) -> Result<Vec<Vec<usize>>> {
+        anyhow::bail!("GPU execution needs macOS and the gpu feature")
+    }
+}
+
+// ============================================================================
+// Bit-parallel 2-bit backend
+// ============================================================================
+
+/// Sequence packed as 2-bit bit planes, 64 bases per u64 word
+///
+/// Base `i` is bit `i % 64` of word `i / 64` in each plane: `lo` and `hi`
+/// hold the two bits of its code (A=00, C=01, G=10, T=11) and `ambiguous`
+/// marks non-ACGT bases. Splitting the code across planes, rather than
+/// interleaving 4 bases per byte as [`BitSeq`] does, makes a mismatch a
+/// single bit: `(lo₁ ^ lo₂) | (hi₁ ^ hi₂) | (ambiguous₁ ^ ambiguous₂)`.
+///
+/// Bases are compared case-insensitively and all non-ACGT bases compare
+/// equal to each other (as N).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitPlanes {
+    length: usize,
+    lo: Vec<u64>,
+    hi: Vec<u64>,
+    /// Empty when the sequence is pure ACGT
+    ambiguous: Vec<u64>,
+}
+
+impl BitPlanes {
+    pub fn from_ascii(seq: &[u8]) -> Self {
+        let words = seq.len().div_ceil(64);
+        let mut planes = Self {
+            length: seq.len(),
+            lo: vec![0; words],
+            hi: vec![0; words],
+            ambiguous: Vec::new(),
+        };
+
+        for (i, &base) in seq.iter().enumerate() {
+            let (word, bit) = (i / 64, 1u64 << (i % 64));
+            match base {
+                b'A' | b'a' => {}
+                b'C' | b'c' => planes.lo[word] |= bit,
+                b'G' | b'g' => planes.hi[word] |= bit,
+                b'T' | b't' => {
+                    planes.lo[word] |= bit;
+                    planes.hi[word] |= bit;
+                }
+                _ => {
+                    if planes.ambiguous.is_empty() {
+                        planes.ambiguous = vec![0; words];
+                    }
+                    planes.ambiguous[word] |= bit;
+                }
+            }
+        }
+        planes
+    }
+
+    /// Repack a [`BitSeq`] (no ambiguity plane: BitSeq stores N as A)
+    pub fn from_bitseq(seq: &BitSeq) -> Self {
+        let words = seq.len().div_ceil(64);
+        let mut planes = Self {
+            length: seq.len(),
+            lo: vec![0; words],
+            hi: vec![0; words],
+            ambiguous: Vec::new(),
+        };
+
+        let data = seq.data();
+        for i in 0..seq.len() {
+            let code = (data[i / 4] >> (6 - (i % 4) * 2)) & 0b11;
+            let (word, shift) = (i / 64, i % 64);
+            planes.lo[word] |= ((code & 1) as u64) << shift;
+            planes.hi[word] |= ((code >> 1) as u64) << shift;
+        }
+        planes
+    }
+
+    /// Length in bases
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Words per plane
+    pub fn words(&self) -> usize {
+        self.lo.len()
+    }
+
+    /// Append the `lo`, `hi` and `ambiguous` planes (GPU layout)
+    pub fn extend_planes(&self, out: &mut Vec<u64>) {
+        out.extend_from_slice(&self.lo);
+        out.extend_from_slice(&self.hi);
+        if self.ambiguous.is_empty() {
+            out.extend(std::iter::repeat_n(0, self.words()));
+        } else {
+            out.extend_from_slice(&self.ambiguous);
+        }
+    }
+}
+
+fn check_lengths(a: &BitPlanes, b: &BitPlanes) -> Result<()> {
+    if a.len() != b.len() {
+        anyhow::bail!(
+            "Sequences must have equal length for Hamming distance: {} vs {}",
+            a.len(),
+            b.len()
+        );
+    }
+    Ok(())
+}
+
+/// Ambiguity plane, or `zeros` for a pure-ACGT sequence
+fn ambiguity<'a>(planes: &'a BitPlanes, zeros: &'a [u64]) -> &'a [u64] {
+    if planes.ambiguous.is_empty() {
+        zeros
+    } else {
+        &planes.ambiguous
+    }
+}
+
+/// Hamming distance on bit planes (scalar: one u64 popcount per 64 bases)
+fn distance_planes_scalar(a: &BitPlanes, b: &BitPlanes) -> Result<usize> {
+    check_lengths(a, b)?;
+
+    let mut mismatches: usize = a
+        .lo
+        .iter()
+        .zip(&b.lo)
+        .zip(a.hi.iter().zip(&b.hi))
+        .map(|((lo1, lo2), (hi1, hi2))| ((lo1 ^ lo2) | (hi1 ^ hi2)).count_ones() as usize)
+        .sum();
+
+    if !a.ambiguous.is_empty() || !b.ambiguous.is_empty() {
+        // Recount with the ambiguity plane
+        let zeros = vec![0u64; a.words()];
+        let (amb1, amb2) = (ambiguity(a, &zeros), ambiguity(b, &zeros));
+        mismatches = (0..a.words())
+            .map(|w| ((a.lo[w] ^ b.lo[w]) | (a.hi[w] ^ b.hi[w]) | (amb1[w] ^ amb2[w])).count_ones() as usize)
+            .sum();
+    }
+    Ok(mismatches)
+}
+
+/// Hamming distance on bit planes (NEON: 128 bases per `vcntq_u8`)
+#[cfg(target_arch = "aarch64")]
+fn distance_planes_neon(a: &BitPlanes, b: &BitPlanes) -> Result<usize> {
+    use std::arch::aarch64::*;
+
+    check_lengths(a, b)?;
+
+    let words = a.words();
+    let zeros = vec![0u64; if a.ambiguous.is_empty() && b.ambiguous.is_empty() { 0 } else { words }];
+    let has_ambiguous = !zeros.is_empty();
+    let (amb1, amb2) = (ambiguity(a, &zeros), ambiguity(b, &zeros));
+
+    let mut mismatches = 0usize;
+    let mut w = 0;
+
+    // Two words (128 bases) per iteration
+    while w + 2 <= words {
+        unsafe {
+            let lo = veorq_u64(vld1q_u64(a.lo.as_ptr().add(w)), vld1q_u64(b.lo.as_ptr().add(w)));
+            let hi = veorq_u64(vld1q_u64(a.hi.as_ptr().add(w)), vld1q_u64(b.hi.as_ptr().add(w)));
+            let mut diff = vorrq_u64(lo, hi);
+            if has_ambiguous {
+                diff = vorrq_u64(diff, veorq_u64(vld1q_u64(amb1.as_ptr().add(w)), vld1q_u64(amb2.as_ptr().add(w))));
+            }
+            // At most 128 set bits, so the byte-wise sum fits in u8
+            mismatches += vaddvq_u8(vcntq_u8(vreinterpretq_u8_u64(diff))) as usize;
+        }
+        w += 2;
+    }
+
+    // Remaining word
+    for w in w..words {
+        let mut diff = (a.lo[w] ^ b.lo[w]) | (a.hi[w] ^ b.hi[w]);
+        if has_ambiguous {
+            diff |= amb1[w] ^ amb2[w];
+        }
+        mismatches += diff.count_ones() as usize;
+    }
+
+    Ok(mismatches)
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+fn distance_planes_neon(a: &BitPlanes, b: &BitPlanes) -> Result<usize> {
+    // Fall back to scalar popcount on non-ARM
+    distance_planes_scalar(a, b)
+}
+
+/// All-vs-all distances on bit planes (N×N matrix)
+fn all_pairs_planes(
+    planes: &[BitPlanes],
+    distance: fn(&BitPlanes, &BitPlanes) -> Result<usize>,
+) -> Result<Vec<Vec<usize>>> {
+    let n = planes.len();
+    let mut distances = vec![vec![0usize; n]; n];
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let dist = distance(&planes[i], &planes[j])?;
+            distances[i][j] = dist;
+            distances[j][i] = dist; // Symmetric
+        }
+    }
+
+    Ok(distances)
+}
+
+/// All-vs-all distances on bit planes, rows in parallel (NEON)
+fn all_pairs_planes_parallel(planes: &[BitPlanes]) -> Result<Vec<Vec<usize>>> {
+    let n = planes.len();
+    let rows: Vec<Vec<usize>> = (0..n)
+        .into_par_iter()
+        .map(|i| {
+            let mut row = vec![0usize; n];
+            for j in (i + 1)..n {
+                row[j] = distance_planes_neon(&planes[i], &planes[j])?;
+            }
+            Ok(row)
+        })
+        .collect::<Result<_>>()?;
+
+    // Mirror the upper triangle
+    let mut distances = rows;
+    for i in 1..n {
+        let (above, below) = distances.split_at_mut(i);
+        for (j, row) in above.iter().enumerate() {
+            below[0][j] = row[i];
+        }
+    }
+    Ok(distances)
 }
 
 impl Default for HammingDistance {
@@ -264,7 +579,7 @@ impl PrimitiveOperation for HammingDistance {
             distances,
         };
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
@@ -275,7 +590,7 @@ impl PrimitiveOperation for HammingDistance {
             distances,
         };
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_parallel(
@@ -283,9 +598,7 @@ impl PrimitiveOperation for HammingDistance {
         data: &[SequenceRecord],
         num_threads: usize,
     ) -> Result<OperationOutput> {
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()?;
+        let pool = crate::thread_pool::get(num_threads)?;
 
         pool.install(|| {
             let n = data.len();
@@ -318,7 +631,7 @@ impl PrimitiveOperation for HammingDistance {
                 distances,
             };
 
-            Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+            Ok(OperationOutput::typed(result))
         })
     }
 
@@ -331,7 +644,53 @@ impl PrimitiveOperation for HammingDistance {
             distances,
         };
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
+    }
+
+    /// All-vs-all on the GPU (one thread per pair, bit-plane packed)
+    fn execute_gpu(&self, data: &[SequenceRecord], _batch_size: usize) -> Result<OperationOutput> {
+        let distances = self.all_pairs_gpu(data)?;
+
+        let result = HammingDistanceResult {
+            num_sequences: data.len(),
+            distances,
+        };
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    /// 2-bit encodings pack into [`BitPlanes`] and use the bit-parallel path
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        if config.use_gpu {
+            return self.execute_gpu(data, config.gpu_batch_size.unwrap_or(data.len()));
+        }
+
+        if matches!(config.encoding, Encoding::TwoBit | Encoding::TwoBitExtended) {
+            let planes: Vec<BitPlanes> = data.iter().map(|r| BitPlanes::from_ascii(&r.sequence)).collect();
+            return if config.use_neon || config.num_threads > 1 {
+                self.execute_planes_neon(&planes, config.num_threads)
+            } else {
+                self.execute_planes_naive(&planes)
+            };
+        }
+
+        if config.use_amx {
+            return self.execute_amx(data);
+        }
+
+        if config.num_threads > 1 {
+            return self.execute_threaded(data, config);
+        }
+
+        if config.use_neon {
+            return self.execute_neon(data);
+        }
+
+        self.execute_naive(data)
     }
 }
 
@@ -340,7 +699,7 @@ impl PrimitiveOperation for HammingDistance {
 // ============================================================================
 
 /// Result of Hamming distance computation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HammingDistanceResult {
     /// Number of sequences compared
     pub num_sequences: usize,
@@ -531,8 +890,7 @@ mod tests {
         let data = sequences;
         let result = op.execute_naive(&data).unwrap();
 
-        if let OperationOutput::Statistics(json) = result {
-            let result: HammingDistanceResult = serde_json::from_value(json).unwrap();
+        if let Some(result) = result.statistics::<HammingDistanceResult>() {
 
             assert_eq!(result.num_sequences, 3);
             assert_eq!(result.get_distance(0, 1), Some(0)); // seq1 vs seq2: identical
@@ -565,8 +923,7 @@ mod tests {
 
         let result = op.execute_naive(&sequences).unwrap();
 
-        if let OperationOutput::Statistics(json) = result {
-            let result: HammingDistanceResult = serde_json::from_value(json).unwrap();
+        if let Some(result) = result.statistics::<HammingDistanceResult>() {
 
             assert_eq!(result.min_distance(), Some(1)); // Closest pair
             assert_eq!(result.max_distance(), Some(4)); // Most distant pair
@@ -579,6 +936,68 @@ mod tests {
         }
     }
 
+    fn distances(output: OperationOutput) -> Vec<Vec<usize>> {
+        output.statistics::<HammingDistanceResult>().unwrap().distances.clone()
+    }
+
+    #[test]
+    fn test_bitplanes_match_ascii() {
+        let op = HammingDistance::new();
+        // 70 bases (two words per plane), with N and lowercase
+        let base = b"ACGTACGTTGCAACGTNACGTACGTTGCAACGTACGTACGTTGCAACGTACGTACGTTGCAACGTACGTA".to_vec();
+        let mut variant = base.clone();
+        variant[0] = b'T';
+        variant[16] = b'A'; // N vs A
+        variant[65] = b'N';
+        let mut lower = base.to_ascii_lowercase();
+        lower[69] = b'c';
+        let sequences = [
+            create_test_record("base", &base),
+            create_test_record("variant", &variant),
+            create_test_record("lower", &lower),
+        ];
+
+        let planes: Vec<BitPlanes> = sequences.iter().map(|r| BitPlanes::from_ascii(&r.sequence)).collect();
+        assert_eq!(planes[0].words(), 2);
+
+        let expected = vec![vec![0, 3, 1], vec![3, 0, 4], vec![1, 4, 0]];
+        assert_eq!(distances(op.execute_planes_naive(&planes).unwrap()), expected);
+        assert_eq!(distances(op.execute_planes_neon(&planes, 1).unwrap()), expected);
+        assert_eq!(distances(op.execute_planes_neon(&planes, 2).unwrap()), expected);
+    }
+
+    #[test]
+    fn test_2bit_matches_naive() {
+        let op = HammingDistance::new();
+        let sequences: Vec<SequenceRecord> = (0..12)
+            .map(|i| {
+                let seq: Vec<u8> = (0..150).map(|j| b"ACGT"[(j * (i + 1) + j / 7) % 4]).collect();
+                create_test_record(&format!("seq{}", i), &seq)
+            })
+            .collect();
+        let encoded: Vec<BitSeq> = sequences.iter().map(|r| BitSeq::from_ascii(&r.sequence)).collect();
+        let expected = distances(op.execute_naive(&sequences).unwrap());
+
+        assert_eq!(distances(op.execute_2bit_naive(&encoded).unwrap()), expected);
+        assert_eq!(distances(op.execute_2bit_neon(&encoded).unwrap()), expected);
+
+        let mut config = HardwareConfig::naive();
+        config.encoding = Encoding::TwoBit;
+        assert_eq!(distances(op.execute_with_config(&sequences, &config).unwrap()), expected);
+
+        if let Ok(output) = op.execute_gpu(&sequences, sequences.len()) {
+            assert_eq!(distances(output), expected);
+        }
+    }
+
+    #[test]
+    fn test_bitplanes_length_mismatch() {
+        let op = HammingDistance::new();
+        let planes = vec![BitPlanes::from_ascii(b"ACGT"), BitPlanes::from_ascii(b"ACGTA")];
+        assert!(op.execute_planes_naive(&planes).is_err());
+        assert!(op.execute_planes_neon(&planes, 2).is_err());
+    }
+
     #[test]
     fn test_parallel_execution() {
         let op = HammingDistance::new();
@@ -595,10 +1014,9 @@ mod tests {
 
         // Both should produce same distances
         match (naive_result, parallel_result) {
-            (OperationOutput::Statistics(naive_json), OperationOutput::Statistics(parallel_json)) => {
-                let naive: HammingDistanceResult = serde_json::from_value(naive_json).unwrap();
-                let parallel: HammingDistanceResult =
-                    serde_json::from_value(parallel_json).unwrap();
+            (OperationOutput::Typed(naive_typed), OperationOutput::Typed(parallel_typed)) => {
+                let naive: &HammingDistanceResult = naive_typed.downcast_ref().unwrap();
+                let parallel: &HammingDistanceResult = parallel_typed.downcast_ref().unwrap();
 
                 assert_eq!(naive.num_sequences, parallel.num_sequences);
                 assert_eq!(naive.distances, parallel.distances);
@@ -0,0 +1,141 @@
+//! Intra-read parallel execution
+//!
+//! Both record-level strategies (`execute_parallel`, `execute_parallel_chunked`)
+//! hand each thread whole reads. For ONT/PacBio data a single 100 kb read
+//! is then one indivisible task: 100 reads on 10 threads leave the pool idle
+//! while the last few long reads finish, and one 2 Mb read serializes the
+//! run. The intra-read strategy instead cuts every read into segments of
+//! about `total_bases / (num_threads * SEGMENTS_PER_THREAD)` bases (never
+//! below [`MIN_SEGMENT_LEN`], so short reads stay whole) and schedules the
+//! segments across the pool.
+//!
+//! # Boundary-aware reduction
+//!
+//! A segment owns the positions `start..start + owned` of its read. Operations
+//! whose features span several bases (k-mers) ask for `overlap` bases of
+//! lookahead: each segment's slice extends up to `overlap` bases past its
+//! owned range, and the operation counts only features *starting* in the
+//! owned range. Every feature is then counted exactly once, whichever
+//! segment boundary it straddles. Position-independent counts (bases, GC)
+//! use no overlap.
+
+use anyhow::Result;
+use asbb_core::SequenceRecord;
+use rayon::prelude::*;
+
+/// Segments per thread (several, so work stealing can balance them)
+pub const SEGMENTS_PER_THREAD: usize = 4;
+
+/// Smallest segment worth scheduling (bases); shorter reads stay whole
+pub const MIN_SEGMENT_LEN: usize = 16 * 1024;
+
+/// A piece of one read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment<'a> {
+    /// Index of the read in the input
+    pub record: usize,
+
+    /// Offset of the segment within the read
+    pub start: usize,
+
+    /// Bases the segment owns (features starting here belong to it)
+    pub owned: usize,
+
+    /// Owned bases plus up to `overlap` bases of lookahead
+    pub bases: &'a [u8],
+}
+
+/// Segment length for `total_bases` split across `num_threads`
+pub fn segment_len(total_bases: usize, num_threads: usize) -> usize {
+    total_bases
+        .div_ceil(num_threads.max(1) * SEGMENTS_PER_THREAD)
+        .max(MIN_SEGMENT_LEN)
+}
+
+/// Cut every read into segments of at most `segment_len` owned bases
+///
+/// Empty reads yield no segments.
+pub fn segments(data: &[SequenceRecord], segment_len: usize, overlap: usize) -> Vec<Segment<'_>> {
+    let segment_len = segment_len.max(1);
+    let mut segments = Vec::new();
+    for (record, read) in data.iter().enumerate() {
+        let sequence = &read.sequence;
+        let mut start = 0;
+        while start < sequence.len() {
+            let owned = segment_len.min(sequence.len() - start);
+            let end = (start + owned + overlap).min(sequence.len());
+            segments.push(Segment { record, start, owned, bases: &sequence[start..end] });
+            start += owned;
+        }
+    }
+    segments
+}
+
+/// Map segments in parallel and reduce the results
+///
+/// Returns `None` when there are no segments (no input bases).
+pub fn map_reduce<'a, T, M, R>(
+    data: &'a [SequenceRecord],
+    num_threads: usize,
+    overlap: usize,
+    map: M,
+    reduce: R,
+) -> Result<Option<T>>
+where
+    T: Send,
+    M: Fn(&Segment<'a>) -> T + Sync + Send,
+    R: Fn(T, T) -> T + Sync + Send,
+{
+    let pool = crate::thread_pool::get(num_threads)?;
+    let total_bases: usize = data.iter().map(|record| record.sequence.len()).sum();
+    let segments = segments(data, segment_len(total_bases, num_threads), overlap);
+
+    Ok(pool.install(|| segments.par_iter().map(map).reduce_with(reduce)))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(sequence: &[u8]) -> SequenceRecord {
+        SequenceRecord::fasta("r".to_string(), sequence.to_vec())
+    }
+
+    #[test]
+    fn test_segments_cover_reads_with_lookahead() {
+        let data = vec![read(b"ACGTACGTAC"), read(b""), read(b"GGG")];
+        let segments = segments(&data, 4, 2);
+
+        let owned: Vec<(usize, usize, usize)> =
+            segments.iter().map(|s| (s.record, s.start, s.owned)).collect();
+        assert_eq!(owned, vec![(0, 0, 4), (0, 4, 4), (0, 8, 2), (2, 0, 3)]);
+        assert_eq!(segments[0].bases, b"ACGTAC");
+        assert_eq!(segments[2].bases, b"AC");
+    }
+
+    #[test]
+    fn test_boundary_spanning_features_counted_once() {
+        // Dinucleotides (k = 2) starting in each segment's owned range; the
+        // 16,384-base segments cut between an A and its C
+        let data = vec![read(&b"ACG".repeat(20_000))];
+        let count_ac = |segment: &Segment| {
+            segment.bases.windows(2).take(segment.owned).filter(|w| w == b"AC").count()
+        };
+
+        for threads in [1, 2, 3] {
+            let total = map_reduce(&data, threads, 1, count_ac, |a, b| a + b).unwrap();
+            assert_eq!(total, Some(20_000));
+        }
+        assert_eq!(map_reduce(&[], 2, 1, count_ac, |a, b| a + b).unwrap(), None);
+    }
+
+    #[test]
+    fn test_segment_len() {
+        assert_eq!(segment_len(1_000, 4), MIN_SEGMENT_LEN);
+        assert_eq!(segment_len(10_000_000, 10), 250_000);
+    }
+}
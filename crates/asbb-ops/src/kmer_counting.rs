@@ -15,6 +15,7 @@
 //! - Parallel implementation uses per-thread hash tables with merge
 
 use crate::{OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use asbb_core::{memory::MemoryEstimate, Encoding};
 use anyhow::Result;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -33,7 +34,7 @@ pub struct KmerCounting {
 }
 
 /// K-mer count output
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct KmerCounts {
     pub counts: HashMap<String, usize>,
     pub total_kmers: usize,
@@ -186,6 +187,19 @@ impl PrimitiveOperation for KmerCounting {
         OperationCategory::Search
     }
 
+    fn estimate_memory(
+        &self,
+        num_sequences: usize,
+        mean_length: f64,
+        encoding: Encoding,
+    ) -> MemoryEstimate {
+        // String key (24-byte header + k bytes), usize count, table slot
+        let (_, distinct) =
+            crate::kmer_spectrum::expected_kmers(num_sequences, mean_length, self.k);
+        MemoryEstimate::input(num_sequences, mean_length, encoding)
+            .with_output(distinct * (56 + self.k) as f64)
+    }
+
     fn execute_naive(&self, sequences: &[SequenceRecord]) -> Result<OperationOutput> {
         let mut all_counts = HashMap::new();
 
@@ -204,7 +218,7 @@ impl PrimitiveOperation for KmerCounting {
             unique_kmers,
         };
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(counts)?))
+        Ok(OperationOutput::typed(counts))
     }
 
     #[cfg(target_arch = "aarch64")]
@@ -226,14 +240,11 @@ impl PrimitiveOperation for KmerCounting {
             unique_kmers,
         };
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(counts)?))
+        Ok(OperationOutput::typed(counts))
     }
 
     fn execute_parallel(&self, sequences: &[SequenceRecord], num_threads: usize) -> Result<OperationOutput> {
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()
-            .unwrap();
+        let pool = crate::thread_pool::get(num_threads)?;
 
         let all_counts = Arc::new(Mutex::new(HashMap::new()));
 
@@ -265,7 +276,41 @@ impl PrimitiveOperation for KmerCounting {
             unique_kmers,
         };
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(counts)?))
+        Ok(OperationOutput::typed(counts))
+    }
+
+    fn execute_parallel_intra_read(&self, sequences: &[SequenceRecord], num_threads: usize) -> Result<OperationOutput> {
+        // Segments look k-1 bases ahead, so every k-mer is extracted by
+        // exactly one segment: the one owning its first base
+        let final_counts = crate::intra_read::map_reduce(
+            sequences,
+            num_threads,
+            self.k - 1,
+            |segment| {
+                #[cfg(target_arch = "aarch64")]
+                let kmers = self.extract_kmers_neon(segment.bases);
+                #[cfg(not(target_arch = "aarch64"))]
+                let kmers = self.extract_kmers_naive(segment.bases);
+
+                self.count_kmers(kmers)
+            },
+            |mut a, b| {
+                Self::merge_counts(&mut a, b);
+                a
+            },
+        )?
+        .unwrap_or_default();
+
+        let total_kmers: usize = final_counts.values().sum();
+        let unique_kmers = final_counts.len();
+
+        let counts = KmerCounts {
+            counts: final_counts,
+            total_kmers,
+            unique_kmers,
+        };
+
+        Ok(OperationOutput::typed(counts))
     }
 }
 
@@ -312,8 +357,7 @@ mod tests {
         let sequences = create_test_sequences();
 
         let output = op.execute_naive(&sequences).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let counts: KmerCounts = serde_json::from_value(json).unwrap();
+        if let Some(counts) = output.statistics::<KmerCounts>() {
             assert!(counts.total_kmers > 0);
             assert!(counts.unique_kmers > 0);
 
@@ -338,8 +382,7 @@ mod tests {
         ];
 
         let output = op.execute_naive(&sequences).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let counts: KmerCounts = serde_json::from_value(json).unwrap();
+        if let Some(counts) = output.statistics::<KmerCounts>() {
             // ACG canonical: ACG (vs CGT)
             // CGT canonical: ACG (CGT -> ACG as revcomp)
             // So canonical ACG should appear twice
@@ -402,10 +445,10 @@ mod tests {
         let naive_output = op.execute_naive(&sequences).unwrap();
         let neon_output = op.execute_neon(&sequences).unwrap();
 
-        if let (OperationOutput::Statistics(naive_json), OperationOutput::Statistics(neon_json)) =
+        if let (OperationOutput::Typed(naive_typed), OperationOutput::Typed(neon_typed)) =
             (naive_output, neon_output) {
-            let naive_counts: KmerCounts = serde_json::from_value(naive_json).unwrap();
-            let neon_counts: KmerCounts = serde_json::from_value(neon_json).unwrap();
+            let naive_counts: &KmerCounts = naive_typed.downcast_ref().unwrap();
+            let neon_counts: &KmerCounts = neon_typed.downcast_ref().unwrap();
             assert_eq!(naive_counts.total_kmers, neon_counts.total_kmers);
             assert_eq!(naive_counts.unique_kmers, neon_counts.unique_kmers);
             assert_eq!(naive_counts.counts, neon_counts.counts);
@@ -422,10 +465,10 @@ mod tests {
         let naive_output = op.execute_naive(&sequences).unwrap();
         let parallel_output = op.execute_parallel(&sequences, 2).unwrap();
 
-        if let (OperationOutput::Statistics(naive_json), OperationOutput::Statistics(parallel_json)) =
+        if let (OperationOutput::Typed(naive_typed), OperationOutput::Typed(parallel_typed)) =
             (naive_output, parallel_output) {
-            let naive_counts: KmerCounts = serde_json::from_value(naive_json).unwrap();
-            let parallel_counts: KmerCounts = serde_json::from_value(parallel_json).unwrap();
+            let naive_counts: &KmerCounts = naive_typed.downcast_ref().unwrap();
+            let parallel_counts: &KmerCounts = parallel_typed.downcast_ref().unwrap();
             assert_eq!(naive_counts.total_kmers, parallel_counts.total_kmers);
             assert_eq!(naive_counts.unique_kmers, parallel_counts.unique_kmers);
             // Note: HashMap equality works because we're comparing counts
@@ -434,4 +477,23 @@ mod tests {
             panic!("Expected Statistics output");
         }
     }
+
+    #[test]
+    fn test_intra_read_matches_naive_across_segment_boundaries() {
+        let op = KmerCounting::new(5, true);
+        let long_read: Vec<u8> = b"ACGGTCANTTGCA".iter().cycle().take(70_001).copied().collect();
+        let sequences = vec![
+            SequenceRecord::fasta("long".to_string(), long_read),
+            SequenceRecord::fasta("short".to_string(), b"ACGTACGT".to_vec()),
+        ];
+
+        let naive = op.execute_naive(&sequences).unwrap();
+        for threads in [1, 4] {
+            let intra = op.execute_parallel_intra_read(&sequences, threads).unwrap();
+            assert_eq!(
+                naive.statistics::<KmerCounts>().unwrap(),
+                intra.statistics::<KmerCounts>().unwrap()
+            );
+        }
+    }
 }
@@ -0,0 +1,197 @@
+//! K-mer composition distance operation
+//!
+//! Computes all pairwise distances between sequences' k-mer composition
+//! profiles (alignment-free comparison, as used for binning and clustering).
+//!
+//! **Operation Category**: Pairwise
+//! - Each sequence becomes a 4^k vector of k-mer counts, L2-normalised
+//! - Distance = 1 - cosine similarity, so the N×N matrix is 1 - X·Xᵀ
+//! - The X·Xᵀ step is a GEMM, which maps onto every matrix-capable unit
+//!   (see [`gram`](crate::gram)): NEON, AMX via Accelerate, a custom Metal
+//!   kernel and Metal Performance Shaders
+//!
+//! K-mers containing non-ACGT bases are skipped.
+
+use anyhow::Result;
+use asbb_core::{OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use serde::{Deserialize, Serialize};
+
+use crate::gram::{gram_matrix, GramBackend};
+
+/// K-mer composition distance operation
+pub struct KmerDistance {
+    k: usize,
+}
+
+/// Pairwise composition distances
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KmerDistanceMatrix {
+    pub num_sequences: usize,
+    pub k: usize,
+    /// 1 - cosine similarity of the k-mer profiles (0 = same composition)
+    pub distances: Vec<Vec<f32>>,
+}
+
+impl KmerDistance {
+    pub fn new(k: usize) -> Self {
+        assert!((1..=6).contains(&k), "K-mer size must be 1-6 (4^k profile dimensions)");
+        Self { k }
+    }
+
+    /// Profile dimensions (4^k)
+    pub fn dimensions(&self) -> usize {
+        1 << (2 * self.k)
+    }
+
+    /// Row-major `n × 4^k` matrix of L2-normalised k-mer profiles
+    pub fn profiles(&self, data: &[SequenceRecord]) -> Vec<f32> {
+        let dims = self.dimensions();
+        let mask = dims - 1;
+        let mut profiles = vec![0.0f32; data.len() * dims];
+
+        for (record, profile) in data.iter().zip(profiles.chunks_mut(dims.max(1))) {
+            let mut code = 0usize;
+            let mut valid = 0usize;
+            for &base in &record.sequence {
+                let bits = match base {
+                    b'A' | b'a' => 0,
+                    b'C' | b'c' => 1,
+                    b'G' | b'g' => 2,
+                    b'T' | b't' => 3,
+                    _ => {
+                        valid = 0;
+                        continue;
+                    }
+                };
+                code = ((code << 2) | bits) & mask;
+                valid += 1;
+                if valid >= self.k {
+                    profile[code] += 1.0;
+                }
+            }
+
+            let norm = profile.iter().map(|c| c * c).sum::<f32>().sqrt();
+            if norm > 0.0 {
+                profile.iter_mut().for_each(|c| *c /= norm);
+            }
+        }
+        profiles
+    }
+
+    /// Distance matrix on a specific backend
+    pub fn execute_backend(&self, data: &[SequenceRecord], backend: GramBackend) -> Result<KmerDistanceMatrix> {
+        let n = data.len();
+        let gram = gram_matrix(&self.profiles(data), n, self.dimensions(), backend)?;
+
+        let distances = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| if i == j { 0.0 } else { (1.0 - gram[i * n + j]).max(0.0) })
+                    .collect()
+            })
+            .collect();
+
+        Ok(KmerDistanceMatrix {
+            num_sequences: n,
+            k: self.k,
+            distances,
+        })
+    }
+}
+
+impl Default for KmerDistance {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl PrimitiveOperation for KmerDistance {
+    fn name(&self) -> &str {
+        "kmer_distance"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Pairwise
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.execute_backend(data, GramBackend::Naive)?))
+    }
+
+    fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.execute_backend(data, GramBackend::Neon)?))
+    }
+
+    fn execute_parallel(&self, data: &[SequenceRecord], num_threads: usize) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.execute_backend(data, GramBackend::Parallel(num_threads))?))
+    }
+
+    /// Custom Metal kernel (use [`GramBackend::Mps`] via `execute_backend`
+    /// for Metal Performance Shaders)
+    fn execute_gpu(&self, data: &[SequenceRecord], _batch_size: usize) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.execute_backend(data, GramBackend::Gpu)?))
+    }
+
+    fn execute_amx(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.execute_backend(data, GramBackend::Accelerate)?))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, seq: &[u8]) -> SequenceRecord {
+        SequenceRecord::fasta(id.to_string(), seq.to_vec())
+    }
+
+    #[test]
+    fn test_profiles_skip_invalid_kmers() {
+        let op = KmerDistance::new(2);
+        // AC, CG | N | GT -> 3 valid 2-mers
+        let profiles = op.profiles(&[record("r", b"ACGNGT")]);
+        let counts: Vec<f32> = profiles.iter().map(|v| v * 3f32.sqrt()).collect();
+        assert!((counts[0b0001] - 1.0).abs() < 1e-6); // AC
+        assert!((counts[0b0110] - 1.0).abs() < 1e-6); // CG
+        assert!((counts[0b1011] - 1.0).abs() < 1e-6); // GT
+        assert!((counts.iter().sum::<f32>() - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_distances() {
+        let op = KmerDistance::new(3);
+        let data = vec![
+            record("a", b"ACGTACGTACGTACGT"),
+            record("b", b"ACGTACGTACGTACGT"),
+            record("c", b"AAAAAAAAAAAAAAAA"),
+        ];
+        let result = op.execute_backend(&data, GramBackend::Naive).unwrap();
+
+        assert_eq!(result.num_sequences, 3);
+        assert!(result.distances[0][1].abs() < 1e-6);
+        assert!((result.distances[0][2] - 1.0).abs() < 1e-6); // no shared 3-mers
+        assert_eq!(result.distances[2][2], 0.0);
+    }
+
+    #[test]
+    fn test_backends_agree() {
+        let op = KmerDistance::default();
+        let data: Vec<SequenceRecord> = (0..20)
+            .map(|i| record(&format!("seq_{}", i), &b"ACGGTCATTGCA".repeat(i % 5 + 2)))
+            .collect();
+        let expected = op.execute_backend(&data, GramBackend::Naive).unwrap();
+
+        for backend in [GramBackend::Neon, GramBackend::Parallel(2)] {
+            let result = op.execute_backend(&data, backend).unwrap();
+            for (row, expected_row) in result.distances.iter().zip(&expected.distances) {
+                for (a, b) in row.iter().zip(expected_row) {
+                    assert!((a - b).abs() < 1e-5);
+                }
+            }
+        }
+    }
+}
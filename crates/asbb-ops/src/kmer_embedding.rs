@@ -0,0 +1,386 @@
+//! K-mer embedding similarity operation (Neural Engine)
+//!
+//! Embeds each read into a short fixed-length vector and compares the
+//! embeddings, as an approximate pre-filter for clustering and
+//! deduplication: only candidate pairs whose embedding similarity clears a
+//! threshold need an exact comparison.
+//!
+//! **Operation Category**: Pairwise
+//! - Profile: L2-normalised 4^k k-mer composition (as in
+//!   [`kmer_distance`](crate::kmer_distance))
+//! - Embedding: a fixed random ±1 projection to `dims` values, L2-normalised.
+//!   Random projections approximately preserve cosine similarity, so
+//!   embedding similarity tracks composition similarity in far fewer
+//!   dimensions
+//! - Similarity: embedding Gram matrix, thresholded into candidate pairs
+//!
+//! The projection is a dense layer, the workload the Neural Engine is built
+//! for. The same weights run as plain Rust (naive, NEON, threads) or as a
+//! Core ML model on the Neural Engine, the GPU (M5 neural accelerators) or
+//! the CPU. The model is built by `scripts/build_kmer_embedding_model.py`,
+//! which regenerates the weights of [`projection_weight`], so every backend
+//! computes the same function (Core ML in float16).
+
+use anyhow::Result;
+use asbb_core::{HardwareConfig, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::gram::{dot_naive, dot_neon, gram_matrix, GramBackend};
+use crate::kmer_distance::KmerDistance;
+
+/// Environment variable overriding the Core ML model path
+pub const MODEL_ENV: &str = "ASBB_EMBEDDING_MODEL";
+
+/// Model built by `scripts/build_kmer_embedding_model.py` (k=4, 64 dims)
+pub const DEFAULT_MODEL: &str = "models/kmer_embedding_k4_d64.mlpackage";
+
+/// Core ML feature names (model contract)
+pub const MODEL_INPUT: &str = "profiles";
+pub const MODEL_OUTPUT: &str = "embedding";
+
+/// Seed of the projection weights (shared with the model build script)
+pub const PROJECTION_SEED: u64 = 0x4153_4242_454D_4244;
+
+/// Projection weight for embedding value `row` and profile value `col`
+///
+/// ±1/√`out_dims`, sign from SplitMix64 of `seed ^ (row * in_dims + col)`.
+pub fn projection_weight(row: usize, col: usize, in_dims: usize, out_dims: usize) -> f32 {
+    let hash = splitmix64(PROJECTION_SEED ^ (row * in_dims + col) as u64);
+    let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+    sign / (out_dims as f32).sqrt()
+}
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Hardware path for the embedding projection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddingBackend {
+    Naive,
+    Neon,
+    /// NEON rows on this many threads
+    Parallel(usize),
+    /// Core ML restricted to the CPU
+    CoreMlCpu,
+    /// Core ML on the GPU (neural accelerators on M5)
+    CoreMlGpu,
+    /// Core ML on the Neural Engine
+    NeuralEngine,
+}
+
+impl EmbeddingBackend {
+    pub fn name(&self) -> String {
+        match self {
+            EmbeddingBackend::Naive => "naive".to_string(),
+            EmbeddingBackend::Neon => "neon".to_string(),
+            EmbeddingBackend::Parallel(threads) => format!("neon_{}t", threads),
+            EmbeddingBackend::CoreMlCpu => "coreml_cpu".to_string(),
+            EmbeddingBackend::CoreMlGpu => "coreml_gpu".to_string(),
+            EmbeddingBackend::NeuralEngine => "coreml_ane".to_string(),
+        }
+    }
+
+    /// Backend for the similarity (Gram) step
+    fn gram_backend(&self) -> GramBackend {
+        match self {
+            EmbeddingBackend::Naive => GramBackend::Naive,
+            EmbeddingBackend::Parallel(threads) => GramBackend::Parallel(*threads),
+            _ => GramBackend::Neon,
+        }
+    }
+}
+
+/// K-mer embedding similarity operation
+pub struct KmerEmbedding {
+    k: usize,
+    dims: usize,
+    threshold: f32,
+    model_path: PathBuf,
+}
+
+/// Embeddings and candidate pairs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddingSimilarity {
+    pub num_sequences: usize,
+    pub dims: usize,
+    pub threshold: f32,
+    /// Row-major `num_sequences × dims`, L2-normalised
+    pub embeddings: Vec<f32>,
+    /// Pairs (i < j) with embedding cosine similarity ≥ `threshold`
+    pub candidate_pairs: Vec<(usize, usize)>,
+}
+
+impl KmerEmbedding {
+    /// `k`-mer profiles (1-6) projected to `dims` values
+    pub fn new(k: usize, dims: usize) -> Self {
+        assert!((1..=6).contains(&k), "K-mer size must be 1-6 (4^k profile dimensions)");
+        assert!(dims > 0, "Embedding needs at least one dimension");
+        Self {
+            k,
+            dims,
+            threshold: 0.9,
+            model_path: std::env::var(MODEL_ENV)
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from(DEFAULT_MODEL)),
+        }
+    }
+
+    /// Minimum embedding similarity for a candidate pair (default 0.9)
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Core ML model for the Core ML backends
+    pub fn with_model_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.model_path = path.into();
+        self
+    }
+
+    /// Profile dimensions (4^k)
+    pub fn input_dimensions(&self) -> usize {
+        1 << (2 * self.k)
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.dims
+    }
+
+    /// Row-major `dims × 4^k` projection matrix
+    pub fn projection(&self) -> Vec<f32> {
+        let in_dims = self.input_dimensions();
+        (0..self.dims)
+            .flat_map(|row| (0..in_dims).map(move |col| projection_weight(row, col, in_dims, self.dims)))
+            .collect()
+    }
+
+    /// Row-major `n × dims` L2-normalised embeddings
+    pub fn embed(&self, data: &[SequenceRecord], backend: EmbeddingBackend) -> Result<Vec<f32>> {
+        let profiles = KmerDistance::new(self.k).profiles(data);
+        let mut embeddings = match backend {
+            EmbeddingBackend::Naive => self.project(&profiles, dot_naive),
+            EmbeddingBackend::Neon => self.project(&profiles, dot_neon),
+            EmbeddingBackend::Parallel(threads) => self.project_parallel(&profiles, threads)?,
+            EmbeddingBackend::CoreMlCpu | EmbeddingBackend::CoreMlGpu | EmbeddingBackend::NeuralEngine => {
+                self.project_coreml(&profiles, data.len(), backend)?
+            }
+        };
+
+        for embedding in embeddings.chunks_mut(self.dims) {
+            let norm = embedding.iter().map(|v| v * v).sum::<f32>().sqrt();
+            if norm > 0.0 {
+                embedding.iter_mut().for_each(|v| *v /= norm);
+            }
+        }
+        Ok(embeddings)
+    }
+
+    /// Embed, then threshold the embedding similarity matrix
+    pub fn execute_backend(&self, data: &[SequenceRecord], backend: EmbeddingBackend) -> Result<EmbeddingSimilarity> {
+        let n = data.len();
+        let embeddings = self.embed(data, backend)?;
+        let similarity = gram_matrix(&embeddings, n, self.dims, backend.gram_backend())?;
+
+        let candidate_pairs = (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .filter(|&(i, j)| similarity[i * n + j] >= self.threshold)
+            .collect();
+
+        Ok(EmbeddingSimilarity {
+            num_sequences: n,
+            dims: self.dims,
+            threshold: self.threshold,
+            embeddings,
+            candidate_pairs,
+        })
+    }
+
+    fn project(&self, profiles: &[f32], dot: fn(&[f32], &[f32]) -> f32) -> Vec<f32> {
+        let in_dims = self.input_dimensions();
+        let projection = self.projection();
+        profiles
+            .chunks(in_dims)
+            .flat_map(|profile| projection.chunks(in_dims).map(move |weights| dot(profile, weights)))
+            .collect()
+    }
+
+    fn project_parallel(&self, profiles: &[f32], threads: usize) -> Result<Vec<f32>> {
+        let pool = crate::thread_pool::get(threads)?;
+        let in_dims = self.input_dimensions();
+        let projection = self.projection();
+        let mut embeddings = vec![0.0f32; profiles.len() / in_dims * self.dims];
+
+        pool.install(|| {
+            embeddings
+                .par_chunks_mut(self.dims)
+                .zip(profiles.par_chunks(in_dims))
+                .for_each(|(embedding, profile)| {
+                    for (value, weights) in embedding.iter_mut().zip(projection.chunks(in_dims)) {
+                        *value = dot_neon(profile, weights);
+                    }
+                });
+        });
+        Ok(embeddings)
+    }
+
+    #[cfg(all(target_os = "macos", feature = "gpu"))]
+    fn project_coreml(&self, profiles: &[f32], rows: usize, backend: EmbeddingBackend) -> Result<Vec<f32>> {
+        use asbb_gpu::coreml::{ComputeUnits, CoreMlModel};
+
+        let units = match backend {
+            EmbeddingBackend::CoreMlCpu => ComputeUnits::CpuOnly,
+            EmbeddingBackend::CoreMlGpu => ComputeUnits::CpuAndGpu,
+            _ => ComputeUnits::CpuAndNeuralEngine,
+        };
+        let model = CoreMlModel::shared(&self.model_path, MODEL_INPUT, MODEL_OUTPUT, units)?;
+        let (_, cols) = model.input_shape();
+        if cols != self.input_dimensions() {
+            anyhow::bail!(
+                "{} takes {} profile values, expected 4^{} = {}",
+                self.model_path.display(),
+                cols,
+                self.k,
+                self.input_dimensions()
+            );
+        }
+
+        let embeddings = model.predict_rows(profiles, rows)?;
+        if embeddings.len() != rows * self.dims {
+            anyhow::bail!(
+                "{} returned {} values per row, expected {}",
+                self.model_path.display(),
+                embeddings.len() / rows.max(1),
+                self.dims
+            );
+        }
+        Ok(embeddings)
+    }
+
+    #[cfg(not(all(target_os = "macos", feature = "gpu")))]
+    fn project_coreml(&self, _profiles: &[f32], _rows: usize, backend: EmbeddingBackend) -> Result<Vec<f32>> {
+        anyhow::bail!("{} backend needs macOS and the gpu feature", backend.name())
+    }
+}
+
+impl Default for KmerEmbedding {
+    fn default() -> Self {
+        Self::new(4, 64)
+    }
+}
+
+impl PrimitiveOperation for KmerEmbedding {
+    fn name(&self) -> &str {
+        "kmer_embedding"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Pairwise
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.execute_backend(data, EmbeddingBackend::Naive)?))
+    }
+
+    fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.execute_backend(data, EmbeddingBackend::Neon)?))
+    }
+
+    fn execute_parallel(&self, data: &[SequenceRecord], num_threads: usize) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.execute_backend(data, EmbeddingBackend::Parallel(num_threads))?))
+    }
+
+    /// Core ML on the GPU
+    fn execute_gpu(&self, data: &[SequenceRecord], _batch_size: usize) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.execute_backend(data, EmbeddingBackend::CoreMlGpu)?))
+    }
+
+    /// Core ML on the Neural Engine
+    fn execute_neural(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.execute_backend(data, EmbeddingBackend::NeuralEngine)?))
+    }
+
+    /// `use_neural_engine` selects the Neural Engine and
+    /// `use_m5_gpu_neural_accel` (or `use_gpu`) the Core ML GPU path
+    fn execute_with_config(&self, data: &[SequenceRecord], config: &HardwareConfig) -> Result<OperationOutput> {
+        let backend = if config.use_neural_engine {
+            EmbeddingBackend::NeuralEngine
+        } else if config.use_m5_gpu_neural_accel || config.use_gpu {
+            EmbeddingBackend::CoreMlGpu
+        } else if config.num_threads > 1 {
+            EmbeddingBackend::Parallel(config.num_threads)
+        } else if config.use_neon {
+            EmbeddingBackend::Neon
+        } else {
+            EmbeddingBackend::Naive
+        };
+        Ok(OperationOutput::typed(self.execute_backend(data, backend)?))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, seq: &[u8]) -> SequenceRecord {
+        SequenceRecord::fasta(id.to_string(), seq.to_vec())
+    }
+
+    #[test]
+    fn test_projection_weights() {
+        let op = KmerEmbedding::new(2, 4);
+        let projection = op.projection();
+        assert_eq!(projection.len(), 4 * 16);
+        assert!(projection.iter().all(|w| (w.abs() - 0.5).abs() < 1e-6));
+        assert!(projection.iter().any(|&w| w > 0.0) && projection.iter().any(|&w| w < 0.0));
+        // Pinned for the model build script
+        assert_eq!(splitmix64(0), 0xE220_A839_7B1D_CDAF);
+    }
+
+    #[test]
+    fn test_candidate_pairs() {
+        let op = KmerEmbedding::default().with_threshold(0.95);
+        let data = vec![
+            record("a", &b"ACGTACGGTCAT".repeat(10)),
+            record("b", &b"ACGTACGGTCAT".repeat(10)),
+            record("c", &b"AAAAAAAAAAAC".repeat(10)),
+        ];
+        let result = op.execute_backend(&data, EmbeddingBackend::Naive).unwrap();
+
+        assert_eq!(result.num_sequences, 3);
+        assert_eq!(result.embeddings.len(), 3 * 64);
+        assert_eq!(result.candidate_pairs, vec![(0, 1)]);
+    }
+
+    #[test]
+    fn test_cpu_backends_agree() {
+        let op = KmerEmbedding::default();
+        let data: Vec<SequenceRecord> = (0..20)
+            .map(|i| record(&format!("seq_{}", i), &b"ACGGTCATTGCA".repeat(i % 5 + 2)))
+            .collect();
+        let expected = op.embed(&data, EmbeddingBackend::Naive).unwrap();
+
+        for backend in [EmbeddingBackend::Neon, EmbeddingBackend::Parallel(2)] {
+            let embeddings = op.embed(&data, backend).unwrap();
+            for (a, b) in embeddings.iter().zip(&expected) {
+                assert!((a - b).abs() < 1e-5, "{}: {} vs {}", backend.name(), a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_missing_model() {
+        let op = KmerEmbedding::default().with_model_path("missing.mlpackage");
+        let data = vec![record("a", b"ACGTACGT")];
+        assert!(op.embed(&data, EmbeddingBackend::NeuralEngine).is_err());
+    }
+}
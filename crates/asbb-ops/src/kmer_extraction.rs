@@ -15,6 +15,8 @@
 //! - NEON accelerates validation but not allocation
 
 use crate::{OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use asbb_core::memory::{MemoryEstimate, RECORD_OVERHEAD_BYTES};
+use asbb_core::Encoding;
 use anyhow::Result;
 use rayon::prelude::*;
 use std::collections::HashSet;
@@ -138,6 +140,18 @@ impl PrimitiveOperation for KmerExtraction {
         OperationCategory::Search
     }
 
+    fn estimate_memory(
+        &self,
+        num_sequences: usize,
+        mean_length: f64,
+        encoding: Encoding,
+    ) -> MemoryEstimate {
+        // One record per k-mer (deduplication only drops repeats within a read)
+        let (total, _) = crate::kmer_spectrum::expected_kmers(num_sequences, mean_length, self.k);
+        MemoryEstimate::input(num_sequences, mean_length, encoding)
+            .with_output(total * (RECORD_OVERHEAD_BYTES + self.k as f64))
+    }
+
     fn execute_naive(&self, sequences: &[SequenceRecord]) -> Result<OperationOutput> {
         let mut all_kmers = Vec::new();
 
@@ -162,10 +176,7 @@ impl PrimitiveOperation for KmerExtraction {
     }
 
     fn execute_parallel(&self, sequences: &[SequenceRecord], num_threads: usize) -> Result<OperationOutput> {
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()
-            .unwrap();
+        let pool = crate::thread_pool::get(num_threads)?;
 
         let results: Vec<Vec<SequenceRecord>> = pool.install(|| {
             sequences.par_iter().map(|record| {
@@ -0,0 +1,538 @@
+//! K-mer Index Build and Lookup Operations
+//!
+//! A hash index from every canonical k-mer of a reference to its positions
+//! (the seed index of a mapper, without an FM-index), split into two
+//! operations that are benchmarked separately:
+//! - [`KmerIndexBuild`]: builds the index over the input records (the
+//!   records are the reference). Streaming, sort-dominated, bandwidth-bound
+//! - [`KmerIndexLookup`]: queries every k-mer of the input reads against an
+//!   index built up front (not timed). One dependent hash probe per k-mer
+//!   into a table far larger than cache, so latency-bound
+//!
+//! # Operation Characteristics
+//! - **Category**: Aggregation (build), Search (lookup)
+//! - **Complexity**: 0.45 (build: encode + sort), 0.5 (lookup: random probes)
+//! - **Output**: Index size statistics (build); hit counts (lookup)
+//! - **NEON benefit**: None from vector lanes (hashing and probing are
+//!   scalar). The lookup's NEON path batches instead: it encodes all of a
+//!   read's k-mers before probing, so independent probes overlap in the
+//!   memory system rather than interleaving with encoding
+//!
+//! # Implementation Notes
+//! - k-mers: canonical 2-bit codes (k ≤ 31, either case); k-mers with other
+//!   bytes are skipped
+//! - Build: collect (k-mer, sequence, position) triples, sort them, then
+//!   store one (offset, count) table entry per distinct k-mer pointing into
+//!   a positions array grouped by k-mer. Triples are unique, so every
+//!   backend builds the same index
+//! - Build parallel: triples extracted and sorted across threads; the table
+//!   insert pass is sequential
+//! - Lookup: the default index is a synthetic 1 Mb reference, which reads
+//!   from the standard random datasets almost never hit (probes miss after
+//!   one cache miss); build the lookup with [`KmerIndexLookup::new`] over
+//!   the reads' source genome for a hit-dominated workload
+
+use crate::kmer_spectrum::expected_kmers;
+use crate::read_mapping::ReadMapping;
+use crate::{HardwareConfig, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use asbb_core::memory::{MemoryEstimate, RECORD_OVERHEAD_BYTES};
+use asbb_core::Encoding;
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Length of the default lookup reference
+pub const DEFAULT_REFERENCE_LENGTH: usize = 1 << 20;
+
+/// Bytes per table entry: key, (offset, count), control byte, and slack
+/// for the table's maximum load factor
+const BYTES_PER_TABLE_ENTRY: usize = 20;
+
+/// Bytes per indexed position
+const BYTES_PER_POSITION: usize = std::mem::size_of::<(u32, u32)>();
+
+/// Bytes per (k-mer, sequence, position) triple while building
+const BYTES_PER_TRIPLE: usize = std::mem::size_of::<(u64, u32, u32)>();
+
+/// Call `f(code, position)` for every valid canonical k-mer of `seq`
+fn for_each_kmer(seq: &[u8], k: usize, mut f: impl FnMut(u64, usize)) {
+    let mask = (1u64 << (2 * k)) - 1;
+    let shift = 2 * (k - 1);
+    let (mut forward, mut reverse, mut valid) = (0u64, 0u64, 0usize);
+
+    for (i, &base) in seq.iter().enumerate() {
+        let code = match base {
+            b'A' | b'a' => 0,
+            b'C' | b'c' => 1,
+            b'G' | b'g' => 2,
+            b'T' | b't' => 3,
+            _ => {
+                valid = 0;
+                continue;
+            }
+        };
+        forward = ((forward << 2) | code) & mask;
+        reverse = (reverse >> 2) | ((3 - code) << shift);
+        valid += 1;
+
+        if valid >= k {
+            f(forward.min(reverse), i + 1 - k);
+        }
+    }
+}
+
+// ============================================================================
+// Index
+// ============================================================================
+
+/// Canonical k-mer → positions index
+#[derive(Debug, Clone, PartialEq)]
+pub struct KmerIndex {
+    k: usize,
+    /// k-mer code → (offset, count) into `positions`
+    table: HashMap<u64, (u32, u32)>,
+    /// (sequence, position) of every occurrence, grouped by k-mer
+    positions: Vec<(u32, u32)>,
+}
+
+impl KmerIndex {
+    /// Index every k-mer of `data`, extracting and sorting on `num_threads`
+    pub fn build(data: &[SequenceRecord], k: usize, num_threads: usize) -> Result<Self> {
+        assert!((1..=31).contains(&k), "k must be 1-31");
+
+        let triples_of = |(index, record): (usize, &SequenceRecord)| {
+            let mut triples = Vec::with_capacity(record.sequence.len().saturating_sub(k - 1));
+            for_each_kmer(&record.sequence, k, |code, position| {
+                triples.push((code, index as u32, position as u32));
+            });
+            triples
+        };
+
+        let triples: Vec<(u64, u32, u32)> = if num_threads > 1 {
+            let pool = crate::thread_pool::get(num_threads)?;
+            pool.install(|| {
+                let mut triples: Vec<_> =
+                    data.par_iter().enumerate().flat_map_iter(triples_of).collect();
+                triples.par_sort_unstable();
+                triples
+            })
+        } else {
+            let mut triples: Vec<_> = data.iter().enumerate().flat_map(triples_of).collect();
+            triples.sort_unstable();
+            triples
+        };
+
+        let mut table = HashMap::new();
+        let mut positions = Vec::with_capacity(triples.len());
+        for run in triples.chunk_by(|a, b| a.0 == b.0) {
+            table.insert(run[0].0, (positions.len() as u32, run.len() as u32));
+            positions.extend(run.iter().map(|&(_, sequence, position)| (sequence, position)));
+        }
+
+        Ok(Self { k, table, positions })
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// (sequence, position) occurrences of a k-mer code
+    pub fn get(&self, code: u64) -> &[(u32, u32)] {
+        match self.table.get(&code) {
+            Some(&(offset, count)) => &self.positions[offset as usize..(offset + count) as usize],
+            None => &[],
+        }
+    }
+
+    /// Size statistics (`sequences` indexed records)
+    pub fn stats(&self, sequences: usize) -> KmerIndexStats {
+        KmerIndexStats {
+            sequences,
+            k: self.k,
+            indexed_kmers: self.positions.len(),
+            distinct_kmers: self.table.len(),
+            max_occurrences: self.table.values().map(|&(_, n)| n as usize).max().unwrap_or(0),
+            size_bytes: self.table.capacity() * BYTES_PER_TABLE_ENTRY
+                + self.positions.len() * BYTES_PER_POSITION,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KmerIndexStats {
+    pub sequences: usize,
+    pub k: usize,
+    /// k-mer occurrences (positions stored)
+    pub indexed_kmers: usize,
+    pub distinct_kmers: usize,
+    /// Occurrences of the most repeated k-mer
+    pub max_occurrences: usize,
+    /// Approximate resident size of table and positions
+    pub size_bytes: usize,
+}
+
+// ============================================================================
+// Build Operation
+// ============================================================================
+
+/// Builds a [`KmerIndex`] over the input records
+pub struct KmerIndexBuild {
+    pub k: usize,
+}
+
+impl KmerIndexBuild {
+    pub fn new(k: usize) -> Self {
+        Self { k }
+    }
+}
+
+impl PrimitiveOperation for KmerIndexBuild {
+    fn name(&self) -> &str {
+        "kmer_index_build"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Aggregation
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({ "k": self.k })
+    }
+
+    fn estimate_memory(
+        &self,
+        num_sequences: usize,
+        mean_length: f64,
+        encoding: Encoding,
+    ) -> MemoryEstimate {
+        // Sorted triples while building; the index itself is the output
+        let (total, distinct) = expected_kmers(num_sequences, mean_length, self.k);
+        let index = distinct * BYTES_PER_TABLE_ENTRY as f64 + total * BYTES_PER_POSITION as f64;
+        MemoryEstimate::input(num_sequences, mean_length, encoding)
+            .with_working(total * BYTES_PER_TRIPLE as f64)
+            .with_output(index)
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        let index = KmerIndex::build(data, self.k, 1)?;
+        Ok(OperationOutput::typed(index.stats(data.len())))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let index = KmerIndex::build(data, self.k, num_threads)?;
+        Ok(OperationOutput::typed(index.stats(data.len())))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        _batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        // The sort is global; per-chunk indexes would need a k-way merge
+        self.execute_parallel(data, num_threads)
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        self.execute_parallel(data, config.num_threads)
+    }
+}
+
+// ============================================================================
+// Lookup Operation
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KmerLookupResult {
+    pub total_sequences: usize,
+    /// Read k-mers probed
+    pub total_kmers: usize,
+    /// Probes that found the k-mer in the index
+    pub hit_kmers: usize,
+    /// Reference positions returned over all hits
+    pub occurrences: usize,
+    /// Reads with at least one hit
+    pub sequences_with_hits: usize,
+}
+
+impl KmerLookupResult {
+    pub fn new() -> Self {
+        Self {
+            total_sequences: 0,
+            total_kmers: 0,
+            hit_kmers: 0,
+            occurrences: 0,
+            sequences_with_hits: 0,
+        }
+    }
+
+    pub fn add(&mut self, other: &Self) {
+        self.total_sequences += other.total_sequences;
+        self.total_kmers += other.total_kmers;
+        self.hit_kmers += other.hit_kmers;
+        self.occurrences += other.occurrences;
+        self.sequences_with_hits += other.sequences_with_hits;
+    }
+
+    /// Count one probed read
+    fn record(&mut self, kmers: usize, hits: usize, occurrences: usize) {
+        self.total_sequences += 1;
+        self.total_kmers += kmers;
+        self.hit_kmers += hits;
+        self.occurrences += occurrences;
+        self.sequences_with_hits += usize::from(hits > 0);
+    }
+
+    /// Fraction of probes that hit
+    pub fn hit_rate(&self) -> f64 {
+        if self.total_kmers == 0 {
+            0.0
+        } else {
+            self.hit_kmers as f64 / self.total_kmers as f64
+        }
+    }
+}
+
+impl Default for KmerLookupResult {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Queries the input reads' k-mers against a prebuilt [`KmerIndex`]
+pub struct KmerIndexLookup {
+    index: Arc<KmerIndex>,
+}
+
+impl KmerIndexLookup {
+    /// Look up reads against `index`
+    pub fn new(index: Arc<KmerIndex>) -> Self {
+        Self { index }
+    }
+
+    pub fn index(&self) -> &KmerIndex {
+        &self.index
+    }
+
+    /// Probe one read, encoding and probing each k-mer in turn
+    fn lookup_read(&self, seq: &[u8], result: &mut KmerLookupResult) {
+        let (mut kmers, mut hits, mut occurrences) = (0, 0, 0);
+        for_each_kmer(seq, self.index.k, |code, _| {
+            let found = self.index.get(code).len();
+            kmers += 1;
+            hits += usize::from(found > 0);
+            occurrences += found;
+        });
+        result.record(kmers, hits, occurrences);
+    }
+
+    /// Probe one read, encoding all k-mers into `codes` before probing
+    fn lookup_read_batched(&self, seq: &[u8], codes: &mut Vec<u64>, result: &mut KmerLookupResult) {
+        codes.clear();
+        for_each_kmer(seq, self.index.k, |code, _| codes.push(code));
+
+        let (mut hits, mut occurrences) = (0, 0);
+        for &code in codes.iter() {
+            let found = self.index.get(code).len();
+            hits += usize::from(found > 0);
+            occurrences += found;
+        }
+        result.record(codes.len(), hits, occurrences);
+    }
+
+    fn lookup_batched(&self, data: &[SequenceRecord]) -> KmerLookupResult {
+        let mut result = KmerLookupResult::new();
+        let mut codes = Vec::new();
+        for record in data {
+            self.lookup_read_batched(&record.sequence, &mut codes, &mut result);
+        }
+        result
+    }
+}
+
+impl Default for KmerIndexLookup {
+    /// k = 21 over a synthetic 1 Mb reference (seed 42)
+    fn default() -> Self {
+        let reference = ReadMapping::synthetic_reference(DEFAULT_REFERENCE_LENGTH, 42);
+        let records = [SequenceRecord::fasta("reference".to_string(), reference)];
+        let index = KmerIndex::build(&records, 21, 1).expect("single-threaded build does not fail");
+        Self::new(Arc::new(index))
+    }
+}
+
+impl PrimitiveOperation for KmerIndexLookup {
+    fn name(&self) -> &str {
+        "kmer_index_lookup"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Search
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "k": self.index.k,
+            "index_distinct_kmers": self.index.table.len(),
+            "index_positions": self.index.positions.len(),
+        })
+    }
+
+    fn estimate_memory(
+        &self,
+        num_sequences: usize,
+        mean_length: f64,
+        encoding: Encoding,
+    ) -> MemoryEstimate {
+        // The index is resident before the run; per-read code buffers only
+        let index = self.index.stats(0).size_bytes as f64;
+        MemoryEstimate::input(num_sequences, mean_length, encoding)
+            .with_working(index + mean_length * 8.0 + RECORD_OVERHEAD_BYTES)
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        let mut result = KmerLookupResult::new();
+        for record in data {
+            self.lookup_read(&record.sequence, &mut result);
+        }
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.lookup_batched(data)))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let pool = crate::thread_pool::get(num_threads)?;
+
+        let result = pool.install(|| {
+            data.par_iter()
+                .fold(
+                    || (KmerLookupResult::new(), Vec::new()),
+                    |(mut result, mut codes), record| {
+                        self.lookup_read_batched(&record.sequence, &mut codes, &mut result);
+                        (result, codes)
+                    },
+                )
+                .map(|(result, _)| result)
+                .reduce(KmerLookupResult::new, |mut a, b| {
+                    a.add(&b);
+                    a
+                })
+        });
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let merge = |mut a: KmerLookupResult, b: KmerLookupResult| {
+            a.add(&b);
+            a
+        };
+        let result = crate::chunked::reduce_neon(self, data, num_threads, batch_size, merge)?
+            .unwrap_or_default();
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        if config.num_threads > 1 {
+            self.execute_threaded(data, config)
+        } else if config.use_neon {
+            self.execute_neon(data)
+        } else {
+            self.execute_naive(data)
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kmer_index_build() {
+        let data = vec![
+            SequenceRecord::fasta("a".to_string(), b"ACGTACGTNAC".to_vec()),
+            SequenceRecord::fasta("b".to_string(), b"cgtac".to_vec()),
+        ];
+        let index = KmerIndex::build(&data, 3, 1).unwrap();
+
+        // ACG/CGT are each other's reverse complement, as are GTA/TAC
+        let code = |kmer: &[u8]| {
+            let mut code = None;
+            for_each_kmer(kmer, 3, |c, _| code = Some(c));
+            code.unwrap()
+        };
+        assert_eq!(index.get(code(b"ACG")), &[(0, 0), (0, 1), (0, 4), (0, 5), (1, 0)]);
+        assert_eq!(index.get(code(b"GTA")), &[(0, 2), (0, 3), (1, 1), (1, 2)]);
+        assert_eq!(index.get(code(b"AAA")), &[]);
+
+        let stats = index.stats(2);
+        assert_eq!(stats.indexed_kmers, 9);
+        assert_eq!(stats.distinct_kmers, 2);
+        assert_eq!(stats.max_occurrences, 5);
+        assert!(stats.size_bytes >= 9 * BYTES_PER_POSITION);
+
+        assert_eq!(KmerIndex::build(&data, 3, 3).unwrap(), index);
+    }
+
+    #[test]
+    fn test_kmer_index_lookup() {
+        let reference = ReadMapping::synthetic_reference(5_000, 9);
+        let records = [SequenceRecord::fasta("ref".to_string(), reference.clone())];
+        let op = KmerIndexLookup::new(Arc::new(KmerIndex::build(&records, 15, 1).unwrap()));
+
+        let mut reads: Vec<SequenceRecord> = (0..40)
+            .map(|i| {
+                let read = reference[i * 100..i * 100 + 100].to_vec();
+                SequenceRecord::fasta(format!("r{}", i), read)
+            })
+            .collect();
+        let unrelated = ReadMapping::synthetic_reference(100, 77);
+        reads.push(SequenceRecord::fasta("miss".to_string(), unrelated));
+        reads.push(SequenceRecord::fasta("short".to_string(), b"ACGT".to_vec()));
+
+        let output = op.execute_naive(&reads).unwrap();
+        let result = output.statistics::<KmerLookupResult>().unwrap().clone();
+        assert_eq!(result.total_sequences, 42);
+        assert_eq!(result.total_kmers, 41 * 86);
+        assert_eq!(result.sequences_with_hits, 40);
+        assert!(result.hit_kmers >= 40 * 86);
+        assert!(result.occurrences >= result.hit_kmers);
+
+        for output in [
+            op.execute_neon(&reads).unwrap(),
+            op.execute_parallel(&reads, 3).unwrap(),
+            op.execute_parallel_chunked(&reads, 2).unwrap(),
+        ] {
+            assert_eq!(output.statistics::<KmerLookupResult>().unwrap(), &result);
+        }
+    }
+}
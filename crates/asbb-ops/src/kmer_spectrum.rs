@@ -0,0 +1,687 @@
+//! K-mer Spectrum Operation
+//!
+//! Builds the k-mer multiplicity histogram ("k-mer spectrum": how many
+//! distinct k-mers occur once, twice, ...) from a k-mer count table. The
+//! spectrum is what genome-size estimation and error-k-mer thresholds are
+//! read from, and building it is dominated by a hash table far larger than
+//! the caches.
+//!
+//! # Operation Characteristics
+//! - **Category**: Aggregation
+//! - **Complexity**: 0.55 (rolling 2-bit k-mers + random hash table access)
+//! - **Output**: Multiplicity histogram + distinct/total k-mer counts
+//! - **NEON benefit**: None expected (hash table bound)
+//!
+//! # Modes
+//! - **Exact**: one `u64 → u32` count table holding every distinct k-mer
+//! - **Bounded**: exact, but k-mers are split into hash partitions counted in
+//!   separate passes over the reads, so at most one partition's table is
+//!   resident. The partition count comes from a HyperLogLog estimate of the
+//!   distinct k-mers and the memory budget.
+//! - **Approximate**: no count table; distinct k-mers are estimated with
+//!   HyperLogLog (2^precision one-byte registers), so no histogram
+//! - **Spill**: exact, single pass; each thread's table is written out to
+//!   on-disk hash shards whenever it outgrows its share of the memory
+//!   budget, and the shards are merged one at a time afterwards (see
+//!   [`crate::spill`]). For machines whose RAM the table does not fit.
+//!
+//! K-mers are upper-case ACGT only (as in `KmerCounting`), 2-bit encoded,
+//! k = 3-31; canonical k-mers are the smaller of the forward and reverse
+//! complement codes (the lexicographic minimum).
+
+use crate::kmer_counting::KmerCounts;
+use crate::spill::{SpillShards, DEFAULT_SPILL_SHARDS};
+use asbb_core::{memory::MemoryEstimate, Encoding};
+use crate::{HardwareConfig, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use anyhow::Result;
+use rayon::prelude::*;
+use rayon::ThreadPool;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Default histogram cap (the last bin holds this multiplicity and above)
+pub const DEFAULT_MAX_MULTIPLICITY: usize = 1_000;
+
+/// Approximate heap bytes per count table entry (key, value, control byte,
+/// load factor headroom); used to size Bounded partitions
+pub const BYTES_PER_TABLE_ENTRY: usize = 24;
+
+/// HyperLogLog precision used to size Bounded partitions
+const PARTITION_ESTIMATE_PRECISION: u8 = 14;
+
+/// How the spectrum is computed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum SpectrumMode {
+    /// Single count table
+    Exact,
+    /// Hash-partitioned passes, each table under `memory_budget_bytes`
+    Bounded { memory_budget_bytes: usize },
+    /// HyperLogLog distinct count only (`precision` 4-18)
+    Approximate { precision: u8 },
+    /// Count tables spilled to disk, resident tables under `memory_budget_bytes`
+    Spill { memory_budget_bytes: usize },
+}
+
+impl SpectrumMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SpectrumMode::Exact => "exact",
+            SpectrumMode::Bounded { .. } => "bounded",
+            SpectrumMode::Approximate { .. } => "approximate",
+            SpectrumMode::Spill { .. } => "spill",
+        }
+    }
+}
+
+/// K-mer spectrum operation
+pub struct KmerSpectrum {
+    /// K-mer size (3-31 bp)
+    pub k: usize,
+    /// Count k-mers together with their reverse complement
+    pub canonical: bool,
+    pub mode: SpectrumMode,
+    pub max_multiplicity: usize,
+}
+
+impl KmerSpectrum {
+    /// Exact spectrum
+    pub fn new(k: usize, canonical: bool) -> Self {
+        assert!((3..=31).contains(&k), "K-mer size must be 3-31");
+        Self {
+            k,
+            canonical,
+            mode: SpectrumMode::Exact,
+            max_multiplicity: DEFAULT_MAX_MULTIPLICITY,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: SpectrumMode) -> Self {
+        if let SpectrumMode::Approximate { precision } = mode {
+            assert!((4..=18).contains(&precision), "HyperLogLog precision must be 4-18");
+        }
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_max_multiplicity(mut self, max_multiplicity: usize) -> Self {
+        assert!(max_multiplicity > 0, "Maximum multiplicity must be at least 1");
+        self.max_multiplicity = max_multiplicity;
+        self
+    }
+
+    /// Call `f` with the 2-bit code of every valid k-mer in `seq`
+    fn for_each_kmer(&self, seq: &[u8], mut f: impl FnMut(u64)) {
+        let k = self.k;
+        let mask = (1u64 << (2 * k)) - 1;
+        let shift = 2 * (k - 1);
+        let (mut forward, mut reverse, mut valid) = (0u64, 0u64, 0usize);
+
+        for &base in seq {
+            let code = match base {
+                b'A' => 0,
+                b'C' => 1,
+                b'G' => 2,
+                b'T' => 3,
+                _ => {
+                    valid = 0;
+                    continue;
+                }
+            };
+            forward = ((forward << 2) | code) & mask;
+            reverse = (reverse >> 2) | ((3 - code) << shift);
+            valid += 1;
+
+            if valid >= k {
+                f(if self.canonical { forward.min(reverse) } else { forward });
+            }
+        }
+    }
+
+    /// 2-bit code of `kmer` (canonical if configured), `None` unless it is
+    /// `k` upper-case ACGT bases
+    pub fn kmer_code(&self, kmer: &[u8]) -> Option<u64> {
+        if kmer.len() != self.k {
+            return None;
+        }
+        let mut code = None;
+        self.for_each_kmer(kmer, |c| code = Some(c));
+        code
+    }
+
+    /// Exact count table of every k-mer (single pass, no partitioning)
+    pub fn count_table(&self, data: &[SequenceRecord], num_threads: usize) -> Result<HashMap<u64, u32>> {
+        let pool = if num_threads > 1 {
+            Some(crate::thread_pool::get(num_threads)?)
+        } else {
+            None
+        };
+        Ok(self.count_partition(data, pool.as_deref(), 1, 0).0)
+    }
+
+    /// Fold over read sequences, sequentially or on `pool`
+    fn fold_reads<T, I, F, M>(&self, data: &[SequenceRecord], pool: Option<&ThreadPool>, init: I, fold: F, merge: M) -> T
+    where
+        T: Send,
+        I: Fn() -> T + Sync + Send,
+        F: Fn(&mut T, &[u8]) + Sync + Send,
+        M: Fn(T, T) -> T + Sync + Send,
+    {
+        match pool {
+            None => {
+                let mut acc = init();
+                for record in data {
+                    fold(&mut acc, &record.sequence);
+                }
+                acc
+            }
+            Some(pool) => pool.install(|| {
+                data.par_iter()
+                    .fold(&init, |mut acc, record| {
+                        fold(&mut acc, &record.sequence);
+                        acc
+                    })
+                    .reduce(&init, &merge)
+            }),
+        }
+    }
+
+    /// Count table for the k-mers in hash partition `partition` of `partitions`
+    fn count_partition(
+        &self,
+        data: &[SequenceRecord],
+        pool: Option<&ThreadPool>,
+        partitions: u64,
+        partition: u64,
+    ) -> (HashMap<u64, u32>, usize) {
+        self.fold_reads(
+            data,
+            pool,
+            || (HashMap::new(), 0usize),
+            |(table, total), seq| {
+                self.for_each_kmer(seq, |code| {
+                    if partitions == 1 || mix64(code) % partitions == partition {
+                        *table.entry(code).or_insert(0) += 1;
+                        *total += 1;
+                    }
+                })
+            },
+            |(a, total_a), (b, total_b)| {
+                // Merge the smaller table into the larger
+                let (mut large, small) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+                for (code, count) in small {
+                    *large.entry(code).or_insert(0) += count;
+                }
+                (large, total_a + total_b)
+            },
+        )
+    }
+
+    /// HyperLogLog sketch of all k-mers plus the exact k-mer total
+    fn sketch(&self, data: &[SequenceRecord], pool: Option<&ThreadPool>, precision: u8) -> (HyperLogLog, usize) {
+        self.fold_reads(
+            data,
+            pool,
+            || (HyperLogLog::new(precision), 0usize),
+            |(hll, total), seq| {
+                self.for_each_kmer(seq, |code| {
+                    hll.insert(mix64(code));
+                    *total += 1;
+                })
+            },
+            |(mut a, total_a), (b, total_b)| {
+                a.merge(&b);
+                (a, total_a + total_b)
+            },
+        )
+    }
+
+    /// Exact spectrum in `partitions` passes
+    fn exact(&self, data: &[SequenceRecord], pool: Option<&ThreadPool>, partitions: usize) -> KmerSpectrumResult {
+        let mut result = KmerSpectrumResult::new(self);
+        result.partitions = partitions;
+
+        for partition in 0..partitions as u64 {
+            let (table, total) = self.count_partition(data, pool, partitions as u64, partition);
+            result.total_kmers += total;
+            result.peak_table_entries = result.peak_table_entries.max(table.len());
+            for count in table.into_values() {
+                result.add_multiplicity(count as usize, 1);
+            }
+        }
+
+        result
+    }
+
+    /// Exact spectrum in one pass, spilling tables past the memory budget
+    ///
+    /// The budget is split between the threads' tables. `partitions` and
+    /// `peak_table_entries` describe the shards (the largest merge table),
+    /// whether or not anything had to be spilled, so results do not depend
+    /// on the thread count.
+    fn spill(&self, data: &[SequenceRecord], pool: Option<&ThreadPool>, memory_budget_bytes: usize) -> Result<KmerSpectrumResult> {
+        let threads = pool.map_or(1, |pool| pool.current_num_threads());
+        let table_limit = (memory_budget_bytes / threads / BYTES_PER_TABLE_ENTRY).max(1);
+        let shards = SpillShards::in_temp_dir(DEFAULT_SPILL_SHARDS)?;
+
+        // (resident table, k-mer total, first spill error)
+        let (mut table, total, error) = self.fold_reads(
+            data,
+            pool,
+            || (HashMap::new(), 0usize, None),
+            |(table, total, error): &mut (HashMap<u64, u32>, usize, Option<anyhow::Error>), seq| {
+                if error.is_some() {
+                    return;
+                }
+                self.for_each_kmer(seq, |code| {
+                    *table.entry(code).or_insert(0) += 1;
+                    *total += 1;
+                });
+                if table.len() > table_limit {
+                    *error = shards.spill(table).err();
+                }
+            },
+            |(a, total_a, error_a), (b, total_b, error_b)| {
+                let (mut large, small) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+                for (code, count) in small {
+                    *large.entry(code).or_insert(0) += count;
+                }
+                let mut error = error_a.or(error_b);
+                if error.is_none() && large.len() > table_limit {
+                    error = shards.spill(&mut large).err();
+                }
+                (large, total_a + total_b, error)
+            },
+        );
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        let mut result = KmerSpectrumResult::new(self);
+        result.total_kmers = total;
+        result.partitions = shards.num_shards();
+
+        if shards.bytes_written() == 0 {
+            // Everything fit: no disk I/O, but report the shards it would use
+            let mut shard_sizes = vec![0usize; shards.num_shards()];
+            for (&code, &count) in &table {
+                shard_sizes[shards.shard_of(code)] += 1;
+                result.add_multiplicity(count as usize, 1);
+            }
+            result.peak_table_entries = shard_sizes.into_iter().max().unwrap_or(0);
+        } else {
+            shards.spill(&mut table)?;
+            shards.merge_each(|shard| {
+                result.peak_table_entries = result.peak_table_entries.max(shard.len());
+                for count in shard.into_values() {
+                    result.add_multiplicity(count as usize, 1);
+                }
+            })?;
+        }
+
+        Ok(result)
+    }
+
+    /// Spectrum for the configured mode
+    pub fn spectrum(&self, data: &[SequenceRecord], num_threads: usize) -> Result<KmerSpectrumResult> {
+        let pool = if num_threads > 1 {
+            Some(crate::thread_pool::get(num_threads)?)
+        } else {
+            None
+        };
+        let pool = pool.as_deref();
+
+        Ok(match self.mode {
+            SpectrumMode::Exact => self.exact(data, pool, 1),
+            SpectrumMode::Bounded { memory_budget_bytes } => {
+                let (hll, _) = self.sketch(data, pool, PARTITION_ESTIMATE_PRECISION);
+                let table_bytes = hll.estimate().ceil() as usize * BYTES_PER_TABLE_ENTRY;
+                self.exact(data, pool, table_bytes.div_ceil(memory_budget_bytes.max(1)).max(1))
+            }
+            SpectrumMode::Approximate { precision } => {
+                let (hll, total) = self.sketch(data, pool, precision);
+                let mut result = KmerSpectrumResult::new(self);
+                result.total_kmers = total;
+                result.distinct_kmers = hll.estimate().round() as usize;
+                result.distinct_estimated = true;
+                result.histogram.clear();
+                result
+            }
+            SpectrumMode::Spill { memory_budget_bytes } => self.spill(data, pool, memory_budget_bytes)?,
+        })
+    }
+}
+
+/// Expected (total, distinct) k-mers in `num_sequences` reads of
+/// `mean_length` bases; distinct is capped by the 4^k possible k-mers
+pub(crate) fn expected_kmers(num_sequences: usize, mean_length: f64, k: usize) -> (f64, f64) {
+    let total = num_sequences as f64 * (mean_length - k as f64 + 1.0).max(0.0);
+    (total, total.min(4f64.powi(k as i32)))
+}
+
+/// splitmix64 finalizer (k-mer codes are far from uniformly distributed)
+pub(crate) fn mix64(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+// ============================================================================
+// HyperLogLog
+// ============================================================================
+
+/// HyperLogLog distinct counter (Flajolet et al. 2007, with the linear
+/// counting small-range correction; 64-bit hashes need no large-range one)
+#[derive(Debug, Clone)]
+pub struct HyperLogLog {
+    precision: u8,
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    pub fn new(precision: u8) -> Self {
+        Self {
+            precision,
+            registers: vec![0; 1 << precision],
+        }
+    }
+
+    /// Add a (well-mixed) 64-bit hash
+    pub fn insert(&mut self, hash: u64) {
+        let index = (hash >> (64 - self.precision)) as usize;
+        // Position of the first 1 bit after the index bits (capped for all-zero)
+        let rank = ((hash << self.precision).leading_zeros() + 1).min(65 - self.precision as u32) as u8;
+        self.registers[index] = self.registers[index].max(rank);
+    }
+
+    /// Union with another sketch of the same precision
+    pub fn merge(&mut self, other: &Self) {
+        debug_assert_eq!(self.precision, other.precision);
+        for (register, &other_register) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(other_register);
+        }
+    }
+
+    /// Estimated number of distinct hashes inserted
+    pub fn estimate(&self) -> f64 {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+        let sum: f64 = self.registers.iter().map(|&r| (-(r as f64)).exp2()).sum();
+        let raw = alpha * m * m / sum;
+
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        if raw <= 2.5 * m && zeros > 0 {
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        }
+    }
+
+    /// Register memory in bytes
+    pub fn size_bytes(&self) -> usize {
+        self.registers.len()
+    }
+}
+
+// ============================================================================
+// Result
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KmerSpectrumResult {
+    pub k: usize,
+    pub mode: String,
+    /// K-mer occurrences (valid k-mers in all reads)
+    pub total_kmers: usize,
+    pub distinct_kmers: usize,
+    /// `distinct_kmers` is a HyperLogLog estimate
+    pub distinct_estimated: bool,
+    /// `histogram[m]`: distinct k-mers occurring `m` times; the last bin also
+    /// holds every higher multiplicity (empty in approximate mode)
+    pub histogram: Vec<usize>,
+    /// Counting passes (hash partitions)
+    pub partitions: usize,
+    /// Largest count table resident at once (entries)
+    pub peak_table_entries: usize,
+}
+
+impl KmerSpectrumResult {
+    pub fn new(op: &KmerSpectrum) -> Self {
+        Self {
+            k: op.k,
+            mode: op.mode.name().to_string(),
+            total_kmers: 0,
+            distinct_kmers: 0,
+            distinct_estimated: false,
+            histogram: vec![0; op.max_multiplicity + 1],
+            partitions: 0,
+            peak_table_entries: 0,
+        }
+    }
+
+    /// Spectrum of an existing `KmerCounting` count table
+    pub fn from_counts(counts: &KmerCounts, k: usize, max_multiplicity: usize) -> Self {
+        let mut result = Self {
+            k,
+            mode: "exact".to_string(),
+            total_kmers: counts.total_kmers,
+            distinct_kmers: 0,
+            distinct_estimated: false,
+            histogram: vec![0; max_multiplicity + 1],
+            partitions: 1,
+            peak_table_entries: counts.unique_kmers,
+        };
+        for &count in counts.counts.values() {
+            result.add_multiplicity(count, 1);
+        }
+        result
+    }
+
+    fn add_multiplicity(&mut self, multiplicity: usize, kmers: usize) {
+        let last = self.histogram.len() - 1;
+        self.histogram[multiplicity.min(last)] += kmers;
+        self.distinct_kmers += kmers;
+    }
+
+    /// Distinct k-mers seen once (mostly sequencing errors at useful coverage)
+    pub fn singletons(&self) -> usize {
+        self.histogram.get(1).copied().unwrap_or(0)
+    }
+
+    /// Multiplicity of the coverage peak: the highest bin after the first
+    /// local minimum (skipping the error peak at multiplicity 1)
+    pub fn peak_multiplicity(&self) -> Option<usize> {
+        let last = self.histogram.len().checked_sub(1)?;
+        let valley = (1..last).find(|&m| self.histogram[m] < self.histogram[m + 1])?;
+        (valley..last)
+            .max_by_key(|&m| (self.histogram[m], std::cmp::Reverse(m)))
+            .filter(|&m| self.histogram[m] > 0)
+    }
+}
+
+impl PrimitiveOperation for KmerSpectrum {
+    fn name(&self) -> &str {
+        "kmer_spectrum"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Aggregation
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "k": self.k,
+            "canonical": self.canonical,
+            "mode": self.mode,
+            "max_multiplicity": self.max_multiplicity,
+        })
+    }
+
+    fn estimate_memory(&self, num_sequences: usize, mean_length: f64, encoding: Encoding) -> MemoryEstimate {
+        let (_, distinct) = expected_kmers(num_sequences, mean_length, self.k);
+        let table_bytes = distinct * BYTES_PER_TABLE_ENTRY as f64;
+        let working = match self.mode {
+            SpectrumMode::Exact => table_bytes,
+            SpectrumMode::Bounded { memory_budget_bytes } | SpectrumMode::Spill { memory_budget_bytes } => {
+                table_bytes.min(memory_budget_bytes as f64)
+            }
+            SpectrumMode::Approximate { precision } => (1u64 << precision) as f64,
+        };
+        MemoryEstimate::input(num_sequences, mean_length, encoding).with_working(working)
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.spectrum(data, 1)?))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.spectrum(data, num_threads)?))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        _batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        // Per-chunk spectra cannot be merged (a k-mer's count spans chunks);
+        // Rayon's fold already keeps one table per split
+        self.execute_parallel(data, num_threads)
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        self.execute_parallel(data, config.num_threads)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kmer_counting::KmerCounting;
+
+    /// Reads from a random 2 kb genome at ~10× coverage with a few errors
+    fn test_reads() -> Vec<SequenceRecord> {
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        let mut next = move || {
+            state = mix64(state.wrapping_add(0x9E37_79B9_7F4A_7C15));
+            state
+        };
+
+        let genome: Vec<u8> = (0..2_000).map(|_| b"ACGT"[(next() % 4) as usize]).collect();
+        (0..134)
+            .map(|i| {
+                let start = (next() % (genome.len() as u64 - 150)) as usize;
+                let mut sequence = genome[start..start + 150].to_vec();
+                if i % 3 == 0 {
+                    sequence[(next() % 150) as usize] = b'N';
+                }
+                if i % 4 == 0 {
+                    let position = (next() % 150) as usize;
+                    sequence[position] = if sequence[position] == b'A' { b'C' } else { b'A' };
+                }
+                SequenceRecord::fasta(format!("read_{}", i), sequence)
+            })
+            .collect()
+    }
+
+    fn spectrum(op: &KmerSpectrum, data: &[SequenceRecord], threads: usize) -> KmerSpectrumResult {
+        op.spectrum(data, threads).unwrap()
+    }
+
+    #[test]
+    fn test_spectrum_small() {
+        // ACGTACGT: ACG(2) CGT(2) GTA(1) TAC(1)
+        let op = KmerSpectrum::new(3, false).with_max_multiplicity(4);
+        let data = vec![SequenceRecord::fasta("s".to_string(), b"ACGTACGTNAAAAAA".to_vec())];
+        let result = spectrum(&op, &data, 1);
+
+        // + AAA ×4 (capped into the last bin)
+        assert_eq!(result.total_kmers, 10);
+        assert_eq!(result.distinct_kmers, 5);
+        assert_eq!(result.histogram, vec![0, 2, 2, 0, 1]);
+        assert_eq!(result.singletons(), 2);
+
+        let canonical = KmerSpectrum::new(3, true).with_max_multiplicity(8);
+        let result = spectrum(&canonical, &data[..], 1);
+        // ACG+CGT → ACG(4), GTA+TAC → GTA(2), AAA(4)
+        assert_eq!(result.distinct_kmers, 3);
+        assert_eq!(result.histogram[2], 1);
+        assert_eq!(result.histogram[4], 2);
+    }
+
+    #[test]
+    fn test_spectrum_matches_count_table() {
+        let data = test_reads();
+        for canonical in [false, true] {
+            let op = KmerSpectrum::new(11, canonical);
+            let exact = spectrum(&op, &data, 1);
+
+            let counts = KmerCounting::new(11, canonical).execute_naive(&data).unwrap();
+            let from_table = KmerSpectrumResult::from_counts(counts.statistics::<KmerCounts>().unwrap(), 11, DEFAULT_MAX_MULTIPLICITY);
+            assert_eq!(exact, from_table);
+
+            assert_eq!(spectrum(&op, &data, 3), exact);
+            assert_eq!(op.execute_parallel_chunked(&data, 2).unwrap().statistics::<KmerSpectrumResult>(), Some(&exact));
+        }
+
+        // ~10× coverage: the peak sits well above the error singletons
+        let result = spectrum(&KmerSpectrum::new(11, true), &data, 1);
+        let peak = result.peak_multiplicity().unwrap();
+        assert!((5..=15).contains(&peak), "peak {}", peak);
+    }
+
+    #[test]
+    fn test_spectrum_bounded_and_approximate() {
+        let data = test_reads();
+        let exact = spectrum(&KmerSpectrum::new(15, true), &data, 1);
+
+        // Budget for roughly a quarter of the table
+        let budget = exact.distinct_kmers * BYTES_PER_TABLE_ENTRY / 4;
+        let bounded = KmerSpectrum::new(15, true).with_mode(SpectrumMode::Bounded { memory_budget_bytes: budget });
+        for threads in [1, 2] {
+            let result = spectrum(&bounded, &data, threads);
+            assert!(result.partitions >= 3, "{} partitions", result.partitions);
+            assert!(result.peak_table_entries < exact.distinct_kmers / 2);
+            assert_eq!(result.histogram, exact.histogram);
+            assert_eq!(result.distinct_kmers, exact.distinct_kmers);
+            assert_eq!(result.total_kmers, exact.total_kmers);
+        }
+
+        // Spill: same spectrum whether or not the budget forces shards to disk
+        let spill = |budget| KmerSpectrum::new(15, true).with_mode(SpectrumMode::Spill { memory_budget_bytes: budget });
+        let in_memory = spectrum(&spill(usize::MAX / 2), &data, 1);
+        assert_eq!(in_memory.histogram, exact.histogram);
+        assert_eq!((in_memory.distinct_kmers, in_memory.total_kmers), (exact.distinct_kmers, exact.total_kmers));
+        for threads in [1, 2] {
+            let before = crate::spill::spilled_bytes();
+            assert_eq!(spectrum(&spill(budget), &data, threads), in_memory);
+            assert!(crate::spill::spilled_bytes() > before);
+        }
+
+        let approximate = KmerSpectrum::new(15, true).with_mode(SpectrumMode::Approximate { precision: 12 });
+        let result = spectrum(&approximate, &data, 2);
+        assert!(result.distinct_estimated);
+        assert!(result.histogram.is_empty());
+        assert_eq!(result.total_kmers, exact.total_kmers);
+        // Standard error 1.04 / sqrt(4096) ≈ 1.6%
+        let error = (result.distinct_kmers as f64 - exact.distinct_kmers as f64).abs() / exact.distinct_kmers as f64;
+        assert!(error < 0.08, "HLL error {:.3}", error);
+    }
+}
@@ -72,7 +72,7 @@ impl PrimitiveOperation for LengthFilter {
         }
 
         result.finalize();
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
@@ -85,9 +85,7 @@ impl PrimitiveOperation for LengthFilter {
         data: &[SequenceRecord],
         num_threads: usize,
     ) -> Result<OperationOutput> {
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()?;
+        let pool = crate::thread_pool::get(num_threads)?;
 
         let threshold = self.min_length;
 
@@ -113,7 +111,24 @@ impl PrimitiveOperation for LengthFilter {
         });
 
         result.finalize();
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let merge = |mut a: LengthFilterResult, b: LengthFilterResult| {
+            a.add(&b);
+            a
+        };
+        let mut result = crate::chunked::reduce_neon(self, data, num_threads, batch_size, merge)?
+            .unwrap_or_else(LengthFilterResult::new);
+        result.finalize();
+
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_with_config(
@@ -122,7 +137,7 @@ impl PrimitiveOperation for LengthFilter {
         config: &HardwareConfig,
     ) -> Result<OperationOutput> {
         if config.num_threads > 1 {
-            self.execute_parallel(data, config.num_threads)
+            self.execute_threaded(data, config)
         } else {
             self.execute_naive(data)
         }
@@ -144,8 +159,7 @@ mod tests {
         let op = LengthFilter::new(10);
         let result = op.execute_naive(&records).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let filter_result: LengthFilterResult = serde_json::from_value(value).unwrap();
+        if let Some(filter_result) = result.statistics::<LengthFilterResult>() {
             assert_eq!(filter_result.total_sequences, 3);
             assert_eq!(filter_result.passed_sequences, 2); // medium + long
             assert_eq!(filter_result.filtered_sequences, 1); // short
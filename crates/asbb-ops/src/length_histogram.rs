@@ -0,0 +1,285 @@
+// Length Histogram Operation
+//
+// Counts sequences per length bin (configurable bin width) in one pass.
+// Bins grow as longer reads arrive, so no pre-pass for the maximum length is
+// needed and the same code handles 150 bp short reads and 100 kb long reads
+// (use a wider bin for the latter).
+//
+// Expected patterns (hypothesis):
+// - NEON: No benefit - one scattered increment per read, no per-base work
+// - Parallel: Per-thread partial histograms merged at the end; merge cost is
+//   proportional to the number of bins, not the number of reads
+// - Chunked parallel should beat per-record parallel (one partial histogram
+//   per chunk instead of one per read)
+//
+// Goal: Add a histogram-shaped reduction (scatter into bins + elementwise
+// merge) to the Aggregation category, alongside the scalar reductions
+//
+// Complexity Score: ~0.25 (simple)
+// - Operations per byte: 0.05 (lengths only, bases are never read)
+// - Accumulator count: 0.5 (one counter per bin)
+// - Horizontal reduction: 0.4 (elementwise merge of partial histograms)
+// - Scalar fallback: 0.2 (no SIMD path)
+// - Memory access: 0.3 (random increments into a small table)
+// - Data dependencies: 0.2 (independent increments)
+
+use crate::PrimitiveOperation;
+use asbb_core::{packed::PackedRecords, HardwareConfig, OperationCategory, OperationOutput, SequenceRecord, SequenceView};
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub struct LengthHistogram {
+    /// Bases per bin (bin `i` holds lengths `i * bin_width..(i + 1) * bin_width`)
+    pub bin_width: usize,
+}
+
+impl LengthHistogram {
+    pub fn new(bin_width: usize) -> Self {
+        assert!(bin_width > 0, "Bin width must be at least 1");
+        Self { bin_width }
+    }
+}
+
+impl Default for LengthHistogram {
+    /// 10 bp bins (short reads)
+    fn default() -> Self {
+        Self::new(10)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LengthHistogramResult {
+    pub bin_width: usize,
+    pub num_sequences: usize,
+    pub total_length: usize,
+    pub min_length: usize,
+    pub max_length: usize,
+    /// Sequences per bin, up to the bin holding `max_length`
+    pub counts: Vec<usize>,
+}
+
+impl LengthHistogramResult {
+    pub fn new(bin_width: usize) -> Self {
+        Self {
+            bin_width,
+            num_sequences: 0,
+            total_length: 0,
+            min_length: usize::MAX,
+            max_length: 0,
+            counts: Vec::new(),
+        }
+    }
+
+    /// Count one sequence length
+    pub fn add_length(&mut self, len: usize) {
+        let bin = len / self.bin_width;
+        if bin >= self.counts.len() {
+            self.counts.resize(bin + 1, 0);
+        }
+        self.counts[bin] += 1;
+
+        self.num_sequences += 1;
+        self.total_length += len;
+        self.min_length = self.min_length.min(len);
+        self.max_length = self.max_length.max(len);
+    }
+
+    /// Merge a partial histogram with the same bin width
+    pub fn add(&mut self, other: &Self) {
+        debug_assert_eq!(self.bin_width, other.bin_width);
+        if other.counts.len() > self.counts.len() {
+            self.counts.resize(other.counts.len(), 0);
+        }
+        for (count, &other_count) in self.counts.iter_mut().zip(&other.counts) {
+            *count += other_count;
+        }
+
+        self.num_sequences += other.num_sequences;
+        self.total_length += other.total_length;
+        self.min_length = self.min_length.min(other.min_length);
+        self.max_length = self.max_length.max(other.max_length);
+    }
+
+    /// Non-empty bins as `(start, end, count)` (lengths `start..end`)
+    pub fn bins(&self) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(i, &count)| (i * self.bin_width, (i + 1) * self.bin_width, count))
+    }
+
+    /// Mean sequence length
+    pub fn mean_length(&self) -> f64 {
+        if self.num_sequences == 0 {
+            0.0
+        } else {
+            self.total_length as f64 / self.num_sequences as f64
+        }
+    }
+}
+
+impl PrimitiveOperation for LengthHistogram {
+    fn name(&self) -> &str {
+        "length_histogram"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Aggregation
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({ "bin_width": self.bin_width })
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        let mut result = LengthHistogramResult::new(self.bin_width);
+
+        for record in data {
+            result.add_length(record.sequence.len());
+        }
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let pool = crate::thread_pool::get(num_threads)?;
+
+        // One partial histogram per Rayon split, merged pairwise
+        let result = pool.install(|| {
+            data.par_iter()
+                .fold(
+                    || LengthHistogramResult::new(self.bin_width),
+                    |mut local, record| {
+                        local.add_length(record.sequence.len());
+                        local
+                    },
+                )
+                .reduce(
+                    || LengthHistogramResult::new(self.bin_width),
+                    |mut a, b| {
+                        a.add(&b);
+                        a
+                    },
+                )
+        });
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let merge = |mut a: LengthHistogramResult, b: LengthHistogramResult| {
+            a.add(&b);
+            a
+        };
+        let result = crate::chunked::reduce_neon(self, data, num_threads, batch_size, merge)?
+            .unwrap_or_else(|| LengthHistogramResult::new(self.bin_width));
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_views(&self, data: &[SequenceView]) -> Result<OperationOutput> {
+        let mut result = LengthHistogramResult::new(self.bin_width);
+
+        for view in data {
+            result.add_length(view.len());
+        }
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_packed(&self, data: &PackedRecords) -> Result<OperationOutput> {
+        // Lengths come straight from the offset table; sequences are not read
+        let mut result = LengthHistogramResult::new(self.bin_width);
+
+        for bounds in data.offsets().windows(2) {
+            result.add_length(bounds[1] - bounds[0]);
+        }
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        if config.num_threads > 1 {
+            self.execute_threaded(data, config)
+        } else {
+            self.execute_naive(data)
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_records() -> Vec<SequenceRecord> {
+        [0usize, 5, 9, 10, 150, 151, 149, 1_000]
+            .iter()
+            .enumerate()
+            .map(|(i, &len)| SequenceRecord::fasta(format!("seq{}", i), vec![b'A'; len]))
+            .collect()
+    }
+
+    fn histogram(output: OperationOutput) -> LengthHistogramResult {
+        output.statistics::<LengthHistogramResult>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_length_histogram_naive() {
+        let op = LengthHistogram::new(10);
+        let result = histogram(op.execute_naive(&create_test_records()).unwrap());
+
+        assert_eq!(result.num_sequences, 8);
+        assert_eq!(result.min_length, 0);
+        assert_eq!(result.max_length, 1_000);
+        assert_eq!(result.counts.len(), 101);
+        assert_eq!(
+            result.bins().collect::<Vec<_>>(),
+            vec![(0, 10, 3), (10, 20, 1), (140, 150, 1), (150, 160, 2), (1_000, 1_010, 1)]
+        );
+        assert!((result.mean_length() - 1_474.0 / 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_length_histogram_backends_match() {
+        let op = LengthHistogram::new(7);
+        let data = create_test_records();
+        let expected = histogram(op.execute_naive(&data).unwrap());
+
+        for threads in [1, 2, 4] {
+            assert_eq!(histogram(op.execute_parallel(&data, threads).unwrap()), expected);
+            assert_eq!(histogram(op.execute_parallel_chunked(&data, threads).unwrap()), expected);
+        }
+
+        let packed = PackedRecords::from_records(&data).unwrap();
+        assert_eq!(histogram(op.execute_packed(&packed).unwrap()), expected);
+    }
+
+    #[test]
+    fn test_length_histogram_empty() {
+        let op = LengthHistogram::default();
+        let result = histogram(op.execute_parallel_chunked(&[], 4).unwrap());
+
+        assert_eq!(result.num_sequences, 0);
+        assert!(result.counts.is_empty());
+        assert_eq!(result.mean_length(), 0.0);
+    }
+}
@@ -0,0 +1,328 @@
+// Length Statistics Operation (assembly-style)
+//
+// N50, N90, auN and min/max/mean/median read (or contig) length. Unlike the
+// other aggregations, Nx and the median depend on the order of the lengths:
+// N50 is the length L such that reads of length >= L hold at least half of
+// all bases, found by walking the lengths from longest to shortest. auN
+// (area under the Nx curve) is sum(L^2) / sum(L) and needs no ordering.
+//
+// Backends:
+// - Naive: collect lengths, full sort, walk from the longest
+// - Parallel: parallel sort (Rayon's unstable parallel quicksort), then the
+//   same walk
+// - Chunked: sort each chunk in parallel, then lazily k-way merge the
+//   sorted chunks only until the median and N90 are reached (a partial
+//   sort: for short-read sets the walk stops about halfway down)
+//
+// Expected patterns (hypothesis):
+// - NEON: No benefit - lengths only, no per-base work (naive fallback)
+// - Parallel: Sorting dominates; O(n log n) spread over threads, but the
+//   serial walk and merge bound the speedup
+// - Chunked: Cheaper than the full parallel sort when the walk stops early
+//
+// Goal: Add a sorting-dominated aggregation to the operation set
+//
+// Complexity Score: ~0.35
+// - Operations per byte: 0.05 (lengths only, bases are never read)
+// - Accumulator count: 0.3 (a length per read, plus running sums)
+// - Horizontal reduction: 0.6 (sort / merge across threads)
+// - Scalar fallback: 0.2 (no SIMD path)
+// - Memory access: 0.5 (sort passes over the length array)
+// - Data dependencies: 0.4 (Nx walk depends on the sorted order)
+
+use crate::PrimitiveOperation;
+use asbb_core::{HardwareConfig, OperationCategory, OperationOutput, SequenceRecord};
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub struct LengthStats;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LengthStatsResult {
+    pub num_sequences: usize,
+    pub total_length: u64,
+    pub min_length: usize,
+    pub max_length: usize,
+    pub mean_length: f64,
+    /// Mean of the two middle lengths for an even count
+    pub median_length: f64,
+    /// Shortest length among the longest reads holding 50% of the bases
+    pub n50: usize,
+    /// Same for 90% of the bases
+    pub n90: usize,
+    /// Area under the Nx curve: sum(L^2) / sum(L)
+    pub aun: f64,
+}
+
+impl LengthStatsResult {
+    pub fn new() -> Self {
+        Self {
+            num_sequences: 0,
+            total_length: 0,
+            min_length: 0,
+            max_length: 0,
+            mean_length: 0.0,
+            median_length: 0.0,
+            n50: 0,
+            n90: 0,
+            aun: 0.0,
+        }
+    }
+}
+
+impl Default for LengthStatsResult {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Order-independent sums, computed before the walk
+#[derive(Debug, Clone, Copy)]
+struct Totals {
+    count: usize,
+    total: u64,
+    sum_squares: u128,
+}
+
+impl Totals {
+    fn of(lengths: &[usize]) -> Self {
+        Self {
+            count: lengths.len(),
+            total: lengths.iter().map(|&len| len as u64).sum(),
+            sum_squares: lengths.iter().map(|&len| (len as u128) * (len as u128)).sum(),
+        }
+    }
+}
+
+/// Statistics from lengths visited longest first
+///
+/// Stops pulling lengths once the median and N90 are known; `min_length` is
+/// passed in because the walk may never reach the shortest read.
+fn summarize(
+    descending: impl Iterator<Item = usize>,
+    totals: Totals,
+    min_length: usize,
+) -> LengthStatsResult {
+    let mut result = LengthStatsResult::new();
+    if totals.count == 0 {
+        return result;
+    }
+    result.num_sequences = totals.count;
+    result.total_length = totals.total;
+    result.min_length = min_length;
+    result.mean_length = totals.total as f64 / totals.count as f64;
+    if totals.total > 0 {
+        result.aun = totals.sum_squares as f64 / totals.total as f64;
+    }
+
+    // Median positions in descending order (equal for an odd count)
+    let (middle_low, middle_high) = ((totals.count - 1) / 2, totals.count / 2);
+    let mut middle_sum = 0.0;
+    let mut cumulative = 0u64;
+    let (mut n50, mut n90) = (None, None);
+
+    for (i, len) in descending.enumerate() {
+        if i == 0 {
+            result.max_length = len;
+        }
+        if i == middle_low || i == middle_high {
+            middle_sum += len as f64;
+        }
+        cumulative += len as u64;
+        if n50.is_none() && cumulative * 2 >= totals.total {
+            n50 = Some(len);
+        }
+        if n90.is_none() && cumulative * 10 >= totals.total * 9 {
+            n90 = Some(len);
+        }
+        if i >= middle_high && n90.is_some() {
+            break;
+        }
+    }
+
+    result.median_length = if middle_low == middle_high { middle_sum } else { middle_sum / 2.0 };
+    result.n50 = n50.unwrap_or(0);
+    result.n90 = n90.unwrap_or(0);
+    result
+}
+
+/// Sorted (descending) runs merged lazily, longest first
+fn merge_descending(runs: &[Vec<usize>]) -> impl Iterator<Item = usize> + '_ {
+    let mut heap: BinaryHeap<(usize, Reverse<usize>, usize)> = runs
+        .iter()
+        .enumerate()
+        .filter(|(_, run)| !run.is_empty())
+        .map(|(r, run)| (run[0], Reverse(r), 0))
+        .collect();
+
+    std::iter::from_fn(move || {
+        let (len, Reverse(r), i) = heap.pop()?;
+        if let Some(&next) = runs[r].get(i + 1) {
+            heap.push((next, Reverse(r), i + 1));
+        }
+        Some(len)
+    })
+}
+
+fn lengths(data: &[SequenceRecord]) -> Vec<usize> {
+    data.iter().map(|record| record.sequence.len()).collect()
+}
+
+impl PrimitiveOperation for LengthStats {
+    fn name(&self) -> &str {
+        "length_stats"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Aggregation
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        let mut lengths = lengths(data);
+        lengths.sort_unstable();
+
+        let totals = Totals::of(&lengths);
+        let min_length = lengths.first().copied().unwrap_or(0);
+        let result = summarize(lengths.iter().rev().copied(), totals, min_length);
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let pool = crate::thread_pool::get(num_threads)?;
+
+        let result = pool.install(|| {
+            let mut lengths: Vec<usize> =
+                data.par_iter().map(|record| record.sequence.len()).collect();
+            lengths.par_sort_unstable_by(|a, b| b.cmp(a));
+
+            let totals = Totals {
+                count: lengths.len(),
+                total: lengths.par_iter().map(|&len| len as u64).sum(),
+                sum_squares: lengths.par_iter().map(|&len| (len as u128) * (len as u128)).sum(),
+            };
+            let min_length = lengths.last().copied().unwrap_or(0);
+            summarize(lengths.iter().copied(), totals, min_length)
+        });
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let pool = crate::thread_pool::get(num_threads)?;
+        let size = crate::chunked::chunk_size(data.len(), num_threads, batch_size);
+
+        // Sorted runs and their sums, one per chunk
+        let runs: Vec<(Vec<usize>, Totals)> = pool.install(|| {
+            data.par_chunks(size)
+                .map(|chunk| {
+                    let mut run = lengths(chunk);
+                    run.sort_unstable_by(|a, b| b.cmp(a));
+                    let totals = Totals::of(&run);
+                    (run, totals)
+                })
+                .collect()
+        });
+
+        let totals = runs.iter().fold(
+            Totals { count: 0, total: 0, sum_squares: 0 },
+            |acc, (_, t)| Totals {
+                count: acc.count + t.count,
+                total: acc.total + t.total,
+                sum_squares: acc.sum_squares + t.sum_squares,
+            },
+        );
+        let min_length = runs.iter().filter_map(|(run, _)| run.last()).min().copied();
+        let runs: Vec<Vec<usize>> = runs.into_iter().map(|(run, _)| run).collect();
+        let result = summarize(merge_descending(&runs), totals, min_length.unwrap_or(0));
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        if config.num_threads > 1 {
+            self.execute_threaded(data, config)
+        } else {
+            self.execute_naive(data)
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(lengths: &[usize]) -> Vec<SequenceRecord> {
+        lengths
+            .iter()
+            .enumerate()
+            .map(|(i, &len)| SequenceRecord::fasta(format!("seq{}", i), vec![b'A'; len]))
+            .collect()
+    }
+
+    fn stats(output: OperationOutput) -> LengthStatsResult {
+        output.statistics::<LengthStatsResult>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_length_stats_naive() {
+        // 100 bases in total: 40 + 30 reach 50%, 40 + 30 + 20 reach 90%
+        let result = stats(LengthStats.execute_naive(&records(&[10, 40, 20, 30])).unwrap());
+
+        assert_eq!(result.num_sequences, 4);
+        assert_eq!(result.total_length, 100);
+        assert_eq!((result.min_length, result.max_length), (10, 40));
+        assert_eq!(result.mean_length, 25.0);
+        assert_eq!(result.median_length, 25.0);
+        assert_eq!((result.n50, result.n90), (30, 20));
+        assert!((result.aun - 3_000.0 / 100.0).abs() < 1e-12);
+
+        let odd = stats(LengthStats.execute_naive(&records(&[5, 1, 3])).unwrap());
+        assert_eq!(odd.median_length, 3.0);
+        assert_eq!((odd.n50, odd.n90), (5, 1));
+    }
+
+    #[test]
+    fn test_length_stats_backends_match() {
+        let lengths: Vec<usize> = (0..1_001).map(|i| 50 + (i * 7_919) % 2_000).collect();
+        let data = records(&lengths);
+        let expected = stats(LengthStats.execute_naive(&data).unwrap());
+
+        for threads in [1, 2, 4] {
+            assert_eq!(stats(LengthStats.execute_parallel(&data, threads).unwrap()), expected);
+            assert_eq!(
+                stats(LengthStats.execute_parallel_chunked(&data, threads).unwrap()),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_length_stats_empty() {
+        let result = stats(LengthStats.execute_parallel_chunked(&[], 4).unwrap());
+        assert_eq!(result, LengthStatsResult::new());
+
+        let zero_length = stats(LengthStats.execute_naive(&records(&[0, 0])).unwrap());
+        assert_eq!((zero_length.n50, zero_length.aun), (0, 0.0));
+    }
+}
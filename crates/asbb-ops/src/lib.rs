@@ -20,26 +20,55 @@
 pub mod adapter_trimming;
 pub mod at_content;
 pub mod base_counting;
+pub mod catalog; // Operations by name, shared by every front end
+pub mod chunked;
 pub mod complexity_score;
+pub mod consensus;
+#[cfg(feature = "compression")]
 pub mod compression; // Hardware Compression pilot utilities
+#[cfg(feature = "compression")]
+pub mod compression_study; // Codec ratio vs throughput (zstd, LZ4, LZFSE)
 pub mod edit_distance;
+pub mod error_correction;
 // pub mod gcd; // Grand Central Dispatch utilities for GCD/QoS pilot (DEFERRED - see experiments/phase1_gcd_qos/DECISION.md)
 pub mod fastq_parsing;
 pub mod gc_content;
+pub mod gc_window;
+pub mod gram; // Gram matrices (X·Xᵀ) for Pairwise operations
 pub mod hamming_distance;
+pub mod intra_read;
 pub mod kmer_counting;
+pub mod kmer_distance;
+pub mod kmer_embedding; // Core ML / Neural Engine embedding similarity
 pub mod kmer_extraction;
+pub mod kmer_index;
+pub mod kmer_spectrum;
 pub mod length_filter;
+pub mod length_histogram;
+pub mod length_stats;
 pub mod minhash_sketching;
+pub mod motif_scan;
 pub mod n_content;
+pub mod overrepresented;
+pub mod phred_encoding;
+pub mod position_content;
+pub mod primer_match;
 pub mod quality_aggregation;
+pub mod quality_denoising;
 pub mod quality_filter;
 pub mod quality_statistics;
+pub mod read_mapping; // Level-2 composite: seed-and-extend mapping
+pub mod record_sort;
 pub mod reverse_complement;
 pub mod sequence_length;
 pub mod sequence_masking;
+pub mod simd;
+pub mod spill;
+pub mod thread_pool;
 pub mod translation;
 
+pub use catalog::{available_operations, create_operation};
+
 // Re-export common types
 pub use asbb_core::{
     DataCharacteristics, HardwareConfig, OperationCategory, OperationOutput,
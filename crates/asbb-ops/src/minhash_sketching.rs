@@ -14,10 +14,12 @@
 //! - Sketch size determines accuracy (larger = more accurate, more memory)
 //! - NEON accelerates k-mer extraction and hash computation
 
+use crate::gram::{gram_matrix, GramBackend};
 use crate::{OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
 use anyhow::Result;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[cfg(target_arch = "aarch64")]
 use std::arch::aarch64::*;
@@ -31,7 +33,7 @@ pub struct MinHashSketching {
 }
 
 /// MinHash sketch output
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MinHashSketch {
     pub sequence_id: String,
     pub sketch: Vec<u64>,
@@ -156,6 +158,18 @@ impl MinHashSketching {
         }
     }
 
+    /// Sketch one record (NEON k-mer extraction where available)
+    pub fn sketch(&self, record: &SequenceRecord) -> MinHashSketch {
+        #[cfg(target_arch = "aarch64")]
+        {
+            self.compute_sketch_neon(record)
+        }
+        #[cfg(not(target_arch = "aarch64"))]
+        {
+            self.compute_sketch_naive(record)
+        }
+    }
+
     /// Compute Jaccard similarity between two sketches
     pub fn jaccard_similarity(sketch1: &MinHashSketch, sketch2: &MinHashSketch) -> f64 {
         if sketch1.sketch.is_empty() || sketch2.sketch.is_empty() {
@@ -189,6 +203,57 @@ impl MinHashSketching {
 
         intersection as f64 / union as f64
     }
+
+    /// All pairwise Jaccard similarities from one matrix product
+    ///
+    /// Builds an indicator matrix with a column per hash shared by at least
+    /// two sketches (a hash repeated within a sketch gets a column per
+    /// occurrence), so `B·Bᵀ` holds every pairwise intersection and the
+    /// comparison runs on any [`GramBackend`]. Agrees with
+    /// [`jaccard_similarity`](Self::jaccard_similarity) on every pair.
+    pub fn jaccard_matrix(sketches: &[MinHashSketch], backend: GramBackend) -> Result<Vec<Vec<f64>>> {
+        // (hash, occurrence) -> sketches containing it (sketches are sorted)
+        let mut owners: HashMap<(u64, usize), Vec<usize>> = HashMap::new();
+        for (row, sketch) in sketches.iter().enumerate() {
+            let mut previous = None;
+            let mut occurrence = 0;
+            for &hash in &sketch.sketch {
+                occurrence = if previous == Some(hash) { occurrence + 1 } else { 0 };
+                previous = Some(hash);
+                owners.entry((hash, occurrence)).or_default().push(row);
+            }
+        }
+        let shared: Vec<Vec<usize>> = owners.into_values().filter(|rows| rows.len() > 1).collect();
+
+        // At least one (empty) column, so every backend sees a valid matrix
+        let n = sketches.len();
+        let cols = shared.len().max(1);
+        let mut indicator = vec![0.0f32; n * cols];
+        for (col, rows) in shared.iter().enumerate() {
+            for &row in rows {
+                indicator[row * cols + col] = 1.0;
+            }
+        }
+        let intersections = gram_matrix(&indicator, n, cols, backend)?;
+
+        Ok((0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| {
+                        let (a, b) = (sketches[i].sketch.len(), sketches[j].sketch.len());
+                        if a == 0 || b == 0 {
+                            0.0
+                        } else if i == j {
+                            1.0
+                        } else {
+                            let intersection = intersections[i * n + j].round() as usize;
+                            intersection as f64 / (a + b - intersection) as f64
+                        }
+                    })
+                    .collect()
+            })
+            .collect())
+    }
 }
 
 impl PrimitiveOperation for MinHashSketching {
@@ -206,7 +271,7 @@ impl PrimitiveOperation for MinHashSketching {
             .map(|record| self.compute_sketch_naive(record))
             .collect();
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(sketches)?))
+        Ok(OperationOutput::typed(sketches))
     }
 
     #[cfg(target_arch = "aarch64")]
@@ -216,14 +281,11 @@ impl PrimitiveOperation for MinHashSketching {
             .map(|record| self.compute_sketch_neon(record))
             .collect();
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(sketches)?))
+        Ok(OperationOutput::typed(sketches))
     }
 
     fn execute_parallel(&self, sequences: &[SequenceRecord], num_threads: usize) -> Result<OperationOutput> {
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()
-            .unwrap();
+        let pool = crate::thread_pool::get(num_threads)?;
 
         let sketches: Vec<MinHashSketch> = pool.install(|| {
             sequences.par_iter().map(|record| {
@@ -238,7 +300,7 @@ impl PrimitiveOperation for MinHashSketching {
             }).collect()
         });
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(sketches)?))
+        Ok(OperationOutput::typed(sketches))
     }
 }
 
@@ -278,8 +340,7 @@ mod tests {
         let seq = create_test_sequence("test1", b"ACGTACGT");
 
         let output = op.execute_naive(&[seq]).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let sketches: Vec<MinHashSketch> = serde_json::from_value(json).unwrap();
+        if let Some(sketches) = output.statistics::<Vec<MinHashSketch>>() {
             assert_eq!(sketches.len(), 1);
             assert_eq!(sketches[0].sequence_id, "test1");
             assert_eq!(sketches[0].k, 3);
@@ -299,8 +360,7 @@ mod tests {
         let seq = create_test_sequence("test1", b"ACGTACGT");
 
         let output = op.execute_naive(&[seq]).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let sketches: Vec<MinHashSketch> = serde_json::from_value(json).unwrap();
+        if let Some(sketches) = output.statistics::<Vec<MinHashSketch>>() {
             // Sketch size limited to 2
             assert_eq!(sketches[0].sketch.len(), 2);
         }
@@ -313,8 +373,7 @@ mod tests {
         let seq = create_test_sequence("test1", b"ACGTACGTACGT");
 
         let output = op.execute_naive(&[seq]).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let sketches: Vec<MinHashSketch> = serde_json::from_value(json).unwrap();
+        if let Some(sketches) = output.statistics::<Vec<MinHashSketch>>() {
 
             // Sketch should be sorted (minimum hashes first)
             let sketch = &sketches[0].sketch;
@@ -332,8 +391,7 @@ mod tests {
         let seq = create_test_sequence("test1", b"ACGNCGT");
 
         let output = op.execute_naive(&[seq]).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let sketches: Vec<MinHashSketch> = serde_json::from_value(json).unwrap();
+        if let Some(sketches) = output.statistics::<Vec<MinHashSketch>>() {
             // Should have 2 hashes for the 2 valid k-mers
             assert_eq!(sketches[0].sketch.len(), 2);
         }
@@ -347,8 +405,7 @@ mod tests {
         let seq2 = create_test_sequence("seq2", b"ACGTACGT");
 
         let output = op.execute_naive(&[seq1, seq2]).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let sketches: Vec<MinHashSketch> = serde_json::from_value(json).unwrap();
+        if let Some(sketches) = output.statistics::<Vec<MinHashSketch>>() {
 
             let similarity = MinHashSketching::jaccard_similarity(&sketches[0], &sketches[1]);
             // Identical sequences should have similarity = 1.0
@@ -364,8 +421,7 @@ mod tests {
         let seq2 = create_test_sequence("seq2", b"GGTTAACC");
 
         let output = op.execute_naive(&[seq1, seq2]).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let sketches: Vec<MinHashSketch> = serde_json::from_value(json).unwrap();
+        if let Some(sketches) = output.statistics::<Vec<MinHashSketch>>() {
 
             let similarity = MinHashSketching::jaccard_similarity(&sketches[0], &sketches[1]);
             // Different sequences should have low similarity
@@ -373,6 +429,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_jaccard_matrix_matches_pairwise() {
+        let op = MinHashSketching::new(3, 8);
+        let sketches: Vec<MinHashSketch> = [
+            &b"ACGTACGTAC"[..],
+            b"ACGTACGTTT",
+            b"GGGGCCCCAA",
+            b"AAAAAAAAAA",
+            b"",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, seq)| op.compute_sketch_naive(&create_test_sequence(&format!("s{}", i), seq)))
+        .collect();
+
+        let matrix = MinHashSketching::jaccard_matrix(&sketches, GramBackend::Naive).unwrap();
+        for i in 0..sketches.len() {
+            for j in 0..sketches.len() {
+                let expected = MinHashSketching::jaccard_similarity(&sketches[i], &sketches[j]);
+                assert!((matrix[i][j] - expected).abs() < 1e-12, "({}, {})", i, j);
+            }
+        }
+    }
+
     #[test]
     fn test_empty_sequence() {
         let op = MinHashSketching::new(3, 5);
@@ -380,8 +460,7 @@ mod tests {
         let seq = create_test_sequence("empty", b"");
 
         let output = op.execute_naive(&[seq]).unwrap();
-        if let OperationOutput::Statistics(json) = output {
-            let sketches: Vec<MinHashSketch> = serde_json::from_value(json).unwrap();
+        if let Some(sketches) = output.statistics::<Vec<MinHashSketch>>() {
             assert_eq!(sketches[0].sketch.len(), 0);
         }
     }
@@ -399,10 +478,10 @@ mod tests {
         let naive_output = op.execute_naive(&sequences).unwrap();
         let neon_output = op.execute_neon(&sequences).unwrap();
 
-        if let (OperationOutput::Statistics(naive_json), OperationOutput::Statistics(neon_json)) =
+        if let (OperationOutput::Typed(naive_typed), OperationOutput::Typed(neon_typed)) =
             (naive_output, neon_output) {
-            let naive_sketches: Vec<MinHashSketch> = serde_json::from_value(naive_json).unwrap();
-            let neon_sketches: Vec<MinHashSketch> = serde_json::from_value(neon_json).unwrap();
+            let naive_sketches: &Vec<MinHashSketch> = naive_typed.downcast_ref().unwrap();
+            let neon_sketches: &Vec<MinHashSketch> = neon_typed.downcast_ref().unwrap();
 
             assert_eq!(naive_sketches.len(), neon_sketches.len());
             for (n, ne) in naive_sketches.iter().zip(neon_sketches.iter()) {
@@ -425,14 +504,14 @@ mod tests {
         let naive_output = op.execute_naive(&sequences).unwrap();
         let parallel_output = op.execute_parallel(&sequences, 2).unwrap();
 
-        if let (OperationOutput::Statistics(naive_json), OperationOutput::Statistics(parallel_json)) =
+        if let (OperationOutput::Typed(naive_typed), OperationOutput::Typed(parallel_typed)) =
             (naive_output, parallel_output) {
-            let naive_sketches: Vec<MinHashSketch> = serde_json::from_value(naive_json).unwrap();
-            let parallel_sketches: Vec<MinHashSketch> = serde_json::from_value(parallel_json).unwrap();
+            let naive_sketches: &Vec<MinHashSketch> = naive_typed.downcast_ref().unwrap();
+            let parallel_sketches: &Vec<MinHashSketch> = parallel_typed.downcast_ref().unwrap();
 
             assert_eq!(naive_sketches.len(), parallel_sketches.len());
             // Sketches should match (order may differ, so sort by ID)
-            for sketch in &naive_sketches {
+            for sketch in naive_sketches {
                 let matching = parallel_sketches.iter().find(|s| s.sequence_id == sketch.sequence_id).unwrap();
                 assert_eq!(sketch.sketch, matching.sketch);
             }
@@ -0,0 +1,572 @@
+//! Motif Scan Operation
+//!
+//! Counts occurrences of a small set of fixed motifs (telomere repeats,
+//! restriction sites, ...) in every read. Overlapping hits all count and
+//! matching is case-insensitive.
+//!
+//! # Operation Characteristics
+//! - **Category**: Search
+//! - **Complexity**: 0.50 (multi-pattern matching + candidate verification)
+//! - **Output**: Per-motif hit counts (aggregation)
+//! - **NEON benefit**: Expected high for rare motifs (16 start positions
+//!   rejected per handful of table lookups)
+//!
+//! # Implementation Notes
+//! - Naive: compare every motif at every position
+//! - Shift-and: all motifs share one 64-bit state word, so each base costs one
+//!   shift, OR and AND however many motifs there are (total motif length is
+//!   limited to 64). Used for the NEON entry point on other targets and for
+//!   sets of more than 8 motifs.
+//! - NEON (Teddy-like): each motif owns one bit of a byte "bucket". Nibble
+//!   lookup tables (`vqtbl1q_u8`) for the first 1-3 motif bytes flag the
+//!   candidate start positions of 16 offsets at once; flagged lanes are
+//!   verified with a full compare.
+
+use crate::{HardwareConfig, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Maximum total motif length (one shift-and state bit per motif base)
+pub const MAX_TOTAL_MOTIF_LENGTH: usize = 64;
+
+/// Maximum motifs for the Teddy NEON path (one bucket bit per motif)
+const MAX_TEDDY_MOTIFS: usize = 8;
+
+/// Motif bytes in the Teddy fingerprint
+const MAX_FINGERPRINT: usize = 3;
+
+/// A named fixed motif (stored upper case)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Motif {
+    pub name: String,
+    pub pattern: Vec<u8>,
+}
+
+impl Motif {
+    pub fn new(name: &str, pattern: &[u8]) -> Self {
+        assert!(!pattern.is_empty(), "Motif {} is empty", name);
+        assert!(
+            pattern.iter().all(|b| matches!(b.to_ascii_uppercase(), b'A' | b'C' | b'G' | b'T')),
+            "Motif {} must contain only ACGT",
+            name
+        );
+        Self {
+            name: name.to_string(),
+            pattern: pattern.to_ascii_uppercase(),
+        }
+    }
+}
+
+/// Matching strategy for a motif scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanBackend {
+    /// Compare every motif at every position
+    Naive,
+    /// Bit-parallel multi-pattern shift-and
+    ShiftAnd,
+    /// NEON nibble-table candidate filter + verification
+    Teddy,
+}
+
+impl ScanBackend {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScanBackend::Naive => "naive",
+            ScanBackend::ShiftAnd => "shift_and",
+            ScanBackend::Teddy => "teddy",
+        }
+    }
+}
+
+/// Motif scan operation
+pub struct MotifScan {
+    motifs: Vec<Motif>,
+    shift_and: ShiftAnd,
+    teddy: Option<Teddy>,
+}
+
+impl MotifScan {
+    pub fn new(motifs: Vec<Motif>) -> Self {
+        assert!(!motifs.is_empty(), "At least one motif is required");
+        let total: usize = motifs.iter().map(|m| m.pattern.len()).sum();
+        assert!(
+            total <= MAX_TOTAL_MOTIF_LENGTH,
+            "Total motif length {} exceeds {}",
+            total,
+            MAX_TOTAL_MOTIF_LENGTH
+        );
+
+        let shift_and = ShiftAnd::new(&motifs);
+        let teddy = (motifs.len() <= MAX_TEDDY_MOTIFS).then(|| Teddy::new(&motifs));
+        Self { motifs, shift_and, teddy }
+    }
+
+    /// Vertebrate telomere repeat on both strands
+    pub fn telomere() -> Self {
+        Self::new(vec![Motif::new("telomere_fwd", b"TTAGGG"), Motif::new("telomere_rev", b"CCCTAA")])
+    }
+
+    /// Common restriction sites (all palindromic, so one strand suffices)
+    pub fn restriction_sites() -> Self {
+        Self::new(restriction_motifs())
+    }
+
+    /// Adapter prefixes checked by FastQC's adapter content module
+    /// (12 bp each; poly-A/poly-G omitted to stay within the shift-and word)
+    pub fn adapters() -> Self {
+        Self::new(vec![
+            Motif::new("illumina_universal", b"AGATCGGAAGAG"),
+            Motif::new("illumina_small_rna_3p", b"TGGAATTCTCGG"),
+            Motif::new("illumina_small_rna_5p", b"GATCGTCGGACT"),
+            Motif::new("nextera", b"CTGTCTCTTATA"),
+            Motif::new("solid_small_rna", b"CGCCTTGGCCGT"),
+        ])
+    }
+
+    pub fn motifs(&self) -> &[Motif] {
+        &self.motifs
+    }
+
+    /// Whether `backend` can run on this machine with this motif set
+    pub fn supports(&self, backend: ScanBackend) -> bool {
+        match backend {
+            ScanBackend::Naive | ScanBackend::ShiftAnd => true,
+            ScanBackend::Teddy => cfg!(target_arch = "aarch64") && self.teddy.is_some(),
+        }
+    }
+
+    /// Best single-threaded backend for this machine and motif set
+    fn fastest_backend(&self) -> ScanBackend {
+        if self.supports(ScanBackend::Teddy) {
+            ScanBackend::Teddy
+        } else {
+            ScanBackend::ShiftAnd
+        }
+    }
+
+    /// Scan records with an explicit backend
+    pub fn execute_backend(&self, data: &[SequenceRecord], backend: ScanBackend) -> Result<MotifScanResult> {
+        if !self.supports(backend) {
+            anyhow::bail!("{} motif scan is not available here", backend.name());
+        }
+
+        let mut result = MotifScanResult::new(&self.motifs);
+        let mut read_hits = vec![0; self.motifs.len()];
+        for record in data {
+            self.count_read(&record.sequence, backend, &mut read_hits);
+            result.add_read(&read_hits);
+        }
+        Ok(result)
+    }
+
+    /// Hits per motif in one read (overwrites `hits`)
+    fn count_read(&self, seq: &[u8], backend: ScanBackend, hits: &mut [usize]) {
+        hits.fill(0);
+        match backend {
+            ScanBackend::Naive => self.count_naive(seq, hits),
+            ScanBackend::ShiftAnd => self.shift_and.count(seq, hits),
+            ScanBackend::Teddy => self.count_teddy(seq, hits),
+        }
+    }
+
+    fn count_naive(&self, seq: &[u8], hits: &mut [usize]) {
+        for (motif, count) in self.motifs.iter().zip(hits.iter_mut()) {
+            *count = seq
+                .windows(motif.pattern.len())
+                .filter(|window| window.eq_ignore_ascii_case(&motif.pattern))
+                .count();
+        }
+    }
+
+    /// Full compare of the motifs flagged in `buckets` at `start`
+    fn verify(&self, seq: &[u8], start: usize, mut buckets: u8, hits: &mut [usize]) {
+        while buckets != 0 {
+            let index = buckets.trailing_zeros() as usize;
+            buckets &= buckets - 1;
+
+            let pattern = &self.motifs[index].pattern;
+            if seq.get(start..start + pattern.len()).is_some_and(|window| window.eq_ignore_ascii_case(pattern)) {
+                hits[index] += 1;
+            }
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    fn count_teddy(&self, seq: &[u8], hits: &mut [usize]) {
+        use std::arch::aarch64::*;
+
+        let teddy = self.teddy.as_ref().expect("Teddy tables require at most 8 motifs");
+        let fingerprint = teddy.fingerprint;
+        let mut start = 0;
+
+        unsafe {
+            let case_mask = vdupq_n_u8(0xDF);
+            let low_nibble = vdupq_n_u8(0x0F);
+            let lo: Vec<uint8x16_t> = teddy.lo.iter().map(|table| vld1q_u8(table.as_ptr())).collect();
+            let hi: Vec<uint8x16_t> = teddy.hi.iter().map(|table| vld1q_u8(table.as_ptr())).collect();
+
+            // Candidate starts start..start+16 need fingerprint bytes up to start+15+fingerprint-1
+            while start + 15 + fingerprint <= seq.len() {
+                let mut buckets = vdupq_n_u8(0xFF);
+                for i in 0..fingerprint {
+                    let bytes = vandq_u8(vld1q_u8(seq.as_ptr().add(start + i)), case_mask);
+                    let lo_match = vqtbl1q_u8(lo[i], vandq_u8(bytes, low_nibble));
+                    let hi_match = vqtbl1q_u8(hi[i], vshrq_n_u8::<4>(bytes));
+                    buckets = vandq_u8(buckets, vandq_u8(lo_match, hi_match));
+                }
+
+                if vmaxvq_u8(buckets) != 0 {
+                    let mut lanes = [0u8; 16];
+                    vst1q_u8(lanes.as_mut_ptr(), buckets);
+                    for (lane, &bits) in lanes.iter().enumerate() {
+                        if bits != 0 {
+                            self.verify(seq, start + lane, bits, hits);
+                        }
+                    }
+                }
+                start += 16;
+            }
+        }
+
+        // Remaining start positions: verify every motif
+        let all = u8::MAX >> (8 - self.motifs.len());
+        for position in start..seq.len() {
+            self.verify(seq, position, all, hits);
+        }
+    }
+
+    #[cfg(not(target_arch = "aarch64"))]
+    fn count_teddy(&self, _seq: &[u8], _hits: &mut [usize]) {
+        panic!("NEON not available on this platform");
+    }
+}
+
+impl Default for MotifScan {
+    /// Telomere repeats and common restriction sites
+    fn default() -> Self {
+        let mut motifs = Self::telomere().motifs;
+        motifs.extend(restriction_motifs());
+        Self::new(motifs)
+    }
+}
+
+fn restriction_motifs() -> Vec<Motif> {
+    vec![
+        Motif::new("EcoRI", b"GAATTC"),
+        Motif::new("BamHI", b"GGATCC"),
+        Motif::new("HindIII", b"AAGCTT"),
+        Motif::new("NotI", b"GCGGCCGC"),
+    ]
+}
+
+// ============================================================================
+// Shift-and and Teddy tables
+// ============================================================================
+
+/// Multi-pattern shift-and: motif `m` occupies state bits `offset..offset+len`
+struct ShiftAnd {
+    /// Bits set where the motif byte equals the input byte (either case)
+    masks: [u64; 256],
+    /// First bit of every motif
+    starts: u64,
+    /// Last bit of every motif
+    ends: u64,
+    /// Motif owning each end bit
+    motif_of_bit: [u8; 64],
+}
+
+impl ShiftAnd {
+    fn new(motifs: &[Motif]) -> Self {
+        let mut masks = [0u64; 256];
+        let mut starts = 0;
+        let mut ends = 0;
+        let mut motif_of_bit = [0u8; 64];
+        let mut offset = 0;
+
+        for (index, motif) in motifs.iter().enumerate() {
+            for (j, &base) in motif.pattern.iter().enumerate() {
+                masks[base as usize] |= 1 << (offset + j);
+                masks[base.to_ascii_lowercase() as usize] |= 1 << (offset + j);
+            }
+            let end = offset + motif.pattern.len() - 1;
+            starts |= 1 << offset;
+            ends |= 1 << end;
+            motif_of_bit[end] = index as u8;
+            offset += motif.pattern.len();
+        }
+
+        Self {
+            masks,
+            starts,
+            ends,
+            motif_of_bit,
+        }
+    }
+
+    fn count(&self, seq: &[u8], hits: &mut [usize]) {
+        let mut state = 0u64;
+        for &base in seq {
+            state = ((state << 1) | self.starts) & self.masks[base as usize];
+
+            let mut matched = state & self.ends;
+            while matched != 0 {
+                hits[self.motif_of_bit[matched.trailing_zeros() as usize] as usize] += 1;
+                matched &= matched - 1;
+            }
+        }
+    }
+}
+
+/// Teddy nibble tables: bit `m` of `lo[i][n]` is set if byte `i` of motif `m`
+/// has low nibble `n` (likewise `hi` for the high nibble)
+struct Teddy {
+    fingerprint: usize,
+    lo: Vec<[u8; 16]>,
+    hi: Vec<[u8; 16]>,
+}
+
+impl Teddy {
+    fn new(motifs: &[Motif]) -> Self {
+        let fingerprint = motifs
+            .iter()
+            .map(|m| m.pattern.len())
+            .min()
+            .unwrap_or(1)
+            .min(MAX_FINGERPRINT);
+        let mut lo = vec![[0u8; 16]; fingerprint];
+        let mut hi = vec![[0u8; 16]; fingerprint];
+
+        for (index, motif) in motifs.iter().enumerate() {
+            for i in 0..fingerprint {
+                let base = motif.pattern[i];
+                lo[i][(base & 0x0F) as usize] |= 1 << index;
+                hi[i][(base >> 4) as usize] |= 1 << index;
+            }
+        }
+
+        Self { fingerprint, lo, hi }
+    }
+}
+
+// ============================================================================
+// Result
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MotifScanResult {
+    pub total_sequences: usize,
+    pub motifs: Vec<String>,
+    /// Occurrences per motif (overlapping hits included)
+    pub hits: Vec<usize>,
+    /// Reads with at least one hit, per motif
+    pub reads_with_hit: Vec<usize>,
+}
+
+impl MotifScanResult {
+    pub fn new(motifs: &[Motif]) -> Self {
+        Self {
+            total_sequences: 0,
+            motifs: motifs.iter().map(|m| m.name.clone()).collect(),
+            hits: vec![0; motifs.len()],
+            reads_with_hit: vec![0; motifs.len()],
+        }
+    }
+
+    fn add_read(&mut self, read_hits: &[usize]) {
+        self.total_sequences += 1;
+        for ((total, reads), &count) in self.hits.iter_mut().zip(&mut self.reads_with_hit).zip(read_hits) {
+            *total += count;
+            *reads += usize::from(count > 0);
+        }
+    }
+
+    pub fn add(&mut self, other: &Self) {
+        self.total_sequences += other.total_sequences;
+        for (total, &count) in self.hits.iter_mut().zip(&other.hits) {
+            *total += count;
+        }
+        for (reads, &count) in self.reads_with_hit.iter_mut().zip(&other.reads_with_hit) {
+            *reads += count;
+        }
+    }
+
+    /// Hits for the motif named `name`
+    pub fn hits_for(&self, name: &str) -> Option<usize> {
+        self.motifs.iter().position(|m| m == name).map(|i| self.hits[i])
+    }
+}
+
+impl PrimitiveOperation for MotifScan {
+    fn name(&self) -> &str {
+        "motif_scan"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Search
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "motifs": self
+                .motifs
+                .iter()
+                .map(|m| (m.name.clone(), String::from_utf8_lossy(&m.pattern).into_owned()))
+                .collect::<std::collections::BTreeMap<_, _>>(),
+        })
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.execute_backend(data, ScanBackend::Naive)?))
+    }
+
+    fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.execute_backend(data, self.fastest_backend())?))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let pool = crate::thread_pool::get(num_threads)?;
+        let backend = self.fastest_backend();
+
+        let result = pool.install(|| {
+            data.par_iter()
+                .fold(
+                    || (MotifScanResult::new(&self.motifs), vec![0; self.motifs.len()]),
+                    |(mut local, mut read_hits), record| {
+                        self.count_read(&record.sequence, backend, &mut read_hits);
+                        local.add_read(&read_hits);
+                        (local, read_hits)
+                    },
+                )
+                .map(|(local, _)| local)
+                .reduce(
+                    || MotifScanResult::new(&self.motifs),
+                    |mut a, b| {
+                        a.add(&b);
+                        a
+                    },
+                )
+        });
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let merge = |mut a: MotifScanResult, b: MotifScanResult| {
+            a.add(&b);
+            a
+        };
+        let result = crate::chunked::reduce_neon(self, data, num_threads, batch_size, merge)?
+            .unwrap_or_else(|| MotifScanResult::new(&self.motifs));
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        if config.num_threads > 1 {
+            self.execute_threaded(data, config)
+        } else if config.use_neon {
+            self.execute_neon(data)
+        } else {
+            self.execute_naive(data)
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(op: &MotifScan, data: &[SequenceRecord]) -> MotifScanResult {
+        op.execute_naive(data).unwrap().statistics::<MotifScanResult>().unwrap().clone()
+    }
+
+    fn test_records() -> Vec<SequenceRecord> {
+        let mut records: Vec<SequenceRecord> = (0..97usize)
+            .map(|i| {
+                let sequence: Vec<u8> = (0..40 + i * 3)
+                    .map(|j| b"ACGTTAGGGAATTCNacgtGGATCCcccTAA"[(i * 13 + j * 7 + j / 11) % 31])
+                    .collect();
+                SequenceRecord::fasta(format!("read_{}", i), sequence)
+            })
+            .collect();
+        records.push(SequenceRecord::fasta("telomere".to_string(), b"TTAGGG".repeat(20)));
+        records.push(SequenceRecord::fasta("short".to_string(), b"GAAT".to_vec()));
+        records.push(SequenceRecord::fasta("empty".to_string(), Vec::new()));
+        records
+    }
+
+    #[test]
+    fn test_motif_scan_counts() {
+        let op = MotifScan::telomere();
+        let data = vec![
+            SequenceRecord::fasta("a".to_string(), b"TTAGGGTTAGGGttagggNNCCCTAA".to_vec()),
+            SequenceRecord::fasta("b".to_string(), b"ACGTACGT".to_vec()),
+        ];
+        let result = scan(&op, &data);
+
+        assert_eq!(result.total_sequences, 2);
+        assert_eq!(result.hits_for("telomere_fwd"), Some(3));
+        assert_eq!(result.hits_for("telomere_rev"), Some(1));
+        assert_eq!(result.reads_with_hit, vec![1, 1]);
+
+        // Overlapping hits all count
+        let op = MotifScan::new(vec![Motif::new("aa", b"aa"), Motif::new("aca", b"ACA")]);
+        let data = vec![SequenceRecord::fasta("r".to_string(), b"AAAACACA".to_vec())];
+        assert_eq!(scan(&op, &data).hits, vec![3, 2]);
+    }
+
+    #[test]
+    fn test_motif_scan_backends_match() {
+        let data = test_records();
+
+        for op in [MotifScan::default(), MotifScan::telomere(), MotifScan::new(vec![Motif::new("g", b"G")])] {
+            let expected = scan(&op, &data);
+            assert!(expected.hits.iter().any(|&hits| hits > 0));
+
+            for backend in [ScanBackend::ShiftAnd, ScanBackend::Teddy] {
+                if op.supports(backend) {
+                    assert_eq!(op.execute_backend(&data, backend).unwrap(), expected, "{}", backend.name());
+                }
+            }
+            for output in [
+                op.execute_neon(&data).unwrap(),
+                op.execute_parallel(&data, 3).unwrap(),
+                op.execute_parallel_chunked(&data, 2).unwrap(),
+            ] {
+                assert_eq!(output.statistics::<MotifScanResult>().unwrap(), &expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_motif_scan_many_motifs() {
+        // More than 8 motifs: no Teddy buckets, shift-and still applies
+        let motifs: Vec<Motif> = ["AC", "CG", "GT", "TA", "AG", "GA", "CT", "TC", "AAT"]
+            .iter()
+            .map(|p| Motif::new(p, p.as_bytes()))
+            .collect();
+        let op = MotifScan::new(motifs);
+        let data = test_records();
+
+        assert!(!op.supports(ScanBackend::Teddy));
+        assert_eq!(op.execute_backend(&data, ScanBackend::ShiftAnd).unwrap(), scan(&op, &data));
+    }
+}
@@ -73,27 +73,19 @@ impl PrimitiveOperation for NContent {
         }
 
         result.finalize();
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
-        #[cfg(target_arch = "aarch64")]
-        {
-            let mut result = NContentResult::new();
-
-            for record in data {
-                let local_result = count_n_content_neon(&record.sequence);
-                result.add(&local_result);
-            }
+        let mut result = NContentResult::new();
 
-            result.finalize();
-            Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        for record in data {
+            let local_result = count_n_content_neon(&record.sequence);
+            result.add(&local_result);
         }
 
-        #[cfg(not(target_arch = "aarch64"))]
-        {
-            self.execute_naive(data)
-        }
+        result.finalize();
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_parallel(
@@ -101,23 +93,11 @@ impl PrimitiveOperation for NContent {
         data: &[SequenceRecord],
         num_threads: usize,
     ) -> Result<OperationOutput> {
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()?;
+        let pool = crate::thread_pool::get(num_threads)?;
 
         let mut result = pool.install(|| {
             data.par_iter()
-                .map(|record| {
-                    #[cfg(target_arch = "aarch64")]
-                    {
-                        count_n_content_neon(&record.sequence)
-                    }
-
-                    #[cfg(not(target_arch = "aarch64"))]
-                    {
-                        count_n_content_naive(&record.sequence)
-                    }
-                })
+                .map(|record| count_n_content_neon(&record.sequence))
                 .reduce(
                     || NContentResult::new(),
                     |mut a, b| {
@@ -128,7 +108,24 @@ impl PrimitiveOperation for NContent {
         });
 
         result.finalize();
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let merge = |mut a: NContentResult, b: NContentResult| {
+            a.add(&b);
+            a
+        };
+        let mut result = crate::chunked::reduce_neon(self, data, num_threads, batch_size, merge)?
+            .unwrap_or_else(NContentResult::new);
+        result.finalize();
+
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_with_config(
@@ -137,7 +134,7 @@ impl PrimitiveOperation for NContent {
         config: &HardwareConfig,
     ) -> Result<OperationOutput> {
         if config.num_threads > 1 {
-            self.execute_parallel(data, config.num_threads)
+            self.execute_threaded(data, config)
         } else if config.use_neon {
             self.execute_neon(data)
         } else {
@@ -174,6 +171,11 @@ fn count_n_content_naive(seq: &[u8]) -> NContentResult {
     result
 }
 
+/// 16-byte chunks a u8 accumulator lane can count before it can overflow
+/// (one increment per chunk; ~4 kb of sequence per block)
+#[cfg(target_arch = "aarch64")]
+const MAX_CHUNKS_PER_FLUSH: usize = 255;
+
 // NEON SIMD implementation
 #[cfg(target_arch = "aarch64")]
 fn count_n_content_neon(seq: &[u8]) -> NContentResult {
@@ -183,10 +185,6 @@ fn count_n_content_neon(seq: &[u8]) -> NContentResult {
     result.total_bases = seq.len();
 
     unsafe {
-        // NEON accumulators
-        let mut vec_count_n = vdupq_n_u8(0);
-        let mut vec_count_acgt = vdupq_n_u8(0);
-
         // Comparison values
         let cmp_n_upper = vdupq_n_u8(b'N');
         let cmp_n_lower = vdupq_n_u8(b'n');
@@ -205,26 +203,34 @@ fn count_n_content_neon(seq: &[u8]) -> NContentResult {
         let chunks = seq.chunks_exact(16);
         let remainder = chunks.remainder();
 
-        for chunk in chunks {
-            let data = vld1q_u8(chunk.as_ptr());
+        // u8 lanes overflow after 255 increments, so flush to usize every block
+        let vector_bytes = &seq[..seq.len() - remainder.len()];
+        for block in vector_bytes.chunks(16 * MAX_CHUNKS_PER_FLUSH) {
+            // NEON accumulators
+            let mut vec_count_n = vdupq_n_u8(0);
+            let mut vec_count_acgt = vdupq_n_u8(0);
 
-            // Check for N
-            let mask_n = vorrq_u8(vceqq_u8(data, cmp_n_upper), vceqq_u8(data, cmp_n_lower));
-            vec_count_n = vaddq_u8(vec_count_n, vandq_u8(mask_n, ones));
+            for chunk in block.chunks_exact(16) {
+                let data = vld1q_u8(chunk.as_ptr());
 
-            // Check for ACGT
-            let mask_a = vorrq_u8(vceqq_u8(data, cmp_a_upper), vceqq_u8(data, cmp_a_lower));
-            let mask_c = vorrq_u8(vceqq_u8(data, cmp_c_upper), vceqq_u8(data, cmp_c_lower));
-            let mask_g = vorrq_u8(vceqq_u8(data, cmp_g_upper), vceqq_u8(data, cmp_g_lower));
-            let mask_t = vorrq_u8(vceqq_u8(data, cmp_t_upper), vceqq_u8(data, cmp_t_lower));
+                // Check for N
+                let mask_n = vorrq_u8(vceqq_u8(data, cmp_n_upper), vceqq_u8(data, cmp_n_lower));
+                vec_count_n = vaddq_u8(vec_count_n, vandq_u8(mask_n, ones));
 
-            let mask_acgt = vorrq_u8(vorrq_u8(mask_a, mask_c), vorrq_u8(mask_g, mask_t));
-            vec_count_acgt = vaddq_u8(vec_count_acgt, vandq_u8(mask_acgt, ones));
-        }
+                // Check for ACGT
+                let mask_a = vorrq_u8(vceqq_u8(data, cmp_a_upper), vceqq_u8(data, cmp_a_lower));
+                let mask_c = vorrq_u8(vceqq_u8(data, cmp_c_upper), vceqq_u8(data, cmp_c_lower));
+                let mask_g = vorrq_u8(vceqq_u8(data, cmp_g_upper), vceqq_u8(data, cmp_g_lower));
+                let mask_t = vorrq_u8(vceqq_u8(data, cmp_t_upper), vceqq_u8(data, cmp_t_lower));
 
-        // Horizontal sum
-        result.count_n = horizontal_sum_u8(vec_count_n);
-        result.count_acgt = horizontal_sum_u8(vec_count_acgt);
+                let mask_acgt = vorrq_u8(vorrq_u8(mask_a, mask_c), vorrq_u8(mask_g, mask_t));
+                vec_count_acgt = vaddq_u8(vec_count_acgt, vandq_u8(mask_acgt, ones));
+            }
+
+            // Horizontal sum
+            result.count_n += horizontal_sum_u8(vec_count_n);
+            result.count_acgt += horizontal_sum_u8(vec_count_acgt);
+        }
 
         // Process remainder with scalar (includes ambiguous counting)
         for &base in remainder {
@@ -255,9 +261,27 @@ fn horizontal_sum_u8(vec: std::arch::aarch64::uint8x16_t) -> usize {
     }
 }
 
+// SSE2/AVX2 (x86_64) or scalar implementation for non-NEON targets
+//
+// Unlike the NEON kernel, ambiguity codes are counted across the whole
+// sequence, so the result matches the naive implementation exactly
 #[cfg(not(target_arch = "aarch64"))]
-fn count_n_content_neon(_seq: &[u8]) -> NContentResult {
-    panic!("NEON not available on this platform");
+fn count_n_content_neon(seq: &[u8]) -> NContentResult {
+    let counts = crate::simd::count_letters(
+        seq,
+        [
+            b'N', b'A', b'C', b'G', b'T', b'R', b'Y', b'S', b'W', b'K', b'M', b'B', b'D', b'H',
+            b'V',
+        ],
+    );
+
+    let mut result = NContentResult::new();
+    result.total_bases = seq.len();
+    result.count_n = counts[0];
+    result.count_acgt = counts[1..5].iter().sum();
+    result.count_ambiguous = counts[5..].iter().sum();
+    result.count_other = seq.len() - result.count_n - result.count_acgt - result.count_ambiguous;
+    result
 }
 
 #[cfg(test)]
@@ -280,8 +304,7 @@ mod tests {
 
         let result = op.execute_naive(&records).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let n_content: NContentResult = serde_json::from_value(value).unwrap();
+        if let Some(n_content) = result.statistics::<NContentResult>() {
 
             // Expected: 4+0+8+2 = 14 N bases
             //           4+8+0+4 = 16 ACGT bases
@@ -298,15 +321,13 @@ mod tests {
     }
 
     #[test]
-    #[cfg(target_arch = "aarch64")]
     fn test_n_content_neon() {
         let records = create_test_records();
         let op = NContent;
 
         let result = op.execute_neon(&records).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let n_content: NContentResult = serde_json::from_value(value).unwrap();
+        if let Some(n_content) = result.statistics::<NContentResult>() {
 
             assert_eq!(n_content.count_n, 14);
             assert_eq!(n_content.count_acgt, 16);
@@ -319,7 +340,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(target_arch = "aarch64")]
     fn test_n_content_neon_matches_naive() {
         let records = create_test_records();
         let op = NContent;
@@ -327,11 +347,11 @@ mod tests {
         let naive_result = op.execute_naive(&records).unwrap();
         let neon_result = op.execute_neon(&records).unwrap();
 
-        if let (OperationOutput::Statistics(naive_val), OperationOutput::Statistics(neon_val)) =
+        if let (OperationOutput::Typed(naive_typed), OperationOutput::Typed(neon_typed)) =
             (naive_result, neon_result)
         {
-            let naive_nc: NContentResult = serde_json::from_value(naive_val).unwrap();
-            let neon_nc: NContentResult = serde_json::from_value(neon_val).unwrap();
+            let naive_nc: &NContentResult = naive_typed.downcast_ref().unwrap();
+            let neon_nc: &NContentResult = neon_typed.downcast_ref().unwrap();
 
             assert_eq!(naive_nc.count_n, neon_nc.count_n);
             assert_eq!(naive_nc.count_acgt, neon_nc.count_acgt);
@@ -349,8 +369,7 @@ mod tests {
 
         let result = op.execute_parallel(&records, 2).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let n_content: NContentResult = serde_json::from_value(value).unwrap();
+        if let Some(n_content) = result.statistics::<NContentResult>() {
 
             assert_eq!(n_content.count_n, 14);
             assert_eq!(n_content.count_acgt, 16);
@@ -360,4 +379,18 @@ mod tests {
             panic!("Expected Statistics output");
         }
     }
+
+    #[test]
+    fn test_n_content_neon_long_reads() {
+        // u8 lanes overflow after 255 chunks (~4 kb of matching bases)
+        let op = NContent;
+        let data = vec![
+            SequenceRecord::fasta("gap".to_string(), vec![b'N'; 100_000]),
+            SequenceRecord::fasta("long_read".to_string(), b"ACGTN".repeat(20_001)),
+        ];
+
+        let result_naive = op.execute_naive(&data).unwrap();
+        let result_neon = op.execute_neon(&data).unwrap();
+        assert_eq!(result_naive, result_neon);
+    }
 }
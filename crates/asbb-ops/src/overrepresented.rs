@@ -0,0 +1,267 @@
+//! Overrepresented Sequences Operation
+//!
+//! Finds read sequences that make up more than a given fraction of all reads
+//! (FastQC's "overrepresented sequences" panel: adapter dimers, primers,
+//! highly expressed transcripts). As in FastQC, reads longer than
+//! `prefix_len` are truncated before counting so that a single late
+//! sequencing error does not split an otherwise identical group.
+//!
+//! # Operation Characteristics
+//! - **Category**: Aggregation
+//! - **Complexity**: 0.50 (hashing every read prefix + table inserts)
+//! - **Output**: Reported sequences with counts (small), after a full-size
+//!   hash table (one entry per distinct prefix)
+//! - **NEON benefit**: None expected (hash table bound)
+//!
+//! # Implementation Notes
+//! - Parallel: per-split hash tables merged into the larger one; the merge
+//!   is proportional to the number of distinct prefixes, which on diverse
+//!   libraries approaches the number of reads
+//! - Reported in descending count order (ties by sequence), so every backend
+//!   produces identical output
+
+use crate::{HardwareConfig, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+type PrefixCounts<'a> = HashMap<&'a [u8], usize>;
+
+/// Overrepresented sequence detection
+pub struct OverrepresentedSequences {
+    /// Bases of each read that are compared
+    pub prefix_len: usize,
+    /// Minimum fraction of all reads for a sequence to be reported
+    pub min_fraction: f64,
+    /// Maximum sequences reported
+    pub max_reported: usize,
+}
+
+/// One reported sequence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OverrepresentedSequence {
+    pub sequence: String,
+    pub count: usize,
+    /// Fraction of all reads
+    pub fraction: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OverrepresentedResult {
+    pub total_sequences: usize,
+    /// Distinct read prefixes
+    pub distinct_sequences: usize,
+    pub sequences: Vec<OverrepresentedSequence>,
+}
+
+impl OverrepresentedSequences {
+    pub fn new(prefix_len: usize, min_fraction: f64) -> Self {
+        assert!(prefix_len > 0, "Prefix length must be at least 1");
+        assert!(
+            (0.0..=1.0).contains(&min_fraction),
+            "Minimum fraction must be within 0..=1"
+        );
+        Self {
+            prefix_len,
+            min_fraction,
+            max_reported: 20,
+        }
+    }
+
+    pub fn with_max_reported(mut self, max_reported: usize) -> Self {
+        self.max_reported = max_reported;
+        self
+    }
+
+    fn prefix<'a>(&self, record: &'a SequenceRecord) -> &'a [u8] {
+        &record.sequence[..record.sequence.len().min(self.prefix_len)]
+    }
+
+    fn count<'a>(&self, counts: &mut PrefixCounts<'a>, record: &'a SequenceRecord) {
+        *counts.entry(self.prefix(record)).or_insert(0) += 1;
+    }
+
+    /// Report sequences over the threshold from complete counts
+    fn report(&self, counts: &PrefixCounts, total_sequences: usize) -> OverrepresentedResult {
+        let min_count = (self.min_fraction * total_sequences as f64).ceil().max(1.0) as usize;
+        let mut reported: Vec<(&[u8], usize)> = counts
+            .iter()
+            .filter(|(_, &count)| count >= min_count)
+            .map(|(&sequence, &count)| (sequence, count))
+            .collect();
+        reported.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        reported.truncate(self.max_reported);
+
+        OverrepresentedResult {
+            total_sequences,
+            distinct_sequences: counts.len(),
+            sequences: reported
+                .into_iter()
+                .map(|(sequence, count)| OverrepresentedSequence {
+                    sequence: String::from_utf8_lossy(sequence).into_owned(),
+                    count,
+                    fraction: count as f64 / total_sequences as f64,
+                })
+                .collect(),
+        }
+    }
+
+    fn merge<'a>(large: PrefixCounts<'a>, small: PrefixCounts<'a>) -> PrefixCounts<'a> {
+        let (mut large, small) = if large.len() >= small.len() { (large, small) } else { (small, large) };
+        for (sequence, count) in small {
+            *large.entry(sequence).or_insert(0) += count;
+        }
+        large
+    }
+}
+
+impl Default for OverrepresentedSequences {
+    /// FastQC defaults: 50 bp prefixes, reported above 0.1% of reads
+    fn default() -> Self {
+        Self::new(50, 0.001)
+    }
+}
+
+impl PrimitiveOperation for OverrepresentedSequences {
+    fn name(&self) -> &str {
+        "overrepresented_sequences"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Aggregation
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "prefix_len": self.prefix_len,
+            "min_fraction": self.min_fraction,
+            "max_reported": self.max_reported,
+        })
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        let mut counts = PrefixCounts::new();
+        for record in data {
+            self.count(&mut counts, record);
+        }
+        Ok(OperationOutput::typed(self.report(&counts, data.len())))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let pool = crate::thread_pool::get(num_threads)?;
+
+        let counts = pool.install(|| {
+            data.par_iter()
+                .fold(PrefixCounts::new, |mut local, record| {
+                    self.count(&mut local, record);
+                    local
+                })
+                .reduce(PrefixCounts::new, Self::merge)
+        });
+
+        Ok(OperationOutput::typed(self.report(&counts, data.len())))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let counts = crate::chunked::map_reduce(
+            data,
+            num_threads,
+            batch_size,
+            |chunk| {
+                let mut local = PrefixCounts::new();
+                for record in chunk {
+                    self.count(&mut local, record);
+                }
+                Ok(local)
+            },
+            Self::merge,
+        )?
+        .unwrap_or_default();
+
+        Ok(OperationOutput::typed(self.report(&counts, data.len())))
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        if config.num_threads > 1 {
+            self.execute_threaded(data, config)
+        } else {
+            self.execute_naive(data)
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(output: OperationOutput) -> OverrepresentedResult {
+        output.statistics::<OverrepresentedResult>().unwrap().clone()
+    }
+
+    /// 1000 distinct reads plus an adapter dimer (3%) and a primer (0.5%)
+    fn test_reads() -> Vec<SequenceRecord> {
+        let mut reads: Vec<SequenceRecord> = (0..1_000)
+            .map(|i: usize| {
+                let sequence = (0..60).map(|j| b"ACGT"[(i.wrapping_mul(2_654_435_761) >> (j % 28)) & 3]).collect();
+                SequenceRecord::fasta(format!("read_{}", i), sequence)
+            })
+            .collect();
+        for i in 0..32 {
+            // Same 50 bp prefix, different tails
+            let mut dimer = b"AGATCGGAAGAGCACACGTCTGAACTCCAGTCACAGATCGGAAGAGCGTC".to_vec();
+            dimer.extend_from_slice(&b"ACGTACGTAC"[..i % 10]);
+            reads.push(SequenceRecord::fasta(format!("dimer_{}", i), dimer));
+        }
+        for i in 0..5 {
+            reads.push(SequenceRecord::fasta(format!("primer_{}", i), b"GTGCCAGCMGCCGCGGTAA".to_vec()));
+        }
+        reads
+    }
+
+    #[test]
+    fn test_overrepresented_reports_above_threshold() {
+        let reads = test_reads();
+        let found = result(OverrepresentedSequences::default().execute_naive(&reads).unwrap());
+
+        assert_eq!(found.total_sequences, 1_037);
+        let reported: Vec<(&str, usize)> = found.sequences.iter().map(|s| (s.sequence.as_str(), s.count)).collect();
+        assert_eq!(reported[0], ("AGATCGGAAGAGCACACGTCTGAACTCCAGTCACAGATCGGAAGAGCGTC", 32));
+        assert_eq!(reported[1], ("GTGCCAGCMGCCGCGGTAA", 5));
+        assert!((found.sequences[0].fraction - 32.0 / 1_037.0).abs() < 1e-12);
+
+        // 1% threshold keeps only the dimer
+        let strict = result(OverrepresentedSequences::new(50, 0.01).execute_naive(&reads).unwrap());
+        assert_eq!(strict.sequences.len(), 1);
+    }
+
+    #[test]
+    fn test_overrepresented_backends_match() {
+        let reads = test_reads();
+        let op = OverrepresentedSequences::new(20, 0.002).with_max_reported(5);
+        let expected = result(op.execute_naive(&reads).unwrap());
+        assert!(expected.sequences.len() <= 5);
+
+        for threads in [1, 2, 4] {
+            assert_eq!(result(op.execute_parallel(&reads, threads).unwrap()), expected);
+            assert_eq!(result(op.execute_parallel_chunked(&reads, threads).unwrap()), expected);
+        }
+    }
+}
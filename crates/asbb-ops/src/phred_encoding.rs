@@ -0,0 +1,540 @@
+//! Phred Encoding Detection and Conversion Operation
+//!
+//! Detects how a FASTQ file's quality scores are encoded (Phred+33, Phred+64
+//! or legacy Solexa+64) from the range of quality bytes, and rewrites the
+//! qualities in a target encoding - the usual first step before feeding old
+//! Illumina data to current tools.
+//!
+//! # Operation Characteristics
+//! - **Category**: IO (format conversion)
+//! - **Complexity**: 0.30 (min/max scan + one byte mapping per quality)
+//! - **Output**: Records with converted qualities (sequences unchanged)
+//! - **NEON benefit**: High (min/max and byte mapping are 16 lanes wide)
+//!
+//! # Implementation Notes
+//! - Detection: Phred+33 if any byte is below ';' (59); Solexa+64 if the
+//!   lowest byte is ';'-'?' and the highest above 'J' (74); Phred+64 if
+//!   everything is '@' or above and something is above 'J'. Bytes all in
+//!   '@'-'J' fit both Phred+33 (Q31-41) and Phred+64 (Q0-10): Phred+33 is
+//!   reported, flagged as ambiguous
+//! - Scores are clamped to the source range, converted, then clamped to the
+//!   target range (Phred+64 and Solexa+64 stop at Q62, '~')
+//! - NEON: Phred+33 ↔ Phred+64 is a clamp plus saturating add/sub of 31;
+//!   conversions involving Solexa's log-odds scale use a 128-entry lookup
+//!   table (`vqtbl4q_u8` + `vqtbx4q_u8`)
+
+use crate::{HardwareConfig, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Quality score encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PhredEncoding {
+    /// Sanger / Illumina 1.8+ ('!' = Q0)
+    Phred33,
+    /// Illumina 1.3-1.7 ('@' = Q0)
+    Phred64,
+    /// Solexa / Illumina 1.0 (';' = Q-5, log-odds scores)
+    Solexa64,
+}
+
+impl PhredEncoding {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PhredEncoding::Phred33 => "phred33",
+            PhredEncoding::Phred64 => "phred64",
+            PhredEncoding::Solexa64 => "solexa64",
+        }
+    }
+
+    pub fn offset(&self) -> i32 {
+        match self {
+            PhredEncoding::Phred33 => 33,
+            PhredEncoding::Phred64 | PhredEncoding::Solexa64 => 64,
+        }
+    }
+
+    /// Lowest and highest representable score ('~' is the last printable byte)
+    pub fn score_range(&self) -> (i32, i32) {
+        match self {
+            PhredEncoding::Phred33 => (0, 93),
+            PhredEncoding::Phred64 => (0, 62),
+            PhredEncoding::Solexa64 => (-5, 62),
+        }
+    }
+
+    fn is_solexa(&self) -> bool {
+        *self == PhredEncoding::Solexa64
+    }
+
+    /// Encoding implied by the lowest and highest quality bytes
+    /// (returns the encoding and whether the call is ambiguous)
+    pub fn from_byte_range(min_byte: u8, max_byte: u8) -> (Self, bool) {
+        match (min_byte, max_byte) {
+            (0..=58, _) => (PhredEncoding::Phred33, false),
+            (59..=63, 75..) => (PhredEncoding::Solexa64, false),
+            (59..=63, _) => (PhredEncoding::Phred33, false),
+            (_, 75..) => (PhredEncoding::Phred64, false),
+            _ => (PhredEncoding::Phred33, true),
+        }
+    }
+}
+
+/// Detected encoding with the evidence it is based on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncodingGuess {
+    pub encoding: PhredEncoding,
+    /// Bytes fit more than one encoding
+    pub ambiguous: bool,
+    pub min_byte: u8,
+    pub max_byte: u8,
+    /// Quality bytes examined
+    pub quality_bases: usize,
+}
+
+/// Quality byte mapping from one encoding to another
+pub struct ConversionTable {
+    pub from: PhredEncoding,
+    pub to: PhredEncoding,
+    table: [u8; 256],
+}
+
+impl ConversionTable {
+    pub fn new(from: PhredEncoding, to: PhredEncoding) -> Self {
+        let (from_min, from_max) = from.score_range();
+        let (to_min, to_max) = to.score_range();
+
+        let mut table = [0u8; 256];
+        for (byte, out) in table.iter_mut().enumerate() {
+            let score = (byte as i32 - from.offset()).clamp(from_min, from_max);
+            let score = match (from.is_solexa(), to.is_solexa()) {
+                (true, false) => solexa_to_phred(score),
+                (false, true) => phred_to_solexa(score),
+                _ => score,
+            };
+            *out = (score.clamp(to_min, to_max) + to.offset()) as u8;
+        }
+
+        Self { from, to, table }
+    }
+
+    /// Whether the mapping is a clamp plus constant shift
+    fn is_linear(&self) -> bool {
+        !self.from.is_solexa() && !self.to.is_solexa()
+    }
+
+    /// Convert qualities in place
+    pub fn apply(&self, quality: &mut [u8], simd: bool) {
+        #[cfg(target_arch = "aarch64")]
+        if simd {
+            if self.is_linear() {
+                self.apply_linear_neon(quality);
+            } else {
+                self.apply_table_neon(quality);
+            }
+            return;
+        }
+
+        for byte in quality {
+            *byte = self.table[*byte as usize];
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    fn apply_linear_neon(&self, quality: &mut [u8]) {
+        use std::arch::aarch64::*;
+
+        let from_offset = self.from.offset() as u8;
+        let to_offset = self.to.offset() as u8;
+        let top = self.from.score_range().1.min(self.to.score_range().1) as u8;
+        let vector_len = quality.len() / 16 * 16;
+
+        unsafe {
+            let low = vdupq_n_u8(from_offset);
+            let high = vdupq_n_u8(from_offset + top);
+            let up = vdupq_n_u8(to_offset.saturating_sub(from_offset));
+            let down = vdupq_n_u8(from_offset.saturating_sub(to_offset));
+
+            for chunk in quality[..vector_len].chunks_exact_mut(16) {
+                let bytes = vminq_u8(vmaxq_u8(vld1q_u8(chunk.as_ptr()), low), high);
+                // One of up/down is zero
+                let shifted = vqsubq_u8(vqaddq_u8(bytes, up), down);
+                vst1q_u8(chunk.as_mut_ptr(), shifted);
+            }
+        }
+
+        for byte in &mut quality[vector_len..] {
+            *byte = self.table[*byte as usize];
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    fn apply_table_neon(&self, quality: &mut [u8]) {
+        use std::arch::aarch64::*;
+
+        // Bytes 32-159 cover every valid score of every encoding; bytes
+        // outside map like their nearest end (both clamp)
+        let window = &self.table[32..160];
+        let vector_len = quality.len() / 16 * 16;
+
+        unsafe {
+            let low_table = vld1q_u8_x4(window.as_ptr());
+            let high_table = vld1q_u8_x4(window.as_ptr().add(64));
+            let base = vdupq_n_u8(32);
+            let last = vdupq_n_u8(127);
+            let sixty_four = vdupq_n_u8(64);
+
+            for chunk in quality[..vector_len].chunks_exact_mut(16) {
+                let index = vminq_u8(vqsubq_u8(vld1q_u8(chunk.as_ptr()), base), last);
+                let low = vqtbl4q_u8(low_table, index);
+                // Indices 64-127 (out of range for the first lookup) come from the second
+                let mapped = vqtbx4q_u8(low, high_table, vsubq_u8(index, sixty_four));
+                vst1q_u8(chunk.as_mut_ptr(), mapped);
+            }
+        }
+
+        for byte in &mut quality[vector_len..] {
+            *byte = self.table[*byte as usize];
+        }
+    }
+}
+
+/// Phred score for a Solexa score (rounded)
+fn solexa_to_phred(score: i32) -> i32 {
+    (10.0 * (10f64.powf(score as f64 / 10.0) + 1.0).log10()).round() as i32
+}
+
+/// Solexa score for a Phred score (rounded; Q0 has no Solexa equivalent
+/// and clamps to the Solexa minimum)
+fn phred_to_solexa(score: i32) -> i32 {
+    let odds = 10f64.powf(score as f64 / 10.0) - 1.0;
+    if odds <= 0.0 {
+        i32::MIN
+    } else {
+        (10.0 * odds.log10()).round() as i32
+    }
+}
+
+/// Lowest and highest quality byte (`None` if empty)
+fn byte_range(quality: &[u8], simd: bool) -> Option<(u8, u8)> {
+    if quality.is_empty() {
+        return None;
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    if simd && quality.len() >= 16 {
+        use std::arch::aarch64::*;
+
+        let vector_len = quality.len() / 16 * 16;
+        let (mut min, mut max) = unsafe {
+            let mut min = vdupq_n_u8(u8::MAX);
+            let mut max = vdupq_n_u8(0);
+            for chunk in quality[..vector_len].chunks_exact(16) {
+                let bytes = vld1q_u8(chunk.as_ptr());
+                min = vminq_u8(min, bytes);
+                max = vmaxq_u8(max, bytes);
+            }
+            (vminvq_u8(min), vmaxvq_u8(max))
+        };
+        for &byte in &quality[vector_len..] {
+            min = min.min(byte);
+            max = max.max(byte);
+        }
+        return Some((min, max));
+    }
+
+    let min = *quality.iter().min()?;
+    let max = *quality.iter().max()?;
+    Some((min, max))
+}
+
+/// Running byte range over many reads
+#[derive(Debug, Clone, Copy)]
+struct RangeAccumulator {
+    min: u8,
+    max: u8,
+    bases: usize,
+}
+
+impl RangeAccumulator {
+    fn new() -> Self {
+        Self {
+            min: u8::MAX,
+            max: 0,
+            bases: 0,
+        }
+    }
+
+    fn add_record(mut self, record: &SequenceRecord, simd: bool) -> Self {
+        if let Some((min, max)) = record.quality.as_deref().and_then(|q| byte_range(q, simd)) {
+            self.min = self.min.min(min);
+            self.max = self.max.max(max);
+            self.bases += record.quality.as_ref().map_or(0, Vec::len);
+        }
+        self
+    }
+
+    fn merge(self, other: Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+            bases: self.bases + other.bases,
+        }
+    }
+
+    fn guess(self) -> Option<EncodingGuess> {
+        if self.bases == 0 {
+            return None;
+        }
+        let (encoding, ambiguous) = PhredEncoding::from_byte_range(self.min, self.max);
+        Some(EncodingGuess {
+            encoding,
+            ambiguous,
+            min_byte: self.min,
+            max_byte: self.max,
+            quality_bases: self.bases,
+        })
+    }
+}
+
+/// Detect the quality encoding of `data` (`None` without quality scores)
+pub fn detect(data: &[SequenceRecord]) -> Option<EncodingGuess> {
+    data.iter()
+        .fold(RangeAccumulator::new(), |acc, record| acc.add_record(record, true))
+        .guess()
+}
+
+/// Phred encoding conversion operation
+pub struct PhredConversion {
+    /// Source encoding (`None`: detect from the data)
+    pub source: Option<PhredEncoding>,
+    pub target: PhredEncoding,
+}
+
+impl PhredConversion {
+    /// Convert to `target`, detecting the source encoding
+    pub fn new(target: PhredEncoding) -> Self {
+        Self { source: None, target }
+    }
+
+    pub fn with_source(mut self, source: PhredEncoding) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Conversion table for `data` (detected data without qualities is
+    /// treated as Phred+33; there is nothing to convert)
+    fn table(&self, data: &[SequenceRecord], pool: Option<&rayon::ThreadPool>, simd: bool) -> ConversionTable {
+        let source = self.source.unwrap_or_else(|| {
+            let range = match pool {
+                Some(pool) => pool.install(|| {
+                    data.par_iter()
+                        .fold(RangeAccumulator::new, |acc, record| acc.add_record(record, simd))
+                        .reduce(RangeAccumulator::new, RangeAccumulator::merge)
+                }),
+                None => data
+                    .iter()
+                    .fold(RangeAccumulator::new(), |acc, record| acc.add_record(record, simd)),
+            };
+            range.guess().map_or(PhredEncoding::Phred33, |guess| guess.encoding)
+        });
+        ConversionTable::new(source, self.target)
+    }
+
+    fn convert(table: &ConversionTable, record: &SequenceRecord, simd: bool) -> SequenceRecord {
+        let mut converted = record.clone();
+        if let Some(quality) = converted.quality.as_mut() {
+            table.apply(quality, simd);
+        }
+        converted
+    }
+
+    fn convert_all(&self, data: &[SequenceRecord], simd: bool) -> Vec<SequenceRecord> {
+        let table = self.table(data, None, simd);
+        data.iter().map(|record| Self::convert(&table, record, simd)).collect()
+    }
+}
+
+impl Default for PhredConversion {
+    /// Detect and convert to Phred+33
+    fn default() -> Self {
+        Self::new(PhredEncoding::Phred33)
+    }
+}
+
+impl PrimitiveOperation for PhredConversion {
+    fn name(&self) -> &str {
+        "phred_conversion"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::IO
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "source": self.source.map_or("detect", |source| source.name()),
+            "target": self.target.name(),
+        })
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::Records(self.convert_all(data, false)))
+    }
+
+    fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::Records(self.convert_all(data, true)))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let pool = crate::thread_pool::get(num_threads)?;
+        let table = self.table(data, Some(&pool), true);
+
+        let records = pool.install(|| {
+            data.par_iter()
+                .map(|record| Self::convert(&table, record, true))
+                .collect()
+        });
+
+        Ok(OperationOutput::Records(records))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        // Detection spans the whole dataset, so chunks share one table
+        let pool = crate::thread_pool::get(num_threads)?;
+        let table = self.table(data, Some(&pool), true);
+
+        let records = crate::chunked::map_reduce(
+            data,
+            num_threads,
+            batch_size,
+            |chunk| Ok(chunk.iter().map(|record| Self::convert(&table, record, true)).collect::<Vec<_>>()),
+            |mut a, b| {
+                a.extend(b);
+                a
+            },
+        )?
+        .unwrap_or_default();
+
+        Ok(OperationOutput::Records(records))
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        if config.num_threads > 1 {
+            self.execute_threaded(data, config)
+        } else if config.use_neon {
+            self.execute_neon(data)
+        } else {
+            self.execute_naive(data)
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(quality: &[u8]) -> Vec<SequenceRecord> {
+        (0..7)
+            .map(|i| {
+                let quality: Vec<u8> = quality.iter().cycle().skip(i * 3).take(20 + i * 9).copied().collect();
+                SequenceRecord::fastq(format!("read_{}", i), vec![b'A'; quality.len()], quality)
+            })
+            .collect()
+    }
+
+    fn qualities(output: OperationOutput) -> Vec<Vec<u8>> {
+        match output {
+            OperationOutput::Records(records) => records.into_iter().map(|r| r.quality.unwrap()).collect(),
+            _ => panic!("Expected record output"),
+        }
+    }
+
+    #[test]
+    fn test_detect_encoding() {
+        let guess = detect(&records(b"#+5?FFJJ")).unwrap();
+        assert_eq!((guess.encoding, guess.ambiguous), (PhredEncoding::Phred33, false));
+        assert_eq!((guess.min_byte, guess.max_byte), (b'#', b'J'));
+
+        assert_eq!(detect(&records(b"BBfhhIh")).unwrap().encoding, PhredEncoding::Phred64);
+        assert_eq!(detect(&records(b";<=@Th")).unwrap().encoding, PhredEncoding::Solexa64);
+
+        let guess = detect(&records(b"@AFJ")).unwrap();
+        assert_eq!((guess.encoding, guess.ambiguous), (PhredEncoding::Phred33, true));
+
+        let fasta = vec![SequenceRecord::fasta("a".to_string(), b"ACGT".to_vec())];
+        assert!(detect(&fasta).is_none());
+    }
+
+    #[test]
+    fn test_conversion_tables() {
+        let to_33 = ConversionTable::new(PhredEncoding::Phred64, PhredEncoding::Phred33);
+        assert_eq!(to_33.table[b'@' as usize], b'!');
+        assert_eq!(to_33.table[b'h' as usize], b'I');
+        assert_eq!(to_33.table[b'!' as usize], b'!'); // below range clamps to Q0
+
+        let to_64 = ConversionTable::new(PhredEncoding::Phred33, PhredEncoding::Phred64);
+        assert_eq!(to_64.table[b'I' as usize], b'h');
+        assert_eq!(to_64.table[b'~' as usize], b'~'); // Q93 clamps to Q62
+
+        // Solexa and Phred agree above ~Q10; Solexa -5 is Phred 1
+        let solexa = ConversionTable::new(PhredEncoding::Solexa64, PhredEncoding::Phred33);
+        assert_eq!(solexa.table[b';' as usize], b'"');
+        assert_eq!(solexa.table[b'@' as usize], b'$'); // Solexa 0 = Phred 3
+        assert_eq!(solexa.table[b'T' as usize], b'5'); // Q20
+
+        let back = ConversionTable::new(PhredEncoding::Phred33, PhredEncoding::Solexa64);
+        assert_eq!(back.table[b'!' as usize], b';'); // Phred 0 clamps to Solexa -5
+        assert_eq!(back.table[b'5' as usize], b'T');
+    }
+
+    #[test]
+    fn test_phred_conversion_backends_match() {
+        let all_bytes: Vec<u8> = (0..=255u8).collect();
+        let data = records(&all_bytes);
+
+        for (source, target) in [
+            (PhredEncoding::Phred64, PhredEncoding::Phred33),
+            (PhredEncoding::Phred33, PhredEncoding::Phred64),
+            (PhredEncoding::Solexa64, PhredEncoding::Phred33),
+            (PhredEncoding::Phred33, PhredEncoding::Solexa64),
+            (PhredEncoding::Phred33, PhredEncoding::Phred33),
+        ] {
+            let op = PhredConversion::new(target).with_source(source);
+            let expected = qualities(op.execute_naive(&data).unwrap());
+
+            for output in [
+                op.execute_neon(&data).unwrap(),
+                op.execute_parallel(&data, 3).unwrap(),
+                op.execute_parallel_chunked(&data, 2).unwrap(),
+            ] {
+                assert_eq!(qualities(output), expected, "{} -> {}", source.name(), target.name());
+            }
+        }
+
+        // Detected Phred+64 round trip
+        let phred64 = records(b"BBfhhIhT");
+        let op = PhredConversion::default();
+        let converted = qualities(op.execute_neon(&phred64).unwrap());
+        assert_eq!(&converted[0][..8], b"##GII*I5");
+    }
+}
@@ -0,0 +1,211 @@
+//! Per-Position Base Content Operation
+//!
+//! Counts A, C, G, T and N at every read position (FastQC's "per base
+//! sequence content" and "per base N content" panels). Lowercase bases count
+//! as their uppercase form; IUPAC codes other than N count as N.
+//!
+//! # Operation Characteristics
+//! - **Category**: Aggregation
+//! - **Complexity**: 0.30 (one table lookup + increment per base)
+//! - **Output**: 5 counters per position (grows with the longest read)
+//! - **NEON benefit**: None expected (scattered increments into a table
+//!   indexed by position and base)
+//!
+//! # Implementation Notes
+//! - Counters live in one flat `Vec<[u64; 5]>` indexed by position, so
+//!   partial results merge elementwise
+//! - Parallel: per-split partial tables merged pairwise (same shape as
+//!   `length_histogram`)
+
+use crate::{HardwareConfig, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Base → counter slot (A, C, G, T, N)
+const SLOT: [u8; 256] = {
+    let mut table = [4u8; 256];
+    table[b'A' as usize] = 0;
+    table[b'a' as usize] = 0;
+    table[b'C' as usize] = 1;
+    table[b'c' as usize] = 1;
+    table[b'G' as usize] = 2;
+    table[b'g' as usize] = 2;
+    table[b'T' as usize] = 3;
+    table[b't' as usize] = 3;
+    table
+};
+
+/// Per-position base content operation
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PositionContent;
+
+impl PositionContent {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Base counts per read position
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PositionContentResult {
+    pub total_sequences: usize,
+    /// `counts[position]` = [A, C, G, T, N]
+    pub counts: Vec<[u64; 5]>,
+}
+
+impl PositionContentResult {
+    fn add_sequence(&mut self, sequence: &[u8]) {
+        if sequence.len() > self.counts.len() {
+            self.counts.resize(sequence.len(), [0; 5]);
+        }
+        for (counts, &base) in self.counts.iter_mut().zip(sequence) {
+            counts[SLOT[base as usize] as usize] += 1;
+        }
+        self.total_sequences += 1;
+    }
+
+    /// Merge a partial result
+    pub fn add(&mut self, other: &Self) {
+        if other.counts.len() > self.counts.len() {
+            self.counts.resize(other.counts.len(), [0; 5]);
+        }
+        for (counts, other_counts) in self.counts.iter_mut().zip(&other.counts) {
+            for (count, &other_count) in counts.iter_mut().zip(other_counts) {
+                *count += other_count;
+            }
+        }
+        self.total_sequences += other.total_sequences;
+    }
+
+    /// GC fraction at `position` (N excluded), or `None` past the longest read
+    pub fn gc_fraction(&self, position: usize) -> Option<f64> {
+        let [a, c, g, t, _] = *self.counts.get(position)?;
+        let called = a + c + g + t;
+        Some(if called == 0 { 0.0 } else { (c + g) as f64 / called as f64 })
+    }
+
+    /// N fraction at `position`, or `None` past the longest read
+    pub fn n_fraction(&self, position: usize) -> Option<f64> {
+        let counts = self.counts.get(position)?;
+        let total: u64 = counts.iter().sum();
+        Some(if total == 0 { 0.0 } else { counts[4] as f64 / total as f64 })
+    }
+}
+
+impl PrimitiveOperation for PositionContent {
+    fn name(&self) -> &str {
+        "position_content"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Aggregation
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        let mut result = PositionContentResult::default();
+        for record in data {
+            result.add_sequence(&record.sequence);
+        }
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let pool = crate::thread_pool::get(num_threads)?;
+
+        let result = pool.install(|| {
+            data.par_iter()
+                .fold(PositionContentResult::default, |mut local, record| {
+                    local.add_sequence(&record.sequence);
+                    local
+                })
+                .reduce(PositionContentResult::default, |mut a, b| {
+                    a.add(&b);
+                    a
+                })
+        });
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let merge = |mut a: PositionContentResult, b: PositionContentResult| {
+            a.add(&b);
+            a
+        };
+        let result = crate::chunked::reduce_neon(self, data, num_threads, batch_size, merge)?
+            .unwrap_or_default();
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        if config.num_threads > 1 {
+            self.execute_threaded(data, config)
+        } else {
+            self.execute_naive(data)
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content(output: OperationOutput) -> PositionContentResult {
+        output.statistics::<PositionContentResult>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_position_content_counts() {
+        let data = vec![
+            SequenceRecord::fasta("a".to_string(), b"ACGTN".to_vec()),
+            SequenceRecord::fasta("b".to_string(), b"aGgR".to_vec()),
+            SequenceRecord::fasta("c".to_string(), b"C".to_vec()),
+        ];
+        let result = content(PositionContent::new().execute_naive(&data).unwrap());
+
+        assert_eq!(result.total_sequences, 3);
+        assert_eq!(result.counts, vec![[2, 1, 0, 0, 0], [0, 1, 1, 0, 0], [0, 0, 2, 0, 0], [0, 0, 0, 1, 1], [0, 0, 0, 0, 1]]);
+        assert_eq!(result.gc_fraction(0), Some(1.0 / 3.0));
+        assert_eq!(result.gc_fraction(3), Some(0.0));
+        assert_eq!(result.n_fraction(3), Some(0.5));
+        assert_eq!(result.gc_fraction(5), None);
+    }
+
+    #[test]
+    fn test_position_content_backends_match() {
+        let data: Vec<SequenceRecord> = (0..300)
+            .map(|i| {
+                let sequence = (0..(50 + i % 70)).map(|j| b"ACGTNacgt"[(i * 7 + j * 3) % 9]).collect();
+                SequenceRecord::fasta(format!("seq{}", i), sequence)
+            })
+            .collect();
+        let op = PositionContent::new();
+        let expected = content(op.execute_naive(&data).unwrap());
+        assert_eq!(expected.counts.len(), 119);
+
+        for threads in [1, 2, 4] {
+            assert_eq!(content(op.execute_parallel(&data, threads).unwrap()), expected);
+            assert_eq!(content(op.execute_parallel_chunked(&data, threads).unwrap()), expected);
+        }
+        assert_eq!(content(op.execute_parallel_chunked(&[], 4).unwrap()), PositionContentResult::default());
+    }
+}
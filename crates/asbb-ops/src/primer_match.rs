@@ -0,0 +1,621 @@
+//! Primer Match Operation
+//!
+//! Finds amplicon primers near the start of each read, allowing up to `k`
+//! mismatches and indels, and assigns each read to its best primer (as in
+//! amplicon demultiplexing and primer trimming).
+//!
+//! # Operation Characteristics
+//! - **Category**: Search
+//! - **Complexity**: 0.60 (approximate matching, one DP column per base)
+//! - **Output**: Per-primer assignment counts and edit distance histograms
+//! - **NEON benefit**: Moderate (two reads per 128-bit vector of u64 lanes)
+//!
+//! # Implementation Notes
+//! - Naive: Sellers semi-global DP (primer fully aligned, free start/end in
+//!   the read prefix), O(primer × prefix) cells per primer
+//! - Myers: Myers' bit-parallel algorithm (Hyyrö's formulation), one u64
+//!   bit-vector column per read base, so primers are limited to 64 bases
+//! - NEON: the same recurrence on `uint64x2_t`, two reads per vector; reads
+//!   of different lengths are masked per lane
+//! - Primers may contain IUPAC degenerate codes; read bases other than ACGT
+//!   match nothing
+
+use crate::{HardwareConfig, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use anyhow::{anyhow, ensure, Result};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Longest supported primer (one Myers bit-vector word)
+pub const MAX_PRIMER_LENGTH: usize = 64;
+
+/// Prefix bases searched beyond the longest primer plus `max_edits`
+/// (room for phased spacers / heterogeneity spacers)
+pub const DEFAULT_PREFIX_SLACK: usize = 8;
+
+/// Read bases matched by an IUPAC nucleotide code (upper case)
+fn iupac_bases(code: u8) -> Option<&'static [u8]> {
+    Some(match code.to_ascii_uppercase() {
+        b'A' => b"A",
+        b'C' => b"C",
+        b'G' => b"G",
+        b'T' | b'U' => b"T",
+        b'R' => b"AG",
+        b'Y' => b"CT",
+        b'S' => b"CG",
+        b'W' => b"AT",
+        b'K' => b"GT",
+        b'M' => b"AC",
+        b'B' => b"CGT",
+        b'D' => b"AGT",
+        b'H' => b"ACT",
+        b'V' => b"ACG",
+        b'N' => b"ACGT",
+        _ => return None,
+    })
+}
+
+/// A named primer (IUPAC codes allowed)
+#[derive(Debug, Clone)]
+pub struct Primer {
+    pub name: String,
+    pub sequence: Vec<u8>,
+    /// Myers pattern masks: bit `i` set if primer base `i` matches the byte
+    peq: Box<[u64; 256]>,
+}
+
+impl Primer {
+    /// Fails on an empty primer, one longer than [`MAX_PRIMER_LENGTH`], or
+    /// a base that is not an IUPAC nucleotide code
+    pub fn new(name: &str, sequence: &[u8]) -> Result<Self> {
+        ensure!(
+            !sequence.is_empty() && sequence.len() <= MAX_PRIMER_LENGTH,
+            "Primer {} must be 1-{} bases (got {})",
+            name,
+            MAX_PRIMER_LENGTH,
+            sequence.len()
+        );
+
+        let mut peq = Box::new([0u64; 256]);
+        for (i, &code) in sequence.iter().enumerate() {
+            let bases = iupac_bases(code)
+                .ok_or_else(|| anyhow!("Primer {} has non-IUPAC base {}", name, code as char))?;
+            for &base in bases {
+                peq[base as usize] |= 1 << i;
+                peq[base.to_ascii_lowercase() as usize] |= 1 << i;
+            }
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            sequence: sequence.to_ascii_uppercase(),
+            peq,
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.sequence.len()
+    }
+
+    /// Whether primer base `i` matches read byte `base`
+    fn matches(&self, i: usize, base: u8) -> bool {
+        self.peq[base as usize] & (1 << i) != 0
+    }
+}
+
+/// Best occurrence of a primer in a read prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrimerHit {
+    /// Edit distance (mismatches + indels)
+    pub distance: usize,
+    /// Prefix position just past the end of the alignment (earliest on ties)
+    pub end: usize,
+}
+
+/// Matching strategy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrimerBackend {
+    /// Full DP matrix per primer
+    Naive,
+    /// Bit-parallel Myers, one read at a time
+    Myers,
+    /// Bit-parallel Myers, two reads per NEON vector
+    MyersNeon,
+}
+
+impl PrimerBackend {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PrimerBackend::Naive => "naive",
+            PrimerBackend::Myers => "myers",
+            PrimerBackend::MyersNeon => "myers_neon",
+        }
+    }
+}
+
+/// Primer match operation
+pub struct PrimerMatch {
+    primers: Vec<Primer>,
+    /// Maximum edit distance for a hit
+    pub max_edits: usize,
+    /// Read bases searched (from the read start)
+    pub prefix_len: usize,
+}
+
+impl PrimerMatch {
+    /// Fails without primers, or if `max_edits` reaches the length of the
+    /// shortest primer (every read would match it)
+    pub fn new(primers: Vec<Primer>, max_edits: usize) -> Result<Self> {
+        ensure!(!primers.is_empty(), "At least one primer is required");
+        let shortest = primers.iter().map(Primer::len).min().unwrap_or(0);
+        ensure!(
+            max_edits < shortest,
+            "max_edits {} must be below the shortest primer length ({})",
+            max_edits,
+            shortest
+        );
+        let longest = primers.iter().map(Primer::len).max().unwrap_or(0);
+        Ok(Self {
+            primers,
+            max_edits,
+            prefix_len: longest + max_edits + DEFAULT_PREFIX_SLACK,
+        })
+    }
+
+    /// 16S V4 primers (515F / 806R, Parada & Apprill), up to 2 edits
+    pub fn v4_16s() -> Result<Self> {
+        Self::new(
+            vec![
+                Primer::new("515F", b"GTGYCAGCMGCCGCGGTAA")?,
+                Primer::new("806R", b"GGACTACNVGGGTWTCTAAT")?,
+            ],
+            2,
+        )
+    }
+
+    pub fn with_prefix_len(mut self, prefix_len: usize) -> Self {
+        self.prefix_len = prefix_len;
+        self
+    }
+
+    pub fn primers(&self) -> &[Primer] {
+        &self.primers
+    }
+
+    /// Whether `backend` runs on this machine
+    pub fn supports(&self, backend: PrimerBackend) -> bool {
+        backend != PrimerBackend::MyersNeon || cfg!(target_arch = "aarch64")
+    }
+
+    fn fastest_backend(&self) -> PrimerBackend {
+        if self.supports(PrimerBackend::MyersNeon) {
+            PrimerBackend::MyersNeon
+        } else {
+            PrimerBackend::Myers
+        }
+    }
+
+    fn prefix<'a>(&self, seq: &'a [u8]) -> &'a [u8] {
+        &seq[..seq.len().min(self.prefix_len)]
+    }
+
+    /// Best hit of every primer in one read (no distance cut-off)
+    pub fn best_hits(&self, seq: &[u8]) -> Vec<PrimerHit> {
+        let text = self.prefix(seq);
+        self.primers.iter().map(|primer| myers_search(primer, text)).collect()
+    }
+
+    /// Primer the read is assigned to: lowest distance within `max_edits`,
+    /// first primer on ties
+    fn assign(&self, hits: impl Iterator<Item = PrimerHit>) -> Option<(usize, PrimerHit)> {
+        hits.enumerate()
+            .filter(|(_, hit)| hit.distance <= self.max_edits)
+            .min_by_key(|(index, hit)| (hit.distance, *index))
+    }
+
+    /// Match records with an explicit backend
+    pub fn execute_backend(&self, data: &[SequenceRecord], backend: PrimerBackend) -> Result<PrimerMatchResult> {
+        if !self.supports(backend) {
+            anyhow::bail!("{} primer matching is not available here", backend.name());
+        }
+
+        let mut result = PrimerMatchResult::new(self);
+        match backend {
+            PrimerBackend::Naive => {
+                for record in data {
+                    let text = self.prefix(&record.sequence);
+                    result.add_read(self.assign(self.primers.iter().map(|p| dp_search(p, text))));
+                }
+            }
+            PrimerBackend::Myers => {
+                for record in data {
+                    result.add_read(self.assign(self.best_hits(&record.sequence).into_iter()));
+                }
+            }
+            PrimerBackend::MyersNeon => {
+                for pair in data.chunks(2) {
+                    let a = self.prefix(&pair[0].sequence);
+                    let b = pair.get(1).map_or(&[][..], |r| self.prefix(&r.sequence));
+                    let hits: Vec<[PrimerHit; 2]> = self.primers.iter().map(|p| myers_search_pair(p, a, b)).collect();
+                    for lane in 0..pair.len() {
+                        result.add_read(self.assign(hits.iter().map(|h| h[lane])));
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+// ============================================================================
+// Matching kernels
+// ============================================================================
+
+/// Sellers semi-global DP: primer fully aligned, free start and end in text
+fn dp_search(primer: &Primer, text: &[u8]) -> PrimerHit {
+    let m = primer.len();
+    let mut column: Vec<usize> = (0..=m).collect();
+    let mut best = PrimerHit { distance: m, end: 0 };
+
+    for (j, &base) in text.iter().enumerate() {
+        let mut diagonal = column[0]; // D[i-1][j-1]
+        column[0] = 0;
+        for i in 1..=m {
+            let substitution = diagonal + usize::from(!primer.matches(i - 1, base));
+            diagonal = column[i];
+            column[i] = substitution.min(column[i] + 1).min(column[i - 1] + 1);
+        }
+        if column[m] < best.distance {
+            best = PrimerHit { distance: column[m], end: j + 1 };
+        }
+    }
+
+    best
+}
+
+/// Myers bit-parallel search (last-row score per text base)
+fn myers_search(primer: &Primer, text: &[u8]) -> PrimerHit {
+    let m = primer.len();
+    let high = 1u64 << (m - 1);
+    let (mut pv, mut mv) = (!0u64, 0u64);
+    let mut score = m;
+    let mut best = PrimerHit { distance: m, end: 0 };
+
+    for (j, &base) in text.iter().enumerate() {
+        let eq = primer.peq[base as usize];
+        let xv = eq | mv;
+        let xh = ((eq & pv).wrapping_add(pv) ^ pv) | eq;
+        let mut ph = mv | !(xh | pv);
+        let mut mh = pv & xh;
+
+        if ph & high != 0 {
+            score += 1;
+        } else if mh & high != 0 {
+            score -= 1;
+        }
+
+        // Searching: row 0 is all zeros, so no carry into bit 0
+        ph <<= 1;
+        mh <<= 1;
+        pv = mh | !(xv | ph);
+        mv = ph & xv;
+
+        if score < best.distance {
+            best = PrimerHit { distance: score, end: j + 1 };
+        }
+    }
+
+    best
+}
+
+/// Myers search of two texts at once (one per u64 lane)
+#[cfg(target_arch = "aarch64")]
+fn myers_search_pair(primer: &Primer, a: &[u8], b: &[u8]) -> [PrimerHit; 2] {
+    use std::arch::aarch64::*;
+
+    let m = primer.len();
+    let length = a.len().max(b.len());
+
+    unsafe {
+        let ones = vdupq_n_u64(!0);
+        let one = vdupq_n_u64(1);
+        let to_high_bit = vdupq_n_s64(-(m as i64 - 1));
+        let lengths = vld1q_u64([a.len() as u64, b.len() as u64].as_ptr());
+
+        let mut pv = ones;
+        let mut mv = vdupq_n_u64(0);
+        let mut score = vdupq_n_s64(m as i64);
+        let mut best = score;
+        let mut end = vdupq_n_u64(0);
+
+        for j in 0..length {
+            // Past the end of a text: no matches, and the lane is masked below
+            let eq_lanes = [
+                a.get(j).map_or(0, |&base| primer.peq[base as usize]),
+                b.get(j).map_or(0, |&base| primer.peq[base as usize]),
+            ];
+            let eq = vld1q_u64(eq_lanes.as_ptr());
+
+            let xv = vorrq_u64(eq, mv);
+            let xh = vorrq_u64(veorq_u64(vaddq_u64(vandq_u64(eq, pv), pv), pv), eq);
+            let ph = vorrq_u64(mv, veorq_u64(vorrq_u64(xh, pv), ones));
+            let mh = vandq_u64(pv, xh);
+
+            // Ph and Mh never share the high bit: score += ph_high - mh_high
+            let ph_high = vandq_u64(vshlq_u64(ph, to_high_bit), one);
+            let mh_high = vandq_u64(vshlq_u64(mh, to_high_bit), one);
+            score = vsubq_s64(vaddq_s64(score, vreinterpretq_s64_u64(ph_high)), vreinterpretq_s64_u64(mh_high));
+
+            let ph = vshlq_n_u64::<1>(ph);
+            let mh = vshlq_n_u64::<1>(mh);
+            pv = vorrq_u64(mh, veorq_u64(vorrq_u64(xv, ph), ones));
+            mv = vandq_u64(ph, xv);
+
+            let active = vcgtq_u64(lengths, vdupq_n_u64(j as u64));
+            let better = vandq_u64(vcltq_s64(score, best), active);
+            best = vbslq_s64(better, score, best);
+            end = vbslq_u64(better, vdupq_n_u64(j as u64 + 1), end);
+        }
+
+        [
+            PrimerHit {
+                distance: vgetq_lane_s64::<0>(best) as usize,
+                end: vgetq_lane_u64::<0>(end) as usize,
+            },
+            PrimerHit {
+                distance: vgetq_lane_s64::<1>(best) as usize,
+                end: vgetq_lane_u64::<1>(end) as usize,
+            },
+        ]
+    }
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+fn myers_search_pair(_primer: &Primer, _a: &[u8], _b: &[u8]) -> [PrimerHit; 2] {
+    panic!("NEON not available on this platform");
+}
+
+// ============================================================================
+// Result
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrimerMatchResult {
+    pub total_sequences: usize,
+    pub primers: Vec<String>,
+    /// Reads assigned to each primer
+    pub matched: Vec<usize>,
+    /// Per primer, assigned reads by edit distance (`0..=max_edits`)
+    pub edit_histograms: Vec<Vec<usize>>,
+    /// Reads with no primer within `max_edits`
+    pub unmatched: usize,
+}
+
+impl PrimerMatchResult {
+    pub fn new(op: &PrimerMatch) -> Self {
+        Self {
+            total_sequences: 0,
+            primers: op.primers.iter().map(|p| p.name.clone()).collect(),
+            matched: vec![0; op.primers.len()],
+            edit_histograms: vec![vec![0; op.max_edits + 1]; op.primers.len()],
+            unmatched: 0,
+        }
+    }
+
+    fn add_read(&mut self, assignment: Option<(usize, PrimerHit)>) {
+        self.total_sequences += 1;
+        match assignment {
+            Some((index, hit)) => {
+                self.matched[index] += 1;
+                self.edit_histograms[index][hit.distance] += 1;
+            }
+            None => self.unmatched += 1,
+        }
+    }
+
+    pub fn add(&mut self, other: &Self) {
+        self.total_sequences += other.total_sequences;
+        self.unmatched += other.unmatched;
+        for (matched, &count) in self.matched.iter_mut().zip(&other.matched) {
+            *matched += count;
+        }
+        for (histogram, other_histogram) in self.edit_histograms.iter_mut().zip(&other.edit_histograms) {
+            for (count, &other_count) in histogram.iter_mut().zip(other_histogram) {
+                *count += other_count;
+            }
+        }
+    }
+
+    /// Fraction of reads assigned to any primer
+    pub fn match_rate(&self) -> f64 {
+        if self.total_sequences == 0 {
+            0.0
+        } else {
+            1.0 - self.unmatched as f64 / self.total_sequences as f64
+        }
+    }
+}
+
+impl PrimitiveOperation for PrimerMatch {
+    fn name(&self) -> &str {
+        "primer_match"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Search
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "primers": self
+                .primers
+                .iter()
+                .map(|p| (p.name.clone(), String::from_utf8_lossy(&p.sequence).into_owned()))
+                .collect::<std::collections::BTreeMap<_, _>>(),
+            "max_edits": self.max_edits,
+            "prefix_len": self.prefix_len,
+        })
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.execute_backend(data, PrimerBackend::Naive)?))
+    }
+
+    fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.execute_backend(data, self.fastest_backend())?))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let pool = crate::thread_pool::get(num_threads)?;
+        let backend = self.fastest_backend();
+
+        // Pairs of reads, so the NEON path keeps both lanes busy
+        let result = pool.install(|| {
+            data.par_chunks(2)
+                .map(|pair| self.execute_backend(pair, backend))
+                .try_reduce(
+                    || PrimerMatchResult::new(self),
+                    |mut a, b| {
+                        a.add(&b);
+                        Ok(a)
+                    },
+                )
+        })?;
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let merge = |mut a: PrimerMatchResult, b: PrimerMatchResult| {
+            a.add(&b);
+            a
+        };
+        let result = crate::chunked::reduce_neon(self, data, num_threads, batch_size, merge)?
+            .unwrap_or_else(|| PrimerMatchResult::new(self));
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        if config.num_threads > 1 {
+            self.execute_threaded(data, config)
+        } else if config.use_neon {
+            self.execute_neon(data)
+        } else {
+            self.execute_naive(data)
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amplicon_reads() -> Vec<SequenceRecord> {
+        let insert = b"ACGTTGCAAGGCTTACCGATTGACCATG";
+        let variants: [&[u8]; 6] = [
+            b"GTGCCAGCAGCCGCGGTAA",    // 515F exact (Y=C, M=A)
+            b"NNGTGTCAGCCGCCGCGGTAA",  // 515F after 2 spacer bases
+            b"GTGCCAGCAGCGCGGTAA",     // 515F with one deletion
+            b"GGACTACATGGGTATCTTAT",   // 806R with two substitutions
+            b"ggactaccggggtttctaat",   // 806R lower case
+            b"TTTTTTTTTTTTTTTTTTTT",   // no primer
+        ];
+        (0..60)
+            .map(|i| {
+                let mut sequence = variants[i % variants.len()].to_vec();
+                sequence.extend_from_slice(&insert[..(i * 7) % insert.len()]);
+                SequenceRecord::fasta(format!("read_{}", i), sequence)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_primer_search_kernels() {
+        let primer = Primer::new("p", b"ACGRT").unwrap();
+
+        for text in [&b"ACGAT"[..], b"TTACGGTTT", b"ACGT", b"CCCCC", b"", b"AxGAT", b"acgatACGAT"] {
+            let expected = dp_search(&primer, text);
+            assert_eq!(myers_search(&primer, text), expected, "{:?}", String::from_utf8_lossy(text));
+            #[cfg(target_arch = "aarch64")]
+            assert_eq!(myers_search_pair(&primer, text, b"ACGG")[0], expected);
+        }
+
+        assert_eq!(dp_search(&primer, b"TTACGGTTT"), PrimerHit { distance: 0, end: 7 });
+        assert_eq!(dp_search(&primer, b"ACGT").distance, 1);
+        assert_eq!(dp_search(&primer, b"").distance, 5);
+
+        // 64-base primer uses every bit of the word
+        let long: Vec<u8> = (0..64).map(|i| b"ACGT"[(i * 3 + i / 5) % 4]).collect();
+        let primer = Primer::new("long", &long).unwrap();
+        let mut text = b"GG".to_vec();
+        text.extend_from_slice(&long[..30]);
+        text.extend_from_slice(&long[31..]);
+        assert_eq!(myers_search(&primer, &text), dp_search(&primer, &text));
+        assert_eq!(myers_search(&primer, &text).distance, 1);
+    }
+
+    #[test]
+    fn test_invalid_primers_are_rejected() {
+        assert!(Primer::new("empty", b"").is_err());
+        assert!(Primer::new("long", &[b'A'; MAX_PRIMER_LENGTH + 1]).is_err());
+        let error = Primer::new("p", b"ACXT").unwrap_err().to_string();
+        assert!(error.contains("non-IUPAC base X"), "{}", error);
+
+        assert!(PrimerMatch::new(Vec::new(), 1).is_err());
+        let primer = || Primer::new("p", b"ACGT").unwrap();
+        assert!(PrimerMatch::new(vec![primer()], 4).is_err());
+        assert!(PrimerMatch::new(vec![primer()], 3).is_ok());
+    }
+
+    #[test]
+    fn test_primer_match_assignment() {
+        let op = PrimerMatch::v4_16s().unwrap();
+        let result = op.execute_naive(&amplicon_reads()).unwrap();
+        let result = result.statistics::<PrimerMatchResult>().unwrap();
+
+        assert_eq!(result.total_sequences, 60);
+        assert_eq!(result.matched, vec![30, 20]);
+        assert_eq!(result.unmatched, 10);
+        assert_eq!(result.edit_histograms[0], vec![20, 10, 0]);
+        assert_eq!(result.edit_histograms[1], vec![10, 0, 10]);
+        assert!((result.match_rate() - 50.0 / 60.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_primer_match_backends_match() {
+        let op = PrimerMatch::v4_16s().unwrap();
+        // Odd count leaves one NEON lane empty
+        let data = &amplicon_reads()[..59];
+        let expected = op.execute_backend(data, PrimerBackend::Naive).unwrap();
+
+        for backend in [PrimerBackend::Myers, PrimerBackend::MyersNeon] {
+            if op.supports(backend) {
+                assert_eq!(op.execute_backend(data, backend).unwrap(), expected, "{}", backend.name());
+            }
+        }
+        for output in [
+            op.execute_neon(data).unwrap(),
+            op.execute_parallel(data, 3).unwrap(),
+            op.execute_parallel_chunked(data, 2).unwrap(),
+        ] {
+            assert_eq!(output.statistics::<PrimerMatchResult>().unwrap(), &expected);
+        }
+    }
+}
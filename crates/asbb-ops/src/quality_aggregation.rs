@@ -7,14 +7,87 @@
 // - NEON: 14-35× speedup (scale-dependent, cache effects)
 // - Parallel: Threshold at 1,000 sequences
 // - Combined: Uses NEON per-thread (40-60× at large scale)
+//
+// Grouped mode (QualityAggregation::grouped_by): statistics per lane or per
+// lane:tile, parsed from Illumina read IDs (CASAVA 1.8+
+// `instrument:run:flowcell:lane:tile:x:y`, or the older
+// `instrument:lane:tile:x:y#index/read`; any whitespace-separated token of
+// the header may carry it, e.g. after an SRA accession). Reads without a
+// recognizable ID are counted as ungrouped. Parallel paths fold per-thread
+// hash maps keyed by slices of the read IDs and merge them, a group-by
+// reduction rather than a single accumulator.
 
 use crate::PrimitiveOperation;
 use asbb_core::{HardwareConfig, OperationCategory, OperationOutput, SequenceRecord};
 use anyhow::Result;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+pub struct QualityAggregation {
+    /// Group statistics by a key parsed from the read ID
+    pub group_by: Option<ReadGroupKey>,
+}
 
-pub struct QualityAggregation;
+/// Key parsed from Illumina read IDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadGroupKey {
+    /// Flow cell lane (`3`)
+    Lane,
+    /// Lane and tile (`3:1101`)
+    Tile,
+}
+
+impl ReadGroupKey {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReadGroupKey::Lane => "lane",
+            ReadGroupKey::Tile => "tile",
+        }
+    }
+
+    /// This key of a read ID, borrowed from it (`None` if not Illumina-style)
+    pub fn of<'a>(&self, id: &'a str) -> Option<&'a str> {
+        let (lane, lane_and_tile) = illumina_location(id)?;
+        match self {
+            ReadGroupKey::Lane => Some(lane),
+            ReadGroupKey::Tile => Some(lane_and_tile),
+        }
+    }
+}
+
+/// (lane, lane:tile) of the first Illumina-style token of a read ID
+fn illumina_location(id: &str) -> Option<(&str, &str)> {
+    id.split_ascii_whitespace().find_map(|token| {
+        let token = token.strip_prefix('@').unwrap_or(token);
+
+        // Byte offsets of the first seven colons
+        let mut colons = [0usize; 7];
+        let mut count = 0;
+        for (i, byte) in token.bytes().enumerate() {
+            if byte == b':' {
+                if count == colons.len() {
+                    return None;
+                }
+                colons[count] = i;
+                count += 1;
+            }
+        }
+        // Lane is field 3 of the 7-field format, field 1 of the 5-field one
+        let lane_field = match count {
+            6 => 3,
+            4 => 1,
+            _ => return None,
+        };
+
+        let start = colons[lane_field - 1] + 1;
+        let lane = &token[start..colons[lane_field]];
+        let tile = &token[colons[lane_field] + 1..colons[lane_field + 1]];
+        let numeric = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        (numeric(lane) && numeric(tile)).then(|| (lane, &token[start..colons[lane_field + 1]]))
+    })
+}
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct QualityStats {
@@ -61,9 +134,110 @@ impl QualityStats {
     }
 }
 
+/// Quality statistics per read group
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupedQualityStats {
+    pub group_by: ReadGroupKey,
+
+    /// Per key (lane, or lane:tile)
+    pub groups: BTreeMap<String, QualityStats>,
+
+    /// Reads whose ID carries no key
+    pub ungrouped: QualityStats,
+
+    /// Every read
+    pub overall: QualityStats,
+}
+
+impl GroupedQualityStats {
+    pub fn new(group_by: ReadGroupKey) -> Self {
+        Self {
+            group_by,
+            groups: BTreeMap::new(),
+            ungrouped: QualityStats::new(),
+            overall: QualityStats::new(),
+        }
+    }
+
+    /// Fold in another partial result (e.g. another chunk's)
+    pub fn merge(mut self, other: Self) -> Self {
+        for (key, stats) in &other.groups {
+            self.groups.entry(key.clone()).or_insert_with(QualityStats::new).add(stats);
+        }
+        self.ungrouped.add(&other.ungrouped);
+        self.overall.add(&other.overall);
+        self
+    }
+
+    pub fn finalize(&mut self) {
+        for stats in self.groups.values_mut() {
+            stats.finalize();
+        }
+        self.ungrouped.finalize();
+        self.overall.finalize();
+    }
+}
+
+/// Partial group-by result, keyed by slices of the read IDs
+struct GroupAccumulator<'a> {
+    groups: HashMap<&'a str, QualityStats>,
+    ungrouped: QualityStats,
+}
+
+impl<'a> GroupAccumulator<'a> {
+    fn new() -> Self {
+        Self { groups: HashMap::new(), ungrouped: QualityStats::new() }
+    }
+
+    fn add(&mut self, key: ReadGroupKey, record: &'a SequenceRecord, neon: bool) {
+        let Some(qual) = &record.quality else {
+            return;
+        };
+        let stats = if neon { aggregate_quality_neon(qual) } else { aggregate_quality_naive(qual) };
+        match key.of(&record.id) {
+            Some(group) => self.groups.entry(group).or_insert_with(QualityStats::new).add(&stats),
+            None => self.ungrouped.add(&stats),
+        }
+    }
+
+    fn merge(mut self, other: Self) -> Self {
+        for (group, stats) in other.groups {
+            self.groups.entry(group).or_insert_with(QualityStats::new).add(&stats);
+        }
+        self.ungrouped.add(&other.ungrouped);
+        self
+    }
+
+    fn finish(self, key: ReadGroupKey) -> GroupedQualityStats {
+        let mut result = GroupedQualityStats::new(key);
+        for (group, stats) in self.groups {
+            result.overall.add(&stats);
+            result.groups.insert(group.to_string(), stats);
+        }
+        result.overall.add(&self.ungrouped);
+        result.ungrouped = self.ungrouped;
+        result.finalize();
+        result
+    }
+}
+
 impl QualityAggregation {
     pub fn new() -> Self {
-        Self
+        Self { group_by: None }
+    }
+
+    /// Statistics per lane or tile instead of one total
+    pub fn grouped_by(key: ReadGroupKey) -> Self {
+        Self { group_by: Some(key) }
+    }
+
+    /// Grouped statistics over `data`, using the NEON kernel if `neon`
+    fn grouped(&self, key: ReadGroupKey, data: &[SequenceRecord], neon: bool) -> OperationOutput {
+        let mut groups = GroupAccumulator::new();
+        for record in data {
+            groups.add(key, record, neon);
+        }
+        OperationOutput::typed(groups.finish(key))
     }
 
     /// Execute quality aggregation using GPU (Metal)
@@ -103,7 +277,17 @@ impl PrimitiveOperation for QualityAggregation {
         OperationCategory::ElementWise
     }
 
+    fn parameters(&self) -> serde_json::Value {
+        match self.group_by {
+            Some(key) => serde_json::json!({ "group_by": key.name() }),
+            None => serde_json::Value::Null,
+        }
+    }
+
     fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        if let Some(key) = self.group_by {
+            return Ok(self.grouped(key, data, false));
+        }
         let mut stats = QualityStats::new();
 
         for record in data {
@@ -114,12 +298,15 @@ impl PrimitiveOperation for QualityAggregation {
         }
 
         stats.finalize();
-        Ok(OperationOutput::Statistics(serde_json::to_value(stats)?))
+        Ok(OperationOutput::typed(stats))
     }
 
     fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
         #[cfg(target_arch = "aarch64")]
         {
+            if let Some(key) = self.group_by {
+                return Ok(self.grouped(key, data, true));
+            }
             let mut stats = QualityStats::new();
 
             for record in data {
@@ -130,7 +317,7 @@ impl PrimitiveOperation for QualityAggregation {
             }
 
             stats.finalize();
-            Ok(OperationOutput::Statistics(serde_json::to_value(stats)?))
+            Ok(OperationOutput::typed(stats))
         }
 
         #[cfg(not(target_arch = "aarch64"))]
@@ -145,9 +332,20 @@ impl PrimitiveOperation for QualityAggregation {
         data: &[SequenceRecord],
         num_threads: usize,
     ) -> Result<OperationOutput> {
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()?;
+        let pool = crate::thread_pool::get(num_threads)?;
+
+        if let Some(key) = self.group_by {
+            let neon = cfg!(target_arch = "aarch64");
+            let groups = pool.install(|| {
+                data.par_iter()
+                    .fold(GroupAccumulator::new, |mut groups, record| {
+                        groups.add(key, record, neon);
+                        groups
+                    })
+                    .reduce(GroupAccumulator::new, GroupAccumulator::merge)
+            });
+            return Ok(OperationOutput::typed(groups.finish(key)));
+        }
 
         let mut stats = pool.install(|| {
             data.par_iter()
@@ -177,7 +375,43 @@ impl PrimitiveOperation for QualityAggregation {
         });
 
         stats.finalize();
-        Ok(OperationOutput::Statistics(serde_json::to_value(stats)?))
+        Ok(OperationOutput::typed(stats))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        if let Some(key) = self.group_by {
+            let neon = cfg!(target_arch = "aarch64");
+            let groups = crate::chunked::map_reduce(
+                data,
+                num_threads,
+                batch_size,
+                |chunk| {
+                    let mut groups = GroupAccumulator::new();
+                    for record in chunk {
+                        groups.add(key, record, neon);
+                    }
+                    Ok(groups)
+                },
+                GroupAccumulator::merge,
+            )?
+            .unwrap_or_else(GroupAccumulator::new);
+            return Ok(OperationOutput::typed(groups.finish(key)));
+        }
+
+        let merge = |mut a: QualityStats, b: QualityStats| {
+            a.add(&b);
+            a
+        };
+        let mut result = crate::chunked::reduce_neon(self, data, num_threads, batch_size, merge)?
+            .unwrap_or_else(QualityStats::new);
+        result.finalize();
+
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_with_config(
@@ -188,7 +422,7 @@ impl PrimitiveOperation for QualityAggregation {
         // Execution precedence (from combined optimization findings):
         // Parallel takes precedence over NEON (parallel uses NEON per-thread)
         if config.num_threads > 1 {
-            self.execute_parallel(data, config.num_threads)
+            self.execute_threaded(data, config)
         } else if config.use_neon {
             self.execute_neon(data)
         } else {
@@ -322,12 +556,11 @@ mod tests {
     #[test]
     fn test_quality_aggregation_naive() {
         let records = create_test_records();
-        let op = QualityAggregation;
+        let op = QualityAggregation::new();
 
         let result = op.execute_naive(&records).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let stats: QualityStats = serde_json::from_value(value).unwrap();
+        if let Some(stats) = result.statistics::<QualityStats>() {
 
             // Expected: min=10, max=45, total=30+35+40+45+20+25+30+35+10+15+20+25=330
             // mean = 330/12 = 27.5
@@ -345,12 +578,11 @@ mod tests {
     #[cfg(target_arch = "aarch64")]
     fn test_quality_aggregation_neon() {
         let records = create_test_records();
-        let op = QualityAggregation;
+        let op = QualityAggregation::new();
 
         let result = op.execute_neon(&records).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let stats: QualityStats = serde_json::from_value(value).unwrap();
+        if let Some(stats) = result.statistics::<QualityStats>() {
 
             assert_eq!(stats.min_quality, 10);
             assert_eq!(stats.max_quality, 45);
@@ -366,16 +598,16 @@ mod tests {
     #[cfg(target_arch = "aarch64")]
     fn test_quality_aggregation_neon_matches_naive() {
         let records = create_test_records();
-        let op = QualityAggregation;
+        let op = QualityAggregation::new();
 
         let naive_result = op.execute_naive(&records).unwrap();
         let neon_result = op.execute_neon(&records).unwrap();
 
-        if let (OperationOutput::Statistics(naive_val), OperationOutput::Statistics(neon_val)) =
+        if let (OperationOutput::Typed(naive_typed), OperationOutput::Typed(neon_typed)) =
             (naive_result, neon_result)
         {
-            let naive_stats: QualityStats = serde_json::from_value(naive_val).unwrap();
-            let neon_stats: QualityStats = serde_json::from_value(neon_val).unwrap();
+            let naive_stats: &QualityStats = naive_typed.downcast_ref().unwrap();
+            let neon_stats: &QualityStats = neon_typed.downcast_ref().unwrap();
 
             assert_eq!(naive_stats.min_quality, neon_stats.min_quality);
             assert_eq!(naive_stats.max_quality, neon_stats.max_quality);
@@ -390,12 +622,11 @@ mod tests {
     #[test]
     fn test_quality_aggregation_parallel() {
         let records = create_test_records();
-        let op = QualityAggregation;
+        let op = QualityAggregation::new();
 
         let result = op.execute_parallel(&records, 2).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let stats: QualityStats = serde_json::from_value(value).unwrap();
+        if let Some(stats) = result.statistics::<QualityStats>() {
 
             assert_eq!(stats.min_quality, 10);
             assert_eq!(stats.max_quality, 45);
@@ -407,15 +638,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_read_group_keys() {
+        let casava = "A00123:8:H5KJ2DSXY:2:1101:1000:2000 1:N:0:ACGT";
+        assert_eq!(ReadGroupKey::Lane.of(casava), Some("2"));
+        assert_eq!(ReadGroupKey::Tile.of(casava), Some("2:1101"));
+        assert_eq!(ReadGroupKey::Tile.of("HWUSI-EAS100R:6:73:941:1973#0/1"), Some("6:73"));
+        // Name after an SRA accession
+        let sra = "SRR001666.1 A00123:8:H5KJ2DSXY:4:2202:1:2 length=150";
+        assert_eq!(ReadGroupKey::Tile.of(sra), Some("4:2202"));
+        assert_eq!(ReadGroupKey::Lane.of("seq_0"), None);
+        assert_eq!(ReadGroupKey::Lane.of("a:b:c:x:1101:1:2"), None);
+    }
+
+    #[test]
+    fn test_grouped_quality_aggregation() {
+        let mut records = create_test_records();
+        records[0].id = "M1:1:FC:1:1101:10:20 1:N:0:1".to_string();
+        records[1].id = "M1:1:FC:1:1102:10:20 1:N:0:1".to_string();
+        // records[2] keeps an ID without a tile
+
+        let op = QualityAggregation::grouped_by(ReadGroupKey::Tile);
+        let naive = op.execute_naive(&records).unwrap();
+        let stats = naive.statistics::<GroupedQualityStats>().unwrap();
+        assert_eq!(stats.groups.keys().collect::<Vec<_>>(), vec!["1:1101", "1:1102"]);
+        assert_eq!(stats.groups["1:1101"].total_quality, 150);
+        assert_eq!(stats.groups["1:1102"].min_quality, 20);
+        assert_eq!(stats.ungrouped.num_bases, 4);
+        assert_eq!(stats.overall.total_quality, 330);
+        assert!((stats.overall.mean_quality - 27.5).abs() < 0.01);
+
+        assert_eq!(op.execute_parallel(&records, 2).unwrap(), naive);
+        assert_eq!(op.execute_parallel_chunked(&records, 2).unwrap(), naive);
+        assert_eq!(op.execute_neon(&records).unwrap(), naive);
+
+        let lanes = QualityAggregation::grouped_by(ReadGroupKey::Lane).execute_naive(&records);
+        let lanes = lanes.unwrap();
+        let lanes = lanes.statistics::<GroupedQualityStats>().unwrap();
+        assert_eq!(lanes.groups["1"].num_bases, 8);
+    }
+
     #[test]
     fn test_quality_aggregation_empty() {
         let records: Vec<SequenceRecord> = vec![];
-        let op = QualityAggregation;
+        let op = QualityAggregation::new();
 
         let result = op.execute_naive(&records).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let stats: QualityStats = serde_json::from_value(value).unwrap();
+        if let Some(stats) = result.statistics::<QualityStats>() {
 
             assert_eq!(stats.num_bases, 0);
             assert_eq!(stats.mean_quality, 0.0);
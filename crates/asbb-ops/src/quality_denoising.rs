@@ -0,0 +1,459 @@
+//! Quality score denoising operation
+//!
+//! Smooths each read's quality string with a small 1-D convolution, so
+//! isolated quality dips and spikes (single-cycle artefacts) do not drive
+//! per-base trimming or masking decisions. Returns the records with
+//! smoothed quality strings; sequences are unchanged.
+//!
+//! **Operation Category**: Element-wise
+//! - Scores are decoded to Phred values, convolved (edges replicate the
+//!   first/last score), rounded and re-encoded
+//! - Default kernel: [1, 2, 3, 2, 1] / 9 (a short triangular window)
+//! - Weights are applied as a correlation (`out[i] = Σ w[t]·q[i + t - r]`)
+//!
+//! # Apple Silicon Considerations
+//!
+//! - **NEON**: 4 output positions per FMA, one FMA per tap
+//! - **AMX path**: Accelerate's BNNS convolution layer, with reads of the
+//!   same length batched through one filter. BNNS runs on the CPU and hands
+//!   matrix-shaped work to the AMX units, so it is the library route to the
+//!   AMX/ANE-adjacent hardware for a realistic QC step. Uses the
+//!   `BNNSFilterCreateConvolutionLayer` API (deprecated in macOS 11 but still
+//!   shipped) for its simpler descriptors.
+
+use anyhow::Result;
+use asbb_core::{OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use rayon::prelude::*;
+
+/// Quality score denoising operation
+pub struct QualityDenoising {
+    /// Normalised kernel (odd length)
+    weights: Vec<f32>,
+    /// Phred encoding offset
+    offset: u8,
+    /// Highest Phred score written back
+    max_quality: u8,
+}
+
+impl QualityDenoising {
+    /// Phred+33 with the default [1, 2, 3, 2, 1] kernel
+    pub fn new() -> Self {
+        Self::with_kernel(&[1.0, 2.0, 3.0, 2.0, 1.0])
+    }
+
+    /// Phred+33 with a custom kernel (odd length, normalised to sum to 1)
+    pub fn with_kernel(kernel: &[f32]) -> Self {
+        assert!(kernel.len() % 2 == 1, "Kernel length must be odd");
+        let sum: f32 = kernel.iter().sum();
+        assert!(sum > 0.0, "Kernel weights must sum to a positive value");
+
+        Self {
+            weights: kernel.iter().map(|w| w / sum).collect(),
+            offset: 33,
+            max_quality: 93, // '~' in Phred+33
+        }
+    }
+
+    /// Taps on each side of the centre
+    fn radius(&self) -> usize {
+        self.weights.len() / 2
+    }
+
+    /// Round, clamp and re-encode a smoothed score
+    fn encode(&self, value: f32) -> u8 {
+        value.round().clamp(0.0, self.max_quality as f32) as u8 + self.offset
+    }
+
+    /// Decoded scores with `radius` replicated values on each side
+    fn padded(&self, quality: &[u8]) -> Vec<f32> {
+        let radius = self.radius();
+        let decode = |q: u8| q.saturating_sub(self.offset) as f32;
+        let first = decode(quality[0]);
+        let last = decode(quality[quality.len() - 1]);
+
+        let mut padded = Vec::with_capacity(quality.len() + 2 * radius);
+        padded.extend(std::iter::repeat_n(first, radius));
+        padded.extend(quality.iter().map(|&q| decode(q)));
+        padded.extend(std::iter::repeat_n(last, radius));
+        padded
+    }
+
+    /// Smooth one quality string (naive)
+    fn denoise_naive(&self, quality: &[u8]) -> Vec<u8> {
+        let n = quality.len();
+        let radius = self.radius();
+
+        (0..n)
+            .map(|i| {
+                let value: f32 = self
+                    .weights
+                    .iter()
+                    .enumerate()
+                    .map(|(t, w)| {
+                        let j = (i + t).saturating_sub(radius).min(n - 1);
+                        w * quality[j].saturating_sub(self.offset) as f32
+                    })
+                    .sum();
+                self.encode(value)
+            })
+            .collect()
+    }
+
+    /// Smooth one quality string (NEON)
+    #[cfg(target_arch = "aarch64")]
+    fn denoise_neon(&self, quality: &[u8]) -> Vec<u8> {
+        use std::arch::aarch64::*;
+
+        let n = quality.len();
+        let padded = self.padded(quality);
+        let mut result = vec![0u8; n];
+        let mut i = 0;
+
+        // 4 output positions per iteration
+        unsafe {
+            let max = vdupq_n_s32(self.max_quality as i32);
+            let zero = vdupq_n_s32(0);
+            while i + 4 <= n {
+                let mut acc = vdupq_n_f32(0.0);
+                for (t, &w) in self.weights.iter().enumerate() {
+                    acc = vfmaq_n_f32(acc, vld1q_f32(padded.as_ptr().add(i + t)), w);
+                }
+                // Round half away from zero (as f32::round), then clamp
+                let scores = vminq_s32(vmaxq_s32(vcvtaq_s32_f32(acc), zero), max);
+                let mut lanes = [0i32; 4];
+                vst1q_s32(lanes.as_mut_ptr(), scores);
+                for (out, score) in result[i..i + 4].iter_mut().zip(lanes) {
+                    *out = score as u8 + self.offset;
+                }
+                i += 4;
+            }
+        }
+
+        // Remaining positions
+        for (j, out) in result.iter_mut().enumerate().skip(i) {
+            let value: f32 = self.weights.iter().enumerate().map(|(t, w)| w * padded[j + t]).sum();
+            *out = self.encode(value);
+        }
+        result
+    }
+
+    #[cfg(not(target_arch = "aarch64"))]
+    fn denoise_neon(&self, quality: &[u8]) -> Vec<u8> {
+        // Fall back to naive on non-ARM
+        self.denoise_naive(quality)
+    }
+
+    /// Apply `denoise` to every record with a (non-empty) quality string
+    fn denoise_record(&self, record: &SequenceRecord, denoise: fn(&Self, &[u8]) -> Vec<u8>) -> SequenceRecord {
+        SequenceRecord {
+            id: record.id.clone(),
+            sequence: record.sequence.clone(),
+            quality: record
+                .quality
+                .as_ref()
+                .map(|quality| if quality.is_empty() { Vec::new() } else { denoise(self, quality) }),
+        }
+    }
+
+    /// Smooth all records with BNNS, one filter per read length
+    #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+    fn denoise_bnns(&self, data: &[SequenceRecord]) -> Result<Vec<SequenceRecord>> {
+        use std::collections::HashMap;
+
+        let mut records: Vec<SequenceRecord> = data.to_vec();
+
+        let mut by_length: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (index, record) in data.iter().enumerate() {
+            if let Some(quality) = record.quality.as_ref().filter(|q| !q.is_empty()) {
+                by_length.entry(quality.len()).or_default().push(index);
+            }
+        }
+
+        for (length, indices) in by_length {
+            let in_width = length + 2 * self.radius();
+            let input: Vec<f32> = indices
+                .iter()
+                .flat_map(|&index| self.padded(data[index].quality.as_deref().unwrap_or_default()))
+                .collect();
+            let mut output = vec![0.0f32; indices.len() * length];
+
+            bnns::convolve_rows(&self.weights, &input, in_width, &mut output, length, indices.len())?;
+
+            for (&index, row) in indices.iter().zip(output.chunks(length)) {
+                records[index].quality = Some(row.iter().map(|&value| self.encode(value)).collect());
+            }
+        }
+        Ok(records)
+    }
+
+    #[cfg(not(all(target_os = "macos", target_arch = "aarch64")))]
+    fn denoise_bnns(&self, _data: &[SequenceRecord]) -> Result<Vec<SequenceRecord>> {
+        anyhow::bail!("BNNS requires macOS on Apple Silicon")
+    }
+}
+
+impl Default for QualityDenoising {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrimitiveOperation for QualityDenoising {
+    fn name(&self) -> &str {
+        "quality_denoising"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::ElementWise
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        let records = data.iter().map(|record| self.denoise_record(record, Self::denoise_naive)).collect();
+        Ok(OperationOutput::Records(records))
+    }
+
+    fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        let records = data.iter().map(|record| self.denoise_record(record, Self::denoise_neon)).collect();
+        Ok(OperationOutput::Records(records))
+    }
+
+    fn execute_parallel(&self, data: &[SequenceRecord], num_threads: usize) -> Result<OperationOutput> {
+        crate::thread_pool::get(num_threads)?.install(|| {
+            let records = data
+                .par_iter()
+                .map(|record| self.denoise_record(record, Self::denoise_neon))
+                .collect();
+            Ok(OperationOutput::Records(records))
+        })
+    }
+
+    /// BNNS convolution (Accelerate)
+    fn execute_amx(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::Records(self.denoise_bnns(data)?))
+    }
+}
+
+// ============================================================================
+// BNNS (Accelerate) bindings
+// ============================================================================
+
+#[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+mod bnns {
+    use anyhow::Result;
+    use std::ffi::c_void;
+    use std::ptr::null;
+
+    /// `BNNSDataTypeFloat32`
+    const FLOAT32: u32 = 0x10000 | 32;
+
+    /// `BNNSActivationFunctionIdentity`
+    const IDENTITY: u32 = 0;
+
+    #[repr(C)]
+    struct ImageStackDescriptor {
+        width: usize,
+        height: usize,
+        channels: usize,
+        row_stride: usize,
+        image_stack_stride: usize,
+        data_type: u32,
+        data_scale: f32,
+        data_bias: f32,
+    }
+
+    #[repr(C)]
+    struct LayerData {
+        data: *const c_void,
+        data_type: u32,
+        data_scale: f32,
+        data_bias: f32,
+        data_table: *const f32,
+    }
+
+    #[repr(C)]
+    struct Activation {
+        function: u32,
+        alpha: f32,
+        beta: f32,
+        iscale: i32,
+        ioffset: i32,
+        ishift: i32,
+        iscale_per_channel: *const i32,
+        ioffset_per_channel: *const i32,
+        ishift_per_channel: *const i32,
+    }
+
+    #[repr(C)]
+    struct ConvolutionLayerParameters {
+        x_stride: usize,
+        y_stride: usize,
+        x_padding: usize,
+        y_padding: usize,
+        k_width: usize,
+        k_height: usize,
+        in_channels: usize,
+        out_channels: usize,
+        weights: LayerData,
+        bias: LayerData,
+        activation: Activation,
+    }
+
+    #[link(name = "Accelerate", kind = "framework")]
+    extern "C" {
+        fn BNNSFilterCreateConvolutionLayer(
+            in_desc: *const ImageStackDescriptor,
+            out_desc: *const ImageStackDescriptor,
+            layer_params: *const ConvolutionLayerParameters,
+            filter_params: *const c_void,
+        ) -> *mut c_void;
+
+        fn BNNSFilterApplyBatch(
+            filter: *mut c_void,
+            batch_size: usize,
+            in_ptr: *const c_void,
+            in_stride: usize,
+            out_ptr: *mut c_void,
+            out_stride: usize,
+        ) -> i32;
+
+        fn BNNSFilterDestroy(filter: *mut c_void);
+    }
+
+    /// Single-channel image descriptor for one `width`-value row
+    fn row(width: usize) -> ImageStackDescriptor {
+        ImageStackDescriptor {
+            width,
+            height: 1,
+            channels: 1,
+            row_stride: width,
+            image_stack_stride: width,
+            data_type: FLOAT32,
+            data_scale: 1.0,
+            data_bias: 0.0,
+        }
+    }
+
+    fn layer_data(data: *const f32) -> LayerData {
+        LayerData {
+            data: data as *const c_void,
+            data_type: FLOAT32,
+            data_scale: 1.0,
+            data_bias: 0.0,
+            data_table: null(),
+        }
+    }
+
+    /// Valid (unpadded) convolution of `rows` rows of `in_width` values
+    pub(super) fn convolve_rows(
+        weights: &[f32],
+        input: &[f32],
+        in_width: usize,
+        output: &mut [f32],
+        out_width: usize,
+        rows: usize,
+    ) -> Result<()> {
+        let bias = [0.0f32];
+        let params = ConvolutionLayerParameters {
+            x_stride: 1,
+            y_stride: 1,
+            x_padding: 0,
+            y_padding: 0,
+            k_width: weights.len(),
+            k_height: 1,
+            in_channels: 1,
+            out_channels: 1,
+            weights: layer_data(weights.as_ptr()),
+            bias: layer_data(bias.as_ptr()),
+            activation: Activation {
+                function: IDENTITY,
+                alpha: 0.0,
+                beta: 0.0,
+                iscale: 1,
+                ioffset: 0,
+                ishift: 0,
+                iscale_per_channel: null(),
+                ioffset_per_channel: null(),
+                ishift_per_channel: null(),
+            },
+        };
+
+        unsafe {
+            let filter = BNNSFilterCreateConvolutionLayer(&row(in_width), &row(out_width), &params, null());
+            if filter.is_null() {
+                anyhow::bail!("BNNS rejected a {}-tap convolution over {} values", weights.len(), in_width);
+            }
+            let status = BNNSFilterApplyBatch(
+                filter,
+                rows,
+                input.as_ptr() as *const c_void,
+                in_width,
+                output.as_mut_ptr() as *mut c_void,
+                out_width,
+            );
+            BNNSFilterDestroy(filter);
+            if status != 0 {
+                anyhow::bail!("BNNS convolution failed (status {})", status);
+            }
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(quality: &[u8]) -> SequenceRecord {
+        SequenceRecord::fastq("read".to_string(), vec![b'A'; quality.len()], quality.to_vec())
+    }
+
+    fn qualities(output: OperationOutput) -> Vec<Vec<u8>> {
+        match output {
+            OperationOutput::Records(records) => records.into_iter().map(|r| r.quality.unwrap_or_default()).collect(),
+            _ => panic!("expected records"),
+        }
+    }
+
+    #[test]
+    fn test_smooths_dip() {
+        let op = QualityDenoising::new();
+        // Q30 with a single Q3 dip
+        let result = qualities(op.execute_naive(&[record(b"?????$?????")]).unwrap());
+        // Dip: (30·6 + 3·3) / 9 = 21; neighbours 24 and 27
+        assert_eq!(&result[0][3..8], b"<969<");
+        assert_eq!(&result[0][..3], b"???");
+    }
+
+    #[test]
+    fn test_constant_and_sequence_unchanged() {
+        let op = QualityDenoising::new();
+        let input = record(b"IIIIIII");
+        let OperationOutput::Records(records) = op.execute_naive(std::slice::from_ref(&input)).unwrap() else {
+            panic!("expected records");
+        };
+        assert_eq!(records[0].quality.as_deref(), Some(&b"IIIIIII"[..]));
+        assert_eq!(records[0].sequence, input.sequence);
+    }
+
+    #[test]
+    fn test_backends_agree() {
+        let op = QualityDenoising::new();
+        let data: Vec<SequenceRecord> = (0..20)
+            .map(|i| {
+                let quality: Vec<u8> = (0..(i * 7 + 1)).map(|j| 35 + ((j * 13 + i) % 40) as u8).collect();
+                record(&quality)
+            })
+            .collect();
+        let expected = qualities(op.execute_naive(&data).unwrap());
+
+        assert_eq!(qualities(op.execute_neon(&data).unwrap()), expected);
+        assert_eq!(qualities(op.execute_parallel(&data, 2).unwrap()), expected);
+        if let Ok(output) = op.execute_amx(&data) {
+            assert_eq!(qualities(output), expected);
+        }
+    }
+}
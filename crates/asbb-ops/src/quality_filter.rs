@@ -17,20 +17,192 @@
 // - Scalar fallback: 0.6 (branch on mean quality)
 // - Memory access: 0.4 (sequential read + conditional write)
 // - Data dependencies: 0.5 (filter decision depends on aggregation)
+//
+// Modes (QualityFilterMode):
+// - MeanQuality: mean score >= threshold (scores compared as stored)
+// - MinWindow: every `window`-base window has mean Phred >= threshold
+//   (catches local quality collapses that a read-wide mean hides)
+// - ExpectedErrors: sum of Phred error probabilities 10^(-Q/10) <= max
+//   (USEARCH/VSEARCH --fastq_maxee)
+// - MaxN: at most `max_n` N bases (sequence only, no qualities needed)
+//
+// with_binning: judge Illumina 8/4-level binned scores instead (quality
+// modes only), to measure how often binning flips a filter decision.
+//
+// NEON paths: window sums over 16 start positions per step, expected
+// errors via 4 byte-plane table lookups (vqtbl4q) that assemble f32
+// probabilities, N counting via compare + subtract.
 
 use crate::PrimitiveOperation;
+use asbb_core::quality_binning::{BinningTable, QualityBinning};
 use asbb_core::{HardwareConfig, OperationCategory, OperationOutput, SequenceRecord};
 use anyhow::Result;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// How reads are judged
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum QualityFilterMode {
+    /// Mean score at least `min_mean_quality`
+    MeanQuality { min_mean_quality: u8 },
+    /// Every window of `window` bases has mean Phred at least `min_quality`
+    /// (reads shorter than the window use the whole-read mean)
+    MinWindow { window: usize, min_quality: u8 },
+    /// Sum of per-base error probabilities at most `max_expected_errors`
+    ExpectedErrors { max_expected_errors: f64 },
+    /// At most `max_n` ambiguous (N) bases
+    MaxN { max_n: usize },
+}
+
+impl QualityFilterMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            QualityFilterMode::MeanQuality { .. } => "mean_quality",
+            QualityFilterMode::MinWindow { .. } => "min_window",
+            QualityFilterMode::ExpectedErrors { .. } => "expected_errors",
+            QualityFilterMode::MaxN { .. } => "max_n",
+        }
+    }
+}
 
 pub struct QualityFilter {
-    pub min_mean_quality: u8,
+    pub mode: QualityFilterMode,
+    /// Subtracted from stored scores to get Phred values (window and
+    /// expected-error modes)
+    pub quality_offset: u8,
+    /// Error probability per Phred score (scores above Q63 use Q63)
+    error_probabilities: [f32; 64],
+    /// Judge binned scores instead of the stored ones
+    binning: Option<BinningTable>,
 }
 
 impl QualityFilter {
+    /// Mean-quality filter; scores are compared as stored (no offset)
     pub fn new(min_mean_quality: u8) -> Self {
-        Self { min_mean_quality }
+        Self::with_mode(QualityFilterMode::MeanQuality { min_mean_quality })
+    }
+
+    /// Minimum sliding-window mean quality (Phred+33)
+    pub fn min_window(window: usize, min_quality: u8) -> Self {
+        assert!(window > 0, "Window must be at least one base");
+        Self::with_mode(QualityFilterMode::MinWindow { window, min_quality }).with_offset(33)
+    }
+
+    /// Maximum expected errors (Phred+33)
+    pub fn expected_errors(max_expected_errors: f64) -> Self {
+        Self::with_mode(QualityFilterMode::ExpectedErrors { max_expected_errors }).with_offset(33)
+    }
+
+    /// Maximum number of N bases
+    pub fn max_n(max_n: usize) -> Self {
+        Self::with_mode(QualityFilterMode::MaxN { max_n })
+    }
+
+    pub fn with_mode(mode: QualityFilterMode) -> Self {
+        let mut error_probabilities = [0.0f32; 64];
+        for (q, p) in error_probabilities.iter_mut().enumerate() {
+            *p = 10f64.powf(-(q as f64) / 10.0) as f32;
+        }
+
+        Self {
+            mode,
+            quality_offset: 0,
+            error_probabilities,
+            binning: None,
+        }
+    }
+
+    /// Set the quality encoding offset (33 for Phred+33)
+    pub fn with_offset(mut self, quality_offset: u8) -> Self {
+        self.quality_offset = quality_offset;
+        self
+    }
+
+    /// Judge reads on binned scores (Phred+33), as if the data had been
+    /// binned before filtering
+    ///
+    /// Qualities are binned per read on the fly; comparing the decisions
+    /// with an unbinned filter gives the accuracy cost of binning.
+    pub fn with_binning(mut self, binning: QualityBinning) -> Self {
+        self.binning = Some(BinningTable::new(binning));
+        self
+    }
+
+    /// The record's qualities, binned if requested
+    fn quality<'a>(&self, record: &'a SequenceRecord, simd: bool) -> Option<Cow<'a, [u8]>> {
+        let quality = record.quality.as_deref()?;
+        Some(match &self.binning {
+            Some(table) => Cow::Owned(table.binned(quality, simd)),
+            None => Cow::Borrowed(quality),
+        })
+    }
+
+    /// Whether a record passes; `None` if the mode needs qualities and the
+    /// record has none
+    fn passes(&self, record: &SequenceRecord, simd: bool) -> Option<bool> {
+        let simd = simd && cfg!(target_arch = "aarch64");
+
+        match self.mode {
+            QualityFilterMode::MeanQuality { min_mean_quality } => {
+                let quality = &*self.quality(record, simd)?;
+                let mean_quality = if simd {
+                    calculate_mean_quality_neon(quality)
+                } else {
+                    calculate_mean_quality_naive(quality)
+                };
+                Some(mean_quality >= min_mean_quality as f64)
+            }
+            QualityFilterMode::MinWindow { window, min_quality } => {
+                let quality = &*self.quality(record, simd)?;
+                if quality.is_empty() {
+                    return Some(false);
+                }
+                let window = window.min(quality.len());
+                let min_sum = if simd && window <= MAX_NEON_WINDOW {
+                    min_window_sum_neon(quality, window)
+                } else {
+                    min_window_sum_naive(quality, window)
+                };
+                // Compare sums: mean - offset >= min  <=>  sum >= (min + offset) * window
+                Some(min_sum >= (min_quality as u64 + self.quality_offset as u64) * window as u64)
+            }
+            QualityFilterMode::ExpectedErrors { max_expected_errors } => {
+                let quality = &*self.quality(record, simd)?;
+                let expected_errors = if simd {
+                    expected_errors_neon(quality, self.quality_offset, &self.error_probabilities)
+                } else {
+                    expected_errors_naive(quality, self.quality_offset, &self.error_probabilities)
+                };
+                Some(expected_errors <= max_expected_errors)
+            }
+            QualityFilterMode::MaxN { max_n } => {
+                let n_count = if simd {
+                    count_n_neon(&record.sequence)
+                } else {
+                    count_n_naive(&record.sequence)
+                };
+                Some(n_count <= max_n)
+            }
+        }
+    }
+
+    /// Filter statistics for a slice of records (sequential)
+    fn filter(&self, data: &[SequenceRecord], simd: bool) -> QualityFilterResult {
+        let mut result = QualityFilterResult::new();
+
+        for record in data {
+            result.total_sequences += 1;
+
+            match self.passes(record, simd) {
+                Some(true) => result.passed_sequences += 1,
+                Some(false) => result.filtered_sequences += 1,
+                None => {}
+            }
+        }
+
+        result
     }
 }
 
@@ -74,52 +246,25 @@ impl PrimitiveOperation for QualityFilter {
         OperationCategory::ElementWise // Filter still processes each sequence independently
     }
 
-    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
-        let mut result = QualityFilterResult::new();
-
-        for record in data {
-            result.total_sequences += 1;
-
-            if let Some(quality) = &record.quality {
-                let mean_quality = calculate_mean_quality_naive(quality);
-                if mean_quality >= self.min_mean_quality as f64 {
-                    result.passed_sequences += 1;
-                } else {
-                    result.filtered_sequences += 1;
-                }
-            }
+    fn parameters(&self) -> serde_json::Value {
+        let mut parameters = serde_json::to_value(self.mode).unwrap_or_default();
+        parameters["quality_offset"] = self.quality_offset.into();
+        if let Some(table) = &self.binning {
+            parameters["binning"] = table.binning.name().into();
         }
+        parameters
+    }
 
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        let mut result = self.filter(data, false);
         result.finalize();
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
-        #[cfg(target_arch = "aarch64")]
-        {
-            let mut result = QualityFilterResult::new();
-
-            for record in data {
-                result.total_sequences += 1;
-
-                if let Some(quality) = &record.quality {
-                    let mean_quality = calculate_mean_quality_neon(quality);
-                    if mean_quality >= self.min_mean_quality as f64 {
-                        result.passed_sequences += 1;
-                    } else {
-                        result.filtered_sequences += 1;
-                    }
-                }
-            }
-
-            result.finalize();
-            Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
-        }
-
-        #[cfg(not(target_arch = "aarch64"))]
-        {
-            self.execute_naive(data)
-        }
+        let mut result = self.filter(data, true);
+        result.finalize();
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_parallel(
@@ -127,11 +272,7 @@ impl PrimitiveOperation for QualityFilter {
         data: &[SequenceRecord],
         num_threads: usize,
     ) -> Result<OperationOutput> {
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()?;
-
-        let threshold = self.min_mean_quality;
+        let pool = crate::thread_pool::get(num_threads)?;
 
         let mut result = pool.install(|| {
             data.par_iter()
@@ -139,18 +280,11 @@ impl PrimitiveOperation for QualityFilter {
                     let mut local = QualityFilterResult::new();
                     local.total_sequences = 1;
 
-                    if let Some(quality) = &record.quality {
-                        #[cfg(target_arch = "aarch64")]
-                        let mean_quality = calculate_mean_quality_neon(quality);
-
-                        #[cfg(not(target_arch = "aarch64"))]
-                        let mean_quality = calculate_mean_quality_naive(quality);
-
-                        if mean_quality >= threshold as f64 {
-                            local.passed_sequences = 1;
-                        } else {
-                            local.filtered_sequences = 1;
-                        }
+                    // NEON per-thread
+                    match self.passes(record, true) {
+                        Some(true) => local.passed_sequences = 1,
+                        Some(false) => local.filtered_sequences = 1,
+                        None => {}
                     }
 
                     local
@@ -165,7 +299,24 @@ impl PrimitiveOperation for QualityFilter {
         });
 
         result.finalize();
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let merge = |mut a: QualityFilterResult, b: QualityFilterResult| {
+            a.add(&b);
+            a
+        };
+        let mut result = crate::chunked::reduce_neon(self, data, num_threads, batch_size, merge)?
+            .unwrap_or_else(QualityFilterResult::new);
+        result.finalize();
+
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_with_config(
@@ -174,7 +325,7 @@ impl PrimitiveOperation for QualityFilter {
         config: &HardwareConfig,
     ) -> Result<OperationOutput> {
         if config.num_threads > 1 {
-            self.execute_parallel(data, config.num_threads)
+            self.execute_threaded(data, config)
         } else if config.use_neon {
             self.execute_neon(data)
         } else {
@@ -248,6 +399,169 @@ fn calculate_mean_quality_neon(_quality: &[u8]) -> f64 {
     panic!("NEON not available on this platform");
 }
 
+// Minimum window sum (sliding window)
+fn min_window_sum_naive(quality: &[u8], window: usize) -> u64 {
+    let mut sum: u64 = quality[..window].iter().map(|&q| q as u64).sum();
+    let mut min_sum = sum;
+
+    for i in window..quality.len() {
+        sum = sum + quality[i] as u64 - quality[i - window] as u64;
+        min_sum = min_sum.min(sum);
+    }
+
+    min_sum
+}
+
+/// Largest window whose sum fits a u16 lane
+const MAX_NEON_WINDOW: usize = 257;
+
+// NEON minimum window sum: 16 window starts per step, one widening add per
+// window offset
+#[cfg(target_arch = "aarch64")]
+fn min_window_sum_neon(quality: &[u8], window: usize) -> u64 {
+    use std::arch::aarch64::*;
+
+    let starts = quality.len() - window + 1;
+    let vector_starts = starts / 16 * 16;
+
+    let mut min_sum = u64::MAX;
+    unsafe {
+        let mut min_low = vdupq_n_u16(u16::MAX);
+        let mut min_high = vdupq_n_u16(u16::MAX);
+
+        for start in (0..vector_starts).step_by(16) {
+            let mut sum_low = vdupq_n_u16(0);
+            let mut sum_high = vdupq_n_u16(0);
+            for offset in 0..window {
+                let data = vld1q_u8(quality.as_ptr().add(start + offset));
+                sum_low = vaddw_u8(sum_low, vget_low_u8(data));
+                sum_high = vaddw_high_u8(sum_high, data);
+            }
+            min_low = vminq_u16(min_low, sum_low);
+            min_high = vminq_u16(min_high, sum_high);
+        }
+
+        if vector_starts > 0 {
+            min_sum = vminvq_u16(vminq_u16(min_low, min_high)) as u64;
+        }
+    }
+
+    // Remaining window starts
+    if vector_starts < starts {
+        min_sum = min_sum.min(min_window_sum_naive(&quality[vector_starts..], window));
+    }
+
+    min_sum
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+fn min_window_sum_neon(_quality: &[u8], _window: usize) -> u64 {
+    panic!("NEON not available on this platform");
+}
+
+// Expected errors: sum of per-base error probabilities
+fn expected_errors_naive(quality: &[u8], offset: u8, probabilities: &[f32; 64]) -> f64 {
+    quality
+        .iter()
+        .map(|&q| probabilities[(q.saturating_sub(offset) as usize).min(63)] as f64)
+        .sum()
+}
+
+// NEON expected errors: each f32 probability is assembled from four byte
+// lookups (one 64-entry vqtbl4q table per byte of the float)
+#[cfg(target_arch = "aarch64")]
+fn expected_errors_neon(quality: &[u8], offset: u8, probabilities: &[f32; 64]) -> f64 {
+    use std::arch::aarch64::*;
+
+    // Byte planes of the little-endian f32 table
+    let mut planes = [[0u8; 64]; 4];
+    for (q, p) in probabilities.iter().enumerate() {
+        for (plane, byte) in planes.iter_mut().zip(p.to_le_bytes()) {
+            plane[q] = byte;
+        }
+    }
+
+    let chunks = quality.chunks_exact(16);
+    let remainder = chunks.remainder();
+
+    let mut total = unsafe {
+        let tables = [
+            vld1q_u8_x4(planes[0].as_ptr()),
+            vld1q_u8_x4(planes[1].as_ptr()),
+            vld1q_u8_x4(planes[2].as_ptr()),
+            vld1q_u8_x4(planes[3].as_ptr()),
+        ];
+        let offset = vdupq_n_u8(offset);
+        let max_q = vdupq_n_u8(63);
+        let mut sums = [vdupq_n_f32(0.0); 4];
+
+        for chunk in chunks {
+            let q = vminq_u8(vqsubq_u8(vld1q_u8(chunk.as_ptr()), offset), max_q);
+            let b0 = vqtbl4q_u8(tables[0], q);
+            let b1 = vqtbl4q_u8(tables[1], q);
+            let b2 = vqtbl4q_u8(tables[2], q);
+            let b3 = vqtbl4q_u8(tables[3], q);
+
+            // Interleave bytes into 16 f32 lanes
+            let low01 = vreinterpretq_u16_u8(vzip1q_u8(b0, b1));
+            let high01 = vreinterpretq_u16_u8(vzip2q_u8(b0, b1));
+            let low23 = vreinterpretq_u16_u8(vzip1q_u8(b2, b3));
+            let high23 = vreinterpretq_u16_u8(vzip2q_u8(b2, b3));
+
+            sums[0] = vaddq_f32(sums[0], vreinterpretq_f32_u16(vzip1q_u16(low01, low23)));
+            sums[1] = vaddq_f32(sums[1], vreinterpretq_f32_u16(vzip2q_u16(low01, low23)));
+            sums[2] = vaddq_f32(sums[2], vreinterpretq_f32_u16(vzip1q_u16(high01, high23)));
+            sums[3] = vaddq_f32(sums[3], vreinterpretq_f32_u16(vzip2q_u16(high01, high23)));
+        }
+
+        sums.iter().map(|&sum| vaddvq_f32(sum) as f64).sum::<f64>()
+    };
+
+    total += expected_errors_naive(remainder, offset, probabilities);
+    total
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+fn expected_errors_neon(_quality: &[u8], _offset: u8, _probabilities: &[f32; 64]) -> f64 {
+    panic!("NEON not available on this platform");
+}
+
+// N count (case-insensitive)
+fn count_n_naive(sequence: &[u8]) -> usize {
+    sequence.iter().filter(|&&base| base == b'N' || base == b'n').count()
+}
+
+#[cfg(target_arch = "aarch64")]
+fn count_n_neon(sequence: &[u8]) -> usize {
+    use std::arch::aarch64::*;
+
+    let vector_len = sequence.len() / 16 * 16;
+    let mut count = 0usize;
+
+    unsafe {
+        let n = vdupq_n_u8(b'N');
+        let case_mask = vdupq_n_u8(0xDF);
+
+        // u8 lanes count at most 255 chunks before flushing
+        for block in sequence[..vector_len].chunks(16 * 255) {
+            let mut counts = vdupq_n_u8(0);
+            for chunk in block.chunks_exact(16) {
+                let upper = vandq_u8(vld1q_u8(chunk.as_ptr()), case_mask);
+                // Matches are 0xFF (-1): subtracting adds one
+                counts = vsubq_u8(counts, vceqq_u8(upper, n));
+            }
+            count += vaddlvq_u8(counts) as usize;
+        }
+    }
+
+    count + count_n_naive(&sequence[vector_len..])
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+fn count_n_neon(_sequence: &[u8]) -> usize {
+    panic!("NEON not available on this platform");
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -284,8 +598,7 @@ mod tests {
 
         let result = op.execute_naive(&records).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let filter_result: QualityFilterResult = serde_json::from_value(value).unwrap();
+        if let Some(filter_result) = result.statistics::<QualityFilterResult>() {
 
             // Expected: high_qual (40), med_qual (25), mixed_qual (25) pass (3/4)
             //           low_qual (10) filtered (1/4)
@@ -306,8 +619,7 @@ mod tests {
 
         let result = op.execute_neon(&records).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let filter_result: QualityFilterResult = serde_json::from_value(value).unwrap();
+        if let Some(filter_result) = result.statistics::<QualityFilterResult>() {
 
             assert_eq!(filter_result.total_sequences, 4);
             assert_eq!(filter_result.passed_sequences, 3);
@@ -317,6 +629,108 @@ mod tests {
         }
     }
 
+    /// Phred+33 records exercising each mode
+    fn create_phred_records() -> Vec<SequenceRecord> {
+        let phred = |scores: &[u8]| scores.iter().map(|q| q + 33).collect::<Vec<u8>>();
+
+        // 40 bases of Q30 with a 5-base Q5 dip in the middle
+        let mut dip = vec![30u8; 40];
+        dip[18..23].fill(5);
+
+        vec![
+            SequenceRecord::fastq("clean".to_string(), vec![b'A'; 40], phred(&[30; 40])),
+            SequenceRecord::fastq("dip".to_string(), vec![b'C'; 40], phred(&dip)),
+            SequenceRecord::fastq("low".to_string(), vec![b'G'; 40], phred(&[10; 40])),
+            SequenceRecord::fastq("ns".to_string(), b"ACGTNNNNacgtnnACGT".to_vec(), phred(&[35; 18])),
+            SequenceRecord::fasta("no_quality".to_string(), b"ACGTN".to_vec()),
+        ]
+    }
+
+    fn passed(op: &QualityFilter, records: &[SequenceRecord]) -> [usize; 3] {
+        let counts = |output: OperationOutput| {
+            let result = output.statistics::<QualityFilterResult>().unwrap().clone();
+            (result.passed_sequences, result.filtered_sequences)
+        };
+        let naive = counts(op.execute_naive(records).unwrap());
+        assert_eq!(counts(op.execute_neon(records).unwrap()), naive);
+        assert_eq!(counts(op.execute_parallel(records, 2).unwrap()), naive);
+        [naive.0, naive.1, records.len()]
+    }
+
+    #[test]
+    fn test_quality_filter_modes() {
+        let records = create_phred_records();
+
+        // Window mean over the dip is Q5; read-wide mean is still Q27
+        assert_eq!(passed(&QualityFilter::min_window(5, 20), &records), [2, 2, 5]);
+        assert_eq!(passed(&QualityFilter::new(20 + 33), &records), [3, 1, 5]);
+
+        // Expected errors: clean 40×0.001 = 0.04, dip ≈ 1.62, low 40×0.1 = 4, ns ≈ 0.006
+        assert_eq!(passed(&QualityFilter::expected_errors(1.0), &records), [2, 2, 5]);
+        assert_eq!(passed(&QualityFilter::expected_errors(2.0), &records), [3, 1, 5]);
+
+        // MaxN does not need qualities: ns has 6 N's, no_quality has 1
+        assert_eq!(passed(&QualityFilter::max_n(0), &records), [3, 2, 5]);
+        assert_eq!(passed(&QualityFilter::max_n(6), &records), [5, 0, 5]);
+    }
+
+    #[test]
+    fn test_quality_filter_binned() {
+        use asbb_core::quality_binning::bin_records;
+
+        let records = create_phred_records();
+        let binned = bin_records(&records, &BinningTable::new(QualityBinning::Illumina4), false);
+        let filters = || {
+            [
+                QualityFilter::new(20 + 33),
+                QualityFilter::min_window(5, 20),
+                QualityFilter::expected_errors(1.0),
+            ]
+        };
+
+        for (plain, binning) in filters().into_iter().zip(filters()) {
+            let binning = binning.with_binning(QualityBinning::Illumina4);
+            assert_eq!(passed(&binning, &records), passed(&plain, &binned));
+            assert_eq!(binning.execute_neon(&records).unwrap(), plain.execute_naive(&binned).unwrap());
+            assert_eq!(binning.parameters()["binning"], "illumina4");
+        }
+    }
+
+    #[test]
+    fn test_quality_filter_neon_kernels() {
+        let quality: Vec<u8> = (0..1000).map(|i| 33 + ((i * 7 + i / 13) % 45) as u8).collect();
+        let op = QualityFilter::expected_errors(1.0);
+        let naive = expected_errors_naive(&quality, 33, &op.error_probabilities);
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            let neon = expected_errors_neon(&quality, 33, &op.error_probabilities);
+            assert!((naive - neon).abs() < 1e-4 * naive);
+
+            for window in [1, 5, 16, 17, 64] {
+                assert_eq!(min_window_sum_neon(&quality, window), min_window_sum_naive(&quality, window));
+            }
+
+            let sequence: Vec<u8> = (0..5000).map(|i| b"ACGTNn"[(i * 11) % 6]).collect();
+            assert_eq!(count_n_neon(&sequence), count_n_naive(&sequence));
+        }
+
+        assert!(naive > 0.0);
+        assert_eq!(min_window_sum_naive(&[1, 2, 3, 0, 0, 4], 2), 0);
+    }
+
+    #[test]
+    fn test_quality_filter_parameters() {
+        let parameters = QualityFilter::expected_errors(1.5).parameters();
+        assert_eq!(parameters["mode"], "expected_errors");
+        assert_eq!(parameters["max_expected_errors"], 1.5);
+        assert_eq!(parameters["quality_offset"], 33);
+
+        let parameters = QualityFilter::new(20).parameters();
+        assert_eq!(parameters["mode"], "mean_quality");
+        assert_eq!(parameters["min_mean_quality"], 20);
+    }
+
     #[test]
     fn test_quality_filter_parallel() {
         let records = create_test_records();
@@ -324,8 +738,7 @@ mod tests {
 
         let result = op.execute_parallel(&records, 2).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let filter_result: QualityFilterResult = serde_json::from_value(value).unwrap();
+        if let Some(filter_result) = result.statistics::<QualityFilterResult>() {
 
             assert_eq!(filter_result.total_sequences, 4);
             assert_eq!(filter_result.passed_sequences, 3);
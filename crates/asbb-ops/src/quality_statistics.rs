@@ -25,8 +25,29 @@
 //! - Collect all quality scores at that position across sequences
 //! - Compute: mean, median, Q1, Q3
 //! - Used for quality profile visualization and QC
+//!
+//! # Columnar Layout
+//!
+//! Record-major input forces a gather of every read per position.
+//! [`QualityStatistics::execute_columnar`] runs on [`QualityColumns`]
+//! (positions × reads) instead, where each position is one contiguous slice
+//! (see `asbb-pilot-columnar` for the comparison including transpose cost).
+//!
+//! # Binned Scores
+//!
+//! [`BinnedQualityStatistics`] bins scores (Illumina 8/4-level) and replaces
+//! the per-position sort with a bin-code histogram (`asbb-pilot-binning`
+//! measures the accuracy-vs-throughput trade-off).
+//!
+//! # Single Pass
+//!
+//! [`StreamingQualityStatistics`] computes the same statistics without the
+//! gather pass, from per-position histograms of raw scores (`asbb strategies`
+//! compares the two strategies across hardware configs).
 
 use anyhow::Result;
+use asbb_core::columnar::QualityColumns;
+use asbb_core::quality_binning::{BinningTable, QualityBinning};
 use asbb_core::{OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -286,6 +307,59 @@ impl QualityStatistics {
 
         Ok(stats)
     }
+
+    /// Quality statistics over column-major storage (NEON where available)
+    ///
+    /// Same result as [`PrimitiveOperation::execute_neon`] on the records the
+    /// columns were built from, without the per-position gather: each column
+    /// is already contiguous and only needs copying into a scratch buffer for
+    /// the sort.
+    pub fn execute_columnar(&self, columns: &QualityColumns) -> Result<OperationOutput> {
+        if columns.num_positions() == 0 {
+            anyhow::bail!("No quality scores found in sequences");
+        }
+
+        let mut scratch = Vec::new();
+        let stats: Vec<PositionStats> = columns
+            .columns()
+            .map(|column| {
+                scratch.clear();
+                scratch.extend_from_slice(column);
+                self.position_stats_neon(&mut scratch)
+            })
+            .collect();
+
+        Ok(OperationOutput::typed(QualityStatisticsResult {
+            num_positions: stats.len(),
+            num_sequences: columns.num_reads(),
+            per_position: stats,
+        }))
+    }
+
+    /// [`Self::execute_columnar`] with positions split across `num_threads`
+    pub fn execute_columnar_parallel(
+        &self,
+        columns: &QualityColumns,
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        if columns.num_positions() == 0 {
+            anyhow::bail!("No quality scores found in sequences");
+        }
+
+        let pool = crate::thread_pool::get(num_threads)?;
+        let stats: Vec<PositionStats> = pool.install(|| {
+            (0..columns.num_positions())
+                .into_par_iter()
+                .map(|p| self.position_stats_neon(&mut columns.column(p).to_vec()))
+                .collect()
+        });
+
+        Ok(OperationOutput::typed(QualityStatisticsResult {
+            num_positions: stats.len(),
+            num_sequences: columns.num_reads(),
+            per_position: stats,
+        }))
+    }
 }
 
 impl Default for QualityStatistics {
@@ -312,7 +386,7 @@ impl PrimitiveOperation for QualityStatistics {
             per_position: stats,
         };
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
@@ -324,7 +398,7 @@ impl PrimitiveOperation for QualityStatistics {
             per_position: stats,
         };
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_parallel(
@@ -332,9 +406,7 @@ impl PrimitiveOperation for QualityStatistics {
         data: &[SequenceRecord],
         num_threads: usize,
     ) -> Result<OperationOutput> {
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()?;
+        let pool = crate::thread_pool::get(num_threads)?;
 
         pool.install(|| {
             let max_len = data
@@ -375,7 +447,7 @@ impl PrimitiveOperation for QualityStatistics {
                 per_position: stats,
             };
 
-            Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+            Ok(OperationOutput::typed(result))
         })
     }
 
@@ -389,7 +461,202 @@ impl PrimitiveOperation for QualityStatistics {
             per_position: stats,
         };
 
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
+    }
+}
+
+// ============================================================================
+// Binned Scores
+// ============================================================================
+
+/// Quality statistics over binned scores
+///
+/// Every score is binned ([`BinningTable`], Phred+33) and counted into a
+/// per-position histogram of bin codes, replacing the gather-and-sort of
+/// [`QualityStatistics`]. The statistics are exact for the binned data
+/// (identical to [`QualityStatistics`] on `bin_records(data)`); comparing
+/// them with [`QualityStatistics`] on the original data gives the accuracy
+/// cost of the reduced precision.
+pub struct BinnedQualityStatistics {
+    table: BinningTable,
+}
+
+impl BinnedQualityStatistics {
+    pub fn new(binning: QualityBinning) -> Self {
+        Self {
+            table: BinningTable::new(binning),
+        }
+    }
+
+    pub fn binning(&self) -> QualityBinning {
+        self.table.binning
+    }
+
+    /// Per-position bin-code counts, `counts[pos * num_bins + code]`
+    fn histograms(&self, data: &[SequenceRecord], max_len: usize, simd: bool) -> Vec<u64> {
+        let num_bins = self.table.binning.num_bins();
+        let mut counts = vec![0u64; max_len * num_bins];
+        let mut codes = Vec::new();
+
+        for quality in data.iter().filter_map(|r| r.quality.as_deref()) {
+            codes.clear();
+            codes.extend_from_slice(quality);
+            self.table.encode_in_place(&mut codes, simd);
+            for (row, &code) in counts.chunks_exact_mut(num_bins).zip(&codes) {
+                row[code as usize] += 1;
+            }
+        }
+
+        counts
+    }
+
+    fn result(&self, data: &[SequenceRecord], counts: &[u64]) -> QualityStatisticsResult {
+        let levels = self.table.levels();
+        let stats: Vec<PositionStats> = counts
+            .chunks_exact(levels.len())
+            .map(|row| histogram_stats(row, &levels))
+            .collect();
+
+        QualityStatisticsResult {
+            num_positions: stats.len(),
+            num_sequences: data.len(),
+            per_position: stats,
+        }
+    }
+
+    fn compute(&self, data: &[SequenceRecord], simd: bool) -> Result<OperationOutput> {
+        let max_len = max_quality_len(data)?;
+        let counts = self.histograms(data, max_len, simd);
+        Ok(OperationOutput::typed(self.result(data, &counts)))
+    }
+}
+
+impl PrimitiveOperation for BinnedQualityStatistics {
+    fn name(&self) -> &str {
+        "quality_statistics_binned"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Aggregation
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({ "binning": self.table.binning.name() })
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        self.compute(data, false)
+    }
+
+    fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        self.compute(data, true)
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let max_len = max_quality_len(data)?;
+        let pool = crate::thread_pool::get(num_threads)?;
+        let chunk_size = data.len().div_ceil(num_threads.max(1)).max(1);
+
+        let counts = pool.install(|| {
+            data.par_chunks(chunk_size)
+                .map(|chunk| self.histograms(chunk, max_len, true))
+                .reduce(
+                    || vec![0u64; max_len * self.table.binning.num_bins()],
+                    |mut a, b| {
+                        a.iter_mut().zip(&b).for_each(|(x, y)| *x += y);
+                        a
+                    },
+                )
+        });
+
+        Ok(OperationOutput::typed(self.result(data, &counts)))
+    }
+}
+
+/// Single-pass quality statistics
+///
+/// The streaming strategy for the same statistics as [`QualityStatistics`]:
+/// one pass adds every score to a per-position histogram of raw score
+/// bytes, and quantiles are read off the histograms, so nothing is gathered
+/// or sorted. Results are identical to the gather pass.
+pub struct StreamingQualityStatistics;
+
+impl StreamingQualityStatistics {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Per-position score counts, `counts[pos * 256 + score]`
+    fn histograms(data: &[SequenceRecord], max_len: usize) -> Vec<u64> {
+        let mut counts = vec![0u64; max_len * 256];
+        for quality in data.iter().filter_map(|r| r.quality.as_deref()) {
+            for (row, &score) in counts.chunks_exact_mut(256).zip(quality) {
+                row[score as usize] += 1;
+            }
+        }
+        counts
+    }
+
+    fn result(data: &[SequenceRecord], counts: &[u64]) -> QualityStatisticsResult {
+        let levels: Vec<u8> = (0..=u8::MAX).collect();
+        let stats: Vec<PositionStats> =
+            counts.chunks_exact(256).map(|row| histogram_stats(row, &levels)).collect();
+
+        QualityStatisticsResult {
+            num_positions: stats.len(),
+            num_sequences: data.len(),
+            per_position: stats,
+        }
+    }
+}
+
+impl Default for StreamingQualityStatistics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrimitiveOperation for StreamingQualityStatistics {
+    fn name(&self) -> &str {
+        "quality_statistics_streaming"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Aggregation
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        let max_len = max_quality_len(data)?;
+        let counts = Self::histograms(data, max_len);
+        Ok(OperationOutput::typed(Self::result(data, &counts)))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let max_len = max_quality_len(data)?;
+        let pool = crate::thread_pool::get(num_threads)?;
+        let chunk_size = data.len().div_ceil(num_threads.max(1)).max(1);
+
+        let counts = pool.install(|| {
+            data.par_chunks(chunk_size)
+                .map(|chunk| Self::histograms(chunk, max_len))
+                .reduce(
+                    || vec![0u64; max_len * 256],
+                    |mut a, b| {
+                        a.iter_mut().zip(&b).for_each(|(x, y)| *x += y);
+                        a
+                    },
+                )
+        });
+
+        Ok(OperationOutput::typed(Self::result(data, &counts)))
     }
 }
 
@@ -397,6 +664,63 @@ impl PrimitiveOperation for QualityStatistics {
 // Helper Functions
 // ============================================================================
 
+/// Length of the longest quality string (fails if there are none)
+fn max_quality_len(data: &[SequenceRecord]) -> Result<usize> {
+    let max_len = data
+        .iter()
+        .filter_map(|r| r.quality.as_ref().map(|q| q.len()))
+        .max()
+        .unwrap_or(0);
+
+    if max_len == 0 {
+        anyhow::bail!("No quality scores found in sequences");
+    }
+    Ok(max_len)
+}
+
+/// Statistics of one position from its score counts (`counts[i]` scores of
+/// `levels[i]`, levels ascending)
+fn histogram_stats(counts: &[u64], levels: &[u8]) -> PositionStats {
+    let count: u64 = counts.iter().sum();
+    if count == 0 {
+        return PositionStats::default();
+    }
+
+    let sum: u64 = counts.iter().zip(levels).map(|(&n, &level)| n * level as u64).sum();
+
+    // Score at a rank of the sorted column
+    let at_rank = |rank: usize| {
+        let mut seen = 0u64;
+        for (&n, &level) in counts.iter().zip(levels) {
+            seen += n;
+            if seen > rank as u64 {
+                return level as f64;
+            }
+        }
+        levels[levels.len() - 1] as f64
+    };
+    // Same interpolation as `percentile` over the sorted scores
+    let quantile = |percentile: f64| {
+        let index = (percentile / 100.0) * (count - 1) as f64;
+        let lower = index.floor() as usize;
+        let upper = index.ceil() as usize;
+        if lower == upper {
+            at_rank(lower)
+        } else {
+            let weight = index - lower as f64;
+            (1.0 - weight) * at_rank(lower) + weight * at_rank(upper)
+        }
+    };
+
+    PositionStats {
+        mean: sum as f64 / count as f64,
+        median: quantile(50.0),
+        q1: quantile(25.0),
+        q3: quantile(75.0),
+        count: count as usize,
+    }
+}
+
 /// Compute percentile from sorted data
 fn percentile(sorted_data: &[u8], percentile: f64) -> f64 {
     if sorted_data.is_empty() {
@@ -420,7 +744,7 @@ fn percentile(sorted_data: &[u8], percentile: f64) -> f64 {
 // ============================================================================
 
 /// Statistics for a single position
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct PositionStats {
     /// Mean quality score at this position
     pub mean: f64,
@@ -435,7 +759,7 @@ pub struct PositionStats {
 }
 
 /// Result of quality statistics computation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct QualityStatisticsResult {
     /// Number of positions analyzed
     pub num_positions: usize,
@@ -536,8 +860,7 @@ mod tests {
 
         let result = op.execute_naive(&sequences).unwrap();
 
-        if let OperationOutput::Statistics(json) = result {
-            let result: QualityStatisticsResult = serde_json::from_value(json).unwrap();
+        if let Some(result) = result.statistics::<QualityStatisticsResult>() {
 
             assert_eq!(result.num_positions, 1);
             assert_eq!(result.num_sequences, 3);
@@ -563,8 +886,7 @@ mod tests {
 
         let result = op.execute_naive(&sequences).unwrap();
 
-        if let OperationOutput::Statistics(json) = result {
-            let result: QualityStatisticsResult = serde_json::from_value(json).unwrap();
+        if let Some(result) = result.statistics::<QualityStatisticsResult>() {
 
             assert_eq!(result.num_positions, 3);
             assert_eq!(result.num_sequences, 3);
@@ -600,8 +922,7 @@ mod tests {
 
         let result = op.execute_naive(&sequences).unwrap();
 
-        if let OperationOutput::Statistics(json) = result {
-            let result: QualityStatisticsResult = serde_json::from_value(json).unwrap();
+        if let Some(result) = result.statistics::<QualityStatisticsResult>() {
 
             assert_eq!(result.num_positions, 3); // Max length
 
@@ -632,8 +953,7 @@ mod tests {
 
         let result = op.execute_naive(&sequences).unwrap();
 
-        if let OperationOutput::Statistics(json) = result {
-            let result: QualityStatisticsResult = serde_json::from_value(json).unwrap();
+        if let Some(result) = result.statistics::<QualityStatisticsResult>() {
 
             let low_positions = result.low_quality_positions(30.0);
             assert_eq!(low_positions, vec![1]); // Position 1 has mean < 30
@@ -658,9 +978,9 @@ mod tests {
         let neon_result = op.execute_neon(&sequences).unwrap();
 
         match (naive_result, neon_result) {
-            (OperationOutput::Statistics(naive_json), OperationOutput::Statistics(neon_json)) => {
-                let naive: QualityStatisticsResult = serde_json::from_value(naive_json).unwrap();
-                let neon: QualityStatisticsResult = serde_json::from_value(neon_json).unwrap();
+            (OperationOutput::Typed(naive_typed), OperationOutput::Typed(neon_typed)) => {
+                let naive: &QualityStatisticsResult = naive_typed.downcast_ref().unwrap();
+                let neon: &QualityStatisticsResult = neon_typed.downcast_ref().unwrap();
 
                 assert_eq!(naive.num_positions, neon.num_positions);
                 assert_eq!(naive.num_sequences, neon.num_sequences);
@@ -685,6 +1005,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_columnar_matches_records() {
+        let op = QualityStatistics::new();
+
+        let sequences: Vec<SequenceRecord> = (0..100)
+            .map(|i| {
+                let quality: Vec<u8> = (0..100 + i % 50).map(|j| 33 + ((i * 7 + j) % 40) as u8).collect();
+                create_test_record(&format!("seq{}", i), &quality)
+            })
+            .collect();
+        let columns = QualityColumns::from_records(&sequences);
+
+        let expected = op.execute_neon(&sequences).unwrap();
+        assert_eq!(op.execute_columnar(&columns).unwrap(), expected);
+        assert_eq!(op.execute_columnar_parallel(&columns, 4).unwrap(), expected);
+        assert!(op.execute_columnar(&QualityColumns::from_records(&[])).is_err());
+    }
+
+    #[test]
+    fn test_binned_matches_statistics_of_binned_records() {
+        use asbb_core::quality_binning::bin_records;
+
+        let sequences: Vec<SequenceRecord> = (0..101)
+            .map(|i| {
+                let quality: Vec<u8> = (0..100 + i % 50).map(|j| 33 + ((i * 7 + j) % 42) as u8).collect();
+                create_test_record(&format!("seq{}", i), &quality)
+            })
+            .collect();
+
+        for binning in QualityBinning::ALL {
+            let op = BinnedQualityStatistics::new(binning);
+            let binned = bin_records(&sequences, &BinningTable::new(binning), false);
+            let expected = QualityStatistics::new().execute_naive(&binned).unwrap();
+
+            assert_eq!(op.execute_naive(&sequences).unwrap(), expected);
+            assert_eq!(op.execute_neon(&sequences).unwrap(), expected);
+            assert_eq!(op.execute_parallel(&sequences, 4).unwrap(), expected);
+            assert_eq!(op.execute_naive(&binned).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_streaming_matches_gather() {
+        let op = StreamingQualityStatistics::new();
+
+        let sequences: Vec<SequenceRecord> = (0..101)
+            .map(|i| {
+                let quality: Vec<u8> = (0..100 + i % 50).map(|j| 33 + ((i * 7 + j) % 42) as u8).collect();
+                create_test_record(&format!("seq{}", i), &quality)
+            })
+            .collect();
+
+        let expected = QualityStatistics::new().execute_naive(&sequences).unwrap();
+        assert_eq!(op.execute_naive(&sequences).unwrap(), expected);
+        assert_eq!(op.execute_parallel(&sequences, 4).unwrap(), expected);
+        assert!(op.execute_naive(&[]).is_err());
+    }
+
     #[test]
     fn test_parallel_execution() {
         let op = QualityStatistics::new();
@@ -700,10 +1078,9 @@ mod tests {
         let parallel_result = op.execute_parallel(&sequences, 4).unwrap();
 
         match (naive_result, parallel_result) {
-            (OperationOutput::Statistics(naive_json), OperationOutput::Statistics(parallel_json)) => {
-                let naive: QualityStatisticsResult = serde_json::from_value(naive_json).unwrap();
-                let parallel: QualityStatisticsResult =
-                    serde_json::from_value(parallel_json).unwrap();
+            (OperationOutput::Typed(naive_typed), OperationOutput::Typed(parallel_typed)) => {
+                let naive: &QualityStatisticsResult = naive_typed.downcast_ref().unwrap();
+                let parallel: &QualityStatisticsResult = parallel_typed.downcast_ref().unwrap();
 
                 assert_eq!(naive.num_positions, parallel.num_positions);
                 assert_eq!(naive.num_sequences, parallel.num_sequences);
@@ -0,0 +1,684 @@
+//! Read Mapping Operation (seed-and-extend)
+//!
+//! A simplified short-read mapper: minimizer seeds of each read are looked
+//! up in an index of a small reference, seed hits vote for a diagonal, and
+//! the best candidate diagonals are extended with banded edit-distance
+//! alignment. Unlike the Level-1 primitives this is a Level-2 composite
+//! workload (hashing, scattered index lookups, sorting and DP for every
+//! read), used to test whether the configs that win on the primitives also
+//! win on a realistic task.
+//!
+//! # Operation Characteristics
+//! - **Category**: Search
+//! - **Complexity**: 0.75 (hash lookups, branchy voting, DP extension)
+//! - **Output**: Mapped/unmapped counts, strand split, edit distance histogram
+//! - **NEON benefit**: Extension only (one band row per 128-bit vector);
+//!   seeding is scalar and lookup-bound
+//!
+//! # Implementation Notes
+//! - Seeds: canonical (k, w) minimizers (k = 15, w = 10) ordered by a
+//!   splitmix64 hash, so low-complexity k-mers are not always chosen; seeds
+//!   occurring more than `max_occurrences` times in the reference are ignored
+//! - Candidates: hits cluster by (contig, strand, diagonal); the
+//!   `max_candidates` clusters with the most seeds (at least `min_seeds`)
+//!   are extended
+//! - Extension: semi-global edit distance (read fully aligned, free ends in
+//!   the reference) within ±[`EXTENSION_BAND`] diagonals of the candidate.
+//!   Naive is scalar; NEON keeps a row of the band in one `uint8x16_t` and
+//!   resolves the in-row dependency with a log-step prefix minimum. Both cap
+//!   distances at 255, so they agree exactly
+//! - A read maps if its best candidate is within `max_error_rate` edits per
+//!   base. Reads whose indels drift further than the band (ONT) do not map
+//! - The default reference is a synthetic 256 kb genome. Reads of the
+//!   standard random datasets are not drawn from it, so they exercise
+//!   seeding and mostly go unmapped; build the operation with
+//!   [`ReadMapping::new`] on the genome the reads were simulated from
+//!   (`asbb_datagen::simulate`) for a mapped workload.
+
+use crate::kmer_spectrum::mix64;
+use crate::{HardwareConfig, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Diagonals searched on either side of a candidate (2 × 7 + 1 lanes fit
+/// one NEON vector)
+pub const EXTENSION_BAND: usize = 7;
+
+/// Cells per band row
+const BAND_WIDTH: usize = 2 * EXTENSION_BAND + 1;
+
+/// Edit distance cap of the extension kernels (saturating u8 lanes)
+const MAX_DISTANCE: u32 = u8::MAX as u32;
+
+/// Edit distances above this share the last histogram bin
+pub const MAX_HISTOGRAM_EDITS: usize = 32;
+
+/// Length of the default synthetic reference
+pub const DEFAULT_REFERENCE_LENGTH: usize = 256 * 1024;
+
+/// Reference bytes outside a contig or not ACGT (never match a read base)
+const SENTINEL: u8 = b'#';
+
+/// A minimizer: hashed canonical k-mer, start in the sequence, and whether
+/// the canonical k-mer is the reverse complement of the forward one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Minimizer {
+    hash: u64,
+    position: u32,
+    reverse: bool,
+}
+
+fn base_code(base: u8) -> Option<u64> {
+    match base {
+        b'A' | b'a' => Some(0),
+        b'C' | b'c' => Some(1),
+        b'G' | b'g' => Some(2),
+        b'T' | b't' => Some(3),
+        _ => None,
+    }
+}
+
+/// (k, w) minimizers of `seq` (k-mers with non-ACGT bases are skipped)
+///
+/// Sequences with fewer than `w` k-mers form a single window.
+fn minimizers(seq: &[u8], k: usize, w: usize) -> Vec<Minimizer> {
+    let mask = (1u64 << (2 * k)) - 1;
+    let shift = 2 * (k - 1);
+    let (mut forward, mut reverse) = (0u64, 0u64);
+    let mut valid = 0; // ACGT bases ending at this position
+
+    let mut kmers = Vec::with_capacity(seq.len());
+    for (i, &base) in seq.iter().enumerate() {
+        match base_code(base) {
+            Some(code) => {
+                forward = ((forward << 2) | code) & mask;
+                reverse = (reverse >> 2) | ((3 - code) << shift);
+                valid += 1;
+            }
+            None => valid = 0,
+        }
+        if i + 1 >= k {
+            // Odd k: a k-mer is never its own reverse complement
+            kmers.push((valid >= k).then(|| Minimizer {
+                hash: mix64(forward.min(reverse)),
+                position: (i + 1 - k) as u32,
+                reverse: reverse < forward,
+            }));
+        }
+    }
+
+    let mut result: Vec<Minimizer> = Vec::new();
+    for window in kmers.windows(w.min(kmers.len()).max(1)) {
+        // First of equal hashes, so the choice is deterministic
+        if let Some(&best) = window.iter().flatten().min_by_key(|m| m.hash) {
+            if result.last() != Some(&best) {
+                result.push(best);
+            }
+        }
+    }
+    result
+}
+
+fn complement(base: u8) -> u8 {
+    match base {
+        b'A' => b'T',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'T' => b'A',
+        other => other,
+    }
+}
+
+/// Read mapping operation against an indexed reference
+pub struct ReadMapping {
+    /// Contigs, upper case ACGT with [`SENTINEL`] for anything else
+    contigs: Vec<Vec<u8>>,
+    /// Minimizer hash → (contig, position, reverse) in the reference
+    seeds: HashMap<u64, Vec<(u32, u32, bool)>>,
+    /// Minimizer k-mer size (odd, ≤ 31)
+    k: usize,
+    /// Minimizer window (k-mers)
+    w: usize,
+    /// Seed hits a candidate needs
+    pub min_seeds: usize,
+    /// Candidates extended per read
+    pub max_candidates: usize,
+    /// Seeds with more reference occurrences are ignored (repeats)
+    pub max_occurrences: usize,
+    /// Edits per read base allowed for a mapping
+    pub max_error_rate: f64,
+}
+
+impl ReadMapping {
+    /// Index `contigs` with (15, 10) minimizers
+    pub fn new(contigs: Vec<Vec<u8>>) -> Self {
+        Self::with_minimizers(contigs, 15, 10)
+    }
+
+    /// Index `contigs` with (k, w) minimizers
+    pub fn with_minimizers(contigs: Vec<Vec<u8>>, k: usize, w: usize) -> Self {
+        assert!(k % 2 == 1 && (3..=31).contains(&k), "Minimizer k must be odd and 3-31");
+        assert!(w >= 1, "Minimizer window must be at least 1");
+
+        let contigs: Vec<Vec<u8>> = contigs
+            .into_iter()
+            .map(|contig| {
+                contig
+                    .iter()
+                    .map(|&base| match base.to_ascii_uppercase() {
+                        b @ (b'A' | b'C' | b'G' | b'T') => b,
+                        _ => SENTINEL,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut seeds: HashMap<u64, Vec<(u32, u32, bool)>> = HashMap::new();
+        for (index, contig) in contigs.iter().enumerate() {
+            for seed in minimizers(contig, k, w) {
+                let occurrence = (index as u32, seed.position, seed.reverse);
+                seeds.entry(seed.hash).or_default().push(occurrence);
+            }
+        }
+
+        Self {
+            contigs,
+            seeds,
+            k,
+            w,
+            min_seeds: 2,
+            max_candidates: 3,
+            max_occurrences: 64,
+            max_error_rate: 0.1,
+        }
+    }
+
+    pub fn with_max_error_rate(mut self, max_error_rate: f64) -> Self {
+        self.max_error_rate = max_error_rate;
+        self
+    }
+
+    /// Synthetic reference of `length` uniformly random bases (deterministic)
+    pub fn synthetic_reference(length: usize, seed: u64) -> Vec<u8> {
+        (0..length as u64).map(|i| b"ACGT"[(mix64(seed ^ i) & 3) as usize]).collect()
+    }
+
+    /// Total reference length
+    pub fn reference_length(&self) -> usize {
+        self.contigs.iter().map(Vec::len).sum()
+    }
+
+    /// Candidate (contig, reverse, diagonal) alignments of a read, best first
+    ///
+    /// The diagonal is the reference position of the (oriented) read start.
+    fn candidates(&self, seq: &[u8], result: &mut ReadMappingResult) -> Vec<(u32, bool, i64)> {
+        let m = seq.len();
+        let mut hits: Vec<(u32, bool, i64)> = Vec::new();
+        for seed in minimizers(seq, self.k, self.w) {
+            let Some(occurrences) = self.seeds.get(&seed.hash) else {
+                continue;
+            };
+            if occurrences.len() > self.max_occurrences {
+                continue;
+            }
+            for &(contig, position, reverse) in occurrences {
+                // Opposite canonical orientation: the read maps reverse complemented
+                let read_reverse = reverse != seed.reverse;
+                let read_position = if read_reverse {
+                    m - self.k - seed.position as usize
+                } else {
+                    seed.position as usize
+                };
+                hits.push((contig, read_reverse, position as i64 - read_position as i64));
+            }
+        }
+        result.seed_hits += hits.len();
+        hits.sort_unstable();
+
+        // Every run of hits on one strand of a contig within the band
+        let band = EXTENSION_BAND as i64;
+        let mut clusters: Vec<(usize, u32, bool, i64)> = Vec::new();
+        let mut end = 0;
+        for start in 0..hits.len() {
+            end = end.max(start + 1);
+            let (contig, reverse, diagonal) = hits[start];
+            while end < hits.len()
+                && hits[end].0 == contig
+                && hits[end].1 == reverse
+                && hits[end].2 - diagonal <= band
+            {
+                end += 1;
+            }
+            if end - start >= self.min_seeds {
+                let median = hits[start + (end - start) / 2].2;
+                clusters.push((end - start, contig, reverse, median));
+            }
+        }
+        clusters.sort_by(|a, b| b.0.cmp(&a.0).then((a.1, a.2, a.3).cmp(&(b.1, b.2, b.3))));
+
+        // Overlapping runs describe the same alignment: keep the best
+        let mut chosen: Vec<(u32, bool, i64)> = Vec::new();
+        for (_, contig, reverse, diagonal) in clusters {
+            if chosen.len() == self.max_candidates {
+                break;
+            }
+            let overlaps = chosen
+                .iter()
+                .any(|&(c, r, d)| c == contig && r == reverse && (d - diagonal).abs() <= band);
+            if !overlaps {
+                chosen.push((contig, reverse, diagonal));
+            }
+        }
+        chosen
+    }
+
+    /// Reference bases a candidate is extended against: the read's span
+    /// plus the band on either side, padded for vector loads
+    fn window(&self, contig: u32, diagonal: i64, read_len: usize) -> Vec<u8> {
+        let contig = &self.contigs[contig as usize];
+        let start = diagonal - EXTENSION_BAND as i64;
+        (0..(read_len + 2 * EXTENSION_BAND + 16) as i64)
+            .map(|offset| {
+                usize::try_from(start + offset)
+                    .ok()
+                    .and_then(|position| contig.get(position).copied())
+                    .unwrap_or(SENTINEL)
+            })
+            .collect()
+    }
+
+    /// Seed, vote and extend one read into `result`
+    fn map_read(&self, sequence: &[u8], simd: bool, result: &mut ReadMappingResult) {
+        result.total_sequences += 1;
+        let read = sequence.to_ascii_uppercase();
+        let candidates = if read.len() >= self.k {
+            self.candidates(&read, result)
+        } else {
+            Vec::new()
+        };
+
+        let max_edits = (read.len() as f64 * self.max_error_rate).ceil() as u32;
+        let mut reverse_complement = None;
+        let mut best: Option<(u32, bool)> = None;
+        for (contig, reverse, diagonal) in candidates {
+            let oriented = if reverse {
+                reverse_complement
+                    .get_or_insert_with(|| read.iter().rev().map(|&b| complement(b)).collect())
+            } else {
+                &read
+            };
+            let window = self.window(contig, diagonal, oriented.len());
+            let distance = if simd {
+                band_distance_neon(oriented, &window)
+            } else {
+                band_distance(oriented, &window)
+            };
+            result.extensions += 1;
+            if distance <= max_edits && best.is_none_or(|(d, _)| distance < d) {
+                best = Some((distance, reverse));
+            }
+        }
+
+        match best {
+            Some((distance, reverse)) => {
+                result.mapped += 1;
+                result.reverse_strand += usize::from(reverse);
+                result.edit_histogram[(distance as usize).min(MAX_HISTOGRAM_EDITS)] += 1;
+            }
+            None => result.unmapped += 1,
+        }
+    }
+
+    /// Map a slice of reads on the calling thread
+    pub fn map_reads(&self, data: &[SequenceRecord], simd: bool) -> ReadMappingResult {
+        let mut result = ReadMappingResult::new();
+        for record in data {
+            self.map_read(&record.sequence, simd, &mut result);
+        }
+        result
+    }
+}
+
+impl Default for ReadMapping {
+    /// Synthetic 256 kb reference (seed 42)
+    fn default() -> Self {
+        Self::new(vec![Self::synthetic_reference(DEFAULT_REFERENCE_LENGTH, 42)])
+    }
+}
+
+// ============================================================================
+// Extension kernels
+// ============================================================================
+
+/// Semi-global edit distance of `read` against `window`: read fully
+/// aligned, starting and ending anywhere in the band
+///
+/// Lane `d` of row `i` is the cell (read prefix `i`, window prefix `i + d`),
+/// so the band covers diagonals `0..BAND_WIDTH` of the window, which starts
+/// [`EXTENSION_BAND`] bases before the candidate. Capped at 255 like the NEON
+/// kernel's saturating lanes.
+fn band_distance(read: &[u8], window: &[u8]) -> u32 {
+    let mut row = [0u32; BAND_WIDTH]; // free start
+    for (i, &base) in read.iter().enumerate() {
+        let mut next = [0u32; BAND_WIDTH];
+        for d in 0..BAND_WIDTH {
+            let diagonal = row[d] + u32::from(window[i + d] != base);
+            let up = row.get(d + 1).map_or(MAX_DISTANCE, |&cell| cell + 1);
+            let left = if d > 0 { next[d - 1] + 1 } else { MAX_DISTANCE };
+            next[d] = diagonal.min(up).min(left).min(MAX_DISTANCE);
+        }
+        row = next;
+    }
+    row.into_iter().min().unwrap_or(MAX_DISTANCE)
+}
+
+/// [`band_distance`] with one band row per `uint8x16_t` (lane 15 unused)
+#[cfg(target_arch = "aarch64")]
+fn band_distance_neon(read: &[u8], window: &[u8]) -> u32 {
+    use std::arch::aarch64::*;
+
+    assert!(window.len() >= read.len() + 16);
+    let mut outside_lanes = [0u8; 16];
+    outside_lanes[BAND_WIDTH..].fill(u8::MAX);
+
+    unsafe {
+        let max = vdupq_n_u8(u8::MAX);
+        let one = vdupq_n_u8(1);
+        let outside = vld1q_u8(outside_lanes.as_ptr());
+
+        let mut row = outside; // free start
+        for (i, &base) in read.iter().enumerate() {
+            // SAFETY: i + 16 ≤ window.len() (asserted above)
+            let text = vld1q_u8(window.as_ptr().add(i));
+            let mismatch = vandq_u8(vmvnq_u8(vceqq_u8(text, vdupq_n_u8(base))), one);
+
+            let diagonal = vqaddq_u8(row, mismatch);
+            let up = vqaddq_u8(vextq_u8::<1>(row, max), one);
+            let mut next = vorrq_u8(vminq_u8(diagonal, up), outside);
+
+            // Left neighbours: next[d] = min over e ≤ d of next[e] + (d - e),
+            // in log steps of lanes shifted up by 1, 2, 4, 8
+            next = vminq_u8(next, vqaddq_u8(vextq_u8::<15>(max, next), vdupq_n_u8(1)));
+            next = vminq_u8(next, vqaddq_u8(vextq_u8::<14>(max, next), vdupq_n_u8(2)));
+            next = vminq_u8(next, vqaddq_u8(vextq_u8::<12>(max, next), vdupq_n_u8(4)));
+            next = vminq_u8(next, vqaddq_u8(vextq_u8::<8>(max, next), vdupq_n_u8(8)));
+            row = vorrq_u8(next, outside);
+        }
+        vminvq_u8(row) as u32
+    }
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+fn band_distance_neon(read: &[u8], window: &[u8]) -> u32 {
+    band_distance(read, window)
+}
+
+// ============================================================================
+// Result
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadMappingResult {
+    pub total_sequences: usize,
+    pub mapped: usize,
+    /// Mapped reads aligned as their reverse complement
+    pub reverse_strand: usize,
+    pub unmapped: usize,
+    /// Mapped reads by edit distance (last bin: [`MAX_HISTOGRAM_EDITS`] or more)
+    pub edit_histogram: Vec<usize>,
+    /// Reference occurrences of the reads' seeds (repeats excluded)
+    pub seed_hits: usize,
+    /// Candidate alignments extended
+    pub extensions: usize,
+}
+
+impl ReadMappingResult {
+    pub fn new() -> Self {
+        Self {
+            total_sequences: 0,
+            mapped: 0,
+            reverse_strand: 0,
+            unmapped: 0,
+            edit_histogram: vec![0; MAX_HISTOGRAM_EDITS + 1],
+            seed_hits: 0,
+            extensions: 0,
+        }
+    }
+
+    pub fn add(&mut self, other: &Self) {
+        self.total_sequences += other.total_sequences;
+        self.mapped += other.mapped;
+        self.reverse_strand += other.reverse_strand;
+        self.unmapped += other.unmapped;
+        for (count, &other_count) in self.edit_histogram.iter_mut().zip(&other.edit_histogram) {
+            *count += other_count;
+        }
+        self.seed_hits += other.seed_hits;
+        self.extensions += other.extensions;
+    }
+
+    /// Fraction of reads mapped
+    pub fn mapping_rate(&self) -> f64 {
+        if self.total_sequences == 0 {
+            0.0
+        } else {
+            self.mapped as f64 / self.total_sequences as f64
+        }
+    }
+}
+
+impl Default for ReadMappingResult {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrimitiveOperation for ReadMapping {
+    fn name(&self) -> &str {
+        "read_mapping"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Search
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({
+            "reference_length": self.reference_length(),
+            "contigs": self.contigs.len(),
+            "k": self.k,
+            "w": self.w,
+            "min_seeds": self.min_seeds,
+            "max_candidates": self.max_candidates,
+            "max_occurrences": self.max_occurrences,
+            "max_error_rate": self.max_error_rate,
+        })
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.map_reads(data, false)))
+    }
+
+    fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        Ok(OperationOutput::typed(self.map_reads(data, true)))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let pool = crate::thread_pool::get(num_threads)?;
+
+        let result = pool.install(|| {
+            data.par_iter()
+                .fold(ReadMappingResult::new, |mut result, record| {
+                    self.map_read(&record.sequence, true, &mut result);
+                    result
+                })
+                .reduce(ReadMappingResult::new, |mut a, b| {
+                    a.add(&b);
+                    a
+                })
+        });
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let merge = |mut a: ReadMappingResult, b: ReadMappingResult| {
+            a.add(&b);
+            a
+        };
+        let result = crate::chunked::reduce_neon(self, data, num_threads, batch_size, merge)?
+            .unwrap_or_default();
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        if config.num_threads > 1 {
+            self.execute_threaded(data, config)
+        } else if config.use_neon {
+            self.execute_neon(data)
+        } else {
+            self.execute_naive(data)
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads sampled from `reference`, every third reverse complemented,
+    /// with substitutions and an indel every few reads; the last reads are
+    /// random (unmapped)
+    fn sampled_reads(reference: &[u8], count: usize) -> Vec<SequenceRecord> {
+        (0..count)
+            .map(|i| {
+                let start = (mix64(i as u64) % (reference.len() as u64 - 200)) as usize;
+                let mut read = reference[start..start + 150].to_vec();
+                if i >= count - 5 {
+                    read = ReadMapping::synthetic_reference(150, 1_000 + i as u64);
+                }
+                for e in 0..i % 4 {
+                    let at = 10 + 37 * e;
+                    read[at] = complement(read[at]);
+                }
+                if i % 5 == 0 {
+                    read.remove(75);
+                }
+                if i % 3 == 0 {
+                    read = read.iter().rev().map(|&b| complement(b)).collect();
+                }
+                SequenceRecord::fasta(format!("read_{}", i), read)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_minimizers() {
+        let seq = ReadMapping::synthetic_reference(500, 7);
+        let seeds = minimizers(&seq, 15, 10);
+        // Roughly 2 / (w + 1) of the k-mers, in increasing position order
+        assert!(seeds.len() > 50 && seeds.len() < 150, "{}", seeds.len());
+        assert!(seeds.windows(2).all(|pair| pair[0].position < pair[1].position));
+
+        // Reverse complement has the same seeds, mirrored, opposite orientation
+        let rc: Vec<u8> = seq.iter().rev().map(|&b| complement(b)).collect();
+        let rc_seeds = minimizers(&rc, 15, 10);
+        assert_eq!(rc_seeds.len(), seeds.len());
+        let mirrored = &rc_seeds[rc_seeds.len() - 1];
+        assert_eq!(mirrored.hash, seeds[0].hash);
+        assert_eq!(mirrored.position as usize, 500 - 15 - seeds[0].position as usize);
+        assert_ne!(mirrored.reverse, seeds[0].reverse);
+
+        // N breaks k-mers
+        assert!(minimizers(b"ACGTNACGT", 3, 1).iter().all(|m| m.position != 2 && m.position != 3));
+    }
+
+    #[test]
+    fn test_band_distance_kernels() {
+        let reference = ReadMapping::synthetic_reference(400, 3);
+        let read = reference[100..250].to_vec();
+        let window_len = 150 + 2 * EXTENSION_BAND + 16;
+        let window_at = |start: usize| reference[start..start + window_len].to_vec();
+
+        // Candidate exactly on the diagonal, or off by up to the band
+        assert_eq!(band_distance(&read, &window_at(100 - EXTENSION_BAND)), 0);
+        assert_eq!(band_distance(&read, &window_at(100 - EXTENSION_BAND + 5)), 0);
+        assert_eq!(band_distance(&read, &window_at(100 - 2 * EXTENSION_BAND)), 0);
+
+        let mut edited = read.clone();
+        edited[20] = complement(edited[20]);
+        edited.remove(60);
+        edited.insert(110, b'A');
+        edited.insert(110, b'A');
+        let window = window_at(100 - EXTENSION_BAND);
+        assert_eq!(band_distance(&edited, &window), 4);
+        assert_eq!(band_distance_neon(&edited, &window), 4);
+
+        // Unrelated and off-diagonal reads agree too, up to the shared cap
+        let random = ReadMapping::synthetic_reference(400, 99);
+        let padded: Vec<u8> = window_at(0).into_iter().chain([SENTINEL; 400]).collect();
+        for read in [&random[..], &random[..150], &read[..], &edited[..], &random[..3]] {
+            assert_eq!(band_distance_neon(read, &padded), band_distance(read, &padded));
+        }
+        assert!(band_distance(&random[..150], &window_at(0)) > 50);
+        assert_eq!(band_distance(&random, &padded), MAX_DISTANCE);
+    }
+
+    #[test]
+    fn test_read_mapping() {
+        let reference = ReadMapping::synthetic_reference(50_000, 11);
+        let op = ReadMapping::new(vec![reference.clone()]);
+        let reads = sampled_reads(&reference, 60);
+
+        let result = op.map_reads(&reads, false);
+        assert_eq!(result.total_sequences, 60);
+        assert_eq!(result.mapped, 55);
+        assert_eq!(result.unmapped, 5);
+        assert_eq!(result.reverse_strand, reads[..55].iter().step_by(3).count());
+        // 0-3 substitutions, plus one deletion every fifth read
+        assert_eq!(result.edit_histogram.iter().sum::<usize>(), 55);
+        assert_eq!(result.edit_histogram[5..].iter().sum::<usize>(), 0);
+        assert!(result.extensions >= 55);
+        assert!((result.mapping_rate() - 55.0 / 60.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_read_mapping_backends_match() {
+        let reference = ReadMapping::synthetic_reference(20_000, 5);
+        let op = ReadMapping::new(vec![reference.clone()]);
+        let mut reads = sampled_reads(&reference, 40);
+        reads.push(SequenceRecord::fasta("short".to_string(), b"ACGT".to_vec()));
+        let lower = reference[500..640].to_ascii_lowercase();
+        reads.push(SequenceRecord::fasta("lower".to_string(), lower));
+
+        let expected = op.map_reads(&reads, false);
+        assert_eq!(expected.mapped, 36);
+        for output in [
+            op.execute_neon(&reads).unwrap(),
+            op.execute_parallel(&reads, 3).unwrap(),
+            op.execute_parallel_chunked(&reads, 2).unwrap(),
+        ] {
+            assert_eq!(output.statistics::<ReadMappingResult>().unwrap(), &expected);
+        }
+    }
+}
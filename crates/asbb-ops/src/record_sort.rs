@@ -0,0 +1,303 @@
+// Record Sort Operation
+//
+// Sorts records by a key: sequence length, GC fraction, or ID
+// (lexicographic). Sorting is the primitive behind deduplication, binning
+// and sorted output in downstream pipelines, and unlike the other operations
+// its cost is dominated by data movement rather than per-base work.
+//
+// Every backend first extracts one u64 key per record, then sorts
+// (key, index) pairs and gathers the records in that order:
+// - Length: the sequence length
+// - GC fraction: (G+C) / length in 32-bit fixed point
+// - ID: the first 8 bytes of the ID, big-endian; records whose prefixes tie
+//   are ordered by their full IDs
+// Records with equal keys keep their input order, so every backend produces
+// the same output.
+//
+// Backends:
+// - Naive: scalar key extraction, std stable comparison sort
+// - NEON: SIMD key extraction (GC counting), LSD radix sort on the keys
+//   (byte passes where every key has the same digit are skipped)
+// - Parallel: key extraction across threads, Rayon's parallel merge sort
+//
+// Expected patterns (hypothesis):
+// - NEON: Radix sort beats comparison sort from ~10K records; SIMD only
+//   helps key extraction, so the GC key benefits most
+// - Parallel: Sorting is memory-bound, so E-cores (ThreadAssignment) should
+//   trail P-cores by less than for compute-bound operations
+//
+// Goal: Characterize sort throughput on P vs E cores
+//
+// Complexity Score: ~0.45
+// - Operations per byte: 0.2 (key extraction; GC reads every base)
+// - Accumulator count: 0.3 (one key per record)
+// - Horizontal reduction: 0.6 (global order across all records)
+// - Scalar fallback: 0.4 (radix sort is scalar)
+// - Memory access: 0.8 (scattered gather of the records)
+// - Data dependencies: 0.4 (comparisons depend on earlier passes)
+
+use crate::PrimitiveOperation;
+use asbb_core::memory::MemoryEstimate;
+use asbb_core::{Encoding, HardwareConfig, OperationCategory, OperationOutput, SequenceRecord};
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+pub struct RecordSort {
+    /// What the records are ordered by
+    pub key: SortKey,
+}
+
+/// Sort key of a record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    /// Sequence length, shortest first
+    Length,
+    /// Fraction of G/C bases, lowest first
+    GcFraction,
+    /// Read ID, lexicographic
+    Id,
+}
+
+impl SortKey {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SortKey::Length => "length",
+            SortKey::GcFraction => "gc_fraction",
+            SortKey::Id => "id",
+        }
+    }
+}
+
+impl RecordSort {
+    pub fn new(key: SortKey) -> Self {
+        Self { key }
+    }
+
+    /// Key of one record (`simd` selects the GC counting kernel)
+    fn key_of(&self, record: &SequenceRecord, simd: bool) -> u64 {
+        match self.key {
+            SortKey::Length => record.sequence.len() as u64,
+            SortKey::GcFraction => {
+                let gc = if simd {
+                    let counts = crate::gc_content::count_gc(&record.sequence);
+                    counts.count_g + counts.count_c
+                } else {
+                    record
+                        .sequence
+                        .iter()
+                        .filter(|&&base| matches!(base, b'G' | b'C' | b'g' | b'c'))
+                        .count()
+                };
+                ((gc as u64) << 32) / record.sequence.len().max(1) as u64
+            }
+            SortKey::Id => {
+                let mut prefix = [0u8; 8];
+                let id = record.id.as_bytes();
+                let len = id.len().min(8);
+                prefix[..len].copy_from_slice(&id[..len]);
+                u64::from_be_bytes(prefix)
+            }
+        }
+    }
+
+    /// Order of two keyed records (full IDs break prefix ties)
+    fn compare(&self, data: &[SequenceRecord], a: &(u64, usize), b: &(u64, usize)) -> Ordering {
+        a.0.cmp(&b.0).then_with(|| match self.key {
+            SortKey::Id => data[a.1].id.cmp(&data[b.1].id),
+            _ => Ordering::Equal,
+        })
+    }
+}
+
+/// Stable LSD radix sort of (key, index) pairs by key, 8 bits per pass
+fn radix_sort(items: &mut Vec<(u64, usize)>) {
+    let mut buffer = vec![(0u64, 0usize); items.len()];
+    for shift in (0..64).step_by(8) {
+        let mut counts = [0usize; 256];
+        for &(key, _) in items.iter() {
+            counts[((key >> shift) & 0xff) as usize] += 1;
+        }
+        // Every key has the same digit: this pass would not move anything
+        if counts.contains(&items.len()) {
+            continue;
+        }
+
+        let mut offset = 0;
+        for count in counts.iter_mut() {
+            let start = offset;
+            offset += *count;
+            *count = start;
+        }
+        for &(key, index) in items.iter() {
+            let digit = ((key >> shift) & 0xff) as usize;
+            buffer[counts[digit]] = (key, index);
+            counts[digit] += 1;
+        }
+        std::mem::swap(items, &mut buffer);
+    }
+}
+
+fn gather(data: &[SequenceRecord], order: &[(u64, usize)]) -> Vec<SequenceRecord> {
+    order.iter().map(|&(_, index)| data[index].clone()).collect()
+}
+
+impl PrimitiveOperation for RecordSort {
+    fn name(&self) -> &str {
+        "record_sort"
+    }
+
+    fn category(&self) -> OperationCategory {
+        OperationCategory::Aggregation
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({ "key": self.key.name() })
+    }
+
+    fn estimate_memory(
+        &self,
+        num_sequences: usize,
+        mean_length: f64,
+        encoding: Encoding,
+    ) -> MemoryEstimate {
+        // A sorted copy of every record, plus (key, index) pairs and the
+        // radix sort's scratch buffer
+        let estimate = MemoryEstimate::input(num_sequences, mean_length, encoding);
+        let records = estimate.input_bytes as f64;
+        estimate.with_working(num_sequences as f64 * 32.0).with_output(records)
+    }
+
+    fn execute_naive(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        let mut order: Vec<(u64, usize)> = data
+            .iter()
+            .enumerate()
+            .map(|(index, record)| (self.key_of(record, false), index))
+            .collect();
+        order.sort_by(|a, b| self.compare(data, a, b));
+
+        Ok(OperationOutput::Records(gather(data, &order)))
+    }
+
+    fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
+        let mut order: Vec<(u64, usize)> = data
+            .iter()
+            .enumerate()
+            .map(|(index, record)| (self.key_of(record, true), index))
+            .collect();
+        radix_sort(&mut order);
+
+        // Radix sort orders by the 8-byte prefix only; refine runs of equal
+        // prefixes by the full ID (stable, so input order breaks full ties)
+        if self.key == SortKey::Id {
+            for run in order.chunk_by_mut(|a, b| a.0 == b.0) {
+                if run.len() > 1 {
+                    run.sort_by(|a, b| data[a.1].id.cmp(&data[b.1].id));
+                }
+            }
+        }
+
+        Ok(OperationOutput::Records(gather(data, &order)))
+    }
+
+    fn execute_parallel(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+    ) -> Result<OperationOutput> {
+        let pool = crate::thread_pool::get(num_threads)?;
+
+        let sorted = pool.install(|| {
+            let mut order: Vec<(u64, usize)> = data
+                .par_iter()
+                .enumerate()
+                .map(|(index, record)| (self.key_of(record, true), index))
+                .collect();
+            order.par_sort_by(|a, b| self.compare(data, a, b));
+            order.par_iter().map(|&(_, index)| data[index].clone()).collect()
+        });
+
+        Ok(OperationOutput::Records(sorted))
+    }
+
+    fn execute_with_config(
+        &self,
+        data: &[SequenceRecord],
+        config: &HardwareConfig,
+    ) -> Result<OperationOutput> {
+        if config.num_threads > 1 {
+            self.execute_threaded(data, config)
+        } else if config.use_neon {
+            self.execute_neon(data)
+        } else {
+            self.execute_naive(data)
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(output: OperationOutput) -> Vec<String> {
+        match output {
+            OperationOutput::Records(records) => records.into_iter().map(|r| r.id).collect(),
+            other => panic!("expected records, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_record_sort_keys() {
+        let data = vec![
+            SequenceRecord::fasta("read_c".to_string(), b"GGGGCC".to_vec()),
+            SequenceRecord::fasta("read_a".to_string(), b"ATAT".to_vec()),
+            SequenceRecord::fasta("read_b".to_string(), b"acgtgc".to_vec()),
+            SequenceRecord::fasta("read_d".to_string(), b"ACGT".to_vec()),
+        ];
+
+        // Equal lengths keep their input order
+        let by_length = RecordSort::new(SortKey::Length).execute_naive(&data).unwrap();
+        assert_eq!(ids(by_length), ["read_a", "read_d", "read_c", "read_b"]);
+
+        // 0, 0.5, 0.667 (lower case counts), 1.0
+        let by_gc = RecordSort::new(SortKey::GcFraction).execute_naive(&data).unwrap();
+        assert_eq!(ids(by_gc), ["read_a", "read_d", "read_b", "read_c"]);
+
+        let by_id = RecordSort::new(SortKey::Id).execute_naive(&data).unwrap();
+        assert_eq!(ids(by_id), ["read_a", "read_b", "read_c", "read_d"]);
+    }
+
+    #[test]
+    fn test_record_sort_backends_match() {
+        // IDs share a 12-byte prefix, so the radix path must refine ties
+        let data: Vec<SequenceRecord> = (0..2_000usize)
+            .map(|i| {
+                let seq: Vec<u8> =
+                    (0..20 + (i * 31) % 130).map(|j| b"ACGT"[(i * 7 + j * j) % 4]).collect();
+                SequenceRecord::fasta(format!("instrument:{}", (i * 7_919) % 2_000), seq)
+            })
+            .collect();
+
+        for key in [SortKey::Length, SortKey::GcFraction, SortKey::Id] {
+            let op = RecordSort::new(key);
+            let expected = ids(op.execute_naive(&data).unwrap());
+            assert_eq!(ids(op.execute_neon(&data).unwrap()), expected, "{:?}", key);
+            for threads in [2, 4] {
+                assert_eq!(ids(op.execute_parallel(&data, threads).unwrap()), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_radix_sort_is_stable() {
+        let mut items = vec![(300, 0), (5, 1), (300, 2), (1 << 40, 3), (5, 4)];
+        radix_sort(&mut items);
+        assert_eq!(items, [(5, 1), (5, 4), (300, 0), (300, 2), (1 << 40, 3)]);
+    }
+}
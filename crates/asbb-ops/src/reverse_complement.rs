@@ -18,9 +18,17 @@
 //! - Requires lookup table or bit manipulation for complement
 //! - Requires reversing the sequence
 //! - NEON can handle both efficiently with table lookups
+//!
+//! # Allocation-free variants
+//!
+//! The record-returning paths allocate an id and a sequence per read. The
+//! in-place variants rewrite a [`PackedRecords`] buffer (sequence reverse
+//! complemented, quality reversed) and [`BitSeq::reverse_complement_in_place`]
+//! rewrites 2-bit data with a byte lookup table (4 bases per lookup), so the
+//! transform touches only the input memory.
 
 use anyhow::Result;
-use asbb_core::{encoding::BitSeq, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
+use asbb_core::{encoding::BitSeq, packed::PackedRecords, OperationCategory, OperationOutput, PrimitiveOperation, SequenceRecord};
 use rayon::prelude::*;
 
 /// Reverse complement operation
@@ -66,6 +74,66 @@ impl ReverseComplement {
         self.execute_2bit_naive(data)
     }
 
+    /// Execute reverse complement on 2-bit encoded sequences (byte lookup table)
+    ///
+    /// One table lookup reverse complements 4 bases, vs one per base in
+    /// [`execute_2bit_naive`](Self::execute_2bit_naive).
+    pub fn execute_2bit_lut(&self, data: &[BitSeq]) -> Result<OperationOutput> {
+        let results = data
+            .iter()
+            .enumerate()
+            .map(|(i, bitseq)| SequenceRecord::fasta(format!("seq{}_revcomp", i), bitseq.reverse_complement_lut().to_ascii()))
+            .collect();
+
+        Ok(OperationOutput::Records(results))
+    }
+
+    /// Reverse complement 2-bit sequences in place (byte lookup table, no allocation)
+    pub fn reverse_complement_2bit_in_place(&self, data: &mut [BitSeq]) {
+        for bitseq in data {
+            bitseq.reverse_complement_in_place();
+        }
+    }
+
+    /// Reverse complement packed records in place (scalar)
+    ///
+    /// Qualities are reversed to stay aligned with their bases. Ids are left
+    /// unchanged (the record-returning paths append `_revcomp`).
+    pub fn execute_in_place_naive(&self, data: &mut PackedRecords) {
+        for (sequence, quality) in data.records_mut() {
+            naive_reverse_complement_in_place(sequence);
+            if let Some(quality) = quality {
+                quality.reverse();
+            }
+        }
+    }
+
+    /// Reverse complement packed records in place (NEON, records on
+    /// `num_threads` threads)
+    pub fn execute_in_place(&self, data: &mut PackedRecords, num_threads: usize) -> Result<()> {
+        if num_threads <= 1 {
+            for (sequence, quality) in data.records_mut() {
+                neon_reverse_complement_in_place(sequence);
+                if let Some(quality) = quality {
+                    quality.reverse();
+                }
+            }
+            return Ok(());
+        }
+
+        let pool = crate::thread_pool::get(num_threads)?;
+        let records: Vec<_> = data.records_mut().collect();
+        pool.install(|| {
+            records.into_par_iter().for_each(|(sequence, quality)| {
+                neon_reverse_complement_in_place(sequence);
+                if let Some(quality) = quality {
+                    quality.reverse();
+                }
+            })
+        });
+        Ok(())
+    }
+
     /// Execute reverse complement using GPU (Metal)
     ///
     /// ## Performance Characteristics
@@ -114,26 +182,17 @@ impl PrimitiveOperation for ReverseComplement {
     }
 
     fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
-        #[cfg(target_arch = "aarch64")]
-        {
-            let mut results = Vec::with_capacity(data.len());
-
-            for record in data {
-                let revcomp = neon_reverse_complement(&record.sequence);
-                results.push(SequenceRecord::fasta(
-                    format!("{}_revcomp", record.id),
-                    revcomp,
-                ));
-            }
+        let mut results = Vec::with_capacity(data.len());
 
-            Ok(OperationOutput::Records(results))
+        for record in data {
+            let revcomp = neon_reverse_complement(&record.sequence);
+            results.push(SequenceRecord::fasta(
+                format!("{}_revcomp", record.id),
+                revcomp,
+            ));
         }
 
-        #[cfg(not(target_arch = "aarch64"))]
-        {
-            // Fall back to naive on non-ARM
-            self.execute_naive(data)
-        }
+        Ok(OperationOutput::Records(results))
     }
 
     fn execute_parallel(
@@ -141,39 +200,33 @@ impl PrimitiveOperation for ReverseComplement {
         data: &[SequenceRecord],
         num_threads: usize,
     ) -> Result<OperationOutput> {
-        // Configure Rayon thread pool
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()?;
+        // Shared Rayon thread pool (built once per thread count)
+        let pool = crate::thread_pool::get(num_threads)?;
 
         let results = pool.install(|| {
             data.par_iter()
                 .map(|record| {
-                    #[cfg(target_arch = "aarch64")]
-                    {
-                        // Use NEON per-thread for true combined optimization
-                        let revcomp = neon_reverse_complement(&record.sequence);
-                        SequenceRecord::fasta(
-                            format!("{}_revcomp", record.id),
-                            revcomp,
-                        )
-                    }
-
-                    #[cfg(not(target_arch = "aarch64"))]
-                    {
-                        // Fall back to naive on non-ARM
-                        let revcomp = naive_reverse_complement(&record.sequence);
-                        SequenceRecord::fasta(
-                            format!("{}_revcomp", record.id),
-                            revcomp,
-                        )
-                    }
+                    // Use SIMD per-thread for true combined optimization
+                    let revcomp = neon_reverse_complement(&record.sequence);
+                    SequenceRecord::fasta(
+                        format!("{}_revcomp", record.id),
+                        revcomp,
+                    )
                 })
                 .collect()
         });
 
         Ok(OperationOutput::Records(results))
     }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        crate::chunked::concat_neon(self, data, num_threads, batch_size)
+    }
 }
 
 impl Default for ReverseComplement {
@@ -209,10 +262,113 @@ fn naive_reverse_complement(seq: &[u8]) -> Vec<u8> {
         .collect()
 }
 
+/// Swap-and-complement from both ends towards the middle
+fn naive_reverse_complement_in_place(seq: &mut [u8]) {
+    let n = seq.len();
+    for i in 0..n / 2 {
+        let (front, back) = (seq[i], seq[n - 1 - i]);
+        seq[i] = COMPLEMENT_TABLE[back as usize];
+        seq[n - 1 - i] = COMPLEMENT_TABLE[front as usize];
+    }
+    if n % 2 == 1 {
+        seq[n / 2] = COMPLEMENT_TABLE[seq[n / 2] as usize];
+    }
+}
+
 // ============================================================================
 // NEON SIMD Implementation
 // ============================================================================
 
+/// SSSE3 (x86_64) or lookup-table implementation for non-NEON targets
+#[cfg(not(target_arch = "aarch64"))]
+fn neon_reverse_complement(seq: &[u8]) -> Vec<u8> {
+    crate::simd::reverse_complement(seq, &COMPLEMENT_TABLE)
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+fn neon_reverse_complement_in_place(seq: &mut [u8]) {
+    naive_reverse_complement_in_place(seq)
+}
+
+/// Complement 16 bases (bytes other than ACGTacgt are left unchanged)
+#[cfg(target_arch = "aarch64")]
+#[inline]
+unsafe fn complement_neon(data: std::arch::aarch64::uint8x16_t) -> std::arch::aarch64::uint8x16_t {
+    use std::arch::aarch64::*;
+
+    let mut complemented = data;
+    for (base, complement) in [
+        (b'A', b'T'),
+        (b'T', b'A'),
+        (b'C', b'G'),
+        (b'G', b'C'),
+        (b'a', b't'),
+        (b't', b'a'),
+        (b'c', b'g'),
+        (b'g', b'c'),
+    ] {
+        let mask = vceqq_u8(data, vdupq_n_u8(base));
+        complemented = vbslq_u8(mask, vdupq_n_u8(complement), complemented);
+    }
+    complemented
+}
+
+/// Reverse the 16 bytes of a vector
+#[cfg(target_arch = "aarch64")]
+#[inline]
+unsafe fn reverse_neon(data: std::arch::aarch64::uint8x16_t) -> std::arch::aarch64::uint8x16_t {
+    use std::arch::aarch64::*;
+
+    let reversed_halves = vrev64q_u8(data);
+    vextq_u8(reversed_halves, reversed_halves, 8)
+}
+
+/// In-place NEON reverse complement: swap 16-byte blocks from both ends
+///
+/// Bytes other than ACGTacgt become `N`, like [`COMPLEMENT_TABLE`]; blocks
+/// containing them take the scalar path.
+#[cfg(target_arch = "aarch64")]
+fn neon_reverse_complement_in_place(seq: &mut [u8]) {
+    use std::arch::aarch64::*;
+
+    let n = seq.len();
+    let mut front = 0;
+
+    unsafe {
+        // Both blocks are loaded before either is stored, so they may not overlap
+        while front + 32 <= n - front {
+            let back = n - front - 16;
+            let head = vld1q_u8(seq.as_ptr().add(front));
+            let tail = vld1q_u8(seq.as_ptr().add(back));
+
+            if !is_acgt_neon(head) || !is_acgt_neon(tail) {
+                break;
+            }
+
+            vst1q_u8(seq.as_mut_ptr().add(front), reverse_neon(complement_neon(tail)));
+            vst1q_u8(seq.as_mut_ptr().add(back), reverse_neon(complement_neon(head)));
+            front += 16;
+        }
+    }
+
+    // Middle (and any block with non-ACGT bytes)
+    naive_reverse_complement_in_place(&mut seq[front..n - front]);
+}
+
+/// Whether all 16 bytes are ACGTacgt
+#[cfg(target_arch = "aarch64")]
+#[inline]
+unsafe fn is_acgt_neon(data: std::arch::aarch64::uint8x16_t) -> bool {
+    use std::arch::aarch64::*;
+
+    let upper = vandq_u8(data, vdupq_n_u8(0xDF));
+    let mut valid = vdupq_n_u8(0);
+    for base in [b'A', b'C', b'G', b'T'] {
+        valid = vorrq_u8(valid, vceqq_u8(upper, vdupq_n_u8(base)));
+    }
+    vminvq_u8(valid) == 0xFF
+}
+
 #[cfg(target_arch = "aarch64")]
 fn neon_reverse_complement(seq: &[u8]) -> Vec<u8> {
     use std::arch::aarch64::*;
@@ -354,7 +510,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg(target_arch = "aarch64")]
     fn test_reverse_complement_neon() {
         let op = ReverseComplement::new();
         let data = create_test_data();
@@ -410,13 +565,70 @@ mod tests {
         let expected = b"ACGTACGTACGTACGTACGTACGT";  // Palindrome
 
         assert_eq!(naive_reverse_complement(seq), expected);
+        assert_eq!(neon_reverse_complement(seq), expected);
+    }
+
+    #[test]
+    fn test_reverse_complement_neon_all_bytes() {
+        // Every byte value, across SIMD chunks and an unaligned tail
+        let seq: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+        assert_eq!(neon_reverse_complement(&seq), naive_reverse_complement(&seq));
+    }
 
-        #[cfg(target_arch = "aarch64")]
-        {
-            assert_eq!(neon_reverse_complement(seq), expected);
+    #[test]
+    fn test_reverse_complement_in_place() {
+        let op = ReverseComplement::new();
+        // Lengths around the 32-byte block boundary, with N and lowercase
+        let data: Vec<SequenceRecord> = [0usize, 1, 15, 31, 32, 33, 64, 150]
+            .iter()
+            .map(|&length| {
+                let seq: Vec<u8> = (0..length).map(|i| b"ACGTacgtNAGGCT"[(i * 5 + length) % 14]).collect();
+                let qual: Vec<u8> = (0..length).map(|i| b'!' + (i % 40) as u8).collect();
+                SequenceRecord::fastq(format!("r{}", length), seq, qual)
+            })
+            .collect();
+
+        let expected = match op.execute_naive(&data).unwrap() {
+            OperationOutput::Records(records) => records,
+            _ => panic!("Expected Records output"),
+        };
+
+        let mut naive = PackedRecords::from_records(&data).unwrap();
+        op.execute_in_place_naive(&mut naive);
+        let mut neon = PackedRecords::from_records(&data).unwrap();
+        op.execute_in_place(&mut neon, 1).unwrap();
+        let mut parallel = PackedRecords::from_records(&data).unwrap();
+        op.execute_in_place(&mut parallel, 4).unwrap();
+
+        for (i, record) in expected.iter().enumerate() {
+            let mut quality = data[i].quality.clone().unwrap();
+            quality.reverse();
+            for packed in [&naive, &neon, &parallel] {
+                assert_eq!(packed.sequence(i), record.sequence.as_slice());
+                assert_eq!(packed.quality(i), Some(quality.as_slice()));
+                assert_eq!(packed.id(i), data[i].id);
+            }
         }
     }
 
+    #[test]
+    fn test_reverse_complement_2bit_lut() {
+        let op = ReverseComplement::new();
+        let bitseqs: Vec<BitSeq> = [&b"ACGT"[..], b"AAAA", b"ATCG", b"ACG", b"ACGTA", b"GATTACAGATTACA"]
+            .iter()
+            .map(|seq| BitSeq::from_ascii(seq))
+            .collect();
+
+        let result_naive = op.execute_2bit_naive(&bitseqs).unwrap();
+        let result_lut = op.execute_2bit_lut(&bitseqs).unwrap();
+        assert_eq!(result_naive, result_lut);
+
+        let mut in_place = bitseqs.clone();
+        op.reverse_complement_2bit_in_place(&mut in_place);
+        let expected: Vec<BitSeq> = bitseqs.iter().map(BitSeq::reverse_complement).collect();
+        assert_eq!(in_place, expected);
+    }
+
     #[test]
     fn test_reverse_complement_2bit_naive() {
         let op = ReverseComplement::new();
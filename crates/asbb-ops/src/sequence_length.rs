@@ -19,7 +19,7 @@
 // - Data dependencies: 0.3 (accumulation only)
 
 use crate::PrimitiveOperation;
-use asbb_core::{encoding::BitSeq, HardwareConfig, OperationCategory, OperationOutput, SequenceRecord};
+use asbb_core::{encoding::BitSeq, packed::PackedRecords, HardwareConfig, OperationCategory, OperationOutput, SequenceRecord, SequenceView};
 use anyhow::Result;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -81,7 +81,7 @@ impl PrimitiveOperation for SequenceLength {
         }
 
         result.finalize();
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_neon(&self, data: &[SequenceRecord]) -> Result<OperationOutput> {
@@ -99,7 +99,7 @@ impl PrimitiveOperation for SequenceLength {
             }
 
             result.finalize();
-            Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+            Ok(OperationOutput::typed(result))
         }
 
         #[cfg(not(target_arch = "aarch64"))]
@@ -113,9 +113,7 @@ impl PrimitiveOperation for SequenceLength {
         data: &[SequenceRecord],
         num_threads: usize,
     ) -> Result<OperationOutput> {
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()?;
+        let pool = crate::thread_pool::get(num_threads)?;
 
         let mut result = pool.install(|| {
             data.par_iter()
@@ -152,7 +150,55 @@ impl PrimitiveOperation for SequenceLength {
         });
 
         result.finalize();
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_parallel_batched(
+        &self,
+        data: &[SequenceRecord],
+        num_threads: usize,
+        batch_size: Option<usize>,
+    ) -> Result<OperationOutput> {
+        let merge = |mut a: SequenceLengthResult, b: SequenceLengthResult| {
+            a.add(&b);
+            a
+        };
+        let mut result = crate::chunked::reduce_neon(self, data, num_threads, batch_size, merge)?
+            .unwrap_or_else(SequenceLengthResult::new);
+        result.finalize();
+
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_views(&self, data: &[SequenceView]) -> Result<OperationOutput> {
+        let mut result = SequenceLengthResult::new();
+
+        for view in data {
+            let len = view.len();
+            result.total_length += len;
+            result.num_sequences += 1;
+            result.min_length = result.min_length.min(len);
+            result.max_length = result.max_length.max(len);
+        }
+
+        result.finalize();
+        Ok(OperationOutput::typed(result))
+    }
+
+    fn execute_packed(&self, data: &PackedRecords) -> Result<OperationOutput> {
+        // Lengths come straight from the offset table; sequences are not read
+        let mut result = SequenceLengthResult::new();
+
+        for bounds in data.offsets().windows(2) {
+            let len = bounds[1] - bounds[0];
+            result.total_length += len;
+            result.num_sequences += 1;
+            result.min_length = result.min_length.min(len);
+            result.max_length = result.max_length.max(len);
+        }
+
+        result.finalize();
+        Ok(OperationOutput::typed(result))
     }
 
     fn execute_with_config(
@@ -161,7 +207,7 @@ impl PrimitiveOperation for SequenceLength {
         config: &HardwareConfig,
     ) -> Result<OperationOutput> {
         if config.num_threads > 1 {
-            self.execute_parallel(data, config.num_threads)
+            self.execute_threaded(data, config)
         } else if config.use_neon {
             self.execute_neon(data)
         } else {
@@ -187,7 +233,7 @@ impl SequenceLength {
         }
 
         result.finalize();
-        Ok(OperationOutput::Statistics(serde_json::to_value(result)?))
+        Ok(OperationOutput::typed(result))
     }
 
     /// Execute with 2-bit encoded data (NEON)
@@ -244,8 +290,7 @@ mod tests {
 
         let result = op.execute_naive(&records).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let length_result: SequenceLengthResult = serde_json::from_value(value).unwrap();
+        if let Some(length_result) = result.statistics::<SequenceLengthResult>() {
 
             // Expected: 4 + 12 + 18 + 2 = 36 bp total
             assert_eq!(length_result.total_length, 36);
@@ -266,8 +311,7 @@ mod tests {
 
         let result = op.execute_neon(&records).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let length_result: SequenceLengthResult = serde_json::from_value(value).unwrap();
+        if let Some(length_result) = result.statistics::<SequenceLengthResult>() {
 
             assert_eq!(length_result.total_length, 36);
             assert_eq!(length_result.num_sequences, 4);
@@ -288,11 +332,11 @@ mod tests {
         let naive_result = op.execute_naive(&records).unwrap();
         let neon_result = op.execute_neon(&records).unwrap();
 
-        if let (OperationOutput::Statistics(naive_val), OperationOutput::Statistics(neon_val)) =
+        if let (OperationOutput::Typed(naive_typed), OperationOutput::Typed(neon_typed)) =
             (naive_result, neon_result)
         {
-            let naive_len: SequenceLengthResult = serde_json::from_value(naive_val).unwrap();
-            let neon_len: SequenceLengthResult = serde_json::from_value(neon_val).unwrap();
+            let naive_len: &SequenceLengthResult = naive_typed.downcast_ref().unwrap();
+            let neon_len: &SequenceLengthResult = neon_typed.downcast_ref().unwrap();
 
             assert_eq!(naive_len.total_length, neon_len.total_length);
             assert_eq!(naive_len.num_sequences, neon_len.num_sequences);
@@ -303,6 +347,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sequence_length_packed_and_views() {
+        let op = SequenceLength;
+        let data = create_test_records();
+        let packed = PackedRecords::from_records(&data).unwrap();
+
+        assert_eq!(op.execute_naive(&data).unwrap(), op.execute_packed(&packed).unwrap());
+
+        let views: Vec<SequenceView> = data.iter().map(SequenceRecord::view).collect();
+        assert_eq!(op.execute_naive(&data).unwrap(), op.execute_views(&views).unwrap());
+    }
+
     #[test]
     fn test_sequence_length_parallel() {
         let records = create_test_records();
@@ -310,8 +366,7 @@ mod tests {
 
         let result = op.execute_parallel(&records, 2).unwrap();
 
-        if let OperationOutput::Statistics(value) = result {
-            let length_result: SequenceLengthResult = serde_json::from_value(value).unwrap();
+        if let Some(length_result) = result.statistics::<SequenceLengthResult>() {
 
             assert_eq!(length_result.total_length, 36);
             assert_eq!(length_result.num_sequences, 4);
@@ -183,9 +183,7 @@ impl PrimitiveOperation for SequenceMasking {
         data: &[SequenceRecord],
         num_threads: usize,
     ) -> Result<OperationOutput> {
-        rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()?
+        crate::thread_pool::get(num_threads)?
             .install(|| {
                 let masked_records: Vec<SequenceRecord> = data
                     .par_iter()
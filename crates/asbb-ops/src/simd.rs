@@ -0,0 +1,269 @@
+//! SIMD kernels for non-NEON targets
+//!
+//! The element-wise operations' `execute_neon` paths use NEON intrinsics on
+//! aarch64 (Apple Silicon and Graviton alike). On x86_64 the same entry points
+//! use the SSE2/AVX2/SSSE3 kernels here, so cross-platform comparisons run
+//! the same crate code instead of silently degrading to the naive path. Other
+//! targets use scalar loops, reported as such by [`backend`].
+//!
+//! SSE2 is part of the x86_64 baseline; AVX2 and SSSE3 are detected at run
+//! time.
+
+/// SIMD instruction set the element-wise `execute_neon` paths use on this machine
+pub fn backend() -> &'static str {
+    #[cfg(target_arch = "aarch64")]
+    {
+        "neon"
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") {
+            "avx2"
+        } else {
+            "sse2"
+        }
+    }
+
+    #[cfg(not(any(target_arch = "aarch64", target_arch = "x86_64")))]
+    {
+        "scalar"
+    }
+}
+
+/// Count case-insensitive occurrences of each letter in `letters`
+///
+/// `letters` must be ASCII letters; upper and lower case count together.
+pub fn count_letters<const K: usize>(seq: &[u8], letters: [u8; K]) -> [usize; K] {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 support was just checked
+            unsafe { x86::count_letters_avx2(seq, &letters) }
+        } else {
+            // SAFETY: SSE2 is part of the x86_64 baseline
+            unsafe { x86::count_letters_sse2(seq, &letters) }
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        count_letters_scalar(seq, &letters)
+    }
+}
+
+/// Reverse complement of `seq` using the byte mapping `table`
+///
+/// The vector path handles A/C/G/T/N in either case and maps every other
+/// byte to `N`; `table` must agree (it is used for the tail and on targets
+/// without SSSE3).
+pub fn reverse_complement(seq: &[u8], table: &[u8; 256]) -> Vec<u8> {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("ssse3") {
+            // SAFETY: SSSE3 support was just checked
+            return unsafe { x86::reverse_complement_ssse3(seq, table) };
+        }
+    }
+
+    seq.iter().rev().map(|&base| table[base as usize]).collect()
+}
+
+fn count_letters_scalar<const K: usize>(seq: &[u8], letters: &[u8; K]) -> [usize; K] {
+    let mut counts = [0usize; K];
+    for &base in seq {
+        let folded = base | 0x20;
+        for (count, &letter) in counts.iter_mut().zip(letters) {
+            *count += usize::from(folded == letter | 0x20);
+        }
+    }
+    counts
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+
+    /// Vector iterations a u8 accumulator lane can count before it can
+    /// overflow (one increment per iteration)
+    const MAX_ITERATIONS_PER_FLUSH: usize = 255;
+
+    /// Complement pairs handled by the vector path; all other bytes become `N`
+    const COMPLEMENT_PAIRS: [(u8, u8); 10] = [
+        (b'A', b'T'),
+        (b'T', b'A'),
+        (b'C', b'G'),
+        (b'G', b'C'),
+        (b'a', b't'),
+        (b't', b'a'),
+        (b'c', b'g'),
+        (b'g', b'c'),
+        (b'N', b'N'),
+        (b'n', b'n'),
+    ];
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn count_letters_sse2<const K: usize>(
+        seq: &[u8],
+        letters: &[u8; K],
+    ) -> [usize; K] {
+        let fold = _mm_set1_epi8(0x20);
+        let mut targets = [_mm_setzero_si128(); K];
+        for (target, &letter) in targets.iter_mut().zip(letters) {
+            *target = _mm_set1_epi8((letter | 0x20) as i8);
+        }
+
+        let remainder = seq.chunks_exact(16).remainder();
+        let mut counts = [0usize; K];
+
+        let vector_bytes = &seq[..seq.len() - remainder.len()];
+        for block in vector_bytes.chunks(16 * MAX_ITERATIONS_PER_FLUSH) {
+            let mut accumulators = [_mm_setzero_si128(); K];
+            for chunk in block.chunks_exact(16) {
+                let data = _mm_or_si128(_mm_loadu_si128(chunk.as_ptr() as *const __m128i), fold);
+                for (acc, &target) in accumulators.iter_mut().zip(&targets) {
+                    // Matches are 0xFF (-1), so subtracting counts them
+                    *acc = _mm_sub_epi8(*acc, _mm_cmpeq_epi8(data, target));
+                }
+            }
+            for (count, &acc) in counts.iter_mut().zip(&accumulators) {
+                *count += horizontal_sum_sse2(acc);
+            }
+        }
+
+        let tail = super::count_letters_scalar(remainder, letters);
+        for (count, extra) in counts.iter_mut().zip(tail) {
+            *count += extra;
+        }
+        counts
+    }
+
+    #[target_feature(enable = "sse2")]
+    unsafe fn horizontal_sum_sse2(v: __m128i) -> usize {
+        let sums = _mm_sad_epu8(v, _mm_setzero_si128());
+        let low = _mm_cvtsi128_si64(sums) as usize;
+        let high = _mm_cvtsi128_si64(_mm_unpackhi_epi64(sums, sums)) as usize;
+        low + high
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn count_letters_avx2<const K: usize>(
+        seq: &[u8],
+        letters: &[u8; K],
+    ) -> [usize; K] {
+        let fold = _mm256_set1_epi8(0x20);
+        let mut targets = [_mm256_setzero_si256(); K];
+        for (target, &letter) in targets.iter_mut().zip(letters) {
+            *target = _mm256_set1_epi8((letter | 0x20) as i8);
+        }
+
+        let remainder = seq.chunks_exact(32).remainder();
+        let mut counts = [0usize; K];
+
+        let vector_bytes = &seq[..seq.len() - remainder.len()];
+        for block in vector_bytes.chunks(32 * MAX_ITERATIONS_PER_FLUSH) {
+            let mut accumulators = [_mm256_setzero_si256(); K];
+            for chunk in block.chunks_exact(32) {
+                let data =
+                    _mm256_or_si256(_mm256_loadu_si256(chunk.as_ptr() as *const __m256i), fold);
+                for (acc, &target) in accumulators.iter_mut().zip(&targets) {
+                    *acc = _mm256_sub_epi8(*acc, _mm256_cmpeq_epi8(data, target));
+                }
+            }
+            for (count, &acc) in counts.iter_mut().zip(&accumulators) {
+                let sums = _mm256_sad_epu8(acc, _mm256_setzero_si256());
+                let mut lanes = [0u64; 4];
+                _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, sums);
+                *count += lanes.iter().sum::<u64>() as usize;
+            }
+        }
+
+        let tail = super::count_letters_scalar(remainder, letters);
+        for (count, extra) in counts.iter_mut().zip(tail) {
+            *count += extra;
+        }
+        counts
+    }
+
+    #[target_feature(enable = "ssse3")]
+    pub(super) unsafe fn reverse_complement_ssse3(seq: &[u8], table: &[u8; 256]) -> Vec<u8> {
+        let mut result = vec![0u8; seq.len()];
+        let reverse = _mm_setr_epi8(15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0);
+
+        let chunks = seq.chunks_exact(16);
+        let remainder = chunks.remainder();
+
+        for (i, chunk) in chunks.enumerate() {
+            let data = _mm_loadu_si128(chunk.as_ptr() as *const __m128i);
+
+            let mut complemented = _mm_set1_epi8(b'N' as i8);
+            for (from, to) in COMPLEMENT_PAIRS {
+                let mask = _mm_cmpeq_epi8(data, _mm_set1_epi8(from as i8));
+                complemented = _mm_or_si128(
+                    _mm_andnot_si128(mask, complemented),
+                    _mm_and_si128(mask, _mm_set1_epi8(to as i8)),
+                );
+            }
+
+            let rev_pos = seq.len() - (i + 1) * 16;
+            _mm_storeu_si128(
+                result[rev_pos..].as_mut_ptr() as *mut __m128i,
+                _mm_shuffle_epi8(complemented, reverse),
+            );
+        }
+
+        // The unaligned tail of the input lands at the start of the output
+        for (i, &base) in remainder.iter().enumerate() {
+            result[remainder.len() - 1 - i] = table[base as usize];
+        }
+
+        result
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every byte value, repeated across block and tail boundaries
+    fn all_bytes(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 7 % 256) as u8).collect()
+    }
+
+    #[test]
+    fn test_count_letters_matches_scalar() {
+        let letters = [b'A', b'C', b'G', b'T', b'N', b'R'];
+        for len in [0, 15, 16, 33, 4096, 16 * 255 + 17, 32 * 255 * 2 + 5] {
+            let seq = all_bytes(len);
+            assert_eq!(
+                count_letters(&seq, letters),
+                count_letters_scalar(&seq, &letters),
+                "length {}",
+                len
+            );
+        }
+
+        let seq = b"ACGTacgtNNnnRr@`".repeat(500);
+        assert_eq!(count_letters(&seq, [b'a', b'N', b'R']), [1000, 2000, 1000]);
+    }
+
+    #[test]
+    fn test_reverse_complement_matches_table() {
+        let mut table = [b'N'; 256];
+        for (from, to) in [(b'A', b'T'), (b'T', b'A'), (b'C', b'G'), (b'G', b'C')] {
+            table[from as usize] = to;
+            table[(from | 0x20) as usize] = to | 0x20;
+        }
+        table[b'n' as usize] = b'n';
+
+        for len in [0, 7, 16, 100, 1000] {
+            let seq = all_bytes(len);
+            let expected: Vec<u8> = seq.iter().rev().map(|&b| table[b as usize]).collect();
+            assert_eq!(reverse_complement(&seq, &table), expected, "length {}", len);
+        }
+    }
+}
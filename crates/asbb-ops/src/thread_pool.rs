@@ -0,0 +1,175 @@
+//! Shared Rayon thread pools for `execute_parallel`
+//!
+//! Building a `ThreadPool` spawns and joins OS threads, which costs tens of
+//! milliseconds. When every `execute_parallel` call built its own pool, that
+//! cost was included in each measured run and dominated small-scale parallel
+//! results. Operations now obtain their pool from [`get`], which returns:
+//!
+//! 1. a pool injected by the harness with [`with_pool`] (if its thread count
+//!    matches), else
+//! 2. a process-wide pool cached per [`PoolKey`], else
+//! 3. a freshly built pool, when reuse is disabled with [`set_pool_reuse`]
+//!    (the old behaviour, for measuring construction overhead)
+//!
+//! Pools are keyed by thread count and QoS class, since a pool's worker
+//! threads keep the QoS they were started with.
+
+use anyhow::Result;
+use asbb_core::QualityOfService;
+use rayon::ThreadPool;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Identifies interchangeable pools
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PoolKey {
+    pub num_threads: usize,
+    pub qos: QualityOfService,
+}
+
+impl PoolKey {
+    pub fn new(num_threads: usize, qos: QualityOfService) -> Self {
+        Self { num_threads, qos }
+    }
+}
+
+static REUSE: AtomicBool = AtomicBool::new(true);
+static POOLS: OnceLock<Mutex<HashMap<PoolKey, Arc<ThreadPool>>>> = OnceLock::new();
+
+thread_local! {
+    static INJECTED: RefCell<Option<Arc<ThreadPool>>> = const { RefCell::new(None) };
+}
+
+/// Enable or disable pool reuse (enabled by default)
+///
+/// With reuse disabled, every [`get`] builds a new pool, so measured runs
+/// include pool construction as they did before pools were shared.
+pub fn set_pool_reuse(enabled: bool) {
+    REUSE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn pool_reuse_enabled() -> bool {
+    REUSE.load(Ordering::Relaxed)
+}
+
+/// Pool for an operation's `execute_parallel(data, num_threads)`
+pub fn get(num_threads: usize) -> Result<Arc<ThreadPool>> {
+    let injected = INJECTED.with(|pool| pool.borrow().clone());
+    match injected {
+        Some(pool) if pool.current_num_threads() == num_threads => Ok(pool),
+        _ => pool_for(PoolKey::new(num_threads, QualityOfService::Default)),
+    }
+}
+
+/// Cached pool for `key` (or a new one when reuse is disabled)
+pub fn pool_for(key: PoolKey) -> Result<Arc<ThreadPool>> {
+    if !pool_reuse_enabled() {
+        return Ok(Arc::new(build(key)?));
+    }
+
+    let mut pools = POOLS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if let Some(pool) = pools.get(&key) {
+        return Ok(Arc::clone(pool));
+    }
+
+    let pool = Arc::new(build(key)?);
+    pools.insert(key, Arc::clone(&pool));
+    Ok(pool)
+}
+
+/// Run `f` with `pool` used by every `execute_parallel` call of matching width
+///
+/// This is how a harness applies a configuration's QoS to operations, whose
+/// `execute_parallel` only receives a thread count.
+pub fn with_pool<R>(pool: Arc<ThreadPool>, f: impl FnOnce() -> R) -> R {
+    let previous = INJECTED.with(|slot| slot.borrow_mut().replace(pool));
+    let result = f();
+    INJECTED.with(|slot| *slot.borrow_mut() = previous);
+    result
+}
+
+/// Number of pools currently cached
+pub fn cached_pool_count() -> usize {
+    POOLS
+        .get()
+        .map(|pools| pools.lock().unwrap_or_else(|e| e.into_inner()).len())
+        .unwrap_or(0)
+}
+
+/// Drop all cached pools (their threads exit once no run holds them)
+pub fn clear_pool_cache() {
+    if let Some(pools) = POOLS.get() {
+        pools.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+fn build(key: PoolKey) -> Result<ThreadPool> {
+    let qos = key.qos;
+    Ok(rayon::ThreadPoolBuilder::new()
+        .num_threads(key.num_threads)
+        .start_handler(move |_| set_thread_qos(qos))
+        .build()?)
+}
+
+/// Apply a QoS class to the calling thread (macOS scheduler hint)
+#[cfg(target_os = "macos")]
+fn set_thread_qos(qos: QualityOfService) {
+    // QOS_CLASS_* values from <sys/qos.h>
+    let qos_class = match qos {
+        QualityOfService::UserInteractive => 0x21,
+        QualityOfService::UserInitiated => 0x19,
+        QualityOfService::Default => 0x15,
+        QualityOfService::Utility => 0x11,
+        QualityOfService::Background => 0x09,
+    };
+
+    extern "C" {
+        fn pthread_set_qos_class_self_np(qos_class: u32, relative_priority: i32) -> i32;
+    }
+
+    // Best-effort hint; failure leaves the default QoS
+    unsafe {
+        let _ = pthread_set_qos_class_self_np(qos_class, 0);
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn set_thread_qos(_qos: QualityOfService) {}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pools_are_cached_per_key() {
+        let key = PoolKey::new(3, QualityOfService::Utility);
+        let a = pool_for(key).unwrap();
+        let b = pool_for(key).unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(a.current_num_threads(), 3);
+
+        let other = pool_for(PoolKey::new(3, QualityOfService::Background)).unwrap();
+        assert!(!Arc::ptr_eq(&a, &other));
+    }
+
+    #[test]
+    fn test_injected_pool_takes_precedence() {
+        let injected = Arc::new(build(PoolKey::new(2, QualityOfService::UserInitiated)).unwrap());
+
+        with_pool(Arc::clone(&injected), || {
+            assert!(Arc::ptr_eq(&get(2).unwrap(), &injected));
+            // Width mismatch falls back to the shared pool
+            assert!(!Arc::ptr_eq(&get(5).unwrap(), &injected));
+        });
+        assert!(!Arc::ptr_eq(&get(2).unwrap(), &injected));
+    }
+}
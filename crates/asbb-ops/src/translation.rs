@@ -230,10 +230,7 @@ impl PrimitiveOperation for Translation {
     }
 
     fn execute_parallel(&self, sequences: &[SequenceRecord], num_threads: usize) -> Result<OperationOutput> {
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build()
-            .unwrap();
+        let pool = crate::thread_pool::get(num_threads)?;
 
         let results: Vec<Option<SequenceRecord>> = pool.install(|| {
             sequences.par_iter().map(|record| {
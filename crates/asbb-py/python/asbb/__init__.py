@@ -0,0 +1,45 @@
+"""Apple Silicon Bio Bench operations and benchmark runner.
+
+The compiled extension (``asbb._asbb``) provides sequence records, the
+registered operations, hardware configurations and the benchmark runner;
+this module re-exports it and adds ``benchmark_frame`` for pandas users.
+"""
+
+from ._asbb import (
+    HardwareConfig,
+    SequenceRecord,
+    __version__,
+    benchmark,
+    benchmark_all,
+    load_fastq,
+    operations,
+    run,
+)
+
+__all__ = [
+    "HardwareConfig",
+    "SequenceRecord",
+    "__version__",
+    "benchmark",
+    "benchmark_all",
+    "benchmark_frame",
+    "load_fastq",
+    "operations",
+    "run",
+]
+
+
+def benchmark_frame(operations, records, configs, warmup_runs=3, measured_runs=10):
+    """Benchmark every (operation, config) pair into a pandas DataFrame.
+
+    Requires pandas (``pip install asbb[pandas]``); ``benchmark_all`` returns
+    the same rows as a list of dicts.
+    """
+    import pandas as pd
+
+    if isinstance(operations, str):
+        operations = [operations]
+    if isinstance(configs, HardwareConfig):
+        configs = [configs]
+    rows = benchmark_all(operations, records, configs, warmup_runs, measured_runs)
+    return pd.DataFrame(rows)
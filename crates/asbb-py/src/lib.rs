@@ -0,0 +1,356 @@
+//! Python bindings for Apple Silicon Bio Bench
+//!
+//! Exposes sequence records, the registered operations, hardware
+//! configurations and [`asbb_explorer::benchmark_operation`] to Python, so
+//! the primitives and the benchmark harness can be driven from notebooks:
+//!
+//! ```python
+//! import asbb
+//!
+//! reads = asbb.load_fastq("datasets/medium_10000_150bp.fq")
+//! asbb.run("gc_content", reads)                 # {'gc_percent': 51.2, ...}
+//!
+//! configs = [asbb.HardwareConfig.naive(), asbb.HardwareConfig.neon(threads=4)]
+//! df = asbb.benchmark_frame(asbb.operations(), reads, configs)
+//! ```
+//!
+//! Results come back as plain dicts (one flat row per benchmark, latencies
+//! in milliseconds); `asbb.benchmark_frame` turns rows into a pandas
+//! DataFrame. Built with maturin (`maturin develop -m crates/asbb-py/Cargo.toml`).
+//! Benchmarks release the GIL while they run.
+
+use asbb_core::io::FastqReader;
+use asbb_core::{
+    HardwareConfig, HardwareProfile, OperationOutput, PrimitiveOperation, SequenceRecord,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+use std::time::Duration;
+
+// ============================================================================
+// Operations
+// ============================================================================
+
+/// Create an operation instance by name (default parameters)
+fn create_operation(name: &str) -> PyResult<Box<dyn PrimitiveOperation>> {
+    asbb_ops::create_operation(name).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Names of the operations `run` and `benchmark` accept
+#[pyfunction]
+fn operations() -> Vec<String> {
+    asbb_ops::available_operations()
+}
+
+// ============================================================================
+// Sequence Records
+// ============================================================================
+
+/// A FASTA or FASTQ record
+#[pyclass(name = "SequenceRecord", module = "asbb")]
+#[derive(Clone)]
+struct PySequenceRecord {
+    inner: SequenceRecord,
+}
+
+#[pymethods]
+impl PySequenceRecord {
+    /// `quality` (Phred+33 string) makes a FASTQ record
+    #[new]
+    #[pyo3(signature = (id, sequence, quality = None))]
+    fn new(id: String, sequence: &str, quality: Option<&str>) -> PyResult<Self> {
+        let sequence = sequence.as_bytes().to_vec();
+        let inner = match quality {
+            Some(quality) if quality.len() != sequence.len() => {
+                return Err(PyValueError::new_err(format!(
+                    "Quality length {} does not match sequence length {}",
+                    quality.len(),
+                    sequence.len()
+                )))
+            }
+            Some(quality) => SequenceRecord::fastq(id, sequence, quality.as_bytes().to_vec()),
+            None => SequenceRecord::fasta(id, sequence),
+        };
+        Ok(Self { inner })
+    }
+
+    #[getter]
+    fn id(&self) -> &str {
+        &self.inner.id
+    }
+
+    #[getter]
+    fn sequence<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new_bound(py, &self.inner.sequence)
+    }
+
+    #[getter]
+    fn quality<'py>(&self, py: Python<'py>) -> Option<Bound<'py, PyBytes>> {
+        self.inner.quality.as_ref().map(|quality| PyBytes::new_bound(py, quality))
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SequenceRecord(id={:?}, length={})", self.inner.id, self.inner.len())
+    }
+}
+
+/// Read every record of a FASTQ file
+#[pyfunction]
+fn load_fastq(py: Python<'_>, path: std::path::PathBuf) -> PyResult<Vec<PySequenceRecord>> {
+    let records = py.allow_threads(|| FastqReader::from_path(&path)?.read_all())?;
+    Ok(records.into_iter().map(|inner| PySequenceRecord { inner }).collect())
+}
+
+fn unwrap_records(records: Vec<PyRef<'_, PySequenceRecord>>) -> Vec<SequenceRecord> {
+    records.iter().map(|record| record.inner.clone()).collect()
+}
+
+// ============================================================================
+// Hardware Configurations
+// ============================================================================
+
+/// A hardware configuration (backend, threads, features)
+#[pyclass(name = "HardwareConfig", module = "asbb")]
+#[derive(Clone)]
+struct PyHardwareConfig {
+    inner: HardwareConfig,
+}
+
+#[pymethods]
+impl PyHardwareConfig {
+    /// Scalar, single-threaded baseline
+    #[staticmethod]
+    fn naive() -> Self {
+        Self {
+            inner: HardwareConfig::naive(),
+        }
+    }
+
+    /// NEON on `threads` threads
+    #[staticmethod]
+    #[pyo3(signature = (threads = 1))]
+    fn neon(threads: usize) -> Self {
+        let mut inner = HardwareConfig::naive();
+        inner.use_neon = true;
+        inner.num_threads = threads.max(1);
+        Self { inner }
+    }
+
+    /// NEON on the P-cores of this machine (requires Apple Silicon)
+    #[staticmethod]
+    fn p_cores() -> PyResult<Self> {
+        Ok(Self {
+            inner: HardwareConfig::p_cores_for_profile(&HardwareProfile::detect()?),
+        })
+    }
+
+    #[getter]
+    fn use_neon(&self) -> bool {
+        self.inner.use_neon
+    }
+
+    #[setter]
+    fn set_use_neon(&mut self, use_neon: bool) {
+        self.inner.use_neon = use_neon;
+    }
+
+    #[getter]
+    fn num_threads(&self) -> usize {
+        self.inner.num_threads
+    }
+
+    #[setter]
+    fn set_num_threads(&mut self, num_threads: usize) {
+        self.inner.num_threads = num_threads.max(1);
+    }
+
+    #[getter]
+    fn use_gpu(&self) -> bool {
+        self.inner.use_gpu
+    }
+
+    #[setter]
+    fn set_use_gpu(&mut self, use_gpu: bool) {
+        self.inner.use_gpu = use_gpu;
+    }
+
+    /// Short name used in result rows (`naive`, `neon`, `neon_4t`, ...)
+    #[getter]
+    fn name(&self) -> String {
+        config_name(&self.inner)
+    }
+
+    /// Every field, as a dict
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        json_to_py(py, &serde_json::to_value(&self.inner).map_err(anyhow::Error::from)?)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("HardwareConfig({})", config_name(&self.inner))
+    }
+}
+
+/// Config name in the style of the DAG harness (`naive`, `neon_4t`, ...)
+fn config_name(config: &HardwareConfig) -> String {
+    let base = if config.use_gpu {
+        "gpu"
+    } else if config.use_neon {
+        "neon"
+    } else {
+        "naive"
+    };
+    if config.num_threads > 1 {
+        format!("{}_{}t", base, config.num_threads)
+    } else {
+        base.to_string()
+    }
+}
+
+// ============================================================================
+// Running and Benchmarking
+// ============================================================================
+
+/// Run one operation and return its output (dict, list of records, bool or int)
+#[pyfunction]
+#[pyo3(signature = (operation, records, config = None))]
+fn run(
+    py: Python<'_>,
+    operation: &str,
+    records: Vec<PyRef<'_, PySequenceRecord>>,
+    config: Option<PyHardwareConfig>,
+) -> PyResult<PyObject> {
+    let operation = create_operation(operation)?;
+    let data = unwrap_records(records);
+    let config = config.map_or_else(HardwareConfig::naive, |config| config.inner);
+    let output = py.allow_threads(|| {
+        asbb_explorer::execute_configured(operation.as_ref(), &data, &config)
+    })?;
+    output_to_py(py, output)
+}
+
+/// Benchmark one operation under one config; returns a flat result row
+#[pyfunction]
+#[pyo3(signature = (operation, records, config = None, warmup_runs = 3, measured_runs = 10))]
+fn benchmark<'py>(
+    py: Python<'py>,
+    operation: &str,
+    records: Vec<PyRef<'_, PySequenceRecord>>,
+    config: Option<PyHardwareConfig>,
+    warmup_runs: usize,
+    measured_runs: usize,
+) -> PyResult<Bound<'py, PyDict>> {
+    let data = unwrap_records(records);
+    let config = config.map_or_else(HardwareConfig::naive, |config| config.inner);
+    benchmark_row(py, operation, &data, &config, warmup_runs, measured_runs)
+}
+
+/// Benchmark every (operation, config) pair; returns one row per pair
+#[pyfunction]
+#[pyo3(signature = (operations, records, configs, warmup_runs = 3, measured_runs = 10))]
+fn benchmark_all<'py>(
+    py: Python<'py>,
+    operations: Vec<String>,
+    records: Vec<PyRef<'_, PySequenceRecord>>,
+    configs: Vec<PyHardwareConfig>,
+    warmup_runs: usize,
+    measured_runs: usize,
+) -> PyResult<Vec<Bound<'py, PyDict>>> {
+    let data = unwrap_records(records);
+    let mut rows = Vec::with_capacity(operations.len() * configs.len());
+    for operation in &operations {
+        for config in &configs {
+            rows.push(benchmark_row(
+                py,
+                operation,
+                &data,
+                &config.inner,
+                warmup_runs,
+                measured_runs,
+            )?);
+        }
+    }
+    Ok(rows)
+}
+
+fn benchmark_row<'py>(
+    py: Python<'py>,
+    name: &str,
+    data: &[SequenceRecord],
+    config: &HardwareConfig,
+    warmup_runs: usize,
+    measured_runs: usize,
+) -> PyResult<Bound<'py, PyDict>> {
+    let operation = create_operation(name)?;
+    let result = py.allow_threads(|| {
+        let operation = operation.as_ref();
+        asbb_explorer::benchmark_operation(operation, data, config, warmup_runs, measured_runs)
+    })?;
+
+    let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+    let row = PyDict::new_bound(py);
+    row.set_item("operation", name)?;
+    row.set_item("config", config_name(config))?;
+    row.set_item("num_sequences", data.len())?;
+    row.set_item("num_threads", config.num_threads)?;
+    row.set_item("use_neon", config.use_neon)?;
+    row.set_item("use_gpu", config.use_gpu)?;
+    row.set_item("throughput_seqs_per_sec", result.throughput_seqs_per_sec)?;
+    row.set_item("throughput_mbps", result.throughput_mbps)?;
+    row.set_item("latency_p50_ms", ms(result.latency_p50))?;
+    row.set_item("latency_p99_ms", ms(result.latency_p99))?;
+    for (percentile, latency) in &result.latency_percentiles {
+        row.set_item(format!("latency_{}_ms", percentile), ms(*latency))?;
+    }
+    row.set_item("output_matches_reference", result.output_matches_reference)?;
+    Ok(row)
+}
+
+// ============================================================================
+// Conversions
+// ============================================================================
+
+/// Python value of an operation output
+fn output_to_py(py: Python<'_>, output: OperationOutput) -> PyResult<PyObject> {
+    Ok(match output {
+        OperationOutput::Records(records) => records
+            .into_iter()
+            .map(|inner| PySequenceRecord { inner })
+            .collect::<Vec<_>>()
+            .into_py(py),
+        OperationOutput::Boolean(value) => value.into_py(py),
+        OperationOutput::Count(count) => count.into_py(py),
+        OperationOutput::Typed(typed) => {
+            json_to_py(py, &typed.to_json().map_err(anyhow::Error::from)?)?.unbind()
+        }
+        OperationOutput::Statistics(value) | OperationOutput::Json(value) => {
+            json_to_py(py, &value)?.unbind()
+        }
+    })
+}
+
+/// Convert JSON to Python objects through the `json` module
+fn json_to_py<'py>(py: Python<'py>, value: &serde_json::Value) -> PyResult<Bound<'py, PyAny>> {
+    py.import_bound("json")?.call_method1("loads", (value.to_string(),))
+}
+
+// ============================================================================
+// Module
+// ============================================================================
+
+#[pymodule]
+fn _asbb(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    m.add_class::<PySequenceRecord>()?;
+    m.add_class::<PyHardwareConfig>()?;
+    m.add_function(wrap_pyfunction!(operations, m)?)?;
+    m.add_function(wrap_pyfunction!(load_fastq, m)?)?;
+    m.add_function(wrap_pyfunction!(run, m)?)?;
+    m.add_function(wrap_pyfunction!(benchmark, m)?)?;
+    m.add_function(wrap_pyfunction!(benchmark_all, m)?)?;
+    Ok(())
+}
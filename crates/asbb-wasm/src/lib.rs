@@ -0,0 +1,207 @@
+//! WebAssembly build of the naive backends and datagen
+//!
+//! Runs the scalar (naive) implementation of each operation on synthetic
+//! reads in the browser, for teaching and the project website: the demo
+//! page (`www/index.html`) measures this machine's scalar throughput and
+//! sets it against the NEON speedups reported on Apple Silicon.
+//!
+//! Only the naive backends are exposed: NEON, GPU and AMX paths do not
+//! exist on wasm32, and rayon cannot spawn threads there. Timing uses the
+//! JS `performance.now()` clock, since `std::time::Instant` is unavailable
+//! on `wasm32-unknown-unknown`. The operations are those of the shared
+//! catalog (`asbb_ops::catalog`) whose metadata lists a naive backend.
+//!
+//! Build with `wasm-pack build crates/asbb-wasm --target web`.
+
+use anyhow::Result;
+use asbb_core::io::FastqReader;
+use asbb_core::operation_registry::{Backend, OperationMetadata};
+use asbb_core::{OperationOutput, PrimitiveOperation, SequenceRecord};
+use asbb_datagen::{ReadGenConfig, ReadGenerator};
+use asbb_ops::catalog::{available_operations, operation_metadata};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    /// `performance.now()` (ms, sub-millisecond resolution)
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    fn performance_now() -> f64;
+}
+
+// ============================================================================
+// Operations
+// ============================================================================
+
+/// NEON speedups over scalar reported on Apple Silicon (OPTIMIZATION_RULES.md, Rule 1)
+const REPORTED_NEON_SPEEDUPS: &[(&str, f64)] = &[
+    ("base_counting", 16.7),
+    ("gc_content", 20.3),
+    ("quality_filter", 25.1),
+    ("complexity_score", 18.2),
+];
+
+/// Whether an operation runs in the browser
+///
+/// Only the scalar backend is available on wasm32, so any implemented
+/// operation whose metadata lists a naive backend qualifies.
+fn runs_in_browser(metadata: &OperationMetadata) -> bool {
+    metadata.implemented && metadata.has_backend(Backend::Naive)
+}
+
+/// Create an operation instance by name (default parameters)
+fn create_operation(name: &str) -> Result<Box<dyn PrimitiveOperation>> {
+    if !operation_metadata(name).is_some_and(|metadata| runs_in_browser(&metadata)) {
+        anyhow::bail!("Unknown operation: {} (available: {})", name, operations().join(", "));
+    }
+    asbb_ops::create_operation(name)
+}
+
+/// Names of the operations `run` and `benchmark` accept, in registry order
+#[wasm_bindgen]
+pub fn operations() -> Vec<String> {
+    available_operations()
+        .into_iter()
+        .filter(|name| operation_metadata(name).is_some_and(|metadata| runs_in_browser(&metadata)))
+        .collect()
+}
+
+/// NEON speedup over scalar reported on Apple Silicon (`undefined` if not measured)
+#[wasm_bindgen(js_name = reportedNeonSpeedup)]
+pub fn reported_neon_speedup(operation: &str) -> Option<f64> {
+    REPORTED_NEON_SPEEDUPS
+        .iter()
+        .find(|(name, _)| *name == operation)
+        .map(|(_, speedup)| *speedup)
+}
+
+fn js_error(e: anyhow::Error) -> JsError {
+    JsError::new(&format!("{:#}", e))
+}
+
+// ============================================================================
+// Datasets
+// ============================================================================
+
+/// Reads held on the wasm side (not copied to JS)
+#[wasm_bindgen]
+pub struct Dataset {
+    records: Vec<SequenceRecord>,
+}
+
+#[wasm_bindgen]
+impl Dataset {
+    /// `num_sequences` synthetic FASTQ reads (mean length `length` bp, ±10)
+    pub fn generate(num_sequences: usize, length: usize, seed: u64) -> Result<Dataset, JsError> {
+        let config = ReadGenConfig::new(num_sequences, seed).with_length(length, 10.0);
+        let records = ReadGenerator::fastq(config).map_err(js_error)?.collect();
+        Ok(Dataset { records })
+    }
+
+    /// Parse FASTQ text (e.g. a file the user dropped on the page)
+    #[wasm_bindgen(js_name = fromFastq)]
+    pub fn from_fastq(text: &str) -> Result<Dataset, JsError> {
+        let records = FastqReader::new(text.as_bytes()).read_all().map_err(js_error)?;
+        Ok(Dataset { records })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.records.len()
+    }
+
+    #[wasm_bindgen(getter, js_name = totalBases)]
+    pub fn total_bases(&self) -> usize {
+        self.records.iter().map(|record| record.len()).sum()
+    }
+}
+
+// ============================================================================
+// Running and Benchmarking
+// ============================================================================
+
+/// Naive output of `operation` on `dataset`, as JSON text
+#[wasm_bindgen]
+pub fn run(operation: &str, dataset: &Dataset) -> Result<String, JsError> {
+    let output = create_operation(operation)
+        .and_then(|op| op.execute_naive(&dataset.records))
+        .map_err(js_error)?;
+    output_json(output).map_err(js_error)
+}
+
+/// Scalar throughput of one operation
+#[wasm_bindgen]
+pub struct BenchmarkResult {
+    /// Median run time (ms)
+    pub median_ms: f64,
+    pub throughput_seqs_per_sec: f64,
+    pub throughput_mbps: f64,
+    pub runs: usize,
+}
+
+/// Time the naive backend over `runs` runs (after one warmup run)
+#[wasm_bindgen]
+pub fn benchmark(
+    operation: &str,
+    dataset: &Dataset,
+    runs: usize,
+) -> Result<BenchmarkResult, JsError> {
+    let op = create_operation(operation).map_err(js_error)?;
+    op.execute_naive(&dataset.records).map_err(js_error)?;
+
+    let mut times = Vec::with_capacity(runs.max(1));
+    for _ in 0..runs.max(1) {
+        let start = performance_now();
+        op.execute_naive(&dataset.records).map_err(js_error)?;
+        times.push(performance_now() - start);
+    }
+    times.sort_by(f64::total_cmp);
+    let median_ms = times[times.len() / 2].max(1e-3);
+
+    let seconds = median_ms / 1000.0;
+    Ok(BenchmarkResult {
+        median_ms,
+        throughput_seqs_per_sec: dataset.records.len() as f64 / seconds,
+        throughput_mbps: dataset.total_bases() as f64 / 1e6 / seconds,
+        runs: times.len(),
+    })
+}
+
+/// JSON text of an operation output (filtered records are summarized)
+fn output_json(output: OperationOutput) -> Result<String> {
+    let value = match output {
+        OperationOutput::Records(records) => serde_json::json!({ "records": records.len() }),
+        OperationOutput::Typed(typed) => typed.to_json()?,
+        OperationOutput::Statistics(value) | OperationOutput::Json(value) => value,
+        OperationOutput::Boolean(value) => value.into(),
+        OperationOutput::Count(count) => count.into(),
+    };
+    Ok(value.to_string())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_naive_operations_on_generated_reads() {
+        let dataset = Dataset::generate(50, 100, 42).map_err(|_| ()).unwrap();
+        assert_eq!(dataset.length(), 50);
+        // Pairwise operations (hamming_distance) need reads of one length
+        let config = ReadGenConfig::new(50, 42).with_length(100, 0.0);
+        let fixed = Dataset { records: ReadGenerator::fastq(config).unwrap().collect() };
+
+        let names = operations();
+        assert!(names.iter().any(|name| name == "read_mapping"));
+        for name in &names {
+            let output = run(name, &fixed).map_err(|_| ()).unwrap();
+            assert!(serde_json::from_str::<serde_json::Value>(&output).is_ok(), "{}", name);
+        }
+        assert_eq!(reported_neon_speedup("gc_content"), Some(20.3));
+        assert_eq!(reported_neon_speedup("n_content"), None);
+        assert!(create_operation("nope").is_err());
+    }
+}
@@ -8,6 +8,14 @@
 #   100K → Large (15 MB)
 #   1M → Very Large (150 MB)
 #   10M → Huge (1.5 GB)
+#
+# Long-read (ONT/PacBio) presets, with --long-reads (~10 MB each):
+#   10K × 1 kb, 1K × 10 kb, 100 × 100 kb
+#
+# To derive the ladder from a real dataset instead of synthetic reads:
+#   asbb datagen subsample --input real.fq --output tiny_100_150bp.fq --count 100 --seed 1
+#   asbb datagen subsample --input real.fq --output small_1000_150bp.fq --count 1000 --seed 2
+#   ...
 
 set -e  # Exit on error
 
@@ -111,6 +119,24 @@ $DATAGEN generate \
     --validate
 echo ""
 
+# Long-read presets: same total bases per tier, so throughput differences
+# reflect read length rather than dataset size
+if [ "$1" = "--long-reads" ]; then
+    for tier in "10000 1kb 11" "1000 10kb 12" "100 100kb 13"; do
+        read -r count preset seed <<< "$tier"
+        echo "🧵 Generating LONG-READ dataset (${count} × ${preset} reads)..."
+        $DATAGEN generate \
+            --output "${SCRIPT_DIR}/long_${count}_${preset}.fq" \
+            --format fastq \
+            --num-sequences $count \
+            --preset $preset \
+            --quality-dist $QUALITY_DIST \
+            --seed $seed \
+            --validate
+        echo ""
+    done
+fi
+
 echo "╔════════════════════════════════════════════════════════════════════╗"
 echo "║  Dataset Generation Complete                                       ║"
 echo "╚════════════════════════════════════════════════════════════════════╝"
@@ -1,11 +1,20 @@
 use anyhow::{Context, Result, bail};
-use clap::{Parser, Subcommand};
-use indicatif::{ProgressBar, ProgressStyle};
+use asbb_datagen::manifest::{register_dataset, DatasetSource};
+use asbb_datagen::simulate::{Platform, ReadSimulator, Reference, SimulationConfig};
+use asbb_datagen::spike::{
+    generate_barcodes, parse_barcodes, SpikeConfig, SpikeTruth, Spiker, DEFAULT_ADAPTER,
+};
+use asbb_core::SequenceRecord;
+use asbb_datagen::{
+    length_preset, parse_quality_dist, write_fasta, write_fastq, LengthClass, ReadGenConfig,
+    ReadGenerator,
+};
 use rand::prelude::*;
-use rand_distr::Normal;
+use clap::{Args, Parser, Subcommand};
+use indicatif::{ProgressBar, ProgressStyle};
 use std::fs::File;
 use std::io::{BufRead, BufReader, BufWriter, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "datagen")]
@@ -39,6 +48,10 @@ enum Commands {
         #[arg(short, long, default_value = "10")]
         length_std: f64,
 
+        /// Read length preset (short, 1kb, 10kb, 100kb); overrides --length-mean/--length-std
+        #[arg(long, value_parser = ["short", "1kb", "10kb", "100kb"], conflicts_with_all = ["length_mean", "length_std"])]
+        preset: Option<String>,
+
         /// Quality score distribution (uniform_high, degrading, realistic)
         #[arg(short, long, default_value = "degrading", value_parser = ["uniform_high", "degrading", "realistic"])]
         quality_dist: String,
@@ -47,6 +60,49 @@ enum Commands {
         #[arg(short, long, default_value = "42")]
         seed: u64,
 
+        #[command(flatten)]
+        spike: SpikeArgs,
+
+        #[command(flatten)]
+        manifest: ManifestArgs,
+
+        /// Run QC validation after generation
+        #[arg(long, default_value = "true")]
+        validate: bool,
+    },
+
+    /// Simulate FASTQ reads from a reference with a sequencing error model
+    Simulate {
+        /// Output file path
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Sequencing platform error/length profile (illumina or ont)
+        #[arg(short, long, default_value = "illumina", value_parser = ["illumina", "ont"])]
+        platform: String,
+
+        /// Number of reads to simulate
+        #[arg(short, long)]
+        num_sequences: usize,
+
+        /// Reference FASTA to sample reads from (synthetic genome if omitted)
+        #[arg(short, long)]
+        reference: Option<PathBuf>,
+
+        /// Synthetic genome size (bp), used when no reference is given
+        #[arg(long, default_value = "5000000")]
+        genome_size: usize,
+
+        /// Random seed for reproducibility
+        #[arg(short, long, default_value = "42")]
+        seed: u64,
+
+        #[command(flatten)]
+        spike: SpikeArgs,
+
+        #[command(flatten)]
+        manifest: ManifestArgs,
+
         /// Run QC validation after generation
         #[arg(long, default_value = "true")]
         validate: bool,
@@ -68,6 +124,82 @@ enum Commands {
     },
 }
 
+/// Adapter/barcode spiking options (ground truth written to `<output>.truth.json`)
+#[derive(Args)]
+struct SpikeArgs {
+    /// Fraction of reads (0.0-1.0) with a 3' adapter (full or partial read-through)
+    #[arg(long, default_value = "0.0")]
+    adapter_fraction: f64,
+
+    /// Adapter sequence to spike
+    #[arg(long)]
+    adapter: Option<String>,
+
+    /// Comma-separated inline barcodes to prepend (one per read)
+    #[arg(long, conflicts_with = "num_barcodes")]
+    barcodes: Option<String>,
+
+    /// Generate this many random 8bp barcodes (Hamming distance >= 3)
+    #[arg(long)]
+    num_barcodes: Option<usize>,
+}
+
+impl SpikeArgs {
+    /// Build a spike config, or None if no spiking was requested
+    fn to_config(&self, seed: u64) -> Result<Option<SpikeConfig>> {
+        let adapter = match &self.adapter {
+            Some(adapter) => adapter.to_ascii_uppercase().into_bytes(),
+            None => DEFAULT_ADAPTER.to_vec(),
+        };
+        let barcodes = match (&self.barcodes, self.num_barcodes) {
+            (Some(list), _) => parse_barcodes(list)?,
+            (None, Some(n)) => generate_barcodes(n, 8, 3, seed.wrapping_add(2))?,
+            (None, None) => Vec::new(),
+        };
+
+        // Offset seeds so barcodes and spiking decisions don't replay the read RNG stream
+        let config = SpikeConfig::new(seed.wrapping_add(1))
+            .with_adapter(adapter, self.adapter_fraction)
+            .with_barcodes(barcodes);
+        Ok(config.is_active().then_some(config))
+    }
+}
+
+/// Dataset manifest options
+#[derive(Args)]
+struct ManifestArgs {
+    /// Dataset manifest to register the output in (default: manifest.toml next to the output)
+    #[arg(long)]
+    manifest: Option<PathBuf>,
+
+    /// Don't register the output in a dataset manifest
+    #[arg(long, conflicts_with = "manifest")]
+    no_manifest: bool,
+}
+
+impl ManifestArgs {
+    /// Record the output's parameters, record count and SHA-256
+    fn register(&self, output: &Path, source: DatasetSource) -> Result<()> {
+        if self.no_manifest {
+            return Ok(());
+        }
+        let manifest = self.manifest.clone().unwrap_or_else(|| {
+            output
+                .parent()
+                .map(|dir| dir.join("manifest.toml"))
+                .unwrap_or_else(|| PathBuf::from("manifest.toml"))
+        });
+        let name = output
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let entry = register_dataset(&manifest, &name, output, source)?;
+        println!("📄 Registered '{}' in {} (sha256 {})", name, manifest.display(), &entry.sha256[..12]);
+        Ok(())
+    }
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
@@ -78,18 +210,34 @@ fn main() -> Result<()> {
             num_sequences,
             length_mean,
             length_std,
+            preset,
             quality_dist,
             seed,
+            spike,
+            manifest,
             validate,
         } => {
+            let (length_mean, length_std) = match preset.as_deref().and_then(LengthClass::from_name) {
+                Some(class) => length_preset(class),
+                None => (length_mean, length_std),
+            };
             println!("🧬 Generating {} {} sequences...", num_sequences, format.to_uppercase());
             println!("   Output: {}", output.display());
             println!("   Mean length: {}bp ± {}bp", length_mean, length_std);
             println!("   Quality distribution: {}", quality_dist);
             println!("   Seed: {}", seed);
 
+            let spike = spike.to_config(seed)?;
+            let source = DatasetSource::Synthetic {
+                format: format.clone(),
+                seed,
+                length_mean,
+                length_std,
+                quality_dist: parse_quality_dist(&quality_dist)?,
+                spike: spike.as_ref().map(|s| s.describe()),
+            };
             let result = if format == "fasta" {
-                generate_fasta(&output, num_sequences, length_mean, length_std, seed)
+                generate_fasta(&output, num_sequences, length_mean, length_std, seed, spike)
             } else {
                 generate_fastq(
                     &output,
@@ -98,6 +246,7 @@ fn main() -> Result<()> {
                     length_std,
                     &quality_dist,
                     seed,
+                    spike,
                 )
             };
 
@@ -109,6 +258,57 @@ fn main() -> Result<()> {
                 println!("\n🔍 Running QC validation...");
                 validate_file(&output, &format, None)?;
             }
+
+            manifest.register(&output, source)?;
+        }
+
+        Commands::Simulate {
+            output,
+            platform,
+            num_sequences,
+            reference,
+            genome_size,
+            seed,
+            spike,
+            manifest,
+            validate,
+        } => {
+            println!("🧬 Simulating {} {} reads...", num_sequences, platform);
+            println!("   Output: {}", output.display());
+            match &reference {
+                Some(path) => println!("   Reference: {}", path.display()),
+                None => println!("   Reference: synthetic ({} bp)", genome_size),
+            }
+            println!("   Seed: {}", seed);
+
+            let spike = spike.to_config(seed)?;
+            let source = DatasetSource::Simulated {
+                seed,
+                platform: platform.clone(),
+                reference: reference.as_ref().map(|p| p.display().to_string()),
+                genome_size: reference.is_none().then_some(genome_size),
+                spike: spike.as_ref().map(|s| s.describe()),
+            };
+
+            simulate_fastq(
+                &output,
+                &platform,
+                num_sequences,
+                reference.as_ref(),
+                genome_size,
+                seed,
+                spike,
+            )
+            .context("Failed to simulate reads")?;
+
+            println!("✅ Simulation complete!");
+
+            if validate {
+                println!("\n🔍 Running QC validation...");
+                validate_file(&output, "fastq", None)?;
+            }
+
+            manifest.register(&output, source)?;
         }
 
         Commands::Validate {
@@ -131,44 +331,12 @@ fn generate_fasta(
     length_mean: usize,
     length_std: f64,
     seed: u64,
+    spike: Option<SpikeConfig>,
 ) -> Result<()> {
-    let mut rng = StdRng::seed_from_u64(seed);
-    let length_dist = Normal::new(length_mean as f64, length_std)
-        .context("Invalid length distribution parameters")?;
-
-    let file = File::create(output).context("Failed to create output file")?;
-    let mut writer = BufWriter::new(file);
+    let config = ReadGenConfig::new(num_sequences, seed).with_length(length_mean, length_std);
+    let generator = ReadGenerator::fasta(config)?;
 
-    let pb = ProgressBar::new(num_sequences as u64);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})")
-            .unwrap()
-            .progress_chars("#>-"),
-    );
-
-    for i in 0..num_sequences {
-        // Sample sequence length (clamp to reasonable range)
-        let length = length_dist.sample(&mut rng).round() as usize;
-        let length = length.clamp(50, length_mean * 2);
-
-        // Generate header
-        writeln!(writer, ">seq_{}", i)?;
-
-        // Generate sequence (60 characters per line, standard FASTA wrapping)
-        let sequence = generate_random_sequence(&mut rng, length);
-        for chunk in sequence.as_bytes().chunks(60) {
-            writer.write_all(chunk)?;
-            writeln!(writer)?;
-        }
-
-        pb.inc(1);
-    }
-
-    pb.finish_with_message("Done!");
-    writer.flush()?;
-
-    Ok(())
+    write_records(output, "fasta", num_sequences, generator, spike)
 }
 
 /// Generate synthetic FASTQ file
@@ -179,91 +347,103 @@ fn generate_fastq(
     length_std: f64,
     quality_dist: &str,
     seed: u64,
+    spike: Option<SpikeConfig>,
 ) -> Result<()> {
-    let mut rng = StdRng::seed_from_u64(seed);
-    let length_dist = Normal::new(length_mean as f64, length_std)
-        .context("Invalid length distribution parameters")?;
+    let config = ReadGenConfig::new(num_sequences, seed)
+        .with_length(length_mean, length_std)
+        .with_quality_dist(parse_quality_dist(quality_dist)?);
+    let generator = ReadGenerator::fastq(config)?;
 
-    let file = File::create(output).context("Failed to create output file")?;
-    let mut writer = BufWriter::new(file);
+    // FASTQ format: exactly 4 lines per record, quality length == sequence length
+    write_records(output, "fastq", num_sequences, generator, spike)
+}
 
-    let pb = ProgressBar::new(num_sequences as u64);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})")
-            .unwrap()
-            .progress_chars("#>-"),
-    );
+/// Simulate FASTQ reads from a reference genome
+fn simulate_fastq(
+    output: &PathBuf,
+    platform: &str,
+    num_sequences: usize,
+    reference: Option<&PathBuf>,
+    genome_size: usize,
+    seed: u64,
+    spike: Option<SpikeConfig>,
+) -> Result<()> {
+    let reference = match reference {
+        Some(path) => Reference::from_fasta(path)?,
+        None => {
+            // Derive the genome seed from the read seed so one seed fixes both
+            let mut rng = StdRng::seed_from_u64(seed ^ 0x9E37_79B9_7F4A_7C15);
+            Reference::synthetic(&mut rng, genome_size, 0.35)
+        }
+    };
 
-    for i in 0..num_sequences {
-        // Sample sequence length
-        let length = length_dist.sample(&mut rng).round() as usize;
-        let length = length.clamp(50, length_mean * 2);
+    let config = SimulationConfig::for_platform(Platform::parse(platform)?, num_sequences, seed);
+    let simulator = ReadSimulator::new(&reference, config)?;
 
-        // Generate sequence
-        let sequence = generate_random_sequence(&mut rng, length);
+    write_records(output, "fastq", num_sequences, simulator, spike)
+}
 
-        // Generate quality scores (CRITICAL: must match sequence length exactly!)
-        let quality = generate_quality_scores(&mut rng, length, quality_dist);
+/// Write records with a progress bar, applying adapter/barcode spiking if requested
+fn write_records(
+    output: &PathBuf,
+    format: &str,
+    num_sequences: usize,
+    records: impl Iterator<Item = SequenceRecord>,
+    spike: Option<SpikeConfig>,
+) -> Result<()> {
+    let file = File::create(output).context("Failed to create output file")?;
+    let mut writer = BufWriter::new(file);
+    let pb = progress_bar(num_sequences);
 
-        // FASTQ format: exactly 4 lines per record, no wrapping
-        writeln!(writer, "@seq_{}", i)?; // Line 1: Header (starts with @)
-        writeln!(writer, "{}", sequence)?; // Line 2: Sequence (no wrapping!)
-        writeln!(writer, "+")?; // Line 3: Plus line (can be empty or repeat header)
-        writeln!(writer, "{}", quality)?; // Line 4: Quality (must match sequence length!)
+    let write = |writer: &mut BufWriter<File>, records: &mut dyn Iterator<Item = SequenceRecord>| {
+        if format == "fasta" {
+            write_fasta(writer, records.inspect(|_| pb.inc(1)))
+        } else {
+            write_fastq(writer, records.inspect(|_| pb.inc(1)))
+        }
+    };
 
-        pb.inc(1);
-    }
+    let truth = match spike {
+        Some(config) => {
+            let mut spiker = Spiker::new(records, config)?;
+            write(&mut writer, &mut spiker)?;
+            Some(spiker.into_truth())
+        }
+        None => {
+            let mut records = records;
+            write(&mut writer, &mut records)?;
+            None
+        }
+    };
 
     pb.finish_with_message("Done!");
     writer.flush()?;
 
-    Ok(())
-}
+    if let Some(truth) = truth {
+        let path = SpikeTruth::sidecar_path(output);
+        truth.save(&path)?;
+        println!(
+            "   Adapters spiked: {} reads ({} full length)",
+            truth.reads_with_adapter, truth.reads_with_full_adapter
+        );
+        if !truth.barcodes.is_empty() {
+            println!("   Barcodes: {} samples", truth.barcodes.len());
+        }
+        println!("📄 Ground truth saved to: {}", path.display());
+    }
 
-/// Generate random DNA sequence
-fn generate_random_sequence(rng: &mut StdRng, length: usize) -> String {
-    const BASES: &[u8] = b"ACGT";
-    (0..length)
-        .map(|_| BASES[rng.gen_range(0..4)] as char)
-        .collect()
+    Ok(())
 }
 
-/// Generate quality scores with specified distribution
-fn generate_quality_scores(rng: &mut StdRng, length: usize, dist: &str) -> String {
-    match dist {
-        "uniform_high" => {
-            // Q40 (Phred+33 = 'I')
-            "I".repeat(length)
-        }
-        "degrading" => {
-            // Q40 → Q20 over read length (realistic Illumina pattern)
-            (0..length)
-                .map(|i| {
-                    let q = 40.0 - (20.0 * i as f64 / length as f64);
-                    let phred = (q as u8).clamp(0, 40);
-                    (phred + 33) as char // Phred+33 encoding
-                })
-                .collect()
-        }
-        "realistic" => {
-            // Simulate realistic Illumina distribution
-            // High quality (Q35-40) with occasional drops
-            (0..length)
-                .map(|_| {
-                    let base_q = rng.gen_range(35..=40);
-                    // 5% chance of quality drop
-                    let q = if rng.gen::<f64>() < 0.05 {
-                        rng.gen_range(20..30)
-                    } else {
-                        base_q
-                    };
-                    (q + 33) as u8 as char
-                })
-                .collect()
-        }
-        _ => unreachable!("Invalid quality distribution"),
-    }
+fn progress_bar(len: usize) -> ProgressBar {
+    let pb = ProgressBar::new(len as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})")
+            .unwrap()
+            .progress_chars("#>-"),
+    );
+    pb
 }
 
 /// Validate FASTA or FASTQ file
@@ -0,0 +1,79 @@
+#!/usr/bin/env python3
+"""
+Build the k-mer embedding Core ML model
+
+Writes the dense projection used by asbb_ops::kmer_embedding as an ML
+Program, so the same weights run on the Neural Engine, GPU and CPU. The
+weights are regenerated from the seed in kmer_embedding.rs (SplitMix64
+signs, scaled by 1/sqrt(dims)) and must stay in sync with
+`projection_weight`.
+
+Model contract:
+    input  "profiles":  float32 [batch, 4^k]  (L2-normalised k-mer profiles)
+    output "embedding": float32 [batch, dims] (unnormalised projection)
+
+Usage:
+    pip install coremltools numpy
+    python3 scripts/build_kmer_embedding_model.py [--k 4] [--dims 64] [--batch 512]
+"""
+
+import argparse
+
+import numpy as np
+import coremltools as ct
+from coremltools.converters.mil import Builder as mb
+
+PROJECTION_SEED = 0x4153_4242_454D_4244
+MASK = (1 << 64) - 1
+
+
+def splitmix64(x):
+    """SplitMix64 finaliser (matches kmer_embedding::splitmix64)"""
+    z = (x + 0x9E37_79B9_7F4A_7C15) & MASK
+    z = ((z ^ (z >> 30)) * 0xBF58_476D_1CE4_E5B9) & MASK
+    z = ((z ^ (z >> 27)) * 0x94D0_49BB_1331_11EB) & MASK
+    return z ^ (z >> 31)
+
+
+def projection(in_dims, out_dims):
+    """[out_dims, in_dims] matrix of +-1/sqrt(out_dims)"""
+    weights = np.empty((out_dims, in_dims), dtype=np.float32)
+    scale = 1.0 / np.sqrt(out_dims)
+    for row in range(out_dims):
+        for col in range(in_dims):
+            hash_value = splitmix64(PROJECTION_SEED ^ (row * in_dims + col))
+            weights[row, col] = scale if hash_value >> 63 == 0 else -scale
+    return weights
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__, formatter_class=argparse.RawDescriptionHelpFormatter)
+    parser.add_argument("--k", type=int, default=4)
+    parser.add_argument("--dims", type=int, default=64)
+    parser.add_argument("--batch", type=int, default=512, help="fixed rows per prediction (ANE needs static shapes)")
+    parser.add_argument("--output", default=None)
+    args = parser.parse_args()
+
+    assert splitmix64(0) == 0xE220_A839_7B1D_CDAF
+    in_dims = 4 ** args.k
+    weights = projection(in_dims, args.dims)
+
+    @mb.program(input_specs=[mb.TensorSpec(shape=(args.batch, in_dims))])
+    def program(profiles):
+        return mb.linear(x=profiles, weight=weights, name="embedding")
+
+    model = ct.convert(
+        program,
+        convert_to="mlprogram",
+        compute_precision=ct.precision.FLOAT16,
+        minimum_deployment_target=ct.target.macOS13,
+    )
+    model.short_description = f"k={args.k} k-mer profile -> {args.dims}-dim random projection (asbb kmer_embedding)"
+
+    output = args.output or f"models/kmer_embedding_k{args.k}_d{args.dims}.mlpackage"
+    model.save(output)
+    print(f"Saved {output} (input profiles [{args.batch}, {in_dims}], output embedding [{args.batch}, {args.dims}])")
+
+
+if __name__ == "__main__":
+    main()
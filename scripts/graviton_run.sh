@@ -20,7 +20,7 @@ SSH_KEY=~/.ssh/${KEY_NAME}.pem
 echo "=== Running Graviton Experiments ==="
 echo "Public IP: $PUBLIC_IP"
 echo "Total experiments: 45 (5 ops × 3 configs × 3 scales)"
-echo "Expected duration: 45-60 minutes"
+echo "Expected duration: 5-10 minutes"
 echo
 
 # Create results directory on instance
@@ -31,7 +31,16 @@ ssh -i "$SSH_KEY" -o StrictHostKeyChecking=no ec2-user@${PUBLIC_IP} "mkdir -p ~/
 echo "Starting experiments..."
 echo
 
-ssh -i "$SSH_KEY" -o StrictHostKeyChecking=no ec2-user@${PUBLIC_IP} "source ~/.cargo/env && cd ~/asbb && ./target/release/asbb-pilot-graviton"
+# Same asbb-ops kernels and CSV columns as the Mac runs (`asbb bench`);
+# datasets travel with the code tarball from graviton_setup.sh
+TIMESTAMP=$(date +%Y%m%d_%H%M%S)
+ssh -i "$SSH_KEY" -o StrictHostKeyChecking=no ec2-user@${PUBLIC_IP} "source ~/.cargo/env && cd ~/asbb && ./target/release/asbb bench \
+    --input Small=datasets/small_1000_150bp.fq \
+    --input Medium=datasets/medium_10000_150bp.fq \
+    --input Large=datasets/large_100000_150bp.fq \
+    --operations base_counting,gc_content,quality_aggregation,at_content,reverse_complement \
+    --threads 4 \
+    --output results/cross_platform_graviton/graviton_raw_${TIMESTAMP}.csv"
 
 echo
 echo "✅ Experiments complete!"
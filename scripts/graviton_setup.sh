@@ -125,13 +125,13 @@ fi
 
 # Compile pilot binary
 echo
-echo "Compiling Graviton pilot binary..."
-ssh -i "$SSH_KEY" -o StrictHostKeyChecking=no ec2-user@${PUBLIC_IP} "source ~/.cargo/env && cd ~/asbb/crates/asbb-cli && cargo build --release --bin asbb-pilot-graviton"
+echo "Compiling asbb binary..."
+ssh -i "$SSH_KEY" -o StrictHostKeyChecking=no ec2-user@${PUBLIC_IP} "source ~/.cargo/env && cd ~/asbb/crates/asbb-cli && cargo build --release --bin asbb"
 echo
 
 # Verify compilation
 echo "Verifying compilation..."
-BINARY_EXISTS=$(ssh -i "$SSH_KEY" -o StrictHostKeyChecking=no ec2-user@${PUBLIC_IP} "[ -f ~/asbb/target/release/asbb-pilot-graviton ] && echo 'yes' || echo 'no'")
+BINARY_EXISTS=$(ssh -i "$SSH_KEY" -o StrictHostKeyChecking=no ec2-user@${PUBLIC_IP} "[ -f ~/asbb/target/release/asbb ] && echo 'yes' || echo 'no'")
 
 if [ "$BINARY_EXISTS" == "yes" ]; then
     echo "✅ Binary compiled successfully"